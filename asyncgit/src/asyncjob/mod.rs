@@ -28,6 +28,11 @@ impl<T: Copy + Send, P: Clone + Send + Sync + PartialEq>
 		Ok(())
 	}
 
+	/// for jobs forwarding notifications from threads of their own
+	pub(crate) fn sender(&self) -> Sender<T> {
+		self.sender.clone()
+	}
+
 	/// set the current progress
 	pub fn set_progress(&self, p: P) -> Result<bool> {
 		Ok(if *self.progress.read()? == p {
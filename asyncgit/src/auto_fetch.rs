@@ -0,0 +1,230 @@
+//! fetching all remotes in the background every `gitui.autoFetch`
+//! seconds
+
+use crate::{
+	error::{Error, Result},
+	sync::{
+		auto_fetch_interval,
+		cred::{
+			extract_username_password_for_remote,
+			need_username_password_for_remote,
+		},
+		fetch_options_config, get_remotes, need_ssh_passphrase,
+		remotes::{fetch_all, RemoteFetchOptions},
+		repo_state, RepoPath, RepoState,
+	},
+	AsyncGitNotification,
+};
+use crossbeam_channel::Sender;
+use std::{
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc, Mutex,
+	},
+	thread,
+	time::{Duration, Instant},
+};
+
+/// how often the config is checked for the interval
+const CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// state of the background fetching shown to the user
+#[derive(Clone, Debug, Default)]
+pub struct AutoFetchStatus {
+	/// `None` while disabled or there are no remotes
+	pub interval: Option<Duration>,
+	///
+	pub pending: bool,
+	/// last successful fetch
+	pub last_fetch: Option<Instant>,
+	/// error of the last attempt, `None` if it succeeded
+	pub last_error: Option<String>,
+}
+
+/// fetches all remotes on a thread of its own whenever the configured
+/// interval passed, without ever asking for credentials. stops once
+/// dropped
+pub struct AsyncAutoFetch {
+	status: Arc<Mutex<AutoFetchStatus>>,
+	stop: Arc<AtomicBool>,
+	paused: Arc<AtomicBool>,
+}
+
+impl AsyncAutoFetch {
+	///
+	pub fn new(
+		repo: RepoPath,
+		sender: &Sender<AsyncGitNotification>,
+	) -> Self {
+		let status = Arc::new(Mutex::new(AutoFetchStatus::default()));
+		let stop = Arc::new(AtomicBool::new(false));
+		let paused = Arc::new(AtomicBool::new(false));
+
+		let arc_status = Arc::clone(&status);
+		let arc_stop = Arc::clone(&stop);
+		let arc_paused = Arc::clone(&paused);
+		let sender = sender.clone();
+
+		thread::spawn(move || {
+			Self::run(
+				&repo,
+				&arc_status,
+				&arc_stop,
+				&arc_paused,
+				&sender,
+			);
+		});
+
+		Self {
+			status,
+			stop,
+			paused,
+		}
+	}
+
+	/// postpones fetching while the user runs network operations of
+	/// their own, e.g. a push whose lease a fetch would invalidate
+	pub fn set_paused(&self, paused: bool) {
+		self.paused.store(paused, Ordering::Relaxed);
+	}
+
+	///
+	pub fn status(&self) -> AutoFetchStatus {
+		self.status
+			.lock()
+			.map(|status| status.clone())
+			.unwrap_or_default()
+	}
+
+	fn run(
+		repo: &RepoPath,
+		status: &Mutex<AutoFetchStatus>,
+		stop: &AtomicBool,
+		paused: &AtomicBool,
+		sender: &Sender<AsyncGitNotification>,
+	) {
+		let mut last_attempt: Option<Instant> = None;
+
+		while !stop.load(Ordering::Relaxed) {
+			let interval = auto_fetch_interval(repo)
+				.ok()
+				.flatten()
+				.filter(|_| {
+					get_remotes(repo)
+						.is_ok_and(|remotes| !remotes.is_empty())
+				});
+
+			if let Ok(mut status) = status.lock() {
+				status.interval = interval;
+			}
+
+			// retried on the next check instead of waiting another
+			// interval
+			let busy = paused.load(Ordering::Relaxed)
+				|| repo_state(repo)
+					.map_or(true, |state| state != RepoState::Clean);
+
+			let due = !busy
+				&& interval.is_some_and(|interval| {
+					last_attempt.map_or(true, |last| {
+						last.elapsed() >= interval
+					})
+				});
+
+			if due {
+				last_attempt = Some(Instant::now());
+
+				if let Ok(mut status) = status.lock() {
+					status.pending = true;
+				}
+
+				let res = Self::fetch(repo);
+
+				if let Ok(mut status) = status.lock() {
+					status.pending = false;
+					match res {
+						Ok(_) => {
+							status.last_fetch = Some(Instant::now());
+							status.last_error = None;
+						}
+						Err(e) => {
+							log::error!("auto fetch error: {}", e);
+							status.last_error = Some(e.to_string());
+						}
+					}
+				}
+
+				if sender
+					.send(AsyncGitNotification::AutoFetch)
+					.is_err()
+				{
+					break;
+				}
+			}
+
+			thread::sleep(CHECK_INTERVAL);
+		}
+	}
+
+	/// like a fetch started by the user but failing instead of asking
+	/// for missing credentials. remotes are fetched one by one with
+	/// their own credentials, the first error is returned after
+	/// trying all of them
+	fn fetch(repo: &RepoPath) -> Result<()> {
+		let options = fetch_options_config(repo)?;
+		let mut res = Ok(());
+
+		for remote in get_remotes(repo)? {
+			let fetched = Self::fetch_remote(repo, &remote, &options);
+
+			if let Err(e) = fetched {
+				log::error!("auto fetch of {} failed: {}", remote, e);
+				if res.is_ok() {
+					res =
+						Err(Error::Generic(format!("{remote}: {e}")));
+				}
+			}
+		}
+
+		res
+	}
+
+	fn fetch_remote(
+		repo: &RepoPath,
+		remote: &str,
+		options: &RemoteFetchOptions,
+	) -> Result<()> {
+		let cred = if need_username_password_for_remote(repo, remote)?
+		{
+			let cred =
+				extract_username_password_for_remote(repo, remote)?;
+			if !cred.is_complete() {
+				return Err(Error::Generic(
+					"credentials required".into(),
+				));
+			}
+			Some(cred)
+		} else if need_ssh_passphrase(repo, remote)? {
+			return Err(Error::Generic(
+				"ssh key passphrase required".into(),
+			));
+		} else {
+			None
+		};
+
+		let options = RemoteFetchOptions {
+			remote: Some(remote.to_string()),
+			..options.clone()
+		};
+
+		fetch_all(repo, &options, &cred, &None)?;
+
+		Ok(())
+	}
+}
+
+impl Drop for AsyncAutoFetch {
+	fn drop(&mut self) {
+		self.stop.store(true, Ordering::Relaxed);
+	}
+}
@@ -0,0 +1,197 @@
+use crate::{
+	asyncjob::{AsyncJob, RunParams},
+	error::Result,
+	sync::{
+		cherry::{
+			get_cherry_applied, get_cherry_applied_upstream,
+			get_duplicate_commits,
+		},
+		commit_files::OldNew,
+		get_commit_details, CommitDetails, CommitId, RepoPath,
+	},
+	AsyncGitNotification,
+};
+use std::{
+	cmp::Reverse,
+	collections::HashSet,
+	sync::{Arc, Mutex},
+};
+
+enum JobState {
+	Request {
+		repo: RepoPath,
+		compare: Option<(CommitId, CommitId)>,
+	},
+	Response(Result<HashSet<CommitId>>),
+}
+
+/// finds local commits that are already applied upstream
+#[derive(Clone, Default)]
+pub struct AsyncCherryJob {
+	state: Arc<Mutex<Option<JobState>>>,
+}
+
+///
+impl AsyncCherryJob {
+	/// compare `HEAD` against the upstream of the current branch
+	pub fn new_upstream(repo: RepoPath) -> Self {
+		Self::new_state(repo, None)
+	}
+
+	/// compare `head` against `upstream`
+	pub fn new(
+		repo: RepoPath,
+		head: CommitId,
+		upstream: CommitId,
+	) -> Self {
+		Self::new_state(repo, Some((head, upstream)))
+	}
+
+	fn new_state(
+		repo: RepoPath,
+		compare: Option<(CommitId, CommitId)>,
+	) -> Self {
+		Self {
+			state: Arc::new(Mutex::new(Some(JobState::Request {
+				repo,
+				compare,
+			}))),
+		}
+	}
+
+	///
+	pub fn result(&self) -> Option<Result<HashSet<CommitId>>> {
+		if let Ok(mut state) = self.state.lock() {
+			if let Some(state) = state.take() {
+				return match state {
+					JobState::Request { .. } => None,
+					JobState::Response(result) => Some(result),
+				};
+			}
+		}
+
+		None
+	}
+}
+
+impl AsyncJob for AsyncCherryJob {
+	type Notification = AsyncGitNotification;
+	type Progress = ();
+
+	fn run(
+		&mut self,
+		_params: RunParams<Self::Notification, Self::Progress>,
+	) -> Result<Self::Notification> {
+		if let Ok(mut state) = self.state.lock() {
+			*state = state.take().map(|state| match state {
+				JobState::Request { repo, compare } => {
+					JobState::Response(compare.map_or_else(
+						|| get_cherry_applied_upstream(&repo),
+						|(head, upstream)| {
+							get_cherry_applied(&repo, head, upstream)
+						},
+					))
+				}
+				JobState::Response(result) => {
+					JobState::Response(result)
+				}
+			});
+		}
+
+		Ok(AsyncGitNotification::Cherry)
+	}
+}
+
+/// commits of the old side of a comparison and the commit on the new
+/// side with the same changes, newest first
+pub type DuplicateDetails = Vec<(CommitDetails, CommitDetails)>;
+
+enum CompareJobState {
+	Request {
+		repo: RepoPath,
+		ids: OldNew<CommitId>,
+	},
+	Response(OldNew<CommitId>, Result<DuplicateDetails>),
+}
+
+/// finds the duplicate commits of both sides of a comparison, see
+/// [`get_duplicate_commits`]
+#[derive(Clone, Default)]
+pub struct AsyncCompareCherryJob {
+	state: Arc<Mutex<Option<CompareJobState>>>,
+}
+
+///
+impl AsyncCompareCherryJob {
+	///
+	pub fn new(repo: RepoPath, ids: OldNew<CommitId>) -> Self {
+		Self {
+			state: Arc::new(Mutex::new(Some(
+				CompareJobState::Request { repo, ids },
+			))),
+		}
+	}
+
+	/// compared commits and the duplicates found
+	pub fn result(
+		&self,
+	) -> Option<(OldNew<CommitId>, Result<DuplicateDetails>)> {
+		if let Ok(mut state) = self.state.lock() {
+			if let Some(state) = state.take() {
+				return match state {
+					CompareJobState::Request { .. } => None,
+					CompareJobState::Response(ids, result) => {
+						Some((ids, result))
+					}
+				};
+			}
+		}
+
+		None
+	}
+
+	fn duplicates(
+		repo: &RepoPath,
+		ids: OldNew<CommitId>,
+	) -> Result<DuplicateDetails> {
+		let mut res = get_duplicate_commits(repo, ids.old, ids.new)?
+			.into_iter()
+			.map(|dup| {
+				Ok((
+					get_commit_details(repo, dup.id)?,
+					get_commit_details(repo, dup.duplicate_of)?,
+				))
+			})
+			.collect::<Result<DuplicateDetails>>()?;
+
+		res.sort_by_key(|(old, _)| Reverse(old.author.time));
+
+		Ok(res)
+	}
+}
+
+impl AsyncJob for AsyncCompareCherryJob {
+	type Notification = AsyncGitNotification;
+	type Progress = ();
+
+	fn run(
+		&mut self,
+		_params: RunParams<Self::Notification, Self::Progress>,
+	) -> Result<Self::Notification> {
+		if let Ok(mut state) = self.state.lock() {
+			*state = state.take().map(|state| match state {
+				CompareJobState::Request { repo, ids } => {
+					CompareJobState::Response(
+						ids,
+						Self::duplicates(&repo, ids),
+					)
+				}
+				CompareJobState::Response(ids, result) => {
+					CompareJobState::Response(ids, result)
+				}
+			});
+		}
+
+		Ok(AsyncGitNotification::Cherry)
+	}
+}
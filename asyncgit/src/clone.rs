@@ -0,0 +1,160 @@
+use crate::{
+	error::{Error, Result},
+	sync::{
+		clone_repo, cred::BasicAuthCredential,
+		remotes::push::ProgressNotification, CloneOptions,
+	},
+	AsyncGitNotification, RemoteProgress,
+};
+use crossbeam_channel::{unbounded, Sender};
+use std::{
+	path::PathBuf,
+	sync::{Arc, Mutex},
+	thread,
+};
+
+///
+#[derive(Default, Clone, Debug)]
+pub struct CloneRequest {
+	///
+	pub url: String,
+	///
+	pub path: PathBuf,
+	///
+	pub options: CloneOptions,
+	///
+	pub basic_credential: Option<BasicAuthCredential>,
+}
+
+#[derive(Default, Clone, Debug)]
+struct CloneState {}
+
+///
+pub struct AsyncClone {
+	state: Arc<Mutex<Option<CloneState>>>,
+	last_result: Arc<Mutex<Option<String>>>,
+	progress: Arc<Mutex<Option<ProgressNotification>>>,
+	sender: Sender<AsyncGitNotification>,
+}
+
+impl AsyncClone {
+	///
+	pub fn new(sender: &Sender<AsyncGitNotification>) -> Self {
+		Self {
+			state: Arc::new(Mutex::new(None)),
+			last_result: Arc::new(Mutex::new(None)),
+			progress: Arc::new(Mutex::new(None)),
+			sender: sender.clone(),
+		}
+	}
+
+	///
+	pub fn is_pending(&self) -> Result<bool> {
+		let state = self.state.lock()?;
+		Ok(state.is_some())
+	}
+
+	/// error of the last clone (if it failed)
+	pub fn last_result(&self) -> Result<Option<String>> {
+		let res = self.last_result.lock()?;
+		Ok(res.clone())
+	}
+
+	///
+	pub fn progress(&self) -> Result<Option<RemoteProgress>> {
+		let res = self.progress.lock()?;
+		Ok(res.as_ref().map(|progress| progress.clone().into()))
+	}
+
+	///
+	pub fn request(&self, params: CloneRequest) -> Result<()> {
+		log::trace!("request");
+
+		if self.is_pending()? {
+			return Ok(());
+		}
+
+		self.set_request()?;
+		RemoteProgress::set_progress(&self.progress, None)?;
+
+		let arc_state = Arc::clone(&self.state);
+		let arc_res = Arc::clone(&self.last_result);
+		let arc_progress = Arc::clone(&self.progress);
+		let sender = self.sender.clone();
+
+		thread::spawn(move || {
+			let (progress_sender, receiver) = unbounded();
+
+			let handle = RemoteProgress::spawn_receiver_thread(
+				AsyncGitNotification::Clone,
+				sender.clone(),
+				receiver,
+				arc_progress,
+				None,
+			);
+
+			let res = clone_repo(
+				&params.url,
+				&params.path,
+				&params.options,
+				params.basic_credential,
+				Some(progress_sender.clone()),
+			);
+
+			progress_sender
+				.send(ProgressNotification::Done)
+				.expect("closing send failed");
+
+			handle.join().expect("joining thread failed");
+
+			Self::set_result(&arc_res, res).expect("result error");
+
+			Self::clear_request(&arc_state).expect("clear error");
+
+			sender
+				.send(AsyncGitNotification::Clone)
+				.expect("AsyncNotification error");
+		});
+
+		Ok(())
+	}
+
+	fn set_request(&self) -> Result<()> {
+		let mut state = self.state.lock()?;
+
+		if state.is_some() {
+			return Err(Error::Generic("pending request".into()));
+		}
+
+		*state = Some(CloneState {});
+
+		Ok(())
+	}
+
+	fn clear_request(
+		state: &Arc<Mutex<Option<CloneState>>>,
+	) -> Result<()> {
+		let mut state = state.lock()?;
+
+		*state = None;
+
+		Ok(())
+	}
+
+	fn set_result(
+		arc_result: &Arc<Mutex<Option<String>>>,
+		res: Result<()>,
+	) -> Result<()> {
+		let mut last_res = arc_result.lock()?;
+
+		*last_res = match res {
+			Ok(()) => None,
+			Err(e) => {
+				log::error!("clone error: {}", e);
+				Some(e.to_string())
+			}
+		};
+
+		Ok(())
+	}
+}
@@ -1,6 +1,8 @@
 use crate::{
 	error::Result,
-	sync::{self, commit_files::OldNew, CommitId, RepoPath},
+	sync::{
+		self, commit_files::OldNew, CommitId, MergeDiffMode, RepoPath,
+	},
 	AsyncGitNotification, StatusItem,
 };
 use crossbeam_channel::Sender;
@@ -19,11 +21,29 @@ pub struct CommitFilesParams {
 	pub id: CommitId,
 	///
 	pub other: Option<CommitId>,
+	/// which parent(s) to diff a merge commit against
+	pub merge_mode: MergeDiffMode,
+}
+
+impl CommitFilesParams {
+	///
+	#[must_use]
+	pub const fn with_merge_mode(
+		mut self,
+		mode: MergeDiffMode,
+	) -> Self {
+		self.merge_mode = mode;
+		self
+	}
 }
 
 impl From<CommitId> for CommitFilesParams {
 	fn from(id: CommitId) -> Self {
-		Self { id, other: None }
+		Self {
+			id,
+			other: None,
+			merge_mode: MergeDiffMode::default(),
+		}
 	}
 }
 
@@ -32,6 +52,7 @@ impl From<(CommitId, CommitId)> for CommitFilesParams {
 		Self {
 			id,
 			other: Some(other),
+			merge_mode: MergeDiffMode::default(),
 		}
 	}
 }
@@ -41,6 +62,7 @@ impl From<OldNew<CommitId>> for CommitFilesParams {
 		Self {
 			id: old_new.new,
 			other: Some(old_new.old),
+			merge_mode: MergeDiffMode::default(),
 		}
 	}
 }
@@ -128,11 +150,21 @@ impl AsyncCommitFiles {
 			Mutex<Option<Request<CommitFilesParams, ResultType>>>,
 		>,
 	) -> Result<()> {
-		let res = sync::get_commit_files(
-			repo_path,
-			params.id,
-			params.other,
-		)?;
+		let res = if params.other.is_none()
+			&& params.merge_mode != MergeDiffMode::default()
+		{
+			sync::get_merge_commit_files(
+				repo_path,
+				params.id,
+				params.merge_mode,
+			)?
+		} else {
+			sync::get_commit_files(
+				repo_path,
+				params.id,
+				params.other,
+			)?
+		};
 
 		log::trace!("get_commit_files: {:?} ({})", params, res.len());
 
@@ -0,0 +1,48 @@
+use crate::{
+	asyncjob::{AsyncJob, RunParams},
+	error::Result,
+	sync::{write_commit_graph, RepoPath},
+	AsyncGitNotification,
+};
+use std::sync::{Arc, Mutex};
+
+/// updates the commit-graph file in the background
+#[derive(Clone)]
+pub struct AsyncCommitGraphJob {
+	repo: RepoPath,
+	result: Arc<Mutex<Option<Result<()>>>>,
+}
+
+///
+impl AsyncCommitGraphJob {
+	///
+	pub fn new(repo: RepoPath) -> Self {
+		Self {
+			repo,
+			result: Arc::new(Mutex::new(None)),
+		}
+	}
+
+	///
+	pub fn result(&self) -> Option<Result<()>> {
+		self.result.lock().ok().and_then(|mut result| result.take())
+	}
+}
+
+impl AsyncJob for AsyncCommitGraphJob {
+	type Notification = AsyncGitNotification;
+	type Progress = ();
+
+	fn run(
+		&mut self,
+		_params: RunParams<Self::Notification, Self::Progress>,
+	) -> Result<Self::Notification> {
+		let res = write_commit_graph(&self.repo);
+
+		if let Ok(mut result) = self.result.lock() {
+			*result = Some(res);
+		}
+
+		Ok(AsyncGitNotification::CommitGraph)
+	}
+}
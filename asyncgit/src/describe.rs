@@ -0,0 +1,75 @@
+use crate::{
+	asyncjob::{AsyncJob, RunParams},
+	error::Result,
+	sync::{describe_commit, CommitId, RepoPath},
+	AsyncGitNotification,
+};
+use std::sync::{Arc, Mutex};
+
+enum JobState {
+	Request { repo: RepoPath, commit: CommitId },
+	Response(CommitId, Result<Option<String>>),
+}
+
+/// runs `git describe --tags` for a commit in the background
+#[derive(Clone, Default)]
+pub struct AsyncDescribeJob {
+	state: Arc<Mutex<Option<JobState>>>,
+}
+
+///
+impl AsyncDescribeJob {
+	///
+	pub fn new(repo: RepoPath, commit: CommitId) -> Self {
+		Self {
+			state: Arc::new(Mutex::new(Some(JobState::Request {
+				repo,
+				commit,
+			}))),
+		}
+	}
+
+	/// commit the description belongs to and the description itself
+	pub fn result(
+		&self,
+	) -> Option<(CommitId, Result<Option<String>>)> {
+		if let Ok(mut state) = self.state.lock() {
+			if let Some(state) = state.take() {
+				return match state {
+					JobState::Request { .. } => None,
+					JobState::Response(commit, result) => {
+						Some((commit, result))
+					}
+				};
+			}
+		}
+
+		None
+	}
+}
+
+impl AsyncJob for AsyncDescribeJob {
+	type Notification = AsyncGitNotification;
+	type Progress = ();
+
+	fn run(
+		&mut self,
+		_params: RunParams<Self::Notification, Self::Progress>,
+	) -> Result<Self::Notification> {
+		if let Ok(mut state) = self.state.lock() {
+			*state = state.take().map(|state| match state {
+				JobState::Request { repo, commit } => {
+					JobState::Response(
+						commit,
+						describe_commit(&repo, commit),
+					)
+				}
+				JobState::Response(commit, result) => {
+					JobState::Response(commit, result)
+				}
+			});
+		}
+
+		Ok(AsyncGitNotification::Describe)
+	}
+}
@@ -3,7 +3,7 @@ use crate::{
 	hash,
 	sync::{
 		self, commit_files::OldNew, diff::DiffOptions, CommitId,
-		RepoPath,
+		MergeDiffMode, RepoPath,
 	},
 	AsyncGitNotification, FileDiff,
 };
@@ -23,10 +23,19 @@ pub enum DiffType {
 	Commits(OldNew<CommitId>),
 	/// diff in a given commit
 	Commit(CommitId),
+	/// diff of a merge commit against the parent(s) chosen by the mode
+	Merge(CommitId, MergeDiffMode),
 	/// diff against staged file
 	Stage,
 	/// diff against file in workdir
 	WorkDir,
+	/// changes to the workdir file when restoring it from a commit
+	Restore(CommitId),
+	/// changes to the workdir file when taking it from a stash
+	StashWorktree(CommitId),
+	/// diff against file in the workdir of the submodule at the given
+	/// path (`DiffParams::path` includes the submodule path)
+	Submodule(String),
 }
 
 ///
@@ -180,12 +189,44 @@ impl AsyncDiff {
 				params.path.clone(),
 				Some(params.options),
 			)?,
+			DiffType::Merge(id, mode) => sync::diff::get_diff_merge(
+				repo_path,
+				id,
+				mode,
+				&params.path,
+				Some(params.options),
+			)?,
 			DiffType::Commits(ids) => sync::diff::get_diff_commits(
 				repo_path,
 				ids,
 				params.path.clone(),
 				Some(params.options),
 			)?,
+			DiffType::Restore(id) => sync::diff::get_diff_restore(
+				repo_path,
+				id,
+				&params.path,
+				Some(params.options),
+			)?,
+			DiffType::StashWorktree(id) => {
+				sync::diff::get_diff_stash_worktree(
+					repo_path,
+					id,
+					&params.path,
+					Some(params.options),
+				)?
+			}
+			DiffType::Submodule(ref submodule) => {
+				sync::diff::get_diff(
+					&sync::submodule_repo_path(repo_path, submodule)?,
+					params
+						.path
+						.strip_prefix(&format!("{submodule}/"))
+						.unwrap_or(&params.path),
+					false,
+					Some(params.options),
+				)?
+			}
 		};
 
 		let mut notify = false;
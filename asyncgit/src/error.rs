@@ -107,6 +107,14 @@ pub enum Error {
 		#[from] gix::reference::find::existing::Error,
 	),
 
+	///
+	#[error("gix::reference::iter error: {0}")]
+	GixReferenceIter(#[from] gix::reference::iter::Error),
+
+	///
+	#[error("gix::reference::iter::init error: {0}")]
+	GixReferenceIterInit(#[from] gix::reference::iter::init::Error),
+
 	///
 	#[error("gix::head::peel::to_commit error: {0}")]
 	GixHeadPeelToCommit(#[from] gix::head::peel::to_commit::Error),
@@ -118,14 +126,6 @@ pub enum Error {
 	///
 	#[error("amend error: config commit.gpgsign=true detected.\ngpg signing is not supported for amending non-last commits")]
 	SignAmendNonLastCommit,
-
-	///
-	#[error("reword error: config commit.gpgsign=true detected.\ngpg signing is not supported for rewording non-last commits")]
-	SignRewordNonLastCommit,
-
-	///
-	#[error("reword error: config commit.gpgsign=true detected.\ngpg signing is not supported for rewording commits with staged changes\ntry unstaging or stashing your changes")]
-	SignRewordLastCommitStaged,
 }
 
 ///
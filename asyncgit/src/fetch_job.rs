@@ -3,22 +3,31 @@
 use crate::{
 	asyncjob::{AsyncJob, RunParams},
 	error::Result,
-	sync::remotes::fetch_all,
+	sync::remotes::{
+		fetch_all, push::ProgressNotification, FetchSummary,
+		RemoteFetchOptions,
+	},
 	sync::{cred::BasicAuthCredential, RepoPath},
-	AsyncGitNotification, ProgressPercent,
+	AsyncGitNotification, RemoteProgress,
+};
+use crossbeam_channel::unbounded;
+use std::sync::{
+	atomic::{AtomicBool, Ordering},
+	Arc, Mutex,
 };
-
-use std::sync::{Arc, Mutex};
 
 enum JobState {
-	Request(Option<BasicAuthCredential>),
-	Response(Result<()>),
+	Request(RemoteFetchOptions, Option<BasicAuthCredential>),
+	Response(Result<FetchSummary>),
 }
 
-///
+/// clones share their state so the progress can be followed and the
+/// fetch cancelled from the outside while the job runs
 #[derive(Clone)]
 pub struct AsyncFetchJob {
 	state: Arc<Mutex<Option<JobState>>>,
+	progress: Arc<Mutex<Option<ProgressNotification>>>,
+	cancel: Arc<AtomicBool>,
 	repo: RepoPath,
 }
 
@@ -27,35 +36,89 @@ impl AsyncFetchJob {
 	///
 	pub fn new(
 		repo: RepoPath,
+		options: RemoteFetchOptions,
 		basic_credential: Option<BasicAuthCredential>,
 	) -> Self {
 		Self {
 			repo,
 			state: Arc::new(Mutex::new(Some(JobState::Request(
+				options,
 				basic_credential,
 			)))),
+			progress: Arc::new(Mutex::new(None)),
+			cancel: Arc::new(AtomicBool::new(false)),
+		}
+	}
+
+	///
+	pub fn result(&self) -> Option<Result<FetchSummary>> {
+		if let Ok(mut state) = self.state.lock() {
+			if let Some(state) = state.take() {
+				return match state {
+					JobState::Request(..) => None,
+					JobState::Response(result) => Some(result),
+				};
+			}
 		}
+
+		None
+	}
+
+	///
+	pub fn progress(&self) -> Option<RemoteProgress> {
+		self.progress
+			.lock()
+			.ok()?
+			.as_ref()
+			.map(|progress| progress.clone().into())
+	}
+
+	/// aborts the fetch with its next transfer update
+	pub fn cancel(&self) {
+		self.cancel.store(true, Ordering::Relaxed);
+	}
+
+	///
+	pub fn is_cancelled(&self) -> bool {
+		self.cancel.load(Ordering::Relaxed)
 	}
 }
 
 impl AsyncJob for AsyncFetchJob {
 	type Notification = AsyncGitNotification;
-	type Progress = ProgressPercent;
+	type Progress = ();
 
 	fn run(
 		&mut self,
-		_params: RunParams<Self::Notification, Self::Progress>,
+		params: RunParams<Self::Notification, Self::Progress>,
 	) -> Result<Self::Notification> {
 		if let Ok(mut state) = self.state.lock() {
 			*state = state.take().map(|state| match state {
-				JobState::Request(basic_credentials) => {
-					//TODO: support progress
+				JobState::Request(options, basic_credentials) => {
+					let (progress_sender, receiver) = unbounded();
+
+					let handle =
+						RemoteProgress::spawn_receiver_thread(
+							AsyncGitNotification::Fetch,
+							params.sender(),
+							receiver,
+							Arc::clone(&self.progress),
+							Some(Arc::clone(&self.cancel)),
+						);
+
 					let result = fetch_all(
 						&self.repo,
+						&options,
 						&basic_credentials,
-						&None,
+						&Some(progress_sender.clone()),
 					);
 
+					// fails if cancelled, the receiver is gone already
+					progress_sender
+						.send(ProgressNotification::Done)
+						.ok();
+					handle.join().ok();
+
 					JobState::Response(result)
 				}
 				JobState::Response(result) => {
@@ -0,0 +1,152 @@
+use crate::{
+	asyncjob::{AsyncJob, RunParams},
+	error::Result,
+	sync::{
+		file_history_walk, line_history_walk, CommitId,
+		FileHistoryEntry, RepoPath,
+	},
+	AsyncGitNotification,
+};
+use std::sync::{
+	atomic::{AtomicBool, Ordering},
+	Arc, Mutex,
+};
+
+enum JobState {
+	Request {
+		repo: RepoPath,
+		path: String,
+		lines: Option<(Option<CommitId>, (usize, usize))>,
+	},
+	Response(Result<()>),
+}
+
+/// walks the history of a file (or lines of it) following renames in
+/// the background. entries are handed out in chunks while the walk
+/// continues, a `FileLog` notification is sent whenever new ones
+/// are waiting to be extracted.
+#[derive(Clone, Default)]
+pub struct AsyncFileHistoryJob {
+	state: Arc<Mutex<Option<JobState>>>,
+	/// found by the walk but not extracted yet
+	entries: Arc<Mutex<Vec<FileHistoryEntry>>>,
+	stopped: Arc<AtomicBool>,
+}
+
+///
+impl AsyncFileHistoryJob {
+	///
+	pub fn new(repo: RepoPath, path: String) -> Self {
+		Self {
+			state: Arc::new(Mutex::new(Some(JobState::Request {
+				repo,
+				path,
+				lines: None,
+			}))),
+			..Self::default()
+		}
+	}
+
+	/// follows `lines` of `path` as they are in `commit` (or `HEAD`)
+	pub fn new_lines(
+		repo: RepoPath,
+		path: String,
+		commit: Option<CommitId>,
+		lines: (usize, usize),
+	) -> Self {
+		Self {
+			state: Arc::new(Mutex::new(Some(JobState::Request {
+				repo,
+				path,
+				lines: Some((commit, lines)),
+			}))),
+			..Self::default()
+		}
+	}
+
+	/// takes the entries found since the last call (newest first)
+	pub fn extract_entries(&self) -> Vec<FileHistoryEntry> {
+		self.entries
+			.lock()
+			.map(|mut entries| std::mem::take(&mut *entries))
+			.unwrap_or_default()
+	}
+
+	/// `Some` once the walk is done
+	pub fn result(&self) -> Option<Result<()>> {
+		if let Ok(mut state) = self.state.lock() {
+			if let Some(state) = state.take() {
+				return match state {
+					JobState::Request { .. } => None,
+					JobState::Response(result) => Some(result),
+				};
+			}
+		}
+
+		None
+	}
+
+	/// makes the walk end at the next entry it finds
+	pub fn stop(&self) {
+		self.stopped.store(true, Ordering::Relaxed);
+	}
+
+	fn push_entry(
+		&self,
+		params: &RunParams<AsyncGitNotification, ()>,
+		entry: FileHistoryEntry,
+	) -> bool {
+		if let Ok(mut entries) = self.entries.lock() {
+			// the ui extracts all of them at once, there only has
+			// to be one notification per chunk
+			if entries.is_empty() {
+				params.send(AsyncGitNotification::FileLog).ok();
+			}
+			entries.push(entry);
+		}
+
+		!self.stopped.load(Ordering::Relaxed)
+	}
+}
+
+impl AsyncJob for AsyncFileHistoryJob {
+	type Notification = AsyncGitNotification;
+	type Progress = ();
+
+	fn run(
+		&mut self,
+		params: RunParams<Self::Notification, Self::Progress>,
+	) -> Result<Self::Notification> {
+		// not holding the lock during the walk, `result` is polled
+		// while it runs
+		let request =
+			self.state.lock().ok().and_then(|mut state| match state
+				.take()
+			{
+				Some(JobState::Request { repo, path, lines }) => {
+					Some((repo, path, lines))
+				}
+				other => {
+					*state = other;
+					None
+				}
+			});
+
+		if let Some((repo, path, lines)) = request {
+			let on_entry = |entry| self.push_entry(&params, entry);
+
+			let result = match lines {
+				Some((commit, lines)) => line_history_walk(
+					&repo, &path, commit, lines, on_entry,
+				),
+				None => file_history_walk(&repo, &path, on_entry),
+			};
+
+			if let Ok(mut state) = self.state.lock() {
+				*state = Some(JobState::Response(result));
+			}
+		}
+
+		Ok(AsyncGitNotification::FileLog)
+	}
+}
@@ -0,0 +1,91 @@
+use crate::{
+	asyncjob::{AsyncJob, RunParams},
+	error::Result,
+	sync::{
+		cred::BasicAuthCredential, force_push_preview,
+		ForcePushPreview, RepoPath,
+	},
+	AsyncGitNotification,
+};
+use std::sync::{Arc, Mutex};
+
+enum JobState {
+	Request {
+		repo: RepoPath,
+		remote: String,
+		branch: String,
+		basic_credential: Option<BasicAuthCredential>,
+	},
+	Response(Result<ForcePushPreview>),
+}
+
+/// fetches the remote tip and lists what a force push would discard
+#[derive(Clone)]
+pub struct AsyncForcePushPreviewJob {
+	state: Arc<Mutex<Option<JobState>>>,
+}
+
+///
+impl AsyncForcePushPreviewJob {
+	///
+	pub fn new(
+		repo: RepoPath,
+		remote: String,
+		branch: String,
+		basic_credential: Option<BasicAuthCredential>,
+	) -> Self {
+		Self {
+			state: Arc::new(Mutex::new(Some(JobState::Request {
+				repo,
+				remote,
+				branch,
+				basic_credential,
+			}))),
+		}
+	}
+
+	///
+	pub fn result(&self) -> Option<Result<ForcePushPreview>> {
+		if let Ok(mut state) = self.state.lock() {
+			if let Some(state) = state.take() {
+				return match state {
+					JobState::Request { .. } => None,
+					JobState::Response(result) => Some(result),
+				};
+			}
+		}
+
+		None
+	}
+}
+
+impl AsyncJob for AsyncForcePushPreviewJob {
+	type Notification = AsyncGitNotification;
+	type Progress = ();
+
+	fn run(
+		&mut self,
+		_params: RunParams<Self::Notification, Self::Progress>,
+	) -> Result<Self::Notification> {
+		if let Ok(mut state) = self.state.lock() {
+			*state = state.take().map(|state| match state {
+				JobState::Request {
+					repo,
+					remote,
+					branch,
+					basic_credential,
+				} => JobState::Response(force_push_preview(
+					&repo,
+					&remote,
+					&branch,
+					basic_credential,
+				)),
+				JobState::Response(result) => {
+					JobState::Response(result)
+				}
+			});
+		}
+
+		Ok(AsyncGitNotification::ForcePushPreview)
+	}
+}
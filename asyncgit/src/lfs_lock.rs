@@ -0,0 +1,78 @@
+use crate::{
+	asyncjob::{AsyncJob, RunParams},
+	error::Result,
+	sync::{lfs_lock, lfs_unlock, RepoPath},
+	AsyncGitNotification,
+};
+use std::sync::{Arc, Mutex};
+
+enum JobState {
+	Request {
+		repo: RepoPath,
+		path: String,
+		lock: bool,
+	},
+	Response(Result<String>),
+}
+
+/// runs `git lfs lock`/`git lfs unlock` for a file in the background,
+/// talking to the LFS server can take a while
+#[derive(Clone, Default)]
+pub struct AsyncLfsLockJob {
+	state: Arc<Mutex<Option<JobState>>>,
+}
+
+///
+impl AsyncLfsLockJob {
+	/// locks `path` or unlocks it if `lock` is `false`
+	pub fn new(repo: RepoPath, path: String, lock: bool) -> Self {
+		Self {
+			state: Arc::new(Mutex::new(Some(JobState::Request {
+				repo,
+				path,
+				lock,
+			}))),
+		}
+	}
+
+	/// output of `git lfs`
+	pub fn result(&self) -> Option<Result<String>> {
+		if let Ok(mut state) = self.state.lock() {
+			if let Some(state) = state.take() {
+				return match state {
+					JobState::Request { .. } => None,
+					JobState::Response(result) => Some(result),
+				};
+			}
+		}
+
+		None
+	}
+}
+
+impl AsyncJob for AsyncLfsLockJob {
+	type Notification = AsyncGitNotification;
+	type Progress = ();
+
+	fn run(
+		&mut self,
+		_params: RunParams<Self::Notification, Self::Progress>,
+	) -> Result<Self::Notification> {
+		if let Ok(mut state) = self.state.lock() {
+			*state = state.take().map(|state| match state {
+				JobState::Request { repo, path, lock } => {
+					JobState::Response(if lock {
+						lfs_lock(&repo, &path)
+					} else {
+						lfs_unlock(&repo, &path)
+					})
+				}
+				JobState::Response(result) => {
+					JobState::Response(result)
+				}
+			});
+		}
+
+		Ok(AsyncGitNotification::LfsLock)
+	}
+}
@@ -33,40 +33,66 @@
 )]
 
 pub mod asyncjob;
+mod auto_fetch;
 mod blame;
 mod branches;
 pub mod cached;
+mod cherry;
+mod clone;
 mod commit_files;
+mod commit_graph;
+mod describe;
 mod diff;
 mod error;
 mod fetch_job;
+mod file_history;
 mod filter_commits;
+mod force_push_preview;
+mod lfs_lock;
+mod notes_sync;
+mod pre_commit;
 mod progress;
 mod pull;
 mod push;
 mod push_tags;
 pub mod remote_progress;
 pub mod remote_tags;
+mod reset_preview;
 mod revlog;
+mod signatures;
 mod status;
 pub mod sync;
 mod tags;
 mod treefiles;
 
 pub use crate::{
+	auto_fetch::{AsyncAutoFetch, AutoFetchStatus},
 	blame::{AsyncBlame, BlameParams},
 	branches::AsyncBranchesJob,
+	cherry::{
+		AsyncCherryJob, AsyncCompareCherryJob, DuplicateDetails,
+	},
+	clone::{AsyncClone, CloneRequest},
 	commit_files::{AsyncCommitFiles, CommitFilesParams},
+	commit_graph::AsyncCommitGraphJob,
+	describe::AsyncDescribeJob,
 	diff::{AsyncDiff, DiffParams, DiffType},
 	error::{Error, Result},
 	fetch_job::AsyncFetchJob,
+	file_history::AsyncFileHistoryJob,
 	filter_commits::{AsyncCommitFilterJob, CommitFilterResult},
+	force_push_preview::AsyncForcePushPreviewJob,
+	lfs_lock::AsyncLfsLockJob,
+	notes_sync::{AsyncNotesSyncJob, NotesSync},
+	pre_commit::AsyncPreCommitJob,
 	progress::ProgressPercent,
 	pull::{AsyncPull, FetchRequest},
 	push::{AsyncPush, PushRequest},
 	push_tags::{AsyncPushTags, PushTagsRequest},
 	remote_progress::{RemoteProgress, RemoteProgressState},
+	reset_preview::AsyncResetPreviewJob,
 	revlog::{AsyncLog, FetchStatus},
+	signatures::{AsyncSignaturesJob, Signatures},
 	status::{AsyncStatus, StatusParams},
 	sync::{
 		diff::{DiffLine, DiffLineType, FileDiff},
@@ -93,7 +119,7 @@ pub enum AsyncGitNotification {
 	Diff,
 	///
 	Log,
-	///
+	/// history of a single file following renames
 	FileLog,
 	///
 	CommitFiles,
@@ -117,6 +143,28 @@ pub enum AsyncGitNotification {
 	TreeFiles,
 	///
 	CommitFilter,
+	///
+	Cherry,
+	///
+	Describe,
+	///
+	Clone,
+	///
+	ForcePushPreview,
+	///
+	PreCommit,
+	///
+	Signatures,
+	///
+	NotesSync,
+	/// background fetch finished
+	AutoFetch,
+	///
+	LfsLock,
+	///
+	ResetPreview,
+	///
+	CommitGraph,
 }
 
 /// helper function to calculate the hash of an arbitrary type that implements the `Hash` trait
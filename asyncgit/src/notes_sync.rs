@@ -0,0 +1,108 @@
+use crate::{
+	asyncjob::{AsyncJob, RunParams},
+	error::Result,
+	sync::{
+		cred::BasicAuthCredential, fetch_notes, push_notes, RepoPath,
+	},
+	AsyncGitNotification,
+};
+use std::sync::{Arc, Mutex};
+
+/// direction to sync the notes ref with a remote in
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum NotesSync {
+	///
+	Push,
+	///
+	Fetch,
+}
+
+enum JobState {
+	Request {
+		repo: RepoPath,
+		remote: String,
+		basic_credential: Option<BasicAuthCredential>,
+	},
+	Response(Result<()>),
+}
+
+/// pushes or fetches the notes ref
+#[derive(Clone)]
+pub struct AsyncNotesSyncJob {
+	sync: NotesSync,
+	state: Arc<Mutex<Option<JobState>>>,
+}
+
+///
+impl AsyncNotesSyncJob {
+	///
+	pub fn new(
+		repo: RepoPath,
+		sync: NotesSync,
+		remote: String,
+		basic_credential: Option<BasicAuthCredential>,
+	) -> Self {
+		Self {
+			sync,
+			state: Arc::new(Mutex::new(Some(JobState::Request {
+				repo,
+				remote,
+				basic_credential,
+			}))),
+		}
+	}
+
+	///
+	pub fn result(&self) -> Option<Result<()>> {
+		if let Ok(mut state) = self.state.lock() {
+			if let Some(state) = state.take() {
+				return match state {
+					JobState::Request { .. } => None,
+					JobState::Response(result) => Some(result),
+				};
+			}
+		}
+
+		None
+	}
+}
+
+impl AsyncJob for AsyncNotesSyncJob {
+	type Notification = AsyncGitNotification;
+	type Progress = ();
+
+	fn run(
+		&mut self,
+		_params: RunParams<Self::Notification, Self::Progress>,
+	) -> Result<Self::Notification> {
+		let sync = self.sync;
+
+		if let Ok(mut state) = self.state.lock() {
+			*state = state.take().map(|state| match state {
+				JobState::Request {
+					repo,
+					remote,
+					basic_credential,
+				} => JobState::Response(match sync {
+					NotesSync::Push => push_notes(
+						&repo,
+						&remote,
+						basic_credential,
+						None,
+					),
+					NotesSync::Fetch => fetch_notes(
+						&repo,
+						&remote,
+						basic_credential,
+						None,
+					),
+				}),
+				JobState::Response(result) => {
+					JobState::Response(result)
+				}
+			});
+		}
+
+		Ok(AsyncGitNotification::NotesSync)
+	}
+}
@@ -0,0 +1,86 @@
+use crate::{
+	asyncjob::{AsyncJob, RunParams},
+	error::Result,
+	sync::{
+		hooks_pre_commit_streamed, HookOutputLine, HookResult,
+		RepoPath,
+	},
+	AsyncGitNotification,
+};
+use std::sync::{
+	atomic::{AtomicBool, Ordering},
+	Arc, Mutex,
+};
+
+/// runs the `pre-commit` hook reporting its output while it runs.
+/// clones share their state so the output can be followed and the
+/// hook cancelled from the outside while the job runs.
+#[derive(Clone)]
+pub struct AsyncPreCommitJob {
+	repo: RepoPath,
+	output: Arc<Mutex<Vec<HookOutputLine>>>,
+	cancel: Arc<AtomicBool>,
+	result: Arc<Mutex<Option<Result<HookResult>>>>,
+}
+
+impl AsyncPreCommitJob {
+	///
+	pub fn new(repo: RepoPath) -> Self {
+		Self {
+			repo,
+			output: Arc::new(Mutex::new(Vec::new())),
+			cancel: Arc::new(AtomicBool::new(false)),
+			result: Arc::new(Mutex::new(None)),
+		}
+	}
+
+	/// output of the hook so far
+	pub fn output(&self) -> Vec<HookOutputLine> {
+		self.output
+			.lock()
+			.map(|output| output.clone())
+			.unwrap_or_default()
+	}
+
+	/// kills the hook
+	pub fn cancel(&self) {
+		self.cancel.store(true, Ordering::Relaxed);
+	}
+
+	///
+	pub fn is_cancelled(&self) -> bool {
+		self.cancel.load(Ordering::Relaxed)
+	}
+
+	/// takes the result once the hook finished
+	pub fn result(&self) -> Option<Result<HookResult>> {
+		self.result.lock().ok().and_then(|mut result| result.take())
+	}
+}
+
+impl AsyncJob for AsyncPreCommitJob {
+	type Notification = AsyncGitNotification;
+	type Progress = ();
+
+	fn run(
+		&mut self,
+		params: RunParams<Self::Notification, Self::Progress>,
+	) -> Result<Self::Notification> {
+		let res = hooks_pre_commit_streamed(
+			&self.repo,
+			&mut |line| {
+				if let Ok(mut output) = self.output.lock() {
+					output.push(line);
+				}
+				params.send(AsyncGitNotification::PreCommit).ok();
+			},
+			&self.cancel,
+		);
+
+		if let Ok(mut result) = self.result.lock() {
+			*result = Some(res);
+		}
+
+		Ok(AsyncGitNotification::PreCommit)
+	}
+}
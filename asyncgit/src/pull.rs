@@ -2,14 +2,17 @@ use crate::{
 	error::{Error, Result},
 	sync::{
 		cred::BasicAuthCredential,
-		remotes::{fetch, push::ProgressNotification},
-		RepoPath,
+		remotes::{fetch, fetch_target, push::ProgressNotification},
+		RemoteTarget, RepoPath,
 	},
 	AsyncGitNotification, RemoteProgress,
 };
 use crossbeam_channel::{unbounded, Sender};
 use std::{
-	sync::{Arc, Mutex},
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc, Mutex,
+	},
 	thread,
 };
 
@@ -22,6 +25,8 @@ pub struct FetchRequest {
 	pub branch: String,
 	///
 	pub basic_credential: Option<BasicAuthCredential>,
+	/// fetch only this remote branch instead of the upstream
+	pub target: Option<RemoteTarget>,
 }
 
 //TODO: since this is empty we can go with a simple AtomicBool to mark that we are fetching or not
@@ -33,6 +38,7 @@ pub struct AsyncPull {
 	state: Arc<Mutex<Option<FetchState>>>,
 	last_result: Arc<Mutex<Option<(usize, String)>>>,
 	progress: Arc<Mutex<Option<ProgressNotification>>>,
+	cancel: Arc<AtomicBool>,
 	sender: Sender<AsyncGitNotification>,
 	repo: RepoPath,
 }
@@ -48,6 +54,7 @@ impl AsyncPull {
 			state: Arc::new(Mutex::new(None)),
 			last_result: Arc::new(Mutex::new(None)),
 			progress: Arc::new(Mutex::new(None)),
+			cancel: Arc::new(AtomicBool::new(false)),
 			sender: sender.clone(),
 		}
 	}
@@ -70,6 +77,16 @@ impl AsyncPull {
 		Ok(res.as_ref().map(|progress| progress.clone().into()))
 	}
 
+	/// aborts the pending request as soon as git allows it to
+	pub fn cancel(&self) {
+		self.cancel.store(true, Ordering::Relaxed);
+	}
+
+	/// `true` if the pending or last request got cancelled
+	pub fn is_cancelled(&self) -> bool {
+		self.cancel.load(Ordering::Relaxed)
+	}
+
 	///
 	pub fn request(&self, params: FetchRequest) -> Result<()> {
 		log::trace!("request");
@@ -80,10 +97,12 @@ impl AsyncPull {
 
 		self.set_request(&params)?;
 		RemoteProgress::set_progress(&self.progress, None)?;
+		self.cancel.store(false, Ordering::Relaxed);
 
 		let arc_state = Arc::clone(&self.state);
 		let arc_res = Arc::clone(&self.last_result);
 		let arc_progress = Arc::clone(&self.progress);
+		let arc_cancel = Arc::clone(&self.cancel);
 		let sender = self.sender.clone();
 		let repo = self.repo.clone();
 
@@ -95,18 +114,27 @@ impl AsyncPull {
 				sender.clone(),
 				receiver,
 				arc_progress,
+				Some(arc_cancel),
 			);
 
-			let res = fetch(
-				&repo,
-				&params.branch,
-				params.basic_credential,
-				Some(progress_sender.clone()),
-			);
-
-			progress_sender
-				.send(ProgressNotification::Done)
-				.expect("closing send failed");
+			let res = if let Some(target) = &params.target {
+				fetch_target(
+					&repo,
+					target,
+					params.basic_credential,
+					Some(progress_sender.clone()),
+				)
+			} else {
+				fetch(
+					&repo,
+					&params.branch,
+					params.basic_credential,
+					Some(progress_sender.clone()),
+				)
+			};
+
+			// fails if cancelled, the receiver is gone already
+			progress_sender.send(ProgressNotification::Done).ok();
 
 			handle.join().expect("joining thread failed");
 
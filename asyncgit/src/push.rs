@@ -2,15 +2,21 @@ use crate::{
 	error::{Error, Result},
 	sync::{
 		cred::BasicAuthCredential,
-		remotes::push::push_raw,
+		remotes::push::{
+			push_branch_to, push_branch_with_lease, push_raw,
+			push_stack,
+		},
 		remotes::push::{ProgressNotification, PushType},
-		RepoPath,
+		CommitId, RemoteTarget, RepoPath,
 	},
 	AsyncGitNotification, RemoteProgress,
 };
 use crossbeam_channel::{unbounded, Sender};
 use std::{
-	sync::{Arc, Mutex},
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc, Mutex,
+	},
 	thread,
 };
 
@@ -27,8 +33,18 @@ pub struct PushRequest {
 	pub force: bool,
 	///
 	pub delete: bool,
+	/// skip the `pre-push` hook, like `git push --no-verify`
+	pub no_verify: bool,
 	///
 	pub basic_credential: Option<BasicAuthCredential>,
+	/// if not empty, all these branches are pushed together
+	/// (force-with-lease) instead of `branch`
+	pub stack: Vec<String>,
+	/// force push `branch` only if the remote branch still points to
+	/// this commit (zero if it is expected to not exist)
+	pub lease: Option<CommitId>,
+	/// push `branch` to this remote branch instead of its upstream
+	pub target: Option<RemoteTarget>,
 }
 
 //TODO: since this is empty we can go with a simple AtomicBool to mark that we are fetching or not
@@ -40,6 +56,7 @@ pub struct AsyncPush {
 	state: Arc<Mutex<Option<PushState>>>,
 	last_result: Arc<Mutex<Option<String>>>,
 	progress: Arc<Mutex<Option<ProgressNotification>>>,
+	cancel: Arc<AtomicBool>,
 	sender: Sender<AsyncGitNotification>,
 	repo: RepoPath,
 }
@@ -55,6 +72,7 @@ impl AsyncPush {
 			state: Arc::new(Mutex::new(None)),
 			last_result: Arc::new(Mutex::new(None)),
 			progress: Arc::new(Mutex::new(None)),
+			cancel: Arc::new(AtomicBool::new(false)),
 			sender: sender.clone(),
 		}
 	}
@@ -77,6 +95,16 @@ impl AsyncPush {
 		Ok(res.as_ref().map(|progress| progress.clone().into()))
 	}
 
+	/// aborts the pending request as soon as git allows it to
+	pub fn cancel(&self) {
+		self.cancel.store(true, Ordering::Relaxed);
+	}
+
+	/// `true` if the pending or last request got cancelled
+	pub fn is_cancelled(&self) -> bool {
+		self.cancel.load(Ordering::Relaxed)
+	}
+
 	///
 	pub fn request(&self, params: PushRequest) -> Result<()> {
 		log::trace!("request");
@@ -87,10 +115,12 @@ impl AsyncPush {
 
 		self.set_request(&params)?;
 		RemoteProgress::set_progress(&self.progress, None)?;
+		self.cancel.store(false, Ordering::Relaxed);
 
 		let arc_state = Arc::clone(&self.state);
 		let arc_res = Arc::clone(&self.last_result);
 		let arc_progress = Arc::clone(&self.progress);
+		let arc_cancel = Arc::clone(&self.cancel);
 		let sender = self.sender.clone();
 		let repo = self.repo.clone();
 
@@ -102,22 +132,54 @@ impl AsyncPush {
 				sender.clone(),
 				receiver,
 				arc_progress,
+				Some(arc_cancel),
 			);
 
-			let res = push_raw(
-				&repo,
-				params.remote.as_str(),
-				params.branch.as_str(),
-				params.push_type,
-				params.force,
-				params.delete,
-				params.basic_credential.clone(),
-				Some(progress_sender.clone()),
-			);
-
-			progress_sender
-				.send(ProgressNotification::Done)
-				.expect("closing send failed");
+			let res = if let Some(target) = &params.target {
+				push_branch_to(
+					&repo,
+					params.branch.as_str(),
+					target,
+					params.force,
+					params.no_verify,
+					params.basic_credential.clone(),
+					Some(progress_sender.clone()),
+				)
+			} else if let Some(lease) = params.lease {
+				push_branch_with_lease(
+					&repo,
+					params.remote.as_str(),
+					params.branch.as_str(),
+					lease,
+					params.no_verify,
+					params.basic_credential.clone(),
+					Some(progress_sender.clone()),
+				)
+			} else if params.stack.is_empty() {
+				push_raw(
+					&repo,
+					params.remote.as_str(),
+					params.branch.as_str(),
+					params.push_type,
+					params.force,
+					params.delete,
+					params.no_verify,
+					params.basic_credential.clone(),
+					Some(progress_sender.clone()),
+				)
+			} else {
+				push_stack(
+					&repo,
+					params.remote.as_str(),
+					&params.stack,
+					params.no_verify,
+					params.basic_credential.clone(),
+					Some(progress_sender.clone()),
+				)
+			};
+
+			// fails if cancelled, the receiver is gone already
+			progress_sender.send(ProgressNotification::Done).ok();
 
 			handle.join().expect("joining thread failed");
 
@@ -93,6 +93,7 @@ impl AsyncPushTags {
 				sender.clone(),
 				receiver,
 				arc_progress,
+				None,
 			);
 
 			let res = push_tags(
@@ -6,13 +6,21 @@ use crate::{
 	sync::remotes::push::{AsyncProgress, ProgressNotification},
 	AsyncGitNotification,
 };
-use crossbeam_channel::{Receiver, Sender};
+use crossbeam_channel::{Receiver, RecvTimeoutError, Sender};
+use easy_cast::{Conv, ConvFloat};
 use git2::PackBuilderStage;
 use std::{
-	sync::{Arc, Mutex},
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc, Mutex,
+	},
 	thread::{self, JoinHandle},
+	time::Duration,
 };
 
+/// how often the receiver thread checks for cancellation
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
 /// used for push/pull
 #[derive(Clone, Debug)]
 pub enum RemoteProgressState {
@@ -35,6 +43,14 @@ pub struct RemoteProgress {
 	pub state: RemoteProgressState,
 	///
 	pub progress: ProgressPercent,
+	/// objects done so far
+	pub current: usize,
+	///
+	pub total: usize,
+	/// transferred so far, zero while packing
+	pub bytes: usize,
+	/// since the transfer started, zero while packing
+	pub elapsed: Duration,
 }
 
 impl RemoteProgress {
@@ -47,14 +63,57 @@ impl RemoteProgress {
 		Self {
 			state,
 			progress: ProgressPercent::new(current, total),
+			current,
+			total,
+			bytes: 0,
+			elapsed: Duration::ZERO,
 		}
 	}
 
+	#[must_use]
+	const fn with_transfer(
+		mut self,
+		bytes: usize,
+		elapsed: Duration,
+	) -> Self {
+		self.bytes = bytes;
+		self.elapsed = elapsed;
+		self
+	}
+
 	///
 	pub const fn get_progress_percent(&self) -> u8 {
 		self.progress.progress
 	}
 
+	/// average transfer rate so far
+	pub fn bytes_per_second(&self) -> Option<u64> {
+		let secs = self.elapsed.as_secs_f64();
+
+		if self.bytes == 0 || secs <= 0.0 {
+			return None;
+		}
+
+		u64::try_conv_nearest(f64::conv(self.bytes) / secs).ok()
+	}
+
+	/// estimated time left assuming the objects keep coming in at
+	/// the same pace
+	pub fn eta(&self) -> Option<Duration> {
+		if self.elapsed.is_zero()
+			|| self.current == 0
+			|| self.current >= self.total
+		{
+			return None;
+		}
+
+		let left = f64::conv(self.total - self.current)
+			/ f64::conv(self.current);
+
+		Duration::try_from_secs_f64(self.elapsed.as_secs_f64() * left)
+			.ok()
+	}
+
 	pub(crate) fn set_progress<T>(
 		progress: &Arc<Mutex<Option<T>>>,
 		state: Option<T>,
@@ -66,7 +125,9 @@ impl RemoteProgress {
 		Ok(())
 	}
 
-	/// spawn thread to listen to progress notifications coming in from blocking remote git method (fetch/push)
+	/// spawn thread to listen to progress notifications coming in from blocking remote git method (fetch/push).
+	/// setting `cancel` stops listening, which makes the remote
+	/// operation abort (see `Callbacks`)
 	pub(crate) fn spawn_receiver_thread<
 		T: 'static + AsyncProgress,
 	>(
@@ -74,9 +135,11 @@ impl RemoteProgress {
 		sender: Sender<AsyncGitNotification>,
 		receiver: Receiver<T>,
 		progress: Arc<Mutex<Option<T>>>,
+		cancel: Option<Arc<AtomicBool>>,
 	) -> JoinHandle<()> {
 		thread::spawn(move || loop {
-			let incoming = receiver.recv();
+			let incoming =
+				receiver.recv_timeout(CANCEL_POLL_INTERVAL);
 			match incoming {
 				Ok(update) => {
 					Self::set_progress(
@@ -94,6 +157,14 @@ impl RemoteProgress {
 						break;
 					}
 				}
+				Err(RecvTimeoutError::Timeout) => {
+					if cancel.as_ref().is_some_and(|cancel| {
+						cancel.load(Ordering::Relaxed)
+					}) {
+						log::debug!("remote progress cancelled");
+						break;
+					}
+				}
 				Err(e) => {
 					log::error!(
 						"remote progress receiver error: {}",
@@ -128,22 +199,54 @@ impl From<ProgressNotification> for RemoteProgress {
 			ProgressNotification::PushTransfer {
 				current,
 				total,
-				..
+				bytes,
+				elapsed,
 			} => Self::new(
 				RemoteProgressState::Pushing,
 				current,
 				total,
-			),
+			)
+			.with_transfer(bytes, elapsed),
 			ProgressNotification::Transfer {
 				objects,
 				total_objects,
-				..
+				received_bytes,
+				elapsed,
 			} => Self::new(
 				RemoteProgressState::Transfer,
 				objects,
 				total_objects,
-			),
+			)
+			.with_transfer(received_bytes, elapsed),
 			_ => Self::new(RemoteProgressState::Done, 1, 1),
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_rate_and_eta() {
+		let progress =
+			RemoteProgress::from(ProgressNotification::Transfer {
+				objects: 25,
+				total_objects: 100,
+				received_bytes: 4096,
+				elapsed: Duration::from_secs(2),
+			});
+
+		assert_eq!(progress.bytes_per_second(), Some(2048));
+		assert_eq!(progress.eta(), Some(Duration::from_secs(6)));
+
+		let packing = RemoteProgress::new(
+			RemoteProgressState::PackingAddingObject,
+			1,
+			2,
+		);
+
+		assert_eq!(packing.bytes_per_second(), None);
+		assert_eq!(packing.eta(), None);
+	}
+}
@@ -0,0 +1,88 @@
+use crate::{
+	asyncjob::{AsyncJob, RunParams},
+	error::Result,
+	sync::{
+		reset_preview, CommitId, RepoPath, ResetPreview, ResetType,
+	},
+	AsyncGitNotification,
+};
+use std::sync::{Arc, Mutex};
+
+enum JobState {
+	Request {
+		repo: RepoPath,
+		commit: CommitId,
+		kind: ResetType,
+	},
+	Response((CommitId, ResetType), Result<ResetPreview>),
+}
+
+/// walks the commits leaving the branch and diffs the affected files
+/// of a reset in the background
+#[derive(Clone, Default)]
+pub struct AsyncResetPreviewJob {
+	state: Arc<Mutex<Option<JobState>>>,
+}
+
+///
+impl AsyncResetPreviewJob {
+	///
+	pub fn new(
+		repo: RepoPath,
+		commit: CommitId,
+		kind: ResetType,
+	) -> Self {
+		Self {
+			state: Arc::new(Mutex::new(Some(JobState::Request {
+				repo,
+				commit,
+				kind,
+			}))),
+		}
+	}
+
+	/// reset target and mode the preview belongs to and the preview
+	/// itself
+	pub fn result(
+		&self,
+	) -> Option<((CommitId, ResetType), Result<ResetPreview>)> {
+		if let Ok(mut state) = self.state.lock() {
+			if let Some(state) = state.take() {
+				return match state {
+					JobState::Request { .. } => None,
+					JobState::Response(target, result) => {
+						Some((target, result))
+					}
+				};
+			}
+		}
+
+		None
+	}
+}
+
+impl AsyncJob for AsyncResetPreviewJob {
+	type Notification = AsyncGitNotification;
+	type Progress = ();
+
+	fn run(
+		&mut self,
+		_params: RunParams<Self::Notification, Self::Progress>,
+	) -> Result<Self::Notification> {
+		if let Ok(mut state) = self.state.lock() {
+			*state = state.take().map(|state| match state {
+				JobState::Request { repo, commit, kind } => {
+					JobState::Response(
+						(commit, kind),
+						reset_preview(&repo, commit, kind),
+					)
+				}
+				JobState::Response(target, result) => {
+					JobState::Response(target, result)
+				}
+			});
+		}
+
+		Ok(AsyncGitNotification::ResetPreview)
+	}
+}
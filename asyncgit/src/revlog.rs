@@ -1,16 +1,17 @@
 use crate::{
 	error::Result,
 	sync::{
-		repo, CommitId, LogWalker, LogWalkerWithoutFilter, RepoPath,
-		SharedCommitFilterFn,
+		repo, CommitId, LogScope, LogWalker, LogWalkerWithoutFilter,
+		RepoPath, SharedCommitFilterFn,
 	},
 	AsyncGitNotification, Error,
 };
 use crossbeam_channel::Sender;
 use scopetime::scope_time;
 use std::{
+	collections::HashMap,
 	sync::{
-		atomic::{AtomicBool, Ordering},
+		atomic::{AtomicBool, AtomicUsize, Ordering},
 		Arc, Mutex,
 	},
 	thread,
@@ -29,27 +30,94 @@ pub enum FetchStatus {
 }
 
 ///
+#[derive(Default)]
 pub struct AsyncLogResult {
 	///
 	pub commits: Vec<CommitId>,
+	/// see `LogWalker::ref_mask`
+	pub ref_masks: HashMap<CommitId, u64>,
 	///
 	pub duration: Duration,
+	/// commits listed so far, unlike `commits` not reset by
+	/// `extract_items`
+	pub loaded: usize,
+	/// the walk reached the end of the history
+	pub complete: bool,
 }
-///
-pub struct AsyncLog {
+/// settings of a single log walk
+#[derive(Clone, Default)]
+struct LogWalkOptions {
+	filter: Option<SharedCommitFilterFn>,
+	first_parent: bool,
+	pathspec: Option<String>,
+	scope: LogScope,
+}
+
+/// state a walk shares with its `AsyncLog`
+#[derive(Clone)]
+struct LogWalkShared {
 	current: Arc<Mutex<AsyncLogResult>>,
-	current_head: Arc<Mutex<Option<CommitId>>>,
-	sender: Sender<AsyncGitNotification>,
 	pending: Arc<AtomicBool>,
 	background: Arc<AtomicBool>,
-	filter: Option<SharedCommitFilterFn>,
+	/// the walk pauses once it listed this many commits
+	wanted: Arc<AtomicUsize>,
+	/// bumped by every new walk, older walks stop once they see it
+	generation: Arc<AtomicUsize>,
+	sender: Sender<AsyncGitNotification>,
+}
+
+impl LogWalkShared {
+	fn new(sender: &Sender<AsyncGitNotification>) -> Self {
+		Self {
+			current: Arc::new(Mutex::new(AsyncLogResult::default())),
+			pending: Arc::new(AtomicBool::new(false)),
+			background: Arc::new(AtomicBool::new(false)),
+			wanted: Arc::new(AtomicUsize::new(PREFETCH_COUNT)),
+			generation: Arc::new(AtomicUsize::new(0)),
+			sender: sender.clone(),
+		}
+	}
+
+	fn is_current(&self, generation: usize) -> bool {
+		self.generation.load(Ordering::Relaxed) == generation
+	}
+
+	/// `false` if a newer walk started in the meantime
+	fn set_pending(
+		&self,
+		generation: usize,
+		pending: bool,
+	) -> Result<bool> {
+		let _current = self.current.lock()?;
+
+		if !self.is_current(generation) {
+			return Ok(false);
+		}
+
+		self.pending.store(pending, Ordering::Relaxed);
+
+		Ok(true)
+	}
+}
+
+/// walks the log on a thread of its own, chunk by chunk. the walk
+/// pauses once it is `PREFETCH_COUNT` commits ahead of what the list
+/// asked for via `ensure_loaded`, so even histories of millions of
+/// commits only get read as far as they are scrolled through
+pub struct AsyncLog {
+	shared: LogWalkShared,
+	current_head: Arc<Mutex<Option<CommitId>>>,
+	options: LogWalkOptions,
 	partial_extract: AtomicBool,
 	repo: RepoPath,
 }
 
 static LIMIT_COUNT: usize = 3000;
+/// commits read ahead of the selection before the walk pauses
+static PREFETCH_COUNT: usize = 10_000;
 static SLEEP_FOREGROUND: Duration = Duration::from_millis(2);
 static SLEEP_BACKGROUND: Duration = Duration::from_millis(1000);
+static SLEEP_PAUSED: Duration = Duration::from_millis(50);
 
 impl AsyncLog {
 	///
@@ -60,22 +128,43 @@ impl AsyncLog {
 	) -> Self {
 		Self {
 			repo,
-			current: Arc::new(Mutex::new(AsyncLogResult {
-				commits: Vec::new(),
-				duration: Duration::default(),
-			})),
+			shared: LogWalkShared::new(sender),
 			current_head: Arc::new(Mutex::new(None)),
-			sender: sender.clone(),
-			pending: Arc::new(AtomicBool::new(false)),
-			background: Arc::new(AtomicBool::new(false)),
-			filter,
+			options: LogWalkOptions {
+				filter,
+				..LogWalkOptions::default()
+			},
 			partial_extract: AtomicBool::new(false),
 		}
 	}
 
 	///
 	pub fn count(&self) -> Result<usize> {
-		Ok(self.current.lock()?.commits.len())
+		Ok(self.shared.current.lock()?.commits.len())
+	}
+
+	/// commits listed by the walk so far, including extracted ones
+	pub fn loaded_count(&self) -> Result<usize> {
+		Ok(self.shared.current.lock()?.loaded)
+	}
+
+	/// `true` once the walk reached the end of the history
+	pub fn is_complete(&self) -> Result<bool> {
+		Ok(self.shared.current.lock()?.complete)
+	}
+
+	/// makes the walk continue until it listed `PREFETCH_COUNT`
+	/// commits past `index`
+	pub fn ensure_loaded(&self, index: usize) {
+		self.shared.wanted.fetch_max(
+			index.saturating_add(PREFETCH_COUNT),
+			Ordering::Relaxed,
+		);
+	}
+
+	/// makes the walk read the whole history without pausing
+	pub fn load_all(&self) {
+		self.shared.wanted.store(usize::MAX, Ordering::Relaxed);
 	}
 
 	///
@@ -88,7 +177,7 @@ impl AsyncLog {
 			return Err(Error::Generic(String::from("Faulty usage of AsyncLog: Cannot partially extract items and rely on get_items slice to still work!")));
 		}
 
-		let list = &self.current.lock()?.commits;
+		let list = &self.shared.current.lock()?.commits;
 		let list_len = list.len();
 		let min = start_index.min(list_len);
 		let max = min + amount;
@@ -102,32 +191,89 @@ impl AsyncLog {
 			return Err(Error::Generic(String::from("Faulty usage of AsyncLog: Cannot partially extract items and rely on get_items slice to still work!")));
 		}
 
-		let list = &self.current.lock()?.commits;
+		let list = &self.shared.current.lock()?.commits;
 		Ok(list.clone())
 	}
 
 	///
 	pub fn extract_items(&self) -> Result<Vec<CommitId>> {
 		self.partial_extract.store(true, Ordering::Relaxed);
-		let list = &mut self.current.lock()?.commits;
-		let result = list.clone();
-		list.clear();
-		Ok(result)
+		let list = &mut self.shared.current.lock()?.commits;
+		Ok(std::mem::take(list))
+	}
+
+	/// takes the ref masks of a `LogScope::Refs` walk fetched so far
+	pub fn extract_ref_masks(
+		&self,
+	) -> Result<HashMap<CommitId, u64>> {
+		Ok(std::mem::take(&mut self.shared.current.lock()?.ref_masks))
 	}
 
 	///
 	pub fn get_last_duration(&self) -> Result<Duration> {
-		Ok(self.current.lock()?.duration)
+		Ok(self.shared.current.lock()?.duration)
 	}
 
-	///
+	/// `false` while the walk is paused
 	pub fn is_pending(&self) -> bool {
-		self.pending.load(Ordering::Relaxed)
+		self.shared.pending.load(Ordering::Relaxed)
 	}
 
 	///
 	pub fn set_background(&self) {
-		self.background.store(true, Ordering::Relaxed);
+		self.shared.background.store(true, Ordering::Relaxed);
+	}
+
+	/// only follow the first parent of merge commits,
+	/// the next `fetch` restarts the walk if this changed
+	pub fn set_first_parent(
+		&mut self,
+		first_parent: bool,
+	) -> Result<()> {
+		if self.options.first_parent != first_parent {
+			self.options.first_parent = first_parent;
+			*self.current_head.lock()? = None;
+		}
+
+		Ok(())
+	}
+
+	/// only list commits touching `pathspec` (a file or directory),
+	/// the next `fetch` restarts the walk if this changed
+	pub fn set_pathspec(
+		&mut self,
+		pathspec: Option<String>,
+	) -> Result<()> {
+		if self.options.pathspec != pathspec {
+			self.options.pathspec = pathspec;
+			*self.current_head.lock()? = None;
+		}
+
+		Ok(())
+	}
+
+	/// the next `fetch` restarts the walk even if `HEAD` did not move,
+	/// e.g. after more history got fetched into a shallow clone
+	pub fn restart(&self) -> Result<()> {
+		*self.current_head.lock()? = None;
+
+		Ok(())
+	}
+
+	///
+	pub fn pathspec(&self) -> Option<&str> {
+		self.options.pathspec.as_deref()
+	}
+
+	/// which refs to walk the log from,
+	/// the next `fetch` restarts the walk if this changed
+	pub fn set_scope(&mut self, scope: LogScope) -> Result<()> {
+		if self.options.scope != scope {
+			self.options.scope = scope;
+			*self.current_head.lock()? = None;
+		}
+
+		Ok(())
 	}
 
 	///
@@ -145,27 +291,23 @@ impl AsyncLog {
 		Ok(false)
 	}
 
-	///
+	/// starts a new walk if `HEAD` moved or the settings changed,
+	/// stopping the previous one
 	pub fn fetch(&self) -> Result<FetchStatus> {
-		self.background.store(false, Ordering::Relaxed);
-
-		if self.is_pending() {
-			return Ok(FetchStatus::Pending);
-		}
+		self.shared.background.store(false, Ordering::Relaxed);
 
 		if !self.head_changed()? {
-			return Ok(FetchStatus::NoChange);
+			return Ok(if self.is_pending() {
+				FetchStatus::Pending
+			} else {
+				FetchStatus::NoChange
+			});
 		}
 
-		self.pending.store(true, Ordering::Relaxed);
+		let generation = self.start_walk()?;
 
-		self.clear()?;
-
-		let arc_current = Arc::clone(&self.current);
-		let sender = self.sender.clone();
-		let arc_pending = Arc::clone(&self.pending);
-		let arc_background = Arc::clone(&self.background);
-		let filter = self.filter.clone();
+		let shared = self.shared.clone();
+		let options = self.options.clone();
 		let repo_path = self.repo.clone();
 
 		if let Ok(head) = repo(&self.repo)?.head() {
@@ -173,21 +315,20 @@ impl AsyncLog {
 				head.target().map(CommitId::new);
 		}
 
-		rayon_core::spawn(move || {
+		// not on the rayon pool: a paused walk keeps its thread
+		// until it gets stopped
+		thread::spawn(move || {
 			scope_time!("async::revlog");
 
 			Self::fetch_helper(
-				&repo_path,
-				&arc_current,
-				&arc_background,
-				&sender,
-				filter,
+				&repo_path, &shared, generation, options,
 			)
 			.expect("failed to fetch");
 
-			arc_pending.store(false, Ordering::Relaxed);
-
-			Self::notify(&sender);
+			if shared.set_pending(generation, false).unwrap_or(false)
+			{
+				Self::notify(&shared.sender);
+			}
 		});
 
 		Ok(FetchStatus::Started)
@@ -195,69 +336,74 @@ impl AsyncLog {
 
 	fn fetch_helper(
 		repo_path: &RepoPath,
-		arc_current: &Arc<Mutex<AsyncLogResult>>,
-		arc_background: &Arc<AtomicBool>,
-		sender: &Sender<AsyncGitNotification>,
-		filter: Option<SharedCommitFilterFn>,
+		shared: &LogWalkShared,
+		generation: usize,
+		options: LogWalkOptions,
 	) -> Result<()> {
-		filter.map_or_else(
-			|| {
-				Self::fetch_helper_without_filter(
-					repo_path,
-					arc_current,
-					arc_background,
-					sender,
-				)
-			},
-			|filter| {
-				Self::fetch_helper_with_filter(
-					repo_path,
-					arc_current,
-					arc_background,
-					sender,
-					filter,
-				)
-			},
-		)
+		if options.filter.is_none()
+			&& options.pathspec.is_none()
+			&& !matches!(options.scope, LogScope::Refs(_))
+		{
+			Self::fetch_helper_without_filter(
+				repo_path,
+				shared,
+				generation,
+				options.first_parent,
+				&options.scope,
+			)
+		} else {
+			Self::fetch_helper_with_filter(
+				repo_path, shared, generation, options,
+			)
+		}
 	}
 
 	fn fetch_helper_with_filter(
 		repo_path: &RepoPath,
-		arc_current: &Arc<Mutex<AsyncLogResult>>,
-		arc_background: &Arc<AtomicBool>,
-		sender: &Sender<AsyncGitNotification>,
-		filter: SharedCommitFilterFn,
+		shared: &LogWalkShared,
+		generation: usize,
+		options: LogWalkOptions,
 	) -> Result<()> {
 		let start_time = Instant::now();
 
-		let mut entries = vec![CommitId::default(); LIMIT_COUNT];
-		entries.resize(0, CommitId::default());
+		let mut entries = Vec::with_capacity(LIMIT_COUNT);
 
 		let r = repo(repo_path)?;
 		let mut walker =
-			LogWalker::new(&r, LIMIT_COUNT)?.filter(Some(filter));
+			LogWalker::with_scope(&r, LIMIT_COUNT, &options.scope)?
+				.filter(options.filter)
+				.first_parent(options.first_parent)
+				.pathspec(options.pathspec);
 
 		loop {
 			entries.clear();
 			let read = walker.read(&mut entries)?;
 
-			let mut current = arc_current.lock()?;
+			let mut current = shared.current.lock()?;
+			if !shared.is_current(generation) {
+				return Ok(());
+			}
 			current.commits.extend(entries.iter());
+			current.ref_masks.extend(entries.iter().filter_map(
+				|id| {
+					let mask = walker.ref_mask(id);
+					(mask != 0).then_some((*id, mask))
+				},
+			));
+			current.loaded += entries.len();
+			current.complete = read == 0;
 			current.duration = start_time.elapsed();
+			let loaded = current.loaded;
+			drop(current);
 
 			if read == 0 {
 				break;
 			}
-			Self::notify(sender);
-
-			let sleep_duration =
-				if arc_background.load(Ordering::Relaxed) {
-					SLEEP_BACKGROUND
-				} else {
-					SLEEP_FOREGROUND
-				};
+			Self::notify(&shared.sender);
 
-			thread::sleep(sleep_duration);
+			if !Self::wait_for_demand(shared, generation, loaded)? {
+				return Ok(());
+			}
 		}
 
 		log::trace!("revlog visited: {}", walker.visited());
@@ -267,42 +413,48 @@ impl AsyncLog {
 
 	fn fetch_helper_without_filter(
 		repo_path: &RepoPath,
-		arc_current: &Arc<Mutex<AsyncLogResult>>,
-		arc_background: &Arc<AtomicBool>,
-		sender: &Sender<AsyncGitNotification>,
+		shared: &LogWalkShared,
+		generation: usize,
+		first_parent: bool,
+		scope: &LogScope,
 	) -> Result<()> {
 		let start_time = Instant::now();
 
-		let mut entries = vec![CommitId::default(); LIMIT_COUNT];
-		entries.resize(0, CommitId::default());
+		let mut entries = Vec::with_capacity(LIMIT_COUNT);
 
 		let mut repo: gix::Repository =
 				gix::ThreadSafeRepository::discover_with_environment_overrides(repo_path.gitpath())
 						.map(Into::into)?;
-		let mut walker =
-			LogWalkerWithoutFilter::new(&mut repo, LIMIT_COUNT)?;
+		let mut walker = LogWalkerWithoutFilter::new(
+			&mut repo,
+			LIMIT_COUNT,
+			first_parent,
+			scope,
+		)?;
 
 		loop {
 			entries.clear();
 			let read = walker.read(&mut entries)?;
 
-			let mut current = arc_current.lock()?;
+			let mut current = shared.current.lock()?;
+			if !shared.is_current(generation) {
+				return Ok(());
+			}
 			current.commits.extend(entries.iter());
+			current.loaded += entries.len();
+			current.complete = read == 0;
 			current.duration = start_time.elapsed();
+			let loaded = current.loaded;
+			drop(current);
 
 			if read == 0 {
 				break;
 			}
-			Self::notify(sender);
+			Self::notify(&shared.sender);
 
-			let sleep_duration =
-				if arc_background.load(Ordering::Relaxed) {
-					SLEEP_BACKGROUND
-				} else {
-					SLEEP_FOREGROUND
-				};
-
-			thread::sleep(sleep_duration);
+			if !Self::wait_for_demand(shared, generation, loaded)? {
+				return Ok(());
+			}
 		}
 
 		log::trace!("revlog visited: {}", walker.visited());
@@ -310,11 +462,62 @@ impl AsyncLog {
 		Ok(())
 	}
 
-	fn clear(&self) -> Result<()> {
-		self.current.lock()?.commits.clear();
+	/// throttles the walk between chunks and pauses it while
+	/// `loaded` commits are all that is wanted, `false` if a newer
+	/// walk started in the meantime
+	fn wait_for_demand(
+		shared: &LogWalkShared,
+		generation: usize,
+		loaded: usize,
+	) -> Result<bool> {
+		let mut paused = false;
+
+		while loaded >= shared.wanted.load(Ordering::Relaxed) {
+			if !paused {
+				if !shared.set_pending(generation, false)? {
+					return Ok(false);
+				}
+				paused = true;
+				Self::notify(&shared.sender);
+			} else if !shared.is_current(generation) {
+				return Ok(false);
+			}
+
+			thread::sleep(SLEEP_PAUSED);
+		}
+
+		if paused && !shared.set_pending(generation, true)? {
+			return Ok(false);
+		}
+
+		let sleep_duration =
+			if shared.background.load(Ordering::Relaxed) {
+				SLEEP_BACKGROUND
+			} else {
+				SLEEP_FOREGROUND
+			};
+
+		thread::sleep(sleep_duration);
+
+		Ok(shared.is_current(generation))
+	}
+
+	/// stops the previous walk and resets the result, returns the
+	/// generation of the new walk
+	fn start_walk(&self) -> Result<usize> {
+		let mut current = self.shared.current.lock()?;
+		*current = AsyncLogResult::default();
+		self.shared.pending.store(true, Ordering::Relaxed);
+		self.shared.wanted.store(PREFETCH_COUNT, Ordering::Relaxed);
+		let generation =
+			self.shared.generation.fetch_add(1, Ordering::Relaxed)
+				+ 1;
+		drop(current);
+
 		*self.current_head.lock()? = None;
 		self.partial_extract.store(false, Ordering::Relaxed);
-		Ok(())
+
+		Ok(generation)
 	}
 
 	fn notify(sender: &Sender<AsyncGitNotification>) {
@@ -324,21 +527,30 @@ impl AsyncLog {
 	}
 }
 
+impl Drop for AsyncLog {
+	fn drop(&mut self) {
+		// stops a paused walk
+		self.shared.generation.fetch_add(1, Ordering::Relaxed);
+	}
+}
+
 #[cfg(test)]
 mod tests {
-	use std::sync::atomic::AtomicBool;
-	use std::sync::{Arc, Mutex};
-	use std::time::Duration;
+	use std::sync::atomic::Ordering;
+	use std::thread;
+	use std::time::{Duration, Instant};
 
 	use crossbeam_channel::unbounded;
 	use serial_test::serial;
 	use tempfile::TempDir;
 
-	use crate::sync::tests::{debug_cmd_print, repo_init};
-	use crate::sync::RepoPath;
+	use crate::sync::tests::{
+		debug_cmd_print, repo_init, write_commit_file,
+	};
+	use crate::sync::{LogScope, RepoPath};
 	use crate::AsyncLog;
 
-	use super::AsyncLogResult;
+	use super::LogWalkShared;
 
 	#[test]
 	#[serial]
@@ -356,17 +568,14 @@ mod tests {
 		let subdir_path: RepoPath =
 			subdir.as_os_str().to_str().unwrap().into();
 
-		let arc_current = Arc::new(Mutex::new(AsyncLogResult {
-			commits: Vec::new(),
-			duration: Duration::default(),
-		}));
-		let arc_background = Arc::new(AtomicBool::new(false));
+		let shared = LogWalkShared::new(&tx_git);
 
 		let result = AsyncLog::fetch_helper_without_filter(
 			&subdir_path,
-			&arc_current,
-			&arc_background,
-			&tx_git,
+			&shared,
+			0,
+			false,
+			&LogScope::Head,
 		);
 
 		assert_eq!(result.unwrap(), ());
@@ -384,24 +593,71 @@ mod tests {
 		let empty_path: RepoPath =
 			empty_dir.path().to_str().unwrap().into();
 
-		let arc_current = Arc::new(Mutex::new(AsyncLogResult {
-			commits: Vec::new(),
-			duration: Duration::default(),
-		}));
-		let arc_background = Arc::new(AtomicBool::new(false));
+		let shared = LogWalkShared::new(&tx_git);
 
 		std::env::set_var("GIT_DIR", git_dir);
 
 		let result = AsyncLog::fetch_helper_without_filter(
 			// We pass an empty path, thus testing whether `GIT_DIR`, set above, is taken into account.
 			&empty_path,
-			&arc_current,
-			&arc_background,
-			&tx_git,
+			&shared,
+			0,
+			false,
+			&LogScope::Head,
 		);
 
 		std::env::remove_var("GIT_DIR");
 
 		assert_eq!(result.unwrap(), ());
 	}
+
+	#[test]
+	#[serial]
+	fn test_pause_until_wanted() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: RepoPath =
+			root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "c1");
+		write_commit_file(&repo, "a.txt", "b", "c2");
+
+		let (tx_git, _rx_git) = unbounded();
+
+		let shared = LogWalkShared::new(&tx_git);
+		shared.wanted.store(1, Ordering::Relaxed);
+		shared.pending.store(true, Ordering::Relaxed);
+
+		let walk = {
+			let shared = shared.clone();
+			thread::spawn(move || {
+				AsyncLog::fetch_helper_without_filter(
+					&repo_path,
+					&shared,
+					0,
+					false,
+					&LogScope::Head,
+				)
+			})
+		};
+
+		let start = Instant::now();
+		while shared.pending.load(Ordering::Relaxed) {
+			assert!(start.elapsed() < Duration::from_secs(10));
+			thread::sleep(Duration::from_millis(10));
+		}
+
+		{
+			let current = shared.current.lock().unwrap();
+			assert_eq!(current.loaded, 3);
+			assert!(!current.complete);
+		}
+
+		shared.wanted.store(usize::MAX, Ordering::Relaxed);
+		walk.join().unwrap().unwrap();
+
+		let current = shared.current.lock().unwrap();
+		assert_eq!(current.commits.len(), 3);
+		assert!(current.complete);
+	}
 }
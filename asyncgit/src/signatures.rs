@@ -0,0 +1,92 @@
+use crate::{
+	asyncjob::{AsyncJob, RunParams},
+	error::Result,
+	sync::{
+		verify_signature, CommitId, RepoPath, SignatureVerification,
+	},
+	AsyncGitNotification,
+};
+use std::{
+	collections::HashMap,
+	sync::{Arc, Mutex},
+};
+
+///
+pub type Signatures =
+	HashMap<CommitId, Option<SignatureVerification>>;
+
+enum JobState {
+	Request { repo: RepoPath, ids: Vec<CommitId> },
+	Response(Signatures),
+}
+
+/// verifies the signatures of a set of commits in the background
+#[derive(Clone, Default)]
+pub struct AsyncSignaturesJob {
+	state: Arc<Mutex<Option<JobState>>>,
+}
+
+///
+impl AsyncSignaturesJob {
+	///
+	pub fn new(repo: RepoPath, ids: Vec<CommitId>) -> Self {
+		Self {
+			state: Arc::new(Mutex::new(Some(JobState::Request {
+				repo,
+				ids,
+			}))),
+		}
+	}
+
+	/// verification per commit, `None` for unsigned commits and
+	/// commits whose signature could not be checked
+	pub fn result(&self) -> Option<Signatures> {
+		if let Ok(mut state) = self.state.lock() {
+			if let Some(state) = state.take() {
+				return match state {
+					JobState::Request { .. } => None,
+					JobState::Response(result) => Some(result),
+				};
+			}
+		}
+
+		None
+	}
+}
+
+impl AsyncJob for AsyncSignaturesJob {
+	type Notification = AsyncGitNotification;
+	type Progress = ();
+
+	fn run(
+		&mut self,
+		_params: RunParams<Self::Notification, Self::Progress>,
+	) -> Result<Self::Notification> {
+		if let Ok(mut state) = self.state.lock() {
+			*state = state.take().map(|state| match state {
+				JobState::Request { repo, ids } => {
+					JobState::Response(
+						ids.into_iter()
+							.map(|id| {
+								let verification =
+									verify_signature(&repo, id)
+										.unwrap_or_else(|e| {
+											log::error!(
+											"verify signature of {id}: {e}"
+										);
+											None
+										});
+								(id, verification)
+							})
+							.collect(),
+					)
+				}
+				JobState::Response(result) => {
+					JobState::Response(result)
+				}
+			});
+		}
+
+		Ok(AsyncGitNotification::Signatures)
+	}
+}
@@ -2,13 +2,16 @@ use crate::{
 	error::Result,
 	hash,
 	sync::{
-		self, status::StatusType, RepoPath, ShowUntrackedFilesConfig,
+		self, status::StatusType, LfsFileState, RepoPath,
+		ShowUntrackedFilesConfig, SubmoduleState,
 	},
 	AsyncGitNotification, StatusItem,
 };
 use crossbeam_channel::Sender;
 use std::{
+	collections::BTreeMap,
 	hash::Hash,
+	path::Path,
 	sync::{
 		atomic::{AtomicUsize, Ordering},
 		Arc, Mutex,
@@ -26,6 +29,13 @@ fn current_tick() -> u128 {
 #[derive(Default, Hash, Clone)]
 pub struct Status {
 	pub items: Vec<StatusItem>,
+	/// paths of submodules whose inner changes are listed in `items`
+	pub submodules: Vec<String>,
+	/// workdir state of the files in `items` tracked by git LFS
+	pub lfs: BTreeMap<String, LfsFileState>,
+	/// submodules that are dirty or not at their recorded commit,
+	/// only for the workdir status
+	pub submodule_states: BTreeMap<String, SubmoduleState>,
 }
 
 ///
@@ -34,6 +44,8 @@ pub struct StatusParams {
 	tick: u128,
 	status_type: StatusType,
 	config: Option<ShowUntrackedFilesConfig>,
+	include_ignored: bool,
+	submodules: bool,
 }
 
 impl StatusParams {
@@ -46,8 +58,27 @@ impl StatusParams {
 			tick: current_tick(),
 			status_type,
 			config,
+			include_ignored: false,
+			submodules: false,
 		}
 	}
+
+	/// also list ignored files
+	#[must_use]
+	pub const fn with_ignored(
+		mut self,
+		include_ignored: bool,
+	) -> Self {
+		self.include_ignored = include_ignored;
+		self
+	}
+
+	/// list the changes inside of dirty submodules
+	#[must_use]
+	pub const fn with_submodules(mut self, submodules: bool) -> Self {
+		self.submodules = submodules;
+		self
+	}
 }
 
 struct Request<R, A>(R, Option<A>);
@@ -120,8 +151,7 @@ impl AsyncStatus {
 		let arc_last = Arc::clone(&self.last);
 		let sender = self.sender.clone();
 		let arc_pending = Arc::clone(&self.pending);
-		let status_type = params.status_type;
-		let config = params.config;
+		let params = *params;
 		let repo = self.repo.clone();
 
 		self.pending.fetch_add(1, Ordering::Relaxed);
@@ -129,8 +159,7 @@ impl AsyncStatus {
 		rayon_core::spawn(move || {
 			if let Err(e) = Self::fetch_helper(
 				&repo,
-				status_type,
-				config,
+				&params,
 				hash_request,
 				&arc_current,
 				&arc_last,
@@ -150,17 +179,16 @@ impl AsyncStatus {
 
 	fn fetch_helper(
 		repo: &RepoPath,
-		status_type: StatusType,
-		config: Option<ShowUntrackedFilesConfig>,
+		params: &StatusParams,
 		hash_request: u64,
 		arc_current: &Arc<Mutex<Request<u64, Status>>>,
 		arc_last: &Arc<Mutex<Status>>,
 	) -> Result<()> {
-		let res = Self::get_status(repo, status_type, config)?;
+		let res = Self::get_status(repo, params)?;
 		log::trace!(
 			"status fetched: {} (type: {:?})",
 			hash_request,
-			status_type,
+			params.status_type,
 		);
 
 		{
@@ -180,15 +208,72 @@ impl AsyncStatus {
 
 	fn get_status(
 		repo: &RepoPath,
-		status_type: StatusType,
-		config: Option<ShowUntrackedFilesConfig>,
+		params: &StatusParams,
 	) -> Result<Status> {
-		Ok(Status {
-			items: sync::status::get_status(
+		let mut items = sync::status::get_status_ext(
+			repo,
+			params.status_type,
+			params.config,
+			params.include_ignored,
+		)?;
+
+		if params.status_type == StatusType::WorkingDir {
+			Self::add_index_flagged(repo, &mut items)?;
+		}
+
+		let (items, submodules) = if params.submodules {
+			sync::expand_submodule_changes(
 				repo,
-				status_type,
-				config,
-			)?,
+				items,
+				params.config,
+			)?
+		} else {
+			(items, Vec::new())
+		};
+
+		let lfs = sync::lfs_file_states(
+			repo,
+			items.iter().map(|item| item.path.as_str()),
+		)?;
+
+		let submodule_states =
+			if params.status_type == StatusType::WorkingDir {
+				sync::get_submodule_states(repo)?
+			} else {
+				BTreeMap::new()
+			};
+
+		Ok(Status {
+			items,
+			submodules,
+			lfs,
+			submodule_states,
 		})
 	}
+
+	/// files hidden by `assume-unchanged`/`skip-worktree` never show up
+	/// as changes, so list them explicitly to make the bits visible
+	fn add_index_flagged(
+		repo: &RepoPath,
+		items: &mut Vec<StatusItem>,
+	) -> Result<()> {
+		let flagged = sync::get_index_flagged(repo)?;
+		if flagged.is_empty() {
+			return Ok(());
+		}
+
+		let flagged = flagged
+			.into_iter()
+			.filter(|flagged| {
+				!items.iter().any(|item| item.path == flagged.path)
+			})
+			.collect::<Vec<_>>();
+
+		items.extend(flagged);
+		items.sort_by(|a, b| {
+			Path::new(a.path.as_str()).cmp(Path::new(b.path.as_str()))
+		});
+
+		Ok(())
+	}
 }
@@ -0,0 +1,867 @@
+//! applying patches to the worktree (`git apply`) or as commits from
+//! a mailbox (`git am`)
+//!
+//! a patch that does not apply cleanly is merged three-way using the
+//! preimages named in its `index` lines (like `--3way`), leaving
+//! conflicts to resolve. `git am` keeps its state in `rebase-apply`
+//! the same way git does so it can be continued by either tool.
+
+use super::{
+	commit::signature_allow_undefined_name, repository::repo,
+	CommitId, RepoPath,
+};
+use crate::error::{Error, Result};
+use git2::{
+	build::{CheckoutBuilder, TreeUpdateBuilder},
+	ApplyLocation, Delta, Diff, FileMode, Index, IndexConflict,
+	ObjectType, Repository, RepositoryState, Signature, Status,
+	StatusOptions, Time,
+};
+use scopetime::scope_time;
+use std::{
+	collections::HashMap,
+	fs,
+	path::{Path, PathBuf},
+};
+
+const STATE_DIR: &str = "rebase-apply";
+
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApplyPatchTarget {
+	/// changes the worktree only (`git apply`)
+	Worktree,
+	/// commits every mail of a mailbox (`git am`)
+	Commits,
+}
+
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApplyPatchResult {
+	/// everything applied, with the number of commits created
+	Applied(usize),
+	/// a patch left conflicts to resolve
+	Conflicts,
+}
+
+/// state of a pending `git am`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApplyMailboxProgress {
+	/// 1-based number of the patch being applied
+	pub current: usize,
+	///
+	pub total: usize,
+	///
+	pub subject: String,
+}
+
+struct MailPatch {
+	author_name: String,
+	author_email: String,
+	time: Option<Time>,
+	subject: String,
+	message: String,
+	diff: String,
+}
+
+/// applies `patch` (a plain diff or a mailbox of `format-patch`
+/// mails) to `target`
+pub fn apply_patch(
+	repo_path: &RepoPath,
+	patch: &str,
+	target: ApplyPatchTarget,
+) -> Result<ApplyPatchResult> {
+	scope_time!("apply_patch");
+
+	let repo = repo(repo_path)?;
+	let mails = split_mailbox(patch);
+
+	match target {
+		ApplyPatchTarget::Worktree => {
+			let diffs = if mails.is_empty() {
+				vec![patch.to_string()]
+			} else {
+				mails
+					.iter()
+					.map(|mail| parse_mail(mail).map(|m| m.diff))
+					.collect::<Result<Vec<_>>>()?
+			};
+
+			for diff in &diffs {
+				if !apply_diff(&repo, diff, ApplyLocation::WorkDir)? {
+					return Ok(ApplyPatchResult::Conflicts);
+				}
+			}
+
+			Ok(ApplyPatchResult::Applied(0))
+		}
+		ApplyPatchTarget::Commits => {
+			if mails.is_empty() {
+				return Err(Error::Generic(
+					"no mails found, expected format-patch output"
+						.into(),
+				));
+			}
+			if repo.state() != RepositoryState::Clean {
+				return Err(Error::Generic(
+					"repository is not in a clean state".into(),
+				));
+			}
+			if has_tracked_changes(&repo)? {
+				return Err(Error::Generic(
+					"commit or stash your changes first".into(),
+				));
+			}
+			// fail before writing any state on garbage
+			for mail in &mails {
+				parse_mail(mail)?;
+			}
+
+			start_mailbox(&repo, &mails)?;
+
+			apply_mailbox(&repo, 0)
+		}
+	}
+}
+
+/// progress of a pending `git am`, `None` if there is none
+pub fn apply_mailbox_progress(
+	repo_path: &RepoPath,
+) -> Result<Option<ApplyMailboxProgress>> {
+	let repo = repo(repo_path)?;
+
+	if !state_dir(&repo).join("applying").exists() {
+		return Ok(None);
+	}
+
+	let current = read_number(&repo, "next")?;
+	let subject =
+		parse_mail(&read_state(&repo, &patch_name(current))?)
+			.map(|mail| mail.subject)
+			.unwrap_or_default();
+
+	Ok(Some(ApplyMailboxProgress {
+		current,
+		total: read_number(&repo, "last")?,
+		subject,
+	}))
+}
+
+/// commits the resolved current patch and applies the remaining ones
+pub fn apply_mailbox_continue(
+	repo_path: &RepoPath,
+) -> Result<ApplyPatchResult> {
+	scope_time!("apply_mailbox_continue");
+
+	let repo = repo(repo_path)?;
+	let current = read_number(&repo, "next")?;
+	let mail = parse_mail(&read_state(&repo, &patch_name(current))?)?;
+
+	let mut index = repo.index()?;
+	if index.has_conflicts() {
+		return Err(Error::Generic(
+			"resolve and stage all conflicts first".into(),
+		));
+	}
+	let head = repo.head()?.peel_to_commit()?;
+	if index.write_tree()? == head.tree_id() {
+		return Err(Error::Generic(
+			"no changes staged, skip the patch instead".into(),
+		));
+	}
+
+	commit_mail(&repo, &mut index, &mail)?;
+	write_state(&repo, "next", &format!("{}\n", current + 1))?;
+
+	apply_mailbox(&repo, 1)
+}
+
+/// drops the current patch and applies the remaining ones
+pub fn apply_mailbox_skip(
+	repo_path: &RepoPath,
+) -> Result<ApplyPatchResult> {
+	scope_time!("apply_mailbox_skip");
+
+	let repo = repo(repo_path)?;
+	let current = read_number(&repo, "next")?;
+
+	discard_changes(&repo)?;
+	write_state(&repo, "next", &format!("{}\n", current + 1))?;
+
+	apply_mailbox(&repo, 0)
+}
+
+/// goes back to where `git am` started
+pub fn apply_mailbox_abort(repo_path: &RepoPath) -> Result<()> {
+	scope_time!("apply_mailbox_abort");
+
+	let repo = repo(repo_path)?;
+	let orig = repo.refname_to_id("ORIG_HEAD")?;
+
+	// a hard reset also cleans up the state directory
+	reset_hard(&repo, &orig.into())?;
+	if state_dir(&repo).exists() {
+		fs::remove_dir_all(state_dir(&repo))?;
+	}
+
+	Ok(())
+}
+
+fn apply_mailbox(
+	repo: &Repository,
+	mut applied: usize,
+) -> Result<ApplyPatchResult> {
+	let last = read_number(repo, "last")?;
+
+	loop {
+		let next = read_number(repo, "next")?;
+		if next > last {
+			break;
+		}
+
+		let mail = parse_mail(&read_state(repo, &patch_name(next))?)?;
+		write_state(repo, "final-commit", &mail.message)?;
+		write_state(repo, "author-script", &author_script(&mail))?;
+
+		if !apply_diff(repo, &mail.diff, ApplyLocation::Both)? {
+			return Ok(ApplyPatchResult::Conflicts);
+		}
+
+		commit_mail(repo, &mut repo.index()?, &mail)?;
+		applied += 1;
+		write_state(repo, "next", &format!("{}\n", next + 1))?;
+	}
+
+	fs::remove_dir_all(state_dir(repo))?;
+
+	Ok(ApplyPatchResult::Applied(applied))
+}
+
+fn start_mailbox(repo: &Repository, mails: &[String]) -> Result<()> {
+	let dir = state_dir(repo);
+	fs::create_dir_all(&dir)?;
+
+	for (idx, mail) in mails.iter().enumerate() {
+		fs::write(dir.join(patch_name(idx + 1)), mail)?;
+	}
+	write_state(repo, "next", "1\n")?;
+	write_state(repo, "last", &format!("{}\n", mails.len()))?;
+	write_state(repo, "applying", "")?;
+	write_state(repo, "threeway", "t\n")?;
+	write_state(repo, "utf8", "t\n")?;
+	write_state(repo, "keep", "f\n")?;
+
+	let head = repo.head()?.peel_to_commit()?.id();
+	repo.reference("ORIG_HEAD", head, true, "gitui: am")?;
+
+	Ok(())
+}
+
+fn commit_mail(
+	repo: &Repository,
+	index: &mut Index,
+	mail: &MailPatch,
+) -> Result<CommitId> {
+	let tree = repo.find_tree(index.write_tree()?)?;
+	let parent = repo.head()?.peel_to_commit()?;
+	let committer = signature_allow_undefined_name(repo)?;
+	let author = match mail.time {
+		Some(time) => Signature::new(
+			&mail.author_name,
+			&mail.author_email,
+			&time,
+		)?,
+		None => {
+			Signature::now(&mail.author_name, &mail.author_email)?
+		}
+	};
+
+	Ok(repo
+		.commit(
+			Some("HEAD"),
+			&author,
+			&committer,
+			&mail.message,
+			&tree,
+			&[&parent],
+		)?
+		.into())
+}
+
+/// applies `diff` to `location`, falls back to a three-way merge,
+/// returns whether it applied without conflicts
+fn apply_diff(
+	repo: &Repository,
+	diff: &str,
+	location: ApplyLocation,
+) -> Result<bool> {
+	let parsed = Diff::from_buffer(diff.as_bytes())?;
+
+	match repo.apply(&parsed, location, None) {
+		Ok(()) => Ok(true),
+		Err(e) => three_way(repo, &parsed, diff)?.ok_or_else(|| {
+			Error::Generic(format!("patch failed: {e}"))
+		}),
+	}
+}
+
+/// merges the patch into the `HEAD` tree via its preimages, writes
+/// the result (with conflict markers) to index and worktree, `None`
+/// if that is not possible
+fn three_way(
+	repo: &Repository,
+	diff: &Diff,
+	text: &str,
+) -> Result<Option<bool>> {
+	let preimages = preimage_ids(text);
+	let ours = repo.head()?.peel_to_tree()?;
+
+	let mut paths = Vec::new();
+	let mut base = TreeUpdateBuilder::new();
+
+	for delta in diff.deltas() {
+		let old = delta.old_file();
+		let new = delta.new_file();
+		let Some(path) = old.path().or_else(|| new.path()) else {
+			continue;
+		};
+
+		for path in [old.path(), new.path()].into_iter().flatten() {
+			if !path_is_clean(repo, path)? {
+				return Ok(None);
+			}
+			paths.push(path.to_path_buf());
+		}
+
+		if delta.status() == Delta::Added {
+			if ours.get_path(path).is_ok() {
+				base.remove(path);
+			}
+			continue;
+		}
+
+		let Some(blob) = preimages
+			.get(path)
+			.and_then(|prefix| {
+				repo.find_object_by_prefix(
+					prefix,
+					Some(ObjectType::Blob),
+				)
+				.ok()
+			})
+			.map(|blob| blob.id())
+		else {
+			return Ok(None);
+		};
+
+		let mode = if old.mode() == git2::FileMode::BlobExecutable {
+			FileMode::BlobExecutable
+		} else {
+			FileMode::Blob
+		};
+		base.upsert(path, blob, mode);
+	}
+
+	let base = repo.find_tree(base.create_updated(repo, &ours)?)?;
+	let theirs = repo.find_tree(
+		repo.apply_to_tree(&base, diff, None)?.write_tree_to(repo)?,
+	)?;
+	let mut merged = repo.merge_trees(&base, &ours, &theirs, None)?;
+	let clean = !merged.has_conflicts();
+
+	let mut checkout = CheckoutBuilder::new();
+	checkout
+		.allow_conflicts(true)
+		.conflict_style_merge(true)
+		.force();
+	for path in &paths {
+		checkout.path(path);
+	}
+	repo.checkout_index(Some(&mut merged), Some(&mut checkout))?;
+
+	update_index(repo, &merged, &paths)?;
+
+	Ok(Some(clean))
+}
+
+/// takes over the entries (or conflicts) of `paths` from `merged`
+fn update_index(
+	repo: &Repository,
+	merged: &Index,
+	paths: &[PathBuf],
+) -> Result<()> {
+	let mut index = repo.index()?;
+
+	let conflicts = merged
+		.conflicts()?
+		.collect::<std::result::Result<Vec<IndexConflict>, _>>()?;
+
+	for path in paths {
+		index.remove_path(path).ok();
+
+		let conflict = conflicts.iter().find(|c| {
+			[&c.ancestor, &c.our, &c.their].into_iter().flatten().any(
+				|entry| {
+					Path::new(&*String::from_utf8_lossy(&entry.path))
+						== path.as_path()
+				},
+			)
+		});
+
+		if let Some(conflict) = conflict {
+			// the entries keep their stage in the flags
+			for entry in
+				[&conflict.ancestor, &conflict.our, &conflict.their]
+					.into_iter()
+					.flatten()
+			{
+				index.add(entry)?;
+			}
+		} else if let Some(entry) = merged.get_path(path, 0) {
+			index.add(&entry)?;
+		}
+	}
+	index.write()?;
+
+	Ok(())
+}
+
+fn path_is_clean(repo: &Repository, path: &Path) -> Result<bool> {
+	match repo.status_file(path) {
+		Ok(status) => Ok(status == Status::CURRENT),
+		Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(true),
+		Err(e) => Err(e.into()),
+	}
+}
+
+fn has_tracked_changes(repo: &Repository) -> Result<bool> {
+	let mut options = StatusOptions::new();
+	options.include_untracked(false).include_ignored(false);
+
+	Ok(!repo.statuses(Some(&mut options))?.is_empty())
+}
+
+/// abbreviated blob ids of the `index <old>..<new>` line per old path
+fn preimage_ids(text: &str) -> HashMap<PathBuf, String> {
+	let mut ids = HashMap::new();
+	let mut current: Option<String> = None;
+
+	for line in text.lines() {
+		if line.starts_with("diff --git ") {
+			current = None;
+		} else if let Some(index) = line.strip_prefix("index ") {
+			current = index
+				.split_once("..")
+				.map(|(old, _)| old.to_string());
+		} else if let Some(path) = line.strip_prefix("--- a/") {
+			if let Some(id) = current.take() {
+				ids.insert(PathBuf::from(path.trim_end()), id);
+			}
+		}
+	}
+
+	ids
+}
+
+/// mails of a mailbox, empty if `text` is no mailbox
+fn split_mailbox(text: &str) -> Vec<String> {
+	let mut mails = Vec::new();
+	let mut current: Option<String> = None;
+
+	for line in text.split_inclusive('\n') {
+		if is_mailbox_separator(line) {
+			mails.extend(current.take());
+			current = Some(String::new());
+		} else if let Some(mail) = &mut current {
+			mail.push_str(line);
+		} else if line.starts_with("From: ")
+			|| line.starts_with("Subject: ")
+		{
+			// a single mail without separator line
+			current = Some(line.to_string());
+		} else if !line.trim().is_empty() {
+			break;
+		}
+	}
+
+	mails.extend(current);
+	mails
+}
+
+/// `From <sha1> Mon Sep 17 00:00:00 2001`
+fn is_mailbox_separator(line: &str) -> bool {
+	line.strip_prefix("From ").is_some_and(|rest| {
+		let rest = rest.trim_end();
+		rest.split(' ').next().is_some_and(|id| {
+			id.len() == 40
+				&& id.chars().all(|c| c.is_ascii_hexdigit())
+		}) || rest.rsplit(' ').next().is_some_and(|year| {
+			year.len() == 4
+				&& year.chars().all(|c| c.is_ascii_digit())
+		})
+	})
+}
+
+fn parse_mail(mail: &str) -> Result<MailPatch> {
+	let mail = mail.replace("\r\n", "\n");
+	let (headers, content) =
+		mail.split_once("\n\n").unwrap_or((mail.as_str(), ""));
+
+	let mut fields: Vec<(String, String)> = Vec::new();
+	for line in headers.lines() {
+		if line.starts_with([' ', '\t']) {
+			if let Some((_, value)) = fields.last_mut() {
+				value.push(' ');
+				value.push_str(line.trim());
+			}
+		} else if let Some((key, value)) = line.split_once(':') {
+			fields
+				.push((key.to_lowercase(), value.trim().to_string()));
+		}
+	}
+	let field = |name: &str| {
+		fields
+			.iter()
+			.find(|(key, _)| key == name)
+			.map(|(_, v)| v.as_str())
+	};
+
+	let (author_name, author_email) =
+		parse_author(field("from").ok_or_else(|| {
+			Error::Generic("mail without a From: header".into())
+		})?);
+	let time = field("date").and_then(|date| {
+		gix::date::parse(date, None)
+			.ok()
+			.map(|time| Time::new(time.seconds, time.offset / 60))
+	});
+	let subject = strip_subject(field("subject").unwrap_or_default());
+
+	let mut body = Vec::new();
+	let mut diff = String::new();
+	let mut in_diff = false;
+	let mut in_notes = false;
+
+	for line in content.split_inclusive('\n') {
+		if in_diff {
+			if line.trim_end_matches('\n') == "-- " {
+				break;
+			}
+			diff.push_str(line);
+		} else if line.starts_with("diff --git ") {
+			in_diff = true;
+			diff.push_str(line);
+		} else if line.trim_end() == "---" {
+			// diffstat and notes follow until the diff
+			in_notes = true;
+		} else if !in_notes {
+			body.push(line.trim_end());
+		}
+	}
+
+	if diff.is_empty() {
+		return Err(Error::Generic(format!(
+			"patch '{subject}' is empty"
+		)));
+	}
+
+	let body = body.join("\n");
+	let body = body.trim();
+	let message = if body.is_empty() {
+		format!("{subject}\n")
+	} else {
+		format!("{subject}\n\n{body}\n")
+	};
+
+	Ok(MailPatch {
+		author_name,
+		author_email,
+		time,
+		subject,
+		message,
+		diff,
+	})
+}
+
+/// `Name <email>` or just `email`
+fn parse_author(from: &str) -> (String, String) {
+	from.split_once('<').map_or_else(
+		|| (from.to_string(), from.to_string()),
+		|(name, email)| {
+			(
+				name.trim().trim_matches('"').to_string(),
+				email.trim_end_matches('>').trim().to_string(),
+			)
+		},
+	)
+}
+
+/// drops `[PATCH n/m]` like prefixes and `Re:`
+fn strip_subject(subject: &str) -> String {
+	let mut subject = subject.trim();
+
+	loop {
+		if subject.starts_with('[') {
+			if let Some((_, rest)) = subject.split_once(']') {
+				subject = rest.trim_start();
+				continue;
+			}
+		}
+		if subject.len() >= 3
+			&& subject[..3].eq_ignore_ascii_case("re:")
+		{
+			subject = subject[3..].trim_start();
+			continue;
+		}
+		break;
+	}
+
+	subject.to_string()
+}
+
+/// same format as git so a `git am --continue` picks up the author
+fn author_script(mail: &MailPatch) -> String {
+	let quote = |s: &str| format!("'{}'", s.replace('\'', "'\\''"));
+	let date = mail.time.map_or_else(String::new, |time| {
+		let offset = time.offset_minutes();
+		format!(
+			"@{} {}{:02}{:02}",
+			time.seconds(),
+			if offset < 0 { '-' } else { '+' },
+			offset.abs() / 60,
+			offset.abs() % 60
+		)
+	});
+
+	format!(
+		"GIT_AUTHOR_NAME={}\nGIT_AUTHOR_EMAIL={}\nGIT_AUTHOR_DATE={}\n",
+		quote(&mail.author_name),
+		quote(&mail.author_email),
+		quote(&date)
+	)
+}
+
+fn reset_hard(repo: &Repository, id: &CommitId) -> Result<()> {
+	let commit = repo.find_commit((*id).into())?;
+	let mut checkout = CheckoutBuilder::new();
+	checkout.force();
+
+	repo.reset(
+		commit.as_object(),
+		git2::ResetType::Hard,
+		Some(&mut checkout),
+	)?;
+
+	Ok(())
+}
+
+/// resets index and worktree to `HEAD` without `git_reset`, which
+/// would remove our state
+fn discard_changes(repo: &Repository) -> Result<()> {
+	let mut index = repo.index()?;
+	index.read_tree(&repo.head()?.peel_to_tree()?)?;
+	index.write()?;
+
+	let mut checkout = CheckoutBuilder::new();
+	checkout.force();
+	repo.checkout_head(Some(&mut checkout))?;
+
+	Ok(())
+}
+
+fn state_dir(repo: &Repository) -> PathBuf {
+	repo.path().join(STATE_DIR)
+}
+
+fn patch_name(idx: usize) -> String {
+	format!("{idx:04}")
+}
+
+fn read_state(repo: &Repository, name: &str) -> Result<String> {
+	Ok(fs::read_to_string(state_dir(repo).join(name))?)
+}
+
+fn read_number(repo: &Repository, name: &str) -> Result<usize> {
+	read_state(repo, name)?.trim().parse().map_err(|_| {
+		Error::Generic(format!("invalid am state file '{name}'"))
+	})
+}
+
+fn write_state(
+	repo: &Repository,
+	name: &str,
+	content: &str,
+) -> Result<()> {
+	fs::write(state_dir(repo).join(name), content)?;
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		format_patches, stage_add_file,
+		tests::{repo_init, write_commit_file},
+		FormatPatchOptions,
+	};
+
+	fn patches_of(repo_path: &RepoPath, ids: &[CommitId]) -> String {
+		format_patches(
+			repo_path,
+			ids,
+			Path::new("../patches"),
+			FormatPatchOptions::default(),
+		)
+		.unwrap()
+		.iter()
+		.map(|file| fs::read_to_string(file).unwrap())
+		.collect()
+	}
+
+	#[test]
+	fn test_parse_mail() {
+		let mail = "From: \"Some One\" <some@one.org>\n\
+			Date: Mon, 2 Jan 2023 10:00:00 +0100\n\
+			Subject: [PATCH 1/2] Re: fix the\n thing\n\n\
+			body\n---\n a.txt | 1 +\n\n\
+			diff --git a/a.txt b/a.txt\n+a\n-- \n2.40.0\n";
+
+		let mail = parse_mail(mail).unwrap();
+
+		assert_eq!(mail.author_name, "Some One");
+		assert_eq!(mail.author_email, "some@one.org");
+		assert_eq!(mail.subject, "fix the thing");
+		assert_eq!(mail.message, "fix the thing\n\nbody\n");
+		assert_eq!(mail.diff, "diff --git a/a.txt b/a.txt\n+a\n");
+		assert_eq!(mail.time.unwrap().offset_minutes(), 60);
+	}
+
+	#[test]
+	fn test_apply_to_worktree() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 = write_commit_file(&repo, "a.txt", "a\n", "add a");
+		let c2 = write_commit_file(&repo, "a.txt", "a\nb\n", "add b");
+		let patch = patches_of(repo_path, &[c2]);
+		reset_hard(&repo, &c1).unwrap();
+
+		assert_eq!(
+			apply_patch(
+				repo_path,
+				&patch,
+				ApplyPatchTarget::Worktree
+			)
+			.unwrap(),
+			ApplyPatchResult::Applied(0)
+		);
+		assert_eq!(
+			fs::read_to_string(root.join("a.txt")).unwrap(),
+			"a\nb\n"
+		);
+		assert_eq!(repo.head().unwrap().target().unwrap(), c1.into());
+	}
+
+	#[test]
+	fn test_apply_mailbox() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let base: CommitId =
+			repo.head().unwrap().target().unwrap().into();
+		let c1 = write_commit_file(&repo, "a.txt", "a\n", "add a");
+		let c2 = write_commit_file(&repo, "a.txt", "a\nb\n", "add b");
+		let patch = patches_of(repo_path, &[c1, c2]);
+		reset_hard(&repo, &base).unwrap();
+
+		assert_eq!(
+			apply_patch(repo_path, &patch, ApplyPatchTarget::Commits)
+				.unwrap(),
+			ApplyPatchResult::Applied(2)
+		);
+
+		let head = repo.head().unwrap().peel_to_commit().unwrap();
+		assert_eq!(head.message(), Some("add b\n"));
+		assert_eq!(
+			head.tree_id(),
+			repo.find_commit(c2.into()).unwrap().tree_id()
+		);
+		assert_eq!(apply_mailbox_progress(repo_path).unwrap(), None);
+	}
+
+	#[test]
+	fn test_apply_mailbox_conflict() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let base =
+			write_commit_file(&repo, "a.txt", "1\n2\n3\n", "base");
+		let theirs =
+			write_commit_file(&repo, "a.txt", "1\nx\n3\n", "theirs");
+		let patch = patches_of(repo_path, &[theirs]);
+		reset_hard(&repo, &base).unwrap();
+		let ours =
+			write_commit_file(&repo, "a.txt", "1\ny\n3\n", "ours");
+
+		assert_eq!(
+			apply_patch(repo_path, &patch, ApplyPatchTarget::Commits)
+				.unwrap(),
+			ApplyPatchResult::Conflicts
+		);
+		assert_eq!(repo.state(), RepositoryState::ApplyMailbox);
+		assert_eq!(
+			apply_mailbox_progress(repo_path).unwrap(),
+			Some(ApplyMailboxProgress {
+				current: 1,
+				total: 1,
+				subject: String::from("theirs"),
+			})
+		);
+		let mut index = repo.index().unwrap();
+		index.read(true).unwrap();
+		assert!(index.has_conflicts());
+		assert!(fs::read_to_string(root.join("a.txt"))
+			.unwrap()
+			.contains("<<<<<<<"));
+
+		apply_mailbox_abort(repo_path).unwrap();
+		assert_eq!(
+			repo.head().unwrap().target().unwrap(),
+			ours.into()
+		);
+		assert_eq!(repo.state(), RepositoryState::Clean);
+
+		apply_patch(repo_path, &patch, ApplyPatchTarget::Commits)
+			.unwrap();
+		assert_eq!(
+			apply_mailbox_skip(repo_path).unwrap(),
+			ApplyPatchResult::Applied(0)
+		);
+		assert_eq!(
+			repo.head().unwrap().target().unwrap(),
+			ours.into()
+		);
+		assert_eq!(repo.state(), RepositoryState::Clean);
+
+		apply_patch(repo_path, &patch, ApplyPatchTarget::Commits)
+			.unwrap();
+		fs::write(root.join("a.txt"), "1\nxy\n3\n").unwrap();
+		stage_add_file(repo_path, Path::new("a.txt")).unwrap();
+
+		assert_eq!(
+			apply_mailbox_continue(repo_path).unwrap(),
+			ApplyPatchResult::Applied(1)
+		);
+		let head = repo.head().unwrap().peel_to_commit().unwrap();
+		assert_eq!(head.message(), Some("theirs\n"));
+		assert_eq!(head.parent_id(0).unwrap(), ours.into());
+		assert_eq!(repo.state(), RepositoryState::Clean);
+	}
+}
@@ -0,0 +1,414 @@
+//! `git bisect`, the state is kept in the same files and refs
+//! (`BISECT_START`, `BISECT_LOG`, `refs/bisect/*`) git uses so a
+//! session can be continued on the command line
+
+use super::{
+	branch::{checkout_branch, checkout_commit},
+	repository::repo,
+	CommitId, RepoPath,
+};
+use crate::error::{Error, Result};
+use git2::{Oid, Repository, Sort};
+use scopetime::scope_time;
+use std::{
+	collections::HashMap,
+	fs::{self, OpenOptions},
+	io::Write,
+};
+
+const START_FILE: &str = "BISECT_START";
+const LOG_FILE: &str = "BISECT_LOG";
+const REF_BAD: &str = "refs/bisect/bad";
+const REF_GOOD_PREFIX: &str = "refs/bisect/good-";
+const REF_SKIP_PREFIX: &str = "refs/bisect/skip-";
+
+/// verdict on a tested commit
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BisectMark {
+	/// does not have the bug
+	Good,
+	/// has the bug
+	Bad,
+	/// cannot be tested
+	Skip,
+}
+
+impl BisectMark {
+	const fn name(self) -> &'static str {
+		match self {
+			Self::Good => "good",
+			Self::Bad => "bad",
+			Self::Skip => "skip",
+		}
+	}
+}
+
+/// progress of a running bisect session
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BisectState {
+	///
+	pub bad: Option<CommitId>,
+	///
+	pub good: Vec<CommitId>,
+	///
+	pub skipped: Vec<CommitId>,
+	/// commits that can still be the first bad one,
+	/// empty until a good and a bad commit are marked
+	pub remaining: Vec<CommitId>,
+	/// commit to test next
+	pub next: Option<CommitId>,
+	/// the first bad commit, once found
+	pub first_bad: Option<CommitId>,
+}
+
+impl BisectState {
+	/// roughly how many more commits have to be tested
+	pub fn steps_left(&self) -> usize {
+		if self.first_bad.is_some() || self.remaining.is_empty() {
+			0
+		} else {
+			self.remaining.len().ilog2() as usize + 1
+		}
+	}
+
+	/// how `id` was marked
+	pub fn mark(&self, id: &CommitId) -> Option<BisectMark> {
+		if self.bad.as_ref() == Some(id) {
+			Some(BisectMark::Bad)
+		} else if self.good.contains(id) {
+			Some(BisectMark::Good)
+		} else if self.skipped.contains(id) {
+			Some(BisectMark::Skip)
+		} else {
+			None
+		}
+	}
+}
+
+///
+pub fn is_bisecting(repo_path: &RepoPath) -> Result<bool> {
+	let repo = repo(repo_path)?;
+
+	Ok(repo.path().join(START_FILE).is_file())
+}
+
+/// state of the running bisect session, `None` if there is none
+pub fn bisect_state(
+	repo_path: &RepoPath,
+) -> Result<Option<BisectState>> {
+	scope_time!("bisect_state");
+
+	let repo = repo(repo_path)?;
+
+	if !repo.path().join(START_FILE).is_file() {
+		return Ok(None);
+	}
+
+	read_state(&repo).map(Some)
+}
+
+/// marks `commit` (starting a session if needed) and checks out the
+/// next commit to test
+pub fn bisect_mark(
+	repo_path: &RepoPath,
+	commit: CommitId,
+	mark: BisectMark,
+) -> Result<BisectState> {
+	scope_time!("bisect_mark");
+
+	let repo = repo(repo_path)?;
+
+	if !repo.path().join(START_FILE).is_file() {
+		let head = repo.head()?;
+		let start = if head.is_branch() {
+			head.shorthand().map(String::from)
+		} else {
+			None
+		}
+		.unwrap_or_else(|| {
+			head.target().map(|id| id.to_string()).unwrap_or_default()
+		});
+
+		fs::write(
+			repo.path().join(START_FILE),
+			format!("{start}\n"),
+		)?;
+		append_log(&repo, "git bisect start\n")?;
+	}
+
+	let name = match mark {
+		BisectMark::Bad => String::from(REF_BAD),
+		BisectMark::Good => format!("{REF_GOOD_PREFIX}{commit}"),
+		BisectMark::Skip => format!("{REF_SKIP_PREFIX}{commit}"),
+	};
+	repo.reference(&name, commit.into(), true, "bisect")?;
+
+	let summary = repo
+		.find_commit(commit.into())?
+		.summary()
+		.unwrap_or_default()
+		.to_string();
+	append_log(
+		&repo,
+		&format!(
+			"# {}: [{commit}] {summary}\ngit bisect {} {commit}\n",
+			mark.name(),
+			mark.name()
+		),
+	)?;
+
+	let state = read_state(&repo)?;
+
+	if let Some(next) = state.next {
+		if repo.head()?.target() != Some(next.into()) {
+			checkout_commit(repo_path, next)?;
+		}
+	}
+
+	Ok(state)
+}
+
+/// ends the session and checks out what was checked out at its start
+pub fn bisect_reset(repo_path: &RepoPath) -> Result<()> {
+	scope_time!("bisect_reset");
+
+	let repo = repo(repo_path)?;
+
+	let start_file = repo.path().join(START_FILE);
+	if !start_file.is_file() {
+		return Err(Error::Generic(String::from("not bisecting")));
+	}
+
+	let start = fs::read_to_string(&start_file)?.trim().to_string();
+
+	if repo.find_branch(&start, git2::BranchType::Local).is_ok() {
+		checkout_branch(repo_path, &start)?;
+	} else if let Ok(id) = Oid::from_str(&start) {
+		checkout_commit(repo_path, id.into())?;
+	}
+
+	for reference in repo.references_glob("refs/bisect/*")? {
+		reference?.delete()?;
+	}
+
+	fs::remove_file(start_file)?;
+	let log_file = repo.path().join(LOG_FILE);
+	if log_file.is_file() {
+		fs::remove_file(log_file)?;
+	}
+
+	Ok(())
+}
+
+fn append_log(repo: &Repository, line: &str) -> Result<()> {
+	OpenOptions::new()
+		.create(true)
+		.append(true)
+		.open(repo.path().join(LOG_FILE))?
+		.write_all(line.as_bytes())?;
+
+	Ok(())
+}
+
+fn read_state(repo: &Repository) -> Result<BisectState> {
+	let mut state = BisectState::default();
+
+	for reference in repo.references_glob("refs/bisect/*")? {
+		let reference = reference?;
+		let (Some(name), Some(target)) =
+			(reference.name(), reference.target())
+		else {
+			continue;
+		};
+
+		if name == REF_BAD {
+			state.bad = Some(target.into());
+		} else if name.starts_with(REF_GOOD_PREFIX) {
+			state.good.push(target.into());
+		} else if name.starts_with(REF_SKIP_PREFIX) {
+			state.skipped.push(target.into());
+		}
+	}
+
+	let Some(bad) = state.bad else {
+		return Ok(state);
+	};
+	if state.good.is_empty() {
+		return Ok(state);
+	}
+
+	let mut walk = repo.revwalk()?;
+	walk.set_sorting(Sort::TOPOLOGICAL)?;
+	walk.push(bad.into())?;
+	for good in &state.good {
+		walk.hide((*good).into())?;
+	}
+
+	state.remaining = walk
+		.map(|id| id.map(CommitId::from))
+		.collect::<std::result::Result<Vec<_>, _>>()?;
+
+	if state.remaining.len() == 1 {
+		state.first_bad = Some(bad);
+	} else {
+		state.next = next_candidate(repo, &state)?;
+	}
+
+	Ok(state)
+}
+
+/// the commit splitting `remaining` most evenly in two,
+/// `remaining` is topologically sorted (children first)
+fn next_candidate(
+	repo: &Repository,
+	state: &BisectState,
+) -> Result<Option<CommitId>> {
+	let len = state.remaining.len();
+	let words = (len + 63) / 64;
+
+	let index: HashMap<CommitId, usize> = state
+		.remaining
+		.iter()
+		.enumerate()
+		.map(|(idx, id)| (*id, idx))
+		.collect();
+
+	// bitset of the remaining commits reachable from each commit,
+	// parents come after their children so walk backwards
+	let mut reachable = vec![vec![0_u64; words]; len];
+	for idx in (0..len).rev() {
+		let commit = repo.find_commit(state.remaining[idx].into())?;
+
+		let mut bits = vec![0_u64; words];
+		bits[idx / 64] |= 1 << (idx % 64);
+
+		for parent in commit.parent_ids() {
+			if let Some(parent) = index.get(&parent.into()) {
+				for (bit, parent_bit) in
+					bits.iter_mut().zip(&reachable[*parent])
+				{
+					*bit |= parent_bit;
+				}
+			}
+		}
+
+		reachable[idx] = bits;
+	}
+
+	Ok(state
+		.remaining
+		.iter()
+		.zip(&reachable)
+		.filter(|(id, _)| {
+			Some(**id) != state.bad && !state.skipped.contains(id)
+		})
+		.map(|(id, bits)| {
+			let count = bits
+				.iter()
+				.map(|word| word.count_ones() as usize)
+				.sum::<usize>();
+
+			(*id, count.min(len - count))
+		})
+		.fold(None, |best: Option<(CommitId, usize)>, current| {
+			match best {
+				Some(best) if best.1 >= current.1 => Some(best),
+				_ => Some(current),
+			}
+		})
+		.map(|(id, _)| id))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		tests::{repo_init, write_commit_file},
+		utils::get_head_repo,
+	};
+
+	#[test]
+	fn test_bisect() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let commits = (0..8)
+			.map(|idx| {
+				write_commit_file(
+					&repo,
+					"a.txt",
+					&idx.to_string(),
+					&format!("c{idx}"),
+				)
+			})
+			.collect::<Vec<_>>();
+
+		assert_eq!(bisect_state(repo_path).unwrap(), None);
+
+		let state =
+			bisect_mark(repo_path, commits[7], BisectMark::Bad)
+				.unwrap();
+		assert!(state.remaining.is_empty());
+		assert!(is_bisecting(repo_path).unwrap());
+
+		let state =
+			bisect_mark(repo_path, commits[0], BisectMark::Good)
+				.unwrap();
+		assert_eq!(state.remaining.len(), 7);
+		assert_eq!(state.steps_left(), 3);
+		assert_eq!(state.next, Some(commits[4]));
+		assert_eq!(get_head_repo(&repo).unwrap(), commits[4]);
+
+		let state =
+			bisect_mark(repo_path, commits[4], BisectMark::Good)
+				.unwrap();
+		assert_eq!(state.remaining.len(), 3);
+		assert_eq!(state.next, Some(commits[6]));
+
+		let state =
+			bisect_mark(repo_path, commits[6], BisectMark::Skip)
+				.unwrap();
+		assert_eq!(state.next, Some(commits[5]));
+		assert_eq!(state.mark(&commits[6]), Some(BisectMark::Skip));
+
+		let state =
+			bisect_mark(repo_path, commits[5], BisectMark::Bad)
+				.unwrap();
+		assert_eq!(state.first_bad, Some(commits[5]));
+
+		bisect_reset(repo_path).unwrap();
+
+		assert!(!is_bisecting(repo_path).unwrap());
+		assert_eq!(get_head_repo(&repo).unwrap(), commits[7]);
+		assert!(repo.head().unwrap().is_branch());
+		assert_eq!(
+			repo.references_glob("refs/bisect/*").unwrap().count(),
+			0
+		);
+	}
+
+	#[test]
+	fn test_bisect_first_bad() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 = write_commit_file(&repo, "a.txt", "1", "c1");
+		let c2 = write_commit_file(&repo, "a.txt", "2", "c2");
+		let c3 = write_commit_file(&repo, "a.txt", "3", "c3");
+
+		bisect_mark(repo_path, c3, BisectMark::Bad).unwrap();
+		let state =
+			bisect_mark(repo_path, c1, BisectMark::Good).unwrap();
+		assert_eq!(state.next, Some(c2));
+
+		let state =
+			bisect_mark(repo_path, c2, BisectMark::Bad).unwrap();
+		assert_eq!(state.first_bad, Some(c2));
+		assert_eq!(state.next, None);
+		assert_eq!(state.steps_left(), 0);
+	}
+}
@@ -1,16 +1,22 @@
 //! Sync git API for fetching a file blame
 
-use super::{utils, CommitId, RepoPath};
+use super::{
+	config::{get_config_string_repo, set_config_string},
+	file_history::{line_in_parent, line_origin_in_parent},
+	get_config_string, utils, CommitId, ConfigOrigin, RepoPath,
+};
 use crate::{
 	error::{Error, Result},
 	sync::{get_commits_info, repository::repo},
 };
-use git2::BlameOptions;
+use git2::{BlameOptions, Oid, Repository};
 use scopetime::scope_time;
 use std::collections::{HashMap, HashSet};
 use std::io::{BufRead, BufReader};
 use std::path::Path;
 
+const IGNORE_REVS_FILE: &str = "blame.ignoreRevsFile";
+
 /// A `BlameHunk` contains all the information that will be shown to the user.
 #[derive(Clone, Hash, Debug, PartialEq, Eq)]
 pub struct BlameHunk {
@@ -26,6 +32,22 @@ pub struct BlameHunk {
 	pub start_line: usize,
 	///
 	pub end_line: usize,
+	/// 0-based line the hunk starts at in `commit_id`
+	pub orig_start_line: usize,
+	/// path of the file in `commit_id`
+	pub orig_path: String,
+}
+
+/// position of a line in the first parent of the commit that last
+/// changed it, to blame the file again from there
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlameParent {
+	///
+	pub commit_id: CommitId,
+	/// path of the file in the parent
+	pub path: String,
+	/// 0-based
+	pub line: usize,
 }
 
 /// A `BlameFile` represents a collection of lines. This is targeted at how the
@@ -85,11 +107,36 @@ pub fn blame_file(
 	let blame =
 		repo.blame_file(Path::new(file_path), Some(&mut opts))?;
 
-	let reader = BufReader::new(blob.content());
+	let ignored = ignored_revs(&repo);
+	let mut parent_blames = HashMap::new();
+
+	let contents = BufReader::new(blob.content())
+		.lines()
+		.map(|line| line.unwrap_or_else(|_| String::new()))
+		.collect::<Vec<_>>();
+
+	// Line indices in a `FileBlame` are 1-based.
+	let origins = (1..=contents.len())
+		.map(|line| {
+			blame
+				.get_line(line)
+				.map(|hunk| LineOrigin::new(&hunk, line, file_path))
+				.map(|origin| {
+					skip_ignored(
+						&repo,
+						origin,
+						&ignored,
+						&mut parent_blames,
+					)
+				})
+				.transpose()
+		})
+		.collect::<Result<Vec<_>>>()?;
 
-	let unique_commit_ids: HashSet<_> = blame
+	let unique_commit_ids: HashSet<_> = origins
 		.iter()
-		.map(|hunk| CommitId::new(hunk.final_commit_id()))
+		.flatten()
+		.map(|origin| CommitId::new(origin.commit))
 		.collect();
 	let mut commit_ids = Vec::with_capacity(unique_commit_ids.len());
 	commit_ids.extend(unique_commit_ids);
@@ -100,42 +147,45 @@ pub fn blame_file(
 		.map(|commit_info| (commit_info.id, commit_info))
 		.collect();
 
-	let lines: Vec<(Option<BlameHunk>, String)> = reader
-		.lines()
-		.enumerate()
-		.map(|(i, line)| {
-			// Line indices in a `FileBlame` are 1-based.
-			let corresponding_hunk = blame.get_line(i + 1);
-
-			if let Some(hunk) = corresponding_hunk {
-				let commit_id = CommitId::new(hunk.final_commit_id());
-				// Line indices in a `BlameHunk` are 1-based.
-				let start_line =
-					hunk.final_start_line().saturating_sub(1);
-				let end_line =
-					start_line.saturating_add(hunk.lines_in_hunk());
-
-				if let Some(commit_info) =
-					unique_commit_infos.get(&commit_id)
-				{
-					let hunk = BlameHunk {
-						commit_id,
-						author: commit_info.author.clone(),
-						time: commit_info.time,
-						start_line,
-						end_line,
-					};
-
-					return (
-						Some(hunk),
-						line.unwrap_or_else(|_| String::new()),
-					);
-				}
-			}
-
-			(None, line.unwrap_or_else(|_| String::new()))
-		})
-		.collect();
+	// hunks are runs of lines continuing each other in the same commit
+	let continues = |idx: usize| {
+		idx > 0
+			&& matches!(
+				(&origins[idx - 1], &origins[idx]),
+				(Some(prev), Some(origin)) if prev.commit == origin.commit
+					&& prev.path == origin.path
+					&& prev.line + 1 == origin.line
+			)
+	};
+
+	let mut lines = Vec::with_capacity(contents.len());
+	let mut start_line = 0;
+
+	for (i, line) in contents.into_iter().enumerate() {
+		if !continues(i) {
+			start_line = i;
+		}
+
+		let hunk = origins[i].as_ref().and_then(|origin| {
+			let commit_id = CommitId::new(origin.commit);
+			let commit_info = unique_commit_infos.get(&commit_id)?;
+			let end_line = (i + 1..origins.len())
+				.find(|idx| !continues(*idx))
+				.unwrap_or(origins.len());
+
+			Some(BlameHunk {
+				commit_id,
+				author: commit_info.author.clone(),
+				time: commit_info.time,
+				start_line,
+				end_line,
+				orig_start_line: origin.line - 1 - (i - start_line),
+				orig_path: origin.path.clone(),
+			})
+		});
+
+		lines.push((hunk, line));
+	}
 
 	let file_blame = FileBlame {
 		commit_id,
@@ -146,6 +196,170 @@ pub fn blame_file(
 	Ok(file_blame)
 }
 
+/// where a line of the blamed file was last changed
+struct LineOrigin {
+	commit: Oid,
+	/// 1-based
+	line: usize,
+	path: String,
+}
+
+impl LineOrigin {
+	fn new(
+		hunk: &git2::BlameHunk<'_>,
+		line: usize,
+		file_path: &str,
+	) -> Self {
+		Self {
+			commit: hunk.final_commit_id(),
+			line: hunk.orig_start_line() + line
+				- hunk.final_start_line(),
+			path: hunk.path().map_or_else(
+				|| file_path.to_string(),
+				|path| fixup_windows_path(&path.to_string_lossy()),
+			),
+		}
+	}
+}
+
+/// commits listed in `blame.ignoreRevsFile`, an unreadable file or
+/// unknown revisions in it are skipped like they were not listed
+fn ignored_revs(repo: &Repository) -> HashSet<Oid> {
+	let Some(path) = get_config_string_repo(repo, IGNORE_REVS_FILE)
+		.ok()
+		.flatten()
+		.filter(|path| !path.is_empty())
+	else {
+		return HashSet::new();
+	};
+
+	let path = repo.workdir().map_or_else(
+		|| Path::new(&path).into(),
+		|dir| dir.join(&path),
+	);
+
+	let content = match std::fs::read_to_string(&path) {
+		Ok(content) => content,
+		Err(e) => {
+			log::warn!("cannot read {}: {e}", path.display());
+			return HashSet::new();
+		}
+	};
+
+	content
+		.lines()
+		.map(|line| line.split('#').next().unwrap_or_default().trim())
+		.filter(|rev| !rev.is_empty())
+		.filter_map(|rev| {
+			repo.revparse_single(rev)
+				.and_then(|object| object.peel_to_commit())
+				.map(|commit| commit.id())
+				.ok()
+		})
+		.collect()
+}
+
+/// follows a line changed by ignored commits back to the commit that
+/// changed it before them, lines added by an ignored commit stay
+/// blamed on it
+fn skip_ignored<'a>(
+	repo: &'a Repository,
+	mut origin: LineOrigin,
+	ignored: &HashSet<Oid>,
+	parent_blames: &mut HashMap<(Oid, String), git2::Blame<'a>>,
+) -> Result<LineOrigin> {
+	while ignored.contains(&origin.commit) {
+		let commit = repo.find_commit(origin.commit)?;
+		let Ok(parent) = commit.parent(0) else {
+			break;
+		};
+		let Some((path, line)) = line_origin_in_parent(
+			repo,
+			&parent,
+			&commit,
+			&origin.path,
+			origin.line,
+		)?
+		else {
+			break;
+		};
+
+		let key = (parent.id(), path.clone());
+		if !parent_blames.contains_key(&key) {
+			let mut opts = BlameOptions::new();
+			opts.newest_commit(parent.id());
+			parent_blames.insert(
+				key.clone(),
+				repo.blame_file(Path::new(&path), Some(&mut opts))?,
+			);
+		}
+
+		let Some(hunk) = parent_blames
+			.get(&key)
+			.and_then(|blame| blame.get_line(line))
+		else {
+			break;
+		};
+
+		origin = LineOrigin::new(&hunk, line, &path);
+	}
+
+	Ok(origin)
+}
+
+/// file listing commits to skip when blaming
+pub fn blame_ignore_revs_file(
+	repo_path: &RepoPath,
+) -> Result<Option<String>> {
+	get_config_string(repo_path, IGNORE_REVS_FILE)
+}
+
+/// sets the file listing commits to skip when blaming in the repo
+/// config, `None` unsets it
+pub fn set_blame_ignore_revs_file(
+	repo_path: &RepoPath,
+	path: Option<&str>,
+) -> Result<()> {
+	set_config_string(
+		repo_path,
+		ConfigOrigin::Local,
+		IGNORE_REVS_FILE,
+		path,
+	)
+}
+
+/// where the 0-based `line` of `file_path` as it is in `commit_id` was
+/// in the first parent of `commit_id`, `None` for root commits and
+/// files added by `commit_id`
+pub fn blame_parent(
+	repo_path: &RepoPath,
+	commit_id: CommitId,
+	file_path: &str,
+	line: usize,
+) -> Result<Option<BlameParent>> {
+	scope_time!("blame_parent");
+
+	let repo = repo(repo_path)?;
+	let commit = repo.find_commit(commit_id.into())?;
+
+	let Ok(parent) = commit.parent(0) else {
+		return Ok(None);
+	};
+
+	Ok(line_in_parent(
+		&repo,
+		&parent,
+		&commit,
+		&fixup_windows_path(file_path),
+		line + 1,
+	)?
+	.map(|(path, line)| BlameParent {
+		commit_id: parent.id().into(),
+		path,
+		line: line - 1,
+	}))
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -259,4 +473,91 @@ mod tests {
 
 		assert!(blame_file(repo_path, "bar\\foo", None).is_ok());
 	}
+
+	#[test]
+	fn test_blame_parent() -> Result<()> {
+		let (_td, repo) = repo_init_empty()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		File::create(root.join("foo"))?.write_all(b"a\nb\nc\n")?;
+		stage_add_file(repo_path, Path::new("foo"))?;
+		let c1 = commit(repo_path, "first commit")?;
+
+		File::create(root.join("foo"))?.write_all(b"x\na\nb\nC\n")?;
+		stage_add_file(repo_path, Path::new("foo"))?;
+		let c2 = commit(repo_path, "second commit")?;
+
+		let blame = blame_file(repo_path, "foo", None)?;
+		let hunk = blame.lines[3].0.clone().unwrap();
+		assert_eq!(hunk.commit_id, c2);
+		assert_eq!(hunk.orig_start_line, 3);
+		assert_eq!(hunk.orig_path, "foo");
+
+		assert_eq!(
+			blame_parent(repo_path, c2, "foo", 3)?,
+			Some(BlameParent {
+				commit_id: c1,
+				path: String::from("foo"),
+				line: 2,
+			})
+		);
+		assert_eq!(
+			blame_parent(repo_path, c2, "foo", 0)?.map(|p| p.line),
+			Some(0)
+		);
+		assert_eq!(blame_parent(repo_path, c1, "foo", 0)?, None);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_blame_ignore_revs() -> Result<()> {
+		let (_td, repo) = repo_init_empty()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let write_commit = |content: &[u8], msg: &str| {
+			File::create(root.join("foo"))?.write_all(content)?;
+			stage_add_file(repo_path, Path::new("foo"))?;
+			commit(repo_path, msg)
+		};
+
+		let c1 = write_commit(b"a\nb\nc\n", "first commit")?;
+		let c2 = write_commit(b"A\nb\nC\nnew\n", "reformat")?;
+		let c3 = write_commit(b"A\nb\nC\nnew\nd\n", "third commit")?;
+
+		let blamed = |repo_path| -> Result<Vec<CommitId>> {
+			Ok(blame_file(repo_path, "foo", None)?
+				.lines
+				.into_iter()
+				.map(|(hunk, _)| hunk.unwrap().commit_id)
+				.collect())
+		};
+
+		assert_eq!(blamed(repo_path)?, vec![c2, c1, c2, c2, c3]);
+
+		File::create(root.join(".git-blame-ignore-revs"))?
+			.write_all(format!("# reformat\n{c2}\n").as_bytes())?;
+		set_blame_ignore_revs_file(
+			repo_path,
+			Some(".git-blame-ignore-revs"),
+		)?;
+		assert_eq!(
+			blame_ignore_revs_file(repo_path)?.as_deref(),
+			Some(".git-blame-ignore-revs")
+		);
+
+		// lines added by the ignored commit stay blamed on it
+		assert_eq!(blamed(repo_path)?, vec![c1, c1, c1, c2, c3]);
+
+		let blame = blame_file(repo_path, "foo", None)?;
+		let hunk = blame.lines[2].0.clone().unwrap();
+		assert_eq!(hunk.orig_start_line, 0);
+		assert_eq!((hunk.start_line, hunk.end_line), (0, 3));
+
+		Ok(())
+	}
 }
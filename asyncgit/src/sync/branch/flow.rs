@@ -0,0 +1,507 @@
+//! git-flow style branching model
+//!
+//! feature branches start from the develop branch and get merged back
+//! into it. release and hotfix branches get merged into the main
+//! branch, tagged there and merged back into develop. all merges are
+//! `--no-ff` merges.
+//!
+//! the model is configured using the keys written by `git flow init`:
+//! * `gitflow.branch.master`: main branch (default: `main` if it
+//!   exists, `master` otherwise)
+//! * `gitflow.branch.develop`: develop branch (default: `develop`)
+//! * `gitflow.prefix.feature|release|hotfix`: branch prefixes
+//!   (default: `feature/`, `release/`, `hotfix/`)
+//! * `gitflow.prefix.versiontag`: prefix of release tags (default: none)
+
+use super::checkout_branch;
+use crate::{
+	error::{Error, Result},
+	sync::{
+		commit::signature_allow_undefined_name, repository::repo,
+		status::is_workdir_clean, CommitId, RepoPath,
+	},
+};
+use git2::{BranchType, Commit, ObjectType, Repository};
+use scopetime::scope_time;
+
+/// config key of the main branch
+pub const CONFIG_FLOW_MAIN: &str = "gitflow.branch.master";
+/// config key of the develop branch
+pub const CONFIG_FLOW_DEVELOP: &str = "gitflow.branch.develop";
+/// config key of the feature branch prefix
+pub const CONFIG_FLOW_FEATURE: &str = "gitflow.prefix.feature";
+/// config key of the release branch prefix
+pub const CONFIG_FLOW_RELEASE: &str = "gitflow.prefix.release";
+/// config key of the hotfix branch prefix
+pub const CONFIG_FLOW_HOTFIX: &str = "gitflow.prefix.hotfix";
+/// config key of the version tag prefix
+pub const CONFIG_FLOW_VERSION_TAG: &str = "gitflow.prefix.versiontag";
+
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlowBranchType {
+	///
+	Feature,
+	///
+	Release,
+	///
+	Hotfix,
+}
+
+impl FlowBranchType {
+	///
+	pub const ALL: [Self; 3] =
+		[Self::Feature, Self::Release, Self::Hotfix];
+
+	/// finishing the branch creates a version tag
+	pub const fn is_tagged(self) -> bool {
+		matches!(self, Self::Release | Self::Hotfix)
+	}
+}
+
+/// the configured branching model
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlowConfig {
+	///
+	pub main: String,
+	///
+	pub develop: String,
+	///
+	pub feature_prefix: String,
+	///
+	pub release_prefix: String,
+	///
+	pub hotfix_prefix: String,
+	///
+	pub version_tag_prefix: String,
+}
+
+impl FlowConfig {
+	/// read the model from the git config
+	pub fn load(repo_path: &RepoPath) -> Result<Self> {
+		scope_time!("FlowConfig::load");
+
+		let repo = repo(repo_path)?;
+		let config = repo.config()?;
+
+		let get = |key: &str, default: &str| {
+			config
+				.get_string(key)
+				.ok()
+				.filter(|value| !value.trim().is_empty())
+				.unwrap_or_else(|| default.to_string())
+		};
+
+		let default_main =
+			if repo.find_branch("main", BranchType::Local).is_ok() {
+				"main"
+			} else {
+				"master"
+			};
+
+		Ok(Self {
+			main: get(CONFIG_FLOW_MAIN, default_main),
+			develop: get(CONFIG_FLOW_DEVELOP, "develop"),
+			feature_prefix: get(CONFIG_FLOW_FEATURE, "feature/"),
+			release_prefix: get(CONFIG_FLOW_RELEASE, "release/"),
+			hotfix_prefix: get(CONFIG_FLOW_HOTFIX, "hotfix/"),
+			version_tag_prefix: config
+				.get_string(CONFIG_FLOW_VERSION_TAG)
+				.unwrap_or_default(),
+		})
+	}
+
+	///
+	pub fn prefix(&self, typ: FlowBranchType) -> &str {
+		match typ {
+			FlowBranchType::Feature => &self.feature_prefix,
+			FlowBranchType::Release => &self.release_prefix,
+			FlowBranchType::Hotfix => &self.hotfix_prefix,
+		}
+	}
+
+	/// branch a new branch of `typ` starts from
+	pub fn base(&self, typ: FlowBranchType) -> &str {
+		match typ {
+			FlowBranchType::Feature | FlowBranchType::Release => {
+				&self.develop
+			}
+			FlowBranchType::Hotfix => &self.main,
+		}
+	}
+
+	/// branches a branch of `typ` gets merged into (in order)
+	pub fn merge_targets(&self, typ: FlowBranchType) -> Vec<&str> {
+		match typ {
+			FlowBranchType::Feature => vec![self.develop.as_str()],
+			FlowBranchType::Release | FlowBranchType::Hotfix => {
+				vec![self.main.as_str(), self.develop.as_str()]
+			}
+		}
+	}
+
+	/// full branch name
+	pub fn branch_name(
+		&self,
+		typ: FlowBranchType,
+		name: &str,
+	) -> String {
+		format!("{}{name}", self.prefix(typ))
+	}
+
+	/// version tag created when finishing a release/hotfix `name`
+	pub fn tag_name(&self, name: &str) -> String {
+		format!("{}{name}", self.version_tag_prefix)
+	}
+
+	/// splits `branch` into its type and name
+	pub fn parse_branch<'a>(
+		&self,
+		branch: &'a str,
+	) -> Option<(FlowBranchType, &'a str)> {
+		FlowBranchType::ALL.into_iter().find_map(|typ| {
+			branch
+				.strip_prefix(self.prefix(typ))
+				.filter(|name| !name.is_empty())
+				.map(|name| (typ, name))
+		})
+	}
+}
+
+/// creates the branch `name` of `typ` from its base and checks it out,
+/// returns the full branch name
+pub fn flow_start(
+	repo_path: &RepoPath,
+	config: &FlowConfig,
+	typ: FlowBranchType,
+	name: &str,
+) -> Result<String> {
+	scope_time!("flow_start");
+
+	let repo = repo(repo_path)?;
+
+	let base = config.base(typ);
+	let base_commit = repo
+		.find_branch(base, BranchType::Local)
+		.map_err(|_| {
+			Error::Generic(format!("base branch '{base}' not found"))
+		})?
+		.get()
+		.peel_to_commit()?;
+
+	let branch = config.branch_name(typ, name);
+	repo.branch(&branch, &base_commit, false)?;
+
+	checkout_branch(repo_path, &branch)?;
+
+	Ok(branch)
+}
+
+fn merges_cleanly(
+	repo: &Repository,
+	target: &str,
+	source: &Commit,
+) -> Result<bool> {
+	let target_commit = repo
+		.find_branch(target, BranchType::Local)
+		.map_err(|_| {
+			Error::Generic(format!("branch '{target}' not found"))
+		})?
+		.get()
+		.peel_to_commit()?;
+
+	Ok(!repo
+		.merge_commits(&target_commit, source, None)?
+		.has_conflicts())
+}
+
+/// merges `source` into the local branch `target` without touching
+/// the workdir, returns the merge commit (if anything was merged)
+fn merge_no_ff(
+	repo: &Repository,
+	target: &str,
+	source_name: &str,
+	source: &Commit,
+) -> Result<Option<CommitId>> {
+	let target_ref = repo
+		.find_branch(target, BranchType::Local)
+		.map_err(|_| {
+			Error::Generic(format!("branch '{target}' not found"))
+		})?
+		.into_reference();
+	let target_commit = target_ref.peel_to_commit()?;
+
+	if target_commit.id() == source.id()
+		|| repo
+			.graph_descendant_of(target_commit.id(), source.id())?
+	{
+		return Ok(None);
+	}
+
+	let mut index =
+		repo.merge_commits(&target_commit, source, None)?;
+	let tree = repo.find_tree(index.write_tree_to(repo)?)?;
+	let signature = signature_allow_undefined_name(repo)?;
+
+	let id = repo.commit(
+		target_ref.name(),
+		&signature,
+		&signature,
+		&format!("Merge branch '{source_name}' into {target}"),
+		&tree,
+		&[&target_commit, source],
+	)?;
+
+	Ok(Some(id.into()))
+}
+
+/// finishes the branch `name` of `typ`: merges it into its targets,
+/// tags releases/hotfixes (using `tag_message` for an annotated tag),
+/// checks out develop and deletes the branch
+pub fn flow_finish(
+	repo_path: &RepoPath,
+	config: &FlowConfig,
+	typ: FlowBranchType,
+	name: &str,
+	tag_message: Option<&str>,
+) -> Result<()> {
+	scope_time!("flow_finish");
+
+	if !is_workdir_clean(repo_path, None)? {
+		return Err(Error::UncommittedChanges);
+	}
+
+	let repo = repo(repo_path)?;
+
+	let branch_name = config.branch_name(typ, name);
+	let mut branch =
+		repo.find_branch(&branch_name, BranchType::Local)?;
+	let source = branch.get().peel_to_commit()?;
+
+	// do not leave a half finished branch behind
+	for target in config.merge_targets(typ) {
+		if !merges_cleanly(&repo, target, &source)? {
+			return Err(Error::Generic(format!(
+				"merging '{branch_name}' into '{target}' conflicts, merge manually"
+			)));
+		}
+	}
+
+	for (idx, target) in
+		config.merge_targets(typ).into_iter().enumerate()
+	{
+		let merged =
+			merge_no_ff(&repo, target, &branch_name, &source)?;
+
+		// the tag goes on the main branch
+		if idx == 0 && typ.is_tagged() {
+			let tagged = match merged {
+				Some(id) => repo.find_commit(id.into())?,
+				None => repo
+					.find_branch(target, BranchType::Local)?
+					.get()
+					.peel_to_commit()?,
+			};
+			let tag = config.tag_name(name);
+			let target = tagged.as_object();
+
+			if let Some(message) =
+				tag_message.filter(|msg| !msg.is_empty())
+			{
+				let signature =
+					signature_allow_undefined_name(&repo)?;
+				repo.tag(&tag, target, &signature, message, false)?;
+			} else {
+				repo.tag_lightweight(&tag, target, false)?;
+			}
+		}
+	}
+
+	// the branches we merged into moved, so update the checked out one
+	let develop = repo
+		.find_branch(&config.develop, BranchType::Local)?
+		.into_reference();
+	repo.checkout_tree(
+		&develop.peel(ObjectType::Tree)?,
+		Some(git2::build::CheckoutBuilder::new().force()),
+	)?;
+	repo.set_head(develop.name().ok_or_else(|| {
+		Error::Generic(String::from("branch ref not found"))
+	})?)?;
+
+	branch.delete()?;
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		branch::get_branch_name,
+		get_tags,
+		tests::{repo_init, write_commit_file},
+	};
+
+	fn setup() -> (tempfile::TempDir, Repository, RepoPath) {
+		let (td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: RepoPath =
+			root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "a");
+		{
+			let head = repo.head().unwrap().peel_to_commit().unwrap();
+			repo.branch("develop", &head, false).unwrap();
+		}
+
+		(td, repo, repo_path)
+	}
+
+	#[test]
+	fn test_config() {
+		let (_td, repo, repo_path) = setup();
+
+		let config = FlowConfig::load(&repo_path).unwrap();
+		assert_eq!(config.main, "master");
+		assert_eq!(config.develop, "develop");
+		assert_eq!(
+			config.parse_branch("feature/x"),
+			Some((FlowBranchType::Feature, "x"))
+		);
+		assert_eq!(
+			config.parse_branch("hotfix/1.0.1"),
+			Some((FlowBranchType::Hotfix, "1.0.1"))
+		);
+		assert_eq!(config.parse_branch("feature/"), None);
+		assert_eq!(config.parse_branch("develop"), None);
+
+		repo.config()
+			.unwrap()
+			.set_str(CONFIG_FLOW_FEATURE, "feat-")
+			.unwrap();
+		let config = FlowConfig::load(&repo_path).unwrap();
+		assert_eq!(
+			config.branch_name(FlowBranchType::Feature, "x"),
+			"feat-x"
+		);
+	}
+
+	#[test]
+	fn test_feature() {
+		let (_td, repo, repo_path) = setup();
+		let config = FlowConfig::load(&repo_path).unwrap();
+
+		write_commit_file(&repo, "main.txt", "m", "on master");
+
+		let branch = flow_start(
+			&repo_path,
+			&config,
+			FlowBranchType::Feature,
+			"x",
+		)
+		.unwrap();
+		assert_eq!(branch, "feature/x");
+		assert_eq!(get_branch_name(&repo_path).unwrap(), "feature/x");
+		// started from develop, not from master
+		assert!(!repo.workdir().unwrap().join("main.txt").exists());
+
+		write_commit_file(&repo, "x.txt", "x", "feature x");
+
+		flow_finish(
+			&repo_path,
+			&config,
+			FlowBranchType::Feature,
+			"x",
+			None,
+		)
+		.unwrap();
+
+		assert_eq!(get_branch_name(&repo_path).unwrap(), "develop");
+		assert!(repo.workdir().unwrap().join("x.txt").exists());
+		assert!(repo
+			.find_branch("feature/x", BranchType::Local)
+			.is_err());
+
+		let head = repo.head().unwrap().peel_to_commit().unwrap();
+		assert_eq!(head.parent_count(), 2);
+		assert_eq!(
+			head.message().unwrap(),
+			"Merge branch 'feature/x' into develop"
+		);
+	}
+
+	#[test]
+	fn test_release() {
+		let (_td, repo, repo_path) = setup();
+		repo.config()
+			.unwrap()
+			.set_str(CONFIG_FLOW_VERSION_TAG, "v")
+			.unwrap();
+		let config = FlowConfig::load(&repo_path).unwrap();
+
+		flow_start(
+			&repo_path,
+			&config,
+			FlowBranchType::Release,
+			"1.0",
+		)
+		.unwrap();
+		write_commit_file(&repo, "version.txt", "1.0", "bump");
+
+		flow_finish(
+			&repo_path,
+			&config,
+			FlowBranchType::Release,
+			"1.0",
+			Some("release 1.0"),
+		)
+		.unwrap();
+
+		assert_eq!(get_branch_name(&repo_path).unwrap(), "develop");
+
+		let master = repo
+			.find_branch("master", BranchType::Local)
+			.unwrap()
+			.get()
+			.peel_to_commit()
+			.unwrap();
+		assert_eq!(master.parent_count(), 2);
+
+		let tags = get_tags(&repo_path).unwrap();
+		assert_eq!(
+			tags.get(&CommitId::new(master.id())).unwrap()[0].name,
+			"v1.0"
+		);
+
+		let develop = repo.head().unwrap().peel_to_commit().unwrap();
+		assert!(repo
+			.graph_descendant_of(
+				develop.id(),
+				master.parent_id(1).unwrap()
+			)
+			.unwrap());
+	}
+
+	#[test]
+	fn test_finish_dirty() {
+		let (_td, repo, repo_path) = setup();
+		let config = FlowConfig::load(&repo_path).unwrap();
+
+		flow_start(&repo_path, &config, FlowBranchType::Feature, "y")
+			.unwrap();
+		std::fs::write(repo.workdir().unwrap().join("a.txt"), "b")
+			.unwrap();
+
+		assert!(matches!(
+			flow_finish(
+				&repo_path,
+				&config,
+				FlowBranchType::Feature,
+				"y",
+				None
+			),
+			Err(Error::UncommittedChanges)
+		));
+	}
+}
@@ -1,9 +1,12 @@
 //! merging from upstream
 
-use super::BranchType;
+use super::upstream_refname;
 use crate::{
 	error::{Error, Result},
-	sync::{merge_msg, repository::repo, CommitId, RepoPath},
+	sync::{
+		hooks::run_post_merge_hook, merge_msg, repository::repo,
+		CommitId, RepoPath,
+	},
 };
 use git2::Commit;
 use scopetime::scope_time;
@@ -17,15 +20,25 @@ pub fn merge_upstream_commit(
 ) -> Result<Option<CommitId>> {
 	scope_time!("merge_upstream_commit");
 
+	let upstream = upstream_refname(&repo(repo_path)?, branch_name)?;
+
+	merge_commit_from(repo_path, &upstream)
+}
+
+/// merges `upstream` (a full refname) into head, see
+/// [`merge_upstream_commit`]
+pub(crate) fn merge_commit_from(
+	repo_path: &RepoPath,
+	upstream: &str,
+) -> Result<Option<CommitId>> {
 	let repo = repo(repo_path)?;
 
-	let branch = repo.find_branch(branch_name, BranchType::Local)?;
-	let upstream = branch.upstream()?;
+	let upstream = repo.find_reference(upstream)?;
 
-	let upstream_commit = upstream.get().peel_to_commit()?;
+	let upstream_commit = upstream.peel_to_commit()?;
 
-	let annotated_upstream = repo
-		.reference_to_annotated_commit(&upstream.into_reference())?;
+	let annotated_upstream =
+		repo.reference_to_annotated_commit(&upstream)?;
 
 	let (analysis, pref) =
 		repo.merge_analysis(&[&annotated_upstream])?;
@@ -89,6 +102,9 @@ pub(crate) fn commit_merge_with_head(
 		)?
 		.into();
 	repo.cleanup_state()?;
+
+	run_post_merge_hook(repo);
+
 	Ok(commit_id)
 }
 
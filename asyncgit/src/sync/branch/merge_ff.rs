@@ -1,9 +1,9 @@
 //! merging from upstream
 
-use super::BranchType;
+use super::upstream_refname;
 use crate::{
 	error::{Error, Result},
-	sync::{repository::repo, RepoPath},
+	sync::{hooks::run_post_merge_hook, repository::repo, RepoPath},
 };
 use scopetime::scope_time;
 
@@ -14,13 +14,20 @@ pub fn branch_merge_upstream_fastforward(
 ) -> Result<()> {
 	scope_time!("branch_merge_upstream");
 
-	let repo = repo(repo_path)?;
+	let upstream = upstream_refname(&repo(repo_path)?, branch)?;
 
-	let branch = repo.find_branch(branch, BranchType::Local)?;
-	let upstream = branch.upstream()?;
+	merge_fastforward_from(repo_path, &upstream)
+}
+
+/// fast-forwards head to `upstream` (a full refname)
+pub(crate) fn merge_fastforward_from(
+	repo_path: &RepoPath,
+	upstream: &str,
+) -> Result<()> {
+	let repo = repo(repo_path)?;
 
 	let upstream_commit =
-		upstream.into_reference().peel_to_commit()?;
+		repo.find_reference(upstream)?.peel_to_commit()?;
 
 	let annotated =
 		repo.find_annotated_commit(upstream_commit.id())?;
@@ -46,6 +53,8 @@ pub fn branch_merge_upstream_fastforward(
 
 	repo.head()?.set_target(annotated.id(), "")?;
 
+	run_post_merge_hook(&repo);
+
 	Ok(())
 }
 
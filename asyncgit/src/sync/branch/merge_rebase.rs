@@ -3,11 +3,10 @@
 use crate::{
 	error::{Error, Result},
 	sync::{
-		rebase::conflict_free_rebase, repository::repo, CommitId,
-		RepoPath,
+		branch::upstream_refname, rebase::conflict_free_rebase,
+		repository::repo, CommitId, RepoPath,
 	},
 };
-use git2::BranchType;
 use scopetime::scope_time;
 
 /// tries merging current branch with its upstream using rebase
@@ -17,6 +16,18 @@ pub fn merge_upstream_rebase(
 ) -> Result<CommitId> {
 	scope_time!("merge_upstream_rebase");
 
+	let upstream = upstream_refname(&repo(repo_path)?, branch_name)?;
+
+	merge_rebase_from(repo_path, branch_name, &upstream)
+}
+
+/// rebases the checked out `branch_name` onto `upstream` (a full
+/// refname)
+pub(crate) fn merge_rebase_from(
+	repo_path: &RepoPath,
+	branch_name: &str,
+	upstream: &str,
+) -> Result<CommitId> {
 	let repo = repo(repo_path)?;
 	if super::get_branch_name_repo(&repo)? != branch_name {
 		return Err(Error::Generic(String::from(
@@ -24,9 +35,8 @@ pub fn merge_upstream_rebase(
 		)));
 	}
 
-	let branch = repo.find_branch(branch_name, BranchType::Local)?;
-	let upstream = branch.upstream()?;
-	let upstream_commit = upstream.get().peel_to_commit()?;
+	let upstream_commit =
+		repo.find_reference(upstream)?.peel_to_commit()?;
 	let annotated_upstream =
 		repo.find_annotated_commit(upstream_commit.id())?;
 
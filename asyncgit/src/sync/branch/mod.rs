@@ -1,21 +1,31 @@
 //! branch functions
 
+pub mod flow;
 pub mod merge_commit;
 pub mod merge_ff;
 pub mod merge_rebase;
+pub mod protected;
+pub mod pull;
 pub mod rename;
+pub mod stack;
 
 use super::{utils::bytes2string, RepoPath};
 use crate::{
 	error::{Error, Result},
 	sync::{
-		remotes::get_default_remote_for_push_in_repo,
-		repository::repo, utils::get_head_repo, CommitId,
+		hooks::run_post_checkout_hook,
+		remotes::{
+			get_default_remote_for_push_in_repo, RemoteTarget,
+		},
+		repository::repo,
+		status::{get_status, StatusType},
+		utils::{get_head_repo, work_dir},
+		CommitId, ShowUntrackedFilesConfig,
 	},
 };
-use git2::{Branch, BranchType, Repository};
+use git2::{Branch, BranchType, Oid, Repository};
 use scopetime::scope_time;
-use std::collections::HashSet;
+use std::{collections::HashSet, path::Path};
 
 /// returns the branch-name head is currently pointing to
 /// this might be expensive, see `cached::BranchName`
@@ -55,6 +65,8 @@ pub struct LocalBranch {
 	pub upstream: Option<UpstreamBranch>,
 	///
 	pub remote: Option<String>,
+	/// branch this one is stacked on
+	pub stack_parent: Option<String>,
 }
 
 ///
@@ -173,6 +185,10 @@ pub fn get_branches_info(
 					has_upstream: upstream_branch.is_some(),
 					upstream: upstream_branch,
 					remote,
+					stack_parent: stack::get_stack_parent_repo(
+						&repo,
+						bytes2string(name_bytes)?.as_str(),
+					)?,
 				})
 			} else {
 				BranchDetails::Remote(RemoteBranch {
@@ -200,7 +216,7 @@ pub fn get_branches_info(
 }
 
 ///
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub struct BranchCompare {
 	///
 	pub ahead: usize,
@@ -227,6 +243,35 @@ pub(crate) fn branch_set_upstream_after_push(
 	Ok(())
 }
 
+/// makes the branch of `target` the upstream of the local `branch`,
+/// its remote tracking ref has to exist already
+pub fn set_branch_upstream(
+	repo_path: &RepoPath,
+	branch: &str,
+	target: &RemoteTarget,
+) -> Result<()> {
+	scope_time!("set_branch_upstream");
+
+	let repo = repo(repo_path)?;
+	let mut branch = repo.find_branch(branch, BranchType::Local)?;
+	branch.set_upstream(Some(&format!(
+		"{}/{}",
+		target.remote, target.branch
+	)))?;
+
+	Ok(())
+}
+
+/// full name of the remote tracking ref `branch` is integrating
+pub(crate) fn upstream_refname(
+	repo: &Repository,
+	branch: &str,
+) -> Result<String> {
+	let branch = repo.find_branch(branch, BranchType::Local)?;
+	let upstream = branch.upstream()?;
+	bytes2string(upstream.get().name_bytes())
+}
+
 /// returns remote of the upstream tracking branch for `branch`
 pub fn get_branch_remote(
 	repo_path: &RepoPath,
@@ -262,43 +307,116 @@ pub fn get_branch_upstream_merge(
 	}
 }
 
-/// returns whether the pull merge strategy is set to rebase
-pub fn config_is_pull_rebase(repo_path: &RepoPath) -> Result<bool> {
-	let repo = repo(repo_path)?;
-	let config = repo.config()?;
+///
+pub fn branch_compare_upstream(
+	repo_path: &RepoPath,
+	branch: &str,
+) -> Result<BranchCompare> {
+	scope_time!("branch_compare_upstream");
 
-	if let Ok(rebase) = config.get_entry("pull.rebase") {
-		let value =
-			rebase.value().map(String::from).unwrap_or_default();
-		return Ok(value == "true");
-	};
+	let upstream = upstream_refname(&repo(repo_path)?, branch)?;
 
-	Ok(false)
+	branch_compare_ref(repo_path, branch, &upstream)
 }
 
-///
-pub fn branch_compare_upstream(
+/// compares the local `branch` with any other ref, like the remote
+/// tracking ref of a [`RemoteTarget`]
+pub fn branch_compare_ref(
 	repo_path: &RepoPath,
 	branch: &str,
+	reference: &str,
 ) -> Result<BranchCompare> {
-	scope_time!("branch_compare_upstream");
+	scope_time!("branch_compare_ref");
 
 	let repo = repo(repo_path)?;
 
-	let branch = repo.find_branch(branch, BranchType::Local)?;
+	let branch_commit = repo
+		.find_branch(branch, BranchType::Local)?
+		.into_reference()
+		.peel_to_commit()?
+		.id();
 
-	let upstream = branch.upstream()?;
+	let other_commit =
+		repo.find_reference(reference)?.peel_to_commit()?.id();
+
+	let (ahead, behind) =
+		repo.graph_ahead_behind(branch_commit, other_commit)?;
+
+	Ok(BranchCompare { ahead, behind })
+}
+
+/// compares each of `branches` with the ref `base` (e.g.
+/// `refs/heads/main`), or with its upstream if `base` is `None`.
+/// `None` for branches without upstream
+pub fn branches_compare(
+	repo_path: &RepoPath,
+	branches: &[BranchInfo],
+	base: Option<&str>,
+) -> Result<Vec<Option<BranchCompare>>> {
+	scope_time!("branches_compare");
+
+	let repo = repo(repo_path)?;
+
+	let base = match base {
+		Some(base) => {
+			Some(repo.find_reference(base)?.peel_to_commit()?.id())
+		}
+		None => None,
+	};
+
+	Ok(branches
+		.iter()
+		.map(|branch| {
+			let other = base.or_else(|| {
+				let upstream =
+					branch.local_details()?.upstream.as_ref()?;
 
-	let branch_commit =
-		branch.into_reference().peel_to_commit()?.id();
+				repo.find_reference(&upstream.reference)
+					.and_then(|r| r.peel_to_commit())
+					.map(|c| c.id())
+					.ok()
+			})?;
+
+			let (ahead, behind) = repo
+				.graph_ahead_behind(
+					branch.top_commit.get_oid(),
+					other,
+				)
+				.ok()?;
+
+			Some(BranchCompare { ahead, behind })
+		})
+		.collect())
+}
+
+/// returns `true` if `commit` is reachable from the upstream of the
+/// currently checked out branch, meaning it was already pushed.
+/// returns `false` if there is no branch or no upstream configured
+pub fn is_commit_pushed(
+	repo_path: &RepoPath,
+	commit: CommitId,
+) -> Result<bool> {
+	scope_time!("is_commit_pushed");
+
+	let repo = repo(repo_path)?;
+
+	let Ok(branch) = get_branch_name_repo(&repo) else {
+		return Ok(false);
+	};
+
+	let Ok(upstream) = repo
+		.find_branch(&branch, BranchType::Local)
+		.and_then(|b| b.upstream())
+	else {
+		return Ok(false);
+	};
 
 	let upstream_commit =
 		upstream.into_reference().peel_to_commit()?.id();
 
-	let (ahead, behind) =
-		repo.graph_ahead_behind(branch_commit, upstream_commit)?;
-
-	Ok(BranchCompare { ahead, behind })
+	Ok(upstream_commit == commit.get_oid()
+		|| repo
+			.graph_descendant_of(upstream_commit, commit.get_oid())?)
 }
 
 /// Switch branch to given `branch_name`.
@@ -313,10 +431,12 @@ pub fn checkout_branch(
 	scope_time!("checkout_branch");
 
 	let repo = repo(repo_path)?;
+	let old_head = head_oid(&repo);
 
 	let branch = repo.find_branch(branch_name, BranchType::Local)?;
 
 	let branch_ref = branch.into_reference();
+	let new_head = branch_ref.peel_to_commit()?.id();
 
 	let target_treeish = branch_ref.peel_to_tree()?;
 	let target_treeish_object = target_treeish.as_object();
@@ -334,9 +454,19 @@ pub fn checkout_branch(
 	// modify HEAD to point to given branch
 	repo.set_head(branch_ref?)?;
 
+	run_post_checkout_hook(&repo, old_head, new_head);
+
 	Ok(())
 }
 
+/// commit HEAD points to, zero if it is unborn
+fn head_oid(repo: &Repository) -> Oid {
+	repo.head()
+		.ok()
+		.and_then(|head| head.target())
+		.unwrap_or_else(Oid::zero)
+}
+
 /// Detach HEAD to point to a commit then checkout HEAD, does not work if there are uncommitted changes
 pub fn checkout_commit(
 	repo_path: &RepoPath,
@@ -361,6 +491,13 @@ pub fn checkout_commit(
 			)?;
 			return Err(Error::Git(e));
 		}
+
+		run_post_checkout_hook(
+			&repo,
+			cur_ref.target().unwrap_or_else(Oid::zero),
+			commit_hash.into(),
+		);
+
 		Ok(())
 	} else {
 		Err(Error::UncommittedChanges)
@@ -407,6 +544,13 @@ pub fn checkout_remote_branch(
 		repo.set_head(bytes2string(cur_ref.name_bytes())?.as_str())?;
 		return Err(Error::Git(e));
 	}
+
+	run_post_checkout_hook(
+		&repo,
+		cur_ref.target().unwrap_or_else(Oid::zero),
+		commit.id(),
+	);
+
 	Ok(())
 }
 
@@ -447,6 +591,98 @@ pub fn create_branch(
 	Ok(branch_ref_name)
 }
 
+/// creates a branch pointing at `commit` without checking it out,
+/// similar to `git branch <name> <commit>`.
+pub fn create_branch_at(
+	repo_path: &RepoPath,
+	name: &str,
+	commit: CommitId,
+) -> Result<String> {
+	scope_time!("create_branch_at");
+
+	let repo = repo(repo_path)?;
+
+	let commit = repo.find_commit(commit.into())?;
+
+	let branch = repo.branch(name, &commit, false)?;
+	let branch_ref = branch.into_reference();
+
+	bytes2string(branch_ref.name_bytes())
+}
+
+/// creates a branch without any history and points `HEAD` at it,
+/// similar to `git checkout --orphan`.
+///
+/// the index and worktree are kept as they are, so the first commit
+/// contains the current files, unless `clear_files` is set: then all
+/// tracked files are removed like `git rm -rf .` would do.
+pub fn create_orphan_branch(
+	repo_path: &RepoPath,
+	name: &str,
+	clear_files: bool,
+) -> Result<String> {
+	scope_time!("create_orphan_branch");
+
+	let repo = repo(repo_path)?;
+
+	let branch_ref_name = format!("refs/heads/{name}");
+
+	if !git2::Reference::is_valid_name(&branch_ref_name) {
+		return Err(Error::Generic(format!(
+			"invalid branch name: {name}"
+		)));
+	}
+
+	if repo.find_branch(name, BranchType::Local).is_ok() {
+		return Err(Error::Generic(format!(
+			"branch already exists: {name}"
+		)));
+	}
+
+	if clear_files {
+		let changes = get_status(
+			repo_path,
+			StatusType::Both,
+			Some(ShowUntrackedFilesConfig::No),
+		)?;
+		if !changes.is_empty() {
+			return Err(Error::UncommittedChanges);
+		}
+
+		let work_dir = work_dir(&repo)?;
+		let mut index = repo.index()?;
+
+		for entry in index.iter() {
+			let path = work_dir.join(bytes2string(&entry.path)?);
+			if path.is_file() || path.is_symlink() {
+				std::fs::remove_file(&path)?;
+			}
+			remove_empty_parents(work_dir, &path);
+		}
+
+		index.clear()?;
+		index.write()?;
+	}
+
+	repo.set_head(&branch_ref_name)?;
+
+	Ok(branch_ref_name)
+}
+
+/// removes directories left empty by deleting `path`
+fn remove_empty_parents(work_dir: &Path, path: &Path) {
+	let mut dir = path.parent();
+
+	while let Some(current) = dir {
+		if current == work_dir
+			|| std::fs::remove_dir(current).is_err()
+		{
+			break;
+		}
+		dir = current.parent();
+	}
+}
+
 #[cfg(test)]
 mod tests_branch_name {
 	use super::*;
@@ -500,10 +736,116 @@ mod tests_create_branch {
 	}
 }
 
+#[cfg(test)]
+mod tests_create_branch_at {
+	use super::*;
+	use crate::sync::tests::{repo_init, write_commit_file};
+
+	#[test]
+	fn test_smoke() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 = write_commit_file(&repo, "a.txt", "1", "c1");
+		write_commit_file(&repo, "a.txt", "2", "c2");
+
+		assert_eq!(
+			create_branch_at(repo_path, "old", c1).unwrap(),
+			"refs/heads/old"
+		);
+
+		assert_eq!(
+			get_branch_name(repo_path).unwrap().as_str(),
+			"master"
+		);
+		assert_eq!(
+			repo.revparse_single("old").unwrap().id(),
+			c1.into()
+		);
+	}
+}
+
+#[cfg(test)]
+mod tests_create_orphan_branch {
+	use super::*;
+	use crate::sync::tests::{repo_init, write_commit_file};
+	use std::fs;
+
+	#[test]
+	fn test_keep_files() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "commit a");
+
+		create_orphan_branch(repo_path, "pages", false).unwrap();
+
+		assert_eq!(
+			repo.find_reference("HEAD").unwrap().symbolic_target(),
+			Some("refs/heads/pages")
+		);
+		// unborn until the first commit
+		assert!(repo.head().is_err());
+		assert!(root.join("a.txt").exists());
+		let mut index = repo.index().unwrap();
+		index.read(true).unwrap();
+		assert_eq!(index.len(), 1);
+		assert!(repo
+			.find_branch("master", BranchType::Local)
+			.is_ok());
+	}
+
+	#[test]
+	fn test_clear_files() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		fs::create_dir(root.join("dir")).unwrap();
+		write_commit_file(&repo, "dir/a.txt", "a", "commit a");
+		fs::write(root.join("untracked.txt"), "u").unwrap();
+
+		create_orphan_branch(repo_path, "pages", true).unwrap();
+
+		assert!(!root.join("dir").exists());
+		assert!(root.join("untracked.txt").exists());
+		let mut index = repo.index().unwrap();
+		index.read(true).unwrap();
+		assert_eq!(index.len(), 0);
+	}
+
+	#[test]
+	fn test_clear_needs_clean_worktree() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "commit a");
+		fs::write(root.join("a.txt"), "changed").unwrap();
+
+		assert!(
+			create_orphan_branch(repo_path, "pages", true).is_err()
+		);
+		assert!(
+			create_orphan_branch(repo_path, "master", false).is_err()
+		);
+		assert_eq!(
+			get_branch_name(repo_path).unwrap().as_str(),
+			"master"
+		);
+	}
+}
+
 #[cfg(test)]
 mod tests_branch_compare {
 	use super::*;
-	use crate::sync::tests::repo_init;
+	use crate::sync::tests::{repo_init, write_commit_file};
 
 	#[test]
 	fn test_smoke() {
@@ -518,6 +860,56 @@ mod tests_branch_compare {
 
 		assert!(res.is_err());
 	}
+
+	#[test]
+	fn test_branches_compare() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		create_branch(repo_path, "feature").unwrap();
+		write_commit_file(&repo, "a.txt", "a", "c1");
+		write_commit_file(&repo, "b.txt", "b", "c2");
+		checkout_branch(repo_path, "master").unwrap();
+		write_commit_file(&repo, "c.txt", "c", "c3");
+
+		let branches = get_branches_info(repo_path, true).unwrap();
+		let compare = |base| {
+			branches
+				.iter()
+				.map(|b| b.name.as_str())
+				.zip(
+					branches_compare(repo_path, &branches, base)
+						.unwrap(),
+				)
+				.collect::<Vec<_>>()
+		};
+
+		assert_eq!(
+			compare(Some("refs/heads/master")),
+			vec![
+				(
+					"feature",
+					Some(BranchCompare {
+						ahead: 2,
+						behind: 1
+					})
+				),
+				(
+					"master",
+					Some(BranchCompare {
+						ahead: 0,
+						behind: 0
+					})
+				),
+			]
+		);
+		assert_eq!(
+			compare(None),
+			vec![("feature", None), ("master", None)]
+		);
+	}
 }
 
 #[cfg(test)]
@@ -768,6 +1160,35 @@ mod tests_checkout {
 		assert!(checkout_branch(repo_path, "test").is_ok());
 	}
 
+	#[test]
+	fn test_post_checkout_hook() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let out = repo.path().join("post-checkout-out");
+		let hook = format!(
+			"#!/bin/sh
+echo \"$1 $2 $3\" > {out:?}
+"
+		);
+		git2_hooks::create_hook(
+			&repo,
+			git2_hooks::HOOK_POST_CHECKOUT,
+			hook.as_bytes(),
+		);
+
+		let head = repo.head().unwrap().target().unwrap();
+		create_branch(repo_path, "test").unwrap();
+		checkout_branch(repo_path, "test").unwrap();
+
+		assert_eq!(
+			std::fs::read_to_string(out).unwrap(),
+			format!("{head} {head} 1\n")
+		);
+	}
+
 	#[test]
 	fn test_branch_with_slash_in_name() {
 		let (_td, repo) = repo_init().unwrap();
@@ -950,6 +1371,30 @@ mod test_remote_branches {
 		assert_eq!(&branches[2].name, "origin/master");
 	}
 
+	#[test]
+	fn test_is_commit_pushed() {
+		let (r1_dir, _repo) = repo_init_bare().unwrap();
+
+		let (clone1_dir, clone1) =
+			repo_clone(r1_dir.path().to_str().unwrap()).unwrap();
+		let clone1_dir: &RepoPath =
+			&clone1_dir.path().to_str().unwrap().into();
+
+		let c1 = write_commit_file(&clone1, "test.txt", "a", "c1");
+
+		assert!(!is_commit_pushed(clone1_dir, c1).unwrap());
+
+		push_branch(
+			clone1_dir, "origin", "master", false, false, None, None,
+		)
+		.unwrap();
+
+		let c2 = write_commit_file(&clone1, "test.txt", "b", "c2");
+
+		assert!(is_commit_pushed(clone1_dir, c1).unwrap());
+		assert!(!is_commit_pushed(clone1_dir, c2).unwrap());
+	}
+
 	#[test]
 	fn test_checkout_remote_branch() {
 		let (r1_dir, _repo) = repo_init_bare().unwrap();
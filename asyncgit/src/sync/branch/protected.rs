@@ -0,0 +1,156 @@
+//! protected branches
+//!
+//! branches matching one of the patterns in `gitui.protectedBranches`
+//! (e.g. `main release/*`) ask for an extra confirmation before they
+//! get pushed. the key can be set multiple times, each value holding
+//! whitespace or comma separated patterns. `*` matches any run of
+//! characters (including `/`), `?` a single one.
+
+use crate::{
+	error::Result,
+	sync::{repository::repo, RepoPath},
+};
+use git2::Repository;
+use scopetime::scope_time;
+
+/// config key listing the protected branch patterns
+pub const CONFIG_PROTECTED_BRANCHES: &str = "gitui.protectedBranches";
+
+/// returns all configured protected branch patterns
+pub fn get_protected_branch_patterns(
+	repo_path: &RepoPath,
+) -> Result<Vec<String>> {
+	let repo = repo(repo_path)?;
+
+	get_protected_branch_patterns_repo(&repo)
+}
+
+fn get_protected_branch_patterns_repo(
+	repo: &Repository,
+) -> Result<Vec<String>> {
+	scope_time!("get_protected_branch_patterns");
+
+	let config = repo.config()?;
+
+	let mut patterns = Vec::new();
+
+	// a missing key is no error, we just get no entries
+	if let Ok(mut entries) =
+		config.multivar(CONFIG_PROTECTED_BRANCHES, None)
+	{
+		while let Some(entry) = entries.next() {
+			if let Some(value) = entry?.value() {
+				patterns.extend(
+					value
+						.split(|c: char| {
+							c == ',' || c.is_whitespace()
+						})
+						.filter(|p| !p.is_empty())
+						.map(String::from),
+				);
+			}
+		}
+	}
+
+	Ok(patterns)
+}
+
+/// returns whether the local `branch` matches a protected pattern
+pub fn is_branch_protected(
+	repo_path: &RepoPath,
+	branch: &str,
+) -> Result<bool> {
+	let repo = repo(repo_path)?;
+
+	Ok(get_protected_branch_patterns_repo(&repo)?
+		.iter()
+		.any(|pattern| pattern_matches(pattern, branch)))
+}
+
+fn pattern_matches(pattern: &str, name: &str) -> bool {
+	let pattern = pattern.chars().collect::<Vec<_>>();
+	let name = name.chars().collect::<Vec<_>>();
+
+	let (mut p, mut n) = (0, 0);
+	// position of the last `*` and the name position it matched up to
+	let mut backtrack = None;
+
+	while n < name.len() {
+		match pattern.get(p) {
+			Some('*') => {
+				backtrack = Some((p, n));
+				p += 1;
+			}
+			Some(c) if *c == '?' || *c == name[n] => {
+				p += 1;
+				n += 1;
+			}
+			_ => {
+				let Some((star, matched)) = backtrack else {
+					return false;
+				};
+				backtrack = Some((star, matched + 1));
+				p = star + 1;
+				n = matched + 1;
+			}
+		}
+	}
+
+	pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::sync::tests::repo_init;
+
+	#[test]
+	fn test_pattern_matches() {
+		assert!(pattern_matches("main", "main"));
+		assert!(!pattern_matches("main", "main2"));
+		assert!(!pattern_matches("main", "mai"));
+		assert!(pattern_matches("release/*", "release/1.0"));
+		assert!(pattern_matches("release/*", "release/1.0/fix"));
+		assert!(!pattern_matches("release/*", "releases"));
+		assert!(pattern_matches("*", "anything"));
+		assert!(pattern_matches("v?", "v1"));
+		assert!(!pattern_matches("v?", "v10"));
+		assert!(pattern_matches("*-stable", "1.0-stable"));
+		assert!(pattern_matches("a*b*c", "aXbYbZc"));
+		assert!(!pattern_matches("a*b*c", "aXbYbZ"));
+	}
+
+	#[test]
+	fn test_protected_branches() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		assert!(get_protected_branch_patterns(repo_path)
+			.unwrap()
+			.is_empty());
+		assert!(!is_branch_protected(repo_path, "master").unwrap());
+
+		let mut config = repo.config().unwrap();
+		config
+			.set_multivar(
+				CONFIG_PROTECTED_BRANCHES,
+				"^$",
+				"master, release/*",
+			)
+			.unwrap();
+		config
+			.set_multivar(CONFIG_PROTECTED_BRANCHES, "^$", "prod")
+			.unwrap();
+
+		assert_eq!(
+			get_protected_branch_patterns(repo_path).unwrap(),
+			vec!["master", "release/*", "prod"]
+		);
+		assert!(is_branch_protected(repo_path, "master").unwrap());
+		assert!(is_branch_protected(repo_path, "release/2").unwrap());
+		assert!(is_branch_protected(repo_path, "prod").unwrap());
+		assert!(!is_branch_protected(repo_path, "feature").unwrap());
+	}
+}
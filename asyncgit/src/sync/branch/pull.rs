@@ -0,0 +1,396 @@
+//! integrating fetched upstream changes like `git pull`
+
+use super::{
+	merge_commit::merge_commit_from,
+	merge_ff::merge_fastforward_from,
+	merge_rebase::merge_rebase_from, upstream_refname,
+};
+use crate::{
+	error::{Error, Result},
+	sync::{
+		repository::repo,
+		stash::{stash_pop, stash_save},
+		status::is_workdir_clean,
+		CommitId, RepoPath, ShowUntrackedFilesConfig,
+	},
+};
+use scopetime::scope_time;
+
+/// how a pull integrates the upstream changes
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PullStrategy {
+	/// fast-forward if possible, otherwise create a merge commit
+	Merge,
+	/// rebase the local commits onto the upstream
+	Rebase,
+	/// refuse to pull if the branches diverged
+	FastForwardOnly,
+}
+
+impl Default for PullStrategy {
+	fn default() -> Self {
+		Self::Merge
+	}
+}
+
+///
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct PullOptions {
+	///
+	pub strategy: PullStrategy,
+	/// stash local changes before and reapply them after the pull
+	pub autostash: bool,
+}
+
+/// what a pull did to the branch
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PullOutcome {
+	///
+	FastForward,
+	///
+	Merged,
+	///
+	Rebased,
+	/// the merge stopped with conflicts to resolve
+	Conflicts,
+}
+
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PullResult {
+	///
+	pub outcome: PullOutcome,
+	/// autostashed changes that could not be reapplied and are kept
+	/// in the stash list
+	pub stashed: Option<CommitId>,
+}
+
+/// pull options configured via `pull.rebase`, `pull.ff` and
+/// `rebase.autoStash`/`merge.autoStash`
+pub fn pull_options_config(
+	repo_path: &RepoPath,
+) -> Result<PullOptions> {
+	let repo = repo(repo_path)?;
+	let config = repo.config()?;
+
+	let rebase =
+		config.get_string("pull.rebase").is_ok_and(|value| {
+			!matches!(
+				value.to_lowercase().as_str(),
+				"" | "false" | "no" | "off" | "0"
+			)
+		});
+	let ff_only = config
+		.get_string("pull.ff")
+		.is_ok_and(|value| value == "only");
+
+	let strategy = if rebase {
+		PullStrategy::Rebase
+	} else if ff_only {
+		PullStrategy::FastForwardOnly
+	} else {
+		PullStrategy::Merge
+	};
+
+	let autostash = config
+		.get_bool(if rebase {
+			"rebase.autoStash"
+		} else {
+			"merge.autoStash"
+		})
+		.unwrap_or_default();
+
+	Ok(PullOptions {
+		strategy,
+		autostash,
+	})
+}
+
+/// integrates the upstream of the checked out `branch` using
+/// `strategy`, `FastForwardOnly` is also used to try a fast-forward
+/// before asking for a merge or rebase
+pub fn pull_upstream(
+	repo_path: &RepoPath,
+	branch: &str,
+	strategy: PullStrategy,
+	autostash: bool,
+) -> Result<PullResult> {
+	scope_time!("pull_upstream");
+
+	let upstream = upstream_refname(&repo(repo_path)?, branch)?;
+
+	pull_from(repo_path, branch, &upstream, strategy, autostash)
+}
+
+/// like [`pull_upstream`] but integrates `upstream` (a full refname
+/// like the tracking ref of a `RemoteTarget`) instead of the
+/// configured upstream of `branch`
+pub fn pull_from(
+	repo_path: &RepoPath,
+	branch: &str,
+	upstream: &str,
+	strategy: PullStrategy,
+	autostash: bool,
+) -> Result<PullResult> {
+	scope_time!("pull_from");
+
+	let stash = if autostash
+		&& !is_workdir_clean(
+			repo_path,
+			Some(ShowUntrackedFilesConfig::No),
+		)? {
+		Some(stash_save(
+			repo_path,
+			Some("autostash"),
+			false,
+			false,
+			false,
+		)?)
+	} else {
+		None
+	};
+
+	let res = match strategy {
+		PullStrategy::FastForwardOnly => {
+			merge_fastforward_from(repo_path, upstream)
+				.map(|()| PullOutcome::FastForward)
+		}
+		PullStrategy::Merge => merge_commit_from(repo_path, upstream)
+			.map(|commit| {
+				if commit.is_some() {
+					PullOutcome::Merged
+				} else {
+					PullOutcome::Conflicts
+				}
+			}),
+		PullStrategy::Rebase => {
+			merge_rebase_from(repo_path, branch, upstream)
+				.map(|_| PullOutcome::Rebased)
+		}
+	};
+
+	let Some(stash) = stash else {
+		return res.map(|outcome| PullResult {
+			outcome,
+			stashed: None,
+		});
+	};
+
+	match res {
+		Ok(PullOutcome::Conflicts) => Ok(PullResult {
+			outcome: PullOutcome::Conflicts,
+			stashed: Some(stash),
+		}),
+		Ok(outcome) => Ok(PullResult {
+			outcome,
+			stashed: stash_pop(repo_path, stash).err().map(|e| {
+				log::error!("reapplying autostash failed: {e}");
+				stash
+			}),
+		}),
+		Err(e) => {
+			if let Err(pop) = stash_pop(repo_path, stash) {
+				return Err(Error::Generic(format!(
+					"{e}\nreapplying autostash failed ({pop}), your changes are kept in the stash list"
+				)));
+			}
+
+			Err(e)
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::sync::{
+		get_stashes,
+		remotes::{
+			fetch, fetch_target,
+			push::{push_branch, push_branch_to},
+			RemoteTarget,
+		},
+		tests::{repo_clone, repo_init_bare, write_commit_file},
+		utils::get_head,
+		utils::repo_write_file,
+	};
+	use std::fs;
+
+	#[test]
+	fn test_pull_options_config() {
+		let (_r1_dir, repo) = repo_init_bare().unwrap();
+		let (clone_dir, clone) =
+			repo_clone(repo.path().to_str().unwrap()).unwrap();
+		let repo_path: &RepoPath =
+			&clone_dir.path().to_str().unwrap().into();
+
+		assert_eq!(
+			pull_options_config(repo_path).unwrap(),
+			PullOptions::default()
+		);
+
+		let mut config = clone.config().unwrap();
+		config.set_str("pull.ff", "only").unwrap();
+		assert_eq!(
+			pull_options_config(repo_path).unwrap().strategy,
+			PullStrategy::FastForwardOnly
+		);
+
+		config.set_str("pull.rebase", "merges").unwrap();
+		config.set_bool("rebase.autoStash", true).unwrap();
+		assert_eq!(
+			pull_options_config(repo_path).unwrap(),
+			PullOptions {
+				strategy: PullStrategy::Rebase,
+				autostash: true,
+			}
+		);
+	}
+
+	#[test]
+	fn test_pull_autostash() {
+		let (r1_dir, _repo) = repo_init_bare().unwrap();
+
+		let (clone1_dir, clone1) =
+			repo_clone(r1_dir.path().to_str().unwrap()).unwrap();
+		let clone1_path: &RepoPath =
+			&clone1_dir.path().to_str().unwrap().into();
+
+		write_commit_file(
+			&clone1,
+			"test.txt",
+			"1\n2\n3\n4\n5\n",
+			"c1",
+		);
+		push_branch(
+			clone1_path,
+			"origin",
+			"master",
+			false,
+			false,
+			None,
+			None,
+		)
+		.unwrap();
+
+		let (clone2_dir, clone2) =
+			repo_clone(r1_dir.path().to_str().unwrap()).unwrap();
+		let clone2_path: &RepoPath =
+			&clone2_dir.path().to_str().unwrap().into();
+
+		write_commit_file(
+			&clone1,
+			"test.txt",
+			"x\n2\n3\n4\n5\n",
+			"c2",
+		);
+		push_branch(
+			clone1_path,
+			"origin",
+			"master",
+			false,
+			false,
+			None,
+			None,
+		)
+		.unwrap();
+
+		repo_write_file(&clone2, "test.txt", "1\n2\n3\n4\ny\n")
+			.unwrap();
+		fetch(clone2_path, "master", None, None).unwrap();
+
+		// local changes block the checkout
+		assert!(pull_upstream(
+			clone2_path,
+			"master",
+			PullStrategy::FastForwardOnly,
+			false
+		)
+		.is_err());
+
+		let res = pull_upstream(
+			clone2_path,
+			"master",
+			PullStrategy::FastForwardOnly,
+			true,
+		)
+		.unwrap();
+
+		assert_eq!(
+			res,
+			PullResult {
+				outcome: PullOutcome::FastForward,
+				stashed: None,
+			}
+		);
+		assert_eq!(
+			fs::read_to_string(clone2_dir.path().join("test.txt"))
+				.unwrap(),
+			"x\n2\n3\n4\ny\n"
+		);
+		assert!(get_stashes(clone2_path).unwrap().is_empty());
+	}
+
+	#[test]
+	fn test_pull_from_target() {
+		let (r1_dir, _repo) = repo_init_bare().unwrap();
+		let (fork_dir, _fork) = repo_init_bare().unwrap();
+		let fork_url = fork_dir.path().to_str().unwrap();
+
+		let (clone1_dir, clone1) =
+			repo_clone(r1_dir.path().to_str().unwrap()).unwrap();
+		let clone1_path: &RepoPath =
+			&clone1_dir.path().to_str().unwrap().into();
+
+		write_commit_file(&clone1, "test.txt", "1", "c1");
+		push_branch(
+			clone1_path,
+			"origin",
+			"master",
+			false,
+			false,
+			None,
+			None,
+		)
+		.unwrap();
+
+		let (clone2_dir, clone2) =
+			repo_clone(r1_dir.path().to_str().unwrap()).unwrap();
+		let clone2_path: &RepoPath =
+			&clone2_dir.path().to_str().unwrap().into();
+
+		let target = RemoteTarget {
+			remote: String::from("fork"),
+			branch: String::from("feature"),
+			set_upstream: false,
+		};
+
+		clone1.remote("fork", fork_url).unwrap();
+		let c2 = write_commit_file(&clone1, "test.txt", "2", "c2");
+		push_branch_to(
+			clone1_path,
+			"master",
+			&target,
+			false,
+			false,
+			None,
+			None,
+		)
+		.unwrap();
+
+		clone2.remote("fork", fork_url).unwrap();
+		fetch_target(clone2_path, &target, None, None).unwrap();
+
+		let res = pull_from(
+			clone2_path,
+			"master",
+			&target.tracking_ref(),
+			PullStrategy::FastForwardOnly,
+			false,
+		)
+		.unwrap();
+
+		assert_eq!(res.outcome, PullOutcome::FastForward);
+		assert_eq!(get_head(clone2_path).unwrap(), c2);
+	}
+}
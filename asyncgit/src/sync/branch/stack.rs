@@ -0,0 +1,405 @@
+//! stacked branches
+//!
+//! a branch can name a parent branch it is stacked on
+//! (`branch.<name>.stackparent`). we also remember the parent commit
+//! the branch was last based on (`branch.<name>.stackbase`) so that
+//! after the parent got rewritten (e.g. amended) only the commits of
+//! the child itself are replayed on top of the new parent.
+
+use super::get_branch_name_repo;
+use crate::{
+	error::{Error, Result},
+	sync::{
+		commit::signature_allow_undefined_name, repository::repo,
+		status::is_workdir_clean, RepoPath,
+	},
+};
+use git2::{
+	build::CheckoutBuilder, BranchType, ErrorCode, Oid,
+	RebaseOptions, Repository,
+};
+use scopetime::scope_time;
+use std::collections::HashSet;
+
+const CONFIG_STACK_PARENT: &str = "stackparent";
+const CONFIG_STACK_BASE: &str = "stackbase";
+
+fn config_key(branch: &str, key: &str) -> String {
+	format!("branch.{branch}.{key}")
+}
+
+/// returns the branch `branch` is stacked on (if any)
+pub fn get_stack_parent(
+	repo_path: &RepoPath,
+	branch: &str,
+) -> Result<Option<String>> {
+	let repo = repo(repo_path)?;
+
+	get_stack_parent_repo(&repo, branch)
+}
+
+pub(super) fn get_stack_parent_repo(
+	repo: &Repository,
+	branch: &str,
+) -> Result<Option<String>> {
+	let config = repo.config()?;
+
+	Ok(config
+		.get_string(&config_key(branch, CONFIG_STACK_PARENT))
+		.ok()
+		.filter(|parent| !parent.is_empty()))
+}
+
+fn get_stack_base_repo(
+	repo: &Repository,
+	branch: &str,
+) -> Option<Oid> {
+	let config = repo.config().ok()?;
+
+	config
+		.get_string(&config_key(branch, CONFIG_STACK_BASE))
+		.ok()
+		.and_then(|base| Oid::from_str(&base).ok())
+}
+
+fn branch_tip(repo: &Repository, branch: &str) -> Result<Oid> {
+	Ok(repo
+		.find_branch(branch, BranchType::Local)?
+		.get()
+		.peel_to_commit()?
+		.id())
+}
+
+/// stacks `branch` on top of `parent`, `None` removes `branch` from
+/// its stack
+pub fn set_stack_parent(
+	repo_path: &RepoPath,
+	branch: &str,
+	parent: Option<&str>,
+) -> Result<()> {
+	scope_time!("set_stack_parent");
+
+	let repo = repo(repo_path)?;
+	let mut config = repo.config()?;
+
+	let Some(parent) = parent else {
+		for key in [CONFIG_STACK_PARENT, CONFIG_STACK_BASE] {
+			match config.remove(&config_key(branch, key)) {
+				Err(e) if e.code() != ErrorCode::NotFound => {
+					return Err(e.into());
+				}
+				_ => (),
+			}
+		}
+		return Ok(());
+	};
+
+	let mut ancestor = Some(parent.to_string());
+	while let Some(name) = ancestor {
+		if name == branch {
+			return Err(Error::Generic(format!(
+				"stacking '{branch}' on '{parent}' would create a cycle"
+			)));
+		}
+		ancestor = get_stack_parent_repo(&repo, &name)?;
+	}
+
+	let base = repo.merge_base(
+		branch_tip(&repo, parent)?,
+		branch_tip(&repo, branch)?,
+	)?;
+
+	config
+		.set_str(&config_key(branch, CONFIG_STACK_PARENT), parent)?;
+	config.set_str(
+		&config_key(branch, CONFIG_STACK_BASE),
+		&base.to_string(),
+	)?;
+
+	Ok(())
+}
+
+/// returns all local branches directly stacked on `branch`
+pub fn get_stack_children(
+	repo_path: &RepoPath,
+	branch: &str,
+) -> Result<Vec<String>> {
+	let repo = repo(repo_path)?;
+
+	get_stack_children_repo(&repo, branch)
+}
+
+fn get_stack_children_repo(
+	repo: &Repository,
+	branch: &str,
+) -> Result<Vec<String>> {
+	let mut children = Vec::new();
+
+	for b in repo.branches(Some(BranchType::Local))? {
+		let (b, _) = b?;
+		if let Some(name) = b.name()? {
+			if get_stack_parent_repo(repo, name)?.as_deref()
+				== Some(branch)
+			{
+				children.push(name.to_string());
+			}
+		}
+	}
+
+	children.sort();
+
+	Ok(children)
+}
+
+/// returns the whole stack `branch` is part of, starting at its
+/// root and listing every parent before its children
+pub fn get_branch_stack(
+	repo_path: &RepoPath,
+	branch: &str,
+) -> Result<Vec<String>> {
+	scope_time!("get_branch_stack");
+
+	let repo = repo(repo_path)?;
+
+	let mut visited = HashSet::new();
+	let mut root = branch.to_string();
+	visited.insert(root.clone());
+	while let Some(parent) = get_stack_parent_repo(&repo, &root)? {
+		if !visited.insert(parent.clone())
+			|| repo.find_branch(&parent, BranchType::Local).is_err()
+		{
+			break;
+		}
+		root = parent;
+	}
+
+	let mut stack = vec![root];
+	let mut idx = 0;
+	while let Some(name) = stack.get(idx) {
+		for child in get_stack_children_repo(&repo, name)? {
+			if !stack.contains(&child) {
+				stack.push(child);
+			}
+		}
+		idx += 1;
+	}
+
+	Ok(stack)
+}
+
+/// rebases every branch stacked (directly or indirectly) on `branch`
+/// onto the current tip of its parent. stops at the first conflict.
+///
+/// returns the branches that were moved
+pub fn restack_children(
+	repo_path: &RepoPath,
+	branch: &str,
+) -> Result<Vec<String>> {
+	scope_time!("restack_children");
+
+	let repo = repo(repo_path)?;
+	let head = get_branch_name_repo(&repo).ok();
+
+	let mut restacked = Vec::new();
+	let mut pending: Vec<(String, String)> =
+		get_stack_children_repo(&repo, branch)?
+			.into_iter()
+			.map(|child| (branch.to_string(), child))
+			.collect();
+
+	while !pending.is_empty() {
+		let (parent, child) = pending.remove(0);
+
+		let is_head = head.as_deref() == Some(child.as_str());
+		if is_head && !is_workdir_clean(repo_path, None)? {
+			return Err(Error::Generic(format!(
+				"cannot restack checked out branch '{child}': uncommitted changes"
+			)));
+		}
+
+		if restack_branch_repo(&repo, &parent, &child)? {
+			if is_head {
+				repo.checkout_head(Some(
+					CheckoutBuilder::new().force(),
+				))?;
+			}
+			restacked.push(child.clone());
+		}
+
+		pending.extend(
+			get_stack_children_repo(&repo, &child)?
+				.into_iter()
+				.filter(|c| *c != parent)
+				.map(|c| (child.clone(), c)),
+		);
+	}
+
+	Ok(restacked)
+}
+
+/// replays the commits of `child` on top of `parent`, returns `false`
+/// if `child` already was based on the tip of `parent`
+fn restack_branch_repo(
+	repo: &Repository,
+	parent: &str,
+	child: &str,
+) -> Result<bool> {
+	let parent_tip = branch_tip(repo, parent)?;
+	let mut child_ref =
+		repo.find_branch(child, BranchType::Local)?.into_reference();
+	let child_tip = child_ref.peel_to_commit()?.id();
+
+	let base = get_stack_base_repo(repo, child)
+		.filter(|base| {
+			*base == child_tip
+				|| repo
+					.graph_descendant_of(child_tip, *base)
+					.unwrap_or_default()
+		})
+		.map_or_else(|| repo.merge_base(parent_tip, child_tip), Ok)?;
+
+	if base == parent_tip {
+		return Ok(false);
+	}
+
+	let signature = signature_allow_undefined_name(repo)?;
+	let mut options = RebaseOptions::new();
+	options.inmemory(true);
+
+	let mut rebase = repo.rebase(
+		Some(&repo.find_annotated_commit(child_tip)?),
+		Some(&repo.find_annotated_commit(base)?),
+		Some(&repo.find_annotated_commit(parent_tip)?),
+		Some(&mut options),
+	)?;
+
+	let mut new_tip = parent_tip;
+	while let Some(op) = rebase.next() {
+		op?;
+
+		if rebase.inmemory_index()?.has_conflicts() {
+			rebase.abort()?;
+			return Err(Error::Generic(format!(
+				"restacking '{child}' on '{parent}' failed: conflict"
+			)));
+		}
+
+		match rebase.commit(None, &signature, None) {
+			Ok(id) => new_tip = id,
+			// commit became empty because the parent already has it
+			Err(e) if e.code() == ErrorCode::Applied => (),
+			Err(e) => {
+				rebase.abort()?;
+				return Err(e.into());
+			}
+		}
+	}
+
+	rebase.finish(None)?;
+
+	child_ref.set_target(new_tip, &format!("restack on {parent}"))?;
+	repo.config()?.set_str(
+		&config_key(child, CONFIG_STACK_BASE),
+		&parent_tip.to_string(),
+	)?;
+
+	Ok(true)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::sync::{
+		amend, checkout_branch, create_branch,
+		tests::{repo_init, write_commit_file},
+		CommitId,
+	};
+
+	#[test]
+	fn test_branch_stack() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		create_branch(repo_path, "a").unwrap();
+		create_branch(repo_path, "b").unwrap();
+		create_branch(repo_path, "c").unwrap();
+
+		set_stack_parent(repo_path, "b", Some("a")).unwrap();
+		set_stack_parent(repo_path, "c", Some("b")).unwrap();
+
+		assert_eq!(
+			get_stack_parent(repo_path, "b").unwrap().as_deref(),
+			Some("a")
+		);
+		assert_eq!(
+			get_branch_stack(repo_path, "b").unwrap(),
+			vec!["a", "b", "c"]
+		);
+		assert_eq!(
+			get_stack_children(repo_path, "a").unwrap(),
+			["b"]
+		);
+
+		assert!(set_stack_parent(repo_path, "a", Some("c")).is_err());
+
+		set_stack_parent(repo_path, "c", None).unwrap();
+		assert_eq!(get_stack_parent(repo_path, "c").unwrap(), None);
+		assert_eq!(
+			get_branch_stack(repo_path, "a").unwrap(),
+			vec!["a", "b"]
+		);
+	}
+
+	#[test]
+	fn test_restack_after_amend() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		create_branch(repo_path, "a").unwrap();
+		let a1 = write_commit_file(&repo, "a.txt", "a", "a1");
+
+		create_branch(repo_path, "b").unwrap();
+		write_commit_file(&repo, "b.txt", "b", "b1");
+		set_stack_parent(repo_path, "b", Some("a")).unwrap();
+
+		create_branch(repo_path, "c").unwrap();
+		write_commit_file(&repo, "c.txt", "c", "c1");
+		set_stack_parent(repo_path, "c", Some("b")).unwrap();
+
+		checkout_branch(repo_path, "a").unwrap();
+		let a2 = amend(repo_path, a1, "a1 amended").unwrap();
+
+		assert_eq!(
+			restack_children(repo_path, "a").unwrap(),
+			vec!["b", "c"]
+		);
+
+		let tip = |name: &str| -> CommitId {
+			branch_tip(&repo, name).unwrap().into()
+		};
+		let parent_of = |id: CommitId| -> CommitId {
+			repo.find_commit(id.into())
+				.unwrap()
+				.parent_id(0)
+				.unwrap()
+				.into()
+		};
+
+		assert_eq!(parent_of(tip("b")), a2);
+		assert_eq!(parent_of(tip("c")), tip("b"));
+		assert_eq!(
+			repo.find_commit(tip("b").into())
+				.unwrap()
+				.summary()
+				.unwrap(),
+			"b1"
+		);
+
+		// nothing to do a second time
+		assert!(restack_children(repo_path, "a").unwrap().is_empty());
+	}
+}
@@ -0,0 +1,214 @@
+//! upstream patch detection similar to `git cherry`
+
+use super::{
+	branch::get_branch_name_repo, repository::repo,
+	utils::get_head_repo, CommitId, RepoPath,
+};
+use crate::error::Result;
+use git2::{BranchType, Oid, Repository, Sort};
+use scopetime::scope_time;
+use std::collections::{HashMap, HashSet};
+
+/// a commit whose changes (same patch-id) also exist as a different
+/// commit on the other side of a comparison
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DuplicateCommit {
+	/// commit on the first side
+	pub id: CommitId,
+	/// commit with the same changes on the second side
+	pub duplicate_of: CommitId,
+}
+
+/// pairs up the commits only reachable from `a` with the commits
+/// only reachable from `b` that introduce the same changes, e.g. to
+/// spot cherry-picks before picking a commit twice
+pub fn get_duplicate_commits(
+	repo_path: &RepoPath,
+	a: CommitId,
+	b: CommitId,
+) -> Result<Vec<DuplicateCommit>> {
+	scope_time!("get_duplicate_commits");
+
+	let repo = repo(repo_path)?;
+
+	duplicates_repo(&repo, a.get_oid(), b.get_oid())
+}
+
+/// returns the commits reachable from `head` but not from `upstream`
+/// whose changes (same patch-id) already exist in a commit reachable
+/// from `upstream` but not from `head`
+pub fn get_cherry_applied(
+	repo_path: &RepoPath,
+	head: CommitId,
+	upstream: CommitId,
+) -> Result<HashSet<CommitId>> {
+	scope_time!("get_cherry_applied");
+
+	let repo = repo(repo_path)?;
+
+	cherry_applied_repo(&repo, head.get_oid(), upstream.get_oid())
+}
+
+/// same as [`get_cherry_applied`] for `HEAD` against the upstream of
+/// the current branch, empty if there is no upstream configured
+pub fn get_cherry_applied_upstream(
+	repo_path: &RepoPath,
+) -> Result<HashSet<CommitId>> {
+	scope_time!("get_cherry_applied_upstream");
+
+	let repo = repo(repo_path)?;
+
+	let Ok(branch) = get_branch_name_repo(&repo) else {
+		return Ok(HashSet::new());
+	};
+
+	let Ok(upstream) = repo
+		.find_branch(&branch, BranchType::Local)
+		.and_then(|b| b.upstream())
+	else {
+		return Ok(HashSet::new());
+	};
+
+	let upstream = upstream.into_reference().peel_to_commit()?.id();
+
+	cherry_applied_repo(
+		&repo,
+		get_head_repo(&repo)?.get_oid(),
+		upstream,
+	)
+}
+
+fn cherry_applied_repo(
+	repo: &Repository,
+	head: Oid,
+	upstream: Oid,
+) -> Result<HashSet<CommitId>> {
+	Ok(duplicates_repo(repo, head, upstream)?
+		.into_iter()
+		.map(|dup| dup.id)
+		.collect())
+}
+
+fn duplicates_repo(
+	repo: &Repository,
+	a: Oid,
+	b: Oid,
+) -> Result<Vec<DuplicateCommit>> {
+	let mut b_ids = HashMap::new();
+	for id in only_in(repo, b, a)? {
+		if let Some(patch) = patch_id(repo, id)? {
+			// keep the newest commit (first in topological order)
+			b_ids.entry(patch).or_insert(id);
+		}
+	}
+
+	if b_ids.is_empty() {
+		return Ok(Vec::new());
+	}
+
+	let mut res = Vec::new();
+	for id in only_in(repo, a, b)? {
+		if let Some(patch) = patch_id(repo, id)? {
+			if let Some(duplicate_of) = b_ids.get(&patch) {
+				res.push(DuplicateCommit {
+					id: id.into(),
+					duplicate_of: (*duplicate_of).into(),
+				});
+			}
+		}
+	}
+
+	Ok(res)
+}
+
+/// commits reachable from `from` but not from `hide`
+fn only_in(
+	repo: &Repository,
+	from: Oid,
+	hide: Oid,
+) -> Result<Vec<Oid>> {
+	let mut walk = repo.revwalk()?;
+	walk.set_sorting(Sort::TOPOLOGICAL)?;
+	walk.push(from)?;
+	walk.hide(hide)?;
+
+	Ok(walk.collect::<std::result::Result<Vec<_>, _>>()?)
+}
+
+/// patch-id of the changes a commit introduces, `None` for merges
+fn patch_id(repo: &Repository, id: Oid) -> Result<Option<Oid>> {
+	let commit = repo.find_commit(id)?;
+
+	if commit.parent_count() > 1 {
+		return Ok(None);
+	}
+
+	let parent_tree = match commit.parent(0) {
+		Ok(parent) => Some(parent.tree()?),
+		Err(_) => None,
+	};
+
+	let diff = repo.diff_tree_to_tree(
+		parent_tree.as_ref(),
+		Some(&commit.tree()?),
+		None,
+	)?;
+
+	Ok(Some(diff.patchid(None)?))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		checkout_branch, create_branch,
+		tests::{repo_init, write_commit_file},
+	};
+
+	#[test]
+	fn test_cherry_applied() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "base.txt", "base", "base");
+
+		create_branch(repo_path, "feature").unwrap();
+
+		let picked =
+			write_commit_file(&repo, "a.txt", "a", "commit a");
+		let kept = write_commit_file(&repo, "b.txt", "b", "commit b");
+
+		checkout_branch(repo_path, "master").unwrap();
+
+		write_commit_file(&repo, "other.txt", "other", "other");
+		let upstream =
+			write_commit_file(&repo, "a.txt", "a", "picked a");
+
+		let applied =
+			get_cherry_applied(repo_path, kept, upstream).unwrap();
+
+		assert_eq!(applied, HashSet::from([picked]));
+
+		assert_eq!(
+			get_duplicate_commits(repo_path, kept, upstream).unwrap(),
+			vec![DuplicateCommit {
+				id: picked,
+				duplicate_of: upstream,
+			}]
+		);
+		assert_eq!(
+			get_duplicate_commits(repo_path, upstream, kept).unwrap(),
+			vec![DuplicateCommit {
+				id: upstream,
+				duplicate_of: picked,
+			}]
+		);
+
+		// no upstream configured for the current branch
+		assert!(get_cherry_applied_upstream(repo_path)
+			.unwrap()
+			.is_empty());
+	}
+}
@@ -0,0 +1,242 @@
+//! sync git api to remove untracked files, like `git clean`
+
+use super::{repository::repo, utils::work_dir, RepoPath};
+use crate::error::{Error, Result};
+use git2::{Status, StatusOptions};
+use scopetime::scope_time;
+use std::{
+	fs,
+	path::{Component, Path},
+};
+
+/// which kinds of paths [`clean_preview`] lists besides plain
+/// untracked files
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CleanOptions {
+	/// untracked folders as a whole, like `git clean -d`
+	pub directories: bool,
+	/// ignored files too, like `git clean -x`
+	pub ignored: bool,
+}
+
+/// a path `git clean` would remove
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CleanEntry {
+	/// relative to the workdir, folders end with `/`
+	pub path: String,
+	///
+	pub ignored: bool,
+}
+
+impl CleanEntry {
+	///
+	pub fn is_dir(&self) -> bool {
+		self.path.ends_with('/')
+	}
+}
+
+/// lists the paths `git clean` would remove with `options`, sorted.
+/// nested repositories are never listed.
+pub fn clean_preview(
+	repo_path: &RepoPath,
+	options: CleanOptions,
+) -> Result<Vec<CleanEntry>> {
+	scope_time!("clean_preview");
+
+	let repo = repo(repo_path)?;
+	let work_dir = work_dir(&repo)?;
+
+	let mut status_options = StatusOptions::new();
+	status_options
+		.include_untracked(true)
+		.recurse_untracked_dirs(false)
+		.include_ignored(options.ignored)
+		.recurse_ignored_dirs(false)
+		.exclude_submodules(true);
+
+	let statuses = repo.statuses(Some(&mut status_options))?;
+
+	let mut entries = statuses
+		.iter()
+		.filter_map(|entry| {
+			let status = entry.status();
+			let ignored = status.contains(Status::IGNORED);
+
+			if !ignored && !status.contains(Status::WT_NEW) {
+				return None;
+			}
+
+			let entry = CleanEntry {
+				path: entry.path()?.to_string(),
+				ignored,
+			};
+
+			if entry.is_dir()
+				&& (!options.directories
+					|| work_dir
+						.join(&entry.path)
+						.join(".git")
+						.exists())
+			{
+				return None;
+			}
+
+			Some(entry)
+		})
+		.collect::<Vec<_>>();
+
+	entries.sort_by(|a, b| a.path.cmp(&b.path));
+
+	Ok(entries)
+}
+
+/// removes untracked or ignored files and folders from the workdir,
+/// `paths` as listed by [`clean_preview`]
+pub fn clean_paths(
+	repo_path: &RepoPath,
+	paths: &[String],
+) -> Result<()> {
+	scope_time!("clean_paths");
+
+	let repo = repo(repo_path)?;
+	let work_dir = work_dir(&repo)?;
+
+	for path in paths {
+		let relative = path.trim_end_matches('/');
+
+		if relative.is_empty()
+			|| !Path::new(relative)
+				.components()
+				.all(|part| matches!(part, Component::Normal(_)))
+		{
+			return Err(Error::Generic(format!(
+				"invalid path to clean: {path}"
+			)));
+		}
+
+		let full_path = work_dir.join(relative);
+
+		if fs::symlink_metadata(&full_path)?.is_dir() {
+			fs::remove_dir_all(full_path)?;
+		} else {
+			fs::remove_file(full_path)?;
+		}
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		discard_backup_paths, discard_backup_restore,
+		tests::{repo_init, write_commit_file},
+	};
+	use pretty_assertions::assert_eq;
+
+	fn preview_paths(
+		repo_path: &RepoPath,
+		directories: bool,
+		ignored: bool,
+	) -> Vec<String> {
+		clean_preview(
+			repo_path,
+			CleanOptions {
+				directories,
+				ignored,
+			},
+		)
+		.unwrap()
+		.into_iter()
+		.map(|entry| entry.path)
+		.collect()
+	}
+
+	#[test]
+	fn test_preview_options() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(
+			&repo,
+			".gitignore",
+			"*.log\nbuild/\n",
+			"c1",
+		);
+		fs::write(root.join("new.txt"), "new").unwrap();
+		fs::write(root.join("debug.log"), "log").unwrap();
+		fs::create_dir(root.join("dir")).unwrap();
+		fs::write(root.join("dir/a.txt"), "a").unwrap();
+		fs::create_dir(root.join("build")).unwrap();
+		fs::write(root.join("build/out"), "out").unwrap();
+
+		assert_eq!(
+			preview_paths(repo_path, false, false),
+			["new.txt"]
+		);
+		assert_eq!(
+			preview_paths(repo_path, true, false),
+			["dir/", "new.txt"]
+		);
+		assert_eq!(
+			preview_paths(repo_path, false, true),
+			["debug.log", "new.txt"]
+		);
+		assert_eq!(
+			preview_paths(repo_path, true, true),
+			["build/", "debug.log", "dir/", "new.txt"]
+		);
+	}
+
+	#[test]
+	fn test_clean_and_restore() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, ".gitignore", "*.log\n", "c1");
+		fs::write(root.join("debug.log"), "log").unwrap();
+		fs::create_dir(root.join("dir")).unwrap();
+		fs::write(root.join("dir/a.txt"), "a").unwrap();
+		fs::write(root.join("keep.txt"), "keep").unwrap();
+
+		let paths =
+			vec![String::from("debug.log"), String::from("dir/")];
+
+		let backup =
+			discard_backup_paths(repo_path, &paths, "clean").unwrap();
+		clean_paths(repo_path, &paths).unwrap();
+
+		assert_eq!(
+			preview_paths(repo_path, true, true),
+			["keep.txt"]
+		);
+
+		discard_backup_restore(repo_path, backup).unwrap();
+
+		assert_eq!(
+			fs::read_to_string(root.join("debug.log")).unwrap(),
+			"log"
+		);
+		assert_eq!(
+			fs::read_to_string(root.join("dir/a.txt")).unwrap(),
+			"a"
+		);
+	}
+
+	#[test]
+	fn test_clean_rejects_outside_paths() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		assert!(
+			clean_paths(repo_path, &[String::from("../x")]).is_err()
+		);
+	}
+}
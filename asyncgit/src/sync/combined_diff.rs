@@ -0,0 +1,492 @@
+//! combined diff of a merge commit against all its parents
+//! (similar to `git diff --cc`)
+
+use super::{
+	diff::{
+		DiffBudget, DiffLine, DiffLinePosition, DiffLineType,
+		DiffOptions, FileDiff, Hunk,
+	},
+	CommitId,
+};
+use crate::{error::Result, hash};
+use easy_cast::Conv;
+use git2::{Commit, Patch, Repository};
+use std::{ops::Range, path::Path};
+
+/// a line of the combined diff
+struct Row {
+	content: String,
+	/// one marker per parent: `+` added, `-` removed or ` `
+	markers: Vec<u8>,
+	/// `None` for lines removed from (some) parents
+	new_lineno: Option<u32>,
+}
+
+impl Row {
+	fn exists_in_parent(&self, parent: usize) -> bool {
+		if self.new_lineno.is_some() {
+			self.markers[parent] != b'+'
+		} else {
+			self.markers[parent] == b'-'
+		}
+	}
+
+	fn is_change(&self) -> bool {
+		self.markers.iter().any(|m| *m != b' ')
+	}
+}
+
+/// lines of one parent's version that are missing from the result
+/// and the result lines added compared to this parent
+#[derive(Default)]
+struct ParentChanges {
+	/// removed lines, keyed by the result line they precede
+	removed: Vec<(u32, String)>,
+	/// sorted line numbers of added result lines
+	added: Vec<u32>,
+}
+
+pub fn combined_file_diff(
+	repo: &Repository,
+	id: CommitId,
+	path: &str,
+	options: Option<DiffOptions>,
+) -> Result<FileDiff> {
+	let commit = repo.find_commit(id.into())?;
+	let options = options.unwrap_or_default();
+
+	let new_content = blob_content(repo, &commit, path)?;
+	let parents = commit
+		.parents()
+		.map(|parent| blob_content(repo, &parent, path))
+		.collect::<Result<Vec<_>>>()?;
+
+	if new_content.contains(&0)
+		|| parents.iter().any(|p| p.contains(&0))
+	{
+		// binary files have no line based diff
+		return Ok(FileDiff::default());
+	}
+
+	let changes = parents
+		.iter()
+		.map(|old| parent_changes(repo, old, &new_content, options))
+		.collect::<Result<Vec<_>>>()?;
+
+	let rows = combine(&new_content, &changes);
+
+	let mut res = FileDiff {
+		sizes: (
+			parents.first().map_or(0, |p| u64::conv(p.len())),
+			u64::conv(new_content.len()),
+		),
+		..FileDiff::default()
+	};
+	res.size_delta =
+		i64::conv(res.sizes.1).saturating_sub(i64::conv(res.sizes.0));
+
+	let mut budget = DiffBudget::new(options.limit);
+
+	for range in hunk_ranges(&rows, options) {
+		let header = hunk_header(&rows, range.clone(), parents.len());
+
+		let mut lines = vec![DiffLine {
+			content: header.as_str().into(),
+			line_type: DiffLineType::Header,
+			position: DiffLinePosition::default(),
+		}];
+
+		lines.extend(rows[range].iter().map(|row| {
+			DiffLine {
+				content: format!(
+					"{}{}",
+					String::from_utf8_lossy(&row.markers),
+					row.content
+				)
+				.into(),
+				line_type: if row.new_lineno.is_none() {
+					DiffLineType::Delete
+				} else if row.is_change() {
+					DiffLineType::Add
+				} else {
+					DiffLineType::None
+				},
+				position: DiffLinePosition {
+					old_lineno: None,
+					new_lineno: row.new_lineno,
+				},
+			}
+		}));
+
+		if let Some(fitting) =
+			lines.iter().position(|line| !budget.take(line))
+		{
+			lines.truncate(fitting);
+			res.truncated = true;
+		}
+
+		if !lines.is_empty() {
+			res.lines += lines.len();
+			res.hunks.push(Hunk {
+				header_hash: hash(&header),
+				lines,
+			});
+		}
+
+		if res.truncated {
+			break;
+		}
+	}
+
+	Ok(res)
+}
+
+fn blob_content(
+	repo: &Repository,
+	commit: &Commit,
+	path: &str,
+) -> Result<Vec<u8>> {
+	let tree = commit.tree()?;
+
+	Ok(match tree.get_path(Path::new(path)) {
+		Ok(entry) => repo.find_blob(entry.id())?.content().to_vec(),
+		Err(_) => Vec::new(),
+	})
+}
+
+fn parent_changes(
+	repo: &Repository,
+	old: &[u8],
+	new: &[u8],
+	options: DiffOptions,
+) -> Result<ParentChanges> {
+	let mut opts = git2::DiffOptions::new();
+	opts.context_lines(0);
+	options.apply_matching(repo, &mut opts);
+
+	let patch =
+		Patch::from_buffers(old, None, new, None, Some(&mut opts))?;
+
+	let mut res = ParentChanges::default();
+
+	for hunk_idx in 0..patch.num_hunks() {
+		let (hunk, lines) = patch.hunk(hunk_idx)?;
+
+		// removed lines precede the first line of the hunk in the
+		// result (or the following line for pure removals)
+		let before = if hunk.new_lines() == 0 {
+			hunk.new_start() + 1
+		} else {
+			hunk.new_start()
+		};
+
+		for line_idx in 0..lines {
+			let line = patch.line_in_hunk(hunk_idx, line_idx)?;
+
+			match line.origin() {
+				'-' => res.removed.push((
+					before,
+					String::from_utf8_lossy(line.content())
+						.trim_end_matches(['\n', '\r'])
+						.to_string(),
+				)),
+				'+' => {
+					if let Some(lineno) = line.new_lineno() {
+						res.added.push(lineno);
+					}
+				}
+				_ => (),
+			}
+		}
+	}
+
+	Ok(res)
+}
+
+fn combine(new: &[u8], changes: &[ParentChanges]) -> Vec<Row> {
+	let new = String::from_utf8_lossy(new);
+	let new_lines = new.lines().collect::<Vec<_>>();
+	let line_count = u32::conv(new_lines.len());
+
+	let mut rows = Vec::new();
+
+	for lineno in 1..=line_count.saturating_add(1) {
+		// removed lines of all parents before `lineno`, identical
+		// lines removed from several parents are shown only once
+		let first_removed = rows.len();
+		for (parent, change) in changes.iter().enumerate() {
+			let mut search_from = first_removed;
+
+			for (_, content) in
+				change.removed.iter().filter(|(l, _)| *l == lineno)
+			{
+				let existing = rows[search_from..]
+					.iter()
+					.position(|row: &Row| {
+						row.markers[parent] == b' '
+							&& &row.content == content
+					})
+					.map(|pos| search_from + pos);
+
+				if let Some(idx) = existing {
+					rows[idx].markers[parent] = b'-';
+					search_from = idx + 1;
+				} else {
+					let mut markers = vec![b' '; changes.len()];
+					markers[parent] = b'-';
+					rows.push(Row {
+						content: content.clone(),
+						markers,
+						new_lineno: None,
+					});
+					search_from = rows.len();
+				}
+			}
+		}
+
+		if let Some(content) =
+			new_lines.get(usize::conv(lineno.saturating_sub(1)))
+		{
+			rows.push(Row {
+				content: (*content).to_string(),
+				markers: changes
+					.iter()
+					.map(|change| {
+						if change.added.binary_search(&lineno).is_ok()
+						{
+							b'+'
+						} else {
+							b' '
+						}
+					})
+					.collect(),
+				new_lineno: Some(lineno),
+			});
+		}
+	}
+
+	rows
+}
+
+/// groups changed rows with their context into hunks, dropping hunks
+/// that just take over the changes of some of the parents
+fn hunk_ranges(
+	rows: &[Row],
+	options: DiffOptions,
+) -> Vec<Range<usize>> {
+	let context = usize::conv(options.context);
+	let interhunk = usize::conv(options.interhunk_lines);
+
+	let mut ranges: Vec<Range<usize>> = Vec::new();
+
+	for (idx, _) in
+		rows.iter().enumerate().filter(|(_, r)| r.is_change())
+	{
+		let start = idx.saturating_sub(context);
+		let end = (idx + 1 + context).min(rows.len());
+
+		match ranges.last_mut() {
+			Some(last) if start <= last.end + interhunk => {
+				last.end = end;
+			}
+			_ => ranges.push(start..end),
+		}
+	}
+
+	// like git: a hunk is only interesting if its changes are not
+	// all against the same subset of parents (unless that is all)
+	ranges.retain(|range| {
+		let mut masks = rows[range.clone()]
+			.iter()
+			.filter(|row| row.is_change())
+			.map(|row| row.markers.iter().map(|m| *m != b' '));
+
+		masks.next().is_some_and(|first| {
+			let first = first.collect::<Vec<_>>();
+
+			first.iter().all(|changed| *changed)
+				|| masks.any(|mask| !mask.eq(first.iter().copied()))
+		})
+	});
+
+	ranges
+}
+
+fn hunk_header(
+	rows: &[Row],
+	range: Range<usize>,
+	parents: usize,
+) -> String {
+	let range_info = |exists: &dyn Fn(&Row) -> bool| {
+		let start = rows[..range.start]
+			.iter()
+			.filter(|row| exists(row))
+			.count();
+		let len = rows[range.clone()]
+			.iter()
+			.filter(|row| exists(row))
+			.count();
+
+		format!("{},{len}", if len == 0 { start } else { start + 1 })
+	};
+
+	let old = (0..parents)
+		.map(|parent| {
+			format!(
+				"-{}",
+				range_info(&|row| row.exists_in_parent(parent))
+			)
+		})
+		.collect::<Vec<_>>();
+	let new = range_info(&|row| row.new_lineno.is_some());
+
+	let marker = "@".repeat(parents + 1);
+
+	format!("{marker} {} +{new} {marker}", old.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		error::Result,
+		sync::{
+			diff::{get_diff_merge, DiffLineType},
+			get_merge_commit_files,
+			tests::repo_init,
+			CommitId, MergeDiffMode, RepoPath,
+		},
+	};
+	use git2::{Oid, Repository};
+
+	fn commit_files(
+		repo: &Repository,
+		parents: &[Oid],
+		files: &[(&str, &str)],
+	) -> Result<Oid> {
+		let mut builder = repo.treebuilder(None)?;
+		for (path, content) in files {
+			let blob = repo.blob(content.as_bytes())?;
+			builder.insert(path, blob, 0o100_644)?;
+		}
+		let tree = repo.find_tree(builder.write()?)?;
+		let parents = parents
+			.iter()
+			.map(|id| repo.find_commit(*id))
+			.collect::<std::result::Result<Vec<_>, _>>()?;
+		let sig = repo.signature()?;
+
+		Ok(repo.commit(
+			None,
+			&sig,
+			&sig,
+			"msg",
+			&tree,
+			&parents.iter().collect::<Vec<_>>(),
+		)?)
+	}
+
+	fn lines(changes: &[(usize, &str)]) -> String {
+		(1..=20)
+			.map(|line| {
+				changes.iter().find(|(l, _)| *l == line).map_or_else(
+					|| format!("l{line}\n"),
+					|(_, c)| format!("{c}\n"),
+				)
+			})
+			.collect()
+	}
+
+	#[test]
+	fn test_combined_diff() -> Result<()> {
+		let (_td, repo) = repo_init()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let base = commit_files(
+			&repo,
+			&[],
+			&[("f.txt", &lines(&[])), ("g.txt", "g\n")],
+		)?;
+		let p1 = commit_files(
+			&repo,
+			&[base],
+			&[("f.txt", &lines(&[(2, "B1")])), ("g.txt", "G\n")],
+		)?;
+		let p2 = commit_files(
+			&repo,
+			&[base],
+			&[("f.txt", &lines(&[(19, "S2")])), ("g.txt", "g\n")],
+		)?;
+
+		let clean = CommitId::new(commit_files(
+			&repo,
+			&[p1, p2],
+			&[
+				("f.txt", &lines(&[(2, "B1"), (19, "S2")])),
+				("g.txt", "G\n"),
+			],
+		)?);
+
+		let diff = get_diff_merge(
+			repo_path,
+			clean,
+			MergeDiffMode::Combined,
+			"f.txt",
+			None,
+		)?;
+		assert!(diff.hunks.is_empty());
+
+		let evil = CommitId::new(commit_files(
+			&repo,
+			&[p1, p2],
+			&[
+				(
+					"f.txt",
+					&lines(&[(2, "B1"), (10, "X"), (19, "S2")]),
+				),
+				("g.txt", "G\n"),
+			],
+		)?);
+
+		let files = get_merge_commit_files(
+			repo_path,
+			evil,
+			MergeDiffMode::Combined,
+		)?;
+		assert_eq!(files.len(), 1);
+		assert_eq!(&files[0].path, "f.txt");
+
+		let diff = get_diff_merge(
+			repo_path,
+			evil,
+			MergeDiffMode::Combined,
+			"f.txt",
+			None,
+		)?;
+		assert_eq!(diff.hunks.len(), 1);
+
+		let lines = &diff.hunks[0].lines;
+		assert_eq!(
+			lines[0].content.as_ref(),
+			"@@@ -7,7 -7,7 +7,7 @@@"
+		);
+		assert_eq!(lines[4].content.as_ref(), "--l10");
+		assert_eq!(lines[4].line_type, DiffLineType::Delete);
+		assert_eq!(lines[5].content.as_ref(), "++X");
+		assert_eq!(lines[5].line_type, DiffLineType::Add);
+		assert_eq!(lines[6].content.as_ref(), "  l11");
+		assert_eq!(lines[6].line_type, DiffLineType::None);
+
+		let diff = get_diff_merge(
+			repo_path,
+			evil,
+			MergeDiffMode::Parent(1),
+			"f.txt",
+			None,
+		)?;
+		assert_eq!(diff.hunks.len(), 2);
+
+		Ok(())
+	}
+}
@@ -7,16 +7,51 @@ use crate::{
 };
 use git2::{
 	message_prettify, ErrorCode, ObjectType, Repository, Signature,
+	Time,
 };
 use scopetime::scope_time;
 
+/// overrides applied to the commit being amended
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct AmendOptions {
+	/// new author `(name, email)`
+	pub author: Option<(String, String)>,
+	/// new author date as seconds since epoch and offset in minutes
+	pub author_time: Option<(i64, i32)>,
+	/// set the committer date to now
+	pub reset_committer_date: bool,
+}
+
+impl AmendOptions {
+	///
+	pub fn is_empty(&self) -> bool {
+		self == &Self::default()
+	}
+}
+
 ///
 pub fn amend(
 	repo_path: &RepoPath,
 	id: CommitId,
 	msg: &str,
 ) -> Result<CommitId> {
-	scope_time!("amend");
+	commit_amend_with_details(
+		repo_path,
+		id,
+		msg,
+		&AmendOptions::default(),
+	)
+}
+
+/// amends `id` (which has to be `HEAD`) with the staged changes and
+/// `msg`, overriding author and dates as given in `options`
+pub fn commit_amend_with_details(
+	repo_path: &RepoPath,
+	id: CommitId,
+	msg: &str,
+	options: &AmendOptions,
+) -> Result<CommitId> {
+	scope_time!("commit_amend_with_details");
 
 	let repo = repo(repo_path)?;
 	let config = repo.config()?;
@@ -27,23 +62,67 @@ pub fn amend(
 	let tree_id = index.write_tree()?;
 	let tree = repo.find_tree(tree_id)?;
 
-	if config.get_bool("commit.gpgsign").unwrap_or(false) {
-		// HACK: we undo the last commit and create a new one
-		use crate::sync::utils::undo_last_commit;
+	let author = if options.author.is_some()
+		|| options.author_time.is_some()
+	{
+		let original = commit.author();
+		let (name, email) = match &options.author {
+			Some((name, email)) => (name.as_str(), email.as_str()),
+			None => (
+				original.name().unwrap_or_default(),
+				original.email().unwrap_or_default(),
+			),
+		};
+		let time = options.author_time.map_or_else(
+			|| original.when(),
+			|(secs, offset)| Time::new(secs, offset),
+		);
 
-		let head = get_head_repo(&repo)?;
-		if head == commit.id().into() {
-			undo_last_commit(repo_path)?;
-			return self::commit(repo_path, msg);
+		Some(Signature::new(name, email, &time)?)
+	} else {
+		None
+	};
+
+	let committer = if options.reset_committer_date {
+		let original = commit.committer();
+		Some(Signature::now(
+			original.name().unwrap_or_default(),
+			original.email().unwrap_or_default(),
+		)?)
+	} else {
+		None
+	};
+
+	if config.get_bool("commit.gpgsign").unwrap_or(false) {
+		// libgit2 can not amend signed, so the amended commit is
+		// created next to the original and `HEAD` moved onto it
+		if get_head_repo(&repo)? != id {
+			return Err(Error::SignAmendNonLastCommit);
 		}
 
-		return Err(Error::SignAmendNonLastCommit);
+		let parents = commit.parents().collect::<Vec<_>>();
+		let author = author.unwrap_or_else(|| commit.author());
+		let committer =
+			committer.unwrap_or_else(|| commit.committer());
+		let new_id = create_signed_commit(
+			&repo,
+			&config,
+			&author,
+			&committer,
+			msg,
+			&tree,
+			&parents.iter().collect::<Vec<_>>(),
+		)?;
+
+		repo.head()?.set_target(new_id, msg)?;
+
+		return Ok(new_id.into());
 	}
 
 	let new_id = commit.amend(
 		Some("HEAD"),
-		None,
-		None,
+		author.as_ref(),
+		committer.as_ref(),
 		None,
 		Some(msg),
 		Some(&tree),
@@ -52,6 +131,35 @@ pub fn amend(
 	Ok(CommitId::new(new_id))
 }
 
+/// creates a commit signed as configured in `gpg.format`,
+/// no reference is updated
+pub(crate) fn create_signed_commit(
+	repo: &Repository,
+	config: &git2::Config,
+	author: &Signature,
+	committer: &Signature,
+	msg: &str,
+	tree: &git2::Tree,
+	parents: &[&git2::Commit],
+) -> Result<git2::Oid> {
+	let buffer = repo.commit_create_buffer(
+		author, committer, msg, tree, parents,
+	)?;
+
+	let commit = std::str::from_utf8(&buffer).map_err(|_e| {
+		SignError::Shellout("utf8 conversion error".to_string())
+	})?;
+
+	let signer = SignBuilder::from_gitconfig(repo, config)?;
+	let (signature, signature_field) = signer.sign(&buffer)?;
+
+	Ok(repo.commit_signed(
+		commit,
+		&signature,
+		signature_field.as_deref(),
+	)?)
+}
+
 /// Wrap `Repository::signature` to allow unknown user.name.
 ///
 /// See <https://github.com/gitui-org/gitui/issues/79>.
@@ -98,58 +206,46 @@ pub fn commit(repo_path: &RepoPath, msg: &str) -> Result<CommitId> {
 
 	let parents = parents.iter().collect::<Vec<_>>();
 
-	let commit_id = if config
-		.get_bool("commit.gpgsign")
-		.unwrap_or(false)
-	{
-		let buffer = repo.commit_create_buffer(
-			&signature,
-			&signature,
-			msg,
-			&tree,
-			parents.as_slice(),
-		)?;
-
-		let commit = std::str::from_utf8(&buffer).map_err(|_e| {
-			SignError::Shellout("utf8 conversion error".to_string())
-		})?;
-
-		let signer = SignBuilder::from_gitconfig(&repo, &config)?;
-		let (signature, signature_field) = signer.sign(&buffer)?;
-		let commit_id = repo.commit_signed(
-			commit,
-			&signature,
-			signature_field.as_deref(),
-		)?;
-
-		// manually advance to the new commit ID
-		// repo.commit does that on its own, repo.commit_signed does not
-		// if there is no head, read default branch or default to "master"
-		if let Ok(mut head) = repo.head() {
-			head.set_target(commit_id, msg)?;
-		} else {
-			let default_branch_name = config
-				.get_str("init.defaultBranch")
-				.unwrap_or("master");
-			repo.reference(
-				&format!("refs/heads/{default_branch_name}"),
-				commit_id,
-				true,
+	let commit_id =
+		if config.get_bool("commit.gpgsign").unwrap_or(false) {
+			let commit_id = create_signed_commit(
+				&repo,
+				&config,
+				&signature,
+				&signature,
 				msg,
+				&tree,
+				parents.as_slice(),
 			)?;
-		}
 
-		commit_id
-	} else {
-		repo.commit(
-			Some("HEAD"),
-			&signature,
-			&signature,
-			msg,
-			&tree,
-			parents.as_slice(),
-		)?
-	};
+			// manually advance to the new commit ID
+			// repo.commit does that on its own, repo.commit_signed does not
+			// if there is no head, read default branch or default to "master"
+			if let Ok(mut head) = repo.head() {
+				head.set_target(commit_id, msg)?;
+			} else {
+				let default_branch_name = config
+					.get_str("init.defaultBranch")
+					.unwrap_or("master");
+				repo.reference(
+					&format!("refs/heads/{default_branch_name}"),
+					commit_id,
+					true,
+					msg,
+				)?;
+			}
+
+			commit_id
+		} else {
+			repo.commit(
+				Some("HEAD"),
+				&signature,
+				&signature,
+				msg,
+				&tree,
+				parents.as_slice(),
+			)?
+		};
 
 	Ok(commit_id.into())
 }
@@ -174,7 +270,16 @@ pub fn tag_commit(
 
 	let c = if let Some(message) = message {
 		let signature = signature_allow_undefined_name(&repo)?;
-		repo.tag(tag, &target, &signature, message, false)?.into()
+		let config = repo.config()?;
+
+		if config.get_bool("tag.gpgSign").unwrap_or(false) {
+			tag_signed(
+				&repo, &config, &target, tag, &signature, message,
+			)?
+			.into()
+		} else {
+			repo.tag(tag, &target, &signature, message, false)?.into()
+		}
 	} else {
 		repo.tag_lightweight(tag, &target, false)?.into()
 	};
@@ -182,6 +287,50 @@ pub fn tag_commit(
 	Ok(c)
 }
 
+/// creates an annotated tag signed like `git tag -s`, libgit2 has no
+/// api for this so the tag object is assembled manually
+fn tag_signed(
+	repo: &Repository,
+	config: &git2::Config,
+	target: &git2::Object,
+	tag: &str,
+	tagger: &Signature,
+	message: &str,
+) -> Result<git2::Oid> {
+	let refname = format!("refs/tags/{tag}");
+	if !git2::Reference::is_valid_name(&refname) {
+		return Err(Error::Generic(format!(
+			"invalid tag name: {tag}"
+		)));
+	}
+
+	let time = tagger.when();
+	let offset = time.offset_minutes();
+	let mut buffer = format!(
+		"object {}\ntype {}\ntag {tag}\ntagger {} <{}> {} {}{:02}{:02}\n\n{message}",
+		target.id(),
+		target.kind().map_or("commit", |kind| kind.str()),
+		tagger.name().unwrap_or_default(),
+		tagger.email().unwrap_or_default(),
+		time.seconds(),
+		if offset < 0 { '-' } else { '+' },
+		offset.abs() / 60,
+		offset.abs() % 60,
+	);
+	if !buffer.ends_with('\n') {
+		buffer.push('\n');
+	}
+
+	let signer = SignBuilder::from_gitconfig(repo, config)?;
+	let (signature, _) = signer.sign(buffer.as_bytes())?;
+	buffer.push_str(&signature);
+
+	let id = repo.odb()?.write(ObjectType::Tag, buffer.as_bytes())?;
+	repo.reference(&refname, id, false, "tag: signed")?;
+
+	Ok(id)
+}
+
 /// Loads the comment prefix from config & uses it to prettify commit messages
 pub fn commit_message_prettify(
 	repo_path: &RepoPath,
@@ -205,12 +354,18 @@ mod tests {
 	use crate::sync::{
 		commit, get_commit_details, get_commit_files, stage_add_file,
 		tags::get_tags,
-		tests::{get_statuses, repo_init, repo_init_empty},
+		tests::{
+			get_statuses, repo_init, repo_init_empty,
+			setup_ssh_signing,
+		},
 		utils::get_head,
-		LogWalker,
+		verify_signature, CommitId, LogWalker, SignatureValidity,
 	};
-	use commit::{amend, commit_message_prettify, tag_commit};
-	use git2::Repository;
+	use commit::{
+		amend, commit_amend_with_details, commit_message_prettify,
+		tag_commit, AmendOptions,
+	};
+	use git2::{Repository, Signature, Time};
 	use std::{fs::File, io::Write, path::Path};
 
 	fn count_commits(repo: &Repository, max: usize) -> usize {
@@ -308,6 +463,84 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	fn test_amend_author_and_date() -> Result<()> {
+		let (_td, repo) = repo_init_empty()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		File::create(root.join("foo"))?.write_all(b"test1")?;
+		stage_add_file(repo_path, Path::new("foo"))?;
+
+		// committed long ago so resetting the committer date shows
+		let old = Signature::new(
+			"name",
+			"email",
+			&Time::new(500_000_000, 0),
+		)?;
+		let mut index = repo.index()?;
+		index.read(true)?;
+		let tree = repo.find_tree(index.write_tree()?)?;
+		let id: CommitId = repo
+			.commit(
+				Some("HEAD"),
+				&old,
+				&old,
+				"commit msg",
+				&tree,
+				&[],
+			)?
+			.into();
+
+		let options = AmendOptions {
+			author: Some((
+				String::from("someone"),
+				String::from("someone@example.com"),
+			)),
+			author_time: Some((1_000_000_000, 120)),
+			reset_committer_date: false,
+		};
+
+		let new_id = commit_amend_with_details(
+			repo_path, id, "amended", &options,
+		)?;
+
+		let commit = repo.find_commit(new_id.into())?;
+		let author = commit.author();
+		assert_eq!(author.name(), Some("someone"));
+		assert_eq!(author.email(), Some("someone@example.com"));
+		assert_eq!(author.when().seconds(), 1_000_000_000);
+		assert_eq!(author.when().offset_minutes(), 120);
+
+		let original = repo.find_commit(id.into())?;
+		assert_eq!(
+			commit.committer().when(),
+			original.committer().when()
+		);
+
+		let options = AmendOptions {
+			author: None,
+			author_time: None,
+			reset_committer_date: true,
+		};
+
+		let newer_id = commit_amend_with_details(
+			repo_path, new_id, "amended", &options,
+		)?;
+
+		// author stays untouched without overrides
+		let newer = repo.find_commit(newer_id.into())?;
+		assert_eq!(newer.author().name(), Some("someone"));
+		assert_eq!(newer.author().when().seconds(), 1_000_000_000);
+
+		let committer = newer.committer();
+		assert_eq!(committer.name(), Some("name"));
+		assert!(committer.when().seconds() > 500_000_000);
+
+		Ok(())
+	}
+
 	#[test]
 	fn test_tag() -> Result<()> {
 		let file_path = Path::new("foo");
@@ -495,4 +728,84 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[test]
+	fn test_commit_and_tag_signed_with_ssh() -> Result<()> {
+		let (_td, repo) = repo_init()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let _keys = setup_ssh_signing(&repo)?;
+
+		File::create(root.join("foo"))?.write_all(b"a")?;
+		stage_add_file(repo_path, Path::new("foo"))?;
+		let id = commit(repo_path, "signed")?;
+
+		let verification = verify_signature(repo_path, id)?.unwrap();
+		assert_eq!(
+			verification.validity,
+			SignatureValidity::Verified
+		);
+		assert_eq!(verification.signer.as_deref(), Some("email"));
+
+		tag_commit(repo_path, &id, "v1", Some("release"))?;
+
+		let tag =
+			repo.find_reference("refs/tags/v1")?.peel_to_tag()?;
+		assert_eq!(tag.target_id(), id.get_oid());
+		assert!(tag
+			.message()
+			.unwrap()
+			.starts_with("release\n-----BEGIN SSH SIGNATURE-----\n"));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_amend_signed_with_overrides() -> Result<()> {
+		let (_td, repo) = repo_init()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let _keys = setup_ssh_signing(&repo)?;
+
+		File::create(root.join("foo"))?.write_all(b"a")?;
+		stage_add_file(repo_path, Path::new("foo"))?;
+		let id = commit(repo_path, "signed")?;
+
+		let options = AmendOptions {
+			author: Some((
+				String::from("someone"),
+				String::from("someone@example.com"),
+			)),
+			author_time: Some((1_000_000_000, 0)),
+			reset_committer_date: false,
+		};
+		let new_id = commit_amend_with_details(
+			repo_path, id, "amended", &options,
+		)?;
+
+		assert_eq!(get_head(repo_path)?, new_id);
+		assert_eq!(count_commits(&repo, 10), 2);
+
+		let commit = repo.find_commit(new_id.into())?;
+		assert_eq!(commit.author().name(), Some("someone"));
+		assert_eq!(commit.author().when().seconds(), 1_000_000_000);
+		assert_eq!(commit.summary(), Some("amended"));
+		assert_eq!(
+			commit.parent_ids().collect::<Vec<_>>(),
+			repo.find_commit(id.into())?
+				.parent_ids()
+				.collect::<Vec<_>>()
+		);
+
+		assert_eq!(
+			verify_signature(repo_path, new_id)?.unwrap().validity,
+			SignatureValidity::Verified
+		);
+
+		Ok(())
+	}
 }
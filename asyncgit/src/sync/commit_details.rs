@@ -1,6 +1,11 @@
-use super::{commits_info::get_message, CommitId, RepoPath};
+use super::{
+	commits_info::get_message, notes::note_of_commit, CommitId,
+	RepoPath,
+};
 use crate::{error::Result, sync::repository::repo};
-use git2::Signature;
+use git2::{
+	DescribeFormatOptions, DescribeOptions, ErrorClass, Signature,
+};
 use scopetime::scope_time;
 
 ///
@@ -26,13 +31,66 @@ impl CommitSignature {
 	}
 }
 
+/// `Key: value` line at the end of a commit message, e.g.
+/// `Signed-off-by: Name <email>`
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CommitTrailer {
+	///
+	pub key: String,
+	/// continuation lines are kept (indented) in here
+	pub value: String,
+}
+
+impl CommitTrailer {
+	fn parse(line: &str) -> Option<Self> {
+		let (key, value) = line.split_once(':')?;
+
+		let valid_key = !key.is_empty()
+			&& key
+				.chars()
+				.all(|c| c.is_ascii_alphanumeric() || c == '-');
+
+		valid_key.then(|| Self {
+			key: key.to_string(),
+			value: value.trim().to_string(),
+		})
+	}
+}
+
 ///
 #[derive(Default, Clone)]
 pub struct CommitMessage {
 	/// first line
 	pub subject: String,
-	/// remaining lines if more than one
+	/// remaining lines if more than one (without trailers)
 	pub body: Option<String>,
+	/// trailers of the last paragraph
+	pub trailers: Vec<CommitTrailer>,
+}
+
+/// parses the last paragraph of `body` as trailers, returns the
+/// number of lines before it (the trailers need to be separated from
+/// the subject by at least one empty line)
+fn parse_trailers(
+	body: &[String],
+) -> Option<(usize, Vec<CommitTrailer>)> {
+	let end = body.iter().rposition(|l| !l.trim().is_empty())? + 1;
+	let start =
+		body[..end].iter().rposition(|l| l.trim().is_empty())? + 1;
+
+	let mut trailers: Vec<CommitTrailer> = Vec::new();
+
+	for line in &body[start..end] {
+		if line.starts_with(char::is_whitespace) {
+			let last = trailers.last_mut()?;
+			last.value.push('\n');
+			last.value.push_str(line);
+		} else {
+			trailers.push(CommitTrailer::parse(line)?);
+		}
+	}
+
+	Some((start, trailers))
 }
 
 impl CommitMessage {
@@ -44,9 +102,21 @@ impl CommitMessage {
 			std::string::ToString::to_string,
 		);
 
-		let body: Vec<String> =
+		let mut body: Vec<String> =
 			lines.map(std::string::ToString::to_string).collect();
 
+		let trailers = match parse_trailers(&body) {
+			Some((start, trailers)) => {
+				body.truncate(start);
+				while body.last().is_some_and(|l| l.trim().is_empty())
+				{
+					body.pop();
+				}
+				trailers
+			}
+			None => Vec::new(),
+		};
+
 		Self {
 			subject,
 			body: if body.is_empty() {
@@ -54,16 +124,29 @@ impl CommitMessage {
 			} else {
 				Some(body.join("\n"))
 			},
+			trailers,
 		}
 	}
 
 	///
 	pub fn combine(self) -> String {
-		if let Some(body) = self.body {
+		let mut msg = if let Some(body) = self.body {
 			format!("{}\n{body}", self.subject)
 		} else {
 			self.subject
+		};
+
+		if !self.trailers.is_empty() {
+			msg.push('\n');
+			for trailer in self.trailers {
+				msg.push('\n');
+				msg.push_str(&trailer.key);
+				msg.push_str(": ");
+				msg.push_str(&trailer.value);
+			}
 		}
+
+		msg
 	}
 }
 
@@ -78,6 +161,8 @@ pub struct CommitDetails {
 	pub message: Option<CommitMessage>,
 	///
 	pub hash: String,
+	/// note attached to the commit in the notes ref
+	pub note: Option<String>,
 }
 
 impl CommitDetails {
@@ -156,18 +241,50 @@ pub fn get_commit_details(
 		committer,
 		message: Some(msg),
 		hash: id.to_string(),
+		note: note_of_commit(&repo, id)?,
 	};
 
 	Ok(details)
 }
 
+/// `git describe --tags` of a commit: the nearest tag reachable from
+/// it plus the distance, `None` if no tag is reachable
+pub fn describe_commit(
+	repo_path: &RepoPath,
+	id: CommitId,
+) -> Result<Option<String>> {
+	scope_time!("describe_commit");
+
+	let repo = repo(repo_path)?;
+	let commit = repo.find_commit(id.into())?;
+
+	let describe = match commit
+		.as_object()
+		.describe(DescribeOptions::new().describe_tags())
+	{
+		Ok(describe) => describe,
+		// libgit2 reports "nothing to describe" as a describe error
+		Err(e) if e.class() == ErrorClass::Describe => {
+			return Ok(None)
+		}
+		Err(e) => return Err(e.into()),
+	};
+
+	Ok(Some(describe.format(Some(&DescribeFormatOptions::new()))?))
+}
+
 #[cfg(test)]
 mod tests {
-	use super::{get_commit_details, CommitMessage};
+	use super::{
+		describe_commit, get_commit_details, CommitMessage,
+		CommitTrailer,
+	};
 	use crate::{
 		error::Result,
 		sync::{
-			commit, stage_add_file, tests::repo_init_empty, RepoPath,
+			commit, stage_add_file, tag_commit,
+			tests::{repo_init_empty, write_commit_file},
+			RepoPath,
 		},
 	};
 	use std::{fs::File, io::Write, path::Path};
@@ -216,4 +333,74 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[test]
+	fn test_commit_message_trailers() {
+		let raw = "foo\n\nbody\n\nSigned-off-by: A <a@b.c>\nFixes: #1\n  and #2\nCo-authored-by: B <b@c.d>";
+		let msg = CommitMessage::from(raw);
+
+		assert_eq!(msg.body, Some(String::from("\nbody")));
+		assert_eq!(
+			msg.trailers,
+			vec![
+				CommitTrailer {
+					key: String::from("Signed-off-by"),
+					value: String::from("A <a@b.c>"),
+				},
+				CommitTrailer {
+					key: String::from("Fixes"),
+					value: String::from("#1\n  and #2"),
+				},
+				CommitTrailer {
+					key: String::from("Co-authored-by"),
+					value: String::from("B <b@c.d>"),
+				},
+			]
+		);
+		assert_eq!(msg.combine(), raw);
+
+		let msg = CommitMessage::from("foo\n\nReviewed-by: A");
+		assert_eq!(msg.body, None);
+		assert_eq!(msg.trailers.len(), 1);
+		assert_eq!(msg.combine(), "foo\n\nReviewed-by: A");
+
+		// not separated from the subject
+		let msg = CommitMessage::from("foo\nReviewed-by: A");
+		assert!(msg.trailers.is_empty());
+
+		// not all lines are trailers
+		let msg = CommitMessage::from("foo\n\nsome text\nFixes: #1");
+		assert!(msg.trailers.is_empty());
+		assert_eq!(
+			msg.body,
+			Some(String::from("\nsome text\nFixes: #1"))
+		);
+	}
+
+	#[test]
+	fn test_describe_commit() -> Result<()> {
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 = write_commit_file(&repo, "a.txt", "a", "commit1");
+
+		assert_eq!(describe_commit(repo_path, c1)?, None);
+
+		tag_commit(repo_path, &c1, "v1.0", None)?;
+
+		let c2 = write_commit_file(&repo, "b.txt", "b", "commit2");
+
+		assert_eq!(
+			describe_commit(repo_path, c1)?,
+			Some(String::from("v1.0"))
+		);
+		assert_eq!(
+			describe_commit(repo_path, c2)?,
+			Some(format!("v1.0-1-g{}", c2.get_short_string()))
+		);
+
+		Ok(())
+	}
 }
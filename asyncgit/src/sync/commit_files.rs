@@ -1,12 +1,14 @@
 //! Functions for getting infos about files in commits
 
-use super::{diff::DiffOptions, CommitId, RepoPath};
+use super::{
+	diff::DiffOptions, renames::diff_trees, CommitId, RepoPath,
+};
 use crate::{
 	error::Result,
 	sync::{get_stashes, repository::repo},
 	StatusItem, StatusItemType,
 };
-use git2::{Diff, Repository};
+use git2::{Delta, Diff, Repository};
 use scopetime::scope_time;
 use std::collections::HashSet;
 
@@ -19,6 +21,33 @@ pub struct OldNew<T> {
 	pub new: T,
 }
 
+/// how to diff a merge commit against its parents
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum MergeDiffMode {
+	/// diff against the parent at the given (0-based) index
+	Parent(usize),
+	/// combined diff against all parents (like `git show --cc`),
+	/// only listing changes that differ from every parent
+	Combined,
+}
+
+impl Default for MergeDiffMode {
+	fn default() -> Self {
+		Self::Parent(0)
+	}
+}
+
+/// number of parents of commit `id`
+pub fn commit_parent_count(
+	repo_path: &RepoPath,
+	id: CommitId,
+) -> Result<usize> {
+	let repo = repo(repo_path)?;
+	let commit = repo.find_commit(id.into())?;
+
+	Ok(commit.parent_count())
+}
+
 /// Sort two commits.
 pub fn sort_commits(
 	repo: &Repository,
@@ -67,8 +96,50 @@ pub fn get_commit_files(
 		)?
 	};
 
-	let res = diff
-		.deltas()
+	Ok(diff_to_status_items(&diff))
+}
+
+/// get all files of a merge commit that are part of the diff
+/// selected by `mode`
+pub fn get_merge_commit_files(
+	repo_path: &RepoPath,
+	id: CommitId,
+	mode: MergeDiffMode,
+) -> Result<Vec<StatusItem>> {
+	scope_time!("get_merge_commit_files");
+
+	let repo = repo(repo_path)?;
+
+	match mode {
+		MergeDiffMode::Parent(parent) => Ok(diff_to_status_items(
+			&get_commit_parent_diff(&repo, id, parent, None, None)?,
+		)),
+		MergeDiffMode::Combined => {
+			let commit = repo.find_commit(id.into())?;
+
+			let mut res = diff_to_status_items(
+				&get_commit_parent_diff(&repo, id, 0, None, None)?,
+			);
+
+			for parent in 1..commit.parent_count() {
+				let paths: HashSet<String> =
+					diff_to_status_items(&get_commit_parent_diff(
+						&repo, id, parent, None, None,
+					)?)
+					.into_iter()
+					.map(|item| item.path)
+					.collect();
+
+				res.retain(|item| paths.contains(&item.path));
+			}
+
+			Ok(res)
+		}
+	}
+}
+
+pub(super) fn diff_to_status_items(diff: &Diff) -> Vec<StatusItem> {
+	diff.deltas()
 		.map(|delta| {
 			let status = StatusItemType::from(delta.status());
 
@@ -79,11 +150,21 @@ pub fn get_commit_files(
 					.map(|p| p.to_str().unwrap_or("").to_string())
 					.unwrap_or_default(),
 				status,
+				old_path: matches!(
+					delta.status(),
+					Delta::Renamed | Delta::Copied
+				)
+				.then(|| {
+					delta
+						.old_file()
+						.path()
+						.and_then(|p| p.to_str())
+						.map(String::from)
+				})
+				.flatten(),
 			}
 		})
-		.collect::<Vec<_>>();
-
-	Ok(res)
+		.collect::<Vec<_>>()
 }
 
 /// get diff of two arbitrary commits
@@ -107,18 +188,44 @@ pub fn get_compare_commits_diff(
 
 	let mut opts = git2::DiffOptions::new();
 	if let Some(options) = options {
-		opts.context_lines(options.context);
-		opts.ignore_whitespace(options.ignore_whitespace);
-		opts.interhunk_lines(options.interhunk_lines);
-	}
-	if let Some(p) = &pathspec {
-		opts.pathspec(p.clone());
+		options.apply_to(repo, &mut opts);
 	}
 
-	let diff: Diff<'_> = repo.diff_tree_to_tree(
+	let diff: Diff<'_> = diff_trees(
+		repo,
 		Some(&trees.old),
 		Some(&trees.new),
-		Some(&mut opts),
+		pathspec.as_deref(),
+		&mut opts,
+	)?;
+
+	Ok(diff)
+}
+
+/// get diff of a commit to its parent at index `parent`
+pub(crate) fn get_commit_parent_diff<'a>(
+	repo: &'a Repository,
+	id: CommitId,
+	parent: usize,
+	pathspec: Option<&str>,
+	options: Option<DiffOptions>,
+) -> Result<Diff<'a>> {
+	let commit = repo.find_commit(id.into())?;
+	let commit_tree = commit.tree()?;
+	let parent_tree = commit.parent(parent)?.tree()?;
+
+	let mut opts = git2::DiffOptions::new();
+	if let Some(options) = options {
+		options.apply_to(repo, &mut opts);
+	}
+	opts.show_binary(true);
+
+	let diff = diff_trees(
+		repo,
+		Some(&parent_tree),
+		Some(&commit_tree),
+		pathspec,
+		&mut opts,
 	)?;
 
 	Ok(diff)
@@ -147,19 +254,16 @@ pub(crate) fn get_commit_diff<'a>(
 
 	let mut opts = git2::DiffOptions::new();
 	if let Some(options) = options {
-		opts.context_lines(options.context);
-		opts.ignore_whitespace(options.ignore_whitespace);
-		opts.interhunk_lines(options.interhunk_lines);
-	}
-	if let Some(p) = &pathspec {
-		opts.pathspec(p.clone());
+		options.apply_to(repo, &mut opts);
 	}
 	opts.show_binary(true);
 
-	let mut diff = repo.diff_tree_to_tree(
+	let mut diff = diff_trees(
+		repo,
 		parent.as_ref(),
 		Some(&commit_tree),
-		Some(&mut opts),
+		pathspec.as_deref(),
+		&mut opts,
 	)?;
 
 	if stashes.is_some_and(|stashes| stashes.contains(&id)) {
@@ -227,7 +331,7 @@ mod tests {
 		File::create(root.join(file_path))?
 			.write_all(b"test file1 content")?;
 
-		let id = stash_save(repo_path, None, true, false)?;
+		let id = stash_save(repo_path, None, true, false, false)?;
 
 		let diff = get_commit_files(repo_path, id, None)?;
 
@@ -256,7 +360,7 @@ mod tests {
 
 		assert_eq!(get_statuses(repo_path), (2, 0));
 
-		let id = stash_save(repo_path, None, true, false)?;
+		let id = stash_save(repo_path, None, true, false, false)?;
 
 		let diff = get_commit_files(repo_path, id, None)?;
 
@@ -5,7 +5,8 @@ use super::{
 use crate::error::Result;
 use bitflags::bitflags;
 use fuzzy_matcher::FuzzyMatcher;
-use git2::{Diff, Repository};
+use git2::{Diff, DiffFile, Patch, Repository};
+use regex::{Regex, RegexBuilder};
 use std::sync::Arc;
 
 ///
@@ -46,12 +47,16 @@ bitflags! {
 		const FILENAMES = 1 << 2;
 		///
 		const AUTHORS = 1 << 3;
+		/// changes to the number of occurrences in the files of the
+		/// commit, like `git log -S`
+		const DIFF_OCCURRENCES = 1 << 4;
+		/// added or removed lines matching the pattern as a regex,
+		/// like `git log -G`
+		const DIFF_REGEX = 1 << 5;
 		//TODO:
-		// const COMMIT_HASHES = 1 << 3;
+		// const COMMIT_HASHES = 1 << 6;
 		// ///
-		// const DATES = 1 << 4;
-		// ///
-		// const DIFFS = 1 << 5;
+		// const DATES = 1 << 7;
 	}
 }
 
@@ -89,6 +94,22 @@ pub struct LogFilterSearchOptions {
 	pub options: SearchOptions,
 }
 
+impl LogFilterSearchOptions {
+	/// `false` if searching diffs by regex with an invalid one
+	pub fn is_valid(&self) -> bool {
+		!self.fields.contains(SearchFields::DIFF_REGEX)
+			|| self.regex().is_ok()
+	}
+
+	fn regex(&self) -> std::result::Result<Regex, regex::Error> {
+		RegexBuilder::new(&self.search_pattern)
+			.case_insensitive(
+				!self.options.contains(SearchOptions::CASE_SENSITIVE),
+			)
+			.build()
+	}
+}
+
 ///
 #[derive(Default)]
 pub struct LogFilterSearch {
@@ -96,12 +117,19 @@ pub struct LogFilterSearch {
 	pub matcher: fuzzy_matcher::skim::SkimMatcherV2,
 	///
 	pub options: LogFilterSearchOptions,
+	///
+	pub regex: Option<Regex>,
 }
 
 impl LogFilterSearch {
 	///
 	pub fn new(options: LogFilterSearchOptions) -> Self {
 		let mut options = options;
+		let regex = options
+			.fields
+			.contains(SearchFields::DIFF_REGEX)
+			.then(|| options.regex().ok())
+			.flatten();
 		if !options.options.contains(SearchOptions::CASE_SENSITIVE) {
 			options.search_pattern =
 				options.search_pattern.to_lowercase();
@@ -109,6 +137,63 @@ impl LogFilterSearch {
 		Self {
 			matcher: fuzzy_matcher::skim::SkimMatcherV2::default(),
 			options,
+			regex,
+		}
+	}
+
+	/// any file with a different number of occurrences before and
+	/// after the commit
+	fn match_diff_occurrences(
+		&self,
+		repo: &Repository,
+		diff: &Diff<'_>,
+	) -> bool {
+		let occurrences = |file: DiffFile<'_>| {
+			if file.id().is_zero() {
+				return 0;
+			}
+
+			repo.find_blob(file.id())
+				.ok()
+				.filter(|blob| !blob.is_binary())
+				.map_or(0, |blob| {
+					self.count_text(&String::from_utf8_lossy(
+						blob.content(),
+					))
+				})
+		};
+
+		diff.deltas().any(|delta| {
+			occurrences(delta.old_file())
+				!= occurrences(delta.new_file())
+		})
+	}
+
+	/// any added or removed line matching the regex
+	fn match_diff_regex(&self, diff: &Diff<'_>) -> bool {
+		let Some(regex) = &self.regex else {
+			return false;
+		};
+
+		(0..diff.deltas().len()).any(|idx| {
+			Patch::from_diff(diff, idx)
+				.ok()
+				.flatten()
+				.is_some_and(|patch| patch_matches(&patch, regex))
+		})
+	}
+
+	fn count_text(&self, text: &str) -> usize {
+		let pattern = self.options.search_pattern.as_str();
+
+		if self
+			.options
+			.options
+			.contains(SearchOptions::CASE_SENSITIVE)
+		{
+			text.matches(pattern).count()
+		} else {
+			text.to_lowercase().matches(pattern).count()
 		}
 	}
 
@@ -154,6 +239,23 @@ impl LogFilterSearch {
 	}
 }
 
+fn patch_matches(patch: &Patch<'_>, regex: &Regex) -> bool {
+	(0..patch.num_hunks()).any(|hunk| {
+		let lines = patch.num_lines_in_hunk(hunk).unwrap_or_default();
+
+		(0..lines).any(|line| {
+			patch.line_in_hunk(hunk, line).is_ok_and(|line| {
+				let content = String::from_utf8_lossy(line.content());
+
+				matches!(line.origin(), '+' | '-')
+					&& regex.is_match(
+						content.trim_end_matches(['\r', '\n']),
+					)
+			})
+		})
+	})
+}
+
 ///
 pub fn filter_commit_by_search(
 	filter: LogFilterSearch,
@@ -198,6 +300,32 @@ pub fn filter_commit_by_search(
 				.flatten()
 				.is_some_and(|diff| filter.match_diff(&diff));
 
+			let diff_fields = SearchFields::DIFF_OCCURRENCES
+				| SearchFields::DIFF_REGEX;
+			let diff_match = filter
+				.options
+				.fields
+				.intersects(diff_fields)
+				.then(|| {
+					get_commit_diff(
+						repo, *commit_id, None, None, None,
+					)
+					.ok()
+				})
+				.flatten()
+				.is_some_and(|diff| {
+					(filter
+						.options
+						.fields
+						.contains(SearchFields::DIFF_OCCURRENCES)
+						&& filter.match_diff_occurrences(repo, &diff))
+						|| (filter
+							.options
+							.fields
+							.contains(SearchFields::DIFF_REGEX)
+							&& filter.match_diff_regex(&diff))
+				});
+
 			let authors_match = filter
 				.options
 				.fields
@@ -218,6 +346,7 @@ pub fn filter_commit_by_search(
 			Ok(msg_summary_match
 				|| msg_body_match
 				|| file_match
+				|| diff_match
 				|| authors_match)
 		},
 	))
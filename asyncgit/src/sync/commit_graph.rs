@@ -0,0 +1,123 @@
+//! git's commit-graph file: parents, generation numbers and commit
+//! dates of all commits in one file, so walking the history (revlog,
+//! ahead/behind) does not have to parse every single commit object
+
+use super::{repository::repo, RepoPath};
+use crate::error::{Error, Result};
+use git2::Repository;
+use scopetime::scope_time;
+use std::{fs, path::Path, process::Command, time::SystemTime};
+
+/// `gitui.commitGraph`: set to `false` to never write the
+/// commit-graph file
+pub const CONFIG_COMMIT_GRAPH: &str = "gitui.commitGraph";
+
+fn modified(path: &Path) -> Option<SystemTime> {
+	fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+/// last write of the single file or the chain of split files
+fn graph_modified(repo: &Repository) -> Option<SystemTime> {
+	let info = repo.commondir().join("objects").join("info");
+
+	modified(&info.join("commit-graphs").join("commit-graph-chain"))
+		.or_else(|| modified(&info.join("commit-graph")))
+}
+
+/// last time commits got added: committing, fetching or updating
+/// packed refs
+fn history_modified(repo: &Repository) -> Option<SystemTime> {
+	let common = repo.commondir();
+
+	[
+		repo.path().join("logs").join("HEAD"),
+		common.join("FETCH_HEAD"),
+		common.join("packed-refs"),
+	]
+	.iter()
+	.filter_map(|path| modified(path))
+	.max()
+}
+
+/// `true` if the commit-graph is missing or older than the newest
+/// commits, `false` as well if it is disabled via `gitui.commitGraph`
+/// or can't be written for shallow clones
+pub fn commit_graph_needs_update(
+	repo_path: &RepoPath,
+) -> Result<bool> {
+	let repo = repo(repo_path)?;
+
+	if repo.is_shallow()
+		|| !repo
+			.config()?
+			.get_bool(CONFIG_COMMIT_GRAPH)
+			.unwrap_or(true)
+	{
+		return Ok(false);
+	}
+
+	Ok(match (graph_modified(&repo), history_modified(&repo)) {
+		(None, _) => true,
+		(Some(graph), Some(history)) => history > graph,
+		(Some(_), None) => false,
+	})
+}
+
+/// adds the commits not in the commit-graph yet, like
+/// `git commit-graph write --reachable --split`
+pub fn write_commit_graph(repo_path: &RepoPath) -> Result<()> {
+	scope_time!("write_commit_graph");
+
+	let repo = repo(repo_path)?;
+
+	let output = Command::new("git")
+		.args(["commit-graph", "write", "--reachable", "--split"])
+		.env("GIT_DIR", repo.commondir())
+		.output()?;
+
+	if output.status.success() {
+		return Ok(());
+	}
+
+	Err(Error::Generic(
+		String::from_utf8_lossy(&output.stderr).trim().to_string(),
+	))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::tests::{repo_init, write_commit_file};
+
+	#[test]
+	fn test_commit_graph() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "c1");
+		write_commit_file(&repo, "a.txt", "b", "c2");
+
+		assert!(commit_graph_needs_update(repo_path).unwrap());
+
+		write_commit_graph(repo_path).unwrap();
+
+		assert!(graph_modified(&repo).is_some());
+		assert!(!commit_graph_needs_update(repo_path).unwrap());
+
+		repo.config()
+			.unwrap()
+			.set_bool(CONFIG_COMMIT_GRAPH, false)
+			.unwrap();
+		fs::remove_dir_all(
+			repo.path()
+				.join("objects")
+				.join("info")
+				.join("commit-graphs"),
+		)
+		.unwrap();
+
+		assert!(!commit_graph_needs_update(repo_path).unwrap());
+	}
+}
@@ -0,0 +1,416 @@
+//! commit message linting
+//!
+//! checks a commit message before the commit is created, configured
+//! via:
+//! * `gitui.commitLint.subjectLength`: max subject length, `0`
+//!   disables the check (default: 50)
+//! * `gitui.commitLint.imperative`: warn if the subject does not look
+//!   like it is written in imperative mood (default: false)
+//! * `gitui.commitLint.conventional`: require the subject to follow
+//!   [Conventional Commits](https://www.conventionalcommits.org)
+//!   (default: false)
+//! * `gitui.commitLint.command`: shell command (e.g. `npx commitlint`)
+//!   getting the message on stdin, failing on an invalid message
+
+use super::{repository::repo, utils::work_dir, RepoPath};
+use crate::error::{Error, Result};
+use scopetime::scope_time;
+use std::{
+	io::Write,
+	process::{Command, Stdio},
+};
+
+/// config key of the max subject length
+pub const CONFIG_LINT_SUBJECT_LENGTH: &str =
+	"gitui.commitLint.subjectLength";
+/// config key enabling the imperative mood heuristic
+pub const CONFIG_LINT_IMPERATIVE: &str =
+	"gitui.commitLint.imperative";
+/// config key enabling the conventional commits check
+pub const CONFIG_LINT_CONVENTIONAL: &str =
+	"gitui.commitLint.conventional";
+/// config key of the external lint command
+pub const CONFIG_LINT_COMMAND: &str = "gitui.commitLint.command";
+
+const DEFAULT_SUBJECT_LENGTH: usize = 50;
+
+// verbs ending like past tense or third person but being imperative
+const IMPERATIVE_EXCEPTIONS: &[&str] = &[
+	"bias", "embed", "exceed", "feed", "focus", "need", "proceed",
+	"seed", "speed", "succeed",
+];
+
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LintSeverity {
+	/// shown but does not prevent the commit
+	Warning,
+	/// prevents the commit (unless verification is turned off)
+	Error,
+}
+
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintProblem {
+	///
+	pub severity: LintSeverity,
+	///
+	pub message: String,
+}
+
+impl LintProblem {
+	const fn warning(message: String) -> Self {
+		Self {
+			severity: LintSeverity::Warning,
+			message,
+		}
+	}
+
+	const fn error(message: String) -> Self {
+		Self {
+			severity: LintSeverity::Error,
+			message,
+		}
+	}
+}
+
+/// the configured lint rules
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitLintConfig {
+	/// `0` disables the check
+	pub subject_length: usize,
+	///
+	pub imperative: bool,
+	///
+	pub conventional: bool,
+	///
+	pub command: Option<String>,
+}
+
+impl Default for CommitLintConfig {
+	fn default() -> Self {
+		Self {
+			subject_length: DEFAULT_SUBJECT_LENGTH,
+			imperative: false,
+			conventional: false,
+			command: None,
+		}
+	}
+}
+
+impl CommitLintConfig {
+	/// read the lint rules from the git config
+	pub fn load(repo_path: &RepoPath) -> Result<Self> {
+		scope_time!("CommitLintConfig::load");
+
+		let repo = repo(repo_path)?;
+		let config = repo.config()?;
+		let default = Self::default();
+
+		Ok(Self {
+			subject_length: config
+				.get_i64(CONFIG_LINT_SUBJECT_LENGTH)
+				.ok()
+				.and_then(|len| usize::try_from(len).ok())
+				.unwrap_or(default.subject_length),
+			imperative: config
+				.get_bool(CONFIG_LINT_IMPERATIVE)
+				.unwrap_or(default.imperative),
+			conventional: config
+				.get_bool(CONFIG_LINT_CONVENTIONAL)
+				.unwrap_or(default.conventional),
+			command: config
+				.get_string(CONFIG_LINT_COMMAND)
+				.ok()
+				.filter(|cmd| !cmd.trim().is_empty()),
+		})
+	}
+}
+
+/// first line that is not a comment
+fn subject(msg: &str) -> &str {
+	msg.lines()
+		.find(|line| !line.starts_with('#'))
+		.unwrap_or_default()
+		.trim_end()
+}
+
+/// splits `type(scope)!: description` into its type and description
+fn parse_conventional(subject: &str) -> Option<(&str, &str)> {
+	let (header, description) = subject.split_once(": ")?;
+	let header = header.strip_suffix('!').unwrap_or(header);
+
+	let typ = match header.split_once('(') {
+		Some((typ, scope)) => {
+			let scope = scope.strip_suffix(')')?;
+			if scope.is_empty() || scope.contains(['(', ')']) {
+				return None;
+			}
+			typ
+		}
+		None => header,
+	};
+
+	let valid_type = !typ.is_empty()
+		&& typ.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+
+	(valid_type && !description.trim().is_empty())
+		.then_some((typ, description))
+}
+
+fn looks_imperative(subject: &str) -> bool {
+	let description =
+		parse_conventional(subject).map_or(subject, |(_, d)| d);
+
+	let Some(word) = description.split_whitespace().next() else {
+		return true;
+	};
+	let word = word
+		.trim_end_matches(|c: char| !c.is_alphabetic())
+		.to_lowercase();
+
+	if IMPERATIVE_EXCEPTIONS.contains(&word.as_str()) {
+		return true;
+	}
+
+	if word.ends_with("ed") || word.ends_with("ing") {
+		return false;
+	}
+
+	// `adds` but not `address`
+	!word.ends_with('s')
+		|| ["ss", "us", "is"].iter().any(|end| word.ends_with(end))
+}
+
+/// checks `msg` against the builtin rules of `config`
+pub fn lint_commit_message(
+	config: &CommitLintConfig,
+	msg: &str,
+) -> Vec<LintProblem> {
+	let subject = subject(msg);
+	if subject.is_empty() {
+		return Vec::new();
+	}
+
+	let mut problems = Vec::new();
+
+	let length = subject.chars().count();
+	if config.subject_length > 0 && length > config.subject_length {
+		problems.push(LintProblem::warning(format!(
+			"subject length: {length}/{}",
+			config.subject_length
+		)));
+	}
+
+	if config.conventional && parse_conventional(subject).is_none() {
+		problems.push(LintProblem::error(String::from(
+			"expected `type(scope): subject`",
+		)));
+	}
+
+	if config.imperative && !looks_imperative(subject) {
+		problems.push(LintProblem::warning(String::from(
+			"use imperative mood",
+		)));
+	}
+
+	problems
+}
+
+/// runs the configured lint command with `msg` on stdin, a failing
+/// command is reported as an error containing its output
+pub fn lint_commit_message_command(
+	repo_path: &RepoPath,
+	command: &str,
+	msg: &str,
+) -> Result<Option<LintProblem>> {
+	scope_time!("lint_commit_message_command");
+
+	let repo = repo(repo_path)?;
+	let work_dir = work_dir(&repo)?;
+
+	let mut cmd = if cfg!(target_os = "windows") {
+		let mut cmd = Command::new("cmd");
+		cmd.arg("/C");
+		cmd
+	} else {
+		let mut cmd = Command::new("sh");
+		cmd.arg("-c");
+		cmd
+	};
+
+	let mut child = cmd
+		.arg(command)
+		.current_dir(work_dir)
+		.stdin(Stdio::piped())
+		.stdout(Stdio::piped())
+		.stderr(Stdio::piped())
+		.spawn()
+		.map_err(|e| {
+			Error::Generic(format!("lint command failed: {e}"))
+		})?;
+
+	if let Some(mut stdin) = child.stdin.take() {
+		// the command may exit without reading its input
+		stdin.write_all(msg.as_bytes()).ok();
+	}
+
+	let output = child.wait_with_output()?;
+
+	if output.status.success() {
+		return Ok(None);
+	}
+
+	let out = format!(
+		"{}{}",
+		String::from_utf8_lossy(&output.stdout),
+		String::from_utf8_lossy(&output.stderr)
+	);
+	let out = out.trim();
+
+	Ok(Some(LintProblem::error(if out.is_empty() {
+		format!("`{command}` rejected the message")
+	} else {
+		out.to_string()
+	})))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::tests::repo_init;
+
+	fn messages(config: &CommitLintConfig, msg: &str) -> Vec<String> {
+		lint_commit_message(config, msg)
+			.into_iter()
+			.map(|p| p.message)
+			.collect()
+	}
+
+	#[test]
+	fn test_subject_length() {
+		let config = CommitLintConfig::default();
+
+		assert!(messages(&config, "add feature").is_empty());
+		assert!(messages(&config, "").is_empty());
+		assert_eq!(
+			messages(&config, &"a".repeat(51)),
+			vec!["subject length: 51/50"]
+		);
+		// comments and body are ignored
+		assert!(messages(
+			&config,
+			&format!(
+				"# {}\nshort\n\n{}",
+				"a".repeat(60),
+				"b".repeat(60)
+			)
+		)
+		.is_empty());
+
+		let config = CommitLintConfig {
+			subject_length: 0,
+			..CommitLintConfig::default()
+		};
+		assert!(messages(&config, &"a".repeat(100)).is_empty());
+	}
+
+	#[test]
+	fn test_conventional() {
+		assert_eq!(
+			parse_conventional("feat: add x"),
+			Some(("feat", "add x"))
+		);
+		assert_eq!(
+			parse_conventional("fix(ui)!: remove y"),
+			Some(("fix", "remove y"))
+		);
+		assert_eq!(parse_conventional("add x"), None);
+		assert_eq!(parse_conventional("feat(): add x"), None);
+		assert_eq!(parse_conventional("feat:add x"), None);
+		assert_eq!(parse_conventional("my feat: add x"), None);
+
+		let config = CommitLintConfig {
+			conventional: true,
+			..CommitLintConfig::default()
+		};
+		let problems = lint_commit_message(&config, "add x");
+		assert_eq!(problems.len(), 1);
+		assert_eq!(problems[0].severity, LintSeverity::Error);
+		assert!(
+			lint_commit_message(&config, "feat: add x").is_empty()
+		);
+	}
+
+	#[test]
+	fn test_imperative() {
+		assert!(looks_imperative("add feature"));
+		assert!(looks_imperative("Fix bug"));
+		assert!(looks_imperative("feat: embed fonts"));
+		assert!(looks_imperative("address review"));
+		assert!(!looks_imperative("added feature"));
+		assert!(!looks_imperative("Fixes bug"));
+		assert!(!looks_imperative("fix: adding tests"));
+
+		let config = CommitLintConfig {
+			imperative: true,
+			..CommitLintConfig::default()
+		};
+		let problems = lint_commit_message(&config, "added x");
+		assert_eq!(problems.len(), 1);
+		assert_eq!(problems[0].severity, LintSeverity::Warning);
+	}
+
+	#[test]
+	fn test_load_config() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		assert_eq!(
+			CommitLintConfig::load(repo_path).unwrap(),
+			CommitLintConfig::default()
+		);
+
+		let mut config = repo.config().unwrap();
+		config.set_i64(CONFIG_LINT_SUBJECT_LENGTH, 72).unwrap();
+		config.set_bool(CONFIG_LINT_CONVENTIONAL, true).unwrap();
+		config.set_str(CONFIG_LINT_COMMAND, "false").unwrap();
+
+		assert_eq!(
+			CommitLintConfig::load(repo_path).unwrap(),
+			CommitLintConfig {
+				subject_length: 72,
+				imperative: false,
+				conventional: true,
+				command: Some(String::from("false")),
+			}
+		);
+	}
+
+	#[test]
+	#[cfg(unix)]
+	fn test_lint_command() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let command =
+			"grep -q '^feat' || { echo 'no feat'; exit 1; }";
+
+		assert_eq!(
+			lint_commit_message_command(
+				repo_path, command, "feat: x"
+			)
+			.unwrap(),
+			None
+		);
+		assert_eq!(
+			lint_commit_message_command(repo_path, command, "fix: x")
+				.unwrap(),
+			Some(LintProblem::error(String::from("no feat")))
+		);
+	}
+}
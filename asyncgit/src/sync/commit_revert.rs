@@ -1,16 +1,30 @@
 use super::{CommitId, RepoPath};
 use crate::{
-	error::Result,
+	error::{Error, Result},
 	sync::{repository::repo, utils::read_file},
 };
+use git2::RevertOptions;
 use scopetime::scope_time;
 
 const GIT_REVERT_HEAD_FILE: &str = "REVERT_HEAD";
+const GIT_MERGE_MSG_FILE: &str = "MERGE_MSG";
 
-///
+/// parent of a merge commit to choose the mainline from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RevertParent {
+	///
+	pub id: CommitId,
+	///
+	pub summary: String,
+}
+
+/// reverts `commit` into index and worktree, `mainline` (1-based
+/// like `git revert -m`) picks the parent a merge commit is reverted
+/// against
 pub fn revert_commit(
 	repo_path: &RepoPath,
 	commit: CommitId,
+	mainline: Option<usize>,
 ) -> Result<()> {
 	scope_time!("revert");
 
@@ -18,11 +32,59 @@ pub fn revert_commit(
 
 	let commit = repo.find_commit(commit.into())?;
 
-	repo.revert(&commit, None)?;
+	if commit.parent_count() > 1 && mainline.is_none() {
+		return Err(Error::Generic(format!(
+			"commit {} is a merge, choose the mainline parent to revert against",
+			commit.id()
+		)));
+	}
+
+	let mut options = RevertOptions::new();
+	if let Some(mainline) = mainline {
+		if mainline == 0 || mainline > commit.parent_count() {
+			return Err(Error::Generic(format!(
+				"commit {} has no parent {mainline}",
+				commit.id()
+			)));
+		}
+		options.mainline(u32::try_from(mainline)?);
+	}
+
+	repo.revert(&commit, Some(&mut options))?;
+
+	if let Some(mainline) = mainline {
+		// same message as git, libgit2 does not mention the mainline
+		let parent = commit.parent_id(mainline - 1)?;
+		let msg = format!(
+			"Revert \"{}\"\n\nThis reverts commit {}, reversing\nchanges made to {}.\n",
+			commit.summary().unwrap_or_default(),
+			commit.id(),
+			parent
+		);
+		std::fs::write(repo.path().join(GIT_MERGE_MSG_FILE), msg)?;
+	}
 
 	Ok(())
 }
 
+/// parents of `commit` in order, to choose the mainline from
+pub fn revert_parents(
+	repo_path: &RepoPath,
+	commit: CommitId,
+) -> Result<Vec<RevertParent>> {
+	let repo = repo(repo_path)?;
+
+	let commit = repo.find_commit(commit.into())?;
+
+	Ok(commit
+		.parents()
+		.map(|parent| RevertParent {
+			id: parent.id().into(),
+			summary: parent.summary().unwrap_or_default().to_string(),
+		})
+		.collect())
+}
+
 ///
 pub fn revert_head(repo_path: &RepoPath) -> Result<CommitId> {
 	scope_time!("revert_head");
@@ -49,3 +111,72 @@ pub fn commit_revert(
 
 	Ok(id)
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::tests::{repo_init, write_commit_file};
+	use git2::ResetType;
+
+	#[test]
+	fn test_revert_merge() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let base = write_commit_file(&repo, "a.txt", "a", "base");
+		let side = write_commit_file(&repo, "b.txt", "b", "side");
+		repo.reset(
+			repo.find_commit(base.into()).unwrap().as_object(),
+			ResetType::Hard,
+			None,
+		)
+		.unwrap();
+		let main = write_commit_file(&repo, "c.txt", "c", "main");
+
+		let main_commit = repo.find_commit(main.into()).unwrap();
+		let side_commit = repo.find_commit(side.into()).unwrap();
+		let tree = repo
+			.merge_commits(&main_commit, &side_commit, None)
+			.unwrap()
+			.write_tree_to(&repo)
+			.unwrap();
+		let sig = repo.signature().unwrap();
+		let merge: CommitId = repo
+			.commit(
+				Some("HEAD"),
+				&sig,
+				&sig,
+				"merge side",
+				&repo.find_tree(tree).unwrap(),
+				&[&main_commit, &side_commit],
+			)
+			.unwrap()
+			.into();
+		repo.checkout_head(Some(
+			git2::build::CheckoutBuilder::new().force(),
+		))
+		.unwrap();
+
+		assert!(revert_commit(repo_path, merge, None).is_err());
+		assert!(revert_commit(repo_path, merge, Some(3)).is_err());
+		assert_eq!(
+			revert_parents(repo_path, merge)
+				.unwrap()
+				.iter()
+				.map(|p| p.id)
+				.collect::<Vec<_>>(),
+			vec![main, side]
+		);
+
+		revert_commit(repo_path, merge, Some(1)).unwrap();
+
+		assert!(!root.join("b.txt").exists());
+		assert!(root.join("c.txt").exists());
+		assert_eq!(revert_head(repo_path).unwrap(), merge);
+		assert!(crate::sync::merge_msg(repo_path)
+			.unwrap()
+			.contains(&format!("reversing\nchanges made to {main}")));
+	}
+}
@@ -1,4 +1,4 @@
-use std::fmt::Display;
+use std::{borrow::Cow, fmt::Display};
 
 use super::RepoPath;
 use crate::{
@@ -42,12 +42,7 @@ impl CommitId {
 		repo_path: &RepoPath,
 		revision: &str,
 	) -> Result<Self> {
-		scope_time!("CommitId::from_revision");
-
-		let repo = repo(repo_path)?;
-
-		let commit_obj = repo.revparse_single(revision)?;
-		Ok(commit_obj.id().into())
+		rev_parse_commit(repo_path, revision)
 	}
 }
 
@@ -72,6 +67,30 @@ impl From<Oid> for CommitId {
 	}
 }
 
+/// resolves any revision expression `git rev-parse` understands
+/// (`HEAD~3`, `@{upstream}`, `:/message`, `branch@{yesterday}`, ...)
+/// to the commit it points at
+pub fn rev_parse_commit(
+	repo_path: &RepoPath,
+	revision: &str,
+) -> Result<CommitId> {
+	scope_time!("rev_parse_commit");
+
+	let repo = repo(repo_path)?;
+
+	// libgit2 does not know `@` as a shortcut for `HEAD`
+	let revision = match revision.strip_prefix('@') {
+		Some(rest) if !rest.starts_with('{') => {
+			Cow::from(format!("HEAD{rest}"))
+		}
+		_ => Cow::from(revision),
+	};
+
+	let commit = repo.revparse_single(&revision)?.peel_to_commit()?;
+
+	Ok(commit.id().into())
+}
+
 ///
 #[derive(Debug)]
 pub struct CommitInfo {
@@ -164,12 +183,14 @@ pub fn get_message(
 
 #[cfg(test)]
 mod tests {
-	use super::get_commits_info;
+	use super::{get_commits_info, rev_parse_commit};
 	use crate::{
 		error::Result,
 		sync::{
-			commit, stage_add_file, tests::repo_init_empty,
-			utils::get_head_repo, CommitId, RepoPath,
+			commit, stage_add_file, tag_commit,
+			tests::{repo_init_empty, write_commit_file},
+			utils::get_head_repo,
+			CommitId, RepoPath,
 		},
 	};
 	use std::{fs::File, io::Write, path::Path};
@@ -280,4 +301,31 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[test]
+	fn test_rev_parse_commit() -> Result<()> {
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 = write_commit_file(&repo, "a.txt", "a", "first: foo");
+		let c2 = write_commit_file(&repo, "b.txt", "b", "second");
+		let c3 = write_commit_file(&repo, "c.txt", "c", "third");
+
+		tag_commit(repo_path, &c2, "v1", Some("annotated"))?;
+
+		assert_eq!(rev_parse_commit(repo_path, "HEAD")?, c3);
+		assert_eq!(rev_parse_commit(repo_path, "HEAD~2")?, c1);
+		assert_eq!(rev_parse_commit(repo_path, "@^")?, c2);
+		assert_eq!(rev_parse_commit(repo_path, ":/first")?, c1);
+		// annotated tags resolve to the tagged commit
+		assert_eq!(rev_parse_commit(repo_path, "v1")?, c2);
+
+		assert!(rev_parse_commit(repo_path, "HEAD~5").is_err());
+		assert!(rev_parse_commit(repo_path, "@{upstream}").is_err());
+		assert!(rev_parse_commit(repo_path, "HEAD:a.txt").is_err());
+
+		Ok(())
+	}
 }
@@ -0,0 +1,161 @@
+//! comparing two commits like `git diff`/`git log` do with `base..target`
+//! and `base...target`
+
+use super::{
+	commit_files::OldNew, repository::repo, CommitId, RepoPath,
+};
+use crate::error::Result;
+use git2::{Oid, Repository, Sort};
+use scopetime::scope_time;
+
+/// range semantics of a comparison
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum CompareMode {
+	/// `base..target`: diff of both trees, commits only reachable
+	/// from the target
+	#[default]
+	TwoDot,
+	/// `base...target`: diff of the merge base and the target,
+	/// commits reachable from either side but not both
+	ThreeDot,
+}
+
+impl CompareMode {
+	///
+	#[must_use]
+	pub const fn toggled(self) -> Self {
+		match self {
+			Self::TwoDot => Self::ThreeDot,
+			Self::ThreeDot => Self::TwoDot,
+		}
+	}
+
+	///
+	#[must_use]
+	pub const fn as_str(self) -> &'static str {
+		match self {
+			Self::TwoDot => "..",
+			Self::ThreeDot => "...",
+		}
+	}
+}
+
+fn merge_base_repo(
+	repo: &Repository,
+	ids: OldNew<CommitId>,
+) -> Result<Oid> {
+	Ok(repo.merge_base(ids.old.get_oid(), ids.new.get_oid())?)
+}
+
+/// commits to diff for `ids` (base as `old`, target as `new`): the
+/// same for [`CompareMode::TwoDot`], the merge base and the target for
+/// [`CompareMode::ThreeDot`]
+pub fn compare_diff_ids(
+	repo_path: &RepoPath,
+	ids: OldNew<CommitId>,
+	mode: CompareMode,
+) -> Result<OldNew<CommitId>> {
+	scope_time!("compare_diff_ids");
+
+	match mode {
+		CompareMode::TwoDot => Ok(ids),
+		CompareMode::ThreeDot => {
+			let repo = repo(repo_path)?;
+
+			Ok(OldNew {
+				old: merge_base_repo(&repo, ids)?.into(),
+				new: ids.new,
+			})
+		}
+	}
+}
+
+/// commits in the range between base (`old`) and target (`new`),
+/// newest first like `git log base..target`/`git log base...target`
+pub fn get_compare_commits(
+	repo_path: &RepoPath,
+	ids: OldNew<CommitId>,
+	mode: CompareMode,
+) -> Result<Vec<CommitId>> {
+	scope_time!("get_compare_commits");
+
+	let repo = repo(repo_path)?;
+
+	let mut walk = repo.revwalk()?;
+	walk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+	walk.push(ids.new.get_oid())?;
+
+	match mode {
+		CompareMode::TwoDot => {
+			walk.hide(ids.old.get_oid())?;
+		}
+		CompareMode::ThreeDot => {
+			walk.push(ids.old.get_oid())?;
+			// unrelated histories have no merge base to hide
+			if let Ok(base) = merge_base_repo(&repo, ids) {
+				walk.hide(base)?;
+			}
+		}
+	}
+
+	Ok(walk.filter_map(|id| id.ok().map(CommitId::new)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		checkout_branch, create_branch,
+		tests::{repo_init, write_commit_file},
+	};
+
+	#[test]
+	fn test_compare_modes() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let base = write_commit_file(&repo, "a.txt", "a", "c1");
+		create_branch(repo_path, "feature").unwrap();
+		let feature = write_commit_file(&repo, "b.txt", "b", "c2");
+		checkout_branch(repo_path, "master").unwrap();
+		let master = write_commit_file(&repo, "c.txt", "c", "c3");
+
+		let ids = OldNew {
+			old: master,
+			new: feature,
+		};
+
+		assert_eq!(
+			get_compare_commits(repo_path, ids, CompareMode::TwoDot)
+				.unwrap(),
+			vec![feature]
+		);
+
+		let mut symmetric = get_compare_commits(
+			repo_path,
+			ids,
+			CompareMode::ThreeDot,
+		)
+		.unwrap();
+		symmetric.sort();
+		let mut expected = vec![feature, master];
+		expected.sort();
+		assert_eq!(symmetric, expected);
+
+		assert_eq!(
+			compare_diff_ids(repo_path, ids, CompareMode::TwoDot)
+				.unwrap(),
+			ids
+		);
+		assert_eq!(
+			compare_diff_ids(repo_path, ids, CompareMode::ThreeDot)
+				.unwrap(),
+			OldNew {
+				old: base,
+				new: feature,
+			}
+		);
+	}
+}
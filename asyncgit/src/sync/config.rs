@@ -1,12 +1,31 @@
 //TODO: hopefully released in next rust (see https://github.com/rust-lang/rust-clippy/issues/9440)
 #![allow(clippy::use_self)]
 
-use crate::error::Result;
-use git2::Repository;
+use crate::error::{Error, Result};
+use git2::{Config, ConfigLevel, ErrorCode, Repository};
 use scopetime::scope_time;
 use serde::{Deserialize, Serialize};
 
-use super::{repository::repo, RepoPath};
+use super::{
+	branch::{
+		flow::{
+			CONFIG_FLOW_DEVELOP, CONFIG_FLOW_FEATURE,
+			CONFIG_FLOW_HOTFIX, CONFIG_FLOW_MAIN,
+			CONFIG_FLOW_RELEASE, CONFIG_FLOW_VERSION_TAG,
+		},
+		protected::CONFIG_PROTECTED_BRANCHES,
+	},
+	commit_lint::{
+		CONFIG_LINT_COMMAND, CONFIG_LINT_CONVENTIONAL,
+		CONFIG_LINT_IMPERATIVE, CONFIG_LINT_SUBJECT_LENGTH,
+	},
+	renames::{
+		CONFIG_DIFF_RENAMES, CONFIG_RENAME_THRESHOLD,
+		CONFIG_STATUS_RENAMES,
+	},
+	repository::repo,
+	RepoPath,
+};
 
 // see https://git-scm.com/docs/git-config#Documentation/git-config.txt-statusshowUntrackedFiles
 /// represents the `status.showUntrackedFiles` git config state
@@ -62,6 +81,74 @@ pub fn untracked_files_config_repo(
 	Ok(ShowUntrackedFilesConfig::All)
 }
 
+// see https://git-scm.com/docs/git-config#Documentation/git-config.txt-diffalgorithm
+/// represents the `diff.algorithm` git config
+#[derive(
+	Debug,
+	Default,
+	Hash,
+	Copy,
+	Clone,
+	PartialEq,
+	Eq,
+	Serialize,
+	Deserialize,
+)]
+pub enum DiffAlgorithm {
+	///
+	#[default]
+	Myers,
+	/// spends extra time to find the smallest diff
+	Minimal,
+	///
+	Patience,
+	/// libgit2 has no histogram implementation, diffs with patience
+	/// which histogram is an extension of
+	Histogram,
+}
+
+impl DiffAlgorithm {
+	/// all algorithms in the order they get cycled through
+	pub const ALL: [Self; 4] =
+		[Self::Myers, Self::Minimal, Self::Patience, Self::Histogram];
+
+	/// name used in `diff.algorithm`
+	pub const fn as_str(self) -> &'static str {
+		match self {
+			Self::Myers => "myers",
+			Self::Minimal => "minimal",
+			Self::Patience => "patience",
+			Self::Histogram => "histogram",
+		}
+	}
+}
+
+impl<'a> TryFrom<&'a str> for DiffAlgorithm {
+	type Error = crate::Error;
+	fn try_from(
+		value: &'a str,
+	) -> std::result::Result<Self, Self::Error> {
+		match value {
+			"default" | "myers" => Ok(Self::Myers),
+			"minimal" => Ok(Self::Minimal),
+			"patience" => Ok(Self::Patience),
+			"histogram" => Ok(Self::Histogram),
+			_ => Err(crate::Error::GitConfig(format!(
+				"malformed value for diff.algorithm: {value}, must be one of default, myers, minimal, patience or histogram"
+			))),
+		}
+	}
+}
+
+pub fn diff_algorithm_config_repo(
+	repo: &Repository,
+) -> Result<DiffAlgorithm> {
+	(get_config_string_repo(repo, "diff.algorithm")?).map_or_else(
+		|| Ok(DiffAlgorithm::default()),
+		|entry_str| DiffAlgorithm::try_from(entry_str.as_str()),
+	)
+}
+
 // see https://git-scm.com/docs/git-config#Documentation/git-config.txt-pushdefault
 /// represents `push.default` git config
 #[derive(PartialEq, Eq)]
@@ -148,11 +235,292 @@ pub fn get_config_string_repo(
 	}
 }
 
+/// config file a value was read from
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConfigOrigin {
+	///
+	System,
+	/// `~/.gitconfig` or `$XDG_CONFIG_HOME/git/config`
+	Global,
+	/// `.git/config`
+	Local,
+	/// `.git/config.worktree`
+	Worktree,
+	/// anything else (e.g. environment or command line)
+	Other,
+}
+
+impl From<ConfigLevel> for ConfigOrigin {
+	fn from(level: ConfigLevel) -> Self {
+		match level {
+			ConfigLevel::ProgramData | ConfigLevel::System => {
+				Self::System
+			}
+			ConfigLevel::XDG | ConfigLevel::Global => Self::Global,
+			ConfigLevel::Local => Self::Local,
+			ConfigLevel::Worktree => Self::Worktree,
+			ConfigLevel::App | ConfigLevel::Highest => Self::Other,
+		}
+	}
+}
+
+/// effective value of a config key
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConfigEntry {
+	///
+	pub name: String,
+	/// `None` if not set anywhere
+	pub value: Option<String>,
+	/// where `value` comes from
+	pub origin: Option<ConfigOrigin>,
+}
+
+/// keys always listed in the config editor, even if unset
+pub const COMMON_CONFIG_KEYS: &[&str] = &[
+	"user.name",
+	"user.email",
+	"user.signingKey",
+	"commit.gpgSign",
+	"core.editor",
+	"core.autocrlf",
+	"init.defaultBranch",
+	"pull.rebase",
+	"push.default",
+	"push.autoSetupRemote",
+	"fetch.prune",
+	"merge.conflictStyle",
+	"diff.algorithm",
+	CONFIG_DIFF_RENAMES,
+	CONFIG_STATUS_RENAMES,
+	CONFIG_RENAME_THRESHOLD,
+	"status.showUntrackedFiles",
+	CONFIG_PROTECTED_BRANCHES,
+	CONFIG_LINT_SUBJECT_LENGTH,
+	CONFIG_LINT_IMPERATIVE,
+	CONFIG_LINT_CONVENTIONAL,
+	CONFIG_LINT_COMMAND,
+	CONFIG_FLOW_MAIN,
+	CONFIG_FLOW_DEVELOP,
+	CONFIG_FLOW_FEATURE,
+	CONFIG_FLOW_RELEASE,
+	CONFIG_FLOW_HOTFIX,
+	CONFIG_FLOW_VERSION_TAG,
+];
+
+/// returns the effective value of the common keys, all aliases and
+/// everything set in the local or global config
+pub fn get_config_entries(
+	repo_path: &RepoPath,
+) -> Result<Vec<ConfigEntry>> {
+	scope_time!("get_config_entries");
+
+	let repo = repo(repo_path)?;
+	let cfg = repo.config()?;
+
+	let mut names: Vec<String> =
+		COMMON_CONFIG_KEYS.iter().map(ToString::to_string).collect();
+	let mut others = Vec::new();
+
+	let mut entries = cfg.entries(None)?;
+	while let Some(entry) = entries.next() {
+		let entry = entry?;
+		let Some(name) = entry.name() else {
+			continue;
+		};
+
+		let listed = name.starts_with("alias.")
+			|| matches!(
+				entry.level(),
+				ConfigLevel::Local
+					| ConfigLevel::Worktree
+					| ConfigLevel::Global
+					| ConfigLevel::XDG
+			);
+
+		if listed
+			&& !names
+				.iter()
+				.chain(others.iter())
+				.any(|n| n.eq_ignore_ascii_case(name))
+		{
+			others.push(name.to_string());
+		}
+	}
+
+	others.sort();
+	names.extend(others);
+
+	Ok(names
+		.into_iter()
+		.map(|name| {
+			let entry = cfg.get_entry(&name).ok();
+			ConfigEntry {
+				value: entry.as_ref().and_then(|e| {
+					e.has_value()
+						.then(|| e.value().map(ToString::to_string))
+						.flatten()
+				}),
+				origin: entry.map(|e| e.level().into()),
+				name,
+			}
+		})
+		.collect())
+}
+
+fn open_config_level(
+	repo: &Repository,
+	origin: ConfigOrigin,
+) -> Result<Config> {
+	let cfg = repo.config()?;
+
+	match origin {
+		ConfigOrigin::Local => {
+			Ok(cfg.open_level(ConfigLevel::Local)?)
+		}
+		ConfigOrigin::Global => {
+			cfg.open_level(ConfigLevel::Global).or_else(|_| {
+				// no global config yet, create `~/.gitconfig`
+				let path = dirs::home_dir()
+					.ok_or_else(|| {
+						Error::Generic("no home directory".into())
+					})?
+					.join(".gitconfig");
+				Ok(Config::open(&path)?)
+			})
+		}
+		_ => Err(Error::Generic(format!(
+			"cannot edit {origin:?} config"
+		))),
+	}
+}
+
+/// sets `key` in the `origin` config file (local or global only),
+/// `None` unsets it
+pub fn set_config_string(
+	repo_path: &RepoPath,
+	origin: ConfigOrigin,
+	key: &str,
+	value: Option<&str>,
+) -> Result<()> {
+	scope_time!("set_config_string");
+
+	let repo = repo(repo_path)?;
+	let mut cfg = open_config_level(&repo, origin)?;
+
+	match value {
+		Some(value) => cfg.set_str(key, value)?,
+		None => match cfg.remove(key) {
+			Err(e) if e.code() != ErrorCode::NotFound => {
+				return Err(e.into());
+			}
+			_ => (),
+		},
+	}
+
+	Ok(())
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
 	use crate::sync::tests::repo_init;
 
+	#[test]
+	fn test_diff_algorithm_config() {
+		let (_td, repo) = repo_init().unwrap();
+
+		assert_eq!(
+			diff_algorithm_config_repo(&repo).unwrap(),
+			DiffAlgorithm::Myers
+		);
+
+		repo.config()
+			.unwrap()
+			.set_str("diff.algorithm", "histogram")
+			.unwrap();
+		assert_eq!(
+			diff_algorithm_config_repo(&repo).unwrap(),
+			DiffAlgorithm::Histogram
+		);
+
+		repo.config()
+			.unwrap()
+			.set_str("diff.algorithm", "fancy")
+			.unwrap();
+		assert!(diff_algorithm_config_repo(&repo).is_err());
+	}
+
+	#[test]
+	fn test_config_entries() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		set_config_string(
+			repo_path,
+			ConfigOrigin::Local,
+			"alias.co",
+			Some("checkout"),
+		)
+		.unwrap();
+		set_config_string(
+			repo_path,
+			ConfigOrigin::Local,
+			"pull.rebase",
+			Some("true"),
+		)
+		.unwrap();
+
+		let entries = get_config_entries(repo_path).unwrap();
+		let find = |name: &str| {
+			entries.iter().find(|e| e.name == name).cloned()
+		};
+
+		assert_eq!(
+			find("pull.rebase").unwrap(),
+			ConfigEntry {
+				name: "pull.rebase".into(),
+				value: Some("true".into()),
+				origin: Some(ConfigOrigin::Local),
+			}
+		);
+		assert_eq!(
+			find("alias.co").unwrap().value.as_deref(),
+			Some("checkout")
+		);
+		assert_eq!(find("fetch.prune").unwrap().value, None);
+		// common keys are listed first and only once
+		assert_eq!(entries[0].name, "user.name");
+		assert_eq!(
+			entries
+				.iter()
+				.filter(|e| e.name.eq_ignore_ascii_case("user.name"))
+				.count(),
+			1
+		);
+
+		set_config_string(
+			repo_path,
+			ConfigOrigin::Local,
+			"pull.rebase",
+			None,
+		)
+		.unwrap();
+		assert_eq!(
+			get_config_string(repo_path, "pull.rebase").unwrap(),
+			None
+		);
+
+		assert!(set_config_string(
+			repo_path,
+			ConfigOrigin::System,
+			"pull.rebase",
+			Some("true"),
+		)
+		.is_err());
+	}
+
 	#[test]
 	fn test_get_config() {
 		let bad_dir_cfg = get_config_string(
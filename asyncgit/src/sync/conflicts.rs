@@ -0,0 +1,363 @@
+//! reading the stages of conflicted files to resolve them region by
+//! region
+
+use super::{repository::repo, utils::work_dir, RepoPath};
+use crate::error::{Error, Result};
+use git2::{IndexEntry, MergeFileOptions};
+use scopetime::scope_time;
+use std::{fs, path::Path};
+
+const MARKER_OURS: &str = "<<<<<<<";
+const MARKER_BASE: &str = "|||||||";
+const MARKER_SEPARATOR: &str = "=======";
+const MARKER_THEIRS: &str = ">>>>>>>";
+
+/// how a conflict region is resolved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConflictResolution {
+	///
+	Ours,
+	///
+	Theirs,
+	/// ours followed by theirs
+	Both,
+	/// the common ancestor, dropping both changes
+	Base,
+	/// text written by the user
+	Edited(String),
+}
+
+/// a region both sides changed differently
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConflictRegion {
+	///
+	pub ours: String,
+	/// `None` if the file was added on both sides
+	pub base: Option<String>,
+	///
+	pub theirs: String,
+	///
+	pub resolution: Option<ConflictResolution>,
+}
+
+impl ConflictRegion {
+	/// text of the region after applying `resolution`
+	pub fn resolved(&self) -> Option<String> {
+		self.resolution.as_ref().map(|resolution| match resolution {
+			ConflictResolution::Ours => self.ours.clone(),
+			ConflictResolution::Theirs => self.theirs.clone(),
+			ConflictResolution::Both => {
+				format!("{}{}", self.ours, self.theirs)
+			}
+			ConflictResolution::Base => {
+				self.base.clone().unwrap_or_default()
+			}
+			ConflictResolution::Edited(text) => text.clone(),
+		})
+	}
+}
+
+/// part of a conflicted file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConflictChunk {
+	/// merged cleanly
+	Merged(String),
+	///
+	Conflict(ConflictRegion),
+}
+
+/// a conflicted file split into cleanly merged and conflicting parts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConflictFile {
+	///
+	pub path: String,
+	///
+	pub chunks: Vec<ConflictChunk>,
+}
+
+impl ConflictFile {
+	/// indices into `chunks` of all conflict regions
+	pub fn conflicts(&self) -> Vec<usize> {
+		self.chunks
+			.iter()
+			.enumerate()
+			.filter(|(_, chunk)| {
+				matches!(chunk, ConflictChunk::Conflict(_))
+			})
+			.map(|(idx, _)| idx)
+			.collect()
+	}
+
+	///
+	pub fn unresolved(&self) -> usize {
+		self.chunks
+			.iter()
+			.filter(|chunk| {
+				matches!(
+					chunk,
+					ConflictChunk::Conflict(ConflictRegion {
+						resolution: None,
+						..
+					})
+				)
+			})
+			.count()
+	}
+
+	/// the merged file, `None` while regions are unresolved
+	pub fn merged(&self) -> Option<String> {
+		self.chunks
+			.iter()
+			.map(|chunk| match chunk {
+				ConflictChunk::Merged(text) => Some(text.clone()),
+				ConflictChunk::Conflict(region) => region.resolved(),
+			})
+			.collect()
+	}
+}
+
+/// merges the stages of the conflicted `path` again to split it into
+/// regions, works independently of the markers in the working
+/// directory. fails for files deleted on one side.
+pub fn get_conflict_file(
+	repo_path: &RepoPath,
+	path: &str,
+) -> Result<ConflictFile> {
+	scope_time!("get_conflict_file");
+
+	let repo = repo(repo_path)?;
+	let index = repo.index()?;
+
+	let conflict = index
+		.conflicts()?
+		.filter_map(std::result::Result::ok)
+		.find(|conflict| {
+			[&conflict.our, &conflict.their, &conflict.ancestor]
+				.into_iter()
+				.flatten()
+				.any(|entry| entry.path == path.as_bytes())
+		})
+		.ok_or_else(|| {
+			Error::Generic(format!("not conflicted: {path}"))
+		})?;
+
+	let (Some(ours), Some(theirs)) = (conflict.our, conflict.their)
+	else {
+		return Err(Error::Generic(format!(
+			"deleted on one side: {path}"
+		)));
+	};
+
+	let has_base = conflict.ancestor.is_some();
+	let ancestor = match conflict.ancestor {
+		Some(ancestor) => ancestor,
+		None => IndexEntry {
+			id: repo.blob(&[])?,
+			..clone_entry(&ours)
+		},
+	};
+
+	let mut opts = MergeFileOptions::new();
+	opts.style_diff3(true);
+
+	let result = repo.merge_file_from_index(
+		&ancestor,
+		&ours,
+		&theirs,
+		Some(&mut opts),
+	)?;
+
+	let content = String::from_utf8(result.content().to_vec())?;
+
+	Ok(ConflictFile {
+		path: path.to_string(),
+		chunks: parse_diff3(&content, has_base),
+	})
+}
+
+/// writes `content` into the working directory and stages it, which
+/// marks the conflict as resolved
+pub fn resolve_conflict_file(
+	repo_path: &RepoPath,
+	path: &str,
+	content: &str,
+) -> Result<()> {
+	scope_time!("resolve_conflict_file");
+
+	let repo = repo(repo_path)?;
+
+	fs::write(work_dir(&repo)?.join(path), content)?;
+
+	let mut index = repo.index()?;
+	index.add_path(Path::new(path))?;
+	index.write()?;
+
+	Ok(())
+}
+
+fn clone_entry(entry: &IndexEntry) -> IndexEntry {
+	IndexEntry {
+		ctime: entry.ctime,
+		mtime: entry.mtime,
+		dev: entry.dev,
+		ino: entry.ino,
+		mode: entry.mode,
+		uid: entry.uid,
+		gid: entry.gid,
+		file_size: entry.file_size,
+		id: entry.id,
+		flags: entry.flags,
+		flags_extended: entry.flags_extended,
+		path: entry.path.clone(),
+	}
+}
+
+#[derive(PartialEq, Eq)]
+enum ParseState {
+	Merged,
+	Ours,
+	Base,
+	Theirs,
+}
+
+fn parse_diff3(content: &str, has_base: bool) -> Vec<ConflictChunk> {
+	let mut chunks = Vec::new();
+
+	let mut state = ParseState::Merged;
+	let mut merged = String::new();
+	let (mut ours, mut base, mut theirs) =
+		(String::new(), String::new(), String::new());
+
+	for line in content.split_inclusive('\n') {
+		match state {
+			ParseState::Merged if line.starts_with(MARKER_OURS) => {
+				if !merged.is_empty() {
+					chunks.push(ConflictChunk::Merged(
+						std::mem::take(&mut merged),
+					));
+				}
+				state = ParseState::Ours;
+			}
+			ParseState::Ours if line.starts_with(MARKER_BASE) => {
+				state = ParseState::Base;
+			}
+			ParseState::Ours | ParseState::Base
+				if line.starts_with(MARKER_SEPARATOR) =>
+			{
+				state = ParseState::Theirs;
+			}
+			ParseState::Theirs if line.starts_with(MARKER_THEIRS) => {
+				chunks.push(ConflictChunk::Conflict(
+					ConflictRegion {
+						ours: std::mem::take(&mut ours),
+						base: has_base.then(|| base.clone()),
+						theirs: std::mem::take(&mut theirs),
+						resolution: None,
+					},
+				));
+				base.clear();
+				state = ParseState::Merged;
+			}
+			ParseState::Merged => merged.push_str(line),
+			ParseState::Ours => ours.push_str(line),
+			ParseState::Base => base.push_str(line),
+			ParseState::Theirs => theirs.push_str(line),
+		}
+	}
+
+	if !merged.is_empty() {
+		chunks.push(ConflictChunk::Merged(merged));
+	}
+
+	chunks
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		commit,
+		tests::{repo_init, write_commit_file},
+		utils::stage_add_file,
+	};
+
+	#[test]
+	fn test_parse_diff3() {
+		let chunks = parse_diff3(
+			"a\n<<<<<<< ours\nb\n||||||| base\nc\n=======\nd\ne\n>>>>>>> theirs\nf\n",
+			true,
+		);
+
+		assert_eq!(
+			chunks,
+			vec![
+				ConflictChunk::Merged(String::from("a\n")),
+				ConflictChunk::Conflict(ConflictRegion {
+					ours: String::from("b\n"),
+					base: Some(String::from("c\n")),
+					theirs: String::from("d\ne\n"),
+					resolution: None,
+				}),
+				ConflictChunk::Merged(String::from("f\n")),
+			]
+		);
+	}
+
+	#[test]
+	fn test_resolve_conflict() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "1\nbase\n3\n4\n", "c1");
+		let base = repo.head().unwrap().peel_to_commit().unwrap();
+		repo.branch("other", &base, false).unwrap();
+
+		write_commit_file(&repo, "a.txt", "1\nours\n3\n4\n", "ours");
+
+		repo.set_head("refs/heads/other").unwrap();
+		repo.checkout_head(Some(
+			git2::build::CheckoutBuilder::new().force(),
+		))
+		.unwrap();
+		fs::write(root.join("a.txt"), "1\ntheirs\n3\n4\n").unwrap();
+		stage_add_file(repo_path, Path::new("a.txt")).unwrap();
+		commit(repo_path, "theirs").unwrap();
+
+		let master = repo
+			.find_annotated_commit(
+				repo.revparse_single("master").unwrap().id(),
+			)
+			.unwrap();
+		repo.merge(&[&master], None, None).unwrap();
+
+		let mut file = get_conflict_file(repo_path, "a.txt").unwrap();
+		assert_eq!(file.conflicts(), vec![1]);
+		assert_eq!(file.unresolved(), 1);
+		assert_eq!(file.merged(), None);
+
+		let ConflictChunk::Conflict(region) = &mut file.chunks[1]
+		else {
+			unreachable!()
+		};
+		assert_eq!(region.ours, "theirs\n");
+		assert_eq!(region.base.as_deref(), Some("base\n"));
+		assert_eq!(region.theirs, "ours\n");
+
+		region.resolution = Some(ConflictResolution::Both);
+
+		let merged = file.merged().unwrap();
+		assert_eq!(merged, "1\ntheirs\nours\n3\n4\n");
+
+		resolve_conflict_file(repo_path, "a.txt", &merged).unwrap();
+
+		let mut index = repo.index().unwrap();
+		index.read(true).unwrap();
+		assert!(!index.has_conflicts());
+		assert_eq!(
+			fs::read_to_string(root.join("a.txt")).unwrap(),
+			merged
+		);
+	}
+}
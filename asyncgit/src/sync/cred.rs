@@ -10,7 +10,14 @@ use super::{
 	RepoPath,
 };
 use crate::error::{Error, Result};
-use git2::CredentialHelper;
+use git2::{CredentialHelper, Repository};
+use scopetime::scope_time;
+use std::{
+	io::Write,
+	path::{Path, PathBuf},
+	process::{Command, Output, Stdio},
+	sync::Mutex,
+};
 
 /// basic Authentication Credentials
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
@@ -85,17 +92,73 @@ pub fn need_username_password_for_push(
 	Ok(is_http)
 }
 
-/// extract username and password
-pub fn extract_username_password(
+/// know if username and password are needed to fetch from `remote`
+pub fn need_username_password_for_remote(
 	repo_path: &RepoPath,
-) -> Result<BasicAuthCredential> {
+	remote: &str,
+) -> Result<bool> {
 	let repo = repo(repo_path)?;
-	let url = repo
-		.find_remote(&get_default_remote_in_repo(&repo)?)?
+	Ok(remote_url(&repo, remote)?.starts_with("http"))
+}
+
+fn remote_url(repo: &Repository, remote: &str) -> Result<String> {
+	Ok(repo
+		.find_remote(remote)?
 		.url()
 		.ok_or(Error::UnknownRemote)?
-		.to_owned();
-	let mut helper = CredentialHelper::new(&url);
+		.to_owned())
+}
+
+/// url `extract_username_password` looks up credentials for
+pub fn credential_url(repo_path: &RepoPath) -> Result<String> {
+	let repo = repo(repo_path)?;
+	remote_url(&repo, &get_default_remote_in_repo(&repo)?)
+}
+
+/// url `extract_username_password_for_fetch` looks up credentials for
+pub fn credential_url_for_fetch(
+	repo_path: &RepoPath,
+) -> Result<String> {
+	let repo = repo(repo_path)?;
+	remote_url(&repo, &get_default_remote_for_fetch_in_repo(&repo)?)
+}
+
+/// url `extract_username_password_for_push` looks up credentials for
+pub fn credential_url_for_push(
+	repo_path: &RepoPath,
+) -> Result<String> {
+	let repo = repo(repo_path)?;
+	remote_url(&repo, &get_default_remote_for_push_in_repo(&repo)?)
+}
+
+/// asks the credential helpers like `git credential fill`, then the
+/// credentials typed in this session and finally looks at the url
+fn extract_for_url(
+	repo: &Repository,
+	url: &str,
+) -> BasicAuthCredential {
+	let dir = repo.workdir().unwrap_or_else(|| repo.path());
+
+	let filled = match credential_fill(dir, url) {
+		Ok(filled) => filled,
+		// no git executable, libgit2 knows the basic helpers at least
+		Err(_) => helper_fill(repo, url),
+	};
+
+	if let Some(cred) = filled {
+		remember_filled(dir, url, &cred);
+		return cred;
+	}
+
+	session_credential(url)
+		.unwrap_or_else(|| extract_cred_from_url(url))
+}
+
+fn helper_fill(
+	repo: &Repository,
+	url: &str,
+) -> Option<BasicAuthCredential> {
+	let mut helper = CredentialHelper::new(url);
 
 	//TODO: look at Cred::credential_helper,
 	//if the username is in the url we need to set it here,
@@ -105,70 +168,327 @@ pub fn extract_username_password(
 		helper.config(&config);
 	}
 
-	Ok(match helper.execute() {
-		Some((username, password)) => {
-			BasicAuthCredential::new(Some(username), Some(password))
-		}
-		None => extract_cred_from_url(&url),
+	helper.execute().map(|(username, password)| {
+		BasicAuthCredential::new(Some(username), Some(password))
 	})
 }
 
 /// extract username and password
-/// TODO: Very similar to `extract_username_password_for_fetch`. Can be refactored.
+pub fn extract_username_password(
+	repo_path: &RepoPath,
+) -> Result<BasicAuthCredential> {
+	let repo = repo(repo_path)?;
+	let url = remote_url(&repo, &get_default_remote_in_repo(&repo)?)?;
+
+	Ok(extract_for_url(&repo, &url))
+}
+
+/// extract username and password
 pub fn extract_username_password_for_fetch(
 	repo_path: &RepoPath,
 ) -> Result<BasicAuthCredential> {
 	let repo = repo(repo_path)?;
-	let url = repo
-		.find_remote(&get_default_remote_for_fetch_in_repo(&repo)?)?
-		.url()
-		.ok_or(Error::UnknownRemote)?
-		.to_owned();
-	let mut helper = CredentialHelper::new(&url);
+	let url = remote_url(
+		&repo,
+		&get_default_remote_for_fetch_in_repo(&repo)?,
+	)?;
 
-	//TODO: look at Cred::credential_helper,
-	//if the username is in the url we need to set it here,
-	//I dont think `config` will pick it up
+	Ok(extract_for_url(&repo, &url))
+}
 
-	if let Ok(config) = repo.config() {
-		helper.config(&config);
-	}
+/// extract username and password to fetch from `remote`
+pub fn extract_username_password_for_remote(
+	repo_path: &RepoPath,
+	remote: &str,
+) -> Result<BasicAuthCredential> {
+	let repo = repo(repo_path)?;
+	let url = remote_url(&repo, remote)?;
 
-	Ok(match helper.execute() {
-		Some((username, password)) => {
-			BasicAuthCredential::new(Some(username), Some(password))
-		}
-		None => extract_cred_from_url(&url),
-	})
+	Ok(extract_for_url(&repo, &url))
 }
 
 /// extract username and password
-/// TODO: Very similar to `extract_username_password_for_fetch`. Can be refactored.
 pub fn extract_username_password_for_push(
 	repo_path: &RepoPath,
 ) -> Result<BasicAuthCredential> {
 	let repo = repo(repo_path)?;
-	let url = repo
-		.find_remote(&get_default_remote_for_push_in_repo(&repo)?)?
-		.url()
-		.ok_or(Error::UnknownRemote)?
-		.to_owned();
-	let mut helper = CredentialHelper::new(&url);
+	let url = remote_url(
+		&repo,
+		&get_default_remote_for_push_in_repo(&repo)?,
+	)?;
 
-	//TODO: look at Cred::credential_helper,
-	//if the username is in the url we need to set it here,
-	//I dont think `config` will pick it up
+	Ok(extract_for_url(&repo, &url))
+}
 
-	if let Ok(config) = repo.config() {
-		helper.config(&config);
+/// where credentials typed into the prompt are kept
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CredentialStorage {
+	/// asked for again next time
+	#[default]
+	None,
+	/// until gitui quits
+	Session,
+	/// in the session and handed to the configured credential helper,
+	/// like `git credential approve`
+	Helper,
+}
+
+impl CredentialStorage {
+	///
+	#[must_use]
+	pub const fn next(self) -> Self {
+		match self {
+			Self::None => Self::Session,
+			Self::Session => Self::Helper,
+			Self::Helper => Self::None,
+		}
 	}
+}
+
+struct SessionCredential {
+	/// scheme, host and port of the url
+	key: String,
+	url: String,
+	cred: BasicAuthCredential,
+	/// where to run `git credential approve/reject`, `None` if the
+	/// helpers should not get them
+	helper_dir: Option<PathBuf>,
+	/// answered by a helper rather than typed into the prompt
+	filled: bool,
+	/// the helpers already got them
+	approved: bool,
+}
+
+static SESSION_CREDENTIALS: Mutex<Vec<SessionCredential>> =
+	Mutex::new(Vec::new());
+
+fn session_key(url: &str) -> Option<String> {
+	let url = url::Url::parse(url).ok()?;
+
+	Some(format!(
+		"{}://{}:{}",
+		url.scheme(),
+		url.host_str()?,
+		url.port_or_known_default()?
+	))
+}
+
+fn session_credential(url: &str) -> Option<BasicAuthCredential> {
+	let key = session_key(url)?;
 
-	Ok(match helper.execute() {
-		Some((username, password)) => {
-			BasicAuthCredential::new(Some(username), Some(password))
+	SESSION_CREDENTIALS
+		.lock()
+		.ok()?
+		.iter()
+		.find(|entry| entry.key == key && !entry.filled)
+		.map(|entry| entry.cred.clone())
+}
+
+/// `git credential <action>` in `dir` to use its configured helpers,
+/// never prompting on the terminal gitui draws to
+fn git_credential(
+	dir: &Path,
+	action: &str,
+	url: &str,
+	cred: Option<&BasicAuthCredential>,
+) -> Result<Output> {
+	let mut child = Command::new("git")
+		.args(["credential", action])
+		.current_dir(dir)
+		.env("GIT_TERMINAL_PROMPT", "0")
+		.stdin(Stdio::piped())
+		.stdout(Stdio::piped())
+		.stderr(Stdio::piped())
+		.spawn()?;
+
+	if let Some(mut stdin) = child.stdin.take() {
+		writeln!(stdin, "url={url}")?;
+		if let Some(cred) = cred {
+			if let Some(username) = &cred.username {
+				writeln!(stdin, "username={username}")?;
+			}
+			if let Some(password) = &cred.password {
+				writeln!(stdin, "password={password}")?;
+			}
 		}
-		None => extract_cred_from_url(&url),
-	})
+		writeln!(stdin)?;
+	}
+
+	Ok(child.wait_with_output()?)
+}
+
+/// the answer of the credential helpers for `url` like
+/// `git credential fill`, `None` if none of them knows it
+fn credential_fill(
+	dir: &Path,
+	url: &str,
+) -> Result<Option<BasicAuthCredential>> {
+	let output = git_credential(dir, "fill", url, None)?;
+
+	if !output.status.success() {
+		return Ok(None);
+	}
+
+	let mut cred = BasicAuthCredential::default();
+	for line in String::from_utf8_lossy(&output.stdout).lines() {
+		match line.split_once('=') {
+			Some(("username", value)) => {
+				cred.username = Some(value.to_string());
+			}
+			Some(("password", value)) => {
+				cred.password = Some(value.to_string());
+			}
+			_ => (),
+		}
+	}
+
+	Ok(cred.is_complete().then_some(cred))
+}
+
+fn run_credential(
+	dir: &Path,
+	action: &str,
+	url: &str,
+	cred: &BasicAuthCredential,
+) -> Result<()> {
+	let output = git_credential(dir, action, url, Some(cred))?;
+
+	if output.status.success() {
+		return Ok(());
+	}
+
+	Err(Error::Generic(format!(
+		"`git credential {action}` failed: {}",
+		String::from_utf8_lossy(&output.stderr).trim()
+	)))
+}
+
+/// keeps what the helpers answered, to hand it back to them once the
+/// remote accepted or rejected it
+fn remember_filled(
+	dir: &Path,
+	url: &str,
+	cred: &BasicAuthCredential,
+) {
+	let (Some(key), Ok(mut session)) =
+		(session_key(url), SESSION_CREDENTIALS.lock())
+	else {
+		return;
+	};
+
+	session.retain(|entry| !(entry.key == key && entry.filled));
+	session.push(SessionCredential {
+		key,
+		url: url.to_string(),
+		cred: cred.clone(),
+		helper_dir: Some(dir.to_path_buf()),
+		filled: true,
+		approved: false,
+	});
+}
+
+/// keeps credentials typed into the prompt for `url` according to
+/// `storage`, so they are not asked for again. the helpers only get
+/// them once the remote accepted them, see `approve_credential`
+pub fn remember_credential(
+	repo_path: &RepoPath,
+	url: &str,
+	cred: &BasicAuthCredential,
+	storage: CredentialStorage,
+) -> Result<()> {
+	scope_time!("remember_credential");
+
+	if storage == CredentialStorage::None || !cred.is_complete() {
+		return Ok(());
+	}
+
+	let Some(key) = session_key(url) else {
+		return Ok(());
+	};
+
+	let helper_dir = if storage == CredentialStorage::Helper {
+		let repo = repo(repo_path)?;
+		Some(
+			repo.workdir()
+				.unwrap_or_else(|| repo.path())
+				.to_path_buf(),
+		)
+	} else {
+		None
+	};
+
+	let mut session = SESSION_CREDENTIALS.lock()?;
+	session.retain(|entry| entry.key != key);
+	session.push(SessionCredential {
+		key,
+		url: url.to_string(),
+		cred: cred.clone(),
+		helper_dir,
+		filled: false,
+		approved: false,
+	});
+
+	Ok(())
+}
+
+/// hands `cred` to the credential helpers after the remote accepted
+/// it for `url`, like git does after a successful request
+pub(crate) fn approve_credential(
+	url: &str,
+	cred: &BasicAuthCredential,
+) {
+	let Some(key) = session_key(url) else {
+		return;
+	};
+
+	let Ok(mut session) = SESSION_CREDENTIALS.lock() else {
+		return;
+	};
+
+	for entry in session.iter_mut().filter(|entry| {
+		entry.key == key && entry.cred == *cred && !entry.approved
+	}) {
+		if let Some(dir) = &entry.helper_dir {
+			match run_credential(dir, "approve", &entry.url, cred) {
+				Ok(()) => entry.approved = true,
+				Err(e) => log::error!("approve credential: {e}"),
+			}
+		}
+	}
+}
+
+/// drops `cred` for `url` after the remote rejected it, from the
+/// session and from the helpers it came from or was meant for
+pub(crate) fn forget_credential(
+	url: &str,
+	cred: &BasicAuthCredential,
+) {
+	let Some(key) = session_key(url) else {
+		return;
+	};
+
+	let Ok(mut session) = SESSION_CREDENTIALS.lock() else {
+		return;
+	};
+
+	let (rejected, kept): (Vec<_>, Vec<_>) = session
+		.drain(..)
+		.partition(|entry| entry.key == key && entry.cred == *cred);
+	*session = kept;
+	drop(session);
+
+	for entry in rejected {
+		if let Some(dir) = entry.helper_dir {
+			if let Err(e) = run_credential(
+				&dir,
+				"reject",
+				&entry.url,
+				&entry.cred,
+			) {
+				log::error!("forget credential: {e}");
+			}
+		}
+	}
 }
 
 /// extract credentials from url
@@ -193,7 +513,9 @@ mod tests {
 	use crate::sync::{
 		cred::{
 			extract_cred_from_url, extract_username_password,
-			need_username_password, BasicAuthCredential,
+			forget_credential, need_username_password,
+			remember_credential, BasicAuthCredential,
+			CredentialStorage,
 		},
 		remotes::DEFAULT_REMOTE_NAME,
 		tests::repo_init,
@@ -364,4 +686,76 @@ mod tests {
 
 		extract_username_password(repo_path).unwrap();
 	}
+
+	#[test]
+	#[serial]
+	fn test_session_credential() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		repo.remote(DEFAULT_REMOTE_NAME, "http://github.com/a/b")
+			.unwrap();
+
+		let cred = BasicAuthCredential::new(
+			Some("user".to_owned()),
+			Some("token".to_owned()),
+		);
+
+		remember_credential(
+			repo_path,
+			"http://github.com/a/b",
+			&cred,
+			CredentialStorage::None,
+		)
+		.unwrap();
+		assert!(!extract_username_password(repo_path)
+			.unwrap()
+			.is_complete());
+
+		remember_credential(
+			repo_path,
+			"http://github.com/a/b",
+			&cred,
+			CredentialStorage::Session,
+		)
+		.unwrap();
+		assert_eq!(
+			extract_username_password(repo_path).unwrap(),
+			cred
+		);
+
+		forget_credential("http://github.com/c/d", &cred);
+		assert!(!extract_username_password(repo_path)
+			.unwrap()
+			.is_complete());
+	}
+
+	#[test]
+	#[serial]
+	fn test_extract_username_password_from_helper() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		repo.remote(DEFAULT_REMOTE_NAME, "http://example.com/a/b")
+			.unwrap();
+		repo.config()
+			.unwrap()
+			.set_str(
+				"credential.helper",
+				"!f() { echo username=u; echo password=p; }; f",
+			)
+			.unwrap();
+
+		assert_eq!(
+			extract_username_password(repo_path).unwrap(),
+			BasicAuthCredential::new(
+				Some("u".to_owned()),
+				Some("p".to_owned())
+			)
+		);
+	}
 }
@@ -1,9 +1,14 @@
 //! sync git api for fetching a diff
 
 use super::{
+	combined_diff::combined_file_diff,
 	commit_files::{
-		get_commit_diff, get_compare_commits_diff, OldNew,
+		get_commit_diff, get_commit_parent_diff,
+		get_compare_commits_diff, MergeDiffMode, OldNew,
 	},
+	config::{diff_algorithm_config_repo, DiffAlgorithm},
+	lfs::{lfs_diff, LfsDiff},
+	textconv::textconv_diff,
 	utils::{get_head_repo, work_dir},
 	CommitId, RepoPath,
 };
@@ -11,11 +16,12 @@ use crate::{
 	error::Error,
 	error::Result,
 	hash,
-	sync::{get_stashes, repository::repo},
+	sync::{get_stashes, repository::repo, stash::stash_full_tree},
 };
 use easy_cast::Conv;
 use git2::{
 	Delta, Diff, DiffDelta, DiffFormat, DiffHunk, Patch, Repository,
+	Tree,
 };
 use scopetime::scope_time;
 use serde::{Deserialize, Serialize};
@@ -130,6 +136,75 @@ pub struct FileDiff {
 	pub sizes: (u64, u64),
 	/// size delta in bytes
 	pub size_delta: i64,
+	/// set if both sides are LFS pointer files
+	pub lfs: Option<LfsDiff>,
+	/// more lines follow that did not fit the `DiffLimit`
+	pub truncated: bool,
+	/// `diff` driver whose textconv command converted both sides, the
+	/// hunks can't be staged or reverted then
+	pub textconv: Option<String>,
+}
+
+/// how much of a diff to read at most, huge diffs get loaded in
+/// chunks of `DiffLimit::CHUNK`
+#[derive(Debug, Hash, Clone, Copy, PartialEq, Eq)]
+pub struct DiffLimit {
+	/// diff lines including hunk headers
+	pub lines: usize,
+	/// bytes of line content
+	pub bytes: usize,
+}
+
+impl DiffLimit {
+	///
+	pub const CHUNK: Self = Self {
+		lines: 5_000,
+		bytes: 1024 * 1024,
+	};
+
+	/// limit to read one more chunk
+	#[must_use]
+	pub const fn next(self) -> Self {
+		Self {
+			lines: self.lines.saturating_add(Self::CHUNK.lines),
+			bytes: self.bytes.saturating_add(Self::CHUNK.bytes),
+		}
+	}
+}
+
+impl Default for DiffLimit {
+	fn default() -> Self {
+		Self::CHUNK
+	}
+}
+
+/// counts the lines read so far against a `DiffLimit`
+pub(crate) struct DiffBudget {
+	limit: Option<DiffLimit>,
+	lines: usize,
+	bytes: usize,
+}
+
+impl DiffBudget {
+	pub const fn new(limit: Option<DiffLimit>) -> Self {
+		Self {
+			limit,
+			lines: 0,
+			bytes: 0,
+		}
+	}
+
+	/// `false` if `line` does not fit anymore
+	pub fn take(&mut self, line: &DiffLine) -> bool {
+		let Some(limit) = self.limit else {
+			return true;
+		};
+
+		self.lines += 1;
+		self.bytes += line.content.len();
+
+		self.lines <= limit.lines && self.bytes <= limit.bytes
+	}
 }
 
 /// see <https://libgit2.org/libgit2/#HEAD/type/git_diff_options>
@@ -137,24 +212,74 @@ pub struct FileDiff {
 	Debug, Hash, Clone, Copy, PartialEq, Eq, Serialize, Deserialize,
 )]
 pub struct DiffOptions {
-	/// see <https://libgit2.org/libgit2/#HEAD/type/git_diff_options>
+	/// ignore all whitespace, like `git diff -w`
 	pub ignore_whitespace: bool,
+	/// ignore changes in the amount of whitespace, like
+	/// `git diff -b`
+	#[serde(default)]
+	pub ignore_whitespace_change: bool,
+	/// ignore lines that are all blank, like
+	/// `git diff --ignore-blank-lines`
+	#[serde(default)]
+	pub ignore_blank_lines: bool,
+	/// `None` follows `diff.algorithm`
+	#[serde(default)]
+	pub algorithm: Option<DiffAlgorithm>,
 	/// see <https://libgit2.org/libgit2/#HEAD/type/git_diff_options>
 	pub context: u32,
 	/// see <https://libgit2.org/libgit2/#HEAD/type/git_diff_options>
 	pub interhunk_lines: u32,
+	/// only read this much of the diff, never stored
+	#[serde(skip)]
+	pub limit: Option<DiffLimit>,
 }
 
 impl Default for DiffOptions {
 	fn default() -> Self {
 		Self {
 			ignore_whitespace: false,
+			ignore_whitespace_change: false,
+			ignore_blank_lines: false,
+			algorithm: None,
 			context: 3,
 			interhunk_lines: 0,
+			limit: None,
 		}
 	}
 }
 
+impl DiffOptions {
+	/// the options deciding which lines match up
+	pub(crate) fn apply_matching(
+		self,
+		repo: &Repository,
+		opt: &mut git2::DiffOptions,
+	) {
+		opt.ignore_whitespace(self.ignore_whitespace);
+		opt.ignore_whitespace_change(self.ignore_whitespace_change);
+		opt.ignore_blank_lines(self.ignore_blank_lines);
+
+		let algorithm = self.algorithm.unwrap_or_else(|| {
+			diff_algorithm_config_repo(repo).unwrap_or_default()
+		});
+		opt.minimal(algorithm == DiffAlgorithm::Minimal);
+		opt.patience(matches!(
+			algorithm,
+			DiffAlgorithm::Patience | DiffAlgorithm::Histogram
+		));
+	}
+
+	pub(crate) fn apply_to(
+		self,
+		repo: &Repository,
+		opt: &mut git2::DiffOptions,
+	) {
+		opt.context_lines(self.context);
+		self.apply_matching(repo, opt);
+		opt.interhunk_lines(self.interhunk_lines);
+	}
+}
+
 pub(crate) fn get_diff_raw<'a>(
 	repo: &'a Repository,
 	p: &str,
@@ -166,9 +291,7 @@ pub(crate) fn get_diff_raw<'a>(
 
 	let mut opt = git2::DiffOptions::new();
 	if let Some(options) = options {
-		opt.context_lines(options.context);
-		opt.ignore_whitespace(options.ignore_whitespace);
-		opt.interhunk_lines(options.interhunk_lines);
+		options.apply_to(repo, &mut opt);
 	}
 	opt.pathspec(p);
 	opt.reverse(reverse);
@@ -213,7 +336,7 @@ pub fn get_diff(
 	let work_dir = work_dir(&repo)?;
 	let diff = get_diff_raw(&repo, p, stage, false, options)?;
 
-	raw_diff_to_file_diff(&diff, work_dir)
+	raw_diff_to_file_diff(&repo, &diff, work_dir, options)
 }
 
 /// returns diff of a specific file inside a commit
@@ -236,7 +359,39 @@ pub fn get_diff_commit(
 		Some(&get_stashes(repo_path)?.into_iter().collect()),
 	)?;
 
-	raw_diff_to_file_diff(&diff, work_dir)
+	raw_diff_to_file_diff(&repo, &diff, work_dir, options)
+}
+
+/// returns diff of a specific file inside a merge commit against the
+/// parent(s) selected by `mode`
+pub fn get_diff_merge(
+	repo_path: &RepoPath,
+	id: CommitId,
+	mode: MergeDiffMode,
+	p: &str,
+	options: Option<DiffOptions>,
+) -> Result<FileDiff> {
+	scope_time!("get_diff_merge");
+
+	let repo = repo(repo_path)?;
+
+	match mode {
+		MergeDiffMode::Parent(parent) => {
+			let work_dir = work_dir(&repo)?;
+			let diff = get_commit_parent_diff(
+				&repo,
+				id,
+				parent,
+				Some(p),
+				options,
+			)?;
+
+			raw_diff_to_file_diff(&repo, &diff, work_dir, options)
+		}
+		MergeDiffMode::Combined => {
+			combined_file_diff(&repo, id, p, options)
+		}
+	}
 }
 
 /// get file changes of a diff between two commits
@@ -253,20 +408,80 @@ pub fn get_diff_commits(
 	let diff =
 		get_compare_commits_diff(&repo, ids, Some(p), options)?;
 
-	raw_diff_to_file_diff(&diff, work_dir)
+	raw_diff_to_file_diff(&repo, &diff, work_dir, options)
+}
+
+/// returns the changes restoring file `p` from commit `id` would
+/// apply to the workdir (workdir being the old and `id` the new side)
+pub fn get_diff_restore(
+	repo_path: &RepoPath,
+	id: CommitId,
+	p: &str,
+	options: Option<DiffOptions>,
+) -> Result<FileDiff> {
+	scope_time!("get_diff_restore");
+
+	let repo = repo(repo_path)?;
+	let work_dir = work_dir(&repo)?;
+
+	let tree = repo.find_commit(id.into())?.tree()?;
+	let diff = get_restore_diff(&repo, &tree, p, options)?;
+
+	raw_diff_to_file_diff(&repo, &diff, work_dir, options)
+}
+
+/// returns the changes applying file `p` from `stash` would make to
+/// the workdir, including the untracked files of the stash
+pub fn get_diff_stash_worktree(
+	repo_path: &RepoPath,
+	stash: CommitId,
+	p: &str,
+	options: Option<DiffOptions>,
+) -> Result<FileDiff> {
+	scope_time!("get_diff_stash_worktree");
+
+	let repo = repo(repo_path)?;
+	let work_dir = work_dir(&repo)?;
+
+	let tree = stash_full_tree(&repo, stash)?;
+	let diff = get_restore_diff(&repo, &tree, p, options)?;
+
+	raw_diff_to_file_diff(&repo, &diff, work_dir, options)
+}
+
+/// diff of the workdir (old side) to file `p` in `tree` (new side)
+pub(super) fn get_restore_diff<'a>(
+	repo: &'a Repository,
+	tree: &Tree,
+	p: &str,
+	options: Option<DiffOptions>,
+) -> Result<Diff<'a>> {
+	let mut opt = git2::DiffOptions::new();
+	if let Some(options) = options {
+		options.apply_to(repo, &mut opt);
+	}
+	opt.pathspec(p);
+	opt.reverse(true);
+	opt.include_untracked(true);
+
+	Ok(repo.diff_tree_to_workdir(Some(tree), Some(&mut opt))?)
 }
 
 ///
 //TODO: refactor into helper type with the inline closures as dedicated functions
 #[allow(clippy::too_many_lines)]
 fn raw_diff_to_file_diff(
+	repo: &Repository,
 	diff: &Diff,
 	work_dir: &Path,
+	options: Option<DiffOptions>,
 ) -> Result<FileDiff> {
 	let res = Rc::new(RefCell::new(FileDiff::default()));
 	{
 		let mut current_lines = Vec::new();
 		let mut current_hunk: Option<HunkHeader> = None;
+		let mut budget =
+			DiffBudget::new(options.and_then(|o| o.limit));
 
 		let res_cell = Rc::clone(&res);
 		let adder = move |header: &HunkHeader,
@@ -282,7 +497,8 @@ fn raw_diff_to_file_diff(
 		let res_cell = Rc::clone(&res);
 		let mut put = |delta: DiffDelta,
 		               hunk: Option<DiffHunk>,
-		               line: git2::DiffLine| {
+		               line: git2::DiffLine|
+		 -> bool {
 			{
 				let mut res = res_cell.borrow_mut();
 				res.sizes = (
@@ -316,11 +532,22 @@ fn raw_diff_to_file_diff(
 					line_type: line.origin_value().into(),
 				};
 
+				if !budget.take(&diff_line) {
+					res_cell.borrow_mut().truncated = true;
+					return false;
+				}
+
 				current_lines.push(diff_line);
 			}
+
+			true
 		};
 
-		let new_file_diff = if diff.deltas().len() == 1 {
+		let textconv = textconv_diff(repo, work_dir, diff)?;
+
+		let new_file_diff = if textconv.is_some() {
+			false
+		} else if diff.deltas().len() == 1 {
 			if let Some(delta) = diff.deltas().next() {
 				if delta.status() == Delta::Untracked {
 					let relative_path =
@@ -344,14 +571,13 @@ fn raw_diff_to_file_diff(
 							None,
 						)?;
 
-						patch.print(
+						ignore_truncation(patch.print(
 							&mut |delta,
 							      hunk: Option<DiffHunk>,
 							      line: git2::DiffLine| {
-								put(delta, hunk, line);
-								true
+								put(delta, hunk, line)
 							},
-						)?;
+						))?;
 
 						true
 					} else {
@@ -367,14 +593,35 @@ fn raw_diff_to_file_diff(
 			false
 		};
 
-		if !new_file_diff {
-			diff.print(
+		if let Some(textconv) = &textconv {
+			let mut opt = git2::DiffOptions::new();
+			if let Some(options) = options {
+				options.apply_to(repo, &mut opt);
+			}
+			opt.force_text(true);
+
+			let mut patch = Patch::from_buffers(
+				&textconv.old,
+				Some(&textconv.path),
+				&textconv.new,
+				Some(&textconv.path),
+				Some(&mut opt),
+			)?;
+
+			ignore_truncation(patch.print(
+				&mut |delta,
+				      hunk: Option<DiffHunk>,
+				      line: git2::DiffLine| {
+					put(delta, hunk, line)
+				},
+			))?;
+		} else if !new_file_diff {
+			ignore_truncation(diff.print(
 				DiffFormat::Patch,
 				move |delta, hunk, line: git2::DiffLine| {
-					put(delta, hunk, line);
-					true
+					put(delta, hunk, line)
 				},
-			)?;
+			))?;
 		}
 
 		if !current_lines.is_empty() {
@@ -390,10 +637,36 @@ fn raw_diff_to_file_diff(
 		if new_file_diff {
 			res.borrow_mut().untracked = true;
 		}
+
+		if let Some(textconv) = textconv {
+			let mut res = res.borrow_mut();
+			res.untracked =
+				diff.deltas().next().is_some_and(|delta| {
+					delta.status() == Delta::Untracked
+				});
+			res.sizes = textconv.sizes;
+			res.size_delta = (i64::conv(res.sizes.1))
+				.saturating_sub(i64::conv(res.sizes.0));
+			res.textconv = Some(textconv.driver);
+		}
+	}
+	let mut res = Rc::try_unwrap(res)
+		.map_err(|_| Error::Generic("rc unwrap error".to_owned()))?
+		.into_inner();
+	res.lfs = lfs_diff(&res);
+
+	Ok(res)
+}
+
+/// printing a diff fails with `ErrorCode::User` if the callback
+/// stopped it once the `DiffLimit` was reached
+fn ignore_truncation(
+	res: std::result::Result<(), git2::Error>,
+) -> Result<()> {
+	match res {
+		Err(e) if e.code() == git2::ErrorCode::User => Ok(()),
+		res => Ok(res?),
 	}
-	let res = Rc::try_unwrap(res)
-		.map_err(|_| Error::Generic("rc unwrap error".to_owned()))?;
-	Ok(res.into_inner())
 }
 
 const fn is_newline(c: char) -> bool {
@@ -420,13 +693,20 @@ fn new_file_content(path: &Path) -> Option<Vec<u8>> {
 
 #[cfg(test)]
 mod tests {
-	use super::{get_diff, get_diff_commit};
+	use super::{
+		get_diff, get_diff_commit, get_diff_restore, DiffLimit,
+		DiffLineType, DiffOptions,
+	};
 	use crate::{
 		error::Result,
 		sync::{
 			commit, stage_add_file,
 			status::{get_status, StatusType},
-			tests::{get_statuses, repo_init, repo_init_empty},
+			tests::{
+				get_statuses, repo_init, repo_init_empty,
+				write_commit_file,
+			},
+			utils::repo_write_file,
 			RepoPath,
 		},
 	};
@@ -560,6 +840,88 @@ mod tests {
 		assert_eq!(res.hunks.len(), 2);
 	}
 
+	#[test]
+	fn test_diff_limit() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let content =
+			(0..20).map(|i| format!("{i}\n")).collect::<String>();
+		fs::write(root.join("a.txt"), content).unwrap();
+
+		let limited = |lines| {
+			get_diff(
+				repo_path,
+				"a.txt",
+				false,
+				Some(DiffOptions {
+					limit: Some(DiffLimit {
+						lines,
+						bytes: usize::MAX,
+					}),
+					..DiffOptions::default()
+				}),
+			)
+			.unwrap()
+		};
+
+		let diff = limited(5);
+		assert!(diff.truncated);
+		assert_eq!(diff.lines, 5);
+
+		let diff = limited(100);
+		assert!(!diff.truncated);
+		assert_eq!(diff.lines, 21);
+
+		let diff = get_diff(repo_path, "a.txt", false, None).unwrap();
+		assert!(!diff.truncated);
+		assert_eq!(diff.lines, 21);
+	}
+
+	#[test]
+	fn test_diff_ignore_whitespace_modes() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a b\nc\n", "c1");
+		fs::write(root.join("a.txt"), "a  b\n\nc\n").unwrap();
+
+		let changed_lines = |options: DiffOptions| {
+			get_diff(repo_path, "a.txt", false, Some(options))
+				.unwrap()
+				.hunks
+				.iter()
+				.flat_map(|hunk| hunk.lines.iter())
+				.filter(|line| {
+					matches!(
+						line.line_type,
+						DiffLineType::Add | DiffLineType::Delete
+					)
+				})
+				.map(|line| line.content.to_string())
+				.collect::<Vec<_>>()
+		};
+
+		assert_eq!(changed_lines(DiffOptions::default()).len(), 3);
+		assert_eq!(
+			changed_lines(DiffOptions {
+				ignore_whitespace_change: true,
+				..DiffOptions::default()
+			}),
+			vec![String::new()]
+		);
+		assert!(changed_lines(DiffOptions {
+			ignore_whitespace_change: true,
+			ignore_blank_lines: true,
+			..DiffOptions::default()
+		})
+		.is_empty());
+	}
+
 	#[test]
 	fn test_diff_newfile_in_sub_dir_current_dir() {
 		let file_path = Path::new("foo/foo.txt");
@@ -671,4 +1033,26 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[test]
+	fn test_diff_restore() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 = write_commit_file(&repo, "a.txt", "old\n", "c1");
+		write_commit_file(&repo, "a.txt", "new\n", "c2");
+		repo_write_file(&repo, "a.txt", "wip\n").unwrap();
+
+		let diff =
+			get_diff_restore(repo_path, c1, "a.txt", None).unwrap();
+
+		assert_eq!(diff.hunks.len(), 1);
+		let lines = &diff.hunks[0].lines;
+		assert_eq!(lines[1].line_type, DiffLineType::Delete);
+		assert_eq!(&*lines[1].content, "wip");
+		assert_eq!(lines[2].line_type, DiffLineType::Add);
+		assert_eq!(&*lines[2].content, "old");
+	}
 }
@@ -0,0 +1,207 @@
+//! launching the external tools configured via `diff.tool` and
+//! `merge.tool`. `git difftool`/`git mergetool` do the actual work so
+//! `difftool.<tool>.cmd` and friends are honored like on the command
+//! line.
+
+use super::{
+	commit_files::OldNew, repository::repo, utils::work_dir,
+	CommitId, RepoPath,
+};
+use crate::error::{Error, Result};
+use git2::Repository;
+use std::process::Command;
+
+/// tool `git difftool` uses, falls back to `merge.tool` like git
+pub const CONFIG_DIFF_TOOL: &str = "diff.tool";
+/// tool `git mergetool` uses
+pub const CONFIG_MERGE_TOOL: &str = "merge.tool";
+
+/// hash of the empty tree to diff root commits against
+const EMPTY_TREE: &str = "4b825dc642cb6eb9a060e54bf8d69288fbee4904";
+
+/// which versions of a file to compare
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffToolTarget {
+	/// index against workdir
+	WorkDir,
+	/// `HEAD` against index
+	Stage,
+	/// commit against its first parent
+	Commit(CommitId),
+	/// two commits
+	Commits(OldNew<CommitId>),
+}
+
+fn config_tool(repo: &Repository, keys: &[&str]) -> Option<String> {
+	let config = repo.config().ok()?;
+
+	keys.iter()
+		.find_map(|key| config.get_string(key).ok())
+		.filter(|tool| !tool.is_empty())
+}
+
+/// `diff.tool` or `merge.tool`, `None` if neither is set
+pub fn configured_diff_tool(
+	repo_path: &RepoPath,
+) -> Result<Option<String>> {
+	let repo = repo(repo_path)?;
+
+	Ok(config_tool(&repo, &[CONFIG_DIFF_TOOL, CONFIG_MERGE_TOOL]))
+}
+
+/// `merge.tool`, `None` if not set
+pub fn configured_merge_tool(
+	repo_path: &RepoPath,
+) -> Result<Option<String>> {
+	let repo = repo(repo_path)?;
+
+	Ok(config_tool(&repo, &[CONFIG_MERGE_TOOL]))
+}
+
+fn git_command(repo: &Repository) -> Result<Command> {
+	let mut cmd = Command::new("git");
+	cmd.current_dir(work_dir(repo)?);
+
+	Ok(cmd)
+}
+
+/// `git difftool` opening `path` in the configured tool, to be run
+/// with the terminal handed over to it
+pub fn difftool_command(
+	repo_path: &RepoPath,
+	path: &str,
+	target: &DiffToolTarget,
+) -> Result<Command> {
+	let repo = repo(repo_path)?;
+
+	let tool =
+		config_tool(&repo, &[CONFIG_DIFF_TOOL, CONFIG_MERGE_TOOL])
+			.ok_or_else(|| {
+				Error::Generic(format!(
+				"no difftool configured, set `{CONFIG_DIFF_TOOL}`"
+			))
+			})?;
+
+	let revisions = match target {
+		DiffToolTarget::WorkDir => Vec::new(),
+		DiffToolTarget::Stage => vec![String::from("--cached")],
+		DiffToolTarget::Commit(id) => {
+			let commit = repo.find_commit((*id).into())?;
+			let parent = commit.parent_id(0).map_or_else(
+				|_| String::from(EMPTY_TREE),
+				|parent| parent.to_string(),
+			);
+
+			vec![parent, id.to_string()]
+		}
+		DiffToolTarget::Commits(ids) => {
+			vec![ids.old.to_string(), ids.new.to_string()]
+		}
+	};
+
+	let mut cmd = git_command(&repo)?;
+	cmd.args(["difftool", "--no-prompt"])
+		.arg(format!("--tool={tool}"))
+		.args(revisions)
+		.args(["--", path]);
+
+	Ok(cmd)
+}
+
+/// `git mergetool` resolving the conflicts of `path` in the
+/// configured tool, to be run with the terminal handed over to it
+pub fn mergetool_command(
+	repo_path: &RepoPath,
+	path: &str,
+) -> Result<Command> {
+	let repo = repo(repo_path)?;
+
+	let tool = config_tool(&repo, &[CONFIG_MERGE_TOOL]).ok_or_else(
+		|| {
+			Error::Generic(format!(
+				"no mergetool configured, set `{CONFIG_MERGE_TOOL}`"
+			))
+		},
+	)?;
+
+	let mut cmd = git_command(&repo)?;
+	cmd.args(["mergetool", "--no-prompt"])
+		.arg(format!("--tool={tool}"))
+		.args(["--", path]);
+
+	Ok(cmd)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::tests::{repo_init, write_commit_file};
+
+	fn args(cmd: &Command) -> Vec<String> {
+		cmd.get_args()
+			.map(|arg| arg.to_string_lossy().to_string())
+			.collect()
+	}
+
+	#[test]
+	fn test_difftool_command() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 = write_commit_file(&repo, "a.txt", "a", "c1");
+		let c2 = write_commit_file(&repo, "a.txt", "b", "c2");
+
+		repo.config()
+			.unwrap()
+			.set_str(CONFIG_MERGE_TOOL, "meld")
+			.unwrap();
+		assert_eq!(
+			configured_diff_tool(repo_path).unwrap().as_deref(),
+			Some("meld")
+		);
+
+		repo.config()
+			.unwrap()
+			.set_str(CONFIG_DIFF_TOOL, "vimdiff")
+			.unwrap();
+
+		let cmd = difftool_command(
+			repo_path,
+			"a.txt",
+			&DiffToolTarget::Commit(c2),
+		)
+		.unwrap();
+		assert_eq!(
+			args(&cmd),
+			vec![
+				String::from("difftool"),
+				String::from("--no-prompt"),
+				String::from("--tool=vimdiff"),
+				c1.to_string(),
+				c2.to_string(),
+				String::from("--"),
+				String::from("a.txt"),
+			]
+		);
+
+		let cmd = difftool_command(
+			repo_path,
+			"a.txt",
+			&DiffToolTarget::Stage,
+		)
+		.unwrap();
+		assert!(args(&cmd).contains(&String::from("--cached")));
+
+		let cmd = mergetool_command(repo_path, "a.txt").unwrap();
+		assert_eq!(
+			args(&cmd)[..3],
+			[
+				String::from("mergetool"),
+				String::from("--no-prompt"),
+				String::from("--tool=meld"),
+			]
+		);
+	}
+}
@@ -0,0 +1,279 @@
+//! backups of working tree content taken before discarding changes,
+//! kept like stashes in the reflog of `refs/gitui/discard-backup`
+
+use super::{
+	commit::signature_allow_undefined_name, repository::repo,
+	CommitId, RepoPath,
+};
+use crate::error::{Error, Result};
+use git2::{build::CheckoutBuilder, IndexAddOption};
+use scopetime::scope_time;
+
+const BACKUP_REF: &str = "refs/gitui/discard-backup";
+const MAX_BACKUPS: usize = 50;
+
+/// content of a path before changes to it were discarded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscardBackup {
+	///
+	pub id: CommitId,
+	/// files or folders the backup covers, comma separated
+	pub path: String,
+	/// what was discarded
+	pub description: String,
+	///
+	pub time: i64,
+}
+
+/// stores the working tree content of `path` (a file or folder,
+/// including untracked files) before it gets discarded
+pub fn discard_backup(
+	repo_path: &RepoPath,
+	path: &str,
+	description: &str,
+) -> Result<CommitId> {
+	scope_time!("discard_backup");
+
+	backup_paths(
+		repo_path,
+		&[path],
+		description,
+		IndexAddOption::DEFAULT,
+	)
+}
+
+/// stores the working tree content of several paths before they get
+/// removed, ignored files included
+pub fn discard_backup_paths(
+	repo_path: &RepoPath,
+	paths: &[String],
+	description: &str,
+) -> Result<CommitId> {
+	scope_time!("discard_backup_paths");
+
+	let paths = paths
+		.iter()
+		.map(|path| path.trim_end_matches('/'))
+		.collect::<Vec<_>>();
+
+	backup_paths(
+		repo_path,
+		&paths,
+		description,
+		IndexAddOption::FORCE,
+	)
+}
+
+fn backup_paths(
+	repo_path: &RepoPath,
+	paths: &[&str],
+	description: &str,
+	add_option: IndexAddOption,
+) -> Result<CommitId> {
+	let repo = repo(repo_path)?;
+	let signature = signature_allow_undefined_name(&repo)?;
+
+	let head =
+		repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+	let parents = head.iter().collect::<Vec<_>>();
+
+	// only in memory, the index on disk stays untouched
+	let mut index = repo.index()?;
+	index.add_all(paths.iter().copied(), add_option, None)?;
+	index.update_all(paths.iter().copied(), None)?;
+	let tree = repo.find_tree(index.write_tree()?)?;
+	index.read(true)?;
+
+	let id = repo.commit(
+		None,
+		&signature,
+		&signature,
+		&format!("{description}\n\n{}\n", paths.join("\n")),
+		&tree,
+		&parents,
+	)?;
+
+	repo.reference_ensure_log(BACKUP_REF)?;
+	repo.reference(BACKUP_REF, id, true, description)?;
+
+	let mut reflog = repo.reflog(BACKUP_REF)?;
+	while reflog.len() > MAX_BACKUPS {
+		reflog.remove(reflog.len() - 1, false)?;
+	}
+	reflog.write()?;
+
+	Ok(id.into())
+}
+
+/// recorded backups, newest first
+pub fn discard_backups(
+	repo_path: &RepoPath,
+) -> Result<Vec<DiscardBackup>> {
+	scope_time!("discard_backups");
+
+	let repo = repo(repo_path)?;
+
+	if repo.find_reference(BACKUP_REF).is_err() {
+		return Ok(Vec::new());
+	}
+
+	repo.reflog(BACKUP_REF)?
+		.iter()
+		.map(|entry| {
+			let commit = repo.find_commit(entry.id_new())?;
+
+			Ok(DiscardBackup {
+				id: entry.id_new().into(),
+				path: backup_paths_of(
+					commit.message().unwrap_or_default(),
+				)
+				.join(", "),
+				description: entry
+					.message()
+					.unwrap_or_default()
+					.to_string(),
+				time: entry.committer().when().seconds(),
+			})
+		})
+		.collect()
+}
+
+/// writes the backed up content of its path back into the working
+/// tree, the index is left alone
+pub fn discard_backup_restore(
+	repo_path: &RepoPath,
+	backup: CommitId,
+) -> Result<()> {
+	scope_time!("discard_backup_restore");
+
+	let repo = repo(repo_path)?;
+	let commit = repo.find_commit(backup.into())?;
+	let paths = backup_paths_of(commit.message().unwrap_or_default());
+
+	if paths.is_empty() {
+		return Err(Error::Generic(String::from(
+			"invalid discard backup",
+		)));
+	}
+
+	let mut checkout = CheckoutBuilder::new();
+	checkout.force().update_index(false);
+	for path in paths {
+		checkout.path(path);
+	}
+
+	repo.checkout_tree(commit.as_object(), Some(&mut checkout))?;
+
+	Ok(())
+}
+
+fn backup_paths_of(msg: &str) -> Vec<&str> {
+	msg.lines()
+		.skip(2)
+		.filter(|path| !path.is_empty())
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		diff::DiffLinePosition,
+		discard_lines, reset_workdir,
+		tests::{get_statuses, repo_init, write_commit_file},
+		utils::repo_write_file,
+	};
+	use std::fs;
+
+	#[test]
+	fn test_restore_discarded_file() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "c1");
+		fs::write(root.join("a.txt"), "changed").unwrap();
+
+		discard_backup(repo_path, "a.txt", "discard a.txt").unwrap();
+		reset_workdir(repo_path, "a.txt").unwrap();
+		assert_eq!(
+			fs::read_to_string(root.join("a.txt")).unwrap(),
+			"a"
+		);
+
+		let backups = discard_backups(repo_path).unwrap();
+		assert_eq!(backups.len(), 1);
+		assert_eq!(backups[0].path, "a.txt");
+		assert_eq!(backups[0].description, "discard a.txt");
+
+		discard_backup_restore(repo_path, backups[0].id).unwrap();
+
+		assert_eq!(
+			fs::read_to_string(root.join("a.txt")).unwrap(),
+			"changed"
+		);
+		// only the working tree is restored
+		assert_eq!(get_statuses(repo_path), (1, 0));
+	}
+
+	#[test]
+	fn test_restore_discarded_untracked_folder() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "c1");
+		fs::create_dir(root.join("dir")).unwrap();
+		fs::write(root.join("dir/new.txt"), "new").unwrap();
+		fs::write(root.join("other.txt"), "other").unwrap();
+
+		let id =
+			discard_backup(repo_path, "dir", "discard dir").unwrap();
+		reset_workdir(repo_path, "dir").unwrap();
+		assert!(!root.join("dir/new.txt").exists());
+
+		discard_backup_restore(repo_path, id).unwrap();
+
+		assert_eq!(
+			fs::read_to_string(root.join("dir/new.txt")).unwrap(),
+			"new"
+		);
+		assert_eq!(get_statuses(repo_path), (2, 0));
+	}
+
+	#[test]
+	fn test_restore_discarded_lines() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "1\n2\n", "c1");
+		repo_write_file(&repo, "a.txt", "1\nx\n2\n").unwrap();
+
+		discard_backup(repo_path, "a.txt", "discard lines").unwrap();
+		discard_lines(
+			repo_path,
+			"a.txt",
+			&[DiffLinePosition {
+				old_lineno: None,
+				new_lineno: Some(2),
+			}],
+		)
+		.unwrap();
+		assert_eq!(
+			fs::read_to_string(root.join("a.txt")).unwrap(),
+			"1\n2\n"
+		);
+
+		let backups = discard_backups(repo_path).unwrap();
+		discard_backup_restore(repo_path, backups[0].id).unwrap();
+
+		assert_eq!(
+			fs::read_to_string(root.join("a.txt")).unwrap(),
+			"1\nx\n2\n"
+		);
+	}
+}
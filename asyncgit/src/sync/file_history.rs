@@ -0,0 +1,761 @@
+//! history of a single file following renames, like
+//! `git log --follow -- <path>`, and of a range of lines in it, like
+//! `git log -L <start>,<end>:<path>`
+
+use super::{repository::repo, CommitId, RepoPath};
+use crate::error::Result;
+use git2::{
+	Commit, DiffFindOptions, DiffOptions, Oid, Patch, Repository,
+	Sort,
+};
+use scopetime::scope_time;
+use std::{collections::HashMap, path::Path};
+
+/// how a commit changed the file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileHistoryChange {
+	///
+	Added,
+	///
+	Modified,
+	///
+	Deleted,
+	/// renamed from the contained path
+	Renamed(String),
+}
+
+/// commit touching the followed file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileHistoryEntry {
+	///
+	pub commit: CommitId,
+	/// path of the file in this commit
+	pub path: String,
+	///
+	pub change: FileHistoryChange,
+	/// followed lines in this commit (1-based, inclusive), only set
+	/// by [`line_history`]
+	pub lines: Option<(usize, usize)>,
+}
+
+/// commits reachable from `HEAD` changing `path` (newest first).
+/// when the file was added by renaming another one, the history of
+/// the old path is followed from there on.
+///
+/// merges are only listed if they differ from all of their parents.
+pub fn file_history(
+	repo_path: &RepoPath,
+	path: &str,
+) -> Result<Vec<FileHistoryEntry>> {
+	let mut entries = Vec::new();
+	file_history_walk(repo_path, path, |entry| {
+		entries.push(entry);
+		true
+	})?;
+
+	Ok(entries)
+}
+
+/// like [`file_history`] but hands every entry to `on_entry` as soon
+/// as the walk got to it, the walk stops once `on_entry` returns
+/// `false`
+pub fn file_history_walk(
+	repo_path: &RepoPath,
+	path: &str,
+	mut on_entry: impl FnMut(FileHistoryEntry) -> bool,
+) -> Result<()> {
+	scope_time!("file_history");
+
+	let repo = repo(repo_path)?;
+
+	let mut walk = repo.revwalk()?;
+	walk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+	walk.push_head()?;
+
+	let mut path = path.to_string();
+
+	for id in walk {
+		let commit = repo.find_commit(id?)?;
+		let blob = blob_at(&commit, &path);
+
+		let parents = commit.parents().collect::<Vec<_>>();
+		let parent_blobs = parents
+			.iter()
+			.map(|parent| blob_at(parent, &path))
+			.collect::<Vec<_>>();
+
+		if parent_blobs.contains(&blob) {
+			continue;
+		}
+
+		let change =
+			match (blob, parent_blobs.first().copied().flatten()) {
+				(None, None) => continue,
+				(None, Some(_)) => FileHistoryChange::Deleted,
+				(Some(_), Some(_)) => FileHistoryChange::Modified,
+				(Some(_), None) => renamed_from(
+					&repo,
+					parents.first(),
+					&commit,
+					&path,
+				)?
+				.map_or(
+					FileHistoryChange::Added,
+					FileHistoryChange::Renamed,
+				),
+			};
+
+		let keep_walking = on_entry(FileHistoryEntry {
+			commit: commit.id().into(),
+			path: path.clone(),
+			change: change.clone(),
+			lines: None,
+		});
+
+		if !keep_walking {
+			break;
+		}
+
+		if let FileHistoryChange::Renamed(old_path) = change {
+			path = old_path;
+		}
+	}
+
+	Ok(())
+}
+
+/// commits reachable from `start` (or `HEAD`) changing any of the
+/// `lines` (1-based, inclusive) of `path` as they are in `start`,
+/// newest first.
+///
+/// the range is mapped through every commit so it keeps following
+/// the same lines, also across renames of the file.
+///
+/// a commit is left out if the lines are unchanged compared to any of
+/// its parents, the commit introducing them ends the history.
+pub fn line_history(
+	repo_path: &RepoPath,
+	path: &str,
+	start: Option<CommitId>,
+	lines: (usize, usize),
+) -> Result<Vec<FileHistoryEntry>> {
+	let mut entries = Vec::new();
+	line_history_walk(repo_path, path, start, lines, |entry| {
+		entries.push(entry);
+		true
+	})?;
+
+	Ok(entries)
+}
+
+/// like [`line_history`] but hands every entry to `on_entry` as soon
+/// as the walk got to it, the walk stops once `on_entry` returns
+/// `false`
+pub fn line_history_walk(
+	repo_path: &RepoPath,
+	path: &str,
+	start: Option<CommitId>,
+	lines: (usize, usize),
+	mut on_entry: impl FnMut(FileHistoryEntry) -> bool,
+) -> Result<()> {
+	scope_time!("line_history");
+
+	let repo = repo(repo_path)?;
+
+	let start = match start {
+		Some(id) => id.into(),
+		None => repo.head()?.peel_to_commit()?.id(),
+	};
+
+	let mut walk = repo.revwalk()?;
+	walk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+	walk.push(start)?;
+
+	// lines followed into each commit, set by its children
+	let mut followed = HashMap::new();
+	followed.insert(start, (path.to_string(), lines));
+
+	for id in walk {
+		let id = id?;
+		let Some((path, lines)) = followed.remove(&id) else {
+			continue;
+		};
+
+		let commit = repo.find_commit(id)?;
+		let Some(blob) = blob_at(&commit, &path) else {
+			continue;
+		};
+
+		let mut parents = Vec::new();
+		for parent in commit.parents() {
+			let parent_path = if blob_at(&parent, &path).is_some() {
+				Some(path.clone())
+			} else {
+				renamed_from(&repo, Some(&parent), &commit, &path)?
+			};
+
+			let mapped = match parent_path.as_ref().and_then(|p| {
+				blob_at(&parent, p)
+					.map(|parent_blob| (p, parent_blob))
+			}) {
+				Some((parent_path, parent_blob)) => {
+					let (changed, parent_lines) =
+						map_lines(&repo, parent_blob, blob, lines)?;
+					parent_lines.map(|parent_lines| {
+						(parent_path.clone(), parent_lines, changed)
+					})
+				}
+				None => None,
+			};
+
+			parents.push((parent.id(), mapped));
+		}
+
+		if let Some((parent, mapped)) =
+			parents.iter().find_map(|(parent, mapped)| {
+				mapped
+					.as_ref()
+					.filter(|(_, _, changed)| !changed)
+					.map(|mapped| (parent, mapped))
+			}) {
+			followed
+				.entry(*parent)
+				.or_insert_with(|| (mapped.0.clone(), mapped.1));
+			continue;
+		}
+
+		let change = match parents.first() {
+			Some((_, Some((parent_path, _, _))))
+				if *parent_path != path =>
+			{
+				FileHistoryChange::Renamed(parent_path.clone())
+			}
+			_ if parents
+				.iter()
+				.all(|(_, mapped)| mapped.is_none()) =>
+			{
+				FileHistoryChange::Added
+			}
+			_ => FileHistoryChange::Modified,
+		};
+
+		let keep_walking = on_entry(FileHistoryEntry {
+			commit: id.into(),
+			path: path.clone(),
+			change,
+			lines: Some(lines),
+		});
+
+		if !keep_walking {
+			break;
+		}
+
+		for (parent, mapped) in parents {
+			if let Some((parent_path, parent_lines, _)) = mapped {
+				followed
+					.entry(parent)
+					.or_insert((parent_path, parent_lines));
+			}
+		}
+	}
+
+	Ok(())
+}
+
+/// whether the change from `old` to `new` touches `lines` of `new`,
+/// and where these lines are in `old` (`None` if they were all added)
+fn map_lines(
+	repo: &Repository,
+	old: Oid,
+	new: Oid,
+	lines: (usize, usize),
+) -> Result<(bool, Option<(usize, usize)>)> {
+	if old == new {
+		return Ok((false, Some(lines)));
+	}
+
+	let hunks = hunk_ranges(repo, old, new)?;
+
+	let (start, end) = lines;
+
+	let changed = hunks.iter().any(|hunk| {
+		if hunk.new_lines == 0 {
+			// lines removed after `new_start`
+			hunk.new_start >= start && hunk.new_start < end
+		} else {
+			hunk.new_start <= end
+				&& hunk.new_start + hunk.new_lines > start
+		}
+	});
+
+	let old_start = map_line(&hunks, start, false);
+	let old_end = map_line(&hunks, end, true);
+
+	Ok((
+		changed,
+		(old_start <= old_end).then_some((old_start, old_end)),
+	))
+}
+
+/// where `line` (1-based) of `path` in `commit` was in `parent`,
+/// `None` if the file did not exist there. lines added by `commit`
+/// map to the position they were inserted at.
+pub(super) fn line_in_parent(
+	repo: &Repository,
+	parent: &Commit,
+	commit: &Commit,
+	path: &str,
+	line: usize,
+) -> Result<Option<(String, usize)>> {
+	Ok(parent_hunks(repo, parent, commit, path)?.map(
+		|(parent_path, hunks)| {
+			(parent_path, map_line(&hunks, line, false).max(1))
+		},
+	))
+}
+
+/// like [`line_in_parent`] but for lines changed by `commit` it is
+/// the line they replaced, pairing the lines of a hunk in order.
+/// `None` for lines added by `commit`, including the lines of a hunk
+/// exceeding the ones it replaced.
+pub(super) fn line_origin_in_parent(
+	repo: &Repository,
+	parent: &Commit,
+	commit: &Commit,
+	path: &str,
+	line: usize,
+) -> Result<Option<(String, usize)>> {
+	let Some((parent_path, hunks)) =
+		parent_hunks(repo, parent, commit, path)?
+	else {
+		return Ok(None);
+	};
+
+	let hunk = hunks.iter().find(|hunk| {
+		(hunk.new_start..hunk.new_start + hunk.new_lines)
+			.contains(&line)
+	});
+
+	Ok(match hunk {
+		Some(hunk) => {
+			(line - hunk.new_start < hunk.old_lines).then(|| {
+				(parent_path, hunk.old_start + line - hunk.new_start)
+			})
+		}
+		None => Some((parent_path, map_line(&hunks, line, false))),
+	})
+}
+
+/// path of the file in `parent` and the changes `commit` made to it
+fn parent_hunks(
+	repo: &Repository,
+	parent: &Commit,
+	commit: &Commit,
+	path: &str,
+) -> Result<Option<(String, Vec<HunkRange>)>> {
+	let Some(blob) = blob_at(commit, path) else {
+		return Ok(None);
+	};
+
+	let parent_path = if blob_at(parent, path).is_some() {
+		Some(path.to_string())
+	} else {
+		renamed_from(repo, Some(parent), commit, path)?
+	};
+
+	let Some((parent_path, parent_blob)) =
+		parent_path.and_then(|parent_path| {
+			blob_at(parent, &parent_path)
+				.map(|parent_blob| (parent_path, parent_blob))
+		})
+	else {
+		return Ok(None);
+	};
+
+	let hunks = if parent_blob == blob {
+		Vec::new()
+	} else {
+		hunk_ranges(repo, parent_blob, blob)?
+	};
+
+	Ok(Some((parent_path, hunks)))
+}
+
+fn hunk_ranges(
+	repo: &Repository,
+	old: Oid,
+	new: Oid,
+) -> Result<Vec<HunkRange>> {
+	let old = repo.find_blob(old)?;
+	let new = repo.find_blob(new)?;
+
+	let mut opts = DiffOptions::new();
+	opts.context_lines(0);
+
+	let patch =
+		Patch::from_blobs(&old, None, &new, None, Some(&mut opts))?;
+
+	let mut hunks = Vec::with_capacity(patch.num_hunks());
+	for idx in 0..patch.num_hunks() {
+		let (hunk, _) = patch.hunk(idx)?;
+		hunks.push(HunkRange {
+			old_start: hunk.old_start() as usize,
+			old_lines: hunk.old_lines() as usize,
+			new_start: hunk.new_start() as usize,
+			new_lines: hunk.new_lines() as usize,
+		});
+	}
+
+	Ok(hunks)
+}
+
+struct HunkRange {
+	old_start: usize,
+	old_lines: usize,
+	new_start: usize,
+	new_lines: usize,
+}
+
+/// maps `line` of the new version to the old one.
+///
+/// lines changed by a hunk map to the first (or for the `end` of a
+/// range the last) line the hunk replaced
+fn map_line(hunks: &[HunkRange], line: usize, end: bool) -> usize {
+	let mut removed = 0;
+	let mut added = 0;
+
+	for hunk in hunks {
+		if hunk.new_lines > 0
+			&& (hunk.new_start..hunk.new_start + hunk.new_lines)
+				.contains(&line)
+		{
+			return match (end, hunk.old_lines) {
+				(false, 0) => hunk.old_start + 1,
+				(false, _) => hunk.old_start,
+				(true, lines) => (hunk.old_start + lines)
+					.saturating_sub(1)
+					.max(hunk.old_start),
+			};
+		}
+
+		let last_line = if hunk.new_lines == 0 {
+			hunk.new_start
+		} else {
+			hunk.new_start + hunk.new_lines - 1
+		};
+
+		if last_line >= line {
+			break;
+		}
+
+		removed += hunk.old_lines;
+		added += hunk.new_lines;
+	}
+
+	(line + removed).saturating_sub(added)
+}
+
+fn blob_at(commit: &Commit, path: &str) -> Option<Oid> {
+	commit
+		.tree()
+		.ok()?
+		.get_path(Path::new(path))
+		.ok()
+		.map(|entry| entry.id())
+}
+
+/// the path `path` was renamed from in `commit`, if it was
+fn renamed_from(
+	repo: &Repository,
+	parent: Option<&Commit>,
+	commit: &Commit,
+	path: &str,
+) -> Result<Option<String>> {
+	let Some(parent) = parent else {
+		return Ok(None);
+	};
+
+	let mut diff = repo.diff_tree_to_tree(
+		Some(&parent.tree()?),
+		Some(&commit.tree()?),
+		None,
+	)?;
+	diff.find_similar(Some(DiffFindOptions::new().renames(true)))?;
+
+	Ok(diff.deltas().find_map(|delta| {
+		let new_path = delta.new_file().path()?;
+		let old_path = delta.old_file().path()?;
+
+		(delta.status() == git2::Delta::Renamed
+			&& new_path == Path::new(path))
+		.then(|| old_path.to_string_lossy().to_string())
+	}))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		commit, stage_add_file,
+		tests::{repo_init, write_commit_file},
+		utils::repo_write_file,
+	};
+	use std::fs;
+
+	#[test]
+	fn test_follow_rename() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let content = "a\nb\nc\nd\ne\n";
+		let c1 = write_commit_file(&repo, "old.txt", content, "c1");
+		write_commit_file(&repo, "other.txt", "x", "c2");
+		let c3 = write_commit_file(
+			&repo,
+			"old.txt",
+			"a\nb\nc\nd\ne\nf\n",
+			"c3",
+		);
+
+		fs::rename(root.join("old.txt"), root.join("new.txt"))
+			.unwrap();
+		let mut index = repo.index().unwrap();
+		index.remove_path(Path::new("old.txt")).unwrap();
+		index.write().unwrap();
+		stage_add_file(repo_path, Path::new("new.txt")).unwrap();
+		let c4 = commit(repo_path, "rename").unwrap();
+
+		repo_write_file(&repo, "new.txt", "a\nb\nc\nd\ne\nf\ng\n")
+			.unwrap();
+		stage_add_file(repo_path, Path::new("new.txt")).unwrap();
+		let c5 = commit(repo_path, "c5").unwrap();
+
+		let history = file_history(repo_path, "new.txt").unwrap();
+
+		assert_eq!(
+			history,
+			vec![
+				FileHistoryEntry {
+					commit: c5,
+					path: "new.txt".into(),
+					change: FileHistoryChange::Modified,
+					lines: None,
+				},
+				FileHistoryEntry {
+					commit: c4,
+					path: "new.txt".into(),
+					change: FileHistoryChange::Renamed(
+						"old.txt".into()
+					),
+					lines: None,
+				},
+				FileHistoryEntry {
+					commit: c3,
+					path: "old.txt".into(),
+					change: FileHistoryChange::Modified,
+					lines: None,
+				},
+				FileHistoryEntry {
+					commit: c1,
+					path: "old.txt".into(),
+					change: FileHistoryChange::Added,
+					lines: None,
+				},
+			]
+		);
+	}
+
+	#[test]
+	fn test_deleted_file() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 = write_commit_file(&repo, "a.txt", "a", "c1");
+		fs::remove_file(root.join("a.txt")).unwrap();
+		let mut index = repo.index().unwrap();
+		index.remove_path(Path::new("a.txt")).unwrap();
+		index.write().unwrap();
+		let c2 = commit(repo_path, "delete").unwrap();
+
+		let history = file_history(repo_path, "a.txt").unwrap();
+
+		assert_eq!(
+			history
+				.iter()
+				.map(|entry| (entry.commit, entry.change.clone()))
+				.collect::<Vec<_>>(),
+			vec![
+				(c2, FileHistoryChange::Deleted),
+				(c1, FileHistoryChange::Added),
+			]
+		);
+	}
+
+	#[test]
+	fn test_walk_stops() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "c1");
+		write_commit_file(&repo, "a.txt", "b", "c2");
+		let c3 = write_commit_file(&repo, "a.txt", "c", "c3");
+
+		let mut walked = Vec::new();
+		file_history_walk(repo_path, "a.txt", |entry| {
+			walked.push(entry.commit);
+			false
+		})
+		.unwrap();
+
+		assert_eq!(walked, vec![c3]);
+	}
+
+	type Lines = Option<(usize, usize)>;
+
+	fn lines_of(
+		history: &[FileHistoryEntry],
+	) -> Vec<(CommitId, FileHistoryChange, Lines)> {
+		history
+			.iter()
+			.map(|entry| {
+				(entry.commit, entry.change.clone(), entry.lines)
+			})
+			.collect()
+	}
+
+	#[test]
+	fn test_line_history() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 = write_commit_file(
+			&repo,
+			"a.txt",
+			"1\n2\n3\n4\n5\n6\n7\n8\n",
+			"c1",
+		);
+		let c2 = write_commit_file(
+			&repo,
+			"a.txt",
+			"1\nx\n3\n4\n5\n6\n7\n8\n",
+			"c2",
+		);
+		let c3 = write_commit_file(
+			&repo,
+			"a.txt",
+			"1\nx\n3\n4\n5\n6\n7\ny\n",
+			"c3",
+		);
+		write_commit_file(
+			&repo,
+			"a.txt",
+			"new\nnew\n1\nx\n3\n4\n5\n6\n7\ny\n",
+			"c4",
+		);
+
+		let history =
+			line_history(repo_path, "a.txt", None, (10, 10)).unwrap();
+		assert_eq!(
+			lines_of(&history),
+			vec![
+				(c3, FileHistoryChange::Modified, Some((8, 8))),
+				(c1, FileHistoryChange::Added, Some((8, 8))),
+			]
+		);
+
+		let history =
+			line_history(repo_path, "a.txt", None, (4, 4)).unwrap();
+		assert_eq!(
+			lines_of(&history),
+			vec![
+				(c2, FileHistoryChange::Modified, Some((2, 2))),
+				(c1, FileHistoryChange::Added, Some((2, 2))),
+			]
+		);
+
+		let history =
+			line_history(repo_path, "a.txt", Some(c2), (5, 8))
+				.unwrap();
+		assert_eq!(
+			lines_of(&history),
+			vec![(c1, FileHistoryChange::Added, Some((5, 8)))]
+		);
+	}
+
+	#[test]
+	fn test_line_history_removed_lines() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 =
+			write_commit_file(&repo, "a.txt", "1\n2\n3\n4\n", "c1");
+		let c2 = write_commit_file(&repo, "a.txt", "1\n4\n", "c2");
+		let c3 = write_commit_file(&repo, "a.txt", "0\n1\n4\n", "c3");
+
+		let history =
+			line_history(repo_path, "a.txt", None, (2, 3)).unwrap();
+		assert_eq!(
+			lines_of(&history),
+			vec![
+				(c2, FileHistoryChange::Modified, Some((1, 2))),
+				(c1, FileHistoryChange::Added, Some((1, 4))),
+			]
+		);
+
+		let history =
+			line_history(repo_path, "a.txt", None, (1, 1)).unwrap();
+		assert_eq!(
+			lines_of(&history),
+			vec![(c3, FileHistoryChange::Added, Some((1, 1)))]
+		);
+	}
+
+	#[test]
+	fn test_line_history_rename() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 = write_commit_file(
+			&repo,
+			"old.txt",
+			"a\nb\nc\nd\ne\n",
+			"c1",
+		);
+
+		fs::rename(root.join("old.txt"), root.join("new.txt"))
+			.unwrap();
+		let mut index = repo.index().unwrap();
+		index.remove_path(Path::new("old.txt")).unwrap();
+		index.write().unwrap();
+		repo_write_file(&repo, "new.txt", "a\nb\nc\nd\nx\n").unwrap();
+		stage_add_file(repo_path, Path::new("new.txt")).unwrap();
+		let c2 = commit(repo_path, "rename").unwrap();
+
+		let history =
+			line_history(repo_path, "new.txt", None, (4, 5)).unwrap();
+		assert_eq!(
+			lines_of(&history),
+			vec![
+				(
+					c2,
+					FileHistoryChange::Renamed("old.txt".into()),
+					Some((4, 5))
+				),
+				(c1, FileHistoryChange::Added, Some((4, 5))),
+			]
+		);
+		assert_eq!(history[1].path, "old.txt");
+	}
+}
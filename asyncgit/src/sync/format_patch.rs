@@ -0,0 +1,314 @@
+//! exporting commits as patch files like `git format-patch`
+
+use super::{
+	commit::signature_allow_undefined_name, repository::repo,
+	CommitId, RepoPath,
+};
+use crate::error::{Error, Result};
+use git2::{
+	Commit, DiffStatsFormat, Email, EmailCreateOptions, Oid,
+	Repository, Sort,
+};
+use scopetime::scope_time;
+use std::{
+	collections::{BTreeMap, HashSet},
+	fmt::Write,
+	fs,
+	path::{Path, PathBuf},
+};
+
+/// same limit as git for the file names of the patches
+const PATCH_NAME_MAX: usize = 64;
+
+///
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct FormatPatchOptions {
+	/// `[PATCH n/m]` subjects even for a single patch (`-n`),
+	/// otherwise only a series of patches is numbered
+	pub numbered: bool,
+	/// writes a `0000-cover-letter.patch` (`--cover-letter`)
+	pub cover_letter: bool,
+}
+
+/// writes one patch per commit into `out_dir` (relative to the
+/// workdir unless absolute), oldest commit first, returns the
+/// written files
+pub fn format_patches(
+	repo_path: &RepoPath,
+	commits: &[CommitId],
+	out_dir: &Path,
+	options: FormatPatchOptions,
+) -> Result<Vec<PathBuf>> {
+	scope_time!("format_patches");
+
+	let repo = repo(repo_path)?;
+	let commits = oldest_first(&repo, commits)?;
+
+	if commits.is_empty() {
+		return Err(Error::Generic("no commits to export".into()));
+	}
+
+	let out_dir = repo
+		.workdir()
+		.map_or_else(|| out_dir.to_path_buf(), |w| w.join(out_dir));
+	fs::create_dir_all(&out_dir)?;
+
+	let total = commits.len();
+	let mut files = Vec::with_capacity(total + 1);
+
+	if options.cover_letter {
+		let path = out_dir.join(patch_file_name(0, "cover letter"));
+		fs::write(&path, cover_letter(&repo, &commits)?)?;
+		files.push(path);
+	}
+
+	for (idx, commit) in commits.iter().enumerate() {
+		let email = commit_email(
+			&repo,
+			commit,
+			idx + 1,
+			total,
+			options.numbered || options.cover_letter,
+		)?;
+
+		let path = out_dir.join(patch_file_name(
+			idx + 1,
+			commit.summary().unwrap_or_default(),
+		));
+		fs::write(&path, email.as_slice())?;
+		files.push(path);
+	}
+
+	Ok(files)
+}
+
+/// `commits` in topological order, the oldest one first
+pub(super) fn oldest_first<'a>(
+	repo: &'a Repository,
+	commits: &[CommitId],
+) -> Result<Vec<Commit<'a>>> {
+	let mut wanted: HashSet<Oid> =
+		commits.iter().map(|id| (*id).into()).collect();
+
+	let mut walk = repo.revwalk()?;
+	walk.set_sorting(Sort::TOPOLOGICAL)?;
+	for id in &wanted {
+		walk.push(*id)?;
+	}
+
+	let mut sorted = Vec::with_capacity(wanted.len());
+	for id in walk {
+		let id = id?;
+		if wanted.remove(&id) {
+			sorted.push(repo.find_commit(id)?);
+		}
+		if wanted.is_empty() {
+			break;
+		}
+	}
+	sorted.reverse();
+
+	Ok(sorted)
+}
+
+fn commit_email(
+	repo: &Repository,
+	commit: &Commit,
+	idx: usize,
+	total: usize,
+	numbered: bool,
+) -> Result<Email> {
+	if commit.parent_count() > 1 {
+		return Err(Error::Generic(format!(
+			"cannot export merge commit {}",
+			commit.id()
+		)));
+	}
+
+	let parent_tree = commit
+		.parents()
+		.next()
+		.map(|parent| parent.tree())
+		.transpose()?;
+	let diff = repo.diff_tree_to_tree(
+		parent_tree.as_ref(),
+		Some(&commit.tree()?),
+		None,
+	)?;
+
+	let mut options = EmailCreateOptions::new();
+	options.always_number(numbered);
+
+	let body = commit.body().unwrap_or_default().trim_end();
+
+	Ok(Email::from_diff(
+		&diff,
+		idx,
+		total,
+		&commit.id(),
+		commit.summary().unwrap_or_default(),
+		if body.is_empty() {
+			String::new()
+		} else {
+			format!("{body}\n")
+		}
+		.as_str(),
+		&commit.author(),
+		&mut options,
+	)?)
+}
+
+/// template for the series description like git writes it: a
+/// shortlog of the commits and the stats of the whole series
+fn cover_letter(
+	repo: &Repository,
+	commits: &[Commit],
+) -> Result<String> {
+	let (Some(first), Some(last)) = (commits.first(), commits.last())
+	else {
+		return Ok(String::new());
+	};
+
+	let signature = signature_allow_undefined_name(repo)?;
+
+	let mut shortlog: BTreeMap<String, Vec<String>> = BTreeMap::new();
+	for commit in commits {
+		shortlog
+			.entry(commit.author().name().unwrap_or_default().into())
+			.or_default()
+			.push(commit.summary().unwrap_or_default().into());
+	}
+
+	let base_tree =
+		first.parents().next().map(|p| p.tree()).transpose()?;
+	let stats = repo
+		.diff_tree_to_tree(
+			base_tree.as_ref(),
+			Some(&last.tree()?),
+			None,
+		)?
+		.stats()?
+		.to_buf(DiffStatsFormat::FULL, 72)?;
+
+	let mut letter = format!(
+		"From {} Mon Sep 17 00:00:00 2001\nFrom: {} <{}>\nSubject: [PATCH 0/{}] *** SUBJECT HERE ***\n\n*** BLURB HERE ***\n\n",
+		last.id(),
+		signature.name().unwrap_or_default(),
+		signature.email().unwrap_or_default(),
+		commits.len(),
+	);
+
+	for (author, subjects) in shortlog {
+		let _ = writeln!(letter, "{author} ({}):", subjects.len());
+		for subject in subjects {
+			let _ = writeln!(letter, "  {subject}");
+		}
+		letter.push('\n');
+	}
+
+	letter.push_str(stats.as_str().unwrap_or_default());
+	letter.push_str("\n-- \n");
+
+	Ok(letter)
+}
+
+/// `0001-subject-of-the-commit.patch`
+fn patch_file_name(idx: usize, subject: &str) -> String {
+	let mut name = String::new();
+
+	for c in subject.chars() {
+		if c.is_ascii_alphanumeric() || c == '_' || c == '.' {
+			name.push(c);
+		} else if !name.is_empty() && !name.ends_with('-') {
+			name.push('-');
+		}
+	}
+
+	let mut name: String = name
+		.trim_end_matches(['-', '.'])
+		.chars()
+		.take(PATCH_NAME_MAX - "0000-".len() - ".patch".len())
+		.collect();
+	while name.ends_with(['-', '.']) {
+		name.pop();
+	}
+
+	format!("{idx:04}-{name}.patch")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::tests::{repo_init, write_commit_file};
+
+	#[test]
+	fn test_patch_file_name() {
+		assert_eq!(
+			patch_file_name(1, "Fix the thing: (really)!"),
+			"0001-Fix-the-thing-really.patch"
+		);
+		assert_eq!(
+			patch_file_name(0, "cover letter"),
+			"0000-cover-letter.patch"
+		);
+		assert_eq!(
+			patch_file_name(12, "a".repeat(80).as_str()).len(),
+			64
+		);
+	}
+
+	#[test]
+	fn test_format_patches() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 = write_commit_file(&repo, "a.txt", "a\n", "add a");
+		let c2 = write_commit_file(&repo, "a.txt", "a\nb\n", "add b");
+
+		let files = format_patches(
+			repo_path,
+			&[c2, c1],
+			Path::new("patches"),
+			FormatPatchOptions {
+				numbered: false,
+				cover_letter: true,
+			},
+		)
+		.unwrap();
+
+		let names: Vec<_> = files
+			.iter()
+			.map(|f| f.file_name().unwrap().to_str().unwrap())
+			.collect();
+		assert_eq!(
+			names,
+			vec![
+				"0000-cover-letter.patch",
+				"0001-add-a.patch",
+				"0002-add-b.patch"
+			]
+		);
+		assert!(files[0].starts_with(root.join("patches")));
+
+		let cover = fs::read_to_string(&files[0]).unwrap();
+		assert!(cover.contains("[PATCH 0/2]"));
+		assert!(cover.contains("name (2):\n  add a\n  add b\n"));
+
+		let second = fs::read_to_string(&files[2]).unwrap();
+		assert!(second.contains(&format!("From {c2} ")));
+		assert!(second.contains("Subject: [PATCH 2/2] add b"));
+		assert!(second.contains("+b\n"));
+
+		let files = format_patches(
+			repo_path,
+			&[c1],
+			Path::new("single"),
+			FormatPatchOptions::default(),
+		)
+		.unwrap();
+		let single = fs::read_to_string(&files[0]).unwrap();
+		assert!(single.contains("Subject: [PATCH] add a"));
+	}
+}
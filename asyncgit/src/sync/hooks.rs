@@ -1,7 +1,11 @@
 use super::{repository::repo, RepoPath};
 use crate::error::Result;
-pub use git2_hooks::PrepareCommitMsgSource;
+use git2::{Oid, Repository};
+pub use git2_hooks::{
+	HookOutputLine, PrePushRef, PrepareCommitMsgSource,
+};
 use scopetime::scope_time;
+use std::sync::atomic::AtomicBool;
 
 ///
 #[derive(Debug, PartialEq, Eq)]
@@ -22,6 +26,9 @@ impl From<git2_hooks::HookResult> for HookResult {
 				stderr,
 				..
 			} => Self::NotOk(format!("{stdout}{stderr}")),
+			git2_hooks::HookResult::Cancelled { .. } => {
+				Self::NotOk(String::from("hook cancelled"))
+			}
 		}
 	}
 }
@@ -47,6 +54,33 @@ pub fn hooks_pre_commit(repo_path: &RepoPath) -> Result<HookResult> {
 	Ok(git2_hooks::hooks_pre_commit(&repo, None)?.into())
 }
 
+/// see `git2_hooks::hooks_pre_commit_streamed`
+pub fn hooks_pre_commit_streamed(
+	repo_path: &RepoPath,
+	on_output: &mut dyn FnMut(HookOutputLine),
+	cancel: &AtomicBool,
+) -> Result<HookResult> {
+	scope_time!("hooks_pre_commit_streamed");
+
+	let repo = repo(repo_path)?;
+
+	Ok(git2_hooks::hooks_pre_commit_streamed(
+		&repo, None, on_output, cancel,
+	)?
+	.into())
+}
+
+/// whether there is a `pre-commit` hook to run
+pub fn hooks_pre_commit_found(repo_path: &RepoPath) -> Result<bool> {
+	let repo = repo(repo_path)?;
+
+	Ok(git2_hooks::hook_found(
+		&repo,
+		None,
+		git2_hooks::HOOK_PRE_COMMIT,
+	)?)
+}
+
 /// see `git2_hooks::hooks_post_commit`
 pub fn hooks_post_commit(repo_path: &RepoPath) -> Result<HookResult> {
 	scope_time!("hooks_post_commit");
@@ -72,6 +106,66 @@ pub fn hooks_prepare_commit_msg(
 	.into())
 }
 
+/// see `git2_hooks::hooks_pre_push`
+pub fn hooks_pre_push(
+	repo_path: &RepoPath,
+	remote: &str,
+	url: &str,
+	updates: &[PrePushRef],
+) -> Result<HookResult> {
+	scope_time!("hooks_pre_push");
+
+	let repo = repo(repo_path)?;
+
+	Ok(
+		git2_hooks::hooks_pre_push(
+			&repo, None, remote, url, updates,
+		)?
+		.into(),
+	)
+}
+
+/// runs the `post-checkout` hook after HEAD moved from `old_head` to
+/// `new_head`. like in git the hook cannot affect the outcome of the
+/// checkout so a failure only gets logged
+pub fn run_post_checkout_hook(
+	repo: &Repository,
+	old_head: Oid,
+	new_head: Oid,
+) {
+	log_post_hook(
+		git2_hooks::HOOK_POST_CHECKOUT,
+		git2_hooks::hooks_post_checkout(
+			repo, None, old_head, new_head, true,
+		),
+	);
+}
+
+/// runs the `post-merge` hook after a successful merge, a failure
+/// only gets logged
+pub fn run_post_merge_hook(repo: &Repository) {
+	log_post_hook(
+		git2_hooks::HOOK_POST_MERGE,
+		git2_hooks::hooks_post_merge(repo, None, false),
+	);
+}
+
+fn log_post_hook(
+	hook: &str,
+	res: std::result::Result<
+		git2_hooks::HookResult,
+		git2_hooks::HooksError,
+	>,
+) {
+	match res.map(HookResult::from) {
+		Ok(HookResult::Ok) => (),
+		Ok(HookResult::NotOk(msg)) => {
+			log::warn!("{hook} hook failed: {msg}");
+		}
+		Err(e) => log::error!("{hook} hook error: {e}"),
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
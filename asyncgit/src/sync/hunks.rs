@@ -1,14 +1,18 @@
 use super::{
 	diff::{get_diff_raw, DiffOptions, HunkHeader},
-	RepoPath,
+	CommitId, RepoPath,
 };
 use crate::{
 	error::{Error, Result},
 	hash,
 	sync::repository::repo,
 };
-use git2::{ApplyLocation, ApplyOptions, Diff};
+use git2::{
+	ApplyLocation, ApplyOptions, Diff, DiffLineType, Patch,
+	Repository,
+};
 use scopetime::scope_time;
+use std::collections::HashSet;
 
 ///
 pub fn stage_hunk(
@@ -148,12 +152,236 @@ pub fn unstage_hunk(
 	Ok(count == 1)
 }
 
+/// diff of `file_path` introduced by commit `id` against its first
+/// parent, `reverse` swaps both sides
+fn get_commit_file_diff<'a>(
+	repo: &'a Repository,
+	id: CommitId,
+	file_path: &str,
+	reverse: bool,
+	options: Option<DiffOptions>,
+) -> Result<Diff<'a>> {
+	let commit = repo.find_commit(id.into())?;
+	let commit_tree = commit.tree()?;
+	let parent_tree = if commit.parent_count() > 0 {
+		Some(commit.parent(0)?.tree()?)
+	} else {
+		None
+	};
+
+	let mut opts = git2::DiffOptions::new();
+	if let Some(options) = options {
+		options.apply_to(repo, &mut opts);
+	}
+	opts.pathspec(file_path);
+	opts.reverse(reverse);
+
+	Ok(repo.diff_tree_to_tree(
+		parent_tree.as_ref(),
+		Some(&commit_tree),
+		Some(&mut opts),
+	)?)
+}
+
+/// applies the hunks (identified by their header hash) that commit
+/// `id` made to `file_path` onto the workdir, like `checkout -p`.
+/// `reverse` applies them inverted instead, a partial revert.
+pub fn apply_commit_hunks(
+	repo_path: &RepoPath,
+	id: CommitId,
+	file_path: &str,
+	hunk_hashes: &[u64],
+	reverse: bool,
+	options: Option<DiffOptions>,
+) -> Result<()> {
+	scope_time!("apply_commit_hunks");
+
+	let repo = repo(repo_path)?;
+
+	let diff =
+		get_commit_file_diff(&repo, id, file_path, false, options)?;
+
+	let indices: HashSet<usize> = hunk_hashes
+		.iter()
+		.filter_map(|hunk_hash| find_hunk_index(&diff, *hunk_hash))
+		.collect();
+
+	if indices.len() != hunk_hashes.len() {
+		return Err(Error::Generic("hunk not found".to_string()));
+	}
+
+	let diff =
+		get_commit_file_diff(&repo, id, file_path, reverse, options)?;
+
+	let mut hunk_idx = 0;
+	let mut opt = ApplyOptions::new();
+	opt.hunk_callback(|_hunk| {
+		let res = indices.contains(&hunk_idx);
+		hunk_idx += 1;
+		res
+	});
+
+	repo.apply(&diff, ApplyLocation::WorkDir, Some(&mut opt))?;
+
+	Ok(())
+}
+
+/// the unstaged hunk as editable patch text: its header followed by
+/// all lines prefixed with ` `, `-` or `+`, like `git add -p` offers
+/// it for editing
+pub fn get_hunk_patch(
+	repo_path: &RepoPath,
+	file_path: &str,
+	hunk_hash: u64,
+	options: Option<DiffOptions>,
+) -> Result<String> {
+	scope_time!("get_hunk_patch");
+
+	let repo = repo(repo_path)?;
+
+	let diff = get_diff_raw(&repo, file_path, false, false, options)?;
+	let patch = Patch::from_diff(&diff, 0)?.ok_or_else(|| {
+		Error::Generic(String::from("no patch found"))
+	})?;
+
+	for hunk_idx in 0..patch.num_hunks() {
+		let (hunk, line_count) = patch.hunk(hunk_idx)?;
+
+		let header =
+			String::from_utf8_lossy(hunk.header()).to_string();
+
+		if hash(&HunkHeader::from(hunk)) != hunk_hash {
+			continue;
+		}
+
+		let mut res = header;
+
+		for line_idx in 0..line_count {
+			let line = patch.line_in_hunk(hunk_idx, line_idx)?;
+
+			match line.origin_value() {
+				DiffLineType::Context
+				| DiffLineType::Addition
+				| DiffLineType::Deletion => {
+					res.push(line.origin());
+					res.push_str(&String::from_utf8_lossy(
+						line.content(),
+					));
+				}
+				DiffLineType::ContextEOFNL
+				| DiffLineType::AddEOFNL
+				| DiffLineType::DeleteEOFNL => {
+					res.push_str(NO_NEWLINE_MARKER);
+					res.push('\n');
+				}
+				_ => (),
+			}
+		}
+
+		return Ok(res);
+	}
+
+	Err(Error::Generic("hunk not found".to_string()))
+}
+
+const NO_NEWLINE_MARKER: &str = "\\ No newline at end of file";
+
+/// stages a hunk edited by the user (see `get_hunk_patch`). lines
+/// starting with `#` are ignored and the line counts of the header are
+/// recalculated, the result has to apply cleanly onto the index.
+pub fn stage_edited_hunk(
+	repo_path: &RepoPath,
+	file_path: &str,
+	hunk_patch: &str,
+) -> Result<()> {
+	scope_time!("stage_edited_hunk");
+
+	let mut lines =
+		hunk_patch.lines().filter(|line| !line.starts_with('#'));
+
+	let invalid_header =
+		|| Error::Generic(String::from("invalid hunk header"));
+	let (old_start, new_start) = lines
+		.next()
+		.and_then(parse_hunk_starts)
+		.ok_or_else(invalid_header)?;
+
+	let mut body = String::new();
+	let (mut old_lines, mut new_lines) = (0_u32, 0_u32);
+	let mut changes = false;
+
+	for line in lines {
+		match line.chars().next() {
+			// editors tend to strip the space of empty context lines
+			None | Some(' ') => {
+				old_lines += 1;
+				new_lines += 1;
+			}
+			Some('-') => {
+				old_lines += 1;
+				changes = true;
+			}
+			Some('+') => {
+				new_lines += 1;
+				changes = true;
+			}
+			Some('\\') => (),
+			Some(_) => {
+				return Err(Error::Generic(format!(
+					"invalid line in hunk: '{line}'"
+				)));
+			}
+		}
+
+		body.push_str(if line.is_empty() { " " } else { line });
+		body.push('\n');
+	}
+
+	if !changes {
+		return Err(Error::Generic(String::from(
+			"hunk contains no changes",
+		)));
+	}
+
+	let patch = format!(
+		"diff --git a/{file_path} b/{file_path}\n--- a/{file_path}\n+++ b/{file_path}\n@@ -{old_start},{old_lines} +{new_start},{new_lines} @@\n{body}"
+	);
+
+	let repo = repo(repo_path)?;
+	let diff = Diff::from_buffer(patch.as_bytes())?;
+
+	repo.apply(&diff, ApplyLocation::Index, None)?;
+
+	Ok(())
+}
+
+/// start lines of `@@ -<old>[,<count>] +<new>[,<count>] @@`
+fn parse_hunk_starts(header: &str) -> Option<(u32, u32)> {
+	let mut parts = header.split_whitespace();
+
+	if parts.next()? != "@@" {
+		return None;
+	}
+
+	let start = |part: &str, prefix: char| {
+		part.strip_prefix(prefix)?.split(',').next()?.parse().ok()
+	};
+
+	let old_start = start(parts.next()?, '-')?;
+	let new_start = start(parts.next()?, '+')?;
+
+	Some((old_start, new_start))
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
 	use crate::{
 		error::Result,
-		sync::{diff::get_diff, tests::repo_init_empty},
+		sync::{
+			diff::{get_diff, get_diff_commit},
+			tests::{repo_init, repo_init_empty, write_commit_file},
+		},
 	};
 	use std::{
 		fs::{self, File},
@@ -191,4 +419,120 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[test]
+	fn apply_and_revert_commit_hunks() -> Result<()> {
+		let (_td, repo) = repo_init()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let options = DiffOptions {
+			context: 0,
+			..DiffOptions::default()
+		};
+
+		write_commit_file(&repo, "f.txt", "a\nb\nc\nd\ne\n", "c1");
+		let c2 = write_commit_file(
+			&repo,
+			"f.txt",
+			"A\nb\nc\nd\nE\n",
+			"c2",
+		);
+		write_commit_file(&repo, "f.txt", "a\nb\nc\nd\ne\n", "c3");
+
+		let diff = get_diff_commit(
+			repo_path,
+			c2,
+			"f.txt".into(),
+			Some(options),
+		)?;
+		assert_eq!(diff.hunks.len(), 2);
+
+		apply_commit_hunks(
+			repo_path,
+			c2,
+			"f.txt",
+			&[diff.hunks[1].header_hash],
+			false,
+			Some(options),
+		)?;
+
+		assert_eq!(
+			fs::read_to_string(root.join("f.txt"))?,
+			"a\nb\nc\nd\nE\n"
+		);
+
+		// the workdir no longer matches the old side of the hunk
+		assert!(apply_commit_hunks(
+			repo_path,
+			c2,
+			"f.txt",
+			&[diff.hunks[1].header_hash],
+			false,
+			Some(options),
+		)
+		.is_err());
+
+		apply_commit_hunks(
+			repo_path,
+			c2,
+			"f.txt",
+			&[diff.hunks[1].header_hash],
+			true,
+			Some(options),
+		)?;
+
+		assert_eq!(
+			fs::read_to_string(root.join("f.txt"))?,
+			"a\nb\nc\nd\ne\n"
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_stage_edited_hunk() -> Result<()> {
+		let (_td, repo) = repo_init()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "1\n2\n3\n", "c1");
+		fs::write(root.join("a.txt"), "1\nx\n3\ny\n")?;
+
+		let diff = get_diff(repo_path, "a.txt", false, None)?;
+		let hunk_hash = diff.hunks[0].header_hash;
+
+		let patch =
+			get_hunk_patch(repo_path, "a.txt", hunk_hash, None)?;
+		assert_eq!(patch, "@@ -1,3 +1,4 @@\n 1\n-2\n+x\n 3\n+y\n");
+
+		assert!(
+			stage_edited_hunk(repo_path, "a.txt", "1\n-2").is_err()
+		);
+		assert!(stage_edited_hunk(
+			repo_path,
+			"a.txt",
+			"@@ -1,3 +1,4 @@\n 1\n?2\n"
+		)
+		.is_err());
+
+		// drop `+y` and turn `x` into `z`, the counts are recalculated
+		stage_edited_hunk(
+			repo_path,
+			"a.txt",
+			"@@ -1,3 +1,4 @@\n# comment\n 1\n-2\n+z\n 3\n",
+		)?;
+
+		let staged = get_diff(repo_path, "a.txt", true, None)?;
+		let lines = staged.hunks[0]
+			.lines
+			.iter()
+			.map(|line| line.content.as_ref())
+			.collect::<Vec<_>>();
+		assert_eq!(lines[1..], ["1", "2", "z", "3"]);
+
+		Ok(())
+	}
 }
@@ -3,15 +3,167 @@ use crate::{
 	error::{Error, Result},
 	sync::repository::repo,
 };
+use git2::{Status, StatusOptions};
+use gix::glob::{pattern::Case, wildmatch, Pattern};
 use scopetime::scope_time;
 use std::{
-	fs::{File, OpenOptions},
+	fs::{self, File, OpenOptions},
 	io::{Read, Seek, SeekFrom, Write},
-	path::Path,
+	path::{Path, PathBuf},
 };
 
 static GITIGNORE: &str = ".gitignore";
 
+/// the ignore rule responsible for excluding a path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IgnoreRule {
+	/// absolute path of the file containing the rule
+	pub file: PathBuf,
+	/// 1-based line number of the rule in `file`
+	pub line: usize,
+	/// the pattern as written in `file`
+	pub pattern: String,
+}
+
+/// kinds of patterns offered to ignore a path
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IgnorePatternKind {
+	/// exactly this file or folder
+	Path,
+	/// all files with the same extension
+	Extension,
+	/// the folder containing the path
+	Directory,
+	/// the file or folder name anywhere in the tree
+	Basename,
+}
+
+impl IgnorePatternKind {
+	///
+	pub const ALL: [Self; 4] = [
+		Self::Path,
+		Self::Extension,
+		Self::Directory,
+		Self::Basename,
+	];
+}
+
+/// the file a pattern is written to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IgnoreFile {
+	/// `.gitignore` in the workdir root, shared via the repo
+	Gitignore,
+	/// `.git/info/exclude`, local to this clone
+	InfoExclude,
+}
+
+/// pattern of `kind` ignoring `path`, `None` if `kind` does not apply
+/// to it (no extension, already at the root).
+///
+/// `path` is relative to the workdir, a trailing `/` marks a folder.
+pub fn ignore_pattern(
+	path: &str,
+	kind: IgnorePatternKind,
+) -> Option<String> {
+	let is_dir = path.ends_with('/');
+	let path = path.trim_end_matches('/');
+	let (parent, name) =
+		path.rsplit_once('/').map_or(("", path), |(p, n)| (p, n));
+	let dir_suffix = if is_dir { "/" } else { "" };
+
+	match kind {
+		IgnorePatternKind::Path => {
+			Some(format!("/{path}{dir_suffix}"))
+		}
+		IgnorePatternKind::Extension => {
+			if is_dir {
+				return None;
+			}
+			Path::new(name)
+				.extension()
+				.and_then(|ext| ext.to_str())
+				.map(|ext| format!("*.{ext}"))
+		}
+		IgnorePatternKind::Directory => {
+			if is_dir {
+				Some(format!("/{path}/"))
+			} else {
+				(!parent.is_empty()).then(|| format!("/{parent}/"))
+			}
+		}
+		IgnorePatternKind::Basename => {
+			Some(format!("{name}{dir_suffix}"))
+		}
+	}
+}
+
+/// absolute path of `file`, which does not need to exist yet
+pub fn ignore_file_path(
+	repo_path: &RepoPath,
+	file: IgnoreFile,
+) -> Result<PathBuf> {
+	let repo = repo(repo_path)?;
+
+	Ok(match file {
+		IgnoreFile::Gitignore => work_dir(&repo)?.join(GITIGNORE),
+		IgnoreFile::InfoExclude => {
+			repo.path().join("info").join("exclude")
+		}
+	})
+}
+
+/// content of `file`, empty if it does not exist
+pub fn read_ignore_file(
+	repo_path: &RepoPath,
+	file: IgnoreFile,
+) -> Result<String> {
+	scope_time!("read_ignore_file");
+
+	let path = ignore_file_path(repo_path, file)?;
+
+	if path.is_file() {
+		Ok(fs::read_to_string(path)?)
+	} else {
+		Ok(String::new())
+	}
+}
+
+/// replaces the content of `file`
+pub fn write_ignore_file(
+	repo_path: &RepoPath,
+	file: IgnoreFile,
+	content: &str,
+) -> Result<()> {
+	scope_time!("write_ignore_file");
+
+	let path = ignore_file_path(repo_path, file)?;
+
+	if let Some(folder) = path.parent() {
+		fs::create_dir_all(folder)?;
+	}
+
+	fs::write(path, content)?;
+
+	Ok(())
+}
+
+/// append `pattern` as a new line to `file`
+pub fn add_pattern_to_ignore(
+	repo_path: &RepoPath,
+	file: IgnoreFile,
+	pattern: &str,
+) -> Result<()> {
+	scope_time!("add_pattern_to_ignore");
+
+	let path = ignore_file_path(repo_path, file)?;
+
+	if let Some(folder) = path.parent() {
+		fs::create_dir_all(folder)?;
+	}
+
+	append_line(&path, pattern)
+}
+
 /// add file or path to root ignore file
 pub fn add_to_ignore(
 	repo_path: &RepoPath,
@@ -29,10 +181,12 @@ pub fn add_to_ignore(
 		)));
 	}
 
-	let ignore_file = work_dir(&repo)?.join(GITIGNORE);
+	append_line(&work_dir(&repo)?.join(GITIGNORE), path_to_ignore)
+}
 
-	let optional_newline = ignore_file.exists()
-		&& !file_ends_with_newline(&ignore_file)?;
+fn append_line(ignore_file: &Path, line: &str) -> Result<()> {
+	let optional_newline =
+		ignore_file.exists() && !file_ends_with_newline(ignore_file)?;
 
 	let mut file = OpenOptions::new()
 		.append(true)
@@ -43,12 +197,280 @@ pub fn add_to_ignore(
 		file,
 		"{}{}",
 		if optional_newline { "\n" } else { "" },
-		path_to_ignore
+		line
 	)?;
 
 	Ok(())
 }
 
+/// untracked and ignored paths of the workdir, to preview which of
+/// them ignore patterns would hide
+pub struct IgnoreCandidates {
+	paths: Vec<String>,
+	case: Case,
+}
+
+/// the paths a single pattern matches
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IgnorePreview {
+	/// 1-based line number of the pattern
+	pub line: usize,
+	///
+	pub pattern: String,
+	///
+	pub negative: bool,
+	/// matching untracked or ignored paths, folders end with `/`
+	pub matches: Vec<String>,
+}
+
+/// collects the paths [`IgnoreCandidates::preview`] matches against.
+/// ignored folders are listed as a whole.
+pub fn ignore_candidates(
+	repo_path: &RepoPath,
+) -> Result<IgnoreCandidates> {
+	scope_time!("ignore_candidates");
+
+	let repo = repo(repo_path)?;
+
+	let case = if repo
+		.config()?
+		.get_bool("core.ignorecase")
+		.unwrap_or(false)
+	{
+		Case::Fold
+	} else {
+		Case::Sensitive
+	};
+
+	let mut options = StatusOptions::new();
+	options
+		.include_untracked(true)
+		.recurse_untracked_dirs(true)
+		.include_ignored(true)
+		.recurse_ignored_dirs(false)
+		.exclude_submodules(true);
+
+	let mut paths = repo
+		.statuses(Some(&mut options))?
+		.iter()
+		.filter(|entry| {
+			entry
+				.status()
+				.intersects(Status::WT_NEW | Status::IGNORED)
+		})
+		.filter_map(|entry| entry.path().map(String::from))
+		.collect::<Vec<_>>();
+
+	paths.sort();
+
+	Ok(IgnoreCandidates { paths, case })
+}
+
+impl IgnoreCandidates {
+	/// matches every pattern of an ignore file in the workdir root
+	/// (or of `info/exclude`) against the candidates
+	pub fn preview(&self, content: &str) -> Vec<IgnorePreview> {
+		content
+			.lines()
+			.enumerate()
+			.filter(|(_, line)| !line.starts_with('#'))
+			.filter_map(|(idx, line)| {
+				let pattern = gix::glob::parse(line.trim_end())?;
+
+				let matches = self
+					.paths
+					.iter()
+					.filter(|path| self.hidden_by(&pattern, path))
+					.cloned()
+					.collect();
+
+				Some(IgnorePreview {
+					line: idx + 1,
+					pattern: line.trim_end().to_string(),
+					negative: pattern.is_negative(),
+					matches,
+				})
+			})
+			.collect()
+	}
+
+	/// whether `pattern` matches `path` or one of its folders
+	fn hidden_by(&self, pattern: &Pattern, path: &str) -> bool {
+		let is_dir = path.ends_with('/');
+		let path = path.trim_end_matches('/');
+
+		path.match_indices('/')
+			.map(|(pos, _)| (&path[..pos], true))
+			.chain(std::iter::once((path, is_dir)))
+			.any(|(candidate, is_dir)| {
+				pattern_matches(pattern, candidate, is_dir, self.case)
+			})
+	}
+}
+
+fn pattern_matches(
+	pattern: &Pattern,
+	path: &str,
+	is_dir: bool,
+	case: Case,
+) -> bool {
+	pattern.matches_repo_relative_path(
+		path.into(),
+		path.rfind('/').map(|pos| pos + 1),
+		Some(is_dir),
+		case,
+		wildmatch::Mode::NO_MATCH_SLASH_LITERAL,
+	)
+}
+
+/// find the rule that causes `path` to be ignored, similar to
+/// `git check-ignore -v`. returns `None` if `path` is not ignored.
+///
+/// `path` is relative to the workdir, a trailing `/` marks a folder.
+pub fn find_ignore_rule(
+	repo_path: &RepoPath,
+	path: &str,
+) -> Result<Option<IgnoreRule>> {
+	scope_time!("find_ignore_rule");
+
+	let repo = repo(repo_path)?;
+	let work_dir = work_dir(&repo)?;
+	let config = repo.config()?;
+
+	let case = if config.get_bool("core.ignorecase").unwrap_or(false)
+	{
+		Case::Fold
+	} else {
+		Case::Sensitive
+	};
+
+	let mut sources = vec![repo.path().join("info").join("exclude")];
+	if let Some(global) = global_excludes_file(&config) {
+		sources.push(global);
+	}
+
+	let is_dir = path.ends_with('/');
+	let path = path.trim_end_matches('/');
+
+	// a file inside an excluded folder is always ignored, so check
+	// all parent folders before the path itself
+	let mut end = 0;
+	loop {
+		let next = path[end..].find('/').map(|pos| end + pos);
+		let candidate = &path[..next.unwrap_or(path.len())];
+		let candidate_is_dir = next.is_some() || is_dir;
+
+		if let Some(rule) = match_candidate(
+			work_dir,
+			&sources,
+			candidate,
+			candidate_is_dir,
+			case,
+		)? {
+			return Ok(Some(rule));
+		}
+
+		match next {
+			Some(pos) => end = pos + 1,
+			None => return Ok(None),
+		}
+	}
+}
+
+fn global_excludes_file(config: &git2::Config) -> Option<PathBuf> {
+	config.get_path("core.excludesFile").ok().or_else(|| {
+		std::env::var_os("XDG_CONFIG_HOME")
+			.map(PathBuf::from)
+			.or_else(|| {
+				dirs::home_dir().map(|home| home.join(".config"))
+			})
+			.map(|config| config.join("git").join("ignore"))
+	})
+}
+
+/// checks the ignore files in order of precedence, the first file
+/// with a matching pattern decides
+fn match_candidate(
+	work_dir: &Path,
+	sources: &[PathBuf],
+	path: &str,
+	is_dir: bool,
+	case: Case,
+) -> Result<Option<IgnoreRule>> {
+	let mut folder = path.rfind('/').map_or("", |pos| &path[..pos]);
+
+	loop {
+		let relative = if folder.is_empty() {
+			path
+		} else {
+			&path[folder.len() + 1..]
+		};
+
+		if let Some((rule, negative)) = match_file(
+			&work_dir.join(folder).join(GITIGNORE),
+			relative,
+			is_dir,
+			case,
+		)? {
+			return Ok((!negative).then_some(rule));
+		}
+
+		if folder.is_empty() {
+			break;
+		}
+		folder = folder.rfind('/').map_or("", |pos| &folder[..pos]);
+	}
+
+	for source in sources {
+		if let Some((rule, negative)) =
+			match_file(source, path, is_dir, case)?
+		{
+			return Ok((!negative).then_some(rule));
+		}
+	}
+
+	Ok(None)
+}
+
+/// returns `None` if no pattern in `file` matches, otherwise the last
+/// matching rule and whether it is a negation
+fn match_file(
+	file: &Path,
+	path: &str,
+	is_dir: bool,
+	case: Case,
+) -> Result<Option<(IgnoreRule, bool)>> {
+	if !file.is_file() {
+		return Ok(None);
+	}
+
+	let content = fs::read_to_string(file)?;
+
+	let matching = content
+		.lines()
+		.enumerate()
+		.filter(|(_, line)| !line.starts_with('#'))
+		.filter_map(|(idx, line)| {
+			gix::glob::parse(line.trim_end())
+				.map(|pattern| (idx, line, pattern))
+		})
+		.filter(|(_, _, pattern)| {
+			pattern_matches(pattern, path, is_dir, case)
+		})
+		.last();
+
+	Ok(matching.map(|(idx, line, pattern)| {
+		(
+			IgnoreRule {
+				file: file.to_path_buf(),
+				line: idx + 1,
+				pattern: line.trim_end().to_string(),
+			},
+			pattern.is_negative(),
+		)
+	}))
+}
+
 fn file_ends_with_newline(file: &Path) -> Result<bool> {
 	let mut file = File::open(file)?;
 	let size = file.metadata()?.len();
@@ -156,4 +578,155 @@ mod tests {
 		let lines = read_lines(root.join(ignore_file_path)).unwrap();
 		assert_eq!(lines.count(), 1);
 	}
+
+	#[test]
+	fn test_find_ignore_rule() -> Result<()> {
+		let (_td, repo) = repo_init()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		repo_write_file(
+			&repo,
+			".gitignore",
+			"# logs\n*.log\n!keep.log\n",
+		)?;
+		std::fs::create_dir(root.join("sub"))?;
+		repo_write_file(&repo, "sub/.gitignore", "/local.txt\n")?;
+		repo_write_file(&repo, ".git/info/exclude", "build/\n")?;
+
+		let rule =
+			find_ignore_rule(repo_path, "sub/foo.log")?.unwrap();
+		assert_eq!(rule.file, root.join(".gitignore"));
+		assert_eq!(rule.line, 2);
+		assert_eq!(&rule.pattern, "*.log");
+
+		assert_eq!(find_ignore_rule(repo_path, "keep.log")?, None);
+		assert_eq!(find_ignore_rule(repo_path, "local.txt")?, None);
+
+		let rule =
+			find_ignore_rule(repo_path, "sub/local.txt")?.unwrap();
+		assert_eq!(rule.file, root.join("sub").join(".gitignore"));
+		assert_eq!(rule.line, 1);
+
+		let rule =
+			find_ignore_rule(repo_path, "build/a/b.txt")?.unwrap();
+		assert_eq!(
+			rule.file,
+			repo.path().join("info").join("exclude")
+		);
+		assert_eq!(&rule.pattern, "build/");
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_ignore_pattern_kinds() {
+		let kinds = |path: &str| {
+			IgnorePatternKind::ALL
+				.iter()
+				.map(|kind| ignore_pattern(path, *kind))
+				.collect::<Vec<_>>()
+		};
+
+		assert_eq!(
+			kinds("src/main.rs"),
+			[
+				Some(String::from("/src/main.rs")),
+				Some(String::from("*.rs")),
+				Some(String::from("/src/")),
+				Some(String::from("main.rs")),
+			]
+		);
+		assert_eq!(
+			kinds(".env"),
+			[
+				Some(String::from("/.env")),
+				None,
+				None,
+				Some(String::from(".env"))
+			]
+		);
+		assert_eq!(
+			kinds("a/build/"),
+			[
+				Some(String::from("/a/build/")),
+				None,
+				Some(String::from("/a/build/")),
+				Some(String::from("build/")),
+			]
+		);
+	}
+
+	#[test]
+	fn test_add_pattern_to_exclude() -> Result<()> {
+		let (_td, repo) = repo_init()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		add_pattern_to_ignore(
+			repo_path,
+			IgnoreFile::InfoExclude,
+			"*.log",
+		)?;
+		repo_write_file(&repo, "debug.log", "log")?;
+
+		assert!(!root.join(".gitignore").exists());
+		assert_eq!(
+			find_ignore_rule(repo_path, "debug.log")?
+				.unwrap()
+				.pattern,
+			"*.log"
+		);
+
+		write_ignore_file(repo_path, IgnoreFile::Gitignore, "a\n")?;
+		assert_eq!(
+			read_ignore_file(repo_path, IgnoreFile::Gitignore)?,
+			"a\n"
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_ignore_preview() -> Result<()> {
+		let (_td, repo) = repo_init()?;
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		std::fs::create_dir_all(root.join("build/out"))?;
+		std::fs::create_dir(root.join("src"))?;
+		repo_write_file(&repo, "build/out/a.o", "")?;
+		repo_write_file(&repo, "a.log", "")?;
+		repo_write_file(&repo, "src/b.log", "")?;
+		repo_write_file(&repo, ".git/info/exclude", "build/\n")?;
+
+		let candidates = ignore_candidates(repo_path)?;
+		let preview = candidates
+			.preview("# comment\n*.log\n/out/\nbuild/\n!a.log");
+
+		assert_eq!(
+			preview
+				.iter()
+				.map(|p| (p.line, p.negative, p.matches.clone()))
+				.collect::<Vec<_>>(),
+			[
+				(
+					2,
+					false,
+					vec![
+						String::from("a.log"),
+						String::from("src/b.log")
+					]
+				),
+				(3, false, vec![]),
+				(4, false, vec![String::from("build/")]),
+				(5, true, vec![String::from("a.log")]),
+			]
+		);
+
+		Ok(())
+	}
 }
@@ -0,0 +1,194 @@
+//! index bits that make git ignore working tree changes of tracked
+//! files, like `git update-index --assume-unchanged/--skip-worktree`
+
+use super::{
+	repository::repo,
+	status::{StatusItem, StatusItemType},
+	RepoPath,
+};
+use crate::error::{Error, Result};
+use scopetime::scope_time;
+use std::path::Path;
+
+/// `GIT_INDEX_ENTRY_VALID` in `flags`
+const ASSUME_UNCHANGED: u16 = 1 << 15;
+/// `GIT_INDEX_ENTRY_EXTENDED` in `flags`
+const EXTENDED: u16 = 1 << 14;
+/// `GIT_INDEX_ENTRY_SKIP_WORKTREE` in `flags_extended`
+const SKIP_WORKTREE: u16 = 1 << 14;
+
+///
+#[derive(Copy, Clone, Hash, PartialEq, Eq, Debug)]
+pub enum IndexFlag {
+	/// promise that the file does not change, for slow file systems
+	AssumeUnchanged,
+	/// keep a local version of the file, used by sparse checkouts
+	SkipWorktree,
+}
+
+/// tracked files with one of the [`IndexFlag`]s set, as status items
+/// of type [`StatusItemType::SkipWorktree`] or
+/// [`StatusItemType::AssumeUnchanged`]
+pub fn get_index_flagged(
+	repo_path: &RepoPath,
+) -> Result<Vec<StatusItem>> {
+	scope_time!("get_index_flagged");
+
+	let repo = repo(repo_path)?;
+	let index = repo.index()?;
+
+	Ok(index
+		.iter()
+		.filter_map(|entry| {
+			let status = if entry.flags_extended & SKIP_WORKTREE != 0
+			{
+				StatusItemType::SkipWorktree
+			} else if entry.flags & ASSUME_UNCHANGED != 0 {
+				StatusItemType::AssumeUnchanged
+			} else {
+				return None;
+			};
+
+			Some(StatusItem {
+				path: String::from_utf8(entry.path).ok()?,
+				status,
+				old_path: None,
+			})
+		})
+		.collect())
+}
+
+/// sets or clears `flag` on the index entry of `path`
+pub fn set_index_flag(
+	repo_path: &RepoPath,
+	path: &str,
+	flag: IndexFlag,
+	enabled: bool,
+) -> Result<()> {
+	scope_time!("set_index_flag");
+
+	let repo = repo(repo_path)?;
+	let mut index = repo.index()?;
+
+	let mut entry =
+		index.get_path(Path::new(path), 0).ok_or_else(|| {
+			Error::Generic(format!("{path} is not tracked"))
+		})?;
+
+	match flag {
+		IndexFlag::AssumeUnchanged => {
+			if enabled {
+				entry.flags |= ASSUME_UNCHANGED;
+			} else {
+				entry.flags &= !ASSUME_UNCHANGED;
+			}
+		}
+		IndexFlag::SkipWorktree => {
+			if enabled {
+				entry.flags_extended |= SKIP_WORKTREE;
+				entry.flags |= EXTENDED;
+			} else {
+				entry.flags_extended &= !SKIP_WORKTREE;
+				if entry.flags_extended == 0 {
+					entry.flags &= !EXTENDED;
+				}
+			}
+		}
+	}
+
+	index.add(&entry)?;
+	index.write()?;
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		status::{get_status, StatusType},
+		tests::{repo_init, write_commit_file},
+		utils::repo_write_file,
+	};
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn test_flags_hide_changes() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "c1");
+		write_commit_file(&repo, "b.txt", "b", "c2");
+
+		set_index_flag(
+			repo_path,
+			"a.txt",
+			IndexFlag::SkipWorktree,
+			true,
+		)
+		.unwrap();
+		set_index_flag(
+			repo_path,
+			"b.txt",
+			IndexFlag::AssumeUnchanged,
+			true,
+		)
+		.unwrap();
+
+		repo_write_file(&repo, "a.txt", "changed").unwrap();
+		repo_write_file(&repo, "b.txt", "changed").unwrap();
+
+		assert!(get_status(repo_path, StatusType::WorkingDir, None)
+			.unwrap()
+			.is_empty());
+		assert_eq!(
+			get_index_flagged(repo_path).unwrap(),
+			[
+				StatusItem {
+					path: String::from("a.txt"),
+					status: StatusItemType::SkipWorktree,
+					old_path: None,
+				},
+				StatusItem {
+					path: String::from("b.txt"),
+					status: StatusItemType::AssumeUnchanged,
+					old_path: None,
+				},
+			]
+		);
+
+		set_index_flag(
+			repo_path,
+			"a.txt",
+			IndexFlag::SkipWorktree,
+			false,
+		)
+		.unwrap();
+
+		assert_eq!(
+			get_status(repo_path, StatusType::WorkingDir, None)
+				.unwrap()
+				.len(),
+			1
+		);
+		assert_eq!(get_index_flagged(repo_path).unwrap().len(), 1);
+	}
+
+	#[test]
+	fn test_flag_untracked_fails() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		assert!(set_index_flag(
+			repo_path,
+			"x.txt",
+			IndexFlag::SkipWorktree,
+			true
+		)
+		.is_err());
+	}
+}
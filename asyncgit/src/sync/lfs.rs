@@ -0,0 +1,324 @@
+//! git LFS: files whose content is kept outside of the repo, git only
+//! stores small pointer files and the `lfs` filter swaps them with the
+//! content on checkout. Locking needs the `git lfs` command.
+
+use super::{
+	diff::{DiffLineType, FileDiff},
+	repository::repo,
+	utils::work_dir,
+	RepoPath,
+};
+use crate::error::{Error, Result};
+use git2::{AttrCheckFlags, Repository};
+use scopetime::scope_time;
+use std::{
+	collections::BTreeMap,
+	fs::{self, File},
+	io::Read,
+	path::Path,
+	process::Command,
+};
+
+const POINTER_VERSION: &str =
+	"version https://git-lfs.github.com/spec/";
+/// pointer files are never larger than this
+const POINTER_MAX_SIZE: u64 = 1024;
+
+/// content of an LFS pointer file
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct LfsPointer {
+	/// hash of the content, e.g. `sha256:4d7a...`
+	pub oid: String,
+	/// size of the content in bytes
+	pub size: u64,
+}
+
+impl LfsPointer {
+	/// hash without its algorithm prefix, shortened like commit ids
+	pub fn short_oid(&self) -> &str {
+		let oid = self
+			.oid
+			.split_once(':')
+			.map_or(self.oid.as_str(), |(_, hash)| hash);
+
+		oid.get(0..7).unwrap_or(oid)
+	}
+}
+
+/// `None` if `content` is not an LFS pointer file
+pub fn parse_lfs_pointer(content: &[u8]) -> Option<LfsPointer> {
+	let content = std::str::from_utf8(content).ok()?;
+
+	if !content.starts_with(POINTER_VERSION) {
+		return None;
+	}
+
+	let mut oid = None;
+	let mut size = None;
+	for line in content.lines() {
+		match line.split_once(' ') {
+			Some(("oid", value)) => oid = Some(value.to_string()),
+			Some(("size", value)) => size = value.parse().ok(),
+			_ => (),
+		}
+	}
+
+	Some(LfsPointer {
+		oid: oid?,
+		size: size?,
+	})
+}
+
+/// what the workdir holds for a file tracked by LFS
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum LfsFileState {
+	/// just the pointer, the content was never downloaded
+	Pointer,
+	/// the actual content
+	Downloaded,
+}
+
+/// both sides of a diff between LFS pointer files, `None` on the side
+/// the file got added or deleted
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct LfsDiff {
+	///
+	pub old: Option<LfsPointer>,
+	///
+	pub new: Option<LfsPointer>,
+}
+
+/// pointer files on both sides of `diff`, `None` unless every side
+/// with content is a pointer
+pub(crate) fn lfs_diff(diff: &FileDiff) -> Option<LfsDiff> {
+	let side = |skipped: DiffLineType| {
+		diff.hunks
+			.iter()
+			.flat_map(|hunk| hunk.lines.iter())
+			.filter(|line| {
+				line.line_type != DiffLineType::Header
+					&& line.line_type != skipped
+			})
+			.map(|line| format!("{}\n", line.content))
+			.collect::<String>()
+	};
+	let parse = |content: String| {
+		if content.is_empty() {
+			Some(None)
+		} else {
+			parse_lfs_pointer(content.as_bytes()).map(Some)
+		}
+	};
+
+	let old = parse(side(DiffLineType::Add))?;
+	let new = parse(side(DiffLineType::Delete))?;
+
+	if old.is_none() && new.is_none() {
+		return None;
+	}
+
+	Some(LfsDiff { old, new })
+}
+
+fn is_tracked(repo: &Repository, path: &str) -> bool {
+	repo.get_attr(
+		Path::new(path),
+		"filter",
+		AttrCheckFlags::FILE_THEN_INDEX,
+	)
+	.ok()
+	.flatten()
+		== Some("lfs")
+}
+
+/// `true` if `path` is tracked by LFS via `.gitattributes`
+pub fn is_lfs_tracked(
+	repo_path: &RepoPath,
+	path: &str,
+) -> Result<bool> {
+	let repo = repo(repo_path)?;
+
+	Ok(is_tracked(&repo, path))
+}
+
+fn workdir_state(path: &Path) -> Option<LfsFileState> {
+	let meta = fs::metadata(path).ok()?;
+	if !meta.is_file() {
+		return None;
+	}
+
+	if meta.len() > POINTER_MAX_SIZE {
+		return Some(LfsFileState::Downloaded);
+	}
+
+	let mut content = Vec::new();
+	File::open(path).ok()?.read_to_end(&mut content).ok()?;
+
+	Some(if parse_lfs_pointer(&content).is_some() {
+		LfsFileState::Pointer
+	} else {
+		LfsFileState::Downloaded
+	})
+}
+
+/// state of those `paths` that are tracked by LFS and exist in the
+/// workdir
+pub fn lfs_file_states<'a>(
+	repo_path: &RepoPath,
+	paths: impl Iterator<Item = &'a str>,
+) -> Result<BTreeMap<String, LfsFileState>> {
+	scope_time!("lfs_file_states");
+
+	let repo = repo(repo_path)?;
+	let Ok(work_dir) = work_dir(&repo) else {
+		return Ok(BTreeMap::new());
+	};
+
+	Ok(paths
+		.filter(|path| is_tracked(&repo, path))
+		.filter_map(|path| {
+			workdir_state(&work_dir.join(path))
+				.map(|state| (path.to_string(), state))
+		})
+		.collect())
+}
+
+fn run_lfs(repo_path: &RepoPath, args: &[&str]) -> Result<String> {
+	let repo = repo(repo_path)?;
+	let work_dir = work_dir(&repo)?;
+
+	// a credential prompt would hang, gitui owns the terminal
+	let output = Command::new("git")
+		.arg("lfs")
+		.args(args)
+		.current_dir(work_dir)
+		.env("GIT_TERMINAL_PROMPT", "0")
+		.output()
+		.map_err(|e| {
+			Error::Generic(format!("git lfs failed: {e}"))
+		})?;
+
+	if output.status.success() {
+		return Ok(String::from_utf8_lossy(&output.stdout)
+			.trim()
+			.to_string());
+	}
+
+	let out = String::from_utf8_lossy(&output.stderr);
+	let out = out.trim();
+
+	Err(Error::Generic(if out.is_empty() {
+		format!("`git lfs {}` failed", args.join(" "))
+	} else {
+		out.to_string()
+	}))
+}
+
+/// locks `path` on the LFS server so nobody else can push changes to
+/// it, like `git lfs lock`, returns its output
+pub fn lfs_lock(repo_path: &RepoPath, path: &str) -> Result<String> {
+	scope_time!("lfs_lock");
+
+	run_lfs(repo_path, &["lock", "--", path])
+}
+
+/// releases our lock of `path`, like `git lfs unlock`, returns its
+/// output
+pub fn lfs_unlock(
+	repo_path: &RepoPath,
+	path: &str,
+) -> Result<String> {
+	scope_time!("lfs_unlock");
+
+	run_lfs(repo_path, &["unlock", "--", path])
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		diff::get_diff,
+		tests::{repo_init, write_commit_file},
+	};
+
+	const POINTER: &str = "version https://git-lfs.github.com/spec/v1\noid sha256:4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393\nsize 12345\n";
+
+	#[test]
+	fn test_parse_lfs_pointer() {
+		let pointer = parse_lfs_pointer(POINTER.as_bytes()).unwrap();
+
+		assert_eq!(pointer.size, 12345);
+		assert_eq!(pointer.short_oid(), "4d7a214");
+		assert_eq!(parse_lfs_pointer(b"size 1\n"), None);
+		assert_eq!(
+			parse_lfs_pointer(
+				b"version https://git-lfs.github.com/spec/v1\n"
+			),
+			None
+		);
+	}
+
+	#[test]
+	fn test_lfs_file_states() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		fs::write(
+			root.join(".gitattributes"),
+			"*.bin filter=lfs diff=lfs merge=lfs -text\n",
+		)
+		.unwrap();
+		fs::write(root.join("pointer.bin"), POINTER).unwrap();
+		fs::write(root.join("content.bin"), [0_u8, 1, 2]).unwrap();
+		fs::write(root.join("plain.txt"), "plain").unwrap();
+
+		assert!(is_lfs_tracked(repo_path, "content.bin").unwrap());
+		assert!(!is_lfs_tracked(repo_path, "plain.txt").unwrap());
+
+		let states = lfs_file_states(
+			repo_path,
+			["pointer.bin", "content.bin", "plain.txt", "gone.bin"]
+				.into_iter(),
+		)
+		.unwrap();
+
+		assert_eq!(
+			states.into_iter().collect::<Vec<_>>(),
+			vec![
+				(
+					String::from("content.bin"),
+					LfsFileState::Downloaded
+				),
+				(String::from("pointer.bin"), LfsFileState::Pointer),
+			]
+		);
+	}
+
+	#[test]
+	fn test_lfs_diff() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.bin", POINTER, "c1");
+		fs::write(
+			root.join("a.bin"),
+			POINTER.replace("12345", "23456"),
+		)
+		.unwrap();
+
+		let diff = get_diff(repo_path, "a.bin", false, None).unwrap();
+		let lfs = diff.lfs.unwrap();
+
+		assert_eq!(lfs.old.unwrap().size, 12345);
+		assert_eq!(lfs.new.unwrap().size, 23456);
+
+		fs::write(root.join("a.bin"), "content").unwrap();
+
+		let diff = get_diff(repo_path, "a.bin", false, None).unwrap();
+		assert_eq!(diff.lfs, None);
+	}
+}
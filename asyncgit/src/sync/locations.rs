@@ -0,0 +1,298 @@
+//! exporting conflict and hunk locations for editors
+//! (vim quickfix lists, SARIF)
+
+use super::{
+	diff::get_diff_raw, repository::repo, utils::work_dir, RepoPath,
+};
+use crate::error::{Error, Result};
+use scopetime::scope_time;
+use serde_json::json;
+use std::{collections::BTreeSet, fmt::Write, fs, path::Path};
+
+const CONFLICT_MARKER: &str = "<<<<<<<";
+
+/// a line in a file of the working directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+	/// path relative to the working directory
+	pub path: String,
+	/// 1-based
+	pub line: usize,
+	///
+	pub message: String,
+}
+
+/// output format of `format_locations`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LocationsFormat {
+	/// `file:line:col: message`, see `:help quickfix` in vim
+	Quickfix,
+	/// SARIF 2.1.0 json log
+	Sarif,
+}
+
+/// conflict markers of all conflicted files, files without markers
+/// (e.g. deleted on one side) are reported at their first line
+pub fn get_conflict_locations(
+	repo_path: &RepoPath,
+) -> Result<Vec<Location>> {
+	scope_time!("get_conflict_locations");
+
+	let repo = repo(repo_path)?;
+	let work_dir = work_dir(&repo)?;
+
+	let mut paths = BTreeSet::new();
+	for conflict in repo.index()?.conflicts()? {
+		let conflict = conflict?;
+
+		if let Some(entry) =
+			conflict.our.or(conflict.their).or(conflict.ancestor)
+		{
+			paths.insert(
+				String::from_utf8_lossy(&entry.path).into_owned(),
+			);
+		}
+	}
+
+	let mut res = Vec::new();
+
+	for path in paths {
+		let content = fs::read_to_string(work_dir.join(&path))
+			.unwrap_or_default();
+
+		let markers = content
+			.lines()
+			.enumerate()
+			.filter(|(_, line)| line.starts_with(CONFLICT_MARKER))
+			.map(|(idx, _)| Location {
+				path: path.clone(),
+				line: idx + 1,
+				message: String::from("conflict"),
+			})
+			.collect::<Vec<_>>();
+
+		if markers.is_empty() {
+			res.push(Location {
+				path,
+				line: 1,
+				message: String::from("conflict"),
+			});
+		} else {
+			res.extend(markers);
+		}
+	}
+
+	Ok(res)
+}
+
+/// start lines of all unstaged and staged hunks
+pub fn get_hunk_locations(
+	repo_path: &RepoPath,
+) -> Result<Vec<Location>> {
+	scope_time!("get_hunk_locations");
+
+	let repo = repo(repo_path)?;
+
+	let mut res = Vec::new();
+
+	for (stage, kind) in [(false, "unstaged"), (true, "staged")] {
+		let diff = get_diff_raw(&repo, "", stage, false, None)?;
+
+		diff.foreach(
+			&mut |_, _| true,
+			None,
+			Some(&mut |delta, hunk| {
+				if let Some(path) = delta.new_file().path() {
+					let header =
+						String::from_utf8_lossy(hunk.header());
+
+					res.push(Location {
+						path: path.to_string_lossy().into_owned(),
+						line: (hunk.new_start() as usize).max(1),
+						message: format!("{kind}: {}", header.trim()),
+					});
+				}
+				true
+			}),
+			None,
+		)?;
+	}
+
+	Ok(res)
+}
+
+/// renders `locations` of the repo at `work_dir`
+pub fn format_locations(
+	locations: &[Location],
+	work_dir: &Path,
+	format: LocationsFormat,
+) -> Result<String> {
+	match format {
+		LocationsFormat::Quickfix => {
+			let mut res = String::new();
+			for location in locations {
+				let _ignore = writeln!(
+					res,
+					"{}:{}:1: {}",
+					work_dir.join(&location.path).display(),
+					location.line,
+					location.message
+				);
+			}
+			Ok(res)
+		}
+		LocationsFormat::Sarif => format_sarif(locations, work_dir),
+	}
+}
+
+fn format_sarif(
+	locations: &[Location],
+	work_dir: &Path,
+) -> Result<String> {
+	let root =
+		url::Url::from_directory_path(work_dir).map_err(|()| {
+			Error::Generic(format!(
+				"invalid working directory: {}",
+				work_dir.display()
+			))
+		})?;
+
+	let results = locations
+		.iter()
+		.map(|location| {
+			json!({
+				"ruleId": "gitui",
+				"level": "note",
+				"message": { "text": location.message },
+				"locations": [{
+					"physicalLocation": {
+						"artifactLocation": {
+							"uri": location.path,
+							"uriBaseId": "SRCROOT",
+						},
+						"region": { "startLine": location.line },
+					},
+				}],
+			})
+		})
+		.collect::<Vec<_>>();
+
+	let log = json!({
+		"version": "2.1.0",
+		"$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+		"runs": [{
+			"tool": {
+				"driver": {
+					"name": "gitui",
+					"informationUri": "https://github.com/gitui-org/gitui",
+				},
+			},
+			"originalUriBaseIds": {
+				"SRCROOT": { "uri": root.as_str() },
+			},
+			"results": results,
+		}],
+	});
+
+	serde_json::to_string_pretty(&log)
+		.map_err(|e| Error::Generic(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		commit, stage_add_file,
+		tests::{repo_init, write_commit_file},
+	};
+
+	#[test]
+	fn test_hunk_locations() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "1\n2\n3\n4\n5\n", "c1");
+
+		fs::write(root.join("a.txt"), "1\n2\n3\n4\nfive\n").unwrap();
+		fs::write(root.join("b.txt"), "new\n").unwrap();
+		stage_add_file(repo_path, Path::new("b.txt")).unwrap();
+
+		let locations = get_hunk_locations(repo_path).unwrap();
+
+		assert_eq!(locations.len(), 2);
+		assert_eq!(locations[0].path, "a.txt");
+		assert_eq!(locations[0].line, 2);
+		assert!(locations[0].message.starts_with("unstaged: @@"));
+		assert_eq!(locations[1].path, "b.txt");
+		assert_eq!(locations[1].line, 1);
+
+		let quickfix = format_locations(
+			&locations,
+			root,
+			LocationsFormat::Quickfix,
+		)
+		.unwrap();
+		assert!(quickfix
+			.lines()
+			.next()
+			.unwrap()
+			.ends_with("a.txt:2:1: unstaged: @@ -2,4 +2,4 @@"));
+
+		let sarif: serde_json::Value = serde_json::from_str(
+			&format_locations(
+				&locations,
+				root,
+				LocationsFormat::Sarif,
+			)
+			.unwrap(),
+		)
+		.unwrap();
+		let result = &sarif["runs"][0]["results"][1];
+		assert_eq!(
+			result["locations"][0]["physicalLocation"]
+				["artifactLocation"]["uri"],
+			"b.txt"
+		);
+	}
+
+	#[test]
+	fn test_conflict_locations() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "base\n", "c1");
+		let base = repo.head().unwrap().peel_to_commit().unwrap();
+		repo.branch("other", &base, false).unwrap();
+
+		write_commit_file(&repo, "a.txt", "ours\n", "ours");
+
+		repo.set_head("refs/heads/other").unwrap();
+		repo.checkout_head(Some(
+			git2::build::CheckoutBuilder::new().force(),
+		))
+		.unwrap();
+		fs::write(root.join("a.txt"), "theirs\n").unwrap();
+		stage_add_file(repo_path, Path::new("a.txt")).unwrap();
+		commit(repo_path, "theirs").unwrap();
+
+		let master = repo
+			.find_annotated_commit(
+				repo.revparse_single("master").unwrap().id(),
+			)
+			.unwrap();
+		repo.merge(&[&master], None, None).unwrap();
+
+		assert_eq!(
+			get_conflict_locations(repo_path).unwrap(),
+			vec![Location {
+				path: String::from("a.txt"),
+				line: 1,
+				message: String::from("conflict"),
+			}]
+		);
+	}
+}
@@ -1,11 +1,12 @@
 #![allow(dead_code)]
 use super::{CommitId, SharedCommitFilterFn};
 use crate::error::Result;
-use git2::{Commit, Oid, Repository};
+use git2::{BranchType, Commit, Oid, Repository};
 use gix::revision::Walk;
 use std::{
 	cmp::Ordering,
-	collections::{BinaryHeap, HashSet},
+	collections::{BinaryHeap, HashMap, HashSet},
+	path::PathBuf,
 };
 
 struct TimeOrderedCommit<'a>(Commit<'a>);
@@ -30,6 +31,64 @@ impl Ord for TimeOrderedCommit<'_> {
 	}
 }
 
+/// refs a log walk starts from
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub enum LogScope {
+	/// only commits reachable from `HEAD`
+	#[default]
+	Head,
+	/// commits reachable from `HEAD` or any local branch
+	LocalBranches,
+	/// commits reachable from `HEAD` or any ref (like `git log --all`)
+	AllRefs,
+	/// commits reachable from any of these refs (like `git log a b`),
+	/// see `LogWalker::ref_mask`
+	Refs(Vec<String>),
+}
+
+/// the commit `reference` (full ref name) points to
+fn ref_commit(repo: &Repository, reference: &str) -> Option<Oid> {
+	repo.find_reference(reference)
+		.and_then(|reference| reference.peel_to_commit())
+		.map(|commit| commit.id())
+		.ok()
+}
+
+/// commits to start a log walk of `scope` from
+fn log_tips(repo: &Repository, scope: &LogScope) -> Result<Vec<Oid>> {
+	let head = repo.head().and_then(|head| head.peel_to_commit());
+
+	let mut tips = match scope {
+		LogScope::Head => return Ok(vec![head?.id()]),
+		LogScope::LocalBranches => repo
+			.branches(Some(BranchType::Local))?
+			.filter_map(|branch| {
+				branch.ok()?.0.get().peel_to_commit().ok()
+			})
+			.map(|commit| commit.id())
+			.collect::<Vec<_>>(),
+		LogScope::AllRefs => repo
+			.references()?
+			.filter_map(|reference| {
+				reference.ok()?.peel_to_commit().ok()
+			})
+			.map(|commit| commit.id())
+			.collect::<Vec<_>>(),
+		LogScope::Refs(refs) => {
+			return Ok(refs
+				.iter()
+				.filter_map(|reference| ref_commit(repo, reference))
+				.collect());
+		}
+	};
+
+	if let Ok(head) = head {
+		tips.insert(0, head.id());
+	}
+
+	Ok(tips)
+}
+
 ///
 pub struct LogWalker<'a> {
 	commits: BinaryHeap<TimeOrderedCommit<'a>>,
@@ -37,23 +96,48 @@ pub struct LogWalker<'a> {
 	limit: usize,
 	repo: &'a Repository,
 	filter: Option<SharedCommitFilterFn>,
+	first_parent: bool,
+	pathspec: Option<PathBuf>,
+	ref_masks: HashMap<Oid, u64>,
 }
 
 impl<'a> LogWalker<'a> {
 	///
 	pub fn new(repo: &'a Repository, limit: usize) -> Result<Self> {
-		let c = repo.head()?.peel_to_commit()?;
-
-		let mut commits = BinaryHeap::with_capacity(10);
-		commits.push(TimeOrderedCommit(c));
+		Self::with_scope(repo, limit, &LogScope::Head)
+	}
 
-		Ok(Self {
-			commits,
+	/// walk all commits reachable from the refs in `scope`
+	pub fn with_scope(
+		repo: &'a Repository,
+		limit: usize,
+		scope: &LogScope,
+	) -> Result<Self> {
+		let mut walker = Self {
+			commits: BinaryHeap::with_capacity(10),
 			limit,
 			visited: HashSet::with_capacity(1000),
 			repo,
 			filter: None,
-		})
+			first_parent: false,
+			pathspec: None,
+			ref_masks: HashMap::new(),
+		};
+
+		if let LogScope::Refs(refs) = scope {
+			for (bit, reference) in refs.iter().take(64).enumerate() {
+				if let Some(id) = ref_commit(repo, reference) {
+					*walker.ref_masks.entry(id).or_default() |=
+						1 << bit;
+				}
+			}
+		}
+
+		for tip in log_tips(repo, scope)? {
+			walker.visit(repo.find_commit(tip)?);
+		}
+
+		Ok(walker)
 	}
 
 	///
@@ -61,6 +145,15 @@ impl<'a> LogWalker<'a> {
 		self.visited.len()
 	}
 
+	/// for a walk of `LogScope::Refs` returns a bitmask of the refs
+	/// (by index, up to 64) a read commit is reachable from
+	pub fn ref_mask(&self, id: &CommitId) -> u64 {
+		self.ref_masks
+			.get(&id.get_oid())
+			.copied()
+			.unwrap_or_default()
+	}
+
 	///
 	#[must_use]
 	pub fn filter(
@@ -70,18 +163,51 @@ impl<'a> LogWalker<'a> {
 		Self { filter, ..self }
 	}
 
+	/// only follow the first parent of merge commits
+	#[must_use]
+	pub fn first_parent(self, first_parent: bool) -> Self {
+		Self {
+			first_parent,
+			..self
+		}
+	}
+
+	/// only include commits touching `pathspec` (a file or directory
+	/// relative to the repository root)
+	#[must_use]
+	pub fn pathspec(self, pathspec: Option<String>) -> Self {
+		Self {
+			pathspec: pathspec.map(PathBuf::from),
+			..self
+		}
+	}
+
 	///
 	pub fn read(&mut self, out: &mut Vec<CommitId>) -> Result<usize> {
 		let mut count = 0_usize;
 
 		while let Some(c) = self.commits.pop() {
-			for p in c.0.parents() {
+			let mask = self.ref_masks.get(&c.0.id()).copied();
+
+			let parents = if self.first_parent {
+				c.0.parent(0).into_iter().collect()
+			} else {
+				c.0.parents().collect::<Vec<_>>()
+			};
+
+			for p in parents {
+				if let Some(mask) = mask {
+					*self.ref_masks.entry(p.id()).or_default() |=
+						mask;
+				}
+
 				self.visit(p);
 			}
 
 			let id: CommitId = c.0.id().into();
-			let commit_should_be_included =
-				if let Some(ref filter) = self.filter {
+			let commit_should_be_included = self
+				.touches_pathspec(&c.0)?
+				&& if let Some(ref filter) = self.filter {
 					filter(self.repo, &id)?
 				} else {
 					true
@@ -100,6 +226,32 @@ impl<'a> LogWalker<'a> {
 		Ok(count)
 	}
 
+	/// like `git log -- <path>` a commit is included unless its
+	/// version of the path equals the one of any of its parents
+	fn touches_pathspec(&self, commit: &Commit) -> Result<bool> {
+		let Some(path) = &self.pathspec else {
+			return Ok(true);
+		};
+
+		let entry_id = |c: &Commit| -> Result<Option<Oid>> {
+			Ok(c.tree()?.get_path(path).ok().map(|e| e.id()))
+		};
+
+		let own = entry_id(commit)?;
+
+		if commit.parent_count() == 0 {
+			return Ok(own.is_some());
+		}
+
+		for parent in commit.parents() {
+			if entry_id(&parent)? == own {
+				return Ok(false);
+			}
+		}
+
+		Ok(true)
+	}
+
 	//
 	fn visit(&mut self, c: Commit<'a>) {
 		if self.visited.insert(c.id()) {
@@ -129,20 +281,67 @@ impl<'a> LogWalkerWithoutFilter<'a> {
 	pub fn new(
 		repo: &'a mut gix::Repository,
 		limit: usize,
+		first_parent: bool,
+		scope: &LogScope,
 	) -> Result<Self> {
 		// This seems to be an object cache size that yields optimal performance. There’s no specific
 		// reason this is 2^14, so benchmarking might reveal that there’s better values.
 		repo.object_cache_size_if_unset(2_usize.pow(14));
 
-		let commit = repo.head()?.peel_to_commit_in_place()?;
+		let mut tips = Vec::new();
+
+		if let LogScope::Refs(refs) = scope {
+			for reference in refs {
+				if let Some(commit) = repo
+					.find_reference(reference.as_str())
+					.ok()
+					.and_then(|mut reference| {
+						reference.peel_to_commit().ok()
+					}) {
+					if !tips.contains(&commit.id) {
+						tips.push(commit.id);
+					}
+				}
+			}
+		} else {
+			match repo.head()?.peel_to_commit_in_place() {
+				Ok(commit) => tips.push(commit.id),
+				Err(e) if *scope == LogScope::Head => {
+					return Err(e.into())
+				}
+				Err(_) => (),
+			}
+		}
 
-		let tips = [commit.id];
+		if matches!(
+			scope,
+			LogScope::LocalBranches | LogScope::AllRefs
+		) {
+			let references = repo.references()?;
+			let iter = if *scope == LogScope::AllRefs {
+				references.all()?
+			} else {
+				references.local_branches()?
+			};
+
+			for mut reference in iter.flatten() {
+				if let Ok(commit) = reference.peel_to_commit() {
+					if !tips.contains(&commit.id) {
+						tips.push(commit.id);
+					}
+				}
+			}
+		}
 
-		let platform = repo
+		let mut platform = repo
 			.rev_walk(tips)
 			.sorting(gix::revision::walk::Sorting::ByCommitTime(gix::traverse::commit::simple::CommitTimeOrder::NewestFirst))
 			.use_commit_graph(false);
 
+		if first_parent {
+			platform = platform.first_parent_only();
+		}
+
 		let walk = platform.all()?;
 
 		Ok(Self {
@@ -274,7 +473,12 @@ mod tests {
 				gix::ThreadSafeRepository::discover_with_environment_overrides(repo_path.gitpath())
 						.map(Into::into)
 						.unwrap();
-		let mut walk = LogWalkerWithoutFilter::new(&mut repo, 100)?;
+		let mut walk = LogWalkerWithoutFilter::new(
+			&mut repo,
+			100,
+			false,
+			&LogScope::Head,
+		)?;
 		let mut items = Vec::new();
 		assert!(matches!(walk.read(&mut items), Ok(2)));
 
@@ -292,6 +496,199 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	fn test_logwalker_first_parent() -> Result<()> {
+		let file_path = Path::new("foo");
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		File::create(root.join(file_path))?.write_all(b"a")?;
+		stage_add_file(repo_path, file_path).unwrap();
+		let oid1 = commit(repo_path, "commit1").unwrap();
+
+		let c1 = repo.find_commit(oid1.into())?;
+		let sig = repo.signature()?;
+		let side = repo.commit(
+			None,
+			&sig,
+			&sig,
+			"side",
+			&c1.tree()?,
+			&[&c1],
+		)?;
+
+		File::create(root.join(file_path))?.write_all(b"b")?;
+		stage_add_file(repo_path, file_path).unwrap();
+		let oid2 = commit(repo_path, "commit2").unwrap();
+
+		let c2 = repo.find_commit(oid2.into())?;
+		let side = repo.find_commit(side)?;
+		let merge = repo.commit(
+			Some("HEAD"),
+			&sig,
+			&sig,
+			"merge",
+			&c2.tree()?,
+			&[&c2, &side],
+		)?;
+
+		assert_eq!(
+			LogWalker::new(&repo, 100)?.read(&mut Vec::new())?,
+			4
+		);
+
+		let mut items = Vec::new();
+		LogWalker::new(&repo, 100)?
+			.first_parent(true)
+			.read(&mut items)?;
+		assert_eq!(items, vec![merge.into(), oid2, oid1]);
+
+		let mut repo: gix::Repository =
+				gix::ThreadSafeRepository::discover_with_environment_overrides(repo_path.gitpath())
+						.map(Into::into)
+						.unwrap();
+		let mut items = Vec::new();
+		LogWalkerWithoutFilter::new(
+			&mut repo,
+			100,
+			true,
+			&LogScope::Head,
+		)?
+		.read(&mut items)?;
+		assert_eq!(items, vec![merge.into(), oid2, oid1]);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_logwalker_scope() -> Result<()> {
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 = write_commit_file(&repo, "a", "a", "commit1");
+		let c2 = write_commit_file(&repo, "a", "b", "commit2");
+
+		let first = repo.find_commit(c1.into())?;
+		let sig = repo.signature()?;
+		let side: CommitId = repo
+			.commit(
+				Some("refs/heads/side"),
+				&sig,
+				&sig,
+				"side",
+				&first.tree()?,
+				&[&first],
+			)?
+			.into();
+		let remote: CommitId = repo
+			.commit(
+				Some("refs/remotes/origin/other"),
+				&sig,
+				&sig,
+				"remote",
+				&first.tree()?,
+				&[&first],
+			)?
+			.into();
+
+		let sorted = |ids: &[CommitId]| {
+			let mut ids = ids.to_vec();
+			ids.sort();
+			ids
+		};
+
+		let mut gix_repo: gix::Repository =
+				gix::ThreadSafeRepository::discover_with_environment_overrides(repo_path.gitpath())
+						.map(Into::into)
+						.unwrap();
+
+		let mut items = Vec::new();
+
+		for (scope, ids) in [
+			(LogScope::Head, vec![c2, c1]),
+			(LogScope::LocalBranches, vec![c2, side, c1]),
+			(LogScope::AllRefs, vec![c2, side, remote, c1]),
+		] {
+			items.clear();
+			LogWalker::with_scope(&repo, 100, &scope)?
+				.read(&mut items)?;
+			assert_eq!(sorted(&items), sorted(&ids));
+
+			items.clear();
+			LogWalkerWithoutFilter::new(
+				&mut gix_repo,
+				100,
+				false,
+				&scope,
+			)?
+			.read(&mut items)?;
+			assert_eq!(sorted(&items), sorted(&ids));
+		}
+
+		let refs = LogScope::Refs(vec![
+			String::from("refs/heads/side"),
+			String::from("refs/remotes/origin/other"),
+		]);
+
+		items.clear();
+		LogWalkerWithoutFilter::new(
+			&mut gix_repo,
+			100,
+			false,
+			&refs,
+		)?
+		.read(&mut items)?;
+		assert_eq!(sorted(&items), sorted(&[side, remote, c1]));
+
+		items.clear();
+		let mut walker = LogWalker::with_scope(&repo, 100, &refs)?;
+		walker.read(&mut items)?;
+		assert_eq!(sorted(&items), sorted(&[side, remote, c1]));
+		assert_eq!(walker.ref_mask(&side), 0b01);
+		assert_eq!(walker.ref_mask(&remote), 0b10);
+		assert_eq!(walker.ref_mask(&c1), 0b11);
+		assert_eq!(walker.ref_mask(&c2), 0);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_logwalker_pathspec() -> Result<()> {
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+		std::fs::create_dir(root.join("dir"))?;
+
+		let c1 = write_commit_file(&repo, "dir/a", "a", "commit1");
+		write_commit_file(&repo, "b", "b", "commit2");
+		let c3 = write_commit_file(&repo, "dir/c", "c", "commit3");
+		let c4 = write_commit_file(&repo, "dir/a", "aa", "commit4");
+
+		let mut items = Vec::new();
+		LogWalker::new(&repo, 100)?
+			.pathspec(Some(String::from("dir")))
+			.read(&mut items)?;
+		assert_eq!(items, vec![c4, c3, c1]);
+
+		items.clear();
+		LogWalker::new(&repo, 100)?
+			.pathspec(Some(String::from("dir/a")))
+			.read(&mut items)?;
+		assert_eq!(items, vec![c4, c1]);
+
+		items.clear();
+		LogWalker::new(&repo, 100)?
+			.pathspec(Some(String::from("dir/a")))
+			.filter(Some(diff_contains_file("dir/c".into())))
+			.read(&mut items)?;
+		assert!(items.is_empty());
+
+		Ok(())
+	}
+
 	#[test]
 	fn test_logwalker_with_filter() -> Result<()> {
 		let file_path = Path::new("foo");
@@ -389,4 +786,47 @@ mod tests {
 
 		assert_eq!(items.len(), 2);
 	}
+
+	#[test]
+	fn test_logwalker_with_filter_pickaxe() {
+		let (_td, repo) = repo_init_empty().unwrap();
+
+		let c1 = write_commit_file(&repo, "foo", "fn a()\n", "c1");
+		let c2 =
+			write_commit_file(&repo, "foo", "fn a()\nfn b()\n", "c2");
+		// moves the line, the number of occurrences stays the same
+		let c3 =
+			write_commit_file(&repo, "foo", "fn b()\nfn a()\n", "c3");
+		let c4 = write_commit_file(&repo, "foo", "fn a()\n", "c4");
+
+		let search = |fields, pattern: &str| {
+			let log_filter = filter_commit_by_search(
+				LogFilterSearch::new(LogFilterSearchOptions {
+					fields,
+					options: SearchOptions::default(),
+					search_pattern: String::from(pattern),
+				}),
+			);
+
+			let mut items = Vec::new();
+			let mut walker = LogWalker::new(&repo, 100)
+				.unwrap()
+				.filter(Some(log_filter));
+			walker.read(&mut items).unwrap();
+			items
+		};
+
+		assert_eq!(
+			search(SearchFields::DIFF_OCCURRENCES, "FN B"),
+			vec![c4, c2]
+		);
+		assert_eq!(
+			search(SearchFields::DIFF_REGEX, r"^fn [ab]\(\)$"),
+			vec![c4, c3, c2, c1]
+		);
+		assert_eq!(
+			search(SearchFields::DIFF_REGEX, r"^fn c"),
+			Vec::<CommitId>::new()
+		);
+	}
 }
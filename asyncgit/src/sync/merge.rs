@@ -4,6 +4,7 @@ use crate::{
 		branch::merge_commit::commit_merge_with_head,
 		rebase::{
 			abort_rebase, continue_rebase, get_rebase_progress,
+			skip_rebase,
 		},
 		repository::repo,
 		reset_stage, reset_workdir, CommitId,
@@ -86,6 +87,17 @@ pub fn continue_pending_rebase(
 	continue_rebase(&repo)
 }
 
+///
+pub fn skip_pending_rebase(
+	repo_path: &RepoPath,
+) -> Result<RebaseState> {
+	scope_time!("skip_pending_rebase");
+
+	let repo = repo(repo_path)?;
+
+	skip_rebase(&repo)
+}
+
 ///
 pub fn abort_pending_rebase(repo_path: &RepoPath) -> Result<()> {
 	scope_time!("abort_pending_rebase");
@@ -3,109 +3,308 @@
 //TODO: remove once we have this activated on the toplevel
 #![deny(clippy::expect_used)]
 
+mod apply_patch;
+mod bisect;
 pub mod blame;
 pub mod branch;
+pub mod cherry;
+mod clean;
+mod combined_diff;
 pub mod commit;
 mod commit_details;
 pub mod commit_files;
 mod commit_filter;
+mod commit_graph;
+mod commit_lint;
 mod commit_revert;
 mod commits_info;
+mod compare;
 mod config;
+mod conflicts;
 pub mod cred;
 pub mod diff;
+mod difftool;
+mod discard_backup;
+mod file_history;
+mod format_patch;
 mod hooks;
 mod hunks;
 mod ignore;
+mod index_flags;
+mod lfs;
+mod locations;
 mod logwalker;
 mod merge;
+mod notes;
 mod patches;
+mod pre_commit;
 mod rebase;
+pub mod rebase_interactive;
+mod reflog;
 pub mod remotes;
+mod renames;
 mod repository;
 mod reset;
 mod reword;
+mod sequencer;
+mod shallow;
 pub mod sign;
+mod signing;
+mod ssh;
 mod staging;
 mod stash;
 mod state;
 pub mod status;
 mod submodules;
+mod symref;
 mod tags;
+mod textconv;
 mod tree;
+mod undo;
 pub mod utils;
+mod verify;
+mod worktrees;
 
-pub use blame::{blame_file, BlameHunk, FileBlame};
+pub use apply_patch::{
+	apply_mailbox_abort, apply_mailbox_continue,
+	apply_mailbox_progress, apply_mailbox_skip, apply_patch,
+	ApplyMailboxProgress, ApplyPatchResult, ApplyPatchTarget,
+};
+pub use bisect::{
+	bisect_mark, bisect_reset, bisect_state, is_bisecting,
+	BisectMark, BisectState,
+};
+pub use blame::{
+	blame_file, blame_ignore_revs_file, blame_parent,
+	set_blame_ignore_revs_file, BlameHunk, BlameParent, FileBlame,
+};
 pub use branch::{
-	branch_compare_upstream, checkout_branch, checkout_commit,
-	config_is_pull_rebase, create_branch, delete_branch,
+	branch_compare_ref, branch_compare_upstream, branches_compare,
+	checkout_branch, checkout_commit, create_branch,
+	create_branch_at, create_orphan_branch, delete_branch,
+	flow::{
+		flow_finish, flow_start, FlowBranchType, FlowConfig,
+		CONFIG_FLOW_DEVELOP, CONFIG_FLOW_FEATURE, CONFIG_FLOW_HOTFIX,
+		CONFIG_FLOW_MAIN, CONFIG_FLOW_RELEASE,
+		CONFIG_FLOW_VERSION_TAG,
+	},
 	get_branch_remote, get_branch_upstream_merge, get_branches_info,
+	is_commit_pushed,
 	merge_commit::merge_upstream_commit,
 	merge_ff::branch_merge_upstream_fastforward,
-	merge_rebase::merge_upstream_rebase, rename::rename_branch,
+	merge_rebase::merge_upstream_rebase,
+	protected::{
+		get_protected_branch_patterns, is_branch_protected,
+		CONFIG_PROTECTED_BRANCHES,
+	},
+	pull::{
+		pull_from, pull_options_config, pull_upstream, PullOptions,
+		PullOutcome, PullResult, PullStrategy,
+	},
+	rename::rename_branch,
+	set_branch_upstream,
+	stack::{
+		get_branch_stack, get_stack_children, get_stack_parent,
+		restack_children, set_stack_parent,
+	},
 	validate_branch_name, BranchCompare, BranchDetails, BranchInfo,
 };
-pub use commit::{amend, commit, tag_commit};
+pub use clean::{
+	clean_paths, clean_preview, CleanEntry, CleanOptions,
+};
+pub use commit::{
+	amend, commit, commit_amend_with_details, tag_commit,
+	AmendOptions,
+};
 pub use commit_details::{
-	get_commit_details, CommitDetails, CommitMessage, CommitSignature,
+	describe_commit, get_commit_details, CommitDetails,
+	CommitMessage, CommitSignature, CommitTrailer,
+};
+pub use commit_files::{
+	commit_parent_count, get_commit_files, get_merge_commit_files,
+	MergeDiffMode,
 };
-pub use commit_files::get_commit_files;
 pub use commit_filter::{
 	diff_contains_file, filter_commit_by_search, LogFilterSearch,
 	LogFilterSearchOptions, SearchFields, SearchOptions,
 	SharedCommitFilterFn,
 };
-pub use commit_revert::{commit_revert, revert_commit, revert_head};
+pub use commit_graph::{
+	commit_graph_needs_update, write_commit_graph,
+	CONFIG_COMMIT_GRAPH,
+};
+pub use commit_lint::{
+	lint_commit_message, lint_commit_message_command,
+	CommitLintConfig, LintProblem, LintSeverity, CONFIG_LINT_COMMAND,
+	CONFIG_LINT_CONVENTIONAL, CONFIG_LINT_IMPERATIVE,
+	CONFIG_LINT_SUBJECT_LENGTH,
+};
+pub use commit_revert::{
+	commit_revert, revert_commit, revert_head, revert_parents,
+	RevertParent,
+};
 pub use commits_info::{
-	get_commit_info, get_commits_info, CommitId, CommitInfo,
+	get_commit_info, get_commits_info, rev_parse_commit, CommitId,
+	CommitInfo,
+};
+pub use compare::{
+	compare_diff_ids, get_compare_commits, CompareMode,
 };
 pub use config::{
-	get_config_string, untracked_files_config,
-	ShowUntrackedFilesConfig,
+	get_config_entries, get_config_string, set_config_string,
+	untracked_files_config, ConfigEntry, ConfigOrigin, DiffAlgorithm,
+	ShowUntrackedFilesConfig, COMMON_CONFIG_KEYS,
+};
+pub use conflicts::{
+	get_conflict_file, resolve_conflict_file, ConflictChunk,
+	ConflictFile, ConflictRegion, ConflictResolution,
 };
 pub use diff::get_diff_commit;
+pub use difftool::{
+	configured_diff_tool, configured_merge_tool, difftool_command,
+	mergetool_command, DiffToolTarget, CONFIG_DIFF_TOOL,
+	CONFIG_MERGE_TOOL,
+};
+pub use discard_backup::{
+	discard_backup, discard_backup_paths, discard_backup_restore,
+	discard_backups, DiscardBackup,
+};
+pub use file_history::{
+	file_history, file_history_walk, line_history, line_history_walk,
+	FileHistoryChange, FileHistoryEntry,
+};
+pub use format_patch::{format_patches, FormatPatchOptions};
 pub use git2::BranchType;
 pub use hooks::{
 	hooks_commit_msg, hooks_post_commit, hooks_pre_commit,
-	hooks_prepare_commit_msg, HookResult, PrepareCommitMsgSource,
+	hooks_pre_commit_found, hooks_pre_commit_streamed,
+	hooks_pre_push, hooks_prepare_commit_msg, HookOutputLine,
+	HookResult, PrePushRef, PrepareCommitMsgSource,
+};
+pub use hunks::{
+	apply_commit_hunks, get_hunk_patch, reset_hunk,
+	stage_edited_hunk, stage_hunk, unstage_hunk,
 };
-pub use hunks::{reset_hunk, stage_hunk, unstage_hunk};
-pub use ignore::add_to_ignore;
-pub use logwalker::{LogWalker, LogWalkerWithoutFilter};
+pub use ignore::{
+	add_pattern_to_ignore, add_to_ignore, find_ignore_rule,
+	ignore_candidates, ignore_file_path, ignore_pattern,
+	read_ignore_file, write_ignore_file, IgnoreCandidates,
+	IgnoreFile, IgnorePatternKind, IgnorePreview, IgnoreRule,
+};
+pub use index_flags::{get_index_flagged, set_index_flag, IndexFlag};
+pub use lfs::{
+	is_lfs_tracked, lfs_file_states, lfs_lock, lfs_unlock,
+	parse_lfs_pointer, LfsDiff, LfsFileState, LfsPointer,
+};
+pub use locations::{
+	format_locations, get_conflict_locations, get_hunk_locations,
+	Location, LocationsFormat,
+};
+pub use logwalker::{LogScope, LogWalker, LogWalkerWithoutFilter};
 pub use merge::{
 	abort_pending_rebase, abort_pending_state,
 	continue_pending_rebase, merge_branch, merge_commit, merge_msg,
-	mergehead_ids, rebase_progress,
+	mergehead_ids, rebase_progress, skip_pending_rebase,
+};
+pub use notes::{
+	fetch_notes, get_note, notes_ref, push_notes, remove_note,
+	set_note,
+};
+pub use pre_commit::{
+	parse_pre_commit_output, uses_pre_commit_framework,
+	workdir_patch, PreCommitCheck, PreCommitCheckStatus,
+	StagedFilesSnapshot, PRE_COMMIT_CONFIG_FILE,
 };
-pub use rebase::rebase_branch;
+pub use rebase::{
+	move_commit, rebase_branch, CommitMove, RebaseState,
+};
+pub use reflog::{get_reflog, get_reflog_refs, ReflogEntry};
 pub use remotes::{
-	add_remote, delete_remote, get_default_remote,
+	auto_fetch_interval,
+	clone::{clone_repo, CloneOptions},
+	fetch_options_config, get_default_remote,
 	get_default_remote_for_fetch, get_default_remote_for_push,
-	get_remote_url, get_remotes, push::AsyncProgress, rename_remote,
-	tags::PushTagsProgress, update_remote_url, validate_remote_name,
+	get_remotes,
+	manage::{
+		add_remote, delete_remote, get_remote_push_url,
+		get_remote_url, rename_remote, update_remote_push_url,
+		update_remote_url, validate_remote_name, validate_remote_url,
+	},
+	push::{
+		force_push_preview, push_branch_to, push_branch_with_lease,
+		AsyncProgress, DiscardedCommit, ForcePushPreview,
+	},
+	remote_proxy,
+	tags::PushTagsProgress,
+	FetchDepth, FetchSummary, FetchedRef, RemoteFetchOptions,
+	RemoteTarget, CONFIG_AUTO_FETCH, CONFIG_PROXY,
+};
+pub use renames::{
+	stage_rename, unstage_rename, CONFIG_DIFF_RENAMES,
+	CONFIG_RENAME_THRESHOLD, CONFIG_STATUS_RENAMES,
 };
 pub(crate) use repository::repo;
 pub use repository::{RepoPath, RepoPathRef};
-pub use reset::{reset_repo, reset_stage, reset_workdir};
+pub use reset::{
+	reset_preview, reset_stage, reset_to, reset_workdir,
+	restore_file, ResetPreview,
+};
 pub use reword::reword;
+pub use sequencer::{
+	cherry_pick_commits, sequencer_abort, sequencer_continue,
+	sequencer_progress, sequencer_skip, CherryPickOptions,
+	SequencerAction, SequencerProgress, SequencerResult,
+};
+pub use shallow::{shallow_boundary, shallow_info, ShallowInfo};
+pub use signing::{
+	get_signing_status, list_signing_keys, set_commit_signing,
+	set_signing_key, SigningFormat, SigningKey, SigningStatus,
+};
+pub use ssh::{
+	is_ssh_key_encrypted, need_ssh_passphrase, ssh_identity,
+	CONFIG_IDENTITY_FILE,
+};
 pub use staging::{discard_lines, stage_lines};
 pub use stash::{
-	get_stashes, stash_apply, stash_drop, stash_pop, stash_save,
+	get_stashes, stash_apply, stash_apply_file, stash_apply_hunks,
+	stash_drop, stash_pop, stash_save, stash_save_hunks,
+	stash_save_paths, stash_worktree_files,
 };
 pub use state::{repo_state, RepoState};
 pub use status::is_workdir_clean;
 pub use submodules::{
-	get_submodules, submodule_parent_info, update_submodule,
-	SubmoduleInfo, SubmoduleParentInfo, SubmoduleStatus,
+	discard_submodule_changes, expand_submodule_changes,
+	get_submodule_states, get_submodules, init_submodule,
+	submodule_parent_info, submodule_repo_path, sync_submodule,
+	update_submodule, SubmoduleInfo, SubmoduleParentInfo,
+	SubmoduleState, SubmoduleStatus,
+};
+pub use symref::{
+	get_symref_targets, get_symrefs, set_symref, SymRef,
 };
 pub use tags::{
-	delete_tag, get_tags, get_tags_with_metadata, CommitTags, Tag,
-	TagWithMetadata, Tags,
+	delete_tag, get_tag_details, get_tags, get_tags_with_metadata,
+	CommitTags, Tag, TagDetails, TagSignature, TagWithMetadata, Tags,
+};
+pub use tree::{
+	file_revisions, tree_file, tree_file_content, tree_files,
+	FileRevisions, TreeFile,
+};
+pub use undo::{
+	undo_restore, undo_snapshot, undo_snapshots, UndoSnapshot,
 };
-pub use tree::{tree_file_content, tree_files, TreeFile};
 pub use utils::{
-	get_head, get_head_tuple, repo_dir, repo_open_error,
-	stage_add_all, stage_add_file, stage_addremoved, Head,
+	get_head, get_head_tuple, init_default_branch, init_repo,
+	is_bare_repo, repo_dir, repo_open_error, repo_watch_dirs,
+	stage_add_all, stage_add_file, stage_add_force, stage_addremoved,
+	Head, RepoWatchDirs,
+};
+pub use verify::{
+	verify_signature, SignatureValidity, SignatureVerification,
+};
+pub use worktrees::{
+	create_worktree, default_worktree_path, get_worktrees,
+	prune_worktrees, WorktreeInfo,
 };
 
 pub use git2::ResetType;
@@ -270,6 +469,41 @@ pub mod tests {
 		Ok((tmp_repo_dir, bare_repo))
 	}
 
+	/// signs commits and tags with a fresh ssh key that is trusted
+	/// for the committer email, the key lives in the returned dir
+	pub fn setup_ssh_signing(repo: &Repository) -> Result<TempDir> {
+		let keys = TempDir::new()?;
+		let dir = keys.path();
+		let key = dir.join("key");
+		let status = std::process::Command::new("ssh-keygen")
+			.args(["-q", "-t", "ed25519", "-N", "", "-f"])
+			.arg(&key)
+			.status()?;
+		assert!(status.success());
+
+		let public_key =
+			std::fs::read_to_string(key.with_extension("pub"))?;
+		let allowed = dir.join("allowed_signers");
+		std::fs::write(&allowed, format!("email {public_key}"))?;
+
+		{
+			let mut config = repo.config()?;
+			config.set_str("gpg.format", "ssh")?;
+			config.set_str(
+				"user.signingKey",
+				key.with_extension("pub").to_str().unwrap(),
+			)?;
+			config.set_str(
+				"gpg.ssh.allowedSignersFile",
+				allowed.to_str().unwrap(),
+			)?;
+			config.set_bool("commit.gpgSign", true)?;
+			config.set_bool("tag.gpgSign", true)?;
+		}
+
+		Ok(keys)
+	}
+
 	/// Calling `set_search_path` with an empty directory makes sure that there
 	/// is no git config interfering with our tests (for example user-local
 	/// `.gitconfig`).
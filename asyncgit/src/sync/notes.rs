@@ -0,0 +1,259 @@
+//! notes attached to commits (`git notes`), stored in the notes ref
+//! configured by `core.notesRef` (`refs/notes/commits` by default)
+
+use super::{
+	commit::signature_allow_undefined_name,
+	cred::BasicAuthCredential,
+	hooks::PrePushRef,
+	remotes::{push::ProgressNotification, repo_proxy, Callbacks},
+	repository::repo,
+	ssh::remote_identity,
+	CommitId, RepoPath,
+};
+use crate::error::{Error, Result};
+use crossbeam_channel::Sender;
+use git2::{ErrorCode, FetchOptions, Oid, PushOptions, Repository};
+use scopetime::scope_time;
+
+/// the ref the notes are read from and written to
+pub fn notes_ref(repo_path: &RepoPath) -> Result<String> {
+	let repo = repo(repo_path)?;
+
+	Ok(repo.note_default_ref()?)
+}
+
+/// note of commit `id`, `None` if it has none
+pub fn get_note(
+	repo_path: &RepoPath,
+	id: CommitId,
+) -> Result<Option<String>> {
+	scope_time!("get_note");
+
+	let repo = repo(repo_path)?;
+
+	note_of_commit(&repo, id)
+}
+
+pub fn note_of_commit(
+	repo: &Repository,
+	id: CommitId,
+) -> Result<Option<String>> {
+	match repo.find_note(None, id.into()) {
+		Ok(note) => Ok(note.message().map(String::from)),
+		Err(e) if e.code() == ErrorCode::NotFound => Ok(None),
+		Err(e) => Err(e.into()),
+	}
+}
+
+/// adds a note to commit `id`, replacing an existing one
+pub fn set_note(
+	repo_path: &RepoPath,
+	id: CommitId,
+	note: &str,
+) -> Result<()> {
+	scope_time!("set_note");
+
+	let repo = repo(repo_path)?;
+	let signature = signature_allow_undefined_name(&repo)?;
+
+	repo.note(&signature, &signature, None, id.into(), note, true)?;
+
+	Ok(())
+}
+
+/// removes the note of commit `id`
+pub fn remove_note(repo_path: &RepoPath, id: CommitId) -> Result<()> {
+	scope_time!("remove_note");
+
+	let repo = repo(repo_path)?;
+	let signature = signature_allow_undefined_name(&repo)?;
+
+	repo.note_delete(id.into(), None, &signature, &signature)?;
+
+	Ok(())
+}
+
+/// pushes the notes ref to the same ref on `remote`, fails if the
+/// remote notes are not an ancestor of ours
+pub fn push_notes(
+	repo_path: &RepoPath,
+	remote: &str,
+	basic_credential: Option<BasicAuthCredential>,
+	progress_sender: Option<Sender<ProgressNotification>>,
+) -> Result<()> {
+	scope_time!("push_notes");
+
+	let repo = repo(repo_path)?;
+	let notes_ref = repo.note_default_ref()?;
+	let local_oid = repo.refname_to_id(&notes_ref).map_err(|_| {
+		Error::Generic(format!("no notes in '{notes_ref}' to push"))
+	})?;
+
+	let mut remote = repo.find_remote(remote)?;
+
+	super::remotes::push::run_pre_push_hook(
+		repo_path,
+		&remote,
+		&[PrePushRef {
+			local_ref: notes_ref.clone(),
+			local_oid,
+			remote_ref: notes_ref.clone(),
+			remote_oid: Oid::zero(),
+		}],
+	)?;
+
+	let mut options = PushOptions::new();
+	let callbacks = Callbacks::new(progress_sender, basic_credential)
+		.with_ssh_identity(remote_identity(&repo, &remote));
+	options.remote_callbacks(callbacks.callbacks());
+	options.packbuilder_parallelism(0);
+	options.proxy_options(repo_proxy(&repo));
+
+	remote.push(
+		&[format!("{notes_ref}:{notes_ref}")],
+		Some(&mut options),
+	)?;
+	callbacks.approve_credential();
+
+	if let Some((reference, msg)) =
+		callbacks.get_stats()?.push_rejected_msg
+	{
+		return Err(Error::Generic(format!(
+			"push to '{reference}' rejected: {msg}"
+		)));
+	}
+
+	Ok(())
+}
+
+/// fetches the notes ref of `remote` into ours, only fast-forwarding
+/// so that local notes that were not pushed yet are never lost
+pub fn fetch_notes(
+	repo_path: &RepoPath,
+	remote: &str,
+	basic_credential: Option<BasicAuthCredential>,
+	progress_sender: Option<Sender<ProgressNotification>>,
+) -> Result<()> {
+	scope_time!("fetch_notes");
+
+	let repo = repo(repo_path)?;
+	let notes_ref = repo.note_default_ref()?;
+	let remote_name = remote;
+	let mut remote = repo.find_remote(remote)?;
+
+	// fetch into a temporary ref first to check for divergence
+	let fetched_ref = format!("refs/notes/remotes/{remote_name}");
+
+	let mut options = FetchOptions::new();
+	let callbacks = Callbacks::new(progress_sender, basic_credential)
+		.with_ssh_identity(remote_identity(&repo, &remote));
+	options.remote_callbacks(callbacks.callbacks());
+	options.proxy_options(repo_proxy(&repo));
+
+	remote.fetch(
+		&[format!("+{notes_ref}:{fetched_ref}")],
+		Some(&mut options),
+		None,
+	)?;
+	callbacks.approve_credential();
+
+	let Ok(fetched) = repo.refname_to_id(&fetched_ref) else {
+		return Err(Error::Generic(format!(
+			"remote '{remote_name}' has no notes in '{notes_ref}'"
+		)));
+	};
+
+	match repo.refname_to_id(&notes_ref) {
+		Ok(local) if local == fetched => {}
+		Ok(local) if !repo.graph_descendant_of(fetched, local)? => {
+			return Err(Error::Generic(format!(
+				"notes of remote '{remote_name}' diverged from local '{notes_ref}'"
+			)));
+		}
+		_ => {
+			repo.reference(
+				&notes_ref,
+				fetched,
+				true,
+				"gitui: fetch notes",
+			)?;
+		}
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::tests::{
+		repo_clone, repo_init, repo_init_bare, write_commit_file,
+	};
+
+	#[test]
+	fn test_notes() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let id = write_commit_file(&repo, "a.txt", "a", "c1");
+
+		assert_eq!(get_note(repo_path, id).unwrap(), None);
+
+		set_note(repo_path, id, "first").unwrap();
+		assert_eq!(
+			get_note(repo_path, id).unwrap().as_deref(),
+			Some("first")
+		);
+
+		set_note(repo_path, id, "second").unwrap();
+		assert_eq!(
+			get_note(repo_path, id).unwrap().as_deref(),
+			Some("second")
+		);
+
+		remove_note(repo_path, id).unwrap();
+		assert_eq!(get_note(repo_path, id).unwrap(), None);
+	}
+
+	#[test]
+	fn test_push_fetch_notes() {
+		let (remote_dir, _remote) = repo_init_bare().unwrap();
+		let remote_path = remote_dir.path().to_str().unwrap();
+
+		let (clone1_dir, clone1) = repo_clone(remote_path).unwrap();
+		let clone1_path: &RepoPath =
+			&clone1_dir.path().to_str().unwrap().into();
+		let (clone2_dir, _clone2) = repo_clone(remote_path).unwrap();
+		let clone2_path: &RepoPath =
+			&clone2_dir.path().to_str().unwrap().into();
+
+		let id = write_commit_file(&clone1, "a.txt", "a", "c1");
+
+		set_note(clone1_path, id, "note").unwrap();
+		push_notes(clone1_path, "origin", None, None).unwrap();
+
+		fetch_notes(clone2_path, "origin", None, None).unwrap();
+		assert_eq!(
+			get_note(clone2_path, id).unwrap().as_deref(),
+			Some("note")
+		);
+
+		// diverge: both clones change the note
+		set_note(clone1_path, id, "remote").unwrap();
+		push_notes(clone1_path, "origin", None, None).unwrap();
+		set_note(clone2_path, id, "local").unwrap();
+
+		assert!(
+			fetch_notes(clone2_path, "origin", None, None).is_err()
+		);
+		assert_eq!(
+			get_note(clone2_path, id).unwrap().as_deref(),
+			Some("local")
+		);
+		assert!(
+			push_notes(clone2_path, "origin", None, None).is_err()
+		);
+	}
+}
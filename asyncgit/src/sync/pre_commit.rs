@@ -0,0 +1,338 @@
+//! support for the [pre-commit](https://pre-commit.com) framework
+//!
+//! when a repo contains a `.pre-commit-config.yaml` the pre-commit
+//! hook is usually installed by the framework. its output lists one
+//! line per check (`name.....Passed`) followed by details of failed
+//! checks which we parse into [`PreCommitCheck`]s.
+
+use super::{
+	repository::repo,
+	status::{get_status, StatusType},
+	utils::work_dir,
+	RepoPath,
+};
+use crate::error::Result;
+use git2::{DiffFormat, DiffOptions, ObjectType, Oid};
+use scopetime::scope_time;
+
+/// config file of the pre-commit framework
+pub const PRE_COMMIT_CONFIG_FILE: &str = ".pre-commit-config.yaml";
+
+/// returns whether the repo is set up for the pre-commit framework
+pub fn uses_pre_commit_framework(
+	repo_path: &RepoPath,
+) -> Result<bool> {
+	let repo = repo(repo_path)?;
+
+	Ok(work_dir(&repo)?.join(PRE_COMMIT_CONFIG_FILE).is_file())
+}
+
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreCommitCheckStatus {
+	///
+	Passed,
+	///
+	Failed,
+	///
+	Skipped,
+}
+
+/// result of a single check run by the pre-commit framework
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreCommitCheck {
+	///
+	pub name: String,
+	///
+	pub status: PreCommitCheckStatus,
+	/// e.g. `no files to check` for skipped checks
+	pub note: Option<String>,
+	///
+	pub hook_id: Option<String>,
+	/// the check changed files (auto-fix)
+	pub files_modified: bool,
+	/// everything else the check printed
+	pub output: String,
+}
+
+fn parse_check_line(line: &str) -> Option<PreCommitCheck> {
+	let (rest, status) = [
+		("Passed", PreCommitCheckStatus::Passed),
+		("Failed", PreCommitCheckStatus::Failed),
+		("Skipped", PreCommitCheckStatus::Skipped),
+	]
+	.into_iter()
+	.find_map(|(suffix, status)| {
+		line.strip_suffix(suffix).map(|rest| (rest, status))
+	})?;
+
+	let (rest, note) = match rest.strip_suffix(')') {
+		Some(rest) => {
+			let start = rest.rfind('(')?;
+			(&rest[..start], Some(rest[start + 1..].to_string()))
+		}
+		None => (rest, None),
+	};
+
+	let name = rest.trim_end_matches('.');
+	if name.len() + 3 > rest.len() {
+		// not enough dots to be a check line
+		return None;
+	}
+
+	Some(PreCommitCheck {
+		name: name.trim().to_string(),
+		status,
+		note,
+		hook_id: None,
+		files_modified: false,
+		output: String::new(),
+	})
+}
+
+fn strip_ansi(line: &str) -> String {
+	let mut res = String::with_capacity(line.len());
+	let mut chars = line.chars();
+
+	while let Some(c) = chars.next() {
+		if c == '\u{1b}' {
+			// skip `ESC [ ... <letter>`
+			for c in chars.by_ref() {
+				if c.is_ascii_alphabetic() {
+					break;
+				}
+			}
+		} else {
+			res.push(c);
+		}
+	}
+
+	res
+}
+
+/// parses the output of the pre-commit framework into its checks,
+/// returns an empty list if the output is not from pre-commit
+pub fn parse_pre_commit_output(output: &str) -> Vec<PreCommitCheck> {
+	let mut checks: Vec<PreCommitCheck> = Vec::new();
+
+	for line in output.lines().map(strip_ansi) {
+		if let Some(check) = parse_check_line(line.trim_end()) {
+			checks.push(check);
+			continue;
+		}
+
+		let Some(check) = checks.last_mut() else {
+			continue;
+		};
+
+		if let Some(id) = line.strip_prefix("- hook id: ") {
+			check.hook_id = Some(id.trim().to_string());
+		} else if line.trim() == "- files were modified by this hook"
+		{
+			check.files_modified = true;
+		} else if !line.trim().is_empty() || !check.output.is_empty()
+		{
+			check.output.push_str(&line);
+			check.output.push('\n');
+		}
+	}
+
+	for check in &mut checks {
+		check.output.truncate(check.output.trim_end().len());
+	}
+
+	checks
+}
+
+/// working dir content of the staged files, taken before running
+/// hooks to find out which files they modified
+pub struct StagedFilesSnapshot(Vec<(String, Option<Oid>)>);
+
+impl StagedFilesSnapshot {
+	///
+	pub fn new(repo_path: &RepoPath) -> Result<Self> {
+		scope_time!("StagedFilesSnapshot::new");
+
+		let repo = repo(repo_path)?;
+		let work_dir = work_dir(&repo)?;
+
+		Ok(Self(
+			get_status(repo_path, StatusType::Stage, None)?
+				.into_iter()
+				.map(|item| {
+					let id = Oid::hash_file(
+						ObjectType::Blob,
+						work_dir.join(&item.path),
+					)
+					.ok();
+					(item.path, id)
+				})
+				.collect(),
+		))
+	}
+
+	/// staged files whose working dir content changed since the
+	/// snapshot was taken
+	pub fn modified_files(
+		&self,
+		repo_path: &RepoPath,
+	) -> Result<Vec<String>> {
+		let repo = repo(repo_path)?;
+		let work_dir = work_dir(&repo)?;
+
+		Ok(self
+			.0
+			.iter()
+			.filter(|(path, id)| {
+				Oid::hash_file(ObjectType::Blob, work_dir.join(path))
+					.ok() != *id
+			})
+			.map(|(path, _)| path.clone())
+			.collect())
+	}
+}
+
+/// unified diff between index and working dir of `paths`
+pub fn workdir_patch(
+	repo_path: &RepoPath,
+	paths: &[String],
+) -> Result<String> {
+	scope_time!("workdir_patch");
+
+	if paths.is_empty() {
+		return Ok(String::new());
+	}
+
+	let repo = repo(repo_path)?;
+
+	let mut options = DiffOptions::new();
+	options.disable_pathspec_match(true);
+	for path in paths {
+		options.pathspec(path);
+	}
+
+	let diff =
+		repo.diff_index_to_workdir(None, Some(&mut options))?;
+
+	let mut patch = String::new();
+	diff.print(DiffFormat::Patch, |_delta, _hunk, line| {
+		if matches!(line.origin(), '+' | '-' | ' ') {
+			patch.push(line.origin());
+		}
+		patch.push_str(&String::from_utf8_lossy(line.content()));
+		true
+	})?;
+
+	Ok(patch)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		stage_add_file,
+		tests::{repo_init, write_commit_file},
+	};
+	use std::{fs, path::Path};
+
+	const OUTPUT: &str = "[INFO] Initializing environment for https://github.com/pre-commit/pre-commit-hooks.
+trim trailing whitespace.................................................Failed
+- hook id: trailing-whitespace
+- exit code: 1
+- files were modified by this hook
+
+Fixing a.txt
+
+fix end of files.........................................................Passed
+check yaml...........................................(no files to check)Skipped
+";
+
+	#[test]
+	fn test_parse_pre_commit_output() {
+		let checks = parse_pre_commit_output(OUTPUT);
+
+		assert_eq!(
+			checks,
+			vec![
+				PreCommitCheck {
+					name: String::from("trim trailing whitespace"),
+					status: PreCommitCheckStatus::Failed,
+					note: None,
+					hook_id: Some(String::from(
+						"trailing-whitespace"
+					)),
+					files_modified: true,
+					output: String::from(
+						"- exit code: 1\n\nFixing a.txt"
+					),
+				},
+				PreCommitCheck {
+					name: String::from("fix end of files"),
+					status: PreCommitCheckStatus::Passed,
+					note: None,
+					hook_id: None,
+					files_modified: false,
+					output: String::new(),
+				},
+				PreCommitCheck {
+					name: String::from("check yaml"),
+					status: PreCommitCheckStatus::Skipped,
+					note: Some(String::from("no files to check")),
+					hook_id: None,
+					files_modified: false,
+					output: String::new(),
+				},
+			]
+		);
+	}
+
+	#[test]
+	fn test_parse_other_output() {
+		assert!(parse_pre_commit_output("lint Failed\nerror: x\n")
+			.is_empty());
+		assert_eq!(
+			parse_pre_commit_output(
+				"\u{1b}[41mlint....\u{1b}[m\u{1b}[41mFailed\u{1b}[m"
+			)[0]
+			.name,
+			"lint"
+		);
+	}
+
+	#[test]
+	fn test_modified_files() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		assert!(!uses_pre_commit_framework(repo_path).unwrap());
+		fs::write(root.join(PRE_COMMIT_CONFIG_FILE), "repos: []\n")
+			.unwrap();
+		assert!(uses_pre_commit_framework(repo_path).unwrap());
+
+		write_commit_file(&repo, "a.txt", "a\n", "a");
+		write_commit_file(&repo, "b.txt", "b\n", "b");
+
+		fs::write(root.join("a.txt"), "a \n").unwrap();
+		fs::write(root.join("b.txt"), "b2\n").unwrap();
+		stage_add_file(repo_path, Path::new("a.txt")).unwrap();
+		stage_add_file(repo_path, Path::new("b.txt")).unwrap();
+
+		let snapshot = StagedFilesSnapshot::new(repo_path).unwrap();
+		assert!(snapshot
+			.modified_files(repo_path)
+			.unwrap()
+			.is_empty());
+
+		// a hook strips the trailing whitespace
+		fs::write(root.join("a.txt"), "a\n").unwrap();
+
+		let modified = snapshot.modified_files(repo_path).unwrap();
+		assert_eq!(modified, vec![String::from("a.txt")]);
+
+		let patch = workdir_patch(repo_path, &modified).unwrap();
+		assert!(patch.starts_with("diff --git a/a.txt b/a.txt\n"));
+		assert!(patch.ends_with("@@ -1 +1 @@\n-a \n+a\n"));
+	}
+}
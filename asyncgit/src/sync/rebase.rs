@@ -1,12 +1,20 @@
 use git2::{BranchType, Repository};
 use scopetime::scope_time;
+use std::{
+	fs,
+	path::{Path, PathBuf},
+	process::Command,
+};
 
 use crate::{
 	error::{Error, Result},
 	sync::repository::repo,
 };
 
-use super::{CommitId, RepoPath};
+use super::{
+	branch::is_commit_pushed, rebase_interactive, utils::work_dir,
+	CommitId, RepoPath,
+};
 
 /// rebase current HEAD on `branch`
 pub fn rebase_branch(
@@ -75,6 +83,8 @@ pub enum RebaseState {
 	Finished,
 	///
 	Conflicted,
+	/// stopped at an `edit` step of an interactive rebase
+	Stopped,
 }
 
 /// rebase
@@ -110,6 +120,13 @@ pub fn rebase(
 pub fn continue_rebase(
 	repo: &git2::Repository,
 ) -> Result<RebaseState> {
+	if rebase_interactive::is_interactive(repo) {
+		return rebase_interactive::continue_interactive(repo);
+	}
+	if external_rebase_dir(repo).is_some() {
+		return external_rebase(repo, "--continue");
+	}
+
 	let mut rebase = repo.open_rebase(None)?;
 	let signature =
 		crate::sync::commit::signature_allow_undefined_name(repo)?;
@@ -123,6 +140,38 @@ pub fn continue_rebase(
 		rebase.commit(None, &signature, None)?;
 	}
 
+	run_remaining(repo, &mut rebase, &signature)
+}
+
+/// drops the changes of the current step and runs the remaining ones
+pub fn skip_rebase(repo: &git2::Repository) -> Result<RebaseState> {
+	if external_rebase_dir(repo).is_some() {
+		return external_rebase(repo, "--skip");
+	}
+
+	let mut index = repo.index()?;
+	index.read_tree(&repo.head()?.peel_to_tree()?)?;
+	index.write()?;
+	repo.checkout_head(Some(
+		git2::build::CheckoutBuilder::new().force(),
+	))?;
+
+	if rebase_interactive::is_interactive(repo) {
+		return rebase_interactive::skip_interactive(repo);
+	}
+
+	let mut rebase = repo.open_rebase(None)?;
+	let signature =
+		crate::sync::commit::signature_allow_undefined_name(repo)?;
+
+	run_remaining(repo, &mut rebase, &signature)
+}
+
+fn run_remaining(
+	repo: &git2::Repository,
+	rebase: &mut git2::Rebase,
+	signature: &git2::Signature,
+) -> Result<RebaseState> {
 	while let Some(op) = rebase.next() {
 		let _op = op?;
 		// dbg!(op.id());
@@ -131,18 +180,214 @@ pub fn continue_rebase(
 			return Ok(RebaseState::Conflicted);
 		}
 
-		rebase.commit(None, &signature, None)?;
+		rebase.commit(None, signature, None)?;
 	}
 
 	if repo.index()?.has_conflicts() {
 		return Ok(RebaseState::Conflicted);
 	}
 
-	rebase.finish(Some(&signature))?;
+	rebase.finish(Some(signature))?;
 
 	Ok(RebaseState::Finished)
 }
 
+/// state directory of a rebase git started that libgit2 cannot drive:
+/// interactive ones and those of the apply backend
+fn external_rebase_dir(repo: &Repository) -> Option<PathBuf> {
+	let merge_dir = repo.path().join("rebase-merge");
+	let apply_dir = repo.path().join("rebase-apply");
+
+	if merge_dir.join("interactive").exists()
+		&& !rebase_interactive::is_interactive(repo)
+	{
+		Some(merge_dir)
+	} else if apply_dir.join("rebasing").exists() {
+		Some(apply_dir)
+	} else {
+		None
+	}
+}
+
+/// hands `git rebase --continue`/`--skip` to git for rebases libgit2
+/// cannot drive, `GIT_EDITOR=true` keeps the messages instead of
+/// waiting for an editor that gitui cannot show
+fn external_rebase(
+	repo: &Repository,
+	arg: &str,
+) -> Result<RebaseState> {
+	let output = Command::new("git")
+		.args(["rebase", arg])
+		.env("GIT_EDITOR", "true")
+		.current_dir(work_dir(repo)?)
+		.output()?;
+
+	let error = || {
+		Error::Generic(
+			String::from_utf8_lossy(&output.stderr)
+				.trim()
+				.to_string(),
+		)
+	};
+
+	if external_rebase_dir(repo).is_none() {
+		return if output.status.success() {
+			Ok(RebaseState::Finished)
+		} else {
+			Err(error())
+		};
+	}
+
+	let mut index = repo.index()?;
+	index.read(true)?;
+
+	if index.has_conflicts() {
+		Ok(RebaseState::Conflicted)
+	} else if output.status.success() {
+		// `edit` or `break` step
+		Ok(RebaseState::Stopped)
+	} else {
+		Err(error())
+	}
+}
+
+fn read_state_file(dir: &Path, name: &str) -> Option<String> {
+	fs::read_to_string(dir.join(name))
+		.ok()
+		.map(|content| content.trim().to_string())
+}
+
+/// progress from the state files, for rebases libgit2 cannot open
+fn external_rebase_progress(dir: &Path) -> Option<RebaseProgress> {
+	let (current, steps) = if dir.ends_with("rebase-apply") {
+		("next", "last")
+	} else {
+		("msgnum", "end")
+	};
+
+	let current: usize =
+		read_state_file(dir, current)?.parse().ok()?;
+
+	Some(RebaseProgress {
+		steps: read_state_file(dir, steps)?.parse().ok()?,
+		current: current.saturating_sub(1),
+		current_commit: read_state_file(dir, "stopped-sha")
+			.or_else(|| read_state_file(dir, "original-commit"))
+			.and_then(|id| repo_rev_commit(dir, &id)),
+	})
+}
+
+/// the state files may hold abbreviated ids
+fn repo_rev_commit(dir: &Path, id: &str) -> Option<CommitId> {
+	let repo = Repository::open(dir.parent()?).ok()?;
+	let commit =
+		repo.revparse_single(id).ok()?.peel_to_commit().ok()?;
+
+	Some(commit.id().into())
+}
+
+/// resets to where git started the rebase and checks out the branch
+/// again, like `git rebase --abort`
+fn abort_external_rebase(
+	repo: &Repository,
+	dir: &Path,
+) -> Result<()> {
+	let orig_head =
+		read_state_file(dir, "orig-head").ok_or_else(|| {
+			Error::Generic(String::from("rebase without orig-head"))
+		})?;
+	let orig_head = git2::Oid::from_str(&orig_head)?;
+	let head_name = read_state_file(dir, "head-name");
+
+	// also removes the state directory
+	repo.reset(
+		repo.find_commit(orig_head)?.as_object(),
+		git2::ResetType::Hard,
+		Some(git2::build::CheckoutBuilder::new().force()),
+	)?;
+
+	match head_name {
+		Some(branch) if branch.starts_with("refs/") => {
+			repo.reference(
+				&branch,
+				orig_head,
+				true,
+				"rebase: abort",
+			)?;
+			repo.set_head(&branch)?;
+		}
+		_ => repo.set_head_detached(orig_head)?,
+	}
+
+	if dir.exists() {
+		fs::remove_dir_all(dir)?;
+	}
+
+	Ok(())
+}
+
+/// direction to move a commit in the log
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CommitMove {
+	/// towards `HEAD`, swapping with its child
+	Up,
+	/// away from `HEAD`, swapping with its parent
+	Down,
+}
+
+/// swaps `commit` with its neighbour on the current branch by running
+/// an interactive rebase over the affected range. stops in a pending
+/// rebase if a conflict appears so it can be resolved in the status
+/// view.
+pub fn move_commit(
+	repo_path: &RepoPath,
+	commit: CommitId,
+	direction: CommitMove,
+) -> Result<RebaseState> {
+	scope_time!("move_commit");
+
+	// older commit of the swapped pair
+	let older = match direction {
+		CommitMove::Up => commit,
+		CommitMove::Down => {
+			let repo = repo(repo_path)?;
+			let commit = repo.find_commit(commit.into())?;
+			commit.parent_id(0).map_err(|_| Error::NoParent)?.into()
+		}
+	};
+
+	if is_commit_pushed(repo_path, older)? {
+		return Err(Error::Generic(String::from(
+			"cannot reorder commits that are already pushed",
+		)));
+	}
+
+	let mut todo = rebase_interactive::rebase_interactive_todo(
+		repo_path, older,
+	)?;
+
+	match direction {
+		CommitMove::Up if todo.items.len() < 2 => {
+			return Err(Error::Generic(String::from(
+				"commit is already the newest",
+			)));
+		}
+		CommitMove::Down
+			if todo.items.get(1).map(|item| item.id)
+				!= Some(commit) =>
+		{
+			return Err(Error::Generic(String::from(
+				"commit is not part of the current branch",
+			)));
+		}
+		_ => (),
+	}
+
+	todo.items.swap(0, 1);
+
+	rebase_interactive::rebase_interactive(repo_path, &todo)
+}
+
 ///
 #[derive(PartialEq, Eq, Debug)]
 pub struct RebaseProgress {
@@ -158,6 +403,12 @@ pub struct RebaseProgress {
 pub fn get_rebase_progress(
 	repo: &git2::Repository,
 ) -> Result<RebaseProgress> {
+	if let Some(dir) = external_rebase_dir(repo) {
+		return external_rebase_progress(&dir).ok_or_else(|| {
+			Error::Generic(String::from("unreadable rebase state"))
+		});
+	}
+
 	let mut rebase = repo.open_rebase(None)?;
 
 	let current_commit: Option<CommitId> = rebase
@@ -176,6 +427,10 @@ pub fn get_rebase_progress(
 
 ///
 pub fn abort_rebase(repo: &git2::Repository) -> Result<()> {
+	if let Some(dir) = external_rebase_dir(repo) {
+		return abort_external_rebase(repo, &dir);
+	}
+
 	let mut rebase = repo.open_rebase(None)?;
 
 	rebase.abort()?;
@@ -287,14 +542,88 @@ mod test_rebase {
 	use crate::sync::{
 		checkout_branch, create_branch,
 		rebase::{
-			abort_rebase, get_rebase_progress, RebaseProgress,
+			abort_rebase, continue_rebase, get_rebase_progress,
+			move_commit, skip_rebase, CommitMove, RebaseProgress,
 			RebaseState,
 		},
-		rebase_branch, repo_state,
-		tests::{repo_init, write_commit_file},
-		RepoPath, RepoState,
+		rebase_branch, repo_state, stage_add_file,
+		tests::{get_commit_ids, repo_init, write_commit_file},
+		CommitId, RepoPath, RepoState,
 	};
-	use git2::BranchType;
+	use git2::{BranchType, Repository};
+	use std::{path::Path, process::Command};
+
+	fn messages(repo: &Repository, ids: &[CommitId]) -> Vec<String> {
+		ids.iter()
+			.map(|id| {
+				repo.find_commit((*id).into())
+					.unwrap()
+					.summary()
+					.unwrap()
+					.to_string()
+			})
+			.collect()
+	}
+
+	#[test]
+	fn test_move_commit() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "commit1");
+		let c2 = write_commit_file(&repo, "b.txt", "b", "commit2");
+		write_commit_file(&repo, "c.txt", "c", "commit3");
+
+		let r = move_commit(repo_path, c2, CommitMove::Up).unwrap();
+
+		assert_eq!(r, RebaseState::Finished);
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+		assert_eq!(
+			messages(&repo, &get_commit_ids(&repo, 3)),
+			vec!["commit2", "commit3", "commit1"]
+		);
+
+		let head = get_commit_ids(&repo, 1)[0];
+		let r =
+			move_commit(repo_path, head, CommitMove::Down).unwrap();
+
+		assert_eq!(r, RebaseState::Finished);
+		assert_eq!(
+			messages(&repo, &get_commit_ids(&repo, 3)),
+			vec!["commit3", "commit2", "commit1"]
+		);
+
+		let head = get_commit_ids(&repo, 1)[0];
+		assert!(move_commit(repo_path, head, CommitMove::Up).is_err());
+	}
+
+	#[test]
+	fn test_move_commit_conflict() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "test.txt", "test1", "commit1");
+		write_commit_file(&repo, "test.txt", "test2", "commit2");
+		let c3 =
+			write_commit_file(&repo, "test.txt", "test3", "commit3");
+
+		let r = move_commit(repo_path, c3, CommitMove::Down).unwrap();
+
+		assert_eq!(r, RebaseState::Conflicted);
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Rebase);
+		assert_eq!(
+			get_rebase_progress(&repo).unwrap().current_commit,
+			Some(c3)
+		);
+
+		abort_rebase(&repo).unwrap();
+
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+	}
 
 	#[test]
 	fn test_conflicted_abort() {
@@ -340,4 +669,160 @@ mod test_rebase {
 
 		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
 	}
+
+	#[test]
+	fn test_conflicted_skip() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "test.txt", "test1", "commit1");
+
+		create_branch(repo_path, "foo").unwrap();
+
+		write_commit_file(&repo, "test.txt", "test2", "commit2");
+
+		checkout_branch(repo_path, "master").unwrap();
+
+		let c3 =
+			write_commit_file(&repo, "test.txt", "test3", "commit3");
+
+		checkout_branch(repo_path, "foo").unwrap();
+
+		let r = rebase_branch(repo_path, "master", BranchType::Local)
+			.unwrap();
+
+		assert_eq!(r, RebaseState::Conflicted);
+
+		let r = skip_rebase(&repo).unwrap();
+
+		assert_eq!(r, RebaseState::Finished);
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+		assert_eq!(get_commit_ids(&repo, 1), vec![c3]);
+	}
+
+	#[test]
+	fn test_abort_external_interactive() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 =
+			write_commit_file(&repo, "test.txt", "test1", "commit1");
+		let c2 =
+			write_commit_file(&repo, "test.txt", "test2", "commit2");
+
+		// what `git rebase -i` leaves behind when stopped at `c2`
+		let dir = repo.path().join("rebase-merge");
+		std::fs::create_dir(&dir).unwrap();
+		for (file, content) in [
+			("interactive", String::new()),
+			("head-name", String::from("refs/heads/master")),
+			("orig-head", c2.to_string()),
+			("onto", c1.to_string()),
+			("msgnum", String::from("1")),
+			("end", String::from("1")),
+			("stopped-sha", c2.get_short_string()),
+		] {
+			std::fs::write(dir.join(file), content).unwrap();
+		}
+		repo.set_head_detached(c1.into()).unwrap();
+		repo.checkout_head(Some(
+			git2::build::CheckoutBuilder::new().force(),
+		))
+		.unwrap();
+
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Rebase);
+		assert_eq!(
+			get_rebase_progress(&repo).unwrap(),
+			RebaseProgress {
+				current: 0,
+				steps: 1,
+				current_commit: Some(c2)
+			}
+		);
+		abort_rebase(&repo).unwrap();
+
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+		assert_eq!(
+			repo.head().unwrap().name(),
+			Some("refs/heads/master")
+		);
+		assert_eq!(get_commit_ids(&repo, 1), vec![c2]);
+		assert!(!dir.exists());
+	}
+
+	/// `foo` and `master` both change `test.txt`, returns the commit
+	/// of `master` after `git rebase -i master` stopped on `foo`
+	fn external_conflict(repo: &Repository, root: &Path) -> CommitId {
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(repo, "test.txt", "test1", "commit1");
+		create_branch(repo_path, "foo").unwrap();
+		write_commit_file(repo, "test.txt", "test2", "commit2");
+		checkout_branch(repo_path, "master").unwrap();
+		let master =
+			write_commit_file(repo, "test.txt", "test3", "commit3");
+		checkout_branch(repo_path, "foo").unwrap();
+
+		let output = Command::new("git")
+			.args(["rebase", "-i", "master"])
+			.env("GIT_SEQUENCE_EDITOR", "true")
+			.current_dir(root)
+			.output()
+			.unwrap();
+		assert!(!output.status.success());
+
+		master
+	}
+
+	#[test]
+	fn test_continue_external() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let master = external_conflict(&repo, root);
+
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Rebase);
+		assert_eq!(
+			continue_rebase(&repo).unwrap(),
+			RebaseState::Conflicted
+		);
+
+		std::fs::write(root.join("test.txt"), "resolved").unwrap();
+		stage_add_file(repo_path, Path::new("test.txt")).unwrap();
+
+		assert_eq!(
+			continue_rebase(&repo).unwrap(),
+			RebaseState::Finished
+		);
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+		assert_eq!(
+			messages(&repo, &get_commit_ids(&repo, 2)),
+			vec!["commit2", "commit3"]
+		);
+		assert_eq!(get_commit_ids(&repo, 2)[1], master);
+	}
+
+	#[test]
+	fn test_skip_external() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let master = external_conflict(&repo, root);
+
+		assert_eq!(
+			skip_rebase(&repo).unwrap(),
+			RebaseState::Finished
+		);
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+		assert_eq!(get_commit_ids(&repo, 1), vec![master]);
+	}
 }
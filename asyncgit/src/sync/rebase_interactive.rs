@@ -0,0 +1,745 @@
+//! interactive rebase (`git rebase -i`) on top of the libgit2 sequencer
+//!
+//! libgit2 only knows how to pick commits. the plan libgit2 keeps in
+//! `rebase-merge/cmt.<n>` is rewritten to reorder and drop commits,
+//! the action of every step is kept next to it in `gitui-todo` so a
+//! rebase stopped by a conflict (or an `edit` step) can be continued
+//! through `continue_pending_rebase`.
+//!
+//! steps are committed by gitui instead of libgit2, which keeps
+//! `commit.gpgsign` working and drops steps that became empty.
+
+use super::{
+	commit::{create_signed_commit, signature_allow_undefined_name},
+	rebase::RebaseState,
+	repository::repo,
+	utils::get_head_repo,
+	CommitId, RepoPath,
+};
+use crate::error::{Error, Result};
+use git2::{Commit, Oid, Rebase, Repository, Signature, Tree};
+use scopetime::scope_time;
+use std::{fs, path::PathBuf};
+
+const TODO_FILE: &str = "gitui-todo";
+const STOPPED_FILE: &str = "gitui-stopped";
+
+/// what to do with a commit of an interactive rebase
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RebaseAction {
+	///
+	Pick,
+	/// pick with a new commit message
+	Reword(String),
+	/// pick and stop to allow amending the commit
+	Edit,
+	/// meld into the previous commit, keeping both messages
+	Squash,
+	/// meld into the previous commit, discarding this message
+	Fixup,
+	/// leave the commit out
+	Drop,
+}
+
+impl RebaseAction {
+	/// name as used in git's todo list
+	pub const fn name(&self) -> &'static str {
+		match self {
+			Self::Pick => "pick",
+			Self::Reword(_) => "reword",
+			Self::Edit => "edit",
+			Self::Squash => "squash",
+			Self::Fixup => "fixup",
+			Self::Drop => "drop",
+		}
+	}
+
+	const fn melds(&self) -> bool {
+		matches!(self, Self::Squash | Self::Fixup)
+	}
+}
+
+/// a line of the todo list
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RebaseTodoItem {
+	///
+	pub action: RebaseAction,
+	///
+	pub id: CommitId,
+	/// full message of the commit
+	pub message: String,
+}
+
+/// todo list of an interactive rebase onto `base`,
+/// items are in the order they get applied (oldest first)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RebaseTodo {
+	///
+	pub base: CommitId,
+	///
+	pub items: Vec<RebaseTodoItem>,
+}
+
+impl RebaseTodo {
+	/// moves `fixup! <subject>` and `squash! <subject>` commits right
+	/// behind the commit they refer to (by subject or hash prefix) and
+	/// marks them to be melded in, like `git rebase --autosquash`
+	pub fn autosquash(&mut self) {
+		let len = self.items.len();
+		// every commit is appended to the group of its target,
+		// groups keep the order the commits were created in
+		let mut group = (0..len).collect::<Vec<_>>();
+		let mut followers = vec![Vec::new(); len];
+
+		for idx in 0..len {
+			let subject = self.items[idx]
+				.message
+				.lines()
+				.next()
+				.unwrap_or_default()
+				.to_string();
+
+			let Some((action, target)) = fixup_target(&subject)
+			else {
+				continue;
+			};
+
+			let found = (0..idx).find(|other| {
+				let item = &self.items[*other];
+				item.message.lines().next() == Some(target)
+			});
+			let found = found.or_else(|| {
+				(0..idx).find(|other| {
+					target.len() >= 4
+						&& self.items[*other]
+							.id
+							.to_string()
+							.starts_with(target)
+				})
+			});
+
+			if let Some(found) = found {
+				self.items[idx].action = action;
+				group[idx] = group[found];
+				followers[group[found]].push(idx);
+			}
+		}
+
+		let mut items = std::mem::take(&mut self.items)
+			.into_iter()
+			.map(Some)
+			.collect::<Vec<_>>();
+
+		for idx in 0..len {
+			if group[idx] != idx {
+				continue;
+			}
+
+			for idx in std::iter::once(idx)
+				.chain(followers[idx].iter().copied())
+			{
+				if let Some(item) = items[idx].take() {
+					self.items.push(item);
+				}
+			}
+		}
+	}
+}
+
+/// splits `fixup! x`/`squash! x` subjects into the action and the
+/// referenced subject (which may be another `fixup! ...`)
+fn fixup_target(subject: &str) -> Option<(RebaseAction, &str)> {
+	subject
+		.strip_prefix("fixup! ")
+		.map(|target| (RebaseAction::Fixup, target))
+		.or_else(|| {
+			subject
+				.strip_prefix("squash! ")
+				.map(|target| (RebaseAction::Squash, target))
+		})
+}
+
+/// message for a commit to be folded into `target` by autosquash
+pub fn fixup_message(
+	repo_path: &RepoPath,
+	target: CommitId,
+	squash: bool,
+) -> Result<String> {
+	let repo = repo(repo_path)?;
+	let commit = repo.find_commit(target.into())?;
+
+	Ok(format!(
+		"{}! {}",
+		if squash { "squash" } else { "fixup" },
+		commit.summary().unwrap_or_default()
+	))
+}
+
+/// todo list picking `commit` and all its descendants up to `HEAD`
+pub fn rebase_interactive_todo(
+	repo_path: &RepoPath,
+	commit: CommitId,
+) -> Result<RebaseTodo> {
+	scope_time!("rebase_interactive_todo");
+
+	let repo = repo(repo_path)?;
+
+	let mut items = Vec::new();
+	let mut current =
+		repo.find_commit(get_head_repo(&repo)?.into())?;
+	loop {
+		if current.parent_count() > 1 {
+			return Err(Error::Generic(String::from(
+				"cannot rebase merge commits interactively",
+			)));
+		}
+
+		items.push(RebaseTodoItem {
+			action: RebaseAction::Pick,
+			id: current.id().into(),
+			message: current
+				.message()
+				.unwrap_or_default()
+				.to_string(),
+		});
+
+		if current.id() == commit.get_oid() {
+			break;
+		}
+
+		current = current.parent(0).map_err(|_| {
+			Error::Generic(String::from(
+				"commit is not part of the current branch",
+			))
+		})?;
+	}
+
+	let base = current.parent_id(0).map_err(|_| {
+		Error::Generic(String::from("cannot rebase the root commit"))
+	})?;
+
+	items.reverse();
+
+	Ok(RebaseTodo {
+		base: base.into(),
+		items,
+	})
+}
+
+/// starts the rebase described by `todo`. stops in a pending rebase
+/// on conflicts or `edit` steps, see `continue_pending_rebase`.
+pub fn rebase_interactive(
+	repo_path: &RepoPath,
+	todo: &RebaseTodo,
+) -> Result<RebaseState> {
+	scope_time!("rebase_interactive");
+
+	let steps = todo
+		.items
+		.iter()
+		.filter(|item| item.action != RebaseAction::Drop)
+		.collect::<Vec<_>>();
+
+	if steps.first().is_some_and(|item| item.action.melds()) {
+		return Err(Error::Generic(String::from(
+			"cannot squash without a previous commit",
+		)));
+	}
+
+	let repo = repo(repo_path)?;
+
+	let upstream = repo.find_annotated_commit(todo.base.into())?;
+
+	{
+		let mut rebase =
+			repo.rebase(None, Some(&upstream), None, None)?;
+
+		if rebase.len() != todo.items.len() {
+			rebase.abort()?;
+			return Err(Error::Generic(String::from(
+				"unexpected rebase sequence",
+			)));
+		}
+	}
+
+	let state_dir = state_dir(&repo);
+
+	for idx in (steps.len() + 1)..=todo.items.len() {
+		fs::remove_file(state_dir.join(format!("cmt.{idx}")))?;
+	}
+
+	let mut actions = String::new();
+	for (idx, item) in steps.iter().enumerate() {
+		fs::write(
+			state_dir.join(format!("cmt.{}", idx + 1)),
+			format!("{}\n", item.id),
+		)?;
+
+		if let RebaseAction::Reword(msg) = &item.action {
+			fs::write(
+				state_dir.join(format!("gitui-msg.{}", idx + 1)),
+				msg,
+			)?;
+		}
+
+		actions.push_str(item.action.name());
+		actions.push('\n');
+	}
+
+	fs::write(state_dir.join("end"), format!("{}\n", steps.len()))?;
+	fs::write(state_dir.join(TODO_FILE), actions)?;
+
+	let mut rebase = repo.open_rebase(None)?;
+
+	run(&repo, &mut rebase)
+}
+
+/// whether the pending rebase was started by `rebase_interactive`
+pub(crate) fn is_interactive(repo: &Repository) -> bool {
+	state_dir(repo).join(TODO_FILE).is_file()
+}
+
+/// commits the current step (unless it stopped after committing it
+/// already) and runs the remaining ones
+pub(crate) fn continue_interactive(
+	repo: &Repository,
+) -> Result<RebaseState> {
+	let mut rebase = repo.open_rebase(None)?;
+
+	let stopped = state_dir(repo).join(STOPPED_FILE);
+
+	if stopped.is_file() {
+		fs::remove_file(stopped)?;
+	} else if let Some(idx) = rebase.operation_current() {
+		if repo.index()?.has_conflicts() {
+			return Ok(RebaseState::Conflicted);
+		}
+
+		let signature = signature_allow_undefined_name(repo)?;
+		commit_step(repo, &mut rebase, idx, &signature)?;
+	}
+
+	run(repo, &mut rebase)
+}
+
+/// runs the remaining steps after the current one was dropped
+pub(crate) fn skip_interactive(
+	repo: &Repository,
+) -> Result<RebaseState> {
+	let mut rebase = repo.open_rebase(None)?;
+
+	let stopped = state_dir(repo).join(STOPPED_FILE);
+	if stopped.is_file() {
+		fs::remove_file(stopped)?;
+	}
+
+	run(repo, &mut rebase)
+}
+
+fn state_dir(repo: &Repository) -> PathBuf {
+	repo.path().join("rebase-merge")
+}
+
+fn action(repo: &Repository, idx: usize) -> Result<RebaseAction> {
+	let state_dir = state_dir(repo);
+
+	let todo = fs::read_to_string(state_dir.join(TODO_FILE))?;
+
+	Ok(match todo.lines().nth(idx).unwrap_or_default() {
+		"reword" => RebaseAction::Reword(fs::read_to_string(
+			state_dir.join(format!("gitui-msg.{}", idx + 1)),
+		)?),
+		"edit" => RebaseAction::Edit,
+		"squash" => RebaseAction::Squash,
+		"fixup" => RebaseAction::Fixup,
+		_ => RebaseAction::Pick,
+	})
+}
+
+fn run(
+	repo: &Repository,
+	rebase: &mut Rebase,
+) -> Result<RebaseState> {
+	let signature = signature_allow_undefined_name(repo)?;
+
+	while let Some(op) = rebase.next() {
+		op?;
+
+		if repo.index()?.has_conflicts() {
+			return Ok(RebaseState::Conflicted);
+		}
+
+		let idx = rebase.operation_current().unwrap_or_default();
+
+		if commit_step(repo, rebase, idx, &signature)? {
+			fs::write(state_dir(repo).join(STOPPED_FILE), "")?;
+			return Ok(RebaseState::Stopped);
+		}
+	}
+
+	rebase.finish(Some(&signature))?;
+
+	Ok(RebaseState::Finished)
+}
+
+/// commits the changes of step `idx`, returns whether to stop
+fn commit_step(
+	repo: &Repository,
+	rebase: &mut Rebase,
+	idx: usize,
+	signature: &Signature,
+) -> Result<bool> {
+	let action = action(repo, idx)?;
+
+	if action.melds() {
+		meld_into_head(repo, rebase, idx, &action, signature)?;
+		return Ok(false);
+	}
+
+	let step = step_commit(repo, rebase, idx)?;
+	let head = repo.head()?.peel_to_commit()?;
+	let tree = repo.find_tree(repo.index()?.write_tree()?)?;
+
+	// nothing left to apply, e.g. the change is already upstream
+	if tree.id() == head.tree_id() {
+		return Ok(action == RebaseAction::Edit);
+	}
+
+	let message = match &action {
+		RebaseAction::Reword(msg) => msg.as_str(),
+		_ => step.message_raw().unwrap_or_default(),
+	};
+
+	let id = write_commit(
+		repo,
+		&step.author(),
+		signature,
+		message,
+		&tree,
+		&[&head],
+	)?;
+
+	repo.set_head_detached(id)?;
+
+	Ok(action == RebaseAction::Edit)
+}
+
+fn step_commit<'a>(
+	repo: &'a Repository,
+	rebase: &mut Rebase,
+	idx: usize,
+) -> Result<Commit<'a>> {
+	let op_id =
+		rebase.nth(idx).map(|op| op.id()).ok_or_else(|| {
+			Error::Generic(String::from("invalid step"))
+		})?;
+
+	Ok(repo.find_commit(op_id)?)
+}
+
+/// creates a commit without updating any reference, signed like
+/// `reword` does when `commit.gpgsign` is set
+fn write_commit(
+	repo: &Repository,
+	author: &Signature,
+	committer: &Signature,
+	message: &str,
+	tree: &Tree,
+	parents: &[&Commit],
+) -> Result<Oid> {
+	let config = repo.config()?;
+
+	if config.get_bool("commit.gpgsign").unwrap_or(false) {
+		return create_signed_commit(
+			repo, &config, author, committer, message, tree, parents,
+		);
+	}
+
+	Ok(repo.commit(None, author, committer, message, tree, parents)?)
+}
+
+/// amends `HEAD` with the changes of step `idx`
+fn meld_into_head(
+	repo: &Repository,
+	rebase: &mut Rebase,
+	idx: usize,
+	action: &RebaseAction,
+	signature: &Signature,
+) -> Result<()> {
+	let step = step_commit(repo, rebase, idx)?;
+
+	let head = repo.head()?.peel_to_commit()?;
+
+	let message = if *action == RebaseAction::Squash {
+		format!(
+			"{}\n\n{}",
+			head.message().unwrap_or_default().trim_end(),
+			step.message().unwrap_or_default()
+		)
+	} else {
+		head.message().unwrap_or_default().to_string()
+	};
+
+	let tree = repo.find_tree(repo.index()?.write_tree()?)?;
+	let parents = head.parents().collect::<Vec<_>>();
+
+	let id = write_commit(
+		repo,
+		&head.author(),
+		signature,
+		&message,
+		&tree,
+		&parents.iter().collect::<Vec<_>>(),
+	)?;
+
+	repo.set_head_detached(id)?;
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		continue_pending_rebase, repo_state, skip_pending_rebase,
+		tests::{get_commit_ids, repo_init, write_commit_file},
+		RepoState,
+	};
+
+	fn messages(repo: &Repository, count: usize) -> Vec<String> {
+		get_commit_ids(repo, count)
+			.iter()
+			.map(|id| {
+				repo.find_commit((*id).into())
+					.unwrap()
+					.message()
+					.unwrap()
+					.to_string()
+			})
+			.collect()
+	}
+
+	#[test]
+	fn test_todo() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 = write_commit_file(&repo, "a.txt", "a", "c1");
+		let c2 = write_commit_file(&repo, "b.txt", "b", "c2");
+		let c3 = write_commit_file(&repo, "c.txt", "c", "c3");
+
+		let todo = rebase_interactive_todo(repo_path, c2).unwrap();
+
+		assert_eq!(todo.base, c1);
+		assert_eq!(
+			todo.items.iter().map(|item| item.id).collect::<Vec<_>>(),
+			vec![c2, c3]
+		);
+	}
+
+	#[test]
+	fn test_squash_first() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "base.txt", "base", "base");
+		let c1 = write_commit_file(&repo, "a.txt", "a", "c1");
+		write_commit_file(&repo, "b.txt", "b", "c2");
+
+		let mut todo =
+			rebase_interactive_todo(repo_path, c1).unwrap();
+		todo.items[0].action = RebaseAction::Drop;
+		todo.items[1].action = RebaseAction::Fixup;
+
+		assert!(rebase_interactive(repo_path, &todo).is_err());
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+	}
+
+	#[test]
+	fn test_rebase_interactive() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "base.txt", "base", "base");
+		let c1 = write_commit_file(&repo, "a.txt", "a", "c1");
+		write_commit_file(&repo, "b.txt", "b", "c2");
+		write_commit_file(&repo, "c.txt", "c", "c3");
+		write_commit_file(&repo, "d.txt", "d", "c4");
+
+		let mut todo =
+			rebase_interactive_todo(repo_path, c1).unwrap();
+		// c1 c2 c3 c4 -> reword(c1) c3 squash(c2) (c4 dropped)
+		todo.items[0].action =
+			RebaseAction::Reword(String::from("first"));
+		todo.items[1].action = RebaseAction::Squash;
+		todo.items[3].action = RebaseAction::Drop;
+		todo.items.swap(1, 2);
+
+		let state = rebase_interactive(repo_path, &todo).unwrap();
+
+		assert_eq!(state, RebaseState::Finished);
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+		assert_eq!(
+			messages(&repo, 3),
+			vec!["c3\n\nc2", "first", "base"]
+		);
+		assert!(root.join("b.txt").is_file());
+		assert!(!root.join("d.txt").exists());
+	}
+
+	#[test]
+	fn test_edit_and_conflict() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "base.txt", "base", "base");
+		let c1 = write_commit_file(&repo, "a.txt", "1", "c1");
+		write_commit_file(&repo, "a.txt", "2", "c2");
+
+		let mut todo =
+			rebase_interactive_todo(repo_path, c1).unwrap();
+		todo.items[0].action = RebaseAction::Edit;
+
+		let state = rebase_interactive(repo_path, &todo).unwrap();
+		assert_eq!(state, RebaseState::Stopped);
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Rebase);
+
+		let state = continue_pending_rebase(repo_path).unwrap();
+		assert_eq!(state, RebaseState::Finished);
+		assert_eq!(messages(&repo, 2), vec!["c2", "c1"]);
+
+		// swapping both edits of `a.txt` conflicts
+		let head = get_commit_ids(&repo, 2)[1];
+		let mut todo =
+			rebase_interactive_todo(repo_path, head).unwrap();
+		todo.items.swap(0, 1);
+
+		let state = rebase_interactive(repo_path, &todo).unwrap();
+		assert_eq!(state, RebaseState::Conflicted);
+		assert_eq!(
+			continue_pending_rebase(repo_path).unwrap(),
+			RebaseState::Conflicted
+		);
+
+		// dropping the conflicting c2 applies c1 on its own
+		assert_eq!(
+			skip_pending_rebase(repo_path).unwrap(),
+			RebaseState::Finished
+		);
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+		assert_eq!(messages(&repo, 2), vec!["c1", "base"]);
+	}
+
+	#[test]
+	fn test_empty_step() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "base.txt", "base", "base");
+		let c1 = write_commit_file(&repo, "a.txt", "a", "c1");
+		// same tree as c1, nothing left to apply when picked
+		write_commit_file(&repo, "a.txt", "a", "c2");
+		write_commit_file(&repo, "b.txt", "b", "c3");
+
+		let todo = rebase_interactive_todo(repo_path, c1).unwrap();
+
+		let state = rebase_interactive(repo_path, &todo).unwrap();
+
+		assert_eq!(state, RebaseState::Finished);
+		assert_eq!(repo_state(repo_path).unwrap(), RepoState::Clean);
+		assert_eq!(messages(&repo, 3), vec!["c3", "c1", "base"]);
+	}
+
+	fn todo_item(id: &str, message: &str) -> RebaseTodoItem {
+		RebaseTodoItem {
+			action: RebaseAction::Pick,
+			id: CommitId::new(git2::Oid::from_str(id).unwrap()),
+			message: message.to_string(),
+		}
+	}
+
+	#[test]
+	fn test_autosquash_order() {
+		let mut todo = RebaseTodo {
+			base: CommitId::default(),
+			items: vec![
+				todo_item("a1", "a\n\nbody"),
+				todo_item("b2", "b"),
+				todo_item("c3", "fixup! a"),
+				todo_item("d4", "squash! b"),
+				todo_item("e5", "fixup! fixup! a"),
+				todo_item("f6", "fixup! unknown"),
+				todo_item("a7", "fixup! b200"),
+			],
+		};
+
+		todo.autosquash();
+
+		assert_eq!(
+			todo.items
+				.iter()
+				.map(|item| (
+					item.message.as_str(),
+					item.action.name()
+				))
+				.collect::<Vec<_>>(),
+			vec![
+				("a\n\nbody", "pick"),
+				("fixup! a", "fixup"),
+				("fixup! fixup! a", "fixup"),
+				("b", "pick"),
+				("squash! b", "squash"),
+				("fixup! b200", "fixup"),
+				("fixup! unknown", "pick"),
+			]
+		);
+	}
+
+	#[test]
+	fn test_autosquash_rebase() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "base.txt", "base", "base");
+		let c1 = write_commit_file(&repo, "a.txt", "a", "c1");
+		write_commit_file(&repo, "b.txt", "b", "c2");
+		let msg = fixup_message(repo_path, c1, false).unwrap();
+		assert_eq!(msg, "fixup! c1");
+		write_commit_file(&repo, "a.txt", "a fixed", &msg);
+
+		let mut todo =
+			rebase_interactive_todo(repo_path, c1).unwrap();
+		todo.autosquash();
+
+		assert_eq!(
+			rebase_interactive(repo_path, &todo).unwrap(),
+			RebaseState::Finished
+		);
+		assert_eq!(messages(&repo, 3), vec!["c2", "c1", "base"]);
+
+		let head = repo.head().unwrap().peel_to_commit().unwrap();
+		let c1_new = head.parent(0).unwrap();
+		let blob = c1_new
+			.tree()
+			.unwrap()
+			.get_name("a.txt")
+			.unwrap()
+			.to_object(&repo)
+			.unwrap()
+			.peel_to_blob()
+			.unwrap();
+		assert_eq!(blob.content(), b"a fixed");
+	}
+}
@@ -0,0 +1,119 @@
+//! reading the reflogs of `HEAD` and local branches
+
+use super::{repository::repo, CommitId, RepoPath};
+use crate::error::Result;
+use git2::BranchType;
+use scopetime::scope_time;
+
+/// a single reflog entry, where the ref pointed to after the change
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReflogEntry {
+	/// `<ref>@{<n>}`
+	pub selector: String,
+	///
+	pub id: CommitId,
+	/// what the ref pointed to before, `None` on creation
+	pub id_old: Option<CommitId>,
+	///
+	pub message: String,
+	///
+	pub committer: String,
+	///
+	pub time: i64,
+}
+
+/// `HEAD` followed by all local branches that have a reflog
+pub fn get_reflog_refs(repo_path: &RepoPath) -> Result<Vec<String>> {
+	scope_time!("get_reflog_refs");
+
+	let repo = repo(repo_path)?;
+
+	let mut refs = vec![String::from("HEAD")];
+
+	for branch in repo.branches(Some(BranchType::Local))? {
+		let (branch, _) = branch?;
+		if let Some(name) = branch.get().name() {
+			if !repo.reflog(name)?.is_empty() {
+				refs.push(name.to_string());
+			}
+		}
+	}
+
+	Ok(refs)
+}
+
+/// entries of the reflog of `refname`, newest first
+pub fn get_reflog(
+	repo_path: &RepoPath,
+	refname: &str,
+) -> Result<Vec<ReflogEntry>> {
+	scope_time!("get_reflog");
+
+	let repo = repo(repo_path)?;
+
+	let short =
+		refname.strip_prefix("refs/heads/").unwrap_or(refname);
+
+	Ok(repo
+		.reflog(refname)?
+		.iter()
+		.enumerate()
+		.map(|(idx, entry)| {
+			let committer = entry.committer();
+
+			ReflogEntry {
+				selector: format!("{short}@{{{idx}}}"),
+				id: entry.id_new().into(),
+				id_old: Some(entry.id_old())
+					.filter(|id| !id.is_zero())
+					.map(CommitId::from),
+				message: entry
+					.message()
+					.unwrap_or_default()
+					.to_string(),
+				committer: committer
+					.name()
+					.unwrap_or_default()
+					.to_string(),
+				time: committer.when().seconds(),
+			}
+		})
+		.collect())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		checkout_commit, create_branch,
+		tests::{repo_init, write_commit_file},
+	};
+
+	#[test]
+	fn test_reflog() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 = write_commit_file(&repo, "a.txt", "1", "c1");
+		let c2 = write_commit_file(&repo, "a.txt", "2", "c2");
+
+		create_branch(repo_path, "other").unwrap();
+		checkout_commit(repo_path, c1).unwrap();
+
+		let refs = get_reflog_refs(repo_path).unwrap();
+		assert_eq!(refs[0], "HEAD");
+		assert!(refs.contains(&String::from("refs/heads/master")));
+
+		let entries = get_reflog(repo_path, "HEAD").unwrap();
+		assert_eq!(entries[0].selector, "HEAD@{0}");
+		assert_eq!(entries[0].id, c1);
+		assert_eq!(entries[0].id_old, Some(c2));
+
+		let entries =
+			get_reflog(repo_path, "refs/heads/master").unwrap();
+		assert_eq!(entries[0].selector, "master@{0}");
+		assert_eq!(entries[0].id, c2);
+	}
+}
@@ -1,10 +1,24 @@
 use super::push::ProgressNotification;
-use crate::{error::Result, sync::cred::BasicAuthCredential};
+use crate::{
+	error::Result,
+	sync::{
+		cred::{
+			approve_credential, forget_credential,
+			BasicAuthCredential,
+		},
+		ssh::ssh_agent_running,
+	},
+};
 use crossbeam_channel::Sender;
-use git2::{Cred, Error as GitError, RemoteCallbacks};
-use std::sync::{
-	atomic::{AtomicBool, Ordering},
-	Arc, Mutex,
+use git2::{Cred, Error as GitError, Oid, RemoteCallbacks};
+use std::{
+	collections::HashMap,
+	path::PathBuf,
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc, Mutex,
+	},
+	time::{Duration, Instant},
 };
 
 ///
@@ -13,13 +27,23 @@ pub struct CallbackStats {
 	pub push_rejected_msg: Option<(String, String)>,
 }
 
-///
+/// hooks a remote operation up to the progress `sender` and the
+/// credentials. once the receiving end of `sender` is gone the
+/// operation is cancelled: fetches abort with the next transfer
+/// update, pushes only before or after uploading the pack since
+/// libgit2 can't abort in between
 #[derive(Clone)]
 pub struct Callbacks {
 	sender: Option<Sender<ProgressNotification>>,
+	cancelled: Arc<AtomicBool>,
+	transfer_start: Arc<Mutex<Option<Instant>>>,
 	basic_credential: Option<BasicAuthCredential>,
 	stats: Arc<Mutex<CallbackStats>>,
 	first_call_to_credentials: Arc<AtomicBool>,
+	lease: Option<Arc<HashMap<String, Oid>>>,
+	ssh_identity: Option<PathBuf>,
+	/// url `basic_credential` got sent to
+	credential_url: Arc<Mutex<Option<String>>>,
 }
 
 impl Callbacks {
@@ -32,12 +56,77 @@ impl Callbacks {
 
 		Self {
 			sender,
+			cancelled: Arc::new(AtomicBool::new(false)),
+			transfer_start: Arc::new(Mutex::new(None)),
 			basic_credential,
 			stats,
 			first_call_to_credentials: Arc::new(AtomicBool::new(
 				true,
 			)),
+			lease: None,
+			ssh_identity: None,
+			credential_url: Arc::new(Mutex::new(None)),
+		}
+	}
+
+	/// to be called once the remote operation succeeded: hands the
+	/// credentials it used to the credential helpers to store them
+	pub fn approve_credential(&self) {
+		let url = self
+			.credential_url
+			.lock()
+			.ok()
+			.and_then(|mut url| url.take());
+
+		if let (Some(url), Some(cred)) = (url, &self.basic_credential)
+		{
+			approve_credential(&url, cred);
+		}
+	}
+
+	/// key file to authenticate with over ssh when there is no agent
+	/// or `basic_credential` holds its passphrase
+	#[must_use]
+	pub fn with_ssh_identity(
+		mut self,
+		identity: Option<PathBuf>,
+	) -> Self {
+		self.ssh_identity = identity;
+		self
+	}
+
+	/// only allow updating the remote refs (key) if they still point
+	/// to the expected commit (value, zero if expected to not exist)
+	#[must_use]
+	pub fn with_lease(mut self, lease: HashMap<String, Oid>) -> Self {
+		self.lease = Some(Arc::new(lease));
+		self
+	}
+
+	/// `true` once nobody listens to the progress anymore
+	pub fn is_cancelled(&self) -> bool {
+		self.cancelled.load(Ordering::Relaxed)
+	}
+
+	/// returns `false` if the operation got cancelled
+	fn send(&self, notification: ProgressNotification) -> bool {
+		if let Some(sender) = &self.sender {
+			if sender.send(notification).is_err() {
+				self.cancelled.store(true, Ordering::Relaxed);
+			}
 		}
+
+		!self.is_cancelled()
+	}
+
+	/// time since the first transferred object
+	fn transfer_elapsed(&self) -> Duration {
+		self.transfer_start.lock().map_or(
+			Duration::ZERO,
+			|mut start| {
+				start.get_or_insert_with(Instant::now).elapsed()
+			},
+		)
 	}
 
 	///
@@ -64,10 +153,8 @@ impl Callbacks {
 		});
 
 		let this = self.clone();
-		callbacks.transfer_progress(move |p| {
-			this.transfer_progress(&p);
-			true
-		});
+		callbacks
+			.transfer_progress(move |p| this.transfer_progress(&p));
 
 		let this = self.clone();
 		callbacks.pack_progress(move |stage, current, total| {
@@ -91,17 +178,57 @@ impl Callbacks {
 			},
 		);
 
+		let this = self.clone();
+		callbacks.push_negotiation(move |updates| {
+			if this.is_cancelled() {
+				return Err(GitError::from_str("push cancelled"));
+			}
+
+			this.lease.as_ref().map_or(Ok(()), |lease| {
+				Self::check_lease(lease, updates)
+			})
+		});
+
+		let this = self.clone();
 		callbacks.sideband_progress(move |data| {
 			log::debug!(
 				"sideband transfer: '{}'",
 				String::from_utf8_lossy(data).trim()
 			);
-			true
+			!this.is_cancelled()
 		});
 
 		callbacks
 	}
 
+	fn check_lease(
+		lease: &HashMap<String, Oid>,
+		updates: &[git2::PushUpdate],
+	) -> std::result::Result<(), GitError> {
+		for update in updates {
+			let Some(dst) = update.dst_refname() else {
+				continue;
+			};
+
+			if let Some(expected) = lease.get(dst) {
+				if update.src() != *expected {
+					log::debug!(
+						"push lease broken: '{}' is {} (expected {})",
+						dst,
+						update.src(),
+						expected
+					);
+
+					return Err(GitError::from_str(&format!(
+						"stale info: '{dst}' changed on remote since last fetch"
+					)));
+				}
+			}
+		}
+
+		Ok(())
+	}
+
 	fn push_update_reference(
 		&self,
 		reference: &str,
@@ -126,37 +253,35 @@ impl Callbacks {
 		current: usize,
 	) {
 		log::debug!("packing: {:?} - {}/{}", stage, current, total);
-		self.sender.clone().map(|sender| {
-			sender.send(ProgressNotification::Packing {
-				stage,
-				total,
-				current,
-			})
+		self.send(ProgressNotification::Packing {
+			stage,
+			total,
+			current,
 		});
 	}
 
-	fn transfer_progress(&self, p: &git2::Progress) {
+	/// returning `false` aborts the fetch
+	fn transfer_progress(&self, p: &git2::Progress) -> bool {
 		log::debug!(
-			"transfer: {}/{}",
+			"transfer: {}/{} ({} B)",
 			p.received_objects(),
-			p.total_objects()
+			p.total_objects(),
+			p.received_bytes()
 		);
-		self.sender.clone().map(|sender| {
-			sender.send(ProgressNotification::Transfer {
-				objects: p.received_objects(),
-				total_objects: p.total_objects(),
-			})
-		});
+		self.send(ProgressNotification::Transfer {
+			objects: p.received_objects(),
+			total_objects: p.total_objects(),
+			received_bytes: p.received_bytes(),
+			elapsed: self.transfer_elapsed(),
+		})
 	}
 
 	fn update_tips(&self, name: &str, a: git2::Oid, b: git2::Oid) {
 		log::debug!("update tips: '{}' [{}] [{}]", name, a, b);
-		self.sender.clone().map(|sender| {
-			sender.send(ProgressNotification::UpdateTips {
-				name: name.to_string(),
-				a: a.into(),
-				b: b.into(),
-			})
+		self.send(ProgressNotification::UpdateTips {
+			name: name.to_string(),
+			a: a.into(),
+			b: b.into(),
 		});
 	}
 
@@ -167,12 +292,11 @@ impl Callbacks {
 		bytes: usize,
 	) {
 		log::debug!("progress: {}/{} ({} B)", current, total, bytes,);
-		self.sender.clone().map(|sender| {
-			sender.send(ProgressNotification::PushTransfer {
-				current,
-				total,
-				bytes,
-			})
+		self.send(ProgressNotification::PushTransfer {
+			current,
+			total,
+			bytes,
+			elapsed: self.transfer_elapsed(),
 		});
 	}
 
@@ -193,28 +317,57 @@ impl Callbacks {
 			allowed_types
 		);
 
+		if self.is_cancelled() {
+			return Err(GitError::from_str("cancelled"));
+		}
+
 		// This boolean is used to avoid multiple calls to credentials callback.
 		if self.first_call_to_credentials.load(Ordering::Relaxed) {
 			self.first_call_to_credentials
 				.store(false, Ordering::Relaxed);
 		} else {
+			if let Some(cred) = &self.basic_credential {
+				forget_credential(url, cred);
+			}
+
 			return Err(GitError::from_str("Bad credentials."));
 		}
 
 		match &self.basic_credential {
-			_ if allowed_types.is_ssh_key() => username_from_url
-				.map_or_else(
-					|| {
-						Err(GitError::from_str(
-							" Couldn't extract username from url.",
-						))
-					},
-					Cred::ssh_key_from_agent,
-				),
+			_ if allowed_types.is_ssh_key() => {
+				let Some(user) = username_from_url else {
+					return Err(GitError::from_str(
+						" Couldn't extract username from url.",
+					));
+				};
+
+				let passphrase = self
+					.basic_credential
+					.as_ref()
+					.and_then(|cred| cred.password.as_deref());
+
+				match &self.ssh_identity {
+					Some(identity)
+						if passphrase.is_some()
+							|| !ssh_agent_running() =>
+					{
+						Cred::ssh_key(
+							user, None, identity, passphrase,
+						)
+					}
+					_ => Cred::ssh_key_from_agent(user),
+				}
+			}
 			Some(BasicAuthCredential {
 				username: Some(user),
 				password: Some(pwd),
 			}) if allowed_types.is_user_pass_plaintext() => {
+				if let Ok(mut credential_url) =
+					self.credential_url.lock()
+				{
+					*credential_url = Some(url.to_string());
+				}
+
 				Cred::userpass_plaintext(user, pwd)
 			}
 			Some(BasicAuthCredential {
@@ -0,0 +1,166 @@
+//! cloning of repositories
+
+use super::{proxy_options, Callbacks};
+use crate::{
+	error::Result,
+	sync::{
+		cred::BasicAuthCredential,
+		remotes::push::ProgressNotification, ssh::identity_for_url,
+	},
+};
+use crossbeam_channel::Sender;
+use git2::{
+	build::RepoBuilder, Config, FetchOptions, Repository,
+	SubmoduleUpdateOptions,
+};
+use scopetime::scope_time;
+use std::path::Path;
+
+///
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
+pub struct CloneOptions {
+	/// only fetch this many commits of history (shallow clone)
+	pub depth: Option<u32>,
+	/// clone and checkout all submodules recursively
+	pub recurse_submodules: bool,
+}
+
+fn fetch_options<'a>(
+	callbacks: &Callbacks,
+	config: Option<&Config>,
+) -> FetchOptions<'a> {
+	let mut options = FetchOptions::new();
+	options.proxy_options(proxy_options(config));
+	options.remote_callbacks(callbacks.callbacks());
+	options
+}
+
+/// clones `url` into `path` (which must not exist or be empty)
+#[allow(clippy::needless_pass_by_value)]
+pub fn clone_repo(
+	url: &str,
+	path: &Path,
+	options: &CloneOptions,
+	basic_credential: Option<BasicAuthCredential>,
+	progress_sender: Option<Sender<ProgressNotification>>,
+) -> Result<()> {
+	scope_time!("clone_repo");
+
+	let callbacks = Callbacks::new(
+		progress_sender.clone(),
+		basic_credential.clone(),
+	)
+	.with_ssh_identity(identity_for_url(
+		Config::open_default().ok().as_ref(),
+		url,
+	));
+
+	let mut fetch_options = fetch_options(
+		&callbacks,
+		Config::open_default().ok().as_ref(),
+	);
+	if let Some(depth) = options.depth {
+		fetch_options.depth(i32::try_from(depth)?);
+	}
+
+	let repo = RepoBuilder::new()
+		.fetch_options(fetch_options)
+		.clone(url, path)?;
+	callbacks.approve_credential();
+
+	if options.recurse_submodules {
+		update_submodules_recursive(
+			&repo,
+			basic_credential.as_ref(),
+			progress_sender.as_ref(),
+		)?;
+	}
+
+	Ok(())
+}
+
+fn update_submodules_recursive(
+	repo: &Repository,
+	basic_credential: Option<&BasicAuthCredential>,
+	progress_sender: Option<&Sender<ProgressNotification>>,
+) -> Result<()> {
+	for mut submodule in repo.submodules()? {
+		log::debug!("clone submodule: {:?}", submodule.path());
+
+		// every remote needs its own callbacks to allow asking for credentials again
+		let callbacks = Callbacks::new(
+			progress_sender.cloned(),
+			basic_credential.cloned(),
+		)
+		.with_ssh_identity(submodule.url().and_then(|url| {
+			identity_for_url(repo.config().ok().as_ref(), url)
+		}));
+
+		let mut options = SubmoduleUpdateOptions::new();
+		options.fetch(fetch_options(
+			&callbacks,
+			repo.config().ok().as_ref(),
+		));
+
+		submodule.update(true, Some(&mut options))?;
+		callbacks.approve_credential();
+
+		update_submodules_recursive(
+			&submodule.open()?,
+			basic_credential,
+			progress_sender,
+		)?;
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::tests::{repo_init, write_commit_file};
+	use tempfile::TempDir;
+
+	#[test]
+	fn test_clone() {
+		let (remote_dir, remote) = repo_init().unwrap();
+		let head = write_commit_file(&remote, "a.txt", "a", "a1");
+
+		let td = TempDir::new().unwrap();
+		let path = td.path().join("cloned");
+
+		clone_repo(
+			remote_dir.path().to_str().unwrap(),
+			&path,
+			&CloneOptions {
+				depth: None,
+				recurse_submodules: true,
+			},
+			None,
+			None,
+		)
+		.unwrap();
+
+		let cloned = Repository::open(&path).unwrap();
+		assert_eq!(
+			cloned.head().unwrap().peel_to_commit().unwrap().id(),
+			head.into()
+		);
+		assert!(path.join("a.txt").exists());
+	}
+
+	#[test]
+	fn test_clone_into_existing_fails() {
+		let (remote_dir, _remote) = repo_init().unwrap();
+		let (target_dir, _target) = repo_init().unwrap();
+
+		assert!(clone_repo(
+			remote_dir.path().to_str().unwrap(),
+			target_dir.path(),
+			&CloneOptions::default(),
+			None,
+			None,
+		)
+		.is_err());
+	}
+}
@@ -0,0 +1,248 @@
+//! adding, renaming, removing remotes and editing their urls
+
+use crate::{
+	error::{Error, Result},
+	sync::{repository::repo, RepoPath},
+};
+use git2::{Remote, Repository};
+use scopetime::scope_time;
+
+/// url schemes git understands natively
+const URL_SCHEMES: [&str; 7] =
+	["http", "https", "ssh", "git", "file", "ftp", "ftps"];
+
+///
+pub fn validate_remote_name(name: &str) -> bool {
+	Remote::is_valid_name(name)
+}
+
+/// whether git can make sense of `url`: a `scheme://` url, a
+/// `transport::address` for remote helpers, an scp like
+/// `user@host:path` or a local path
+pub fn validate_remote_url(url: &str) -> bool {
+	if url.is_empty() || url.chars().any(char::is_whitespace) {
+		return false;
+	}
+
+	if let Some((scheme, rest)) = url.split_once("://") {
+		return URL_SCHEMES.contains(&scheme.to_lowercase().as_str())
+			&& !rest.is_empty();
+	}
+
+	if let Some((transport, address)) = url.split_once("::") {
+		return !transport.is_empty() && !address.is_empty();
+	}
+
+	true
+}
+
+fn ensure_name_available(
+	repo: &Repository,
+	name: &str,
+) -> Result<()> {
+	if !validate_remote_name(name) {
+		return Err(Error::Generic(format!(
+			"'{name}' is not a valid remote name"
+		)));
+	}
+
+	if repo.find_remote(name).is_ok() {
+		return Err(Error::Generic(format!(
+			"remote '{name}' already exists"
+		)));
+	}
+
+	Ok(())
+}
+
+fn ensure_url_valid(url: &str) -> Result<()> {
+	if validate_remote_url(url) {
+		Ok(())
+	} else {
+		Err(Error::Generic(format!("'{url}' is not a valid url")))
+	}
+}
+
+///
+pub fn add_remote(
+	repo_path: &RepoPath,
+	name: &str,
+	url: &str,
+) -> Result<()> {
+	scope_time!("add_remote");
+
+	let repo = repo(repo_path)?;
+	ensure_name_available(&repo, name)?;
+	ensure_url_valid(url)?;
+
+	repo.remote(name, url)?;
+	Ok(())
+}
+
+/// renames the remote together with its remote tracking branches
+/// and the upstream config of local branches
+pub fn rename_remote(
+	repo_path: &RepoPath,
+	name: &str,
+	new_name: &str,
+) -> Result<()> {
+	scope_time!("rename_remote");
+
+	let repo = repo(repo_path)?;
+	ensure_name_available(&repo, new_name)?;
+
+	repo.remote_rename(name, new_name)?;
+	Ok(())
+}
+
+///
+pub fn update_remote_url(
+	repo_path: &RepoPath,
+	name: &str,
+	new_url: &str,
+) -> Result<()> {
+	scope_time!("update_remote_url");
+
+	ensure_url_valid(new_url)?;
+
+	let repo = repo(repo_path)?;
+	repo.remote_set_url(name, new_url)?;
+	Ok(())
+}
+
+/// sets the url used for pushing (`remote.<name>.pushurl`), an empty
+/// `new_url` removes it so pushes use the fetch url again
+pub fn update_remote_push_url(
+	repo_path: &RepoPath,
+	name: &str,
+	new_url: &str,
+) -> Result<()> {
+	scope_time!("update_remote_push_url");
+
+	let repo = repo(repo_path)?;
+
+	if new_url.is_empty() {
+		repo.remote_set_pushurl(name, None)?;
+	} else {
+		ensure_url_valid(new_url)?;
+		repo.remote_set_pushurl(name, Some(new_url))?;
+	}
+
+	Ok(())
+}
+
+/// removes the remote together with its remote tracking branches
+pub fn delete_remote(
+	repo_path: &RepoPath,
+	remote_name: &str,
+) -> Result<()> {
+	scope_time!("delete_remote");
+
+	let repo = repo(repo_path)?;
+	repo.remote_delete(remote_name)?;
+	Ok(())
+}
+
+///
+pub fn get_remote_url(
+	repo_path: &RepoPath,
+	remote_name: &str,
+) -> Result<Option<String>> {
+	let repo = repo(repo_path)?;
+	let remote = repo.find_remote(remote_name)?;
+	Ok(remote.url().map(String::from))
+}
+
+/// the explicitly configured push url of the remote, if any
+pub fn get_remote_push_url(
+	repo_path: &RepoPath,
+	remote_name: &str,
+) -> Result<Option<String>> {
+	let repo = repo(repo_path)?;
+	let remote = repo.find_remote(remote_name)?;
+	Ok(remote.pushurl().map(String::from))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		remotes::get_remotes,
+		tests::{repo_clone, repo_init},
+	};
+
+	#[test]
+	fn test_validate_remote_url() {
+		assert!(validate_remote_url("https://example.com/repo.git"));
+		assert!(validate_remote_url("git@example.com:repo.git"));
+		assert!(validate_remote_url("ssh://git@example.com/repo"));
+		assert!(validate_remote_url("/tmp/repo"));
+		assert!(validate_remote_url("hg::https://example.com/repo"));
+
+		assert!(!validate_remote_url(""));
+		assert!(!validate_remote_url("https://"));
+		assert!(!validate_remote_url("foo://example.com/repo"));
+		assert!(!validate_remote_url("https://example.com/my repo"));
+	}
+
+	#[test]
+	fn test_manage_remotes() {
+		let (remote_dir, _remote) = repo_init().unwrap();
+		let remote_path = remote_dir.path().to_str().unwrap();
+		let (repo_dir, _repo) = repo_clone(remote_path).unwrap();
+		let repo_path: &RepoPath =
+			&repo_dir.path().to_str().unwrap().into();
+
+		assert!(add_remote(repo_path, "origin", remote_path).is_err());
+		assert!(
+			add_remote(repo_path, "in valid", remote_path).is_err()
+		);
+		assert!(add_remote(repo_path, "second", "").is_err());
+
+		add_remote(repo_path, "second", remote_path).unwrap();
+		assert!(rename_remote(repo_path, "second", "origin").is_err());
+		rename_remote(repo_path, "second", "third").unwrap();
+
+		assert_eq!(
+			get_remotes(repo_path).unwrap(),
+			vec![String::from("origin"), String::from("third")]
+		);
+
+		assert!(update_remote_url(repo_path, "third", "").is_err());
+		update_remote_url(
+			repo_path,
+			"third",
+			"git@example.com:x.git",
+		)
+		.unwrap();
+		update_remote_push_url(
+			repo_path,
+			"third",
+			"ssh://git@example.com/push.git",
+		)
+		.unwrap();
+
+		assert_eq!(
+			get_remote_url(repo_path, "third").unwrap().as_deref(),
+			Some("git@example.com:x.git")
+		);
+		assert_eq!(
+			get_remote_push_url(repo_path, "third")
+				.unwrap()
+				.as_deref(),
+			Some("ssh://git@example.com/push.git")
+		);
+
+		update_remote_push_url(repo_path, "third", "").unwrap();
+		assert_eq!(
+			get_remote_push_url(repo_path, "third").unwrap(),
+			None
+		);
+
+		delete_remote(repo_path, "third").unwrap();
+		assert_eq!(
+			get_remotes(repo_path).unwrap(),
+			vec![String::from("origin")]
+		);
+	}
+}
@@ -1,6 +1,8 @@
 //!
 
 mod callbacks;
+pub(crate) mod clone;
+pub mod manage;
 pub(crate) mod push;
 pub(crate) mod tags;
 
@@ -8,18 +10,24 @@ use crate::{
 	error::{Error, Result},
 	sync::{
 		cred::BasicAuthCredential,
-		remotes::push::ProgressNotification, repository::repo, utils,
+		remotes::push::ProgressNotification, repository::repo,
+		ssh::remote_identity, utils, CommitId,
 	},
-	ProgressPercent,
 };
 use crossbeam_channel::Sender;
 use git2::{
-	BranchType, FetchOptions, ProxyOptions, Remote, Repository,
+	BranchType, Config, FetchOptions, Oid, ProxyOptions, Repository,
 };
 use scopetime::scope_time;
+use std::{collections::HashMap, time::Duration};
 use utils::bytes2string;
 
 pub use callbacks::Callbacks;
+pub use manage::{
+	add_remote, delete_remote, get_remote_push_url, get_remote_url,
+	rename_remote, update_remote_push_url, update_remote_url,
+	validate_remote_name, validate_remote_url,
+};
 pub use tags::tags_missing_remote;
 
 use super::RepoPath;
@@ -27,59 +35,92 @@ use super::RepoPath;
 /// origin
 pub const DEFAULT_REMOTE_NAME: &str = "origin";
 
-///
-pub fn proxy_auto<'a>() -> ProxyOptions<'a> {
-	let mut proxy = ProxyOptions::new();
-	proxy.auto();
-	proxy
+/// a branch on a chosen remote to push to or pull from instead of
+/// the upstream of the local branch
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RemoteTarget {
+	///
+	pub remote: String,
+	/// branch name on the remote
+	pub branch: String,
+	/// make this the upstream of the local branch afterwards
+	pub set_upstream: bool,
 }
 
-///
-pub fn add_remote(
-	repo_path: &RepoPath,
-	name: &str,
-	url: &str,
-) -> Result<()> {
-	let repo = repo(repo_path)?;
-	repo.remote(name, url)?;
-	Ok(())
+impl RemoteTarget {
+	/// remote tracking ref the target branch is fetched into
+	pub fn tracking_ref(&self) -> String {
+		format!("refs/remotes/{}/{}", self.remote, self.branch)
+	}
 }
 
-///
-pub fn rename_remote(
-	repo_path: &RepoPath,
-	name: &str,
-	new_name: &str,
-) -> Result<()> {
-	let repo = repo(repo_path)?;
-	repo.remote_rename(name, new_name)?;
-	Ok(())
+/// `gitui.proxy`: proxy url for all network operations of gitui,
+/// overriding `remote.<name>.proxy`, `http.proxy` and the
+/// `http(s)_proxy` environment variables. empty to connect directly
+pub const CONFIG_PROXY: &str = "gitui.proxy";
+
+/// proxy from `gitui.proxy` in `config`, otherwise detected by
+/// libgit2 like git does it
+pub fn proxy_options<'a>(
+	config: Option<&Config>,
+) -> ProxyOptions<'a> {
+	let mut proxy = ProxyOptions::new();
+
+	match config
+		.and_then(|config| config.get_string(CONFIG_PROXY).ok())
+	{
+		Some(url) if url.trim().is_empty() => (),
+		Some(url) => {
+			proxy.url(url.trim());
+		}
+		None => {
+			proxy.auto();
+		}
+	}
+
+	proxy
 }
 
-///
-pub fn update_remote_url(
-	repo_path: &RepoPath,
-	name: &str,
-	new_url: &str,
-) -> Result<()> {
-	let repo = repo(repo_path)?;
-	repo.remote_set_url(name, new_url)?;
-	Ok(())
+pub(crate) fn repo_proxy<'a>(repo: &Repository) -> ProxyOptions<'a> {
+	proxy_options(repo.config().ok().as_ref())
 }
 
-///
-pub fn delete_remote(
+/// proxy the network operations with `remote` go through, `None` for
+/// a direct connection. looks at `gitui.proxy`, `remote.<name>.proxy`,
+/// `http.proxy` and the environment (ignoring `no_proxy`)
+pub fn remote_proxy(
 	repo_path: &RepoPath,
-	remote_name: &str,
-) -> Result<()> {
+	remote: &str,
+) -> Result<Option<String>> {
 	let repo = repo(repo_path)?;
-	repo.remote_delete(remote_name)?;
-	Ok(())
-}
+	let config = repo.config()?;
 
-///
-pub fn validate_remote_name(name: &str) -> bool {
-	Remote::is_valid_name(name)
+	if let Ok(url) = config.get_string(CONFIG_PROXY) {
+		return Ok(Some(url).filter(|url| !url.trim().is_empty()));
+	}
+
+	let url = get_remote_url(repo_path, remote)?.unwrap_or_default();
+	if !url.starts_with("http") {
+		return Ok(None);
+	}
+
+	if let Ok(proxy) = config
+		.get_string(&format!("remote.{remote}.proxy"))
+		.or_else(|_| config.get_string("http.proxy"))
+	{
+		return Ok(Some(proxy).filter(|proxy| !proxy.is_empty()));
+	}
+
+	let env = if url.starts_with("https") {
+		["https_proxy", "HTTPS_PROXY"]
+	} else {
+		["http_proxy", "HTTP_PROXY"]
+	};
+
+	Ok(env
+		.iter()
+		.find_map(|name| std::env::var(name).ok())
+		.filter(|proxy| !proxy.is_empty()))
 }
 
 ///
@@ -94,20 +135,6 @@ pub fn get_remotes(repo_path: &RepoPath) -> Result<Vec<String>> {
 	Ok(remotes)
 }
 
-///
-pub fn get_remote_url(
-	repo_path: &RepoPath,
-	remote_name: &str,
-) -> Result<Option<String>> {
-	let repo = repo(repo_path)?;
-	let remote = repo.find_remote(remote_name)?.clone();
-	let url = remote.url();
-	if let Some(u) = url {
-		return Ok(Some(u.to_string()));
-	}
-	Ok(None)
-}
-
 /// tries to find origin or the only remote that is defined if any
 /// in case of multiple remotes and none named *origin* we fail
 pub fn get_default_remote(repo_path: &RepoPath) -> Result<String> {
@@ -266,10 +293,160 @@ pub(crate) fn get_default_remote_in_repo(
 	Err(Error::NoDefaultRemoteFound)
 }
 
+/// what `fetch_all` fetches, like the options of `git fetch`
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RemoteFetchOptions {
+	/// only fetch this remote instead of all of them
+	pub remote: Option<String>,
+	/// remove remote tracking branches (and tags if `tags` is set)
+	/// that no longer exist on the remote, like `--prune`
+	pub prune: bool,
+	/// fetch all tags, like `--tags`, otherwise only tags pointing
+	/// into the fetched history
+	pub tags: bool,
+	/// how much history to fetch into a shallow clone, `None` keeps
+	/// the current graft points
+	pub depth: Option<FetchDepth>,
+}
+
+/// how much history a fetch loads into a shallow clone
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FetchDepth {
+	/// history up to this many commits from the remote tips, like
+	/// `--depth`
+	Commits(u32),
+	/// the complete history, like `--unshallow`
+	Unshallow,
+}
+
+/// fetch options as configured via `fetch.prune`, fetching all
+/// remotes and their tags
+pub fn fetch_options_config(
+	repo_path: &RepoPath,
+) -> Result<RemoteFetchOptions> {
+	let repo = repo(repo_path)?;
+	let prune =
+		repo.config()?.get_bool("fetch.prune").unwrap_or_default();
+
+	Ok(RemoteFetchOptions {
+		remote: None,
+		prune,
+		tags: true,
+		depth: None,
+	})
+}
+
+/// `gitui.autoFetch`: seconds between fetching all remotes in the
+/// background, unset or zero to not fetch automatically
+pub const CONFIG_AUTO_FETCH: &str = "gitui.autoFetch";
+
+/// `None` unless background fetching is enabled
+pub fn auto_fetch_interval(
+	repo_path: &RepoPath,
+) -> Result<Option<Duration>> {
+	let repo = repo(repo_path)?;
+	let secs = repo.config()?.get_i64(CONFIG_AUTO_FETCH).unwrap_or(0);
+
+	Ok(u64::try_from(secs)
+		.ok()
+		.filter(|secs| *secs > 0)
+		.map(Duration::from_secs))
+}
+
+/// a remote tracking branch or tag changed by a fetch
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FetchedRef {
+	///
+	pub name: String,
+	/// `None` if the ref got created
+	pub old: Option<CommitId>,
+	/// `None` if the ref got deleted
+	pub new: Option<CommitId>,
+}
+
+/// refs created, updated or deleted by `fetch_all`
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FetchSummary {
+	/// sorted by name
+	pub refs: Vec<FetchedRef>,
+}
+
+impl FetchSummary {
+	///
+	pub fn created(&self) -> impl Iterator<Item = &FetchedRef> {
+		self.refs.iter().filter(|r| r.old.is_none())
+	}
+
+	///
+	pub fn updated(&self) -> impl Iterator<Item = &FetchedRef> {
+		self.refs
+			.iter()
+			.filter(|r| r.old.is_some() && r.new.is_some())
+	}
+
+	///
+	pub fn deleted(&self) -> impl Iterator<Item = &FetchedRef> {
+		self.refs.iter().filter(|r| r.new.is_none())
+	}
+}
+
+/// targets of the remote tracking branches of `remotes` and all tags
+fn fetched_refs(
+	repo: &Repository,
+	remotes: &[String],
+) -> Result<HashMap<String, Oid>> {
+	let prefixes = remotes
+		.iter()
+		.map(|remote| format!("refs/remotes/{remote}/"))
+		.chain(std::iter::once(String::from("refs/tags/")))
+		.collect::<Vec<_>>();
+
+	let mut refs = HashMap::new();
+	for reference in repo.references()?.flatten() {
+		let (Some(name), Some(target)) =
+			(reference.name(), reference.target())
+		else {
+			continue;
+		};
+
+		if prefixes.iter().any(|prefix| name.starts_with(prefix)) {
+			refs.insert(name.to_string(), target);
+		}
+	}
+
+	Ok(refs)
+}
+
+fn fetch_summary(
+	before: &HashMap<String, Oid>,
+	after: &HashMap<String, Oid>,
+) -> FetchSummary {
+	let mut refs = before
+		.keys()
+		.chain(
+			after.keys().filter(|name| !before.contains_key(*name)),
+		)
+		.filter_map(|name| {
+			let old = before.get(name).copied();
+			let new = after.get(name).copied();
+
+			(old != new).then(|| FetchedRef {
+				name: name.clone(),
+				old: old.map(CommitId::new),
+				new: new.map(CommitId::new),
+			})
+		})
+		.collect::<Vec<_>>();
+	refs.sort_by(|a, b| a.name.cmp(&b.name));
+
+	FetchSummary { refs }
+}
+
 ///
 fn fetch_from_remote(
 	repo_path: &RepoPath,
 	remote: &str,
+	fetch_options: &RemoteFetchOptions,
 	basic_credential: Option<BasicAuthCredential>,
 	progress_sender: Option<Sender<ProgressNotification>>,
 ) -> Result<()> {
@@ -278,54 +455,80 @@ fn fetch_from_remote(
 	let mut remote = repo.find_remote(remote)?;
 
 	let mut options = FetchOptions::new();
-	let callbacks = Callbacks::new(progress_sender, basic_credential);
-	options.prune(git2::FetchPrune::On);
-	options.proxy_options(proxy_auto());
-	options.download_tags(git2::AutotagOption::All);
+	let callbacks = Callbacks::new(progress_sender, basic_credential)
+		.with_ssh_identity(remote_identity(&repo, &remote));
+	options.prune(if fetch_options.prune {
+		git2::FetchPrune::On
+	} else {
+		git2::FetchPrune::Off
+	});
+	options.proxy_options(repo_proxy(&repo));
+	options.download_tags(if fetch_options.tags {
+		git2::AutotagOption::All
+	} else {
+		git2::AutotagOption::Auto
+	});
+	match fetch_options.depth {
+		Some(FetchDepth::Commits(depth)) => {
+			options.depth(i32::try_from(depth)?);
+		}
+		// libgit2 treats `i32::MAX` as `--unshallow`
+		Some(FetchDepth::Unshallow) => {
+			options.depth(i32::MAX);
+		}
+		None => (),
+	}
 	options.remote_callbacks(callbacks.callbacks());
 	remote.fetch(&[] as &[&str], Some(&mut options), None)?;
-	// fetch tags (also removing remotely deleted ones)
-	remote.fetch(
-		&["refs/tags/*:refs/tags/*"],
-		Some(&mut options),
-		None,
-	)?;
+	callbacks.approve_credential();
+
+	if fetch_options.tags {
+		// fetch tags (also removing remotely deleted ones if pruning)
+		remote.fetch(
+			&["refs/tags/*:refs/tags/*"],
+			Some(&mut options),
+			None,
+		)?;
+	}
 
 	Ok(())
 }
 
-/// updates/prunes all branches from all remotes
+/// updates (and optionally prunes) all branches from all remotes or
+/// just the one picked in `options`
 pub fn fetch_all(
 	repo_path: &RepoPath,
+	options: &RemoteFetchOptions,
 	basic_credential: &Option<BasicAuthCredential>,
-	progress_sender: &Option<Sender<ProgressPercent>>,
-) -> Result<()> {
+	progress_sender: &Option<Sender<ProgressNotification>>,
+) -> Result<FetchSummary> {
 	scope_time!("fetch_all");
 
 	let repo = repo(repo_path)?;
-	let remotes = repo
-		.remotes()?
-		.iter()
-		.flatten()
-		.map(String::from)
-		.collect::<Vec<_>>();
-	let remotes_count = remotes.len();
+	let remotes = match &options.remote {
+		Some(remote) => vec![remote.clone()],
+		None => repo
+			.remotes()?
+			.iter()
+			.flatten()
+			.map(String::from)
+			.collect::<Vec<_>>(),
+	};
+	let before = fetched_refs(&repo, &remotes)?;
 
-	for (idx, remote) in remotes.into_iter().enumerate() {
+	for remote in &remotes {
 		fetch_from_remote(
 			repo_path,
-			&remote,
+			remote,
+			options,
 			basic_credential.clone(),
-			None,
+			progress_sender.clone(),
 		)?;
-
-		if let Some(sender) = progress_sender {
-			let progress = ProgressPercent::new(idx, remotes_count);
-			sender.send(progress)?;
-		}
 	}
 
-	Ok(())
+	let after = fetched_refs(&repo, &remotes)?;
+
+	Ok(fetch_summary(&before, &after))
 }
 
 /// fetches from upstream/remote for local `branch`
@@ -348,11 +551,43 @@ pub(crate) fn fetch(
 
 	let mut options = FetchOptions::new();
 	options.download_tags(git2::AutotagOption::All);
-	let callbacks = Callbacks::new(progress_sender, basic_credential);
+	let callbacks = Callbacks::new(progress_sender, basic_credential)
+		.with_ssh_identity(remote_identity(&repo, &remote));
 	options.remote_callbacks(callbacks.callbacks());
-	options.proxy_options(proxy_auto());
+	options.proxy_options(repo_proxy(&repo));
 
 	remote.fetch(&[branch], Some(&mut options), None)?;
+	callbacks.approve_credential();
+
+	Ok(remote.stats().received_bytes())
+}
+
+/// fetches only the branch of `target` into its remote tracking ref
+pub(crate) fn fetch_target(
+	repo_path: &RepoPath,
+	target: &RemoteTarget,
+	basic_credential: Option<BasicAuthCredential>,
+	progress_sender: Option<Sender<ProgressNotification>>,
+) -> Result<usize> {
+	scope_time!("fetch_target");
+
+	let repo = repo(repo_path)?;
+	let mut remote = repo.find_remote(&target.remote)?;
+
+	let mut options = FetchOptions::new();
+	options.download_tags(git2::AutotagOption::All);
+	let callbacks = Callbacks::new(progress_sender, basic_credential)
+		.with_ssh_identity(remote_identity(&repo, &remote));
+	options.remote_callbacks(callbacks.callbacks());
+	options.proxy_options(repo_proxy(&repo));
+
+	let refspec = format!(
+		"+refs/heads/{}:{}",
+		target.branch,
+		target.tracking_ref()
+	);
+	remote.fetch(&[refspec], Some(&mut options), None)?;
+	callbacks.approve_credential();
 
 	Ok(remote.stats().received_bytes())
 }
@@ -360,8 +595,13 @@ pub(crate) fn fetch(
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::sync::tests::{
-		debug_cmd_print, repo_clone, repo_init,
+	use crate::sync::{
+		create_branch,
+		remotes::push::{push_branch, push_raw, PushType},
+		tests::{
+			debug_cmd_print, repo_clone, repo_init, repo_init_bare,
+			write_commit_file,
+		},
 	};
 
 	#[test]
@@ -580,4 +820,141 @@ mod tests {
 			matches!(default_push_remote, Ok(remote_name) if remote_name == "branchpushremote")
 		);
 	}
+
+	#[test]
+	fn test_fetch_summary() {
+		let (r1_dir, _repo) = repo_init_bare().unwrap();
+		let r1_dir = r1_dir.path().to_str().unwrap();
+
+		let (clone1_dir, clone1) = repo_clone(r1_dir).unwrap();
+		let clone1_path: &RepoPath =
+			&clone1_dir.path().to_str().unwrap().into();
+
+		write_commit_file(&clone1, "test.txt", "test", "c1");
+		push_branch(
+			clone1_path,
+			"origin",
+			"master",
+			false,
+			false,
+			None,
+			None,
+		)
+		.unwrap();
+
+		let (clone2_dir, _clone2) = repo_clone(r1_dir).unwrap();
+		let clone2_path: &RepoPath =
+			&clone2_dir.path().to_str().unwrap().into();
+
+		create_branch(clone1_path, "feature").unwrap();
+		let c2 = write_commit_file(&clone1, "test.txt", "2", "c2");
+		push_branch(
+			clone1_path,
+			"origin",
+			"feature",
+			false,
+			false,
+			None,
+			None,
+		)
+		.unwrap();
+
+		let options = RemoteFetchOptions {
+			remote: Some(String::from("origin")),
+			prune: false,
+			tags: false,
+			depth: None,
+		};
+		let summary =
+			fetch_all(clone2_path, &options, &None, &None).unwrap();
+
+		assert_eq!(
+			summary.refs,
+			vec![FetchedRef {
+				name: String::from("refs/remotes/origin/feature"),
+				old: None,
+				new: Some(c2),
+			}]
+		);
+
+		push_raw(
+			clone1_path,
+			"origin",
+			"feature",
+			PushType::Branch,
+			false,
+			true,
+			false,
+			None,
+			None,
+		)
+		.unwrap();
+
+		// without pruning the remote tracking branch stays
+		let summary =
+			fetch_all(clone2_path, &options, &None, &None).unwrap();
+		assert!(summary.refs.is_empty());
+
+		let options = RemoteFetchOptions {
+			prune: true,
+			..options
+		};
+		let summary =
+			fetch_all(clone2_path, &options, &None, &None).unwrap();
+		assert_eq!(summary.deleted().count(), 1);
+		assert_eq!(summary.created().count(), 0);
+	}
+
+	#[test]
+	fn test_remote_proxy() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		repo.remote("origin", "https://example.com/a/b").unwrap();
+		repo.remote("local", "/tmp/a").unwrap();
+
+		let mut config = repo.config().unwrap();
+		config
+			.set_str("remote.origin.proxy", "http://proxy:3128")
+			.unwrap();
+
+		assert_eq!(
+			remote_proxy(repo_path, "origin").unwrap().as_deref(),
+			Some("http://proxy:3128")
+		);
+		assert_eq!(remote_proxy(repo_path, "local").unwrap(), None);
+
+		config
+			.set_str(CONFIG_PROXY, "http://override:8080")
+			.unwrap();
+		assert_eq!(
+			remote_proxy(repo_path, "origin").unwrap().as_deref(),
+			Some("http://override:8080")
+		);
+
+		config.set_str(CONFIG_PROXY, "").unwrap();
+		assert_eq!(remote_proxy(repo_path, "origin").unwrap(), None);
+	}
+
+	#[test]
+	fn test_auto_fetch_interval() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		assert_eq!(auto_fetch_interval(repo_path).unwrap(), None);
+
+		let mut config = repo.config().unwrap();
+		config.set_i64(CONFIG_AUTO_FETCH, 300).unwrap();
+		assert_eq!(
+			auto_fetch_interval(repo_path).unwrap(),
+			Some(Duration::from_secs(300))
+		);
+
+		config.set_i64(CONFIG_AUTO_FETCH, 0).unwrap();
+		assert_eq!(auto_fetch_interval(repo_path).unwrap(), None);
+	}
 }
@@ -2,21 +2,32 @@ use crate::{
 	error::{Error, Result},
 	progress::ProgressPercent,
 	sync::{
-		branch::branch_set_upstream_after_push,
+		branch::{
+			branch_set_upstream_after_push, set_branch_upstream,
+		},
 		config::{
 			push_default_strategy_config_repo,
 			PushDefaultStrategyConfig,
 		},
 		cred::BasicAuthCredential,
 		get_branch_upstream_merge,
-		remotes::{proxy_auto, Callbacks},
+		hooks::{hooks_pre_push, HookResult, PrePushRef},
+		remotes::{repo_proxy, Callbacks, RemoteTarget},
 		repository::repo,
+		ssh::remote_identity,
 		CommitId, RepoPath,
 	},
 };
 use crossbeam_channel::Sender;
-use git2::{PackBuilderStage, PushOptions};
+use git2::{
+	BranchType, FetchOptions, Oid, PackBuilderStage, PushOptions,
+	Remote,
+};
 use scopetime::scope_time;
+use std::{
+	collections::{HashMap, HashSet},
+	time::Duration,
+};
 
 ///
 pub trait AsyncProgress: Clone + Send + Sync {
@@ -44,6 +55,10 @@ pub enum ProgressNotification {
 		objects: usize,
 		///
 		total_objects: usize,
+		///
+		received_bytes: usize,
+		/// since the transfer started
+		elapsed: Duration,
 	},
 	///
 	PushTransfer {
@@ -53,6 +68,8 @@ pub enum ProgressNotification {
 		total: usize,
 		///
 		bytes: usize,
+		/// since the transfer started
+		elapsed: Duration,
 	},
 	///
 	Packing {
@@ -111,6 +128,53 @@ impl Default for PushType {
 	}
 }
 
+/// remote ref a push of the local `branch` updates
+fn push_destination(
+	repo_path: &RepoPath,
+	push_default_strategy: &PushDefaultStrategyConfig,
+	branch: &str,
+) -> String {
+	let upstream_merge = if *push_default_strategy
+		== PushDefaultStrategyConfig::Upstream
+	{
+		get_branch_upstream_merge(repo_path, branch).ok().flatten()
+	} else {
+		None
+	};
+
+	upstream_merge.unwrap_or_else(|| format!("refs/heads/{branch}"))
+}
+
+/// local remote tracking ref of the remote ref `dst`
+fn tracking_ref(remote: &str, dst: &str) -> String {
+	format!(
+		"refs/remotes/{remote}/{}",
+		dst.strip_prefix("refs/heads/").unwrap_or(dst)
+	)
+}
+
+/// runs the `pre-push` hook for the ref `updates` about to be pushed
+/// to `remote`, a rejecting hook aborts the push
+pub fn run_pre_push_hook(
+	repo_path: &RepoPath,
+	remote: &Remote,
+	updates: &[PrePushRef],
+) -> Result<()> {
+	let url = remote.pushurl().or_else(|| remote.url()).unwrap_or("");
+
+	match hooks_pre_push(
+		repo_path,
+		remote.name().unwrap_or(url),
+		url,
+		updates,
+	)? {
+		HookResult::Ok => Ok(()),
+		HookResult::NotOk(msg) => Err(Error::Generic(format!(
+			"pre-push hook rejected push:\n{msg}"
+		))),
+	}
+}
+
 #[cfg(test)]
 pub fn push_branch(
 	repo_path: &RepoPath,
@@ -128,6 +192,7 @@ pub fn push_branch(
 		PushType::Branch,
 		force,
 		delete,
+		false,
 		basic_credential,
 		progress_sender,
 	)
@@ -142,21 +207,24 @@ pub fn push_raw(
 	ref_type: PushType,
 	force: bool,
 	delete: bool,
+	no_verify: bool,
 	basic_credential: Option<BasicAuthCredential>,
 	progress_sender: Option<Sender<ProgressNotification>>,
 ) -> Result<()> {
 	scope_time!("push");
 
 	let repo = repo(repo_path)?;
+	let remote_name = remote;
 	let mut remote = repo.find_remote(remote)?;
 
 	let push_default_strategy =
 		push_default_strategy_config_repo(&repo)?;
 
 	let mut options = PushOptions::new();
-	options.proxy_options(proxy_auto());
+	options.proxy_options(repo_proxy(&repo));
 
-	let callbacks = Callbacks::new(progress_sender, basic_credential);
+	let callbacks = Callbacks::new(progress_sender, basic_credential)
+		.with_ssh_identity(remote_identity(&repo, &remote));
 	options.remote_callbacks(callbacks.callbacks());
 	options.packbuilder_parallelism(0);
 
@@ -171,8 +239,9 @@ pub fn push_raw(
 		PushType::Tag => "tags",
 	};
 
-	let mut push_ref =
-		format!("{branch_modifier}refs/{git_ref_type}/{branch}");
+	let local_ref = format!("refs/{git_ref_type}/{branch}");
+	let mut push_ref = format!("{branch_modifier}{local_ref}");
+	let mut remote_ref = local_ref.clone();
 
 	if !delete
 		&& ref_type == PushType::Branch
@@ -183,11 +252,38 @@ pub fn push_raw(
 			get_branch_upstream_merge(repo_path, branch)
 		{
 			push_ref.push_str(&format!(":{branch_upstream_merge}"));
+			remote_ref = branch_upstream_merge;
 		}
 	}
 
+	let remote_oid = match ref_type {
+		PushType::Branch => repo
+			.refname_to_id(&tracking_ref(remote_name, &remote_ref))
+			.unwrap_or_else(|_| Oid::zero()),
+		PushType::Tag => Oid::zero(),
+	};
+	let update = if delete {
+		PrePushRef {
+			local_ref: String::from("(delete)"),
+			local_oid: Oid::zero(),
+			remote_ref,
+			remote_oid,
+		}
+	} else {
+		PrePushRef {
+			local_oid: repo.refname_to_id(&local_ref)?,
+			local_ref,
+			remote_ref,
+			remote_oid,
+		}
+	};
+	if !no_verify {
+		run_pre_push_hook(repo_path, &remote, &[update])?;
+	}
+
 	log::debug!("push to: {push_ref}");
 	remote.push(&[push_ref], Some(&mut options))?;
+	callbacks.approve_credential();
 
 	if let Some((reference, msg)) =
 		callbacks.get_stats()?.push_rejected_msg
@@ -204,6 +300,328 @@ pub fn push_raw(
 	Ok(())
 }
 
+/// pushes all `branches` to `remote` at once using force-with-lease:
+/// a remote branch is only overwritten if it still points to the
+/// commit our remote tracking branch knows about
+pub fn push_stack(
+	repo_path: &RepoPath,
+	remote: &str,
+	branches: &[String],
+	no_verify: bool,
+	basic_credential: Option<BasicAuthCredential>,
+	progress_sender: Option<Sender<ProgressNotification>>,
+) -> Result<()> {
+	scope_time!("push_stack");
+
+	let branches = branches
+		.iter()
+		.map(|branch| (branch.clone(), None))
+		.collect::<Vec<_>>();
+
+	push_leased(
+		repo_path,
+		remote,
+		&branches,
+		no_verify,
+		basic_credential,
+		progress_sender,
+	)
+}
+
+/// force pushes `branch` to `remote` like `git push
+/// --force-with-lease=<ref>:<expected>`: the push is refused if the
+/// remote branch no longer points to `expected` (zero if it is
+/// expected to not exist)
+pub fn push_branch_with_lease(
+	repo_path: &RepoPath,
+	remote: &str,
+	branch: &str,
+	expected: CommitId,
+	no_verify: bool,
+	basic_credential: Option<BasicAuthCredential>,
+	progress_sender: Option<Sender<ProgressNotification>>,
+) -> Result<()> {
+	scope_time!("push_branch_with_lease");
+
+	push_leased(
+		repo_path,
+		remote,
+		&[(branch.to_string(), Some(expected))],
+		no_verify,
+		basic_credential,
+		progress_sender,
+	)
+}
+
+/// pushes the local `branch` to the branch and remote of `target`
+/// regardless of its upstream, like `git push <remote>
+/// <branch>:<target>`
+pub fn push_branch_to(
+	repo_path: &RepoPath,
+	branch: &str,
+	target: &RemoteTarget,
+	force: bool,
+	no_verify: bool,
+	basic_credential: Option<BasicAuthCredential>,
+	progress_sender: Option<Sender<ProgressNotification>>,
+) -> Result<()> {
+	scope_time!("push_branch_to");
+
+	let repo = repo(repo_path)?;
+	let mut remote = repo.find_remote(&target.remote)?;
+
+	let local_ref = format!("refs/heads/{branch}");
+	let remote_ref = format!("refs/heads/{}", target.branch);
+
+	if !no_verify {
+		let update = PrePushRef {
+			local_oid: repo.refname_to_id(&local_ref)?,
+			local_ref: local_ref.clone(),
+			remote_oid: repo
+				.refname_to_id(&target.tracking_ref())
+				.unwrap_or_else(|_| Oid::zero()),
+			remote_ref: remote_ref.clone(),
+		};
+		run_pre_push_hook(repo_path, &remote, &[update])?;
+	}
+
+	let mut options = PushOptions::new();
+	options.proxy_options(repo_proxy(&repo));
+
+	let callbacks = Callbacks::new(progress_sender, basic_credential)
+		.with_ssh_identity(remote_identity(&repo, &remote));
+	options.remote_callbacks(callbacks.callbacks());
+	options.packbuilder_parallelism(0);
+
+	let modifier = if force { "+" } else { "" };
+	let push_ref = format!("{modifier}{local_ref}:{remote_ref}");
+
+	log::debug!("push to: {push_ref}");
+	remote.push(&[push_ref], Some(&mut options))?;
+	callbacks.approve_credential();
+
+	if let Some((reference, msg)) =
+		callbacks.get_stats()?.push_rejected_msg
+	{
+		return Err(Error::Generic(format!(
+			"push to '{reference}' rejected: {msg}"
+		)));
+	}
+
+	if target.set_upstream {
+		set_branch_upstream(repo_path, branch, target)?;
+	}
+
+	Ok(())
+}
+
+/// force pushes each branch with a lease on the given commit, or on
+/// the commit of its remote tracking branch if none is given
+fn push_leased(
+	repo_path: &RepoPath,
+	remote: &str,
+	branches: &[(String, Option<CommitId>)],
+	no_verify: bool,
+	basic_credential: Option<BasicAuthCredential>,
+	progress_sender: Option<Sender<ProgressNotification>>,
+) -> Result<()> {
+	let repo = repo(repo_path)?;
+	let remote_name = remote;
+	let mut remote = repo.find_remote(remote)?;
+
+	let push_default_strategy =
+		push_default_strategy_config_repo(&repo)?;
+
+	let mut push_refs = Vec::with_capacity(branches.len());
+	let mut lease = HashMap::new();
+	let mut updates = Vec::with_capacity(branches.len());
+
+	for (branch, expected) in branches {
+		let dst = push_destination(
+			repo_path,
+			&push_default_strategy,
+			branch,
+		);
+		let expected = expected.map_or_else(
+			|| {
+				repo.refname_to_id(&tracking_ref(remote_name, &dst))
+					.unwrap_or_else(|_| Oid::zero())
+			},
+			CommitId::get_oid,
+		);
+
+		let local_ref = format!("refs/heads/{branch}");
+		updates.push(PrePushRef {
+			local_oid: repo.refname_to_id(&local_ref)?,
+			local_ref,
+			remote_ref: dst.clone(),
+			remote_oid: expected,
+		});
+
+		push_refs.push(format!("+refs/heads/{branch}:{dst}"));
+		lease.insert(dst, expected);
+	}
+
+	if !no_verify {
+		run_pre_push_hook(repo_path, &remote, &updates)?;
+	}
+
+	let mut options = PushOptions::new();
+	options.proxy_options(repo_proxy(&repo));
+
+	let callbacks = Callbacks::new(progress_sender, basic_credential)
+		.with_lease(lease)
+		.with_ssh_identity(remote_identity(&repo, &remote));
+	options.remote_callbacks(callbacks.callbacks());
+	options.packbuilder_parallelism(0);
+
+	log::debug!("push with lease: {push_refs:?}");
+	remote.push(&push_refs, Some(&mut options))?;
+	callbacks.approve_credential();
+
+	if let Some((reference, msg)) =
+		callbacks.get_stats()?.push_rejected_msg
+	{
+		return Err(Error::Generic(format!(
+			"push to '{reference}' rejected: {msg}"
+		)));
+	}
+
+	for (branch, _) in branches {
+		branch_set_upstream_after_push(&repo, branch)?;
+	}
+
+	Ok(())
+}
+
+/// a commit on the remote branch that a force push drops
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscardedCommit {
+	///
+	pub id: CommitId,
+	///
+	pub summary: String,
+	/// known locally before the remote tip got refreshed, either on
+	/// a local branch or from the last fetch
+	pub local: bool,
+}
+
+/// what force pushing a branch would discard on the remote
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ForcePushPreview {
+	/// remote ref the push overwrites
+	pub remote_ref: String,
+	/// refreshing the remote tip failed, the preview is based on
+	/// the last fetched state
+	pub fetch_error: Option<String>,
+	/// the remote tip moved since our last fetch
+	pub stale: bool,
+	/// remote tip as known before the refresh (zero if the remote
+	/// branch was unknown), a force-with-lease push expects to still
+	/// find it on the remote
+	pub lease: CommitId,
+	/// remote commits no longer reachable after the push, newest first
+	pub discarded: Vec<DiscardedCommit>,
+}
+
+impl ForcePushPreview {
+	/// whether every discarded commit is still reachable locally
+	pub fn all_local(&self) -> bool {
+		self.discarded.iter().all(|c| c.local)
+	}
+}
+
+/// fetches the remote tip of `branch` and lists the remote commits
+/// a force push of `branch` to `remote` would orphan
+pub fn force_push_preview(
+	repo_path: &RepoPath,
+	remote: &str,
+	branch: &str,
+	basic_credential: Option<BasicAuthCredential>,
+) -> Result<ForcePushPreview> {
+	scope_time!("force_push_preview");
+
+	let repo = repo(repo_path)?;
+
+	let push_default_strategy =
+		push_default_strategy_config_repo(&repo)?;
+	let dst =
+		push_destination(repo_path, &push_default_strategy, branch);
+	let tracking = tracking_ref(remote, &dst);
+
+	let tip_before = repo.refname_to_id(&tracking).ok();
+
+	let fetch_error = {
+		let mut remote = repo.find_remote(remote)?;
+		let mut options = FetchOptions::new();
+		options.proxy_options(repo_proxy(&repo));
+		let callbacks = Callbacks::new(None, basic_credential)
+			.with_ssh_identity(remote_identity(&repo, &remote));
+		options.remote_callbacks(callbacks.callbacks());
+
+		let fetched = remote.fetch(
+			&[format!("+{dst}:{tracking}")],
+			Some(&mut options),
+			None,
+		);
+		if fetched.is_ok() {
+			callbacks.approve_credential();
+		}
+
+		fetched.err().map(|e| e.message().to_string())
+	};
+
+	let tip = repo.refname_to_id(&tracking).ok();
+
+	let mut preview = ForcePushPreview {
+		remote_ref: dst,
+		stale: fetch_error.is_none() && tip != tip_before,
+		lease: tip_before.unwrap_or_else(Oid::zero).into(),
+		fetch_error,
+		discarded: Vec::new(),
+	};
+
+	let Some(tip) = tip else {
+		return Ok(preview);
+	};
+
+	let local_head = repo
+		.find_branch(branch, BranchType::Local)?
+		.get()
+		.peel_to_commit()?
+		.id();
+
+	let mut walk = repo.revwalk()?;
+	walk.push(tip)?;
+	walk.hide(local_head)?;
+	let discarded =
+		walk.collect::<std::result::Result<Vec<_>, _>>()?;
+
+	let mut walk = repo.revwalk()?;
+	walk.push(tip)?;
+	if let Some(tip_before) = tip_before {
+		walk.hide(tip_before)?;
+	}
+	for local_branch in repo.branches(Some(BranchType::Local))? {
+		if let Some(id) = local_branch?.0.get().target() {
+			walk.hide(id)?;
+		}
+	}
+	let remote_only =
+		walk.collect::<std::result::Result<HashSet<_>, _>>()?;
+
+	for id in discarded {
+		let commit = repo.find_commit(id)?;
+		preview.discarded.push(DiscardedCommit {
+			id: id.into(),
+			summary: commit.summary().unwrap_or_default().to_string(),
+			local: !remote_only.contains(&id),
+		});
+	}
+
+	Ok(preview)
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -444,6 +862,69 @@ mod tests {
 		assert_eq!(new_upstream_parent, repo_2_parent,);
 	}
 
+	#[test]
+	fn test_pre_push_hook_rejects() {
+		let (upstream_dir, upstream_repo) = repo_init_bare().unwrap();
+		let (tmp_repo_dir, repo) =
+			repo_clone(upstream_dir.path().to_str().unwrap())
+				.unwrap();
+		let repo_path: &RepoPath =
+			&tmp_repo_dir.path().to_str().unwrap().into();
+
+		write_commit_file(&repo, "temp_file.txt", "a", "commit");
+
+		let hook = b"#!/bin/sh
+read local_ref local_oid remote_ref remote_oid
+echo \"$1 $remote_ref\"
+exit 1
+        ";
+		git2_hooks::create_hook(
+			&repo,
+			git2_hooks::HOOK_PRE_PUSH,
+			hook,
+		);
+
+		let res = push_branch(
+			repo_path, "origin", "master", false, false, None, None,
+		);
+
+		assert!(matches!(
+			res,
+			Err(Error::Generic(msg)) if msg.contains("origin refs/heads/master")
+		));
+		assert!(upstream_repo.head().is_err());
+
+		// skipping the hook like `git push --no-verify`
+		push_raw(
+			repo_path,
+			"origin",
+			"master",
+			PushType::Branch,
+			false,
+			false,
+			true,
+			None,
+			None,
+		)
+		.unwrap();
+		assert!(upstream_repo.head().is_ok());
+
+		write_commit_file(&repo, "temp_file.txt", "b", "commit2");
+
+		git2_hooks::create_hook(
+			&repo,
+			git2_hooks::HOOK_PRE_PUSH,
+			b"#!/bin/sh\nexit 0\n",
+		);
+
+		push_branch(
+			repo_path, "origin", "master", false, false, None, None,
+		)
+		.unwrap();
+
+		assert!(upstream_repo.head().is_ok());
+	}
+
 	#[test]
 	fn test_delete_remote_branch() {
 		// This test mimics the scenario of a user creating a branch, push it, and then remove it on the remote
@@ -523,4 +1004,271 @@ mod tests {
 			.map(|(i, _)| i.name().unwrap().unwrap().to_string())
 			.any(|i| &i == "test_branch"));
 	}
+
+	#[test]
+	fn test_push_stack_with_lease() {
+		let (tmp_repo_dir, repo) = repo_init().unwrap();
+		let (tmp_upstream_dir, upstream) = repo_init_bare().unwrap();
+		let repo_path: &RepoPath =
+			&tmp_repo_dir.path().to_str().unwrap().into();
+
+		repo.remote(
+			"origin",
+			tmp_upstream_dir.path().to_str().unwrap(),
+		)
+		.unwrap();
+		push_branch(
+			repo_path, "origin", "master", false, false, None, None,
+		)
+		.unwrap();
+
+		sync::create_branch(repo_path, "a").unwrap();
+		let a1 = write_commit_file(&repo, "a.txt", "a", "a1");
+		sync::create_branch(repo_path, "b").unwrap();
+		write_commit_file(&repo, "b.txt", "b", "b1");
+
+		let stack = vec![String::from("a"), String::from("b")];
+		push_stack(repo_path, "origin", &stack, false, None, None)
+			.unwrap();
+
+		// rewriting the stack locally is fine to push
+		sync::checkout_branch(repo_path, "a").unwrap();
+		let a2 = sync::amend(repo_path, a1, "a1 amended").unwrap();
+		push_stack(repo_path, "origin", &stack, false, None, None)
+			.unwrap();
+		assert_eq!(
+			upstream.refname_to_id("refs/heads/a").unwrap(),
+			a2.into()
+		);
+
+		// someone else updates `b` on the remote
+		let (tmp_other_dir, other) =
+			repo_clone(tmp_upstream_dir.path().to_str().unwrap())
+				.unwrap();
+		let other_path: &RepoPath =
+			&tmp_other_dir.path().to_str().unwrap().into();
+		sync::branch::checkout_remote_branch(
+			other_path,
+			&sync::get_branches_info(other_path, false)
+				.unwrap()
+				.into_iter()
+				.find(|b| b.name == "origin/b")
+				.unwrap(),
+		)
+		.unwrap();
+		let other_b = write_commit_file(&other, "o.txt", "o", "o1");
+		push_branch(
+			other_path, "origin", "b", false, false, None, None,
+		)
+		.unwrap();
+
+		// our lease on `b` is broken now
+		sync::amend(repo_path, a2, "a1 amended again").unwrap();
+		assert!(push_stack(
+			repo_path, "origin", &stack, false, None, None
+		)
+		.is_err());
+		assert_eq!(
+			upstream.refname_to_id("refs/heads/b").unwrap(),
+			other_b.into()
+		);
+	}
+	#[test]
+	fn test_force_push_preview() {
+		let (tmp_repo_dir, repo) = repo_init().unwrap();
+		let (tmp_upstream_dir, _upstream) = repo_init_bare().unwrap();
+		let repo_path: &RepoPath =
+			&tmp_repo_dir.path().to_str().unwrap().into();
+
+		repo.remote(
+			"origin",
+			tmp_upstream_dir.path().to_str().unwrap(),
+		)
+		.unwrap();
+		let ours = write_commit_file(&repo, "a.txt", "a", "ours");
+		push_branch(
+			repo_path, "origin", "master", false, false, None, None,
+		)
+		.unwrap();
+
+		// nothing to discard while we are ahead
+		let preview =
+			force_push_preview(repo_path, "origin", "master", None)
+				.unwrap();
+		assert_eq!(preview.remote_ref, "refs/heads/master");
+		assert!(preview.fetch_error.is_none());
+		assert!(!preview.stale);
+		assert!(preview.discarded.is_empty());
+
+		// someone else pushes on top
+		let (tmp_other_dir, other) =
+			repo_clone(tmp_upstream_dir.path().to_str().unwrap())
+				.unwrap();
+		let other_path: &RepoPath =
+			&tmp_other_dir.path().to_str().unwrap().into();
+		let theirs =
+			write_commit_file(&other, "o.txt", "o", "theirs");
+		push_branch(
+			other_path, "origin", "master", false, false, None, None,
+		)
+		.unwrap();
+
+		// while we rewrite our commit
+		sync::amend(repo_path, ours, "ours amended").unwrap();
+
+		let preview =
+			force_push_preview(repo_path, "origin", "master", None)
+				.unwrap();
+		assert!(preview.stale);
+		assert!(!preview.all_local());
+		assert_eq!(
+			preview
+				.discarded
+				.iter()
+				.map(|c| (c.id, c.summary.as_str(), c.local))
+				.collect::<Vec<_>>(),
+			vec![(theirs, "theirs", false), (ours, "ours", true)]
+		);
+
+		// once fetched the remote commits are known
+		let preview =
+			force_push_preview(repo_path, "origin", "master", None)
+				.unwrap();
+		assert!(!preview.stale);
+		assert!(preview.all_local());
+		assert_eq!(preview.discarded.len(), 2);
+	}
+
+	#[test]
+	fn test_push_with_lease() {
+		let (tmp_repo_dir, repo) = repo_init().unwrap();
+		let (tmp_upstream_dir, upstream) = repo_init_bare().unwrap();
+		let repo_path: &RepoPath =
+			&tmp_repo_dir.path().to_str().unwrap().into();
+
+		repo.remote(
+			"origin",
+			tmp_upstream_dir.path().to_str().unwrap(),
+		)
+		.unwrap();
+		let ours = write_commit_file(&repo, "a.txt", "a", "ours");
+		push_branch(
+			repo_path, "origin", "master", false, false, None, None,
+		)
+		.unwrap();
+
+		// someone else pushes on top
+		let (tmp_other_dir, other) =
+			repo_clone(tmp_upstream_dir.path().to_str().unwrap())
+				.unwrap();
+		let other_path: &RepoPath =
+			&tmp_other_dir.path().to_str().unwrap().into();
+		let theirs =
+			write_commit_file(&other, "o.txt", "o", "theirs");
+		push_branch(
+			other_path, "origin", "master", false, false, None, None,
+		)
+		.unwrap();
+
+		let ours =
+			sync::amend(repo_path, ours, "ours amended").unwrap();
+
+		// the preview refreshes the tracking branch but keeps the
+		// lease on the tip we knew before
+		let preview =
+			force_push_preview(repo_path, "origin", "master", None)
+				.unwrap();
+		assert!(preview.stale);
+		assert!(push_branch_with_lease(
+			repo_path,
+			"origin",
+			"master",
+			preview.lease,
+			false,
+			None,
+			None,
+		)
+		.is_err());
+		assert_eq!(
+			upstream.refname_to_id("refs/heads/master").unwrap(),
+			theirs.into()
+		);
+
+		// after looking at the remote changes the lease holds
+		let preview =
+			force_push_preview(repo_path, "origin", "master", None)
+				.unwrap();
+		assert!(!preview.stale);
+		assert_eq!(preview.lease, theirs);
+		push_branch_with_lease(
+			repo_path,
+			"origin",
+			"master",
+			preview.lease,
+			false,
+			None,
+			None,
+		)
+		.unwrap();
+		assert_eq!(
+			upstream.refname_to_id("refs/heads/master").unwrap(),
+			ours.into()
+		);
+	}
+
+	#[test]
+	fn test_push_branch_to_target() {
+		let (tmp_repo_dir, repo) = repo_init().unwrap();
+		let (tmp_origin_dir, _origin) = repo_init_bare().unwrap();
+		let (tmp_fork_dir, fork) = repo_init_bare().unwrap();
+		let repo_path: &RepoPath =
+			&tmp_repo_dir.path().to_str().unwrap().into();
+
+		repo.remote(
+			"origin",
+			tmp_origin_dir.path().to_str().unwrap(),
+		)
+		.unwrap();
+		repo.remote("fork", tmp_fork_dir.path().to_str().unwrap())
+			.unwrap();
+		let commit = write_commit_file(&repo, "a.txt", "a", "c1");
+
+		let target = RemoteTarget {
+			remote: String::from("fork"),
+			branch: String::from("feature"),
+			set_upstream: false,
+		};
+		push_branch_to(
+			repo_path, "master", &target, false, false, None, None,
+		)
+		.unwrap();
+
+		assert_eq!(
+			fork.refname_to_id("refs/heads/feature").unwrap(),
+			commit.into()
+		);
+		assert_eq!(
+			sync::get_branch_remote(repo_path, "master").unwrap(),
+			None
+		);
+
+		let target = RemoteTarget {
+			set_upstream: true,
+			..target
+		};
+		push_branch_to(
+			repo_path, "master", &target, false, false, None, None,
+		)
+		.unwrap();
+
+		assert_eq!(
+			sync::get_branch_remote(repo_path, "master").unwrap(),
+			Some(String::from("fork"))
+		);
+		assert_eq!(
+			sync::get_branch_upstream_merge(repo_path, "master")
+				.unwrap(),
+			Some(String::from("refs/heads/feature"))
+		);
+	}
 }
@@ -1,18 +1,20 @@
 //!
 
-use super::push::AsyncProgress;
+use super::push::{run_pre_push_hook, AsyncProgress};
 use crate::{
 	error::Result,
 	progress::ProgressPercent,
 	sync::{
 		cred::BasicAuthCredential,
-		remotes::{proxy_auto, Callbacks},
+		hooks::PrePushRef,
+		remotes::{repo_proxy, Callbacks},
 		repository::repo,
+		ssh::remote_identity,
 		RepoPath,
 	},
 };
 use crossbeam_channel::Sender;
-use git2::{Direction, PushOptions};
+use git2::{Direction, Oid, PushOptions};
 use scopetime::scope_time;
 use std::collections::HashSet;
 
@@ -57,12 +59,14 @@ fn remote_tag_refs(
 
 	let repo = repo(repo_path)?;
 	let mut remote = repo.find_remote(remote)?;
-	let callbacks = Callbacks::new(None, basic_credential);
+	let callbacks = Callbacks::new(None, basic_credential)
+		.with_ssh_identity(remote_identity(&repo, &remote));
 	let conn = remote.connect_auth(
 		Direction::Fetch,
 		Some(callbacks.callbacks()),
-		Some(proxy_auto()),
+		Some(repo_proxy(&repo)),
 	)?;
+	callbacks.approve_credential();
 
 	let remote_heads = conn.list()?;
 	let remote_tags = remote_heads
@@ -123,6 +127,19 @@ pub fn push_tags(
 	let repo = repo(repo_path)?;
 	let mut remote = repo.find_remote(remote)?;
 
+	let updates = tags_missing
+		.iter()
+		.map(|tag| {
+			Ok(PrePushRef {
+				local_ref: tag.clone(),
+				local_oid: repo.refname_to_id(tag)?,
+				remote_ref: tag.clone(),
+				remote_oid: Oid::zero(),
+			})
+		})
+		.collect::<Result<Vec<_>>>()?;
+	run_pre_push_hook(repo_path, &remote, &updates)?;
+
 	let total = tags_missing.len();
 
 	progress_sender.as_ref().map(|sender| {
@@ -132,11 +149,13 @@ pub fn push_tags(
 	for (idx, tag) in tags_missing.into_iter().enumerate() {
 		let mut options = PushOptions::new();
 		let callbacks =
-			Callbacks::new(None, basic_credential.clone());
+			Callbacks::new(None, basic_credential.clone())
+				.with_ssh_identity(remote_identity(&repo, &remote));
 		options.remote_callbacks(callbacks.callbacks());
 		options.packbuilder_parallelism(0);
-		options.proxy_options(proxy_auto());
+		options.proxy_options(repo_proxy(&repo));
 		remote.push(&[tag.as_str()], Some(&mut options))?;
+		callbacks.approve_credential();
 
 		progress_sender.as_ref().map(|sender| {
 			sender.send(PushTagsProgress::Push {
@@ -162,6 +181,7 @@ mod tests {
 			remotes::{
 				fetch, fetch_all,
 				push::{push_branch, push_raw},
+				RemoteFetchOptions,
 			},
 			tests::{repo_clone, repo_init_bare},
 		},
@@ -170,6 +190,15 @@ mod tests {
 	use pretty_assertions::assert_eq;
 	use sync::tests::write_commit_file;
 
+	fn fetch_pruning() -> RemoteFetchOptions {
+		RemoteFetchOptions {
+			remote: None,
+			prune: true,
+			tags: true,
+			depth: None,
+		}
+	}
+
 	#[test]
 	fn test_push_pull_tags() {
 		let (r1_dir, _repo) = repo_init_bare().unwrap();
@@ -365,7 +394,8 @@ mod tests {
 
 		// clone 2 - pull
 
-		fetch_all(clone2_dir, &None, &None).unwrap();
+		fetch_all(clone2_dir, &fetch_pruning(), &None, &None)
+			.unwrap();
 
 		let tags2 = sync::get_tags(clone2_dir).unwrap();
 
@@ -399,7 +429,8 @@ mod tests {
 
 		// clone 2 - pull
 
-		fetch_all(clone2_dir, &None, &None).unwrap();
+		fetch_all(clone2_dir, &fetch_pruning(), &None, &None)
+			.unwrap();
 		assert_eq!(sync::get_tags(clone2_dir).unwrap().len(), 1);
 
 		// delete on clone 1
@@ -413,6 +444,7 @@ mod tests {
 			PushType::Tag,
 			false,
 			true,
+			false,
 			None,
 			None,
 		)
@@ -422,7 +454,8 @@ mod tests {
 
 		// clone 2
 
-		fetch_all(clone2_dir, &None, &None).unwrap();
+		fetch_all(clone2_dir, &fetch_pruning(), &None, &None)
+			.unwrap();
 		assert_eq!(sync::get_tags(clone2_dir).unwrap().len(), 0);
 	}
 }
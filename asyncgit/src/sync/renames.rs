@@ -0,0 +1,317 @@
+//! rename and copy detection: pairing removed and added files with
+//! similar content like `git diff -M`/`-C` does, configured via
+//! `diff.renames`, `status.renames` and `gitui.renameThreshold`
+
+use super::{repository::repo, utils::get_head_repo, RepoPath};
+use crate::error::Result;
+use git2::{
+	Delta, Diff, DiffFindOptions, DiffOptions, ObjectType,
+	Repository, StatusOptions, Tree,
+};
+use scopetime::scope_time;
+use std::path::Path;
+
+/// `diff.renames`: detect renames (`true`), also copies (`copies`)
+/// or neither (`false`) in commit diffs
+pub const CONFIG_DIFF_RENAMES: &str = "diff.renames";
+/// `status.renames`: like `diff.renames` for the status, falls back
+/// to it if unset
+pub const CONFIG_STATUS_RENAMES: &str = "status.renames";
+/// `gitui.renameThreshold`: similarity in percent a file needs to be
+/// considered renamed or copied, 50 by default like git
+pub const CONFIG_RENAME_THRESHOLD: &str = "gitui.renameThreshold";
+
+const DEFAULT_THRESHOLD: u16 = 50;
+
+/// what to look for when pairing removed and added files
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RenameDetection {
+	///
+	Off,
+	///
+	Renames,
+	/// renames and copies of files changed in the same diff
+	Copies,
+}
+
+fn detection_from_config(
+	repo: &Repository,
+	key: &str,
+) -> Option<RenameDetection> {
+	let config = repo.config().ok()?;
+
+	if let Ok(value) = config.get_bool(key) {
+		return Some(if value {
+			RenameDetection::Renames
+		} else {
+			RenameDetection::Off
+		});
+	}
+
+	match config.get_string(key).ok()?.as_str() {
+		"copy" | "copies" => Some(RenameDetection::Copies),
+		_ => None,
+	}
+}
+
+/// detection used for commit diffs, on by default like in git
+pub(crate) fn diff_rename_detection(
+	repo: &Repository,
+) -> RenameDetection {
+	detection_from_config(repo, CONFIG_DIFF_RENAMES)
+		.unwrap_or(RenameDetection::Renames)
+}
+
+/// detection used for the status
+pub(crate) fn status_rename_detection(
+	repo: &Repository,
+) -> RenameDetection {
+	detection_from_config(repo, CONFIG_STATUS_RENAMES)
+		.unwrap_or_else(|| diff_rename_detection(repo))
+}
+
+/// similarity in percent, clamped to `0..=100`
+pub(crate) fn rename_threshold(repo: &Repository) -> u16 {
+	repo.config()
+		.ok()
+		.and_then(|config| {
+			config.get_i32(CONFIG_RENAME_THRESHOLD).ok()
+		})
+		.and_then(|threshold| {
+			u16::try_from(threshold.clamp(0, 100)).ok()
+		})
+		.unwrap_or(DEFAULT_THRESHOLD)
+}
+
+/// turns on rename detection of the status as configured
+pub(crate) fn set_status_renames(
+	repo: &Repository,
+	options: &mut StatusOptions,
+) {
+	let detect =
+		status_rename_detection(repo) != RenameDetection::Off;
+
+	options
+		.renames_head_to_index(detect)
+		.renames_index_to_workdir(detect)
+		.rename_threshold(rename_threshold(repo));
+}
+
+/// pairs removed and added files of `diff` as configured
+pub(crate) fn find_renames(
+	repo: &Repository,
+	diff: &mut Diff,
+	detection: RenameDetection,
+) -> Result<()> {
+	if detection == RenameDetection::Off {
+		return Ok(());
+	}
+
+	let threshold = rename_threshold(repo);
+
+	let mut opts = DiffFindOptions::new();
+	opts.renames(true)
+		.copies(detection == RenameDetection::Copies)
+		.rename_threshold(threshold)
+		.copy_threshold(threshold);
+
+	diff.find_similar(Some(&mut opts))?;
+
+	Ok(())
+}
+
+/// path `path` got renamed or copied from in `diff`
+fn renamed_from(diff: &Diff, path: &str) -> Option<String> {
+	diff.deltas()
+		.find(|delta| {
+			matches!(delta.status(), Delta::Renamed | Delta::Copied)
+				&& delta.new_file().path() == Some(Path::new(path))
+		})
+		.and_then(|delta| {
+			delta.old_file().path()?.to_str().map(String::from)
+		})
+}
+
+/// diff of two trees limited to `pathspec`, including the file it got
+/// renamed or copied from so both show up as a single change
+pub(crate) fn diff_trees<'a>(
+	repo: &'a Repository,
+	old: Option<&Tree>,
+	new: Option<&Tree>,
+	pathspec: Option<&str>,
+	opts: &mut DiffOptions,
+) -> Result<Diff<'a>> {
+	let detection = diff_rename_detection(repo);
+
+	if let Some(p) = pathspec {
+		opts.pathspec(p);
+	}
+
+	let mut diff = repo.diff_tree_to_tree(old, new, Some(opts))?;
+
+	if let Some(p) = pathspec {
+		let added = detection != RenameDetection::Off
+			&& diff
+				.deltas()
+				.any(|delta| delta.status() == Delta::Added);
+
+		if added {
+			// the source is only known looking at all changes
+			let mut all = repo.diff_tree_to_tree(old, new, None)?;
+			find_renames(repo, &mut all, detection)?;
+
+			if let Some(source) = renamed_from(&all, p) {
+				opts.pathspec(source);
+				diff =
+					repo.diff_tree_to_tree(old, new, Some(opts))?;
+			}
+		}
+	}
+
+	find_renames(repo, &mut diff, detection)?;
+
+	Ok(diff)
+}
+
+/// stages the removal of `old` and the addition of `new` in a single
+/// index write
+pub fn stage_rename(
+	repo_path: &RepoPath,
+	old: &str,
+	new: &str,
+) -> Result<()> {
+	scope_time!("stage_rename");
+
+	let repo = repo(repo_path)?;
+	let mut index = repo.index()?;
+
+	index.remove_path(Path::new(old))?;
+	index.add_path(Path::new(new))?;
+	index.write()?;
+
+	Ok(())
+}
+
+/// unstages both sides of a staged rename
+pub fn unstage_rename(
+	repo_path: &RepoPath,
+	old: &str,
+	new: &str,
+) -> Result<()> {
+	scope_time!("unstage_rename");
+
+	let repo = repo(repo_path)?;
+
+	if let Ok(id) = get_head_repo(&repo) {
+		let obj =
+			repo.find_object(id.into(), Some(ObjectType::Commit))?;
+
+		repo.reset_default(Some(&obj), [old, new])?;
+	} else {
+		repo.reset_default(None, [old, new])?;
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		sync::{
+			commit,
+			diff::{get_diff_commit, DiffLineType},
+			get_commit_files,
+			status::{get_status, StatusType},
+			tests::{repo_init, write_commit_file},
+			ShowUntrackedFilesConfig,
+		},
+		StatusItemType,
+	};
+	use std::fs;
+
+	const CONTENT: &str = "a\nb\nc\nd\ne\nf\ng\nh\n";
+
+	#[test]
+	fn test_workdir_rename() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", CONTENT, "c1");
+		fs::rename(root.join("a.txt"), root.join("b.txt")).unwrap();
+
+		let status = |status_type| {
+			get_status(
+				repo_path,
+				status_type,
+				Some(ShowUntrackedFilesConfig::All),
+			)
+			.unwrap()
+		};
+
+		let workdir = status(StatusType::WorkingDir);
+		assert_eq!(workdir.len(), 1);
+		assert_eq!(workdir[0].path, "b.txt");
+		assert_eq!(workdir[0].status, StatusItemType::Renamed);
+		assert_eq!(workdir[0].old_path.as_deref(), Some("a.txt"));
+
+		stage_rename(repo_path, "a.txt", "b.txt").unwrap();
+
+		assert!(status(StatusType::WorkingDir).is_empty());
+		let stage = status(StatusType::Stage);
+		assert_eq!(stage.len(), 1);
+		assert_eq!(stage[0].status, StatusItemType::Renamed);
+		assert_eq!(stage[0].old_path.as_deref(), Some("a.txt"));
+
+		unstage_rename(repo_path, "a.txt", "b.txt").unwrap();
+
+		assert!(status(StatusType::Stage).is_empty());
+	}
+
+	#[test]
+	fn test_commit_rename() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", CONTENT, "c1");
+		fs::remove_file(root.join("a.txt")).unwrap();
+		fs::write(root.join("b.txt"), CONTENT.replace('h', "x"))
+			.unwrap();
+		stage_rename(repo_path, "a.txt", "b.txt").unwrap();
+		let id = commit(repo_path, "rename").unwrap();
+
+		let files = get_commit_files(repo_path, id, None).unwrap();
+		assert_eq!(files.len(), 1);
+		assert_eq!(files[0].status, StatusItemType::Renamed);
+		assert_eq!(files[0].old_path.as_deref(), Some("a.txt"));
+
+		let diff = get_diff_commit(
+			repo_path,
+			id,
+			String::from("b.txt"),
+			None,
+		)
+		.unwrap();
+		let added = diff
+			.hunks
+			.iter()
+			.flat_map(|hunk| hunk.lines.iter())
+			.filter(|line| line.line_type == DiffLineType::Add)
+			.count();
+		assert_eq!(added, 1);
+
+		repo.config()
+			.unwrap()
+			.set_bool(CONFIG_DIFF_RENAMES, false)
+			.unwrap();
+
+		assert_eq!(
+			get_commit_files(repo_path, id, None).unwrap().len(),
+			2
+		);
+	}
+}
@@ -1,7 +1,24 @@
 use super::{utils::get_head_repo, CommitId, RepoPath};
-use crate::{error::Result, sync::repository::repo};
-use git2::{build::CheckoutBuilder, ObjectType, ResetType};
+use crate::{
+	error::{Error, Result},
+	sync::repository::repo,
+	StatusItem, StatusItemType,
+};
+use git2::{
+	build::CheckoutBuilder, ObjectType, Repository, ResetType, Sort,
+	Tree,
+};
 use scopetime::scope_time;
+use std::path::Path;
+
+/// what a `reset_to` would change, used to preview before confirming
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ResetPreview {
+	/// commits that will no longer be reachable from the branch (newest first)
+	pub commits: Vec<CommitId>,
+	/// files in index/workdir that will be affected by the reset
+	pub files: Vec<StatusItem>,
+}
 
 ///
 pub fn reset_stage(repo_path: &RepoPath, path: &str) -> Result<()> {
@@ -38,13 +55,13 @@ pub fn reset_workdir(repo_path: &RepoPath, path: &str) -> Result<()> {
 	Ok(())
 }
 
-///
-pub fn reset_repo(
+/// reset current branch to `commit` using `kind` (soft/mixed/hard)
+pub fn reset_to(
 	repo_path: &RepoPath,
 	commit: CommitId,
 	kind: ResetType,
 ) -> Result<()> {
-	scope_time!("reset_repo");
+	scope_time!("reset_to");
 
 	let repo = repo(repo_path)?;
 
@@ -55,19 +72,142 @@ pub fn reset_repo(
 	Ok(())
 }
 
+/// restores `path` in the workdir to the version found in `commit`
+/// (`git restore --source <commit> [--staged] --worktree <path>`),
+/// optionally staging it as well
+pub fn restore_file(
+	repo_path: &RepoPath,
+	commit: CommitId,
+	path: &str,
+	stage: bool,
+) -> Result<()> {
+	scope_time!("restore_file");
+
+	let repo = repo(repo_path)?;
+
+	let tree = repo.find_commit(commit.into())?.tree()?;
+
+	restore_file_from_tree(&repo, &tree, path, stage)
+}
+
+/// see [`restore_file`]
+pub(super) fn restore_file_from_tree(
+	repo: &Repository,
+	tree: &Tree,
+	path: &str,
+	stage: bool,
+) -> Result<()> {
+	if tree.get_path(Path::new(path)).is_err() {
+		// file did not exist in `commit`: restoring means removing it
+		let work_dir = repo.workdir().ok_or(Error::NoWorkDir)?;
+		let file = work_dir.join(path);
+		if file.exists() {
+			std::fs::remove_file(file)?;
+		}
+
+		if stage {
+			let mut index = repo.index()?;
+			index.remove_path(Path::new(path))?;
+			index.write()?;
+		}
+
+		return Ok(());
+	}
+
+	let mut checkout_opts = CheckoutBuilder::new();
+	checkout_opts
+		.update_index(stage)
+		.force()
+		.disable_pathspec_match(true)
+		.path(path);
+
+	repo.checkout_tree(tree.as_object(), Some(&mut checkout_opts))?;
+
+	Ok(())
+}
+
+/// computes which commits leave the branch and which files are
+/// touched when resetting to `commit` using `kind`
+pub fn reset_preview(
+	repo_path: &RepoPath,
+	commit: CommitId,
+	kind: ResetType,
+) -> Result<ResetPreview> {
+	scope_time!("reset_preview");
+
+	let repo = repo(repo_path)?;
+
+	let target = repo.find_commit(commit.into())?;
+	let target_tree = target.tree()?;
+
+	let mut commits = Vec::new();
+	if let Ok(head) = get_head_repo(&repo) {
+		let mut walk = repo.revwalk()?;
+		walk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+		walk.push(head.into())?;
+		walk.hide(commit.into())?;
+
+		for id in walk {
+			commits.push(CommitId::new(id?));
+		}
+	}
+
+	let diff = match kind {
+		// index and workdir stay untouched, only HEAD moves
+		ResetType::Soft => {
+			let head_tree = repo.head()?.peel_to_tree()?;
+			repo.diff_tree_to_tree(
+				Some(&target_tree),
+				Some(&head_tree),
+				None,
+			)?
+		}
+		// index is rewritten, workdir stays untouched
+		ResetType::Mixed => {
+			repo.diff_tree_to_index(Some(&target_tree), None, None)?
+		}
+		// index and workdir are rewritten
+		ResetType::Hard => repo.diff_tree_to_workdir_with_index(
+			Some(&target_tree),
+			None,
+		)?,
+	};
+
+	let files = diff
+		.deltas()
+		.map(|delta| StatusItem {
+			path: delta
+				.new_file()
+				.path()
+				.or_else(|| delta.old_file().path())
+				.map(|p| p.to_str().unwrap_or("").to_string())
+				.unwrap_or_default(),
+			status: StatusItemType::from(delta.status()),
+			old_path: None,
+		})
+		.collect();
+
+	Ok(ResetPreview { commits, files })
+}
+
 #[cfg(test)]
 mod tests {
-	use super::{reset_stage, reset_workdir};
+	use super::{
+		reset_preview, reset_stage, reset_to, reset_workdir,
+		restore_file,
+	};
 	use crate::error::Result;
 	use crate::sync::{
 		commit,
 		status::{get_status, StatusType},
 		tests::{
-			debug_cmd_print, get_statuses, repo_init, repo_init_empty,
+			debug_cmd_print, get_statuses, repo_init,
+			repo_init_empty, write_commit_file,
 		},
-		utils::{stage_add_all, stage_add_file},
+		utils::{repo_write_file, stage_add_all, stage_add_file},
 		RepoPath,
 	};
+	use git2::ResetType;
 	use std::{
 		fs::{self, File},
 		io::Write,
@@ -336,4 +476,69 @@ mod tests {
 
 		assert_eq!(get_statuses(repo_path), (0, 0));
 	}
+
+	#[test]
+	fn test_reset_preview() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 = write_commit_file(&repo, "a.txt", "a", "c1");
+		let c2 = write_commit_file(&repo, "b.txt", "b", "c2");
+		let c3 = write_commit_file(&repo, "c.txt", "c", "c3");
+
+		repo_write_file(&repo, "a.txt", "changed").unwrap();
+
+		let soft =
+			reset_preview(repo_path, c1, ResetType::Soft).unwrap();
+		assert_eq!(soft.commits, vec![c3, c2]);
+		assert_eq!(soft.files.len(), 2);
+
+		let mixed =
+			reset_preview(repo_path, c1, ResetType::Mixed).unwrap();
+		assert_eq!(mixed.files.len(), 2);
+
+		let hard =
+			reset_preview(repo_path, c1, ResetType::Hard).unwrap();
+		assert_eq!(hard.commits, vec![c3, c2]);
+		assert_eq!(hard.files.len(), 3);
+
+		reset_to(repo_path, c1, ResetType::Hard).unwrap();
+
+		assert_eq!(get_statuses(repo_path), (0, 0));
+		assert!(reset_preview(repo_path, c1, ResetType::Hard)
+			.unwrap()
+			.commits
+			.is_empty());
+	}
+
+	#[test]
+	fn test_restore_file() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 = write_commit_file(&repo, "a.txt", "old", "c1");
+		write_commit_file(&repo, "a.txt", "new", "c2");
+		write_commit_file(&repo, "b.txt", "b", "c3");
+
+		restore_file(repo_path, c1, "a.txt", false).unwrap();
+
+		assert_eq!(
+			fs::read_to_string(root.join("a.txt")).unwrap(),
+			"old"
+		);
+		assert_eq!(get_statuses(repo_path), (1, 0));
+
+		restore_file(repo_path, c1, "a.txt", true).unwrap();
+
+		assert_eq!(get_statuses(repo_path), (0, 1));
+
+		restore_file(repo_path, c1, "b.txt", true).unwrap();
+
+		assert!(!root.join("b.txt").exists());
+		assert_eq!(get_statuses(repo_path), (0, 2));
+	}
 }
@@ -1,7 +1,7 @@
 use git2::{Oid, RebaseOptions, Repository};
 
 use super::{
-	commit::signature_allow_undefined_name,
+	commit::{create_signed_commit, signature_allow_undefined_name},
 	repo,
 	utils::{bytes2string, get_head_refname, get_head_repo},
 	CommitId, RepoPath,
@@ -17,28 +17,28 @@ pub fn reword(
 	let repo = repo(repo_path)?;
 	let config = repo.config()?;
 
+	let chain =
+		commits_to_rewrite(&repo, get_head_repo(&repo)?, commit)?;
+
 	if config.get_bool("commit.gpgsign").unwrap_or(false) {
-		// HACK: we undo the last commit and create a new one
-		use crate::sync::utils::undo_last_commit;
-
-		let head = get_head_repo(&repo)?;
-		if head == commit {
-			// Check if there are any staged changes
-			let parent = repo.find_commit(head.into())?;
-			let tree = parent.tree()?;
-			if repo
-				.diff_tree_to_index(Some(&tree), None, None)?
-				.deltas()
-				.len() == 0
-			{
-				undo_last_commit(repo_path)?;
-				return super::commit(repo_path, message);
-			}
+		return Ok(
+			reword_signed(&repo, &config, &chain, message)?.into()
+		);
+	}
 
-			return Err(Error::SignRewordLastCommitStaged);
-		}
+	if get_head_repo(&repo)? == commit {
+		// rewording HEAD is a plain amend of the message, this keeps
+		// staged and unstaged changes untouched
+		let new_id = repo.find_commit(commit.into())?.amend(
+			Some("HEAD"),
+			None,
+			None,
+			None,
+			Some(message),
+			None,
+		)?;
 
-		return Err(Error::SignRewordNonLastCommit);
+		return Ok(new_id.into());
 	}
 
 	let cur_branch_ref = get_head_refname(&repo)?;
@@ -57,6 +57,89 @@ pub fn reword(
 	}
 }
 
+/// `commit` and its descendants up to `head`, oldest first.
+///
+/// rewording must not touch commits outside of the current branch
+/// and can not rewrite merges linearly
+fn commits_to_rewrite(
+	repo: &Repository,
+	head: CommitId,
+	commit: CommitId,
+) -> Result<Vec<Oid>> {
+	let mut chain = Vec::new();
+	let mut current = repo.find_commit(head.into())?;
+
+	loop {
+		chain.push(current.id());
+
+		if current.id() == commit.get_oid() {
+			break;
+		}
+
+		if current.parent_count() > 1 {
+			return Err(Error::Generic(String::from(
+				"rewording commits behind a merge is not supported",
+			)));
+		}
+
+		current = current.parent(0).map_err(|_| {
+			Error::Generic(String::from(
+				"commit is not part of the current branch",
+			))
+		})?;
+	}
+
+	chain.reverse();
+
+	Ok(chain)
+}
+
+/// rewrites `chain` (oldest first) with a new message for the first
+/// commit, signing every rewritten commit.
+///
+/// the trees stay the same, so neither index nor workdir are touched
+fn reword_signed(
+	repo: &Repository,
+	config: &git2::Config,
+	chain: &[Oid],
+	message: &str,
+) -> Result<Oid> {
+	let committer = signature_allow_undefined_name(repo)?;
+
+	let mut parent = chain.first().and_then(|first| {
+		repo.find_commit(*first).ok()?.parent(0).ok()
+	});
+	let mut reworded = None;
+
+	for id in chain {
+		let commit = repo.find_commit(*id)?;
+		let msg = if reworded.is_none() {
+			message
+		} else {
+			commit.message_raw().unwrap_or_default()
+		};
+
+		let new_id = create_signed_commit(
+			repo,
+			config,
+			&commit.author(),
+			&committer,
+			msg,
+			&commit.tree()?,
+			&parent.iter().collect::<Vec<_>>(),
+		)?;
+
+		reworded.get_or_insert(new_id);
+		parent = Some(repo.find_commit(new_id)?);
+	}
+
+	if let Some(head) = parent {
+		repo.head()?.set_target(head.id(), "reword")?;
+	}
+
+	reworded.ok_or(Error::NoParent)
+}
+
 /// Gets the current branch the user is on.
 /// Returns none if they are not on a branch
 /// and Err if there was a problem finding the branch
@@ -152,7 +235,12 @@ mod tests {
 	use super::*;
 	use crate::sync::{
 		get_commit_info,
-		tests::{repo_init_empty, write_commit_file},
+		tests::{
+			get_statuses, repo_init_empty, setup_ssh_signing,
+			write_commit_file,
+		},
+		utils::repo_write_file,
+		verify_signature, SignatureValidity,
 	};
 	use pretty_assertions::assert_eq;
 
@@ -192,4 +280,123 @@ mod tests {
 			get_commit_info(repo_path, &reworded).unwrap().message
 		);
 	}
+
+	#[test]
+	fn test_reword_head_with_changes() {
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "foo", "a", "commit1");
+		let oid2 = write_commit_file(&repo, "foo", "ab", "commit2");
+
+		repo_write_file(&repo, "foo", "abc").unwrap();
+
+		let reworded = reword(repo_path, oid2, "reworded").unwrap();
+
+		assert_eq!(
+			get_commit_info(repo_path, &reworded).unwrap().message,
+			"reworded"
+		);
+		assert_eq!(get_statuses(repo_path), (1, 0));
+	}
+
+	#[test]
+	fn test_reword_earlier_commit() {
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "foo", "a", "commit1");
+		let oid2 = write_commit_file(&repo, "foo", "ab", "commit2");
+		write_commit_file(&repo, "foo", "abc", "commit3");
+
+		let reworded = reword(repo_path, oid2, "reworded").unwrap();
+
+		assert_eq!(
+			get_commit_info(repo_path, &reworded).unwrap().message,
+			"reworded"
+		);
+
+		let head = get_head_repo(&repo).unwrap();
+		let head = repo.find_commit(head.into()).unwrap();
+		assert_eq!(head.message().unwrap(), "commit3");
+		assert_eq!(head.parent_id(0).unwrap(), reworded.get_oid());
+	}
+
+	#[test]
+	fn test_reword_signed_earlier_commit() {
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "foo", "a", "commit1");
+		let oid2 = write_commit_file(&repo, "foo", "ab", "commit2");
+		write_commit_file(&repo, "foo", "abc", "commit3");
+
+		let _keys = setup_ssh_signing(&repo).unwrap();
+		repo_write_file(&repo, "foo", "abcd").unwrap();
+
+		let reworded = reword(repo_path, oid2, "reworded").unwrap();
+
+		assert_eq!(
+			get_commit_info(repo_path, &reworded).unwrap().message,
+			"reworded"
+		);
+
+		let head = get_head_repo(&repo).unwrap();
+		let head_commit = repo.find_commit(head.into()).unwrap();
+		assert_eq!(head_commit.message().unwrap(), "commit3");
+		assert_eq!(
+			head_commit.parent_id(0).unwrap(),
+			reworded.get_oid()
+		);
+
+		for id in [head, reworded] {
+			assert_eq!(
+				verify_signature(repo_path, id)
+					.unwrap()
+					.unwrap()
+					.validity,
+				SignatureValidity::Verified
+			);
+		}
+
+		// workdir changes survive
+		assert_eq!(get_statuses(repo_path), (1, 0));
+	}
+
+	#[test]
+	fn test_reword_refuses_other_branch() {
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let oid1 = write_commit_file(&repo, "foo", "a", "commit1");
+		let oid2 = write_commit_file(&repo, "foo", "ab", "commit2");
+
+		repo.branch(
+			"other",
+			&repo.find_commit(oid2.into()).unwrap(),
+			false,
+		)
+		.unwrap();
+		repo.reset(
+			repo.find_commit(oid1.into()).unwrap().as_object(),
+			git2::ResetType::Hard,
+			None,
+		)
+		.unwrap();
+
+		assert!(reword(repo_path, oid2, "reworded").is_err());
+		assert_eq!(get_head_repo(&repo).unwrap(), oid1);
+	}
 }
@@ -0,0 +1,686 @@
+//! cherry-picking and reverting commits one by one
+//!
+//! the remaining steps are kept in `sequencer/` the same way git does
+//! so a series stopped at a conflict can be continued, skipped or
+//! aborted by either tool. a single pick or revert stopped by git
+//! (without `sequencer/`) is handled too.
+
+use super::{
+	commit::signature_allow_undefined_name,
+	format_patch::oldest_first, repository::repo, CommitId, RepoPath,
+};
+use crate::error::{Error, Result};
+use git2::{
+	build::CheckoutBuilder, CherrypickOptions, Commit, Index, Oid,
+	Repository, RepositoryState, RevertOptions, StatusOptions,
+};
+use scopetime::scope_time;
+use std::{fmt::Write, fs, path::PathBuf};
+
+const SEQUENCER_DIR: &str = "sequencer";
+const CHERRY_PICK_HEAD: &str = "CHERRY_PICK_HEAD";
+const REVERT_HEAD: &str = "REVERT_HEAD";
+const MERGE_MSG: &str = "MERGE_MSG";
+
+///
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CherryPickOptions {
+	/// append `(cherry picked from commit ...)` like `-x`
+	pub record_origin: bool,
+}
+
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SequencerAction {
+	///
+	Pick,
+	///
+	Revert,
+}
+
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SequencerResult {
+	/// all steps done, with the number of commits created
+	/// (steps that turned out empty are dropped)
+	Done(usize),
+	/// this commit left conflicts to resolve
+	Conflicts(CommitId),
+}
+
+/// state of a stopped cherry-pick or revert
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SequencerProgress {
+	///
+	pub action: SequencerAction,
+	/// commit being picked or reverted
+	pub current: CommitId,
+	/// steps left including the current one
+	pub remaining: usize,
+}
+
+/// cherry-picks `commits` onto `HEAD`, oldest first
+pub fn cherry_pick_commits(
+	repo_path: &RepoPath,
+	commits: &[CommitId],
+	options: CherryPickOptions,
+) -> Result<SequencerResult> {
+	scope_time!("cherry_pick_commits");
+
+	let repo = repo(repo_path)?;
+
+	if repo.state() != RepositoryState::Clean {
+		return Err(Error::Generic(
+			"repository is not in a clean state".into(),
+		));
+	}
+	if has_tracked_changes(&repo)? {
+		return Err(Error::Generic(
+			"commit or stash your changes first".into(),
+		));
+	}
+
+	let commits = oldest_first(&repo, commits)?;
+	if commits.iter().any(|c| c.parent_count() > 1) {
+		return Err(Error::Generic(
+			"merge commits cannot be cherry-picked".into(),
+		));
+	}
+
+	let head = repo.head()?.peel_to_commit()?.id();
+	let dir = sequencer_dir(&repo);
+	fs::create_dir_all(&dir)?;
+	fs::write(dir.join("head"), format!("{head}\n"))?;
+	fs::write(dir.join("abort-safety"), format!("{head}\n"))?;
+	fs::write(
+		dir.join("opts"),
+		if options.record_origin {
+			"[options]\n\trecord-origin = true\n"
+		} else {
+			""
+		},
+	)?;
+	let todo = commits
+		.iter()
+		.map(|c| (SequencerAction::Pick, c.id().into()))
+		.collect::<Vec<_>>();
+	write_todo(&repo, &todo)?;
+
+	run_todo(&repo, 0)
+}
+
+/// progress of a stopped cherry-pick or revert, `None` if there is
+/// none
+pub fn sequencer_progress(
+	repo_path: &RepoPath,
+) -> Result<Option<SequencerProgress>> {
+	let repo = repo(repo_path)?;
+
+	let todo = read_todo(&repo)?;
+
+	if let Some((action, current)) = todo.first() {
+		return Ok(Some(SequencerProgress {
+			action: *action,
+			current: *current,
+			remaining: todo.len(),
+		}));
+	}
+
+	Ok(stopped_step(&repo)?.map(|(action, current)| {
+		SequencerProgress {
+			action,
+			current,
+			remaining: 1,
+		}
+	}))
+}
+
+/// commits the resolved current step and runs the remaining ones
+pub fn sequencer_continue(
+	repo_path: &RepoPath,
+) -> Result<SequencerResult> {
+	scope_time!("sequencer_continue");
+
+	let repo = repo(repo_path)?;
+
+	let mut index = repo.index()?;
+	if index.has_conflicts() {
+		return Err(Error::Generic(
+			"resolve and stage all conflicts first".into(),
+		));
+	}
+
+	let mut done = 0;
+	if let Some((action, id)) = stopped_step(&repo)? {
+		let commit = repo.find_commit(id.into())?;
+		if commit_step(&repo, action, &commit, &mut index)? {
+			done += 1;
+		}
+	}
+
+	next_step(&repo, done)
+}
+
+/// drops the current step and runs the remaining ones
+pub fn sequencer_skip(
+	repo_path: &RepoPath,
+) -> Result<SequencerResult> {
+	scope_time!("sequencer_skip");
+
+	let repo = repo(repo_path)?;
+
+	// resets index and worktree without `git_reset`, which would
+	// remove the sequencer state
+	let mut index = repo.index()?;
+	index.read_tree(&repo.head()?.peel_to_tree()?)?;
+	index.write()?;
+	let mut checkout = CheckoutBuilder::new();
+	checkout.force();
+	repo.checkout_head(Some(&mut checkout))?;
+
+	remove_step_files(&repo)?;
+
+	next_step(&repo, 0)
+}
+
+/// goes back to where the cherry-pick or revert started
+pub fn sequencer_abort(repo_path: &RepoPath) -> Result<()> {
+	scope_time!("sequencer_abort");
+
+	let repo = repo(repo_path)?;
+	let dir = sequencer_dir(&repo);
+
+	let head = match fs::read_to_string(dir.join("head")) {
+		Ok(head) => Oid::from_str(head.trim())?,
+		Err(_) => repo.head()?.peel_to_commit()?.id(),
+	};
+
+	let mut checkout = CheckoutBuilder::new();
+	checkout.force();
+	// also cleans up `sequencer/` and the `*_HEAD` files
+	repo.reset(
+		repo.find_commit(head)?.as_object(),
+		git2::ResetType::Hard,
+		Some(&mut checkout),
+	)?;
+	if dir.exists() {
+		fs::remove_dir_all(dir)?;
+	}
+
+	Ok(())
+}
+
+/// drops the current step from the todo (if any) and runs the rest
+fn next_step(
+	repo: &Repository,
+	done: usize,
+) -> Result<SequencerResult> {
+	if !sequencer_dir(repo).join("todo").exists() {
+		return Ok(SequencerResult::Done(done));
+	}
+
+	let todo = read_todo(repo)?;
+	write_todo(repo, todo.get(1..).unwrap_or_default())?;
+
+	run_todo(repo, done)
+}
+
+fn run_todo(
+	repo: &Repository,
+	mut done: usize,
+) -> Result<SequencerResult> {
+	let mainline = mainline(repo);
+
+	loop {
+		let todo = read_todo(repo)?;
+		let Some((action, next)) = todo.first().copied() else {
+			break;
+		};
+
+		let commit = repo.find_commit(next.into())?;
+		match action {
+			SequencerAction::Pick => {
+				let mut options = CherrypickOptions::new();
+				if commit.parent_count() > 1 {
+					options.mainline(mainline);
+				}
+				repo.cherrypick(&commit, Some(&mut options))?;
+			}
+			SequencerAction::Revert => {
+				let mut options = RevertOptions::new();
+				if commit.parent_count() > 1 {
+					options.mainline(mainline);
+				}
+				repo.revert(&commit, Some(&mut options))?;
+			}
+		}
+
+		let mut index = repo.index()?;
+		if index.has_conflicts() {
+			return Ok(SequencerResult::Conflicts(next));
+		}
+
+		if commit_step(repo, action, &commit, &mut index)? {
+			done += 1;
+		}
+		write_todo(repo, &todo[1..])?;
+	}
+
+	fs::remove_dir_all(sequencer_dir(repo))?;
+
+	Ok(SequencerResult::Done(done))
+}
+
+/// the step git or we stopped at, from `CHERRY_PICK_HEAD` or
+/// `REVERT_HEAD`
+fn stopped_step(
+	repo: &Repository,
+) -> Result<Option<(SequencerAction, CommitId)>> {
+	for (file, action) in [
+		(CHERRY_PICK_HEAD, SequencerAction::Pick),
+		(REVERT_HEAD, SequencerAction::Revert),
+	] {
+		if let Ok(id) = fs::read_to_string(repo.path().join(file)) {
+			return Ok(Some((
+				action,
+				Oid::from_str(id.trim())?.into(),
+			)));
+		}
+	}
+
+	Ok(None)
+}
+
+/// commits the index for `commit`, returns false if the step turned
+/// out empty and was dropped
+fn commit_step(
+	repo: &Repository,
+	action: SequencerAction,
+	commit: &Commit,
+	index: &mut Index,
+) -> Result<bool> {
+	let tree = repo.find_tree(index.write_tree()?)?;
+	let head = repo.head()?.peel_to_commit()?;
+
+	let created = if tree.id() == head.tree_id() {
+		false
+	} else {
+		let committer = signature_allow_undefined_name(repo)?;
+		let (author, message) = match action {
+			SequencerAction::Pick => (
+				commit.author().to_owned(),
+				pick_message(repo, commit),
+			),
+			SequencerAction::Revert => {
+				(committer.to_owned(), revert_message(repo, commit))
+			}
+		};
+
+		let id = repo.commit(
+			Some("HEAD"),
+			&author,
+			&committer,
+			&message,
+			&tree,
+			&[&head],
+		)?;
+
+		let dir = sequencer_dir(repo);
+		if dir.exists() {
+			fs::write(dir.join("abort-safety"), format!("{id}\n"))?;
+		}
+		true
+	};
+
+	remove_step_files(repo)?;
+
+	Ok(created)
+}
+
+fn pick_message(repo: &Repository, commit: &Commit) -> String {
+	let message =
+		String::from_utf8_lossy(commit.message_bytes()).into_owned();
+
+	if read_opts(repo, |config| {
+		config.get_bool("options.record-origin")
+	})
+	.unwrap_or_default()
+	{
+		format!(
+			"{}\n\n(cherry picked from commit {})\n",
+			message.trim_end(),
+			commit.id()
+		)
+	} else {
+		message
+	}
+}
+
+/// the prepared message without comments, git's default otherwise
+fn revert_message(repo: &Repository, commit: &Commit) -> String {
+	let prepared = fs::read_to_string(repo.path().join(MERGE_MSG))
+		.unwrap_or_default()
+		.lines()
+		.filter(|line| !line.starts_with('#'))
+		.collect::<Vec<_>>()
+		.join("\n");
+
+	if prepared.trim().is_empty() {
+		format!(
+			"Revert \"{}\"\n\nThis reverts commit {}.\n",
+			commit.summary().unwrap_or_default(),
+			commit.id()
+		)
+	} else {
+		format!("{}\n", prepared.trim_end())
+	}
+}
+
+/// not `cleanup_state`, that would drop the sequencer too
+fn remove_step_files(repo: &Repository) -> Result<()> {
+	for file in [CHERRY_PICK_HEAD, REVERT_HEAD, MERGE_MSG] {
+		let path = repo.path().join(file);
+		if path.exists() {
+			fs::remove_file(path)?;
+		}
+	}
+
+	Ok(())
+}
+
+/// `-m` of a sequence picking or reverting merges, 1 by default
+fn mainline(repo: &Repository) -> u32 {
+	read_opts(repo, |config| config.get_i32("options.mainline"))
+		.and_then(|mainline| u32::try_from(mainline).ok())
+		.filter(|mainline| *mainline > 0)
+		.unwrap_or(1)
+}
+
+fn read_opts<T>(
+	repo: &Repository,
+	f: impl FnOnce(&git2::Config) -> std::result::Result<T, git2::Error>,
+) -> Option<T> {
+	git2::Config::open(&sequencer_dir(repo).join("opts"))
+		.and_then(|config| f(&config))
+		.ok()
+}
+
+fn has_tracked_changes(repo: &Repository) -> Result<bool> {
+	let mut options = StatusOptions::new();
+	options.include_untracked(false).include_ignored(false);
+
+	Ok(!repo.statuses(Some(&mut options))?.is_empty())
+}
+
+fn sequencer_dir(repo: &Repository) -> PathBuf {
+	repo.path().join(SEQUENCER_DIR)
+}
+
+/// `pick <id> <subject>` lines like git writes them
+fn write_todo(
+	repo: &Repository,
+	todo: &[(SequencerAction, CommitId)],
+) -> Result<()> {
+	let mut content = String::new();
+	for (action, id) in todo {
+		let commit = repo.find_commit((*id).into())?;
+		let _ = writeln!(
+			content,
+			"{} {} {}",
+			match action {
+				SequencerAction::Pick => "pick",
+				SequencerAction::Revert => "revert",
+			},
+			commit.id(),
+			commit.summary().unwrap_or_default()
+		);
+	}
+
+	fs::write(sequencer_dir(repo).join("todo"), content)?;
+
+	Ok(())
+}
+
+/// empty without a `sequencer/todo`
+fn read_todo(
+	repo: &Repository,
+) -> Result<Vec<(SequencerAction, CommitId)>> {
+	let Ok(todo) =
+		fs::read_to_string(sequencer_dir(repo).join("todo"))
+	else {
+		return Ok(Vec::new());
+	};
+
+	todo.lines()
+		.map(str::trim)
+		.filter(|line| !line.is_empty() && !line.starts_with('#'))
+		.map(|line| {
+			let mut parts = line.split_whitespace();
+			let action = match parts.next() {
+				Some("pick" | "p") => SequencerAction::Pick,
+				Some("revert") => SequencerAction::Revert,
+				_ => {
+					return Err(Error::Generic(format!(
+						"unsupported sequencer command: '{line}'"
+					)))
+				}
+			};
+			let id = parts.next().ok_or_else(|| {
+				Error::Generic(format!("missing commit: '{line}'"))
+			})?;
+
+			Ok((
+				action,
+				repo.revparse_single(id)?
+					.peel_to_commit()?
+					.id()
+					.into(),
+			))
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		revert_commit, stage_add_file,
+		tests::{repo_init, write_commit_file},
+	};
+	use std::path::Path;
+
+	#[test]
+	fn test_cherry_pick_series() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let base: CommitId =
+			repo.head().unwrap().target().unwrap().into();
+		let c1 = write_commit_file(&repo, "a.txt", "a\n", "add a");
+		let c2 = write_commit_file(&repo, "b.txt", "b\n", "add b");
+		repo.reset(
+			repo.find_commit(base.into()).unwrap().as_object(),
+			git2::ResetType::Hard,
+			None,
+		)
+		.unwrap();
+
+		assert_eq!(
+			cherry_pick_commits(
+				repo_path,
+				&[c2, c1],
+				CherryPickOptions {
+					record_origin: true
+				}
+			)
+			.unwrap(),
+			SequencerResult::Done(2)
+		);
+
+		let head = repo.head().unwrap().peel_to_commit().unwrap();
+		assert_eq!(
+			head.message(),
+			Some(
+				format!(
+					"add b\n\n(cherry picked from commit {c2})\n"
+				)
+				.as_str()
+			)
+		);
+		assert_eq!(head.parent(0).unwrap().summary(), Some("add a"));
+		assert_eq!(repo.state(), RepositoryState::Clean);
+		assert_eq!(sequencer_progress(repo_path).unwrap(), None);
+	}
+
+	#[test]
+	fn test_cherry_pick_conflict() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let base = write_commit_file(&repo, "a.txt", "1\n", "base");
+		let c1 = write_commit_file(&repo, "a.txt", "x\n", "change a");
+		let c2 = write_commit_file(&repo, "b.txt", "b\n", "add b");
+		repo.reset(
+			repo.find_commit(base.into()).unwrap().as_object(),
+			git2::ResetType::Hard,
+			None,
+		)
+		.unwrap();
+		let ours = write_commit_file(&repo, "a.txt", "y\n", "ours");
+
+		let options = CherryPickOptions::default();
+		assert_eq!(
+			cherry_pick_commits(repo_path, &[c1, c2], options)
+				.unwrap(),
+			SequencerResult::Conflicts(c1)
+		);
+		assert_eq!(repo.state(), RepositoryState::CherryPickSequence);
+		assert_eq!(
+			sequencer_progress(repo_path).unwrap(),
+			Some(SequencerProgress {
+				action: SequencerAction::Pick,
+				current: c1,
+				remaining: 2
+			})
+		);
+		assert!(sequencer_continue(repo_path).is_err());
+
+		sequencer_abort(repo_path).unwrap();
+		assert_eq!(
+			repo.head().unwrap().target().unwrap(),
+			ours.into()
+		);
+		assert_eq!(repo.state(), RepositoryState::Clean);
+
+		cherry_pick_commits(repo_path, &[c1, c2], options).unwrap();
+		assert_eq!(
+			sequencer_skip(repo_path).unwrap(),
+			SequencerResult::Done(1)
+		);
+		let head = repo.head().unwrap().peel_to_commit().unwrap();
+		assert_eq!(head.summary(), Some("add b"));
+		assert_eq!(head.parent_id(0).unwrap(), ours.into());
+		repo.reset(
+			repo.find_commit(ours.into()).unwrap().as_object(),
+			git2::ResetType::Hard,
+			None,
+		)
+		.unwrap();
+
+		cherry_pick_commits(repo_path, &[c1, c2], options).unwrap();
+		fs::write(root.join("a.txt"), "xy\n").unwrap();
+		stage_add_file(repo_path, Path::new("a.txt")).unwrap();
+
+		assert_eq!(
+			sequencer_continue(repo_path).unwrap(),
+			SequencerResult::Done(2)
+		);
+		let head = repo.head().unwrap().peel_to_commit().unwrap();
+		assert_eq!(head.summary(), Some("add b"));
+		assert_eq!(
+			head.parent(0).unwrap().summary(),
+			Some("change a")
+		);
+		assert_eq!(repo.state(), RepositoryState::Clean);
+	}
+
+	#[test]
+	fn test_revert_sequence() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "1\n", "base");
+		let c1 = write_commit_file(&repo, "a.txt", "2\n", "two");
+		let c2 = write_commit_file(&repo, "b.txt", "b\n", "add b");
+		write_commit_file(&repo, "a.txt", "3\n", "three");
+
+		// like `git revert c2 c1` stopped at the conflicting c1
+		let repo = crate::sync::repository::repo(repo_path).unwrap();
+		let dir = sequencer_dir(&repo);
+		fs::create_dir_all(&dir).unwrap();
+		let head = repo.head().unwrap().target().unwrap();
+		fs::write(dir.join("head"), format!("{head}\n")).unwrap();
+		write_todo(
+			&repo,
+			&[
+				(SequencerAction::Revert, c2),
+				(SequencerAction::Revert, c1),
+			],
+		)
+		.unwrap();
+		assert_eq!(
+			run_todo(&repo, 0).unwrap(),
+			SequencerResult::Conflicts(c1)
+		);
+		assert_eq!(repo.state(), RepositoryState::RevertSequence);
+
+		fs::write(root.join("a.txt"), "1\n").unwrap();
+		stage_add_file(repo_path, Path::new("a.txt")).unwrap();
+		assert_eq!(
+			sequencer_continue(repo_path).unwrap(),
+			SequencerResult::Done(1)
+		);
+
+		let head = repo.head().unwrap().peel_to_commit().unwrap();
+		assert_eq!(head.summary(), Some("Revert \"two\""));
+		assert_eq!(
+			head.parent(0).unwrap().summary(),
+			Some("Revert \"add b\"")
+		);
+		assert_eq!(repo.state(), RepositoryState::Clean);
+	}
+
+	#[test]
+	fn test_single_revert() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 = write_commit_file(&repo, "a.txt", "a\n", "add a");
+
+		revert_commit(repo_path, c1, None).unwrap();
+		assert_eq!(
+			sequencer_progress(repo_path).unwrap(),
+			Some(SequencerProgress {
+				action: SequencerAction::Revert,
+				current: c1,
+				remaining: 1
+			})
+		);
+
+		assert_eq!(
+			sequencer_continue(repo_path).unwrap(),
+			SequencerResult::Done(1)
+		);
+		let head = repo.head().unwrap().peel_to_commit().unwrap();
+		assert_eq!(head.summary(), Some("Revert \"add a\""));
+		assert_eq!(repo.state(), RepositoryState::Clean);
+	}
+}
@@ -0,0 +1,100 @@
+//! shallow clones, whose history ends at the commits listed in
+//! `.git/shallow` (the graft points)
+
+use super::{repository::repo, CommitId, RepoPath};
+use crate::error::Result;
+use git2::{Oid, Repository};
+use scopetime::scope_time;
+use std::fs;
+
+const SHALLOW_FILE: &str = "shallow";
+
+/// where the history of a shallow clone is cut off
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ShallowInfo {
+	/// commits whose parents are missing
+	pub boundary: Vec<CommitId>,
+	/// number of commits reachable from `HEAD`
+	pub depth: usize,
+}
+
+fn read_boundary(repo: &Repository) -> Vec<CommitId> {
+	fs::read_to_string(repo.path().join(SHALLOW_FILE))
+		.unwrap_or_default()
+		.lines()
+		.filter_map(|line| Oid::from_str(line.trim()).ok())
+		.map(CommitId::new)
+		.collect()
+}
+
+/// graft points of a shallow clone, empty for a complete one.
+/// cheap enough to poll for changes, unlike `shallow_info`
+pub fn shallow_boundary(
+	repo_path: &RepoPath,
+) -> Result<Vec<CommitId>> {
+	let repo = repo(repo_path)?;
+
+	if !repo.is_shallow() {
+		return Ok(Vec::new());
+	}
+
+	Ok(read_boundary(&repo))
+}
+
+/// `None` unless the repo is a shallow clone
+pub fn shallow_info(
+	repo_path: &RepoPath,
+) -> Result<Option<ShallowInfo>> {
+	scope_time!("shallow_info");
+
+	let repo = repo(repo_path)?;
+
+	if !repo.is_shallow() {
+		return Ok(None);
+	}
+
+	let boundary = read_boundary(&repo);
+
+	let depth = if repo.head().is_ok() {
+		let mut walk = repo.revwalk()?;
+		walk.push_head()?;
+		walk.flatten().count()
+	} else {
+		0
+	};
+
+	Ok(Some(ShallowInfo { boundary, depth }))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::tests::{repo_init, write_commit_file};
+
+	#[test]
+	fn test_shallow_info() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "c1");
+		let c2 = write_commit_file(&repo, "a.txt", "b", "c2");
+		write_commit_file(&repo, "a.txt", "c", "c3");
+
+		assert_eq!(shallow_info(repo_path).unwrap(), None);
+		assert!(shallow_boundary(repo_path).unwrap().is_empty());
+
+		fs::write(repo.path().join(SHALLOW_FILE), format!("{c2}\n"))
+			.unwrap();
+
+		assert_eq!(
+			shallow_info(repo_path).unwrap(),
+			Some(ShallowInfo {
+				boundary: vec![c2],
+				depth: 2,
+			})
+		);
+		assert_eq!(shallow_boundary(repo_path).unwrap(), vec![c2]);
+	}
+}
@@ -156,30 +156,48 @@ impl SignBuilder {
 				String::from("x509"),
 			)),
 			"ssh" => {
-				let ssh_signer = config
+				// https://git-scm.com/docs/git-config#Documentation/git-config.txt-gpgsshprogram
+				let program = config
+					.get_string("gpg.ssh.program")
+					.unwrap_or_else(|_| "ssh-keygen".to_string());
+
+				let signing_key = config
 					.get_string("user.signingKey")
 					.ok()
-					.and_then(|key_path| {
-						key_path.strip_prefix('~').map_or_else(
-							|| Some(PathBuf::from(&key_path)),
-							|ssh_key_path| {
-								dirs::home_dir().map(|home| {
-									home.join(
-										ssh_key_path
-											.strip_prefix('/')
-											.unwrap_or(ssh_key_path),
-									)
-								})
-							},
-						)
-					})
+					.or_else(|| allowed_signers_key(repo, config))
 					.ok_or_else(|| {
 						SignBuilderError::SSHSigningKey(String::from(
 							"ssh key setting absent",
 						))
-					})
-					.and_then(SSHSign::new)?;
-				let signer: Box<dyn Sign> = Box::new(ssh_signer);
+					})?;
+
+				if let Some(key) = literal_ssh_key(&signing_key) {
+					return Ok(Box::new(SSHProgramSign::new(
+						&program,
+						SSHKey::Literal(key.to_string()),
+					)));
+				}
+
+				let key_path =
+					expand_home(&signing_key).ok_or_else(|| {
+						SignBuilderError::SSHSigningKey(String::from(
+							"home directory not found",
+						))
+					})?;
+
+				// keys that can not be read from disk (encrypted or
+				// only held by an ssh-agent) are left to ssh-keygen
+				let signer: Box<dyn Sign> =
+					match SSHSign::new(key_path.clone()) {
+						Ok(signer) => Box::new(signer),
+						Err(err) => {
+							log::debug!("ssh-keygen fallback: {err}");
+							Box::new(SSHProgramSign::new(
+								&program,
+								SSHKey::Path(key_path),
+							))
+						}
+					};
 				Ok(signer)
 			}
 			_ => Err(SignBuilderError::InvalidFormat(format)),
@@ -187,6 +205,66 @@ impl SignBuilder {
 	}
 }
 
+/// `user.signingKey` given as the public key itself
+/// (`key::ssh-ed25519 AAAA...` or `ssh-ed25519 AAAA...`)
+fn literal_ssh_key(signing_key: &str) -> Option<&str> {
+	signing_key.strip_prefix("key::").or_else(|| {
+		signing_key.starts_with("ssh-").then_some(signing_key)
+	})
+}
+
+fn expand_home(path: &str) -> Option<PathBuf> {
+	path.strip_prefix('~').map_or_else(
+		|| Some(PathBuf::from(path)),
+		|path| {
+			dirs::home_dir().map(|home| {
+				home.join(path.strip_prefix('/').unwrap_or(path))
+			})
+		},
+	)
+}
+
+/// public key listed for the committer's email in
+/// `gpg.ssh.allowedSignersFile`, used if `user.signingKey` is unset
+fn allowed_signers_key(
+	repo: &git2::Repository,
+	config: &git2::Config,
+) -> Option<String> {
+	let signature =
+		crate::sync::commit::signature_allow_undefined_name(repo)
+			.ok()?;
+	let email = signature.email()?;
+	let file = config.get_path("gpg.ssh.allowedSignersFile").ok()?;
+
+	parse_allowed_signers(&std::fs::read_to_string(file).ok()?, email)
+}
+
+/// finds the key of `principal` in the `ssh-keygen` allowed signers
+/// format: `principals [options] keytype base64 [comment]`
+fn parse_allowed_signers(
+	content: &str,
+	principal: &str,
+) -> Option<String> {
+	content.lines().find_map(|line| {
+		let mut fields = line.split_whitespace();
+		let principals = fields.next()?;
+
+		if principals.starts_with('#')
+			|| !principals.split(',').any(|p| p == principal)
+		{
+			return None;
+		}
+
+		let mut fields = fields.skip_while(|field| {
+			!(field.starts_with("ssh-")
+				|| field.starts_with("ecdsa-")
+				|| field.starts_with("sk-"))
+		});
+
+		Some(format!("{} {}", fields.next()?, fields.next()?))
+	})
+}
+
 /// Sign commit data using `OpenPGP`
 pub struct GPGSign {
 	program: String,
@@ -337,6 +415,108 @@ impl Sign for SSHSign {
 	}
 }
 
+/// ssh key passed to `ssh-keygen -Y sign`
+pub enum SSHKey {
+	/// path of a private or public key, the latter is looked up in
+	/// the ssh-agent
+	Path(PathBuf),
+	/// public key whose private part is held by the ssh-agent
+	Literal(String),
+}
+
+/// Sign commit data using `ssh-keygen -Y sign`
+pub struct SSHProgramSign {
+	program: String,
+	key: SSHKey,
+	#[cfg(test)]
+	signing_key: String,
+}
+
+impl SSHProgramSign {
+	/// Create new [`SSHProgramSign`] using given program and key.
+	pub fn new(program: &str, key: SSHKey) -> Self {
+		Self {
+			program: program.to_string(),
+			#[cfg(test)]
+			signing_key: match &key {
+				SSHKey::Path(path) => format!("{}", path.display()),
+				SSHKey::Literal(key) => key.clone(),
+			},
+			key,
+		}
+	}
+}
+
+impl Sign for SSHProgramSign {
+	fn sign(
+		&self,
+		commit: &[u8],
+	) -> Result<(String, Option<String>), SignError> {
+		use crate::sync::utils::TempFile;
+		use std::process::{Command, Stdio};
+
+		let temp_file = |prefix: &str, content: &[u8]| {
+			TempFile::new(prefix, content)
+				.map_err(|e| SignError::WriteBuffer(e.to_string()))
+		};
+
+		let buffer = temp_file("ssh-buffer", commit)?;
+
+		let mut cmd = Command::new(&self.program);
+		cmd.stdin(Stdio::null())
+			.args(["-Y", "sign", "-n", "git", "-f"]);
+
+		let _literal_key = match &self.key {
+			SSHKey::Path(path) => {
+				cmd.arg(path);
+				None
+			}
+			SSHKey::Literal(key) => {
+				let file = temp_file("ssh-key", key.as_bytes())?;
+				cmd.arg(file.path()).arg("-U");
+				Some(file)
+			}
+		};
+
+		cmd.arg(buffer.path());
+
+		log::trace!("signing command: {cmd:?}");
+
+		let output = cmd
+			.output()
+			.map_err(|e| SignError::Spawn(e.to_string()))?;
+
+		// written next to the buffer by ssh-keygen
+		let sig_path =
+			PathBuf::from(format!("{}.sig", buffer.path().display()));
+		let signature = std::fs::read_to_string(&sig_path);
+		std::fs::remove_file(&sig_path).ok();
+
+		if !output.status.success() {
+			return Err(SignError::Shellout(format!(
+				"failed to sign data, program '{}' exited non-zero: {}",
+				&self.program,
+				String::from_utf8_lossy(&output.stderr)
+			)));
+		}
+
+		let signature = signature
+			.map_err(|e| SignError::Output(e.to_string()))?;
+
+		Ok((signature, None))
+	}
+
+	#[cfg(test)]
+	fn program(&self) -> &String {
+		&self.program
+	}
+
+	#[cfg(test)]
+	fn signing_key(&self) -> &String {
+		&self.signing_key
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -439,4 +619,98 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[test]
+	fn test_ssh_literal_key() -> Result<()> {
+		let (_tmp_dir, repo) = repo_init_empty()?;
+
+		{
+			let mut config = repo.config()?;
+			config.set_str("gpg.format", "ssh")?;
+			config.set_str(
+				"user.signingKey",
+				"key::ssh-ed25519 AAAATEST jane@laptop",
+			)?;
+		}
+
+		let sign =
+			SignBuilder::from_gitconfig(&repo, &repo.config()?)?;
+
+		assert_eq!("ssh-keygen", sign.program());
+		assert_eq!(
+			"ssh-ed25519 AAAATEST jane@laptop",
+			sign.signing_key()
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_ssh_key_from_allowed_signers() -> Result<()> {
+		let (tmp_dir, repo) = repo_init_empty()?;
+		let allowed = tmp_dir.path().join("allowed_signers");
+		std::fs::write(
+			&allowed,
+			"other ssh-ed25519 AAAAOTHER\nemail ssh-ed25519 AAAAMINE\n",
+		)?;
+
+		{
+			let mut config = repo.config()?;
+			config.set_str("gpg.format", "ssh")?;
+			config.set_str("gpg.ssh.program", "SSH_PROGRAM_TEST")?;
+			config.set_str(
+				"gpg.ssh.allowedSignersFile",
+				allowed.to_str().unwrap(),
+			)?;
+		}
+
+		let sign =
+			SignBuilder::from_gitconfig(&repo, &repo.config()?)?;
+
+		assert_eq!("SSH_PROGRAM_TEST", sign.program());
+		assert_eq!("ssh-ed25519 AAAAMINE", sign.signing_key());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_parse_allowed_signers() {
+		let content = "\
+# comment
+a@b.c,jane@x.y namespaces=\"git\" ssh-ed25519 AAAAJANE jane
+bob@x.y ecdsa-sha2-nistp256 AAAABOB
+";
+
+		assert_eq!(
+			parse_allowed_signers(content, "jane@x.y").as_deref(),
+			Some("ssh-ed25519 AAAAJANE")
+		);
+		assert_eq!(
+			parse_allowed_signers(content, "bob@x.y").as_deref(),
+			Some("ecdsa-sha2-nistp256 AAAABOB")
+		);
+		assert_eq!(parse_allowed_signers(content, "x.y"), None);
+	}
+
+	#[test]
+	fn test_ssh_program_sign() -> Result<()> {
+		let tmp_dir = tempfile::TempDir::new()?;
+		let key = tmp_dir.path().join("key");
+		let status = std::process::Command::new("ssh-keygen")
+			.args(["-q", "-t", "ed25519", "-N", "", "-f"])
+			.arg(&key)
+			.status()?;
+		assert!(status.success());
+
+		let sign =
+			SSHProgramSign::new("ssh-keygen", SSHKey::Path(key));
+		let (signature, field) = sign.sign(b"data").unwrap();
+
+		assert!(
+			signature.starts_with("-----BEGIN SSH SIGNATURE-----")
+		);
+		assert_eq!(field, None);
+
+		Ok(())
+	}
 }
@@ -0,0 +1,308 @@
+//! selecting the key commits get signed with
+
+use super::{
+	config::{set_config_string, ConfigOrigin},
+	repository::repo,
+	RepoPath,
+};
+use crate::error::Result;
+use git2::Config;
+use scopetime::scope_time;
+use std::{path::Path, process::Command};
+
+/// `gpg.format` of a signing key
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SigningFormat {
+	///
+	OpenPgp,
+	///
+	Ssh,
+	///
+	X509,
+}
+
+impl SigningFormat {
+	/// value of `gpg.format`
+	pub const fn config_value(self) -> &'static str {
+		match self {
+			Self::OpenPgp => "openpgp",
+			Self::Ssh => "ssh",
+			Self::X509 => "x509",
+		}
+	}
+
+	fn from_config(config: &Config) -> Self {
+		match config.get_string("gpg.format").as_deref() {
+			Ok("ssh") => Self::Ssh,
+			Ok("x509") => Self::X509,
+			_ => Self::OpenPgp,
+		}
+	}
+}
+
+/// a key usable as `user.signingKey`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SigningKey {
+	///
+	pub format: SigningFormat,
+	/// value to store in `user.signingKey`
+	pub key: String,
+	/// user id of gpg keys, comment of ssh keys
+	pub description: String,
+}
+
+/// whether and how the next commit gets signed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SigningStatus {
+	/// `commit.gpgSign`
+	pub enabled: bool,
+	///
+	pub format: SigningFormat,
+	/// `user.signingKey`, `None` falls back to the committer identity
+	pub key: Option<String>,
+}
+
+/// reads the effective signing configuration
+pub fn get_signing_status(
+	repo_path: &RepoPath,
+) -> Result<SigningStatus> {
+	scope_time!("get_signing_status");
+
+	let repo = repo(repo_path)?;
+	let config = repo.config()?;
+
+	Ok(SigningStatus {
+		enabled: config.get_bool("commit.gpgSign").unwrap_or(false),
+		format: SigningFormat::from_config(&config),
+		key: config.get_string("user.signingKey").ok(),
+	})
+}
+
+/// lists secret gpg keys and ssh keys in `~/.ssh` that have both
+/// the private and the public part on disk
+pub fn list_signing_keys(
+	repo_path: &RepoPath,
+) -> Result<Vec<SigningKey>> {
+	scope_time!("list_signing_keys");
+
+	let repo = repo(repo_path)?;
+	let config = repo.config()?;
+
+	let program = config
+		.get_string("gpg.openpgp.program")
+		.or_else(|_| config.get_string("gpg.program"))
+		.unwrap_or_else(|_| "gpg".to_string());
+
+	let mut keys = Command::new(program)
+		.args(["--list-secret-keys", "--with-colons"])
+		.output()
+		.ok()
+		.filter(|output| output.status.success())
+		.map(|output| {
+			parse_gpg_secret_keys(&String::from_utf8_lossy(
+				&output.stdout,
+			))
+		})
+		.unwrap_or_default();
+
+	if let Some(home) = dirs::home_dir() {
+		keys.extend(list_ssh_keys(&home.join(".ssh")));
+	}
+
+	Ok(keys)
+}
+
+/// stores the key (and its format) in the repo local config
+pub fn set_signing_key(
+	repo_path: &RepoPath,
+	key: &SigningKey,
+) -> Result<()> {
+	scope_time!("set_signing_key");
+
+	set_config_string(
+		repo_path,
+		ConfigOrigin::Local,
+		"gpg.format",
+		Some(key.format.config_value()),
+	)?;
+	set_config_string(
+		repo_path,
+		ConfigOrigin::Local,
+		"user.signingKey",
+		Some(&key.key),
+	)
+}
+
+/// enables or disables commit signing in the repo local config
+pub fn set_commit_signing(
+	repo_path: &RepoPath,
+	enabled: bool,
+) -> Result<()> {
+	scope_time!("set_commit_signing");
+
+	set_config_string(
+		repo_path,
+		ConfigOrigin::Local,
+		"commit.gpgSign",
+		Some(if enabled { "true" } else { "false" }),
+	)
+}
+
+/// parses `gpg --list-secret-keys --with-colons` output
+fn parse_gpg_secret_keys(output: &str) -> Vec<SigningKey> {
+	let mut keys: Vec<SigningKey> = Vec::new();
+	let mut in_secret_key = false;
+
+	for line in output.lines() {
+		let fields = line.split(':').collect::<Vec<_>>();
+
+		match fields.first() {
+			Some(&"sec") => {
+				in_secret_key = true;
+				if let Some(id) = fields.get(4) {
+					keys.push(SigningKey {
+						format: SigningFormat::OpenPgp,
+						key: (*id).to_string(),
+						description: String::new(),
+					});
+				}
+			}
+			Some(&"uid") if in_secret_key => {
+				if let (Some(key), Some(uid)) =
+					(keys.last_mut(), fields.get(9))
+				{
+					if key.description.is_empty() {
+						key.description = (*uid).to_string();
+					}
+				}
+			}
+			Some(&"pub") => in_secret_key = false,
+			_ => (),
+		}
+	}
+
+	keys
+}
+
+/// `*.pub` files in `dir` whose private key exists next to them
+fn list_ssh_keys(dir: &Path) -> Vec<SigningKey> {
+	let Ok(entries) = std::fs::read_dir(dir) else {
+		return Vec::new();
+	};
+
+	let mut keys = entries
+		.filter_map(|entry| {
+			let path = entry.ok()?.path();
+			if path.extension()? != "pub"
+				|| !path.with_extension("").is_file()
+			{
+				return None;
+			}
+
+			let content = std::fs::read_to_string(&path).ok()?;
+			let comment = content
+				.split_whitespace()
+				.nth(2)
+				.unwrap_or_default()
+				.to_string();
+
+			Some(SigningKey {
+				format: SigningFormat::Ssh,
+				key: path.to_str()?.to_string(),
+				description: comment,
+			})
+		})
+		.collect::<Vec<_>>();
+
+	keys.sort_by(|a, b| a.key.cmp(&b.key));
+
+	keys
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::tests::repo_init;
+	use tempfile::TempDir;
+
+	#[test]
+	fn test_parse_gpg_secret_keys() {
+		let output = "\
+sec:u:255:22:AAAA1111BBBB2222:1700000000:::u:::scESC:::+:::ed25519:::0:
+fpr:::::::::0123456789ABCDEF0123AAAA1111BBBB2222:
+uid:u::::1700000000::HASH::Jane Doe <jane@example.com>::::::::::0:
+uid:u::::1700000000::HASH::Jane Work <jane@work.com>::::::::::0:
+ssb:u:255:18:CCCC3333DDDD4444:1700000000::::::e:::+:::cv25519::
+sec:u:4096:1:EEEE5555FFFF6666:1600000000:::u:::scESC:::+::::::0:
+uid:u::::1600000000::HASH::Old Key <old@example.com>::::::::::0:
+";
+
+		let keys = parse_gpg_secret_keys(output);
+
+		assert_eq!(keys.len(), 2);
+		assert_eq!(keys[0].key, "AAAA1111BBBB2222");
+		assert_eq!(
+			keys[0].description,
+			"Jane Doe <jane@example.com>"
+		);
+		assert_eq!(keys[0].format, SigningFormat::OpenPgp);
+		assert_eq!(keys[1].key, "EEEE5555FFFF6666");
+		assert_eq!(keys[1].description, "Old Key <old@example.com>");
+	}
+
+	#[test]
+	fn test_list_ssh_keys() {
+		let dir = TempDir::new().unwrap();
+		let path = dir.path();
+
+		std::fs::write(path.join("id_ed25519"), "private").unwrap();
+		std::fs::write(
+			path.join("id_ed25519.pub"),
+			"ssh-ed25519 AAAAC3Nz jane@laptop\n",
+		)
+		.unwrap();
+		// public key without private counterpart
+		std::fs::write(path.join("other.pub"), "ssh-rsa AAAA x\n")
+			.unwrap();
+		std::fs::write(path.join("known_hosts"), "").unwrap();
+
+		let keys = list_ssh_keys(path);
+
+		assert_eq!(keys.len(), 1);
+		assert_eq!(keys[0].format, SigningFormat::Ssh);
+		assert!(keys[0].key.ends_with("id_ed25519.pub"));
+		assert_eq!(keys[0].description, "jane@laptop");
+	}
+
+	#[test]
+	fn test_set_signing_key() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let status = get_signing_status(repo_path).unwrap();
+		assert!(!status.enabled);
+		assert_eq!(status.key, None);
+
+		set_signing_key(
+			repo_path,
+			&SigningKey {
+				format: SigningFormat::Ssh,
+				key: String::from("/keys/id.pub"),
+				description: String::new(),
+			},
+		)
+		.unwrap();
+		set_commit_signing(repo_path, true).unwrap();
+
+		assert_eq!(
+			get_signing_status(repo_path).unwrap(),
+			SigningStatus {
+				enabled: true,
+				format: SigningFormat::Ssh,
+				key: Some(String::from("/keys/id.pub")),
+			}
+		);
+	}
+}
@@ -1,4 +1,6 @@
-use super::{apply_selection, load_file};
+use super::{
+	apply_selection, load_file, untracked::discard_untracked_lines,
+};
 use crate::{
 	error::Result,
 	sync::{
@@ -8,6 +10,7 @@ use crate::{
 	},
 };
 use scopetime::scope_time;
+use std::path::Path;
 
 /// discards specific lines in an unstaged hunk of a diff
 pub fn discard_lines(
@@ -24,7 +27,9 @@ pub fn discard_lines(
 	let repo = repo(repo_path)?;
 	repo.index()?.read(true)?;
 
-	//TODO: check that file is not new (status modified)
+	if repo.status_file(Path::new(file_path))?.is_wt_new() {
+		return discard_untracked_lines(&repo, file_path, lines);
+	}
 
 	let new_content = {
 		let patch =
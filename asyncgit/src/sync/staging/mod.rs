@@ -1,5 +1,6 @@
 mod discard_tracked;
 mod stage_tracked;
+mod untracked;
 
 pub use discard_tracked::discard_lines;
 pub use stage_tracked::stage_lines;
@@ -1,4 +1,4 @@
-use super::apply_selection;
+use super::{apply_selection, untracked::stage_untracked_lines};
 use crate::{
 	error::{Error, Result},
 	sync::{
@@ -28,12 +28,16 @@ pub fn stage_lines(
 
 	let mut index = repo.index()?;
 	index.read(true)?;
-	let mut idx =
-		index.get_path(Path::new(file_path), 0).ok_or_else(|| {
-			Error::Generic(String::from(
+	let Some(mut idx) = index.get_path(Path::new(file_path), 0)
+	else {
+		if is_stage {
+			return Err(Error::Generic(String::from(
 				"only non new files supported",
-			))
-		})?;
+			)));
+		}
+
+		return stage_untracked_lines(&repo, file_path, lines);
+	};
 	let blob = repo.find_blob(idx.id)?;
 	let indexed_content = String::from_utf8(blob.content().into())?;
 
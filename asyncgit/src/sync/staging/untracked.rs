@@ -0,0 +1,162 @@
+//! line selections of files that are not in the index yet
+
+use super::{load_file, NEWLINE};
+use crate::{
+	error::Result,
+	sync::{diff::DiffLinePosition, utils::repo_write_file},
+};
+use easy_cast::Conv;
+use git2::{IndexEntry, IndexTime, Repository};
+use std::collections::HashSet;
+
+/// adds the file to the index containing only the selected `lines`
+pub(super) fn stage_untracked_lines(
+	repo: &Repository,
+	file_path: &str,
+	lines: &[DiffLinePosition],
+) -> Result<()> {
+	let content = load_file(repo, file_path)?;
+	let new_content = filter_lines(&content, lines, true);
+
+	let mut index = repo.index()?;
+	index.read(true)?;
+
+	let entry = IndexEntry {
+		ctime: IndexTime::new(0, 0),
+		mtime: IndexTime::new(0, 0),
+		dev: 0,
+		ino: 0,
+		mode: file_mode(repo, file_path),
+		uid: 0,
+		gid: 0,
+		file_size: u32::try_conv(new_content.len())?,
+		id: repo.blob(new_content.as_bytes())?,
+		flags: 0,
+		flags_extended: 0,
+		path: file_path.as_bytes().to_vec(),
+	};
+
+	index.add_frombuffer(&entry, new_content.as_bytes())?;
+	index.write()?;
+
+	Ok(())
+}
+
+/// removes the selected `lines` from the file in the workdir
+pub(super) fn discard_untracked_lines(
+	repo: &Repository,
+	file_path: &str,
+	lines: &[DiffLinePosition],
+) -> Result<()> {
+	let content = load_file(repo, file_path)?;
+	let new_content = filter_lines(&content, lines, false);
+
+	repo_write_file(repo, file_path, &new_content)?;
+
+	Ok(())
+}
+
+/// every line of an untracked file is an addition, `keep_selected`
+/// decides whether the selected or the other lines remain
+fn filter_lines(
+	content: &str,
+	lines: &[DiffLinePosition],
+	keep_selected: bool,
+) -> String {
+	let selected = lines
+		.iter()
+		.filter_map(|line| line.new_lineno)
+		.collect::<HashSet<_>>();
+
+	let mut res = String::with_capacity(content.len());
+
+	for (idx, line) in content.lines().enumerate() {
+		let lineno = u32::try_from(idx + 1).unwrap_or(u32::MAX);
+
+		if selected.contains(&lineno) == keep_selected {
+			res.push_str(line);
+			res.push(NEWLINE);
+		}
+	}
+
+	res
+}
+
+#[cfg(unix)]
+fn file_mode(repo: &Repository, file_path: &str) -> u32 {
+	use std::os::unix::fs::PermissionsExt;
+
+	let executable = repo
+		.workdir()
+		.and_then(|workdir| {
+			std::fs::metadata(workdir.join(file_path)).ok()
+		})
+		.is_some_and(|meta| meta.permissions().mode() & 0o111 != 0);
+
+	if executable {
+		0o100_755
+	} else {
+		0o100_644
+	}
+}
+
+#[cfg(not(unix))]
+const fn file_mode(_repo: &Repository, _file_path: &str) -> u32 {
+	0o100_644
+}
+
+#[cfg(test)]
+mod test {
+	use crate::sync::{
+		diff::{get_diff, DiffLinePosition},
+		discard_lines, stage_lines,
+		tests::{repo_init, write_commit_file},
+		utils::repo_write_file,
+		RepoPath,
+	};
+	use std::fs;
+
+	fn line(new_lineno: u32) -> DiffLinePosition {
+		DiffLinePosition {
+			old_lineno: None,
+			new_lineno: Some(new_lineno),
+		}
+	}
+
+	#[test]
+	fn test_stage_untracked_lines() {
+		let (td, repo) = repo_init().unwrap();
+		let path: &RepoPath = &td.path().to_str().unwrap().into();
+
+		write_commit_file(&repo, "other.txt", "x\n", "c1");
+		repo_write_file(&repo, "new.txt", "a\nb\nc\n").unwrap();
+
+		stage_lines(path, "new.txt", false, &[line(1), line(3)])
+			.unwrap();
+
+		let staged = get_diff(path, "new.txt", true, None).unwrap();
+		assert_eq!(staged.lines, 3);
+		assert_eq!(&*staged.hunks[0].lines[1].content, "a");
+		assert_eq!(&*staged.hunks[0].lines[2].content, "c");
+
+		let unstaged =
+			get_diff(path, "new.txt", false, None).unwrap();
+		assert_eq!(&*unstaged.hunks[0].lines[2].content, "b");
+	}
+
+	#[test]
+	fn test_discard_untracked_lines() {
+		let (td, repo) = repo_init().unwrap();
+		let path: &RepoPath = &td.path().to_str().unwrap().into();
+
+		write_commit_file(&repo, "other.txt", "x\n", "c1");
+		repo_write_file(&repo, "new.txt", "a\nb\nc\n").unwrap();
+
+		discard_lines(path, "new.txt", &[line(2)]).unwrap();
+
+		assert_eq!(
+			fs::read_to_string(td.path().join("new.txt")).unwrap(),
+			"a\nc\n"
+		);
+	}
+}
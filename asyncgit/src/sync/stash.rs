@@ -1,14 +1,24 @@
-use super::{CommitId, RepoPath};
+use super::{
+	commit_files::{diff_to_status_items, get_commit_files},
+	diff::{get_diff_raw, get_restore_diff, DiffOptions, HunkHeader},
+	reset::restore_file_from_tree,
+	CommitId, RepoPath,
+};
 use crate::{
 	error::{Error, Result},
+	hash,
 	sync::repository::repo,
+	StatusItem,
 };
 use git2::{
-	build::CheckoutBuilder, Oid, Repository, StashApplyOptions,
-	StashFlags,
+	build::CheckoutBuilder, ApplyLocation, ApplyOptions, Index, Oid,
+	Repository, StashApplyOptions, StashFlags, StashSaveOptions,
+	Tree,
 };
 use scopetime::scope_time;
 
+const STASH_REF: &str = "refs/stash";
+
 ///
 pub fn get_stashes(repo_path: &RepoPath) -> Result<Vec<CommitId>> {
 	scope_time!("get_stashes");
@@ -102,33 +112,337 @@ pub fn stash_save(
 	repo_path: &RepoPath,
 	message: Option<&str>,
 	include_untracked: bool,
+	include_ignored: bool,
 	keep_index: bool,
 ) -> Result<CommitId> {
 	scope_time!("stash_save");
 
+	stash_save_paths(
+		repo_path,
+		message,
+		&[],
+		include_untracked,
+		include_ignored,
+		keep_index,
+	)
+}
+
+/// like [`stash_save`] but only stashes the changes of `paths`
+///
+/// `paths` are files or folders, like `git stash push -- <paths>`.
+/// stashes everything if `paths` is empty.
+pub fn stash_save_paths(
+	repo_path: &RepoPath,
+	message: Option<&str>,
+	paths: &[String],
+	include_untracked: bool,
+	include_ignored: bool,
+	keep_index: bool,
+) -> Result<CommitId> {
+	scope_time!("stash_save_paths");
+
 	let mut repo = repo(repo_path)?;
 
 	let sig = repo.signature()?;
 
-	let mut options = StashFlags::DEFAULT;
+	let mut flags = StashFlags::DEFAULT;
 
 	if include_untracked {
-		options.insert(StashFlags::INCLUDE_UNTRACKED);
+		flags.insert(StashFlags::INCLUDE_UNTRACKED);
+	}
+	if include_ignored {
+		flags.insert(StashFlags::INCLUDE_IGNORED);
 	}
 	if keep_index {
-		options.insert(StashFlags::KEEP_INDEX);
+		flags.insert(StashFlags::KEEP_INDEX);
+	}
+
+	if paths.is_empty() {
+		let id = repo.stash_save2(&sig, message, Some(flags))?;
+
+		return Ok(CommitId::new(id));
 	}
 
-	let id = repo.stash_save2(&sig, message, Some(options))?;
+	// libgit2 would reset the whole worktree afterwards, not just
+	// `paths`, so that is left to `reset_stashed_paths`
+	let mut options = StashSaveOptions::new(sig);
+	options.flags(Some(flags | StashFlags::KEEP_ALL));
+	for path in paths {
+		options.pathspec(path.as_str());
+	}
+
+	let mut id = repo.stash_save_ext(Some(&mut options))?;
+
+	reset_stashed_paths(&repo, paths, flags)?;
+
+	// git2 has no way to pass the message along with a pathspec
+	if let Some(message) = message {
+		let branch = repo.head().ok().and_then(|head| {
+			head.is_branch()
+				.then(|| head.shorthand().map(String::from))
+				.flatten()
+		});
+		let message = format!(
+			"On {}: {message}",
+			branch.as_deref().unwrap_or("(no branch)")
+		);
+
+		id = repo.find_commit(id)?.amend(
+			None,
+			None,
+			None,
+			None,
+			Some(&message),
+			None,
+		)?;
+
+		// replace the entry of the unnamed stash
+		repo.reference(STASH_REF, id, true, &message)?;
+		let mut reflog = repo.reflog(STASH_REF)?;
+		reflog.remove(1, true)?;
+		reflog.write()?;
+	}
 
 	Ok(CommitId::new(id))
 }
 
+/// tree of `stash` including its untracked files (kept in the third
+/// parent)
+pub(super) fn stash_full_tree(
+	repo: &Repository,
+	stash: CommitId,
+) -> Result<Tree<'_>> {
+	let commit = repo.find_commit(stash.into())?;
+	let tree = commit.tree()?;
+
+	let Ok(untracked) = commit.parent(2) else {
+		return Ok(tree);
+	};
+
+	let mut index = Index::new()?;
+	index.read_tree(&tree)?;
+
+	let mut untracked_index = Index::new()?;
+	untracked_index.read_tree(&untracked.tree()?)?;
+	for entry in untracked_index.iter() {
+		index.add(&entry)?;
+	}
+
+	Ok(repo.find_tree(index.write_tree_to(repo)?)?)
+}
+
+/// files changed by `stash` whose version in the workdir differs
+/// from the stashed one
+pub fn stash_worktree_files(
+	repo_path: &RepoPath,
+	stash: CommitId,
+) -> Result<Vec<StatusItem>> {
+	scope_time!("stash_worktree_files");
+
+	let stashed = get_commit_files(repo_path, stash, None)?;
+	if stashed.is_empty() {
+		return Ok(Vec::new());
+	}
+
+	let repo = repo(repo_path)?;
+	let tree = stash_full_tree(&repo, stash)?;
+
+	let mut opt = git2::DiffOptions::new();
+	opt.reverse(true)
+		.include_untracked(true)
+		.recurse_untracked_dirs(true)
+		.disable_pathspec_match(true);
+	for file in &stashed {
+		opt.pathspec(&file.path);
+	}
+
+	let diff =
+		repo.diff_tree_to_workdir(Some(&tree), Some(&mut opt))?;
+
+	Ok(diff_to_status_items(&diff))
+}
+
+/// restores `path` in the workdir to its version in `stash`
+pub fn stash_apply_file(
+	repo_path: &RepoPath,
+	stash: CommitId,
+	path: &str,
+) -> Result<()> {
+	scope_time!("stash_apply_file");
+
+	let repo = repo(repo_path)?;
+	let tree = stash_full_tree(&repo, stash)?;
+
+	restore_file_from_tree(&repo, &tree, path, false)
+}
+
+/// applies the hunks (identified by their header hash) of the
+/// differences between the workdir and `stash` in `path`, see
+/// [`get_diff_stash_worktree`](super::diff::get_diff_stash_worktree)
+pub fn stash_apply_hunks(
+	repo_path: &RepoPath,
+	stash: CommitId,
+	path: &str,
+	hunk_hashes: &[u64],
+	options: Option<DiffOptions>,
+) -> Result<()> {
+	scope_time!("stash_apply_hunks");
+
+	let repo = repo(repo_path)?;
+	let tree = stash_full_tree(&repo, stash)?;
+
+	let diff = get_restore_diff(&repo, &tree, path, options)?;
+
+	let mut opt = ApplyOptions::new();
+	opt.hunk_callback(|hunk| {
+		hunk.is_some_and(|hunk| {
+			hunk_hashes.contains(&hash(&HunkHeader::from(hunk)))
+		})
+	});
+
+	repo.apply(&diff, ApplyLocation::WorkDir, Some(&mut opt))?;
+
+	Ok(())
+}
+
+/// checks out `HEAD` (or the index if it is kept) for `paths`
+fn reset_stashed_paths(
+	repo: &Repository,
+	paths: &[String],
+	flags: StashFlags,
+) -> Result<()> {
+	let mut checkout = CheckoutBuilder::new();
+	checkout.force();
+	if flags.contains(StashFlags::INCLUDE_UNTRACKED) {
+		checkout.remove_untracked(true);
+	}
+	if flags.contains(StashFlags::INCLUDE_IGNORED) {
+		checkout.remove_ignored(true);
+	}
+	for path in paths {
+		checkout.path(path);
+	}
+
+	if flags.contains(StashFlags::KEEP_INDEX) {
+		repo.checkout_index(None, Some(&mut checkout))?;
+	} else {
+		repo.checkout_tree(
+			repo.head()?.peel_to_tree()?.as_object(),
+			Some(&mut checkout),
+		)?;
+	}
+
+	Ok(())
+}
+
+/// stashes some hunks of the unstaged changes to `file_path`
+///
+/// the hunks (identified by their header hash) are removed from the
+/// workdir, like picking hunks in `git stash -p`. the index is kept
+/// and stored in the stash as usual.
+pub fn stash_save_hunks(
+	repo_path: &RepoPath,
+	message: Option<&str>,
+	file_path: &str,
+	hunk_hashes: &[u64],
+	options: Option<DiffOptions>,
+) -> Result<CommitId> {
+	scope_time!("stash_save_hunks");
+
+	let repo = repo(repo_path)?;
+
+	let sig = repo.signature()?;
+	let head = repo.head()?;
+	let head_commit = head.peel_to_commit()?;
+	let index_tree = repo.find_tree(repo.index()?.write_tree()?)?;
+
+	let diff = get_diff_raw(&repo, file_path, false, false, options)?;
+
+	let mut selected = Vec::new();
+	let mut hunk_idx = 0;
+	let mut opt = ApplyOptions::new();
+	opt.hunk_callback(|hunk| {
+		let hit = hunk.is_some_and(|hunk| {
+			hunk_hashes.contains(&hash(&HunkHeader::from(hunk)))
+		});
+		if hit {
+			selected.push(hunk_idx);
+		}
+		hunk_idx += 1;
+		hit
+	});
+
+	let tree = {
+		let mut stashed =
+			repo.apply_to_tree(&index_tree, &diff, Some(&mut opt))?;
+		repo.find_tree(stashed.write_tree_to(&repo)?)?
+	};
+	drop(opt);
+
+	if tree.id() == index_tree.id() {
+		return Err(Error::Generic(
+			"no changes selected to stash".to_string(),
+		));
+	}
+
+	// same messages `git stash` writes
+	let branch = if head.is_branch() {
+		head.shorthand().unwrap_or_default()
+	} else {
+		"(no branch)"
+	};
+	let summary = format!(
+		"{branch}: {} {}",
+		CommitId::new(head_commit.id()).get_short_string(),
+		head_commit.summary().unwrap_or_default()
+	);
+	let message = message.map_or_else(
+		|| format!("WIP on {summary}"),
+		|message| format!("On {branch}: {message}"),
+	);
+
+	let index_commit = repo.commit(
+		None,
+		&sig,
+		&sig,
+		&format!("index on {summary}"),
+		&index_tree,
+		&[&head_commit],
+	)?;
+	let stash = repo.commit(
+		None,
+		&sig,
+		&sig,
+		&message,
+		&tree,
+		&[&head_commit, &repo.find_commit(index_commit)?],
+	)?;
+
+	repo.reference_ensure_log(STASH_REF)?;
+	repo.reference(STASH_REF, stash, true, &message)?;
+
+	// hunk headers differ once reversed, so pick them by position
+	let mut hunk_idx = 0;
+	let mut opt = ApplyOptions::new();
+	opt.hunk_callback(|_hunk| {
+		let hit = selected.contains(&hunk_idx);
+		hunk_idx += 1;
+		hit
+	});
+
+	let diff = get_diff_raw(&repo, file_path, false, true, options)?;
+	repo.apply(&diff, ApplyLocation::WorkDir, Some(&mut opt))?;
+
+	Ok(CommitId::new(stash))
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
 	use crate::sync::{
-		commit, get_commit_files, get_commits_info, stage_add_file,
+		commit,
+		diff::{get_diff, get_diff_stash_worktree},
+		get_commit_files, get_commits_info, stage_add_file,
 		tests::{
 			debug_cmd_print, get_statuses, repo_init,
 			write_commit_file,
@@ -144,7 +458,9 @@ mod tests {
 		let repo_path: &RepoPath =
 			&root.as_os_str().to_str().unwrap().into();
 
-		assert!(!stash_save(repo_path, None, true, false).is_ok());
+		assert!(
+			!stash_save(repo_path, None, true, false, false).is_ok()
+		);
 
 		assert!(get_stashes(repo_path).unwrap().is_empty());
 	}
@@ -161,7 +477,7 @@ mod tests {
 
 		assert_eq!(get_statuses(repo_path), (1, 0));
 
-		stash_save(repo_path, None, true, false)?;
+		stash_save(repo_path, None, true, false, false)?;
 
 		assert_eq!(get_statuses(repo_path), (0, 0));
 
@@ -178,7 +494,7 @@ mod tests {
 		File::create(root.join("foo.txt"))?
 			.write_all(b"test\nfoo")?;
 
-		stash_save(repo_path, Some("foo"), true, false)?;
+		stash_save(repo_path, Some("foo"), true, false, false)?;
 
 		let res = get_stashes(repo_path)?;
 
@@ -192,6 +508,30 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	fn test_stash_ignored() -> Result<()> {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, ".gitignore", "*.log", "ignore");
+		File::create(root.join("foo.log"))?.write_all(b"test")?;
+		File::create(root.join("foo.txt"))?.write_all(b"test")?;
+
+		stash_save(repo_path, None, true, false, false)?;
+		assert!(root.join("foo.log").exists());
+		assert!(!root.join("foo.txt").exists());
+
+		stash_save(repo_path, None, false, true, false)?;
+		assert!(!root.join("foo.log").exists());
+
+		stash_pop(repo_path, get_stashes(repo_path)?[0])?;
+		assert!(root.join("foo.log").exists());
+
+		Ok(())
+	}
+
 	#[test]
 	fn test_stash_nothing_untracked() -> Result<()> {
 		let (_td, repo) = repo_init().unwrap();
@@ -202,9 +542,14 @@ mod tests {
 		File::create(root.join("foo.txt"))?
 			.write_all(b"test\nfoo")?;
 
-		assert!(
-			stash_save(repo_path, Some("foo"), false, false).is_err()
-		);
+		assert!(stash_save(
+			repo_path,
+			Some("foo"),
+			false,
+			false,
+			false
+		)
+		.is_err());
 
 		Ok(())
 	}
@@ -248,7 +593,8 @@ mod tests {
 		repo_write_file(&repo, "test.txt", "test").unwrap();
 
 		let id =
-			stash_save(repo_path, Some("foo"), true, false).unwrap();
+			stash_save(repo_path, Some("foo"), true, false, false)
+				.unwrap();
 
 		repo_write_file(&repo, "test.txt", "foo").unwrap();
 
@@ -269,7 +615,8 @@ mod tests {
 		repo_write_file(&repo, "test.txt", "test2").unwrap();
 
 		let id =
-			stash_save(repo_path, Some("foo"), true, false).unwrap();
+			stash_save(repo_path, Some("foo"), true, false, false)
+				.unwrap();
 
 		repo_write_file(&repo, "test.txt", "test3").unwrap();
 
@@ -290,7 +637,8 @@ mod tests {
 		repo_write_file(&repo, "test.txt", "test2").unwrap();
 
 		let id =
-			stash_save(repo_path, Some("foo"), true, false).unwrap();
+			stash_save(repo_path, Some("foo"), true, false, false)
+				.unwrap();
 
 		repo_write_file(&repo, "test.txt", "test3").unwrap();
 
@@ -315,7 +663,8 @@ mod tests {
 		repo_write_file(&repo, "test.txt", "test2").unwrap();
 
 		let id =
-			stash_save(repo_path, Some("foo"), true, false).unwrap();
+			stash_save(repo_path, Some("foo"), true, false, false)
+				.unwrap();
 
 		let res = stash_pop(repo_path, id);
 
@@ -336,7 +685,8 @@ mod tests {
 		repo_write_file(&repo, "test.txt", "test").unwrap();
 
 		let id =
-			stash_save(repo_path, Some("foo"), true, false).unwrap();
+			stash_save(repo_path, Some("foo"), true, false, false)
+				.unwrap();
 
 		repo_write_file(&repo, "test.txt", "test2").unwrap();
 
@@ -361,7 +711,8 @@ mod tests {
 		repo_write_file(&repo, "test.txt", "test2").unwrap();
 
 		let id =
-			stash_save(repo_path, Some("foo"), true, false).unwrap();
+			stash_save(repo_path, Some("foo"), true, false, false)
+				.unwrap();
 
 		repo_write_file(&repo, "test.txt", "test3").unwrap();
 
@@ -373,4 +724,141 @@ mod tests {
 			"test3"
 		);
 	}
+
+	#[test]
+	fn test_stash_paths() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "c1");
+		write_commit_file(&repo, "b.txt", "b", "c2");
+
+		repo_write_file(&repo, "a.txt", "a2").unwrap();
+		repo_write_file(&repo, "b.txt", "b2").unwrap();
+
+		let id = stash_save_paths(
+			repo_path,
+			Some("foo"),
+			&[String::from("a.txt")],
+			false,
+			false,
+			false,
+		)
+		.unwrap();
+
+		assert_eq!(repo_read_file(&repo, "a.txt").unwrap(), "a");
+		assert_eq!(repo_read_file(&repo, "b.txt").unwrap(), "b2");
+		assert_eq!(get_stashes(repo_path).unwrap(), vec![id]);
+		assert_eq!(
+			get_commits_info(repo_path, &[id], 100).unwrap()[0]
+				.message,
+			"On master: foo"
+		);
+
+		stash_pop(repo_path, id).unwrap();
+
+		assert_eq!(repo_read_file(&repo, "a.txt").unwrap(), "a2");
+		assert!(get_stashes(repo_path).unwrap().is_empty());
+	}
+
+	#[test]
+	fn test_stash_hunks() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let lines =
+			(0..20).map(|i| i.to_string() + "\n").collect::<String>();
+		write_commit_file(&repo, "test.txt", &lines, "c1");
+
+		let changed =
+			lines.replace("\n2\n", "\nx\n").replace("18\n", "y\n");
+		repo_write_file(&repo, "test.txt", &changed).unwrap();
+
+		let diff =
+			get_diff(repo_path, "test.txt", false, None).unwrap();
+		assert_eq!(diff.hunks.len(), 2);
+
+		let id = stash_save_hunks(
+			repo_path,
+			None,
+			"test.txt",
+			&[diff.hunks[0].header_hash],
+			None,
+		)
+		.unwrap();
+
+		assert_eq!(
+			repo_read_file(&repo, "test.txt").unwrap(),
+			lines.replace("18\n", "y\n")
+		);
+		assert_eq!(get_stashes(repo_path).unwrap(), vec![id]);
+		assert!(get_commits_info(repo_path, &[id], 100).unwrap()[0]
+			.message
+			.starts_with("WIP on master: "));
+
+		// only the stashed hunk comes back
+		repo_write_file(&repo, "test.txt", &lines).unwrap();
+		stash_pop(repo_path, id).unwrap();
+
+		assert_eq!(
+			repo_read_file(&repo, "test.txt").unwrap(),
+			lines.replace("\n2\n", "\nx\n")
+		);
+	}
+
+	#[test]
+	fn test_stash_worktree() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let lines =
+			(0..20).map(|i| i.to_string() + "\n").collect::<String>();
+		write_commit_file(&repo, "a.txt", &lines, "c1");
+		write_commit_file(&repo, "b.txt", "b", "c2");
+
+		let changed =
+			lines.replace("\n2\n", "\nx\n").replace("18\n", "y\n");
+		repo_write_file(&repo, "a.txt", &changed).unwrap();
+		repo_write_file(&repo, "c.txt", "c").unwrap();
+
+		let id =
+			stash_save(repo_path, None, true, false, false).unwrap();
+
+		let files = stash_worktree_files(repo_path, id).unwrap();
+		assert_eq!(
+			files.iter().map(|f| f.path.as_str()).collect::<Vec<_>>(),
+			vec!["a.txt", "c.txt"]
+		);
+
+		let diff =
+			get_diff_stash_worktree(repo_path, id, "a.txt", None)
+				.unwrap();
+		assert_eq!(diff.hunks.len(), 2);
+
+		stash_apply_hunks(
+			repo_path,
+			id,
+			"a.txt",
+			&[diff.hunks[1].header_hash],
+			None,
+		)
+		.unwrap();
+		assert_eq!(
+			repo_read_file(&repo, "a.txt").unwrap(),
+			lines.replace("18\n", "y\n")
+		);
+
+		stash_apply_file(repo_path, id, "c.txt").unwrap();
+		assert_eq!(repo_read_file(&repo, "c.txt").unwrap(), "c");
+
+		let files = stash_worktree_files(repo_path, id).unwrap();
+		assert_eq!(files.len(), 1);
+		assert_eq!(get_stashes(repo_path).unwrap(), vec![id]);
+	}
 }
@@ -12,9 +12,15 @@ pub enum RepoState {
 	Merge,
 	///
 	Rebase,
-	///
+	/// a revert (series) stopped at a conflict
 	Revert,
 	///
+	Bisect,
+	/// `git am` stopped at a patch
+	ApplyMailbox,
+	/// a cherry-pick (series) stopped at a conflict
+	CherryPick,
+	///
 	Other,
 }
 
@@ -23,9 +29,16 @@ impl From<RepositoryState> for RepoState {
 		match state {
 			RepositoryState::Clean => Self::Clean,
 			RepositoryState::Merge => Self::Merge,
-			RepositoryState::Revert => Self::Revert,
-			RepositoryState::RebaseMerge => Self::Rebase,
-			_ => {
+			RepositoryState::Revert
+			| RepositoryState::RevertSequence => Self::Revert,
+			RepositoryState::Bisect => Self::Bisect,
+			RepositoryState::RebaseMerge
+			| RepositoryState::RebaseInteractive
+			| RepositoryState::Rebase => Self::Rebase,
+			RepositoryState::ApplyMailbox => Self::ApplyMailbox,
+			RepositoryState::CherryPick
+			| RepositoryState::CherryPickSequence => Self::CherryPick,
+			RepositoryState::ApplyMailboxOrRebase => {
 				log::warn!("state not supported yet: {:?}", state);
 				Self::Other
 			}
@@ -3,11 +3,17 @@
 use crate::{
 	error::Error,
 	error::Result,
-	sync::{config::untracked_files_config_repo, repository::repo},
+	sync::{
+		config::untracked_files_config_repo,
+		renames::set_status_renames, repository::repo,
+		utils::work_dir,
+	},
+};
+use git2::{
+	Delta, DiffDelta, Repository, Status, StatusOptions, StatusShow,
 };
-use git2::{Delta, Status, StatusOptions, StatusShow};
 use scopetime::scope_time;
-use std::path::Path;
+use std::{path::Path, process::Command};
 
 use super::{RepoPath, ShowUntrackedFilesConfig};
 
@@ -23,14 +29,24 @@ pub enum StatusItemType {
 	///
 	Renamed,
 	///
+	Copied,
+	///
 	Typechange,
 	///
 	Conflicted,
+	/// excluded by an ignore rule (only listed on request)
+	Ignored,
+	/// tracked file with the `assume-unchanged` index bit set
+	AssumeUnchanged,
+	/// tracked file with the `skip-worktree` index bit set
+	SkipWorktree,
 }
 
 impl From<Status> for StatusItemType {
 	fn from(s: Status) -> Self {
-		if s.is_index_new() || s.is_wt_new() {
+		if s.is_ignored() {
+			Self::Ignored
+		} else if s.is_index_new() || s.is_wt_new() {
 			Self::New
 		} else if s.is_index_deleted() || s.is_wt_deleted() {
 			Self::Deleted
@@ -52,6 +68,7 @@ impl From<Delta> for StatusItemType {
 			Delta::Added => Self::New,
 			Delta::Deleted => Self::Deleted,
 			Delta::Renamed => Self::Renamed,
+			Delta::Copied => Self::Copied,
 			Delta::Typechange => Self::Typechange,
 			_ => Self::Modified,
 		}
@@ -65,6 +82,8 @@ pub struct StatusItem {
 	pub path: String,
 	///
 	pub status: StatusItemType,
+	/// path before being renamed or copied
+	pub old_path: Option<String>,
 }
 
 ///
@@ -131,6 +150,17 @@ pub fn get_status(
 	repo_path: &RepoPath,
 	status_type: StatusType,
 	show_untracked: Option<ShowUntrackedFilesConfig>,
+) -> Result<Vec<StatusItem>> {
+	get_status_ext(repo_path, status_type, show_untracked, false)
+}
+
+/// like `get_status` but optionally also lists ignored files
+/// (ignored folders are not recursed into)
+pub fn get_status_ext(
+	repo_path: &RepoPath,
+	status_type: StatusType,
+	show_untracked: Option<ShowUntrackedFilesConfig>,
+	include_ignored: bool,
 ) -> Result<Vec<StatusItem>> {
 	scope_time!("get_status");
 
@@ -146,15 +176,28 @@ pub fn get_status(
 		untracked_files_config_repo(&repo)?
 	};
 
+	if status_type == StatusType::WorkingDir
+		&& !include_ignored
+		&& git_status_is_faster(&repo)
+	{
+		match workdir_status_from_git(&repo, show_untracked) {
+			Ok(res) => return Ok(res),
+			Err(e) => log::error!("git status failed: {}", e),
+		}
+	}
+
 	let mut options = StatusOptions::default();
 	options
 		.show(status_type.into())
 		.update_index(true)
 		.include_untracked(show_untracked.include_untracked())
-		.renames_head_to_index(true)
+		.include_ignored(
+			include_ignored && status_type != StatusType::Stage,
+		)
 		.recurse_untracked_dirs(
 			show_untracked.recurse_untracked_dirs(),
 		);
+	set_status_renames(&repo, &mut options);
 
 	let statuses = repo.statuses(Some(&mut options))?;
 
@@ -163,6 +206,27 @@ pub fn get_status(
 	for e in statuses.iter() {
 		let status: Status = e.status();
 
+		// the workdir side of a rename is only known to this delta
+		if let Some(delta) =
+			e.index_to_workdir().filter(|_| status.is_wt_renamed())
+		{
+			if let (Some(path), Some(old_path)) =
+				(delta_path(&delta, true), delta_path(&delta, false))
+			{
+				res.push(StatusItem {
+					path,
+					status: StatusItemType::Renamed,
+					old_path: Some(old_path),
+				});
+				continue;
+			}
+		}
+
+		let old_path = e
+			.head_to_index()
+			.filter(|_| status.is_index_renamed())
+			.and_then(|delta| delta_path(&delta, false));
+
 		let path = match e.head_to_index() {
 			Some(diff) => diff
 				.new_file()
@@ -186,6 +250,7 @@ pub fn get_status(
 		res.push(StatusItem {
 			path,
 			status: StatusItemType::from(status),
+			old_path,
 		});
 	}
 
@@ -195,3 +260,237 @@ pub fn get_status(
 
 	Ok(res)
 }
+
+fn delta_path(delta: &DiffDelta, new: bool) -> Option<String> {
+	let file = if new {
+		delta.new_file()
+	} else {
+		delta.old_file()
+	};
+
+	file.path().and_then(Path::to_str).map(String::from)
+}
+
+/// libgit2 knows neither `core.fsmonitor` nor the untracked cache, with
+/// either of them `git status` only rescans what changed
+fn git_status_is_faster(repo: &Repository) -> bool {
+	let Ok(config) = repo.config() else {
+		return false;
+	};
+
+	let fsmonitor =
+		config.get_bool("core.fsmonitor").unwrap_or_else(|_| {
+			config
+				.get_string("core.fsmonitor")
+				.is_ok_and(|hook| !hook.is_empty())
+		});
+
+	fsmonitor
+		|| config.get_bool("core.untrackedCache").unwrap_or_default()
+}
+
+/// workdir status via `git status`, sorted like `get_status`
+fn workdir_status_from_git(
+	repo: &Repository,
+	show_untracked: ShowUntrackedFilesConfig,
+) -> Result<Vec<StatusItem>> {
+	scope_time!("workdir_status_from_git");
+
+	let untracked = match show_untracked {
+		ShowUntrackedFilesConfig::No => "no",
+		ShowUntrackedFilesConfig::Normal => "normal",
+		ShowUntrackedFilesConfig::All => "all",
+	};
+
+	// without taking `index.lock` to refresh the index, which would
+	// fail concurrent staging and wake up the watcher
+	let output = Command::new("git")
+		.args([
+			"--no-optional-locks",
+			"status",
+			"--porcelain=v2",
+			"-z",
+		])
+		.arg(format!("--untracked-files={untracked}"))
+		.current_dir(work_dir(repo)?)
+		.output()?;
+
+	if !output.status.success() {
+		return Err(Error::Generic(
+			String::from_utf8_lossy(&output.stderr)
+				.trim()
+				.to_string(),
+		));
+	}
+
+	let mut res = parse_porcelain_v2(&output.stdout);
+	res.sort_by(|a, b| {
+		Path::new(a.path.as_str()).cmp(Path::new(b.path.as_str()))
+	});
+
+	Ok(res)
+}
+
+/// worktree side of `git status --porcelain=v2 -z`
+fn parse_porcelain_v2(output: &[u8]) -> Vec<StatusItem> {
+	let mut entries = output
+		.split(|b| *b == 0)
+		.map(|entry| String::from_utf8_lossy(entry).into_owned());
+	let mut res = Vec::new();
+
+	while let Some(entry) = entries.next() {
+		let (kind, fields) = match entry.split_once(' ') {
+			Some((kind, fields)) => (kind, fields),
+			None => continue,
+		};
+
+		let mut old_path = None;
+		let (xy, path) = match kind {
+			"1" => (fields.get(..2), fields.splitn(8, ' ').nth(7)),
+			"2" => {
+				// followed by the path it got renamed from
+				old_path = entries.next();
+				(fields.get(..2), fields.splitn(9, ' ').nth(8))
+			}
+			"u" => (Some("UU"), fields.splitn(10, ' ').nth(9)),
+			"?" => (Some("??"), Some(fields)),
+			_ => continue,
+		};
+		let (Some(xy), Some(path)) = (xy, path) else {
+			continue;
+		};
+
+		let status = match (kind, xy.as_bytes().get(1)) {
+			("u", _) => StatusItemType::Conflicted,
+			("?", _) | (_, Some(b'A')) => StatusItemType::New,
+			(_, Some(b'M')) => StatusItemType::Modified,
+			(_, Some(b'D')) => StatusItemType::Deleted,
+			(_, Some(b'T')) => StatusItemType::Typechange,
+			(_, Some(b'R')) => StatusItemType::Renamed,
+			(_, Some(b'C')) => StatusItemType::Copied,
+			_ => continue,
+		};
+
+		res.push(StatusItem {
+			path: path.to_string(),
+			status,
+			old_path: old_path.filter(|_| {
+				matches!(
+					status,
+					StatusItemType::Renamed | StatusItemType::Copied
+				)
+			}),
+		});
+	}
+
+	res
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::tests::{repo_init, write_commit_file};
+	use std::fs;
+
+	#[test]
+	fn test_parse_porcelain_v2() {
+		let output = b"1 .M N... 100644 100644 100644 1111 1111 a b.txt\02 R. N... 100644 100644 100644 1111 1111 R100 new.txt\0old.txt\01 .D N... 100644 100644 000000 1111 1111 gone.txt\0u UU N... 100644 100644 100644 100644 1111 2222 3333 conflict.txt\0? dir/\0";
+
+		assert_eq!(
+			parse_porcelain_v2(output),
+			vec![
+				StatusItem {
+					path: String::from("a b.txt"),
+					status: StatusItemType::Modified,
+					old_path: None,
+				},
+				StatusItem {
+					path: String::from("gone.txt"),
+					status: StatusItemType::Deleted,
+					old_path: None,
+				},
+				StatusItem {
+					path: String::from("conflict.txt"),
+					status: StatusItemType::Conflicted,
+					old_path: None,
+				},
+				StatusItem {
+					path: String::from("dir/"),
+					status: StatusItemType::New,
+					old_path: None,
+				},
+			]
+		);
+	}
+
+	#[test]
+	fn test_status_with_untracked_cache() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "c1");
+		write_commit_file(&repo, "b.txt", "b", "c2");
+		fs::write(root.join("a.txt"), "changed").unwrap();
+		fs::remove_file(root.join("b.txt")).unwrap();
+		fs::create_dir(root.join("dir")).unwrap();
+		fs::write(root.join("dir/new.txt"), "new").unwrap();
+
+		let libgit2 = get_status(
+			repo_path,
+			StatusType::WorkingDir,
+			Some(ShowUntrackedFilesConfig::Normal),
+		)
+		.unwrap();
+
+		repo.config()
+			.unwrap()
+			.set_bool("core.untrackedCache", true)
+			.unwrap();
+		assert!(git_status_is_faster(&repo));
+
+		let git = get_status(
+			repo_path,
+			StatusType::WorkingDir,
+			Some(ShowUntrackedFilesConfig::Normal),
+		)
+		.unwrap();
+
+		assert_eq!(git, libgit2);
+		assert_eq!(git.len(), 3);
+	}
+
+	#[test]
+	fn test_git_status_keeps_index() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "c1");
+		repo.config()
+			.unwrap()
+			.set_bool("core.untrackedCache", true)
+			.unwrap();
+		// same content but new stat data, `git status` would refresh
+		// the index entry if it was allowed to
+		fs::remove_file(root.join("a.txt")).unwrap();
+		fs::write(root.join("a.txt"), "a").unwrap();
+		fs::write(root.join("new.txt"), "new").unwrap();
+
+		let index = repo.path().join("index");
+		let before = fs::read(&index).unwrap();
+
+		let status = get_status(
+			repo_path,
+			StatusType::WorkingDir,
+			Some(ShowUntrackedFilesConfig::Normal),
+		)
+		.unwrap();
+
+		assert_eq!(status.len(), 1);
+		assert_eq!(fs::read(&index).unwrap(), before);
+		assert!(!repo.path().join("index.lock").exists());
+	}
+}
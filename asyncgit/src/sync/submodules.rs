@@ -1,13 +1,25 @@
-use std::path::{Path, PathBuf};
+use std::{
+	collections::BTreeMap,
+	path::{Path, PathBuf},
+};
 
 use git2::{
-	Repository, RepositoryOpenFlags, Submodule,
+	build::CheckoutBuilder, FetchOptions, Repository,
+	RepositoryOpenFlags, ResetType, Submodule,
 	SubmoduleUpdateOptions,
 };
 use scopetime::scope_time;
 
-use super::{repo, CommitId, RepoPath};
-use crate::{error::Result, sync::utils::work_dir, Error};
+use super::{
+	remotes::repo_proxy,
+	repo,
+	status::{get_status, StatusType},
+	CommitId, RepoPath, ShowUntrackedFilesConfig,
+};
+use crate::{
+	error::Result, sync::utils::work_dir, Error, StatusItem,
+	StatusItemType,
+};
 
 pub use git2::SubmoduleStatus;
 
@@ -87,10 +99,117 @@ pub fn get_submodules(
 	Ok(res)
 }
 
-///
+/// how the checkout of a submodule differs from the commit recorded
+/// in the parent repo
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub struct SubmoduleState {
+	/// uncommitted changes or untracked files inside
+	pub dirty: bool,
+	/// commits checked out on top of the recorded one
+	pub ahead: usize,
+	/// commits the recorded one has on top of the checkout
+	pub behind: usize,
+}
+
+/// state of all checked out submodules differing from what the parent
+/// repo records, by path
+pub fn get_submodule_states(
+	repo_path: &RepoPath,
+) -> Result<BTreeMap<String, SubmoduleState>> {
+	scope_time!("get_submodule_states");
+
+	let repo = repo(repo_path)?;
+	let mut res = BTreeMap::new();
+
+	for submodule in repo.submodules()? {
+		let Ok(sub_repo) = submodule.open() else {
+			continue;
+		};
+
+		let status = repo
+			.submodule_status(
+				submodule.name().unwrap_or_default(),
+				git2::SubmoduleIgnore::None,
+			)
+			.unwrap_or(SubmoduleStatus::empty());
+
+		let dirty = status.intersects(
+			SubmoduleStatus::WD_INDEX_MODIFIED
+				| SubmoduleStatus::WD_WD_MODIFIED
+				| SubmoduleStatus::WD_UNTRACKED,
+		);
+
+		let (ahead, behind) =
+			match (submodule.workdir_id(), submodule.index_id()) {
+				(Some(checkout), Some(recorded))
+					if checkout != recorded =>
+				{
+					sub_repo
+						.graph_ahead_behind(checkout, recorded)
+						.unwrap_or_default()
+				}
+				_ => (0, 0),
+			};
+
+		let state = SubmoduleState {
+			dirty,
+			ahead,
+			behind,
+		};
+
+		if state != SubmoduleState::default() {
+			res.insert(
+				submodule.path().to_string_lossy().to_string(),
+				state,
+			);
+		}
+	}
+
+	Ok(res)
+}
+
+/// registers the submodule in `.git/config` (like `git submodule
+/// init`), needed before it can be updated
+pub fn init_submodule(
+	repo_path: &RepoPath,
+	name: &str,
+) -> Result<()> {
+	scope_time!("init_submodule");
+
+	let repo = repo(repo_path)?;
+
+	repo.find_submodule(name)?.init(false)?;
+
+	Ok(())
+}
+
+fn fetch_options<'a>(repo: &Repository) -> FetchOptions<'a> {
+	let mut options = FetchOptions::new();
+	options.proxy_options(repo_proxy(repo));
+	options
+}
+
+fn update_nested_submodules(repo: &Repository) -> Result<()> {
+	for mut submodule in repo.submodules()? {
+		let mut options = SubmoduleUpdateOptions::new();
+		options.allow_fetch(true);
+		options.fetch(fetch_options(repo));
+
+		submodule.update(true, Some(&mut options))?;
+
+		update_nested_submodules(&submodule.open()?)?;
+	}
+
+	Ok(())
+}
+
+/// checks out the commit recorded for the submodule, initializing and
+/// cloning it if needed. `recursive` does the same for all submodules
+/// nested inside (like `git submodule update --init --recursive`)
 pub fn update_submodule(
 	repo_path: &RepoPath,
 	name: &str,
+	recursive: bool,
 ) -> Result<()> {
 	scope_time!("update_submodule");
 
@@ -100,9 +219,128 @@ pub fn update_submodule(
 
 	let mut options = SubmoduleUpdateOptions::new();
 	options.allow_fetch(true);
+	options.fetch(fetch_options(&repo));
 
 	submodule.update(true, Some(&mut options))?;
 
+	if recursive {
+		update_nested_submodules(&submodule.open()?)?;
+	}
+
+	Ok(())
+}
+
+/// copies the url of the submodule from `.gitmodules` into the config
+/// of the parent repo and the `origin` of the submodule (like
+/// `git submodule sync`)
+pub fn sync_submodule(
+	repo_path: &RepoPath,
+	name: &str,
+) -> Result<()> {
+	scope_time!("sync_submodule");
+
+	let repo = repo(repo_path)?;
+
+	repo.find_submodule(name)?.sync()?;
+
+	Ok(())
+}
+
+/// repo path of the submodule checked out at `path`
+pub fn submodule_repo_path(
+	repo_path: &RepoPath,
+	path: &str,
+) -> Result<RepoPath> {
+	let repo = repo(repo_path)?;
+
+	Ok(RepoPath::Path(work_dir(&repo)?.join(path)))
+}
+
+/// replaces the entries of submodules with changes inside of their
+/// workdir by these changes (prefixed with the submodule path).
+///
+/// the changes are read from the submodule repo itself, returns the new list and the paths of the expanded submodules.
+pub fn expand_submodule_changes(
+	repo_path: &RepoPath,
+	items: Vec<StatusItem>,
+	show_untracked: Option<ShowUntrackedFilesConfig>,
+) -> Result<(Vec<StatusItem>, Vec<String>)> {
+	scope_time!("expand_submodule_changes");
+
+	let repo = repo(repo_path)?;
+	let submodules = repo.submodules()?;
+
+	if submodules.is_empty() {
+		return Ok((items, Vec::new()));
+	}
+
+	let work_dir = work_dir(&repo)?;
+
+	let mut res = Vec::with_capacity(items.len());
+	let mut expanded = Vec::new();
+
+	for item in items {
+		let is_submodule = item.status == StatusItemType::Modified
+			&& submodules
+				.iter()
+				.any(|s| s.path() == Path::new(&item.path));
+
+		if is_submodule {
+			let sub_path = RepoPath::Path(work_dir.join(&item.path));
+
+			// not checked out or otherwise unreadable: keep the entry
+			if let Ok(changes) = get_status(
+				&sub_path,
+				StatusType::Both,
+				show_untracked,
+			) {
+				if !changes.is_empty() {
+					res.extend(changes.into_iter().map(|change| {
+						StatusItem {
+							path: format!(
+								"{}/{}",
+								item.path, change.path
+							),
+							status: change.status,
+							old_path: change.old_path.map(|old| {
+								format!("{}/{old}", item.path)
+							}),
+						}
+					}));
+					expanded.push(item.path);
+					continue;
+				}
+			}
+		}
+
+		res.push(item);
+	}
+
+	Ok((res, expanded))
+}
+
+/// discards all changes inside of the submodule at `path`.
+///
+/// this includes untracked files, the commit checked out in the
+/// submodule stays untouched
+pub fn discard_submodule_changes(
+	repo_path: &RepoPath,
+	path: &str,
+) -> Result<()> {
+	scope_time!("discard_submodule_changes");
+
+	let repo = repo(repo_path)?;
+	let sub_repo = repo.find_submodule(path)?.open()?;
+
+	let head = sub_repo.head()?.peel_to_commit()?;
+
+	sub_repo.reset(head.as_object(), ResetType::Hard, None)?;
+
+	// the reset leaves untracked files behind
+	let mut checkout = CheckoutBuilder::new();
+	checkout.force().remove_untracked(true);
+	sub_repo.checkout_head(Some(&mut checkout))?;
+
 	Ok(())
 }
 
@@ -155,13 +393,22 @@ pub fn submodule_parent_info(
 
 #[cfg(test)]
 mod tests {
-	use super::get_submodules;
-	use crate::sync::{
-		submodules::submodule_parent_info, tests::repo_init, RepoPath,
+	use super::{
+		discard_submodule_changes, expand_submodule_changes,
+		get_submodule_states, get_submodules, SubmoduleState,
+	};
+	use crate::{
+		sync::{
+			status::{get_status, StatusType},
+			submodules::submodule_parent_info,
+			tests::{repo_init, write_commit_file},
+			RepoPath,
+		},
+		StatusItem, StatusItemType,
 	};
 	use git2::Repository;
 	use pretty_assertions::assert_eq;
-	use std::path::Path;
+	use std::{fs, path::Path};
 
 	#[test]
 	fn test_smoke() {
@@ -198,4 +445,113 @@ mod tests {
 
 		assert_eq!(&info.submodule_info.name, "foo/bar");
 	}
+
+	#[test]
+	fn test_submodule_changes() {
+		let (sub_td, sub_repo) = repo_init().unwrap();
+		write_commit_file(&sub_repo, "a.txt", "a", "a");
+
+		let (td, repo) = repo_init().unwrap();
+		{
+			let mut s = repo
+				.submodule(
+					sub_td.path().to_str().unwrap(),
+					Path::new("sub"),
+					true,
+				)
+				.unwrap();
+			s.clone(None).unwrap();
+			s.add_finalize().unwrap();
+		}
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		fs::write(root.join("sub/a.txt"), "b").unwrap();
+		fs::write(root.join("sub/new.txt"), "new").unwrap();
+		fs::write(root.join("foo.txt"), "foo").unwrap();
+
+		let items =
+			get_status(repo_path, StatusType::WorkingDir, None)
+				.unwrap();
+		let (items, expanded) =
+			expand_submodule_changes(repo_path, items, None).unwrap();
+
+		assert_eq!(expanded, vec![String::from("sub")]);
+		assert_eq!(
+			items,
+			vec![
+				StatusItem {
+					path: String::from("foo.txt"),
+					status: StatusItemType::New,
+					old_path: None,
+				},
+				StatusItem {
+					path: String::from("sub/a.txt"),
+					status: StatusItemType::Modified,
+					old_path: None,
+				},
+				StatusItem {
+					path: String::from("sub/new.txt"),
+					status: StatusItemType::New,
+					old_path: None,
+				},
+			]
+		);
+
+		discard_submodule_changes(repo_path, "sub").unwrap();
+
+		assert_eq!(
+			fs::read_to_string(root.join("sub/a.txt")).unwrap(),
+			"a"
+		);
+		assert!(!root.join("sub/new.txt").exists());
+
+		let items =
+			get_status(repo_path, StatusType::WorkingDir, None)
+				.unwrap();
+		let (_, expanded) =
+			expand_submodule_changes(repo_path, items, None).unwrap();
+		assert!(expanded.is_empty());
+
+		drop(td);
+	}
+
+	#[test]
+	fn test_submodule_states() {
+		let (sub_td, sub_repo) = repo_init().unwrap();
+		write_commit_file(&sub_repo, "a.txt", "a", "a");
+
+		let (_td, repo) = repo_init().unwrap();
+		{
+			let mut s = repo
+				.submodule(
+					sub_td.path().to_str().unwrap(),
+					Path::new("sub"),
+					true,
+				)
+				.unwrap();
+			s.clone(None).unwrap();
+			s.add_finalize().unwrap();
+		}
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		assert!(get_submodule_states(repo_path).unwrap().is_empty());
+
+		let checkout = Repository::open(root.join("sub")).unwrap();
+		write_commit_file(&checkout, "b.txt", "b", "b");
+		fs::write(root.join("sub/a.txt"), "changed").unwrap();
+
+		let states = get_submodule_states(repo_path).unwrap();
+		assert_eq!(
+			states.get("sub"),
+			Some(&SubmoduleState {
+				dirty: true,
+				ahead: 1,
+				behind: 0,
+			})
+		);
+	}
 }
@@ -0,0 +1,246 @@
+//! symbolic refs like `HEAD` or `refs/remotes/origin/HEAD`
+
+use super::{repository::repo, RepoPath};
+use crate::error::{Error, Result};
+use git2::ReferenceType;
+use scopetime::scope_time;
+
+const HEAD: &str = "HEAD";
+
+/// a symbolic ref and the ref it points to
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SymRef {
+	/// full name of the symbolic ref
+	pub name: String,
+	/// full name of the ref it points to
+	pub target: String,
+}
+
+/// returns `HEAD` followed by all other symbolic refs of the repo
+pub fn get_symrefs(repo_path: &RepoPath) -> Result<Vec<SymRef>> {
+	scope_time!("get_symrefs");
+
+	let repo = repo(repo_path)?;
+
+	let mut res = Vec::new();
+
+	for reference in repo.references()? {
+		let reference = reference?;
+
+		if reference.kind() != Some(ReferenceType::Symbolic) {
+			continue;
+		}
+
+		if let (Some(name), Some(target)) =
+			(reference.name(), reference.symbolic_target())
+		{
+			res.push(SymRef {
+				name: name.to_string(),
+				target: target.to_string(),
+			});
+		}
+	}
+
+	res.sort_by(|a, b| a.name.cmp(&b.name));
+
+	// `HEAD` is not part of the reference iterator
+	let head = repo.find_reference(HEAD)?;
+	if let Some(target) = head.symbolic_target() {
+		res.insert(
+			0,
+			SymRef {
+				name: HEAD.to_string(),
+				target: target.to_string(),
+			},
+		);
+	}
+
+	Ok(res)
+}
+
+/// refs `name` can be pointed at: local branches for `HEAD`,
+/// siblings in the same namespace for all other symbolic refs
+pub fn get_symref_targets(
+	repo_path: &RepoPath,
+	name: &str,
+) -> Result<Vec<String>> {
+	scope_time!("get_symref_targets");
+
+	let repo = repo(repo_path)?;
+
+	let namespace = if name == HEAD {
+		"refs/heads/"
+	} else {
+		name.rfind('/').map_or("refs/", |idx| &name[..=idx])
+	};
+
+	let mut res = Vec::new();
+
+	for reference in repo.references_glob(&format!("{namespace}*"))? {
+		let reference = reference?;
+
+		if reference.kind() != Some(ReferenceType::Direct) {
+			continue;
+		}
+
+		if let Some(target) = reference.name() {
+			res.push(target.to_string());
+		}
+	}
+
+	res.sort();
+
+	Ok(res)
+}
+
+/// points symbolic ref `name` at `target`.
+///
+/// `HEAD` can only be changed in bare repos, where it defines the
+/// default branch; in a worktree use a checkout instead.
+pub fn set_symref(
+	repo_path: &RepoPath,
+	name: &str,
+	target: &str,
+) -> Result<()> {
+	scope_time!("set_symref");
+
+	let repo = repo(repo_path)?;
+
+	repo.find_reference(target)?;
+
+	if name == HEAD {
+		if !repo.is_bare() {
+			return Err(Error::Generic(String::from(
+				"HEAD of a non-bare repository can only be changed by a checkout",
+			)));
+		}
+
+		repo.set_head(target)?;
+	} else {
+		repo.reference_symbolic(
+			name,
+			target,
+			true,
+			&format!("symref: {name} -> {target}"),
+		)?;
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		create_branch,
+		tests::{repo_init, repo_init_bare},
+	};
+
+	#[test]
+	fn test_head_in_worktree() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		create_branch(repo_path, "other").unwrap();
+
+		let symrefs = get_symrefs(repo_path).unwrap();
+		assert_eq!(
+			symrefs,
+			vec![SymRef {
+				name: String::from("HEAD"),
+				target: String::from("refs/heads/other"),
+			}]
+		);
+
+		assert_eq!(
+			get_symref_targets(repo_path, "HEAD").unwrap(),
+			vec!["refs/heads/master", "refs/heads/other"]
+		);
+
+		assert!(set_symref(repo_path, "HEAD", "refs/heads/master")
+			.is_err());
+	}
+
+	#[test]
+	fn test_default_branch_of_bare_repo() {
+		let (_td, repo) = repo_init().unwrap();
+		let (bare_dir, _bare) = repo_init_bare().unwrap();
+		let bare_path: &RepoPath =
+			&bare_dir.path().to_str().unwrap().into();
+
+		repo.remote("origin", bare_dir.path().to_str().unwrap())
+			.unwrap()
+			.push(
+				&[
+					"refs/heads/master:refs/heads/master",
+					"refs/heads/master:refs/heads/develop",
+				],
+				None,
+			)
+			.unwrap();
+
+		set_symref(bare_path, "HEAD", "refs/heads/develop").unwrap();
+
+		assert_eq!(
+			get_symrefs(bare_path).unwrap()[0].target,
+			"refs/heads/develop"
+		);
+
+		assert!(set_symref(bare_path, "HEAD", "refs/heads/missing")
+			.is_err());
+	}
+
+	#[test]
+	fn test_remote_head() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let head = repo.head().unwrap().target().unwrap();
+		repo.reference("refs/remotes/origin/master", head, false, "")
+			.unwrap();
+		repo.reference(
+			"refs/remotes/origin/develop",
+			head,
+			false,
+			"",
+		)
+		.unwrap();
+		repo.reference_symbolic(
+			"refs/remotes/origin/HEAD",
+			"refs/remotes/origin/master",
+			false,
+			"",
+		)
+		.unwrap();
+
+		let symrefs = get_symrefs(repo_path).unwrap();
+		assert_eq!(symrefs.len(), 2);
+		assert_eq!(symrefs[0].name, "HEAD");
+		assert_eq!(symrefs[1].name, "refs/remotes/origin/HEAD");
+
+		assert_eq!(
+			get_symref_targets(repo_path, "refs/remotes/origin/HEAD")
+				.unwrap(),
+			vec![
+				"refs/remotes/origin/develop",
+				"refs/remotes/origin/master"
+			]
+		);
+
+		set_symref(
+			repo_path,
+			"refs/remotes/origin/HEAD",
+			"refs/remotes/origin/develop",
+		)
+		.unwrap();
+
+		assert_eq!(
+			get_symrefs(repo_path).unwrap()[1].target,
+			"refs/remotes/origin/develop"
+		);
+	}
+}
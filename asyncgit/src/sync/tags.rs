@@ -49,8 +49,41 @@ pub struct TagWithMetadata {
 	pub annotation: Option<String>,
 }
 
+/// signature found in the message of an annotated tag
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TagSignature {
+	/// no signature block attached
+	Unsigned,
+	/// `OpenPGP` signature block attached (not verified)
+	Gpg,
+	/// `SSH` signature block attached (not verified)
+	Ssh,
+}
+
+/// details of an annotated tag object
+#[derive(Clone, Debug)]
+pub struct TagDetails {
+	///
+	pub name: String,
+	///
+	pub tagger_name: String,
+	///
+	pub tagger_email: String,
+	/// time the tag was created
+	pub time: i64,
+	/// full message without the signature block
+	pub message: String,
+	///
+	pub signature: TagSignature,
+}
+
 static MAX_MESSAGE_WIDTH: usize = 100;
 
+static SIGNATURE_BLOCKS: [(&str, TagSignature); 2] = [
+	("-----BEGIN PGP SIGNATURE-----", TagSignature::Gpg),
+	("-----BEGIN SSH SIGNATURE-----", TagSignature::Ssh),
+];
+
 /// returns `Tags` type filled with all tags found in repo
 pub fn get_tags(repo_path: &RepoPath) -> Result<Tags> {
 	scope_time!("get_tags");
@@ -174,6 +207,62 @@ pub fn get_tags_with_metadata(
 	Ok(tags)
 }
 
+/// splits a trailing signature block off a tag message
+fn split_signature(message: &str) -> (&str, TagSignature) {
+	SIGNATURE_BLOCKS
+		.iter()
+		.find_map(|(header, kind)| {
+			message.find(header).map(|idx| (&message[..idx], *kind))
+		})
+		.unwrap_or((message, TagSignature::Unsigned))
+}
+
+/// resolves the tag object behind `refs/tags/<name>`,
+/// returns `None` for lightweight tags
+pub fn get_tag_details(
+	repo_path: &RepoPath,
+	name: &str,
+) -> Result<Option<TagDetails>> {
+	scope_time!("get_tag_details");
+
+	let repo = repo(repo_path)?;
+
+	let reference =
+		repo.find_reference(&format!("refs/tags/{name}"))?;
+	let Ok(tag) = reference.peel_to_tag() else {
+		return Ok(None);
+	};
+
+	let message = tag
+		.message_bytes()
+		.map(bytes2string)
+		.transpose()?
+		.unwrap_or_default();
+	let (message, signature) = split_signature(&message);
+
+	let (tagger_name, tagger_email, time) = tag.tagger().map_or_else(
+		|| (String::new(), String::new(), 0),
+		|tagger| {
+			(
+				String::from_utf8_lossy(tagger.name_bytes())
+					.into_owned(),
+				String::from_utf8_lossy(tagger.email_bytes())
+					.into_owned(),
+				tagger.when().seconds(),
+			)
+		},
+	);
+
+	Ok(Some(TagDetails {
+		name: name.to_string(),
+		tagger_name,
+		tagger_email,
+		time,
+		message: message.trim_end().to_string(),
+		signature,
+	}))
+}
+
 ///
 pub fn delete_tag(
 	repo_path: &RepoPath,
@@ -251,4 +340,60 @@ mod tests {
 
 		assert_eq!(tags.len(), 0);
 	}
+
+	#[test]
+	fn test_tag_details() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let sig = repo.signature().unwrap();
+		let target = repo
+			.find_object(
+				repo.head().unwrap().target().unwrap(),
+				Some(ObjectType::Commit),
+			)
+			.unwrap();
+
+		repo.tag_lightweight("light", &target, false).unwrap();
+		repo.tag(
+			"annotated",
+			&target,
+			&sig,
+			"subject\n\nbody line 1\nbody line 2\n",
+			false,
+		)
+		.unwrap();
+		repo.tag(
+			"signed",
+			&target,
+			&sig,
+			"signed tag\n-----BEGIN PGP SIGNATURE-----\n\nabc\n-----END PGP SIGNATURE-----\n",
+			false,
+		)
+		.unwrap();
+
+		assert!(get_tag_details(repo_path, "light")
+			.unwrap()
+			.is_none());
+
+		let details =
+			get_tag_details(repo_path, "annotated").unwrap().unwrap();
+		assert_eq!(details.name, "annotated");
+		assert_eq!(details.tagger_name, "name");
+		assert_eq!(details.tagger_email, "email");
+		assert_eq!(
+			details.message,
+			"subject\n\nbody line 1\nbody line 2"
+		);
+		assert_eq!(details.signature, TagSignature::Unsigned);
+
+		let details =
+			get_tag_details(repo_path, "signed").unwrap().unwrap();
+		assert_eq!(details.message, "signed tag");
+		assert_eq!(details.signature, TagSignature::Gpg);
+
+		assert!(get_tag_details(repo_path, "missing").is_err());
+	}
 }
@@ -0,0 +1,220 @@
+//! textconv diff drivers: `.gitattributes` assigning `diff=<driver>`
+//! to a path and `diff.<driver>.textconv` naming a command that turns
+//! the content into text, so documents or images diff like they do in
+//! `git diff` instead of showing up as binary
+
+use crate::error::{Error, Result};
+use git2::{AttrCheckFlags, AttrValue, Diff, DiffFile, Repository};
+use std::{
+	fs,
+	path::{Path, PathBuf},
+	process::Command,
+	sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// keeps the temp files of diffs loaded in parallel apart
+static TEMP_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// both sides of a single file diff run through its textconv driver
+pub(crate) struct TextconvDiff {
+	/// name of the `diff` driver
+	pub driver: String,
+	///
+	pub path: PathBuf,
+	///
+	pub old: Vec<u8>,
+	///
+	pub new: Vec<u8>,
+	/// sizes of the unconverted content
+	pub sizes: (u64, u64),
+}
+
+/// driver name and textconv command configured for `path`
+fn textconv_driver(
+	repo: &Repository,
+	path: &Path,
+) -> Option<(String, String)> {
+	let attr = repo
+		.get_attr(path, "diff", AttrCheckFlags::FILE_THEN_INDEX)
+		.ok()?;
+
+	let AttrValue::String(driver) = AttrValue::from_string(attr)
+	else {
+		return None;
+	};
+
+	let command = repo
+		.config()
+		.ok()?
+		.get_string(&format!("diff.{driver}.textconv"))
+		.ok()
+		.filter(|command| !command.trim().is_empty())?;
+
+	Some((driver.to_string(), command))
+}
+
+/// content of one side from the odb, or from the workdir for the
+/// side not written to the odb yet
+fn side_content(
+	repo: &Repository,
+	work_dir: &Path,
+	file: &DiffFile,
+) -> Result<Vec<u8>> {
+	if !file.id().is_zero() {
+		if let Ok(blob) = repo.find_blob(file.id()) {
+			return Ok(blob.content().to_vec());
+		}
+	}
+
+	let path = file.path().ok_or_else(|| {
+		Error::Generic("diff file path is unspecified.".to_string())
+	})?;
+
+	Ok(fs::read(work_dir.join(path))?)
+}
+
+/// runs `command` on `content` written to a temp file, like git the
+/// command gets the file name appended and has to print the text
+fn run_textconv(
+	command: &str,
+	work_dir: &Path,
+	path: &Path,
+	content: &[u8],
+) -> Result<Vec<u8>> {
+	let mut name = format!(
+		"gitui-textconv-{}-{}",
+		std::process::id(),
+		TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+	);
+	// some converters pick the format by extension
+	if let Some(extension) = path.extension() {
+		name.push('.');
+		name.push_str(&extension.to_string_lossy());
+	}
+	let temp_file = std::env::temp_dir().join(name);
+
+	fs::write(&temp_file, content)?;
+
+	let mut cmd = if cfg!(target_os = "windows") {
+		let mut cmd = Command::new("cmd");
+		cmd.arg("/C")
+			.arg(format!("{command} \"{}\"", temp_file.display()));
+		cmd
+	} else {
+		let mut cmd = Command::new("sh");
+		cmd.arg("-c")
+			.arg(format!("{command} \"$1\""))
+			.arg(command)
+			.arg(&temp_file);
+		cmd
+	};
+
+	let output = cmd.current_dir(work_dir).output();
+
+	fs::remove_file(&temp_file).ok();
+
+	let output = output.map_err(|e| {
+		Error::Generic(format!("textconv `{command}` failed: {e}"))
+	})?;
+
+	if output.status.success() {
+		return Ok(output.stdout);
+	}
+
+	Err(Error::Generic(format!(
+		"textconv `{command}` failed: {}",
+		String::from_utf8_lossy(&output.stderr).trim()
+	)))
+}
+
+/// converted content of both sides if `diff` is about a single file
+/// with a textconv driver configured
+pub(crate) fn textconv_diff(
+	repo: &Repository,
+	work_dir: &Path,
+	diff: &Diff,
+) -> Result<Option<TextconvDiff>> {
+	if diff.deltas().len() != 1 {
+		return Ok(None);
+	}
+	let Some(delta) = diff.deltas().next() else {
+		return Ok(None);
+	};
+	let Some(path) =
+		delta.new_file().path().or_else(|| delta.old_file().path())
+	else {
+		return Ok(None);
+	};
+	let Some((driver, command)) = textconv_driver(repo, path) else {
+		return Ok(None);
+	};
+
+	let convert = |file: &DiffFile| -> Result<Vec<u8>> {
+		if !file.exists() {
+			return Ok(Vec::new());
+		}
+
+		let content = side_content(repo, work_dir, file)?;
+
+		run_textconv(&command, work_dir, path, &content)
+	};
+
+	Ok(Some(TextconvDiff {
+		driver,
+		path: path.to_path_buf(),
+		old: convert(&delta.old_file())?,
+		new: convert(&delta.new_file())?,
+		sizes: (delta.old_file().size(), delta.new_file().size()),
+	}))
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::sync::{
+		diff::{get_diff, DiffLineType},
+		tests::{repo_init, write_commit_file},
+		RepoPath,
+	};
+	use std::fs;
+
+	#[test]
+	fn test_textconv_diff() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		fs::write(root.join(".gitattributes"), "*.bin diff=upper\n")
+			.unwrap();
+		repo.config()
+			.unwrap()
+			.set_str("diff.upper.textconv", "tr a-z A-Z <")
+			.unwrap();
+
+		write_commit_file(&repo, "a.bin", "abc\0\n", "c1");
+		fs::write(root.join("a.bin"), "xyz\0\n").unwrap();
+
+		let diff = get_diff(repo_path, "a.bin", false, None).unwrap();
+		let lines = diff
+			.hunks
+			.iter()
+			.flat_map(|hunk| hunk.lines.iter())
+			.filter(|line| line.line_type != DiffLineType::Header)
+			.map(|line| (line.line_type, line.content.to_string()))
+			.collect::<Vec<_>>();
+
+		assert_eq!(diff.textconv.as_deref(), Some("upper"));
+		assert_eq!(diff.sizes, (5, 5));
+		assert_eq!(
+			lines,
+			vec![
+				(DiffLineType::Delete, String::from("ABC\0")),
+				(DiffLineType::Add, String::from("XYZ\0")),
+			]
+		);
+
+		let diff = get_diff(repo_path, ".gitattributes", false, None)
+			.unwrap();
+		assert_eq!(diff.textconv, None);
+	}
+}
@@ -1,4 +1,4 @@
-use super::{CommitId, RepoPath};
+use super::{CommitId, LogWalker, RepoPath};
 use crate::{
 	error::{Error, Result},
 	sync::repository::repo,
@@ -91,6 +91,78 @@ pub fn tree_file_content(
 	Ok(content)
 }
 
+/// `path` (relative to the repo root) in the tree of `commit`,
+/// `None` if it does not exist there
+pub fn tree_file(
+	repo_path: &RepoPath,
+	commit: CommitId,
+	path: &str,
+) -> Result<Option<TreeFile>> {
+	scope_time!("tree_file");
+
+	let repo = repo(repo_path)?;
+
+	let tree = repo.find_commit(commit.into())?.tree()?;
+
+	let Ok(entry) = tree.get_path(Path::new(path)) else {
+		return Ok(None);
+	};
+
+	if entry.kind() != Some(git2::ObjectType::Blob) {
+		return Ok(None);
+	}
+
+	Ok(Some(TreeFile {
+		path: PathBuf::from("./").join(path),
+		filemode: entry.filemode(),
+		id: entry.id(),
+	}))
+}
+
+/// versions of a file in the history of `HEAD`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct FileRevisions {
+	/// commits changing the file (newest first) that still contain it
+	pub commits: Vec<CommitId>,
+	/// index into `commits` of the version `at` was looked up with
+	pub current: Option<usize>,
+}
+
+/// like `git log HEAD -- <path>` but without commits deleting `path`,
+/// `current` is the first revision `at` equals or descends from
+pub fn file_revisions(
+	repo_path: &RepoPath,
+	path: &str,
+	at: CommitId,
+) -> Result<FileRevisions> {
+	scope_time!("file_revisions");
+
+	let repo = repo(repo_path)?;
+
+	let mut commits = Vec::<CommitId>::new();
+	LogWalker::new(&repo, usize::MAX)?
+		.pathspec(Some(path.to_string()))
+		.read(&mut commits)?;
+
+	commits.retain(|id| {
+		repo.find_commit((*id).into())
+			.and_then(|c| c.tree())
+			.is_ok_and(|tree| tree.get_path(Path::new(path)).is_ok())
+	});
+
+	let mut current = None;
+	for (idx, id) in commits.iter().enumerate() {
+		if *id == at
+			|| repo.graph_descendant_of(at.into(), (*id).into())?
+		{
+			current = Some(idx);
+			break;
+		}
+	}
+
+	Ok(FileRevisions { commits, current })
+}
+
 ///
 fn tree_recurse(
 	repo: &Repository,
@@ -154,6 +226,39 @@ mod tests {
 		assert_ne!(files_c2[0], files[0]);
 	}
 
+	#[test]
+	fn test_file_revisions() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 = write_commit_file(&repo, "test.txt", "v1", "c1");
+		let c2 = write_commit_file(&repo, "other.txt", "x", "c2");
+		let c3 = write_commit_file(&repo, "test.txt", "v2", "c3");
+
+		let revisions =
+			file_revisions(repo_path, "test.txt", c3).unwrap();
+		assert_eq!(revisions.commits, vec![c3, c1]);
+		assert_eq!(revisions.current, Some(0));
+
+		let revisions =
+			file_revisions(repo_path, "test.txt", c2).unwrap();
+		assert_eq!(revisions.current, Some(1));
+
+		let file =
+			tree_file(repo_path, c1, "test.txt").unwrap().unwrap();
+		assert_eq!(
+			tree_file_content(repo_path, &file).unwrap(),
+			"v1"
+		);
+		assert_eq!(file.path, PathBuf::from("./test.txt"));
+
+		assert!(tree_file(repo_path, c1, "other.txt")
+			.unwrap()
+			.is_none());
+	}
+
 	#[test]
 	fn test_sorting() {
 		let mut list = ["file", "folder/file", "folder/afile"]
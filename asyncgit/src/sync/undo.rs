@@ -0,0 +1,392 @@
+//! snapshots of `HEAD`, the local branches, the index and the
+//! working tree taken before destructive operations so they can be
+//! undone. every snapshot is a commit recorded in the reflog of
+//! `refs/gitui/undo`, laid out like a stash: its tree is the working
+//! tree and its parent a commit of the index on top of `HEAD`
+
+use super::{
+	commit::signature_allow_undefined_name, repository::repo,
+	CommitId, RepoPath,
+};
+use crate::error::{Error, Result};
+use git2::{
+	build::CheckoutBuilder, BranchType, IndexAddOption, Oid,
+	Repository, RepositoryState,
+};
+use scopetime::scope_time;
+
+const UNDO_REF: &str = "refs/gitui/undo";
+const MAX_SNAPSHOTS: usize = 50;
+
+/// state recorded before an operation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UndoSnapshot {
+	///
+	pub id: CommitId,
+	/// description of the operation that followed the snapshot
+	pub operation: String,
+	///
+	pub time: i64,
+}
+
+/// records the current state before running `operation`, only the
+/// last `MAX_SNAPSHOTS` snapshots are kept
+pub fn undo_snapshot(
+	repo_path: &RepoPath,
+	operation: &str,
+) -> Result<CommitId> {
+	scope_time!("undo_snapshot");
+
+	let repo = repo(repo_path)?;
+
+	let id = create_snapshot(&repo, operation)?;
+
+	repo.reference_ensure_log(UNDO_REF)?;
+	repo.reference(UNDO_REF, id, true, operation)?;
+
+	let mut reflog = repo.reflog(UNDO_REF)?;
+	while reflog.len() > MAX_SNAPSHOTS {
+		reflog.remove(reflog.len() - 1, false)?;
+	}
+	reflog.write()?;
+
+	Ok(id.into())
+}
+
+/// recorded snapshots, newest first
+pub fn undo_snapshots(
+	repo_path: &RepoPath,
+) -> Result<Vec<UndoSnapshot>> {
+	scope_time!("undo_snapshots");
+
+	let repo = repo(repo_path)?;
+
+	if repo.find_reference(UNDO_REF).is_err() {
+		return Ok(Vec::new());
+	}
+
+	Ok(repo
+		.reflog(UNDO_REF)?
+		.iter()
+		.map(|entry| UndoSnapshot {
+			id: entry.id_new().into(),
+			operation: entry
+				.message()
+				.unwrap_or_default()
+				.to_string(),
+			time: entry.committer().when().seconds(),
+		})
+		.collect())
+}
+
+/// brings `HEAD`, the local branches, the index and the working tree
+/// back to the state of `snapshot`. the current state is recorded as
+/// a snapshot first, so this can be undone as well.
+///
+/// branches created after the snapshot and untracked files not part
+/// of it are left alone.
+pub fn undo_restore(
+	repo_path: &RepoPath,
+	snapshot: CommitId,
+) -> Result<()> {
+	scope_time!("undo_restore");
+
+	let repo = repo(repo_path)?;
+
+	if repo.state() != RepositoryState::Clean {
+		return Err(Error::Generic(String::from(
+			"cannot undo while repo is not in a clean state",
+		)));
+	}
+
+	let commit = repo.find_commit(snapshot.into())?;
+	let state =
+		SnapshotState::parse(commit.message().unwrap_or_default())?;
+	let index_commit = commit.parent(0)?;
+
+	undo_snapshot(
+		repo_path,
+		&format!("undo: {}", commit.summary().unwrap_or_default()),
+	)?;
+
+	for (name, id) in &state.branches {
+		repo.reference(name, *id, true, "gitui: undo")?;
+	}
+
+	match &state.head {
+		Head::Branch(name) => repo.set_head(name)?,
+		Head::Detached(id) => repo.set_head_detached(*id)?,
+	}
+
+	repo.checkout_tree(
+		commit.as_object(),
+		Some(CheckoutBuilder::new().force()),
+	)?;
+
+	let mut index = repo.index()?;
+	index.read_tree(&index_commit.tree()?)?;
+	index.write()?;
+
+	Ok(())
+}
+
+enum Head {
+	Branch(String),
+	Detached(Oid),
+}
+
+struct SnapshotState {
+	head: Head,
+	branches: Vec<(String, Oid)>,
+}
+
+impl SnapshotState {
+	fn read(repo: &Repository) -> Result<Self> {
+		let head = repo.find_reference("HEAD")?;
+		let head =
+			match head.symbolic_target() {
+				Some(name) => Head::Branch(name.to_string()),
+				None => Head::Detached(head.target().ok_or_else(
+					|| Error::Generic(String::from("invalid HEAD")),
+				)?),
+			};
+
+		let mut branches = Vec::new();
+		for branch in repo.branches(Some(BranchType::Local))? {
+			let reference = branch?.0.into_reference();
+			if let (Some(name), Some(id)) =
+				(reference.name(), reference.target())
+			{
+				branches.push((name.to_string(), id));
+			}
+		}
+
+		Ok(Self { head, branches })
+	}
+
+	/// message body listing `HEAD` and the branches, one per line
+	fn to_message(&self, operation: &str) -> String {
+		let mut msg = format!("{operation}\n\n");
+
+		match &self.head {
+			Head::Branch(name) => {
+				msg.push_str("HEAD ");
+				msg.push_str(name);
+			}
+			Head::Detached(id) => {
+				msg.push_str("HEAD ");
+				msg.push_str(&id.to_string());
+			}
+		}
+		msg.push('\n');
+
+		for (name, id) in &self.branches {
+			msg.push_str(name);
+			msg.push(' ');
+			msg.push_str(&id.to_string());
+			msg.push('\n');
+		}
+
+		msg
+	}
+
+	fn parse(msg: &str) -> Result<Self> {
+		let invalid =
+			|| Error::Generic(String::from("invalid undo snapshot"));
+
+		let mut head = None;
+		let mut branches = Vec::new();
+
+		for line in msg.lines().skip(2) {
+			let (name, target) =
+				line.split_once(' ').ok_or_else(invalid)?;
+
+			if name == "HEAD" {
+				head = Some(Oid::from_str(target).map_or_else(
+					|_| Head::Branch(target.to_string()),
+					Head::Detached,
+				));
+			} else {
+				branches.push((
+					name.to_string(),
+					Oid::from_str(target).map_err(|_| invalid())?,
+				));
+			}
+		}
+
+		Ok(Self {
+			head: head.ok_or_else(invalid)?,
+			branches,
+		})
+	}
+}
+
+fn create_snapshot(
+	repo: &Repository,
+	operation: &str,
+) -> Result<Oid> {
+	let state = SnapshotState::read(repo)?;
+	let signature = signature_allow_undefined_name(repo)?;
+
+	let head =
+		repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+	let parents = head.iter().collect::<Vec<_>>();
+
+	let mut index = repo.index()?;
+	let index_tree = repo.find_tree(index.write_tree()?)?;
+	let index_commit = repo.find_commit(repo.commit(
+		None,
+		&signature,
+		&signature,
+		&format!("index of {operation}"),
+		&index_tree,
+		&parents,
+	)?)?;
+
+	// only in memory, the index on disk stays untouched
+	index.add_all(["*"], IndexAddOption::DEFAULT, None)?;
+	index.update_all(["*"], None)?;
+	let workdir_tree = repo.find_tree(index.write_tree()?)?;
+	index.read(true)?;
+
+	Ok(repo.commit(
+		None,
+		&signature,
+		&signature,
+		&state.to_message(operation),
+		&workdir_tree,
+		&[&index_commit],
+	)?)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::{
+		delete_branch, reset_to, reset_workdir, stage_add_file,
+		tests::{repo_init, write_commit_file},
+		ResetType,
+	};
+	use std::{fs, path::Path};
+
+	#[test]
+	fn test_undo_reset() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 = write_commit_file(&repo, "a.txt", "a", "c1");
+		let c2 = write_commit_file(&repo, "a.txt", "b", "c2");
+
+		undo_snapshot(repo_path, "reset").unwrap();
+		reset_to(repo_path, c1, ResetType::Hard).unwrap();
+
+		let snapshots = undo_snapshots(repo_path).unwrap();
+		assert_eq!(snapshots.len(), 1);
+		assert_eq!(snapshots[0].operation, "reset");
+
+		undo_restore(repo_path, snapshots[0].id).unwrap();
+
+		assert_eq!(repo.head().unwrap().target().unwrap(), c2.into());
+		assert_eq!(
+			fs::read_to_string(root.join("a.txt")).unwrap(),
+			"b"
+		);
+
+		let snapshots = undo_snapshots(repo_path).unwrap();
+		assert_eq!(snapshots.len(), 2);
+		assert_eq!(snapshots[0].operation, "undo: reset");
+	}
+
+	#[test]
+	fn test_undo_discard() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		write_commit_file(&repo, "a.txt", "a", "c1");
+		fs::write(root.join("a.txt"), "staged").unwrap();
+		stage_add_file(repo_path, Path::new("a.txt")).unwrap();
+		fs::write(root.join("a.txt"), "unstaged").unwrap();
+		fs::write(root.join("new.txt"), "new").unwrap();
+
+		let id = undo_snapshot(repo_path, "discard").unwrap();
+		reset_workdir(repo_path, "*").unwrap();
+		reset_to(
+			repo_path,
+			repo.head().unwrap().target().unwrap().into(),
+			ResetType::Hard,
+		)
+		.unwrap();
+		assert!(!root.join("new.txt").exists());
+
+		undo_restore(repo_path, id).unwrap();
+
+		assert_eq!(
+			fs::read_to_string(root.join("a.txt")).unwrap(),
+			"unstaged"
+		);
+		assert_eq!(
+			fs::read_to_string(root.join("new.txt")).unwrap(),
+			"new"
+		);
+
+		let mut index = repo.index().unwrap();
+		index.read(true).unwrap();
+		let entry = index.get_path(Path::new("a.txt"), 0).unwrap();
+		let blob = repo.find_blob(entry.id).unwrap();
+		assert_eq!(blob.content(), b"staged");
+		assert!(index.get_path(Path::new("new.txt"), 0).is_none());
+	}
+
+	#[test]
+	fn test_undo_delete_branch() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 = write_commit_file(&repo, "a.txt", "a", "c1");
+		repo.branch(
+			"feature",
+			&repo.find_commit(c1.into()).unwrap(),
+			false,
+		)
+		.unwrap();
+
+		let id = undo_snapshot(repo_path, "delete branch").unwrap();
+		delete_branch(repo_path, "refs/heads/feature").unwrap();
+		assert!(repo
+			.find_branch("feature", BranchType::Local)
+			.is_err());
+
+		undo_restore(repo_path, id).unwrap();
+
+		let branch =
+			repo.find_branch("feature", BranchType::Local).unwrap();
+		assert_eq!(branch.get().target().unwrap(), c1.into());
+	}
+
+	#[test]
+	fn test_undo_snapshots_limit() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		assert!(undo_snapshots(repo_path).unwrap().is_empty());
+
+		for idx in 0..=MAX_SNAPSHOTS {
+			undo_snapshot(repo_path, &format!("op {idx}")).unwrap();
+		}
+
+		let snapshots = undo_snapshots(repo_path).unwrap();
+		assert_eq!(snapshots.len(), MAX_SNAPSHOTS);
+		assert_eq!(
+			snapshots[0].operation,
+			format!("op {MAX_SNAPSHOTS}")
+		);
+	}
+}
@@ -7,12 +7,13 @@ use crate::{
 	error::{Error, Result},
 	sync::config::untracked_files_config_repo,
 };
-use git2::{IndexAddOption, Repository, RepositoryOpenFlags};
+use git2::{IndexAddOption, Repository, RepositoryInitOptions};
 use scopetime::scope_time;
 use std::{
 	fs::File,
 	io::Write,
 	path::{Path, PathBuf},
+	sync::atomic::{AtomicUsize, Ordering},
 };
 
 ///
@@ -26,12 +27,35 @@ pub struct Head {
 
 ///
 pub fn repo_open_error(repo_path: &RepoPath) -> Option<String> {
-	Repository::open_ext(
-		repo_path.gitpath(),
-		RepositoryOpenFlags::FROM_ENV,
-		Vec::<&Path>::new(),
-	)
-	.map_or_else(|e| Some(e.to_string()), |_| None)
+	repo(repo_path).map_or_else(|e| Some(e.to_string()), |_| None)
+}
+
+/// initial branch name configured in `init.defaultBranch` (if any)
+pub fn init_default_branch() -> Option<String> {
+	git2::Config::open_default()
+		.ok()?
+		.get_string("init.defaultBranch")
+		.ok()
+		.filter(|branch| !branch.is_empty())
+}
+
+/// creates a new (non-bare) repository at `path`,
+/// `initial_branch` defaults to what libgit2 picks (`init.defaultBranch` or `master`)
+pub fn init_repo(
+	path: &Path,
+	initial_branch: Option<&str>,
+) -> Result<()> {
+	scope_time!("init_repo");
+
+	let mut options = RepositoryInitOptions::new();
+	options.mkdir(true);
+	if let Some(branch) = initial_branch {
+		options.initial_head(branch);
+	}
+
+	Repository::init_opts(path, &options)?;
+
+	Ok(())
 }
 
 ///
@@ -39,12 +63,47 @@ pub(crate) fn work_dir(repo: &Repository) -> Result<&Path> {
 	repo.workdir().ok_or(Error::NoWorkDir)
 }
 
+/// `true` if the repository has no worktree
+pub fn is_bare_repo(repo_path: &RepoPath) -> Result<bool> {
+	let repo = repo(repo_path)?;
+	Ok(repo.is_bare())
+}
+
 /// path to .git folder
 pub fn repo_dir(repo_path: &RepoPath) -> Result<PathBuf> {
 	let repo = repo(repo_path)?;
 	Ok(repo.path().to_owned())
 }
 
+/// directories git changes on commits, checkouts and ref updates
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepoWatchDirs {
+	/// the git dir and the common dir of a linked worktree, only
+	/// the files directly in them matter (`HEAD`, `index`,
+	/// `packed-refs`)
+	pub dirs: Vec<PathBuf>,
+	/// to be watched recursively
+	pub refs: PathBuf,
+}
+
+/// where `HEAD`, the index and the refs of the repo live, which is
+/// outside of the workdir for linked worktrees and submodules
+pub fn repo_watch_dirs(
+	repo_path: &RepoPath,
+) -> Result<RepoWatchDirs> {
+	let repo = repo(repo_path)?;
+
+	let mut dirs = vec![repo.path().to_owned()];
+	if repo.commondir() != repo.path() {
+		dirs.push(repo.commondir().to_owned());
+	}
+
+	Ok(RepoWatchDirs {
+		dirs,
+		refs: repo.commondir().join("refs"),
+	})
+}
+
 ///
 pub fn repo_work_dir(repo_path: &RepoPath) -> Result<String> {
 	let repo = repo(repo_path)?;
@@ -136,6 +195,27 @@ pub fn stage_add_all(
 	Ok(())
 }
 
+/// stage a file or folder even if it is ignored (`git add -f`)
+pub fn stage_add_force(
+	repo_path: &RepoPath,
+	path: &str,
+) -> Result<()> {
+	scope_time!("stage_add_force");
+
+	let repo = repo(repo_path)?;
+
+	let mut index = repo.index()?;
+
+	index.add_all(
+		vec![path.trim_end_matches('/')],
+		IndexAddOption::FORCE,
+		None,
+	)?;
+	index.write()?;
+
+	Ok(())
+}
+
 /// Undo last commit in repo
 pub fn undo_last_commit(repo_path: &RepoPath) -> Result<()> {
 	let repo = repo(repo_path)?;
@@ -198,6 +278,35 @@ pub fn read_file(path: &Path) -> Result<String> {
 	Ok(String::from_utf8(buffer)?)
 }
 
+/// file in the temp dir handed to external programs,
+/// removed again on drop
+pub(crate) struct TempFile(PathBuf);
+
+impl TempFile {
+	pub(crate) fn new(prefix: &str, content: &[u8]) -> Result<Self> {
+		static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+		let path = std::env::temp_dir().join(format!(
+			"gitui-{prefix}-{}-{}",
+			std::process::id(),
+			COUNTER.fetch_add(1, Ordering::Relaxed)
+		));
+		std::fs::write(&path, content)?;
+
+		Ok(Self(path))
+	}
+
+	pub(crate) fn path(&self) -> &Path {
+		&self.0
+	}
+}
+
+impl Drop for TempFile {
+	fn drop(&mut self) {
+		std::fs::remove_file(&self.0).ok();
+	}
+}
+
 #[cfg(test)]
 pub(crate) fn repo_read_file(
 	repo: &Repository,
@@ -225,7 +334,7 @@ mod tests {
 		diff::get_diff,
 		status::{get_status, StatusType},
 		tests::{
-			debug_cmd_print, get_statuses, repo_init,
+			debug_cmd_print, get_statuses, repo_init, repo_init_bare,
 			repo_init_empty, write_commit_file,
 		},
 	};
@@ -235,6 +344,31 @@ mod tests {
 		path::Path,
 	};
 
+	#[test]
+	fn test_repo_watch_dirs() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let dirs = repo_watch_dirs(repo_path).unwrap();
+		assert_eq!(dirs.dirs, vec![repo.path().to_owned()]);
+		assert_eq!(dirs.refs, repo.path().join("refs"));
+
+		let wt_dir = tempfile::TempDir::new().unwrap();
+		let wt_path = wt_dir.path().join("wt");
+		repo.worktree("wt", &wt_path, None).unwrap();
+
+		let wt_repo_path: &RepoPath =
+			&wt_path.as_os_str().to_str().unwrap().into();
+		let dirs = repo_watch_dirs(wt_repo_path).unwrap();
+
+		assert_eq!(dirs.dirs.len(), 2);
+		assert!(dirs.dirs[0].ends_with("worktrees/wt"));
+		assert_eq!(dirs.dirs[1], repo.path());
+		assert_eq!(dirs.refs, repo.path().join("refs"));
+	}
+
 	#[test]
 	fn test_stage_add_smoke() {
 		let file_path = Path::new("foo");
@@ -299,6 +433,34 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	fn test_staging_ignored() -> Result<()> {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let status_count = |s: StatusType| -> usize {
+			get_status(repo_path, s, None).unwrap().len()
+		};
+
+		fs::create_dir_all(root.join("build"))?;
+		File::create(root.join(".git/info/exclude"))?
+			.write_all(b"*.log\nbuild/\n")?;
+		File::create(root.join("foo.log"))?.write_all(b"foo")?;
+		File::create(root.join("build/out.txt"))?
+			.write_all(b"foo")?;
+
+		assert_eq!(status_count(StatusType::WorkingDir), 0);
+
+		stage_add_force(repo_path, "foo.log")?;
+		stage_add_force(repo_path, "build/")?;
+
+		assert_eq!(status_count(StatusType::Stage), 2);
+
+		Ok(())
+	}
+
 	#[test]
 	fn test_undo_commit_empty_repo() {
 		let (_td, repo) = repo_init().unwrap();
@@ -456,4 +618,76 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[test]
+	fn test_init_repo() {
+		let td = tempfile::TempDir::new().unwrap();
+		let path = td.path().join("new");
+
+		init_repo(&path, Some("trunk")).unwrap();
+
+		let repo = Repository::open(&path).unwrap();
+		assert!(!repo.is_bare());
+		assert_eq!(
+			repo.find_reference("HEAD")
+				.unwrap()
+				.symbolic_target()
+				.unwrap(),
+			"refs/heads/trunk"
+		);
+		assert!(
+			repo_open_error(&path.to_str().unwrap().into()).is_none()
+		);
+	}
+
+	#[test]
+	fn test_is_bare_repo() {
+		let (bare_dir, _repo) = repo_init_bare().unwrap();
+		let bare_path: &RepoPath =
+			&bare_dir.path().to_str().unwrap().into();
+		assert!(is_bare_repo(bare_path).unwrap());
+		assert!(repo_work_dir(bare_path).is_err());
+		assert!(repo_dir(bare_path).is_ok());
+
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+		assert!(!is_bare_repo(repo_path).unwrap());
+	}
+
+	#[test]
+	fn test_separate_workdir() {
+		let (git_dir, repo) = repo_init_bare().unwrap();
+		let mut config = repo.config().unwrap();
+		config.set_str("user.name", "name").unwrap();
+		config.set_str("user.email", "email").unwrap();
+
+		let work_dir = tempfile::TempDir::new().unwrap();
+		let repo_path = RepoPath::Workdir {
+			gitdir: git_dir.path().to_path_buf(),
+			workdir: work_dir.path().to_path_buf(),
+		};
+
+		assert!(repo_open_error(&repo_path).is_none());
+		assert!(!is_bare_repo(&repo_path).unwrap());
+		assert_eq!(
+			Path::new(&repo_work_dir(&repo_path).unwrap()),
+			work_dir.path()
+		);
+
+		File::create(work_dir.path().join("foo.txt"))
+			.unwrap()
+			.write_all(b"test")
+			.unwrap();
+
+		assert_eq!(get_statuses(&repo_path), (1, 0));
+		stage_add_file(&repo_path, Path::new("foo.txt")).unwrap();
+		assert_eq!(get_statuses(&repo_path), (0, 1));
+		commit(&repo_path, "commit msg").unwrap();
+		assert_eq!(get_statuses(&repo_path), (0, 0));
+
+		assert!(get_head(&repo_path).is_ok());
+		assert!(!git_dir.path().join("foo.txt").exists());
+	}
 }
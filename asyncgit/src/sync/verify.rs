@@ -0,0 +1,410 @@
+//! verifying commit signatures
+
+use super::{
+	repository::repo, utils::TempFile, CommitId, RepoPath,
+	SigningFormat,
+};
+use crate::error::Result;
+use git2::{Config, ErrorCode};
+use scopetime::scope_time;
+use std::{
+	collections::HashMap,
+	io::Write,
+	path::{Path, PathBuf},
+	process::{Command, Output, Stdio},
+	sync::{Mutex, OnceLock},
+};
+
+/// outcome of checking a signature
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SignatureValidity {
+	/// good signature made by a trusted key
+	Verified,
+	/// signature could not be checked against a trusted key
+	/// (unknown, untrusted, expired or revoked key)
+	Unverified,
+	/// signature does not match the commit
+	Bad,
+}
+
+/// result of verifying a signed commit
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignatureVerification {
+	///
+	pub format: SigningFormat,
+	///
+	pub validity: SignatureValidity,
+	/// user id (gpg) or principal (ssh) of the signer
+	pub signer: Option<String>,
+	/// fingerprint of the signing key
+	pub key: Option<String>,
+}
+
+type Cache =
+	HashMap<(PathBuf, CommitId), Option<SignatureVerification>>;
+
+fn cache() -> &'static Mutex<Cache> {
+	static CACHE: OnceLock<Mutex<Cache>> = OnceLock::new();
+	CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// verifies the signature of a commit using the programs configured
+/// in `gpg.*`, returns `None` for unsigned commits.
+///
+/// results are cached per repository and commit.
+pub fn verify_signature(
+	repo_path: &RepoPath,
+	id: CommitId,
+) -> Result<Option<SignatureVerification>> {
+	scope_time!("verify_signature");
+
+	let repo = repo(repo_path)?;
+	let key = (repo.path().to_path_buf(), id);
+
+	if let Some(cached) = cache()
+		.lock()
+		.ok()
+		.and_then(|cache| cache.get(&key).cloned())
+	{
+		return Ok(cached);
+	}
+
+	let (signature, data) = match repo
+		.extract_signature(&id.into(), None)
+	{
+		Ok(extracted) => extracted,
+		Err(e) if e.code() == ErrorCode::NotFound => return Ok(None),
+		Err(e) => return Err(e.into()),
+	};
+
+	let config = repo.config()?;
+	let signature = signature.as_str().unwrap_or_default();
+	let result = verify(&config, signature, &data)?;
+
+	if let Ok(mut cache) = cache().lock() {
+		cache.insert(key, result.clone());
+	}
+
+	Ok(result)
+}
+
+fn verify(
+	config: &Config,
+	signature: &str,
+	data: &[u8],
+) -> Result<Option<SignatureVerification>> {
+	let Some(format) = signature_format(signature) else {
+		return Ok(None);
+	};
+
+	// the verifying programs read the signature from a file and
+	// the signed data from stdin
+	let sig_file = TempFile::new("signature", signature.as_bytes())?;
+
+	let verification = match format {
+		SigningFormat::OpenPgp => {
+			let program = config
+				.get_string("gpg.openpgp.program")
+				.or_else(|_| config.get_string("gpg.program"))
+				.unwrap_or_else(|_| "gpg".to_string());
+			verify_gpg(&program, format, sig_file.path(), data)?
+		}
+		SigningFormat::X509 => {
+			let program = config
+				.get_string("gpg.x509.program")
+				.unwrap_or_else(|_| "gpgsm".to_string());
+			verify_gpg(&program, format, sig_file.path(), data)?
+		}
+		SigningFormat::Ssh => {
+			let program = config
+				.get_string("gpg.ssh.program")
+				.unwrap_or_else(|_| "ssh-keygen".to_string());
+			let allowed_signers =
+				config.get_path("gpg.ssh.allowedSignersFile").ok();
+			verify_ssh(
+				&program,
+				allowed_signers.as_deref(),
+				sig_file.path(),
+				data,
+			)?
+		}
+	};
+
+	Ok(Some(verification))
+}
+
+fn signature_format(signature: &str) -> Option<SigningFormat> {
+	let header = signature.lines().next()?.trim();
+
+	match header {
+		"-----BEGIN PGP SIGNATURE-----" => {
+			Some(SigningFormat::OpenPgp)
+		}
+		"-----BEGIN SSH SIGNATURE-----" => Some(SigningFormat::Ssh),
+		"-----BEGIN SIGNED MESSAGE-----" => Some(SigningFormat::X509),
+		_ => None,
+	}
+}
+
+fn run_with_stdin(cmd: &mut Command, data: &[u8]) -> Result<Output> {
+	let mut child = cmd
+		.stdin(Stdio::piped())
+		.stdout(Stdio::piped())
+		.stderr(Stdio::piped())
+		.spawn()?;
+
+	if let Some(mut stdin) = child.stdin.take() {
+		// the program may exit before consuming all input
+		stdin.write_all(data).ok();
+	}
+
+	Ok(child.wait_with_output()?)
+}
+
+fn verify_gpg(
+	program: &str,
+	format: SigningFormat,
+	sig_file: &Path,
+	data: &[u8],
+) -> Result<SignatureVerification> {
+	let output = run_with_stdin(
+		Command::new(program)
+			.arg("--status-fd=1")
+			.arg("--verify")
+			.arg(sig_file)
+			.arg("-"),
+		data,
+	)?;
+
+	Ok(parse_gpg_status(
+		format,
+		&String::from_utf8_lossy(&output.stdout),
+	))
+}
+
+/// parses the machine readable `--status-fd` output of gpg/gpgsm
+fn parse_gpg_status(
+	format: SigningFormat,
+	status: &str,
+) -> SignatureVerification {
+	let mut verification = SignatureVerification {
+		format,
+		validity: SignatureValidity::Unverified,
+		signer: None,
+		key: None,
+	};
+	let mut good = false;
+	let mut trusted = false;
+	let mut bad = false;
+
+	for line in status.lines() {
+		let Some(line) = line.strip_prefix("[GNUPG:] ") else {
+			continue;
+		};
+		let (keyword, args) =
+			line.split_once(' ').unwrap_or((line, ""));
+
+		match keyword {
+			"GOODSIG" => {
+				good = true;
+				verification.signer = args
+					.split_once(' ')
+					.map(|(_, uid)| uid.to_string());
+			}
+			"BADSIG" => {
+				bad = true;
+				verification.signer = args
+					.split_once(' ')
+					.map(|(_, uid)| uid.to_string());
+			}
+			"EXPSIG" | "EXPKEYSIG" | "REVKEYSIG" => {
+				verification.signer = args
+					.split_once(' ')
+					.map(|(_, uid)| uid.to_string());
+			}
+			"VALIDSIG" => {
+				verification.key =
+					args.split(' ').next().map(ToString::to_string);
+			}
+			"ERRSIG" if verification.key.is_none() => {
+				verification.key =
+					args.split(' ').next().map(ToString::to_string);
+			}
+			"TRUST_FULLY" | "TRUST_ULTIMATE" => trusted = true,
+			_ => (),
+		}
+	}
+
+	verification.validity = if bad {
+		SignatureValidity::Bad
+	} else if good && trusted {
+		SignatureValidity::Verified
+	} else {
+		SignatureValidity::Unverified
+	};
+
+	verification
+}
+
+fn verify_ssh(
+	program: &str,
+	allowed_signers: Option<&Path>,
+	sig_file: &Path,
+	data: &[u8],
+) -> Result<SignatureVerification> {
+	let principal = allowed_signers.and_then(|allowed_signers| {
+		Command::new(program)
+			.args(["-Y", "find-principals", "-f"])
+			.arg(allowed_signers)
+			.arg("-s")
+			.arg(sig_file)
+			.output()
+			.ok()
+			.filter(|output| output.status.success())
+			.and_then(|output| {
+				String::from_utf8_lossy(&output.stdout)
+					.lines()
+					.next()
+					.map(ToString::to_string)
+			})
+	});
+
+	let output = match (allowed_signers, &principal) {
+		(Some(allowed_signers), Some(principal)) => run_with_stdin(
+			Command::new(program)
+				.args(["-Y", "verify", "-n", "git", "-f"])
+				.arg(allowed_signers)
+				.arg("-I")
+				.arg(principal)
+				.arg("-s")
+				.arg(sig_file),
+			data,
+		)?,
+		_ => run_with_stdin(
+			Command::new(program)
+				.args(["-Y", "check-novalidate", "-n", "git", "-s"])
+				.arg(sig_file),
+			data,
+		)?,
+	};
+
+	let validity = match (output.status.success(), &principal) {
+		(false, _) => SignatureValidity::Bad,
+		(true, Some(_)) => SignatureValidity::Verified,
+		(true, None) => SignatureValidity::Unverified,
+	};
+
+	Ok(SignatureVerification {
+		format: SigningFormat::Ssh,
+		validity,
+		signer: principal,
+		key: parse_ssh_key(&String::from_utf8_lossy(&output.stdout)),
+	})
+}
+
+/// extracts the key fingerprint from
+/// `Good "git" signature for x with ED25519 key SHA256:...`
+fn parse_ssh_key(output: &str) -> Option<String> {
+	output
+		.lines()
+		.find_map(|line| line.split_once(" key "))
+		.map(|(_, key)| key.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::tests::{repo_init, write_commit_file};
+
+	#[test]
+	fn test_unsigned() {
+		let (_td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let id = write_commit_file(&repo, "a.txt", "a", "c1");
+
+		assert_eq!(verify_signature(repo_path, id).unwrap(), None);
+	}
+
+	#[test]
+	fn test_parse_gpg_status() {
+		let status = "\
+[GNUPG:] NEWSIG
+[GNUPG:] KEY_CONSIDERED 0123456789ABCDEF0123AAAA1111BBBB2222 0
+[GNUPG:] SIG_ID abc 2024-01-01 1700000000
+[GNUPG:] GOODSIG AAAA1111BBBB2222 Jane Doe <jane@example.com>
+[GNUPG:] VALIDSIG 0123456789ABCDEF0123AAAA1111BBBB2222 2024-01-01 1700000000 0 4 0 22 10 00 0123456789ABCDEF0123AAAA1111BBBB2222
+[GNUPG:] TRUST_ULTIMATE 0 pgp
+";
+
+		let verification =
+			parse_gpg_status(SigningFormat::OpenPgp, status);
+
+		assert_eq!(
+			verification.validity,
+			SignatureValidity::Verified
+		);
+		assert_eq!(
+			verification.signer.as_deref(),
+			Some("Jane Doe <jane@example.com>")
+		);
+		assert_eq!(
+			verification.key.as_deref(),
+			Some("0123456789ABCDEF0123AAAA1111BBBB2222")
+		);
+
+		let untrusted =
+			status.replace("TRUST_ULTIMATE", "TRUST_UNDEFINED");
+		assert_eq!(
+			parse_gpg_status(SigningFormat::OpenPgp, &untrusted)
+				.validity,
+			SignatureValidity::Unverified
+		);
+
+		let bad = "[GNUPG:] BADSIG AAAA1111BBBB2222 Jane Doe <jane@example.com>\n";
+		assert_eq!(
+			parse_gpg_status(SigningFormat::OpenPgp, bad).validity,
+			SignatureValidity::Bad
+		);
+
+		let missing_key = "[GNUPG:] ERRSIG AAAA1111BBBB2222 22 10 00 1700000000 9 -\n[GNUPG:] NO_PUBKEY AAAA1111BBBB2222\n";
+		let verification =
+			parse_gpg_status(SigningFormat::OpenPgp, missing_key);
+		assert_eq!(
+			verification.validity,
+			SignatureValidity::Unverified
+		);
+		assert_eq!(
+			verification.key.as_deref(),
+			Some("AAAA1111BBBB2222")
+		);
+	}
+
+	#[test]
+	fn test_signature_format() {
+		assert_eq!(
+			signature_format("-----BEGIN SSH SIGNATURE-----\nabc"),
+			Some(SigningFormat::Ssh)
+		);
+		assert_eq!(
+			signature_format("-----BEGIN PGP SIGNATURE-----\nabc"),
+			Some(SigningFormat::OpenPgp)
+		);
+		assert_eq!(signature_format("garbage"), None);
+	}
+
+	#[test]
+	fn test_parse_ssh_key() {
+		assert_eq!(
+			parse_ssh_key("Good \"git\" signature for a@b with ED25519 key SHA256:tUJr/skJk\n")
+				.as_deref(),
+			Some("SHA256:tUJr/skJk")
+		);
+		assert_eq!(
+			parse_ssh_key("Could not verify signature.\n"),
+			None
+		);
+	}
+}
@@ -0,0 +1,236 @@
+//! `git worktree` list/add/prune
+
+use super::{repository::repo, RepoPath};
+use crate::error::{Error, Result};
+use git2::{
+	BranchType, Repository, WorktreeAddOptions, WorktreeLockStatus,
+};
+use scopetime::scope_time;
+use std::path::{Path, PathBuf};
+
+/// a working tree of the repository
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorktreeInfo {
+	/// `None` for the main worktree
+	pub name: Option<String>,
+	///
+	pub path: PathBuf,
+	/// short name of the checked out branch, `None` if detached
+	pub branch: Option<String>,
+	/// the worktree the repo is currently opened in
+	pub is_current: bool,
+	///
+	pub is_locked: bool,
+	/// the worktree directory is gone, see `prune_worktrees`
+	pub is_prunable: bool,
+}
+
+/// the main worktree (unless the repo is bare) followed by all
+/// linked worktrees
+pub fn get_worktrees(
+	repo_path: &RepoPath,
+) -> Result<Vec<WorktreeInfo>> {
+	scope_time!("get_worktrees");
+
+	let repo = repo(repo_path)?;
+	let current = repo.workdir().map(canonicalize);
+
+	let main = if repo.is_worktree() {
+		Repository::open(repo.commondir())?
+	} else {
+		repo
+	};
+
+	let mut res = Vec::new();
+
+	if let Some(path) = main.workdir() {
+		res.push(WorktreeInfo {
+			name: None,
+			path: path.to_path_buf(),
+			branch: head_branch(&main),
+			is_current: current.as_ref() == Some(&canonicalize(path)),
+			is_locked: false,
+			is_prunable: false,
+		});
+	}
+
+	for name in main.worktrees()?.iter().flatten() {
+		let worktree = main.find_worktree(name)?;
+		let valid = worktree.validate().is_ok();
+
+		res.push(WorktreeInfo {
+			name: Some(name.to_string()),
+			path: worktree.path().to_path_buf(),
+			branch: valid
+				.then(|| {
+					Repository::open_from_worktree(&worktree).ok()
+				})
+				.flatten()
+				.as_ref()
+				.and_then(head_branch),
+			is_current: current.as_ref()
+				== Some(&canonicalize(worktree.path())),
+			is_locked: matches!(
+				worktree.is_locked()?,
+				WorktreeLockStatus::Locked(_)
+			),
+			is_prunable: !valid,
+		});
+	}
+
+	Ok(res)
+}
+
+/// checks out the local `branch` in a new worktree at `path`,
+/// the worktree is named after the last component of `path`
+pub fn create_worktree(
+	repo_path: &RepoPath,
+	branch: &str,
+	path: &Path,
+) -> Result<()> {
+	scope_time!("create_worktree");
+
+	let repo = repo(repo_path)?;
+
+	let name = path
+		.file_name()
+		.and_then(|name| name.to_str())
+		.ok_or_else(|| {
+			Error::Generic(format!(
+				"invalid worktree path: {}",
+				path.display()
+			))
+		})?;
+
+	let branch = repo.find_branch(branch, BranchType::Local)?;
+	let reference = branch.into_reference();
+
+	repo.worktree(
+		name,
+		path,
+		Some(WorktreeAddOptions::new().reference(Some(&reference))),
+	)?;
+
+	Ok(())
+}
+
+/// removes the administrative files of worktrees whose directory is
+/// gone, returns how many were pruned
+pub fn prune_worktrees(repo_path: &RepoPath) -> Result<usize> {
+	scope_time!("prune_worktrees");
+
+	let repo = repo(repo_path)?;
+
+	let mut pruned = 0;
+	for name in repo.worktrees()?.iter().flatten() {
+		let worktree = repo.find_worktree(name)?;
+
+		if worktree.is_prunable(None)? {
+			worktree.prune(None)?;
+			pruned += 1;
+		}
+	}
+
+	Ok(pruned)
+}
+
+/// default location of a new worktree for `branch`:
+/// next to the main worktree, named `<repo>-<branch>`
+pub fn default_worktree_path(
+	repo_path: &RepoPath,
+	branch: &str,
+) -> Result<PathBuf> {
+	let repo = repo(repo_path)?;
+
+	let main = if repo.is_worktree() {
+		Repository::open(repo.commondir())?
+	} else {
+		repo
+	};
+
+	let workdir = main.workdir().ok_or_else(|| {
+		Error::Generic(String::from("bare repository"))
+	})?;
+	let workdir = canonicalize(workdir);
+
+	let repo_name = workdir
+		.file_name()
+		.map(|name| name.to_string_lossy().into_owned())
+		.unwrap_or_default();
+
+	Ok(workdir
+		.parent()
+		.unwrap_or(&workdir)
+		.join(format!("{repo_name}-{}", branch.replace('/', "-"))))
+}
+
+fn head_branch(repo: &Repository) -> Option<String> {
+	let head = repo.head().ok()?;
+
+	head.is_branch()
+		.then(|| head.shorthand().map(String::from))
+		.flatten()
+}
+
+fn canonicalize(path: &Path) -> PathBuf {
+	path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::tests::{repo_init, write_commit_file};
+	use std::fs;
+
+	#[test]
+	fn test_worktrees() {
+		let (td, repo) = repo_init().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let c1 = write_commit_file(&repo, "a.txt", "1", "c1");
+		repo.branch(
+			"feature/x",
+			&repo.find_commit(c1.into()).unwrap(),
+			false,
+		)
+		.unwrap();
+
+		let path =
+			default_worktree_path(repo_path, "feature/x").unwrap();
+		assert!(path
+			.file_name()
+			.unwrap()
+			.to_string_lossy()
+			.ends_with("-feature-x"));
+
+		let path = td.path().join("wt");
+		create_worktree(repo_path, "feature/x", &path).unwrap();
+		assert!(path.join("a.txt").is_file());
+
+		let worktrees = get_worktrees(repo_path).unwrap();
+		assert_eq!(worktrees.len(), 2);
+		assert_eq!(worktrees[0].name, None);
+		assert!(worktrees[0].is_current);
+		assert_eq!(worktrees[0].branch.as_deref(), Some("master"));
+		assert_eq!(worktrees[1].name.as_deref(), Some("wt"));
+		assert_eq!(worktrees[1].branch.as_deref(), Some("feature/x"));
+		assert!(!worktrees[1].is_current);
+		assert!(!worktrees[1].is_prunable);
+
+		let wt_path: &RepoPath =
+			&path.as_os_str().to_str().unwrap().into();
+		let worktrees = get_worktrees(wt_path).unwrap();
+		assert!(!worktrees[0].is_current);
+		assert!(worktrees[1].is_current);
+
+		assert_eq!(prune_worktrees(repo_path).unwrap(), 0);
+
+		fs::remove_dir_all(&path).unwrap();
+
+		assert!(get_worktrees(repo_path).unwrap()[1].is_prunable);
+		assert_eq!(prune_worktrees(repo_path).unwrap(), 1);
+		assert_eq!(get_worktrees(repo_path).unwrap().len(), 1);
+	}
+}
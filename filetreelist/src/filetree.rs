@@ -98,6 +98,12 @@ impl FileTree {
 		})
 	}
 
+	/// like `selected_file` but also returns selected folders
+	pub fn selected_item(&self) -> Option<&TreeItemInfo> {
+		self.selection
+			.map(|index| self.items.tree_items[index].info())
+	}
+
 	///
 	pub fn collapse_recursive(&mut self) {
 		if let Some(selection) = self.selection {
@@ -1,12 +1,18 @@
 use git2::Repository;
 
-use crate::{error::Result, HookResult, HooksError};
+use crate::{error::Result, HookOutputLine, HookResult, HooksError};
 
 use std::{
 	env,
+	io::{BufRead, BufReader, ErrorKind, Read, Write},
 	path::{Path, PathBuf},
-	process::Command,
+	process::{Command, ExitStatus, Stdio},
 	str::FromStr,
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		mpsc::{self, RecvTimeoutError, Sender},
+	},
+	time::Duration,
 };
 
 pub struct HookPaths {
@@ -17,6 +23,8 @@ pub struct HookPaths {
 
 const CONFIG_HOOKS_PATH: &str = "core.hooksPath";
 const DEFAULT_HOOKS_PATH: &str = "hooks";
+/// how often a streamed hook checks whether it got cancelled
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(50);
 
 impl HookPaths {
 	/// `core.hooksPath` always takes precedence.
@@ -107,6 +115,124 @@ impl HookPaths {
 	/// this function calls hook scripts based on conventions documented here
 	/// see <https://git-scm.com/docs/githooks>
 	pub fn run_hook(&self, args: &[&str]) -> Result<HookResult> {
+		self.run_hook_with_stdin(args, &[])
+	}
+
+	/// same as [`Self::run_hook`] but feeds `stdin` to the hook
+	pub fn run_hook_with_stdin(
+		&self,
+		args: &[&str],
+		stdin: &[u8],
+	) -> Result<HookResult> {
+		let mut child = self
+			.command(args)
+			.stdin(Stdio::piped())
+			.stdout(Stdio::piped())
+			.stderr(Stdio::piped())
+			.spawn()?;
+
+		if let Some(mut pipe) = child.stdin.take() {
+			// a hook is free to not read its input at all
+			match pipe.write_all(stdin) {
+				Err(e) if e.kind() != ErrorKind::BrokenPipe => {
+					return Err(e.into());
+				}
+				_ => {}
+			}
+		}
+
+		let output = child.wait_with_output()?;
+
+		Ok(self.result(
+			output.status,
+			String::from_utf8_lossy(&output.stdout).to_string(),
+			String::from_utf8_lossy(&output.stderr).to_string(),
+		))
+	}
+
+	/// same as [`Self::run_hook`] but hands every line the hook
+	/// prints to `on_output` as soon as it arrives.
+	/// setting `cancel` kills the hook.
+	pub fn run_hook_streamed(
+		&self,
+		args: &[&str],
+		on_output: &mut dyn FnMut(HookOutputLine),
+		cancel: &AtomicBool,
+	) -> Result<HookResult> {
+		let mut child = self
+			.command(args)
+			.stdin(Stdio::null())
+			.stdout(Stdio::piped())
+			.stderr(Stdio::piped())
+			.spawn()?;
+
+		let (tx, rx) = mpsc::channel();
+
+		if let Some(stdout) = child.stdout.take() {
+			forward_lines(stdout, tx.clone(), HookOutputLine::Stdout);
+		}
+		if let Some(stderr) = child.stderr.take() {
+			forward_lines(stderr, tx, HookOutputLine::Stderr);
+		}
+
+		let mut stdout = String::new();
+		let mut stderr = String::new();
+
+		loop {
+			if cancel.load(Ordering::Relaxed) {
+				child.kill()?;
+				child.wait()?;
+
+				return Ok(HookResult::Cancelled {
+					hook: self.hook.clone(),
+				});
+			}
+
+			match rx.recv_timeout(CANCEL_POLL_INTERVAL) {
+				Ok(line) => {
+					match &line {
+						HookOutputLine::Stdout(l) => {
+							stdout.push_str(l);
+							stdout.push('\n');
+						}
+						HookOutputLine::Stderr(l) => {
+							stderr.push_str(l);
+							stderr.push('\n');
+						}
+					}
+					on_output(line);
+				}
+				Err(RecvTimeoutError::Timeout) => (),
+				Err(RecvTimeoutError::Disconnected) => break,
+			}
+		}
+
+		let status = child.wait()?;
+
+		Ok(self.result(status, stdout, stderr))
+	}
+
+	fn result(
+		&self,
+		status: ExitStatus,
+		stdout: String,
+		stderr: String,
+	) -> HookResult {
+		let hook = self.hook.clone();
+
+		if status.success() {
+			HookResult::Ok { hook }
+		} else {
+			HookResult::RunNotSuccessful {
+				code: status.code(),
+				stdout,
+				stderr,
+				hook,
+			}
+		}
+	}
+
+	fn command(&self, args: &[&str]) -> Command {
 		let hook = self.hook.clone();
 
 		let arg_str = format!("{:?} {}", hook, args.join(" "));
@@ -119,7 +245,18 @@ impl HookPaths {
 		let git_shell = find_bash_executable()
 			.or_else(find_default_unix_shell)
 			.unwrap_or_else(|| "bash".into());
-		let output = Command::new(git_shell)
+		let mut command = Command::new(git_shell);
+
+		// git dir and work tree are not in the usual layout (e.g. a
+		// bare dotfiles repo with `--work-tree=$HOME`) so git in the
+		// hook would not find them on its own
+		if !self.git.starts_with(&self.pwd) {
+			command
+				.env("GIT_DIR", &self.git)
+				.env("GIT_WORK_TREE", &self.pwd);
+		}
+
+		command
 			.args(bash_args)
 			.with_no_window()
 			.current_dir(&self.pwd)
@@ -129,25 +266,34 @@ impl HookPaths {
 			.env(
 				"DUMMY_ENV_TO_FIX_WINDOWS_CMD_RUNS",
 				"FixPathHandlingOnWindows",
-			)
-			.output()?;
+			);
 
-		if output.status.success() {
-			Ok(HookResult::Ok { hook })
-		} else {
-			let stderr =
-				String::from_utf8_lossy(&output.stderr).to_string();
-			let stdout =
-				String::from_utf8_lossy(&output.stdout).to_string();
+		command
+	}
+}
 
-			Ok(HookResult::RunNotSuccessful {
-				code: output.status.code(),
-				stdout,
-				stderr,
-				hook,
-			})
+/// reads `pipe` line by line on a separate thread and sends each line
+/// to `tx` until the pipe gets closed
+fn forward_lines<R: Read + Send + 'static>(
+	pipe: R,
+	tx: Sender<HookOutputLine>,
+	wrap: fn(String) -> HookOutputLine,
+) {
+	std::thread::spawn(move || {
+		let mut reader = BufReader::new(pipe);
+		let mut buf = Vec::new();
+
+		// hooks do not necessarily print valid utf8
+		while matches!(reader.read_until(b'\n', &mut buf), Ok(n) if n > 0)
+		{
+			let line = String::from_utf8_lossy(&buf);
+			let line = line.trim_end_matches(['\n', '\r']);
+			if tx.send(wrap(line.to_string())).is_err() {
+				break;
+			}
+			buf.clear();
 		}
-	}
+	});
 }
 
 #[cfg(unix)]
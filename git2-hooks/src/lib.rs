@@ -31,6 +31,7 @@ use std::{
 	fs::File,
 	io::{Read, Write},
 	path::{Path, PathBuf},
+	sync::atomic::AtomicBool,
 };
 
 pub use error::HooksError;
@@ -43,6 +44,9 @@ pub const HOOK_POST_COMMIT: &str = "post-commit";
 pub const HOOK_PRE_COMMIT: &str = "pre-commit";
 pub const HOOK_COMMIT_MSG: &str = "commit-msg";
 pub const HOOK_PREPARE_COMMIT_MSG: &str = "prepare-commit-msg";
+pub const HOOK_PRE_PUSH: &str = "pre-push";
+pub const HOOK_POST_CHECKOUT: &str = "post-checkout";
+pub const HOOK_POST_MERGE: &str = "post-merge";
 
 const HOOK_COMMIT_MSG_TEMP_FILE: &str = "COMMIT_EDITMSG";
 
@@ -66,6 +70,20 @@ pub enum HookResult {
 		/// path of the hook that was run
 		hook: PathBuf,
 	},
+	/// Hook got killed before it finished
+	Cancelled {
+		/// path of the hook that was run
+		hook: PathBuf,
+	},
+}
+
+/// a single line of output of a hook run via the `*_streamed` variants
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HookOutputLine {
+	/// line printed to stdout
+	Stdout(String),
+	/// line printed to stderr
+	Stderr(String),
 }
 
 impl HookResult {
@@ -158,6 +176,32 @@ pub fn hooks_pre_commit(
 	hook.run_hook(&[])
 }
 
+/// same as [`hooks_pre_commit`] but reports the output of the hook
+/// line by line to `on_output` while it runs, setting `cancel` kills it
+pub fn hooks_pre_commit_streamed(
+	repo: &Repository,
+	other_paths: Option<&[&str]>,
+	on_output: &mut dyn FnMut(HookOutputLine),
+	cancel: &AtomicBool,
+) -> Result<HookResult> {
+	let hook = HookPaths::new(repo, other_paths, HOOK_PRE_COMMIT)?;
+
+	if !hook.found() {
+		return Ok(HookResult::NoHookFound);
+	}
+
+	hook.run_hook_streamed(&[], on_output, cancel)
+}
+
+/// checks whether `hook` would be run at all
+pub fn hook_found(
+	repo: &Repository,
+	other_paths: Option<&[&str]>,
+	hook: &str,
+) -> Result<bool> {
+	Ok(HookPaths::new(repo, other_paths, hook)?.found())
+}
+
 /// this hook is documented here <https://git-scm.com/docs/githooks#_post_commit>
 pub fn hooks_post_commit(
 	repo: &Repository,
@@ -172,6 +216,90 @@ pub fn hooks_post_commit(
 	hook.run_hook(&[])
 }
 
+/// a single ref update reported to the `pre-push` hook
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrePushRef {
+	/// local ref pushed, `(delete)` when deleting the remote ref
+	pub local_ref: String,
+	/// object pushed, zero when deleting the remote ref
+	pub local_oid: git2::Oid,
+	/// remote ref that gets updated
+	pub remote_ref: String,
+	/// object the remote ref currently points to as far as we know,
+	/// zero if it does not exist yet
+	pub remote_oid: git2::Oid,
+}
+
+/// this hook is documented here <https://git-scm.com/docs/githooks#_pre_push>
+///
+/// `remote` and `url` are passed as arguments, one line per entry in
+/// `updates` is written to the hooks stdin.
+pub fn hooks_pre_push(
+	repo: &Repository,
+	other_paths: Option<&[&str]>,
+	remote: &str,
+	url: &str,
+	updates: &[PrePushRef],
+) -> Result<HookResult> {
+	use std::fmt::Write as _;
+
+	let hook = HookPaths::new(repo, other_paths, HOOK_PRE_PUSH)?;
+
+	if !hook.found() {
+		return Ok(HookResult::NoHookFound);
+	}
+
+	let stdin = updates.iter().fold(String::new(), |mut s, u| {
+		let _ = writeln!(
+			s,
+			"{} {} {} {}",
+			u.local_ref, u.local_oid, u.remote_ref, u.remote_oid
+		);
+		s
+	});
+
+	hook.run_hook_with_stdin(&[remote, url], stdin.as_bytes())
+}
+
+/// this hook is documented here <https://git-scm.com/docs/githooks#_post_checkout>
+///
+/// `branch_checkout` distinguishes switching branches (or commits)
+/// from checking out individual files.
+pub fn hooks_post_checkout(
+	repo: &Repository,
+	other_paths: Option<&[&str]>,
+	old_head: git2::Oid,
+	new_head: git2::Oid,
+	branch_checkout: bool,
+) -> Result<HookResult> {
+	let hook = HookPaths::new(repo, other_paths, HOOK_POST_CHECKOUT)?;
+
+	if !hook.found() {
+		return Ok(HookResult::NoHookFound);
+	}
+
+	hook.run_hook(&[
+		old_head.to_string().as_str(),
+		new_head.to_string().as_str(),
+		if branch_checkout { "1" } else { "0" },
+	])
+}
+
+/// this hook is documented here <https://git-scm.com/docs/githooks#_post_merge>
+pub fn hooks_post_merge(
+	repo: &Repository,
+	other_paths: Option<&[&str]>,
+	squash: bool,
+) -> Result<HookResult> {
+	let hook = HookPaths::new(repo, other_paths, HOOK_POST_MERGE)?;
+
+	if !hook.found() {
+		return Ok(HookResult::NoHookFound);
+	}
+
+	hook.run_hook(&[if squash { "1" } else { "0" }])
+}
+
 pub enum PrepareCommitMsgSource {
 	Message,
 	Template,
@@ -549,6 +677,28 @@ exit 0
 		assert_eq!(hook.pwd, git_root);
 	}
 
+	#[test]
+	#[cfg(unix)]
+	fn test_hook_env_separate_workdir() {
+		let (_td, repo) = repo_init_bare();
+		let workdir = TempDir::new().unwrap();
+		repo.set_workdir(workdir.path(), false).unwrap();
+
+		let hook = b"#!/bin/sh
+echo $GIT_DIR
+exit 1
+        ";
+
+		create_hook(&repo, HOOK_PRE_COMMIT, hook);
+		let res = hooks_pre_commit(&repo, None).unwrap();
+
+		let HookResult::RunNotSuccessful { stdout, .. } = res else {
+			unreachable!()
+		};
+
+		assert_eq!(Path::new(stdout.trim()), repo.path());
+	}
+
 	#[test]
 	fn test_hook_pwd() {
 		let (_td, repo) = repo_init();
@@ -620,4 +770,169 @@ exit 2
 			)
 		);
 	}
+
+	#[test]
+	fn test_pre_push_args_and_stdin() {
+		let (td, repo) = repo_init();
+
+		let out = td.path().join("pre-push-out");
+		let hook = format!(
+			"#!/bin/sh
+echo \"$1 $2\" > {out:?}
+cat >> {out:?}
+exit 0
+"
+		);
+
+		create_hook(&repo, HOOK_PRE_PUSH, hook.as_bytes());
+
+		let id = repo.head().unwrap().target().unwrap();
+		let res = hooks_pre_push(
+			&repo,
+			None,
+			"origin",
+			"https://example.com/repo.git",
+			&[PrePushRef {
+				local_ref: String::from("refs/heads/master"),
+				local_oid: id,
+				remote_ref: String::from("refs/heads/master"),
+				remote_oid: git2::Oid::zero(),
+			}],
+		)
+		.unwrap();
+
+		assert!(res.is_ok());
+		assert_eq!(
+			std::fs::read_to_string(out).unwrap(),
+			format!(
+				"origin https://example.com/repo.git\nrefs/heads/master {id} refs/heads/master {}\n",
+				git2::Oid::zero()
+			)
+		);
+	}
+
+	#[test]
+	fn test_pre_push_reject() {
+		let (_td, repo) = repo_init();
+
+		let hook = b"#!/bin/sh
+echo 'no push'
+exit 1
+        ";
+
+		create_hook(&repo, HOOK_PRE_PUSH, hook);
+
+		let res = hooks_pre_push(&repo, None, "origin", "url", &[])
+			.unwrap();
+
+		assert!(res.is_not_successful());
+	}
+
+	#[test]
+	fn test_post_checkout_args() {
+		let (_td, repo) = repo_init();
+
+		let hook = b"#!/bin/sh
+echo \"$1 $2 $3\"
+exit 1
+        ";
+
+		create_hook(&repo, HOOK_POST_CHECKOUT, hook);
+
+		let id = repo.head().unwrap().target().unwrap();
+		let res = hooks_post_checkout(
+			&repo,
+			None,
+			git2::Oid::zero(),
+			id,
+			true,
+		)
+		.unwrap();
+
+		let HookResult::RunNotSuccessful { stdout, .. } = res else {
+			unreachable!()
+		};
+
+		assert_eq!(stdout, format!("{} {id} 1\n", git2::Oid::zero()));
+	}
+
+	#[test]
+	fn test_post_merge_args() {
+		let (_td, repo) = repo_init();
+
+		let hook = b"#!/bin/sh
+echo \"$1\"
+exit 1
+        ";
+
+		create_hook(&repo, HOOK_POST_MERGE, hook);
+
+		let res = hooks_post_merge(&repo, None, true).unwrap();
+
+		let HookResult::RunNotSuccessful { stdout, .. } = res else {
+			unreachable!()
+		};
+
+		assert_eq!(stdout, "1\n");
+	}
+
+	#[test]
+	fn test_pre_commit_streamed() {
+		let (_td, repo) = repo_init();
+
+		let hook = b"#!/bin/sh
+echo 'out'
+echo 'err' >&2
+exit 1
+        ";
+
+		create_hook(&repo, HOOK_PRE_COMMIT, hook);
+
+		let mut lines = Vec::new();
+		let res = hooks_pre_commit_streamed(
+			&repo,
+			None,
+			&mut |line| lines.push(line),
+			&AtomicBool::new(false),
+		)
+		.unwrap();
+
+		let HookResult::RunNotSuccessful { stdout, stderr, .. } = res
+		else {
+			unreachable!()
+		};
+
+		assert_eq!(stdout, "out\n");
+		assert!(stderr.ends_with("err\n"));
+		assert!(lines.contains(&HookOutputLine::Stdout("out".into())));
+		assert!(lines.contains(&HookOutputLine::Stderr("err".into())));
+	}
+
+	#[test]
+	fn test_pre_commit_streamed_cancel() {
+		let (_td, repo) = repo_init();
+
+		let hook = b"#!/bin/sh
+echo 'started'
+sleep 10
+        ";
+
+		create_hook(&repo, HOOK_PRE_COMMIT, hook);
+
+		let cancel = AtomicBool::new(false);
+		let res = hooks_pre_commit_streamed(
+			&repo,
+			None,
+			&mut |_| {
+				cancel.store(
+					true,
+					std::sync::atomic::Ordering::Relaxed,
+				);
+			},
+			&cancel,
+		)
+		.unwrap();
+
+		assert!(matches!(res, HookResult::Cancelled { .. }));
+	}
 }
@@ -6,24 +6,36 @@ use crate::{
 		DrawableComponent, FuzzyFinderTarget,
 	},
 	input::{Input, InputEvent, InputState},
+	ipc::IpcCommand,
 	keys::{key_match, KeyConfig, SharedKeyConfig},
 	options::{Options, SharedOptions},
 	popup_stack::PopupStack,
 	popups::{
-		AppOption, BlameFilePopup, BranchListPopup, CommitPopup,
-		CompareCommitsPopup, ConfirmPopup, CreateBranchPopup,
-		CreateRemotePopup, ExternalEditorPopup, FetchPopup,
-		FileRevlogPopup, FuzzyFindPopup, HelpPopup,
-		InspectCommitPopup, LogSearchPopupPopup, MsgPopup,
-		OptionsPopup, PullPopup, PushPopup, PushTagsPopup,
-		RemoteListPopup, RenameBranchPopup, RenameRemotePopup,
-		ResetPopup, RevisionFilesPopup, StashMsgPopup,
-		SubmodulesListPopup, TagCommitPopup, TagListPopup,
-		UpdateRemoteUrlPopup,
+		record_undo, AppOption, ApplyPatchPopup, BisectPopup,
+		BlameFileOpen, BlameFilePopup, BlameIgnoreRevsPopup,
+		BranchListPopup, CleanPopup, ClonePopup, CommitNotePopup,
+		CommitPopup, CompareCommitsPopup, ConfirmPopup,
+		ConflictEditorPopup, CreateBranchPopup, CreateRemotePopup,
+		DiscardedPopup, EditHunkPopup, ExternalEditorPopup,
+		FetchOptionsPopup, FetchPopup, FileRevlogPopup, FlowPopup,
+		ForcePushPopup, FormatPatchPopup, FuzzyFindPopup,
+		GitConfigEditPopup, GitConfigPopup, HelpPopup,
+		HookProgressPopup, IgnoreEditPopup, IgnorePatternPopup,
+		InspectCommitOpen, InspectCommitPopup, LogRefsPopup,
+		LogSearchPopupPopup, MsgPopup, NotesSyncPopup, OptionsPopup,
+		PreCommitPopup, PullOptionsPopup, PullPopup, PushPopup,
+		PushTagsPopup, RebaseInteractivePopup, ReflogPopup,
+		RemoteListPopup, RemoteTargetPopup, RenameBranchPopup,
+		RenameRemotePopup, ResetPopup, RestoreFilePopup,
+		RevertMergePopup, RevisionFilesPopup, RevisionPopup,
+		SigningKeyPopup, StashMsgPopup, StashWorktreePopup,
+		SubmodulesListPopup, SymRefsPopup, TagCommitPopup,
+		TagListPopup, UndoPopup, UpdateRemoteUrlPopup,
+		WorktreesPopup,
 	},
 	queue::{
-		Action, AppTabs, InternalEvent, NeedsUpdate, Queue,
-		StackablePopupOpen,
+		Action, AppTabs, ExternalTool, InternalEvent, NeedsUpdate,
+		Queue, StackablePopupOpen,
 	},
 	setup_popups,
 	strings::{self, ellipsis_trim_start, order},
@@ -34,12 +46,14 @@ use crate::{
 };
 use anyhow::{bail, Result};
 use asyncgit::{
+	asyncjob::AsyncSingleJob,
 	sync::{
 		self,
 		utils::{repo_work_dir, undo_last_commit},
 		RepoPath, RepoPathRef,
 	},
-	AsyncGitNotification, PushType,
+	AsyncAutoFetch, AsyncCommitGraphJob, AsyncGitNotification,
+	PushType,
 };
 use crossbeam_channel::Sender;
 use crossterm::event::{Event, KeyEvent};
@@ -55,14 +69,18 @@ use std::{
 	cell::{Cell, RefCell},
 	path::{Path, PathBuf},
 	rc::Rc,
+	time::{Duration, Instant},
 };
 use unicode_width::UnicodeWidthStr;
 
+/// how often to check whether the commit-graph needs an update
+const COMMIT_GRAPH_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
 #[derive(Clone)]
 pub enum QuitState {
 	None,
 	Close,
-	OpenSubmodule(RepoPath),
+	OpenRepo(RepoPath),
 }
 
 /// the main app type
@@ -82,22 +100,55 @@ pub struct App {
 	revision_files_popup: RevisionFilesPopup,
 	fuzzy_find_popup: FuzzyFindPopup,
 	log_search_popup: LogSearchPopupPopup,
+	log_refs_popup: LogRefsPopup,
 	push_popup: PushPopup,
+	pre_commit_popup: PreCommitPopup,
+	hook_progress_popup: HookProgressPopup,
+	force_push_popup: ForcePushPopup,
+	flow_popup: FlowPopup,
+	symrefs_popup: SymRefsPopup,
+	signing_key_popup: SigningKeyPopup,
+	rebase_interactive_popup: RebaseInteractivePopup,
+	bisect_popup: BisectPopup,
+	reflog_popup: ReflogPopup,
+	undo_popup: UndoPopup,
+	discarded_popup: DiscardedPopup,
+	clean_popup: CleanPopup,
+	ignore_pattern_popup: IgnorePatternPopup,
+	ignore_edit_popup: IgnoreEditPopup,
+	worktrees_popup: WorktreesPopup,
+	edit_hunk_popup: EditHunkPopup,
+	conflict_editor_popup: ConflictEditorPopup,
 	push_tags_popup: PushTagsPopup,
 	pull_popup: PullPopup,
+	pull_options_popup: PullOptionsPopup,
+	fetch_options_popup: FetchOptionsPopup,
+	remote_target_popup: RemoteTargetPopup,
 	fetch_popup: FetchPopup,
+	clone_popup: ClonePopup,
 	tag_commit_popup: TagCommitPopup,
+	commit_note_popup: CommitNotePopup,
+	notes_sync_popup: NotesSyncPopup,
+	format_patch_popup: FormatPatchPopup,
+	apply_patch_popup: ApplyPatchPopup,
+	revert_merge_popup: RevertMergePopup,
+	stash_worktree_popup: StashWorktreePopup,
 	create_branch_popup: CreateBranchPopup,
 	create_remote_popup: CreateRemotePopup,
 	rename_remote_popup: RenameRemotePopup,
+	blame_ignore_revs_popup: BlameIgnoreRevsPopup,
 	update_remote_url_popup: UpdateRemoteUrlPopup,
 	remotes_popup: RemoteListPopup,
+	git_config_popup: GitConfigPopup,
+	git_config_edit_popup: GitConfigEditPopup,
 	rename_branch_popup: RenameBranchPopup,
 	select_branch_popup: BranchListPopup,
 	options_popup: OptionsPopup,
 	submodule_popup: SubmodulesListPopup,
 	tags_popup: TagListPopup,
 	reset_popup: ResetPopup,
+	restore_file_popup: RestoreFilePopup,
+	revision_popup: RevisionPopup,
 	cmdbar: RefCell<CommandBar>,
 	tab: usize,
 	revlog: Revlog,
@@ -112,10 +163,15 @@ pub struct App {
 	popup_stack: PopupStack,
 	options: SharedOptions,
 	repo_path_text: String,
+	bare: bool,
+	auto_fetch: AsyncAutoFetch,
+	commit_graph: AsyncSingleJob<AsyncCommitGraphJob>,
+	commit_graph_checked: Option<Instant>,
 
 	// "Flags"
 	requires_redraw: Cell<bool>,
 	file_to_open: Option<String>,
+	tool_to_open: Option<ExternalTool>,
 }
 
 pub struct Environment {
@@ -159,8 +215,19 @@ impl App {
 	) -> Result<Self> {
 		log::trace!("open repo at: {:?}", &repo);
 
-		let repo_path_text =
-			repo_work_dir(&repo.borrow()).unwrap_or_default();
+		let bare =
+			sync::is_bare_repo(&repo.borrow()).unwrap_or_default();
+		let repo_path_text = if bare {
+			sync::repo_dir(&repo.borrow()).map_or_else(
+				|_| String::new(),
+				|dir| strings::bare_repo_path(&dir.to_string_lossy()),
+			)
+		} else {
+			repo_work_dir(&repo.borrow()).unwrap_or_default()
+		};
+		let auto_fetch =
+			AsyncAutoFetch::new(repo.borrow().clone(), &sender_git);
+		let commit_graph = AsyncSingleJob::new(sender_git.clone());
 
 		let env = Environment {
 			queue: Queue::new(),
@@ -189,22 +256,57 @@ impl App {
 			compare_commits_popup: CompareCommitsPopup::new(&env),
 			external_editor_popup: ExternalEditorPopup::new(&env),
 			push_popup: PushPopup::new(&env),
+			pre_commit_popup: PreCommitPopup::new(&env),
+			hook_progress_popup: HookProgressPopup::new(&env),
+			force_push_popup: ForcePushPopup::new(&env),
+			flow_popup: FlowPopup::new(&env),
+			symrefs_popup: SymRefsPopup::new(&env),
+			signing_key_popup: SigningKeyPopup::new(&env),
+			rebase_interactive_popup: RebaseInteractivePopup::new(
+				&env,
+			),
+			bisect_popup: BisectPopup::new(&env),
+			reflog_popup: ReflogPopup::new(&env),
+			undo_popup: UndoPopup::new(&env),
+			discarded_popup: DiscardedPopup::new(&env),
+			clean_popup: CleanPopup::new(&env),
+			ignore_pattern_popup: IgnorePatternPopup::new(&env),
+			ignore_edit_popup: IgnoreEditPopup::new(&env),
+			worktrees_popup: WorktreesPopup::new(&env),
+			edit_hunk_popup: EditHunkPopup::new(&env),
+			conflict_editor_popup: ConflictEditorPopup::new(&env),
 			push_tags_popup: PushTagsPopup::new(&env),
 			reset_popup: ResetPopup::new(&env),
+			restore_file_popup: RestoreFilePopup::new(&env),
+			revision_popup: RevisionPopup::new(&env),
 			pull_popup: PullPopup::new(&env),
+			pull_options_popup: PullOptionsPopup::new(&env),
+			fetch_options_popup: FetchOptionsPopup::new(&env),
+			remote_target_popup: RemoteTargetPopup::new(&env),
 			fetch_popup: FetchPopup::new(&env),
+			clone_popup: ClonePopup::new(&env),
 			tag_commit_popup: TagCommitPopup::new(&env),
+			commit_note_popup: CommitNotePopup::new(&env),
+			notes_sync_popup: NotesSyncPopup::new(&env),
+			format_patch_popup: FormatPatchPopup::new(&env),
+			apply_patch_popup: ApplyPatchPopup::new(&env),
+			revert_merge_popup: RevertMergePopup::new(&env),
+			stash_worktree_popup: StashWorktreePopup::new(&env),
 			create_branch_popup: CreateBranchPopup::new(&env),
 			create_remote_popup: CreateRemotePopup::new(&env),
 			rename_remote_popup: RenameRemotePopup::new(&env),
+			blame_ignore_revs_popup: BlameIgnoreRevsPopup::new(&env),
 			update_remote_url_popup: UpdateRemoteUrlPopup::new(&env),
 			remotes_popup: RemoteListPopup::new(&env),
+			git_config_popup: GitConfigPopup::new(&env),
+			git_config_edit_popup: GitConfigEditPopup::new(&env),
 			rename_branch_popup: RenameBranchPopup::new(&env),
 			select_branch_popup: BranchListPopup::new(&env),
 			tags_popup: TagListPopup::new(&env),
 			options_popup: OptionsPopup::new(&env),
 			submodule_popup: SubmodulesListPopup::new(&env),
 			log_search_popup: LogSearchPopupPopup::new(&env),
+			log_refs_popup: LogRefsPopup::new(&env),
 			fuzzy_find_popup: FuzzyFindPopup::new(&env),
 			do_quit: QuitState::None,
 			cmdbar: RefCell::new(CommandBar::new(
@@ -225,8 +327,13 @@ impl App {
 			key_config: env.key_config,
 			requires_redraw: Cell::new(false),
 			file_to_open: None,
+			tool_to_open: None,
 			repo: env.repo,
 			repo_path_text,
+			bare,
+			auto_fetch,
+			commit_graph,
+			commit_graph_checked: None,
 			popup_stack: PopupStack::default(),
 		};
 
@@ -284,6 +391,7 @@ impl App {
 	}
 
 	///
+	#[allow(clippy::too_many_lines)]
 	pub fn event(&mut self, ev: InputEvent) -> Result<()> {
 		log::trace!("event: {:?}", ev);
 
@@ -341,6 +449,24 @@ impl App {
 				) {
 					self.options_popup.show()?;
 					NeedsUpdate::ALL
+				} else if key_match(
+					k,
+					self.key_config.keys.open_git_config,
+				) {
+					self.git_config_popup.open()?;
+					NeedsUpdate::ALL
+				} else if key_match(
+					k,
+					self.key_config.keys.clone_repo,
+				) {
+					self.clone_popup.open()?;
+					NeedsUpdate::ALL
+				} else if key_match(
+					k,
+					self.key_config.keys.undo_history,
+				) {
+					self.queue.push(InternalEvent::OpenUndoPopup);
+					NeedsUpdate::empty()
 				} else {
 					NeedsUpdate::empty()
 				};
@@ -352,8 +478,24 @@ impl App {
 		} else if let InputEvent::State(polling_state) = ev {
 			self.external_editor_popup.hide();
 			if matches!(polling_state, InputState::Paused) {
-				let result =
-					if let Some(path) = self.file_to_open.take() {
+				if let Some(tool) = self.tool_to_open.take() {
+					if let Err(e) = ExternalEditorPopup::open_tool(
+						&self.repo.borrow(),
+						&tool,
+					) {
+						let msg =
+							format!("external tool error:\n{e}");
+						log::error!("{}", msg.as_str());
+						self.msg_popup.show_error(msg.as_str())?;
+					}
+
+					self.queue.push(InternalEvent::Update(
+						NeedsUpdate::ALL,
+					));
+				} else {
+					let result = if let Some(path) =
+						self.file_to_open.take()
+					{
 						ExternalEditorPopup::open_file_in_editor(
 							&self.repo.borrow(),
 							Path::new(&path),
@@ -364,11 +506,12 @@ impl App {
 						self.commit_popup.show_editor(changes)
 					};
 
-				if let Err(e) = result {
-					let msg =
-						format!("failed to launch editor:\n{e}");
-					log::error!("{}", msg.as_str());
-					self.msg_popup.show_error(msg.as_str())?;
+					if let Err(e) = result {
+						let msg =
+							format!("failed to launch editor:\n{e}");
+						log::error!("{}", msg.as_str());
+						self.msg_popup.show_error(msg.as_str())?;
+					}
 				}
 
 				self.requires_redraw.set(true);
@@ -391,12 +534,54 @@ impl App {
 		self.stashing_tab.update()?;
 		self.stashlist_tab.update()?;
 		self.reset_popup.update()?;
+		self.git_config_popup.update()?;
+		self.update_commit_graph();
 
 		self.update_commands();
 
 		Ok(())
 	}
 
+	/// runs a command received from an editor integration
+	pub fn ipc_command(&mut self, cmd: IpcCommand) -> Result<()> {
+		log::trace!("ipc command: {:?}", cmd);
+
+		match cmd {
+			IpcCommand::Refresh => {}
+			IpcCommand::Blame { path, line } => {
+				self.queue.push(InternalEvent::OpenPopup(
+					StackablePopupOpen::BlameFile(BlameFileOpen {
+						file_path: path,
+						commit_id: None,
+						selection: line
+							.map(|line| line.saturating_sub(1)),
+					}),
+				));
+			}
+			IpcCommand::OpenCommit(rev) => {
+				match sync::rev_parse_commit(
+					&self.repo.borrow(),
+					&rev,
+				) {
+					Ok(id) => {
+						self.queue.push(InternalEvent::OpenPopup(
+							StackablePopupOpen::InspectCommit(
+								InspectCommitOpen::new(id),
+							),
+						));
+					}
+					Err(e) => {
+						self.queue.push(InternalEvent::ShowErrorMsg(
+							format!("unknown revision '{rev}':\n{e}"),
+						));
+					}
+				}
+			}
+		}
+
+		self.process_queue(NeedsUpdate::ALL)
+	}
+
 	///
 	pub fn update_async(
 		&mut self,
@@ -411,11 +596,34 @@ impl App {
 			self.file_revlog_popup.update_git(ev)?;
 			self.inspect_commit_popup.update_git(ev)?;
 			self.compare_commits_popup.update_git(ev)?;
+			self.stash_worktree_popup.update_git(ev)?;
 			self.push_popup.update_git(ev)?;
+			self.force_push_popup.update_git(ev);
+			self.hook_progress_popup.update_git(ev);
 			self.push_tags_popup.update_git(ev)?;
 			self.pull_popup.update_git(ev);
 			self.fetch_popup.update_git(ev);
+			self.notes_sync_popup.update_git(ev);
+			self.clone_popup.update_git(ev)?;
 			self.select_branch_popup.update_git(ev)?;
+			self.restore_file_popup.update_git(ev)?;
+			self.reset_popup.update_git(ev);
+
+			if ev == AsyncGitNotification::CommitGraph {
+				if let Some(Err(e)) = self
+					.commit_graph
+					.take_last()
+					.and_then(|job| job.result())
+				{
+					log::error!("commit-graph error: {}", e);
+				}
+			}
+
+			if ev == AsyncGitNotification::AutoFetch {
+				self.queue.push(InternalEvent::Update(
+					NeedsUpdate::ALL | NeedsUpdate::BRANCHES,
+				));
+			}
 		}
 
 		self.files_tab.update_async(ev)?;
@@ -451,13 +659,20 @@ impl App {
 			|| self.file_revlog_popup.any_work_pending()
 			|| self.inspect_commit_popup.any_work_pending()
 			|| self.compare_commits_popup.any_work_pending()
+			|| self.stash_worktree_popup.any_work_pending()
 			|| self.input.is_state_changing()
 			|| self.push_popup.any_work_pending()
+			|| self.force_push_popup.any_work_pending()
+			|| self.hook_progress_popup.any_work_pending()
 			|| self.push_tags_popup.any_work_pending()
 			|| self.pull_popup.any_work_pending()
 			|| self.fetch_popup.any_work_pending()
+			|| self.notes_sync_popup.any_work_pending()
+			|| self.clone_popup.any_work_pending()
 			|| self.revision_files_popup.any_work_pending()
 			|| self.tags_popup.any_work_pending()
+			|| self.restore_file_popup.any_work_pending()
+			|| self.reset_popup.any_work_pending()
 	}
 
 	///
@@ -477,9 +692,15 @@ impl App {
 		self,
 		[
 			log_search_popup,
+			log_refs_popup,
 			fuzzy_find_popup,
 			msg_popup,
 			confirm_popup,
+			restore_file_popup,
+			revision_popup,
+			hook_progress_popup,
+			pre_commit_popup,
+			signing_key_popup,
 			commit_popup,
 			blame_file_popup,
 			file_revlog_popup,
@@ -488,20 +709,47 @@ impl App {
 			compare_commits_popup,
 			external_editor_popup,
 			push_popup,
+			force_push_popup,
+			flow_popup,
+			symrefs_popup,
 			push_tags_popup,
 			pull_popup,
+			pull_options_popup,
+			fetch_options_popup,
+			remote_target_popup,
 			fetch_popup,
+			clone_popup,
 			tag_commit_popup,
+			commit_note_popup,
+			notes_sync_popup,
+			format_patch_popup,
+			apply_patch_popup,
+			revert_merge_popup,
+			stash_worktree_popup,
 			reset_popup,
+			edit_hunk_popup,
+			conflict_editor_popup,
+			rebase_interactive_popup,
+			bisect_popup,
 			create_branch_popup,
 			create_remote_popup,
 			rename_remote_popup,
+			blame_ignore_revs_popup,
 			update_remote_url_popup,
 			remotes_popup,
+			git_config_edit_popup,
+			git_config_popup,
 			rename_branch_popup,
+			reflog_popup,
+			undo_popup,
+			discarded_popup,
+			clean_popup,
+			ignore_pattern_popup,
+			ignore_edit_popup,
 			select_branch_popup,
 			revision_files_popup,
 			submodule_popup,
+			worktrees_popup,
 			tags_popup,
 			options_popup,
 			help_popup,
@@ -517,31 +765,64 @@ impl App {
 		self,
 		[
 			commit_popup,
+			hook_progress_popup,
+			pre_commit_popup,
+			signing_key_popup,
 			stashmsg_popup,
 			help_popup,
+			reflog_popup,
+			undo_popup,
+			discarded_popup,
+			clean_popup,
+			ignore_pattern_popup,
+			ignore_edit_popup,
 			inspect_commit_popup,
 			compare_commits_popup,
 			blame_file_popup,
 			file_revlog_popup,
 			external_editor_popup,
 			tag_commit_popup,
+			commit_note_popup,
+			notes_sync_popup,
+			format_patch_popup,
+			apply_patch_popup,
+			revert_merge_popup,
+			stash_worktree_popup,
 			select_branch_popup,
 			remotes_popup,
 			create_remote_popup,
 			rename_remote_popup,
+			blame_ignore_revs_popup,
 			update_remote_url_popup,
+			git_config_popup,
+			git_config_edit_popup,
 			submodule_popup,
+			worktrees_popup,
 			tags_popup,
 			reset_popup,
+			edit_hunk_popup,
+			conflict_editor_popup,
+			rebase_interactive_popup,
+			bisect_popup,
+			restore_file_popup,
+			revision_popup,
 			create_branch_popup,
 			rename_branch_popup,
 			revision_files_popup,
 			fuzzy_find_popup,
 			log_search_popup,
+			log_refs_popup,
 			push_popup,
+			force_push_popup,
+			flow_popup,
+			symrefs_popup,
 			push_tags_popup,
 			pull_popup,
+			pull_options_popup,
+			fetch_options_popup,
+			remote_target_popup,
 			fetch_popup,
+			clone_popup,
 			options_popup,
 			confirm_popup,
 			msg_popup
@@ -581,13 +862,27 @@ impl App {
 		]
 	}
 
+	/// bare repositories have no worktree to show status or stashes of
+	const fn tab_available(&self, tab: usize) -> bool {
+		!self.bare || matches!(tab, 1 | 2)
+	}
+
 	fn toggle_tabs(&mut self, reverse: bool) -> Result<()> {
 		let tabs_len = self.get_tabs().len();
-		let new_tab = if reverse {
-			self.tab.wrapping_sub(1).min(tabs_len.saturating_sub(1))
-		} else {
-			self.tab.saturating_add(1) % tabs_len
-		};
+		let mut new_tab = self.tab;
+		loop {
+			new_tab = if reverse {
+				new_tab
+					.wrapping_sub(1)
+					.min(tabs_len.saturating_sub(1))
+			} else {
+				new_tab.saturating_add(1) % tabs_len
+			};
+
+			if self.tab_available(new_tab) {
+				break;
+			}
+		}
 
 		self.set_tab(new_tab)
 	}
@@ -609,6 +904,8 @@ impl App {
 	}
 
 	fn set_tab(&mut self, tab: usize) -> Result<()> {
+		let tab = if self.tab_available(tab) { tab } else { 1 };
+
 		let tabs = self.get_tabs();
 		for (i, t) in tabs.into_iter().enumerate() {
 			if tab == i {
@@ -635,6 +932,28 @@ impl App {
 		Ok(())
 	}
 
+	/// keeps the commit-graph up to date so walking the history stays
+	/// fast on huge repos, checking at most every
+	/// `COMMIT_GRAPH_CHECK_INTERVAL`
+	fn update_commit_graph(&mut self) {
+		if self.commit_graph.is_pending()
+			|| self.commit_graph_checked.is_some_and(|checked| {
+				checked.elapsed() < COMMIT_GRAPH_CHECK_INTERVAL
+			}) {
+			return;
+		}
+
+		self.commit_graph_checked = Some(Instant::now());
+
+		if sync::commit_graph_needs_update(&self.repo.borrow())
+			.unwrap_or_default()
+		{
+			self.commit_graph.spawn(AsyncCommitGraphJob::new(
+				self.repo.borrow().clone(),
+			));
+		}
+	}
+
 	fn update_commands(&mut self) {
 		if self.help_popup.is_visible() {
 			self.help_popup.set_cmds(self.commands(true));
@@ -656,7 +975,9 @@ impl App {
 			self.status_tab.update_diff()?;
 			self.inspect_commit_popup.update_diff()?;
 			self.compare_commits_popup.update_diff()?;
+			self.stash_worktree_popup.update_diff()?;
 			self.file_revlog_popup.update_diff()?;
+			self.restore_file_popup.update_diff()?;
 		}
 		if flags.contains(NeedsUpdate::COMMANDS) {
 			self.update_commands();
@@ -668,9 +989,23 @@ impl App {
 			self.remotes_popup.update_remotes()?;
 		}
 
+		self.auto_fetch.set_paused(self.network_work_pending());
+
 		Ok(())
 	}
 
+	/// user operations a background fetch would race with, the force
+	/// push popup relies on its lease check while it is open
+	fn network_work_pending(&self) -> bool {
+		self.push_popup.any_work_pending()
+			|| self.force_push_popup.is_visible()
+			|| self.push_tags_popup.any_work_pending()
+			|| self.pull_popup.any_work_pending()
+			|| self.fetch_popup.any_work_pending()
+			|| self.notes_sync_popup.any_work_pending()
+			|| self.tags_popup.any_work_pending()
+	}
+
 	fn open_popup(
 		&mut self,
 		popup: StackablePopupOpen,
@@ -741,29 +1076,61 @@ impl App {
 			InternalEvent::RewordCommit(id) => {
 				self.commit_popup.open(Some(id))?;
 			}
-			InternalEvent::PopupStashing(opts) => {
-				self.stashmsg_popup.options(opts);
+			InternalEvent::PopupStashing(opts, selection) => {
+				self.stashmsg_popup.options(opts, selection);
 				self.stashmsg_popup.show()?;
 			}
 			InternalEvent::TagCommit(id) => {
 				self.tag_commit_popup.open(id)?;
 			}
+			InternalEvent::OpenCommitNotePopup(id) => {
+				self.commit_note_popup.open(id)?;
+			}
+			InternalEvent::SyncNotes(sync) => {
+				self.notes_sync_popup.open(sync)?;
+			}
+			InternalEvent::OpenFormatPatchPopup(commits) => {
+				self.format_patch_popup.open(commits)?;
+			}
+			InternalEvent::OpenApplyPatchPopup => {
+				self.apply_patch_popup.open()?;
+			}
+			InternalEvent::OpenStashWorktreePopup(id) => {
+				self.stash_worktree_popup.open(id)?;
+			}
+			InternalEvent::OpenRevertMergePopup(id) => {
+				self.revert_merge_popup.open(id)?;
+			}
 			InternalEvent::CreateRemote => {
 				self.create_remote_popup.open()?;
 			}
 			InternalEvent::RenameRemote(cur_name) => {
 				self.rename_remote_popup.open(cur_name)?;
 			}
-			InternalEvent::UpdateRemoteUrl(remote_name, cur_url) => {
-				self.update_remote_url_popup
-					.open(remote_name, cur_url)?;
+			InternalEvent::UpdateRemoteUrl(
+				remote_name,
+				cur_url,
+				push,
+			) => {
+				self.update_remote_url_popup.open(
+					remote_name,
+					cur_url,
+					push,
+				)?;
 			}
 			InternalEvent::ViewRemotes => {
 				self.remotes_popup.open()?;
 			}
+			InternalEvent::EditGitConfig { origin, key, value } => {
+				self.git_config_edit_popup
+					.open(origin, key, value)?;
+			}
 			InternalEvent::CreateBranch => {
 				self.create_branch_popup.open()?;
 			}
+			InternalEvent::CreateBranchAt(commit) => {
+				self.create_branch_popup.open_at(commit)?;
+			}
 			InternalEvent::RenameBranch(branch_ref, cur_name) => {
 				self.rename_branch_popup
 					.open(branch_ref, cur_name)?;
@@ -774,6 +1141,15 @@ impl App {
 			InternalEvent::ViewSubmodules => {
 				self.submodule_popup.open()?;
 			}
+			InternalEvent::ViewWorktrees => {
+				self.worktrees_popup.open()?;
+			}
+			InternalEvent::OpenEditHunk(path, hunk) => {
+				self.edit_hunk_popup.open(path, hunk)?;
+			}
+			InternalEvent::OpenConflictEditor(path) => {
+				self.conflict_editor_popup.open(&path)?;
+			}
 			InternalEvent::Tags => {
 				self.tags_popup.open()?;
 			}
@@ -792,33 +1168,167 @@ impl App {
 					flags.insert(NeedsUpdate::ALL);
 				}
 			}
+			InternalEvent::OpenLogForPath(path) => {
+				self.revlog.set_pathspec(Some(path))?;
+				self.revision_files_popup.hide();
+				self.switch_to_tab(&AppTabs::Log)?;
+				flags.insert(NeedsUpdate::ALL);
+			}
+			InternalEvent::OpenLogRefsPopup => {
+				self.log_refs_popup.open(&self.revlog.log_refs())?;
+			}
+			InternalEvent::SetLogRefs(refs) => {
+				self.revlog.set_log_refs(refs)?;
+				flags.insert(NeedsUpdate::ALL);
+			}
 			InternalEvent::OpenExternalEditor(path) => {
 				self.input.set_polling(false);
 				self.external_editor_popup.show()?;
 				self.file_to_open = path;
 				flags.insert(NeedsUpdate::COMMANDS);
 			}
-			InternalEvent::Push(branch, push_type, force, delete) => {
-				self.push_popup
-					.push(branch, push_type, force, delete)?;
+			InternalEvent::OpenExternalTool(tool) => {
+				self.input.set_polling(false);
+				self.external_editor_popup.show()?;
+				self.tool_to_open = Some(tool);
+				flags.insert(NeedsUpdate::COMMANDS);
+			}
+			InternalEvent::Push(
+				branch,
+				push_type,
+				force,
+				delete,
+				no_verify,
+			) => {
+				self.push_popup.push(
+					branch, push_type, force, delete, no_verify,
+				)?;
+				flags.insert(NeedsUpdate::ALL);
+			}
+			InternalEvent::PushWithLease(branch, lease) => {
+				self.push_popup.push_with_lease(branch, lease)?;
+				flags.insert(NeedsUpdate::ALL);
+			}
+			InternalEvent::OpenHookProgress => {
+				self.hook_progress_popup.open()?;
 				flags.insert(NeedsUpdate::ALL);
 			}
-			InternalEvent::Pull(branch) => {
-				if let Err(error) = self.pull_popup.fetch(branch) {
+			InternalEvent::PreCommitFinished(result) => {
+				self.commit_popup.pre_commit_finished(result)?;
+				flags.insert(NeedsUpdate::ALL);
+			}
+			InternalEvent::OpenPreCommitResults(results) => {
+				self.pre_commit_popup.open(results)?;
+				flags.insert(NeedsUpdate::ALL);
+			}
+			InternalEvent::OpenForcePushPopup(branch) => {
+				self.force_push_popup.open(branch)?;
+				flags.insert(NeedsUpdate::ALL);
+			}
+			InternalEvent::OpenSigningKeyPopup => {
+				self.signing_key_popup.open()?;
+				flags.insert(NeedsUpdate::ALL);
+			}
+			InternalEvent::OpenRebaseInteractive(id) => {
+				self.rebase_interactive_popup.open(id)?;
+				flags.insert(NeedsUpdate::ALL);
+			}
+			InternalEvent::OpenRebaseAutosquash(id) => {
+				self.rebase_interactive_popup.open_autosquash(id)?;
+				flags.insert(NeedsUpdate::ALL);
+			}
+			InternalEvent::OpenCommitFixup(id, squash) => {
+				self.commit_popup.open_fixup(id, squash)?;
+			}
+			InternalEvent::OpenBisectPopup(id) => {
+				self.bisect_popup.open(id)?;
+				flags.insert(NeedsUpdate::ALL);
+			}
+			InternalEvent::OpenReflogPopup => {
+				self.reflog_popup.open()?;
+				flags.insert(NeedsUpdate::ALL);
+			}
+			InternalEvent::OpenBlameIgnoreRevsPopup => {
+				self.blame_ignore_revs_popup.open()?;
+				flags
+					.insert(NeedsUpdate::ALL | NeedsUpdate::COMMANDS);
+			}
+			InternalEvent::OpenUndoPopup => {
+				self.undo_popup.open()?;
+				flags.insert(NeedsUpdate::ALL);
+			}
+			InternalEvent::OpenDiscardedPopup => {
+				self.discarded_popup.open()?;
+				flags.insert(NeedsUpdate::ALL);
+			}
+			InternalEvent::OpenCleanPopup => {
+				self.clean_popup.open()?;
+				flags.insert(NeedsUpdate::ALL);
+			}
+			InternalEvent::OpenIgnorePatternPopup(path) => {
+				self.ignore_pattern_popup.open(path)?;
+				flags.insert(NeedsUpdate::ALL);
+			}
+			InternalEvent::OpenIgnoreEditPopup(file) => {
+				self.ignore_edit_popup.open(file)?;
+				flags.insert(NeedsUpdate::ALL);
+			}
+			InternalEvent::OpenSymRefsPopup => {
+				self.symrefs_popup.open()?;
+				flags.insert(NeedsUpdate::ALL);
+			}
+			InternalEvent::OpenPullOptionsPopup(branch) => {
+				self.pull_options_popup.open(branch)?;
+				flags.insert(NeedsUpdate::ALL);
+			}
+			InternalEvent::OpenFetchOptionsPopup => {
+				self.fetch_options_popup.open()?;
+				flags.insert(NeedsUpdate::ALL);
+			}
+			InternalEvent::OpenRemoteTargetPopup(branch, push) => {
+				self.remote_target_popup.open(branch, push)?;
+				flags.insert(NeedsUpdate::ALL);
+			}
+			InternalEvent::PushTo(branch, target) => {
+				self.push_popup.push_to(branch, target)?;
+				flags.insert(NeedsUpdate::ALL);
+			}
+			InternalEvent::PullFrom(branch, target) => {
+				if let Err(error) =
+					self.pull_popup.fetch_from(branch, target)
+				{
 					self.queue.push(InternalEvent::ShowErrorMsg(
 						error.to_string(),
 					));
 				}
 				flags.insert(NeedsUpdate::ALL);
 			}
-			InternalEvent::FetchRemotes => {
-				if let Err(error) = self.fetch_popup.fetch() {
+			InternalEvent::OpenFlowPopup => {
+				self.flow_popup.open()?;
+				flags.insert(NeedsUpdate::ALL);
+			}
+			InternalEvent::Pull(branch, options) => {
+				if let Err(error) =
+					self.pull_popup.fetch(branch, options)
+				{
 					self.queue.push(InternalEvent::ShowErrorMsg(
 						error.to_string(),
 					));
 				}
 				flags.insert(NeedsUpdate::ALL);
 			}
+			InternalEvent::FetchRemotes(options) => {
+				if let Err(error) = self.fetch_popup.fetch(options) {
+					self.queue.push(InternalEvent::ShowErrorMsg(
+						error.to_string(),
+					));
+				}
+				flags.insert(NeedsUpdate::ALL);
+			}
+			InternalEvent::PushStack(branches) => {
+				self.push_popup.push_stack(branches)?;
+				flags.insert(NeedsUpdate::ALL);
+			}
 			InternalEvent::PushTags => {
 				self.push_tags_popup.push_tags()?;
 				flags.insert(NeedsUpdate::ALL);
@@ -841,10 +1351,16 @@ impl App {
 					AppOption::StatusShowUntracked => {
 						self.status_tab.update()?;
 					}
+					AppOption::StatusDiscardBackup => (),
 					AppOption::DiffContextLines
 					| AppOption::DiffIgnoreWhitespaces
-					| AppOption::DiffInterhunkLines => {
+					| AppOption::DiffIgnoreWhitespaceChange
+					| AppOption::DiffIgnoreBlankLines
+					| AppOption::DiffAlgorithm
+					| AppOption::DiffInterhunkLines
+					| AppOption::DiffSyntaxHighlight => {
 						self.status_tab.update_diff()?;
+						flags.insert(NeedsUpdate::DIFF);
 					}
 				}
 
@@ -897,11 +1413,21 @@ impl App {
 				);
 				//TODO: validate this is a valid repo first, so we can show proper error otherwise
 				self.do_quit =
-					QuitState::OpenSubmodule(submodule_repo_path);
+					QuitState::OpenRepo(submodule_repo_path);
+			}
+			InternalEvent::OpenNewRepo(path) => {
+				self.do_quit =
+					QuitState::OpenRepo(RepoPath::Path(path));
 			}
 			InternalEvent::OpenResetPopup(id) => {
 				self.reset_popup.open(id)?;
 			}
+			InternalEvent::OpenRestoreFilePopup(id, path) => {
+				self.restore_file_popup.open(id, path)?;
+			}
+			InternalEvent::OpenRevisionPopup(target) => {
+				self.revision_popup.open(target)?;
+			}
 			InternalEvent::CommitSearch(options) => {
 				self.revlog.search(options);
 			}
@@ -910,15 +1436,21 @@ impl App {
 		Ok(flags)
 	}
 
+	#[allow(clippy::too_many_lines)]
 	fn process_confirmed_action(
 		&mut self,
 		action: Action,
 		flags: &mut NeedsUpdate,
 	) -> Result<()> {
+		self.record_undo(&action);
+
 		match action {
 			Action::Reset(r) => {
 				self.status_tab.reset(&r);
 			}
+			Action::DiscardSubmodule(path) => {
+				self.status_tab.discard_submodule(&path);
+			}
 			Action::StashDrop(_) | Action::StashPop(_) => {
 				if let Err(e) = self
 					.stashlist_tab
@@ -971,25 +1503,39 @@ impl App {
 					PushType::Tag,
 					false,
 					true,
+					false,
 				));
 			}
-			Action::ForcePush(branch, force) => {
+			Action::PushProtected(branch, no_verify) => {
 				self.queue.push(InternalEvent::Push(
 					branch,
 					PushType::Branch,
-					force,
 					false,
+					false,
+					no_verify,
 				));
 			}
+			Action::PushStack(branches) => {
+				self.queue.push(InternalEvent::PushStack(branches));
+			}
 			Action::PullMerge { rebase, .. } => {
 				self.pull_popup.try_conflict_free_merge(rebase);
 			}
-			Action::AbortRevert | Action::AbortMerge => {
+			Action::AbortMerge => {
 				self.status_tab.revert_pending_state();
 			}
+			Action::AbortRevert => {
+				self.status_tab.abort_revert();
+			}
 			Action::AbortRebase => {
 				self.status_tab.abort_rebase();
 			}
+			Action::AbortApplyMailbox => {
+				self.status_tab.abort_apply_mailbox();
+			}
+			Action::AbortCherryPick => {
+				self.status_tab.abort_cherry_pick();
+			}
 			Action::UndoCommit => {
 				try_or_popup!(
 					self,
@@ -997,6 +1543,29 @@ impl App {
 					undo_last_commit(&self.repo.borrow())
 				);
 			}
+			Action::RewordPushedCommit(id) => {
+				self.queue.push(InternalEvent::RewordCommit(id));
+			}
+			Action::RestoreDiscarded(id, _) => {
+				try_or_popup!(
+					self,
+					"restore failed:",
+					sync::discard_backup_restore(
+						&self.repo.borrow(),
+						id
+					)
+				);
+			}
+			Action::Clean { paths, backup } => {
+				self.clean(&paths, backup);
+			}
+			Action::UndoRestore(id, _) => {
+				try_or_popup!(
+					self,
+					"undo failed:",
+					sync::undo_restore(&self.repo.borrow(), id)
+				);
+			}
 		};
 
 		flags.insert(NeedsUpdate::ALL);
@@ -1004,6 +1573,63 @@ impl App {
 		Ok(())
 	}
 
+	/// records an undo snapshot before destructive actions and backs
+	/// up discarded content if enabled
+	fn record_undo(&self, action: &Action) {
+		let (operation, discarded) = match action {
+			Action::Reset(item) => (
+				strings::undo_op_discard(&item.path),
+				Some(&item.path),
+			),
+			Action::ResetHunk(path, _) => {
+				(strings::undo_op_discard_hunk(path), Some(path))
+			}
+			Action::ResetLines(path, lines) => (
+				strings::undo_op_discard_lines(path, lines.len()),
+				Some(path),
+			),
+			Action::DeleteLocalBranch(branch_ref) => {
+				(strings::undo_op_delete_branch(branch_ref), None)
+			}
+			Action::UndoCommit => {
+				(strings::undo_op_undo_commit(), None)
+			}
+			_ => return,
+		};
+
+		if let Some(path) = discarded
+			.filter(|_| self.options.borrow().discard_backup())
+		{
+			if let Err(e) = sync::discard_backup(
+				&self.repo.borrow(),
+				path,
+				&operation,
+			) {
+				log::error!("discard backup failed: {e}");
+			}
+		}
+
+		record_undo(&self.repo.borrow(), &operation);
+	}
+
+	fn clean(&self, paths: &[String], backup: bool) {
+		if backup {
+			if let Err(e) = sync::discard_backup_paths(
+				&self.repo.borrow(),
+				paths,
+				&strings::clean_backup(paths.len()),
+			) {
+				log::error!("discard backup failed: {e}");
+			}
+		}
+
+		try_or_popup!(
+			self,
+			"clean failed:",
+			sync::clean_paths(&self.repo.borrow(), paths)
+		);
+	}
+
 	fn delete_tag(&mut self, tag_name: String) -> Result<()> {
 		if let Err(error) =
 			sync::delete_tag(&self.repo.borrow(), &tag_name)
@@ -1041,6 +1667,7 @@ impl App {
 						PushType::Branch,
 						false,
 						true,
+						false,
 					)
 				},
 			),
@@ -1109,6 +1736,31 @@ impl App {
 			)
 			.order(order::NAV),
 		);
+		res.push(
+			CommandInfo::new(
+				strings::commands::git_config_popup(&self.key_config),
+				true,
+				!self.any_popup_visible(),
+			)
+			.order(order::NAV),
+		);
+		res.push(
+			CommandInfo::new(
+				strings::commands::clone_popup(&self.key_config),
+				true,
+				!self.any_popup_visible(),
+			)
+			.order(order::NAV),
+		);
+
+		res.push(
+			CommandInfo::new(
+				strings::commands::undo_history(&self.key_config),
+				true,
+				!self.any_popup_visible(),
+			)
+			.order(order::NAV),
+		);
 
 		res.push(
 			CommandInfo::new(
@@ -1123,6 +1775,39 @@ impl App {
 	}
 
 	//TODO: make this dynamic
+	/// top bar indicator of the background fetch, `None` while
+	/// disabled
+	fn auto_fetch_status(&self) -> Option<Span<'static>> {
+		let status = self.auto_fetch.status();
+		status.interval?;
+
+		if status.pending {
+			return Some(Span::styled(
+				strings::auto_fetch_pending(),
+				self.theme.text(false, false),
+			));
+		}
+		if let Some(error) = &status.last_error {
+			return Some(Span::styled(
+				strings::auto_fetch_failed(
+					error.lines().next().unwrap_or_default(),
+				),
+				self.theme.text_danger(),
+			));
+		}
+
+		let secs = status.last_fetch?.elapsed().as_secs();
+		let text = if secs < 60 {
+			strings::auto_fetch_just_now()
+		} else if secs < 60 * 60 {
+			strings::auto_fetch_ago_minutes(secs / 60)
+		} else {
+			strings::auto_fetch_ago_hours(secs / (60 * 60))
+		};
+
+		Some(Span::styled(text, self.theme.text(false, false)))
+	}
+
 	fn draw_top_bar(&self, f: &mut Frame, r: Rect) {
 		const DIVIDER_PAD_SPACES: usize = 2;
 		const SIDE_PADS: usize = 2;
@@ -1133,13 +1818,23 @@ impl App {
 			horizontal: 1,
 		});
 
-		let tab_labels = [
-			Span::raw(strings::tab_status(&self.key_config)),
-			Span::raw(strings::tab_log(&self.key_config)),
-			Span::raw(strings::tab_files(&self.key_config)),
-			Span::raw(strings::tab_stashing(&self.key_config)),
-			Span::raw(strings::tab_stashes(&self.key_config)),
-		];
+		let tab_labels: Vec<Span> = [
+			strings::tab_status(&self.key_config),
+			strings::tab_log(&self.key_config),
+			strings::tab_files(&self.key_config),
+			strings::tab_stashing(&self.key_config),
+			strings::tab_stashes(&self.key_config),
+		]
+		.into_iter()
+		.enumerate()
+		.map(|(i, label)| {
+			if self.tab_available(i) {
+				Span::raw(label)
+			} else {
+				Span::styled(label, self.theme.text(false, false))
+			}
+		})
+		.collect();
 		let divider = strings::tab_divider(&self.key_config);
 
 		// heuristic, since tui doesn't provide a way to know
@@ -1180,15 +1875,23 @@ impl App {
 			table_area,
 		);
 
+		let mut spans = Vec::new();
+		if let Some(status) = self.auto_fetch_status() {
+			spans.push(Span::styled(
+				format!("{}  ", status.content),
+				status.style,
+			));
+		}
+		let path_width = (text_area.width as usize)
+			.saturating_sub(spans.iter().map(Span::width).sum());
+		spans.push(Span::styled(
+			ellipsis_trim_start(&self.repo_path_text, path_width),
+			self.theme.title(false),
+		));
+
 		f.render_widget(
-			Paragraph::new(Line::from(vec![Span::styled(
-				ellipsis_trim_start(
-					&self.repo_path_text,
-					text_area.width as usize,
-				),
-				self.theme.title(false),
-			)]))
-			.alignment(Alignment::Right),
+			Paragraph::new(Line::from(spans))
+				.alignment(Alignment::Right),
 			text_area,
 		);
 	}
@@ -1,6 +1,6 @@
 use crate::bug_report;
 use anyhow::{anyhow, Result};
-use asyncgit::sync::RepoPath;
+use asyncgit::sync::{self, LocationsFormat, RepoPath};
 use clap::{
 	builder::ArgPredicate, crate_authors, crate_description,
 	crate_name, Arg, Command as ClapApp,
@@ -9,13 +9,14 @@ use simplelog::{Config, LevelFilter, WriteLogger};
 use std::{
 	env,
 	fs::{self, File},
-	path::PathBuf,
+	path::{Path, PathBuf},
 };
 
 pub struct CliArgs {
 	pub theme: PathBuf,
 	pub repo_path: RepoPath,
 	pub notify_watcher: bool,
+	pub ipc_socket: Option<PathBuf>,
 }
 
 pub fn process_cmdline() -> Result<CliArgs> {
@@ -45,6 +46,19 @@ pub fn process_cmdline() -> Result<CliArgs> {
 		RepoPath::Path(gitdir)
 	};
 
+	if let Some(source) = arg_matches.get_one::<String>("export") {
+		let format = match arg_matches
+			.get_one::<String>("export-format")
+			.map(String::as_str)
+		{
+			Some("sarif") => LocationsFormat::Sarif,
+			_ => LocationsFormat::Quickfix,
+		};
+
+		export_locations(&repo_path, source == "conflicts", format)?;
+		std::process::exit(0);
+	}
+
 	let arg_theme = arg_matches
 		.get_one::<String>("theme")
 		.map_or_else(|| PathBuf::from("theme.ron"), PathBuf::from);
@@ -56,10 +70,14 @@ pub fn process_cmdline() -> Result<CliArgs> {
 	let notify_watcher: bool =
 		*arg_matches.get_one("watcher").unwrap_or(&false);
 
+	let ipc_socket =
+		arg_matches.get_one::<String>("ipc").map(PathBuf::from);
+
 	Ok(CliArgs {
 		theme,
 		repo_path,
 		notify_watcher,
+		ipc_socket,
 	})
 }
 
@@ -106,17 +124,43 @@ fn app() -> ClapApp {
 				.long("watcher")
 				.action(clap::ArgAction::SetTrue),
 		)
+		.arg(
+			Arg::new("ipc")
+				.help("Accept commands (refresh, blame <file>[:<line>], commit <rev>) from editor integrations on a unix socket at this path (not supported on Windows)")
+				.long("ipc")
+				.value_name("SOCKET")
+				.num_args(1),
+		)
 		.arg(
 			Arg::new("bugreport")
 				.help("Generate a bug report")
 				.long("bugreport")
 				.action(clap::ArgAction::SetTrue),
 		)
+		.arg(
+			Arg::new("export")
+				.help("Print the conflict markers or diff hunks of the repository for editors to jump to and exit")
+				.long("export")
+				.value_name("LOCATIONS")
+				.value_parser(["conflicts", "hunks"])
+				.num_args(1),
+		)
+		.arg(
+			Arg::new("export-format")
+				.help("Format of --export: vim quickfix list or SARIF log")
+				.long("export-format")
+				.value_name("FORMAT")
+				.value_parser(["quickfix", "sarif"])
+				.default_value("quickfix")
+				.requires("export")
+				.num_args(1),
+		)
 		.arg(
 			Arg::new("directory")
 				.help("Set the git directory")
 				.short('d')
 				.long("directory")
+				.visible_alias("git-dir")
 				.env("GIT_DIR")
 				.num_args(1),
 		)
@@ -125,11 +169,37 @@ fn app() -> ClapApp {
 				.help("Set the working directory")
 				.short('w')
 				.long("workdir")
+				.visible_alias("work-tree")
 				.env("GIT_WORK_TREE")
 				.num_args(1),
 		)
 }
 
+fn export_locations(
+	repo_path: &RepoPath,
+	conflicts: bool,
+	format: LocationsFormat,
+) -> Result<()> {
+	let locations = if conflicts {
+		sync::get_conflict_locations(repo_path)?
+	} else {
+		sync::get_hunk_locations(repo_path)?
+	};
+
+	let work_dir = sync::utils::repo_work_dir(repo_path)?;
+
+	print!(
+		"{}",
+		sync::format_locations(
+			&locations,
+			Path::new(&work_dir),
+			format
+		)?
+	);
+
+	Ok(())
+}
+
 fn setup_logging(path_override: Option<PathBuf>) -> Result<()> {
 	let path = if let Some(path) = path_override {
 		path
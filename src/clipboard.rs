@@ -49,6 +49,32 @@ fn exec_copy_with_args(
 	}
 }
 
+fn exec_paste_with_args(
+	command: &str,
+	args: &[&str],
+) -> Result<String> {
+	let binary = which(command)
+		.ok()
+		.unwrap_or_else(|| PathBuf::from(command));
+
+	let out = Command::new(binary)
+		.args(args)
+		.stdin(Stdio::null())
+		.output()
+		.map_err(|e| anyhow!("`{:?}`: {}", command, e))?;
+
+	if out.status.success() {
+		Ok(String::from_utf8_lossy(&out.stdout).into_owned())
+	} else {
+		let msg = if out.stderr.is_empty() {
+			format!("{}", out.status).into()
+		} else {
+			String::from_utf8_lossy(&out.stderr)
+		};
+		Err(anyhow!("`{command:?}`: {msg}"))
+	}
+}
+
 // Implementation taken from https://crates.io/crates/wsl.
 // Using /proc/sys/kernel/osrelease as an authoratative source
 // based on this comment: https://github.com/microsoft/WSL/issues/423#issuecomment-221627364
@@ -142,6 +168,38 @@ pub fn copy_string(text: &str) -> Result<()> {
 	exec_copy("clip", text)
 }
 
+#[cfg(all(target_family = "unix", not(target_os = "macos")))]
+pub fn read_string() -> Result<String> {
+	if std::env::var("WAYLAND_DISPLAY").is_ok() {
+		return exec_paste_with_args("wl-paste", &["--no-newline"]);
+	}
+
+	if is_wsl() {
+		return exec_paste_with_args(
+			"powershell.exe",
+			&["-NoProfile", "-Command", "Get-Clipboard -Raw"],
+		);
+	}
+
+	exec_paste_with_args("xclip", &["-o", "-selection", "clipboard"])
+		.or_else(|_| {
+			exec_paste_with_args("xsel", &["--clipboard", "--output"])
+		})
+}
+
+#[cfg(target_os = "macos")]
+pub fn read_string() -> Result<String> {
+	exec_paste_with_args("pbpaste", &[])
+}
+
+#[cfg(windows)]
+pub fn read_string() -> Result<String> {
+	exec_paste_with_args(
+		"powershell",
+		&["-NoProfile", "-Command", "Get-Clipboard -Raw"],
+	)
+}
+
 #[cfg(test)]
 mod tests {
 	#[test]
@@ -8,17 +8,25 @@ use crate::{
 	components::{CommandInfo, Component, EventState},
 	keys::{key_match, SharedKeyConfig},
 	options::SharedOptions,
-	queue::{Action, InternalEvent, NeedsUpdate, Queue, ResetItem},
+	queue::{
+		Action, ExternalTool, InternalEvent, NeedsUpdate, Queue,
+		ResetItem,
+	},
 	strings, try_or_popup,
 };
 use anyhow::Result;
 use asyncgit::{
-	sync::{self, RepoPathRef},
-	StatusItem, StatusItemType,
+	asyncjob::AsyncSingleJob,
+	sync::{
+		self, DiffToolTarget, IndexFlag, LfsFileState, RepoPathRef,
+		SubmoduleState,
+	},
+	AsyncGitNotification, AsyncLfsLockJob, StatusItem,
+	StatusItemType,
 };
 use crossterm::event::Event;
 use ratatui::{layout::Rect, Frame};
-use std::path::Path;
+use std::{collections::BTreeMap, path::Path};
 
 ///
 pub struct ChangesComponent {
@@ -28,6 +36,7 @@ pub struct ChangesComponent {
 	queue: Queue,
 	key_config: SharedKeyConfig,
 	options: SharedOptions,
+	git_lfs_lock: AsyncSingleJob<AsyncLfsLockJob>,
 }
 
 impl ChangesComponent {
@@ -45,6 +54,36 @@ impl ChangesComponent {
 			key_config: env.key_config.clone(),
 			options: env.options.clone(),
 			repo: env.repo.clone(),
+			git_lfs_lock: AsyncSingleJob::new(env.sender_git.clone()),
+		}
+	}
+
+	///
+	pub fn any_work_pending(&self) -> bool {
+		self.git_lfs_lock.is_pending()
+	}
+
+	///
+	pub fn update_git(&mut self, ev: AsyncGitNotification) {
+		if ev != AsyncGitNotification::LfsLock {
+			return;
+		}
+
+		match self
+			.git_lfs_lock
+			.take_last()
+			.and_then(|job| job.result())
+		{
+			Some(Ok(msg)) if !msg.is_empty() => {
+				self.queue.push(InternalEvent::ShowInfoMsg(msg));
+			}
+			Some(Err(e)) => {
+				log::error!("lfs lock error: {}", e);
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("lfs lock error:\n{e}"),
+				));
+			}
+			_ => (),
 		}
 	}
 
@@ -55,6 +94,27 @@ impl ChangesComponent {
 		Ok(())
 	}
 
+	///
+	pub fn set_submodules(&mut self, submodules: Vec<String>) {
+		self.files.set_submodules(submodules);
+	}
+
+	///
+	pub fn set_submodule_states(
+		&mut self,
+		states: BTreeMap<String, SubmoduleState>,
+	) {
+		self.files.set_submodule_states(states);
+	}
+
+	///
+	pub fn set_lfs_files(
+		&mut self,
+		lfs: BTreeMap<String, LfsFileState>,
+	) {
+		self.files.set_lfs_files(lfs);
+	}
+
 	///
 	pub fn selection(&self) -> Option<FileTreeItem> {
 		self.files.selection()
@@ -76,12 +136,70 @@ impl ChangesComponent {
 		self.files.is_file_selected()
 	}
 
+	/// submodule containing the change at `path`
+	pub fn submodule_of(&self, path: &str) -> Option<String> {
+		self.files.submodule_of(path).cloned()
+	}
+
+	/// submodule whose entry is selected
+	fn selected_submodule(&self) -> Option<String> {
+		self.selection().and_then(|tree_item| {
+			self.files
+				.submodule_of(&tree_item.info.full_path)
+				.filter(|sub| **sub == tree_item.info.full_path)
+				.cloned()
+		})
+	}
+
+	/// selection is a change inside of a submodule, these can not be
+	/// staged, reset or ignored from the parent repo
+	fn is_submodule_change_selected(&self) -> bool {
+		self.selection().is_some_and(|tree_item| {
+			self.files
+				.submodule_of(&tree_item.info.full_path)
+				.is_some_and(|sub| *sub != tree_item.info.full_path)
+		})
+	}
+
 	fn index_add_remove(&self) -> Result<bool> {
+		if self.is_working_dir && self.is_submodule_change_selected()
+		{
+			return Ok(false);
+		}
+
 		if let Some(tree_item) = self.selection() {
 			if self.is_working_dir {
-				if let FileTreeItemKind::File(i) = tree_item.kind {
+				if let Some(path) = self.selected_submodule() {
+					// stages the commit checked out in the submodule
+					sync::stage_add_file(
+						&self.repo.borrow(),
+						Path::new(&path),
+					)?;
+				} else if let FileTreeItemKind::File(i) =
+					tree_item.kind
+				{
 					let path = Path::new(i.path.as_str());
 					match i.status {
+						StatusItemType::Renamed => {
+							if let Some(old) = &i.old_path {
+								sync::stage_rename(
+									&self.repo.borrow(),
+									old,
+									&i.path,
+								)?;
+							} else {
+								sync::stage_add_file(
+									&self.repo.borrow(),
+									path,
+								)?;
+							}
+						}
+						StatusItemType::Ignored => {
+							sync::stage_add_force(
+								&self.repo.borrow(),
+								i.path.as_str(),
+							)?;
+						}
 						StatusItemType::Deleted => {
 							sync::stage_addremoved(
 								&self.repo.borrow(),
@@ -119,7 +237,22 @@ impl ChangesComponent {
 			} else {
 				// this is a staged entry, so lets unstage it
 				let path = tree_item.info.full_path.as_str();
-				sync::reset_stage(&self.repo.borrow(), path)?;
+				match &tree_item.kind {
+					FileTreeItemKind::File(StatusItem {
+						status: StatusItemType::Renamed,
+						old_path: Some(old),
+						..
+					}) => {
+						sync::unstage_rename(
+							&self.repo.borrow(),
+							old,
+							path,
+						)?;
+					}
+					_ => {
+						sync::reset_stage(&self.repo.borrow(), path)?
+					}
+				}
 			}
 
 			return Ok(true);
@@ -147,6 +280,18 @@ impl ChangesComponent {
 	}
 
 	fn dispatch_reset_workdir(&self) -> bool {
+		if let Some(path) = self.selected_submodule() {
+			self.queue.push(InternalEvent::ConfirmAction(
+				Action::DiscardSubmodule(path),
+			));
+
+			return true;
+		}
+
+		if self.is_submodule_change_selected() {
+			return false;
+		}
+
 		if let Some(tree_item) = self.selection() {
 			self.queue.push(InternalEvent::ConfirmAction(
 				Action::Reset(ResetItem {
@@ -160,26 +305,130 @@ impl ChangesComponent {
 	}
 
 	fn add_to_ignore(&self) -> bool {
+		if self.is_submodule_change_selected() {
+			return false;
+		}
+
 		if let Some(tree_item) = self.selection() {
-			if let Err(e) = sync::add_to_ignore(
-				&self.repo.borrow(),
-				&tree_item.info.full_path,
-			) {
-				self.queue.push(InternalEvent::ShowErrorMsg(
-					format!(
-						"ignore error:\n{}\nfile:\n{:?}",
-						e, tree_item.info.full_path
-					),
+			let mut path = tree_item.info.full_path;
+			if matches!(tree_item.kind, FileTreeItemKind::Path(_)) {
+				path.push('/');
+			}
+
+			self.queue
+				.push(InternalEvent::OpenIgnorePatternPopup(path));
+
+			return true;
+		}
+
+		false
+	}
+
+	fn selected_ignored(&self) -> Option<String> {
+		self.selection().and_then(|tree_item| match tree_item.kind {
+			FileTreeItemKind::File(i)
+				if i.status == StatusItemType::Ignored =>
+			{
+				Some(i.path)
+			}
+			_ => None,
+		})
+	}
+
+	fn selected_file(&self) -> Option<StatusItem> {
+		if self.is_submodule_change_selected() {
+			return None;
+		}
+
+		self.selection().and_then(|tree_item| match tree_item.kind {
+			FileTreeItemKind::File(i) => Some(i),
+			FileTreeItemKind::Path(_) => None,
+		})
+	}
+
+	/// selected file with changes `diff.tool` can show
+	fn selected_diff_tool_file(&self) -> Option<String> {
+		self.selected_file()
+			.filter(|item| match item.status {
+				StatusItemType::Ignored
+				| StatusItemType::Conflicted => false,
+				StatusItemType::New => !self.is_working_dir,
+				_ => true,
+			})
+			.map(|item| item.path)
+	}
+
+	fn selected_conflict(&self) -> Option<String> {
+		self.selected_file()
+			.filter(|item| item.status == StatusItemType::Conflicted)
+			.map(|item| item.path)
+	}
+
+	fn toggle_index_flag(&self, flag: IndexFlag) -> Result<()> {
+		let Some(item) = self.selected_file() else {
+			return Ok(());
+		};
+
+		let flagged = match flag {
+			IndexFlag::AssumeUnchanged => {
+				item.status == StatusItemType::AssumeUnchanged
+			}
+			IndexFlag::SkipWorktree => {
+				item.status == StatusItemType::SkipWorktree
+			}
+		};
+
+		sync::set_index_flag(
+			&self.repo.borrow(),
+			&item.path,
+			flag,
+			!flagged,
+		)?;
+
+		Ok(())
+	}
+
+	/// selected file if it is tracked by git LFS
+	fn selected_lfs_file(&self) -> Option<String> {
+		self.selected_file()
+			.map(|item| item.path)
+			.filter(|path| self.files.lfs_state(path).is_some())
+	}
+
+	fn lfs_lock(&mut self, lock: bool) {
+		let Some(path) = self.selected_lfs_file() else {
+			return;
+		};
+
+		self.git_lfs_lock.spawn(AsyncLfsLockJob::new(
+			self.repo.borrow().clone(),
+			path,
+			lock,
+		));
+	}
+
+	fn open_ignore_rule(&self) -> Result<()> {
+		if let Some(path) = self.selected_ignored() {
+			if let Some(rule) =
+				sync::find_ignore_rule(&self.repo.borrow(), &path)?
+			{
+				self.queue.push(InternalEvent::ShowInfoMsg(format!(
+					"{path:?} ignored by:\n{}:{}: {}",
+					rule.file.display(),
+					rule.line,
+					rule.pattern
+				)));
+				self.queue.push(InternalEvent::OpenExternalEditor(
+					Some(rule.file.to_string_lossy().to_string()),
 				));
 			} else {
-				self.queue
-					.push(InternalEvent::Update(NeedsUpdate::ALL));
-
-				return true;
+				self.queue.push(InternalEvent::ShowInfoMsg(format!(
+					"no ignore rule found for {path:?}"
+				)));
 			}
 		}
 
-		false
+		Ok(())
 	}
 }
 
@@ -200,28 +449,89 @@ impl Component for ChangesComponent {
 		self.files.commands(out, force_all);
 
 		let some_selection = self.selection().is_some();
+		let ignored_selection = self.selected_ignored().is_some();
+		let submodule_selection = self.selected_submodule().is_some();
+		let submodule_change = self.is_working_dir
+			&& self.is_submodule_change_selected();
 
-		if self.is_working_dir {
+		if self.is_working_dir && submodule_selection {
 			out.push(CommandInfo::new(
 				strings::commands::stage_all(&self.key_config),
 				true,
-				some_selection && self.focused(),
+				self.focused(),
 			));
 			out.push(CommandInfo::new(
-				strings::commands::stage_item(&self.key_config),
+				strings::commands::stage_submodule(&self.key_config),
+				true,
+				self.focused(),
+			));
+			out.push(CommandInfo::new(
+				strings::commands::discard_submodule(
+					&self.key_config,
+				),
+				true,
+				self.focused(),
+			));
+		} else if self.is_working_dir {
+			out.push(CommandInfo::new(
+				strings::commands::stage_all(&self.key_config),
 				true,
 				some_selection && self.focused(),
 			));
+			if ignored_selection {
+				out.push(CommandInfo::new(
+					strings::commands::stage_ignored_item(
+						&self.key_config,
+					),
+					true,
+					self.focused(),
+				));
+				out.push(CommandInfo::new(
+					strings::commands::open_ignore_rule(
+						&self.key_config,
+					),
+					true,
+					self.focused(),
+				));
+			} else {
+				out.push(CommandInfo::new(
+					strings::commands::stage_item(&self.key_config),
+					!submodule_change,
+					some_selection && self.focused(),
+				));
+			}
 			out.push(CommandInfo::new(
 				strings::commands::reset_item(&self.key_config),
-				true,
+				!submodule_change,
 				some_selection && self.focused(),
 			));
 			out.push(CommandInfo::new(
 				strings::commands::ignore_item(&self.key_config),
-				true,
+				!submodule_change,
 				some_selection && self.focused(),
 			));
+
+			let file = self.selected_file();
+			out.push(CommandInfo::new(
+				strings::commands::toggle_assume_unchanged(
+					&self.key_config,
+					file.as_ref().is_some_and(|i| {
+						i.status == StatusItemType::AssumeUnchanged
+					}),
+				),
+				file.is_some(),
+				self.focused(),
+			));
+			out.push(CommandInfo::new(
+				strings::commands::toggle_skip_worktree(
+					&self.key_config,
+					file.as_ref().is_some_and(|i| {
+						i.status == StatusItemType::SkipWorktree
+					}),
+				),
+				file.is_some(),
+				self.focused(),
+			));
 		} else {
 			out.push(CommandInfo::new(
 				strings::commands::unstage_item(&self.key_config),
@@ -235,6 +545,29 @@ impl Component for ChangesComponent {
 			));
 		}
 
+		out.push(CommandInfo::new(
+			strings::commands::open_diff_tool(&self.key_config),
+			self.selected_diff_tool_file().is_some(),
+			self.focused(),
+		));
+		out.push(CommandInfo::new(
+			strings::commands::open_merge_tool(&self.key_config),
+			true,
+			self.selected_conflict().is_some() && self.focused(),
+		));
+
+		let lfs_file = self.selected_lfs_file().is_some();
+		out.push(CommandInfo::new(
+			strings::commands::lfs_lock(&self.key_config),
+			true,
+			lfs_file && self.focused() && !self.any_work_pending(),
+		));
+		out.push(CommandInfo::new(
+			strings::commands::lfs_unlock(&self.key_config),
+			true,
+			lfs_file && self.focused() && !self.any_work_pending(),
+		));
+
 		CommandBlocking::PassingOn
 	}
 
@@ -289,6 +622,88 @@ impl Component for ChangesComponent {
 					&& !self.is_empty()
 				{
 					Ok(self.add_to_ignore().into())
+				} else if key_match(
+					e,
+					self.key_config.keys.status_ignore_rule,
+				) && self.is_working_dir
+					&& self.selected_ignored().is_some()
+				{
+					try_or_popup!(
+						self,
+						"ignore rule error:",
+						self.open_ignore_rule()
+					);
+					Ok(EventState::Consumed)
+				} else if self.is_working_dir
+					&& (key_match(
+						e,
+						self.key_config.keys.status_assume_unchanged,
+					) || key_match(
+						e,
+						self.key_config.keys.status_skip_worktree,
+					)) {
+					let flag = if key_match(
+						e,
+						self.key_config.keys.status_assume_unchanged,
+					) {
+						IndexFlag::AssumeUnchanged
+					} else {
+						IndexFlag::SkipWorktree
+					};
+
+					try_or_popup!(
+						self,
+						"index flag error:",
+						self.toggle_index_flag(flag)
+					);
+					self.queue.push(InternalEvent::Update(
+						NeedsUpdate::ALL,
+					));
+					Ok(EventState::Consumed)
+				} else if (key_match(
+					e,
+					self.key_config.keys.lfs_lock,
+				) || key_match(
+					e,
+					self.key_config.keys.lfs_unlock,
+				)) && self.selected_lfs_file().is_some()
+					&& !self.any_work_pending()
+				{
+					let lock =
+						key_match(e, self.key_config.keys.lfs_lock);
+					self.lfs_lock(lock);
+					Ok(EventState::Consumed)
+				} else if key_match(
+					e,
+					self.key_config.keys.open_diff_tool,
+				) {
+					if let Some(path) = self.selected_diff_tool_file()
+					{
+						let target = if self.is_working_dir {
+							DiffToolTarget::WorkDir
+						} else {
+							DiffToolTarget::Stage
+						};
+						self.queue.push(
+							InternalEvent::OpenExternalTool(
+								ExternalTool::Diff(path, target),
+							),
+						);
+					}
+					Ok(EventState::Consumed)
+				} else if key_match(
+					e,
+					self.key_config.keys.open_merge_tool,
+				) && self.selected_conflict().is_some()
+				{
+					if let Some(path) = self.selected_conflict() {
+						self.queue.push(
+							InternalEvent::OpenExternalTool(
+								ExternalTool::Merge(path),
+							),
+						);
+					}
+					Ok(EventState::Consumed)
 				} else {
 					Ok(EventState::NotConsumed)
 				};
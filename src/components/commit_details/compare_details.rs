@@ -13,8 +13,13 @@ use crate::{
 	ui::style::SharedTheme,
 };
 use anyhow::Result;
-use asyncgit::sync::{
-	self, commit_files::OldNew, CommitDetails, CommitId, RepoPathRef,
+use asyncgit::{
+	asyncjob::AsyncSingleJob,
+	sync::{
+		self, commit_files::OldNew, CommitDetails, CommitId,
+		RepoPathRef,
+	},
+	AsyncCompareCherryJob, AsyncGitNotification, DuplicateDetails,
 };
 use crossterm::event::Event;
 use ratatui::{
@@ -26,6 +31,12 @@ use ratatui::{
 pub struct CompareDetailsComponent {
 	repo: RepoPathRef,
 	data: Option<OldNew<CommitDetails>>,
+	/// old side commits and their new side duplicate
+	duplicates: DuplicateDetails,
+	/// comparison the duplicates are requested for, pairing up the
+	/// commits of far apart refs takes a while
+	duplicates_ids: Option<OldNew<CommitId>>,
+	git_cherry: AsyncSingleJob<AsyncCompareCherryJob>,
 	theme: SharedTheme,
 	focused: bool,
 }
@@ -35,6 +46,9 @@ impl CompareDetailsComponent {
 	pub fn new(env: &Environment, focused: bool) -> Self {
 		Self {
 			data: None,
+			duplicates: Vec::new(),
+			duplicates_ids: None,
+			git_cherry: AsyncSingleJob::new(env.sender_git.clone()),
 			theme: env.theme.clone(),
 			focused,
 			repo: env.repo.clone(),
@@ -42,6 +56,8 @@ impl CompareDetailsComponent {
 	}
 
 	pub fn set_commits(&mut self, ids: Option<OldNew<CommitId>>) {
+		self.request_duplicates(ids);
+
 		self.data = ids.and_then(|ids| {
 			let old = sync::get_commit_details(
 				&self.repo.borrow(),
@@ -58,6 +74,44 @@ impl CompareDetailsComponent {
 		});
 	}
 
+	fn request_duplicates(&mut self, ids: Option<OldNew<CommitId>>) {
+		if self.duplicates_ids == ids {
+			return;
+		}
+
+		self.duplicates_ids = ids;
+		self.duplicates.clear();
+
+		if let Some(ids) = ids {
+			// commits of the old side whose changes exist as a
+			// different commit on the new side (like `git cherry`)
+			self.git_cherry.spawn(AsyncCompareCherryJob::new(
+				self.repo.borrow().clone(),
+				ids,
+			));
+		}
+	}
+
+	///
+	pub fn any_work_pending(&self) -> bool {
+		self.git_cherry.is_pending()
+	}
+
+	///
+	pub fn update_git(&mut self, ev: AsyncGitNotification) {
+		if ev == AsyncGitNotification::Cherry {
+			if let Some((ids, Ok(duplicates))) = self
+				.git_cherry
+				.take_last()
+				.and_then(|job| job.result())
+			{
+				if self.duplicates_ids == Some(ids) {
+					self.duplicates = duplicates;
+				}
+			}
+		}
+	}
+
 	#[allow(unstable_name_collisions)]
 	fn get_commit_text(&self, data: &CommitDetails) -> Vec<Line> {
 		let mut res = vec![
@@ -98,6 +152,52 @@ impl CompareDetailsComponent {
 
 		res
 	}
+
+	/// both commit infos plus the list of duplicates
+	pub fn height(&self) -> u16 {
+		let duplicates = if self.git_cherry.is_pending() {
+			// title while still searching
+			2
+		} else if self.duplicates.is_empty() {
+			0
+		} else {
+			// including borders
+			u16::try_from(self.duplicates.len())
+				.unwrap_or(u16::MAX)
+				.saturating_add(2)
+		};
+
+		duplicates.saturating_add(10)
+	}
+
+	fn get_duplicates_text(&self) -> Vec<Line<'_>> {
+		self.duplicates
+			.iter()
+			.map(|(old, new)| {
+				Line::from(vec![
+					Span::styled(
+						Cow::from(old.short_hash()),
+						self.theme.commit_hash(false),
+					),
+					Span::raw(strings::COMPARE_DUPLICATE_OF),
+					Span::styled(
+						Cow::from(new.short_hash()),
+						self.theme.commit_hash(false),
+					),
+					Span::raw(" "),
+					Span::styled(
+						Cow::from(
+							old.message
+								.as_ref()
+								.map(|msg| msg.subject.clone())
+								.unwrap_or_default(),
+						),
+						self.theme.text(true, false),
+					),
+				])
+			})
+			.collect()
+	}
 }
 
 impl DrawableComponent for CompareDetailsComponent {
@@ -105,8 +205,12 @@ impl DrawableComponent for CompareDetailsComponent {
 		let chunks = Layout::default()
 			.direction(Direction::Vertical)
 			.constraints(
-				[Constraint::Length(5), Constraint::Length(5)]
-					.as_ref(),
+				[
+					Constraint::Length(5),
+					Constraint::Length(5),
+					Constraint::Min(0),
+				]
+				.as_ref(),
 			)
 			.split(rect);
 
@@ -136,6 +240,30 @@ impl DrawableComponent for CompareDetailsComponent {
 				),
 				chunks[1],
 			);
+
+			if self.git_cherry.is_pending() {
+				f.render_widget(
+					dialog_paragraph(
+						&strings::commit::compare_details_duplicates_pending_title(),
+						Text::default(),
+						&self.theme,
+						false,
+					),
+					chunks[2],
+				);
+			} else if !self.duplicates.is_empty() {
+				f.render_widget(
+					dialog_paragraph(
+						&strings::commit::compare_details_duplicates_title(
+							self.duplicates.len(),
+						),
+						Text::from(self.get_duplicates_text()),
+						&self.theme,
+						false,
+					),
+					chunks[2],
+				);
+			}
 		}
 
 		Ok(())
@@ -8,12 +8,19 @@ use crate::{
 		EventState, ScrollType,
 	},
 	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, Queue},
 	strings::{self, order},
+	try_or_popup,
 	ui::style::SharedTheme,
 };
 use anyhow::Result;
-use asyncgit::sync::{
-	self, CommitDetails, CommitId, CommitMessage, RepoPathRef, Tag,
+use asyncgit::{
+	asyncjob::AsyncSingleJob,
+	sync::{
+		self, CommitDetails, CommitId, CommitMessage, RepoPathRef,
+		SignatureValidity, SignatureVerification, Tag,
+	},
+	AsyncDescribeJob, AsyncGitNotification, AsyncSignaturesJob,
 };
 use crossterm::event::Event;
 use ratatui::{
@@ -31,6 +38,13 @@ pub struct DetailsComponent {
 	repo: RepoPathRef,
 	data: Option<CommitDetails>,
 	tags: Vec<Tag>,
+	describe_commit: Option<CommitId>,
+	describe: Option<String>,
+	git_describe: AsyncSingleJob<AsyncDescribeJob>,
+	signature_commit: Option<CommitId>,
+	signature: Option<SignatureVerification>,
+	git_signature: AsyncSingleJob<AsyncSignaturesJob>,
+	queue: Queue,
 	theme: SharedTheme,
 	focused: bool,
 	current_width: Cell<u16>,
@@ -49,6 +63,15 @@ impl DetailsComponent {
 			repo: env.repo.clone(),
 			data: None,
 			tags: Vec::new(),
+			describe_commit: None,
+			describe: None,
+			git_describe: AsyncSingleJob::new(env.sender_git.clone()),
+			signature_commit: None,
+			signature: None,
+			git_signature: AsyncSingleJob::new(
+				env.sender_git.clone(),
+			),
+			queue: env.queue.clone(),
 			theme: env.theme.clone(),
 			focused,
 			scroll_to_bottom_next_draw: Cell::new(false),
@@ -74,6 +97,90 @@ impl DetailsComponent {
 		if let Some(tags) = tags {
 			self.tags.extend(tags);
 		}
+
+		self.request_describe(id);
+		self.request_signature(id);
+	}
+
+	/// describing needs a tag walk so it is computed in the background
+	/// and only once per commit
+	fn request_describe(&mut self, id: Option<CommitId>) {
+		if self.describe_commit == id {
+			return;
+		}
+
+		self.describe_commit = id;
+		self.describe = None;
+
+		if let Some(id) = id {
+			self.git_describe.spawn(AsyncDescribeJob::new(
+				self.repo.borrow().clone(),
+				id,
+			));
+		}
+	}
+
+	/// verifying runs external programs, see `request_describe`
+	fn request_signature(&mut self, id: Option<CommitId>) {
+		if self.signature_commit == id {
+			return;
+		}
+
+		self.signature_commit = id;
+		self.signature = None;
+
+		if let Some(id) = id {
+			self.git_signature.spawn(AsyncSignaturesJob::new(
+				self.repo.borrow().clone(),
+				vec![id],
+			));
+		}
+	}
+
+	///
+	pub fn any_work_pending(&self) -> bool {
+		self.git_describe.is_pending()
+			|| self.git_signature.is_pending()
+	}
+
+	///
+	pub fn update_git(&mut self, ev: AsyncGitNotification) {
+		if ev == AsyncGitNotification::Describe {
+			if let Some((id, Ok(describe))) = self
+				.git_describe
+				.take_last()
+				.and_then(|job| job.result())
+			{
+				if self.describe_commit == Some(id) {
+					self.describe = describe;
+				}
+			}
+		} else if ev == AsyncGitNotification::Signatures {
+			if let Some(mut signatures) = self
+				.git_signature
+				.take_last()
+				.and_then(|job| job.result())
+			{
+				if let Some(signature) = self
+					.signature_commit
+					.and_then(|id| signatures.remove(&id))
+				{
+					self.signature = signature;
+				}
+			}
+		}
+	}
+
+	///
+	pub fn copy_describe(&self) -> Result<()> {
+		if let Some(describe) = &self.describe {
+			crate::clipboard::copy_string(describe)?;
+			self.queue.push(InternalEvent::ShowInfoMsg(
+				strings::copy_success(describe),
+			));
+		}
+
+		Ok(())
 	}
 
 	fn wrap_commit_details(
@@ -101,6 +208,58 @@ impl DetailsComponent {
 		}
 	}
 
+	/// wrapped `Key: value` lines per trailer
+	fn wrap_trailers(
+		message: &CommitMessage,
+		width: usize,
+	) -> Vec<Vec<String>> {
+		let width = width.max(1);
+
+		message
+			.trailers
+			.iter()
+			.map(|trailer| {
+				let line =
+					format!("{}: {}", trailer.key, trailer.value);
+				bwrap::wrap!(&line, width)
+					.lines()
+					.map(String::from)
+					.collect()
+			})
+			.collect()
+	}
+
+	fn get_wrapped_trailers(
+		data: Option<&CommitDetails>,
+		width: usize,
+	) -> Vec<Vec<String>> {
+		data.and_then(|data| data.message.as_ref())
+			.map(|message| Self::wrap_trailers(message, width))
+			.unwrap_or_default()
+	}
+
+	/// wrapped lines of the commit's note
+	fn get_wrapped_note(
+		data: Option<&CommitDetails>,
+		width: usize,
+	) -> Vec<String> {
+		let width = width.max(1);
+
+		data.and_then(|data| data.note.as_ref())
+			.map(|note| {
+				note.trim_end()
+					.lines()
+					.flat_map(|line| {
+						bwrap::wrap!(line, width)
+							.lines()
+							.map(String::from)
+							.collect::<Vec<_>>()
+					})
+					.collect()
+			})
+			.unwrap_or_default()
+	}
+
 	fn get_wrapped_lines(
 		data: Option<&CommitDetails>,
 		width: usize,
@@ -120,8 +279,28 @@ impl DetailsComponent {
 	) -> usize {
 		let (wrapped_title, wrapped_message) =
 			Self::get_wrapped_lines(details, width);
+		let trailers = Self::get_wrapped_trailers(details, width);
+
+		let trailer_lines = if trailers.is_empty() {
+			0
+		} else {
+			// empty line and section title
+			2 + trailers.iter().map(Vec::len).sum::<usize>()
+		};
+
+		let note = Self::get_wrapped_note(details, width);
+
+		let note_lines = if note.is_empty() {
+			0
+		} else {
+			// empty line and section title
+			2 + note.len()
+		};
 
-		wrapped_title.len() + wrapped_message.len()
+		wrapped_title.len()
+			+ wrapped_message.len()
+			+ trailer_lines
+			+ note_lines
 	}
 
 	fn get_theme_for_line(&self, bold: bool) -> Style {
@@ -140,21 +319,129 @@ impl DetailsComponent {
 		let (wrapped_title, wrapped_message) =
 			Self::get_wrapped_lines(self.data.as_ref(), width);
 
-		[&wrapped_title[..], &wrapped_message[..]]
-			.concat()
-			.iter()
-			.enumerate()
+		let mut lines: Vec<Line> =
+			[&wrapped_title[..], &wrapped_message[..]]
+				.concat()
+				.into_iter()
+				.enumerate()
+				.map(|(i, line)| {
+					Line::from(vec![Span::styled(
+						line,
+						self.get_theme_for_line(
+							i < wrapped_title.len(),
+						),
+					)])
+				})
+				.collect();
+
+		lines.extend(self.get_trailer_lines(width));
+		lines.extend(self.get_note_lines(width));
+
+		lines
+			.into_iter()
 			.skip(self.scroll.get_top())
 			.take(height)
-			.map(|(i, line)| {
-				Line::from(vec![Span::styled(
-					line.clone(),
-					self.get_theme_for_line(i < wrapped_title.len()),
-				)])
-			})
 			.collect()
 	}
 
+	fn get_trailer_lines(&self, width: usize) -> Vec<Line<'_>> {
+		let Some(message) =
+			self.data.as_ref().and_then(|data| data.message.as_ref())
+		else {
+			return Vec::new();
+		};
+
+		if message.trailers.is_empty() {
+			return Vec::new();
+		}
+
+		let mut lines = vec![
+			Line::default(),
+			Line::from(style_detail(&self.theme, &Detail::Trailers)),
+		];
+
+		for (trailer, wrapped) in message
+			.trailers
+			.iter()
+			.zip(Self::wrap_trailers(message, width))
+		{
+			for (i, line) in wrapped.into_iter().enumerate() {
+				// the key is never wrapped as it contains no spaces
+				let key_len = if i == 0 {
+					(trailer.key.len() + 1).min(line.len())
+				} else {
+					0
+				};
+				let (key, value) = line.split_at(key_len);
+
+				lines.push(Line::from(vec![
+					Span::styled(
+						key.to_string(),
+						self.theme.text(false, false),
+					),
+					Span::styled(
+						value.to_string(),
+						self.theme.text(true, false),
+					),
+				]));
+			}
+		}
+
+		lines
+	}
+
+	fn get_note_lines(&self, width: usize) -> Vec<Line<'_>> {
+		let note = Self::get_wrapped_note(self.data.as_ref(), width);
+
+		if note.is_empty() {
+			return Vec::new();
+		}
+
+		let mut lines = vec![
+			Line::default(),
+			Line::from(style_detail(&self.theme, &Detail::Notes)),
+		];
+
+		lines.extend(note.into_iter().map(|line| {
+			Line::from(Span::styled(
+				line,
+				self.theme.text(true, false),
+			))
+		}));
+
+		lines
+	}
+
+	/// copies all trailers as `Key: value` lines
+	pub fn copy_trailers(&self) -> Result<()> {
+		if let Some(message) =
+			self.data.as_ref().and_then(|data| data.message.as_ref())
+		{
+			if !message.trailers.is_empty() {
+				let trailers = message
+					.trailers
+					.iter()
+					.map(|t| format!("{}: {}", t.key, t.value))
+					.collect::<Vec<_>>()
+					.join("\n");
+
+				crate::clipboard::copy_string(&trailers)?;
+				self.queue.push(InternalEvent::ShowInfoMsg(
+					strings::copy_success(&trailers),
+				));
+			}
+		}
+
+		Ok(())
+	}
+
+	fn has_trailers(&self) -> bool {
+		self.data
+			.as_ref()
+			.and_then(|data| data.message.as_ref())
+			.is_some_and(|message| !message.trailers.is_empty())
+	}
+
 	#[allow(unstable_name_collisions, clippy::too_many_lines)]
 	fn get_text_info(&self) -> Vec<Line> {
 		self.data.as_ref().map_or_else(Vec::new, |data| {
@@ -217,6 +504,34 @@ impl DetailsComponent {
 				),
 			]));
 
+			if let Some(describe) = &self.describe {
+				res.push(Line::from(vec![
+					style_detail(&self.theme, &Detail::Describe),
+					Span::styled(
+						Cow::from(describe.clone()),
+						self.theme.text(true, false),
+					),
+				]));
+			}
+
+			if let Some(signature) = &self.signature {
+				res.push(Line::from(vec![
+					style_detail(&self.theme, &Detail::Signature),
+					Span::styled(
+						Cow::from(strings::signature_verification(
+							signature,
+						)),
+						if signature.validity
+							== SignatureValidity::Bad
+						{
+							self.theme.text_danger()
+						} else {
+							self.theme.text(true, false)
+						},
+					),
+				]));
+			}
+
 			if !self.tags.is_empty() {
 				res.push(Line::from(style_detail(
 					&self.theme,
@@ -261,7 +576,7 @@ impl DrawableComponent for DetailsComponent {
 		let chunks = Layout::default()
 			.direction(Direction::Vertical)
 			.constraints(
-				[Constraint::Length(8), Constraint::Min(10)].as_ref(),
+				[Constraint::Length(9), Constraint::Min(10)].as_ref(),
 			)
 			.split(rect);
 
@@ -355,10 +670,44 @@ impl Component for DetailsComponent {
 			.order(order::NAV),
 		);
 
+		out.push(CommandInfo::new(
+			strings::commands::copy_describe(&self.key_config),
+			self.describe.is_some(),
+			self.data.is_some() || force_all,
+		));
+
+		out.push(CommandInfo::new(
+			strings::commands::copy_trailers(&self.key_config),
+			self.has_trailers(),
+			self.data.is_some() || force_all,
+		));
+
 		CommandBlocking::PassingOn
 	}
 
 	fn event(&mut self, event: &Event) -> Result<EventState> {
+		if let Event::Key(e) = event {
+			if key_match(e, self.key_config.keys.copy_describe)
+				&& self.describe.is_some()
+			{
+				try_or_popup!(
+					self,
+					strings::POPUP_FAIL_COPY,
+					self.copy_describe()
+				);
+				return Ok(EventState::Consumed);
+			} else if key_match(e, self.key_config.keys.copy_trailers)
+				&& self.has_trailers()
+			{
+				try_or_popup!(
+					self,
+					strings::POPUP_FAIL_COPY,
+					self.copy_trailers()
+				);
+				return Ok(EventState::Consumed);
+			}
+		}
+
 		if self.focused {
 			if let Event::Key(e) = event {
 				return Ok(
@@ -486,6 +835,7 @@ mod test_line_count {
 			message: Some(CommitMessage {
 				subject: String::from("subject line"),
 				body: Some(String::from("body lone")),
+				trailers: Vec::new(),
 			}),
 			..CommitDetails::default()
 		};
@@ -501,4 +851,35 @@ mod test_line_count {
 		);
 		assert_eq!(lines, 4);
 	}
+
+	#[test]
+	fn test_trailers() {
+		let commit = CommitDetails {
+			message: Some(CommitMessage::from(
+				"subject line\n\nSigned-off-by: A <a@b.c>",
+			)),
+			..CommitDetails::default()
+		};
+		// subject, empty line, title, trailer
+		let lines = DetailsComponent::get_number_of_lines(
+			Some(commit).as_ref(),
+			50,
+		);
+		assert_eq!(lines, 4);
+	}
+
+	#[test]
+	fn test_note() {
+		let commit = CommitDetails {
+			message: Some(CommitMessage::from("subject line")),
+			note: Some(String::from("first\nsecond\n")),
+			..CommitDetails::default()
+		};
+		// subject, empty line, title, two note lines
+		let lines = DetailsComponent::get_number_of_lines(
+			Some(commit).as_ref(),
+			50,
+		);
+		assert_eq!(lines, 5);
+	}
 }
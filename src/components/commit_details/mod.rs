@@ -15,7 +15,7 @@ use crate::{
 use anyhow::Result;
 use asyncgit::{
 	sync::{commit_files::OldNew, CommitTags},
-	AsyncCommitFiles, CommitFilesParams,
+	AsyncCommitFiles, AsyncGitNotification, CommitFilesParams,
 };
 use compare_details::CompareDetailsComponent;
 use crossterm::event::Event;
@@ -113,6 +113,24 @@ impl CommitDetailsComponent {
 	///
 	pub fn any_work_pending(&self) -> bool {
 		self.git_commit_files.is_pending()
+			|| self.single_details.any_work_pending()
+			|| self.compare_details.any_work_pending()
+	}
+
+	///
+	pub fn update_git(&mut self, ev: AsyncGitNotification) {
+		self.single_details.update_git(ev);
+		self.compare_details.update_git(ev);
+	}
+
+	///
+	pub fn copy_describe(&self) -> Result<()> {
+		self.single_details.copy_describe()
+	}
+
+	///
+	pub fn copy_trailers(&self) -> Result<()> {
+		self.single_details.copy_trailers()
 	}
 
 	///
@@ -145,7 +163,14 @@ impl DrawableComponent for CommitDetailsComponent {
 		}
 
 		let constraints = if self.is_compare() {
-			[Constraint::Length(10), Constraint::Min(0)]
+			[
+				Constraint::Length(
+					self.compare_details
+						.height()
+						.min(rect.height.saturating_sub(5)),
+				),
+				Constraint::Min(0),
+			]
 		} else {
 			let details_focused = self.details_focused();
 			let percentages = if self.file_tree.focused() {
@@ -8,6 +8,10 @@ pub enum Detail {
 	Committer,
 	Sha,
 	Message,
+	Describe,
+	Signature,
+	Trailers,
+	Notes,
 }
 
 pub fn style_detail<'a>(
@@ -35,5 +39,21 @@ pub fn style_detail<'a>(
 			Cow::from(strings::commit::details_message()),
 			theme.text(false, false),
 		),
+		Detail::Describe => Span::styled(
+			Cow::from(strings::commit::details_describe()),
+			theme.text(false, false),
+		),
+		Detail::Signature => Span::styled(
+			Cow::from(strings::commit::details_signature()),
+			theme.text(false, false),
+		),
+		Detail::Trailers => Span::styled(
+			Cow::from(strings::commit::details_trailers()),
+			theme.text(false, false),
+		),
+		Detail::Notes => Span::styled(
+			Cow::from(strings::commit::details_notes()),
+			theme.text(false, false),
+		),
 	}
 }
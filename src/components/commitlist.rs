@@ -14,8 +14,8 @@ use crate::{
 };
 use anyhow::Result;
 use asyncgit::sync::{
-	self, checkout_commit, BranchDetails, BranchInfo, CommitId,
-	RepoPathRef, Tags,
+	self, checkout_commit, BisectState, BranchDetails, BranchInfo,
+	CommitId, RepoPathRef, SignatureValidity, Tags,
 };
 use chrono::{DateTime, Local};
 use crossterm::event::Event;
@@ -29,7 +29,11 @@ use ratatui::{
 	Frame,
 };
 use std::{
-	borrow::Cow, cell::Cell, cmp, collections::BTreeMap, rc::Rc,
+	borrow::Cow,
+	cell::Cell,
+	cmp,
+	collections::{BTreeMap, HashMap, HashSet},
+	rc::Rc,
 	time::Instant,
 };
 
@@ -44,12 +48,22 @@ pub struct CommitList {
 	highlighted_selection: Option<usize>,
 	items: ItemBatch,
 	highlights: Option<Rc<IndexSet<CommitId>>>,
+	/// highlighted commit to select once the walk got to it
+	pending_highlight: Option<CommitId>,
 	commits: IndexSet<CommitId>,
 	marked: Vec<(usize, CommitId)>,
 	scroll_state: (Instant, f32),
 	tags: Option<Tags>,
 	local_branches: BTreeMap<CommitId, Vec<BranchInfo>>,
 	remote_branches: BTreeMap<CommitId, Vec<BranchInfo>>,
+	applied_upstream: HashSet<CommitId>,
+	bisect: Option<BisectState>,
+	ref_columns: usize,
+	/// `false` while older commits are still to be walked
+	complete: bool,
+	ref_masks: HashMap<CommitId, u64>,
+	signatures: HashMap<CommitId, Option<SignatureValidity>>,
+	any_signed: bool,
 	current_size: Cell<Option<(u16, u16)>>,
 	scroll_top: Cell<usize>,
 	theme: SharedTheme,
@@ -68,10 +82,18 @@ impl CommitList {
 			highlighted_selection: None,
 			commits: IndexSet::new(),
 			highlights: None,
+			pending_highlight: None,
 			scroll_state: (Instant::now(), 0_f32),
 			tags: None,
 			local_branches: BTreeMap::default(),
 			remote_branches: BTreeMap::default(),
+			applied_upstream: HashSet::new(),
+			bisect: None,
+			ref_columns: 0,
+			complete: true,
+			ref_masks: HashMap::new(),
+			signatures: HashMap::new(),
+			any_signed: false,
 			current_size: Cell::new(None),
 			scroll_top: Cell::new(0),
 			theme: env.theme.clone(),
@@ -86,10 +108,16 @@ impl CommitList {
 		self.tags.as_ref()
 	}
 
+	///
+	pub fn set_title(&mut self, title: &str) {
+		self.title = title.into();
+	}
+
 	///
 	pub fn clear(&mut self) {
 		self.items.clear();
 		self.commits.clear();
+		self.ref_masks.clear();
 	}
 
 	///
@@ -213,6 +241,56 @@ impl CommitList {
 		}
 	}
 
+	/// commits to mark as already applied upstream
+	pub fn set_applied_upstream(&mut self, ids: HashSet<CommitId>) {
+		self.applied_upstream = ids;
+	}
+
+	/// marks of a running bisect session
+	pub fn set_bisect(&mut self, bisect: Option<BisectState>) {
+		self.bisect = bisect;
+	}
+
+	/// number of refs to show a column for that marks the commits
+	/// reachable from that ref, see `LogWalker::ref_mask`
+	pub fn set_ref_columns(&mut self, columns: usize) {
+		self.ref_columns = columns;
+	}
+
+	///
+	pub fn extend_ref_masks(
+		&mut self,
+		masks: HashMap<CommitId, u64>,
+	) {
+		self.ref_masks.extend(masks);
+	}
+
+	/// signature validity of commits, `None` for unsigned ones
+	pub fn extend_signatures(
+		&mut self,
+		signatures: HashMap<CommitId, Option<SignatureValidity>>,
+	) {
+		self.any_signed |= signatures.values().any(Option::is_some);
+		self.signatures.extend(signatures);
+	}
+
+	/// commits in and around the visible part of the list whose
+	/// signature was not verified yet
+	pub fn unverified_signatures(&self) -> Vec<CommitId> {
+		let height = self
+			.current_size
+			.get()
+			.map_or(0, |size| usize::from(size.1));
+
+		self.items
+			.iter()
+			.skip(self.scroll_top.get())
+			.take(height * 2)
+			.map(|e| e.id)
+			.filter(|id| !self.signatures.contains_key(id))
+			.collect()
+	}
+
 	///
 	pub fn set_commits(&mut self, commits: IndexSet<CommitId>) {
 		if commits != self.commits {
@@ -227,6 +305,13 @@ impl CommitList {
 		let new_commits = !commits.is_empty();
 		self.commits.extend(commits);
 
+		if let Some(id) = self.pending_highlight {
+			if self.commits.contains(&id) {
+				self.pending_highlight = None;
+				self.select_commit(id).ok();
+			}
+		}
+
 		let selection = self.selection();
 		let selection_max = self.selection_max();
 
@@ -250,8 +335,20 @@ impl CommitList {
 			highlighting
 		};
 
+		self.pending_highlight = None;
 		self.select_next_highlight();
+
 		self.set_highlighted_selection_index();
+
+		// matches may all be older than the loaded part of the log
+		if !self.selection_highlighted() {
+			self.pending_highlight = self
+				.highlights
+				.as_ref()
+				.and_then(|highlights| highlights.first().copied());
+			self.highlighted_selection =
+				self.pending_highlight.map(|_| 0);
+		}
 		self.fetch_commits(true);
 	}
 
@@ -281,16 +378,22 @@ impl CommitList {
 	fn set_highlighted_selection_index(&mut self) {
 		self.highlighted_selection =
 			self.highlights.as_ref().and_then(|highlights| {
-				highlights.iter().position(|entry| {
-					entry == &self.commits[self.selection]
-				})
+				let selected =
+					self.commits.get_index(self.selection)?;
+				highlights.iter().position(|entry| entry == selected)
 			});
 	}
 
-	const fn selection(&self) -> usize {
+	///
+	pub const fn selection(&self) -> usize {
 		self.selection
 	}
 
+	/// `false` marks the commits as only the first part of the log
+	pub fn set_complete(&mut self, complete: bool) {
+		self.complete = complete;
+	}
+
 	/// will return view size or None before the first render
 	fn current_size(&self) -> Option<(u16, u16)> {
 		self.current_size.get()
@@ -306,7 +409,11 @@ impl CommitList {
 			.unwrap_or_default()
 	}
 
-	fn move_selection(&mut self, scroll: ScrollType) -> Result<bool> {
+	///
+	pub fn move_selection(
+		&mut self,
+		scroll: ScrollType,
+	) -> Result<bool> {
 		let needs_update = if self.items.highlighting() {
 			self.move_selection_highlighting(scroll)?
 		} else {
@@ -348,7 +455,14 @@ impl CommitList {
 			});
 
 		if let Some(c) = new_selected_commit {
-			self.select_commit(c)?;
+			if self.commits.contains(&c) {
+				self.pending_highlight = None;
+				self.select_commit(c)?;
+			} else {
+				// selected as soon as the walk got to it
+				self.pending_highlight = Some(c);
+				self.highlighted_selection = Some(new_index);
+			}
 			return Ok(true);
 		}
 
@@ -447,6 +561,59 @@ impl CommitList {
 		}
 	}
 
+	/// marks which of the selected refs the commit is reachable from
+	fn ref_columns_span(
+		&self,
+		id: &CommitId,
+		selected: bool,
+	) -> Option<Span<'static>> {
+		if self.ref_columns == 0 {
+			return None;
+		}
+
+		let mask =
+			self.ref_masks.get(id).copied().unwrap_or_default();
+
+		Some(Span::styled(
+			(0..self.ref_columns)
+				.map(|column| {
+					if mask & (1 << column) == 0 {
+						symbol::REF_NOT_CONTAINS
+					} else {
+						symbol::REF_CONTAINS
+					}
+				})
+				.collect::<String>(),
+			self.theme.log_marker(selected),
+		))
+	}
+
+	/// verification badge, only shown once any signed commit is known
+	fn signature_span(
+		&self,
+		id: &CommitId,
+		selected: bool,
+	) -> Option<Span<'static>> {
+		if !self.any_signed {
+			return None;
+		}
+
+		Some(self.signatures.get(id).copied().flatten().map_or_else(
+			|| {
+				Span::styled(
+					symbol::EMPTY_SPACE,
+					self.theme.text(true, selected),
+				)
+			},
+			|validity| {
+				Span::styled(
+					strings::log_signature_mark(validity),
+					self.theme.log_signature(validity, selected),
+				)
+			},
+		))
+	}
+
 	#[allow(clippy::too_many_arguments)]
 	fn get_entry_to_add<'a>(
 		&self,
@@ -459,6 +626,8 @@ impl CommitList {
 		width: usize,
 		now: DateTime<Local>,
 		marked: Option<bool>,
+		applied_upstream: bool,
+		bisect: Option<&'static str>,
 	) -> Line<'a> {
 		let mut txt: Vec<Span> = Vec::with_capacity(
 			ELEMENTS_PER_LINE + if marked.is_some() { 2 } else { 0 },
@@ -490,6 +659,11 @@ impl CommitList {
 			txt.push(splitter.clone());
 		}
 
+		if let Some(refs) = self.ref_columns_span(&e.id, selected) {
+			txt.push(refs);
+			txt.push(splitter.clone());
+		}
+
 		let style_hash = normal
 			.then(|| theme.commit_hash(selected))
 			.unwrap_or_else(|| theme.commit_unhighlighted());
@@ -514,6 +688,12 @@ impl CommitList {
 
 		txt.push(splitter.clone());
 
+		if let Some(signature) = self.signature_span(&e.id, selected)
+		{
+			txt.push(signature);
+			txt.push(splitter.clone());
+		}
+
 		// commit timestamp
 		txt.push(Span::styled(
 			Cow::from(e.time_to_string(now)),
@@ -546,7 +726,23 @@ impl CommitList {
 			txt.push(Span::styled(remote_branches, style_branches));
 		}
 
-		txt.push(splitter);
+		txt.push(splitter.clone());
+
+		if applied_upstream {
+			txt.push(Span::styled(
+				Cow::from(strings::LOG_APPLIED_UPSTREAM),
+				theme.log_marker(selected),
+			));
+			txt.push(splitter.clone());
+		}
+
+		if let Some(bisect) = bisect {
+			txt.push(Span::styled(
+				Cow::from(bisect),
+				theme.log_marker(selected),
+			));
+			txt.push(splitter);
+		}
 
 		let message_width = width.saturating_sub(
 			txt.iter().map(|span| span.content.len()).sum(),
@@ -612,12 +808,25 @@ impl CommitList {
 				width,
 				now,
 				marked,
+				self.applied_upstream.contains(&e.id),
+				self.bisect_marker(&e.id),
 			));
 		}
 
 		txt
 	}
 
+	fn bisect_marker(&self, id: &CommitId) -> Option<&'static str> {
+		self.bisect.as_ref().and_then(|state| {
+			state.mark(id).map(strings::log_bisect_mark).or_else(
+				|| {
+					(state.next.as_ref() == Some(id))
+						.then_some(strings::LOG_BISECT_NEXT)
+				},
+			)
+		})
+	}
+
 	fn remote_branches_string(&self, e: &LogEntry) -> Option<String> {
 		self.remote_branches.get(&e.id).and_then(|remote_branches| {
 			let filtered_branches: Vec<_> = remote_branches
@@ -705,11 +914,14 @@ impl CommitList {
 	}
 
 	fn selection_highlighted(&self) -> bool {
-		let commit = self.commits[self.selection];
+		let Some(commit) = self.commits.get_index(self.selection)
+		else {
+			return false;
+		};
 
 		self.highlights
 			.as_ref()
-			.is_some_and(|highlights| highlights.contains(&commit))
+			.is_some_and(|highlights| highlights.contains(commit))
 	}
 
 	fn needs_data(&self, idx: usize, idx_max: usize) -> bool {
@@ -785,10 +997,11 @@ impl DrawableComponent for CommitList {
 		));
 
 		let title = format!(
-			"{} {}/{}",
+			"{} {}/{}{}",
 			self.title,
 			self.commits.len().saturating_sub(self.selection),
 			self.commits.len(),
+			if self.complete { "" } else { "+" },
 		);
 
 		f.render_widget(
@@ -894,6 +1107,69 @@ impl Component for CommitList {
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use asyncgit::sync::{self, ConfigOrigin, RepoPath};
+
+	/// `count` commits, newest first like the log lists them
+	fn commits(repo: &RepoPath, count: usize) -> Vec<CommitId> {
+		for (key, value) in
+			[("user.name", "name"), ("user.email", "email")]
+		{
+			sync::set_config_string(
+				repo,
+				ConfigOrigin::Local,
+				key,
+				Some(value),
+			)
+			.unwrap();
+		}
+
+		let mut ids = (0..count)
+			.map(|idx| {
+				sync::commit(repo, &format!("c{idx}")).unwrap()
+			})
+			.collect::<Vec<_>>();
+		ids.reverse();
+		ids
+	}
+
+	#[test]
+	fn test_highlight_beyond_loaded() {
+		let td = tempfile::tempdir().unwrap();
+		sync::init_repo(td.path(), None).unwrap();
+
+		let env = Environment::test_env();
+		env.repo.replace(RepoPath::Path(td.path().to_path_buf()));
+		let ids = commits(&env.repo.borrow(), 4);
+
+		let mut list = CommitList::new(&env, "");
+		// the walk paused after the first two commits
+		list.refresh_extend_data(ids[..2].to_vec());
+
+		list.set_highlighting(Some(Rc::new(
+			[ids[0], ids[3]].into_iter().collect(),
+		)));
+		assert_eq!(list.selection(), 0);
+		assert_eq!(list.highlighted_selection_info(), (0, 2));
+
+		assert!(list
+			.move_selection_highlighting(ScrollType::Down)
+			.unwrap());
+		assert_eq!(list.highlighted_selection_info(), (1, 2));
+		assert_eq!(list.selection(), 0);
+
+		list.refresh_extend_data(ids[2..].to_vec());
+		assert_eq!(list.selection(), 3);
+		assert_eq!(list.highlighted_selection_info(), (1, 2));
+
+		// only matches the walk did not get to yet
+		let mut list = CommitList::new(&env, "");
+		list.refresh_extend_data(ids[..2].to_vec());
+		list.set_highlighting(Some(Rc::new(
+			[ids[2]].into_iter().collect(),
+		)));
+		list.refresh_extend_data(ids[2..].to_vec());
+		assert_eq!(list.selection(), 2);
+	}
 
 	#[test]
 	fn test_string_width_align() {
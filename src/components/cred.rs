@@ -2,11 +2,17 @@ use anyhow::Result;
 use crossterm::event::Event;
 use ratatui::{layout::Rect, Frame};
 
-use asyncgit::sync::cred::BasicAuthCredential;
+use asyncgit::sync::{
+	cred::{
+		remember_credential, BasicAuthCredential, CredentialStorage,
+	},
+	RepoPathRef,
+};
 
 use crate::app::Environment;
 use crate::components::{EventState, InputType, TextInputComponent};
 use crate::keys::key_match;
+use crate::queue::{InternalEvent, Queue};
 use crate::{
 	components::{
 		visibility_blocking, CommandBlocking, CommandInfo, Component,
@@ -16,20 +22,28 @@ use crate::{
 	strings,
 };
 
-///
+/// asks for username and password or token, which can be remembered
+/// for the session or handed to the credential helper
 pub struct CredComponent {
+	repo: RepoPathRef,
+	queue: Queue,
 	visible: bool,
 	key_config: SharedKeyConfig,
 	input_username: TextInputComponent,
 	input_password: TextInputComponent,
 	cred: BasicAuthCredential,
+	url: String,
+	storage: CredentialStorage,
+	passphrase: bool,
 }
 
 impl CredComponent {
 	///
 	pub fn new(env: &Environment) -> Self {
 		let key_config = env.key_config.clone();
-		Self {
+		let mut cred = Self {
+			repo: env.repo.clone(),
+			queue: env.queue.clone(),
 			visible: false,
 			input_username: TextInputComponent::new(
 				env,
@@ -47,11 +61,76 @@ impl CredComponent {
 			.with_input_type(InputType::Password),
 			key_config,
 			cred: BasicAuthCredential::new(None, None),
-		}
+			url: String::new(),
+			storage: CredentialStorage::None,
+			passphrase: false,
+		};
+		cred.update_titles();
+		cred
 	}
 
-	pub fn set_cred(&mut self, cred: BasicAuthCredential) {
+	/// `url` is the remote the credentials get remembered for
+	pub fn set_cred(
+		&mut self,
+		cred: BasicAuthCredential,
+		url: String,
+	) {
 		self.cred = cred;
+		self.url = url;
+		self.passphrase = false;
+		self.update_titles();
+	}
+
+	/// only asks for the passphrase of an ssh key, handed to the
+	/// remote callbacks as password
+	pub fn set_passphrase(&mut self) {
+		self.cred =
+			BasicAuthCredential::new(Some(String::new()), None);
+		self.url = String::new();
+		self.passphrase = true;
+		self.update_titles();
+	}
+
+	fn update_titles(&mut self) {
+		if self.passphrase {
+			self.input_password.set_title(
+				strings::ssh_passphrase_popup_title(&self.key_config),
+			);
+			self.input_password.set_default_msg(
+				strings::ssh_passphrase_popup_msg(&self.key_config),
+			);
+			return;
+		}
+
+		self.input_password.set_default_msg(
+			strings::password_popup_msg(&self.key_config),
+		);
+		self.input_username.set_title(strings::cred_popup_title(
+			&strings::username_popup_title(&self.key_config),
+			self.storage,
+		));
+		self.input_password.set_title(strings::cred_popup_title(
+			&strings::password_popup_title(&self.key_config),
+			self.storage,
+		));
+	}
+
+	fn toggle_storage(&mut self) {
+		self.storage = self.storage.next();
+		self.update_titles();
+	}
+
+	fn remember(&self) {
+		if let Err(e) = remember_credential(
+			&self.repo.borrow(),
+			&self.url,
+			&self.cred,
+			self.storage,
+		) {
+			self.queue.push(InternalEvent::ShowErrorMsg(format!(
+				"remember credentials:\n{e}"
+			)));
+		}
 	}
 
 	pub const fn get_cred(&self) -> &BasicAuthCredential {
@@ -85,6 +164,11 @@ impl Component for CredComponent {
 				true,
 				true,
 			));
+			out.push(CommandInfo::new(
+				strings::commands::cred_storage(&self.key_config),
+				true,
+				!self.passphrase,
+			));
 			out.push(CommandInfo::new(
 				strings::commands::close_popup(&self.key_config),
 				true,
@@ -102,6 +186,12 @@ impl Component for CredComponent {
 					self.hide();
 					return Ok(EventState::Consumed);
 				}
+				if !self.passphrase
+					&& key_match(e, self.key_config.keys.cred_storage)
+				{
+					self.toggle_storage();
+					return Ok(EventState::Consumed);
+				}
 				if self.input_username.event(ev)?.is_consumed()
 					|| self.input_password.event(ev)?.is_consumed()
 				{
@@ -129,6 +219,7 @@ impl Component for CredComponent {
 						);
 						self.input_password.hide();
 						self.input_password.clear();
+						self.remember();
 						return Ok(EventState::NotConsumed);
 					} else {
 						self.hide();
@@ -8,16 +8,23 @@ use crate::{
 	components::{CommandInfo, Component, EventState},
 	keys::{key_match, SharedKeyConfig},
 	options::SharedOptions,
+	popups::{AppOption, StashSelection},
 	queue::{Action, InternalEvent, NeedsUpdate, Queue, ResetItem},
 	string_utils::tabs_to_spaces,
 	string_utils::trim_offset,
-	strings, try_or_popup,
-	ui::style::SharedTheme,
+	strings,
+	tabs::StashingOptions,
+	try_or_popup,
+	ui::{self, style::SharedTheme, DiffLineHighlight},
 };
 use anyhow::Result;
 use asyncgit::{
 	hash,
-	sync::{self, diff::DiffLinePosition, RepoPathRef},
+	sync::{
+		self,
+		diff::{DiffLimit, DiffLinePosition, DiffOptions},
+		CommitId, LfsDiff, LfsPointer, RepoPathRef,
+	},
 	DiffLine, DiffLineType, FileDiff,
 };
 use bytesize::ByteSize;
@@ -30,6 +37,7 @@ use ratatui::{
 	Frame,
 };
 use std::{borrow::Cow, cell::Cell, cmp, path::Path};
+use unicode_width::UnicodeWidthStr;
 
 #[derive(Default)]
 struct Current {
@@ -112,12 +120,23 @@ pub struct DiffComponent {
 	current_size: Cell<(u16, u16)>,
 	focused: bool,
 	current: Current,
+	title_info: Option<String>,
 	vertical_scroll: VerticalScroll,
 	horizontal_scroll: HorizontalScroll,
 	queue: Queue,
 	theme: SharedTheme,
 	key_config: SharedKeyConfig,
 	is_immutable: bool,
+	/// commit the diff belongs to, allows applying its hunks
+	commit: Option<CommitId>,
+	/// syntax highlight per diff line, see `highlight_diff`
+	highlight: Option<Vec<DiffLineHighlight>>,
+	/// how much of the diff of a path to load, raised once its
+	/// truncation marker gets selected
+	limit: (String, DiffLimit),
+	/// more of the diff got requested, the part loaded so far stays
+	/// visible meanwhile
+	loading_more: bool,
 	options: SharedOptions,
 }
 
@@ -128,6 +147,7 @@ impl DiffComponent {
 			focused: false,
 			queue: env.queue.clone(),
 			current: Current::default(),
+			title_info: None,
 			pending: false,
 			selected_hunk: None,
 			diff: None,
@@ -139,6 +159,10 @@ impl DiffComponent {
 			theme: env.theme.clone(),
 			key_config: env.key_config.clone(),
 			is_immutable,
+			commit: None,
+			highlight: None,
+			limit: (String::new(), DiffLimit::default()),
+			loading_more: false,
 			repo: env.repo.clone(),
 			options: env.options.clone(),
 		}
@@ -151,8 +175,56 @@ impl DiffComponent {
 	pub fn current(&self) -> (String, bool) {
 		(self.current.path.clone(), self.current.is_stage)
 	}
+	/// set the commit whose changes are shown to allow applying
+	/// its hunks onto the workdir
+	pub fn set_commit(&mut self, commit: Option<CommitId>) {
+		self.commit = commit;
+	}
+	/// extra info shown in the title after the path
+	pub fn set_title_info(&mut self, info: Option<String>) {
+		self.title_info = info;
+	}
+	/// `options` limited to the part of the diff of `path` that got
+	/// scrolled to so far
+	pub fn limit_options(
+		&self,
+		path: &str,
+		options: DiffOptions,
+	) -> DiffOptions {
+		DiffOptions {
+			limit: Some(self.limit_of(path)),
+			..options
+		}
+	}
+
+	fn limit_of(&self, path: &str) -> DiffLimit {
+		if self.limit.0 == path {
+			self.limit.1
+		} else {
+			DiffLimit::default()
+		}
+	}
+
+	/// requests the next chunk of a truncated diff
+	fn load_more(&mut self) {
+		if self.loading_more {
+			return;
+		}
+
+		let path = self.current.path.clone();
+		self.limit = (path.clone(), self.limit_of(&path).next());
+		self.loading_more = true;
+
+		self.queue.push(InternalEvent::Update(NeedsUpdate::DIFF));
+	}
+
 	///
 	pub fn clear(&mut self, pending: bool) {
+		if pending && self.loading_more {
+			return;
+		}
+
+		self.loading_more = false;
 		self.current = Current::default();
 		self.diff = None;
 		self.longest_line = 0;
@@ -160,6 +232,7 @@ impl DiffComponent {
 		self.horizontal_scroll.reset();
 		self.selection = Selection::Single(0);
 		self.selected_hunk = None;
+		self.highlight = None;
 		self.pending = pending;
 	}
 	///
@@ -170,10 +243,12 @@ impl DiffComponent {
 		diff: FileDiff,
 	) {
 		self.pending = false;
+		self.loading_more = false;
 
 		let hash = hash(&diff);
+		let hash_changed = self.current.hash != hash;
 
-		if self.current.hash != hash {
+		if hash_changed {
 			let reset_selection = self.current.path != path;
 
 			self.current = Current {
@@ -215,11 +290,31 @@ impl DiffComponent {
 				self.update_selection(old_selection);
 			}
 		}
+
+		let highlight_enabled =
+			self.options.borrow().diff_syntax_highlight();
+
+		// unknown file types are retried on every update, that is cheap
+		if hash_changed
+			|| highlight_enabled != self.highlight.is_some()
+		{
+			self.highlight = self
+				.diff
+				.as_ref()
+				.filter(|_| highlight_enabled)
+				.and_then(|diff| {
+					ui::highlight_diff(
+						Path::new(&self.current.path),
+						diff,
+						&self.theme.get_syntax(),
+					)
+				});
+		}
 	}
 
 	fn move_selection(&mut self, move_type: ScrollType) {
-		if let Some(diff) = &self.diff {
-			let max = diff.lines.saturating_sub(1);
+		if self.diff.is_some() {
+			let max = self.lines_count().saturating_sub(1);
 
 			let new_start = match move_type {
 				ScrollType::Down => {
@@ -250,16 +345,23 @@ impl DiffComponent {
 
 	fn update_selection(&mut self, new_start: usize) {
 		if let Some(diff) = &self.diff {
-			let max = diff.lines.saturating_sub(1);
+			let max = self.lines_count().saturating_sub(1);
 			let new_start = cmp::min(max, new_start);
 			self.selection = Selection::Single(new_start);
 			self.selected_hunk =
 				Self::find_selected_hunk(diff, new_start);
+
+			if diff.truncated && new_start >= diff.lines {
+				self.load_more();
+			}
 		}
 	}
 
+	/// diff lines plus the marker of a truncated diff
 	fn lines_count(&self) -> usize {
-		self.diff.as_ref().map_or(0, |diff| diff.lines)
+		self.diff.as_ref().map_or(0, |diff| {
+			diff.lines + usize::from(diff.truncated)
+		})
 	}
 
 	fn max_scroll_right(&self) -> usize {
@@ -328,6 +430,8 @@ impl DiffComponent {
 		if let Some(diff) = &self.diff {
 			return if diff.hunks.is_empty() {
 				self.get_text_binary(diff)
+			} else if let Some(lfs) = &diff.lfs {
+				self.get_text_lfs(lfs)
 			} else {
 				let mut res: Vec<Line> = Vec::new();
 
@@ -357,19 +461,46 @@ impl DiffComponent {
 							if line_cursor >= min
 								&& line_cursor <= max
 							{
-								res.push(Self::get_line_to_add(
-									width,
-									line,
-									self.focused()
-										&& self
-											.selection
-											.contains(line_cursor),
-									hunk_selected,
-									i == hunk_len - 1,
-									&self.theme,
-									self.horizontal_scroll
-										.get_right(),
-								));
+								let selected = self.focused()
+									&& self
+										.selection
+										.contains(line_cursor);
+								let end_of_hunk = i == hunk_len - 1;
+								let scrolled_right = self
+									.horizontal_scroll
+									.get_right();
+
+								res.push(
+									self.highlight_of(
+										line,
+										line_cursor,
+									)
+									.map_or_else(
+										|| {
+											Self::get_line_to_add(
+												width,
+												line,
+												selected,
+												hunk_selected,
+												end_of_hunk,
+												&self.theme,
+												scrolled_right,
+											)
+										},
+										|highlight| {
+											Self::get_highlighted_line_to_add(
+													width,
+													line,
+													highlight,
+													selected,
+													hunk_selected,
+													end_of_hunk,
+													&self.theme,
+													scrolled_right,
+												)
+										},
+									),
+								);
 								lines_added += 1;
 							}
 
@@ -380,6 +511,16 @@ impl DiffComponent {
 					}
 				}
 
+				if diff.truncated
+					&& lines_added < height as usize
+					&& line_cursor >= min
+				{
+					res.push(self.get_load_more_line(
+						self.focused()
+							&& self.selection.contains(line_cursor),
+					));
+				}
+
 				res
 			};
 		}
@@ -387,6 +528,38 @@ impl DiffComponent {
 		vec![]
 	}
 
+	fn get_load_more_line(&self, selected: bool) -> Line {
+		Line::from(Span::styled(
+			Cow::from(if self.loading_more {
+				strings::DIFF_LOADING_MORE.to_string()
+			} else {
+				strings::diff_load_more(
+					self.diff.as_ref().map_or(0, |diff| diff.lines),
+				)
+			}),
+			self.theme.text(false, selected),
+		))
+	}
+
+	/// syntax highlight of a content line, empty lines keep showing
+	/// the line break symbol
+	fn highlight_of(
+		&self,
+		line: &DiffLine,
+		line_cursor: usize,
+	) -> Option<&DiffLineHighlight> {
+		if line.content.is_empty()
+			|| matches!(line.line_type, DiffLineType::Header)
+		{
+			return None;
+		}
+
+		self.highlight
+			.as_ref()
+			.and_then(|highlight| highlight.get(line_cursor))
+			.filter(|highlight| !highlight.is_empty())
+	}
+
 	fn get_text_binary(&self, diff: &FileDiff) -> Vec<Line> {
 		let is_positive = diff.size_delta >= 0;
 		let delta_byte_size =
@@ -419,6 +592,32 @@ impl DiffComponent {
 		])]
 	}
 
+	/// the objects the pointer files refer to instead of their content
+	fn get_text_lfs(&self, lfs: &LfsDiff) -> Vec<Line> {
+		let side = |pointer: &Option<LfsPointer>,
+		            line_type: DiffLineType,
+		            sign: char| {
+			pointer.as_ref().map(|pointer| {
+				Line::from(Span::styled(
+					Cow::from(format!(
+						"{sign} {} ({})",
+						pointer.short_oid(),
+						ByteSize::b(pointer.size)
+					)),
+					self.theme.diff_line(line_type, false),
+				))
+			})
+		};
+
+		std::iter::once(Line::from(Span::styled(
+			Cow::from(strings::DIFF_LFS_OBJECT),
+			self.theme.text(false, false),
+		)))
+		.chain(side(&lfs.old, DiffLineType::Delete, '-'))
+		.chain(side(&lfs.new, DiffLineType::Add, '+'))
+		.collect()
+	}
+
 	fn get_line_to_add<'a>(
 		width: u16,
 		line: &'a DiffLine,
@@ -428,25 +627,15 @@ impl DiffComponent {
 		theme: &SharedTheme,
 		scrolled_right: usize,
 	) -> Line<'a> {
-		let style = theme.diff_hunk_marker(selected_hunk);
-
 		let is_content_line =
 			matches!(line.line_type, DiffLineType::None);
 
-		let left_side_of_line = if end_of_hunk {
-			Span::styled(Cow::from(symbols::line::BOTTOM_LEFT), style)
-		} else {
-			match line.line_type {
-				DiffLineType::Header => Span::styled(
-					Cow::from(symbols::line::TOP_LEFT),
-					style,
-				),
-				_ => Span::styled(
-					Cow::from(symbols::line::VERTICAL),
-					style,
-				),
-			}
-		};
+		let left_side_of_line = Self::hunk_marker(
+			line,
+			selected_hunk,
+			end_of_hunk,
+			theme,
+		);
 
 		let content =
 			if !is_content_line && line.content.as_ref().is_empty() {
@@ -473,6 +662,88 @@ impl DiffComponent {
 		])
 	}
 
+	/// like `get_line_to_add` but colors the content using the
+	/// syntax `highlight` of the line
+	#[allow(clippy::too_many_arguments)]
+	fn get_highlighted_line_to_add<'a>(
+		width: u16,
+		line: &'a DiffLine,
+		highlight: &DiffLineHighlight,
+		selected: bool,
+		selected_hunk: bool,
+		end_of_hunk: bool,
+		theme: &SharedTheme,
+		scrolled_right: usize,
+	) -> Line<'a> {
+		let content =
+			tabs_to_spaces(line.content.as_ref().to_string());
+		let visible = trim_offset(&content, scrolled_right);
+		let offset = content.len() - visible.len();
+
+		let mut spans = vec![Self::hunk_marker(
+			line,
+			selected_hunk,
+			end_of_hunk,
+			theme,
+		)];
+
+		for (style, range) in highlight {
+			let start = range.start.max(offset);
+			if let Some(text) = content.get(start..range.end) {
+				if !text.is_empty() {
+					spans.push(Span::styled(
+						Cow::from(text.to_string()),
+						theme.diff_line_highlighted(
+							line.line_type,
+							*style,
+							selected,
+						),
+					));
+				}
+			}
+		}
+
+		let filled = if selected {
+			// pad the selected line up to the full width
+			let padding =
+				usize::from(width).saturating_sub(visible.width());
+			format!("{:padding$}\n", "")
+		} else {
+			String::from("\n")
+		};
+
+		spans.push(Span::styled(
+			Cow::from(filled),
+			theme.diff_line(line.line_type, selected),
+		));
+
+		Line::from(spans)
+	}
+
+	fn hunk_marker(
+		line: &DiffLine,
+		selected_hunk: bool,
+		end_of_hunk: bool,
+		theme: &SharedTheme,
+	) -> Span<'static> {
+		let style = theme.diff_hunk_marker(selected_hunk);
+
+		if end_of_hunk {
+			Span::styled(Cow::from(symbols::line::BOTTOM_LEFT), style)
+		} else {
+			match line.line_type {
+				DiffLineType::Header => Span::styled(
+					Cow::from(symbols::line::TOP_LEFT),
+					style,
+				),
+				_ => Span::styled(
+					Cow::from(symbols::line::VERTICAL),
+					style,
+				),
+			}
+		}
+	}
+
 	const fn hunk_visible(
 		hunk_min: usize,
 		hunk_max: usize,
@@ -536,6 +807,166 @@ impl DiffComponent {
 		Ok(())
 	}
 
+	/// whitespace ignoring modes in effect, shown in the title
+	fn whitespace_info(&self) -> Option<String> {
+		let options = self.options.borrow().diff_options();
+
+		let modes = [
+			(
+				options.ignore_whitespace,
+				strings::DIFF_IGNORE_ALL_SPACE,
+			),
+			(
+				options.ignore_whitespace_change
+					&& !options.ignore_whitespace,
+				strings::DIFF_IGNORE_SPACE_CHANGE,
+			),
+			(
+				options.ignore_blank_lines,
+				strings::DIFF_IGNORE_BLANK_LINES,
+			),
+		]
+		.into_iter()
+		.filter_map(|(on, mode)| on.then_some(mode))
+		.collect::<Vec<_>>();
+
+		(!modes.is_empty())
+			.then(|| strings::diff_ignoring_suffix(&modes))
+	}
+
+	fn cycle_whitespace(&self) {
+		self.options.borrow_mut().diff_cycle_whitespace();
+		self.queue.push(InternalEvent::OptionSwitched(
+			AppOption::DiffIgnoreWhitespaces,
+		));
+	}
+
+	fn change_context(&self, increase: bool) {
+		self.options.borrow_mut().diff_context_change(increase);
+		self.queue.push(InternalEvent::OptionSwitched(
+			AppOption::DiffContextLines,
+		));
+	}
+
+	fn toggle_blank_lines(&self) {
+		self.options.borrow_mut().diff_toggle_blank_lines();
+		self.queue.push(InternalEvent::OptionSwitched(
+			AppOption::DiffIgnoreBlankLines,
+		));
+	}
+
+	/// textconv output can't be mapped back onto the actual content
+	fn is_editable(&self) -> bool {
+		!self.is_immutable
+			&& self
+				.diff
+				.as_ref()
+				.map_or(true, |diff| diff.textconv.is_none())
+	}
+
+	fn can_edit_hunk(&self) -> bool {
+		self.is_editable()
+			&& !self.is_stage()
+			&& self.selected_hunk.is_some()
+			&& self.diff.as_ref().is_some_and(|diff| !diff.untracked)
+	}
+
+	fn edit_hunk(&self) {
+		if let (Some(diff), Some(hunk)) =
+			(&self.diff, self.selected_hunk)
+		{
+			self.queue.push(InternalEvent::OpenEditHunk(
+				self.current.path.clone(),
+				diff.hunks[hunk].header_hash,
+			));
+		}
+	}
+
+	/// hunks touched by the current selection
+	/// header hashes of all hunks touched by the selection
+	pub fn selected_hunk_hashes(&self) -> Vec<u64> {
+		self.diff.as_ref().map_or_else(Vec::new, |diff| {
+			self.selected_hunks()
+				.into_iter()
+				.map(|hunk| diff.hunks[hunk].header_hash)
+				.collect()
+		})
+	}
+
+	fn selected_hunks(&self) -> Vec<usize> {
+		let Some(diff) = &self.diff else {
+			return Vec::new();
+		};
+
+		let top = self.selection.get_top();
+		let bottom = self.selection.get_bottom();
+
+		let mut line_cursor = 0_usize;
+		let mut res = Vec::new();
+		for (i, hunk) in diff.hunks.iter().enumerate() {
+			let hunk_min = line_cursor;
+			line_cursor += hunk.lines.len();
+
+			if hunk_min <= bottom && line_cursor > top {
+				res.push(i);
+			}
+		}
+
+		res
+	}
+
+	fn can_apply_hunks(&self) -> bool {
+		self.commit.is_some()
+			&& self.diff.as_ref().is_some_and(|diff| {
+				!diff.hunks.is_empty() && diff.textconv.is_none()
+			})
+	}
+
+	fn apply_commit_hunks(&self, reverse: bool) -> Result<()> {
+		if let Some(commit) = self.commit {
+			sync::apply_commit_hunks(
+				&self.repo.borrow(),
+				commit,
+				&self.current.path,
+				&self.selected_hunk_hashes(),
+				reverse,
+				Some(self.options.borrow().diff_options()),
+			)?;
+
+			self.queue_update();
+		}
+
+		Ok(())
+	}
+
+	fn can_stash_hunks(&self) -> bool {
+		self.is_editable()
+			&& !self.is_stage()
+			&& self.commit.is_none()
+			&& self.diff.as_ref().is_some_and(|diff| {
+				!diff.untracked && !diff.hunks.is_empty()
+			})
+	}
+
+	fn stash_hunks(&self) -> Result<()> {
+		if self.diff.is_some() {
+			let hunks = self.selected_hunk_hashes();
+
+			self.queue.push(InternalEvent::PopupStashing(
+				StashingOptions::from_options(
+					&self.repo,
+					&self.options,
+				)?,
+				StashSelection::Hunks {
+					path: self.current.path.clone(),
+					hunks,
+				},
+			));
+		}
+
+		Ok(())
+	}
+
 	fn queue_update(&self) {
 		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
 	}
@@ -565,24 +996,21 @@ impl DiffComponent {
 	}
 
 	fn stage_lines(&self) {
-		if let Some(diff) = &self.diff {
-			//TODO: support untracked files as well
-			if !diff.untracked {
-				let selected_lines = self.selected_lines();
-
-				try_or_popup!(
-					self,
-					"(un)stage lines:",
-					sync::stage_lines(
-						&self.repo.borrow(),
-						&self.current.path,
-						self.is_stage(),
-						&selected_lines,
-					)
-				);
+		if self.diff.is_some() {
+			let selected_lines = self.selected_lines();
 
-				self.queue_update();
-			}
+			try_or_popup!(
+				self,
+				"(un)stage lines:",
+				sync::stage_lines(
+					&self.repo.borrow(),
+					&self.current.path,
+					self.is_stage(),
+					&selected_lines,
+				)
+			);
+
+			self.queue_update();
 		}
 	}
 
@@ -700,9 +1128,19 @@ impl DrawableComponent for DiffComponent {
 		);
 
 		let title = format!(
-			"{}{}",
+			"{}{}{}{}{}",
 			strings::title_diff(&self.key_config),
-			self.current.path
+			self.current.path,
+			self.diff
+				.as_ref()
+				.and_then(|diff| diff.textconv.as_deref())
+				.map(strings::diff_textconv_suffix)
+				.unwrap_or_default(),
+			self.whitespace_info().unwrap_or_default(),
+			self.title_info
+				.as_ref()
+				.map(|info| format!(" ({info})"))
+				.unwrap_or_default()
 		);
 
 		let txt = if self.pending {
@@ -768,8 +1206,27 @@ impl Component for DiffComponent {
 			)
 			.hidden(),
 		);
+		out.push(CommandInfo::new(
+			strings::commands::diff_ignore_whitespace(
+				&self.key_config,
+			),
+			true,
+			self.focused(),
+		));
+		out.push(CommandInfo::new(
+			strings::commands::diff_ignore_blank_lines(
+				&self.key_config,
+			),
+			true,
+			self.focused(),
+		));
+		out.push(CommandInfo::new(
+			strings::commands::diff_context_change(&self.key_config),
+			true,
+			self.focused(),
+		));
 
-		if !self.is_immutable {
+		if self.is_editable() {
 			out.push(CommandInfo::new(
 				strings::commands::diff_hunk_remove(&self.key_config),
 				self.selected_hunk.is_some(),
@@ -785,6 +1242,16 @@ impl Component for DiffComponent {
 				self.selected_hunk.is_some(),
 				self.focused() && !self.is_stage(),
 			));
+			out.push(CommandInfo::new(
+				strings::commands::diff_hunk_edit(&self.key_config),
+				self.can_edit_hunk(),
+				self.focused() && !self.is_stage(),
+			));
+			out.push(CommandInfo::new(
+				strings::commands::diff_hunks_stash(&self.key_config),
+				self.can_stash_hunks(),
+				self.focused() && !self.is_stage(),
+			));
 			out.push(CommandInfo::new(
 				strings::commands::diff_lines_revert(
 					&self.key_config,
@@ -809,6 +1276,21 @@ impl Component for DiffComponent {
 			));
 		}
 
+		if self.commit.is_some() {
+			out.push(CommandInfo::new(
+				strings::commands::diff_hunks_apply(&self.key_config),
+				self.can_apply_hunks(),
+				self.focused(),
+			));
+			out.push(CommandInfo::new(
+				strings::commands::diff_hunks_revert_commit(
+					&self.key_config,
+				),
+				self.can_apply_hunks(),
+				self.focused(),
+			));
+		}
+
 		out.push(CommandInfo::new(
 			strings::commands::copy(&self.key_config),
 			true,
@@ -876,10 +1358,34 @@ impl Component for DiffComponent {
 				) {
 					self.diff_hunk_move_up_down(-1);
 					Ok(EventState::Consumed)
+				} else if key_match(
+					e,
+					self.key_config.keys.diff_ignore_whitespace,
+				) {
+					self.cycle_whitespace();
+					Ok(EventState::Consumed)
+				} else if key_match(
+					e,
+					self.key_config.keys.diff_ignore_blank_lines,
+				) {
+					self.toggle_blank_lines();
+					Ok(EventState::Consumed)
+				} else if key_match(
+					e,
+					self.key_config.keys.diff_context_increase,
+				) {
+					self.change_context(true);
+					Ok(EventState::Consumed)
+				} else if key_match(
+					e,
+					self.key_config.keys.diff_context_decrease,
+				) {
+					self.change_context(false);
+					Ok(EventState::Consumed)
 				} else if key_match(
 					e,
 					self.key_config.keys.stage_unstage_item,
-				) && !self.is_immutable
+				) && self.is_editable()
 				{
 					try_or_popup!(
 						self,
@@ -891,7 +1397,7 @@ impl Component for DiffComponent {
 				} else if key_match(
 					e,
 					self.key_config.keys.status_reset_item,
-				) && !self.is_immutable
+				) && self.is_editable()
 					&& !self.is_stage()
 				{
 					if let Some(diff) = &self.diff {
@@ -905,23 +1411,60 @@ impl Component for DiffComponent {
 				} else if key_match(
 					e,
 					self.key_config.keys.diff_stage_lines,
-				) && !self.is_immutable
+				) && self.is_editable()
 				{
 					self.stage_lines();
 					Ok(EventState::Consumed)
+				} else if key_match(
+					e,
+					self.key_config.keys.diff_edit_hunk,
+				) && self.can_edit_hunk()
+				{
+					self.edit_hunk();
+					Ok(EventState::Consumed)
 				} else if key_match(
 					e,
 					self.key_config.keys.diff_reset_lines,
-				) && !self.is_immutable
+				) && self.is_editable()
 					&& !self.is_stage()
 				{
-					if let Some(diff) = &self.diff {
-						//TODO: reset untracked lines
-						if !diff.untracked {
-							self.reset_lines();
-						}
+					if self.diff.is_some() {
+						self.reset_lines();
 					}
 					Ok(EventState::Consumed)
+				} else if key_match(
+					e,
+					self.key_config.keys.diff_apply_hunks,
+				) && self.can_apply_hunks()
+				{
+					try_or_popup!(
+						self,
+						"apply hunks error:",
+						self.apply_commit_hunks(false)
+					);
+					Ok(EventState::Consumed)
+				} else if key_match(
+					e,
+					self.key_config.keys.diff_revert_hunks,
+				) && self.can_apply_hunks()
+				{
+					try_or_popup!(
+						self,
+						"revert hunks error:",
+						self.apply_commit_hunks(true)
+					);
+					Ok(EventState::Consumed)
+				} else if key_match(
+					e,
+					self.key_config.keys.diff_stash_hunks,
+				) && self.can_stash_hunks()
+				{
+					try_or_popup!(
+						self,
+						"stash hunks error:",
+						self.stash_hunks()
+					);
+					Ok(EventState::Consumed)
 				} else if key_match(e, self.key_config.keys.copy) {
 					self.copy_selection();
 					Ok(EventState::Consumed)
@@ -1012,4 +1555,40 @@ mod tests {
 			);
 		}
 	}
+
+	#[test]
+	fn test_highlighted_line_scrolled() {
+		let diff_line = DiffLine {
+			content: "let x".into(),
+			line_type: DiffLineType::Add,
+			position: DiffLinePosition::default(),
+		};
+		let keyword = ratatui::style::Style::default()
+			.fg(ratatui::style::Color::Red);
+		let highlight = vec![
+			(keyword, 0..3),
+			(ratatui::style::Style::default(), 3..5),
+		];
+		let theme = Rc::new(Theme::default());
+
+		let line = DiffComponent::get_highlighted_line_to_add(
+			10, &diff_line, &highlight, false, false, false, &theme,
+			2,
+		);
+
+		let texts: Vec<_> = line
+			.spans
+			.iter()
+			.map(|span| span.content.as_ref())
+			.collect();
+		assert_eq!(texts[1..], ["t", " x", "\n"]);
+		assert_eq!(
+			line.spans[1].style,
+			theme.diff_line_highlighted(
+				DiffLineType::Add,
+				keyword,
+				false
+			)
+		);
+	}
 }
@@ -17,7 +17,8 @@ use anyhow::Result;
 use asyncgit::{
 	asyncjob::AsyncSingleJob,
 	sync::{
-		get_commit_info, CommitId, CommitInfo, RepoPathRef, TreeFile,
+		self, get_commit_info, CommitId, CommitInfo, RepoPathRef,
+		TreeFile,
 	},
 	AsyncGitNotification, AsyncTreeFilesJob,
 };
@@ -39,6 +40,13 @@ enum Focus {
 	File,
 }
 
+/// stepping through the versions of the selected file
+struct FileRevisionsState {
+	path: String,
+	commits: Vec<CommitId>,
+	idx: usize,
+}
+
 pub struct RevisionFilesComponent {
 	repo: RepoPathRef,
 	queue: Queue,
@@ -52,6 +60,9 @@ pub struct RevisionFilesComponent {
 	visible: bool,
 	revision: Option<CommitInfo>,
 	focus: Focus,
+	file_revisions: Option<FileRevisionsState>,
+	/// selected file is tracked by git LFS
+	lfs_tracked: bool,
 	key_config: SharedKeyConfig,
 }
 
@@ -70,6 +81,8 @@ impl RevisionFilesComponent {
 			),
 			revision: None,
 			focus: Focus::Tree,
+			file_revisions: None,
+			lfs_tracked: false,
 			key_config: env.key_config.clone(),
 			repo: env.repo.clone(),
 			visible: false,
@@ -85,6 +98,7 @@ impl RevisionFilesComponent {
 
 		if !same_id {
 			self.files = None;
+			self.file_revisions = None;
 
 			self.request_files(commit);
 
@@ -212,6 +226,14 @@ impl RevisionFilesComponent {
 		})
 	}
 
+	fn path_log(&self) -> bool {
+		self.selected_item_path().is_some_and(|path| {
+			self.queue.push(InternalEvent::OpenLogForPath(path));
+
+			true
+		})
+	}
+
 	fn open_finder(&self) {
 		if let Some(files) = self.files.clone() {
 			self.queue.push(InternalEvent::OpenFuzzyFinder(
@@ -251,7 +273,113 @@ impl RevisionFilesComponent {
 		})
 	}
 
+	/// path of the selected file or folder
+	fn selected_item_path(&self) -> Option<String> {
+		self.tree.selected_item().and_then(|item| {
+			item.full_path_str()
+				.strip_prefix("./")
+				.filter(|path| !path.is_empty())
+				.map(ToString::to_string)
+		})
+	}
+
+	/// shows the selected file as of the next older (or newer)
+	/// commit changing it
+	fn step_file_revision(&mut self, older: bool) -> Result<()> {
+		let (Some(path), Some(revision)) =
+			(self.selected_file_path(), self.revision.as_ref())
+		else {
+			return Ok(());
+		};
+
+		if !self
+			.file_revisions
+			.as_ref()
+			.is_some_and(|state| state.path == path)
+		{
+			let revisions = sync::file_revisions(
+				&self.repo.borrow(),
+				&path,
+				revision.id,
+			)?;
+
+			let Some(idx) = revisions.current else {
+				return Ok(());
+			};
+
+			self.file_revisions = Some(FileRevisionsState {
+				path: path.clone(),
+				commits: revisions.commits,
+				idx,
+			});
+		}
+
+		let Some(state) = self.file_revisions.as_mut() else {
+			return Ok(());
+		};
+
+		let idx = if older {
+			state.idx.saturating_add(1)
+		} else {
+			state.idx.saturating_sub(1)
+		};
+
+		if idx == state.idx || idx >= state.commits.len() {
+			return Ok(());
+		}
+
+		state.idx = idx;
+
+		let commit = state.commits[idx];
+		let count = state.commits.len();
+
+		if let Some(item) =
+			sync::tree_file(&self.repo.borrow(), commit, &path)?
+		{
+			let info = get_commit_info(&self.repo.borrow(), &commit)?;
+
+			self.current_file.load_file_revision(
+				path,
+				&item,
+				strings::file_revision(
+					&commit.get_short_string(),
+					count - idx,
+					count,
+					info.message.lines().next().unwrap_or_default(),
+				),
+			);
+		}
+
+		Ok(())
+	}
+
+	fn lfs_lock(&self, lock: bool) -> Result<()> {
+		let Some(path) = self.selected_file_path() else {
+			return Ok(());
+		};
+
+		let repo = self.repo.borrow();
+		let msg = if lock {
+			sync::lfs_lock(&repo, &path)?
+		} else {
+			sync::lfs_unlock(&repo, &path)?
+		};
+
+		if !msg.is_empty() {
+			self.queue.push(InternalEvent::ShowInfoMsg(msg));
+		}
+
+		Ok(())
+	}
+
 	fn selection_changed(&mut self) {
+		self.file_revisions = None;
+		self.lfs_tracked =
+			self.selected_file_path().is_some_and(|path| {
+				sync::is_lfs_tracked(&self.repo.borrow(), &path)
+					.unwrap_or_default()
+			});
+
 		//TODO: retrieve TreeFile from tree datastructure
 		if let Some(file) = self.selected_file_path_with_prefix() {
 			if let Some(files) = &self.files {
@@ -436,6 +564,16 @@ impl Component for RevisionFilesComponent {
 				)
 				.order(order::RARE_ACTION),
 			);
+			out.push(
+				CommandInfo::new(
+					strings::commands::open_path_log(
+						&self.key_config,
+					),
+					self.selected_item_path().is_some(),
+					true,
+				)
+				.order(order::RARE_ACTION),
+			);
 			out.push(
 				CommandInfo::new(
 					strings::commands::copy_path(&self.key_config),
@@ -444,11 +582,38 @@ impl Component for RevisionFilesComponent {
 				)
 				.order(order::RARE_ACTION),
 			);
+			out.push(
+				CommandInfo::new(
+					strings::commands::lfs_lock(&self.key_config),
+					true,
+					self.lfs_tracked,
+				)
+				.order(order::RARE_ACTION),
+			);
+			out.push(
+				CommandInfo::new(
+					strings::commands::lfs_unlock(&self.key_config),
+					true,
+					self.lfs_tracked,
+				)
+				.order(order::RARE_ACTION),
+			);
 			tree_nav_cmds(&self.tree, &self.key_config, out);
 		} else {
 			self.current_file.commands(out, force_all);
 		}
 
+		out.push(
+			CommandInfo::new(
+				strings::commands::file_revision_step(
+					&self.key_config,
+				),
+				self.tree.selected_file().is_some(),
+				true,
+			)
+			.order(order::RARE_ACTION),
+		);
+
 		CommandBlocking::PassingOn
 	}
 
@@ -480,6 +645,27 @@ impl Component for RevisionFilesComponent {
 					self.hide();
 					return Ok(EventState::Consumed);
 				}
+			} else if key_match(
+				key,
+				self.key_config.keys.file_revision_prev,
+			) || key_match(
+				key,
+				self.key_config.keys.file_revision_next,
+			) {
+				let older = key_match(
+					key,
+					self.key_config.keys.file_revision_prev,
+				);
+				try_or_popup!(
+					self,
+					"file revision error:",
+					self.step_file_revision(older)
+				);
+				return Ok(EventState::Consumed);
+			} else if key_match(key, self.key_config.keys.file_log) {
+				if is_tree_focused && self.path_log() {
+					return Ok(EventState::Consumed);
+				}
 			} else if key_match(key, self.key_config.keys.move_right)
 			{
 				if is_tree_focused {
@@ -512,6 +698,19 @@ impl Component for RevisionFilesComponent {
 					);
 					return Ok(EventState::Consumed);
 				}
+			} else if (key_match(key, self.key_config.keys.lfs_lock)
+				|| key_match(key, self.key_config.keys.lfs_unlock))
+				&& is_tree_focused
+				&& self.lfs_tracked
+			{
+				let lock =
+					key_match(key, self.key_config.keys.lfs_lock);
+				try_or_popup!(
+					self,
+					"lfs lock error:",
+					self.lfs_lock(lock)
+				);
+				return Ok(EventState::Consumed);
 			} else if key_match(key, self.key_config.keys.copy) {
 				if let Some(file) = self.selected_file_path() {
 					try_or_popup!(
@@ -15,10 +15,19 @@ use crate::{
 	ui::{self, style::SharedTheme},
 };
 use anyhow::Result;
-use asyncgit::{hash, sync::CommitId, StatusItem, StatusItemType};
+use asyncgit::{
+	hash,
+	sync::{CommitId, LfsFileState, SubmoduleState},
+	StatusItem, StatusItemType,
+};
 use crossterm::event::Event;
 use ratatui::{layout::Rect, text::Span, Frame};
-use std::{borrow::Cow, cell::Cell, path::Path};
+use std::{
+	borrow::Cow,
+	cell::Cell,
+	collections::{BTreeMap, HashSet},
+	path::Path,
+};
 
 //TODO: use new `filetreelist` crate
 
@@ -37,6 +46,10 @@ pub struct StatusTreeComponent {
 	scroll_top: Cell<usize>,
 	visible: bool,
 	revision: Option<CommitId>,
+	submodules: Vec<String>,
+	submodule_states: BTreeMap<String, SubmoduleState>,
+	lfs: BTreeMap<String, LfsFileState>,
+	marked: HashSet<String>,
 }
 
 impl StatusTreeComponent {
@@ -55,6 +68,10 @@ impl StatusTreeComponent {
 			pending: true,
 			visible: false,
 			revision: None,
+			submodules: Vec::new(),
+			submodule_states: BTreeMap::new(),
+			lfs: BTreeMap::new(),
+			marked: HashSet::new(),
 		}
 	}
 
@@ -70,11 +87,49 @@ impl StatusTreeComponent {
 		if self.current_hash != new_hash {
 			self.tree.update(list)?;
 			self.current_hash = new_hash;
+			self.marked.retain(|path| {
+				list.iter().any(|item| &item.path == path)
+			});
 		}
 
 		Ok(())
 	}
 
+	/// paths of the submodules whose inner changes are listed
+	pub fn set_submodules(&mut self, submodules: Vec<String>) {
+		self.submodules = submodules;
+	}
+
+	/// submodules that are dirty or not at their recorded commit
+	pub fn set_submodule_states(
+		&mut self,
+		states: BTreeMap<String, SubmoduleState>,
+	) {
+		self.submodule_states = states;
+	}
+
+	/// workdir state of the listed files tracked by git LFS
+	pub fn set_lfs_files(
+		&mut self,
+		lfs: BTreeMap<String, LfsFileState>,
+	) {
+		self.lfs = lfs;
+	}
+
+	///
+	pub fn lfs_state(&self, path: &str) -> Option<LfsFileState> {
+		self.lfs.get(path).copied()
+	}
+
+	/// submodule containing `path` (or being `path` itself)
+	pub fn submodule_of(&self, path: &str) -> Option<&String> {
+		self.submodules.iter().find(|sub| {
+			path.strip_prefix(sub.as_str()).is_some_and(|rest| {
+				rest.is_empty() || rest.starts_with('/')
+			})
+		})
+	}
+
 	///
 	pub fn selection(&self) -> Option<FileTreeItem> {
 		self.tree.selected_item()
@@ -91,6 +146,50 @@ impl StatusTreeComponent {
 		})
 	}
 
+	/// marks or unmarks the selected file, or all files below the
+	/// selected folder
+	pub fn toggle_mark(&mut self) {
+		let Some(selection) = self.selection() else {
+			return;
+		};
+
+		let files = match selection.kind {
+			FileTreeItemKind::File(item) => vec![item.path],
+			FileTreeItemKind::Path(_) => {
+				let folder = format!("{}/", selection.info.full_path);
+				self.tree
+					.tree
+					.items()
+					.iter()
+					.filter_map(|item| match &item.kind {
+						FileTreeItemKind::File(file)
+							if file.path.starts_with(&folder) =>
+						{
+							Some(file.path.clone())
+						}
+						_ => None,
+					})
+					.collect()
+			}
+		};
+
+		if files.iter().all(|file| self.marked.contains(file)) {
+			for file in &files {
+				self.marked.remove(file);
+			}
+		} else {
+			self.marked.extend(files);
+		}
+	}
+
+	/// paths of the marked files, sorted
+	pub fn marked(&self) -> Vec<String> {
+		let mut marked: Vec<_> =
+			self.marked.iter().cloned().collect();
+		marked.sort();
+		marked
+	}
+
 	///
 	pub fn show_selection(&mut self, show: bool) {
 		self.show_selection = show;
@@ -144,51 +243,93 @@ impl StatusTreeComponent {
 			StatusItemType::New => '+',
 			StatusItemType::Deleted => '-',
 			StatusItemType::Renamed => 'R',
+			StatusItemType::Copied => 'C',
 			StatusItemType::Typechange => ' ',
 			StatusItemType::Conflicted => '!',
+			StatusItemType::Ignored => 'I',
+			StatusItemType::AssumeUnchanged => 'a',
+			StatusItemType::SkipWorktree => 'S',
 		}
 	}
 
 	fn item_to_text<'b>(
-		string: &str,
-		indent: usize,
-		visible: bool,
-		file_item_kind: &FileTreeItemKind,
+		&self,
+		draw_text_info: &TextDrawInfo,
 		width: u16,
 		selected: bool,
 		theme: &'b SharedTheme,
 	) -> Option<Span<'b>> {
+		let string = &draw_text_info.name;
+		let indent = draw_text_info.indent as usize;
 		let indent_str = if indent == 0 {
 			String::new()
 		} else {
 			format!("{:w$}", " ", w = indent * 2)
 		};
 
-		if !visible {
+		if !draw_text_info.visible {
 			return None;
 		}
 
-		match file_item_kind {
+		match draw_text_info.item_kind {
 			FileTreeItemKind::File(status_item) => {
 				let status_char =
 					Self::item_status_char(status_item.status);
+				let mark_char =
+					if self.marked.contains(&status_item.path) {
+						'*'
+					} else {
+						' '
+					};
 				let file = Path::new(&status_item.path)
 					.file_name()
 					.and_then(std::ffi::OsStr::to_str)
 					.expect("invalid path.");
-
-				let txt = if selected {
-					format!(
-						"{} {}{:w$}",
-						status_char,
-						indent_str,
-						file,
-						w = width as usize
-					)
-				} else {
-					format!("{status_char} {indent_str}{file}")
+				let renamed =
+					status_item.old_path.as_ref().map(|old| {
+						strings::status_renamed_from(old, file)
+					});
+				let file = renamed.as_deref().unwrap_or(file);
+				let file = match self.lfs_state(&status_item.path) {
+					Some(LfsFileState::Pointer) => {
+						Cow::from(format!(
+							"{file}{}",
+							strings::STATUS_LFS_POINTER_SUFFIX
+						))
+					}
+					Some(LfsFileState::Downloaded) => {
+						Cow::from(format!(
+							"{file}{}",
+							strings::STATUS_LFS_SUFFIX
+						))
+					}
+					None => self
+						.submodule_states
+						.get(&status_item.path)
+						.map_or(Cow::from(file), |state| {
+							Cow::from(format!(
+								"{file}{}",
+								strings::status_submodule_suffix(
+									Some(state)
+								)
+							))
+						}),
 				};
 
+				let txt =
+					if selected {
+						format!(
+							"{}{}{}{:w$}",
+							status_char,
+							mark_char,
+							indent_str,
+							file,
+							w = width as usize
+						)
+					} else {
+						format!("{status_char}{mark_char}{indent_str}{file}")
+					};
+
 				Some(Span::styled(
 					Cow::from(txt),
 					theme.item(status_item.status, selected),
@@ -243,8 +384,22 @@ impl StatusTreeComponent {
 				selection_offset_visible += 1;
 			}
 
+			let name =
+				if self.submodules.contains(&item.info.full_path) {
+					format!(
+						"{}{}",
+						item.info.path,
+						strings::status_submodule_suffix(
+							self.submodule_states
+								.get(&item.info.full_path)
+						)
+					)
+				} else {
+					item.info.path.clone()
+				};
+
 			vec_draw_text_info.push(TextDrawInfo {
-				name: item.info.path.clone(),
+				name,
 				indent: item.info.indent,
 				visible: item.info.visible,
 				item_kind: &item.kind,
@@ -362,11 +517,8 @@ impl DrawableComponent for StatusTreeComponent {
 				.iter()
 				.enumerate()
 				.filter_map(|(index, draw_text_info)| {
-					Self::item_to_text(
-						&draw_text_info.name,
-						draw_text_info.indent as usize,
-						draw_text_info.visible,
-						draw_text_info.item_kind,
+					self.item_to_text(
+						draw_text_info,
 						r.width,
 						self.show_selection && select == index,
 						&self.theme,
@@ -423,6 +575,16 @@ impl Component for StatusTreeComponent {
 			.order(order::RARE_ACTION),
 		);
 
+		out.push(
+			CommandInfo::new(
+				strings::commands::restore_file(&self.key_config),
+				self.selection_file().is_some(),
+				(self.focused && self.revision.is_some())
+					|| force_all,
+			)
+			.order(order::RARE_ACTION),
+		);
+
 		out.push(
 			CommandInfo::new(
 				strings::commands::edit_item(&self.key_config),
@@ -474,6 +636,22 @@ impl Component for StatusTreeComponent {
 						));
 					}
 					Ok(EventState::Consumed)
+				} else if key_match(
+					e,
+					self.key_config.keys.restore_file,
+				) && self.revision.is_some()
+				{
+					if let (Some(status_item), Some(id)) =
+						(self.selection_file(), self.revision)
+					{
+						self.queue.push(
+							InternalEvent::OpenRestoreFilePopup(
+								id,
+								status_item.path,
+							),
+						);
+					}
+					Ok(EventState::Consumed)
 				} else if key_match(e, self.key_config.keys.edit_file)
 				{
 					if let Some(status_item) = self.selection_file() {
@@ -557,6 +735,7 @@ mod tests {
 			.map(|a| StatusItem {
 				path: String::from(*a),
 				status: StatusItemType::Modified,
+				old_path: None,
 			})
 			.collect::<Vec<_>>()
 	}
@@ -33,6 +33,8 @@ use std::{cell::Cell, path::Path};
 pub struct SyntaxTextComponent {
 	repo: RepoPathRef,
 	current_file: Option<(String, Either<ui::SyntaxText, String>)>,
+	/// shown next to the path when browsing older versions of a file
+	current_revision: Option<String>,
 	async_highlighting: AsyncSingleJob<AsyncSyntaxJob>,
 	syntax_progress: Option<ProgressPercent>,
 	key_config: SharedKeyConfig,
@@ -50,6 +52,7 @@ impl SyntaxTextComponent {
 			),
 			syntax_progress: None,
 			current_file: None,
+			current_revision: None,
 			paragraph_state: Cell::new(ParagraphState::default()),
 			focused: false,
 			key_config: env.key_config.clone(),
@@ -97,16 +100,38 @@ impl SyntaxTextComponent {
 	///
 	pub fn clear(&mut self) {
 		self.current_file = None;
+		self.current_revision = None;
 	}
 
 	///
 	pub fn load_file(&mut self, path: String, item: &TreeFile) {
-		let already_loaded = self
-			.current_file
-			.as_ref()
-			.is_some_and(|(current_file, _)| current_file == &path);
+		self.load(path, item, None);
+	}
+
+	/// load the version of a file at `revision` (used in the title)
+	pub fn load_file_revision(
+		&mut self,
+		path: String,
+		item: &TreeFile,
+		revision: String,
+	) {
+		self.load(path, item, Some(revision));
+	}
+
+	fn load(
+		&mut self,
+		path: String,
+		item: &TreeFile,
+		revision: Option<String>,
+	) {
+		let already_loaded =
+			self.current_file.as_ref().is_some_and(
+				|(current_file, _)| current_file == &path,
+			) && self.current_revision == revision;
 
 		if !already_loaded {
+			self.current_revision = revision;
+
 			//TODO: fetch file content async as well
 			match sync::tree_file_content(&self.repo.borrow(), item) {
 				Ok(content) => {
@@ -194,11 +219,15 @@ impl DrawableComponent for SyntaxTextComponent {
 		);
 
 		let title = format!(
-			"{}{}",
+			"{}{}{}",
 			self.current_file
 				.as_ref()
 				.map(|(name, _)| name.clone())
 				.unwrap_or_default(),
+			self.current_revision
+				.as_ref()
+				.map(|rev| format!(" @ {rev}"))
+				.unwrap_or_default(),
 			self.syntax_progress
 				.map(|p| format!(" ({}%)", p.progress))
 				.unwrap_or_default()
@@ -165,22 +165,22 @@ impl TextInputComponent {
 			);
 
 			if !self.embed {
-				text_area.set_block(
-					Block::default()
-						.borders(Borders::ALL)
-						.border_style(
-							ratatui::style::Style::default()
-								.add_modifier(
-									ratatui::style::Modifier::BOLD,
-								),
-						)
-						.title(self.title.clone()),
-				);
+				text_area.set_block(self.block());
 			};
 			text_area
 		});
 	}
 
+	fn block(&self) -> Block<'static> {
+		Block::default()
+			.borders(Borders::ALL)
+			.border_style(
+				ratatui::style::Style::default()
+					.add_modifier(ratatui::style::Modifier::BOLD),
+			)
+			.title(self.title.clone())
+	}
+
 	/// Set the `msg`.
 	pub fn set_text(&mut self, msg: String) {
 		self.msg = msg.into();
@@ -192,6 +192,12 @@ impl TextInputComponent {
 	/// Set the `title`.
 	pub fn set_title(&mut self, t: String) {
 		self.title = t;
+		if !self.embed {
+			let block = self.block();
+			if let Some(ta) = &mut self.textarea {
+				ta.set_block(block);
+			}
+		}
 	}
 
 	///
@@ -268,6 +268,7 @@ mod tests {
 			.map(|a| StatusItem {
 				path: String::from(*a),
 				status: StatusItemType::Modified,
+				old_path: None,
 			})
 			.collect::<Vec<_>>()
 	}
@@ -0,0 +1,196 @@
+//! opt-in local socket (`--ipc <path>`) for editor integrations.
+//!
+//! a client writes one command per line and gets `ok` or
+//! `error: <reason>` back:
+//!
+//! * `refresh`
+//! * `blame <path>[:<line>]`
+//! * `commit <rev>`
+
+use anyhow::Result;
+use crossbeam_channel::{unbounded, Receiver, Sender};
+use std::path::{Path, PathBuf};
+
+/// command received from an ipc client
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IpcCommand {
+	/// update all views
+	Refresh,
+	/// open the blame popup of a file, optionally at a (1-based) line
+	Blame { path: String, line: Option<usize> },
+	/// inspect the commit `rev` resolves to
+	OpenCommit(String),
+}
+
+impl IpcCommand {
+	fn parse(line: &str) -> Result<Self, String> {
+		let line = line.trim();
+		let (cmd, arg) = line
+			.split_once(' ')
+			.map_or((line, ""), |(cmd, arg)| (cmd, arg.trim()));
+
+		match (cmd, arg) {
+			("refresh", "") => Ok(Self::Refresh),
+			("blame", arg) if !arg.is_empty() => {
+				let (path, line) = arg
+					.rsplit_once(':')
+					.and_then(|(path, line)| {
+						Some((path, line.parse::<usize>().ok()?))
+					})
+					.map_or((arg, None), |(path, line)| {
+						(path, Some(line))
+					});
+
+				Ok(Self::Blame {
+					path: path.to_string(),
+					line,
+				})
+			}
+			("commit", arg) if !arg.is_empty() => {
+				Ok(Self::OpenCommit(arg.to_string()))
+			}
+			_ => Err(format!("unknown command: {line}")),
+		}
+	}
+}
+
+/// listens on a unix socket and forwards commands to the app loop,
+/// the socket file is removed again on drop
+pub struct IpcServer {
+	receiver: Receiver<IpcCommand>,
+	path: PathBuf,
+}
+
+impl IpcServer {
+	///
+	pub fn new(path: &Path) -> Result<Self> {
+		let (tx, rx) = unbounded();
+
+		listen(path, tx)?;
+
+		Ok(Self {
+			receiver: rx,
+			path: path.to_path_buf(),
+		})
+	}
+
+	///
+	pub fn receiver(&self) -> Receiver<IpcCommand> {
+		self.receiver.clone()
+	}
+}
+
+impl Drop for IpcServer {
+	fn drop(&mut self) {
+		if let Err(e) = std::fs::remove_file(&self.path) {
+			log::error!("failed to remove ipc socket: {e}");
+		}
+	}
+}
+
+#[cfg(unix)]
+fn listen(path: &Path, sender: Sender<IpcCommand>) -> Result<()> {
+	use std::{
+		io::{BufRead, BufReader, Write},
+		os::unix::net::{UnixListener, UnixStream},
+		thread,
+	};
+
+	// a socket left behind by an instance that did not shut down
+	// cleanly, refuse to steal one that is still in use
+	if path.exists() {
+		if UnixStream::connect(path).is_ok() {
+			anyhow::bail!(
+				"ipc socket already in use: {}",
+				path.display()
+			);
+		}
+		std::fs::remove_file(path)?;
+	}
+
+	let listener = UnixListener::bind(path)?;
+
+	thread::spawn(move || {
+		for stream in listener.incoming() {
+			let stream = match stream {
+				Ok(stream) => stream,
+				Err(e) => {
+					log::error!("ipc accept error: {e}");
+					continue;
+				}
+			};
+
+			let sender = sender.clone();
+
+			thread::spawn(move || {
+				let mut writer = match stream.try_clone() {
+					Ok(writer) => writer,
+					Err(e) => {
+						log::error!("ipc stream error: {e}");
+						return;
+					}
+				};
+
+				for line in BufReader::new(stream).lines() {
+					let Ok(line) = line else {
+						break;
+					};
+
+					let response = match IpcCommand::parse(&line) {
+						Ok(cmd) => {
+							if sender.send(cmd).is_err() {
+								break;
+							}
+							String::from("ok")
+						}
+						Err(e) => format!("error: {e}"),
+					};
+
+					if writeln!(writer, "{response}").is_err() {
+						break;
+					}
+				}
+			});
+		}
+	});
+
+	Ok(())
+}
+
+#[cfg(not(unix))]
+fn listen(_path: &Path, _sender: Sender<IpcCommand>) -> Result<()> {
+	anyhow::bail!("--ipc needs unix sockets and is not supported on this platform")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_parse() {
+		assert_eq!(
+			IpcCommand::parse("refresh\n"),
+			Ok(IpcCommand::Refresh)
+		);
+		assert_eq!(
+			IpcCommand::parse("blame src/main.rs:42"),
+			Ok(IpcCommand::Blame {
+				path: String::from("src/main.rs"),
+				line: Some(42),
+			})
+		);
+		assert_eq!(
+			IpcCommand::parse("blame dir/a:b.rs"),
+			Ok(IpcCommand::Blame {
+				path: String::from("dir/a:b.rs"),
+				line: None,
+			})
+		);
+		assert_eq!(
+			IpcCommand::parse("commit HEAD~2"),
+			Ok(IpcCommand::OpenCommit(String::from("HEAD~2")))
+		);
+		assert!(IpcCommand::parse("blame").is_err());
+		assert!(IpcCommand::parse("refresh now").is_err());
+	}
+}
@@ -52,6 +52,9 @@ pub struct KeysList {
 	pub open_commit_editor: GituiKeyEvent,
 	pub open_help: GituiKeyEvent,
 	pub open_options: GituiKeyEvent,
+	pub clone_repo: GituiKeyEvent,
+	pub undo_history: GituiKeyEvent,
+	pub open_git_config: GituiKeyEvent,
 	pub move_left: GituiKeyEvent,
 	pub move_right: GituiKeyEvent,
 	pub move_up: GituiKeyEvent,
@@ -69,30 +72,105 @@ pub struct KeysList {
 	pub enter: GituiKeyEvent,
 	pub blame: GituiKeyEvent,
 	pub file_history: GituiKeyEvent,
+	pub blame_mark_lines: GituiKeyEvent,
+	pub blame_parent: GituiKeyEvent,
+	pub blame_ignore_revs: GituiKeyEvent,
+	pub file_revision_prev: GituiKeyEvent,
+	pub file_revision_next: GituiKeyEvent,
+	pub file_log: GituiKeyEvent,
+	pub restore_file: GituiKeyEvent,
+	pub restore_toggle_stage: GituiKeyEvent,
 	pub edit_file: GituiKeyEvent,
 	pub status_stage_all: GituiKeyEvent,
 	pub status_reset_item: GituiKeyEvent,
 	pub status_ignore_file: GituiKeyEvent,
+	pub status_show_ignored: GituiKeyEvent,
+	pub status_ignore_rule: GituiKeyEvent,
+	pub status_assume_unchanged: GituiKeyEvent,
+	pub status_skip_worktree: GituiKeyEvent,
+	pub lfs_lock: GituiKeyEvent,
+	pub lfs_unlock: GituiKeyEvent,
+	pub ignore_toggle_file: GituiKeyEvent,
+	pub ignore_edit: GituiKeyEvent,
+	pub status_discarded: GituiKeyEvent,
+	pub status_clean: GituiKeyEvent,
+	pub clean_toggle_entry: GituiKeyEvent,
+	pub clean_toggle_dirs: GituiKeyEvent,
+	pub clean_toggle_ignored: GituiKeyEvent,
+	pub status_apply_patch: GituiKeyEvent,
+	pub status_skip_step: GituiKeyEvent,
+	pub apply_patch_target: GituiKeyEvent,
 	pub diff_stage_lines: GituiKeyEvent,
+	pub diff_edit_hunk: GituiKeyEvent,
 	pub diff_reset_lines: GituiKeyEvent,
+	pub diff_apply_hunks: GituiKeyEvent,
+	pub diff_revert_hunks: GituiKeyEvent,
+	pub diff_stash_hunks: GituiKeyEvent,
 	pub stashing_save: GituiKeyEvent,
 	pub stashing_toggle_untracked: GituiKeyEvent,
 	pub stashing_toggle_index: GituiKeyEvent,
+	pub stashing_toggle_ignored: GituiKeyEvent,
+	pub stashing_mark_file: GituiKeyEvent,
+	pub stash_msg_toggle_untracked: GituiKeyEvent,
+	pub stash_msg_toggle_ignored: GituiKeyEvent,
 	pub stash_apply: GituiKeyEvent,
 	pub stash_open: GituiKeyEvent,
 	pub stash_drop: GituiKeyEvent,
+	pub stash_worktree_diff: GituiKeyEvent,
 	pub cmd_bar_toggle: GituiKeyEvent,
 	pub log_tag_commit: GituiKeyEvent,
 	pub log_mark_commit: GituiKeyEvent,
 	pub log_checkout_commit: GituiKeyEvent,
 	pub log_reset_commit: GituiKeyEvent,
 	pub log_reword_commit: GituiKeyEvent,
+	pub log_move_commit_up: GituiKeyEvent,
+	pub log_move_commit_down: GituiKeyEvent,
+	pub log_rebase_interactive: GituiKeyEvent,
+	pub log_fixup_commit: GituiKeyEvent,
+	pub log_squash_commit: GituiKeyEvent,
+	pub log_autosquash: GituiKeyEvent,
+	pub rebase_pick: GituiKeyEvent,
+	pub rebase_reword: GituiKeyEvent,
+	pub rebase_edit: GituiKeyEvent,
+	pub rebase_squash: GituiKeyEvent,
+	pub rebase_fixup: GituiKeyEvent,
+	pub rebase_drop: GituiKeyEvent,
+	pub log_bisect: GituiKeyEvent,
+	pub bisect_good: GituiKeyEvent,
+	pub bisect_bad: GituiKeyEvent,
+	pub bisect_skip: GituiKeyEvent,
+	pub bisect_reset: GituiKeyEvent,
+	pub log_reflog: GituiKeyEvent,
+	pub view_worktrees: GituiKeyEvent,
+	pub worktree_add: GituiKeyEvent,
+	pub worktree_prune: GituiKeyEvent,
 	pub log_find: GituiKeyEvent,
+	pub log_first_parent: GituiKeyEvent,
+	pub log_scope: GituiKeyEvent,
+	pub log_select_refs: GituiKeyEvent,
+	pub log_edit_note: GituiKeyEvent,
+	pub log_push_notes: GituiKeyEvent,
+	pub log_fetch_notes: GituiKeyEvent,
+	pub log_format_patch: GituiKeyEvent,
+	pub log_cherry_pick: GituiKeyEvent,
+	pub log_cherry_pick_origin: GituiKeyEvent,
+	pub log_deepen: GituiKeyEvent,
+	pub log_unshallow: GituiKeyEvent,
+	pub format_patch_numbered: GituiKeyEvent,
+	pub format_patch_cover_letter: GituiKeyEvent,
+	pub select_revision: GituiKeyEvent,
 	pub find_commit_sha: GituiKeyEvent,
 	pub commit_amend: GituiKeyEvent,
+	pub commit_amend_author: GituiKeyEvent,
+	pub commit_amend_date: GituiKeyEvent,
+	pub commit_amend_reset_date: GituiKeyEvent,
 	pub toggle_signoff: GituiKeyEvent,
+	pub commit_signing_key: GituiKeyEvent,
+	pub signing_toggle: GituiKeyEvent,
 	pub toggle_verify: GituiKeyEvent,
 	pub copy: GituiKeyEvent,
+	pub copy_describe: GituiKeyEvent,
+	pub copy_trailers: GituiKeyEvent,
 	pub create_branch: GituiKeyEvent,
 	pub rename_branch: GituiKeyEvent,
 	pub select_branch: GituiKeyEvent,
@@ -100,31 +178,77 @@ pub struct KeysList {
 	pub merge_branch: GituiKeyEvent,
 	pub rebase_branch: GituiKeyEvent,
 	pub reset_branch: GituiKeyEvent,
+	pub stack_branch: GituiKeyEvent,
+	pub stack_push: GituiKeyEvent,
+	pub create_branch_orphan: GituiKeyEvent,
+	pub create_branch_orphan_clear: GituiKeyEvent,
+	pub branch_flow: GituiKeyEvent,
+	pub branch_symrefs: GituiKeyEvent,
+	pub branch_compare_base: GituiKeyEvent,
+	pub branch_diff_base: GituiKeyEvent,
+	pub clone_toggle_submodules: GituiKeyEvent,
+	pub cred_storage: GituiKeyEvent,
 	pub compare_commits: GituiKeyEvent,
+	pub compare_toggle_mode: GituiKeyEvent,
+	pub compare_toggle_commits: GituiKeyEvent,
+	pub compare_swap: GituiKeyEvent,
+	pub compare_set_base: GituiKeyEvent,
 	pub tags: GituiKeyEvent,
 	pub delete_tag: GituiKeyEvent,
 	pub select_tag: GituiKeyEvent,
 	pub push: GituiKeyEvent,
+	pub push_no_verify: GituiKeyEvent,
 	pub open_file_tree: GituiKeyEvent,
+	pub diff_merge_mode: GituiKeyEvent,
 	pub file_find: GituiKeyEvent,
 	pub branch_find: GituiKeyEvent,
 	pub force_push: GituiKeyEvent,
+	pub force_push_lease: GituiKeyEvent,
 	pub fetch: GituiKeyEvent,
 	pub pull: GituiKeyEvent,
+	pub pull_options: GituiKeyEvent,
+	pub pull_toggle_autostash: GituiKeyEvent,
+	pub fetch_options: GituiKeyEvent,
+	pub fetch_toggle_prune: GituiKeyEvent,
+	pub fetch_toggle_tags: GituiKeyEvent,
+	pub push_to: GituiKeyEvent,
+	pub pull_from: GituiKeyEvent,
+	pub remote_target_toggle_upstream: GituiKeyEvent,
 	pub abort_merge: GituiKeyEvent,
 	pub undo_commit: GituiKeyEvent,
 	pub diff_hunk_next: GituiKeyEvent,
 	pub diff_hunk_prev: GituiKeyEvent,
+	pub diff_ignore_whitespace: GituiKeyEvent,
+	pub diff_ignore_blank_lines: GituiKeyEvent,
+	pub diff_context_increase: GituiKeyEvent,
+	pub diff_context_decrease: GituiKeyEvent,
 	pub stage_unstage_item: GituiKeyEvent,
 	pub tag_annotate: GituiKeyEvent,
 	pub view_submodules: GituiKeyEvent,
+	pub status_export_locations: GituiKeyEvent,
+	pub status_resolve_conflict: GituiKeyEvent,
+	pub open_diff_tool: GituiKeyEvent,
+	pub open_merge_tool: GituiKeyEvent,
+	pub conflict_take_ours: GituiKeyEvent,
+	pub conflict_take_theirs: GituiKeyEvent,
+	pub conflict_take_both: GituiKeyEvent,
+	pub conflict_take_base: GituiKeyEvent,
+	pub conflict_edit: GituiKeyEvent,
 	pub view_remotes: GituiKeyEvent,
 	pub update_remote_name: GituiKeyEvent,
 	pub update_remote_url: GituiKeyEvent,
+	pub remote_identity_file: GituiKeyEvent,
+	pub update_remote_push_url: GituiKeyEvent,
 	pub add_remote: GituiKeyEvent,
 	pub delete_remote: GituiKeyEvent,
+	pub config_edit_global: GituiKeyEvent,
+	pub config_add: GituiKeyEvent,
+	pub config_unset: GituiKeyEvent,
 	pub view_submodule_parent: GituiKeyEvent,
 	pub update_submodule: GituiKeyEvent,
+	pub update_submodule_recursive: GituiKeyEvent,
+	pub init_submodule: GituiKeyEvent,
+	pub sync_submodule: GituiKeyEvent,
 	pub commit_history_next: GituiKeyEvent,
 	pub commit: GituiKeyEvent,
 	pub newline: GituiKeyEvent,
@@ -132,6 +256,7 @@ pub struct KeysList {
 
 #[rustfmt::skip]
 impl Default for KeysList {
+	#[allow(clippy::too_many_lines)]
 	fn default() -> Self {
 		Self {
 			tab_status: GituiKeyEvent::new(KeyCode::Char('1'), KeyModifiers::empty()),
@@ -149,6 +274,9 @@ impl Default for KeysList {
 			open_commit_editor: GituiKeyEvent::new(KeyCode::Char('e'), KeyModifiers::CONTROL),
 			open_help: GituiKeyEvent::new(KeyCode::Char('h'),  KeyModifiers::empty()),
 			open_options: GituiKeyEvent::new(KeyCode::Char('o'),  KeyModifiers::empty()),
+			clone_repo: GituiKeyEvent::new(KeyCode::Char('o'),  KeyModifiers::CONTROL),
+			undo_history: GituiKeyEvent::new(KeyCode::Char('z'),  KeyModifiers::CONTROL),
+			open_git_config: GituiKeyEvent::new(KeyCode::Char('O'),  KeyModifiers::SHIFT),
 			move_left: GituiKeyEvent::new(KeyCode::Left,  KeyModifiers::empty()),
 			move_right: GituiKeyEvent::new(KeyCode::Right,  KeyModifiers::empty()),
 			tree_collapse_recursive: GituiKeyEvent::new(KeyCode::Left,  KeyModifiers::SHIFT),
@@ -166,30 +294,105 @@ impl Default for KeysList {
 			enter: GituiKeyEvent::new(KeyCode::Enter,  KeyModifiers::empty()),
 			blame: GituiKeyEvent::new(KeyCode::Char('B'),  KeyModifiers::SHIFT),
 			file_history: GituiKeyEvent::new(KeyCode::Char('H'),  KeyModifiers::SHIFT),
+			blame_mark_lines: GituiKeyEvent::new(KeyCode::Char('v'),  KeyModifiers::empty()),
+			blame_parent: GituiKeyEvent::new(KeyCode::Char('p'),  KeyModifiers::empty()),
+			blame_ignore_revs: GituiKeyEvent::new(KeyCode::Char('i'),  KeyModifiers::empty()),
+			file_revision_prev: GituiKeyEvent::new(KeyCode::Char('['),  KeyModifiers::empty()),
+			file_revision_next: GituiKeyEvent::new(KeyCode::Char(']'),  KeyModifiers::empty()),
+			file_log: GituiKeyEvent::new(KeyCode::Char('L'),  KeyModifiers::SHIFT),
+			restore_file: GituiKeyEvent::new(KeyCode::Char('r'),  KeyModifiers::empty()),
+			restore_toggle_stage: GituiKeyEvent::new(KeyCode::Char('s'),  KeyModifiers::empty()),
 			edit_file: GituiKeyEvent::new(KeyCode::Char('e'),  KeyModifiers::empty()),
 			status_stage_all: GituiKeyEvent::new(KeyCode::Char('a'),  KeyModifiers::empty()),
 			status_reset_item: GituiKeyEvent::new(KeyCode::Char('D'),  KeyModifiers::SHIFT),
 			diff_reset_lines: GituiKeyEvent::new(KeyCode::Char('d'),  KeyModifiers::empty()),
+			diff_apply_hunks: GituiKeyEvent::new(KeyCode::Char('a'),  KeyModifiers::empty()),
+			diff_revert_hunks: GituiKeyEvent::new(KeyCode::Char('A'),  KeyModifiers::SHIFT),
+			diff_stash_hunks: GituiKeyEvent::new(KeyCode::Char('s'),  KeyModifiers::CONTROL),
 			status_ignore_file: GituiKeyEvent::new(KeyCode::Char('i'),  KeyModifiers::empty()),
+			status_show_ignored: GituiKeyEvent::new(KeyCode::Char('I'),  KeyModifiers::SHIFT),
+			status_ignore_rule: GituiKeyEvent::new(KeyCode::Char('W'),  KeyModifiers::SHIFT),
+			status_assume_unchanged: GituiKeyEvent::new(KeyCode::Char('a'),  KeyModifiers::ALT),
+			status_skip_worktree: GituiKeyEvent::new(KeyCode::Char('s'),  KeyModifiers::ALT),
+			lfs_lock: GituiKeyEvent::new(KeyCode::Char('l'),  KeyModifiers::ALT),
+			lfs_unlock: GituiKeyEvent::new(KeyCode::Char('u'),  KeyModifiers::ALT),
+			ignore_toggle_file: GituiKeyEvent::new(KeyCode::Char('f'),  KeyModifiers::empty()),
+			ignore_edit: GituiKeyEvent::new(KeyCode::Char('e'),  KeyModifiers::empty()),
+			status_discarded: GituiKeyEvent::new(KeyCode::Char('Z'),  KeyModifiers::SHIFT),
+			status_clean: GituiKeyEvent::new(KeyCode::Char('C'),  KeyModifiers::SHIFT),
+			clean_toggle_entry: GituiKeyEvent::new(KeyCode::Char(' '),  KeyModifiers::empty()),
+			clean_toggle_dirs: GituiKeyEvent::new(KeyCode::Char('d'),  KeyModifiers::empty()),
+			clean_toggle_ignored: GituiKeyEvent::new(KeyCode::Char('x'),  KeyModifiers::empty()),
+			status_apply_patch: GituiKeyEvent::new(KeyCode::Char('a'),  KeyModifiers::CONTROL),
+			status_skip_step: GituiKeyEvent::new(KeyCode::Char('k'),  KeyModifiers::CONTROL),
+			apply_patch_target: GituiKeyEvent::new(KeyCode::Char('t'),  KeyModifiers::CONTROL),
 			diff_stage_lines: GituiKeyEvent::new(KeyCode::Char('s'),  KeyModifiers::empty()),
+			diff_edit_hunk: GituiKeyEvent::new(KeyCode::Char('e'),  KeyModifiers::empty()),
 			stashing_save: GituiKeyEvent::new(KeyCode::Char('s'),  KeyModifiers::empty()),
 			stashing_toggle_untracked: GituiKeyEvent::new(KeyCode::Char('u'),  KeyModifiers::empty()),
 			stashing_toggle_index: GituiKeyEvent::new(KeyCode::Char('i'),  KeyModifiers::empty()),
+			stashing_toggle_ignored: GituiKeyEvent::new(KeyCode::Char('a'),  KeyModifiers::empty()),
+			stashing_mark_file: GituiKeyEvent::new(KeyCode::Char(' '),  KeyModifiers::empty()),
+			stash_msg_toggle_untracked: GituiKeyEvent::new(KeyCode::Char('u'),  KeyModifiers::CONTROL),
+			stash_msg_toggle_ignored: GituiKeyEvent::new(KeyCode::Char('g'),  KeyModifiers::CONTROL),
 			stash_apply: GituiKeyEvent::new(KeyCode::Char('a'),  KeyModifiers::empty()),
 			stash_open: GituiKeyEvent::new(KeyCode::Right,  KeyModifiers::empty()),
 			stash_drop: GituiKeyEvent::new(KeyCode::Char('D'),  KeyModifiers::SHIFT),
+			stash_worktree_diff: GituiKeyEvent::new(KeyCode::Char('w'),  KeyModifiers::empty()),
 			cmd_bar_toggle: GituiKeyEvent::new(KeyCode::Char('.'),  KeyModifiers::empty()),
 			log_tag_commit: GituiKeyEvent::new(KeyCode::Char('t'),  KeyModifiers::empty()),
 			log_mark_commit: GituiKeyEvent::new(KeyCode::Char(' '),  KeyModifiers::empty()),
 			log_checkout_commit: GituiKeyEvent { code: KeyCode::Char('S'), modifiers: KeyModifiers::SHIFT },
 			log_reset_commit: GituiKeyEvent { code: KeyCode::Char('R'), modifiers: KeyModifiers::SHIFT },
 			log_reword_commit: GituiKeyEvent { code: KeyCode::Char('r'), modifiers: KeyModifiers::empty() },
+			log_move_commit_up: GituiKeyEvent::new(KeyCode::Up,  KeyModifiers::ALT),
+			log_move_commit_down: GituiKeyEvent::new(KeyCode::Down,  KeyModifiers::ALT),
+			log_rebase_interactive: GituiKeyEvent::new(KeyCode::Char('i'),  KeyModifiers::empty()),
+			log_fixup_commit: GituiKeyEvent::new(KeyCode::Char('x'),  KeyModifiers::empty()),
+			log_squash_commit: GituiKeyEvent::new(KeyCode::Char('X'),  KeyModifiers::SHIFT),
+			log_autosquash: GituiKeyEvent::new(KeyCode::Char('a'),  KeyModifiers::empty()),
+			rebase_pick: GituiKeyEvent::new(KeyCode::Char('p'),  KeyModifiers::empty()),
+			rebase_reword: GituiKeyEvent::new(KeyCode::Char('r'),  KeyModifiers::empty()),
+			rebase_edit: GituiKeyEvent::new(KeyCode::Char('e'),  KeyModifiers::empty()),
+			rebase_squash: GituiKeyEvent::new(KeyCode::Char('s'),  KeyModifiers::empty()),
+			rebase_fixup: GituiKeyEvent::new(KeyCode::Char('f'),  KeyModifiers::empty()),
+			rebase_drop: GituiKeyEvent::new(KeyCode::Char('d'),  KeyModifiers::empty()),
+			log_bisect: GituiKeyEvent::new(KeyCode::Char('b'),  KeyModifiers::CONTROL),
+			bisect_good: GituiKeyEvent::new(KeyCode::Char('g'),  KeyModifiers::empty()),
+			bisect_bad: GituiKeyEvent::new(KeyCode::Char('b'),  KeyModifiers::empty()),
+			bisect_skip: GituiKeyEvent::new(KeyCode::Char('s'),  KeyModifiers::empty()),
+			bisect_reset: GituiKeyEvent::new(KeyCode::Char('r'),  KeyModifiers::empty()),
+			log_reflog: GituiKeyEvent::new(KeyCode::Char('E'),  KeyModifiers::SHIFT),
+			view_worktrees: GituiKeyEvent::new(KeyCode::Char('w'),  KeyModifiers::CONTROL),
+			worktree_add: GituiKeyEvent::new(KeyCode::Char('a'),  KeyModifiers::empty()),
+			worktree_prune: GituiKeyEvent::new(KeyCode::Char('p'),  KeyModifiers::empty()),
 			log_find: GituiKeyEvent { code: KeyCode::Char('f'), modifiers: KeyModifiers::empty() },
+			log_first_parent: GituiKeyEvent::new(KeyCode::Char('M'),  KeyModifiers::SHIFT),
+			log_scope: GituiKeyEvent::new(KeyCode::Char('A'),  KeyModifiers::SHIFT),
+			log_select_refs: GituiKeyEvent::new(KeyCode::Char('V'),  KeyModifiers::SHIFT),
+			log_edit_note: GituiKeyEvent::new(KeyCode::Char('n'),  KeyModifiers::empty()),
+			log_push_notes: GituiKeyEvent::new(KeyCode::Char('N'),  KeyModifiers::SHIFT),
+			log_fetch_notes: GituiKeyEvent::new(KeyCode::Char('n'),  KeyModifiers::ALT),
+			log_format_patch: GituiKeyEvent::new(KeyCode::Char('e'),  KeyModifiers::empty()),
+			log_cherry_pick: GituiKeyEvent::new(KeyCode::Char('c'),  KeyModifiers::empty()),
+			log_cherry_pick_origin: GituiKeyEvent::new(KeyCode::Char('x'),  KeyModifiers::CONTROL),
+			log_deepen: GituiKeyEvent::new(KeyCode::Char('d'),  KeyModifiers::CONTROL),
+			log_unshallow: GituiKeyEvent::new(KeyCode::Char('d'),  KeyModifiers::ALT),
+			format_patch_numbered: GituiKeyEvent::new(KeyCode::Char('n'),  KeyModifiers::CONTROL),
+			format_patch_cover_letter: GituiKeyEvent::new(KeyCode::Char('l'),  KeyModifiers::CONTROL),
+			select_revision: GituiKeyEvent::new(KeyCode::Char('e'),  KeyModifiers::empty()),
 			find_commit_sha: GituiKeyEvent::new(KeyCode::Char('j'), KeyModifiers::CONTROL),
 			commit_amend: GituiKeyEvent::new(KeyCode::Char('a'),  KeyModifiers::CONTROL),
+			commit_amend_author: GituiKeyEvent::new(KeyCode::Char('t'),  KeyModifiers::CONTROL),
+			commit_amend_date: GituiKeyEvent::new(KeyCode::Char('g'),  KeyModifiers::CONTROL),
+			commit_amend_reset_date: GituiKeyEvent::new(KeyCode::Char('l'),  KeyModifiers::CONTROL),
 			toggle_signoff: GituiKeyEvent::new(KeyCode::Char('s'),  KeyModifiers::CONTROL),
+			commit_signing_key: GituiKeyEvent::new(KeyCode::Char('k'),  KeyModifiers::CONTROL),
+			signing_toggle: GituiKeyEvent::new(KeyCode::Char('s'),  KeyModifiers::empty()),
 			toggle_verify: GituiKeyEvent::new(KeyCode::Char('f'),  KeyModifiers::CONTROL),
 			copy: GituiKeyEvent::new(KeyCode::Char('y'),  KeyModifiers::empty()),
+			copy_describe: GituiKeyEvent::new(KeyCode::Char('Y'),  KeyModifiers::SHIFT),
+			copy_trailers: GituiKeyEvent::new(KeyCode::Char('y'),  KeyModifiers::CONTROL),
 			create_branch: GituiKeyEvent::new(KeyCode::Char('c'),  KeyModifiers::empty()),
 			rename_branch: GituiKeyEvent::new(KeyCode::Char('r'),  KeyModifiers::empty()),
 			select_branch: GituiKeyEvent::new(KeyCode::Char('b'),  KeyModifiers::empty()),
@@ -197,31 +400,77 @@ impl Default for KeysList {
 			merge_branch: GituiKeyEvent::new(KeyCode::Char('m'),  KeyModifiers::empty()),
 			rebase_branch: GituiKeyEvent::new(KeyCode::Char('R'),  KeyModifiers::SHIFT),
 			reset_branch: GituiKeyEvent::new(KeyCode::Char('s'),  KeyModifiers::empty()),
+			stack_branch: GituiKeyEvent::new(KeyCode::Char('a'),  KeyModifiers::empty()),
+			stack_push: GituiKeyEvent::new(KeyCode::Char('P'),  KeyModifiers::SHIFT),
+			create_branch_orphan: GituiKeyEvent::new(KeyCode::Char('o'),  KeyModifiers::CONTROL),
+			create_branch_orphan_clear: GituiKeyEvent::new(KeyCode::Char('r'),  KeyModifiers::CONTROL),
+			branch_flow: GituiKeyEvent::new(KeyCode::Char('g'),  KeyModifiers::empty()),
+			branch_symrefs: GituiKeyEvent::new(KeyCode::Char('H'),  KeyModifiers::SHIFT),
+			branch_compare_base: GituiKeyEvent::new(KeyCode::Char('B'),  KeyModifiers::SHIFT),
+			branch_diff_base: GituiKeyEvent::new(KeyCode::Char('d'),  KeyModifiers::empty()),
+			clone_toggle_submodules: GituiKeyEvent::new(KeyCode::Char('s'),  KeyModifiers::CONTROL),
+			cred_storage: GituiKeyEvent::new(KeyCode::Char('s'),  KeyModifiers::CONTROL),
 			compare_commits: GituiKeyEvent::new(KeyCode::Char('C'),  KeyModifiers::SHIFT),
+			compare_toggle_mode: GituiKeyEvent::new(KeyCode::Char('m'),  KeyModifiers::empty()),
+			compare_toggle_commits: GituiKeyEvent::new(KeyCode::Char('c'),  KeyModifiers::empty()),
+			compare_swap: GituiKeyEvent::new(KeyCode::Char('x'),  KeyModifiers::empty()),
+			compare_set_base: GituiKeyEvent::new(KeyCode::Char('b'),  KeyModifiers::empty()),
 			tags: GituiKeyEvent::new(KeyCode::Char('T'),  KeyModifiers::SHIFT),
 			delete_tag: GituiKeyEvent::new(KeyCode::Char('D'),  KeyModifiers::SHIFT),
 			select_tag: GituiKeyEvent::new(KeyCode::Enter,  KeyModifiers::empty()),
 			push: GituiKeyEvent::new(KeyCode::Char('p'),  KeyModifiers::empty()),
+			push_no_verify: GituiKeyEvent::new(KeyCode::Char('p'),  KeyModifiers::CONTROL),
 			force_push: GituiKeyEvent::new(KeyCode::Char('P'),  KeyModifiers::SHIFT),
+			force_push_lease: GituiKeyEvent::new(KeyCode::Char('l'),  KeyModifiers::CONTROL),
 			undo_commit: GituiKeyEvent::new(KeyCode::Char('U'),  KeyModifiers::SHIFT),
 			fetch: GituiKeyEvent::new(KeyCode::Char('F'),  KeyModifiers::SHIFT),
 			pull: GituiKeyEvent::new(KeyCode::Char('f'),  KeyModifiers::empty()),
+			pull_options: GituiKeyEvent::new(KeyCode::Char('f'),  KeyModifiers::ALT),
+			fetch_options: GituiKeyEvent::new(KeyCode::Char('F'),  KeyModifiers::SHIFT | KeyModifiers::ALT),
+			fetch_toggle_prune: GituiKeyEvent::new(KeyCode::Char('p'),  KeyModifiers::empty()),
+			fetch_toggle_tags: GituiKeyEvent::new(KeyCode::Char('t'),  KeyModifiers::empty()),
+			push_to: GituiKeyEvent::new(KeyCode::Char('p'),  KeyModifiers::ALT),
+			pull_from: GituiKeyEvent::new(KeyCode::Char('f'),  KeyModifiers::CONTROL),
+			remote_target_toggle_upstream: GituiKeyEvent::new(KeyCode::Char('u'),  KeyModifiers::CONTROL),
+			pull_toggle_autostash: GituiKeyEvent::new(KeyCode::Char('a'),  KeyModifiers::empty()),
 			abort_merge: GituiKeyEvent::new(KeyCode::Char('A'),  KeyModifiers::SHIFT),
 			open_file_tree: GituiKeyEvent::new(KeyCode::Char('F'),  KeyModifiers::SHIFT),
+			diff_merge_mode: GituiKeyEvent::new(KeyCode::Char('m'),  KeyModifiers::empty()),
 			file_find: GituiKeyEvent::new(KeyCode::Char('f'),  KeyModifiers::empty()),
 			branch_find: GituiKeyEvent::new(KeyCode::Char('f'),  KeyModifiers::empty()),
 			diff_hunk_next: GituiKeyEvent::new(KeyCode::Char('n'),  KeyModifiers::empty()),
 			diff_hunk_prev: GituiKeyEvent::new(KeyCode::Char('p'),  KeyModifiers::empty()),
+			diff_ignore_whitespace: GituiKeyEvent::new(KeyCode::Char('w'),  KeyModifiers::ALT),
+			diff_ignore_blank_lines: GituiKeyEvent::new(KeyCode::Char('b'),  KeyModifiers::ALT),
+			diff_context_increase: GituiKeyEvent::new(KeyCode::Char('+'),  KeyModifiers::empty()),
+			diff_context_decrease: GituiKeyEvent::new(KeyCode::Char('-'),  KeyModifiers::empty()),
 			stage_unstage_item: GituiKeyEvent::new(KeyCode::Enter,  KeyModifiers::empty()),
 			tag_annotate: GituiKeyEvent::new(KeyCode::Char('a'),  KeyModifiers::CONTROL),
 			view_submodules: GituiKeyEvent::new(KeyCode::Char('S'),  KeyModifiers::SHIFT),
+			status_export_locations: GituiKeyEvent::new(KeyCode::Char('X'),  KeyModifiers::SHIFT),
+			status_resolve_conflict: GituiKeyEvent::new(KeyCode::Char('M'),  KeyModifiers::SHIFT),
+			open_diff_tool: GituiKeyEvent::new(KeyCode::Char('t'),  KeyModifiers::ALT),
+			open_merge_tool: GituiKeyEvent::new(KeyCode::Char('m'),  KeyModifiers::ALT),
+			conflict_take_ours: GituiKeyEvent::new(KeyCode::Char('o'),  KeyModifiers::empty()),
+			conflict_take_theirs: GituiKeyEvent::new(KeyCode::Char('t'),  KeyModifiers::empty()),
+			conflict_take_both: GituiKeyEvent::new(KeyCode::Char('b'),  KeyModifiers::empty()),
+			conflict_take_base: GituiKeyEvent::new(KeyCode::Char('B'),  KeyModifiers::SHIFT),
+			conflict_edit: GituiKeyEvent::new(KeyCode::Char('e'),  KeyModifiers::empty()),
 			view_remotes: GituiKeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL),
 			update_remote_name: GituiKeyEvent::new(KeyCode::Char('n'),KeyModifiers::NONE),
 			update_remote_url: GituiKeyEvent::new(KeyCode::Char('u'),KeyModifiers::NONE),
+			remote_identity_file: GituiKeyEvent::new(KeyCode::Char('i'),KeyModifiers::NONE),
+			update_remote_push_url: GituiKeyEvent::new(KeyCode::Char('p'),KeyModifiers::NONE),
 			add_remote: GituiKeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE),
 			delete_remote: GituiKeyEvent::new(KeyCode::Char('r'), KeyModifiers::NONE),
+			config_edit_global: GituiKeyEvent::new(KeyCode::Char('g'), KeyModifiers::NONE),
+			config_add: GituiKeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE),
+			config_unset: GituiKeyEvent::new(KeyCode::Char('D'), KeyModifiers::SHIFT),
 			view_submodule_parent: GituiKeyEvent::new(KeyCode::Char('p'),  KeyModifiers::empty()),
 			update_submodule: GituiKeyEvent::new(KeyCode::Char('u'),  KeyModifiers::empty()),
+			update_submodule_recursive: GituiKeyEvent::new(KeyCode::Char('U'),  KeyModifiers::SHIFT),
+			init_submodule: GituiKeyEvent::new(KeyCode::Char('i'),  KeyModifiers::empty()),
+			sync_submodule: GituiKeyEvent::new(KeyCode::Char('s'),  KeyModifiers::empty()),
 			commit_history_next: GituiKeyEvent::new(KeyCode::Char('n'),  KeyModifiers::CONTROL),
 			commit: GituiKeyEvent::new(KeyCode::Char('d'),  KeyModifiers::CONTROL),
 			newline: GituiKeyEvent::new(KeyCode::Enter,  KeyModifiers::empty()),
@@ -261,6 +510,68 @@ mod tests {
 		keys_list.apply(patch);
 	}
 
+	#[test]
+	fn test_log_keys_unique() {
+		let keys = KeysList::default();
+		// everything the log tab and its commit list react to
+		let log_keys = [
+			("compare_commits", keys.compare_commits),
+			("copy", keys.copy),
+			("copy_describe", keys.copy_describe),
+			("copy_trailers", keys.copy_trailers),
+			("end", keys.end),
+			("enter", keys.enter),
+			("exit_popup", keys.exit_popup),
+			("home", keys.home),
+			("log_autosquash", keys.log_autosquash),
+			("log_bisect", keys.log_bisect),
+			("log_checkout_commit", keys.log_checkout_commit),
+			("log_cherry_pick", keys.log_cherry_pick),
+			("log_cherry_pick_origin", keys.log_cherry_pick_origin),
+			("log_deepen", keys.log_deepen),
+			("log_edit_note", keys.log_edit_note),
+			("log_fetch_notes", keys.log_fetch_notes),
+			("log_find", keys.log_find),
+			("log_first_parent", keys.log_first_parent),
+			("log_fixup_commit", keys.log_fixup_commit),
+			("log_format_patch", keys.log_format_patch),
+			("log_mark_commit", keys.log_mark_commit),
+			("log_move_commit_down", keys.log_move_commit_down),
+			("log_move_commit_up", keys.log_move_commit_up),
+			("log_push_notes", keys.log_push_notes),
+			("log_rebase_interactive", keys.log_rebase_interactive),
+			("log_reflog", keys.log_reflog),
+			("log_reset_commit", keys.log_reset_commit),
+			("log_reword_commit", keys.log_reword_commit),
+			("log_scope", keys.log_scope),
+			("log_select_refs", keys.log_select_refs),
+			("log_squash_commit", keys.log_squash_commit),
+			("log_tag_commit", keys.log_tag_commit),
+			("log_unshallow", keys.log_unshallow),
+			("move_down", keys.move_down),
+			("move_right", keys.move_right),
+			("move_up", keys.move_up),
+			("open_file_tree", keys.open_file_tree),
+			("page_down", keys.page_down),
+			("page_up", keys.page_up),
+			("push", keys.push),
+			("select_branch", keys.select_branch),
+			("shift_down", keys.shift_down),
+			("shift_up", keys.shift_up),
+			("status_reset_item", keys.status_reset_item),
+			("tags", keys.tags),
+		];
+
+		for (i, (name, key)) in log_keys.iter().enumerate() {
+			for (other, other_key) in &log_keys[i + 1..] {
+				assert_ne!(
+					key, other_key,
+					"{name} and {other} collide"
+				);
+			}
+		}
+	}
+
 	#[test]
 	fn test_smoke() {
 		let mut file = NamedTempFile::new().unwrap();
@@ -33,6 +33,7 @@ mod clipboard;
 mod cmdbar;
 mod components;
 mod input;
+mod ipc;
 mod keys;
 mod notify_mutex;
 mod options;
@@ -46,16 +47,29 @@ mod tabs;
 mod ui;
 mod watcher;
 
-use crate::{app::App, args::process_cmdline};
+use crate::{
+	app::{App, Environment},
+	args::process_cmdline,
+	components::{Component, DrawableComponent},
+	keys::key_match,
+	options::Options,
+	popups::{ClonePopup, InitRepoPopup},
+	queue::{InternalEvent, Queue},
+};
 use anyhow::{anyhow, bail, Result};
 use app::QuitState;
 use asyncgit::{
-	sync::{utils::repo_work_dir, RepoPath},
+	sync::{
+		repo_dir, repo_watch_dirs, utils::repo_work_dir, RepoPath,
+	},
 	AsyncGitNotification,
 };
 use backtrace::Backtrace;
-use crossbeam_channel::{never, tick, unbounded, Receiver, Select};
+use crossbeam_channel::{
+	never, select, tick, unbounded, Receiver, Select,
+};
 use crossterm::{
+	event::Event,
 	terminal::{
 		disable_raw_mode, enable_raw_mode, EnterAlternateScreen,
 		LeaveAlternateScreen,
@@ -63,8 +77,13 @@ use crossterm::{
 	ExecutableCommand,
 };
 use input::{Input, InputEvent, InputState};
+use ipc::{IpcCommand, IpcServer};
 use keys::KeyConfig;
-use ratatui::backend::CrosstermBackend;
+use ratatui::{
+	backend::CrosstermBackend,
+	layout::Alignment,
+	widgets::{Block, Borders, Paragraph},
+};
 use scopeguard::defer;
 use scopetime::scope_time;
 use spinner::Spinner;
@@ -74,6 +93,7 @@ use std::{
 	panic,
 	path::Path,
 	process,
+	rc::Rc,
 	time::{Duration, Instant},
 };
 use ui::style::Theme;
@@ -92,6 +112,7 @@ pub enum QueueEvent {
 	SpinnerUpdate,
 	AsyncEvent(AsyncNotification),
 	InputEvent(InputEvent),
+	Ipc(IpcCommand),
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -127,11 +148,19 @@ fn main() -> Result<()> {
 
 	asyncgit::register_tracing_logging();
 
-	if !valid_path(&cliargs.repo_path) {
-		eprintln!("invalid path\nplease run gitui inside of a non-bare git repository");
+	if !cliargs.repo_path.gitpath().is_dir() {
+		eprintln!("invalid path\nplease run gitui inside of a git repository");
 		return Ok(());
 	}
 
+	let ipc_server = cliargs
+		.ipc_socket
+		.as_deref()
+		.map(IpcServer::new)
+		.transpose()?;
+	let rx_ipc =
+		ipc_server.as_ref().map_or_else(never, IpcServer::receiver);
+
 	let key_config = KeyConfig::init()
 		.map_err(|e| eprintln!("KeyConfig loading error: {e}"))
 		.unwrap_or_default();
@@ -148,6 +177,19 @@ fn main() -> Result<()> {
 	let mut terminal = start_terminal(io::stdout(), &repo_path)?;
 	let input = Input::new();
 
+	if !valid_path(&repo_path) {
+		match run_start_screen(
+			repo_path.clone(),
+			theme.clone(),
+			key_config.clone(),
+			&input,
+			&mut terminal,
+		)? {
+			Some(cloned) => repo_path = cloned,
+			None => return Ok(()),
+		}
+	}
+
 	let updater = if cliargs.notify_watcher {
 		Updater::NotifyWatcher
 	} else {
@@ -162,11 +204,12 @@ fn main() -> Result<()> {
 			key_config.clone(),
 			&input,
 			updater,
+			&rx_ipc,
 			&mut terminal,
 		)?;
 
 		match quit_state {
-			QuitState::OpenSubmodule(p) => {
+			QuitState::OpenRepo(p) => {
 				repo_path = p;
 			}
 			_ => break,
@@ -176,6 +219,7 @@ fn main() -> Result<()> {
 	Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn run_app(
 	app_start: Instant,
 	repo: RepoPath,
@@ -183,6 +227,7 @@ fn run_app(
 	key_config: KeyConfig,
 	input: &Input,
 	updater: Updater,
+	rx_ipc: &Receiver<IpcCommand>,
 	terminal: &mut Terminal,
 ) -> Result<QuitState, anyhow::Error> {
 	let (tx_git, rx_git) = unbounded();
@@ -192,8 +237,14 @@ fn run_app(
 
 	let (rx_ticker, rx_watcher) = match updater {
 		Updater::NotifyWatcher => {
-			let repo_watcher =
-				RepoWatcher::new(repo_work_dir(&repo)?.as_str());
+			let watch_path = repo_work_dir(&repo).or_else(|_| {
+				repo_dir(&repo)
+					.map(|dir| dir.to_string_lossy().to_string())
+			})?;
+			let repo_watcher = RepoWatcher::new(
+				watch_path.as_str(),
+				repo_watch_dirs(&repo).ok(),
+			);
 
 			(never(), repo_watcher.receiver())
 		}
@@ -228,6 +279,7 @@ fn run_app(
 				&rx_ticker,
 				&rx_watcher,
 				&spinner_ticker,
+				rx_ipc,
 			)?
 		};
 
@@ -264,6 +316,9 @@ fn run_app(
 						app.update_async(ev)?;
 					}
 				}
+				QueueEvent::Ipc(cmd) => {
+					app.ipc_command(cmd)?;
+				}
 				QueueEvent::SpinnerUpdate => unreachable!(),
 			}
 
@@ -281,6 +336,89 @@ fn run_app(
 	Ok(app.quit_state())
 }
 
+/// shown instead of the app when not started inside a repository:
+/// offers to initialize or clone one and returns its path once done
+fn run_start_screen(
+	repo: RepoPath,
+	theme: Theme,
+	key_config: KeyConfig,
+	input: &Input,
+	terminal: &mut Terminal,
+) -> Result<Option<RepoPath>> {
+	let (tx_git, rx_git) = unbounded();
+	let (tx_app, _rx_app) = unbounded();
+
+	let repo = RefCell::new(repo);
+	let env = Environment {
+		queue: Queue::new(),
+		theme: Rc::new(theme),
+		key_config: Rc::new(key_config),
+		options: Options::new(repo.clone()),
+		repo,
+		sender_git: tx_git,
+		sender_app: tx_app,
+	};
+
+	let mut init_popup = InitRepoPopup::new(&env);
+	init_popup.open()?;
+	let mut clone_popup = ClonePopup::new(&env);
+
+	let rx_input = input.receiver();
+
+	loop {
+		terminal.draw(|f| {
+			f.render_widget(
+				Paragraph::new(strings::CLONE_SCREEN_MSG)
+					.alignment(Alignment::Center)
+					.block(
+						Block::default()
+							.title(strings::CLONE_SCREEN_TITLE)
+							.borders(Borders::ALL),
+					),
+				f.area(),
+			);
+			if let Err(e) = init_popup
+				.draw(f, f.area())
+				.and_then(|()| clone_popup.draw(f, f.area()))
+			{
+				log::error!("failed to draw: {:?}", e);
+			}
+		})?;
+
+		select! {
+			recv(rx_input) -> ev => {
+				if let InputEvent::Input(ev) = ev? {
+					if let Event::Key(k) = &ev {
+						if key_match(k, env.key_config.keys.exit) {
+							return Ok(None);
+						}
+						if !clone_popup.is_visible()
+							&& key_match(k, env.key_config.keys.clone_repo)
+						{
+							clone_popup.open()?;
+							continue;
+						}
+					}
+					if !clone_popup.event(&ev)?.is_consumed() {
+						init_popup.event(&ev)?;
+					}
+				}
+			}
+			recv(rx_git) -> ev => clone_popup.update_git(ev?)?,
+		}
+
+		while let Some(ev) = env.queue.pop() {
+			if let InternalEvent::OpenNewRepo(path) = ev {
+				return Ok(Some(RepoPath::Path(path)));
+			}
+		}
+
+		if !init_popup.is_visible() && !clone_popup.is_visible() {
+			return Ok(None);
+		}
+	}
+}
+
 fn setup_terminal() -> Result<()> {
 	enable_raw_mode()?;
 	io::stdout().execute(EnterAlternateScreen)?;
@@ -331,6 +469,7 @@ fn select_event(
 	rx_ticker: &Receiver<Instant>,
 	rx_notify: &Receiver<()>,
 	rx_spinner: &Receiver<Instant>,
+	rx_ipc: &Receiver<IpcCommand>,
 ) -> Result<QueueEvent> {
 	let mut sel = Select::new();
 
@@ -340,6 +479,7 @@ fn select_event(
 	sel.recv(rx_ticker);
 	sel.recv(rx_notify);
 	sel.recv(rx_spinner);
+	sel.recv(rx_ipc);
 
 	let oper = sel.select();
 	let index = oper.index();
@@ -355,6 +495,7 @@ fn select_event(
 		3 => oper.recv(rx_ticker).map(|_| QueueEvent::Notify),
 		4 => oper.recv(rx_notify).map(|()| QueueEvent::Notify),
 		5 => oper.recv(rx_spinner).map(|_| QueueEvent::SpinnerUpdate),
+		6 => oper.recv(rx_ipc).map(QueueEvent::Ipc),
 		_ => bail!("unknown select source"),
 	}?;
 
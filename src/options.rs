@@ -1,6 +1,6 @@
 use anyhow::Result;
 use asyncgit::sync::{
-	diff::DiffOptions, repo_dir, RepoPathRef,
+	diff::DiffOptions, repo_dir, DiffAlgorithm, RepoPathRef,
 	ShowUntrackedFilesConfig,
 };
 use ron::{
@@ -22,6 +22,10 @@ struct OptionsData {
 	pub diff: DiffOptions,
 	pub status_show_untracked: Option<ShowUntrackedFilesConfig>,
 	pub commit_msgs: Vec<String>,
+	pub stash_untracked: Option<bool>,
+	pub stash_ignored: Option<bool>,
+	pub diff_syntax_highlight: Option<bool>,
+	pub discard_backup: Option<bool>,
 }
 
 const COMMIT_MSG_HISTORY_LENGTH: usize = 20;
@@ -80,6 +84,26 @@ impl Options {
 		self.save();
 	}
 
+	/// `None` until toggled once, stashing then follows
+	/// `status.showUntrackedFiles`
+	pub const fn stash_untracked(&self) -> Option<bool> {
+		self.data.stash_untracked
+	}
+
+	pub fn set_stash_untracked(&mut self, value: bool) {
+		self.data.stash_untracked = Some(value);
+		self.save();
+	}
+
+	pub fn stash_ignored(&self) -> bool {
+		self.data.stash_ignored.unwrap_or_default()
+	}
+
+	pub fn set_stash_ignored(&mut self, value: bool) {
+		self.data.stash_ignored = Some(value);
+		self.save();
+	}
+
 	pub fn diff_context_change(&mut self, increase: bool) {
 		self.data.diff.context = if increase {
 			self.data.diff.context.saturating_add(1)
@@ -90,6 +114,30 @@ impl Options {
 		self.save();
 	}
 
+	/// steps through following `diff.algorithm` and each algorithm
+	pub fn diff_algorithm_change(&mut self, forward: bool) {
+		let all = DiffAlgorithm::ALL;
+		let current = self
+			.data
+			.diff
+			.algorithm
+			.and_then(|algorithm| {
+				all.iter().position(|a| *a == algorithm)
+			})
+			.map_or(0, |idx| idx + 1);
+
+		let next = if forward {
+			(current + 1) % (all.len() + 1)
+		} else {
+			(current + all.len()) % (all.len() + 1)
+		};
+
+		self.data.diff.algorithm =
+			next.checked_sub(1).map(|idx| all[idx]);
+
+		self.save();
+	}
+
 	pub fn diff_hunk_lines_change(&mut self, increase: bool) {
 		self.data.diff.interhunk_lines = if increase {
 			self.data.diff.interhunk_lines.saturating_add(1)
@@ -107,6 +155,61 @@ impl Options {
 		self.save();
 	}
 
+	pub fn diff_toggle_whitespace_change(&mut self) {
+		self.data.diff.ignore_whitespace_change =
+			!self.data.diff.ignore_whitespace_change;
+
+		self.save();
+	}
+
+	pub fn diff_toggle_blank_lines(&mut self) {
+		self.data.diff.ignore_blank_lines =
+			!self.data.diff.ignore_blank_lines;
+
+		self.save();
+	}
+
+	/// steps from showing all whitespace changes to ignoring changes
+	/// in the amount of whitespace to ignoring all whitespace
+	pub fn diff_cycle_whitespace(&mut self) {
+		let diff = &mut self.data.diff;
+
+		(diff.ignore_whitespace_change, diff.ignore_whitespace) =
+			match (
+				diff.ignore_whitespace_change,
+				diff.ignore_whitespace,
+			) {
+				(false, false) => (true, false),
+				(true, false) => (false, true),
+				_ => (false, false),
+			};
+
+		self.save();
+	}
+
+	/// syntax highlighting of diff content, on by default
+	pub fn diff_syntax_highlight(&self) -> bool {
+		self.data.diff_syntax_highlight.unwrap_or(true)
+	}
+
+	pub fn diff_toggle_syntax_highlight(&mut self) {
+		self.data.diff_syntax_highlight =
+			Some(!self.diff_syntax_highlight());
+
+		self.save();
+	}
+
+	/// back up content before discarding it, on by default
+	pub fn discard_backup(&self) -> bool {
+		self.data.discard_backup.unwrap_or(true)
+	}
+
+	pub fn toggle_discard_backup(&mut self) {
+		self.data.discard_backup = Some(!self.discard_backup());
+
+		self.save();
+	}
+
 	pub fn add_commit_msg(&mut self, msg: &str) {
 		self.data.commit_msgs.push(msg.to_owned());
 		while self.data.commit_msgs.len() > COMMIT_MSG_HISTORY_LENGTH
@@ -0,0 +1,176 @@
+use crate::components::{
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState, InputType, TextInputComponent,
+};
+use crate::{
+	app::Environment,
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, NeedsUpdate, Queue},
+	strings,
+};
+use anyhow::Result;
+use asyncgit::sync::{self, ApplyPatchTarget, RepoPathRef};
+use crossterm::event::Event;
+use ratatui::{layout::Rect, Frame};
+use std::path::Path;
+
+/// asks for a patch or mbox file (or takes the clipboard) and applies
+/// it to the worktree or as commits
+pub struct ApplyPatchPopup {
+	repo: RepoPathRef,
+	input: TextInputComponent,
+	target: ApplyPatchTarget,
+	queue: Queue,
+	key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for ApplyPatchPopup {
+	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
+		self.input.draw(f, rect)?;
+
+		Ok(())
+	}
+}
+
+impl Component for ApplyPatchPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			self.input.commands(out, force_all);
+
+			out.push(CommandInfo::new(
+				strings::commands::apply_patch_confirm(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::apply_patch_target(
+					&self.key_config,
+					self.target,
+				),
+				true,
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.is_visible() {
+			if let Event::Key(e) = ev {
+				if key_match(e, self.key_config.keys.enter) {
+					self.apply();
+					return Ok(EventState::Consumed);
+				} else if key_match(
+					e,
+					self.key_config.keys.apply_patch_target,
+				) {
+					self.target = match self.target {
+						ApplyPatchTarget::Worktree => {
+							ApplyPatchTarget::Commits
+						}
+						ApplyPatchTarget::Commits => {
+							ApplyPatchTarget::Worktree
+						}
+					};
+					self.input.set_title(
+						strings::apply_patch_popup_title(self.target),
+					);
+					return Ok(EventState::Consumed);
+				}
+			}
+
+			self.input.event(ev)?;
+			return Ok(EventState::Consumed);
+		}
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.input.is_visible()
+	}
+
+	fn hide(&mut self) {
+		self.input.hide();
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.input.show()?;
+
+		Ok(())
+	}
+}
+
+impl ApplyPatchPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		let target = ApplyPatchTarget::Commits;
+		let input = TextInputComponent::new(
+			env,
+			&strings::apply_patch_popup_title(target),
+			&strings::apply_patch_popup_msg(),
+			true,
+		)
+		.with_input_type(InputType::Singleline);
+
+		Self {
+			queue: env.queue.clone(),
+			input,
+			target,
+			key_config: env.key_config.clone(),
+			repo: env.repo.clone(),
+		}
+	}
+
+	/// keeps path and target of the last patch
+	pub fn open(&mut self) -> Result<()> {
+		self.show()
+	}
+
+	/// the file relative to the repository root, or the clipboard
+	fn read_patch(&self) -> Result<String> {
+		match self.input.get_text().trim() {
+			"" => crate::clipboard::read_string(),
+			file => {
+				let root =
+					sync::utils::repo_work_dir(&self.repo.borrow())?;
+				Ok(std::fs::read_to_string(
+					Path::new(&root).join(file),
+				)?)
+			}
+		}
+	}
+
+	fn apply(&mut self) {
+		let result = self.read_patch().and_then(|patch| {
+			Ok(sync::apply_patch(
+				&self.repo.borrow(),
+				&patch,
+				self.target,
+			)?)
+		});
+
+		match result {
+			Ok(result) => {
+				self.queue.push(InternalEvent::ShowInfoMsg(
+					strings::apply_patch_result(result),
+				));
+			}
+			Err(e) => {
+				log::error!("apply patch: {e}");
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("apply patch error:\n{e}"),
+				));
+			}
+		}
+
+		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+		self.hide();
+	}
+}
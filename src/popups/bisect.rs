@@ -0,0 +1,242 @@
+use crate::{
+	app::Environment,
+	components::{
+		visibility_blocking, CommandBlocking, CommandInfo, Component,
+		DrawableComponent, EventState,
+	},
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, NeedsUpdate, Queue},
+	strings, try_or_popup,
+	ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::sync::{
+	self, BisectMark, BisectState, CommitId, RepoPathRef,
+};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Margin, Rect},
+	text::{Line, Span, Text},
+	widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+	Frame,
+};
+
+/// marks commits good/bad/skipped, after every mark the next commit
+/// to test is checked out and becomes the one the popup marks
+pub struct BisectPopup {
+	repo: RepoPathRef,
+	queue: Queue,
+	visible: bool,
+	commit: Option<(CommitId, String)>,
+	state: Option<BisectState>,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl BisectPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			repo: env.repo.clone(),
+			queue: env.queue.clone(),
+			visible: false,
+			commit: None,
+			state: None,
+			theme: env.theme.clone(),
+			key_config: env.key_config.clone(),
+		}
+	}
+
+	///
+	pub fn open(&mut self, commit: CommitId) -> Result<()> {
+		self.state = sync::bisect_state(&self.repo.borrow())?;
+		self.set_commit(commit)?;
+
+		self.show()
+	}
+
+	fn set_commit(&mut self, commit: CommitId) -> Result<()> {
+		let summary =
+			sync::get_commit_details(&self.repo.borrow(), commit)?
+				.message
+				.map(|msg| msg.subject)
+				.unwrap_or_default();
+
+		self.commit = Some((commit, summary));
+
+		Ok(())
+	}
+
+	fn mark(&mut self, mark: BisectMark) -> Result<()> {
+		if let Some((commit, _)) = self.commit {
+			let state =
+				sync::bisect_mark(&self.repo.borrow(), commit, mark)?;
+
+			if let Some(next) = state.next.or(state.first_bad) {
+				self.set_commit(next)?;
+			}
+
+			self.state = Some(state);
+			self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+		}
+
+		Ok(())
+	}
+
+	fn reset(&mut self) -> Result<()> {
+		sync::bisect_reset(&self.repo.borrow())?;
+
+		self.state = None;
+		self.hide();
+		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+
+		Ok(())
+	}
+
+	fn text(&self) -> Text<'_> {
+		let mut lines = Vec::new();
+
+		if let Some((commit, summary)) = &self.commit {
+			lines.push(Line::styled(
+				strings::bisect_commit(*commit, summary),
+				self.theme.text(true, false),
+			));
+			lines.push(Line::default());
+		}
+
+		if let Some(state) = &self.state {
+			lines.push(Line::styled(
+				strings::bisect_progress(state),
+				self.theme.text(true, false),
+			));
+			lines.push(Line::styled(
+				strings::bisect_marks(state),
+				self.theme.text(false, false),
+			));
+		} else {
+			lines.push(Line::styled(
+				strings::BISECT_NOT_STARTED,
+				self.theme.text(false, false),
+			));
+		}
+
+		Text::from(lines)
+	}
+}
+
+impl DrawableComponent for BisectPopup {
+	fn draw(&self, f: &mut Frame, _rect: Rect) -> Result<()> {
+		if !self.visible {
+			return Ok(());
+		}
+
+		let area = ui::centered_rect(60, 30, f.area());
+
+		f.render_widget(Clear, area);
+		f.render_widget(
+			Block::default()
+				.title(Span::styled(
+					strings::POPUP_TITLE_BISECT,
+					self.theme.title(true),
+				))
+				.borders(Borders::ALL)
+				.border_type(BorderType::Thick)
+				.border_style(self.theme.block(true)),
+			area,
+		);
+
+		f.render_widget(
+			Paragraph::new(self.text()).wrap(Wrap { trim: false }),
+			area.inner(Margin {
+				horizontal: 1,
+				vertical: 1,
+			}),
+		);
+
+		Ok(())
+	}
+}
+
+impl Component for BisectPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.visible || force_all {
+			out.push(CommandInfo::new(
+				strings::commands::bisect_mark(&self.key_config),
+				self.commit.is_some(),
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::bisect_reset(&self.key_config),
+				self.state.is_some(),
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::close_popup(&self.key_config),
+				true,
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.visible {
+			if let Event::Key(e) = ev {
+				let keys = &self.key_config.keys;
+
+				if key_match(e, keys.exit_popup) {
+					self.hide();
+				} else if key_match(e, keys.bisect_good) {
+					try_or_popup!(
+						self,
+						"bisect error:",
+						self.mark(BisectMark::Good)
+					);
+				} else if key_match(e, keys.bisect_bad) {
+					try_or_popup!(
+						self,
+						"bisect error:",
+						self.mark(BisectMark::Bad)
+					);
+				} else if key_match(e, keys.bisect_skip) {
+					try_or_popup!(
+						self,
+						"bisect error:",
+						self.mark(BisectMark::Skip)
+					);
+				} else if key_match(e, keys.bisect_reset)
+					&& self.state.is_some()
+				{
+					try_or_popup!(
+						self,
+						"bisect error:",
+						self.reset()
+					);
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
@@ -16,7 +16,7 @@ use crate::{
 use anyhow::Result;
 use asyncgit::{
 	asyncjob::AsyncSingleJob,
-	sync::{BlameHunk, CommitId, FileBlame, RepoPathRef},
+	sync::{self, BlameHunk, CommitId, FileBlame, RepoPathRef},
 	AsyncBlame, AsyncGitNotification, BlameParams,
 };
 use crossbeam_channel::Sender;
@@ -93,6 +93,10 @@ pub struct BlameFilePopup {
 	table_state: std::cell::Cell<TableState>,
 	key_config: SharedKeyConfig,
 	current_height: std::cell::Cell<usize>,
+	/// first line of the marked range, the selection is the other end
+	marked_line: Option<usize>,
+	/// blames navigated away from by blaming a parent
+	back_stack: Vec<BlameFileOpen>,
 	blame: Option<BlameProcess>,
 	app_sender: Sender<AsyncAppNotification>,
 	git_sender: Sender<AsyncGitNotification>,
@@ -200,12 +204,38 @@ impl Component for BlameFilePopup {
 		if self.is_visible() || force_all {
 			out.push(
 				CommandInfo::new(
-					strings::commands::close_popup(&self.key_config),
+					if self.back_stack.is_empty() {
+						strings::commands::close_popup(
+							&self.key_config,
+						)
+					} else {
+						strings::commands::blame_back(
+							&self.key_config,
+						)
+					},
+					true,
+					true,
+				)
+				.order(1),
+			);
+			out.push(
+				CommandInfo::new(
+					strings::commands::blame_ignore_revs(
+						&self.key_config,
+					),
 					true,
 					true,
 				)
 				.order(1),
 			);
+			out.push(
+				CommandInfo::new(
+					strings::commands::blame_parent(&self.key_config),
+					self.selected_commit().is_some(),
+					has_result,
+				)
+				.order(1),
+			);
 			out.push(
 				CommandInfo::new(
 					strings::commands::scroll(&self.key_config),
@@ -226,7 +256,7 @@ impl Component for BlameFilePopup {
 			);
 			out.push(
 				CommandInfo::new(
-					strings::commands::open_file_history(
+					strings::commands::blame_mark_lines(
 						&self.key_config,
 					),
 					true,
@@ -234,6 +264,22 @@ impl Component for BlameFilePopup {
 				)
 				.order(1),
 			);
+			out.push(
+				CommandInfo::new(
+					if self.marked_line.is_some() {
+						strings::commands::open_line_history(
+							&self.key_config,
+						)
+					} else {
+						strings::commands::open_file_history(
+							&self.key_config,
+						)
+					},
+					true,
+					has_result,
+				)
+				.order(1),
+			);
 		}
 
 		visibility_blocking(self)
@@ -246,7 +292,34 @@ impl Component for BlameFilePopup {
 		if self.is_visible() {
 			if let Event::Key(key) = event {
 				if key_match(key, self.key_config.keys.exit_popup) {
-					self.hide_stacked(false);
+					if self.marked_line.is_some() {
+						self.marked_line = None;
+					} else if let Some(open) = self.back_stack.pop() {
+						self.open_blame(open)?;
+					} else {
+						self.hide_stacked(false);
+					}
+				} else if key_match(
+					key,
+					self.key_config.keys.blame_ignore_revs,
+				) {
+					self.hide_stacked(true);
+					self.queue.push(
+						InternalEvent::OpenBlameIgnoreRevsPopup,
+					);
+				} else if key_match(
+					key,
+					self.key_config.keys.blame_parent,
+				) {
+					self.blame_parent()?;
+				} else if key_match(
+					key,
+					self.key_config.keys.blame_mark_lines,
+				) {
+					self.marked_line = match self.marked_line {
+						Some(_) => None,
+						None => self.get_selection(),
+					};
 				} else if key_match(key, self.key_config.keys.move_up)
 				{
 					self.move_selection(ScrollType::Up);
@@ -295,16 +368,21 @@ impl Component for BlameFilePopup {
 					key,
 					self.key_config.keys.file_history,
 				) {
-					if let Some(filepath) = self
-						.params
-						.as_ref()
-						.map(|p| p.file_path.clone())
-					{
+					if let Some(params) = self.params.clone() {
+						let open = match self.marked_lines() {
+							Some((start, end)) => FileRevOpen::lines(
+								params.file_path,
+								params.commit_id,
+								(start + 1, end + 1),
+							),
+							None => {
+								FileRevOpen::new(params.file_path)
+							}
+						};
+
 						self.hide_stacked(true);
 						self.queue.push(InternalEvent::OpenPopup(
-							StackablePopupOpen::FileRevlog(
-								FileRevOpen::new(filepath),
-							),
+							StackablePopupOpen::FileRevlog(open),
 						));
 					}
 				}
@@ -340,6 +418,8 @@ impl BlameFilePopup {
 			table_state: std::cell::Cell::new(TableState::default()),
 			key_config: env.key_config.clone(),
 			current_height: std::cell::Cell::new(0),
+			marked_line: None,
+			back_stack: Vec::new(),
 			app_sender: env.sender_app.clone(),
 			git_sender: env.sender_git.clone(),
 			blame: None,
@@ -366,6 +446,11 @@ impl BlameFilePopup {
 
 	///
 	pub fn open(&mut self, open: BlameFileOpen) -> Result<()> {
+		self.back_stack.clear();
+		self.open_blame(open)
+	}
+
+	fn open_blame(&mut self, open: BlameFileOpen) -> Result<()> {
 		self.open_request = Some(open.clone());
 		self.params = Some(BlameParams {
 			file_path: open.file_path,
@@ -377,6 +462,7 @@ impl BlameFilePopup {
 				&self.git_sender,
 			)));
 		self.table_state.get_mut().select(Some(0));
+		self.marked_line = None;
 		self.visible = true;
 		self.update()?;
 
@@ -508,10 +594,11 @@ impl BlameFilePopup {
 			}
 			(false, Some(params), Some(file_blame)) => {
 				format!(
-					"{} -- {} -- {}",
+					"{} -- {} -- {}{}",
 					self.title,
 					params.file_path,
-					file_blame.commit_id().get_short_string()
+					file_blame.commit_id().get_short_string(),
+					self.breadcrumb()
 				)
 			}
 			(false, Some(params), None) => {
@@ -524,6 +611,20 @@ impl BlameFilePopup {
 		}
 	}
 
+	/// the blames to go back to, most recent first
+	fn breadcrumb(&self) -> String {
+		self.back_stack
+			.iter()
+			.rev()
+			.map(|open| {
+				open.commit_id.map_or_else(
+					|| String::from(" < HEAD"),
+					|id| format!(" < {}", id.get_short_string()),
+				)
+			})
+			.collect()
+	}
+
 	///
 	fn get_rows(&self, width: usize) -> Vec<Row> {
 		self.blame
@@ -534,6 +635,7 @@ impl BlameFilePopup {
 					.styled_text
 					.as_ref()
 					.map(std::convert::Into::into);
+				let age_range = age_range(file_blame);
 				file_blame
 					.lines()
 					.iter()
@@ -543,6 +645,7 @@ impl BlameFilePopup {
 							width,
 							i,
 							(blame_hunk.as_ref(), line.as_ref()),
+							age_range,
 							file_blame,
 							styled_text.as_ref(),
 						)
@@ -585,6 +688,7 @@ impl BlameFilePopup {
 		width: usize,
 		line_number: usize,
 		hunk_and_line: (Option<&BlameHunk>, &str),
+		age_range: (i64, i64),
 		file_blame: &'a SyntaxFileBlame,
 		styled_text: Option<&Text<'a>>,
 	) -> Row<'a> {
@@ -628,11 +732,23 @@ impl BlameFilePopup {
 			Cell::from(format!(
 				"{line_number:>line_number_width$}{VERTICAL}",
 			))
-			.style(self.theme.text(true, false)),
+			.style(hunk_for_line.map_or_else(
+				|| self.theme.text(true, false),
+				|hunk| self.theme.blame_age(hunk.time, age_range),
+			)),
 		);
 		cells.push(text_cell);
 
-		Row::new(cells)
+		let marked =
+			self.marked_lines().is_some_and(|(start, end)| {
+				(start..=end).contains(&line_number)
+			});
+
+		if marked {
+			Row::new(cells).style(self.theme.text(true, true))
+		} else {
+			Row::new(cells)
+		}
 	}
 
 	fn get_metadata_for_line_blame(
@@ -721,6 +837,7 @@ impl BlameFilePopup {
 		if let Some(selection) =
 			self.open_request.as_ref().and_then(|req| req.selection)
 		{
+			let selection = selection.min(self.get_max_line_number());
 			let mut table_state = self.table_state.take();
 			table_state.select(Some(selection));
 			self.table_state.set(table_state);
@@ -742,6 +859,68 @@ impl BlameFilePopup {
 			})
 	}
 
+	/// blames the file again at the parent of the commit that last
+	/// changed the selected line, keeping the current blame to go
+	/// back to
+	fn blame_parent(&mut self) -> Result<()> {
+		let Some((hunk, selection)) = self
+			.blame
+			.as_ref()
+			.and_then(|blame| blame.result())
+			.and_then(|file_blame| {
+				let selection = self.get_selection()?;
+				let hunk =
+					file_blame.lines().get(selection)?.0.clone()?;
+
+				Some((hunk, selection))
+			})
+		else {
+			return Ok(());
+		};
+
+		let line = hunk.orig_start_line
+			+ selection.saturating_sub(hunk.start_line);
+
+		let parent = sync::blame_parent(
+			&self.repo.borrow(),
+			hunk.commit_id,
+			&hunk.orig_path,
+			line,
+		)?;
+
+		match parent {
+			Some(parent) => {
+				if let Some(request) = self.open_request.clone() {
+					self.back_stack.push(BlameFileOpen {
+						selection: Some(selection),
+						..request
+					});
+				}
+
+				self.open_blame(BlameFileOpen {
+					file_path: parent.path,
+					commit_id: Some(parent.commit_id),
+					selection: Some(parent.line),
+				})?;
+			}
+			None => {
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					strings::BLAME_NO_PARENT.to_string(),
+				));
+			}
+		}
+
+		Ok(())
+	}
+
+	/// marked range of lines (0-based, inclusive)
+	fn marked_lines(&self) -> Option<(usize, usize)> {
+		let marked = self.marked_line?;
+		let selection = self.get_selection()?;
+
+		Some((marked.min(selection), marked.max(selection)))
+	}
+
 	fn selected_commit(&self) -> Option<CommitId> {
 		self.blame
 			.as_ref()
@@ -764,6 +943,16 @@ impl BlameFilePopup {
 	}
 }
 
+/// oldest and newest commit time of all lines in the blame
+fn age_range(file_blame: &SyntaxFileBlame) -> (i64, i64) {
+	let times = file_blame
+		.lines()
+		.iter()
+		.filter_map(|(hunk, _)| hunk.as_ref().map(|hunk| hunk.time));
+
+	times.clone().min().zip(times.max()).unwrap_or_default()
+}
+
 fn get_author_width(width: usize) -> usize {
 	(width.saturating_sub(19) / 3)
 		.clamp(MIN_AUTHOR_WIDTH, MAX_AUTHOR_WIDTH)
@@ -0,0 +1,139 @@
+use anyhow::Result;
+use asyncgit::sync::{self, RepoPathRef};
+use crossterm::event::Event;
+use ratatui::{layout::Rect, Frame};
+
+use crate::{
+	app::Environment,
+	components::{
+		visibility_blocking, CommandBlocking, CommandInfo, Component,
+		DrawableComponent, EventState, InputType, TextInputComponent,
+	},
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, Queue},
+	strings,
+};
+
+/// picks the file listing commits to skip when blaming, stored as
+/// `blame.ignoreRevsFile` in the repo config
+pub struct BlameIgnoreRevsPopup {
+	repo: RepoPathRef,
+	input: TextInputComponent,
+	key_config: SharedKeyConfig,
+	queue: Queue,
+}
+
+impl DrawableComponent for BlameIgnoreRevsPopup {
+	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
+		if self.is_visible() {
+			self.input.draw(f, rect)?;
+		}
+		Ok(())
+	}
+}
+
+impl Component for BlameIgnoreRevsPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			self.input.commands(out, force_all);
+
+			out.push(CommandInfo::new(
+				strings::commands::blame_ignore_revs_confirm(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
+		}
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.is_visible() {
+			if let Event::Key(e) = ev {
+				if key_match(e, self.key_config.keys.exit_popup) {
+					self.close();
+					return Ok(EventState::Consumed);
+				} else if key_match(e, self.key_config.keys.enter) {
+					self.confirm();
+					return Ok(EventState::Consumed);
+				}
+			}
+
+			self.input.event(ev)?;
+
+			return Ok(EventState::Consumed);
+		}
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.input.is_visible()
+	}
+
+	fn hide(&mut self) {
+		self.input.hide();
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.input.show()?;
+
+		Ok(())
+	}
+}
+
+impl BlameIgnoreRevsPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			repo: env.repo.clone(),
+			input: TextInputComponent::new(
+				env,
+				&strings::blame_ignore_revs_popup_title(),
+				&strings::blame_ignore_revs_popup_msg(),
+				true,
+			)
+			.with_input_type(InputType::Singleline),
+			key_config: env.key_config.clone(),
+			queue: env.queue.clone(),
+		}
+	}
+
+	///
+	pub fn open(&mut self) -> Result<()> {
+		self.input.set_text(
+			sync::blame_ignore_revs_file(&self.repo.borrow())?
+				.unwrap_or_default(),
+		);
+		self.show()?;
+
+		Ok(())
+	}
+
+	fn confirm(&mut self) {
+		let path = self.input.get_text().trim();
+
+		if let Err(e) = sync::set_blame_ignore_revs_file(
+			&self.repo.borrow(),
+			(!path.is_empty()).then_some(path),
+		) {
+			log::error!("set blame ignore revs file: {e}");
+			self.queue.push(InternalEvent::ShowErrorMsg(format!(
+				"set blame ignore revs file error:\n{e}",
+			)));
+		}
+
+		self.close();
+	}
+
+	/// back to the blame, which gets computed again
+	fn close(&mut self) {
+		self.input.clear();
+		self.hide();
+		self.queue.push(InternalEvent::PopupStackPop);
+	}
+}
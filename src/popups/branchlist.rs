@@ -6,6 +6,7 @@ use crate::{
 	app::Environment,
 	components::ScrollType,
 	keys::{key_match, SharedKeyConfig},
+	popups::record_undo,
 	queue::{
 		Action, InternalEvent, NeedsUpdate, Queue, StackablePopupOpen,
 	},
@@ -17,11 +18,11 @@ use asyncgit::{
 	sync::{
 		self,
 		branch::{
-			checkout_remote_branch, BranchDetails, LocalBranch,
-			RemoteBranch,
+			checkout_remote_branch, BranchCompare, BranchDetails,
+			LocalBranch, RemoteBranch,
 		},
 		checkout_branch, get_branches_info, BranchInfo, BranchType,
-		CommitId, RepoPathRef, RepoState,
+		CommitId, CompareMode, RepoPathRef, RepoState,
 	},
 	AsyncGitNotification,
 };
@@ -41,11 +42,19 @@ use unicode_truncate::UnicodeTruncateStr;
 use super::InspectCommitOpen;
 
 ///
+#[allow(clippy::struct_excessive_bools)]
 pub struct BranchListPopup {
 	repo: RepoPathRef,
 	branches: Vec<BranchInfo>,
+	/// branch to count commits ahead/behind against instead of the
+	/// upstream
+	compare_base: Option<BranchInfo>,
+	/// ahead/behind of `branches`, `None` if there is nothing to
+	/// compare with
+	compare: Vec<Option<BranchCompare>>,
 	local: bool,
 	has_remotes: bool,
+	bare: bool,
 	visible: bool,
 	selection: u16,
 	scroll: VerticalScroll,
@@ -72,9 +81,16 @@ impl DrawableComponent for BranchListPopup {
 
 			f.render_widget(Clear, area);
 
+			let title = self
+				.compare_base
+				.as_ref()
+				.map_or_else(strings::title_branches, |base| {
+					strings::title_branches_base(&base.name)
+				});
+
 			f.render_widget(
 				Block::default()
-					.title(strings::title_branches())
+					.title(title)
 					.border_type(BorderType::Thick)
 					.borders(Borders::ALL),
 				area,
@@ -118,7 +134,7 @@ impl Component for BranchListPopup {
 	}
 
 	//TODO: cleanup
-	#[allow(clippy::cognitive_complexity)]
+	#[allow(clippy::cognitive_complexity, clippy::too_many_lines)]
 	fn event(&mut self, ev: &Event) -> Result<EventState> {
 		if !self.visible {
 			return Ok(EventState::NotConsumed);
@@ -132,7 +148,8 @@ impl Component for BranchListPopup {
 			let selection_is_cur_branch =
 				self.selection_is_cur_branch();
 
-			if key_match(e, self.key_config.keys.enter) {
+			if key_match(e, self.key_config.keys.enter) && !self.bare
+			{
 				try_or_popup!(
 					self,
 					"switch branch error:",
@@ -169,6 +186,36 @@ impl Component for BranchListPopup {
 					"rebase error:",
 					self.rebase_branch()
 				);
+			} else if key_match(e, self.key_config.keys.stack_branch)
+				&& self.local
+				&& !selection_is_cur_branch
+				&& self.valid_selection()
+			{
+				try_or_popup!(
+					self,
+					"stack branch error:",
+					self.stack_on_selected_branch()
+				);
+			} else if key_match(e, self.key_config.keys.stack_push)
+				&& self.local
+				&& self.valid_selection()
+			{
+				try_or_popup!(
+					self,
+					"push stack error:",
+					self.push_stack()
+				);
+			} else if key_match(e, self.key_config.keys.branch_flow)
+				&& self.local
+			{
+				self.hide();
+				self.queue.push(InternalEvent::OpenFlowPopup);
+			} else if key_match(
+				e,
+				self.key_config.keys.branch_symrefs,
+			) {
+				self.hide();
+				self.queue.push(InternalEvent::OpenSymRefsPopup);
 			} else if key_match(e, self.key_config.keys.move_right)
 				&& self.valid_selection()
 			{
@@ -186,10 +233,30 @@ impl Component for BranchListPopup {
 						),
 					));
 				}
+			} else if key_match(
+				e,
+				self.key_config.keys.branch_compare_base,
+			) && self.valid_selection()
+			{
+				try_or_popup!(
+					self,
+					"compare base error:",
+					self.toggle_compare_base()
+				);
+			} else if key_match(
+				e,
+				self.key_config.keys.branch_diff_base,
+			) && self.can_diff_base()
+			{
+				try_or_popup!(
+					self,
+					"diff base error:",
+					self.diff_against_base()
+				);
 			} else if key_match(e, self.key_config.keys.fetch)
 				&& self.has_remotes
 			{
-				self.queue.push(InternalEvent::FetchRemotes);
+				self.queue.push(InternalEvent::FetchRemotes(None));
 			} else if key_match(e, self.key_config.keys.view_remotes)
 			{
 				self.queue.push(InternalEvent::ViewRemotes);
@@ -241,8 +308,12 @@ impl BranchListPopup {
 	pub fn new(env: &Environment) -> Self {
 		Self {
 			branches: Vec::new(),
+			compare_base: None,
+			compare: Vec::new(),
 			local: true,
 			has_remotes: false,
+			bare: sync::is_bare_repo(&env.repo.borrow())
+				.unwrap_or_default(),
 			visible: false,
 			selection: 0,
 			scroll: VerticalScroll::new(),
@@ -324,6 +395,14 @@ impl BranchListPopup {
 					.position(|b| b.name.ends_with("/HEAD"))
 					.map(|idx| self.branches.remove(idx));
 			}
+			self.compare = sync::branches_compare(
+				&self.repo.borrow(),
+				&self.branches,
+				self.compare_base
+					.as_ref()
+					.map(|base| base.reference.as_str()),
+			)
+			.unwrap_or_default();
 			self.set_selection(self.selection)?;
 		}
 		Ok(())
@@ -365,6 +444,10 @@ impl BranchListPopup {
 		if let Some(branch) =
 			self.branches.get(usize::from(self.selection))
 		{
+			record_undo(
+				&self.repo.borrow(),
+				&strings::undo_op_rebase(&branch.name),
+			);
 			sync::rebase_branch(
 				&self.repo.borrow(),
 				&branch.name,
@@ -377,6 +460,118 @@ impl BranchListPopup {
 		Ok(())
 	}
 
+	/// stacks the current branch on the selected one, or removes it
+	/// from the stack if it already is stacked on it
+	fn stack_on_selected_branch(&mut self) -> Result<()> {
+		let Some(head) = self.branches.iter().find(|b| {
+			b.local_details().is_some_and(|details| details.is_head)
+		}) else {
+			return Ok(());
+		};
+
+		let selected = &self.branches[self.selection as usize].name;
+		let parent = if head
+			.local_details()
+			.and_then(|details| details.stack_parent.as_ref())
+			== Some(selected)
+		{
+			None
+		} else {
+			Some(selected.as_str())
+		};
+
+		sync::set_stack_parent(
+			&self.repo.borrow(),
+			&head.name,
+			parent,
+		)?;
+
+		self.update_branches()
+	}
+
+	fn push_stack(&self) -> Result<()> {
+		let stack = sync::get_branch_stack(
+			&self.repo.borrow(),
+			&self.branches[self.selection as usize].name,
+		)?;
+
+		self.queue.push(InternalEvent::ConfirmAction(
+			Action::PushStack(stack),
+		));
+
+		Ok(())
+	}
+
+	fn is_compare_base(&self, branch: &BranchInfo) -> bool {
+		self.compare_base
+			.as_ref()
+			.is_some_and(|base| base.reference == branch.reference)
+	}
+
+	/// makes the selected branch the base, or goes back to comparing
+	/// with the upstream if it already is
+	fn toggle_compare_base(&mut self) -> Result<()> {
+		let Some(branch) =
+			self.branches.get(usize::from(self.selection))
+		else {
+			return Ok(());
+		};
+
+		self.compare_base = if self.is_compare_base(branch) {
+			None
+		} else {
+			Some(branch.clone())
+		};
+
+		self.update_branches()
+	}
+
+	/// reference the selected branch gets diffed against
+	fn diff_base_reference(&self) -> Option<&str> {
+		let branch =
+			self.branches.get(usize::from(self.selection))?;
+
+		match &self.compare_base {
+			Some(base) if !self.is_compare_base(branch) => {
+				Some(base.reference.as_str())
+			}
+			Some(_) => None,
+			None => branch
+				.local_details()?
+				.upstream
+				.as_ref()
+				.map(|upstream| upstream.reference.as_str()),
+		}
+	}
+
+	fn can_diff_base(&self) -> bool {
+		self.diff_base_reference().is_some()
+	}
+
+	/// compares the selected branch with the base since they diverged
+	fn diff_against_base(&mut self) -> Result<()> {
+		let (Some(reference), Some(commit_id)) =
+			(self.diff_base_reference(), self.get_selected_commit())
+		else {
+			return Ok(());
+		};
+
+		let base =
+			sync::rev_parse_commit(&self.repo.borrow(), reference)?;
+
+		self.hide();
+		self.queue.push(InternalEvent::OpenPopup(
+			StackablePopupOpen::CompareCommits(InspectCommitOpen {
+				commit_id,
+				compare_id: Some(base),
+				compare_mode: Some(CompareMode::ThreeDot),
+				tags: None,
+			}),
+		));
+
+		Ok(())
+	}
+
 	fn inspect_head_of_branch(&mut self) {
 		if let Some(commit_id) = self.get_selected_commit() {
 			self.hide();
@@ -468,6 +663,7 @@ impl BranchListPopup {
 	}
 
 	/// Get branches to display
+	#[allow(clippy::too_many_lines)]
 	fn get_text(
 		&self,
 		theme: &SharedTheme,
@@ -477,11 +673,22 @@ impl BranchListPopup {
 		const UPSTREAM_SYMBOL: char = '\u{2191}';
 		const TRACKING_SYMBOL: char = '\u{2193}';
 		const HEAD_SYMBOL: char = '*';
+		const STACK_SYMBOL: char = '\u{2190}';
 		const EMPTY_SYMBOL: char = ' ';
 		const THREE_DOTS: &str = "...";
 		const THREE_DOTS_LENGTH: usize = THREE_DOTS.len(); // "..."
 		const COMMIT_HASH_LENGTH: usize = 8;
 		const IS_HEAD_STAR_LENGTH: usize = 3; // "*  "
+		const COMPARE_LENGTH: usize = 12; // "↑123 ↓4567  "
+
+		let compare_length =
+			if self.compare.iter().any(Option::is_some)
+				|| self.compare_base.is_some()
+			{
+				COMPARE_LENGTH
+			} else {
+				0
+			};
 
 		let branch_name_length: usize =
 			width_available as usize * 40 / 100;
@@ -490,12 +697,19 @@ impl BranchListPopup {
 			.saturating_sub(COMMIT_HASH_LENGTH)
 			.saturating_sub(branch_name_length)
 			.saturating_sub(IS_HEAD_STAR_LENGTH)
+			.saturating_sub(compare_length)
 			.saturating_sub(THREE_DOTS_LENGTH);
 		let mut txt = Vec::new();
 
-		for (i, displaybranch) in self
+		for (i, (displaybranch, compare)) in self
 			.branches
 			.iter()
+			.zip(
+				self.compare
+					.iter()
+					.copied()
+					.chain(std::iter::repeat(None)),
+			)
 			.skip(self.scroll.get_top())
 			.take(height)
 			.enumerate()
@@ -510,7 +724,18 @@ impl BranchListPopup {
 				commit_message += THREE_DOTS;
 			}
 
-			let mut branch_name = displaybranch.name.clone();
+			let mut branch_name = displaybranch
+				.local_details()
+				.and_then(|details| details.stack_parent.as_ref())
+				.map_or_else(
+					|| displaybranch.name.clone(),
+					|parent| {
+						format!(
+							"{} {STACK_SYMBOL} {parent}",
+							displaybranch.name
+						)
+					},
+				);
 			if branch_name.len()
 				> branch_name_length.saturating_sub(THREE_DOTS_LENGTH)
 			{
@@ -564,10 +789,25 @@ impl BranchListPopup {
 				format!("{branch_name:branch_name_length$} "),
 				theme.branch(selected, is_head),
 			);
+			let compare_str = if self.is_compare_base(displaybranch) {
+				strings::BRANCH_COMPARE_BASE.to_string()
+			} else {
+				compare.map_or_else(String::new, |compare| {
+					format!(
+						"\u{2191}{} \u{2193}{}",
+						compare.ahead, compare.behind
+					)
+				})
+			};
+			let span_compare = Span::styled(
+				format!("{compare_str:compare_length$}"),
+				theme.text(false, selected),
+			);
 
 			txt.push(Line::from(vec![
 				span_prefix,
 				span_name,
+				span_compare,
 				span_hash,
 				span_msg,
 			]));
@@ -676,6 +916,7 @@ impl BranchListPopup {
 		));
 	}
 
+	#[allow(clippy::too_many_lines)]
 	fn add_commands_internal(&self, out: &mut Vec<CommandInfo>) {
 		let selection_is_cur_branch = self.selection_is_cur_branch();
 
@@ -714,7 +955,9 @@ impl BranchListPopup {
 
 		out.push(CommandInfo::new(
 			strings::commands::select_branch_popup(&self.key_config),
-			!selection_is_cur_branch && self.valid_selection(),
+			!self.bare
+				&& !selection_is_cur_branch
+				&& self.valid_selection(),
 			true,
 		));
 
@@ -744,6 +987,53 @@ impl BranchListPopup {
 			true,
 		));
 
+		out.push(CommandInfo::new(
+			strings::commands::branch_popup_stack(&self.key_config),
+			!selection_is_cur_branch && self.valid_selection(),
+			self.local,
+		));
+
+		out.push(CommandInfo::new(
+			strings::commands::branch_popup_push_stack(
+				&self.key_config,
+			),
+			self.valid_selection(),
+			self.local,
+		));
+
+		out.push(CommandInfo::new(
+			strings::commands::branch_popup_flow(&self.key_config),
+			true,
+			self.local,
+		));
+
+		out.push(CommandInfo::new(
+			strings::commands::branch_popup_symrefs(&self.key_config),
+			true,
+			true,
+		));
+
+		out.push(CommandInfo::new(
+			strings::commands::branch_popup_compare_base(
+				&self.key_config,
+				self.branches
+					.get(usize::from(self.selection))
+					.is_some_and(|branch| {
+						self.is_compare_base(branch)
+					}),
+			),
+			self.valid_selection(),
+			true,
+		));
+
+		out.push(CommandInfo::new(
+			strings::commands::branch_popup_diff_base(
+				&self.key_config,
+			),
+			self.can_diff_base(),
+			true,
+		));
+
 		out.push(CommandInfo::new(
 			strings::commands::rename_branch_popup(&self.key_config),
 			true,
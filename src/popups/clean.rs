@@ -0,0 +1,294 @@
+use crate::{
+	app::Environment,
+	components::{
+		visibility_blocking, CommandBlocking, CommandInfo, Component,
+		DrawableComponent, EventState,
+	},
+	keys::{key_match, SharedKeyConfig},
+	options::SharedOptions,
+	queue::{Action, InternalEvent, Queue},
+	strings,
+	ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::sync::{self, CleanEntry, CleanOptions, RepoPathRef};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Margin, Rect},
+	text::{Line, Span, Text},
+	widgets::{Block, BorderType, Borders, Clear, Paragraph},
+	Frame,
+};
+use std::collections::HashSet;
+
+/// previews what `git clean` would remove and removes the checked
+/// paths
+pub struct CleanPopup {
+	repo: RepoPathRef,
+	queue: Queue,
+	visible: bool,
+	options: CleanOptions,
+	entries: Vec<CleanEntry>,
+	/// paths the user chose to keep
+	unchecked: HashSet<String>,
+	selection: usize,
+	app_options: SharedOptions,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl CleanPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			repo: env.repo.clone(),
+			queue: env.queue.clone(),
+			visible: false,
+			options: CleanOptions::default(),
+			entries: Vec::new(),
+			unchecked: HashSet::new(),
+			selection: 0,
+			app_options: env.options.clone(),
+			theme: env.theme.clone(),
+			key_config: env.key_config.clone(),
+		}
+	}
+
+	///
+	pub fn open(&mut self) -> Result<()> {
+		self.options = CleanOptions::default();
+		self.unchecked.clear();
+		self.update()?;
+
+		self.show()
+	}
+
+	fn update(&mut self) -> Result<()> {
+		self.entries =
+			sync::clean_preview(&self.repo.borrow(), self.options)?;
+		self.unchecked.retain(|path| {
+			self.entries.iter().any(|entry| &entry.path == path)
+		});
+		self.selection =
+			self.selection.min(self.entries.len().saturating_sub(1));
+
+		Ok(())
+	}
+
+	fn move_selection(&mut self, up: bool) {
+		let max = self.entries.len().saturating_sub(1);
+
+		self.selection = if up {
+			self.selection.saturating_sub(1)
+		} else {
+			self.selection.saturating_add(1).min(max)
+		};
+	}
+
+	fn toggle_selected(&mut self) {
+		if let Some(entry) = self.entries.get(self.selection) {
+			if !self.unchecked.remove(&entry.path) {
+				self.unchecked.insert(entry.path.clone());
+			}
+		}
+	}
+
+	fn checked(&self) -> Vec<String> {
+		self.entries
+			.iter()
+			.filter(|entry| !self.unchecked.contains(&entry.path))
+			.map(|entry| entry.path.clone())
+			.collect()
+	}
+
+	fn text(&self) -> Text<'_> {
+		if self.entries.is_empty() {
+			return Text::from(Span::styled(
+				strings::CLEAN_EMPTY,
+				self.theme.text(false, false),
+			));
+		}
+
+		Text::from(
+			self.entries
+				.iter()
+				.enumerate()
+				.map(|(idx, entry)| {
+					let selected = idx == self.selection;
+					let checked =
+						!self.unchecked.contains(&entry.path);
+
+					Line::from(vec![
+						Span::styled(
+							if selected { "> " } else { "  " },
+							self.theme.text(true, selected),
+						),
+						Span::styled(
+							if checked { "[x] " } else { "[ ] " },
+							self.theme.text(true, selected),
+						),
+						Span::styled(
+							entry.path.as_str(),
+							self.theme.text(!entry.ignored, selected),
+						),
+					])
+				})
+				.collect::<Vec<_>>(),
+		)
+	}
+}
+
+impl DrawableComponent for CleanPopup {
+	fn draw(&self, f: &mut Frame, _rect: Rect) -> Result<()> {
+		if !self.visible {
+			return Ok(());
+		}
+
+		let area = ui::centered_rect(60, 50, f.area());
+
+		f.render_widget(Clear, area);
+		f.render_widget(
+			Block::default()
+				.title(Span::styled(
+					strings::clean_title(self.options),
+					self.theme.title(true),
+				))
+				.borders(Borders::ALL)
+				.border_type(BorderType::Thick)
+				.border_style(self.theme.block(true)),
+			area,
+		);
+
+		let inner = area.inner(Margin {
+			horizontal: 1,
+			vertical: 1,
+		});
+
+		let scroll = self.selection.saturating_sub(
+			usize::from(inner.height).saturating_sub(1),
+		);
+
+		f.render_widget(
+			Paragraph::new(self.text()).scroll((
+				u16::try_from(scroll).unwrap_or(u16::MAX),
+				0,
+			)),
+			inner,
+		);
+
+		Ok(())
+	}
+}
+
+impl Component for CleanPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.visible || force_all {
+			let selected = !self.entries.is_empty();
+
+			out.push(CommandInfo::new(
+				strings::commands::scroll(&self.key_config),
+				selected,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::clean_toggle_entry(
+					&self.key_config,
+				),
+				selected,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::clean_toggle_dirs(
+					&self.key_config,
+					self.options.directories,
+				),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::clean_toggle_ignored(
+					&self.key_config,
+					self.options.ignored,
+				),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::clean_confirm(&self.key_config),
+				self.entries.len() > self.unchecked.len(),
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::close_popup(&self.key_config),
+				true,
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.visible {
+			if let Event::Key(e) = ev {
+				let keys = &self.key_config.keys;
+
+				if key_match(e, keys.exit_popup) {
+					self.hide();
+				} else if key_match(e, keys.move_up) {
+					self.move_selection(true);
+				} else if key_match(e, keys.move_down) {
+					self.move_selection(false);
+				} else if key_match(e, keys.clean_toggle_entry) {
+					self.toggle_selected();
+				} else if key_match(e, keys.clean_toggle_dirs) {
+					self.options.directories =
+						!self.options.directories;
+					self.update()?;
+				} else if key_match(e, keys.clean_toggle_ignored) {
+					self.options.ignored = !self.options.ignored;
+					self.update()?;
+				} else if key_match(e, keys.enter) {
+					let paths = self.checked();
+
+					if !paths.is_empty() {
+						self.queue.push(
+							InternalEvent::ConfirmAction(
+								Action::Clean {
+									paths,
+									backup: self
+										.app_options
+										.borrow()
+										.discard_backup(),
+								},
+							),
+						);
+						self.hide();
+					}
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
@@ -0,0 +1,485 @@
+use crate::components::{
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState, InputType, TextInputComponent,
+};
+use crate::{
+	app::Environment,
+	keys::{key_match, SharedKeyConfig},
+	popups::PushPopup,
+	queue::{InternalEvent, Queue},
+	strings,
+	ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::{
+	sync::CloneOptions, AsyncClone, AsyncGitNotification,
+	CloneRequest, RemoteProgress,
+};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Constraint, Direction, Layout, Margin, Rect},
+	text::{Line, Span},
+	widgets::{Block, BorderType, Borders, Clear, Gauge, Paragraph},
+	Frame,
+};
+use std::path::PathBuf;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Field {
+	Url,
+	Path,
+	Depth,
+}
+
+impl Field {
+	const fn next(self) -> Self {
+		match self {
+			Self::Url => Self::Path,
+			Self::Path => Self::Depth,
+			Self::Depth => Self::Url,
+		}
+	}
+
+	const fn prev(self) -> Self {
+		match self {
+			Self::Url => Self::Depth,
+			Self::Path => Self::Url,
+			Self::Depth => Self::Path,
+		}
+	}
+}
+
+/// directory name `git clone` would pick for `url`
+fn dest_from_url(url: &str) -> String {
+	let url = url.trim().trim_end_matches('/');
+	let name = url.rsplit(['/', ':', '\\']).next().unwrap_or(url);
+
+	name.strip_suffix(".git").unwrap_or(name).to_string()
+}
+
+pub struct ClonePopup {
+	queue: Queue,
+	visible: bool,
+	focus: Field,
+	url: TextInputComponent,
+	path: TextInputComponent,
+	depth: TextInputComponent,
+	recurse_submodules: bool,
+	git_clone: AsyncClone,
+	pending: bool,
+	progress: Option<RemoteProgress>,
+	target: Option<PathBuf>,
+	error: Option<String>,
+	key_config: SharedKeyConfig,
+	theme: SharedTheme,
+}
+
+impl ClonePopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		let input = |default_msg: &str| {
+			let mut input =
+				TextInputComponent::new(env, "", default_msg, false)
+					.with_input_type(InputType::Singleline);
+			input.embed();
+			input
+		};
+
+		Self {
+			queue: env.queue.clone(),
+			visible: false,
+			focus: Field::Url,
+			url: input(strings::CLONE_POPUP_URL_MSG),
+			path: input(""),
+			depth: input(strings::CLONE_POPUP_DEPTH_MSG),
+			recurse_submodules: false,
+			git_clone: AsyncClone::new(&env.sender_git),
+			pending: false,
+			progress: None,
+			target: None,
+			error: None,
+			key_config: env.key_config.clone(),
+			theme: env.theme.clone(),
+		}
+	}
+
+	///
+	pub fn open(&mut self) -> Result<()> {
+		if self.pending {
+			return self.show();
+		}
+
+		self.focus = Field::Url;
+		self.recurse_submodules = false;
+		self.target = None;
+		self.error = None;
+		self.progress = None;
+
+		for input in [&mut self.url, &mut self.path, &mut self.depth]
+		{
+			input.show()?;
+			input.set_text(String::new());
+		}
+		self.path.set_default_msg(String::new());
+
+		self.show()
+	}
+
+	///
+	pub const fn any_work_pending(&self) -> bool {
+		self.pending
+	}
+
+	///
+	pub fn update_git(
+		&mut self,
+		ev: AsyncGitNotification,
+	) -> Result<()> {
+		if ev == AsyncGitNotification::Clone {
+			self.update()?;
+		}
+
+		Ok(())
+	}
+
+	fn update(&mut self) -> Result<()> {
+		self.pending = self.git_clone.is_pending()?;
+		self.progress = self.git_clone.progress()?;
+
+		if !self.pending {
+			if let Some(err) = self.git_clone.last_result()? {
+				self.error = Some(err);
+			} else if let Some(path) = self.target.take() {
+				self.hide();
+				self.queue.push(InternalEvent::OpenNewRepo(
+					path.canonicalize().unwrap_or(path),
+				));
+			}
+		}
+
+		Ok(())
+	}
+
+	fn focused_input(&mut self) -> &mut TextInputComponent {
+		match self.focus {
+			Field::Url => &mut self.url,
+			Field::Path => &mut self.path,
+			Field::Depth => &mut self.depth,
+		}
+	}
+
+	fn dest_path(&self) -> String {
+		let path = self.path.get_text().trim();
+		if path.is_empty() {
+			dest_from_url(self.url.get_text())
+		} else {
+			path.to_string()
+		}
+	}
+
+	fn clone(&mut self) -> Result<()> {
+		let url = self.url.get_text().trim().to_string();
+		if url.is_empty() {
+			self.error = Some(strings::CLONE_POPUP_NO_URL.into());
+			return Ok(());
+		}
+
+		let depth = self.depth.get_text().trim();
+		let depth = if depth.is_empty() {
+			None
+		} else if let Ok(depth) = depth.parse::<u32>() {
+			Some(depth).filter(|depth| *depth > 0)
+		} else {
+			self.error =
+				Some(strings::CLONE_POPUP_INVALID_DEPTH.into());
+			return Ok(());
+		};
+
+		let path = PathBuf::from(self.dest_path());
+
+		self.error = None;
+		self.pending = true;
+		self.progress = None;
+		self.target = Some(path.clone());
+		self.git_clone.request(CloneRequest {
+			url,
+			path,
+			options: CloneOptions {
+				depth,
+				recurse_submodules: self.recurse_submodules,
+			},
+			basic_credential: None,
+		})?;
+
+		Ok(())
+	}
+
+	fn draw_field(
+		&self,
+		f: &mut Frame,
+		area: Rect,
+		field: Field,
+		label: &str,
+	) -> Result<()> {
+		let (input, placeholder) = match field {
+			Field::Url => (&self.url, String::new()),
+			Field::Path => {
+				(&self.path, dest_from_url(self.url.get_text()))
+			}
+			Field::Depth => (&self.depth, String::new()),
+		};
+		let focused = self.focus == field && !self.pending;
+
+		let chunks = Layout::default()
+			.direction(Direction::Horizontal)
+			.constraints([Constraint::Length(12), Constraint::Min(1)])
+			.split(area);
+
+		f.render_widget(
+			Paragraph::new(Span::styled(
+				label.to_string(),
+				self.theme.text(focused, false),
+			)),
+			chunks[0],
+		);
+
+		if focused {
+			input.draw(f, chunks[1])?;
+		} else {
+			let text = input.get_text();
+			f.render_widget(
+				Paragraph::new(if text.is_empty() {
+					Span::styled(
+						placeholder,
+						self.theme.text(false, false),
+					)
+				} else {
+					Span::styled(
+						text.to_string(),
+						self.theme.text(true, false),
+					)
+				}),
+				chunks[1],
+			);
+		}
+
+		Ok(())
+	}
+
+	fn draw_status(&self, f: &mut Frame, area: Rect) {
+		if self.pending {
+			let (state, progress) = PushPopup::get_progress(
+				self.progress.as_ref(),
+				false,
+			);
+
+			f.render_widget(
+				Gauge::default()
+					.label(state.as_str())
+					.gauge_style(self.theme.push_gauge())
+					.percent(u16::from(progress)),
+				area,
+			);
+		} else if let Some(error) = &self.error {
+			f.render_widget(
+				Paragraph::new(Span::styled(
+					error.clone(),
+					self.theme.text_danger(),
+				)),
+				area,
+			);
+		}
+	}
+}
+
+impl DrawableComponent for ClonePopup {
+	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
+		if self.is_visible() {
+			const SIZE: (u16, u16) = (70, 9);
+			let area =
+				ui::centered_rect_absolute(SIZE.0, SIZE.1, rect);
+
+			f.render_widget(Clear, area);
+			f.render_widget(
+				Block::default()
+					.borders(Borders::ALL)
+					.border_type(BorderType::Thick)
+					.border_style(self.theme.block(true))
+					.title(Span::styled(
+						strings::CLONE_POPUP_MSG,
+						self.theme.title(true),
+					)),
+				area,
+			);
+
+			let chunks = Layout::default()
+				.direction(Direction::Vertical)
+				.constraints([
+					Constraint::Length(1),
+					Constraint::Length(1),
+					Constraint::Length(1),
+					Constraint::Length(1),
+					Constraint::Length(1),
+					Constraint::Length(1),
+				])
+				.split(area.inner(Margin {
+					horizontal: 1,
+					vertical: 1,
+				}));
+
+			self.draw_field(f, chunks[0], Field::Url, "URL:")?;
+			self.draw_field(f, chunks[1], Field::Path, "Path:")?;
+			self.draw_field(f, chunks[2], Field::Depth, "Depth:")?;
+
+			f.render_widget(
+				Paragraph::new(Line::from(vec![
+					Span::styled(
+						"Submodules: ",
+						self.theme.text(false, false),
+					),
+					Span::styled(
+						if self.recurse_submodules {
+							"yes"
+						} else {
+							"no"
+						},
+						self.theme.option(self.recurse_submodules),
+					),
+				])),
+				chunks[3],
+			);
+
+			self.draw_status(f, chunks[5]);
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for ClonePopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			out.push(
+				CommandInfo::new(
+					strings::commands::close_popup(&self.key_config),
+					!self.pending,
+					true,
+				)
+				.order(1),
+			);
+
+			out.push(
+				CommandInfo::new(
+					strings::commands::clone_confirm(
+						&self.key_config,
+					),
+					!self.pending,
+					true,
+				)
+				.order(1),
+			);
+
+			out.push(
+				CommandInfo::new(
+					strings::commands::clone_toggle_submodules(
+						&self.key_config,
+					),
+					!self.pending,
+					true,
+				)
+				.order(1),
+			);
+
+			out.push(
+				CommandInfo::new(
+					strings::commands::clone_next_field(
+						&self.key_config,
+					),
+					!self.pending,
+					true,
+				)
+				.order(1),
+			);
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, event: &Event) -> Result<EventState> {
+		if self.is_visible() {
+			if self.pending {
+				return Ok(EventState::Consumed);
+			}
+
+			if let Event::Key(key) = &event {
+				if key_match(key, self.key_config.keys.exit_popup) {
+					self.hide();
+				} else if key_match(key, self.key_config.keys.enter) {
+					self.clone()?;
+				} else if key_match(
+					key,
+					self.key_config.keys.clone_toggle_submodules,
+				) {
+					self.recurse_submodules =
+						!self.recurse_submodules;
+				} else if key_match(
+					key,
+					self.key_config.keys.tab_toggle,
+				) || key_match(
+					key,
+					self.key_config.keys.move_down,
+				) {
+					self.focus = self.focus.next();
+				} else if key_match(
+					key,
+					self.key_config.keys.tab_toggle_reverse,
+				) || key_match(
+					key,
+					self.key_config.keys.move_up,
+				) {
+					self.focus = self.focus.prev();
+				} else {
+					self.focused_input().event(event)?;
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_dest_from_url() {
+		assert_eq!(
+			dest_from_url("https://github.com/gitui-org/gitui.git"),
+			"gitui"
+		);
+		assert_eq!(dest_from_url("git@github.com:foo/bar"), "bar");
+		assert_eq!(dest_from_url("/tmp/some/repo/"), "repo");
+		assert_eq!(dest_from_url("host:repo.git"), "repo");
+	}
+}
@@ -1,6 +1,6 @@
 use crate::components::{
 	visibility_blocking, CommandBlocking, CommandInfo, Component,
-	DrawableComponent, EventState, TextInputComponent,
+	DrawableComponent, EventState, InputType, TextInputComponent,
 };
 use crate::{
 	app::Environment,
@@ -15,15 +15,19 @@ use asyncgit::sync::commit::commit_message_prettify;
 use asyncgit::{
 	cached,
 	sync::{
-		self, get_config_string, CommitId, HookResult,
+		self, get_config_string, AmendOptions, CommitId,
+		CommitLintConfig, CommitSignature, HookResult, LintSeverity,
 		PrepareCommitMsgSource, RepoPathRef, RepoState,
+		SigningStatus, StagedFilesSnapshot,
 	},
 	StatusItem, StatusItemType,
 };
+use chrono::{DateTime, FixedOffset, Local};
 use crossterm::event::Event;
 use easy_cast::Cast;
 use ratatui::{
 	layout::{Alignment, Rect},
+	text::{Line, Span},
 	widgets::Paragraph,
 	Frame,
 };
@@ -34,13 +38,27 @@ use std::{
 	str::FromStr,
 };
 
-use super::ExternalEditorPopup;
+use super::{ExternalEditorPopup, PreCommitResults};
 
 enum CommitResult {
 	CommitDone,
+	/// waiting for the `pre-commit` hook to finish
+	HookRunning,
 	Aborted,
 }
 
+/// commit waiting for the `pre-commit` hook
+struct PendingCommit {
+	msg: String,
+	snapshot: Option<StagedFilesSnapshot>,
+}
+
+#[derive(Clone, Copy)]
+enum AmendField {
+	Author,
+	Date,
+}
+
 enum Mode {
 	Normal,
 	Amend(CommitId),
@@ -61,10 +79,15 @@ pub struct CommitPopup {
 	commit_msg_history_idx: usize,
 	options: SharedOptions,
 	verify: bool,
+	amend_options: AmendOptions,
+	amend_original: Option<CommitSignature>,
+	amend_field: Option<AmendField>,
+	amend_input: TextInputComponent,
+	lint_config: CommitLintConfig,
+	signing: Option<SigningStatus>,
+	pending_commit: Option<PendingCommit>,
 }
 
-const FIRST_LINE_LIMIT: usize = 50;
-
 impl CommitPopup {
 	///
 	pub fn new(env: &Environment) -> Self {
@@ -87,17 +110,53 @@ impl CommitPopup {
 			commit_msg_history_idx: 0,
 			options: env.options.clone(),
 			verify: true,
+			amend_options: AmendOptions::default(),
+			amend_original: None,
+			amend_field: None,
+			amend_input: TextInputComponent::new(env, "", "", false)
+				.with_input_type(InputType::Singleline),
+			lint_config: CommitLintConfig::default(),
+			signing: None,
+			pending_commit: None,
 		}
 	}
 
 	///
 	pub fn update(&mut self) {
 		self.git_branch_name.lookup().ok();
+
+		if self.is_visible() {
+			self.update_signing();
+		}
+	}
+
+	fn update_signing(&mut self) {
+		self.signing =
+			sync::get_signing_status(&self.repo.borrow()).ok();
 	}
 
+	/// branch name, whether the commit is going to be signed and
+	/// whether hooks are skipped
 	fn draw_branch_name(&self, f: &mut Frame) {
 		if let Some(name) = self.git_branch_name.last() {
-			let w = Paragraph::new(format!("{{{name}}}"))
+			let mut spans = Vec::new();
+			if !self.verify {
+				spans.push(Span::styled(
+					strings::COMMIT_NO_VERIFY_INDICATOR,
+					self.theme.text_danger(),
+				));
+				spans.push(Span::raw(" "));
+			}
+			if let Some(signing) = &self.signing {
+				spans.push(Span::styled(
+					strings::commit_signing_indicator(signing),
+					self.theme.option(signing.enabled),
+				));
+				spans.push(Span::raw(" "));
+			}
+			spans.push(Span::raw(format!("{{{name}}}")));
+
+			let w = Paragraph::new(Line::from(spans))
 				.alignment(Alignment::Right);
 
 			let rect = {
@@ -112,17 +171,18 @@ impl CommitPopup {
 	}
 
 	fn draw_warnings(&self, f: &mut Frame) {
-		let first_line = self
-			.input
-			.get_text()
-			.lines()
-			.next()
-			.map(str::len)
-			.unwrap_or_default();
-
-		if first_line > FIRST_LINE_LIMIT {
-			let msg = strings::commit_first_line_warning(first_line);
-			let msg_length: u16 = msg.len().cast();
+		let problems = sync::lint_commit_message(
+			&self.lint_config,
+			self.input.get_text(),
+		);
+
+		if !problems.is_empty() {
+			let msg = problems
+				.iter()
+				.map(|p| strings::commit_lint_warning(&p.message))
+				.collect::<Vec<_>>()
+				.join(" ");
+			let msg_length: u16 = msg.chars().count().cast();
 			let w =
 				Paragraph::new(msg).style(self.theme.text_danger());
 
@@ -130,8 +190,9 @@ impl CommitPopup {
 				let mut rect = self.input.get_area();
 				rect.y += rect.height.saturating_sub(1);
 				rect.height = 1;
+				// keep the left border visible
 				let offset =
-					rect.width.saturating_sub(msg_length + 1);
+					rect.width.saturating_sub(msg_length + 1).max(1);
 				rect.width = rect.width.saturating_sub(offset + 1);
 				rect.x += offset;
 
@@ -142,6 +203,42 @@ impl CommitPopup {
 		}
 	}
 
+	fn draw_amend_overrides(&self, f: &mut Frame) {
+		let mut parts = Vec::new();
+
+		if let Some((name, email)) = &self.amend_options.author {
+			parts.push(format!("author: {name} <{email}>"));
+		}
+		if let Some((secs, offset)) = self.amend_options.author_time {
+			parts.push(format!(
+				"date: {}",
+				format_amend_time(secs, offset)
+			));
+		}
+		if self.amend_options.reset_committer_date {
+			parts.push(String::from("reset committer date"));
+		}
+
+		if parts.is_empty() {
+			return;
+		}
+
+		let msg = format!("[{}]", parts.join(", "));
+		let w =
+			Paragraph::new(msg).style(self.theme.text(true, false));
+
+		let rect = {
+			let mut rect = self.input.get_area();
+			rect.y += rect.height.saturating_sub(1);
+			rect.height = 1;
+			rect.x += 1;
+			rect.width = rect.width.saturating_sub(2);
+			rect
+		};
+
+		f.render_widget(w, rect);
+	}
+
 	const fn item_status_char(
 		item_type: StatusItemType,
 	) -> &'static str {
@@ -150,8 +247,12 @@ impl CommitPopup {
 			StatusItemType::New => "new file",
 			StatusItemType::Deleted => "deleted",
 			StatusItemType::Renamed => "renamed",
+			StatusItemType::Copied => "copied",
 			StatusItemType::Typechange => " ",
 			StatusItemType::Conflicted => "conflicted",
+			StatusItemType::Ignored => "ignored",
+			StatusItemType::AssumeUnchanged => "assume-unchanged",
+			StatusItemType::SkipWorktree => "skip-worktree",
 		}
 	}
 
@@ -178,8 +279,15 @@ impl CommitPopup {
 			for change in changes {
 				let status_char =
 					Self::item_status_char(change.status);
-				let message =
-					format!("\n#\t{status_char}: {}", change.path);
+				let message = change.old_path.as_ref().map_or_else(
+					|| format!("\n#\t{status_char}: {}", change.path),
+					|old| {
+						format!(
+							"\n#\t{status_char}: {old} -> {}",
+							change.path
+						)
+					},
+				);
 				file.write_all(message.as_bytes())?;
 			}
 		}
@@ -211,20 +319,82 @@ impl CommitPopup {
 			self.commit_with_msg(msg)?,
 			CommitResult::CommitDone
 		) {
-			self.options
-				.borrow_mut()
-				.add_commit_msg(self.input.get_text());
-			self.commit_msg_history_idx = 0;
-
-			self.hide();
-			self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
-			self.queue.push(InternalEvent::StatusLastFileMoved);
-			self.input.clear();
+			self.commit_done();
 		}
 
 		Ok(())
 	}
 
+	fn commit_done(&mut self) {
+		if self.is_amend() {
+			self.restack_children();
+		}
+
+		self.options
+			.borrow_mut()
+			.add_commit_msg(self.input.get_text());
+		self.commit_msg_history_idx = 0;
+
+		self.hide();
+		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+		self.queue.push(InternalEvent::StatusLastFileMoved);
+		self.input.clear();
+	}
+
+	/// continues the commit that waited for the `pre-commit` hook
+	pub fn pre_commit_finished(
+		&mut self,
+		result: Result<HookResult, String>,
+	) -> Result<()> {
+		let Some(pending) = self.pending_commit.take() else {
+			return Ok(());
+		};
+
+		match result {
+			Err(e) => {
+				log::error!("pre-commit hook error: {}", e);
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("pre-commit hook error:\n{e}"),
+				));
+			}
+			std::result::Result::Ok(HookResult::NotOk(e)) => {
+				log::error!("pre-commit hook error: {}", e);
+				self.pre_commit_failed(&e, pending.snapshot.as_ref());
+			}
+			std::result::Result::Ok(HookResult::Ok) => {
+				if matches!(
+					self.commit_verified(pending.msg, true)?,
+					CommitResult::CommitDone
+				) {
+					self.commit_done();
+				}
+			}
+		}
+
+		Ok(())
+	}
+
+	/// moves branches stacked on the current one onto the amended commit
+	fn restack_children(&mut self) {
+		let Some(branch) = self.git_branch_name.lookup().ok() else {
+			return;
+		};
+
+		match sync::restack_children(&self.repo.borrow(), &branch) {
+			Err(e) => {
+				log::error!("restack error: {}", e);
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("restack error:\n{e}"),
+				));
+			}
+			std::result::Result::Ok(restacked) => {
+				if !restacked.is_empty() {
+					log::info!("restacked: {restacked:?}");
+				}
+			}
+		}
+	}
+
 	fn commit_with_msg(
 		&mut self,
 		msg: String,
@@ -233,22 +403,31 @@ impl CommitPopup {
 		let verify = self.verify;
 		self.verify = true;
 
+		let msg = commit_message_prettify(&self.repo.borrow(), msg)?;
+
 		if verify {
-			// run pre commit hook - can reject commit
-			if let HookResult::NotOk(e) =
-				sync::hooks_pre_commit(&self.repo.borrow())?
-			{
-				log::error!("pre-commit hook error: {}", e);
-				self.queue.push(InternalEvent::ShowErrorMsg(
-					format!("pre-commit hook error:\n{e}"),
-				));
+			// lint before any hook gets to reject the message
+			if !self.lint_passed(&msg)? {
 				return Ok(CommitResult::Aborted);
 			}
+
+			// the hook runs async, the commit continues in
+			// `pre_commit_finished`
+			if sync::hooks_pre_commit_found(&self.repo.borrow())? {
+				self.start_pre_commit(msg);
+				return Ok(CommitResult::HookRunning);
+			}
 		}
 
-		let mut msg =
-			commit_message_prettify(&self.repo.borrow(), msg)?;
+		self.commit_verified(msg, verify)
+	}
 
+	/// commits `msg` once the `pre-commit` hook passed
+	fn commit_verified(
+		&self,
+		mut msg: String,
+		verify: bool,
+	) -> Result<CommitResult> {
 		if verify {
 			// run commit message check hook - can reject commit
 			if let HookResult::NotOk(e) =
@@ -275,12 +454,96 @@ impl CommitPopup {
 		Ok(CommitResult::CommitDone)
 	}
 
+	/// runs the builtin lint rules and the lint command, shows the
+	/// errors preventing the commit
+	fn lint_passed(&self, msg: &str) -> Result<bool> {
+		let mut problems =
+			sync::lint_commit_message(&self.lint_config, msg);
+
+		if let Some(command) = &self.lint_config.command {
+			problems.extend(sync::lint_commit_message_command(
+				&self.repo.borrow(),
+				command,
+				msg,
+			)?);
+		}
+
+		let errors = problems
+			.into_iter()
+			.filter(|p| p.severity == LintSeverity::Error)
+			.map(|p| p.message)
+			.collect::<Vec<_>>();
+
+		if errors.is_empty() {
+			return Ok(true);
+		}
+
+		log::error!("commit message lint error: {:?}", errors);
+		self.queue.push(InternalEvent::ShowErrorMsg(format!(
+			"commit message lint error:\n{}",
+			errors.join("\n")
+		)));
+
+		Ok(false)
+	}
+
+	fn start_pre_commit(&mut self, msg: String) {
+		let snapshot =
+			sync::uses_pre_commit_framework(&self.repo.borrow())
+				.unwrap_or_default()
+				.then(|| {
+					StagedFilesSnapshot::new(&self.repo.borrow()).ok()
+				})
+				.flatten();
+
+		self.pending_commit = Some(PendingCommit { msg, snapshot });
+		self.queue.push(InternalEvent::OpenHookProgress);
+	}
+
+	/// shows the per check results if the pre-commit framework ran
+	/// the hook, the raw hook output otherwise
+	fn pre_commit_failed(
+		&self,
+		output: &str,
+		snapshot: Option<&StagedFilesSnapshot>,
+	) {
+		let checks = sync::parse_pre_commit_output(output);
+
+		match snapshot {
+			Some(snapshot) if !checks.is_empty() => {
+				let repo = self.repo.borrow();
+				let modified_files = snapshot
+					.modified_files(&repo)
+					.unwrap_or_default();
+				let patch =
+					sync::workdir_patch(&repo, &modified_files)
+						.unwrap_or_default();
+
+				self.queue.push(InternalEvent::OpenPreCommitResults(
+					PreCommitResults {
+						checks,
+						modified_files,
+						patch,
+					},
+				));
+			}
+			_ => {
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("pre-commit hook error:\n{output}"),
+				));
+			}
+		}
+	}
+
 	fn do_commit(&self, msg: &str) -> Result<()> {
 		match &self.mode {
 			Mode::Normal => sync::commit(&self.repo.borrow(), msg)?,
-			Mode::Amend(amend) => {
-				sync::amend(&self.repo.borrow(), *amend, msg)?
-			}
+			Mode::Amend(amend) => sync::commit_amend_with_details(
+				&self.repo.borrow(),
+				*amend,
+				msg,
+				&self.amend_options,
+			)?,
 			Mode::Merge(ids) => {
 				sync::merge_commit(&self.repo.borrow(), msg, ids)?
 			}
@@ -330,8 +593,82 @@ impl CommitPopup {
 			if let Some(msg) = details.message {
 				self.input.set_text(msg.combine());
 			}
+
+			self.amend_options = AmendOptions::default();
+			self.amend_original = Some(details.author);
+		}
+
+		Ok(())
+	}
+
+	const fn is_amend(&self) -> bool {
+		matches!(self.mode, Mode::Amend(_))
+	}
+
+	fn open_amend_field(&mut self, field: AmendField) -> Result<()> {
+		let (title, msg, text) = match field {
+			AmendField::Author => (
+				strings::commit_amend_author_title(),
+				strings::commit_amend_author_msg(),
+				self.amend_options
+					.author
+					.clone()
+					.or_else(|| {
+						self.amend_original.as_ref().map(|a| {
+							(a.name.clone(), a.email.clone())
+						})
+					})
+					.map(|(name, email)| format!("{name} <{email}>")),
+			),
+			AmendField::Date => (
+				strings::commit_amend_date_title(),
+				strings::commit_amend_date_msg(),
+				self.amend_options
+					.author_time
+					.map(|(secs, offset)| {
+						format_amend_time(secs, offset)
+					})
+					.or_else(|| {
+						self.amend_original
+							.as_ref()
+							.map(|a| format_amend_time_local(a.time))
+					}),
+			),
+		};
+
+		self.amend_input.set_title(title);
+		self.amend_input.set_default_msg(msg);
+		self.amend_input.set_text(text.unwrap_or_default());
+		self.amend_input.show()?;
+		self.amend_field = Some(field);
+
+		Ok(())
+	}
+
+	fn confirm_amend_field(&mut self) -> Result<()> {
+		let text = self.amend_input.get_text().trim().to_string();
+
+		match self.amend_field {
+			Some(AmendField::Author) => {
+				self.amend_options.author = if text.is_empty() {
+					None
+				} else {
+					Some(parse_amend_author(&text)?)
+				};
+			}
+			Some(AmendField::Date) => {
+				self.amend_options.author_time = if text.is_empty() {
+					None
+				} else {
+					Some(parse_amend_time(&text)?)
+				};
+			}
+			None => (),
 		}
 
+		self.amend_input.hide();
+		self.amend_field = None;
+
 		Ok(())
 	}
 	fn signoff_commit(&mut self) {
@@ -345,6 +682,15 @@ impl CommitPopup {
 		self.verify = !self.verify;
 	}
 
+	fn load_lint_config(&self) -> CommitLintConfig {
+		CommitLintConfig::load(&self.repo.borrow())
+			.map_err(|e| {
+				log::error!("load commit lint config failed: {}", e);
+				e
+			})
+			.unwrap_or_default()
+	}
+
 	pub fn open(&mut self, reword: Option<CommitId>) -> Result<()> {
 		//only clear text if it was not a normal commit dlg before, so to preserve old commit msg that was edited
 		if !matches!(self.mode, Mode::Normal) {
@@ -352,6 +698,10 @@ impl CommitPopup {
 		}
 
 		self.mode = Mode::Normal;
+		self.amend_options = AmendOptions::default();
+		self.amend_original = None;
+		self.lint_config = self.load_lint_config();
+		self.update_signing();
 
 		let repo_state = sync::repo_state(&self.repo.borrow())?;
 
@@ -454,6 +804,34 @@ impl CommitPopup {
 		Ok(())
 	}
 
+	/// opens a normal commit prefilled with `fixup! <subject>` (or
+	/// `squash! <subject>`) of `target` for a later autosquash
+	pub fn open_fixup(
+		&mut self,
+		target: CommitId,
+		squash: bool,
+	) -> Result<()> {
+		let staged = sync::status::get_status(
+			&self.repo.borrow(),
+			sync::status::StatusType::Stage,
+			None,
+		)?;
+		if staged.is_empty() {
+			bail!("nothing staged to commit as fixup");
+		}
+
+		let msg = sync::rebase_interactive::fixup_message(
+			&self.repo.borrow(),
+			target,
+			squash,
+		)?;
+
+		self.mode = Mode::Normal;
+		self.input.set_text(msg);
+
+		self.open(None)
+	}
+
 	fn add_sign_off(&self, msg: &str) -> Result<String> {
 		const CONFIG_KEY_USER_NAME: &str = "user.name";
 		const CONFIG_KEY_USER_MAIL: &str = "user.email";
@@ -485,6 +863,14 @@ impl DrawableComponent for CommitPopup {
 			self.input.draw(f, rect)?;
 			self.draw_branch_name(f);
 			self.draw_warnings(f);
+
+			if self.is_amend() {
+				self.draw_amend_overrides(f);
+			}
+
+			if self.amend_input.is_visible() {
+				self.amend_input.draw(f, rect)?;
+			}
 		}
 
 		Ok(())
@@ -521,12 +907,47 @@ impl Component for CommitPopup {
 				true,
 			));
 
+			if self.is_amend() {
+				out.push(CommandInfo::new(
+					strings::commands::commit_amend_author(
+						&self.key_config,
+					),
+					true,
+					true,
+				));
+
+				out.push(CommandInfo::new(
+					strings::commands::commit_amend_date(
+						&self.key_config,
+					),
+					true,
+					true,
+				));
+
+				out.push(CommandInfo::new(
+					strings::commands::commit_amend_reset_date(
+						&self.key_config,
+						self.amend_options.reset_committer_date,
+					),
+					true,
+					true,
+				));
+			}
+
 			out.push(CommandInfo::new(
 				strings::commands::commit_signoff(&self.key_config),
 				true,
 				true,
 			));
 
+			out.push(CommandInfo::new(
+				strings::commands::commit_signing_key(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
+
 			out.push(CommandInfo::new(
 				strings::commands::commit_open_editor(
 					&self.key_config,
@@ -553,8 +974,26 @@ impl Component for CommitPopup {
 		visibility_blocking(self)
 	}
 
+	#[allow(clippy::too_many_lines)]
 	fn event(&mut self, ev: &Event) -> Result<EventState> {
 		if self.is_visible() {
+			if self.amend_input.is_visible() {
+				if let Event::Key(e) = ev {
+					if key_match(e, self.key_config.keys.enter) {
+						try_or_popup!(
+							self,
+							"amend error:",
+							self.confirm_amend_field()
+						);
+						return Ok(EventState::Consumed);
+					}
+				}
+
+				self.amend_input.event(ev)?;
+
+				return Ok(EventState::Consumed);
+			}
+
 			if let Event::Key(e) = ev {
 				let input_consumed =
 					if key_match(e, self.key_config.keys.commit)
@@ -580,6 +1019,28 @@ impl Component for CommitPopup {
 					{
 						self.amend()?;
 						true
+					} else if key_match(
+						e,
+						self.key_config.keys.commit_amend_author,
+					) && self.is_amend()
+					{
+						self.open_amend_field(AmendField::Author)?;
+						true
+					} else if key_match(
+						e,
+						self.key_config.keys.commit_amend_date,
+					) && self.is_amend()
+					{
+						self.open_amend_field(AmendField::Date)?;
+						true
+					} else if key_match(
+						e,
+						self.key_config.keys.commit_amend_reset_date,
+					) && self.is_amend()
+					{
+						self.amend_options.reset_committer_date =
+							!self.amend_options.reset_committer_date;
+						true
 					} else if key_match(
 						e,
 						self.key_config.keys.open_commit_editor,
@@ -608,6 +1069,13 @@ impl Component for CommitPopup {
 					) {
 						self.signoff_commit();
 						true
+					} else if key_match(
+						e,
+						self.key_config.keys.commit_signing_key,
+					) {
+						self.queue
+							.push(InternalEvent::OpenSigningKeyPopup);
+						true
 					} else {
 						false
 					};
@@ -629,6 +1097,7 @@ impl Component for CommitPopup {
 	}
 
 	fn hide(&mut self) {
+		self.amend_input.hide();
 		self.input.hide();
 	}
 
@@ -637,3 +1106,48 @@ impl Component for CommitPopup {
 		Ok(())
 	}
 }
+
+const AMEND_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S %z";
+
+fn format_amend_time(secs: i64, offset_minutes: i32) -> String {
+	let Some(offset) = FixedOffset::east_opt(offset_minutes * 60)
+	else {
+		return format_amend_time_local(secs);
+	};
+
+	DateTime::from_timestamp(secs, 0)
+		.unwrap_or_default()
+		.with_timezone(&offset)
+		.format(AMEND_TIME_FORMAT)
+		.to_string()
+}
+
+fn format_amend_time_local(secs: i64) -> String {
+	DateTime::from_timestamp(secs, 0)
+		.unwrap_or_default()
+		.with_timezone(&Local)
+		.format(AMEND_TIME_FORMAT)
+		.to_string()
+}
+
+fn parse_amend_author(text: &str) -> Result<(String, String)> {
+	let Some((name, email)) =
+		text.strip_suffix('>').and_then(|text| text.split_once('<'))
+	else {
+		bail!("expected author as `Name <email>`");
+	};
+
+	Ok((name.trim().to_string(), email.trim().to_string()))
+}
+
+fn parse_amend_time(text: &str) -> Result<(i64, i32)> {
+	let time = DateTime::parse_from_str(text, AMEND_TIME_FORMAT)
+		.map_err(|e| {
+			anyhow::anyhow!(
+				"expected date as `{}`: {e}",
+				strings::commit_amend_date_msg()
+			)
+		})?;
+
+	Ok((time.timestamp(), time.offset().local_minus_utc() / 60))
+}
@@ -0,0 +1,145 @@
+use crate::components::{
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState, InputType, TextInputComponent,
+};
+use crate::{
+	app::Environment,
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, NeedsUpdate, Queue},
+	strings,
+};
+use anyhow::Result;
+use asyncgit::sync::{self, CommitId, RepoPathRef};
+use crossterm::event::Event;
+use ratatui::{layout::Rect, Frame};
+
+/// adds, edits or (when emptied) removes the note of a commit
+pub struct CommitNotePopup {
+	repo: RepoPathRef,
+	input: TextInputComponent,
+	commit_id: Option<CommitId>,
+	queue: Queue,
+	key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for CommitNotePopup {
+	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
+		self.input.draw(f, rect)?;
+
+		Ok(())
+	}
+}
+
+impl Component for CommitNotePopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			self.input.commands(out, force_all);
+
+			out.push(CommandInfo::new(
+				strings::commands::commit_note_confirm(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.is_visible() {
+			if let Event::Key(e) = ev {
+				if key_match(e, self.key_config.keys.commit) {
+					self.confirm();
+					return Ok(EventState::Consumed);
+				}
+			}
+
+			self.input.event(ev)?;
+			return Ok(EventState::Consumed);
+		}
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.input.is_visible()
+	}
+
+	fn hide(&mut self) {
+		self.input.hide();
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.input.show()?;
+
+		Ok(())
+	}
+}
+
+impl CommitNotePopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			queue: env.queue.clone(),
+			input: TextInputComponent::new(
+				env,
+				&strings::commit_note_popup_title(),
+				&strings::commit_note_popup_msg(),
+				true,
+			)
+			.with_input_type(InputType::Multiline),
+			commit_id: None,
+			key_config: env.key_config.clone(),
+			repo: env.repo.clone(),
+		}
+	}
+
+	///
+	pub fn open(&mut self, id: CommitId) -> Result<()> {
+		self.commit_id = Some(id);
+		self.input.set_text(
+			sync::get_note(&self.repo.borrow(), id)?
+				.unwrap_or_default(),
+		);
+		self.show()?;
+
+		Ok(())
+	}
+
+	fn confirm(&mut self) {
+		let Some(commit_id) = self.commit_id else {
+			return;
+		};
+
+		let repo = self.repo.borrow();
+		let note = self.input.get_text().trim();
+
+		let result = if note.is_empty() {
+			match sync::get_note(&repo, commit_id) {
+				Ok(Some(_)) => sync::remove_note(&repo, commit_id),
+				Ok(None) => Ok(()),
+				Err(e) => Err(e),
+			}
+		} else {
+			sync::set_note(&repo, commit_id, &format!("{note}\n"))
+		};
+
+		if let Err(e) = result {
+			log::error!("note error: {e}");
+			self.queue.push(InternalEvent::ShowErrorMsg(format!(
+				"note error:\n{e}",
+			)));
+		}
+
+		drop(repo);
+
+		self.input.clear();
+		self.hide();
+		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+	}
+}
@@ -1,24 +1,27 @@
 use crate::components::{
 	command_pump, event_pump, visibility_blocking, CommandBlocking,
-	CommandInfo, CommitDetailsComponent, Component, DiffComponent,
-	DrawableComponent, EventState,
+	CommandInfo, CommitDetailsComponent, CommitList, Component,
+	DiffComponent, DrawableComponent, EventState,
 };
 use crate::{
 	accessors,
 	app::Environment,
 	keys::{key_match, SharedKeyConfig},
 	options::SharedOptions,
-	popups::InspectCommitOpen,
-	queue::{InternalEvent, Queue, StackablePopupOpen},
+	popups::{InspectCommitOpen, RevisionTarget},
+	queue::{ExternalTool, InternalEvent, Queue, StackablePopupOpen},
 	strings,
 };
 use anyhow::Result;
 use asyncgit::{
-	sync::{self, commit_files::OldNew, CommitId, RepoPathRef},
+	sync::{
+		self, commit_files::OldNew, CommitId, CompareMode,
+		DiffToolTarget, RepoPathRef,
+	},
 	AsyncDiff, AsyncGitNotification, CommitFilesParams, DiffParams,
 	DiffType,
 };
-use crossterm::event::Event;
+use crossterm::event::{Event, KeyEvent};
 use ratatui::{
 	layout::{Constraint, Direction, Layout, Rect},
 	widgets::Clear,
@@ -28,8 +31,14 @@ use ratatui::{
 pub struct CompareCommitsPopup {
 	repo: RepoPathRef,
 	open_request: Option<InspectCommitOpen>,
+	mode: CompareMode,
+	/// what gets diffed, the merge base replaces the base for
+	/// [`CompareMode::ThreeDot`]
+	diff_ids: Option<OldNew<CommitId>>,
 	diff: DiffComponent,
 	details: CommitDetailsComponent,
+	commits: CommitList,
+	show_commits: bool,
 	git_diff: AsyncDiff,
 	visible: bool,
 	key_config: SharedKeyConfig,
@@ -40,6 +49,12 @@ pub struct CompareCommitsPopup {
 impl DrawableComponent for CompareCommitsPopup {
 	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
 		if self.is_visible() {
+			if self.show_commits {
+				f.render_widget(Clear, rect);
+				self.commits.draw(f, rect)?;
+				return Ok(());
+			}
+
 			let percentages = if self.diff.focused() {
 				(0, 100)
 			} else {
@@ -74,11 +89,15 @@ impl Component for CompareCommitsPopup {
 		force_all: bool,
 	) -> CommandBlocking {
 		if self.is_visible() || force_all {
-			command_pump(
-				out,
-				force_all,
-				self.components().as_slice(),
-			);
+			if self.show_commits {
+				self.commits.commands(out, force_all);
+			} else {
+				command_pump(
+					out,
+					force_all,
+					self.components().as_slice(),
+				);
+			}
 
 			out.push(
 				CommandInfo::new(
@@ -89,16 +108,62 @@ impl Component for CompareCommitsPopup {
 				.order(1),
 			);
 
+			out.push(CommandInfo::new(
+				strings::commands::compare_toggle_commits(
+					&self.key_config,
+					self.show_commits,
+				),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::compare_toggle_mode(
+					&self.key_config,
+					self.mode,
+				),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::compare_swap(&self.key_config),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::compare_set_base(&self.key_config),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::log_details_toggle(
+					&self.key_config,
+				),
+				self.commits.selected_entry().is_some(),
+				self.show_commits || force_all,
+			));
+
 			out.push(CommandInfo::new(
 				strings::commands::diff_focus_right(&self.key_config),
 				self.can_focus_diff(),
-				!self.diff.focused() || force_all,
+				(!self.show_commits && !self.diff.focused())
+					|| force_all,
 			));
 
 			out.push(CommandInfo::new(
 				strings::commands::diff_focus_left(&self.key_config),
 				true,
-				self.diff.focused() || force_all,
+				(!self.show_commits && self.diff.focused())
+					|| force_all,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::open_diff_tool(&self.key_config),
+				self.diff_tool().is_some(),
+				!self.show_commits || force_all,
 			));
 		}
 
@@ -107,6 +172,10 @@ impl Component for CompareCommitsPopup {
 
 	fn event(&mut self, ev: &Event) -> Result<EventState> {
 		if self.is_visible() {
+			if self.show_commits {
+				return self.commits_event(ev);
+			}
+
 			if event_pump(ev, self.components_mut().as_mut_slice())?
 				.is_consumed()
 			{
@@ -134,6 +203,17 @@ impl Component for CompareCommitsPopup {
 				} else if key_match(e, self.key_config.keys.move_left)
 				{
 					self.hide_stacked(false);
+				} else if key_match(
+					e,
+					self.key_config.keys.open_diff_tool,
+				) {
+					if let Some(tool) = self.diff_tool() {
+						self.queue.push(
+							InternalEvent::OpenExternalTool(tool),
+						);
+					}
+				} else {
+					self.range_event(e)?;
 				}
 
 				return Ok(EventState::Consumed);
@@ -168,7 +248,11 @@ impl CompareCommitsPopup {
 			repo: env.repo.clone(),
 			details: CommitDetailsComponent::new(env),
 			diff: DiffComponent::new(env, true),
+			commits: CommitList::new(env, ""),
+			show_commits: false,
 			open_request: None,
+			mode: CompareMode::default(),
+			diff_ids: None,
 			git_diff: AsyncDiff::new(
 				env.repo.borrow().clone(),
 				&env.sender_git,
@@ -187,11 +271,16 @@ impl CompareCommitsPopup {
 		} else {
 			sync::get_head_tuple(&self.repo.borrow())?.id
 		};
+		if let Some(mode) = open.compare_mode {
+			self.mode = mode;
+		}
 		self.open_request = Some(InspectCommitOpen {
 			commit_id: open.commit_id,
 			compare_id: Some(compare_id),
+			compare_mode: None,
 			tags: open.tags,
 		});
+		self.update_range();
 		self.show()?;
 
 		Ok(())
@@ -212,6 +301,8 @@ impl CompareCommitsPopup {
 				self.update()?;
 			} else if ev == AsyncGitNotification::Diff {
 				self.update_diff()?;
+			} else if ev == AsyncGitNotification::Cherry {
+				self.details.update_git(ev);
 			}
 		}
 
@@ -233,16 +324,113 @@ impl CompareCommitsPopup {
 		})
 	}
 
+	/// diff and commit list of the range in the current mode
+	fn update_range(&mut self) {
+		self.diff_ids = None;
+		self.commits.clear();
+
+		let Some(ids) = self.get_ids() else {
+			return;
+		};
+
+		let repo = self.repo.borrow().clone();
+
+		match sync::compare_diff_ids(&repo, ids, self.mode) {
+			Ok(diff_ids) => self.diff_ids = Some(diff_ids),
+			Err(e) => {
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("compare error:\n{e}"),
+				));
+			}
+		}
+
+		self.commits.set_title(
+			&strings::commit::compare_commits_title(
+				self.mode,
+				&ids.old.get_short_string(),
+				&ids.new.get_short_string(),
+			),
+		);
+		self.commits.set_commits(
+			sync::get_compare_commits(&repo, ids, self.mode)
+				.unwrap_or_default()
+				.into_iter()
+				.collect(),
+		);
+	}
+
+	fn commits_event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.commits.event(ev)?.is_consumed() {
+			return Ok(EventState::Consumed);
+		}
+
+		if let Event::Key(e) = ev {
+			if key_match(e, self.key_config.keys.exit_popup) {
+				self.hide_stacked(false);
+			} else if key_match(e, self.key_config.keys.enter) {
+				if let Some(id) =
+					self.commits.selected_entry().map(|e| e.id)
+				{
+					self.hide_stacked(true);
+					self.queue.push(InternalEvent::OpenPopup(
+						StackablePopupOpen::InspectCommit(
+							InspectCommitOpen::new(id),
+						),
+					));
+				}
+			} else {
+				self.range_event(e)?;
+			}
+		}
+
+		Ok(EventState::Consumed)
+	}
+
+	/// keys changing the compared range, shared by both views
+	fn range_event(&mut self, e: &KeyEvent) -> Result<()> {
+		if key_match(e, self.key_config.keys.compare_toggle_commits) {
+			self.show_commits = !self.show_commits;
+		} else if key_match(
+			e,
+			self.key_config.keys.compare_toggle_mode,
+		) {
+			self.mode = self.mode.toggled();
+			self.update_range();
+			self.update()?;
+		} else if key_match(e, self.key_config.keys.compare_swap) {
+			if let Some(open) = self.open_request.as_mut() {
+				if let Some(compare_id) = open.compare_id {
+					open.compare_id = Some(open.commit_id);
+					open.commit_id = compare_id;
+				}
+			}
+			self.update_range();
+			self.update()?;
+		} else if key_match(e, self.key_config.keys.compare_set_base)
+		{
+			if let Some(ids) = self.get_ids() {
+				self.queue.push(InternalEvent::OpenRevisionPopup(
+					RevisionTarget::CompareBase(ids.new),
+				));
+			}
+		}
+
+		Ok(())
+	}
+
 	/// called when any tree component changed selection
 	pub fn update_diff(&mut self) -> Result<()> {
 		if self.is_visible() {
-			if let Some(ids) = self.get_ids() {
+			if let Some(ids) = self.diff_ids {
 				if let Some(f) = self.details.files().selection_file()
 				{
 					let diff_params = DiffParams {
 						path: f.path.clone(),
 						diff_type: DiffType::Commits(ids),
-						options: self.options.borrow().diff_options(),
+						options: self.diff.limit_options(
+							&f.path,
+							self.options.borrow().diff_options(),
+						),
 					};
 
 					if let Some((params, last)) =
@@ -268,7 +456,7 @@ impl CompareCommitsPopup {
 
 	fn update(&mut self) -> Result<()> {
 		self.details.set_commits(
-			self.get_ids().map(CommitFilesParams::from),
+			self.diff_ids.map(CommitFilesParams::from),
 			None,
 		)?;
 		self.update_diff()?;
@@ -280,12 +468,25 @@ impl CompareCommitsPopup {
 		self.details.files().selection_file().is_some()
 	}
 
+	/// selected file in `diff.tool`
+	fn diff_tool(&self) -> Option<ExternalTool> {
+		Some(ExternalTool::Diff(
+			self.details.files().selection_file()?.path,
+			DiffToolTarget::Commits(self.diff_ids?),
+		))
+	}
+
 	fn hide_stacked(&mut self, stack: bool) {
 		self.hide();
 		if stack {
 			if let Some(request) = self.open_request.clone() {
 				self.queue.push(InternalEvent::PopupStackPush(
-					StackablePopupOpen::CompareCommits(request),
+					StackablePopupOpen::CompareCommits(
+						InspectCommitOpen {
+							compare_mode: Some(self.mode),
+							..request
+						},
+					),
 				));
 			}
 		} else {
@@ -2,15 +2,21 @@ use crate::{
 	app::Environment,
 	components::{
 		visibility_blocking, CommandBlocking, CommandInfo, Component,
-		DrawableComponent, EventState,
+		DrawableComponent, EventState, InputType, TextInputComponent,
 	},
 	keys::{key_match, SharedKeyConfig},
 	queue::{Action, InternalEvent, Queue},
 	strings, ui,
 };
 use anyhow::Result;
+use asyncgit::sync::{self, RepoPathRef};
 use crossterm::event::Event;
-use ratatui::{layout::Rect, text::Text, widgets::Clear, Frame};
+use ratatui::{
+	layout::{Margin, Rect},
+	text::Text,
+	widgets::Clear,
+	Frame,
+};
 use std::borrow::Cow;
 use ui::style::SharedTheme;
 
@@ -18,7 +24,11 @@ use super::popup_paragraph;
 
 ///
 pub struct ConfirmPopup {
+	repo: RepoPathRef,
 	target: Option<Action>,
+	/// text the user has to type to confirm
+	required_input: Option<String>,
+	input: TextInputComponent,
 	visible: bool,
 	queue: Queue,
 	theme: SharedTheme,
@@ -41,6 +51,17 @@ impl DrawableComponent for ConfirmPopup {
 				popup_paragraph(&title, txt, &self.theme, true, true),
 				area,
 			);
+
+			if self.required_input.is_some() {
+				let mut input_area = area.inner(Margin {
+					horizontal: 1,
+					vertical: 1,
+				});
+				input_area.y += input_area.height.saturating_sub(1);
+				input_area.height = input_area.height.min(1);
+
+				self.input.draw(f, input_area)?;
+			}
 		}
 
 		Ok(())
@@ -55,7 +76,7 @@ impl Component for ConfirmPopup {
 	) -> CommandBlocking {
 		out.push(CommandInfo::new(
 			strings::commands::confirm_action(&self.key_config),
-			true,
+			self.can_confirm(),
 			self.visible,
 		));
 		out.push(CommandInfo::new(
@@ -74,6 +95,8 @@ impl Component for ConfirmPopup {
 					self.hide();
 				} else if key_match(e, self.key_config.keys.enter) {
 					self.confirm();
+				} else if self.required_input.is_some() {
+					self.input.event(ev)?;
 				}
 
 				return Ok(EventState::Consumed);
@@ -89,10 +112,12 @@ impl Component for ConfirmPopup {
 
 	fn hide(&mut self) {
 		self.visible = false;
+		self.input.hide();
 	}
 
 	fn show(&mut self) -> Result<()> {
 		self.visible = true;
+		self.input.show()?;
 
 		Ok(())
 	}
@@ -101,8 +126,15 @@ impl Component for ConfirmPopup {
 impl ConfirmPopup {
 	///
 	pub fn new(env: &Environment) -> Self {
+		let mut input = TextInputComponent::new(env, "", "", false)
+			.with_input_type(InputType::Singleline);
+		input.embed();
+
 		Self {
+			repo: env.repo.clone(),
 			target: None,
+			required_input: None,
+			input,
 			visible: false,
 			queue: env.queue.clone(),
 			theme: env.theme.clone(),
@@ -111,13 +143,23 @@ impl ConfirmPopup {
 	}
 	///
 	pub fn open(&mut self, a: Action) -> Result<()> {
+		self.required_input = self.required_input_for(&a);
+		if let Some(required) = &self.required_input {
+			self.input
+				.set_default_msg(strings::type_to_confirm(required));
+		}
 		self.target = Some(a);
 		self.show()?;
+		self.input.set_text(String::new());
 
 		Ok(())
 	}
 	///
 	pub fn confirm(&mut self) {
+		if !self.can_confirm() {
+			return;
+		}
+
 		if let Some(a) = self.target.take() {
 			self.queue.push(InternalEvent::ConfirmedAction(a));
 		}
@@ -125,96 +167,167 @@ impl ConfirmPopup {
 		self.hide();
 	}
 
+	fn can_confirm(&self) -> bool {
+		self.required_input.as_ref().map_or(true, |required| {
+			self.input.get_text() == required
+		})
+	}
+
+	/// pushing to a protected branch needs its name typed out
+	fn required_input_for(&self, a: &Action) -> Option<String> {
+		let is_protected = |branch: &String| {
+			sync::is_branch_protected(&self.repo.borrow(), branch)
+				.unwrap_or_default()
+		};
+
+		match a {
+			Action::PushProtected(branch, _) => Some(branch.clone()),
+			Action::PushStack(branches) => {
+				branches.iter().find(|b| is_protected(b)).cloned()
+			}
+			_ => None,
+		}
+	}
+
+	#[allow(clippy::too_many_lines)]
 	fn get_text(&self) -> (String, String) {
 		if let Some(ref a) = self.target {
 			return match a {
-                Action::Reset(_) => (
-                    strings::confirm_title_reset(),
-                    strings::confirm_msg_reset(),
-                ),
-                Action::StashDrop(ids) => (
-                    strings::confirm_title_stashdrop(
-                        &self.key_config,ids.len()>1
-                    ),
-                    strings::confirm_msg_stashdrop(&self.key_config,ids),
-                ),
-                Action::StashPop(_) => (
-                    strings::confirm_title_stashpop(&self.key_config),
-                    strings::confirm_msg_stashpop(&self.key_config),
-                ),
-                Action::ResetHunk(_, _) => (
-                    strings::confirm_title_reset(),
-                    strings::confirm_msg_resethunk(&self.key_config),
-                ),
-                Action::ResetLines(_, lines) => (
-                    strings::confirm_title_reset(),
-                    strings::confirm_msg_reset_lines(lines.len()),
-                ),
-                Action::DeleteLocalBranch(branch_ref) => (
-                    strings::confirm_title_delete_branch(
-                        &self.key_config,
-                    ),
-                    strings::confirm_msg_delete_branch(
-                        &self.key_config,
-                        branch_ref,
-                    ),
-                ),
-                Action::DeleteRemoteBranch(branch_ref) => (
-                    strings::confirm_title_delete_remote_branch(
-                        &self.key_config,
-                    ),
-                    strings::confirm_msg_delete_remote_branch(
-                        &self.key_config,
-                        branch_ref,
-                    ),
-                ),
-		Action::DeleteRemote(remote_name)=>(
-			strings::confirm_title_delete_remote(&self.key_config),
-			strings::confirm_msg_delete_remote(&self.key_config,remote_name),
-		),
-                Action::DeleteTag(tag_name) => (
-                    strings::confirm_title_delete_tag(
-                        &self.key_config,
-                    ),
-                    strings::confirm_msg_delete_tag(
-                        &self.key_config,
-                        tag_name,
-                    ),
-                ),
-				Action::DeleteRemoteTag(_tag_name,remote) => (
-                    strings::confirm_title_delete_tag_remote(),
-                    strings::confirm_msg_delete_tag_remote(remote),
-                ),
-                Action::ForcePush(branch, _force) => (
-                    strings::confirm_title_force_push(
-                        &self.key_config,
-                    ),
-                    strings::confirm_msg_force_push(
-                        &self.key_config,
-                        branch.rsplit('/').next().expect("There was no / in the head reference which is impossible in git"),
-                    ),
-                ),
-                Action::PullMerge{incoming,rebase} => (
-                    strings::confirm_title_merge(&self.key_config,*rebase),
-                    strings::confirm_msg_merge(&self.key_config,*incoming,*rebase),
-                ),
-                Action::AbortMerge => (
-                    strings::confirm_title_abortmerge(),
-                    strings::confirm_msg_revertchanges(),
-                ),
+				Action::Reset(_) => (
+					strings::confirm_title_reset(),
+					strings::confirm_msg_reset(),
+				),
+				Action::DiscardSubmodule(path) => (
+					strings::confirm_title_discard_submodule(),
+					strings::confirm_msg_discard_submodule(path),
+				),
+				Action::StashDrop(ids) => (
+					strings::confirm_title_stashdrop(
+						&self.key_config,
+						ids.len() > 1,
+					),
+					strings::confirm_msg_stashdrop(
+						&self.key_config,
+						ids,
+					),
+				),
+				Action::StashPop(_) => (
+					strings::confirm_title_stashpop(&self.key_config),
+					strings::confirm_msg_stashpop(&self.key_config),
+				),
+				Action::ResetHunk(_, _) => (
+					strings::confirm_title_reset(),
+					strings::confirm_msg_resethunk(&self.key_config),
+				),
+				Action::ResetLines(_, lines) => (
+					strings::confirm_title_reset(),
+					strings::confirm_msg_reset_lines(lines.len()),
+				),
+				Action::DeleteLocalBranch(branch_ref) => (
+					strings::confirm_title_delete_branch(
+						&self.key_config,
+					),
+					strings::confirm_msg_delete_branch(
+						&self.key_config,
+						branch_ref,
+					),
+				),
+				Action::DeleteRemoteBranch(branch_ref) => (
+					strings::confirm_title_delete_remote_branch(
+						&self.key_config,
+					),
+					strings::confirm_msg_delete_remote_branch(
+						&self.key_config,
+						branch_ref,
+					),
+				),
+				Action::DeleteRemote(remote_name) => (
+					strings::confirm_title_delete_remote(
+						&self.key_config,
+					),
+					strings::confirm_msg_delete_remote(
+						&self.key_config,
+						remote_name,
+					),
+				),
+				Action::DeleteTag(tag_name) => (
+					strings::confirm_title_delete_tag(
+						&self.key_config,
+					),
+					strings::confirm_msg_delete_tag(
+						&self.key_config,
+						tag_name,
+					),
+				),
+				Action::DeleteRemoteTag(_tag_name, remote) => (
+					strings::confirm_title_delete_tag_remote(),
+					strings::confirm_msg_delete_tag_remote(remote),
+				),
+				Action::PushProtected(branch, _) => (
+					strings::confirm_title_push_protected(),
+					strings::confirm_msg_push_protected(branch),
+				),
+				Action::PushStack(branches) => (
+					strings::confirm_title_push_stack(
+						&self.key_config,
+					),
+					strings::confirm_msg_push_stack(
+						&self.key_config,
+						branches,
+					),
+				),
+				Action::PullMerge { incoming, rebase } => (
+					strings::confirm_title_merge(
+						&self.key_config,
+						*rebase,
+					),
+					strings::confirm_msg_merge(
+						&self.key_config,
+						*incoming,
+						*rebase,
+					),
+				),
+				Action::AbortMerge => (
+					strings::confirm_title_abortmerge(),
+					strings::confirm_msg_revertchanges(),
+				),
 				Action::AbortRebase => (
-                    strings::confirm_title_abortrebase(),
-                    strings::confirm_msg_abortrebase(),
-                ),
+					strings::confirm_title_abortrebase(),
+					strings::confirm_msg_abortrebase(),
+				),
 				Action::AbortRevert => (
-                    strings::confirm_title_abortrevert(),
-                    strings::confirm_msg_revertchanges(),
-                ),
-                Action::UndoCommit => (
-                    strings::confirm_title_undo_commit(),
-                    strings::confirm_msg_undo_commit(),
-                ),
-            };
+					strings::confirm_title_abortrevert(),
+					strings::confirm_msg_revertchanges(),
+				),
+				Action::AbortApplyMailbox => (
+					strings::confirm_title_abort_apply_mailbox(),
+					strings::confirm_msg_abort_apply_mailbox(),
+				),
+				Action::AbortCherryPick => (
+					strings::confirm_title_abort_cherry_pick(),
+					strings::confirm_msg_abort_cherry_pick(),
+				),
+				Action::UndoCommit => (
+					strings::confirm_title_undo_commit(),
+					strings::confirm_msg_undo_commit(),
+				),
+				Action::RestoreDiscarded(_, path) => (
+					strings::confirm_title_restore_discarded(),
+					strings::confirm_msg_restore_discarded(path),
+				),
+				Action::Clean { paths, backup } => (
+					strings::confirm_title_clean(),
+					strings::confirm_msg_clean(paths.len(), *backup),
+				),
+				Action::UndoRestore(_, operation) => (
+					strings::confirm_title_undo_restore(),
+					strings::confirm_msg_undo_restore(operation),
+				),
+				Action::RewordPushedCommit(_) => (
+					strings::confirm_title_reword_pushed(),
+					strings::confirm_msg_reword_pushed(),
+				),
+			};
 		}
 
 		(String::new(), String::new())
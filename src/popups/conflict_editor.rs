@@ -0,0 +1,432 @@
+use crate::{
+	app::Environment,
+	components::{
+		visibility_blocking, CommandBlocking, CommandInfo, Component,
+		DrawableComponent, EventState, InputType, TextInputComponent,
+	},
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, NeedsUpdate, Queue},
+	strings, try_or_popup,
+	ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::sync::{
+	self, ConflictChunk, ConflictFile, ConflictRegion,
+	ConflictResolution, RepoPathRef,
+};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Constraint, Direction, Layout, Margin, Rect},
+	text::{Line, Span},
+	widgets::{Block, BorderType, Borders, Clear, Paragraph},
+	Frame,
+};
+
+/// shows base, ours and theirs of every conflict region of a file and
+/// resolves them one by one, the merged result is written and staged
+pub struct ConflictEditorPopup {
+	repo: RepoPathRef,
+	queue: Queue,
+	visible: bool,
+	file: Option<ConflictFile>,
+	/// index into `ConflictFile::conflicts`
+	selection: usize,
+	scroll: u16,
+	input: TextInputComponent,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl ConflictEditorPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			repo: env.repo.clone(),
+			queue: env.queue.clone(),
+			visible: false,
+			file: None,
+			selection: 0,
+			scroll: 0,
+			input: TextInputComponent::new(
+				env,
+				strings::POPUP_TITLE_CONFLICT_EDIT,
+				"",
+				true,
+			)
+			.with_input_type(InputType::Multiline),
+			theme: env.theme.clone(),
+			key_config: env.key_config.clone(),
+		}
+	}
+
+	///
+	pub fn open(&mut self, path: &str) -> Result<()> {
+		self.file =
+			Some(sync::get_conflict_file(&self.repo.borrow(), path)?);
+		self.selection = 0;
+		self.scroll = 0;
+
+		self.show()
+	}
+
+	fn conflicts(&self) -> Vec<usize> {
+		self.file
+			.as_ref()
+			.map(ConflictFile::conflicts)
+			.unwrap_or_default()
+	}
+
+	fn region(&self) -> Option<&ConflictRegion> {
+		let idx = *self.conflicts().get(self.selection)?;
+
+		match self.file.as_ref()?.chunks.get(idx)? {
+			ConflictChunk::Conflict(region) => Some(region),
+			ConflictChunk::Merged(_) => None,
+		}
+	}
+
+	fn region_mut(&mut self) -> Option<&mut ConflictRegion> {
+		let idx = *self.conflicts().get(self.selection)?;
+
+		match self.file.as_mut()?.chunks.get_mut(idx)? {
+			ConflictChunk::Conflict(region) => Some(region),
+			ConflictChunk::Merged(_) => None,
+		}
+	}
+
+	fn move_selection(&mut self, up: bool) {
+		let max = self.conflicts().len().saturating_sub(1);
+
+		self.selection = if up {
+			self.selection.saturating_sub(1)
+		} else {
+			self.selection.saturating_add(1).min(max)
+		};
+		self.scroll = 0;
+	}
+
+	/// resolves the selected region and moves on to the next one
+	fn resolve(&mut self, resolution: ConflictResolution) {
+		if let Some(region) = self.region_mut() {
+			region.resolution = Some(resolution);
+			self.move_selection(false);
+		}
+	}
+
+	fn open_edit(&mut self) -> Result<()> {
+		if let Some(region) = self.region() {
+			let text = region
+				.resolved()
+				.unwrap_or_else(|| region.ours.clone());
+
+			self.input.set_text(text);
+			self.input.show()?;
+		}
+
+		Ok(())
+	}
+
+	fn confirm_edit(&mut self) {
+		let mut text = self.input.get_text().to_string();
+		self.input.hide();
+
+		if !text.is_empty() && !text.ends_with('\n') {
+			text.push('\n');
+		}
+
+		self.resolve(ConflictResolution::Edited(text));
+	}
+
+	fn can_write(&self) -> bool {
+		self.file
+			.as_ref()
+			.is_some_and(|file| file.unresolved() == 0)
+	}
+
+	fn write(&mut self) -> Result<()> {
+		if let Some(file) = &self.file {
+			if let Some(merged) = file.merged() {
+				sync::resolve_conflict_file(
+					&self.repo.borrow(),
+					&file.path,
+					&merged,
+				)?;
+
+				self.hide();
+				self.queue
+					.push(InternalEvent::Update(NeedsUpdate::ALL));
+			}
+		}
+
+		Ok(())
+	}
+
+	fn draw_pane(
+		&self,
+		f: &mut Frame,
+		area: Rect,
+		title: &str,
+		text: Option<&str>,
+		placeholder: &str,
+	) {
+		let lines = text.map_or_else(
+			|| {
+				vec![Line::styled(
+					placeholder.to_string(),
+					self.theme.text(false, false),
+				)]
+			},
+			|text| {
+				text.lines()
+					.map(|line| {
+						Line::styled(
+							line.replace('\t', "  "),
+							self.theme.text(true, false),
+						)
+					})
+					.collect()
+			},
+		);
+
+		f.render_widget(
+			Paragraph::new(lines).scroll((self.scroll, 0)).block(
+				Block::default()
+					.title(Span::styled(
+						title.to_string(),
+						self.theme.title(true),
+					))
+					.borders(Borders::ALL)
+					.border_style(self.theme.block(false)),
+			),
+			area,
+		);
+	}
+}
+
+impl DrawableComponent for ConflictEditorPopup {
+	fn draw(&self, f: &mut Frame, _rect: Rect) -> Result<()> {
+		if !self.visible {
+			return Ok(());
+		}
+
+		let Some(file) = &self.file else {
+			return Ok(());
+		};
+
+		let area = ui::centered_rect(90, 80, f.area());
+
+		f.render_widget(Clear, area);
+		f.render_widget(
+			Block::default()
+				.title(Span::styled(
+					strings::conflict_editor_title(
+						&file.path,
+						file.unresolved(),
+					),
+					self.theme.title(true),
+				))
+				.borders(Borders::ALL)
+				.border_type(BorderType::Thick)
+				.border_style(self.theme.block(true)),
+			area,
+		);
+
+		let inner = area.inner(Margin {
+			horizontal: 1,
+			vertical: 1,
+		});
+
+		let [status, sides, result] = *Layout::default()
+			.direction(Direction::Vertical)
+			.constraints([
+				Constraint::Length(1),
+				Constraint::Percentage(60),
+				Constraint::Min(3),
+			])
+			.split(inner)
+		else {
+			return Ok(());
+		};
+
+		let region = self.region();
+
+		f.render_widget(
+			Paragraph::new(Line::styled(
+				strings::conflict_region(
+					self.selection,
+					self.conflicts().len(),
+					region.and_then(|region| {
+						region.resolution.as_ref()
+					}),
+				),
+				self.theme.text(true, false),
+			)),
+			status,
+		);
+
+		let columns = Layout::default()
+			.direction(Direction::Horizontal)
+			.constraints([
+				Constraint::Ratio(1, 3),
+				Constraint::Ratio(1, 3),
+				Constraint::Ratio(1, 3),
+			])
+			.split(sides);
+
+		if let Some(region) = region {
+			self.draw_pane(
+				f,
+				columns[0],
+				strings::CONFLICT_OURS,
+				Some(&region.ours),
+				"",
+			);
+			self.draw_pane(
+				f,
+				columns[1],
+				strings::CONFLICT_BASE,
+				region.base.as_deref(),
+				strings::CONFLICT_NO_BASE,
+			);
+			self.draw_pane(
+				f,
+				columns[2],
+				strings::CONFLICT_THEIRS,
+				Some(&region.theirs),
+				"",
+			);
+
+			let resolved = region.resolved();
+			self.draw_pane(
+				f,
+				result,
+				strings::CONFLICT_RESULT,
+				resolved.as_deref(),
+				"",
+			);
+		}
+
+		self.input.draw(f, area)?;
+
+		Ok(())
+	}
+}
+
+impl Component for ConflictEditorPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.input.is_visible() {
+			self.input.commands(out, force_all);
+
+			out.push(CommandInfo::new(
+				strings::commands::conflict_edit_confirm(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
+		} else if self.visible || force_all {
+			let selected = self.region().is_some();
+
+			out.push(CommandInfo::new(
+				strings::commands::scroll(&self.key_config),
+				selected,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::conflict_take(&self.key_config),
+				selected,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::conflict_edit(&self.key_config),
+				selected,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::conflict_write(&self.key_config),
+				self.can_write(),
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::close_popup(&self.key_config),
+				true,
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.visible {
+			if self.input.is_visible() {
+				if let Event::Key(e) = ev {
+					if key_match(e, self.key_config.keys.commit) {
+						self.confirm_edit();
+						return Ok(EventState::Consumed);
+					}
+				}
+
+				self.input.event(ev)?;
+
+				return Ok(EventState::Consumed);
+			}
+
+			if let Event::Key(e) = ev {
+				let keys = &self.key_config.keys;
+
+				if key_match(e, keys.exit_popup) {
+					self.hide();
+				} else if key_match(e, keys.move_up) {
+					self.move_selection(true);
+				} else if key_match(e, keys.move_down) {
+					self.move_selection(false);
+				} else if key_match(e, keys.page_up) {
+					self.scroll = self.scroll.saturating_sub(5);
+				} else if key_match(e, keys.page_down) {
+					self.scroll = self.scroll.saturating_add(5);
+				} else if key_match(e, keys.conflict_take_ours) {
+					self.resolve(ConflictResolution::Ours);
+				} else if key_match(e, keys.conflict_take_theirs) {
+					self.resolve(ConflictResolution::Theirs);
+				} else if key_match(e, keys.conflict_take_both) {
+					self.resolve(ConflictResolution::Both);
+				} else if key_match(e, keys.conflict_take_base) {
+					self.resolve(ConflictResolution::Base);
+				} else if key_match(e, keys.conflict_edit) {
+					self.open_edit()?;
+				} else if key_match(e, keys.enter) && self.can_write()
+				{
+					try_or_popup!(
+						self,
+						"resolve conflict error:",
+						self.write()
+					);
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+		self.input.hide();
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
@@ -10,14 +10,25 @@ use crate::{
 	ui::style::SharedTheme,
 };
 use anyhow::Result;
-use asyncgit::sync::{self, RepoPathRef};
+use asyncgit::sync::{self, CommitId, RepoPathRef};
 use crossterm::event::Event;
 use easy_cast::Cast;
-use ratatui::{layout::Rect, widgets::Paragraph, Frame};
+use ratatui::{
+	layout::Rect,
+	text::{Line, Span},
+	widgets::Paragraph,
+	Frame,
+};
 
 pub struct CreateBranchPopup {
 	repo: RepoPathRef,
 	input: TextInputComponent,
+	/// start a branch without history
+	orphan: bool,
+	/// remove tracked files when creating an orphan branch
+	clear_files: bool,
+	/// create the branch at this commit without checking it out
+	start: Option<CommitId>,
 	queue: Queue,
 	key_config: SharedKeyConfig,
 	theme: SharedTheme,
@@ -27,6 +38,7 @@ impl DrawableComponent for CreateBranchPopup {
 	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
 		if self.is_visible() {
 			self.input.draw(f, rect)?;
+			self.draw_options(f);
 			self.draw_warnings(f);
 		}
 
@@ -43,6 +55,21 @@ impl Component for CreateBranchPopup {
 		if self.is_visible() || force_all {
 			self.input.commands(out, force_all);
 
+			out.push(CommandInfo::new(
+				strings::commands::create_branch_toggle_orphan(
+					&self.key_config,
+				),
+				self.start.is_none(),
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::create_branch_toggle_orphan_clear(
+					&self.key_config,
+				),
+				self.orphan,
+				true,
+			));
+
 			out.push(CommandInfo::new(
 				strings::commands::create_branch_confirm_msg(
 					&self.key_config,
@@ -57,6 +84,24 @@ impl Component for CreateBranchPopup {
 
 	fn event(&mut self, ev: &Event) -> Result<EventState> {
 		if self.is_visible() {
+			if let Event::Key(e) = ev {
+				if key_match(
+					e,
+					self.key_config.keys.create_branch_orphan,
+				) && self.start.is_none()
+				{
+					self.orphan = !self.orphan;
+					return Ok(EventState::Consumed);
+				} else if key_match(
+					e,
+					self.key_config.keys.create_branch_orphan_clear,
+				) && self.orphan
+				{
+					self.clear_files = !self.clear_files;
+					return Ok(EventState::Consumed);
+				}
+			}
+
 			if self.input.event(ev)?.is_consumed() {
 				return Ok(EventState::Consumed);
 			}
@@ -99,6 +144,9 @@ impl CreateBranchPopup {
 				true,
 			)
 			.with_input_type(InputType::Singleline),
+			orphan: false,
+			clear_files: false,
+			start: None,
 			theme: env.theme.clone(),
 			key_config: env.key_config.clone(),
 			repo: env.repo.clone(),
@@ -107,17 +155,47 @@ impl CreateBranchPopup {
 
 	///
 	pub fn open(&mut self) -> Result<()> {
+		self.orphan = false;
+		self.clear_files = false;
+		self.start = None;
+		self.input.set_title(strings::create_branch_popup_title(
+			&self.key_config,
+		));
 		self.show()?;
 
 		Ok(())
 	}
 
+	/// branch off `commit` instead of `HEAD`
+	pub fn open_at(&mut self, commit: CommitId) -> Result<()> {
+		self.open()?;
+		self.start = Some(commit);
+		self.input
+			.set_title(strings::create_branch_at_popup_title(commit));
+
+		Ok(())
+	}
+
 	///
 	pub fn create_branch(&mut self) {
-		let res = sync::create_branch(
-			&self.repo.borrow(),
-			self.input.get_text(),
-		);
+		let res = if self.orphan {
+			sync::create_orphan_branch(
+				&self.repo.borrow(),
+				self.input.get_text(),
+				self.clear_files,
+			)
+		} else if let Some(commit) = self.start {
+			sync::create_branch_at(
+				&self.repo.borrow(),
+				self.input.get_text(),
+				commit,
+			)
+		} else {
+			sync::create_branch(
+				&self.repo.borrow(),
+				self.input.get_text(),
+			)
+		};
 
 		self.input.clear();
 		self.hide();
@@ -137,6 +215,34 @@ impl CreateBranchPopup {
 		}
 	}
 
+	fn draw_options(&self, f: &mut Frame) {
+		let option = |on: bool, label: &'static str| {
+			[
+				Span::styled(
+					if on { "[x]" } else { "[_]" },
+					self.theme.option(on),
+				),
+				Span::styled(label, self.theme.text(true, false)),
+			]
+		};
+
+		let mut spans = option(self.orphan, " orphan ").to_vec();
+		if self.orphan {
+			spans.extend(option(self.clear_files, " clear files"));
+		}
+
+		let rect = {
+			let mut rect = self.input.get_area();
+			rect.y += rect.height.saturating_sub(1);
+			rect.height = 1;
+			rect.x += 1;
+			rect.width = rect.width.saturating_sub(2);
+			rect
+		};
+
+		f.render_widget(Paragraph::new(Line::from(spans)), rect);
+	}
+
 	fn draw_warnings(&self, f: &mut Frame) {
 		let current_text = self.input.get_text();
 
@@ -1,5 +1,7 @@
 use anyhow::Result;
-use asyncgit::sync::{self, validate_remote_name, RepoPathRef};
+use asyncgit::sync::{
+	self, validate_remote_name, validate_remote_url, RepoPathRef,
+};
 use crossterm::event::Event;
 use easy_cast::Cast;
 use ratatui::{widgets::Paragraph, Frame};
@@ -33,6 +35,7 @@ pub struct CreateRemotePopup {
 	queue: Queue,
 	key_config: SharedKeyConfig,
 	state: State,
+	existing: Vec<String>,
 	theme: SharedTheme,
 }
 
@@ -122,11 +125,13 @@ impl CreateRemotePopup {
 				.with_input_type(InputType::Singleline),
 			key_config: env.key_config.clone(),
 			state: State::Name,
+			existing: Vec::new(),
 			theme: env.theme.clone(),
 		}
 	}
 
 	pub fn open(&mut self) -> Result<()> {
+		self.existing = sync::get_remotes(&self.repo.borrow())?;
 		self.state = State::Name;
 		self.input.clear();
 		self.show()?;
@@ -134,40 +139,58 @@ impl CreateRemotePopup {
 		Ok(())
 	}
 
-	fn draw_warnings(&self, f: &mut Frame) {
-		let remote_name = match self.state {
-			State::Name => self.input.get_text(),
-			State::Url { .. } => return,
-		};
+	/// problem with the current input, if any
+	fn input_problem(&self) -> Option<&'static str> {
+		let text = self.input.get_text();
 
-		if !remote_name.is_empty() {
-			let valid = validate_remote_name(remote_name);
-
-			if !valid {
-				let msg = strings::remote_name_invalid();
-				let msg_length: u16 = msg.len().cast();
-				let w = Paragraph::new(msg)
-					.style(self.theme.text_danger());
-
-				let rect = {
-					let mut rect = self.input.get_area();
-					rect.y += rect.height.saturating_sub(1);
-					rect.height = 1;
-					let offset =
-						rect.width.saturating_sub(msg_length + 1);
-					rect.width =
-						rect.width.saturating_sub(offset + 1);
-					rect.x += offset;
-
-					rect
-				};
+		if text.is_empty() {
+			return None;
+		}
 
-				f.render_widget(w, rect);
+		match self.state {
+			State::Name => {
+				if !validate_remote_name(text) {
+					Some(strings::remote_name_invalid())
+				} else if self.existing.iter().any(|r| r == text) {
+					Some(strings::remote_name_exists())
+				} else {
+					None
+				}
 			}
+			State::Url { .. } => (!validate_remote_url(text))
+				.then(strings::remote_url_invalid),
+		}
+	}
+
+	fn draw_warnings(&self, f: &mut Frame) {
+		if let Some(msg) = self.input_problem() {
+			let msg_length: u16 = msg.len().cast();
+			let w =
+				Paragraph::new(msg).style(self.theme.text_danger());
+
+			let rect = {
+				let mut rect = self.input.get_area();
+				rect.y += rect.height.saturating_sub(1);
+				rect.height = 1;
+				let offset =
+					rect.width.saturating_sub(msg_length + 1);
+				rect.width = rect.width.saturating_sub(offset + 1);
+				rect.x += offset;
+
+				rect
+			};
+
+			f.render_widget(w, rect);
 		}
 	}
 
 	fn handle_submit(&mut self) {
+		if self.input.get_text().is_empty()
+			|| self.input_problem().is_some()
+		{
+			return;
+		}
+
 		match &self.state {
 			State::Name => {
 				self.state = State::Url {
@@ -0,0 +1,225 @@
+use crate::{
+	app::Environment,
+	components::{
+		time_to_string, visibility_blocking, CommandBlocking,
+		CommandInfo, Component, DrawableComponent, EventState,
+	},
+	keys::{key_match, SharedKeyConfig},
+	queue::{Action, InternalEvent, Queue},
+	strings,
+	ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::sync::{self, DiscardBackup, RepoPathRef};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Margin, Rect},
+	text::{Line, Span, Text},
+	widgets::{Block, BorderType, Borders, Clear, Paragraph},
+	Frame,
+};
+
+/// lists backups of recently discarded changes to restore them
+pub struct DiscardedPopup {
+	repo: RepoPathRef,
+	queue: Queue,
+	visible: bool,
+	backups: Vec<DiscardBackup>,
+	selection: usize,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl DiscardedPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			repo: env.repo.clone(),
+			queue: env.queue.clone(),
+			visible: false,
+			backups: Vec::new(),
+			selection: 0,
+			theme: env.theme.clone(),
+			key_config: env.key_config.clone(),
+		}
+	}
+
+	///
+	pub fn open(&mut self) -> Result<()> {
+		self.backups = sync::discard_backups(&self.repo.borrow())?;
+		self.selection = 0;
+
+		self.show()
+	}
+
+	fn move_selection(&mut self, up: bool) {
+		let max = self.backups.len().saturating_sub(1);
+
+		self.selection = if up {
+			self.selection.saturating_sub(1)
+		} else {
+			self.selection.saturating_add(1).min(max)
+		};
+	}
+
+	fn selected(&self) -> Option<&DiscardBackup> {
+		self.backups.get(self.selection)
+	}
+
+	fn text(&self) -> Text<'_> {
+		if self.backups.is_empty() {
+			return Text::from(Span::styled(
+				strings::DISCARDED_EMPTY,
+				self.theme.text(false, false),
+			));
+		}
+
+		Text::from(
+			self.backups
+				.iter()
+				.enumerate()
+				.map(|(idx, backup)| {
+					let selected = idx == self.selection;
+
+					Line::from(vec![
+						Span::styled(
+							if selected { "> " } else { "  " },
+							self.theme.text(true, selected),
+						),
+						Span::styled(
+							format!(
+								"{} ",
+								time_to_string(backup.time, false)
+							),
+							self.theme.commit_time(selected),
+						),
+						Span::styled(
+							backup.description.as_str(),
+							self.theme.text(true, selected),
+						),
+					])
+				})
+				.collect::<Vec<_>>(),
+		)
+	}
+}
+
+impl DrawableComponent for DiscardedPopup {
+	fn draw(&self, f: &mut Frame, _rect: Rect) -> Result<()> {
+		if !self.visible {
+			return Ok(());
+		}
+
+		let area = ui::centered_rect(60, 50, f.area());
+
+		f.render_widget(Clear, area);
+		f.render_widget(
+			Block::default()
+				.title(Span::styled(
+					strings::discarded_title(),
+					self.theme.title(true),
+				))
+				.borders(Borders::ALL)
+				.border_type(BorderType::Thick)
+				.border_style(self.theme.block(true)),
+			area,
+		);
+
+		let inner = area.inner(Margin {
+			horizontal: 1,
+			vertical: 1,
+		});
+
+		let scroll = self.selection.saturating_sub(
+			usize::from(inner.height).saturating_sub(1),
+		);
+
+		f.render_widget(
+			Paragraph::new(self.text()).scroll((
+				u16::try_from(scroll).unwrap_or(u16::MAX),
+				0,
+			)),
+			inner,
+		);
+
+		Ok(())
+	}
+}
+
+impl Component for DiscardedPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.visible || force_all {
+			let selected = self.selected().is_some();
+
+			out.push(CommandInfo::new(
+				strings::commands::scroll(&self.key_config),
+				selected,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::discarded_restore(
+					&self.key_config,
+				),
+				selected,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::close_popup(&self.key_config),
+				true,
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.visible {
+			if let Event::Key(e) = ev {
+				let keys = &self.key_config.keys;
+
+				if key_match(e, keys.exit_popup) {
+					self.hide();
+				} else if key_match(e, keys.move_up) {
+					self.move_selection(true);
+				} else if key_match(e, keys.move_down) {
+					self.move_selection(false);
+				} else if key_match(e, keys.enter) {
+					if let Some(backup) = self.selected() {
+						self.queue.push(
+							InternalEvent::ConfirmAction(
+								Action::RestoreDiscarded(
+									backup.id,
+									backup.path.clone(),
+								),
+							),
+						);
+						self.hide();
+					}
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
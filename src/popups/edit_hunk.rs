@@ -0,0 +1,147 @@
+use crate::{
+	app::Environment,
+	components::{
+		visibility_blocking, CommandBlocking, CommandInfo, Component,
+		DrawableComponent, EventState, InputType, TextInputComponent,
+	},
+	keys::{key_match, SharedKeyConfig},
+	options::SharedOptions,
+	queue::{InternalEvent, NeedsUpdate, Queue},
+	strings, try_or_popup,
+};
+use anyhow::Result;
+use asyncgit::sync::{self, RepoPathRef};
+use crossterm::event::Event;
+use ratatui::{layout::Rect, Frame};
+
+/// edits an unstaged hunk as patch text and stages the result, like
+/// `e` in `git add -p`
+pub struct EditHunkPopup {
+	repo: RepoPathRef,
+	queue: Queue,
+	options: SharedOptions,
+	input: TextInputComponent,
+	file_path: Option<String>,
+	key_config: SharedKeyConfig,
+}
+
+impl EditHunkPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			repo: env.repo.clone(),
+			queue: env.queue.clone(),
+			options: env.options.clone(),
+			input: TextInputComponent::new(
+				env,
+				strings::POPUP_TITLE_EDIT_HUNK,
+				"",
+				true,
+			)
+			.with_input_type(InputType::Multiline),
+			file_path: None,
+			key_config: env.key_config.clone(),
+		}
+	}
+
+	///
+	pub fn open(
+		&mut self,
+		file_path: String,
+		hunk: u64,
+	) -> Result<()> {
+		let patch = sync::get_hunk_patch(
+			&self.repo.borrow(),
+			&file_path,
+			hunk,
+			Some(self.options.borrow().diff_options()),
+		)?;
+
+		self.input
+			.set_text(format!("{patch}{}", strings::EDIT_HUNK_HELP));
+		self.file_path = Some(file_path);
+
+		self.show()
+	}
+
+	fn stage(&mut self) -> Result<()> {
+		if let Some(file_path) = &self.file_path {
+			sync::stage_edited_hunk(
+				&self.repo.borrow(),
+				file_path,
+				self.input.get_text(),
+			)?;
+
+			self.hide();
+			self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+		}
+
+		Ok(())
+	}
+}
+
+impl DrawableComponent for EditHunkPopup {
+	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
+		if self.is_visible() {
+			self.input.draw(f, rect)?;
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for EditHunkPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			self.input.commands(out, force_all);
+
+			out.push(CommandInfo::new(
+				strings::commands::edit_hunk_confirm(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.is_visible() {
+			if let Event::Key(e) = ev {
+				if key_match(e, self.key_config.keys.commit) {
+					try_or_popup!(
+						self,
+						"stage edited hunk error:",
+						self.stage()
+					);
+
+					return Ok(EventState::Consumed);
+				}
+			}
+
+			self.input.event(ev)?;
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.input.is_visible()
+	}
+
+	fn hide(&mut self) {
+		self.input.hide();
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.input.show()
+	}
+}
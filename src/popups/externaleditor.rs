@@ -5,16 +5,18 @@ use crate::{
 		DrawableComponent, EventState,
 	},
 	keys::SharedKeyConfig,
+	queue::ExternalTool,
 	strings,
 	ui::{self, style::SharedTheme},
 };
 use anyhow::{anyhow, bail, Result};
 use asyncgit::sync::{
-	get_config_string, utils::repo_work_dir, RepoPath,
+	difftool_command, get_config_string, mergetool_command,
+	utils::repo_work_dir, RepoPath,
 };
 use crossterm::{
 	event::Event,
-	terminal::{EnterAlternateScreen, LeaveAlternateScreen},
+	terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
 	ExecutableCommand,
 };
 use ratatui::{
@@ -117,6 +119,38 @@ impl ExternalEditorPopup {
 
 		Ok(())
 	}
+
+	/// runs `tool` on the plain terminal until it exits
+	pub fn open_tool(
+		repo: &RepoPath,
+		tool: &ExternalTool,
+	) -> Result<()> {
+		let (name, mut command) = match tool {
+			ExternalTool::Diff(path, target) => {
+				("difftool", difftool_command(repo, path, target)?)
+			}
+			ExternalTool::Merge(path) => {
+				("mergetool", mergetool_command(repo, path)?)
+			}
+		};
+
+		io::stdout().execute(LeaveAlternateScreen)?;
+		terminal::disable_raw_mode()?;
+		defer! {
+			terminal::enable_raw_mode().expect("reset terminal");
+			io::stdout().execute(EnterAlternateScreen).expect("reset terminal");
+		}
+
+		let status = command
+			.status()
+			.map_err(|e| anyhow!("git {}: {}", name, e))?;
+
+		if !status.success() {
+			bail!("git {name} failed: {status}");
+		}
+
+		Ok(())
+	}
 }
 
 impl DrawableComponent for ExternalEditorPopup {
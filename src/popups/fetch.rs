@@ -4,7 +4,8 @@ use crate::{
 		visibility_blocking, CommandBlocking, CommandInfo, Component,
 		CredComponent, DrawableComponent, EventState,
 	},
-	keys::SharedKeyConfig,
+	keys::{key_match, SharedKeyConfig},
+	popups::PushPopup,
 	queue::{InternalEvent, NeedsUpdate, Queue},
 	strings,
 	ui::{self, style::SharedTheme},
@@ -13,13 +14,14 @@ use anyhow::Result;
 use asyncgit::{
 	asyncjob::AsyncSingleJob,
 	sync::{
+		self,
 		cred::{
-			extract_username_password, need_username_password,
-			BasicAuthCredential,
+			credential_url, extract_username_password,
+			need_username_password, BasicAuthCredential,
 		},
-		RepoPathRef,
+		need_ssh_passphrase, RemoteFetchOptions, RepoPathRef,
 	},
-	AsyncFetchJob, AsyncGitNotification, ProgressPercent,
+	AsyncFetchJob, AsyncGitNotification, RemoteProgress,
 };
 use crossterm::event::Event;
 use ratatui::{
@@ -34,8 +36,11 @@ pub struct FetchPopup {
 	repo: RepoPathRef,
 	visible: bool,
 	async_fetch: AsyncSingleJob<AsyncFetchJob>,
-	progress: Option<ProgressPercent>,
+	/// shares its state with the spawned job
+	job: Option<AsyncFetchJob>,
+	progress: Option<RemoteProgress>,
 	pending: bool,
+	options: RemoteFetchOptions,
 	queue: Queue,
 	theme: SharedTheme,
 	key_config: SharedKeyConfig,
@@ -48,8 +53,10 @@ impl FetchPopup {
 		Self {
 			queue: env.queue.clone(),
 			pending: false,
+			options: RemoteFetchOptions::default(),
 			visible: false,
 			async_fetch: AsyncSingleJob::new(env.sender_git.clone()),
+			job: None,
 			progress: None,
 			input_cred: CredComponent::new(env),
 			theme: env.theme.clone(),
@@ -58,8 +65,16 @@ impl FetchPopup {
 		}
 	}
 
-	///
-	pub fn fetch(&mut self) -> Result<()> {
+	/// fetches using the configured options unless `options` are
+	/// given
+	pub fn fetch(
+		&mut self,
+		options: Option<RemoteFetchOptions>,
+	) -> Result<()> {
+		self.options = match options {
+			Some(options) => options,
+			None => sync::fetch_options_config(&self.repo.borrow())?,
+		};
 		self.show()?;
 		if need_username_password(&self.repo.borrow())? {
 			let cred = extract_username_password(&self.repo.borrow())
@@ -69,9 +84,21 @@ impl FetchPopup {
 			if cred.is_complete() {
 				self.fetch_all(Some(cred));
 			} else {
-				self.input_cred.set_cred(cred);
+				self.input_cred.set_cred(
+					cred,
+					credential_url(&self.repo.borrow())
+						.unwrap_or_default(),
+				);
 				self.input_cred.show()?;
 			}
+		} else if need_ssh_passphrase(
+			&self.repo.borrow(),
+			&self.remote()?,
+		)
+		.unwrap_or_default()
+		{
+			self.input_cred.set_passphrase();
+			self.input_cred.show()?;
 		} else {
 			self.fetch_all(None);
 		}
@@ -79,14 +106,31 @@ impl FetchPopup {
 		Ok(())
 	}
 
+	/// the chosen remote or the default one when fetching all
+	fn remote(&self) -> Result<String> {
+		match &self.options.remote {
+			Some(remote) => Ok(remote.clone()),
+			None => {
+				Ok(sync::get_default_remote(&self.repo.borrow())?)
+			}
+		}
+	}
+
 	fn fetch_all(&mut self, cred: Option<BasicAuthCredential>) {
 		self.pending = true;
 		self.progress = None;
-		self.progress = Some(ProgressPercent::empty());
-		self.async_fetch.spawn(AsyncFetchJob::new(
+
+		let job = AsyncFetchJob::new(
 			self.repo.borrow().clone(),
+			self.options.clone(),
 			cred,
-		));
+		);
+		self.job = Some(job.clone());
+		self.async_fetch.spawn(job);
+	}
+
+	fn is_cancelled(&self) -> bool {
+		self.job.as_ref().is_some_and(AsyncFetchJob::is_cancelled)
 	}
 
 	///
@@ -104,12 +148,39 @@ impl FetchPopup {
 	///
 	fn update(&mut self) {
 		self.pending = self.async_fetch.is_pending();
-		self.progress = self.async_fetch.progress();
+		self.progress =
+			self.job.as_ref().and_then(AsyncFetchJob::progress);
 
 		if !self.pending {
+			let cancelled = self.is_cancelled();
+			self.job = None;
+
 			self.hide();
 			self.queue
 				.push(InternalEvent::Update(NeedsUpdate::BRANCHES));
+
+			match self
+				.async_fetch
+				.take_last()
+				.and_then(|job| job.result())
+			{
+				Some(Ok(summary)) if !summary.refs.is_empty() => {
+					self.queue.push(InternalEvent::ShowInfoMsg(
+						strings::fetch_summary(&summary),
+					));
+				}
+				Some(Err(_)) if cancelled => {
+					self.queue.push(InternalEvent::ShowInfoMsg(
+						strings::FETCH_CANCELLED_MSG.to_string(),
+					));
+				}
+				Some(Err(e)) => {
+					self.queue.push(InternalEvent::ShowErrorMsg(
+						format!("fetch failed:\n{e}"),
+					));
+				}
+				_ => (),
+			}
 		}
 	}
 }
@@ -117,13 +188,21 @@ impl FetchPopup {
 impl DrawableComponent for FetchPopup {
 	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
 		if self.visible {
-			let progress = self.progress.unwrap_or_default().progress;
+			let (state, progress) = PushPopup::get_progress(
+				self.progress.as_ref(),
+				self.pending && self.is_cancelled(),
+			);
 
-			let area = ui::centered_rect_absolute(30, 3, f.area());
+			let area = ui::centered_rect_absolute(
+				PushPopup::PROGRESS_WIDTH,
+				3,
+				f.area(),
+			);
 
 			f.render_widget(Clear, area);
 			f.render_widget(
 				Gauge::default()
+					.label(state.as_str())
 					.block(
 						Block::default()
 							.title(Span::styled(
@@ -159,11 +238,21 @@ impl Component for FetchPopup {
 			if self.input_cred.is_visible() {
 				return self.input_cred.commands(out, force_all);
 			}
-			out.push(CommandInfo::new(
-				strings::commands::close_msg(&self.key_config),
-				!self.pending,
-				self.visible,
-			));
+			if self.pending {
+				out.push(CommandInfo::new(
+					strings::commands::remote_progress_cancel(
+						&self.key_config,
+					),
+					!self.is_cancelled(),
+					self.visible,
+				));
+			} else {
+				out.push(CommandInfo::new(
+					strings::commands::close_msg(&self.key_config),
+					true,
+					self.visible,
+				));
+			}
 		}
 
 		visibility_blocking(self)
@@ -171,7 +260,7 @@ impl Component for FetchPopup {
 
 	fn event(&mut self, ev: &Event) -> Result<EventState> {
 		if self.visible {
-			if let Event::Key(_) = ev {
+			if let Event::Key(e) = ev {
 				if self.input_cred.is_visible() {
 					self.input_cred.event(ev)?;
 
@@ -183,6 +272,12 @@ impl Component for FetchPopup {
 						));
 						self.input_cred.hide();
 					}
+				} else if self.pending
+					&& key_match(e, self.key_config.keys.exit_popup)
+				{
+					if let Some(job) = &self.job {
+						job.cancel();
+					}
 				}
 			}
 			return Ok(EventState::Consumed);
@@ -0,0 +1,238 @@
+use crate::{
+	app::Environment,
+	components::{
+		visibility_blocking, CommandBlocking, CommandInfo, Component,
+		DrawableComponent, EventState,
+	},
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, Queue},
+	strings,
+	ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::sync::{self, RemoteFetchOptions, RepoPathRef};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Margin, Rect},
+	text::{Line, Span, Text},
+	widgets::{Block, BorderType, Borders, Clear, Paragraph},
+	Frame,
+};
+
+/// picks the remote to fetch and whether to prune and fetch tags,
+/// preselected from `fetch.prune`
+pub struct FetchOptionsPopup {
+	repo: RepoPathRef,
+	queue: Queue,
+	visible: bool,
+	remotes: Vec<String>,
+	options: RemoteFetchOptions,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl FetchOptionsPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			repo: env.repo.clone(),
+			queue: env.queue.clone(),
+			visible: false,
+			remotes: Vec::new(),
+			options: RemoteFetchOptions::default(),
+			theme: env.theme.clone(),
+			key_config: env.key_config.clone(),
+		}
+	}
+
+	///
+	pub fn open(&mut self) -> Result<()> {
+		self.remotes = sync::get_remotes(&self.repo.borrow())?;
+		self.options =
+			sync::fetch_options_config(&self.repo.borrow())?;
+
+		self.show()
+	}
+
+	/// index into `remotes` shifted by one for "all remotes"
+	fn selection(&self) -> usize {
+		self.options
+			.remote
+			.as_ref()
+			.and_then(|remote| {
+				self.remotes.iter().position(|r| r == remote)
+			})
+			.map_or(0, |idx| idx + 1)
+	}
+
+	fn move_selection(&mut self, up: bool) {
+		let idx = self.selection();
+
+		let idx = if up {
+			idx.saturating_sub(1)
+		} else {
+			idx.saturating_add(1).min(self.remotes.len())
+		};
+
+		self.options.remote = idx
+			.checked_sub(1)
+			.and_then(|idx| self.remotes.get(idx))
+			.cloned();
+	}
+
+	fn text(&self) -> Text<'_> {
+		let selection = self.selection();
+
+		let mut lines = std::iter::once(strings::FETCH_ALL_REMOTES)
+			.chain(self.remotes.iter().map(String::as_str))
+			.enumerate()
+			.map(|(idx, name)| {
+				let selected = idx == selection;
+
+				Line::from(vec![
+					Span::styled(
+						if selected { "> " } else { "  " },
+						self.theme.text(true, selected),
+					),
+					Span::styled(
+						name,
+						self.theme.text(true, selected),
+					),
+				])
+			})
+			.collect::<Vec<_>>();
+
+		lines.push(Line::default());
+		lines.push(Line::from(Span::styled(
+			strings::fetch_prune(self.options.prune),
+			self.theme.text(false, false),
+		)));
+		lines.push(Line::from(Span::styled(
+			strings::fetch_tags(self.options.tags),
+			self.theme.text(false, false),
+		)));
+
+		Text::from(lines)
+	}
+}
+
+impl DrawableComponent for FetchOptionsPopup {
+	fn draw(&self, f: &mut Frame, _rect: Rect) -> Result<()> {
+		if !self.visible {
+			return Ok(());
+		}
+
+		let height = u16::try_from(self.remotes.len())
+			.unwrap_or(u16::MAX)
+			.saturating_add(6);
+		let area = ui::centered_rect_absolute(40, height, f.area());
+
+		f.render_widget(Clear, area);
+		f.render_widget(
+			Block::default()
+				.title(Span::styled(
+					strings::FETCH_OPTIONS_TITLE,
+					self.theme.title(true),
+				))
+				.borders(Borders::ALL)
+				.border_type(BorderType::Thick)
+				.border_style(self.theme.block(true)),
+			area,
+		);
+
+		f.render_widget(
+			Paragraph::new(self.text()),
+			area.inner(Margin {
+				horizontal: 1,
+				vertical: 1,
+			}),
+		);
+
+		Ok(())
+	}
+}
+
+impl Component for FetchOptionsPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.visible || force_all {
+			out.push(CommandInfo::new(
+				strings::commands::scroll(&self.key_config),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::fetch_start(&self.key_config),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::fetch_toggle_prune(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::fetch_toggle_tags(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::close_popup(&self.key_config),
+				true,
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.visible {
+			if let Event::Key(e) = ev {
+				let keys = &self.key_config.keys;
+
+				if key_match(e, keys.exit_popup) {
+					self.hide();
+				} else if key_match(e, keys.move_up) {
+					self.move_selection(true);
+				} else if key_match(e, keys.move_down) {
+					self.move_selection(false);
+				} else if key_match(e, keys.fetch_toggle_prune) {
+					self.options.prune = !self.options.prune;
+				} else if key_match(e, keys.fetch_toggle_tags) {
+					self.options.tags = !self.options.tags;
+				} else if key_match(e, keys.enter) {
+					self.queue.push(InternalEvent::FetchRemotes(
+						Some(self.options.clone()),
+					));
+					self.hide();
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
@@ -13,13 +13,15 @@ use crate::{
 };
 use anyhow::Result;
 use asyncgit::{
+	asyncjob::AsyncSingleJob,
 	sync::{
-		diff_contains_file, get_commits_info, CommitId, RepoPathRef,
+		get_commits_info, CommitId, FileHistoryChange,
+		FileHistoryEntry, RepoPathRef,
 	},
-	AsyncDiff, AsyncGitNotification, AsyncLog, DiffParams, DiffType,
+	AsyncDiff, AsyncFileHistoryJob, AsyncGitNotification, DiffParams,
+	DiffType, FileDiff,
 };
 use chrono::{DateTime, Local};
-use crossbeam_channel::Sender;
 use crossterm::event::Event;
 use ratatui::{
 	layout::{Constraint, Direction, Layout, Rect},
@@ -36,6 +38,9 @@ const SLICE_SIZE: usize = 1200;
 pub struct FileRevOpen {
 	pub file_path: String,
 	pub selection: Option<usize>,
+	/// lines (1-based, inclusive) as they are in the commit (or
+	/// `HEAD`) to only show the history of
+	pub lines: Option<(Option<CommitId>, (usize, usize))>,
 }
 
 impl FileRevOpen {
@@ -43,17 +48,32 @@ impl FileRevOpen {
 		Self {
 			file_path,
 			selection: None,
+			lines: None,
+		}
+	}
+
+	pub const fn lines(
+		file_path: String,
+		commit: Option<CommitId>,
+		lines: (usize, usize),
+	) -> Self {
+		Self {
+			file_path,
+			selection: None,
+			lines: Some((commit, lines)),
 		}
 	}
 }
 
 ///
 pub struct FileRevlogPopup {
-	git_log: Option<AsyncLog>,
+	git_history: AsyncSingleJob<AsyncFileHistoryJob>,
+	/// walk `history` is filled from, `None` once it is done
+	history_job: Option<AsyncFileHistoryJob>,
+	history: Vec<FileHistoryEntry>,
 	git_diff: AsyncDiff,
 	theme: SharedTheme,
 	queue: Queue,
-	sender: Sender<AsyncGitNotification>,
 	diff: DiffComponent,
 	visible: bool,
 	repo_path: RepoPathRef,
@@ -73,9 +93,10 @@ impl FileRevlogPopup {
 		Self {
 			theme: env.theme.clone(),
 			queue: env.queue.clone(),
-			sender: env.sender_git.clone(),
 			diff: DiffComponent::new(env, true),
-			git_log: None,
+			git_history: AsyncSingleJob::new(env.sender_git.clone()),
+			history_job: None,
+			history: Vec::new(),
 			git_diff: AsyncDiff::new(
 				env.repo.borrow().clone(),
 				&env.sender_git,
@@ -101,13 +122,27 @@ impl FileRevlogPopup {
 	pub fn open(&mut self, open_request: FileRevOpen) -> Result<()> {
 		self.open_request = Some(open_request.clone());
 
-		let filter = diff_contains_file(open_request.file_path);
-		self.git_log = Some(AsyncLog::new(
-			self.repo_path.borrow().clone(),
-			&self.sender,
-			Some(filter),
-		));
+		if let Some(job) = self.history_job.take() {
+			job.stop();
+		}
+
+		let repo = self.repo_path.borrow().clone();
+		let job = match open_request.lines {
+			Some((commit, lines)) => AsyncFileHistoryJob::new_lines(
+				repo,
+				open_request.file_path,
+				commit,
+				lines,
+			),
+			None => {
+				AsyncFileHistoryJob::new(repo, open_request.file_path)
+			}
+		};
+		self.history_job = Some(job.clone());
+		self.git_history.spawn(job);
 
+		self.history.clear();
+		self.count_total = 0;
 		self.items.clear();
 		self.set_selection(open_request.selection.unwrap_or(0));
 
@@ -123,17 +158,54 @@ impl FileRevlogPopup {
 
 	///
 	pub fn any_work_pending(&self) -> bool {
-		self.git_diff.is_pending()
-			|| self.git_log.as_ref().is_some_and(AsyncLog::is_pending)
+		self.git_diff.is_pending() || self.git_history.is_pending()
 	}
 
 	///
 	pub fn update(&mut self) -> Result<()> {
-		if let Some(ref mut git_log) = self.git_log {
-			git_log.fetch()?;
+		self.fetch_commits_if_needed();
+		self.update_diff()?;
+
+		Ok(())
+	}
+
+	fn update_history(&mut self) -> Result<()> {
+		// finished jobs are only read through `history_job`, an
+		// older walk might have been the last one to finish
+		self.git_history.take_last();
+
+		let Some(job) = self.history_job.as_ref() else {
+			return Ok(());
+		};
+
+		let entries = job.extract_entries();
+		let result = job.result();
+
+		if result.is_some() {
+			self.history_job = None;
+		}
+
+		if !entries.is_empty() || result.is_some() {
+			self.history.extend(entries);
+			self.count_total = self.history.len();
+			self.items.clear();
+
+			let selection =
+				self.table_state.get_mut().selected().unwrap_or(0);
+			// the selection of a reopened popup might not be
+			// loaded yet
+			let selection = if result.is_some() {
+				selection.min(self.get_max_selection())
+			} else {
+				selection
+			};
+			self.set_selection(selection);
+
+			self.update()?;
+		}
 
-			self.fetch_commits_if_needed()?;
-			self.update_diff()?;
+		if let Some(result) = result {
+			result?;
 		}
 
 		Ok(())
@@ -146,8 +218,9 @@ impl FileRevlogPopup {
 	) -> Result<()> {
 		if self.visible {
 			match event {
-				AsyncGitNotification::CommitFiles
-				| AsyncGitNotification::Log => self.update()?,
+				AsyncGitNotification::FileLog => {
+					self.update_history()?;
+				}
 				AsyncGitNotification::Diff => self.update_diff()?,
 				_ => (),
 			}
@@ -158,33 +231,36 @@ impl FileRevlogPopup {
 
 	pub fn update_diff(&mut self) -> Result<()> {
 		if self.is_visible() {
-			if let Some(commit_id) = self.selected_commit() {
-				if let Some(open_request) = &self.open_request {
-					let diff_params = DiffParams {
-						path: open_request.file_path.clone(),
-						diff_type: DiffType::Commit(commit_id),
-						options: self.options.borrow().diff_options(),
-					};
-
-					if let Some((params, last)) =
-						self.git_diff.last()?
-					{
-						if params == diff_params {
-							self.diff.update(
-								open_request.file_path.to_string(),
-								false,
-								last,
-							);
-
-							return Ok(());
-						}
+			if let Some(entry) = self.selected_entry() {
+				let diff_params = DiffParams {
+					path: entry.path.clone(),
+					diff_type: DiffType::Commit(entry.commit),
+					options: self.diff.limit_options(
+						&entry.path,
+						self.options.borrow().diff_options(),
+					),
+				};
+
+				if let Some((params, last)) = self.git_diff.last()? {
+					if params == diff_params {
+						let last = match entry.lines {
+							Some(lines) => diff_of_lines(last, lines),
+							None => last,
+						};
+						self.diff.update(
+							diff_params.path,
+							false,
+							last,
+						);
+
+						return Ok(());
 					}
+				}
 
-					self.git_diff.request(diff_params)?;
-					self.diff.clear(true);
+				self.git_diff.request(diff_params)?;
+				self.diff.clear(true);
 
-					return Ok(());
-				}
+				return Ok(());
 			}
 
 			self.diff.clear(false);
@@ -197,45 +273,36 @@ impl FileRevlogPopup {
 		&mut self,
 		new_offset: usize,
 		new_max_offset: usize,
-	) -> Result<()> {
-		if let Some(git_log) = &mut self.git_log {
-			let amount = new_max_offset
-				.saturating_sub(new_offset)
-				.max(SLICE_SIZE);
-
-			let commits = get_commits_info(
-				&self.repo_path.borrow(),
-				&git_log.get_slice(new_offset, amount)?,
-				self.current_width.get(),
-			);
+	) {
+		let amount =
+			new_max_offset.saturating_sub(new_offset).max(SLICE_SIZE);
 
-			if let Ok(commits) = commits {
-				self.items.set_items(new_offset, commits, None);
-			}
+		let ids = self
+			.history
+			.iter()
+			.skip(new_offset)
+			.take(amount)
+			.map(|entry| entry.commit)
+			.collect::<Vec<_>>();
+
+		let commits = get_commits_info(
+			&self.repo_path.borrow(),
+			&ids,
+			self.current_width.get(),
+		);
 
-			self.count_total = git_log.count()?;
+		if let Ok(commits) = commits {
+			self.items.set_items(new_offset, commits, None);
 		}
+	}
 
-		Ok(())
+	fn selected_entry(&self) -> Option<&FileHistoryEntry> {
+		self.get_selection()
+			.and_then(|selected| self.history.get(selected))
 	}
 
 	fn selected_commit(&self) -> Option<CommitId> {
-		let table_state = self.table_state.take();
-
-		let commit_id = table_state.selected().and_then(|selected| {
-			self.items
-				.iter()
-				.nth(
-					selected
-						.saturating_sub(self.items.index_offset()),
-				)
-				.as_ref()
-				.map(|entry| entry.id)
-		});
-
-		self.table_state.set(table_state);
-
-		commit_id
+		self.selected_entry().map(|entry| entry.commit)
 	}
 
 	fn can_focus_diff(&self) -> bool {
@@ -253,20 +320,32 @@ impl FileRevlogPopup {
 
 		self.open_request.as_ref().map_or(
 			"<no history available>".into(),
-			|open_request| {
-				strings::file_log_title(
+			|open_request| match open_request.lines {
+				Some((_, lines)) => strings::line_log_title(
 					&open_request.file_path,
+					lines,
 					selected,
 					revisions,
-				)
+				),
+				None => strings::file_log_title(
+					&open_request.file_path,
+					selected,
+					revisions,
+				),
 			},
 		)
 	}
 
 	fn get_rows(&self, now: DateTime<Local>) -> Vec<Row> {
+		let offset = self.items.index_offset();
+
 		self.items
 			.iter()
-			.map(|entry| {
+			.enumerate()
+			.map(|(idx, entry)| {
+				let change =
+					self.history.get(offset + idx).map(|e| &e.change);
+
 				let spans = Line::from(vec![
 					Span::styled(
 						entry.hash_short.to_string(),
@@ -287,23 +366,32 @@ impl FileRevlogPopup {
 				let mut text = Text::from(spans);
 				text.extend(Text::raw(entry.msg.to_string()));
 
-				let cells = vec![Cell::from(""), Cell::from(text)];
+				if let Some(FileHistoryChange::Renamed(old_path)) =
+					change
+				{
+					text.extend(Text::styled(
+						strings::file_log_renamed_from(old_path),
+						self.theme.text(false, false),
+					));
+				}
 
-				Row::new(cells).height(2)
+				let height =
+					u16::try_from(text.height()).unwrap_or(2);
+				let cells = vec![
+					Cell::from(change.map_or("", change_marker)),
+					Cell::from(text),
+				];
+
+				Row::new(cells).height(height)
 			})
 			.collect()
 	}
 
 	fn get_max_selection(&self) -> usize {
-		self.git_log.as_ref().map_or(0, |log| {
-			log.count().unwrap_or(0).saturating_sub(1)
-		})
+		self.history.len().saturating_sub(1)
 	}
 
-	fn move_selection(
-		&mut self,
-		scroll_type: ScrollType,
-	) -> Result<()> {
+	fn move_selection(&mut self, scroll_type: ScrollType) {
 		let old_selection =
 			self.table_state.get_mut().selected().unwrap_or(0);
 		let max_selection = self.get_max_selection();
@@ -330,9 +418,7 @@ impl FileRevlogPopup {
 		}
 
 		self.set_selection(new_selection);
-		self.fetch_commits_if_needed()?;
-
-		Ok(())
+		self.fetch_commits_if_needed();
 	}
 
 	fn set_selection(&mut self, selection: usize) {
@@ -349,7 +435,7 @@ impl FileRevlogPopup {
 		self.table_state.get_mut().select(Some(selection));
 	}
 
-	fn fetch_commits_if_needed(&mut self) -> Result<()> {
+	fn fetch_commits_if_needed(&mut self) {
 		let selection =
 			self.table_state.get_mut().selected().unwrap_or(0);
 		let offset = *self.table_state.get_mut().offset_mut();
@@ -359,10 +445,8 @@ impl FileRevlogPopup {
 			selection.saturating_add(height_in_items);
 
 		if self.items.needs_data(offset, new_max_offset) {
-			self.fetch_commits(offset, new_max_offset)?;
+			self.fetch_commits(offset, new_max_offset);
 		}
-
-		Ok(())
 	}
 
 	fn get_selection(&self) -> Option<usize> {
@@ -375,7 +459,8 @@ impl FileRevlogPopup {
 
 	fn draw_revlog(&self, f: &mut Frame, area: Rect) {
 		let constraints = [
-			// type of change: (A)dded, (M)odified, (D)eleted
+			// type of change: (A)dded, (M)odified, (D)eleted,
+			// (R)enamed
 			Constraint::Length(1),
 			// commit details
 			Constraint::Percentage(100),
@@ -449,6 +534,7 @@ impl FileRevlogPopup {
 					StackablePopupOpen::FileRevlog(FileRevOpen {
 						file_path: open_request.file_path,
 						selection: self.get_selection(),
+						lines: open_request.lines,
 					}),
 				));
 			}
@@ -523,28 +609,40 @@ impl Component for FileRevlogPopup {
 						));
 					};
 				} else if key_match(key, self.key_config.keys.blame) {
-					if let Some(open_request) =
-						self.open_request.clone()
+					if let Some(entry) =
+						self.selected_entry().cloned()
 					{
 						self.hide_stacked(true);
 						self.queue.push(InternalEvent::OpenPopup(
 							StackablePopupOpen::BlameFile(
 								BlameFileOpen {
-									file_path: open_request.file_path,
-									commit_id: self.selected_commit(),
+									file_path: entry.path,
+									commit_id: Some(entry.commit),
 									selection: None,
 								},
 							),
 						));
 					}
+				} else if key_match(
+					key,
+					self.key_config.keys.restore_file,
+				) {
+					if let Some(entry) = self.selected_entry() {
+						self.queue.push(
+							InternalEvent::OpenRestoreFilePopup(
+								entry.commit,
+								entry.path.clone(),
+							),
+						);
+					}
 				} else if key_match(key, self.key_config.keys.move_up)
 				{
-					self.move_selection(ScrollType::Up)?;
+					self.move_selection(ScrollType::Up);
 				} else if key_match(
 					key,
 					self.key_config.keys.move_down,
 				) {
-					self.move_selection(ScrollType::Down)?;
+					self.move_selection(ScrollType::Down);
 				} else if key_match(
 					key,
 					self.key_config.keys.shift_up,
@@ -552,7 +650,7 @@ impl Component for FileRevlogPopup {
 					key,
 					self.key_config.keys.home,
 				) {
-					self.move_selection(ScrollType::Home)?;
+					self.move_selection(ScrollType::Home);
 				} else if key_match(
 					key,
 					self.key_config.keys.shift_down,
@@ -560,15 +658,15 @@ impl Component for FileRevlogPopup {
 					key,
 					self.key_config.keys.end,
 				) {
-					self.move_selection(ScrollType::End)?;
+					self.move_selection(ScrollType::End);
 				} else if key_match(key, self.key_config.keys.page_up)
 				{
-					self.move_selection(ScrollType::PageUp)?;
+					self.move_selection(ScrollType::PageUp);
 				} else if key_match(
 					key,
 					self.key_config.keys.page_down,
 				) {
-					self.move_selection(ScrollType::PageDown)?;
+					self.move_selection(ScrollType::PageDown);
 				}
 			}
 
@@ -611,6 +709,15 @@ impl Component for FileRevlogPopup {
 				.order(1),
 			);
 
+			out.push(
+				CommandInfo::new(
+					strings::commands::restore_file(&self.key_config),
+					true,
+					self.selected_commit().is_some(),
+				)
+				.order(1),
+			);
+
 			out.push(CommandInfo::new(
 				strings::commands::diff_focus_right(&self.key_config),
 				self.can_focus_diff(),
@@ -640,3 +747,33 @@ impl Component for FileRevlogPopup {
 		Ok(())
 	}
 }
+
+/// only keeps the hunks touching `lines` of the new version
+fn diff_of_lines(diff: FileDiff, lines: (usize, usize)) -> FileDiff {
+	let hunks = diff
+		.hunks
+		.into_iter()
+		.filter(|hunk| {
+			hunk.lines.iter().any(|line| {
+				line.position.new_lineno.is_some_and(|lineno| {
+					(lines.0..=lines.1).contains(&(lineno as usize))
+				})
+			})
+		})
+		.collect::<Vec<_>>();
+
+	FileDiff {
+		lines: hunks.iter().map(|hunk| hunk.lines.len()).sum(),
+		hunks,
+		..diff
+	}
+}
+
+const fn change_marker(change: &FileHistoryChange) -> &'static str {
+	match change {
+		FileHistoryChange::Added => "A",
+		FileHistoryChange::Modified => "M",
+		FileHistoryChange::Deleted => "D",
+		FileHistoryChange::Renamed(_) => "R",
+	}
+}
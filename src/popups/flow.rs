@@ -0,0 +1,443 @@
+use crate::{
+	app::Environment,
+	components::{
+		visibility_blocking, CommandBlocking, CommandInfo, Component,
+		DrawableComponent, EventState, InputType, TextInputComponent,
+	},
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, NeedsUpdate, Queue},
+	strings,
+	ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::{
+	cached::BranchName,
+	sync::{self, FlowBranchType, FlowConfig, RepoPathRef},
+};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Constraint, Direction, Layout, Margin, Rect},
+	text::{Line, Span, Text},
+	widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+	Frame,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum FlowAction {
+	Start(FlowBranchType),
+	Finish(FlowBranchType),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum FlowStep {
+	Select,
+	Confirm(FlowAction),
+}
+
+/// guides through starting and finishing git-flow branches
+pub struct FlowPopup {
+	repo: RepoPathRef,
+	queue: Queue,
+	visible: bool,
+	config: Option<FlowConfig>,
+	/// flow branch currently checked out
+	current: Option<(FlowBranchType, String)>,
+	step: FlowStep,
+	selection: usize,
+	input: TextInputComponent,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl FlowPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		let mut input = TextInputComponent::new(env, "", "", false)
+			.with_input_type(InputType::Singleline);
+		input.embed();
+
+		Self {
+			repo: env.repo.clone(),
+			queue: env.queue.clone(),
+			visible: false,
+			config: None,
+			current: None,
+			step: FlowStep::Select,
+			selection: 0,
+			input,
+			theme: env.theme.clone(),
+			key_config: env.key_config.clone(),
+		}
+	}
+
+	///
+	pub fn open(&mut self) -> Result<()> {
+		let config = FlowConfig::load(&self.repo.borrow())?;
+
+		let branch = BranchName::new(self.repo.clone()).lookup().ok();
+		self.current = branch.as_deref().and_then(|branch| {
+			config
+				.parse_branch(branch)
+				.map(|(typ, name)| (typ, name.to_string()))
+		});
+
+		self.config = Some(config);
+		self.step = FlowStep::Select;
+		self.selection = 0;
+
+		self.show()
+	}
+
+	fn current_name(&self) -> &str {
+		self.current
+			.as_ref()
+			.map(|(_, name)| name.as_str())
+			.unwrap_or_default()
+	}
+
+	fn actions(&self) -> Vec<FlowAction> {
+		self.current
+			.iter()
+			.map(|(typ, _)| FlowAction::Finish(*typ))
+			.chain(
+				FlowBranchType::ALL
+					.into_iter()
+					.map(FlowAction::Start),
+			)
+			.collect()
+	}
+
+	fn move_selection(&mut self, up: bool) {
+		let max = self.actions().len().saturating_sub(1);
+
+		self.selection = if up {
+			self.selection.saturating_sub(1)
+		} else {
+			self.selection.saturating_add(1).min(max)
+		};
+	}
+
+	fn select(&mut self) -> Result<()> {
+		if let Some(action) = self.actions().get(self.selection) {
+			self.step = FlowStep::Confirm(*action);
+
+			let default = match action {
+				FlowAction::Start(_) => strings::FLOW_NAME_MSG,
+				FlowAction::Finish(_) => strings::FLOW_TAG_MSG,
+			};
+
+			self.input.set_default_msg(default.to_string());
+			self.input.set_text(String::new());
+			self.input.show()?;
+		}
+
+		Ok(())
+	}
+
+	/// input is used for the branch name when starting a branch and
+	/// for the tag message when finishing a release/hotfix
+	const fn needs_input(&self) -> bool {
+		match self.step {
+			FlowStep::Select => false,
+			FlowStep::Confirm(FlowAction::Start(_)) => true,
+			FlowStep::Confirm(FlowAction::Finish(typ)) => {
+				typ.is_tagged()
+			}
+		}
+	}
+
+	fn can_confirm(&self) -> bool {
+		if let FlowStep::Confirm(FlowAction::Start(_)) = self.step {
+			let name = self.input.get_text();
+			return !name.is_empty()
+				&& sync::validate_branch_name(name)
+					.unwrap_or_default();
+		}
+
+		true
+	}
+
+	fn confirm(&mut self, action: FlowAction) {
+		let Some(config) = &self.config else {
+			return;
+		};
+
+		let res = match action {
+			FlowAction::Start(typ) => sync::flow_start(
+				&self.repo.borrow(),
+				config,
+				typ,
+				self.input.get_text(),
+			)
+			.map(|_| ()),
+			FlowAction::Finish(typ) => sync::flow_finish(
+				&self.repo.borrow(),
+				config,
+				typ,
+				self.current_name(),
+				Some(self.input.get_text()),
+			),
+		};
+
+		self.hide();
+
+		if let Err(e) = res {
+			self.queue.push(InternalEvent::ShowErrorMsg(format!(
+				"git-flow error:\n{e}"
+			)));
+		}
+
+		self.queue.push(InternalEvent::Update(
+			NeedsUpdate::ALL | NeedsUpdate::BRANCHES,
+		));
+	}
+
+	fn select_text(&self, config: &FlowConfig) -> Text<'_> {
+		let mut lines = vec![
+			Line::styled(
+				strings::flow_model(&config.main, &config.develop),
+				self.theme.text(false, false),
+			),
+			Line::default(),
+		];
+
+		lines.extend(self.actions().into_iter().enumerate().map(
+			|(idx, action)| {
+				let selected = idx == self.selection;
+				let label = match action {
+					FlowAction::Start(typ) => {
+						strings::flow_start_action(
+							typ,
+							config.prefix(typ),
+							config.base(typ),
+						)
+					}
+					FlowAction::Finish(typ) => {
+						strings::flow_finish_action(
+							&config.branch_name(
+								typ,
+								self.current_name(),
+							),
+						)
+					}
+				};
+
+				Line::from(Span::styled(
+					format!(
+						"{}{label}",
+						if selected { "> " } else { "  " }
+					),
+					self.theme.text(true, selected),
+				))
+			},
+		));
+
+		Text::from(lines)
+	}
+
+	/// lists the steps the action is going to take
+	fn confirm_text(
+		&self,
+		config: &FlowConfig,
+		action: FlowAction,
+	) -> Text<'_> {
+		let text = self.theme.text(true, false);
+
+		let steps = match action {
+			FlowAction::Start(typ) => {
+				let name = self.input.get_text();
+				vec![strings::flow_step_create(
+					&config.branch_name(
+						typ,
+						if name.is_empty() { "…" } else { name },
+					),
+					config.base(typ),
+				)]
+			}
+			FlowAction::Finish(typ) => {
+				let name = self.current_name();
+				let branch = config.branch_name(typ, name);
+
+				let mut steps = Vec::new();
+				for (idx, target) in
+					config.merge_targets(typ).into_iter().enumerate()
+				{
+					steps.push(strings::flow_step_merge(
+						&branch, target,
+					));
+					if idx == 0 && typ.is_tagged() {
+						steps.push(strings::flow_step_tag(
+							&config.tag_name(name),
+							target,
+						));
+					}
+				}
+				steps.push(strings::flow_step_delete(
+					&branch,
+					&config.develop,
+				));
+				steps
+			}
+		};
+
+		Text::from(
+			steps
+				.into_iter()
+				.map(|step| Line::styled(format!("* {step}"), text))
+				.collect::<Vec<_>>(),
+		)
+	}
+}
+
+impl DrawableComponent for FlowPopup {
+	fn draw(&self, f: &mut Frame, _rect: Rect) -> Result<()> {
+		if !self.visible {
+			return Ok(());
+		}
+
+		let Some(config) = &self.config else {
+			return Ok(());
+		};
+
+		let area = ui::centered_rect(60, 40, f.area());
+
+		let title = match self.step {
+			FlowStep::Select => strings::POPUP_TITLE_FLOW.to_string(),
+			FlowStep::Confirm(FlowAction::Start(typ)) => {
+				strings::flow_start_title(typ)
+			}
+			FlowStep::Confirm(FlowAction::Finish(typ)) => {
+				strings::flow_finish_title(
+					&config.branch_name(typ, self.current_name()),
+				)
+			}
+		};
+
+		f.render_widget(Clear, area);
+		f.render_widget(
+			Block::default()
+				.title(Span::styled(title, self.theme.title(true)))
+				.borders(Borders::ALL)
+				.border_type(BorderType::Thick)
+				.border_style(self.theme.block(true)),
+			area,
+		);
+
+		let input_height = if self.needs_input() { 2 } else { 0 };
+
+		let chunks = Layout::default()
+			.direction(Direction::Vertical)
+			.constraints(
+				[
+					Constraint::Length(input_height),
+					Constraint::Min(1),
+				]
+				.as_ref(),
+			)
+			.split(area.inner(Margin {
+				horizontal: 1,
+				vertical: 1,
+			}));
+
+		let text = match self.step {
+			FlowStep::Select => self.select_text(config),
+			FlowStep::Confirm(action) => {
+				self.confirm_text(config, action)
+			}
+		};
+
+		if input_height > 0 {
+			self.input.draw(f, chunks[0])?;
+		}
+
+		f.render_widget(
+			Paragraph::new(text).wrap(Wrap { trim: false }),
+			chunks[1],
+		);
+
+		Ok(())
+	}
+}
+
+impl Component for FlowPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.visible || force_all {
+			if self.step == FlowStep::Select {
+				out.push(CommandInfo::new(
+					strings::commands::scroll(&self.key_config),
+					true,
+					true,
+				));
+			}
+			out.push(CommandInfo::new(
+				strings::commands::confirm_action(&self.key_config),
+				self.can_confirm(),
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::close_popup(&self.key_config),
+				true,
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.visible {
+			if let Event::Key(e) = ev {
+				let keys = &self.key_config.keys;
+
+				if key_match(e, keys.exit_popup) {
+					if self.step == FlowStep::Select {
+						self.hide();
+					} else {
+						self.step = FlowStep::Select;
+						self.input.hide();
+					}
+				} else if key_match(e, keys.enter) {
+					match self.step {
+						FlowStep::Select => self.select()?,
+						FlowStep::Confirm(action) => {
+							if self.can_confirm() {
+								self.confirm(action);
+							}
+						}
+					}
+				} else if self.step == FlowStep::Select {
+					if key_match(e, keys.move_up) {
+						self.move_selection(true);
+					} else if key_match(e, keys.move_down) {
+						self.move_selection(false);
+					}
+				} else if self.needs_input() {
+					self.input.event(ev)?;
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+		self.input.hide();
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
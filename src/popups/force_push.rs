@@ -0,0 +1,432 @@
+use crate::{
+	app::Environment,
+	components::{
+		visibility_blocking, CommandBlocking, CommandInfo, Component,
+		DrawableComponent, EventState, InputType, TextInputComponent,
+	},
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, Queue},
+	strings,
+	ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::{
+	asyncjob::AsyncSingleJob,
+	sync::{
+		cred::{
+			extract_username_password_for_push,
+			need_username_password_for_push, BasicAuthCredential,
+		},
+		get_branch_remote, is_branch_protected,
+		remotes::get_default_remote_for_push,
+		CommitId, ForcePushPreview, RepoPathRef,
+	},
+	AsyncForcePushPreviewJob, AsyncGitNotification, PushType,
+};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Constraint, Direction, Layout, Margin, Rect},
+	text::{Line, Span, Text},
+	widgets::{Block, BorderType, Borders, Clear},
+	Frame,
+};
+
+use super::popup_paragraph;
+
+/// previews the remote commits a force push discards before pushing
+pub struct ForcePushPopup {
+	repo: RepoPathRef,
+	queue: Queue,
+	visible: bool,
+	branch: String,
+	protected: bool,
+	preview: Option<Result<ForcePushPreview, String>>,
+	async_preview: AsyncSingleJob<AsyncForcePushPreviewJob>,
+	confirm_input: TextInputComponent,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl ForcePushPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		let mut confirm_input =
+			TextInputComponent::new(env, "", "", false)
+				.with_input_type(InputType::Singleline);
+		confirm_input.embed();
+
+		Self {
+			repo: env.repo.clone(),
+			queue: env.queue.clone(),
+			visible: false,
+			branch: String::new(),
+			protected: false,
+			preview: None,
+			async_preview: AsyncSingleJob::new(
+				env.sender_git.clone(),
+			),
+			confirm_input,
+			theme: env.theme.clone(),
+			key_config: env.key_config.clone(),
+		}
+	}
+
+	/// fetch the remote tip of `branch` and preview the force push
+	pub fn open(&mut self, branch: String) -> Result<()> {
+		let repo = self.repo.borrow().clone();
+
+		let remote = if let Ok(Some(remote)) =
+			get_branch_remote(&repo, &branch)
+		{
+			remote
+		} else {
+			get_default_remote_for_push(&repo)?
+		};
+
+		let basic_credential =
+			if need_username_password_for_push(&repo)? {
+				extract_username_password_for_push(&repo)
+					.ok()
+					.filter(BasicAuthCredential::is_complete)
+			} else {
+				None
+			};
+
+		self.protected =
+			is_branch_protected(&repo, &branch).unwrap_or_default();
+
+		self.async_preview.spawn(AsyncForcePushPreviewJob::new(
+			repo,
+			remote,
+			branch.clone(),
+			basic_credential,
+		));
+
+		self.confirm_input
+			.set_default_msg(strings::type_to_confirm(&branch));
+		self.branch = branch;
+		self.preview = None;
+
+		self.show()?;
+		self.confirm_input.set_text(String::new());
+
+		Ok(())
+	}
+
+	///
+	pub fn update_git(&mut self, ev: AsyncGitNotification) {
+		if ev == AsyncGitNotification::ForcePushPreview {
+			if let Some(job) = self.async_preview.take_last() {
+				if let Some(result) = job.result() {
+					self.preview =
+						Some(result.map_err(|e| e.to_string()));
+				}
+			}
+		}
+	}
+
+	///
+	pub fn any_work_pending(&self) -> bool {
+		self.async_preview.is_pending()
+	}
+
+	/// remote commits could get lost for good, so the user has to
+	/// type the branch name instead of just hitting enter
+	fn needs_typed_confirmation(&self) -> bool {
+		if self.protected && self.preview.is_some() {
+			return true;
+		}
+
+		match &self.preview {
+			Some(Ok(preview)) => {
+				preview.fetch_error.is_some() || !preview.all_local()
+			}
+			Some(Err(_)) => true,
+			None => false,
+		}
+	}
+
+	fn can_confirm(&self) -> bool {
+		self.preview.is_some()
+			&& (!self.needs_typed_confirmation()
+				|| self.confirm_input.get_text() == self.branch)
+	}
+
+	/// the lease already refuses to overwrite unseen remote commits,
+	/// only protected branches still need the typed branch name
+	fn can_confirm_lease(&self) -> bool {
+		matches!(self.preview, Some(Ok(_)))
+			&& (!self.protected
+				|| self.confirm_input.get_text() == self.branch)
+	}
+
+	fn confirm_lease(&mut self) {
+		if self.can_confirm_lease() {
+			if let Some(Ok(preview)) = &self.preview {
+				self.queue.push(InternalEvent::PushWithLease(
+					self.branch.clone(),
+					preview.lease,
+				));
+			}
+
+			self.hide();
+		}
+	}
+
+	fn lease_line(&self, preview: &ForcePushPreview) -> Line<'_> {
+		let tip = preview.lease.get_short_string();
+
+		if preview.stale {
+			Line::styled(
+				strings::force_push_lease_broken(
+					&preview.remote_ref,
+					&tip,
+				),
+				self.theme.text_danger(),
+			)
+		} else if preview.fetch_error.is_some() {
+			Line::styled(
+				strings::force_push_lease_unchecked(
+					&preview.remote_ref,
+					&tip,
+				),
+				self.theme.text(true, false),
+			)
+		} else if preview.lease == CommitId::default() {
+			Line::styled(
+				strings::force_push_lease_new(&preview.remote_ref),
+				self.theme.text(true, false),
+			)
+		} else {
+			Line::styled(
+				strings::force_push_lease_holds(
+					&preview.remote_ref,
+					&tip,
+				),
+				self.theme.text(true, false),
+			)
+		}
+	}
+
+	fn confirm(&mut self) {
+		if self.can_confirm() {
+			self.queue.push(InternalEvent::Push(
+				self.branch.clone(),
+				PushType::Branch,
+				true,
+				false,
+				false,
+			));
+
+			self.hide();
+		}
+	}
+
+	fn get_text(&self) -> Text<'_> {
+		let danger = self.theme.text_danger();
+		let text = self.theme.text(true, false);
+
+		let preview = match &self.preview {
+			None => {
+				return Text::styled(
+					strings::force_push_checking(&self.branch),
+					text,
+				)
+			}
+			Some(Err(e)) => {
+				return Text::styled(
+					strings::force_push_preview_failed(e),
+					danger,
+				)
+			}
+			Some(Ok(preview)) => preview,
+		};
+
+		let mut lines = Vec::new();
+
+		if self.protected {
+			lines.push(Line::styled(
+				strings::force_push_protected(&self.branch),
+				danger,
+			));
+		}
+
+		if let Some(e) = &preview.fetch_error {
+			lines.push(Line::styled(
+				strings::force_push_fetch_failed(e),
+				danger,
+			));
+		}
+
+		if preview.stale {
+			lines.push(Line::styled(
+				strings::FORCE_PUSH_STALE,
+				danger,
+			));
+		}
+
+		lines.push(self.lease_line(preview));
+
+		if preview.discarded.is_empty() {
+			lines.push(Line::styled(
+				strings::force_push_nothing_discarded(
+					&preview.remote_ref,
+				),
+				text,
+			));
+		} else {
+			lines.push(Line::styled(
+				strings::force_push_discards(
+					&preview.remote_ref,
+					preview.discarded.len(),
+				),
+				text,
+			));
+		}
+
+		lines.extend(preview.discarded.iter().map(|commit| {
+			let mut spans = vec![
+				Span::styled(
+					format!("{} ", commit.id.get_short_string()),
+					self.theme.commit_hash(false),
+				),
+				Span::styled(commit.summary.clone(), text),
+			];
+			if !commit.local {
+				spans.push(Span::styled(
+					strings::FORCE_PUSH_REMOTE_ONLY,
+					danger,
+				));
+			}
+			Line::from(spans)
+		}));
+
+		Text::from(lines)
+	}
+}
+
+impl DrawableComponent for ForcePushPopup {
+	fn draw(&self, f: &mut Frame, _rect: Rect) -> Result<()> {
+		if self.visible {
+			let area = ui::centered_rect(60, 50, f.area());
+
+			f.render_widget(Clear, area);
+			f.render_widget(
+				Block::default()
+					.title(Span::styled(
+						strings::confirm_title_force_push(
+							&self.key_config,
+						),
+						self.theme.title(true),
+					))
+					.borders(Borders::ALL)
+					.border_type(BorderType::Thick)
+					.border_style(self.theme.block(true)),
+				area,
+			);
+
+			let input_height =
+				u16::from(self.needs_typed_confirmation());
+
+			let chunks = Layout::default()
+				.direction(Direction::Vertical)
+				.constraints(
+					[
+						Constraint::Percentage(100),
+						Constraint::Length(input_height),
+					]
+					.as_ref(),
+				)
+				.split(area.inner(Margin {
+					horizontal: 1,
+					vertical: 1,
+				}));
+
+			f.render_widget(
+				popup_paragraph(
+					"",
+					self.get_text(),
+					&self.theme,
+					true,
+					false,
+				),
+				chunks[0],
+			);
+
+			if input_height > 0 {
+				self.confirm_input.draw(f, chunks[1])?;
+			}
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for ForcePushPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.visible || force_all {
+			out.push(CommandInfo::new(
+				strings::commands::confirm_action(&self.key_config),
+				self.can_confirm(),
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::force_push_with_lease(
+					&self.key_config,
+				),
+				self.can_confirm_lease(),
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::close_popup(&self.key_config),
+				true,
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.visible {
+			if let Event::Key(e) = ev {
+				if key_match(e, self.key_config.keys.exit_popup) {
+					self.hide();
+				} else if key_match(e, self.key_config.keys.enter) {
+					self.confirm();
+				} else if key_match(
+					e,
+					self.key_config.keys.force_push_lease,
+				) {
+					self.confirm_lease();
+				} else if self.needs_typed_confirmation() {
+					self.confirm_input.event(ev)?;
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+		self.confirm_input.hide();
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+		self.confirm_input.show()?;
+
+		Ok(())
+	}
+}
@@ -0,0 +1,186 @@
+use crate::components::{
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState, InputType, TextInputComponent,
+};
+use crate::{
+	app::Environment,
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, Queue},
+	strings,
+};
+use anyhow::Result;
+use asyncgit::sync::{
+	self, CommitId, FormatPatchOptions, RepoPathRef,
+};
+use crossterm::event::Event;
+use ratatui::{layout::Rect, Frame};
+use std::path::Path;
+
+/// asks for the directory to export commits as patch files to
+pub struct FormatPatchPopup {
+	repo: RepoPathRef,
+	input: TextInputComponent,
+	commits: Vec<CommitId>,
+	options: FormatPatchOptions,
+	queue: Queue,
+	key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for FormatPatchPopup {
+	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
+		self.input.draw(f, rect)?;
+
+		Ok(())
+	}
+}
+
+impl Component for FormatPatchPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			self.input.commands(out, force_all);
+
+			out.push(CommandInfo::new(
+				strings::commands::format_patch_confirm(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::format_patch_numbered(
+					&self.key_config,
+					self.options.numbered,
+				),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::format_patch_cover_letter(
+					&self.key_config,
+					self.options.cover_letter,
+				),
+				true,
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.is_visible() {
+			if let Event::Key(e) = ev {
+				if key_match(e, self.key_config.keys.enter) {
+					self.export();
+					return Ok(EventState::Consumed);
+				} else if key_match(
+					e,
+					self.key_config.keys.format_patch_numbered,
+				) {
+					self.options.numbered = !self.options.numbered;
+					self.update_title();
+					return Ok(EventState::Consumed);
+				} else if key_match(
+					e,
+					self.key_config.keys.format_patch_cover_letter,
+				) {
+					self.options.cover_letter =
+						!self.options.cover_letter;
+					self.update_title();
+					return Ok(EventState::Consumed);
+				}
+			}
+
+			self.input.event(ev)?;
+			return Ok(EventState::Consumed);
+		}
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.input.is_visible()
+	}
+
+	fn hide(&mut self) {
+		self.input.hide();
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.input.show()?;
+
+		Ok(())
+	}
+}
+
+impl FormatPatchPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		let input = TextInputComponent::new(
+			env,
+			&strings::format_patch_popup_title(
+				0,
+				FormatPatchOptions::default(),
+			),
+			&strings::format_patch_popup_msg(),
+			true,
+		)
+		.with_input_type(InputType::Singleline);
+
+		Self {
+			queue: env.queue.clone(),
+			input,
+			commits: Vec::new(),
+			options: FormatPatchOptions::default(),
+			key_config: env.key_config.clone(),
+			repo: env.repo.clone(),
+		}
+	}
+
+	/// keeps directory and options of the last export
+	pub fn open(&mut self, commits: Vec<CommitId>) -> Result<()> {
+		self.commits = commits;
+		self.update_title();
+		self.show()?;
+
+		Ok(())
+	}
+
+	fn update_title(&mut self) {
+		self.input.set_title(strings::format_patch_popup_title(
+			self.commits.len(),
+			self.options,
+		));
+	}
+
+	fn export(&mut self) {
+		let dir = match self.input.get_text().trim() {
+			"" => ".",
+			dir => dir,
+		};
+
+		match sync::format_patches(
+			&self.repo.borrow(),
+			&self.commits,
+			Path::new(dir),
+			self.options,
+		) {
+			Ok(files) => {
+				self.queue.push(InternalEvent::ShowInfoMsg(
+					strings::format_patch_success(files.len(), dir),
+				));
+			}
+			Err(e) => {
+				log::error!("format patch: {e}");
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("export patches error:\n{e}"),
+				));
+			}
+		}
+
+		self.hide();
+	}
+}
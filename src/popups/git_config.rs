@@ -0,0 +1,373 @@
+use crate::components::{
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState, ScrollType,
+};
+use crate::{
+	app::Environment,
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, Queue},
+	strings, try_or_popup,
+	ui::{self, style::SharedTheme, Size},
+};
+use anyhow::Result;
+use asyncgit::sync::{
+	get_config_entries, set_config_string, ConfigEntry, ConfigOrigin,
+	RepoPathRef,
+};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Constraint, Margin, Rect},
+	text::Span,
+	widgets::{
+		Block, BorderType, Borders, Cell, Clear, Row, Table,
+		TableState,
+	},
+	Frame,
+};
+use std::cell::Cell as StdCell;
+
+/// lists effective git config values and where they come from
+pub struct GitConfigPopup {
+	repo: RepoPathRef,
+	queue: Queue,
+	visible: bool,
+	entries: Vec<ConfigEntry>,
+	table_state: StdCell<TableState>,
+	current_height: StdCell<usize>,
+	key_config: SharedKeyConfig,
+	theme: SharedTheme,
+}
+
+impl GitConfigPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			repo: env.repo.clone(),
+			queue: env.queue.clone(),
+			visible: false,
+			entries: Vec::new(),
+			table_state: StdCell::new(TableState::default()),
+			current_height: StdCell::new(0),
+			key_config: env.key_config.clone(),
+			theme: env.theme.clone(),
+		}
+	}
+
+	///
+	pub fn open(&mut self) -> Result<()> {
+		self.table_state.get_mut().select(Some(0));
+		self.show()?;
+		self.update()
+	}
+
+	/// reload config values
+	pub fn update(&mut self) -> Result<()> {
+		if self.is_visible() {
+			self.entries = get_config_entries(&self.repo.borrow())?;
+
+			let max = self.entries.len().saturating_sub(1);
+			let table_state = self.table_state.get_mut();
+			table_state
+				.select(table_state.selected().map(|s| s.min(max)));
+		}
+
+		Ok(())
+	}
+
+	fn selected_entry(&self) -> Option<&ConfigEntry> {
+		let table_state = self.table_state.take();
+		let selection = table_state.selected();
+		self.table_state.set(table_state);
+
+		selection.and_then(|s| self.entries.get(s))
+	}
+
+	fn can_unset(&self) -> bool {
+		self.selected_entry().is_some_and(|entry| {
+			matches!(
+				entry.origin,
+				Some(ConfigOrigin::Local | ConfigOrigin::Global)
+			)
+		})
+	}
+
+	fn edit(&self, origin: ConfigOrigin) {
+		if let Some(entry) = self.selected_entry() {
+			self.queue.push(InternalEvent::EditGitConfig {
+				origin,
+				key: Some(entry.name.clone()),
+				value: entry.value.clone(),
+			});
+		}
+	}
+
+	fn unset(&mut self) -> Result<()> {
+		if let Some(ConfigEntry {
+			name,
+			origin: Some(origin),
+			..
+		}) = self.selected_entry().cloned()
+		{
+			try_or_popup!(
+				self,
+				"unset config value:",
+				set_config_string(
+					&self.repo.borrow(),
+					origin,
+					&name,
+					None
+				)
+			);
+		}
+
+		self.update()
+	}
+
+	fn move_selection(&self, scroll_type: ScrollType) {
+		let mut table_state = self.table_state.take();
+
+		let old_selection = table_state.selected().unwrap_or(0);
+		let max_selection = self.entries.len().saturating_sub(1);
+		let page = self.current_height.get().saturating_sub(1);
+
+		let new_selection = match scroll_type {
+			ScrollType::Up => old_selection.saturating_sub(1),
+			ScrollType::Down => {
+				old_selection.saturating_add(1).min(max_selection)
+			}
+			ScrollType::Home => 0,
+			ScrollType::End => max_selection,
+			ScrollType::PageUp => old_selection.saturating_sub(page),
+			ScrollType::PageDown => {
+				old_selection.saturating_add(page).min(max_selection)
+			}
+		};
+
+		table_state.select(Some(new_selection));
+		self.table_state.set(table_state);
+	}
+
+	fn get_rows(&self) -> Vec<Row<'_>> {
+		self.entries
+			.iter()
+			.map(|entry| {
+				let (value, value_style) =
+					entry.value.as_ref().map_or_else(
+						|| {
+							(
+								String::from("-"),
+								self.theme.text(false, false),
+							)
+						},
+						|value| {
+							(
+								value.clone(),
+								self.theme.text(true, false),
+							)
+						},
+					);
+
+				Row::new(vec![
+					Cell::from(Span::styled(
+						entry.name.clone(),
+						self.theme.text(true, false),
+					)),
+					Cell::from(Span::styled(value, value_style)),
+					Cell::from(Span::styled(
+						entry
+							.origin
+							.map_or("", strings::git_config_origin),
+						self.theme.text(false, false),
+					)),
+				])
+			})
+			.collect()
+	}
+}
+
+impl DrawableComponent for GitConfigPopup {
+	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
+		if self.visible {
+			const PERCENT_SIZE: Size = Size::new(80, 60);
+			const MIN_SIZE: Size = Size::new(60, 20);
+
+			let area = ui::centered_rect(
+				PERCENT_SIZE.width,
+				PERCENT_SIZE.height,
+				f.area(),
+			);
+			let area =
+				ui::rect_inside(MIN_SIZE, f.area().into(), area);
+			let area = area.intersection(rect);
+
+			let name_width = self
+				.entries
+				.iter()
+				.fold(0, |acc, entry| acc.max(entry.name.len()));
+
+			let constraints = [
+				Constraint::Length(name_width.try_into()?),
+				Constraint::Percentage(100),
+				// origin
+				Constraint::Length(8),
+			];
+
+			let rows = self.get_rows();
+			let number_of_rows = rows.len();
+
+			let table = Table::new(rows, constraints)
+				.column_spacing(1)
+				.row_highlight_style(self.theme.text(true, true))
+				.block(
+					Block::default()
+						.borders(Borders::ALL)
+						.title(Span::styled(
+							strings::title_git_config(),
+							self.theme.title(true),
+						))
+						.border_style(self.theme.block(true))
+						.border_type(BorderType::Thick),
+				);
+
+			let mut table_state = self.table_state.take();
+
+			f.render_widget(Clear, area);
+			f.render_stateful_widget(table, area, &mut table_state);
+
+			let area = area.inner(Margin {
+				vertical: 1,
+				horizontal: 0,
+			});
+
+			ui::draw_scrollbar(
+				f,
+				area,
+				&self.theme,
+				number_of_rows,
+				table_state.selected().unwrap_or(0),
+				ui::Orientation::Vertical,
+			);
+
+			self.table_state.set(table_state);
+			self.current_height.set(area.height.into());
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for GitConfigPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.visible || force_all {
+			if !force_all {
+				out.clear();
+			}
+
+			out.push(CommandInfo::new(
+				strings::commands::scroll(&self.key_config),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::close_popup(&self.key_config),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::git_config_edit_local(
+					&self.key_config,
+				),
+				self.selected_entry().is_some(),
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::git_config_edit_global(
+					&self.key_config,
+				),
+				self.selected_entry().is_some(),
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::git_config_add(&self.key_config),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::git_config_unset(&self.key_config),
+				self.can_unset(),
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, event: &Event) -> Result<EventState> {
+		if self.visible {
+			if let Event::Key(key) = event {
+				let keys = &self.key_config.keys;
+
+				if key_match(key, keys.exit_popup) {
+					self.hide();
+				} else if key_match(key, keys.move_up) {
+					self.move_selection(ScrollType::Up);
+				} else if key_match(key, keys.move_down) {
+					self.move_selection(ScrollType::Down);
+				} else if key_match(key, keys.shift_up)
+					|| key_match(key, keys.home)
+				{
+					self.move_selection(ScrollType::Home);
+				} else if key_match(key, keys.shift_down)
+					|| key_match(key, keys.end)
+				{
+					self.move_selection(ScrollType::End);
+				} else if key_match(key, keys.page_down) {
+					self.move_selection(ScrollType::PageDown);
+				} else if key_match(key, keys.page_up) {
+					self.move_selection(ScrollType::PageUp);
+				} else if key_match(key, keys.enter) {
+					self.edit(ConfigOrigin::Local);
+				} else if key_match(key, keys.config_edit_global) {
+					self.edit(ConfigOrigin::Global);
+				} else if key_match(key, keys.config_add) {
+					self.queue.push(InternalEvent::EditGitConfig {
+						origin: ConfigOrigin::Local,
+						key: None,
+						value: None,
+					});
+				} else if key_match(key, keys.config_unset)
+					&& self.can_unset()
+				{
+					self.unset()?;
+				}
+			}
+
+			Ok(EventState::Consumed)
+		} else {
+			Ok(EventState::NotConsumed)
+		}
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
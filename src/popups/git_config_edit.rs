@@ -0,0 +1,178 @@
+use anyhow::Result;
+use asyncgit::sync::{self, ConfigOrigin, RepoPathRef};
+use crossterm::event::Event;
+use ratatui::{layout::Rect, Frame};
+
+use crate::{
+	app::Environment,
+	components::{
+		visibility_blocking, CommandBlocking, CommandInfo, Component,
+		DrawableComponent, EventState, InputType, TextInputComponent,
+	},
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, NeedsUpdate, Queue},
+	strings,
+};
+
+enum State {
+	// free-form entry: first we ask for the key
+	Key,
+	// then for the value of the key
+	Value { key: String },
+}
+
+pub struct GitConfigEditPopup {
+	repo: RepoPathRef,
+	input: TextInputComponent,
+	queue: Queue,
+	key_config: SharedKeyConfig,
+	origin: ConfigOrigin,
+	state: State,
+}
+
+impl DrawableComponent for GitConfigEditPopup {
+	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
+		if self.is_visible() {
+			self.input.draw(f, rect)?;
+		}
+		Ok(())
+	}
+}
+
+impl Component for GitConfigEditPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			self.input.commands(out, force_all);
+
+			out.push(CommandInfo::new(
+				strings::commands::git_config_confirm(
+					&self.key_config,
+				),
+				!self.input.get_text().trim().is_empty()
+					|| matches!(self.state, State::Value { .. }),
+				true,
+			));
+		}
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.is_visible() {
+			if self.input.event(ev)?.is_consumed() {
+				return Ok(EventState::Consumed);
+			}
+
+			if let Event::Key(e) = ev {
+				if key_match(e, self.key_config.keys.enter) {
+					self.handle_submit();
+				}
+
+				return Ok(EventState::Consumed);
+			}
+		}
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.input.is_visible()
+	}
+
+	fn hide(&mut self) {
+		self.input.hide();
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.input.show()?;
+
+		Ok(())
+	}
+}
+
+impl GitConfigEditPopup {
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			repo: env.repo.clone(),
+			queue: env.queue.clone(),
+			input: TextInputComponent::new(env, "", "", true)
+				.with_input_type(InputType::Singleline),
+			key_config: env.key_config.clone(),
+			origin: ConfigOrigin::Local,
+			state: State::Key,
+		}
+	}
+
+	/// edit `key` in the config file of `origin`, asks for the key
+	/// first if `None`
+	pub fn open(
+		&mut self,
+		origin: ConfigOrigin,
+		key: Option<String>,
+		value: Option<String>,
+	) -> Result<()> {
+		self.origin = origin;
+		self.input.clear();
+
+		if let Some(key) = key {
+			self.ask_value(key);
+			self.input.set_text(value.unwrap_or_default());
+		} else {
+			self.state = State::Key;
+			self.input
+				.set_title(strings::git_config_key_popup_title());
+			self.input
+				.set_default_msg(strings::git_config_key_popup_msg());
+		}
+
+		self.show()
+	}
+
+	fn ask_value(&mut self, key: String) {
+		self.input.set_title(strings::git_config_edit_popup_title(
+			&key,
+			self.origin,
+		));
+		self.input
+			.set_default_msg(strings::git_config_value_popup_msg());
+		self.state = State::Value { key };
+	}
+
+	fn handle_submit(&mut self) {
+		match &self.state {
+			State::Key => {
+				let key = self.input.get_text().trim().to_string();
+				if !key.is_empty() {
+					self.input.clear();
+					self.ask_value(key);
+				}
+			}
+			State::Value { key } => {
+				let res = sync::set_config_string(
+					&self.repo.borrow(),
+					self.origin,
+					key,
+					Some(self.input.get_text()),
+				);
+
+				match res {
+					Ok(()) => {
+						self.queue.push(InternalEvent::Update(
+							NeedsUpdate::ALL,
+						));
+					}
+					Err(e) => {
+						log::error!("set config value: {}", e);
+						self.queue.push(InternalEvent::ShowErrorMsg(
+							format!("set config value error:\n{e}"),
+						));
+					}
+				}
+
+				self.hide();
+			}
+		}
+	}
+}
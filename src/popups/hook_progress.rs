@@ -0,0 +1,220 @@
+use crate::{
+	app::Environment,
+	components::{
+		visibility_blocking, CommandBlocking, CommandInfo, Component,
+		DrawableComponent, EventState,
+	},
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, Queue},
+	strings,
+	ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::{
+	asyncjob::AsyncSingleJob,
+	sync::{HookOutputLine, RepoPathRef},
+	AsyncGitNotification, AsyncPreCommitJob,
+};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Margin, Rect},
+	text::{Line, Span},
+	widgets::{Block, BorderType, Borders, Clear, Paragraph},
+	Frame,
+};
+
+/// shows the output of the `pre-commit` hook while it runs so long
+/// running hook frameworks do not look like a frozen ui
+pub struct HookProgressPopup {
+	repo: RepoPathRef,
+	queue: Queue,
+	visible: bool,
+	job: Option<AsyncPreCommitJob>,
+	async_job: AsyncSingleJob<AsyncPreCommitJob>,
+	output: Vec<HookOutputLine>,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl HookProgressPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			repo: env.repo.clone(),
+			queue: env.queue.clone(),
+			visible: false,
+			job: None,
+			async_job: AsyncSingleJob::new(env.sender_git.clone()),
+			output: Vec::new(),
+			theme: env.theme.clone(),
+			key_config: env.key_config.clone(),
+		}
+	}
+
+	/// starts the `pre-commit` hook
+	pub fn open(&mut self) -> Result<()> {
+		let job = AsyncPreCommitJob::new(self.repo.borrow().clone());
+
+		self.async_job.spawn(job.clone());
+		self.job = Some(job);
+		self.output.clear();
+
+		self.show()
+	}
+
+	///
+	pub fn update_git(&mut self, ev: AsyncGitNotification) {
+		if ev != AsyncGitNotification::PreCommit {
+			return;
+		}
+
+		if let Some(job) = &self.job {
+			self.output = job.output();
+		}
+
+		if let Some(job) = self.async_job.take_last() {
+			if let Some(result) = job.result() {
+				if !job.is_cancelled() {
+					self.queue.push(
+						InternalEvent::PreCommitFinished(
+							result.map_err(|e| e.to_string()),
+						),
+					);
+				}
+			}
+
+			self.job = None;
+			self.hide();
+		}
+	}
+
+	///
+	pub fn any_work_pending(&self) -> bool {
+		self.async_job.is_pending()
+	}
+
+	fn is_cancelled(&self) -> bool {
+		self.job
+			.as_ref()
+			.is_some_and(AsyncPreCommitJob::is_cancelled)
+	}
+
+	fn cancel(&self) {
+		if let Some(job) = &self.job {
+			job.cancel();
+		}
+	}
+
+	fn get_lines(&self, height: usize) -> Vec<Line<'_>> {
+		if self.output.is_empty() {
+			return vec![Line::styled(
+				strings::HOOK_PROGRESS_WAITING,
+				self.theme.text(false, false),
+			)];
+		}
+
+		// follow the output as it comes in
+		let skip = self.output.len().saturating_sub(height);
+
+		self.output
+			.iter()
+			.skip(skip)
+			.map(|line| match line {
+				HookOutputLine::Stdout(l) => Line::styled(
+					l.as_str(),
+					self.theme.text(true, false),
+				),
+				HookOutputLine::Stderr(l) => {
+					Line::styled(l.as_str(), self.theme.text_danger())
+				}
+			})
+			.collect()
+	}
+}
+
+impl DrawableComponent for HookProgressPopup {
+	fn draw(&self, f: &mut Frame, _rect: Rect) -> Result<()> {
+		if self.visible {
+			let area = ui::centered_rect(70, 60, f.area());
+
+			let title = if self.is_cancelled() {
+				strings::HOOK_PROGRESS_CANCELLING
+			} else {
+				strings::POPUP_TITLE_HOOK_PROGRESS
+			};
+
+			f.render_widget(Clear, area);
+			f.render_widget(
+				Block::default()
+					.title(Span::styled(
+						title,
+						self.theme.title(true),
+					))
+					.borders(Borders::ALL)
+					.border_type(BorderType::Thick)
+					.border_style(self.theme.block(true)),
+				area,
+			);
+
+			let inner = area.inner(Margin {
+				horizontal: 1,
+				vertical: 1,
+			});
+
+			f.render_widget(
+				Paragraph::new(self.get_lines(inner.height.into())),
+				inner,
+			);
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for HookProgressPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.visible || force_all {
+			out.push(CommandInfo::new(
+				strings::commands::hook_progress_cancel(
+					&self.key_config,
+				),
+				!self.is_cancelled(),
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.visible {
+			if let Event::Key(e) = ev {
+				if key_match(e, self.key_config.keys.exit_popup) {
+					self.cancel();
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
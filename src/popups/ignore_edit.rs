@@ -0,0 +1,253 @@
+use crate::{
+	app::Environment,
+	components::{
+		visibility_blocking, CommandBlocking, CommandInfo, Component,
+		DrawableComponent, EventState, InputType, TextInputComponent,
+	},
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, NeedsUpdate, Queue},
+	strings,
+	ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::sync::{
+	self, IgnoreCandidates, IgnoreFile, IgnorePreview, RepoPathRef,
+};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Constraint, Direction, Layout, Rect},
+	text::{Line, Span, Text},
+	widgets::{Block, Borders, Clear, Paragraph, Wrap},
+	Frame,
+};
+
+/// edits `.gitignore` or `info/exclude`, previewing which untracked
+/// or ignored paths each pattern matches
+pub struct IgnoreEditPopup {
+	repo: RepoPathRef,
+	input: TextInputComponent,
+	file: IgnoreFile,
+	candidates: Option<IgnoreCandidates>,
+	preview: Vec<IgnorePreview>,
+	queue: Queue,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl IgnoreEditPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		let mut input = TextInputComponent::new(env, "", "", false)
+			.with_input_type(InputType::Multiline);
+		input.embed();
+
+		Self {
+			repo: env.repo.clone(),
+			input,
+			file: IgnoreFile::Gitignore,
+			candidates: None,
+			preview: Vec::new(),
+			queue: env.queue.clone(),
+			theme: env.theme.clone(),
+			key_config: env.key_config.clone(),
+		}
+	}
+
+	///
+	pub fn open(&mut self, file: IgnoreFile) -> Result<()> {
+		let repo = self.repo.borrow().clone();
+
+		self.file = file;
+		self.candidates = Some(sync::ignore_candidates(&repo)?);
+
+		self.show()?;
+		self.input.set_text(
+			sync::read_ignore_file(&repo, file)?
+				.trim_end_matches('\n')
+				.to_string(),
+		);
+		self.update_preview();
+
+		Ok(())
+	}
+
+	fn update_preview(&mut self) {
+		self.preview = self
+			.candidates
+			.as_ref()
+			.map(|candidates| {
+				candidates.preview(self.input.get_text())
+			})
+			.unwrap_or_default();
+	}
+
+	fn save(&mut self) {
+		let mut content =
+			self.input.get_text().trim_end().to_string();
+		if !content.is_empty() {
+			content.push('\n');
+		}
+
+		if let Err(e) = sync::write_ignore_file(
+			&self.repo.borrow(),
+			self.file,
+			&content,
+		) {
+			log::error!("ignore file error: {e}");
+			self.queue.push(InternalEvent::ShowErrorMsg(format!(
+				"ignore file error:\n{e}",
+			)));
+			return;
+		}
+
+		self.hide();
+		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+	}
+
+	fn preview_text(&self) -> Text<'_> {
+		if self.preview.is_empty() {
+			return Text::from(Span::styled(
+				strings::IGNORE_EDIT_NO_PATTERNS,
+				self.theme.text(false, false),
+			));
+		}
+
+		Text::from(
+			self.preview
+				.iter()
+				.flat_map(|preview| {
+					let header = Line::from(vec![
+						Span::styled(
+							format!("{:>3} ", preview.line),
+							self.theme.text(false, false),
+						),
+						Span::styled(
+							preview.pattern.as_str(),
+							self.theme.text(true, true),
+						),
+						Span::styled(
+							strings::ignore_edit_matches(
+								preview.matches.len(),
+								preview.negative,
+							),
+							self.theme.text(false, false),
+						),
+					]);
+
+					std::iter::once(header).chain(
+						preview.matches.iter().map(|path| {
+							Line::from(Span::styled(
+								format!("    {path}"),
+								self.theme.text(true, false),
+							))
+						}),
+					)
+				})
+				.collect::<Vec<_>>(),
+		)
+	}
+}
+
+impl DrawableComponent for IgnoreEditPopup {
+	fn draw(&self, f: &mut Frame, _rect: Rect) -> Result<()> {
+		if !self.is_visible() {
+			return Ok(());
+		}
+
+		let area = ui::centered_rect(80, 80, f.area());
+
+		f.render_widget(Clear, area);
+
+		let chunks = Layout::default()
+			.direction(Direction::Horizontal)
+			.constraints(
+				[
+					Constraint::Percentage(50),
+					Constraint::Percentage(50),
+				]
+				.as_ref(),
+			)
+			.split(area);
+
+		let editor = Block::default()
+			.title(Span::styled(
+				strings::ignore_edit_title(self.file),
+				self.theme.title(true),
+			))
+			.borders(Borders::ALL)
+			.border_style(self.theme.block(true));
+		let editor_area = editor.inner(chunks[0]);
+		f.render_widget(editor, chunks[0]);
+		self.input.draw(f, editor_area)?;
+
+		f.render_widget(
+			Paragraph::new(self.preview_text())
+				.block(
+					Block::default()
+						.title(Span::styled(
+							strings::IGNORE_EDIT_PREVIEW_TITLE,
+							self.theme.title(false),
+						))
+						.borders(Borders::ALL)
+						.border_style(self.theme.block(false)),
+				)
+				.wrap(Wrap { trim: false }),
+			chunks[1],
+		);
+
+		Ok(())
+	}
+}
+
+impl Component for IgnoreEditPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			self.input.commands(out, force_all);
+
+			out.push(CommandInfo::new(
+				strings::commands::ignore_edit_save(&self.key_config),
+				true,
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.is_visible() {
+			if let Event::Key(e) = ev {
+				if key_match(e, self.key_config.keys.commit) {
+					self.save();
+					return Ok(EventState::Consumed);
+				}
+			}
+
+			if self.input.event(ev)?.is_consumed() {
+				self.update_preview();
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.input.is_visible()
+	}
+
+	fn hide(&mut self) {
+		self.input.hide();
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.input.show()?;
+
+		Ok(())
+	}
+}
@@ -0,0 +1,314 @@
+use crate::{
+	app::Environment,
+	components::{
+		visibility_blocking, CommandBlocking, CommandInfo, Component,
+		DrawableComponent, EventState,
+	},
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, NeedsUpdate, Queue},
+	strings, try_or_popup,
+	ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::sync::{
+	self, IgnoreFile, IgnorePatternKind, RepoPathRef,
+};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Margin, Rect},
+	text::{Line, Span, Text},
+	widgets::{Block, BorderType, Borders, Clear, Paragraph},
+	Frame,
+};
+
+/// picks the pattern and the file to ignore a path with, previewing
+/// what else each pattern hides
+pub struct IgnorePatternPopup {
+	repo: RepoPathRef,
+	queue: Queue,
+	visible: bool,
+	path: String,
+	/// applicable kinds with their pattern and the paths it matches
+	patterns: Vec<(IgnorePatternKind, String, Vec<String>)>,
+	selection: usize,
+	file: IgnoreFile,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl IgnorePatternPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			repo: env.repo.clone(),
+			queue: env.queue.clone(),
+			visible: false,
+			path: String::new(),
+			patterns: Vec::new(),
+			selection: 0,
+			file: IgnoreFile::Gitignore,
+			theme: env.theme.clone(),
+			key_config: env.key_config.clone(),
+		}
+	}
+
+	/// `path` is relative to the workdir, a trailing `/` marks a
+	/// folder
+	pub fn open(&mut self, path: String) -> Result<()> {
+		let candidates =
+			sync::ignore_candidates(&self.repo.borrow())?;
+
+		self.patterns = IgnorePatternKind::ALL
+			.iter()
+			.filter_map(|kind| {
+				let pattern = sync::ignore_pattern(&path, *kind)?;
+				let matches = candidates
+					.preview(&pattern)
+					.pop()
+					.map(|preview| preview.matches)
+					.unwrap_or_default();
+
+				Some((*kind, pattern, matches))
+			})
+			.collect();
+		self.patterns.dedup_by(|a, b| a.1 == b.1);
+
+		self.path = path;
+		self.selection = 0;
+		self.file = IgnoreFile::Gitignore;
+
+		self.show()
+	}
+
+	fn move_selection(&mut self, up: bool) {
+		let max = self.patterns.len().saturating_sub(1);
+
+		self.selection = if up {
+			self.selection.saturating_sub(1)
+		} else {
+			self.selection.saturating_add(1).min(max)
+		};
+	}
+
+	fn add_pattern(&self) -> Result<()> {
+		if let Some((_, pattern, _)) =
+			self.patterns.get(self.selection)
+		{
+			sync::add_pattern_to_ignore(
+				&self.repo.borrow(),
+				self.file,
+				pattern,
+			)?;
+
+			self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+		}
+
+		Ok(())
+	}
+
+	fn text(&self, height: usize) -> Text<'_> {
+		let mut lines = self
+			.patterns
+			.iter()
+			.enumerate()
+			.map(|(idx, (kind, pattern, matches))| {
+				let selected = idx == self.selection;
+
+				Line::from(vec![
+					Span::styled(
+						if selected { "> " } else { "  " },
+						self.theme.text(true, selected),
+					),
+					Span::styled(
+						format!(
+							"{:<12}",
+							strings::ignore_pattern_kind(*kind)
+						),
+						self.theme.text(false, selected),
+					),
+					Span::styled(
+						pattern.as_str(),
+						self.theme.text(true, selected),
+					),
+					Span::styled(
+						format!(" ({})", matches.len()),
+						self.theme.text(false, selected),
+					),
+				])
+			})
+			.collect::<Vec<_>>();
+
+		if let Some((_, _, matches)) =
+			self.patterns.get(self.selection)
+		{
+			lines.push(Line::default());
+			lines.push(Line::from(Span::styled(
+				strings::IGNORE_PATTERN_HIDES,
+				self.theme.text(false, false),
+			)));
+
+			let room = height.saturating_sub(lines.len());
+			let shown = if matches.len() > room {
+				room.saturating_sub(1)
+			} else {
+				matches.len()
+			};
+
+			lines.extend(matches.iter().take(shown).map(|path| {
+				Line::from(Span::styled(
+					format!("  {path}"),
+					self.theme.text(true, false),
+				))
+			}));
+
+			if shown < matches.len() {
+				lines.push(Line::from(Span::styled(
+					strings::ignore_pattern_more(
+						matches.len() - shown,
+					),
+					self.theme.text(false, false),
+				)));
+			}
+		}
+
+		Text::from(lines)
+	}
+}
+
+impl DrawableComponent for IgnorePatternPopup {
+	fn draw(&self, f: &mut Frame, _rect: Rect) -> Result<()> {
+		if !self.visible {
+			return Ok(());
+		}
+
+		let area = ui::centered_rect(60, 50, f.area());
+
+		f.render_widget(Clear, area);
+		f.render_widget(
+			Block::default()
+				.title(Span::styled(
+					strings::ignore_pattern_title(
+						&self.path, self.file,
+					),
+					self.theme.title(true),
+				))
+				.borders(Borders::ALL)
+				.border_type(BorderType::Thick)
+				.border_style(self.theme.block(true)),
+			area,
+		);
+
+		let inner = area.inner(Margin {
+			horizontal: 1,
+			vertical: 1,
+		});
+
+		f.render_widget(
+			Paragraph::new(self.text(usize::from(inner.height))),
+			inner,
+		);
+
+		Ok(())
+	}
+}
+
+impl Component for IgnorePatternPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.visible || force_all {
+			out.push(CommandInfo::new(
+				strings::commands::scroll(&self.key_config),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::ignore_pattern_add(
+					&self.key_config,
+				),
+				!self.patterns.is_empty(),
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::ignore_toggle_file(
+					&self.key_config,
+					self.file,
+				),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::ignore_edit(
+					&self.key_config,
+					self.file,
+				),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::close_popup(&self.key_config),
+				true,
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.visible {
+			if let Event::Key(e) = ev {
+				let keys = &self.key_config.keys;
+
+				if key_match(e, keys.exit_popup) {
+					self.hide();
+				} else if key_match(e, keys.move_up) {
+					self.move_selection(true);
+				} else if key_match(e, keys.move_down) {
+					self.move_selection(false);
+				} else if key_match(e, keys.ignore_toggle_file) {
+					self.file = match self.file {
+						IgnoreFile::Gitignore => {
+							IgnoreFile::InfoExclude
+						}
+						IgnoreFile::InfoExclude => {
+							IgnoreFile::Gitignore
+						}
+					};
+				} else if key_match(e, keys.ignore_edit) {
+					self.hide();
+					self.queue.push(
+						InternalEvent::OpenIgnoreEditPopup(self.file),
+					);
+				} else if key_match(e, keys.enter) {
+					try_or_popup!(
+						self,
+						"ignore error:",
+						self.add_pattern()
+					);
+					self.hide();
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
@@ -0,0 +1,273 @@
+use crate::components::{
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState, InputType, TextInputComponent,
+};
+use crate::{
+	app::Environment,
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, Queue},
+	strings,
+	ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::sync::{self, init_default_branch, init_repo};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Constraint, Direction, Layout, Margin, Rect},
+	text::{Line, Span},
+	widgets::{Block, BorderType, Borders, Clear, Paragraph},
+	Frame,
+};
+use std::path::PathBuf;
+
+/// offers to initialize a new repository when gitui was started
+/// outside of one
+pub struct InitRepoPopup {
+	queue: Queue,
+	visible: bool,
+	path: PathBuf,
+	branch: TextInputComponent,
+	error: Option<String>,
+	key_config: SharedKeyConfig,
+	theme: SharedTheme,
+}
+
+impl InitRepoPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		let mut branch = TextInputComponent::new(
+			env,
+			"",
+			&init_default_branch().unwrap_or_else(|| {
+				strings::INIT_REPO_POPUP_DEFAULT_BRANCH.to_string()
+			}),
+			false,
+		)
+		.with_input_type(InputType::Singleline);
+		branch.embed();
+
+		Self {
+			queue: env.queue.clone(),
+			visible: false,
+			path: env.repo.borrow().gitpath().to_path_buf(),
+			branch,
+			error: None,
+			key_config: env.key_config.clone(),
+			theme: env.theme.clone(),
+		}
+	}
+
+	///
+	pub fn open(&mut self) -> Result<()> {
+		self.error = None;
+		self.branch.show()?;
+		self.branch.set_text(String::new());
+
+		self.show()
+	}
+
+	fn init(&mut self) {
+		let branch = self.branch.get_text().trim();
+		let branch = (!branch.is_empty()).then_some(branch);
+
+		if let Some(branch) = branch {
+			if !sync::validate_branch_name(branch).unwrap_or_default()
+			{
+				self.error = Some(
+					strings::INIT_REPO_POPUP_INVALID_BRANCH.into(),
+				);
+				return;
+			}
+		}
+
+		match init_repo(&self.path, branch) {
+			Ok(()) => {
+				self.hide();
+				self.queue.push(InternalEvent::OpenNewRepo(
+					self.path
+						.canonicalize()
+						.unwrap_or_else(|_| self.path.clone()),
+				));
+			}
+			Err(e) => self.error = Some(e.to_string()),
+		}
+	}
+
+	fn get_hints(&self) -> Line<'_> {
+		let keys = &self.key_config.keys;
+		let hint = |key, text: &str| {
+			Span::styled(
+				format!(
+					"{text} [{}]  ",
+					self.key_config.get_hint(key)
+				),
+				self.theme.text(false, false),
+			)
+		};
+
+		Line::from(vec![
+			hint(keys.enter, "Init"),
+			hint(keys.clone_repo, "Clone"),
+			hint(keys.exit_popup, "Quit"),
+		])
+	}
+}
+
+impl DrawableComponent for InitRepoPopup {
+	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
+		if self.is_visible() {
+			const SIZE: (u16, u16) = (70, 7);
+			let area =
+				ui::centered_rect_absolute(SIZE.0, SIZE.1, rect);
+
+			f.render_widget(Clear, area);
+			f.render_widget(
+				Block::default()
+					.borders(Borders::ALL)
+					.border_type(BorderType::Thick)
+					.border_style(self.theme.block(true))
+					.title(Span::styled(
+						strings::INIT_REPO_POPUP_MSG,
+						self.theme.title(true),
+					)),
+				area,
+			);
+
+			let chunks = Layout::default()
+				.direction(Direction::Vertical)
+				.constraints([
+					Constraint::Length(1),
+					Constraint::Length(1),
+					Constraint::Length(1),
+					Constraint::Length(1),
+					Constraint::Length(1),
+				])
+				.split(area.inner(Margin {
+					horizontal: 1,
+					vertical: 1,
+				}));
+
+			f.render_widget(
+				Paragraph::new(Line::from(vec![
+					Span::styled(
+						"Path:       ",
+						self.theme.text(false, false),
+					),
+					Span::styled(
+						self.path.to_string_lossy().to_string(),
+						self.theme.text(true, false),
+					),
+				])),
+				chunks[0],
+			);
+
+			let branch_chunks = Layout::default()
+				.direction(Direction::Horizontal)
+				.constraints([
+					Constraint::Length(12),
+					Constraint::Min(1),
+				])
+				.split(chunks[1]);
+			f.render_widget(
+				Paragraph::new(Span::styled(
+					"Branch:",
+					self.theme.text(true, false),
+				)),
+				branch_chunks[0],
+			);
+			self.branch.draw(f, branch_chunks[1])?;
+
+			if let Some(error) = &self.error {
+				f.render_widget(
+					Paragraph::new(Span::styled(
+						error.clone(),
+						self.theme.text_danger(),
+					)),
+					chunks[3],
+				);
+			}
+
+			f.render_widget(
+				Paragraph::new(self.get_hints()),
+				chunks[4],
+			);
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for InitRepoPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			out.push(
+				CommandInfo::new(
+					strings::commands::init_repo_quit(
+						&self.key_config,
+					),
+					true,
+					true,
+				)
+				.order(1),
+			);
+
+			out.push(
+				CommandInfo::new(
+					strings::commands::init_repo_confirm(
+						&self.key_config,
+					),
+					true,
+					true,
+				)
+				.order(1),
+			);
+
+			out.push(
+				CommandInfo::new(
+					strings::commands::clone_popup(&self.key_config),
+					true,
+					true,
+				)
+				.order(1),
+			);
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, event: &Event) -> Result<EventState> {
+		if self.is_visible() {
+			if let Event::Key(key) = &event {
+				if key_match(key, self.key_config.keys.exit_popup) {
+					self.hide();
+				} else if key_match(key, self.key_config.keys.enter) {
+					self.init();
+				} else if self.branch.event(event)?.is_consumed() {
+					self.error = None;
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
@@ -8,13 +8,17 @@ use crate::{
 	app::Environment,
 	keys::{key_match, SharedKeyConfig},
 	options::SharedOptions,
-	queue::{InternalEvent, Queue, StackablePopupOpen},
+	queue::{ExternalTool, InternalEvent, Queue, StackablePopupOpen},
 	strings,
 };
 use anyhow::Result;
 use asyncgit::{
-	sync::{CommitId, CommitTags},
-	AsyncDiff, AsyncGitNotification, DiffParams, DiffType,
+	sync::{
+		commit_parent_count, CommitId, CommitTags, CompareMode,
+		DiffToolTarget, MergeDiffMode, RepoPathRef,
+	},
+	AsyncDiff, AsyncGitNotification, CommitFilesParams, DiffParams,
+	DiffType,
 };
 use crossterm::event::Event;
 use ratatui::{
@@ -30,6 +34,8 @@ pub struct InspectCommitOpen {
 	pub commit_id: CommitId,
 	/// in case we wanna compare
 	pub compare_id: Option<CommitId>,
+	/// range semantics of the comparison, keeps the last used if `None`
+	pub compare_mode: Option<CompareMode>,
 	pub tags: Option<CommitTags>,
 }
 
@@ -38,6 +44,7 @@ impl InspectCommitOpen {
 		Self {
 			commit_id,
 			compare_id: None,
+			compare_mode: None,
 			tags: None,
 		}
 	}
@@ -49,17 +56,21 @@ impl InspectCommitOpen {
 		Self {
 			commit_id,
 			compare_id: None,
+			compare_mode: None,
 			tags,
 		}
 	}
 }
 
 pub struct InspectCommitPopup {
+	repo: RepoPathRef,
 	queue: Queue,
 	open_request: Option<InspectCommitOpen>,
 	diff: DiffComponent,
 	details: CommitDetailsComponent,
 	git_diff: AsyncDiff,
+	parent_count: usize,
+	merge_mode: MergeDiffMode,
 	visible: bool,
 	key_config: SharedKeyConfig,
 	options: SharedOptions,
@@ -136,6 +147,18 @@ impl Component for InspectCommitPopup {
 				true,
 				true,
 			));
+
+			out.push(CommandInfo::new(
+				strings::commands::diff_merge_mode(&self.key_config),
+				true,
+				self.is_merge() || force_all,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::open_diff_tool(&self.key_config),
+				self.diff_tool().is_some(),
+				true,
+			));
 		}
 
 		visibility_blocking(self)
@@ -171,6 +194,21 @@ impl Component for InspectCommitPopup {
 				} else if key_match(e, self.key_config.keys.move_left)
 				{
 					self.hide_stacked(false);
+				} else if key_match(
+					e,
+					self.key_config.keys.diff_merge_mode,
+				) && self.is_merge()
+				{
+					self.switch_merge_mode()?;
+				} else if key_match(
+					e,
+					self.key_config.keys.open_diff_tool,
+				) {
+					if let Some(tool) = self.diff_tool() {
+						self.queue.push(
+							InternalEvent::OpenExternalTool(tool),
+						);
+					}
 				} else if key_match(
 					e,
 					self.key_config.keys.open_file_tree,
@@ -220,6 +258,7 @@ impl InspectCommitPopup {
 	///
 	pub fn new(env: &Environment) -> Self {
 		Self {
+			repo: env.repo.clone(),
 			queue: env.queue.clone(),
 			details: CommitDetailsComponent::new(env),
 			diff: DiffComponent::new(env, true),
@@ -228,6 +267,8 @@ impl InspectCommitPopup {
 				env.repo.borrow().clone(),
 				&env.sender_git,
 			),
+			parent_count: 0,
+			merge_mode: MergeDiffMode::default(),
 			visible: false,
 			key_config: env.key_config.clone(),
 			options: env.options.clone(),
@@ -236,6 +277,12 @@ impl InspectCommitPopup {
 
 	///
 	pub fn open(&mut self, open: InspectCommitOpen) -> Result<()> {
+		self.parent_count = if open.compare_id.is_some() {
+			0
+		} else {
+			commit_parent_count(&self.repo.borrow(), open.commit_id)?
+		};
+		self.merge_mode = MergeDiffMode::default();
 		self.open_request = Some(open);
 		self.show()?;
 
@@ -257,6 +304,13 @@ impl InspectCommitPopup {
 				self.update()?;
 			} else if ev == AsyncGitNotification::Diff {
 				self.update_diff()?;
+			} else if matches!(
+				ev,
+				AsyncGitNotification::Describe
+					| AsyncGitNotification::Signatures
+					| AsyncGitNotification::Cherry
+			) {
+				self.details.update_git(ev);
 			}
 		}
 
@@ -269,12 +323,28 @@ impl InspectCommitPopup {
 			if let Some(request) = &self.open_request {
 				if let Some(f) = self.details.files().selection_file()
 				{
+					let plain_diff =
+						self.merge_mode == MergeDiffMode::default();
+					self.diff.set_commit(
+						plain_diff.then_some(request.commit_id),
+					);
+
+					let diff_type = if plain_diff {
+						DiffType::Commit(request.commit_id)
+					} else {
+						DiffType::Merge(
+							request.commit_id,
+							self.merge_mode,
+						)
+					};
+
 					let diff_params = DiffParams {
 						path: f.path.clone(),
-						diff_type: DiffType::Commit(
-							request.commit_id,
+						diff_type,
+						options: self.diff.limit_options(
+							&f.path,
+							self.options.borrow().diff_options(),
 						),
-						options: self.options.borrow().diff_options(),
 					};
 
 					if let Some((params, last)) =
@@ -301,19 +371,57 @@ impl InspectCommitPopup {
 	fn update(&mut self) -> Result<()> {
 		if let Some(request) = &self.open_request {
 			self.details.set_commits(
-				Some(request.commit_id.into()),
+				Some(
+					CommitFilesParams::from(request.commit_id)
+						.with_merge_mode(self.merge_mode),
+				),
 				request.tags.as_ref(),
 			)?;
+			self.diff.set_title_info(
+				self.is_merge().then(|| {
+					strings::merge_diff_mode(self.merge_mode)
+				}),
+			);
 			self.update_diff()?;
 		}
 
 		Ok(())
 	}
 
+	const fn is_merge(&self) -> bool {
+		self.parent_count > 1
+	}
+
+	/// cycles through diffing against each parent and the combined diff
+	fn switch_merge_mode(&mut self) -> Result<()> {
+		self.merge_mode = match self.merge_mode {
+			MergeDiffMode::Parent(parent)
+				if parent + 1 < self.parent_count =>
+			{
+				MergeDiffMode::Parent(parent + 1)
+			}
+			MergeDiffMode::Parent(_) => MergeDiffMode::Combined,
+			MergeDiffMode::Combined => MergeDiffMode::default(),
+		};
+
+		self.update()
+	}
+
 	fn can_focus_diff(&self) -> bool {
 		self.details.files().selection_file().is_some()
 	}
 
+	/// selected file in `diff.tool`, merges against their first
+	/// parent
+	fn diff_tool(&self) -> Option<ExternalTool> {
+		Some(ExternalTool::Diff(
+			self.details.files().selection_file()?.path,
+			DiffToolTarget::Commit(
+				self.open_request.as_ref()?.commit_id,
+			),
+		))
+	}
+
 	fn hide_stacked(&mut self, stack: bool) {
 		self.hide();
 
@@ -0,0 +1,329 @@
+use crate::components::{
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState, ScrollType,
+};
+use crate::{
+	app::Environment,
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, Queue},
+	strings::{self, symbol},
+	ui::{self, style::SharedTheme, Size},
+};
+use anyhow::Result;
+use asyncgit::sync::{get_branches_info, get_tags, RepoPathRef};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Constraint, Margin, Rect},
+	text::Span,
+	widgets::{
+		Block, BorderType, Borders, Cell, Clear, Row, Table,
+		TableState,
+	},
+	Frame,
+};
+use std::cell::Cell as StdCell;
+
+struct LogRef {
+	/// full ref name
+	reference: String,
+	kind: &'static str,
+	selected: bool,
+}
+
+/// picks the branches and tags whose history the log shows together
+pub struct LogRefsPopup {
+	repo: RepoPathRef,
+	queue: Queue,
+	visible: bool,
+	refs: Vec<LogRef>,
+	table_state: StdCell<TableState>,
+	current_height: StdCell<usize>,
+	key_config: SharedKeyConfig,
+	theme: SharedTheme,
+}
+
+impl LogRefsPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			repo: env.repo.clone(),
+			queue: env.queue.clone(),
+			visible: false,
+			refs: Vec::new(),
+			table_state: StdCell::new(TableState::default()),
+			current_height: StdCell::new(0),
+			key_config: env.key_config.clone(),
+			theme: env.theme.clone(),
+		}
+	}
+
+	/// list all refs, `selected` (full ref names) checked
+	pub fn open(&mut self, selected: &[String]) -> Result<()> {
+		let repo = self.repo.borrow();
+
+		let branches = get_branches_info(&repo, true)?
+			.into_iter()
+			.map(|b| (b.reference, strings::LOG_REFS_KIND_BRANCH))
+			.chain(get_branches_info(&repo, false)?.into_iter().map(
+				|b| (b.reference, strings::LOG_REFS_KIND_REMOTE),
+			));
+
+		let mut tags = get_tags(&repo)?
+			.into_values()
+			.flatten()
+			.map(|tag| format!("refs/tags/{}", tag.name))
+			.collect::<Vec<_>>();
+		tags.sort();
+
+		self.refs = branches
+			.chain(
+				tags.into_iter()
+					.map(|tag| (tag, strings::LOG_REFS_KIND_TAG)),
+			)
+			.map(|(reference, kind)| LogRef {
+				selected: selected.contains(&reference),
+				reference,
+				kind,
+			})
+			.collect();
+
+		drop(repo);
+
+		self.table_state.get_mut().select(Some(0));
+		self.show()
+	}
+
+	fn selection(&self) -> Option<usize> {
+		let table_state = self.table_state.take();
+		let selection = table_state.selected();
+		self.table_state.set(table_state);
+
+		selection.filter(|s| *s < self.refs.len())
+	}
+
+	fn toggle_selected(&mut self) {
+		if let Some(selection) = self.selection() {
+			let log_ref = &mut self.refs[selection];
+			log_ref.selected = !log_ref.selected;
+		}
+	}
+
+	fn confirm(&mut self) {
+		self.queue.push(InternalEvent::SetLogRefs(
+			self.refs
+				.iter()
+				.filter(|r| r.selected)
+				.map(|r| r.reference.clone())
+				.collect(),
+		));
+
+		self.hide();
+	}
+
+	fn move_selection(&self, scroll_type: ScrollType) {
+		let mut table_state = self.table_state.take();
+
+		let old_selection = table_state.selected().unwrap_or(0);
+		let max_selection = self.refs.len().saturating_sub(1);
+		let page = self.current_height.get().saturating_sub(1);
+
+		let new_selection = match scroll_type {
+			ScrollType::Up => old_selection.saturating_sub(1),
+			ScrollType::Down => {
+				old_selection.saturating_add(1).min(max_selection)
+			}
+			ScrollType::Home => 0,
+			ScrollType::End => max_selection,
+			ScrollType::PageUp => old_selection.saturating_sub(page),
+			ScrollType::PageDown => {
+				old_selection.saturating_add(page).min(max_selection)
+			}
+		};
+
+		table_state.select(Some(new_selection));
+		self.table_state.set(table_state);
+	}
+
+	fn get_rows(&self) -> Vec<Row<'_>> {
+		self.refs
+			.iter()
+			.map(|log_ref| {
+				Row::new(vec![
+					Cell::from(Span::styled(
+						if log_ref.selected {
+							symbol::CHECKMARK
+						} else {
+							symbol::EMPTY_SPACE
+						},
+						self.theme.log_marker(false),
+					)),
+					Cell::from(Span::styled(
+						strings::short_ref_name(&log_ref.reference),
+						self.theme.text(true, false),
+					)),
+					Cell::from(Span::styled(
+						log_ref.kind,
+						self.theme.text(false, false),
+					)),
+				])
+			})
+			.collect()
+	}
+}
+
+impl DrawableComponent for LogRefsPopup {
+	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
+		if self.visible {
+			const PERCENT_SIZE: Size = Size::new(60, 60);
+			const MIN_SIZE: Size = Size::new(40, 20);
+
+			let area = ui::centered_rect(
+				PERCENT_SIZE.width,
+				PERCENT_SIZE.height,
+				f.area(),
+			);
+			let area =
+				ui::rect_inside(MIN_SIZE, f.area().into(), area);
+			let area = area.intersection(rect);
+
+			let constraints = [
+				// checkmark
+				Constraint::Length(1),
+				Constraint::Percentage(100),
+				// kind
+				Constraint::Length(6),
+			];
+
+			let rows = self.get_rows();
+			let number_of_rows = rows.len();
+
+			let table = Table::new(rows, constraints)
+				.column_spacing(1)
+				.row_highlight_style(self.theme.text(true, true))
+				.block(
+					Block::default()
+						.borders(Borders::ALL)
+						.title(Span::styled(
+							strings::POPUP_TITLE_LOG_REFS,
+							self.theme.title(true),
+						))
+						.border_style(self.theme.block(true))
+						.border_type(BorderType::Thick),
+				);
+
+			let mut table_state = self.table_state.take();
+
+			f.render_widget(Clear, area);
+			f.render_stateful_widget(table, area, &mut table_state);
+
+			let area = area.inner(Margin {
+				vertical: 1,
+				horizontal: 0,
+			});
+
+			ui::draw_scrollbar(
+				f,
+				area,
+				&self.theme,
+				number_of_rows,
+				table_state.selected().unwrap_or(0),
+				ui::Orientation::Vertical,
+			);
+
+			self.table_state.set(table_state);
+			self.current_height.set(area.height.into());
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for LogRefsPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.visible || force_all {
+			if !force_all {
+				out.clear();
+			}
+
+			out.push(CommandInfo::new(
+				strings::commands::scroll(&self.key_config),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::close_popup(&self.key_config),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::log_refs_toggle(&self.key_config),
+				self.selection().is_some(),
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::log_refs_confirm(&self.key_config),
+				true,
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, event: &Event) -> Result<EventState> {
+		if self.visible {
+			if let Event::Key(key) = event {
+				let keys = &self.key_config.keys;
+
+				if key_match(key, keys.exit_popup) {
+					self.hide();
+				} else if key_match(key, keys.move_up) {
+					self.move_selection(ScrollType::Up);
+				} else if key_match(key, keys.move_down) {
+					self.move_selection(ScrollType::Down);
+				} else if key_match(key, keys.shift_up)
+					|| key_match(key, keys.home)
+				{
+					self.move_selection(ScrollType::Home);
+				} else if key_match(key, keys.shift_down)
+					|| key_match(key, keys.end)
+				{
+					self.move_selection(ScrollType::End);
+				} else if key_match(key, keys.page_down) {
+					self.move_selection(ScrollType::PageDown);
+				} else if key_match(key, keys.page_up) {
+					self.move_selection(ScrollType::PageUp);
+				} else if key_match(key, keys.log_mark_commit) {
+					self.toggle_selected();
+				} else if key_match(key, keys.enter) {
+					self.confirm();
+				}
+			}
+
+			Ok(EventState::Consumed)
+		} else {
+			Ok(EventState::NotConsumed)
+		}
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
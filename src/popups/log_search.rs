@@ -33,6 +33,8 @@ enum Selection {
 	MessageBodySearch,
 	FilenameSearch,
 	AuthorsSearch,
+	DiffOccurrencesSearch,
+	DiffRegexSearch,
 }
 
 enum PopupMode {
@@ -106,7 +108,8 @@ impl LogSearchPopupPopup {
 			PopupMode::JumpCommitSha => {
 				self.mode = PopupMode::JumpCommitSha;
 				self.jump_commit_id = None;
-				self.find_text.set_default_msg("commit sha".into());
+				self.find_text
+					.set_default_msg("commit sha or revision".into());
 				self.find_text.enabled(false);
 				self.selection = Selection::EnterText;
 			}
@@ -123,14 +126,7 @@ impl LogSearchPopupPopup {
 		match self.mode {
 			PopupMode::Search => {
 				self.queue.push(InternalEvent::CommitSearch(
-					LogFilterSearchOptions {
-						fields: self.options.0,
-						options: self.options.1,
-						search_pattern: self
-							.find_text
-							.get_text()
-							.to_string(),
-					},
+					self.search_options(),
 				));
 			}
 			PopupMode::JumpCommitSha => {
@@ -143,10 +139,19 @@ impl LogSearchPopupPopup {
 		}
 	}
 
+	fn search_options(&self) -> LogFilterSearchOptions {
+		LogFilterSearchOptions {
+			fields: self.options.0,
+			options: self.options.1,
+			search_pattern: self.find_text.get_text().to_string(),
+		}
+	}
+
 	fn is_valid(&self) -> bool {
 		match self.mode {
 			PopupMode::Search => {
 				!self.find_text.get_text().trim().is_empty()
+					&& self.search_options().is_valid()
 			}
 			PopupMode::JumpCommitSha => self.jump_commit_id.is_some(),
 		}
@@ -164,6 +169,7 @@ impl LogSearchPopupPopup {
 		}
 	}
 
+	#[allow(clippy::too_many_lines)]
 	fn get_text_options(&self) -> Vec<Line> {
 		let x_summary =
 			if self.options.0.contains(SearchFields::MESSAGE_SUMMARY)
@@ -194,6 +200,23 @@ impl LogSearchPopupPopup {
 				" "
 			};
 
+		let x_diff_occurrences = if self
+			.options
+			.0
+			.contains(SearchFields::DIFF_OCCURRENCES)
+		{
+			"X"
+		} else {
+			" "
+		};
+
+		let x_diff_regex =
+			if self.options.0.contains(SearchFields::DIFF_REGEX) {
+				"X"
+			} else {
+				" "
+			};
+
 		let x_opt_fuzzy =
 			if self.options.1.contains(SearchOptions::FUZZY_SEARCH) {
 				"X"
@@ -264,6 +287,30 @@ impl LogSearchPopupPopup {
 					false,
 				),
 			)]),
+			Line::from(vec![Span::styled(
+				format!(
+					"[{x_diff_occurrences}] changed occurrences in diff (-S)",
+				),
+				self.theme.text(
+					matches!(
+						self.selection,
+						Selection::DiffOccurrencesSearch
+					),
+					false,
+				),
+			)]),
+			Line::from(vec![Span::styled(
+				format!(
+					"[{x_diff_regex}] changed lines matching regex (-G)",
+				),
+				self.theme.text(
+					matches!(
+						self.selection,
+						Selection::DiffRegexSearch
+					),
+					false,
+				),
+			)]),
 		]
 	}
 
@@ -306,6 +353,24 @@ impl LogSearchPopupPopup {
 			Selection::AuthorsSearch => {
 				self.options.0.toggle(SearchFields::AUTHORS);
 
+				if self.options.0.is_empty() {
+					self.options
+						.0
+						.set(SearchFields::DIFF_OCCURRENCES, true);
+				}
+			}
+			Selection::DiffOccurrencesSearch => {
+				self.options.0.toggle(SearchFields::DIFF_OCCURRENCES);
+
+				if self.options.0.is_empty() {
+					self.options
+						.0
+						.set(SearchFields::DIFF_REGEX, true);
+				}
+			}
+			Selection::DiffRegexSearch => {
+				self.options.0.toggle(SearchFields::DIFF_REGEX);
+
 				if self.options.0.is_empty() {
 					self.options
 						.0
@@ -319,7 +384,7 @@ impl LogSearchPopupPopup {
 		if arg {
 			//up
 			self.selection = match self.selection {
-				Selection::EnterText => Selection::AuthorsSearch,
+				Selection::EnterText => Selection::DiffRegexSearch,
 				Selection::FuzzyOption => Selection::EnterText,
 				Selection::CaseOption => Selection::FuzzyOption,
 				Selection::SummarySearch => Selection::CaseOption,
@@ -330,6 +395,12 @@ impl LogSearchPopupPopup {
 					Selection::MessageBodySearch
 				}
 				Selection::AuthorsSearch => Selection::FilenameSearch,
+				Selection::DiffOccurrencesSearch => {
+					Selection::AuthorsSearch
+				}
+				Selection::DiffRegexSearch => {
+					Selection::DiffOccurrencesSearch
+				}
 			};
 		} else {
 			self.selection = match self.selection {
@@ -343,7 +414,13 @@ impl LogSearchPopupPopup {
 					Selection::FilenameSearch
 				}
 				Selection::FilenameSearch => Selection::AuthorsSearch,
-				Selection::AuthorsSearch => Selection::EnterText,
+				Selection::AuthorsSearch => {
+					Selection::DiffOccurrencesSearch
+				}
+				Selection::DiffOccurrencesSearch => {
+					Selection::DiffRegexSearch
+				}
+				Selection::DiffRegexSearch => Selection::EnterText,
 			};
 		}
 
@@ -356,7 +433,7 @@ impl LogSearchPopupPopup {
 		f: &mut Frame,
 		area: Rect,
 	) -> Result<()> {
-		const SIZE: (u16, u16) = (60, 10);
+		const SIZE: (u16, u16) = (60, 12);
 		let area = ui::centered_rect_absolute(SIZE.0, SIZE.1, area);
 
 		f.render_widget(Clear, area);
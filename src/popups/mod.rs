@@ -1,62 +1,130 @@
+mod apply_patch;
+mod bisect;
 mod blame_file;
+mod blame_ignore_revs;
 mod branchlist;
+mod clean;
+mod clone;
 mod commit;
+mod commit_note;
 mod compare_commits;
 mod confirm;
+mod conflict_editor;
 mod create_branch;
 mod create_remote;
+mod discarded;
+mod edit_hunk;
 mod externaleditor;
 mod fetch;
+mod fetch_options;
 mod file_revlog;
+mod flow;
+mod force_push;
+mod format_patch;
 mod fuzzy_find;
+mod git_config;
+mod git_config_edit;
 mod help;
+mod hook_progress;
+mod ignore_edit;
+mod ignore_pattern;
+mod init_repo;
 mod inspect_commit;
+mod log_refs;
 mod log_search;
 mod msg;
+mod notes_sync;
 mod options;
+mod pre_commit;
 mod pull;
+mod pull_options;
 mod push;
 mod push_tags;
+mod rebase_interactive;
+mod reflog;
+mod remote_target;
 mod remotelist;
 mod rename_branch;
 mod rename_remote;
 mod reset;
+mod restore_file;
+mod revert_merge;
+mod revision;
 mod revision_files;
+mod signing_key;
+mod stash_worktree;
 mod stashmsg;
 mod submodules;
+mod symrefs;
 mod tag_commit;
 mod taglist;
+mod undo;
 mod update_remote_url;
+mod worktrees;
 
+pub use apply_patch::ApplyPatchPopup;
+pub use bisect::BisectPopup;
 pub use blame_file::{BlameFileOpen, BlameFilePopup};
+pub use blame_ignore_revs::BlameIgnoreRevsPopup;
 pub use branchlist::BranchListPopup;
+pub use clean::CleanPopup;
+pub use clone::ClonePopup;
 pub use commit::CommitPopup;
+pub use commit_note::CommitNotePopup;
 pub use compare_commits::CompareCommitsPopup;
 pub use confirm::ConfirmPopup;
+pub use conflict_editor::ConflictEditorPopup;
 pub use create_branch::CreateBranchPopup;
 pub use create_remote::CreateRemotePopup;
+pub use discarded::DiscardedPopup;
+pub use edit_hunk::EditHunkPopup;
 pub use externaleditor::ExternalEditorPopup;
 pub use fetch::FetchPopup;
+pub use fetch_options::FetchOptionsPopup;
 pub use file_revlog::{FileRevOpen, FileRevlogPopup};
+pub use flow::FlowPopup;
+pub use force_push::ForcePushPopup;
+pub use format_patch::FormatPatchPopup;
 pub use fuzzy_find::FuzzyFindPopup;
+pub use git_config::GitConfigPopup;
+pub use git_config_edit::GitConfigEditPopup;
 pub use help::HelpPopup;
+pub use hook_progress::HookProgressPopup;
+pub use ignore_edit::IgnoreEditPopup;
+pub use ignore_pattern::IgnorePatternPopup;
+pub use init_repo::InitRepoPopup;
 pub use inspect_commit::{InspectCommitOpen, InspectCommitPopup};
+pub use log_refs::LogRefsPopup;
 pub use log_search::LogSearchPopupPopup;
 pub use msg::MsgPopup;
+pub use notes_sync::NotesSyncPopup;
 pub use options::{AppOption, OptionsPopup};
+pub use pre_commit::{PreCommitPopup, PreCommitResults};
 pub use pull::PullPopup;
+pub use pull_options::PullOptionsPopup;
 pub use push::PushPopup;
 pub use push_tags::PushTagsPopup;
+pub use rebase_interactive::RebaseInteractivePopup;
+pub use reflog::ReflogPopup;
+pub use remote_target::RemoteTargetPopup;
 pub use remotelist::RemoteListPopup;
 pub use rename_branch::RenameBranchPopup;
 pub use rename_remote::RenameRemotePopup;
 pub use reset::ResetPopup;
+pub use restore_file::RestoreFilePopup;
+pub use revert_merge::RevertMergePopup;
+pub use revision::{RevisionPopup, RevisionTarget};
 pub use revision_files::{FileTreeOpen, RevisionFilesPopup};
-pub use stashmsg::StashMsgPopup;
+pub use signing_key::SigningKeyPopup;
+pub use stash_worktree::StashWorktreePopup;
+pub use stashmsg::{StashMsgPopup, StashSelection};
 pub use submodules::SubmodulesListPopup;
+pub use symrefs::SymRefsPopup;
 pub use tag_commit::TagCommitPopup;
 pub use taglist::TagListPopup;
+pub use undo::{record_undo, UndoPopup};
 pub use update_remote_url::UpdateRemoteUrlPopup;
+pub use worktrees::WorktreesPopup;
 
 use crate::ui::style::Theme;
 use ratatui::{
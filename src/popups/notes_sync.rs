@@ -0,0 +1,233 @@
+use crate::{
+	app::Environment,
+	components::{
+		visibility_blocking, CommandBlocking, CommandInfo, Component,
+		CredComponent, DrawableComponent, EventState,
+	},
+	keys::SharedKeyConfig,
+	queue::{InternalEvent, NeedsUpdate, Queue},
+	strings,
+	ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::{
+	asyncjob::AsyncSingleJob,
+	sync::{
+		self,
+		cred::{
+			credential_url, extract_username_password,
+			need_username_password, BasicAuthCredential,
+		},
+		RepoPathRef,
+	},
+	AsyncGitNotification, AsyncNotesSyncJob, NotesSync,
+};
+use crossterm::event::Event;
+use ratatui::{
+	layout::Rect,
+	text::Span,
+	widgets::{Block, BorderType, Borders, Clear, Paragraph},
+	Frame,
+};
+
+/// pushes or fetches the notes ref to/from the default remote
+pub struct NotesSyncPopup {
+	repo: RepoPathRef,
+	visible: bool,
+	sync: NotesSync,
+	remote: String,
+	job: AsyncSingleJob<AsyncNotesSyncJob>,
+	queue: Queue,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+	input_cred: CredComponent,
+}
+
+impl NotesSyncPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			queue: env.queue.clone(),
+			visible: false,
+			sync: NotesSync::Push,
+			remote: String::new(),
+			job: AsyncSingleJob::new(env.sender_git.clone()),
+			input_cred: CredComponent::new(env),
+			theme: env.theme.clone(),
+			key_config: env.key_config.clone(),
+			repo: env.repo.clone(),
+		}
+	}
+
+	///
+	pub fn open(&mut self, sync: NotesSync) -> Result<()> {
+		let repo = self.repo.borrow().clone();
+
+		self.sync = sync;
+		self.remote = match sync {
+			NotesSync::Push => {
+				sync::get_default_remote_for_push(&repo)
+			}
+			NotesSync::Fetch => {
+				sync::get_default_remote_for_fetch(&repo)
+			}
+		}?;
+
+		self.show()?;
+
+		if need_username_password(&repo)? {
+			let cred = extract_username_password(&repo)
+				.unwrap_or_else(|_| {
+					BasicAuthCredential::new(None, None)
+				});
+			if cred.is_complete() {
+				self.start(Some(cred));
+			} else {
+				self.input_cred.set_cred(
+					cred,
+					credential_url(&repo).unwrap_or_default(),
+				);
+				self.input_cred.show()?;
+			}
+		} else if sync::need_ssh_passphrase(&repo, &self.remote)
+			.unwrap_or_default()
+		{
+			self.input_cred.set_passphrase();
+			self.input_cred.show()?;
+		} else {
+			self.start(None);
+		}
+
+		Ok(())
+	}
+
+	fn start(&self, cred: Option<BasicAuthCredential>) {
+		self.job.spawn(AsyncNotesSyncJob::new(
+			self.repo.borrow().clone(),
+			self.sync,
+			self.remote.clone(),
+			cred,
+		));
+	}
+
+	///
+	pub fn any_work_pending(&self) -> bool {
+		self.job.is_pending()
+	}
+
+	///
+	pub fn update_git(&mut self, ev: AsyncGitNotification) {
+		if self.is_visible() && ev == AsyncGitNotification::NotesSync
+		{
+			self.update();
+		}
+	}
+
+	fn update(&mut self) {
+		if self.job.is_pending() {
+			return;
+		}
+
+		if let Some(Err(e)) =
+			self.job.take_last().and_then(|job| job.result())
+		{
+			self.queue.push(InternalEvent::ShowErrorMsg(format!(
+				"{} error:\n{e}",
+				strings::notes_sync_title(self.sync)
+			)));
+		}
+
+		self.hide();
+		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+	}
+}
+
+impl DrawableComponent for NotesSyncPopup {
+	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
+		if self.visible {
+			let area = ui::centered_rect_absolute(30, 3, f.area());
+
+			f.render_widget(Clear, area);
+			f.render_widget(
+				Paragraph::new(Span::styled(
+					strings::notes_sync_msg(self.sync, &self.remote),
+					self.theme.text(true, false),
+				))
+				.block(
+					Block::default()
+						.title(Span::styled(
+							strings::notes_sync_title(self.sync),
+							self.theme.title(true),
+						))
+						.borders(Borders::ALL)
+						.border_type(BorderType::Thick)
+						.border_style(self.theme.block(true)),
+				),
+				area,
+			);
+			self.input_cred.draw(f, rect)?;
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for NotesSyncPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			if !force_all {
+				out.clear();
+			}
+
+			if self.input_cred.is_visible() {
+				return self.input_cred.commands(out, force_all);
+			}
+			out.push(CommandInfo::new(
+				strings::commands::close_msg(&self.key_config),
+				!self.job.is_pending(),
+				self.visible,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.visible {
+			if let Event::Key(_) = ev {
+				if self.input_cred.is_visible() {
+					self.input_cred.event(ev)?;
+
+					if self.input_cred.get_cred().is_complete()
+						|| !self.input_cred.is_visible()
+					{
+						self.start(Some(
+							self.input_cred.get_cred().clone(),
+						));
+						self.input_cred.hide();
+					}
+				}
+			}
+			return Ok(EventState::Consumed);
+		}
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
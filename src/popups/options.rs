@@ -11,7 +11,7 @@ use crate::{
 	ui::{self, style::SharedTheme},
 };
 use anyhow::Result;
-use asyncgit::sync::ShowUntrackedFilesConfig;
+use asyncgit::sync::{DiffAlgorithm, ShowUntrackedFilesConfig};
 use crossterm::event::Event;
 use ratatui::{
 	layout::{Alignment, Rect},
@@ -24,9 +24,14 @@ use ratatui::{
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum AppOption {
 	StatusShowUntracked,
+	StatusDiscardBackup,
 	DiffIgnoreWhitespaces,
+	DiffIgnoreWhitespaceChange,
+	DiffIgnoreBlankLines,
+	DiffAlgorithm,
 	DiffContextLines,
 	DiffInterhunkLines,
+	DiffSyntaxHighlight,
 }
 
 pub struct OptionsPopup {
@@ -74,6 +79,13 @@ impl OptionsPopup {
 			},
 			self.is_select(AppOption::StatusShowUntracked),
 		);
+		self.add_entry(
+			txt,
+			width,
+			"Backup discarded",
+			&self.options.borrow().discard_backup().to_string(),
+			self.is_select(AppOption::StatusDiscardBackup),
+		);
 		Self::add_header(txt, "");
 
 		let diff = self.options.borrow().diff_options();
@@ -85,6 +97,27 @@ impl OptionsPopup {
 			&diff.ignore_whitespace.to_string(),
 			self.is_select(AppOption::DiffIgnoreWhitespaces),
 		);
+		self.add_entry(
+			txt,
+			width,
+			"Ignore whitespace changes",
+			&diff.ignore_whitespace_change.to_string(),
+			self.is_select(AppOption::DiffIgnoreWhitespaceChange),
+		);
+		self.add_entry(
+			txt,
+			width,
+			"Ignore blank lines",
+			&diff.ignore_blank_lines.to_string(),
+			self.is_select(AppOption::DiffIgnoreBlankLines),
+		);
+		self.add_entry(
+			txt,
+			width,
+			"Algorithm",
+			diff.algorithm.map_or("Gitconfig", DiffAlgorithm::as_str),
+			self.is_select(AppOption::DiffAlgorithm),
+		);
 		self.add_entry(
 			txt,
 			width,
@@ -99,6 +132,17 @@ impl OptionsPopup {
 			&diff.interhunk_lines.to_string(),
 			self.is_select(AppOption::DiffInterhunkLines),
 		);
+		self.add_entry(
+			txt,
+			width,
+			"Syntax highlighting",
+			&self
+				.options
+				.borrow()
+				.diff_syntax_highlight()
+				.to_string(),
+			self.is_select(AppOption::DiffSyntaxHighlight),
+		);
 	}
 
 	fn is_select(&self, kind: AppOption) -> bool {
@@ -138,30 +182,60 @@ impl OptionsPopup {
 		if up {
 			self.selection = match self.selection {
 				AppOption::StatusShowUntracked => {
-					AppOption::DiffInterhunkLines
+					AppOption::DiffSyntaxHighlight
 				}
-				AppOption::DiffIgnoreWhitespaces => {
+				AppOption::StatusDiscardBackup => {
 					AppOption::StatusShowUntracked
 				}
-				AppOption::DiffContextLines => {
+				AppOption::DiffIgnoreWhitespaces => {
+					AppOption::StatusDiscardBackup
+				}
+				AppOption::DiffIgnoreWhitespaceChange => {
 					AppOption::DiffIgnoreWhitespaces
 				}
+				AppOption::DiffIgnoreBlankLines => {
+					AppOption::DiffIgnoreWhitespaceChange
+				}
+				AppOption::DiffAlgorithm => {
+					AppOption::DiffIgnoreBlankLines
+				}
+				AppOption::DiffContextLines => {
+					AppOption::DiffAlgorithm
+				}
 				AppOption::DiffInterhunkLines => {
 					AppOption::DiffContextLines
 				}
+				AppOption::DiffSyntaxHighlight => {
+					AppOption::DiffInterhunkLines
+				}
 			};
 		} else {
 			self.selection = match self.selection {
 				AppOption::StatusShowUntracked => {
+					AppOption::StatusDiscardBackup
+				}
+				AppOption::StatusDiscardBackup => {
 					AppOption::DiffIgnoreWhitespaces
 				}
 				AppOption::DiffIgnoreWhitespaces => {
+					AppOption::DiffIgnoreWhitespaceChange
+				}
+				AppOption::DiffIgnoreWhitespaceChange => {
+					AppOption::DiffIgnoreBlankLines
+				}
+				AppOption::DiffIgnoreBlankLines => {
+					AppOption::DiffAlgorithm
+				}
+				AppOption::DiffAlgorithm => {
 					AppOption::DiffContextLines
 				}
 				AppOption::DiffContextLines => {
 					AppOption::DiffInterhunkLines
 				}
 				AppOption::DiffInterhunkLines => {
+					AppOption::DiffSyntaxHighlight
+				}
+				AppOption::DiffSyntaxHighlight => {
 					AppOption::StatusShowUntracked
 				}
 			};
@@ -192,11 +266,29 @@ impl OptionsPopup {
 						.borrow_mut()
 						.set_status_show_untracked(untracked);
 				}
+				AppOption::StatusDiscardBackup => {
+					self.options.borrow_mut().toggle_discard_backup();
+				}
 				AppOption::DiffIgnoreWhitespaces => {
 					self.options
 						.borrow_mut()
 						.diff_toggle_whitespace();
 				}
+				AppOption::DiffIgnoreWhitespaceChange => {
+					self.options
+						.borrow_mut()
+						.diff_toggle_whitespace_change();
+				}
+				AppOption::DiffIgnoreBlankLines => {
+					self.options
+						.borrow_mut()
+						.diff_toggle_blank_lines();
+				}
+				AppOption::DiffAlgorithm => {
+					self.options
+						.borrow_mut()
+						.diff_algorithm_change(true);
+				}
 				AppOption::DiffContextLines => {
 					self.options
 						.borrow_mut()
@@ -207,6 +299,11 @@ impl OptionsPopup {
 						.borrow_mut()
 						.diff_hunk_lines_change(true);
 				}
+				AppOption::DiffSyntaxHighlight => {
+					self.options
+						.borrow_mut()
+						.diff_toggle_syntax_highlight();
+				}
 			};
 		} else {
 			match self.selection {
@@ -231,11 +328,29 @@ impl OptionsPopup {
 						.borrow_mut()
 						.set_status_show_untracked(untracked);
 				}
+				AppOption::StatusDiscardBackup => {
+					self.options.borrow_mut().toggle_discard_backup();
+				}
 				AppOption::DiffIgnoreWhitespaces => {
 					self.options
 						.borrow_mut()
 						.diff_toggle_whitespace();
 				}
+				AppOption::DiffIgnoreWhitespaceChange => {
+					self.options
+						.borrow_mut()
+						.diff_toggle_whitespace_change();
+				}
+				AppOption::DiffIgnoreBlankLines => {
+					self.options
+						.borrow_mut()
+						.diff_toggle_blank_lines();
+				}
+				AppOption::DiffAlgorithm => {
+					self.options
+						.borrow_mut()
+						.diff_algorithm_change(false);
+				}
 				AppOption::DiffContextLines => {
 					self.options
 						.borrow_mut()
@@ -246,6 +361,11 @@ impl OptionsPopup {
 						.borrow_mut()
 						.diff_hunk_lines_change(false);
 				}
+				AppOption::DiffSyntaxHighlight => {
+					self.options
+						.borrow_mut()
+						.diff_toggle_syntax_highlight();
+				}
 			};
 		}
 
@@ -257,7 +377,7 @@ impl OptionsPopup {
 impl DrawableComponent for OptionsPopup {
 	fn draw(&self, f: &mut Frame, area: Rect) -> Result<()> {
 		if self.is_visible() {
-			const SIZE: (u16, u16) = (50, 10);
+			const SIZE: (u16, u16) = (50, 15);
 			let area =
 				ui::centered_rect_absolute(SIZE.0, SIZE.1, area);
 
@@ -0,0 +1,330 @@
+use crate::{
+	app::Environment,
+	components::{
+		visibility_blocking, CommandBlocking, CommandInfo, Component,
+		DrawableComponent, EventState,
+	},
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, NeedsUpdate, Queue},
+	strings,
+	ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::{
+	sync::{self, PreCommitCheck, PreCommitCheckStatus, RepoPathRef},
+	DiffLineType,
+};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Constraint, Direction, Layout, Rect},
+	style::Style,
+	text::{Line, Span, Text},
+	widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+	Frame,
+};
+use std::path::Path;
+
+/// failed run of the pre-commit framework
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PreCommitResults {
+	///
+	pub checks: Vec<PreCommitCheck>,
+	/// staged files the hooks changed in the working dir
+	pub modified_files: Vec<String>,
+	/// diff of those changes
+	pub patch: String,
+}
+
+/// shows the per check results of the pre-commit framework
+pub struct PreCommitPopup {
+	repo: RepoPathRef,
+	queue: Queue,
+	visible: bool,
+	results: PreCommitResults,
+	selection: usize,
+	scroll: u16,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl PreCommitPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			repo: env.repo.clone(),
+			queue: env.queue.clone(),
+			visible: false,
+			results: PreCommitResults::default(),
+			selection: 0,
+			scroll: 0,
+			theme: env.theme.clone(),
+			key_config: env.key_config.clone(),
+		}
+	}
+
+	///
+	pub fn open(&mut self, results: PreCommitResults) -> Result<()> {
+		// start on the first failing check
+		self.selection = results
+			.checks
+			.iter()
+			.position(|c| c.status == PreCommitCheckStatus::Failed)
+			.unwrap_or_default();
+		self.scroll = 0;
+		self.results = results;
+
+		self.show()
+	}
+
+	fn move_selection(&mut self, up: bool) {
+		let max = self.results.checks.len().saturating_sub(1);
+
+		self.selection = if up {
+			self.selection.saturating_sub(1)
+		} else {
+			self.selection.saturating_add(1).min(max)
+		};
+		self.scroll = 0;
+	}
+
+	fn restage(&mut self) -> Result<()> {
+		for path in &self.results.modified_files {
+			sync::stage_add_file(
+				&self.repo.borrow(),
+				Path::new(path),
+			)?;
+		}
+
+		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+		self.hide();
+
+		Ok(())
+	}
+
+	fn status_style(&self, status: PreCommitCheckStatus) -> Style {
+		match status {
+			PreCommitCheckStatus::Passed => {
+				self.theme.diff_line(DiffLineType::Add, false)
+			}
+			PreCommitCheckStatus::Failed => self.theme.text_danger(),
+			PreCommitCheckStatus::Skipped => {
+				self.theme.text(false, false)
+			}
+		}
+	}
+
+	fn checks_text(&self) -> Text<'_> {
+		Text::from(
+			self.results
+				.checks
+				.iter()
+				.enumerate()
+				.map(|(idx, check)| {
+					let selected = idx == self.selection;
+					let mut spans = vec![
+						Span::styled(
+							format!(
+								"{:<8}",
+								strings::pre_commit_status(
+									check.status
+								)
+							),
+							self.status_style(check.status),
+						),
+						Span::styled(
+							check.name.as_str(),
+							self.theme.text(true, selected),
+						),
+					];
+					if let Some(note) = &check.note {
+						spans.push(Span::styled(
+							format!(" ({note})"),
+							self.theme.text(false, selected),
+						));
+					}
+					Line::from(spans)
+				})
+				.collect::<Vec<_>>(),
+		)
+	}
+
+	fn details_text(&self) -> Text<'_> {
+		let Some(check) = self.results.checks.get(self.selection)
+		else {
+			return Text::default();
+		};
+
+		let text = self.theme.text(true, false);
+		let mut lines = Vec::new();
+
+		if let Some(id) = &check.hook_id {
+			lines.push(Line::styled(
+				strings::pre_commit_hook_id(id),
+				self.theme.text(false, false),
+			));
+		}
+
+		lines.extend(
+			check.output.lines().map(|l| Line::styled(l, text)),
+		);
+
+		if check.files_modified && !self.results.patch.is_empty() {
+			lines.push(Line::default());
+			lines.push(Line::styled(
+				strings::PRE_COMMIT_AUTO_FIXES,
+				self.theme.title(true),
+			));
+			lines.extend(self.results.patch.lines().map(|l| {
+				let typ = if l.starts_with("+++")
+					|| l.starts_with("---")
+					|| l.starts_with("@@")
+				{
+					DiffLineType::Header
+				} else if l.starts_with('+') {
+					DiffLineType::Add
+				} else if l.starts_with('-') {
+					DiffLineType::Delete
+				} else {
+					DiffLineType::None
+				};
+				Line::styled(l, self.theme.diff_line(typ, false))
+			}));
+		}
+
+		Text::from(lines)
+	}
+}
+
+impl DrawableComponent for PreCommitPopup {
+	fn draw(&self, f: &mut Frame, _rect: Rect) -> Result<()> {
+		if self.visible {
+			let area = ui::centered_rect(70, 70, f.area());
+
+			let list_height =
+				u16::try_from(self.results.checks.len())
+					.unwrap_or(u16::MAX)
+					.min(area.height / 2)
+					.saturating_add(2);
+
+			let chunks = Layout::default()
+				.direction(Direction::Vertical)
+				.constraints(
+					[
+						Constraint::Length(list_height),
+						Constraint::Min(1),
+					]
+					.as_ref(),
+				)
+				.split(area);
+
+			let block = |title: &'static str| {
+				Block::default()
+					.title(Span::styled(
+						title,
+						self.theme.title(true),
+					))
+					.borders(Borders::ALL)
+					.border_type(BorderType::Thick)
+					.border_style(self.theme.block(true))
+			};
+
+			let list_inner = list_height.saturating_sub(2);
+			let selection =
+				u16::try_from(self.selection).unwrap_or_default();
+
+			f.render_widget(Clear, area);
+			f.render_widget(
+				Paragraph::new(self.checks_text())
+					.block(block(strings::POPUP_TITLE_PRE_COMMIT))
+					.scroll((
+						selection.saturating_sub(
+							list_inner.saturating_sub(1),
+						),
+						0,
+					)),
+				chunks[0],
+			);
+			f.render_widget(
+				Paragraph::new(self.details_text())
+					.block(block(strings::PRE_COMMIT_DETAILS))
+					.wrap(Wrap { trim: false })
+					.scroll((self.scroll, 0)),
+				chunks[1],
+			);
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for PreCommitPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.visible || force_all {
+			out.push(CommandInfo::new(
+				strings::commands::scroll(&self.key_config),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::pre_commit_restage(
+					&self.key_config,
+				),
+				!self.results.modified_files.is_empty(),
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::close_popup(&self.key_config),
+				true,
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.visible {
+			if let Event::Key(e) = ev {
+				let keys = &self.key_config.keys;
+
+				if key_match(e, keys.exit_popup) {
+					self.hide();
+				} else if key_match(e, keys.move_up) {
+					self.move_selection(true);
+				} else if key_match(e, keys.move_down) {
+					self.move_selection(false);
+				} else if key_match(e, keys.page_down) {
+					self.scroll = self.scroll.saturating_add(10);
+				} else if key_match(e, keys.page_up) {
+					self.scroll = self.scroll.saturating_sub(10);
+				} else if key_match(e, keys.stage_unstage_item)
+					&& !self.results.modified_files.is_empty()
+				{
+					self.restage()?;
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
@@ -4,10 +4,10 @@ use crate::{
 		visibility_blocking, CommandBlocking, CommandInfo, Component,
 		CredComponent, DrawableComponent, EventState,
 	},
-	keys::SharedKeyConfig,
+	keys::{key_match, SharedKeyConfig},
 	popups::PushPopup,
 	queue::{Action, InternalEvent, Queue},
-	strings, try_or_popup,
+	strings,
 	ui::{self, style::SharedTheme},
 };
 use anyhow::Result;
@@ -15,11 +15,13 @@ use asyncgit::{
 	sync::{
 		self,
 		cred::{
+			credential_url_for_fetch,
 			extract_username_password_for_fetch,
 			need_username_password_for_fetch, BasicAuthCredential,
 		},
+		need_ssh_passphrase,
 		remotes::get_default_remote_for_fetch,
-		RepoPathRef,
+		PullOptions, PullStrategy, RemoteTarget, RepoPathRef,
 	},
 	AsyncGitNotification, AsyncPull, FetchRequest, RemoteProgress,
 };
@@ -40,6 +42,8 @@ pub struct PullPopup {
 	progress: Option<RemoteProgress>,
 	pending: bool,
 	branch: String,
+	options: PullOptions,
+	target: Option<RemoteTarget>,
 	queue: Queue,
 	theme: SharedTheme,
 	key_config: SharedKeyConfig,
@@ -55,6 +59,8 @@ impl PullPopup {
 			pending: false,
 			visible: false,
 			branch: String::new(),
+			options: PullOptions::default(),
+			target: None,
 			git_fetch: AsyncPull::new(
 				env.repo.borrow().clone(),
 				&env.sender_git,
@@ -66,9 +72,39 @@ impl PullPopup {
 		}
 	}
 
-	///
-	pub fn fetch(&mut self, branch: String) -> Result<()> {
+	/// fetches and integrates the upstream of `branch`, using the
+	/// configured pull strategy unless `options` are given
+	pub fn fetch(
+		&mut self,
+		branch: String,
+		options: Option<PullOptions>,
+	) -> Result<()> {
+		self.options = match options {
+			Some(options) => options,
+			None => sync::pull_options_config(&self.repo.borrow())?,
+		};
 		self.branch = branch;
+		self.target = None;
+
+		self.start()
+	}
+
+	/// fetches the remote branch of `target` and integrates it into
+	/// `branch` using the configured pull strategy
+	pub fn fetch_from(
+		&mut self,
+		branch: String,
+		target: RemoteTarget,
+	) -> Result<()> {
+		self.options =
+			sync::pull_options_config(&self.repo.borrow())?;
+		self.branch = branch;
+		self.target = Some(target);
+
+		self.start()
+	}
+
+	fn start(&mut self) -> Result<()> {
 		self.show()?;
 		if need_username_password_for_fetch(&self.repo.borrow())? {
 			let cred = extract_username_password_for_fetch(
@@ -78,26 +114,48 @@ impl PullPopup {
 			if cred.is_complete() {
 				self.fetch_from_remote(Some(cred))
 			} else {
-				self.input_cred.set_cred(cred);
+				self.input_cred.set_cred(
+					cred,
+					credential_url_for_fetch(&self.repo.borrow())
+						.unwrap_or_default(),
+				);
 				self.input_cred.show()
 			}
+		} else if need_ssh_passphrase(
+			&self.repo.borrow(),
+			&self.remote()?,
+		)
+		.unwrap_or_default()
+		{
+			self.input_cred.set_passphrase();
+			self.input_cred.show()
 		} else {
 			self.fetch_from_remote(None)
 		}
 	}
 
+	fn remote(&self) -> Result<String> {
+		Ok(match &self.target {
+			Some(target) => target.remote.clone(),
+			None => {
+				get_default_remote_for_fetch(&self.repo.borrow())?
+			}
+		})
+	}
+
 	fn fetch_from_remote(
 		&mut self,
 		cred: Option<BasicAuthCredential>,
 	) -> Result<()> {
 		self.pending = true;
 		self.progress = None;
+		let remote = self.remote()?;
+
 		self.git_fetch.request(FetchRequest {
-			remote: get_default_remote_for_fetch(
-				&self.repo.borrow(),
-			)?,
+			remote,
 			branch: self.branch.clone(),
 			basic_credential: cred,
+			target: self.target.clone(),
 		})?;
 
 		Ok(())
@@ -132,6 +190,11 @@ impl PullPopup {
 			{
 				if err.is_empty() {
 					self.try_ff_merge()?;
+				} else if self.git_fetch.is_cancelled() {
+					self.hide();
+					self.queue.push(InternalEvent::ShowInfoMsg(
+						strings::PULL_CANCELLED_MSG.to_string(),
+					));
 				} else {
 					anyhow::bail!(err);
 				}
@@ -141,71 +204,121 @@ impl PullPopup {
 		Ok(())
 	}
 
-	// check if something is incoming and try a ff merge then
+	// check if something is incoming and fast-forward if we did not
+	// diverge, otherwise ask for a merge or rebase
 	fn try_ff_merge(&mut self) -> Result<()> {
-		let branch_compare = sync::branch_compare_upstream(
-			&self.repo.borrow(),
-			&self.branch,
-		)?;
-		if branch_compare.behind > 0 {
-			let ff_res = sync::branch_merge_upstream_fastforward(
+		// once it is the upstream, integrate it like any other pull
+		if let Some(target) =
+			self.target.as_ref().filter(|target| target.set_upstream)
+		{
+			sync::set_branch_upstream(
 				&self.repo.borrow(),
 				&self.branch,
-			);
-			if let Err(err) = ff_res {
-				log::trace!("ff failed: {}", err);
-				self.confirm_merge(branch_compare.behind);
-			}
+				target,
+			)?;
+			self.target = None;
 		}
 
+		let branch_compare = match &self.target {
+			Some(target) => sync::branch_compare_ref(
+				&self.repo.borrow(),
+				&self.branch,
+				&target.tracking_ref(),
+			)?,
+			None => sync::branch_compare_upstream(
+				&self.repo.borrow(),
+				&self.branch,
+			)?,
+		};
+
 		self.hide();
 
+		if branch_compare.behind == 0 {
+			return Ok(());
+		}
+
+		if branch_compare.ahead == 0 {
+			self.integrate(PullStrategy::FastForwardOnly);
+		} else if self.options.strategy
+			== PullStrategy::FastForwardOnly
+		{
+			self.queue.push(InternalEvent::ShowErrorMsg(
+				strings::PULL_FF_ONLY_DIVERGED.to_string(),
+			));
+		} else {
+			self.confirm_merge(branch_compare.behind);
+		}
+
 		Ok(())
 	}
 
 	pub fn try_conflict_free_merge(&self, rebase: bool) {
-		if rebase {
-			try_or_popup!(
-				self,
-				"rebase failed:",
-				sync::merge_upstream_rebase(
-					&self.repo.borrow(),
-					&self.branch
-				)
-			);
+		self.integrate(if rebase {
+			PullStrategy::Rebase
 		} else {
-			try_or_popup!(
-				self,
-				"merge failed:",
-				sync::merge_upstream_commit(
-					&self.repo.borrow(),
-					&self.branch
-				)
-			);
+			PullStrategy::Merge
+		});
+	}
+
+	fn integrate(&self, strategy: PullStrategy) {
+		let res = match &self.target {
+			Some(target) => sync::pull_from(
+				&self.repo.borrow(),
+				&self.branch,
+				&target.tracking_ref(),
+				strategy,
+				self.options.autostash,
+			),
+			None => sync::pull_upstream(
+				&self.repo.borrow(),
+				&self.branch,
+				strategy,
+				self.options.autostash,
+			),
+		};
+
+		match res {
+			Ok(res) => {
+				if res.stashed.is_some() {
+					self.queue.push(InternalEvent::ShowInfoMsg(
+						strings::PULL_AUTOSTASH_KEPT.to_string(),
+					));
+				}
+			}
+			Err(e) => {
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!(
+						"{} failed:\n{e}",
+						strings::pull_strategy_name(strategy)
+					),
+				));
+			}
 		}
 	}
 
-	fn confirm_merge(&mut self, incoming: usize) {
+	fn confirm_merge(&self, incoming: usize) {
 		self.queue.push(InternalEvent::ConfirmAction(
 			Action::PullMerge {
 				incoming,
-				rebase: sync::config_is_pull_rebase(
-					&self.repo.borrow(),
-				)
-				.unwrap_or_default(),
+				rebase: self.options.strategy == PullStrategy::Rebase,
 			},
 		));
-		self.hide();
 	}
 }
 
 impl DrawableComponent for PullPopup {
 	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
 		if self.visible {
-			let (state, progress) =
-				PushPopup::get_progress(self.progress.as_ref());
+			let (state, progress) = PushPopup::get_progress(
+				self.progress.as_ref(),
+				self.pending && self.git_fetch.is_cancelled(),
+			);
 
-			let area = ui::centered_rect_absolute(30, 3, f.area());
+			let area = ui::centered_rect_absolute(
+				PushPopup::PROGRESS_WIDTH,
+				3,
+				f.area(),
+			);
 
 			f.render_widget(Clear, area);
 			f.render_widget(
@@ -246,11 +359,21 @@ impl Component for PullPopup {
 			if self.input_cred.is_visible() {
 				return self.input_cred.commands(out, force_all);
 			}
-			out.push(CommandInfo::new(
-				strings::commands::close_msg(&self.key_config),
-				!self.pending,
-				self.visible,
-			));
+			if self.pending {
+				out.push(CommandInfo::new(
+					strings::commands::remote_progress_cancel(
+						&self.key_config,
+					),
+					!self.git_fetch.is_cancelled(),
+					self.visible,
+				));
+			} else {
+				out.push(CommandInfo::new(
+					strings::commands::close_msg(&self.key_config),
+					true,
+					self.visible,
+				));
+			}
 		}
 
 		visibility_blocking(self)
@@ -258,7 +381,7 @@ impl Component for PullPopup {
 
 	fn event(&mut self, ev: &Event) -> Result<EventState> {
 		if self.visible {
-			if let Event::Key(_) = ev {
+			if let Event::Key(e) = ev {
 				if self.input_cred.is_visible() {
 					self.input_cred.event(ev)?;
 
@@ -270,6 +393,10 @@ impl Component for PullPopup {
 						))?;
 						self.input_cred.hide();
 					}
+				} else if self.pending
+					&& key_match(e, self.key_config.keys.exit_popup)
+				{
+					self.git_fetch.cancel();
 				}
 			}
 			return Ok(EventState::Consumed);
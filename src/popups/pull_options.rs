@@ -0,0 +1,215 @@
+use crate::{
+	app::Environment,
+	components::{
+		visibility_blocking, CommandBlocking, CommandInfo, Component,
+		DrawableComponent, EventState,
+	},
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, Queue},
+	strings,
+	ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::sync::{self, PullOptions, PullStrategy, RepoPathRef};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Margin, Rect},
+	text::{Line, Span, Text},
+	widgets::{Block, BorderType, Borders, Clear, Paragraph},
+	Frame,
+};
+
+const STRATEGIES: [PullStrategy; 3] = [
+	PullStrategy::Merge,
+	PullStrategy::Rebase,
+	PullStrategy::FastForwardOnly,
+];
+
+/// picks how a single pull integrates the upstream, preselected
+/// from `pull.rebase`/`pull.ff`
+pub struct PullOptionsPopup {
+	repo: RepoPathRef,
+	queue: Queue,
+	visible: bool,
+	branch: String,
+	options: PullOptions,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl PullOptionsPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			repo: env.repo.clone(),
+			queue: env.queue.clone(),
+			visible: false,
+			branch: String::new(),
+			options: PullOptions::default(),
+			theme: env.theme.clone(),
+			key_config: env.key_config.clone(),
+		}
+	}
+
+	///
+	pub fn open(&mut self, branch: String) -> Result<()> {
+		self.options =
+			sync::pull_options_config(&self.repo.borrow())?;
+		self.branch = branch;
+
+		self.show()
+	}
+
+	fn move_selection(&mut self, up: bool) {
+		let idx = STRATEGIES
+			.iter()
+			.position(|s| *s == self.options.strategy)
+			.unwrap_or_default();
+
+		let idx = if up {
+			idx.saturating_sub(1)
+		} else {
+			idx.saturating_add(1).min(STRATEGIES.len() - 1)
+		};
+
+		self.options.strategy = STRATEGIES[idx];
+	}
+
+	fn text(&self) -> Text<'_> {
+		let mut lines = STRATEGIES
+			.iter()
+			.map(|strategy| {
+				let selected = *strategy == self.options.strategy;
+
+				Line::from(vec![
+					Span::styled(
+						if selected { "> " } else { "  " },
+						self.theme.text(true, selected),
+					),
+					Span::styled(
+						strings::pull_strategy_name(*strategy),
+						self.theme.text(true, selected),
+					),
+				])
+			})
+			.collect::<Vec<_>>();
+
+		lines.push(Line::default());
+		lines.push(Line::from(Span::styled(
+			strings::pull_autostash(self.options.autostash),
+			self.theme.text(false, false),
+		)));
+
+		Text::from(lines)
+	}
+}
+
+impl DrawableComponent for PullOptionsPopup {
+	fn draw(&self, f: &mut Frame, _rect: Rect) -> Result<()> {
+		if !self.visible {
+			return Ok(());
+		}
+
+		let area = ui::centered_rect_absolute(40, 7, f.area());
+
+		f.render_widget(Clear, area);
+		f.render_widget(
+			Block::default()
+				.title(Span::styled(
+					strings::pull_options_title(&self.branch),
+					self.theme.title(true),
+				))
+				.borders(Borders::ALL)
+				.border_type(BorderType::Thick)
+				.border_style(self.theme.block(true)),
+			area,
+		);
+
+		f.render_widget(
+			Paragraph::new(self.text()),
+			area.inner(Margin {
+				horizontal: 1,
+				vertical: 1,
+			}),
+		);
+
+		Ok(())
+	}
+}
+
+impl Component for PullOptionsPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.visible || force_all {
+			out.push(CommandInfo::new(
+				strings::commands::scroll(&self.key_config),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::pull_start(&self.key_config),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::pull_toggle_autostash(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::close_popup(&self.key_config),
+				true,
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.visible {
+			if let Event::Key(e) = ev {
+				let keys = &self.key_config.keys;
+
+				if key_match(e, keys.exit_popup) {
+					self.hide();
+				} else if key_match(e, keys.move_up) {
+					self.move_selection(true);
+				} else if key_match(e, keys.move_down) {
+					self.move_selection(false);
+				} else if key_match(e, keys.pull_toggle_autostash) {
+					self.options.autostash = !self.options.autostash;
+				} else if key_match(e, keys.enter) {
+					self.queue.push(InternalEvent::Pull(
+						self.branch.clone(),
+						Some(self.options),
+					));
+					self.hide();
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
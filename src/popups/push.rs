@@ -13,12 +13,13 @@ use anyhow::Result;
 use asyncgit::{
 	sync::{
 		cred::{
+			credential_url_for_push,
 			extract_username_password_for_push,
 			need_username_password_for_push, BasicAuthCredential,
 		},
-		get_branch_remote,
+		get_branch_remote, need_ssh_passphrase,
 		remotes::get_default_remote_for_push,
-		RepoPathRef,
+		CommitId, RemoteTarget, RepoPathRef,
 	},
 	AsyncGitNotification, AsyncPush, PushRequest, PushType,
 	RemoteProgress, RemoteProgressState,
@@ -58,7 +59,11 @@ pub struct PushPopup {
 	progress: Option<RemoteProgress>,
 	pending: bool,
 	branch: String,
+	stack: Vec<String>,
+	lease: Option<CommitId>,
+	target: Option<RemoteTarget>,
 	push_type: PushType,
+	no_verify: bool,
 	queue: Queue,
 	theme: SharedTheme,
 	key_config: SharedKeyConfig,
@@ -75,7 +80,11 @@ impl PushPopup {
 			pending: false,
 			visible: false,
 			branch: String::new(),
+			stack: Vec::new(),
+			lease: None,
+			target: None,
 			push_type: PushType::Branch,
+			no_verify: false,
 			git_push: AsyncPush::new(
 				env.repo.borrow().clone(),
 				&env.sender_git,
@@ -94,9 +103,14 @@ impl PushPopup {
 		push_type: PushType,
 		force: bool,
 		delete: bool,
+		no_verify: bool,
 	) -> Result<()> {
 		self.branch = branch;
+		self.stack.clear();
+		self.lease = None;
+		self.target = None;
 		self.push_type = push_type;
+		self.no_verify = no_verify;
 		self.modifier = match (force, delete) {
 			(true, true) => PushComponentModifier::ForceDelete,
 			(false, true) => PushComponentModifier::Delete,
@@ -104,6 +118,63 @@ impl PushPopup {
 			(false, false) => PushComponentModifier::None,
 		};
 
+		self.start(force)
+	}
+
+	/// force pushes (with lease) all `branches` of a stack at once,
+	/// the remote is picked based on the first (root) branch
+	pub fn push_stack(
+		&mut self,
+		branches: Vec<String>,
+	) -> Result<()> {
+		self.branch = branches.first().cloned().unwrap_or_default();
+		self.stack = branches;
+		self.lease = None;
+		self.target = None;
+		self.push_type = PushType::Branch;
+		self.no_verify = false;
+		self.modifier = PushComponentModifier::Force;
+
+		self.start(true)
+	}
+
+	/// force pushes `branch` unless the remote branch moved away
+	/// from `lease` (force-with-lease)
+	pub fn push_with_lease(
+		&mut self,
+		branch: String,
+		lease: CommitId,
+	) -> Result<()> {
+		self.branch = branch;
+		self.stack.clear();
+		self.lease = Some(lease);
+		self.target = None;
+		self.push_type = PushType::Branch;
+		self.no_verify = false;
+		self.modifier = PushComponentModifier::Force;
+
+		self.start(true)
+	}
+
+	/// pushes `branch` to the remote branch of `target` instead of
+	/// its upstream
+	pub fn push_to(
+		&mut self,
+		branch: String,
+		target: RemoteTarget,
+	) -> Result<()> {
+		self.branch = branch;
+		self.stack.clear();
+		self.lease = None;
+		self.target = Some(target);
+		self.push_type = PushType::Branch;
+		self.no_verify = false;
+		self.modifier = PushComponentModifier::None;
+
+		self.start(false)
+	}
+
+	fn start(&mut self, force: bool) -> Result<()> {
 		self.show()?;
 
 		if need_username_password_for_push(&self.repo.borrow())? {
@@ -114,20 +185,30 @@ impl PushPopup {
 			if cred.is_complete() {
 				self.push_to_remote(Some(cred), force)
 			} else {
-				self.input_cred.set_cred(cred);
+				self.input_cred.set_cred(
+					cred,
+					credential_url_for_push(&self.repo.borrow())
+						.unwrap_or_default(),
+				);
 				self.input_cred.show()
 			}
+		} else if need_ssh_passphrase(
+			&self.repo.borrow(),
+			&self.remote()?,
+		)
+		.unwrap_or_default()
+		{
+			self.input_cred.set_passphrase();
+			self.input_cred.show()
 		} else {
 			self.push_to_remote(None, force)
 		}
 	}
 
-	fn push_to_remote(
-		&mut self,
-		cred: Option<BasicAuthCredential>,
-		force: bool,
-	) -> Result<()> {
-		let remote = if let Ok(Some(remote)) =
+	fn remote(&self) -> Result<String> {
+		Ok(if let Some(target) = &self.target {
+			target.remote.clone()
+		} else if let Ok(Some(remote)) =
 			get_branch_remote(&self.repo.borrow(), &self.branch)
 		{
 			log::info!("push: branch '{}' has upstream for remote '{}' - using that",self.branch,remote);
@@ -142,7 +223,15 @@ impl PushPopup {
 				remote
 			);
 			remote
-		};
+		})
+	}
+
+	fn push_to_remote(
+		&mut self,
+		cred: Option<BasicAuthCredential>,
+		force: bool,
+	) -> Result<()> {
+		let remote = self.remote()?;
 
 		self.pending = true;
 		self.progress = None;
@@ -152,7 +241,11 @@ impl PushPopup {
 			push_type: self.push_type,
 			force,
 			delete: self.modifier.delete(),
+			no_verify: self.no_verify,
 			basic_credential: cred,
+			stack: self.stack.clone(),
+			lease: self.lease,
+			target: self.target.clone(),
 		})?;
 		Ok(())
 	}
@@ -176,9 +269,15 @@ impl PushPopup {
 
 		if !self.pending {
 			if let Some(err) = self.git_push.last_result()? {
-				self.queue.push(InternalEvent::ShowErrorMsg(
-					format!("push failed:\n{err}"),
-				));
+				self.queue.push(if self.git_push.is_cancelled() {
+					InternalEvent::ShowInfoMsg(
+						strings::PUSH_CANCELLED_MSG.to_string(),
+					)
+				} else {
+					InternalEvent::ShowErrorMsg(format!(
+						"push failed:\n{err}"
+					))
+				});
 			}
 			self.hide();
 		}
@@ -191,16 +290,35 @@ impl PushPopup {
 		self.pending
 	}
 
-	///
+	/// wide enough for the transfer details next to the state
+	pub const PROGRESS_WIDTH: u16 = 64;
+
+	/// label and percentage of the progress gauge
 	pub fn get_progress(
 		progress: Option<&RemoteProgress>,
+		cancelled: bool,
 	) -> (String, u8) {
-		progress.as_ref().map_or(
+		let percent =
+			progress.map_or(0, RemoteProgress::get_progress_percent);
+
+		if cancelled {
+			return (strings::PUSH_POPUP_CANCELLING.into(), percent);
+		}
+
+		progress.map_or(
 			(strings::PUSH_POPUP_PROGRESS_NONE.into(), 0),
 			|progress| {
+				let state =
+					Self::progress_state_name(&progress.state);
+				let details = strings::remote_progress(progress);
+
 				(
-					Self::progress_state_name(&progress.state),
-					progress.get_progress_percent(),
+					if details.is_empty() {
+						state
+					} else {
+						format!("{state}: {details}")
+					},
+					percent,
 				)
 			},
 		)
@@ -231,10 +349,16 @@ impl PushPopup {
 impl DrawableComponent for PushPopup {
 	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
 		if self.visible {
-			let (state, progress) =
-				Self::get_progress(self.progress.as_ref());
+			let (state, progress) = Self::get_progress(
+				self.progress.as_ref(),
+				self.pending && self.git_push.is_cancelled(),
+			);
 
-			let area = ui::centered_rect_absolute(30, 3, f.area());
+			let area = ui::centered_rect_absolute(
+				Self::PROGRESS_WIDTH,
+				3,
+				f.area(),
+			);
 
 			f.render_widget(Clear, area);
 			f.render_widget(
@@ -279,11 +403,21 @@ impl Component for PushPopup {
 			if self.input_cred.is_visible() {
 				return self.input_cred.commands(out, force_all);
 			}
-			out.push(CommandInfo::new(
-				strings::commands::close_msg(&self.key_config),
-				!self.pending,
-				self.visible,
-			));
+			if self.pending {
+				out.push(CommandInfo::new(
+					strings::commands::remote_progress_cancel(
+						&self.key_config,
+					),
+					!self.git_push.is_cancelled(),
+					self.visible,
+				));
+			} else {
+				out.push(CommandInfo::new(
+					strings::commands::close_msg(&self.key_config),
+					true,
+					self.visible,
+				));
+			}
 		}
 
 		visibility_blocking(self)
@@ -307,9 +441,12 @@ impl Component for PushPopup {
 				} else if key_match(
 					e,
 					self.key_config.keys.exit_popup,
-				) && !self.pending
-				{
-					self.hide();
+				) {
+					if self.pending {
+						self.git_push.cancel();
+					} else {
+						self.hide();
+					}
 				}
 			}
 			return Ok(EventState::Consumed);
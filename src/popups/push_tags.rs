@@ -13,11 +13,11 @@ use anyhow::Result;
 use asyncgit::{
 	sync::{
 		cred::{
-			extract_username_password, need_username_password,
-			BasicAuthCredential,
+			credential_url, extract_username_password,
+			need_username_password, BasicAuthCredential,
 		},
-		get_default_remote, AsyncProgress, PushTagsProgress,
-		RepoPathRef,
+		get_default_remote, need_ssh_passphrase, AsyncProgress,
+		PushTagsProgress, RepoPathRef,
 	},
 	AsyncGitNotification, AsyncPushTags, PushTagsRequest,
 };
@@ -72,9 +72,21 @@ impl PushTagsPopup {
 			if cred.is_complete() {
 				self.push_to_remote(Some(cred))
 			} else {
-				self.input_cred.set_cred(cred);
+				self.input_cred.set_cred(
+					cred,
+					credential_url(&self.repo.borrow())
+						.unwrap_or_default(),
+				);
 				self.input_cred.show()
 			}
+		} else if need_ssh_passphrase(
+			&self.repo.borrow(),
+			&get_default_remote(&self.repo.borrow())?,
+		)
+		.unwrap_or_default()
+		{
+			self.input_cred.set_passphrase();
+			self.input_cred.show()
 		} else {
 			self.push_to_remote(None)
 		}
@@ -0,0 +1,403 @@
+use crate::{
+	app::Environment,
+	components::{
+		visibility_blocking, CommandBlocking, CommandInfo, Component,
+		DrawableComponent, EventState, InputType, TextInputComponent,
+	},
+	keys::{key_match, SharedKeyConfig},
+	popups::record_undo,
+	queue::{InternalEvent, NeedsUpdate, Queue},
+	strings, try_or_popup,
+	ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::sync::{
+	self,
+	rebase_interactive::{RebaseAction, RebaseTodo},
+	CommitId, RebaseState, RepoPathRef,
+};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Margin, Rect},
+	text::{Line, Span, Text},
+	widgets::{Block, BorderType, Borders, Clear, Paragraph},
+	Frame,
+};
+
+/// edits the todo list of an interactive rebase (oldest commit on top)
+pub struct RebaseInteractivePopup {
+	repo: RepoPathRef,
+	queue: Queue,
+	visible: bool,
+	todo: Option<RebaseTodo>,
+	selection: usize,
+	message: TextInputComponent,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl RebaseInteractivePopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			repo: env.repo.clone(),
+			queue: env.queue.clone(),
+			visible: false,
+			todo: None,
+			selection: 0,
+			message: TextInputComponent::new(
+				env,
+				strings::POPUP_TITLE_REBASE_REWORD,
+				"",
+				true,
+			)
+			.with_input_type(InputType::Multiline),
+			theme: env.theme.clone(),
+			key_config: env.key_config.clone(),
+		}
+	}
+
+	/// opens the todo list for `commit` and all its descendants
+	pub fn open(&mut self, commit: CommitId) -> Result<()> {
+		let todo = sync::rebase_interactive::rebase_interactive_todo(
+			&self.repo.borrow(),
+			commit,
+		)?;
+
+		self.todo = Some(todo);
+		self.selection = 0;
+
+		self.show()
+	}
+
+	/// like `open` with `fixup!`/`squash!` commits already moved
+	/// behind their targets
+	pub fn open_autosquash(
+		&mut self,
+		commit: CommitId,
+	) -> Result<()> {
+		self.open(commit)?;
+
+		if let Some(todo) = self.todo.as_mut() {
+			todo.autosquash();
+		}
+
+		Ok(())
+	}
+
+	fn len(&self) -> usize {
+		self.todo.as_ref().map_or(0, |todo| todo.items.len())
+	}
+
+	fn move_selection(&mut self, up: bool) {
+		let max = self.len().saturating_sub(1);
+
+		self.selection = if up {
+			self.selection.saturating_sub(1)
+		} else {
+			self.selection.saturating_add(1).min(max)
+		};
+	}
+
+	fn move_item(&mut self, up: bool) {
+		let Some(todo) = &mut self.todo else {
+			return;
+		};
+
+		let other = if up {
+			self.selection.checked_sub(1)
+		} else {
+			Some(self.selection + 1)
+				.filter(|idx| *idx < todo.items.len())
+		};
+
+		if let Some(other) = other {
+			todo.items.swap(self.selection, other);
+			self.selection = other;
+		}
+	}
+
+	fn set_action(&mut self, action: RebaseAction) {
+		if let Some(item) = self
+			.todo
+			.as_mut()
+			.and_then(|todo| todo.items.get_mut(self.selection))
+		{
+			item.action = action;
+		}
+	}
+
+	fn open_reword(&mut self) -> Result<()> {
+		if let Some(item) = self
+			.todo
+			.as_ref()
+			.and_then(|todo| todo.items.get(self.selection))
+		{
+			let msg = match &item.action {
+				RebaseAction::Reword(msg) => msg.clone(),
+				_ => item.message.clone(),
+			};
+
+			self.message.show()?;
+			self.message.set_text(msg);
+		}
+
+		Ok(())
+	}
+
+	fn confirm_reword(&mut self) {
+		let msg = self.message.get_text().trim().to_string();
+		self.message.hide();
+
+		if !msg.is_empty() {
+			self.set_action(RebaseAction::Reword(msg));
+		}
+	}
+
+	fn start(&mut self) -> Result<()> {
+		if let Some(todo) = &self.todo {
+			record_undo(
+				&self.repo.borrow(),
+				&strings::undo_op_rebase_interactive(todo.base),
+			);
+			let state = sync::rebase_interactive::rebase_interactive(
+				&self.repo.borrow(),
+				todo,
+			)?;
+
+			self.hide();
+
+			match state {
+				RebaseState::Finished => (),
+				RebaseState::Conflicted => {
+					self.queue.push(InternalEvent::TabSwitchStatus);
+				}
+				RebaseState::Stopped => {
+					self.queue.push(InternalEvent::TabSwitchStatus);
+					self.queue.push(InternalEvent::ShowInfoMsg(
+						strings::REBASE_INTERACTIVE_STOPPED
+							.to_string(),
+					));
+				}
+			}
+
+			self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+		}
+
+		Ok(())
+	}
+
+	fn text(&self) -> Text<'_> {
+		let Some(todo) = &self.todo else {
+			return Text::default();
+		};
+
+		Text::from(
+			todo.items
+				.iter()
+				.enumerate()
+				.map(|(idx, item)| {
+					let selected = idx == self.selection;
+					let summary = match &item.action {
+						RebaseAction::Reword(msg) => msg,
+						_ => &item.message,
+					};
+
+					Line::from(Span::styled(
+						format!(
+							"{}{}",
+							if selected { "> " } else { "  " },
+							strings::rebase_interactive_entry(
+								item.action.name(),
+								&item.id.get_short_string(),
+								summary
+									.lines()
+									.next()
+									.unwrap_or_default(),
+							)
+						),
+						self.theme.text(
+							item.action != RebaseAction::Drop,
+							selected,
+						),
+					))
+				})
+				.collect::<Vec<_>>(),
+		)
+	}
+}
+
+impl DrawableComponent for RebaseInteractivePopup {
+	fn draw(&self, f: &mut Frame, _rect: Rect) -> Result<()> {
+		if !self.visible {
+			return Ok(());
+		}
+
+		let area = ui::centered_rect(70, 60, f.area());
+
+		f.render_widget(Clear, area);
+		f.render_widget(
+			Block::default()
+				.title(Span::styled(
+					strings::POPUP_TITLE_REBASE_INTERACTIVE,
+					self.theme.title(true),
+				))
+				.borders(Borders::ALL)
+				.border_type(BorderType::Thick)
+				.border_style(self.theme.block(true)),
+			area,
+		);
+
+		let inner = area.inner(Margin {
+			horizontal: 1,
+			vertical: 1,
+		});
+
+		let scroll = self.selection.saturating_sub(
+			usize::from(inner.height).saturating_sub(1),
+		);
+
+		f.render_widget(
+			Paragraph::new(self.text()).scroll((
+				u16::try_from(scroll).unwrap_or(u16::MAX),
+				0,
+			)),
+			inner,
+		);
+
+		self.message.draw(f, area)?;
+
+		Ok(())
+	}
+}
+
+impl Component for RebaseInteractivePopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.message.is_visible() {
+			self.message.commands(out, force_all);
+
+			out.push(CommandInfo::new(
+				strings::commands::rebase_reword_confirm(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
+		} else if self.visible || force_all {
+			out.push(CommandInfo::new(
+				strings::commands::scroll(&self.key_config),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::rebase_interactive_action(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::rebase_interactive_meld(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::rebase_interactive_move(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::rebase_interactive_start(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::close_popup(&self.key_config),
+				true,
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.visible {
+			if self.message.is_visible() {
+				if let Event::Key(e) = ev {
+					if key_match(e, self.key_config.keys.commit) {
+						self.confirm_reword();
+						return Ok(EventState::Consumed);
+					}
+				}
+
+				self.message.event(ev)?;
+
+				return Ok(EventState::Consumed);
+			}
+
+			if let Event::Key(e) = ev {
+				let keys = &self.key_config.keys;
+
+				if key_match(e, keys.exit_popup) {
+					self.hide();
+				} else if key_match(e, keys.enter) {
+					try_or_popup!(
+						self,
+						"rebase error:",
+						self.start()
+					);
+				} else if key_match(e, keys.log_move_commit_up) {
+					self.move_item(true);
+				} else if key_match(e, keys.log_move_commit_down) {
+					self.move_item(false);
+				} else if key_match(e, keys.move_up) {
+					self.move_selection(true);
+				} else if key_match(e, keys.move_down) {
+					self.move_selection(false);
+				} else if key_match(e, keys.rebase_pick) {
+					self.set_action(RebaseAction::Pick);
+				} else if key_match(e, keys.rebase_reword) {
+					self.open_reword()?;
+				} else if key_match(e, keys.rebase_edit) {
+					self.set_action(RebaseAction::Edit);
+				} else if key_match(e, keys.rebase_squash) {
+					self.set_action(RebaseAction::Squash);
+				} else if key_match(e, keys.rebase_fixup) {
+					self.set_action(RebaseAction::Fixup);
+				} else if key_match(e, keys.rebase_drop) {
+					self.set_action(RebaseAction::Drop);
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+		self.message.hide();
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
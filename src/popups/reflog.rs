@@ -0,0 +1,312 @@
+use crate::{
+	app::Environment,
+	components::{
+		time_to_string, visibility_blocking, CommandBlocking,
+		CommandInfo, Component, DrawableComponent, EventState,
+	},
+	keys::{key_match, SharedKeyConfig},
+	popups::InspectCommitOpen,
+	queue::{InternalEvent, NeedsUpdate, Queue, StackablePopupOpen},
+	strings, try_or_popup,
+	ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::sync::{self, ReflogEntry, RepoPathRef};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Margin, Rect},
+	text::{Line, Span, Text},
+	widgets::{Block, BorderType, Borders, Clear, Paragraph},
+	Frame,
+};
+
+/// lists the reflog of `HEAD` and the local branches to get back to
+/// commits that are not reachable anymore
+pub struct ReflogPopup {
+	repo: RepoPathRef,
+	queue: Queue,
+	visible: bool,
+	refs: Vec<String>,
+	ref_idx: usize,
+	entries: Vec<ReflogEntry>,
+	selection: usize,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl ReflogPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			repo: env.repo.clone(),
+			queue: env.queue.clone(),
+			visible: false,
+			refs: Vec::new(),
+			ref_idx: 0,
+			entries: Vec::new(),
+			selection: 0,
+			theme: env.theme.clone(),
+			key_config: env.key_config.clone(),
+		}
+	}
+
+	///
+	pub fn open(&mut self) -> Result<()> {
+		self.refs = sync::get_reflog_refs(&self.repo.borrow())?;
+		self.ref_idx = 0;
+		self.load()?;
+
+		self.show()
+	}
+
+	fn load(&mut self) -> Result<()> {
+		self.entries = match self.refs.get(self.ref_idx) {
+			Some(refname) => {
+				sync::get_reflog(&self.repo.borrow(), refname)?
+			}
+			None => Vec::new(),
+		};
+		self.selection = 0;
+
+		Ok(())
+	}
+
+	fn switch_ref(&mut self, next: bool) -> Result<()> {
+		if self.refs.is_empty() {
+			return Ok(());
+		}
+
+		self.ref_idx = if next {
+			(self.ref_idx + 1) % self.refs.len()
+		} else {
+			self.ref_idx.checked_sub(1).unwrap_or(self.refs.len() - 1)
+		};
+
+		self.load()
+	}
+
+	fn move_selection(&mut self, up: bool) {
+		let max = self.entries.len().saturating_sub(1);
+
+		self.selection = if up {
+			self.selection.saturating_sub(1)
+		} else {
+			self.selection.saturating_add(1).min(max)
+		};
+	}
+
+	fn selected(&self) -> Option<&ReflogEntry> {
+		self.entries.get(self.selection)
+	}
+
+	fn checkout(&mut self) -> Result<()> {
+		if let Some(entry) = self.selected() {
+			sync::checkout_commit(&self.repo.borrow(), entry.id)?;
+
+			self.hide();
+			self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+		}
+
+		Ok(())
+	}
+
+	fn title(&self) -> String {
+		strings::reflog_title(
+			self.refs.get(self.ref_idx).map_or("", String::as_str),
+			self.ref_idx,
+			self.refs.len(),
+		)
+	}
+
+	fn text(&self) -> Text<'_> {
+		Text::from(
+			self.entries
+				.iter()
+				.enumerate()
+				.map(|(idx, entry)| {
+					let selected = idx == self.selection;
+
+					Line::from(vec![
+						Span::styled(
+							if selected { "> " } else { "  " },
+							self.theme.text(true, selected),
+						),
+						Span::styled(
+							entry.id.get_short_string(),
+							self.theme.commit_hash(selected),
+						),
+						Span::styled(
+							format!(
+								" {} ",
+								time_to_string(entry.time, false)
+							),
+							self.theme.commit_time(selected),
+						),
+						Span::styled(
+							strings::reflog_entry(
+								&entry.selector,
+								&entry.message,
+							),
+							self.theme.text(true, selected),
+						),
+					])
+				})
+				.collect::<Vec<_>>(),
+		)
+	}
+}
+
+impl DrawableComponent for ReflogPopup {
+	fn draw(&self, f: &mut Frame, _rect: Rect) -> Result<()> {
+		if !self.visible {
+			return Ok(());
+		}
+
+		let area = ui::centered_rect(80, 70, f.area());
+
+		f.render_widget(Clear, area);
+		f.render_widget(
+			Block::default()
+				.title(Span::styled(
+					self.title(),
+					self.theme.title(true),
+				))
+				.borders(Borders::ALL)
+				.border_type(BorderType::Thick)
+				.border_style(self.theme.block(true)),
+			area,
+		);
+
+		let inner = area.inner(Margin {
+			horizontal: 1,
+			vertical: 1,
+		});
+
+		let scroll = self.selection.saturating_sub(
+			usize::from(inner.height).saturating_sub(1),
+		);
+
+		f.render_widget(
+			Paragraph::new(self.text()).scroll((
+				u16::try_from(scroll).unwrap_or(u16::MAX),
+				0,
+			)),
+			inner,
+		);
+
+		Ok(())
+	}
+}
+
+impl Component for ReflogPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.visible || force_all {
+			let selected = self.selected().is_some();
+
+			out.push(CommandInfo::new(
+				strings::commands::scroll(&self.key_config),
+				selected,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::reflog_switch_ref(
+					&self.key_config,
+				),
+				self.refs.len() > 1,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::reflog_inspect(&self.key_config),
+				selected,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::reflog_checkout(&self.key_config),
+				selected,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::reflog_branch(&self.key_config),
+				selected,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::reflog_reset(&self.key_config),
+				selected,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::close_popup(&self.key_config),
+				true,
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.visible {
+			if let Event::Key(e) = ev {
+				let keys = &self.key_config.keys;
+				let selected = self.selected().map(|entry| entry.id);
+
+				if key_match(e, keys.exit_popup) {
+					self.hide();
+				} else if key_match(e, keys.move_up) {
+					self.move_selection(true);
+				} else if key_match(e, keys.move_down) {
+					self.move_selection(false);
+				} else if key_match(e, keys.move_left) {
+					self.switch_ref(false)?;
+				} else if key_match(e, keys.move_right) {
+					self.switch_ref(true)?;
+				} else if let Some(id) = selected {
+					if key_match(e, keys.enter) {
+						self.queue.push(InternalEvent::OpenPopup(
+							StackablePopupOpen::InspectCommit(
+								InspectCommitOpen::new(id),
+							),
+						));
+					} else if key_match(e, keys.log_checkout_commit) {
+						try_or_popup!(
+							self,
+							"checkout error:",
+							self.checkout()
+						);
+					} else if key_match(e, keys.create_branch) {
+						self.hide();
+						self.queue
+							.push(InternalEvent::CreateBranchAt(id));
+					} else if key_match(e, keys.log_reset_commit) {
+						self.hide();
+						self.queue
+							.push(InternalEvent::OpenResetPopup(id));
+					}
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
@@ -0,0 +1,302 @@
+use crate::{
+	app::Environment,
+	components::{
+		visibility_blocking, CommandBlocking, CommandInfo, Component,
+		DrawableComponent, EventState, InputType, TextInputComponent,
+	},
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, Queue},
+	strings,
+	ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::sync::{
+	self, remotes::get_default_remote_for_push, RemoteTarget,
+	RepoPathRef,
+};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Constraint, Direction, Layout, Margin, Rect},
+	text::{Line, Span, Text},
+	widgets::{Block, BorderType, Borders, Clear, Paragraph},
+	Frame,
+};
+
+/// picks the remote and remote branch to push `branch` to or to pull
+/// into it, for branches without upstream or to bypass the upstream
+pub struct RemoteTargetPopup {
+	repo: RepoPathRef,
+	queue: Queue,
+	visible: bool,
+	push: bool,
+	branch: String,
+	remotes: Vec<String>,
+	selection: usize,
+	set_upstream: bool,
+	input: TextInputComponent,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl RemoteTargetPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		let mut input = TextInputComponent::new(
+			env,
+			"",
+			strings::REMOTE_TARGET_BRANCH_MSG,
+			false,
+		)
+		.with_input_type(InputType::Singleline);
+		input.embed();
+
+		Self {
+			repo: env.repo.clone(),
+			queue: env.queue.clone(),
+			visible: false,
+			push: true,
+			branch: String::new(),
+			remotes: Vec::new(),
+			selection: 0,
+			set_upstream: false,
+			input,
+			theme: env.theme.clone(),
+			key_config: env.key_config.clone(),
+		}
+	}
+
+	/// preselects the upstream of `branch` if it has one, otherwise
+	/// the default remote and a remote branch of the same name
+	pub fn open(&mut self, branch: String, push: bool) -> Result<()> {
+		let repo = self.repo.borrow().clone();
+
+		self.remotes = sync::get_remotes(&repo)?;
+
+		let upstream_remote =
+			sync::get_branch_remote(&repo, &branch).ok().flatten();
+		let remote = upstream_remote
+			.clone()
+			.or_else(|| get_default_remote_for_push(&repo).ok());
+		self.selection = remote
+			.and_then(|remote| {
+				self.remotes.iter().position(|r| *r == remote)
+			})
+			.unwrap_or_default();
+
+		let remote_branch =
+			sync::get_branch_upstream_merge(&repo, &branch)
+				.ok()
+				.flatten()
+				.and_then(|merge| {
+					merge
+						.strip_prefix("refs/heads/")
+						.map(String::from)
+				})
+				.unwrap_or_else(|| branch.clone());
+
+		self.set_upstream = upstream_remote.is_none();
+		self.push = push;
+		self.branch = branch;
+
+		self.show()?;
+		self.input.set_text(remote_branch);
+
+		Ok(())
+	}
+
+	fn move_selection(&mut self, up: bool) {
+		self.selection = if up {
+			self.selection.saturating_sub(1)
+		} else {
+			self.selection
+				.saturating_add(1)
+				.min(self.remotes.len().saturating_sub(1))
+		};
+	}
+
+	fn target(&self) -> Option<RemoteTarget> {
+		let remote = self.remotes.get(self.selection)?;
+		let branch = self.input.get_text().trim();
+
+		if branch.is_empty()
+			|| !sync::validate_branch_name(branch).unwrap_or_default()
+		{
+			return None;
+		}
+
+		Some(RemoteTarget {
+			remote: remote.clone(),
+			branch: branch.to_string(),
+			set_upstream: self.set_upstream,
+		})
+	}
+
+	fn confirm(&mut self) {
+		if let Some(target) = self.target() {
+			self.queue.push(if self.push {
+				InternalEvent::PushTo(self.branch.clone(), target)
+			} else {
+				InternalEvent::PullFrom(self.branch.clone(), target)
+			});
+
+			self.hide();
+		}
+	}
+
+	fn text(&self) -> Text<'_> {
+		let mut lines = self
+			.remotes
+			.iter()
+			.enumerate()
+			.map(|(idx, remote)| {
+				let selected = idx == self.selection;
+
+				Line::from(vec![
+					Span::styled(
+						if selected { "> " } else { "  " },
+						self.theme.text(true, selected),
+					),
+					Span::styled(
+						remote.as_str(),
+						self.theme.text(true, selected),
+					),
+				])
+			})
+			.collect::<Vec<_>>();
+
+		lines.push(Line::default());
+		lines.push(Line::from(Span::styled(
+			strings::remote_target_set_upstream(self.set_upstream),
+			self.theme.text(false, false),
+		)));
+
+		Text::from(lines)
+	}
+}
+
+impl DrawableComponent for RemoteTargetPopup {
+	fn draw(&self, f: &mut Frame, _rect: Rect) -> Result<()> {
+		if !self.visible {
+			return Ok(());
+		}
+
+		let height = u16::try_from(self.remotes.len())
+			.unwrap_or(u16::MAX)
+			.saturating_add(6);
+		let area = ui::centered_rect_absolute(50, height, f.area());
+
+		f.render_widget(Clear, area);
+		f.render_widget(
+			Block::default()
+				.title(Span::styled(
+					strings::remote_target_title(
+						&self.branch,
+						self.push,
+					),
+					self.theme.title(true),
+				))
+				.borders(Borders::ALL)
+				.border_type(BorderType::Thick)
+				.border_style(self.theme.block(true)),
+			area,
+		);
+
+		let chunks = Layout::default()
+			.direction(Direction::Vertical)
+			.constraints(
+				[Constraint::Min(1), Constraint::Length(1)].as_ref(),
+			)
+			.split(area.inner(Margin {
+				horizontal: 1,
+				vertical: 1,
+			}));
+
+		f.render_widget(Paragraph::new(self.text()), chunks[0]);
+		self.input.draw(f, chunks[1])?;
+
+		Ok(())
+	}
+}
+
+impl Component for RemoteTargetPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.visible || force_all {
+			out.push(CommandInfo::new(
+				strings::commands::scroll(&self.key_config),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::remote_target_confirm(
+					&self.key_config,
+					self.push,
+				),
+				self.target().is_some(),
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::remote_target_toggle_upstream(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::close_popup(&self.key_config),
+				true,
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.visible {
+			if let Event::Key(e) = ev {
+				let keys = &self.key_config.keys;
+
+				if key_match(e, keys.exit_popup) {
+					self.hide();
+				} else if key_match(e, keys.move_up) {
+					self.move_selection(true);
+				} else if key_match(e, keys.move_down) {
+					self.move_selection(false);
+				} else if key_match(
+					e,
+					keys.remote_target_toggle_upstream,
+				) {
+					self.set_upstream = !self.set_upstream;
+				} else if key_match(e, keys.enter) {
+					self.confirm();
+				} else {
+					self.input.event(ev)?;
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+		self.input.hide();
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+		self.input.show()?;
+
+		Ok(())
+	}
+}
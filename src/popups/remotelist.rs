@@ -1,6 +1,10 @@
 use std::cell::Cell;
 
-use asyncgit::sync::{get_remote_url, get_remotes, RepoPathRef};
+use asyncgit::sync::{
+	get_remote_push_url, get_remote_url, get_remotes,
+	is_ssh_key_encrypted, remote_proxy, ssh_identity, ConfigOrigin,
+	RepoPathRef, CONFIG_IDENTITY_FILE,
+};
 use ratatui::{
 	layout::{
 		Alignment, Constraint, Direction, Layout, Margin, Rect,
@@ -66,7 +70,7 @@ impl DrawableComponent for RemoteListPopup {
 				.constraints(vec![
 					Constraint::Min(1),
 					Constraint::Length(1),
-					Constraint::Length(2),
+					Constraint::Length(3),
 				])
 				.split(area);
 			self.draw_remotes_list(f, chunks[0])?;
@@ -112,6 +116,22 @@ impl Component for RemoteListPopup {
 				self.valid_selection(),
 			));
 
+			out.push(CommandInfo::new(
+				strings::commands::update_remote_push_url(
+					&self.key_config,
+				),
+				true,
+				self.valid_selection(),
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::remote_identity_file(
+					&self.key_config,
+				),
+				true,
+				self.valid_selection(),
+			));
+
 			out.push(CommandInfo::new(
 				strings::commands::create_remote(&self.key_config),
 				true,
@@ -153,6 +173,18 @@ impl Component for RemoteListPopup {
 				self.key_config.keys.update_remote_url,
 			) {
 				self.update_remote_url();
+			} else if key_match(
+				e,
+				self.key_config.keys.update_remote_push_url,
+			) && self.valid_selection()
+			{
+				self.update_remote_push_url();
+			} else if key_match(
+				e,
+				self.key_config.keys.remote_identity_file,
+			) && self.valid_selection()
+			{
+				self.edit_identity_file();
 			}
 		}
 		Ok(EventState::Consumed)
@@ -326,11 +358,29 @@ impl RemoteListPopup {
 			REMOTE_NAME_LABEL.len();
 		const REMOTE_URL_LABEL: &str = "url: ";
 		const REMOTE_URL_LABEL_LENGTH: usize = REMOTE_URL_LABEL.len();
+		const REMOTE_PUSH_URL_LABEL: &str = "push url: ";
+		const REMOTE_PUSH_URL_LABEL_LENGTH: usize =
+			REMOTE_PUSH_URL_LABEL.len();
+		const REMOTE_IDENTITY_LABEL: &str = "ssh key: ";
+		const REMOTE_IDENTITY_LABEL_LENGTH: usize =
+			REMOTE_IDENTITY_LABEL.len();
+		const REMOTE_PROXY_LABEL: &str = "proxy: ";
+		const REMOTE_PROXY_LABEL_LENGTH: usize =
+			REMOTE_PROXY_LABEL.len();
 
 		let name_length: usize = (r.width.saturating_sub(1) as usize)
 			.saturating_sub(REMOTE_NAME_LABEL_LENGTH);
 		let url_length: usize = (r.width.saturating_sub(1) as usize)
 			.saturating_sub(REMOTE_URL_LABEL_LENGTH);
+		let push_url_length: usize = (r.width.saturating_sub(1)
+			as usize)
+			.saturating_sub(REMOTE_PUSH_URL_LABEL_LENGTH);
+		let identity_length: usize = (r.width.saturating_sub(1)
+			as usize)
+			.saturating_sub(REMOTE_IDENTITY_LABEL_LENGTH);
+		let proxy_length: usize = (r.width.saturating_sub(1)
+			as usize)
+			.saturating_sub(REMOTE_PROXY_LABEL_LENGTH);
 
 		let remote =
 			self.remote_names.get(usize::from(self.selection));
@@ -354,26 +404,49 @@ impl RemoteListPopup {
 				),
 				self.theme.text(true, false),
 			)));
-			let remote_url =
-				get_remote_url(&self.repo.borrow(), remote);
-			if let Ok(Some(mut remote_url)) = remote_url {
-				if remote_url.len()
-					> url_length.saturating_sub(THREE_DOTS_LENGTH)
-				{
-					remote_url = remote_url
-						.chars()
-						.skip(
-							remote_url.len()
-								- url_length.saturating_sub(
-									THREE_DOTS_LENGTH,
-								),
+			let repo = self.repo.borrow();
+			if let Ok(Some(remote_url)) =
+				get_remote_url(&repo, remote)
+			{
+				lines.push(Line::from(Span::styled(
+					format!(
+						"{REMOTE_URL_LABEL}{:url_length$}",
+						Self::truncate_url(remote_url, url_length)
+					),
+					self.theme.text(true, false),
+				)));
+			}
+			if let Ok(Some(push_url)) =
+				get_remote_push_url(&repo, remote)
+			{
+				lines.push(Line::from(Span::styled(
+					format!(
+						"{REMOTE_PUSH_URL_LABEL}{:push_url_length$}",
+						Self::truncate_url(push_url, push_url_length)
+					),
+					self.theme.text(true, false),
+				)));
+			}
+			if let Ok(Some(identity)) = ssh_identity(&repo, remote) {
+				lines.push(Line::from(Span::styled(
+					format!(
+						"{REMOTE_IDENTITY_LABEL}{:identity_length$}",
+						Self::truncate_url(
+							strings::ssh_identity(
+								&identity,
+								is_ssh_key_encrypted(&identity),
+							),
+							identity_length
 						)
-						.collect::<String>();
-					remote_url = format!("{THREE_DOTS}{remote_url}");
-				}
+					),
+					self.theme.text(true, false),
+				)));
+			}
+			if let Ok(Some(proxy)) = remote_proxy(&repo, remote) {
 				lines.push(Line::from(Span::styled(
 					format!(
-						"{REMOTE_URL_LABEL}{remote_url:url_length$}"
+						"{REMOTE_PROXY_LABEL}{:proxy_length$}",
+						Self::truncate_url(proxy, proxy_length)
 					),
 					self.theme.text(true, false),
 				)));
@@ -392,6 +465,22 @@ impl RemoteListPopup {
 		}
 	}
 
+	/// keeps the end of `url` which tells remotes apart better
+	fn truncate_url(url: String, max_length: usize) -> String {
+		const THREE_DOTS: &str = "...";
+
+		let keep = max_length.saturating_sub(THREE_DOTS.len());
+		if url.len() > keep {
+			let tail = url
+				.chars()
+				.skip(url.chars().count().saturating_sub(keep))
+				.collect::<String>();
+			format!("{THREE_DOTS}{tail}")
+		} else {
+			url
+		}
+	}
+
 	///
 	fn move_selection(&mut self, scroll: ScrollType) -> Result<bool> {
 		let new_selection = match scroll {
@@ -469,7 +558,41 @@ impl RemoteListPopup {
 			self.queue.push(InternalEvent::UpdateRemoteUrl(
 				remote_name,
 				url,
+				false,
 			));
 		}
 	}
+
+	/// `remote.<name>.identityFile` in the repo config
+	fn edit_identity_file(&self) {
+		let remote_name = &self.remote_names[self.selection as usize];
+		let key =
+			format!("remote.{remote_name}.{CONFIG_IDENTITY_FILE}");
+		let value = ssh_identity(&self.repo.borrow(), remote_name)
+			.ok()
+			.flatten()
+			.map(|identity| identity.to_string_lossy().to_string());
+
+		self.queue.push(InternalEvent::EditGitConfig {
+			origin: ConfigOrigin::Local,
+			key: Some(key),
+			value,
+		});
+	}
+
+	fn update_remote_push_url(&self) {
+		let remote_name =
+			self.remote_names[self.selection as usize].clone();
+		let push_url =
+			get_remote_push_url(&self.repo.borrow(), &remote_name)
+				.ok()
+				.flatten()
+				.unwrap_or_default();
+
+		self.queue.push(InternalEvent::UpdateRemoteUrl(
+			remote_name,
+			push_url,
+			true,
+		));
+	}
 }
@@ -5,14 +5,19 @@ use crate::components::{
 use crate::{
 	app::Environment,
 	keys::{key_match, SharedKeyConfig},
-	queue::Queue,
+	popups::{record_undo, RevisionTarget},
+	queue::{InternalEvent, Queue},
 	strings, try_or_popup,
 	ui::{self, style::SharedTheme},
 };
 use anyhow::Result;
 use asyncgit::{
+	asyncjob::AsyncSingleJob,
 	cached,
-	sync::{CommitId, RepoPath, ResetType},
+	sync::{
+		get_commits_info, CommitId, RepoPath, ResetPreview, ResetType,
+	},
+	AsyncGitNotification, AsyncResetPreviewJob,
 };
 use crossterm::event::Event;
 use ratatui::{
@@ -39,11 +44,17 @@ const fn type_to_string(
 	}
 }
 
+const PREVIEW_MAX_ITEMS: usize = 8;
+const PREVIEW_MSG_LENGTH: usize = 40;
+
 pub struct ResetPopup {
 	queue: Queue,
 	repo: RepoPath,
 	commit: Option<CommitId>,
 	kind: ResetType,
+	preview: ResetPreview,
+	preview_commits: Vec<String>,
+	git_preview: AsyncSingleJob<AsyncResetPreviewJob>,
 	git_branch_name: cached::BranchName,
 	visible: bool,
 	key_config: SharedKeyConfig,
@@ -58,6 +69,9 @@ impl ResetPopup {
 			repo: env.repo.borrow().clone(),
 			commit: None,
 			kind: ResetType::Soft,
+			preview: ResetPreview::default(),
+			preview_commits: Vec::new(),
+			git_preview: AsyncSingleJob::new(env.sender_git.clone()),
 			git_branch_name: cached::BranchName::new(
 				env.repo.clone(),
 			),
@@ -105,6 +119,50 @@ impl ResetPopup {
 			Span::styled(kind_desc, self.theme.text(true, false)),
 		]));
 
+		txt.push(Line::default());
+
+		if self.git_preview.is_pending() {
+			txt.push(Line::from(Span::styled(
+				strings::reset_preview_pending(),
+				self.theme.text(false, false),
+			)));
+			return txt;
+		}
+
+		txt.push(Line::from(Span::styled(
+			format!(
+				"Commits leaving branch ({}):",
+				self.preview.commits.len()
+			),
+			self.theme.text(true, false),
+		)));
+		txt.extend(
+			self.preview_commits
+				.iter()
+				.map(|c| Line::from(format!("  {c}"))),
+		);
+		if self.preview.commits.len() > self.preview_commits.len() {
+			txt.push(Line::from("  ..."));
+		}
+
+		txt.push(Line::from(Span::styled(
+			format!("Files affected ({}):", self.preview.files.len()),
+			self.theme.text(true, false),
+		)));
+		txt.extend(
+			self.preview.files.iter().take(PREVIEW_MAX_ITEMS).map(
+				|f| {
+					Line::from(Span::styled(
+						format!("  {}", f.path),
+						self.theme.item(f.status, false),
+					))
+				},
+			),
+		);
+		if self.preview.files.len() > PREVIEW_MAX_ITEMS {
+			txt.push(Line::from("  ..."));
+		}
+
 		txt
 	}
 
@@ -113,10 +171,81 @@ impl ResetPopup {
 		self.show()?;
 
 		self.commit = Some(id);
+		self.update_preview();
 
 		Ok(())
 	}
 
+	fn update_preview(&mut self) {
+		let Some(id) = self.commit else {
+			return;
+		};
+
+		self.preview = ResetPreview::default();
+		self.preview_commits.clear();
+
+		self.git_preview.spawn(AsyncResetPreviewJob::new(
+			self.repo.clone(),
+			id,
+			self.kind,
+		));
+	}
+
+	///
+	pub fn any_work_pending(&self) -> bool {
+		self.git_preview.is_pending()
+	}
+
+	///
+	pub fn update_git(&mut self, ev: AsyncGitNotification) {
+		if ev != AsyncGitNotification::ResetPreview {
+			return;
+		}
+
+		let Some((target, result)) =
+			self.git_preview.take_last().and_then(|job| job.result())
+		else {
+			return;
+		};
+
+		// the target or mode changed while the job ran
+		if Some(target) != self.commit.map(|id| (id, self.kind)) {
+			return;
+		}
+
+		match result {
+			Ok(preview) => self.preview = preview,
+			Err(e) => {
+				log::error!("reset preview: {e}");
+				self.preview = ResetPreview::default();
+			}
+		}
+
+		let ids = self
+			.preview
+			.commits
+			.iter()
+			.take(PREVIEW_MAX_ITEMS)
+			.copied()
+			.collect::<Vec<_>>();
+
+		self.preview_commits =
+			get_commits_info(&self.repo, &ids, PREVIEW_MSG_LENGTH)
+				.map(|infos| {
+					infos
+						.into_iter()
+						.map(|c| {
+							format!(
+								"{} {}",
+								c.id.get_short_string(),
+								c.message
+							)
+						})
+						.collect()
+				})
+				.unwrap_or_default();
+	}
+
 	///
 	#[allow(clippy::unnecessary_wraps)]
 	pub fn update(&mut self) -> Result<()> {
@@ -127,10 +256,17 @@ impl ResetPopup {
 
 	fn reset(&mut self) {
 		if let Some(id) = self.commit {
+			record_undo(
+				&self.repo,
+				&strings::undo_op_reset(
+					type_to_string(self.kind).0,
+					id,
+				),
+			);
 			try_or_popup!(
 				self,
 				"reset:",
-				asyncgit::sync::reset_repo(&self.repo, id, self.kind)
+				asyncgit::sync::reset_to(&self.repo, id, self.kind)
 			);
 		}
 
@@ -151,21 +287,25 @@ impl ResetPopup {
 				ResetType::Hard => ResetType::Mixed,
 			}
 		};
+
+		self.update_preview();
 	}
 }
 
 impl DrawableComponent for ResetPopup {
 	fn draw(&self, f: &mut Frame, area: Rect) -> Result<()> {
 		if self.is_visible() {
-			const SIZE: (u16, u16) = (55, 5);
-			let area =
-				ui::centered_rect_absolute(SIZE.0, SIZE.1, area);
+			const WIDTH: u16 = 60;
 
-			let width = area.width;
+			let txt = self.get_text(WIDTH);
+			let height =
+				u16::try_from(txt.len()).unwrap_or(u16::MAX) + 2;
+			let area =
+				ui::centered_rect_absolute(WIDTH, height, area);
 
 			f.render_widget(Clear, area);
 			f.render_widget(
-				Paragraph::new(self.get_text(width))
+				Paragraph::new(txt)
 					.block(
 						Block::default()
 							.borders(Borders::ALL)
@@ -209,6 +349,17 @@ impl Component for ResetPopup {
 				.order(1),
 			);
 
+			out.push(
+				CommandInfo::new(
+					strings::commands::reset_select_revision(
+						&self.key_config,
+					),
+					true,
+					true,
+				)
+				.order(1),
+			);
+
 			out.push(
 				CommandInfo::new(
 					strings::commands::reset_type(&self.key_config),
@@ -240,6 +391,15 @@ impl Component for ResetPopup {
 					self.change_kind(false);
 				} else if key_match(key, self.key_config.keys.enter) {
 					self.reset();
+				} else if key_match(
+					key,
+					self.key_config.keys.select_revision,
+				) {
+					self.queue.push(
+						InternalEvent::OpenRevisionPopup(
+							RevisionTarget::Reset,
+						),
+					);
 				}
 			}
 
@@ -0,0 +1,300 @@
+use crate::components::{
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DiffComponent, DrawableComponent, EventState,
+};
+use crate::{
+	app::Environment,
+	keys::{key_match, SharedKeyConfig},
+	options::SharedOptions,
+	queue::{InternalEvent, NeedsUpdate, Queue},
+	strings, try_or_popup,
+	ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::{
+	sync::{restore_file, CommitId, RepoPathRef},
+	AsyncDiff, AsyncGitNotification, DiffParams, DiffType,
+};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Alignment, Constraint, Direction, Layout, Rect},
+	text::{Line, Span},
+	widgets::{Block, Borders, Clear, Paragraph},
+	Frame,
+};
+
+pub struct RestoreFilePopup {
+	queue: Queue,
+	repo: RepoPathRef,
+	request: Option<(CommitId, String)>,
+	stage: bool,
+	diff: DiffComponent,
+	git_diff: AsyncDiff,
+	visible: bool,
+	key_config: SharedKeyConfig,
+	options: SharedOptions,
+	theme: SharedTheme,
+}
+
+impl RestoreFilePopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			queue: env.queue.clone(),
+			repo: env.repo.clone(),
+			request: None,
+			stage: false,
+			diff: DiffComponent::new(env, true),
+			git_diff: AsyncDiff::new(
+				env.repo.borrow().clone(),
+				&env.sender_git,
+			),
+			visible: false,
+			key_config: env.key_config.clone(),
+			options: env.options.clone(),
+			theme: env.theme.clone(),
+		}
+	}
+
+	///
+	pub fn open(&mut self, id: CommitId, path: String) -> Result<()> {
+		self.request = Some((id, path));
+		self.stage = false;
+		self.show()?;
+
+		self.diff.focus(true);
+		self.diff.clear(false);
+		self.update_diff()?;
+
+		Ok(())
+	}
+
+	///
+	pub fn any_work_pending(&self) -> bool {
+		self.git_diff.is_pending()
+	}
+
+	///
+	pub fn update_git(
+		&mut self,
+		ev: AsyncGitNotification,
+	) -> Result<()> {
+		if self.is_visible() && ev == AsyncGitNotification::Diff {
+			self.update_diff()?;
+		}
+
+		Ok(())
+	}
+
+	///
+	pub fn update_diff(&mut self) -> Result<()> {
+		if !self.is_visible() {
+			return Ok(());
+		}
+
+		if let Some((id, path)) = &self.request {
+			let diff_params = DiffParams {
+				path: path.clone(),
+				diff_type: DiffType::Restore(*id),
+				options: self.diff.limit_options(
+					path,
+					self.options.borrow().diff_options(),
+				),
+			};
+
+			if let Some((params, last)) = self.git_diff.last()? {
+				if params == diff_params {
+					self.diff.update(path.clone(), false, last);
+					return Ok(());
+				}
+			}
+
+			self.git_diff.request(diff_params)?;
+			self.diff.clear(true);
+		}
+
+		Ok(())
+	}
+
+	fn get_text(&self) -> Vec<Line> {
+		let Some((id, path)) = &self.request else {
+			return Vec::new();
+		};
+
+		vec![
+			Line::from(vec![
+				Span::styled(
+					String::from("File: "),
+					self.theme.text(true, false),
+				),
+				Span::styled(
+					path.clone(),
+					self.theme.text(true, true),
+				),
+			]),
+			Line::from(vec![
+				Span::styled(
+					String::from("From: "),
+					self.theme.text(true, false),
+				),
+				Span::styled(
+					id.get_short_string(),
+					self.theme.commit_hash(false),
+				),
+			]),
+			Line::from(vec![
+				Span::styled(
+					String::from("Stage: "),
+					self.theme.text(true, false),
+				),
+				Span::styled(
+					if self.stage { "yes" } else { "no" },
+					self.theme.option(self.stage),
+				),
+			]),
+		]
+	}
+
+	fn restore(&mut self) {
+		if let Some((id, path)) = self.request.take() {
+			try_or_popup!(
+				self,
+				"restore file:",
+				restore_file(
+					&self.repo.borrow(),
+					id,
+					&path,
+					self.stage
+				)
+			);
+
+			self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+		}
+
+		self.hide();
+	}
+}
+
+impl DrawableComponent for RestoreFilePopup {
+	fn draw(&self, f: &mut Frame, area: Rect) -> Result<()> {
+		if self.is_visible() {
+			const INFO_HEIGHT: u16 = 5;
+			let area = ui::centered_rect(70, 70, area);
+
+			let chunks = Layout::default()
+				.direction(Direction::Vertical)
+				.constraints(
+					[
+						Constraint::Length(INFO_HEIGHT),
+						Constraint::Min(1),
+					]
+					.as_ref(),
+				)
+				.split(area);
+
+			f.render_widget(Clear, area);
+			f.render_widget(
+				Paragraph::new(self.get_text())
+					.block(
+						Block::default()
+							.borders(Borders::ALL)
+							.title(Span::styled(
+								strings::restore_file_popup_title(),
+								self.theme.title(true),
+							))
+							.border_style(self.theme.block(true)),
+					)
+					.alignment(Alignment::Left),
+				chunks[0],
+			);
+			self.diff.draw(f, chunks[1])?;
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for RestoreFilePopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			self.diff.commands(out, force_all);
+
+			out.push(
+				CommandInfo::new(
+					strings::commands::close_popup(&self.key_config),
+					true,
+					true,
+				)
+				.order(1),
+			);
+
+			out.push(
+				CommandInfo::new(
+					strings::commands::restore_file_confirm(
+						&self.key_config,
+					),
+					true,
+					true,
+				)
+				.order(1),
+			);
+
+			out.push(
+				CommandInfo::new(
+					strings::commands::restore_file_toggle_stage(
+						&self.key_config,
+					),
+					true,
+					true,
+				)
+				.order(1),
+			);
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(
+		&mut self,
+		event: &crossterm::event::Event,
+	) -> Result<EventState> {
+		if self.is_visible() {
+			if let Event::Key(key) = &event {
+				if key_match(key, self.key_config.keys.exit_popup) {
+					self.hide();
+				} else if key_match(
+					key,
+					self.key_config.keys.restore_toggle_stage,
+				) {
+					self.stage = !self.stage;
+				} else if key_match(key, self.key_config.keys.enter) {
+					self.restore();
+				} else {
+					self.diff.event(event)?;
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
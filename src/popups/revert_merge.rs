@@ -0,0 +1,255 @@
+use crate::components::{
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState, ScrollType,
+};
+use crate::{
+	app::Environment,
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, NeedsUpdate, Queue},
+	strings,
+	ui::{self, style::SharedTheme, Size},
+};
+use anyhow::Result;
+use asyncgit::sync::{self, CommitId, RepoPathRef, RevertParent};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Constraint, Rect},
+	text::Span,
+	widgets::{
+		Block, BorderType, Borders, Cell, Clear, Row, Table,
+		TableState,
+	},
+	Frame,
+};
+use std::cell::Cell as StdCell;
+
+/// picks the mainline parent to revert a merge commit against
+pub struct RevertMergePopup {
+	repo: RepoPathRef,
+	queue: Queue,
+	visible: bool,
+	commit: Option<CommitId>,
+	parents: Vec<RevertParent>,
+	table_state: StdCell<TableState>,
+	key_config: SharedKeyConfig,
+	theme: SharedTheme,
+}
+
+impl RevertMergePopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			repo: env.repo.clone(),
+			queue: env.queue.clone(),
+			visible: false,
+			commit: None,
+			parents: Vec::new(),
+			table_state: StdCell::new(TableState::default()),
+			key_config: env.key_config.clone(),
+			theme: env.theme.clone(),
+		}
+	}
+
+	/// lists the parents of the merge `commit`, the first selected
+	pub fn open(&mut self, commit: CommitId) -> Result<()> {
+		self.parents =
+			sync::revert_parents(&self.repo.borrow(), commit)?;
+		self.commit = Some(commit);
+
+		self.table_state.get_mut().select(Some(0));
+		self.show()
+	}
+
+	fn selection(&self) -> Option<usize> {
+		let table_state = self.table_state.take();
+		let selection = table_state.selected();
+		self.table_state.set(table_state);
+
+		selection.filter(|s| *s < self.parents.len())
+	}
+
+	fn confirm(&mut self) {
+		if let (Some(commit), Some(selection)) =
+			(self.commit, self.selection())
+		{
+			match sync::revert_commit(
+				&self.repo.borrow(),
+				commit,
+				Some(selection + 1),
+			) {
+				Ok(()) => {
+					self.queue.push(InternalEvent::TabSwitchStatus);
+				}
+				Err(e) => {
+					self.queue.push(InternalEvent::ShowErrorMsg(
+						format!("revert error:\n{e}"),
+					));
+				}
+			}
+			self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+		}
+
+		self.hide();
+	}
+
+	fn move_selection(&self, scroll_type: ScrollType) {
+		let mut table_state = self.table_state.take();
+
+		let old_selection = table_state.selected().unwrap_or(0);
+		let max_selection = self.parents.len().saturating_sub(1);
+
+		let new_selection = match scroll_type {
+			ScrollType::Up => old_selection.saturating_sub(1),
+			ScrollType::Down => {
+				old_selection.saturating_add(1).min(max_selection)
+			}
+			_ => old_selection,
+		};
+
+		table_state.select(Some(new_selection));
+		self.table_state.set(table_state);
+	}
+
+	fn get_rows(&self) -> Vec<Row<'_>> {
+		self.parents
+			.iter()
+			.enumerate()
+			.map(|(idx, parent)| {
+				Row::new(vec![
+					Cell::from(Span::styled(
+						format!("{}", idx + 1),
+						self.theme.text(false, false),
+					)),
+					Cell::from(Span::styled(
+						parent.id.get_short_string(),
+						self.theme.commit_hash(false),
+					)),
+					Cell::from(Span::styled(
+						parent.summary.as_str(),
+						self.theme.text(true, false),
+					)),
+				])
+			})
+			.collect()
+	}
+}
+
+impl DrawableComponent for RevertMergePopup {
+	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
+		if self.visible {
+			const PERCENT_SIZE: Size = Size::new(60, 20);
+			const MIN_SIZE: Size = Size::new(40, 6);
+
+			let area = ui::centered_rect(
+				PERCENT_SIZE.width,
+				PERCENT_SIZE.height,
+				f.area(),
+			);
+			let area =
+				ui::rect_inside(MIN_SIZE, f.area().into(), area);
+			let area = area.intersection(rect);
+
+			let constraints = [
+				// mainline number
+				Constraint::Length(2),
+				Constraint::Length(7),
+				Constraint::Percentage(100),
+			];
+
+			let table = Table::new(self.get_rows(), constraints)
+				.column_spacing(1)
+				.row_highlight_style(self.theme.text(true, true))
+				.block(
+					Block::default()
+						.borders(Borders::ALL)
+						.title(Span::styled(
+							strings::POPUP_TITLE_REVERT_MERGE,
+							self.theme.title(true),
+						))
+						.border_style(self.theme.block(true))
+						.border_type(BorderType::Thick),
+				);
+
+			let mut table_state = self.table_state.take();
+
+			f.render_widget(Clear, area);
+			f.render_stateful_widget(table, area, &mut table_state);
+
+			self.table_state.set(table_state);
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for RevertMergePopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.visible || force_all {
+			if !force_all {
+				out.clear();
+			}
+
+			out.push(CommandInfo::new(
+				strings::commands::scroll(&self.key_config),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::close_popup(&self.key_config),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::revert_merge_confirm(
+					&self.key_config,
+				),
+				self.selection().is_some(),
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, event: &Event) -> Result<EventState> {
+		if self.visible {
+			if let Event::Key(key) = event {
+				let keys = &self.key_config.keys;
+
+				if key_match(key, keys.exit_popup) {
+					self.hide();
+				} else if key_match(key, keys.move_up) {
+					self.move_selection(ScrollType::Up);
+				} else if key_match(key, keys.move_down) {
+					self.move_selection(ScrollType::Down);
+				} else if key_match(key, keys.enter) {
+					self.confirm();
+				}
+			}
+
+			Ok(EventState::Consumed)
+		} else {
+			Ok(EventState::NotConsumed)
+		}
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
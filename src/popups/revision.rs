@@ -0,0 +1,281 @@
+use crate::components::{
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DrawableComponent, EventState, InputType, TextInputComponent,
+};
+use crate::{
+	app::Environment,
+	keys::{key_match, SharedKeyConfig},
+	popups::InspectCommitOpen,
+	queue::{InternalEvent, Queue, StackablePopupOpen},
+	strings,
+	ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::sync::{
+	get_commit_info, rev_parse_commit, CommitId, RepoPathRef,
+};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Constraint, Direction, Layout, Margin, Rect},
+	text::{Line, Span},
+	widgets::{Block, Borders, Clear, Paragraph},
+	Frame,
+};
+
+/// what to do with the revision once it is confirmed
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RevisionTarget {
+	/// compare the revision against `HEAD`
+	Compare,
+	/// compare the given target against the revision
+	CompareBase(CommitId),
+	/// open the reset popup for the revision
+	Reset,
+}
+
+pub struct RevisionPopup {
+	repo: RepoPathRef,
+	queue: Queue,
+	visible: bool,
+	target: RevisionTarget,
+	input: TextInputComponent,
+	resolved: Option<Result<(CommitId, String), String>>,
+	key_config: SharedKeyConfig,
+	theme: SharedTheme,
+}
+
+impl RevisionPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		let mut input = TextInputComponent::new(
+			env,
+			"",
+			&strings::revision_popup_msg(),
+			false,
+		)
+		.with_input_type(InputType::Singleline);
+		input.embed();
+		input.enabled(true);
+
+		Self {
+			repo: env.repo.clone(),
+			queue: env.queue.clone(),
+			visible: false,
+			target: RevisionTarget::Compare,
+			input,
+			resolved: None,
+			key_config: env.key_config.clone(),
+			theme: env.theme.clone(),
+		}
+	}
+
+	///
+	pub fn open(&mut self, target: RevisionTarget) -> Result<()> {
+		self.target = target;
+		self.resolved = None;
+		self.show()?;
+		self.input.show()?;
+		self.input.set_text(String::new());
+
+		Ok(())
+	}
+
+	fn validate(&mut self) {
+		let revision = self.input.get_text().trim();
+
+		self.resolved = if revision.is_empty() {
+			None
+		} else {
+			let repo = self.repo.borrow();
+			Some(
+				rev_parse_commit(&repo, revision)
+					.and_then(|id| {
+						let info = get_commit_info(&repo, &id)?;
+						let subject = info
+							.message
+							.lines()
+							.next()
+							.unwrap_or_default()
+							.to_string();
+						Ok((id, subject))
+					})
+					.map_err(|e| e.to_string()),
+			)
+		};
+	}
+
+	fn confirm(&mut self) {
+		let Some(Ok((id, _))) = &self.resolved else {
+			return;
+		};
+
+		self.queue.push(match self.target {
+			RevisionTarget::Compare => InternalEvent::OpenPopup(
+				StackablePopupOpen::CompareCommits(
+					InspectCommitOpen::new(*id),
+				),
+			),
+			RevisionTarget::CompareBase(target) => {
+				InternalEvent::OpenPopup(
+					StackablePopupOpen::CompareCommits(
+						InspectCommitOpen {
+							commit_id: target,
+							compare_id: Some(*id),
+							compare_mode: None,
+							tags: None,
+						},
+					),
+				)
+			}
+			RevisionTarget::Reset => {
+				InternalEvent::OpenResetPopup(*id)
+			}
+		});
+
+		self.hide();
+	}
+
+	fn get_feedback(&self) -> Line {
+		match &self.resolved {
+			Some(Ok((id, subject))) => Line::from(vec![
+				Span::styled(
+					id.get_short_string(),
+					self.theme.commit_hash(false),
+				),
+				Span::raw(" "),
+				Span::styled(
+					subject.clone(),
+					self.theme.text(true, false),
+				),
+			]),
+			Some(Err(e)) => Line::from(Span::styled(
+				e.clone(),
+				self.theme.text_danger(),
+			)),
+			None => Line::default(),
+		}
+	}
+}
+
+impl DrawableComponent for RevisionPopup {
+	fn draw(&self, f: &mut Frame, area: Rect) -> Result<()> {
+		if self.is_visible() {
+			const SIZE: (u16, u16) = (60, 4);
+			let area =
+				ui::centered_rect_absolute(SIZE.0, SIZE.1, area);
+
+			let mut block_style = self.theme.title(true);
+			if matches!(self.resolved, Some(Err(_))) {
+				block_style =
+					block_style.patch(self.theme.text_danger());
+			}
+
+			let title = match self.target {
+				RevisionTarget::Compare => {
+					strings::revision_popup_title_compare()
+				}
+				RevisionTarget::CompareBase(_) => {
+					strings::revision_popup_title_compare_base()
+				}
+				RevisionTarget::Reset => {
+					strings::revision_popup_title_reset()
+				}
+			};
+
+			f.render_widget(Clear, area);
+			f.render_widget(
+				Block::default()
+					.borders(Borders::all())
+					.style(block_style)
+					.title(Span::styled(
+						title,
+						self.theme.title(true),
+					)),
+				area,
+			);
+
+			let chunks = Layout::default()
+				.direction(Direction::Vertical)
+				.constraints(
+					[Constraint::Length(1), Constraint::Length(1)]
+						.as_ref(),
+				)
+				.split(area.inner(Margin {
+					horizontal: 1,
+					vertical: 1,
+				}));
+
+			self.input.draw(f, chunks[0])?;
+			f.render_widget(
+				Paragraph::new(self.get_feedback()),
+				chunks[1],
+			);
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for RevisionPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			out.push(
+				CommandInfo::new(
+					strings::commands::close_popup(&self.key_config),
+					true,
+					true,
+				)
+				.order(1),
+			);
+
+			out.push(
+				CommandInfo::new(
+					strings::commands::confirm_action(
+						&self.key_config,
+					),
+					matches!(self.resolved, Some(Ok(_))),
+					true,
+				)
+				.order(1),
+			);
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, event: &Event) -> Result<EventState> {
+		if self.is_visible() {
+			if let Event::Key(key) = &event {
+				if key_match(key, self.key_config.keys.exit_popup) {
+					self.hide();
+				} else if key_match(key, self.key_config.keys.enter) {
+					self.confirm();
+				} else if self.input.event(event)?.is_consumed() {
+					self.validate();
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
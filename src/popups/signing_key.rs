@@ -0,0 +1,277 @@
+use crate::{
+	app::Environment,
+	components::{
+		visibility_blocking, CommandBlocking, CommandInfo, Component,
+		DrawableComponent, EventState,
+	},
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, NeedsUpdate, Queue},
+	strings, try_or_popup,
+	ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::sync::{self, RepoPathRef, SigningKey, SigningStatus};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Margin, Rect},
+	text::{Line, Span, Text},
+	widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+	Frame,
+};
+
+/// picks the key commits get signed with and toggles signing,
+/// both persisted to the repo local config
+pub struct SigningKeyPopup {
+	repo: RepoPathRef,
+	queue: Queue,
+	visible: bool,
+	status: Option<SigningStatus>,
+	keys: Vec<SigningKey>,
+	selection: usize,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl SigningKeyPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			repo: env.repo.clone(),
+			queue: env.queue.clone(),
+			visible: false,
+			status: None,
+			keys: Vec::new(),
+			selection: 0,
+			theme: env.theme.clone(),
+			key_config: env.key_config.clone(),
+		}
+	}
+
+	///
+	pub fn open(&mut self) -> Result<()> {
+		self.keys = sync::list_signing_keys(&self.repo.borrow())?;
+		self.update_status()?;
+
+		self.selection = self
+			.status
+			.as_ref()
+			.and_then(|status| {
+				self.keys.iter().position(|key| {
+					status.key.as_deref() == Some(key.key.as_str())
+				})
+			})
+			.unwrap_or_default();
+
+		self.show()
+	}
+
+	fn update_status(&mut self) -> Result<()> {
+		self.status =
+			Some(sync::get_signing_status(&self.repo.borrow())?);
+
+		Ok(())
+	}
+
+	fn move_selection(&mut self, up: bool) {
+		let max = self.keys.len().saturating_sub(1);
+
+		self.selection = if up {
+			self.selection.saturating_sub(1)
+		} else {
+			self.selection.saturating_add(1).min(max)
+		};
+	}
+
+	fn select_key(&mut self) -> Result<()> {
+		if let Some(key) = self.keys.get(self.selection) {
+			sync::set_signing_key(&self.repo.borrow(), key)?;
+
+			self.hide();
+			self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+		}
+
+		Ok(())
+	}
+
+	fn toggle_signing(&mut self) -> Result<()> {
+		let enabled =
+			self.status.as_ref().is_some_and(|status| status.enabled);
+
+		sync::set_commit_signing(&self.repo.borrow(), !enabled)?;
+		self.update_status()?;
+
+		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+
+		Ok(())
+	}
+
+	fn text(&self) -> Text<'_> {
+		let mut lines = Vec::new();
+
+		if let Some(status) = &self.status {
+			lines.push(Line::from(vec![
+				Span::styled(
+					if status.enabled { "[x]" } else { "[_]" },
+					self.theme.option(status.enabled),
+				),
+				Span::styled(
+					strings::SIGNING_KEY_ENABLED,
+					self.theme.text(true, false),
+				),
+			]));
+			lines.push(Line::styled(
+				strings::signing_key_current(status),
+				self.theme.text(false, false),
+			));
+			lines.push(Line::default());
+		}
+
+		if self.keys.is_empty() {
+			lines.push(Line::styled(
+				strings::SIGNING_KEY_NONE_FOUND,
+				self.theme.text(false, false),
+			));
+		}
+
+		let current = self
+			.status
+			.as_ref()
+			.and_then(|status| status.key.as_deref());
+
+		lines.extend(self.keys.iter().enumerate().map(
+			|(idx, key)| {
+				let selected = idx == self.selection;
+
+				Line::from(Span::styled(
+					format!(
+						"{}{}",
+						if selected { "> " } else { "  " },
+						strings::signing_key_entry(
+							key.format,
+							&key.key,
+							&key.description,
+							current == Some(key.key.as_str()),
+						)
+					),
+					self.theme.text(true, selected),
+				))
+			},
+		));
+
+		Text::from(lines)
+	}
+}
+
+impl DrawableComponent for SigningKeyPopup {
+	fn draw(&self, f: &mut Frame, _rect: Rect) -> Result<()> {
+		if !self.visible {
+			return Ok(());
+		}
+
+		let area = ui::centered_rect(70, 50, f.area());
+
+		f.render_widget(Clear, area);
+		f.render_widget(
+			Block::default()
+				.title(Span::styled(
+					strings::POPUP_TITLE_SIGNING_KEY,
+					self.theme.title(true),
+				))
+				.borders(Borders::ALL)
+				.border_type(BorderType::Thick)
+				.border_style(self.theme.block(true)),
+			area,
+		);
+
+		f.render_widget(
+			Paragraph::new(self.text()).wrap(Wrap { trim: false }),
+			area.inner(Margin {
+				horizontal: 1,
+				vertical: 1,
+			}),
+		);
+
+		Ok(())
+	}
+}
+
+impl Component for SigningKeyPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.visible || force_all {
+			out.push(CommandInfo::new(
+				strings::commands::scroll(&self.key_config),
+				!self.keys.is_empty(),
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::signing_key_select(
+					&self.key_config,
+				),
+				!self.keys.is_empty(),
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::signing_toggle(&self.key_config),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::close_popup(&self.key_config),
+				true,
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.visible {
+			if let Event::Key(e) = ev {
+				let keys = &self.key_config.keys;
+
+				if key_match(e, keys.exit_popup) {
+					self.hide();
+				} else if key_match(e, keys.enter) {
+					try_or_popup!(
+						self,
+						"signing key error:",
+						self.select_key()
+					);
+				} else if key_match(e, keys.signing_toggle) {
+					try_or_popup!(
+						self,
+						"signing error:",
+						self.toggle_signing()
+					);
+				} else if key_match(e, keys.move_up) {
+					self.move_selection(true);
+				} else if key_match(e, keys.move_down) {
+					self.move_selection(false);
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
@@ -0,0 +1,334 @@
+use crate::components::{
+	visibility_blocking, CommandBlocking, CommandInfo, Component,
+	DiffComponent, DrawableComponent, EventState,
+	StatusTreeComponent,
+};
+use crate::{
+	app::Environment,
+	keys::{key_match, SharedKeyConfig},
+	options::SharedOptions,
+	queue::{InternalEvent, NeedsUpdate, Queue},
+	strings, try_or_popup, ui,
+};
+use anyhow::Result;
+use asyncgit::{
+	sync::{self, CommitId, RepoPathRef},
+	AsyncDiff, AsyncGitNotification, DiffParams, DiffType,
+};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Constraint, Direction, Layout, Rect},
+	widgets::Clear,
+	Frame,
+};
+
+/// diffs a stash against the working tree and takes single files or
+/// hunks from it
+pub struct StashWorktreePopup {
+	repo: RepoPathRef,
+	stash: Option<CommitId>,
+	files: StatusTreeComponent,
+	diff: DiffComponent,
+	git_diff: AsyncDiff,
+	visible: bool,
+	key_config: SharedKeyConfig,
+	queue: Queue,
+	options: SharedOptions,
+}
+
+impl StashWorktreePopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			repo: env.repo.clone(),
+			stash: None,
+			files: StatusTreeComponent::new(env, "", true),
+			diff: DiffComponent::new(env, true),
+			git_diff: AsyncDiff::new(
+				env.repo.borrow().clone(),
+				&env.sender_git,
+			),
+			visible: false,
+			key_config: env.key_config.clone(),
+			queue: env.queue.clone(),
+			options: env.options.clone(),
+		}
+	}
+
+	///
+	pub fn open(&mut self, stash: CommitId) -> Result<()> {
+		self.stash = Some(stash);
+		self.files.set_title(strings::stash_worktree_title(stash));
+		self.files.clear()?;
+		self.show()?;
+
+		self.files.focus(true);
+		self.diff.focus(false);
+		self.update_files()?;
+
+		Ok(())
+	}
+
+	///
+	pub fn any_work_pending(&self) -> bool {
+		self.git_diff.is_pending()
+	}
+
+	///
+	pub fn update_git(
+		&mut self,
+		ev: AsyncGitNotification,
+	) -> Result<()> {
+		if self.is_visible() && ev == AsyncGitNotification::Diff {
+			self.update_diff()?;
+		}
+
+		Ok(())
+	}
+
+	fn update_files(&mut self) -> Result<()> {
+		if let Some(stash) = self.stash {
+			let files = sync::stash_worktree_files(
+				&self.repo.borrow(),
+				stash,
+			)?;
+			self.files.update(&files)?;
+		}
+
+		self.update_diff()
+	}
+
+	/// called when any tree component changed selection
+	pub fn update_diff(&mut self) -> Result<()> {
+		if !self.is_visible() {
+			return Ok(());
+		}
+
+		if let (Some(stash), Some(file)) =
+			(self.stash, self.files.selection_file())
+		{
+			let diff_params = DiffParams {
+				path: file.path.clone(),
+				diff_type: DiffType::StashWorktree(stash),
+				options: self.diff.limit_options(
+					&file.path,
+					self.options.borrow().diff_options(),
+				),
+			};
+
+			if let Some((params, last)) = self.git_diff.last()? {
+				if params == diff_params {
+					self.diff.update(file.path, false, last);
+					return Ok(());
+				}
+			}
+
+			self.git_diff.request(diff_params)?;
+			self.diff.clear(true);
+		} else {
+			self.diff.clear(false);
+		}
+
+		Ok(())
+	}
+
+	fn apply_file(&mut self) -> Result<()> {
+		if let (Some(stash), Some(file)) =
+			(self.stash, self.files.selection_file())
+		{
+			sync::stash_apply_file(
+				&self.repo.borrow(),
+				stash,
+				&file.path,
+			)?;
+
+			self.applied()?;
+		}
+
+		Ok(())
+	}
+
+	fn apply_hunks(&mut self) -> Result<()> {
+		if let (Some(stash), Some(file)) =
+			(self.stash, self.files.selection_file())
+		{
+			sync::stash_apply_hunks(
+				&self.repo.borrow(),
+				stash,
+				&file.path,
+				&self.diff.selected_hunk_hashes(),
+				Some(self.options.borrow().diff_options()),
+			)?;
+
+			self.applied()?;
+		}
+
+		Ok(())
+	}
+
+	fn applied(&mut self) -> Result<()> {
+		self.update_files()?;
+		self.git_diff.refresh()?;
+		if self.files.selection_file().is_none() {
+			self.focus_files();
+		}
+		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+
+		Ok(())
+	}
+
+	fn focus_files(&mut self) {
+		self.files.focus(true);
+		self.diff.focus(false);
+	}
+
+	fn can_focus_diff(&self) -> bool {
+		self.files.selection_file().is_some()
+	}
+}
+
+impl DrawableComponent for StashWorktreePopup {
+	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
+		if self.is_visible() {
+			let area = ui::centered_rect(90, 90, rect);
+
+			let chunks = Layout::default()
+				.direction(Direction::Horizontal)
+				.constraints(
+					[
+						Constraint::Percentage(30),
+						Constraint::Percentage(70),
+					]
+					.as_ref(),
+				)
+				.split(area);
+
+			f.render_widget(Clear, area);
+
+			self.files.draw(f, chunks[0])?;
+			self.diff.draw(f, chunks[1])?;
+		}
+
+		Ok(())
+	}
+}
+
+impl Component for StashWorktreePopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.is_visible() || force_all {
+			if self.diff.focused() {
+				self.diff.commands(out, force_all);
+			}
+
+			out.push(
+				CommandInfo::new(
+					strings::commands::close_popup(&self.key_config),
+					true,
+					true,
+				)
+				.order(1),
+			);
+
+			out.push(CommandInfo::new(
+				strings::commands::diff_focus_right(&self.key_config),
+				self.can_focus_diff(),
+				!self.diff.focused() || force_all,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::diff_focus_left(&self.key_config),
+				true,
+				self.diff.focused() || force_all,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::stash_worktree_apply_file(
+					&self.key_config,
+				),
+				self.files.selection_file().is_some(),
+				!self.diff.focused() || force_all,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::stash_worktree_apply_hunks(
+					&self.key_config,
+				),
+				true,
+				self.diff.focused() || force_all,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.is_visible() {
+			if let Event::Key(key) = ev {
+				if self.diff.focused() {
+					if key_match(key, self.key_config.keys.exit_popup)
+						|| key_match(
+							key,
+							self.key_config.keys.move_left,
+						) {
+						self.focus_files();
+					} else if key_match(
+						key,
+						self.key_config.keys.diff_apply_hunks,
+					) {
+						try_or_popup!(
+							self,
+							"apply hunks error:",
+							self.apply_hunks()
+						);
+					} else {
+						self.diff.event(ev)?;
+					}
+				} else if key_match(
+					key,
+					self.key_config.keys.exit_popup,
+				) {
+					self.hide();
+				} else if key_match(
+					key,
+					self.key_config.keys.move_right,
+				) && self.can_focus_diff()
+				{
+					self.files.focus(false);
+					self.diff.focus(true);
+				} else if key_match(
+					key,
+					self.key_config.keys.stash_apply,
+				) {
+					try_or_popup!(
+						self,
+						"apply file error:",
+						self.apply_file()
+					);
+				} else {
+					self.files.event(ev)?;
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+		self.files.show()?;
+
+		Ok(())
+	}
+}
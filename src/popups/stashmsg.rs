@@ -5,20 +5,41 @@ use crate::components::{
 use crate::{
 	app::Environment,
 	keys::{key_match, SharedKeyConfig},
+	options::SharedOptions,
 	queue::{AppTabs, InternalEvent, Queue},
 	strings,
 	tabs::StashingOptions,
+	ui::style::SharedTheme,
 };
 use anyhow::Result;
 use asyncgit::sync::{self, RepoPathRef};
 use crossterm::event::Event;
-use ratatui::{layout::Rect, Frame};
+use ratatui::{
+	layout::Rect,
+	text::{Line, Span},
+	widgets::Paragraph,
+	Frame,
+};
+
+/// which changes the popup stashes
+#[derive(Debug, Default, Clone)]
+pub enum StashSelection {
+	#[default]
+	All,
+	/// only these files
+	Paths(Vec<String>),
+	/// only these hunks (by header hash) of a file's unstaged changes
+	Hunks { path: String, hunks: Vec<u64> },
+}
 
 pub struct StashMsgPopup {
 	repo: RepoPathRef,
 	options: StashingOptions,
+	selection: StashSelection,
+	shared_options: SharedOptions,
 	input: TextInputComponent,
 	queue: Queue,
+	theme: SharedTheme,
 	key_config: SharedKeyConfig,
 }
 
@@ -26,6 +47,10 @@ impl DrawableComponent for StashMsgPopup {
 	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
 		self.input.draw(f, rect)?;
 
+		if self.is_visible() {
+			self.draw_options(f);
+		}
+
 		Ok(())
 	}
 }
@@ -46,6 +71,20 @@ impl Component for StashMsgPopup {
 				true,
 				true,
 			));
+			out.push(CommandInfo::new(
+				strings::commands::stash_msg_toggle_untracked(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::stash_msg_toggle_ignored(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
 		}
 
 		visibility_blocking(self)
@@ -53,22 +92,41 @@ impl Component for StashMsgPopup {
 
 	fn event(&mut self, ev: &Event) -> Result<EventState> {
 		if self.is_visible() {
+			if let Event::Key(e) = ev {
+				if key_match(
+					e,
+					self.key_config.keys.stash_msg_toggle_untracked,
+				) {
+					self.options.stash_untracked =
+						!self.options.stash_untracked;
+					self.shared_options
+						.borrow_mut()
+						.set_stash_untracked(
+							self.options.stash_untracked,
+						);
+					return Ok(EventState::Consumed);
+				} else if key_match(
+					e,
+					self.key_config.keys.stash_msg_toggle_ignored,
+				) {
+					self.options.stash_ignored =
+						!self.options.stash_ignored;
+					self.shared_options
+						.borrow_mut()
+						.set_stash_ignored(
+							self.options.stash_ignored,
+						);
+					return Ok(EventState::Consumed);
+				}
+			}
+
 			if self.input.event(ev)?.is_consumed() {
 				return Ok(EventState::Consumed);
 			}
 
 			if let Event::Key(e) = ev {
 				if key_match(e, self.key_config.keys.enter) {
-					let result = sync::stash_save(
-						&self.repo.borrow(),
-						if self.input.get_text().is_empty() {
-							None
-						} else {
-							Some(self.input.get_text())
-						},
-						self.options.stash_untracked,
-						self.options.keep_index,
-					);
+					let result = self.stash();
 					match result {
 						Ok(_) => {
 							self.input.clear();
@@ -124,6 +182,8 @@ impl StashMsgPopup {
 	pub fn new(env: &Environment) -> Self {
 		Self {
 			options: StashingOptions::default(),
+			selection: StashSelection::default(),
+			shared_options: env.options.clone(),
 			queue: env.queue.clone(),
 			input: TextInputComponent::new(
 				env,
@@ -132,13 +192,110 @@ impl StashMsgPopup {
 				true,
 			)
 			.with_input_type(InputType::Singleline),
+			theme: env.theme.clone(),
 			key_config: env.key_config.clone(),
 			repo: env.repo.clone(),
 		}
 	}
 
+	/// toggles shown in the bottom border of the input
+	fn draw_options(&self, f: &mut Frame) {
+		let option = |on: bool, label: &'static str| {
+			[
+				Span::styled(
+					if on { "[x]" } else { "[_]" },
+					self.theme.option(on),
+				),
+				Span::styled(label, self.theme.text(true, false)),
+			]
+		};
+
+		let selection = match &self.selection {
+			StashSelection::All => String::new(),
+			StashSelection::Paths(paths) => format!(
+				" · {}",
+				strings::stash_popup_marked_files(paths.len())
+			),
+			StashSelection::Hunks { path, hunks } => {
+				strings::stash_popup_hunks(hunks.len(), path)
+			}
+		};
+		let selection =
+			Span::styled(selection, self.theme.text(true, false));
+		let line =
+			if matches!(self.selection, StashSelection::Hunks { .. })
+			{
+				Line::from(selection)
+			} else {
+				let mut spans = [
+					option(
+						self.options.stash_untracked,
+						" untracked ",
+					),
+					option(self.options.stash_ignored, " ignored"),
+				]
+				.concat();
+				spans.push(selection);
+				Line::from(spans)
+			};
+
+		let rect = {
+			let mut rect = self.input.get_area();
+			rect.y += rect.height.saturating_sub(1);
+			rect.height = 1;
+			rect.x += 1;
+			rect.width = rect.width.saturating_sub(2);
+			rect
+		};
+
+		f.render_widget(Paragraph::new(line), rect);
+	}
+
 	///
-	pub fn options(&mut self, options: StashingOptions) {
+	pub fn options(
+		&mut self,
+		options: StashingOptions,
+		selection: StashSelection,
+	) {
 		self.options = options;
+		self.selection = selection;
+	}
+
+	fn stash(&self) -> Result<sync::CommitId> {
+		let repo = self.repo.borrow();
+		let message = if self.input.get_text().is_empty() {
+			None
+		} else {
+			Some(self.input.get_text())
+		};
+
+		let id = match &self.selection {
+			StashSelection::All => sync::stash_save(
+				&repo,
+				message,
+				self.options.stash_untracked,
+				self.options.stash_ignored,
+				self.options.keep_index,
+			),
+			StashSelection::Paths(paths) => sync::stash_save_paths(
+				&repo,
+				message,
+				paths,
+				self.options.stash_untracked,
+				self.options.stash_ignored,
+				self.options.keep_index,
+			),
+			StashSelection::Hunks { path, hunks } => {
+				sync::stash_save_hunks(
+					&repo,
+					message,
+					path,
+					hunks,
+					Some(self.shared_options.borrow().diff_options()),
+				)
+			}
+		}?;
+
+		Ok(id)
 	}
 }
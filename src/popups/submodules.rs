@@ -11,9 +11,10 @@ use crate::{
 };
 use anyhow::Result;
 use asyncgit::sync::{
-	get_submodules, repo_dir, submodule_parent_info,
-	update_submodule, RepoPathRef, SubmoduleInfo,
-	SubmoduleParentInfo,
+	get_submodule_states, get_submodules, init_submodule, repo_dir,
+	submodule_parent_info, sync_submodule, update_submodule,
+	RepoPath, RepoPathRef, SubmoduleInfo, SubmoduleParentInfo,
+	SubmoduleState,
 };
 use crossterm::event::Event;
 use ratatui::{
@@ -24,7 +25,7 @@ use ratatui::{
 	widgets::{Block, Borders, Clear, Paragraph},
 	Frame,
 };
-use std::cell::Cell;
+use std::{cell::Cell, collections::BTreeMap};
 use ui::style::SharedTheme;
 use unicode_truncate::UnicodeTruncateStr;
 
@@ -34,6 +35,7 @@ pub struct SubmodulesListPopup {
 	repo_path: String,
 	queue: Queue,
 	submodules: Vec<SubmoduleInfo>,
+	states: BTreeMap<String, SubmoduleState>,
 	submodule_parent: Option<SubmoduleParentInfo>,
 	visible: bool,
 	current_height: Cell<u16>,
@@ -132,6 +134,26 @@ impl Component for SubmodulesListPopup {
 				true,
 			));
 
+			out.push(CommandInfo::new(
+				strings::commands::update_submodule_recursive(
+					&self.key_config,
+				),
+				self.is_valid_selection(),
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::init_submodule(&self.key_config),
+				self.is_valid_selection(),
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::sync_submodule(&self.key_config),
+				self.is_valid_selection(),
+				true,
+			));
+
 			out.push(CommandInfo::new(
 				strings::commands::open_submodule_parent(
 					&self.key_config,
@@ -186,23 +208,36 @@ impl Component for SubmodulesListPopup {
 			} else if key_match(
 				e,
 				self.key_config.keys.update_submodule,
+			) || key_match(
+				e,
+				self.key_config.keys.update_submodule_recursive,
 			) {
-				if let Some(submodule) = self.selected_entry() {
-					try_or_popup!(
-						self,
-						"update submodule:",
-						update_submodule(
-							&self.repo.borrow(),
-							&submodule.name,
-						)
-					);
-
-					self.update_submodules()?;
-
-					self.queue.push(InternalEvent::Update(
-						NeedsUpdate::ALL,
-					));
-				}
+				let recursive = key_match(
+					e,
+					self.key_config.keys.update_submodule_recursive,
+				);
+				self.run_on_selected(
+					"update submodule:",
+					|repo, name| {
+						update_submodule(repo, name, recursive)
+					},
+				)?;
+			} else if key_match(
+				e,
+				self.key_config.keys.init_submodule,
+			) {
+				self.run_on_selected(
+					"init submodule:",
+					init_submodule,
+				)?;
+			} else if key_match(
+				e,
+				self.key_config.keys.sync_submodule,
+			) {
+				self.run_on_selected(
+					"sync submodule:",
+					sync_submodule,
+				)?;
 			} else if key_match(
 				e,
 				self.key_config.keys.view_submodule_parent,
@@ -243,6 +278,7 @@ impl SubmodulesListPopup {
 	pub fn new(env: &Environment) -> Self {
 		Self {
 			submodules: Vec::new(),
+			states: BTreeMap::new(),
 			submodule_parent: None,
 			scroll: VerticalScroll::new(),
 			queue: env.queue.clone(),
@@ -268,6 +304,7 @@ impl SubmodulesListPopup {
 	pub fn update_submodules(&mut self) -> Result<()> {
 		if self.is_visible() {
 			self.submodules = get_submodules(&self.repo.borrow())?;
+			self.states = get_submodule_states(&self.repo.borrow())?;
 
 			self.submodule_parent =
 				submodule_parent_info(&self.repo.borrow())?;
@@ -281,6 +318,36 @@ impl SubmodulesListPopup {
 		Ok(())
 	}
 
+	/// runs `f` with the name of the selected submodule and refreshes
+	/// everything afterwards
+	fn run_on_selected(
+		&mut self,
+		msg: &str,
+		f: impl FnOnce(&RepoPath, &str) -> asyncgit::Result<()>,
+	) -> Result<()> {
+		let Some(name) = self
+			.selected_entry()
+			.map(|submodule| submodule.name.clone())
+		else {
+			return Ok(());
+		};
+
+		try_or_popup!(self, msg, f(&self.repo.borrow(), &name));
+
+		self.update_submodules()?;
+
+		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+
+		Ok(())
+	}
+
+	fn state_of(
+		&self,
+		submodule: &SubmoduleInfo,
+	) -> Option<&SubmoduleState> {
+		self.states.get(submodule.path.to_string_lossy().as_ref())
+	}
+
 	fn selected_entry(&self) -> Option<&SubmoduleInfo> {
 		self.submodules.get(self.selection as usize)
 	}
@@ -387,7 +454,15 @@ impl SubmodulesListPopup {
 				theme.text(true, selected),
 			);
 
-			txt.push(Line::from(vec![span_name, span_hash]));
+			let mut spans = vec![span_name, span_hash];
+			if let Some(state) = self.state_of(submodule) {
+				spans.push(Span::styled(
+					strings::submodule_state(state),
+					theme.text(false, selected),
+				));
+			}
+
+			txt.push(Line::from(spans));
 		}
 
 		Text::from(txt)
@@ -425,6 +500,16 @@ impl SubmodulesListPopup {
 					theme.text(true, false),
 				);
 
+				let span_title_state =
+					Span::styled("State:", theme.text(false, false));
+				let span_state = Span::styled(
+					self.state_of(submodule).map_or_else(
+						|| String::from("clean"),
+						strings::submodule_state,
+					),
+					theme.text(true, false),
+				);
+
 				Text::from(vec![
 					Line::from(vec![span_title_path]),
 					Line::from(vec![span_path]),
@@ -437,6 +522,9 @@ impl SubmodulesListPopup {
 					Line::from(vec![]),
 					Line::from(vec![span_title_status]),
 					Line::from(vec![span_status]),
+					Line::from(vec![]),
+					Line::from(vec![span_title_state]),
+					Line::from(vec![span_state]),
 				])
 			},
 		)
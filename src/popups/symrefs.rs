@@ -0,0 +1,310 @@
+use crate::{
+	app::Environment,
+	components::{
+		visibility_blocking, CommandBlocking, CommandInfo, Component,
+		DrawableComponent, EventState,
+	},
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, NeedsUpdate, Queue},
+	strings,
+	ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::sync::{
+	self, get_symref_targets, get_symrefs, RepoPathRef, SymRef,
+};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Margin, Rect},
+	text::{Line, Span, Text},
+	widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+	Frame,
+};
+
+enum SymRefsStep {
+	List,
+	/// picking a new target for the symref at the given index
+	Target(usize, Vec<String>),
+}
+
+/// shows symbolic refs (`HEAD`, `refs/remotes/*/HEAD`) and allows
+/// pointing them somewhere else, e.g. the default branch of a bare repo
+pub struct SymRefsPopup {
+	repo: RepoPathRef,
+	queue: Queue,
+	visible: bool,
+	bare: bool,
+	symrefs: Vec<SymRef>,
+	step: SymRefsStep,
+	selection: usize,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl SymRefsPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			repo: env.repo.clone(),
+			queue: env.queue.clone(),
+			visible: false,
+			bare: false,
+			symrefs: Vec::new(),
+			step: SymRefsStep::List,
+			selection: 0,
+			theme: env.theme.clone(),
+			key_config: env.key_config.clone(),
+		}
+	}
+
+	///
+	pub fn open(&mut self) -> Result<()> {
+		self.bare = sync::is_bare_repo(&self.repo.borrow())
+			.unwrap_or_default();
+		self.symrefs = get_symrefs(&self.repo.borrow())?;
+		self.step = SymRefsStep::List;
+		self.selection = 0;
+
+		self.show()
+	}
+
+	/// `HEAD` of a worktree is changed by checking out a branch
+	fn can_change(&self, symref: &SymRef) -> bool {
+		self.bare || symref.name != "HEAD"
+	}
+
+	fn len(&self) -> usize {
+		match &self.step {
+			SymRefsStep::List => self.symrefs.len(),
+			SymRefsStep::Target(_, targets) => targets.len(),
+		}
+	}
+
+	fn move_selection(&mut self, up: bool) {
+		let max = self.len().saturating_sub(1);
+
+		self.selection = if up {
+			self.selection.saturating_sub(1)
+		} else {
+			self.selection.saturating_add(1).min(max)
+		};
+	}
+
+	fn can_confirm(&self) -> bool {
+		match &self.step {
+			SymRefsStep::List => self
+				.symrefs
+				.get(self.selection)
+				.is_some_and(|symref| self.can_change(symref)),
+			SymRefsStep::Target(_, targets) => {
+				self.selection < targets.len()
+			}
+		}
+	}
+
+	fn confirm(&mut self) -> Result<()> {
+		match &self.step {
+			SymRefsStep::List => {
+				let idx = self.selection;
+				let symref = &self.symrefs[idx];
+				let targets = get_symref_targets(
+					&self.repo.borrow(),
+					&symref.name,
+				)?;
+
+				self.selection = targets
+					.iter()
+					.position(|target| *target == symref.target)
+					.unwrap_or_default();
+				self.step = SymRefsStep::Target(idx, targets);
+			}
+			SymRefsStep::Target(idx, targets) => {
+				let name = self.symrefs[*idx].name.clone();
+				let target = targets[self.selection].clone();
+
+				if let Err(e) = sync::set_symref(
+					&self.repo.borrow(),
+					&name,
+					&target,
+				) {
+					self.queue.push(InternalEvent::ShowErrorMsg(
+						format!("symref error:\n{e}"),
+					));
+				}
+
+				self.queue.push(InternalEvent::Update(
+					NeedsUpdate::ALL | NeedsUpdate::BRANCHES,
+				));
+
+				let idx = *idx;
+				self.symrefs = get_symrefs(&self.repo.borrow())?;
+				self.step = SymRefsStep::List;
+				self.selection = idx;
+			}
+		}
+
+		Ok(())
+	}
+
+	fn back(&mut self) {
+		if let SymRefsStep::Target(idx, _) = self.step {
+			self.selection = idx;
+			self.step = SymRefsStep::List;
+		} else {
+			self.hide();
+		}
+	}
+
+	fn line(&self, idx: usize, text: &str) -> Line<'_> {
+		let selected = idx == self.selection;
+
+		Line::from(Span::styled(
+			format!("{}{text}", if selected { "> " } else { "  " }),
+			self.theme.text(true, selected),
+		))
+	}
+
+	fn text(&self) -> Text<'_> {
+		let lines = match &self.step {
+			SymRefsStep::List => self
+				.symrefs
+				.iter()
+				.enumerate()
+				.map(|(idx, symref)| {
+					self.line(
+						idx,
+						&strings::symref_entry(
+							&symref.name,
+							&symref.target,
+							!self.can_change(symref),
+						),
+					)
+				})
+				.collect::<Vec<_>>(),
+			SymRefsStep::Target(symref, targets) => {
+				let current = &self.symrefs[*symref].target;
+
+				targets
+					.iter()
+					.enumerate()
+					.map(|(idx, target)| {
+						self.line(
+							idx,
+							&strings::symref_target_entry(
+								target,
+								target == current,
+							),
+						)
+					})
+					.collect::<Vec<_>>()
+			}
+		};
+
+		Text::from(lines)
+	}
+}
+
+impl DrawableComponent for SymRefsPopup {
+	fn draw(&self, f: &mut Frame, _rect: Rect) -> Result<()> {
+		if !self.visible {
+			return Ok(());
+		}
+
+		let area = ui::centered_rect(60, 40, f.area());
+
+		let title = match &self.step {
+			SymRefsStep::List => {
+				strings::POPUP_TITLE_SYMREFS.to_string()
+			}
+			SymRefsStep::Target(idx, _) => {
+				strings::symref_target_title(&self.symrefs[*idx].name)
+			}
+		};
+
+		f.render_widget(Clear, area);
+		f.render_widget(
+			Block::default()
+				.title(Span::styled(title, self.theme.title(true)))
+				.borders(Borders::ALL)
+				.border_type(BorderType::Thick)
+				.border_style(self.theme.block(true)),
+			area,
+		);
+
+		f.render_widget(
+			Paragraph::new(self.text()).wrap(Wrap { trim: false }),
+			area.inner(Margin {
+				horizontal: 1,
+				vertical: 1,
+			}),
+		);
+
+		Ok(())
+	}
+}
+
+impl Component for SymRefsPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.visible || force_all {
+			out.push(CommandInfo::new(
+				strings::commands::scroll(&self.key_config),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::confirm_action(&self.key_config),
+				self.can_confirm(),
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::close_popup(&self.key_config),
+				true,
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.visible {
+			if let Event::Key(e) = ev {
+				let keys = &self.key_config.keys;
+
+				if key_match(e, keys.exit_popup) {
+					self.back();
+				} else if key_match(e, keys.enter) {
+					if self.can_confirm() {
+						self.confirm()?;
+					}
+				} else if key_match(e, keys.move_up) {
+					self.move_selection(true);
+				} else if key_match(e, keys.move_down) {
+					self.move_selection(false);
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
@@ -6,7 +6,7 @@ use crate::{
 	app::Environment,
 	keys::{key_match, SharedKeyConfig},
 	queue::{InternalEvent, NeedsUpdate, Queue},
-	strings, try_or_popup,
+	strings,
 };
 use anyhow::Result;
 use asyncgit::sync::{
@@ -78,13 +78,13 @@ impl Component for TagCommitPopup {
 					&& key_match(e, self.key_config.keys.enter)
 					&& self.is_valid_tag()
 				{
-					try_or_popup!(self, "tag error:", self.tag());
+					self.tag();
 					return Ok(EventState::Consumed);
 				}
 				if is_annotation_mode
 					&& key_match(e, self.key_config.keys.commit)
 				{
-					try_or_popup!(self, "tag error:", self.tag());
+					self.tag();
 					return Ok(EventState::Consumed);
 				} else if key_match(
 					e,
@@ -161,7 +161,7 @@ impl TagCommitPopup {
 		}
 	}
 
-	pub fn tag(&mut self) -> Result<()> {
+	pub fn tag(&mut self) {
 		let gpgsign =
 			get_config_string(&self.repo.borrow(), "tag.gpgsign")
 				.ok()
@@ -169,10 +169,15 @@ impl TagCommitPopup {
 				.and_then(|val| val.parse::<bool>().ok())
 				.unwrap_or_default();
 
-		anyhow::ensure!(!gpgsign, "config tag.gpgsign=true detected.\ngpg signing not supported.\ndeactivate in your repo/gitconfig to be able to tag without signing.");
-
 		let (tag_name, tag_annotation) = self.tag_info();
 
+		// signed tags are annotated, ask for the annotation like
+		// `git tag` opens the editor
+		if gpgsign && tag_annotation.is_none() {
+			self.start_annotate_mode();
+			return;
+		}
+
 		if let Some(commit_id) = self.commit_id {
 			let result = sync::tag_commit(
 				&self.repo.borrow(),
@@ -201,8 +206,6 @@ impl TagCommitPopup {
 				}
 			}
 		}
-
-		Ok(())
 	}
 
 	fn start_annotate_mode(&mut self) {
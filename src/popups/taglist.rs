@@ -6,7 +6,8 @@ use crate::{
 	app::Environment,
 	components::ScrollType,
 	keys::{key_match, SharedKeyConfig},
-	queue::{Action, InternalEvent, Queue},
+	popups::InspectCommitOpen,
+	queue::{Action, InternalEvent, Queue, StackablePopupOpen},
 	strings,
 	ui::{self, Size},
 	AsyncNotification,
@@ -20,18 +21,19 @@ use asyncgit::{
 		BasicAuthCredential,
 	},
 	sync::{
-		self, get_tags_with_metadata, RepoPathRef, TagWithMetadata,
+		self, get_tag_details, get_tags_with_metadata, RepoPathRef,
+		TagDetails, TagWithMetadata,
 	},
 	AsyncGitNotification,
 };
 
 use crossterm::event::Event;
 use ratatui::{
-	layout::{Constraint, Margin, Rect},
-	text::Span,
+	layout::{Constraint, Direction, Layout, Margin, Rect},
+	text::{Line, Span, Text},
 	widgets::{
-		Block, BorderType, Borders, Cell, Clear, Row, Table,
-		TableState,
+		Block, BorderType, Borders, Cell, Clear, Paragraph, Row,
+		Table, TableState, Wrap,
 	},
 	Frame,
 };
@@ -43,6 +45,8 @@ pub struct TagListPopup {
 	theme: SharedTheme,
 	queue: Queue,
 	tags: Option<Vec<TagWithMetadata>>,
+	/// annotation details of the selected tag
+	details: Option<TagDetails>,
 	visible: bool,
 	table_state: std::cell::Cell<TableState>,
 	current_height: std::cell::Cell<usize>,
@@ -56,8 +60,8 @@ pub struct TagListPopup {
 impl DrawableComponent for TagListPopup {
 	fn draw(&self, f: &mut Frame, rect: Rect) -> Result<()> {
 		if self.visible {
-			const PERCENT_SIZE: Size = Size::new(80, 50);
-			const MIN_SIZE: Size = Size::new(60, 20);
+			const PERCENT_SIZE: Size = Size::new(80, 70);
+			const MIN_SIZE: Size = Size::new(60, 24);
 
 			let area = ui::centered_rect(
 				PERCENT_SIZE.width,
@@ -68,6 +72,20 @@ impl DrawableComponent for TagListPopup {
 				ui::rect_inside(MIN_SIZE, f.area().into(), area);
 			let area = area.intersection(rect);
 
+			f.render_widget(Clear, area);
+
+			let chunks = Layout::default()
+				.direction(Direction::Vertical)
+				.constraints(
+					[
+						Constraint::Percentage(60),
+						Constraint::Percentage(40),
+					]
+					.as_ref(),
+				)
+				.split(area);
+			let area = chunks[0];
+
 			let tag_name_width =
 				self.tags.as_ref().map_or(0, |tags| {
 					tags.iter()
@@ -108,7 +126,6 @@ impl DrawableComponent for TagListPopup {
 
 			let mut table_state = self.table_state.take();
 
-			f.render_widget(Clear, area);
 			f.render_stateful_widget(table, area, &mut table_state);
 
 			let area = area.inner(Margin {
@@ -127,6 +144,8 @@ impl DrawableComponent for TagListPopup {
 
 			self.table_state.set(table_state);
 			self.current_height.set(area.height.into());
+
+			self.draw_details(f, chunks[1]);
 		}
 
 		Ok(())
@@ -166,6 +185,13 @@ impl Component for TagListPopup {
 				self.valid_selection(),
 				true,
 			));
+			out.push(CommandInfo::new(
+				strings::commands::compare_with_head(
+					&self.key_config,
+				),
+				self.valid_selection(),
+				true,
+			));
 			out.push(CommandInfo::new(
 				strings::commands::push_tags(&self.key_config),
 				self.has_remotes,
@@ -257,11 +283,27 @@ impl Component for TagListPopup {
 							Ok(EventState::Consumed)
 						},
 					);
+				} else if key_match(
+					key,
+					self.key_config.keys.compare_commits,
+				) {
+					if let Some(commit_id) =
+						self.selected_tag().map(|tag| tag.commit_id)
+					{
+						self.hide();
+						self.queue.push(InternalEvent::OpenPopup(
+							StackablePopupOpen::CompareCommits(
+								InspectCommitOpen::new(commit_id),
+							),
+						));
+					}
 				} else if key_match(key, self.key_config.keys.push)
 					&& self.has_remotes
 				{
 					self.queue.push(InternalEvent::PushTags);
 				}
+
+				self.update_details();
 			}
 
 			Ok(EventState::Consumed)
@@ -291,6 +333,7 @@ impl TagListPopup {
 			theme: env.theme.clone(),
 			queue: env.queue.clone(),
 			tags: None,
+			details: None,
 			visible: false,
 			has_remotes: false,
 			table_state: std::cell::Cell::new(TableState::default()),
@@ -370,6 +413,7 @@ impl TagListPopup {
 		let tags = get_tags_with_metadata(&self.repo.borrow())?;
 
 		self.tags = Some(tags);
+		self.update_details();
 
 		Ok(())
 	}
@@ -416,6 +460,83 @@ impl TagListPopup {
 		needs_update
 	}
 
+	/// resolves the tag object of the selection if it changed
+	fn update_details(&mut self) {
+		let selected =
+			self.selected_tag().map(|tag| tag.name.clone());
+
+		if self.details.as_ref().map(|details| &details.name)
+			== selected.as_ref()
+		{
+			return;
+		}
+
+		self.details = selected.and_then(|name| {
+			get_tag_details(&self.repo.borrow(), &name).ok().flatten()
+		});
+	}
+
+	fn details_text(&self) -> Text<'_> {
+		let Some(details) = &self.details else {
+			return Text::from(Line::styled(
+				strings::TAG_DETAILS_LIGHTWEIGHT,
+				self.theme.text(false, false),
+			));
+		};
+
+		let detail = |label: String, value: String| {
+			Line::from(vec![
+				Span::styled(label, self.theme.text(false, false)),
+				Span::styled(value, self.theme.text(true, false)),
+			])
+		};
+
+		let mut lines = vec![
+			detail(
+				strings::tag_details_tagger(),
+				format!(
+					"{} <{}>",
+					details.tagger_name, details.tagger_email
+				),
+			),
+			detail(
+				strings::commit::details_date(),
+				time_to_string(details.time, false),
+			),
+			detail(
+				strings::tag_details_signature(),
+				strings::tag_signature(details.signature),
+			),
+			Line::default(),
+		];
+
+		lines.extend(details.message.lines().map(|line| {
+			Line::styled(
+				line.to_string(),
+				self.theme.text(true, false),
+			)
+		}));
+
+		Text::from(lines)
+	}
+
+	fn draw_details(&self, f: &mut Frame, area: Rect) {
+		f.render_widget(
+			Paragraph::new(self.details_text())
+				.block(
+					Block::default()
+						.borders(Borders::ALL)
+						.title(Span::styled(
+							strings::title_tag_details(),
+							self.theme.title(false),
+						))
+						.border_style(self.theme.block(false)),
+				)
+				.wrap(Wrap { trim: false }),
+			area,
+		);
+	}
+
 	fn show_annotation(&self) {
 		if let Some(tag) = self.selected_tag() {
 			if let Some(annotation) = &tag.annotation {
@@ -0,0 +1,232 @@
+use crate::{
+	app::Environment,
+	components::{
+		time_to_string, visibility_blocking, CommandBlocking,
+		CommandInfo, Component, DrawableComponent, EventState,
+	},
+	keys::{key_match, SharedKeyConfig},
+	queue::{Action, InternalEvent, Queue},
+	strings,
+	ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::sync::{self, RepoPath, RepoPathRef, UndoSnapshot};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Margin, Rect},
+	text::{Line, Span, Text},
+	widgets::{Block, BorderType, Borders, Clear, Paragraph},
+	Frame,
+};
+
+/// records the state before `operation` so it can be undone, a
+/// failing snapshot is only logged to not block the operation
+pub fn record_undo(repo: &RepoPath, operation: &str) {
+	if let Err(e) = sync::undo_snapshot(repo, operation) {
+		log::error!("undo snapshot failed: {e}");
+	}
+}
+
+/// lists the states recorded before destructive operations to go
+/// back to one of them
+pub struct UndoPopup {
+	repo: RepoPathRef,
+	queue: Queue,
+	visible: bool,
+	snapshots: Vec<UndoSnapshot>,
+	selection: usize,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl UndoPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			repo: env.repo.clone(),
+			queue: env.queue.clone(),
+			visible: false,
+			snapshots: Vec::new(),
+			selection: 0,
+			theme: env.theme.clone(),
+			key_config: env.key_config.clone(),
+		}
+	}
+
+	///
+	pub fn open(&mut self) -> Result<()> {
+		self.snapshots = sync::undo_snapshots(&self.repo.borrow())?;
+		self.selection = 0;
+
+		self.show()
+	}
+
+	fn move_selection(&mut self, up: bool) {
+		let max = self.snapshots.len().saturating_sub(1);
+
+		self.selection = if up {
+			self.selection.saturating_sub(1)
+		} else {
+			self.selection.saturating_add(1).min(max)
+		};
+	}
+
+	fn selected(&self) -> Option<&UndoSnapshot> {
+		self.snapshots.get(self.selection)
+	}
+
+	fn text(&self) -> Text<'_> {
+		if self.snapshots.is_empty() {
+			return Text::from(Span::styled(
+				strings::UNDO_EMPTY,
+				self.theme.text(false, false),
+			));
+		}
+
+		Text::from(
+			self.snapshots
+				.iter()
+				.enumerate()
+				.map(|(idx, snapshot)| {
+					let selected = idx == self.selection;
+
+					Line::from(vec![
+						Span::styled(
+							if selected { "> " } else { "  " },
+							self.theme.text(true, selected),
+						),
+						Span::styled(
+							format!(
+								"{} ",
+								time_to_string(snapshot.time, false)
+							),
+							self.theme.commit_time(selected),
+						),
+						Span::styled(
+							snapshot.operation.as_str(),
+							self.theme.text(true, selected),
+						),
+					])
+				})
+				.collect::<Vec<_>>(),
+		)
+	}
+}
+
+impl DrawableComponent for UndoPopup {
+	fn draw(&self, f: &mut Frame, _rect: Rect) -> Result<()> {
+		if !self.visible {
+			return Ok(());
+		}
+
+		let area = ui::centered_rect(60, 50, f.area());
+
+		f.render_widget(Clear, area);
+		f.render_widget(
+			Block::default()
+				.title(Span::styled(
+					strings::undo_title(),
+					self.theme.title(true),
+				))
+				.borders(Borders::ALL)
+				.border_type(BorderType::Thick)
+				.border_style(self.theme.block(true)),
+			area,
+		);
+
+		let inner = area.inner(Margin {
+			horizontal: 1,
+			vertical: 1,
+		});
+
+		let scroll = self.selection.saturating_sub(
+			usize::from(inner.height).saturating_sub(1),
+		);
+
+		f.render_widget(
+			Paragraph::new(self.text()).scroll((
+				u16::try_from(scroll).unwrap_or(u16::MAX),
+				0,
+			)),
+			inner,
+		);
+
+		Ok(())
+	}
+}
+
+impl Component for UndoPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.visible || force_all {
+			let selected = self.selected().is_some();
+
+			out.push(CommandInfo::new(
+				strings::commands::scroll(&self.key_config),
+				selected,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::undo_restore(&self.key_config),
+				selected,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::close_popup(&self.key_config),
+				true,
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.visible {
+			if let Event::Key(e) = ev {
+				let keys = &self.key_config.keys;
+
+				if key_match(e, keys.exit_popup) {
+					self.hide();
+				} else if key_match(e, keys.move_up) {
+					self.move_selection(true);
+				} else if key_match(e, keys.move_down) {
+					self.move_selection(false);
+				} else if key_match(e, keys.enter) {
+					if let Some(snapshot) = self.selected() {
+						self.queue.push(
+							InternalEvent::ConfirmAction(
+								Action::UndoRestore(
+									snapshot.id,
+									snapshot.operation.clone(),
+								),
+							),
+						);
+						self.hide();
+					}
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
@@ -20,6 +20,7 @@ pub struct UpdateRemoteUrlPopup {
 	queue: Queue,
 	remote_name: Option<String>,
 	initial_url: Option<String>,
+	push: bool,
 }
 
 impl DrawableComponent for UpdateRemoteUrlPopup {
@@ -106,6 +107,7 @@ impl UpdateRemoteUrlPopup {
 			queue: env.queue.clone(),
 			initial_url: None,
 			remote_name: None,
+			push: false,
 		}
 	}
 
@@ -114,8 +116,34 @@ impl UpdateRemoteUrlPopup {
 		&mut self,
 		remote_name: String,
 		cur_url: String,
+		push: bool,
 	) -> Result<()> {
+		if push {
+			self.input.set_title(
+				strings::update_remote_push_url_popup_title(
+					&self.key_config,
+				),
+			);
+			self.input.set_default_msg(
+				strings::update_remote_push_url_popup_msg(
+					&self.key_config,
+				),
+			);
+		} else {
+			self.input.set_title(
+				strings::update_remote_url_popup_title(
+					&self.key_config,
+				),
+			);
+			self.input.set_default_msg(
+				strings::update_remote_url_popup_msg(
+					&self.key_config,
+				),
+			);
+		}
+
 		self.input.set_text(cur_url.clone());
+		self.push = push;
 		self.remote_name = Some(remote_name);
 		self.initial_url = Some(cur_url);
 		self.show()?;
@@ -126,7 +154,12 @@ impl UpdateRemoteUrlPopup {
 	///
 	pub fn update_remote_url(&mut self) {
 		if let Some(remote_name) = &self.remote_name {
-			let res = sync::update_remote_url(
+			let update = if self.push {
+				sync::update_remote_push_url
+			} else {
+				sync::update_remote_url
+			};
+			let res = update(
 				&self.repo.borrow(),
 				remote_name,
 				self.input.get_text(),
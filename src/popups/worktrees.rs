@@ -0,0 +1,330 @@
+use crate::{
+	app::Environment,
+	components::{
+		visibility_blocking, CommandBlocking, CommandInfo, Component,
+		DrawableComponent, EventState, InputType, TextInputComponent,
+	},
+	keys::{key_match, SharedKeyConfig},
+	queue::{InternalEvent, NeedsUpdate, Queue},
+	strings, try_or_popup,
+	ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::sync::{self, RepoPathRef, WorktreeInfo};
+use crossterm::event::Event;
+use ratatui::{
+	layout::{Margin, Rect},
+	text::{Line, Span, Text},
+	widgets::{Block, BorderType, Borders, Clear, Paragraph},
+	Frame,
+};
+use std::path::PathBuf;
+
+/// lists the worktrees of the repo, adds and prunes them and switches
+/// to another one
+pub struct WorktreesPopup {
+	repo: RepoPathRef,
+	queue: Queue,
+	visible: bool,
+	worktrees: Vec<WorktreeInfo>,
+	selection: usize,
+	input: TextInputComponent,
+	theme: SharedTheme,
+	key_config: SharedKeyConfig,
+}
+
+impl WorktreesPopup {
+	///
+	pub fn new(env: &Environment) -> Self {
+		Self {
+			repo: env.repo.clone(),
+			queue: env.queue.clone(),
+			visible: false,
+			worktrees: Vec::new(),
+			selection: 0,
+			input: TextInputComponent::new(
+				env,
+				strings::POPUP_TITLE_WORKTREE_ADD,
+				strings::POPUP_MSG_WORKTREE_ADD,
+				true,
+			)
+			.with_input_type(InputType::Singleline),
+			theme: env.theme.clone(),
+			key_config: env.key_config.clone(),
+		}
+	}
+
+	///
+	pub fn open(&mut self) -> Result<()> {
+		self.update_worktrees()?;
+		self.selection = self
+			.worktrees
+			.iter()
+			.position(|worktree| worktree.is_current)
+			.unwrap_or_default();
+
+		self.show()
+	}
+
+	fn update_worktrees(&mut self) -> Result<()> {
+		self.worktrees = sync::get_worktrees(&self.repo.borrow())?;
+		self.selection = self
+			.selection
+			.min(self.worktrees.len().saturating_sub(1));
+
+		Ok(())
+	}
+
+	fn move_selection(&mut self, up: bool) {
+		let max = self.worktrees.len().saturating_sub(1);
+
+		self.selection = if up {
+			self.selection.saturating_sub(1)
+		} else {
+			self.selection.saturating_add(1).min(max)
+		};
+	}
+
+	fn selected(&self) -> Option<&WorktreeInfo> {
+		self.worktrees.get(self.selection)
+	}
+
+	fn can_switch(&self) -> bool {
+		self.selected().is_some_and(|worktree| {
+			!worktree.is_current && !worktree.is_prunable
+		})
+	}
+
+	fn switch(&mut self) {
+		if let Some(worktree) = self.selected() {
+			if self.can_switch() {
+				self.queue.push(InternalEvent::OpenNewRepo(
+					worktree.path.clone(),
+				));
+				self.hide();
+			}
+		}
+	}
+
+	/// input is `<branch> [<path>]`
+	fn add(&mut self) -> Result<()> {
+		let text = self.input.get_text().trim().to_string();
+		self.input.clear();
+		self.input.hide();
+
+		let (branch, path) = text.split_once(' ').map_or(
+			(text.as_str(), None),
+			|(branch, path)| {
+				(branch, Some(PathBuf::from(path.trim())))
+			},
+		);
+
+		if branch.is_empty() {
+			return Ok(());
+		}
+
+		let path = match path {
+			Some(path) => path,
+			None => sync::default_worktree_path(
+				&self.repo.borrow(),
+				branch,
+			)?,
+		};
+
+		sync::create_worktree(&self.repo.borrow(), branch, &path)?;
+
+		self.update_worktrees()?;
+		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+
+		Ok(())
+	}
+
+	fn prune(&mut self) -> Result<()> {
+		let pruned = sync::prune_worktrees(&self.repo.borrow())?;
+
+		self.update_worktrees()?;
+		self.queue.push(InternalEvent::ShowInfoMsg(
+			strings::worktrees_pruned(pruned),
+		));
+
+		Ok(())
+	}
+
+	fn text(&self) -> Text<'_> {
+		Text::from(
+			self.worktrees
+				.iter()
+				.enumerate()
+				.map(|(idx, worktree)| {
+					let selected = idx == self.selection;
+
+					Line::from(Span::styled(
+						format!(
+							"{}{}",
+							if selected { "> " } else { "  " },
+							strings::worktree_entry(worktree)
+						),
+						self.theme
+							.text(!worktree.is_prunable, selected),
+					))
+				})
+				.collect::<Vec<_>>(),
+		)
+	}
+}
+
+impl DrawableComponent for WorktreesPopup {
+	fn draw(&self, f: &mut Frame, _rect: Rect) -> Result<()> {
+		if !self.visible {
+			return Ok(());
+		}
+
+		let area = ui::centered_rect(80, 50, f.area());
+
+		f.render_widget(Clear, area);
+		f.render_widget(
+			Block::default()
+				.title(Span::styled(
+					strings::POPUP_TITLE_WORKTREES,
+					self.theme.title(true),
+				))
+				.borders(Borders::ALL)
+				.border_type(BorderType::Thick)
+				.border_style(self.theme.block(true)),
+			area,
+		);
+
+		let inner = area.inner(Margin {
+			horizontal: 1,
+			vertical: 1,
+		});
+
+		let scroll = self.selection.saturating_sub(
+			usize::from(inner.height).saturating_sub(1),
+		);
+
+		f.render_widget(
+			Paragraph::new(self.text()).scroll((
+				u16::try_from(scroll).unwrap_or(u16::MAX),
+				0,
+			)),
+			inner,
+		);
+
+		self.input.draw(f, area)?;
+
+		Ok(())
+	}
+}
+
+impl Component for WorktreesPopup {
+	fn commands(
+		&self,
+		out: &mut Vec<CommandInfo>,
+		force_all: bool,
+	) -> CommandBlocking {
+		if self.input.is_visible() {
+			self.input.commands(out, force_all);
+
+			out.push(CommandInfo::new(
+				strings::commands::worktree_add_confirm(
+					&self.key_config,
+				),
+				true,
+				true,
+			));
+		} else if self.visible || force_all {
+			out.push(CommandInfo::new(
+				strings::commands::scroll(&self.key_config),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::worktree_switch(&self.key_config),
+				self.can_switch(),
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::worktree_add(&self.key_config),
+				true,
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::worktree_prune(&self.key_config),
+				self.worktrees
+					.iter()
+					.any(|worktree| worktree.is_prunable),
+				true,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::close_popup(&self.key_config),
+				true,
+				true,
+			));
+		}
+
+		visibility_blocking(self)
+	}
+
+	fn event(&mut self, ev: &Event) -> Result<EventState> {
+		if self.visible {
+			if self.input.is_visible() {
+				if let Event::Key(e) = ev {
+					if key_match(e, self.key_config.keys.enter) {
+						try_or_popup!(
+							self,
+							"add worktree error:",
+							self.add()
+						);
+						return Ok(EventState::Consumed);
+					}
+				}
+
+				self.input.event(ev)?;
+
+				return Ok(EventState::Consumed);
+			}
+
+			if let Event::Key(e) = ev {
+				let keys = &self.key_config.keys;
+
+				if key_match(e, keys.exit_popup) {
+					self.hide();
+				} else if key_match(e, keys.enter) {
+					self.switch();
+				} else if key_match(e, keys.move_up) {
+					self.move_selection(true);
+				} else if key_match(e, keys.move_down) {
+					self.move_selection(false);
+				} else if key_match(e, keys.worktree_add) {
+					self.input.show()?;
+				} else if key_match(e, keys.worktree_prune) {
+					try_or_popup!(
+						self,
+						"prune worktrees error:",
+						self.prune()
+					);
+				}
+			}
+
+			return Ok(EventState::Consumed);
+		}
+
+		Ok(EventState::NotConsumed)
+	}
+
+	fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	fn hide(&mut self) {
+		self.visible = false;
+		self.input.hide();
+	}
+
+	fn show(&mut self) -> Result<()> {
+		self.visible = true;
+
+		Ok(())
+	}
+}
@@ -2,15 +2,19 @@ use crate::{
 	components::FuzzyFinderTarget,
 	popups::{
 		AppOption, BlameFileOpen, FileRevOpen, FileTreeOpen,
-		InspectCommitOpen,
+		InspectCommitOpen, PreCommitResults, RevisionTarget,
+		StashSelection,
 	},
 	tabs::StashingOptions,
 };
 use asyncgit::{
 	sync::{
-		diff::DiffLinePosition, CommitId, LogFilterSearchOptions,
+		diff::DiffLinePosition, CommitId, ConfigOrigin,
+		DiffToolTarget, HookResult, IgnoreFile,
+		LogFilterSearchOptions, PullOptions, RemoteFetchOptions,
+		RemoteTarget,
 	},
-	PushType,
+	NotesSync, PushType,
 };
 use bitflags::bitflags;
 use std::{
@@ -39,9 +43,18 @@ pub struct ResetItem {
 	pub path: String,
 }
 
+/// external tool to hand the terminal over to
+pub enum ExternalTool {
+	/// `diff.tool` comparing a file
+	Diff(String, DiffToolTarget),
+	/// `merge.tool` resolving the conflicts of a file
+	Merge(String),
+}
+
 ///
 pub enum Action {
 	Reset(ResetItem),
+	DiscardSubmodule(String),
 	ResetHunk(String, u64),
 	ResetLines(String, Vec<DiffLinePosition>),
 	StashDrop(Vec<CommitId>),
@@ -51,12 +64,29 @@ pub enum Action {
 	DeleteTag(String),
 	DeleteRemoteTag(String, String),
 	DeleteRemote(String),
-	ForcePush(String, bool),
-	PullMerge { incoming: usize, rebase: bool },
+	/// branch and whether to skip the `pre-push` hook
+	PushProtected(String, bool),
+	PushStack(Vec<String>),
+	PullMerge {
+		incoming: usize,
+		rebase: bool,
+	},
 	AbortMerge,
 	AbortRebase,
 	AbortRevert,
+	AbortApplyMailbox,
+	AbortCherryPick,
 	UndoCommit,
+	RewordPushedCommit(CommitId),
+	/// undo snapshot and the operation it was recorded before
+	UndoRestore(CommitId, String),
+	/// discard backup and the path it covers
+	RestoreDiscarded(CommitId, String),
+	/// untracked paths to remove and whether to back them up first
+	Clean {
+		paths: Vec<String>,
+		backup: bool,
+	},
 }
 
 #[derive(Debug)]
@@ -98,23 +128,49 @@ pub enum InternalEvent {
 	/// open commit msg input
 	OpenCommit,
 	///
-	PopupStashing(StashingOptions),
+	PopupStashing(StashingOptions, StashSelection),
 	///
 	TabSwitchStatus,
 	///
 	TabSwitch(AppTabs),
 	///
 	SelectCommitInRevlog(CommitId),
+	/// open the log tab limited to commits touching this path
+	OpenLogForPath(String),
+	///
+	OpenLogRefsPopup,
+	/// show the history of these refs together in the log tab
+	SetLogRefs(Vec<String>),
 	///
 	TagCommit(CommitId),
 	///
+	OpenCommitNotePopup(CommitId),
+	///
+	SyncNotes(NotesSync),
+	///
+	OpenFormatPatchPopup(Vec<CommitId>),
+	///
+	OpenApplyPatchPopup,
+	/// pick the mainline parent to revert a merge against
+	OpenRevertMergePopup(CommitId),
+	///
+	OpenStashWorktreePopup(CommitId),
+	///
 	Tags,
 	///
 	CreateBranch,
+	/// branch off a commit without checking it out
+	CreateBranchAt(CommitId),
 	///
 	RenameRemote(String),
-	///
-	UpdateRemoteUrl(String, String),
+	/// edit the fetch url of a remote or its push url if `true`
+	UpdateRemoteUrl(String, String, bool),
+	/// edit `key` (ask for one if `None`) in the given config file
+	EditGitConfig {
+		origin: ConfigOrigin,
+		key: Option<String>,
+		value: Option<String>,
+	},
 	///
 	RenameBranch(String, String),
 	///
@@ -122,12 +178,72 @@ pub enum InternalEvent {
 	///
 	OpenExternalEditor(Option<String>),
 	///
-	Push(String, PushType, bool, bool),
+	OpenExternalTool(ExternalTool),
+	/// branch, type, force, delete and whether to skip the
+	/// `pre-push` hook
+	Push(String, PushType, bool, bool, bool),
+	/// force push branch only if the remote still points to the commit
+	PushWithLease(String, CommitId),
+	///
+	OpenForcePushPopup(String),
+	///
+	OpenFlowPopup,
+	///
+	OpenSymRefsPopup,
 	///
-	Pull(String),
+	OpenSigningKeyPopup,
+	/// todo list of an interactive rebase starting at the commit
+	OpenRebaseInteractive(CommitId),
+	/// todo list of an interactive rebase starting at the commit with
+	/// fixup!/squash! commits already moved into place
+	OpenRebaseAutosquash(CommitId),
+	/// commit popup prefilled with `fixup! ..` (or `squash! ..` if true)
+	/// for the commit
+	OpenCommitFixup(CommitId, bool),
+	/// bisect popup marking the commit
+	OpenBisectPopup(CommitId),
+	///
+	OpenReflogPopup,
+	///
+	OpenUndoPopup,
+	///
+	OpenBlameIgnoreRevsPopup,
+	///
+	OpenDiscardedPopup,
+	///
+	OpenCleanPopup,
+	/// path relative to the workdir, folders end with `/`
+	OpenIgnorePatternPopup(String),
+	///
+	OpenIgnoreEditPopup(IgnoreFile),
+	///
+	ViewWorktrees,
+	/// file path and hash of the unstaged hunk to edit
+	OpenEditHunk(String, u64),
+	/// path of a conflicted file
+	OpenConflictEditor(String),
+	///
+	OpenPreCommitResults(PreCommitResults),
+	///
+	OpenHookProgress,
+	/// outcome of the `pre-commit` hook run by `OpenHookProgress`
+	PreCommitFinished(Result<HookResult, String>),
+	/// branch and the strategy to use instead of the configured one
+	Pull(String, Option<PullOptions>),
+	///
+	OpenPullOptionsPopup(String),
+	/// pick the remote branch to push `branch` to (`true`) or to
+	/// pull from
+	OpenRemoteTargetPopup(String, bool),
+	///
+	PushTo(String, RemoteTarget),
+	///
+	PullFrom(String, RemoteTarget),
 	///
 	PushTags,
 	///
+	PushStack(Vec<String>),
+	///
 	OptionSwitched(AppOption),
 	///
 	OpenFuzzyFinder(Vec<String>, FuzzyFinderTarget),
@@ -135,8 +251,10 @@ pub enum InternalEvent {
 	OpenLogSearchPopup,
 	///
 	FuzzyFinderChanged(usize, String, FuzzyFinderTarget),
+	/// fetch with these options instead of the configured ones
+	FetchRemotes(Option<RemoteFetchOptions>),
 	///
-	FetchRemotes,
+	OpenFetchOptionsPopup,
 	///
 	OpenPopup(StackablePopupOpen),
 	///
@@ -151,9 +269,15 @@ pub enum InternalEvent {
 	CreateRemote,
 	///
 	OpenRepo { path: PathBuf },
+	/// open a freshly cloned or initialized repository at this path
+	OpenNewRepo(PathBuf),
 	///
 	OpenResetPopup(CommitId),
 	///
+	OpenRestoreFilePopup(CommitId, String),
+	///
+	OpenRevisionPopup(RevisionTarget),
+	///
 	RewordCommit(CommitId),
 	///
 	CommitSearch(LogFilterSearchOptions),
@@ -1,6 +1,25 @@
-use std::borrow::Cow;
+use std::{
+	borrow::Cow,
+	path::{Path, PathBuf},
+	time::Duration,
+};
 
-use asyncgit::sync::CommitId;
+use asyncgit::{
+	sync::{
+		cred::CredentialStorage, ApplyMailboxProgress,
+		ApplyPatchResult, ApplyPatchTarget, BisectMark, BisectState,
+		CleanOptions, CommitId, CompareMode, ConfigOrigin,
+		ConflictResolution, FetchSummary, FetchedRef, FlowBranchType,
+		FormatPatchOptions, IgnoreFile, IgnorePatternKind, LogScope,
+		MergeDiffMode, PreCommitCheckStatus, PullStrategy, RepoState,
+		SequencerAction, SequencerProgress, SequencerResult,
+		SignatureValidity, SignatureVerification, SigningFormat,
+		SigningStatus, SubmoduleState, TagSignature, WorktreeInfo,
+	},
+	NotesSync, RemoteProgress,
+};
+use bytesize::ByteSize;
+use easy_cast::Conv;
 use unicode_truncate::UnicodeTruncateStr;
 use unicode_width::UnicodeWidthStr;
 
@@ -17,12 +36,29 @@ pub static PUSH_POPUP_MSG: &str = "Push";
 pub static FORCE_PUSH_POPUP_MSG: &str = "Force Push";
 pub static PULL_POPUP_MSG: &str = "Pull";
 pub static FETCH_POPUP_MSG: &str = "Fetch";
+pub static CLONE_POPUP_MSG: &str = "Clone";
+pub static CLONE_SCREEN_TITLE: &str = "gitui";
+pub static CLONE_SCREEN_MSG: &str =
+	"not inside a git repository - initialize or clone one to get started";
+pub static CLONE_POPUP_URL_MSG: &str = "repository url or path";
+pub static CLONE_POPUP_DEPTH_MSG: &str = "full history";
+pub static CLONE_POPUP_NO_URL: &str = "no url to clone from";
+pub static CLONE_POPUP_INVALID_DEPTH: &str =
+	"depth must be a positive number";
+pub static INIT_REPO_POPUP_MSG: &str = "Initialize Repository";
+pub static INIT_REPO_POPUP_DEFAULT_BRANCH: &str = "master";
+pub static INIT_REPO_POPUP_INVALID_BRANCH: &str =
+	"invalid initial branch name";
 pub static PUSH_POPUP_PROGRESS_NONE: &str = "preparing...";
 pub static PUSH_POPUP_STATES_ADDING: &str = "adding objects (1/3)";
 pub static PUSH_POPUP_STATES_DELTAS: &str = "deltas (2/3)";
 pub static PUSH_POPUP_STATES_PUSHING: &str = "pushing (3/3)";
 pub static PUSH_POPUP_STATES_TRANSFER: &str = "transfer";
 pub static PUSH_POPUP_STATES_DONE: &str = "done";
+pub static PUSH_POPUP_CANCELLING: &str = "cancelling...";
+pub static PUSH_CANCELLED_MSG: &str = "push cancelled";
+pub static PULL_CANCELLED_MSG: &str = "pull cancelled";
+pub static FETCH_CANCELLED_MSG: &str = "fetch cancelled";
 
 pub static PUSH_TAGS_POPUP_MSG: &str = "Push Tags";
 pub static PUSH_TAGS_STATES_FETCHING: &str = "fetching";
@@ -34,33 +70,166 @@ pub static POPUP_TITLE_REMOTES: &str = "Remotes";
 pub static POPUP_SUBTITLE_REMOTES: &str = "Details";
 pub static POPUP_TITLE_FUZZY_FIND: &str = "Fuzzy Finder";
 pub static POPUP_TITLE_LOG_SEARCH: &str = "Search";
+pub static POPUP_TITLE_LOG_REFS: &str = "Show History Of";
+pub static POPUP_TITLE_REVERT_MERGE: &str = "Revert Merge Against";
+pub static POPUP_TITLE_PRE_COMMIT: &str = "pre-commit";
+pub static POPUP_TITLE_HOOK_PROGRESS: &str =
+	"Running pre-commit hook";
+pub static HOOK_PROGRESS_CANCELLING: &str =
+	"Cancelling pre-commit hook...";
+pub static HOOK_PROGRESS_WAITING: &str = "waiting for output...";
+pub static POPUP_TITLE_FLOW: &str = "Git Flow";
+pub static POPUP_TITLE_SYMREFS: &str = "Symbolic Refs";
+pub static POPUP_TITLE_SIGNING_KEY: &str = "Signing Key";
+pub static POPUP_TITLE_REBASE_INTERACTIVE: &str =
+	"Interactive Rebase";
+pub static POPUP_TITLE_REBASE_REWORD: &str = "Reword";
+pub static POPUP_TITLE_BISECT: &str = "Bisect";
+pub static POPUP_TITLE_EDIT_HUNK: &str = "Edit Hunk";
+pub static EDIT_HUNK_HELP: &str =
+	"# To drop a '-' line, make it a ' ' line.
+# To drop a '+' line, delete it.
+# Lines starting with # are ignored.";
+pub static POPUP_TITLE_CONFLICT_EDIT: &str = "Edit Region";
+pub static CONFLICT_OURS: &str = "Ours";
+pub static CONFLICT_BASE: &str = "Base";
+pub static CONFLICT_THEIRS: &str = "Theirs";
+pub static CONFLICT_RESULT: &str = "Result";
+pub static CONFLICT_NO_BASE: &str = "(added on both sides)";
+pub static POPUP_TITLE_WORKTREES: &str = "Worktrees";
+pub static POPUP_TITLE_WORKTREE_ADD: &str = "Add Worktree";
+pub static POPUP_MSG_WORKTREE_ADD: &str = "branch [path]";
 
 pub static POPUP_FAIL_COPY: &str = "Failed to copy text";
 pub static POPUP_SUCCESS_COPY: &str = "Copied Text";
 pub static POPUP_COMMIT_SHA_INVALID: &str = "Invalid commit sha";
 
+pub static LOG_APPLIED_UPSTREAM: &str = "[applied]";
+pub static LOG_BISECT_NEXT: &str = "[bisect]";
+pub static BISECT_NOT_STARTED: &str =
+	"not bisecting, mark the commit good or bad to start";
+pub const fn log_bisect_mark(mark: BisectMark) -> &'static str {
+	match mark {
+		BisectMark::Good => "[good]",
+		BisectMark::Bad => "[bad]",
+		BisectMark::Skip => "[skip]",
+	}
+}
+pub const fn log_signature_mark(
+	validity: SignatureValidity,
+) -> &'static str {
+	match validity {
+		SignatureValidity::Verified => symbol::CHECKMARK,
+		SignatureValidity::Unverified => "?",
+		SignatureValidity::Bad => symbol::CROSS,
+	}
+}
+pub static COMPARE_DUPLICATE_OF: &str = " = ";
+pub static BRANCH_COMPARE_BASE: &str = "base";
+pub static LOG_REFS_KIND_BRANCH: &str = "branch";
+pub static LOG_REFS_KIND_REMOTE: &str = "remote";
+pub static LOG_REFS_KIND_TAG: &str = "tag";
+pub static LOG_SELECT_COMMIT_NOT_FOUND_MSG: &str =
+	"Could not select commit. It might be on a different branch.";
+
+pub static PRE_COMMIT_DETAILS: &str = "Details";
+pub static PRE_COMMIT_AUTO_FIXES: &str = "Changes made by the hooks:";
+pub const fn pre_commit_status(
+	status: PreCommitCheckStatus,
+) -> &'static str {
+	match status {
+		PreCommitCheckStatus::Passed => "Passed",
+		PreCommitCheckStatus::Failed => "Failed",
+		PreCommitCheckStatus::Skipped => "Skipped",
+	}
+}
+pub fn pre_commit_hook_id(id: &str) -> String {
+	format!("hook id: {id}")
+}
+
 pub mod symbol {
 	pub const CHECKMARK: &str = "\u{2713}"; //✓
+	pub const CROSS: &str = "\u{2717}"; //✗
 	pub const SPACE: &str = "\u{02FD}"; //˽
 	pub const EMPTY_SPACE: &str = " ";
 	pub const FOLDER_ICON_COLLAPSED: &str = "\u{25b8}"; //▸
 	pub const FOLDER_ICON_EXPANDED: &str = "\u{25be}"; //▾
 	pub const EMPTY_STR: &str = "";
+	pub const REF_CONTAINS: &str = "\u{25cf}"; //●
+	pub const REF_NOT_CONTAINS: &str = "\u{00b7}"; //·
 	pub const ELLIPSIS: char = '\u{2026}'; // …
 }
 
 pub fn title_branches() -> String {
 	"Branches".to_string()
 }
+pub fn title_branches_base(base: &str) -> String {
+	format!("Branches (compared to {base})")
+}
 pub fn title_tags() -> String {
 	"Tags".to_string()
 }
+pub fn title_tag_details() -> String {
+	"Tag Details".to_string()
+}
+pub static TAG_DETAILS_LIGHTWEIGHT: &str =
+	"lightweight tag (no annotation)";
+pub fn tag_details_tagger() -> String {
+	"Tagger: ".to_string()
+}
+pub fn tag_details_signature() -> String {
+	"Signature: ".to_string()
+}
+pub fn tag_signature(signature: TagSignature) -> String {
+	match signature {
+		TagSignature::Unsigned => "none",
+		TagSignature::Gpg => "gpg (not verified)",
+		TagSignature::Ssh => "ssh (not verified)",
+	}
+	.to_string()
+}
+pub fn title_git_config() -> String {
+	"Git Config".to_string()
+}
+pub const fn git_config_origin(origin: ConfigOrigin) -> &'static str {
+	match origin {
+		ConfigOrigin::System => "system",
+		ConfigOrigin::Global => "global",
+		ConfigOrigin::Local => "local",
+		ConfigOrigin::Worktree => "worktree",
+		ConfigOrigin::Other => "other",
+	}
+}
+pub fn git_config_edit_popup_title(
+	key: &str,
+	origin: ConfigOrigin,
+) -> String {
+	format!("{key} ({})", git_config_origin(origin))
+}
+pub fn git_config_key_popup_title() -> String {
+	"New config key".to_string()
+}
+pub fn git_config_key_popup_msg() -> String {
+	"section.key".to_string()
+}
+pub fn git_config_value_popup_msg() -> String {
+	"value".to_string()
+}
 pub fn title_status(_key_config: &SharedKeyConfig) -> String {
 	"Unstaged Changes".to_string()
 }
 pub fn title_diff(_key_config: &SharedKeyConfig) -> String {
 	"Diff: ".to_string()
 }
+pub fn merge_diff_mode(mode: MergeDiffMode) -> String {
+	match mode {
+		MergeDiffMode::Parent(0) => "first parent".to_string(),
+		MergeDiffMode::Parent(parent) => {
+			format!("parent {}", parent + 1)
+		}
+		MergeDiffMode::Combined => "combined".to_string(),
+	}
+}
 pub fn title_index(_key_config: &SharedKeyConfig) -> String {
 	"Staged Changes".to_string()
 }
@@ -70,6 +239,24 @@ pub fn tab_status(key_config: &SharedKeyConfig) -> String {
 		key_config.get_hint(key_config.keys.tab_status)
 	)
 }
+pub fn bare_repo_path(path: &str) -> String {
+	format!("{path} (bare)")
+}
+pub fn auto_fetch_pending() -> String {
+	"fetching...".to_string()
+}
+pub fn auto_fetch_failed(err: &str) -> String {
+	format!("fetch failed: {err}")
+}
+pub fn auto_fetch_just_now() -> String {
+	"fetched just now".to_string()
+}
+pub fn auto_fetch_ago_minutes(minutes: u64) -> String {
+	format!("fetched {minutes}m ago")
+}
+pub fn auto_fetch_ago_hours(hours: u64) -> String {
+	format!("fetched {hours}h ago")
+}
 pub fn tab_log(key_config: &SharedKeyConfig) -> String {
 	format!("Log [{}]", key_config.get_hint(key_config.keys.tab_log))
 }
@@ -119,14 +306,38 @@ pub fn commit_title_merge() -> String {
 pub fn commit_title_revert() -> String {
 	"Commit (Revert)".to_string()
 }
+pub fn revision_popup_title_compare() -> String {
+	"Compare with HEAD".to_string()
+}
+pub fn revision_popup_title_compare_base() -> String {
+	"Compare base".to_string()
+}
+pub fn revision_popup_title_reset() -> String {
+	"Reset to".to_string()
+}
+pub fn revision_popup_msg() -> String {
+	"revision (e.g. HEAD~3, @{upstream}, :/message)".to_string()
+}
 pub fn commit_title_amend() -> String {
 	"Commit (Amend)".to_string()
 }
+pub fn commit_amend_author_title() -> String {
+	"Author".to_string()
+}
+pub fn commit_amend_author_msg() -> String {
+	"Name <email>".to_string()
+}
+pub fn commit_amend_date_title() -> String {
+	"Author date".to_string()
+}
+pub fn commit_amend_date_msg() -> String {
+	"YYYY-MM-DD HH:MM:SS +ZZZZ".to_string()
+}
 pub fn commit_msg(_key_config: &SharedKeyConfig) -> String {
 	"type commit message..".to_string()
 }
-pub fn commit_first_line_warning(count: usize) -> String {
-	format!("[subject length: {count}]")
+pub fn commit_lint_warning(msg: &str) -> String {
+	format!("[{msg}]")
 }
 pub const fn branch_name_invalid() -> &'static str {
 	"[invalid name]"
@@ -143,9 +354,21 @@ pub fn stash_popup_title(_key_config: &SharedKeyConfig) -> String {
 pub fn stash_popup_msg(_key_config: &SharedKeyConfig) -> String {
 	"type name (optional)".to_string()
 }
+pub fn stash_popup_marked_files(count: usize) -> String {
+	format!("{count} marked file(s)")
+}
+pub fn stash_popup_hunks(count: usize, path: &str) -> String {
+	format!("{count} hunk(s) of {path}")
+}
 pub fn confirm_title_reset() -> String {
 	"Reset".to_string()
 }
+pub fn reset_preview_pending() -> String {
+	"Looking up the commits and files affected...".to_string()
+}
+pub fn restore_file_popup_title() -> String {
+	"Restore File".to_string()
+}
 pub fn confirm_title_undo_commit() -> String {
 	"Undo commit".to_string()
 }
@@ -199,6 +422,73 @@ pub fn confirm_msg_abortrebase() -> String {
 	"This will revert all uncommitted changes. Are you sure?"
 		.to_string()
 }
+pub fn confirm_title_abort_apply_mailbox() -> String {
+	"Abort applying patches?".to_string()
+}
+pub fn confirm_msg_abort_apply_mailbox() -> String {
+	"This resets to the commit before the first patch and reverts all uncommitted changes. Are you sure?"
+		.to_string()
+}
+pub fn confirm_title_abort_cherry_pick() -> String {
+	"Abort cherry-pick?".to_string()
+}
+pub fn confirm_msg_abort_cherry_pick() -> String {
+	"This drops the commits picked so far and reverts all uncommitted changes. Are you sure?"
+		.to_string()
+}
+pub const STATUS_SUBMODULE_SUFFIX: &str = " [submodule]";
+pub fn status_submodule_suffix(
+	state: Option<&SubmoduleState>,
+) -> String {
+	let Some(state) = state else {
+		return STATUS_SUBMODULE_SUFFIX.to_string();
+	};
+
+	format!(" [submodule: {}]", submodule_state(state))
+}
+pub fn submodule_state(state: &SubmoduleState) -> String {
+	let mut parts = Vec::new();
+	if state.dirty {
+		parts.push(String::from("dirty"));
+	}
+	if state.ahead > 0 {
+		parts.push(format!("{} ahead", state.ahead));
+	}
+	if state.behind > 0 {
+		parts.push(format!("{} behind", state.behind));
+	}
+
+	if parts.is_empty() {
+		String::from("clean")
+	} else {
+		parts.join(", ")
+	}
+}
+pub fn status_renamed_from(old: &str, new: &str) -> String {
+	format!("{old} \u{2192} {new}")
+}
+pub const STATUS_LFS_SUFFIX: &str = " [lfs]";
+pub const STATUS_LFS_POINTER_SUFFIX: &str = " [lfs pointer]";
+pub const DIFF_LFS_OBJECT: &str = "git LFS object:";
+pub fn diff_textconv_suffix(driver: &str) -> String {
+	format!(" [textconv: {driver}]")
+}
+pub const DIFF_IGNORE_ALL_SPACE: &str = "all space";
+pub const DIFF_IGNORE_SPACE_CHANGE: &str = "space change";
+pub const DIFF_IGNORE_BLANK_LINES: &str = "blank lines";
+pub fn diff_ignoring_suffix(modes: &[&str]) -> String {
+	format!(" [ignoring {}]", modes.join(", "))
+}
+pub const DIFF_LOADING_MORE: &str = "··· loading more ···";
+pub fn diff_load_more(lines: usize) -> String {
+	format!("··· {lines} lines loaded, select to load more ···")
+}
+pub fn confirm_title_discard_submodule() -> String {
+	"Discard".to_string()
+}
+pub fn confirm_msg_discard_submodule(path: &str) -> String {
+	format!("discard all changes inside of submodule '{path}'?")
+}
 pub fn confirm_msg_reset() -> String {
 	"confirm file reset?".to_string()
 }
@@ -207,9 +497,41 @@ pub fn confirm_msg_reset_lines(lines: usize) -> String {
 		"are you sure you want to discard {lines} selected lines?"
 	)
 }
+pub fn confirm_title_undo_restore() -> String {
+	"Undo".to_string()
+}
+pub fn confirm_msg_undo_restore(operation: &str) -> String {
+	format!("restore the state before '{operation}'?\nchanges made since then are overwritten, the current state is kept in the undo history")
+}
+pub fn confirm_title_restore_discarded() -> String {
+	"Restore discarded".to_string()
+}
+pub fn confirm_msg_restore_discarded(path: &str) -> String {
+	format!("overwrite '{path}' in the working tree with the discarded content?")
+}
+pub fn confirm_title_clean() -> String {
+	"Clean".to_string()
+}
+pub fn confirm_msg_clean(paths: usize, backup: bool) -> String {
+	format!(
+		"remove {paths} untracked path(s) from the working tree?{}",
+		if backup {
+			"\na backup is kept in the discarded changes"
+		} else {
+			"\nthis cannot be undone"
+		}
+	)
+}
 pub fn confirm_msg_undo_commit() -> String {
 	"confirm undo last commit?".to_string()
 }
+pub fn confirm_title_reword_pushed() -> String {
+	"Reword pushed commit?".to_string()
+}
+pub fn confirm_msg_reword_pushed() -> String {
+	"This commit is already part of the upstream branch. Rewording it rewrites published history and requires a force push. Are you sure?"
+		.to_string()
+}
 pub fn confirm_msg_stashdrop(
 	_key_config: &SharedKeyConfig,
 	ids: &[CommitId],
@@ -292,1248 +614,3780 @@ pub fn confirm_title_force_push(
 ) -> String {
 	"Force Push".to_string()
 }
-pub fn confirm_msg_force_push(
-	_key_config: &SharedKeyConfig,
-	branch_ref: &str,
-) -> String {
-	format!(
-        "Confirm force push to branch '{branch_ref}' ?  This may rewrite history."
-    )
+pub static FLOW_NAME_MSG: &str = "name";
+pub static FLOW_TAG_MSG: &str = "tag message (optional)";
+const fn flow_type_name(typ: FlowBranchType) -> &'static str {
+	match typ {
+		FlowBranchType::Feature => "feature",
+		FlowBranchType::Release => "release",
+		FlowBranchType::Hotfix => "hotfix",
+	}
 }
-pub fn log_title(_key_config: &SharedKeyConfig) -> String {
-	"Commit".to_string()
+pub fn flow_model(main: &str, develop: &str) -> String {
+	format!("main: {main}  develop: {develop}")
 }
-pub fn file_log_title(
-	file_path: &str,
-	selected: usize,
-	revisions: usize,
-) -> String {
-	format!("Revisions of '{file_path}' ({selected}/{revisions})")
+pub fn flow_start_title(typ: FlowBranchType) -> String {
+	format!("Start {}", flow_type_name(typ))
 }
-pub fn blame_title(_key_config: &SharedKeyConfig) -> String {
-	"Blame".to_string()
+pub fn flow_finish_title(name: &str) -> String {
+	format!("Finish '{name}'")
 }
-pub fn tag_popup_name_title() -> String {
-	"Tag".to_string()
+pub fn flow_start_action(
+	typ: FlowBranchType,
+	prefix: &str,
+	base: &str,
+) -> String {
+	format!("start {} ({prefix}… from {base})", flow_type_name(typ))
 }
-pub fn tag_popup_name_msg() -> String {
-	"type tag name".to_string()
+pub fn flow_finish_action(branch: &str) -> String {
+	format!("finish {branch}")
 }
-pub fn tag_popup_annotation_title(name: &str) -> String {
-	format!("Tag Annotation ({name})")
+pub fn flow_step_create(branch: &str, base: &str) -> String {
+	format!("create '{branch}' from '{base}' and check it out")
 }
-pub fn tag_popup_annotation_msg() -> String {
-	"type tag annotation".to_string()
+pub fn flow_step_merge(branch: &str, target: &str) -> String {
+	format!("merge '{branch}' into '{target}' (no fast-forward)")
 }
-pub fn stashlist_title(_key_config: &SharedKeyConfig) -> String {
-	"Stashes".to_string()
+pub fn flow_step_tag(tag: &str, target: &str) -> String {
+	format!("tag '{target}' as '{tag}'")
 }
-pub fn help_title(_key_config: &SharedKeyConfig) -> String {
-	"Help: all commands".to_string()
+pub static SIGNING_KEY_ENABLED: &str = " sign commits";
+pub static SIGNING_KEY_NONE_FOUND: &str =
+	"no gpg secret keys or ssh keys (~/.ssh) found";
+const fn signing_format_name(format: SigningFormat) -> &'static str {
+	match format {
+		SigningFormat::OpenPgp => "gpg",
+		SigningFormat::Ssh => "ssh",
+		SigningFormat::X509 => "x509",
+	}
 }
-pub fn stashing_files_title(_key_config: &SharedKeyConfig) -> String {
-	"Files to Stash".to_string()
+pub fn signing_key_current(status: &SigningStatus) -> String {
+	format!(
+		"current key: {} ({})",
+		status.key.as_deref().unwrap_or("committer identity"),
+		signing_format_name(status.format)
+	)
 }
-pub fn stashing_options_title(
-	_key_config: &SharedKeyConfig,
+pub fn signing_key_entry(
+	format: SigningFormat,
+	key: &str,
+	description: &str,
+	current: bool,
 ) -> String {
-	"Options".to_string()
-}
-pub fn loading_text(_key_config: &SharedKeyConfig) -> String {
-	"Loading ...".to_string()
+	format!(
+		"[{}] {key} {description}{}",
+		signing_format_name(format),
+		if current { " (current)" } else { "" }
+	)
 }
-pub fn create_branch_popup_title(
-	_key_config: &SharedKeyConfig,
+pub fn signature_verification(
+	verification: &SignatureVerification,
 ) -> String {
-	"Branch".to_string()
+	let validity = match verification.validity {
+		SignatureValidity::Verified => "verified",
+		SignatureValidity::Unverified => "unverified",
+		SignatureValidity::Bad => "bad",
+	};
+	format!(
+		"{validity} {} signature{}{}",
+		signing_format_name(verification.format),
+		verification
+			.signer
+			.as_ref()
+			.map(|signer| format!(" by {signer}"))
+			.unwrap_or_default(),
+		verification
+			.key
+			.as_ref()
+			.map(|key| format!(" ({key})"))
+			.unwrap_or_default()
+	)
 }
-pub fn create_branch_popup_msg(
-	_key_config: &SharedKeyConfig,
-) -> String {
-	"type branch name".to_string()
+pub static COMMIT_NO_VERIFY_INDICATOR: &str = "[no verify]";
+pub fn commit_signing_indicator(status: &SigningStatus) -> String {
+	if status.enabled {
+		format!(
+			"[signed: {}]",
+			status.key.as_deref().unwrap_or("committer identity")
+		)
+	} else {
+		"[unsigned]".to_string()
+	}
 }
-pub fn rename_remote_popup_title(
-	_key_config: &SharedKeyConfig,
+pub fn rebase_interactive_entry(
+	action: &str,
+	short_id: &str,
+	summary: &str,
 ) -> String {
-	"Rename remote".to_string()
+	format!("{action:<6} {short_id} {summary}")
 }
-pub fn rename_remote_popup_msg(
-	_key_config: &SharedKeyConfig,
-) -> String {
-	"new remote name".to_string()
+pub fn undo_title() -> String {
+	"Undo History".to_string()
 }
-pub fn update_remote_url_popup_title(
-	_key_config: &SharedKeyConfig,
-) -> String {
-	"Update url".to_string()
+pub static UNDO_EMPTY: &str = "no undoable operations recorded yet";
+pub fn undo_op_reset(kind: &str, id: CommitId) -> String {
+	format!("reset ({kind}) to {}", id.get_short_string())
 }
-pub fn update_remote_url_popup_msg(
-	_key_config: &SharedKeyConfig,
-) -> String {
-	"new remote url".to_string()
+pub fn undo_op_discard(path: &str) -> String {
+	format!("discard changes of '{path}'")
 }
-pub fn create_remote_popup_title_name(
-	_key_config: &SharedKeyConfig,
-) -> String {
-	"Remote name".to_string()
+pub fn undo_op_discard_hunk(path: &str) -> String {
+	format!("discard hunk of '{path}'")
 }
-pub fn create_remote_popup_title_url(
-	_key_config: &SharedKeyConfig,
-) -> String {
-	"Remote url".to_string()
+pub fn undo_op_discard_lines(path: &str, lines: usize) -> String {
+	format!("discard {lines} lines of '{path}'")
 }
-pub fn create_remote_popup_msg_name(
-	_key_config: &SharedKeyConfig,
-) -> String {
-	"type remote name".to_string()
+pub fn undo_op_delete_branch(branch_ref: &str) -> String {
+	format!(
+		"delete branch '{}'",
+		branch_ref.strip_prefix("refs/heads/").unwrap_or(branch_ref)
+	)
 }
-pub fn create_remote_popup_msg_url(
-	_key_config: &SharedKeyConfig,
-) -> String {
-	"type remote url".to_string()
+pub fn undo_op_rebase(onto: &str) -> String {
+	format!("rebase onto '{onto}'")
 }
-pub const fn remote_name_invalid() -> &'static str {
-	"[invalid name]"
+pub fn undo_op_rebase_interactive(base: CommitId) -> String {
+	format!("interactive rebase onto {}", base.get_short_string())
 }
-pub fn username_popup_title(_key_config: &SharedKeyConfig) -> String {
-	"Username".to_string()
+pub fn undo_op_undo_commit() -> String {
+	"undo last commit".to_string()
 }
-pub fn username_popup_msg(_key_config: &SharedKeyConfig) -> String {
-	"type username".to_string()
+pub fn discarded_title() -> String {
+	"Recently Discarded".to_string()
 }
-pub fn password_popup_title(_key_config: &SharedKeyConfig) -> String {
-	"Password".to_string()
+pub static DISCARDED_EMPTY: &str =
+	"no discarded changes backed up yet";
+pub const fn ignore_file_name(file: IgnoreFile) -> &'static str {
+	match file {
+		IgnoreFile::Gitignore => ".gitignore",
+		IgnoreFile::InfoExclude => ".git/info/exclude",
+	}
 }
-pub fn password_popup_msg(_key_config: &SharedKeyConfig) -> String {
-	"type password".to_string()
+pub const fn ignore_pattern_kind(
+	kind: IgnorePatternKind,
+) -> &'static str {
+	match kind {
+		IgnorePatternKind::Path => "exact",
+		IgnorePatternKind::Extension => "extension",
+		IgnorePatternKind::Directory => "folder",
+		IgnorePatternKind::Basename => "anywhere",
+	}
 }
-
-pub fn rename_branch_popup_title(
-	_key_config: &SharedKeyConfig,
-) -> String {
-	"Rename Branch".to_string()
+pub const fn pull_strategy_name(
+	strategy: PullStrategy,
+) -> &'static str {
+	match strategy {
+		PullStrategy::Merge => "merge",
+		PullStrategy::Rebase => "rebase",
+		PullStrategy::FastForwardOnly => "fast-forward only",
+	}
 }
-pub fn rename_branch_popup_msg(
-	_key_config: &SharedKeyConfig,
-) -> String {
-	"new branch name".to_string()
+pub fn pull_options_title(branch: &str) -> String {
+	format!("Pull '{branch}'")
+}
+pub fn pull_autostash(autostash: bool) -> String {
+	format!(
+		"autostash local changes: {}",
+		if autostash { "yes" } else { "no" }
+	)
+}
+pub static PULL_FF_ONLY_DIVERGED: &str =
+	"Not possible to fast-forward, the branch diverged from its upstream.";
+pub static PULL_AUTOSTASH_KEPT: &str =
+	"Reapplying the autostashed changes failed, they are kept in the stash list.";
+pub static REMOTE_TARGET_BRANCH_MSG: &str = "remote branch";
+pub static FETCH_OPTIONS_TITLE: &str = "Fetch";
+pub static FETCH_ALL_REMOTES: &str = "all remotes";
+pub fn fetch_prune(prune: bool) -> String {
+	format!(
+		"prune deleted branches: {}",
+		if prune { "yes" } else { "no" }
+	)
+}
+pub fn fetch_tags(tags: bool) -> String {
+	format!("fetch all tags: {}", if tags { "yes" } else { "no" })
 }
+fn fetched_ref_name(fetched: &FetchedRef) -> &str {
+	fetched
+		.name
+		.strip_prefix("refs/remotes/")
+		.or_else(|| fetched.name.strip_prefix("refs/tags/"))
+		.unwrap_or(&fetched.name)
+}
+/// one line per changed ref, like the output of `git fetch`
+pub fn fetch_summary(summary: &FetchSummary) -> String {
+	summary
+		.refs
+		.iter()
+		.map(|fetched| {
+			let kind = if fetched.name.starts_with("refs/tags/") {
+				"tag"
+			} else {
+				"branch"
+			};
+			let name = fetched_ref_name(fetched);
 
-pub fn copy_success(s: &str) -> String {
-	format!("{POPUP_SUCCESS_COPY} \"{s}\"")
+			match (fetched.old, fetched.new) {
+				(None, _) => format!(" * [new {kind}] {name}"),
+				(_, None) => format!(" - [deleted] {name}"),
+				(Some(old), Some(new)) => format!(
+					"   {}..{} {name}",
+					old.get_short_string(),
+					new.get_short_string()
+				),
+			}
+		})
+		.collect::<Vec<_>>()
+		.join("\n")
 }
+fn eta(eta: Duration) -> String {
+	let secs = eta.as_secs();
 
-pub fn ellipsis_trim_start(s: &str, width: usize) -> Cow<str> {
-	if s.width() <= width {
-		Cow::Borrowed(s)
+	if secs < 60 {
+		format!("{secs}s")
 	} else {
-		Cow::Owned(format!(
-			"[{}]{}",
-			symbol::ELLIPSIS,
-			s.unicode_truncate_start(
-				width.saturating_sub(3 /* front indicator */)
-			)
-			.0
-		))
+		format!("{}:{:02}", secs / 60, secs % 60)
 	}
 }
-
-pub mod commit {
-	use crate::keys::SharedKeyConfig;
-
-	pub fn details_author() -> String {
-		"Author: ".to_string()
-	}
-	pub fn details_committer() -> String {
-		"Committer: ".to_string()
-	}
-	pub fn details_sha() -> String {
-		"Sha: ".to_string()
-	}
-	pub fn details_date() -> String {
-		"Date: ".to_string()
-	}
-	pub fn details_tags() -> String {
-		"Tags: ".to_string()
-	}
-	pub fn details_message() -> String {
-		"Subject: ".to_string()
-	}
-	pub fn details_info_title(
-		_key_config: &SharedKeyConfig,
-	) -> String {
-		"Info".to_string()
+/// objects, bytes, rate and time left of a transfer, empty while
+/// packing
+pub fn remote_progress(progress: &RemoteProgress) -> String {
+	if progress.bytes == 0 {
+		return String::new();
 	}
-	pub fn compare_details_info_title(
-		old: bool,
-		hash: &str,
-	) -> String {
-		format!("{}: {hash}", if old { "Old" } else { "New" })
+
+	let mut details = vec![
+		format!("{}/{} objects", progress.current, progress.total),
+		ByteSize::b(u64::conv(progress.bytes)).to_string(),
+	];
+	if let Some(rate) = progress.bytes_per_second() {
+		details.push(format!("{}/s", ByteSize::b(rate)));
 	}
-	pub fn details_message_title(
-		_key_config: &SharedKeyConfig,
-	) -> String {
-		"Message".to_string()
+	if let Some(left) = progress.eta() {
+		details.push(format!("ETA {}", eta(left)));
 	}
-	pub fn details_files_title(
-		_key_config: &SharedKeyConfig,
-	) -> String {
-		"Files:".to_string()
+
+	details.join(", ")
+}
+pub fn remote_target_title(branch: &str, push: bool) -> String {
+	if push {
+		format!("Push '{branch}' to")
+	} else {
+		format!("Pull into '{branch}' from")
 	}
 }
+pub fn remote_target_set_upstream(set_upstream: bool) -> String {
+	format!(
+		"set as upstream: {}",
+		if set_upstream { "yes" } else { "no" }
+	)
+}
+pub fn ignore_pattern_title(path: &str, file: IgnoreFile) -> String {
+	format!("Ignore '{path}' in {}", ignore_file_name(file))
+}
+pub static IGNORE_PATTERN_HIDES: &str = "hides:";
+pub fn ignore_pattern_more(count: usize) -> String {
+	format!("  ... and {count} more")
+}
+pub fn ignore_edit_title(file: IgnoreFile) -> String {
+	format!("Edit {}", ignore_file_name(file))
+}
+pub static IGNORE_EDIT_PREVIEW_TITLE: &str = "Matched paths";
+pub static IGNORE_EDIT_NO_PATTERNS: &str = "no patterns yet";
+pub fn ignore_edit_matches(count: usize, negative: bool) -> String {
+	format!(
+		"  {count} {}",
+		if negative { "re-included" } else { "hidden" }
+	)
+}
+pub fn clean_title(options: CleanOptions) -> String {
+	format!(
+		"Clean{}{}",
+		if options.directories { " -d" } else { "" },
+		if options.ignored { " -x" } else { "" },
+	)
+}
+pub static CLEAN_EMPTY: &str = "nothing to clean";
+pub fn clean_backup(paths: usize) -> String {
+	format!("clean {paths} untracked path(s)")
+}
+pub static REBASE_INTERACTIVE_STOPPED: &str =
+	"stopped to edit a commit: amend it and continue the rebase in the status tab";
+/// `position` counts from the oldest revision (1) to the newest (`count`)
+pub fn file_revision(
+	short_id: &str,
+	position: usize,
+	count: usize,
+	summary: &str,
+) -> String {
+	format!("{{{short_id}}} ({position}/{count}) [{summary}]")
+}
+pub fn locations_exported(count: usize, files: &[PathBuf]) -> String {
+	format!(
+		"exported {count} locations to:\n{}",
+		files
+			.iter()
+			.map(|file| file.display().to_string())
+			.collect::<Vec<_>>()
+			.join("\n")
+	)
+}
+pub fn symref_entry(name: &str, target: &str, fixed: bool) -> String {
+	format!(
+		"{name} -> {target}{}",
+		if fixed { " (change via checkout)" } else { "" }
+	)
+}
+pub fn symref_target_title(name: &str) -> String {
+	format!("Point {name} at")
+}
+pub fn symref_target_entry(target: &str, current: bool) -> String {
+	format!("{target}{}", if current { " (current)" } else { "" })
+}
+pub fn flow_step_delete(branch: &str, develop: &str) -> String {
+	format!("check out '{develop}' and delete '{branch}'")
+}
+pub static STATUS_BRANCH_PROTECTED: &str = "protected branch! ";
+pub fn force_push_checking(branch: &str) -> String {
+	format!("Fetching remote tip of '{branch}'...")
+}
+pub fn force_push_preview_failed(err: &str) -> String {
+	format!("Could not check what the force push discards: {err}")
+}
+pub fn force_push_fetch_failed(err: &str) -> String {
+	format!(
+		"Could not refresh the remote tip ({err}), showing the state of the last fetch."
+	)
+}
+pub static FORCE_PUSH_STALE: &str =
+	"Warning: the remote branch moved since your last fetch!";
+pub fn force_push_discards(remote_ref: &str, count: usize) -> String {
+	format!(
+		"Force push discards {count} commit{} from '{remote_ref}':",
+		if count == 1 { "" } else { "s" }
+	)
+}
+pub fn force_push_nothing_discarded(remote_ref: &str) -> String {
+	format!("Force push discards no commits from '{remote_ref}'.")
+}
+pub static FORCE_PUSH_REMOTE_ONLY: &str = " (not present locally)";
+pub fn force_push_lease_holds(remote_ref: &str, tip: &str) -> String {
+	format!("Lease holds: '{remote_ref}' is still at {tip}.")
+}
+pub fn force_push_lease_new(remote_ref: &str) -> String {
+	format!(
+		"Lease holds: '{remote_ref}' does not exist on the remote."
+	)
+}
+pub fn force_push_lease_unchecked(
+	remote_ref: &str,
+	tip: &str,
+) -> String {
+	format!("Lease unchecked: '{remote_ref}' is expected at {tip}.")
+}
+pub fn force_push_lease_broken(
+	remote_ref: &str,
+	tip: &str,
+) -> String {
+	format!(
+		"Lease broken: '{remote_ref}' moved away from {tip}, a push with lease will be refused."
+	)
+}
+pub fn force_push_protected(branch: &str) -> String {
+	format!("'{branch}' is a protected branch.")
+}
+pub fn type_to_confirm(text: &str) -> String {
+	format!("type '{text}' to confirm")
+}
+pub fn confirm_title_push_protected() -> String {
+	"Push Protected Branch".to_string()
+}
+pub fn confirm_msg_push_protected(branch: &str) -> String {
+	format!("'{branch}' is a protected branch. Confirm push?")
+}
+pub fn confirm_title_push_stack(
+	_key_config: &SharedKeyConfig,
+) -> String {
+	"Push Stack".to_string()
+}
+pub fn confirm_msg_push_stack(
+	_key_config: &SharedKeyConfig,
+	branches: &[String],
+) -> String {
+	format!(
+		"Confirm force push (with lease) of the stack '{}' ?  This may rewrite history.",
+		branches.join(" \u{2190} ")
+	)
+}
+pub fn log_title(_key_config: &SharedKeyConfig) -> String {
+	"Commit".to_string()
+}
+pub fn log_title_options(
+	key_config: &SharedKeyConfig,
+	scope: &LogScope,
+	first_parent: bool,
+) -> String {
+	let options = [
+		(*scope != LogScope::Head).then(|| log_scope(scope)),
+		first_parent.then(|| "first parent".to_string()),
+	]
+	.into_iter()
+	.flatten()
+	.collect::<Vec<_>>();
 
-pub mod commands {
-	use crate::components::CommandText;
-	use crate::keys::SharedKeyConfig;
-
-	static CMD_GROUP_GENERAL: &str = "-- General --";
-	static CMD_GROUP_DIFF: &str = "-- Diff --";
-	static CMD_GROUP_CHANGES: &str = "-- Changes --";
-	static CMD_GROUP_COMMIT_POPUP: &str = "-- Commit Popup --";
-	static CMD_GROUP_STASHING: &str = "-- Stashing --";
-	static CMD_GROUP_STASHES: &str = "-- Stashes --";
-	static CMD_GROUP_LOG: &str = "-- Log --";
-	static CMD_GROUP_BRANCHES: &str = "-- Branches --";
-
-	pub fn toggle_tabs(key_config: &SharedKeyConfig) -> CommandText {
+	if options.is_empty() {
+		log_title(key_config)
+	} else {
+		format!("{} ({})", log_title(key_config), options.join(", "))
+	}
+}
+pub fn log_scope(scope: &LogScope) -> String {
+	match scope {
+		LogScope::Head => "current branch".to_string(),
+		LogScope::LocalBranches => "local branches".to_string(),
+		LogScope::AllRefs => "all refs".to_string(),
+		LogScope::Refs(refs) => refs
+			.iter()
+			.map(|r| short_ref_name(r))
+			.collect::<Vec<_>>()
+			.join(", "),
+	}
+}
+/// `refs/heads/main` -> `main`, `refs/remotes/origin/main` -> `origin/main`
+pub fn short_ref_name(reference: &str) -> &str {
+	["refs/heads/", "refs/remotes/", "refs/tags/"]
+		.iter()
+		.find_map(|prefix| reference.strip_prefix(prefix))
+		.unwrap_or(reference)
+}
+pub fn log_title_pathspec(title: &str, pathspec: &str) -> String {
+	format!("{title} of '{pathspec}'")
+}
+pub fn log_title_bisect(title: &str, state: &BisectState) -> String {
+	format!("{title} | bisect: {}", bisect_progress(state))
+}
+pub fn log_title_shallow(title: &str, depth: usize) -> String {
+	format!("{title} | shallow: {depth} commits")
+}
+pub fn bisect_progress(state: &BisectState) -> String {
+	match (state.first_bad, state.next) {
+		(Some(first_bad), _) => {
+			format!(
+				"first bad commit is {}",
+				first_bad.get_short_string()
+			)
+		}
+		(None, Some(next)) => format!(
+			"{} commits left (~{} steps), testing {}",
+			state.remaining.len(),
+			state.steps_left(),
+			next.get_short_string()
+		),
+		(None, None) if state.remaining.is_empty() => {
+			String::from("mark a good and a bad commit")
+		}
+		(None, None) => format!(
+			"{} commits left, only skipped commits to test",
+			state.remaining.len()
+		),
+	}
+}
+pub fn bisect_commit(id: CommitId, summary: &str) -> String {
+	format!("commit: {} {summary}", id.get_short_string())
+}
+pub fn bisect_marks(state: &BisectState) -> String {
+	format!(
+		"bad: {} good: {} skipped: {}",
+		state
+			.bad
+			.as_ref()
+			.map(CommitId::get_short_string)
+			.unwrap_or_default(),
+		state.good.len(),
+		state.skipped.len()
+	)
+}
+pub fn file_log_title(
+	file_path: &str,
+	selected: usize,
+	revisions: usize,
+) -> String {
+	format!("Revisions of '{file_path}' ({selected}/{revisions})")
+}
+pub fn line_log_title(
+	file_path: &str,
+	lines: (usize, usize),
+	selected: usize,
+	revisions: usize,
+) -> String {
+	format!(
+		"Revisions of '{file_path}' lines {}-{} ({selected}/{revisions})",
+		lines.0, lines.1
+	)
+}
+pub const BLAME_NO_PARENT: &str =
+	"line was not changed by a commit with a parent";
+pub fn file_log_renamed_from(old_path: &str) -> String {
+	format!("renamed from '{old_path}'")
+}
+pub fn blame_title(_key_config: &SharedKeyConfig) -> String {
+	"Blame".to_string()
+}
+pub fn commit_note_popup_title() -> String {
+	"Note".to_string()
+}
+pub fn commit_note_popup_msg() -> String {
+	"type note (empty to remove it)".to_string()
+}
+pub fn notes_sync_title(sync: NotesSync) -> String {
+	match sync {
+		NotesSync::Push => "Push Notes",
+		NotesSync::Fetch => "Fetch Notes",
+	}
+	.to_string()
+}
+pub fn notes_sync_msg(sync: NotesSync, remote: &str) -> String {
+	match sync {
+		NotesSync::Push => format!("pushing to '{remote}'..."),
+		NotesSync::Fetch => format!("fetching from '{remote}'..."),
+	}
+}
+pub fn format_patch_popup_title(
+	commits: usize,
+	options: FormatPatchOptions,
+) -> String {
+	let mut title = format!(
+		"Export {commits} Patch{}",
+		if commits == 1 { "" } else { "es" }
+	);
+	if options.numbered {
+		title.push_str(" [numbered]");
+	}
+	if options.cover_letter {
+		title.push_str(" [cover letter]");
+	}
+	title
+}
+pub fn format_patch_popup_msg() -> String {
+	"directory to write the patches to (default: repository root)"
+		.to_string()
+}
+pub fn format_patch_success(files: usize, dir: &str) -> String {
+	format!("wrote {files} patch file(s) to '{dir}'")
+}
+pub fn apply_patch_popup_title(target: ApplyPatchTarget) -> String {
+	match target {
+		ApplyPatchTarget::Worktree => {
+			"Apply Patch [to worktree]".to_string()
+		}
+		ApplyPatchTarget::Commits => {
+			"Apply Patch [as commits]".to_string()
+		}
+	}
+}
+pub fn apply_patch_popup_msg() -> String {
+	"patch or mbox file (default: read from clipboard)".to_string()
+}
+pub fn apply_patch_result(result: ApplyPatchResult) -> String {
+	match result {
+		ApplyPatchResult::Applied(0) => {
+			"patch applied to the worktree".to_string()
+		}
+		ApplyPatchResult::Applied(commits) => {
+			format!("applied {commits} patch(es) as commits")
+		}
+		ApplyPatchResult::Conflicts => {
+			"patch did not apply cleanly, resolve the conflicts in the status tab"
+				.to_string()
+		}
+	}
+}
+pub fn apply_mailbox_progress(
+	progress: &ApplyMailboxProgress,
+) -> String {
+	format!(
+		"Patch: {}/{} {}",
+		progress.current, progress.total, progress.subject
+	)
+}
+pub fn sequencer_result(
+	action: SequencerAction,
+	result: SequencerResult,
+) -> String {
+	let (done, doing) = match action {
+		SequencerAction::Pick => ("cherry-picked", "cherry-picking"),
+		SequencerAction::Revert => ("reverted", "reverting"),
+	};
+
+	match result {
+		SequencerResult::Done(commits) => {
+			format!("{done} {commits} commit(s)")
+		}
+		SequencerResult::Conflicts(id) => format!(
+			"{doing} {} stopped at conflicts, resolve them in the status tab",
+			id.get_short_string()
+		),
+	}
+}
+pub fn sequencer_progress(progress: &SequencerProgress) -> String {
+	format!(
+		"{}: {} ({} left)",
+		match progress.action {
+			SequencerAction::Pick => "Picking",
+			SequencerAction::Revert => "Reverting",
+		},
+		progress.current.get_short_string(),
+		progress.remaining
+	)
+}
+pub fn pending_state_title(
+	key_config: &SharedKeyConfig,
+	state: &RepoState,
+) -> String {
+	let keys = &key_config.keys;
+	let hints = match state {
+		RepoState::Merge => {
+			format!(
+				"abort [{}]",
+				key_config.get_hint(keys.abort_merge)
+			)
+		}
+		RepoState::Rebase
+		| RepoState::Revert
+		| RepoState::ApplyMailbox
+		| RepoState::CherryPick => format!(
+			"continue [{}] skip [{}] abort [{}]",
+			key_config.get_hint(keys.rebase_branch),
+			key_config.get_hint(keys.status_skip_step),
+			key_config.get_hint(keys.abort_merge),
+		),
+		_ => return format!("Pending {state:?}"),
+	};
+
+	format!("Pending {state:?}: {hints}")
+}
+pub fn tag_popup_name_title() -> String {
+	"Tag".to_string()
+}
+pub fn tag_popup_name_msg() -> String {
+	"type tag name".to_string()
+}
+pub fn tag_popup_annotation_title(name: &str) -> String {
+	format!("Tag Annotation ({name})")
+}
+pub fn tag_popup_annotation_msg() -> String {
+	"type tag annotation".to_string()
+}
+pub fn stashlist_title(_key_config: &SharedKeyConfig) -> String {
+	"Stashes".to_string()
+}
+pub fn stash_worktree_title(stash: CommitId) -> String {
+	format!("Stash {} vs. working tree", stash.get_short_string())
+}
+pub fn help_title(_key_config: &SharedKeyConfig) -> String {
+	"Help: all commands".to_string()
+}
+pub fn stashing_files_title(_key_config: &SharedKeyConfig) -> String {
+	"Files to Stash".to_string()
+}
+pub fn stashing_options_title(
+	_key_config: &SharedKeyConfig,
+) -> String {
+	"Options".to_string()
+}
+pub fn loading_text(_key_config: &SharedKeyConfig) -> String {
+	"Loading ...".to_string()
+}
+pub fn create_branch_popup_title(
+	_key_config: &SharedKeyConfig,
+) -> String {
+	"Branch".to_string()
+}
+pub fn conflict_editor_title(
+	path: &str,
+	unresolved: usize,
+) -> String {
+	format!("Resolve '{path}' ({unresolved} unresolved)")
+}
+pub fn conflict_region(
+	idx: usize,
+	count: usize,
+	resolution: Option<&ConflictResolution>,
+) -> String {
+	let resolution = match resolution {
+		None => "unresolved",
+		Some(ConflictResolution::Ours) => "ours",
+		Some(ConflictResolution::Theirs) => "theirs",
+		Some(ConflictResolution::Both) => "ours + theirs",
+		Some(ConflictResolution::Base) => "base",
+		Some(ConflictResolution::Edited(_)) => "edited",
+	};
+	format!("conflict {}/{count}: {resolution}", idx + 1)
+}
+pub fn worktree_entry(worktree: &WorktreeInfo) -> String {
+	let mut res = format!(
+		"{} {} {}",
+		worktree.name.as_deref().unwrap_or("(main)"),
+		worktree.branch.as_deref().unwrap_or("(detached)"),
+		worktree.path.display()
+	);
+	if worktree.is_current {
+		res.push_str(" [current]");
+	}
+	if worktree.is_locked {
+		res.push_str(" [locked]");
+	}
+	if worktree.is_prunable {
+		res.push_str(" [prunable]");
+	}
+	res
+}
+pub fn worktrees_pruned(count: usize) -> String {
+	format!("pruned {count} worktree(s)")
+}
+pub fn reflog_title(
+	refname: &str,
+	idx: usize,
+	count: usize,
+) -> String {
+	format!("Reflog of {refname} ({}/{count})", idx + 1)
+}
+pub fn reflog_entry(selector: &str, message: &str) -> String {
+	format!("{selector}: {message}")
+}
+pub fn create_branch_at_popup_title(commit: CommitId) -> String {
+	format!("Branch at {}", commit.get_short_string())
+}
+pub fn create_branch_popup_msg(
+	_key_config: &SharedKeyConfig,
+) -> String {
+	"type branch name".to_string()
+}
+pub fn blame_ignore_revs_popup_title() -> String {
+	"Blame ignore revs file".to_string()
+}
+pub fn blame_ignore_revs_popup_msg() -> String {
+	"path relative to the repository, empty to not ignore any"
+		.to_string()
+}
+pub fn rename_remote_popup_title(
+	_key_config: &SharedKeyConfig,
+) -> String {
+	"Rename remote".to_string()
+}
+pub fn rename_remote_popup_msg(
+	_key_config: &SharedKeyConfig,
+) -> String {
+	"new remote name".to_string()
+}
+pub fn update_remote_url_popup_title(
+	_key_config: &SharedKeyConfig,
+) -> String {
+	"Update url".to_string()
+}
+pub fn update_remote_url_popup_msg(
+	_key_config: &SharedKeyConfig,
+) -> String {
+	"new remote url".to_string()
+}
+pub fn update_remote_push_url_popup_title(
+	_key_config: &SharedKeyConfig,
+) -> String {
+	"Update push url".to_string()
+}
+pub fn update_remote_push_url_popup_msg(
+	_key_config: &SharedKeyConfig,
+) -> String {
+	"new push url (empty to push to the fetch url)".to_string()
+}
+pub fn create_remote_popup_title_name(
+	_key_config: &SharedKeyConfig,
+) -> String {
+	"Remote name".to_string()
+}
+pub fn create_remote_popup_title_url(
+	_key_config: &SharedKeyConfig,
+) -> String {
+	"Remote url".to_string()
+}
+pub fn create_remote_popup_msg_name(
+	_key_config: &SharedKeyConfig,
+) -> String {
+	"type remote name".to_string()
+}
+pub fn create_remote_popup_msg_url(
+	_key_config: &SharedKeyConfig,
+) -> String {
+	"type remote url".to_string()
+}
+pub const fn remote_name_invalid() -> &'static str {
+	"[invalid name]"
+}
+pub const fn remote_name_exists() -> &'static str {
+	"[already exists]"
+}
+pub const fn remote_url_invalid() -> &'static str {
+	"[invalid url]"
+}
+pub fn username_popup_title(_key_config: &SharedKeyConfig) -> String {
+	"Username".to_string()
+}
+pub fn username_popup_msg(_key_config: &SharedKeyConfig) -> String {
+	"type username".to_string()
+}
+pub fn password_popup_title(_key_config: &SharedKeyConfig) -> String {
+	"Password / Token".to_string()
+}
+pub fn password_popup_msg(_key_config: &SharedKeyConfig) -> String {
+	"type password, or access token if 2FA is enabled".to_string()
+}
+pub fn ssh_identity(identity: &Path, encrypted: bool) -> String {
+	format!(
+		"{}{}",
+		identity.display(),
+		if encrypted { " (passphrase)" } else { "" }
+	)
+}
+pub fn ssh_passphrase_popup_title(
+	_key_config: &SharedKeyConfig,
+) -> String {
+	"SSH Key Passphrase".to_string()
+}
+pub fn ssh_passphrase_popup_msg(
+	_key_config: &SharedKeyConfig,
+) -> String {
+	"type passphrase of the key (no ssh agent running)".to_string()
+}
+pub fn cred_popup_title(
+	title: &str,
+	storage: CredentialStorage,
+) -> String {
+	format!(
+		"{title} (remember: {})",
+		match storage {
+			CredentialStorage::None => "no",
+			CredentialStorage::Session => "this session",
+			CredentialStorage::Helper => "credential helper",
+		}
+	)
+}
+
+pub fn rename_branch_popup_title(
+	_key_config: &SharedKeyConfig,
+) -> String {
+	"Rename Branch".to_string()
+}
+pub fn rename_branch_popup_msg(
+	_key_config: &SharedKeyConfig,
+) -> String {
+	"new branch name".to_string()
+}
+
+pub fn copy_success(s: &str) -> String {
+	format!("{POPUP_SUCCESS_COPY} \"{s}\"")
+}
+
+pub fn ellipsis_trim_start(s: &str, width: usize) -> Cow<str> {
+	if s.width() <= width {
+		Cow::Borrowed(s)
+	} else {
+		Cow::Owned(format!(
+			"[{}]{}",
+			symbol::ELLIPSIS,
+			s.unicode_truncate_start(
+				width.saturating_sub(3 /* front indicator */)
+			)
+			.0
+		))
+	}
+}
+
+pub mod commit {
+	use crate::keys::SharedKeyConfig;
+
+	pub fn details_author() -> String {
+		"Author: ".to_string()
+	}
+	pub fn details_committer() -> String {
+		"Committer: ".to_string()
+	}
+	pub fn details_sha() -> String {
+		"Sha: ".to_string()
+	}
+	pub fn details_date() -> String {
+		"Date: ".to_string()
+	}
+	pub fn details_tags() -> String {
+		"Tags: ".to_string()
+	}
+	pub fn details_describe() -> String {
+		"Describe: ".to_string()
+	}
+	pub fn details_signature() -> String {
+		"Signature: ".to_string()
+	}
+	pub fn details_trailers() -> String {
+		"Trailers:".to_string()
+	}
+	pub fn details_notes() -> String {
+		"Notes:".to_string()
+	}
+	pub fn details_message() -> String {
+		"Subject: ".to_string()
+	}
+	pub fn details_info_title(
+		_key_config: &SharedKeyConfig,
+	) -> String {
+		"Info".to_string()
+	}
+	pub fn compare_details_info_title(
+		old: bool,
+		hash: &str,
+	) -> String {
+		format!("{}: {hash}", if old { "Old" } else { "New" })
+	}
+	pub fn compare_details_duplicates_title(count: usize) -> String {
+		format!("Duplicate changes (old = new): {count}")
+	}
+	pub fn compare_details_duplicates_pending_title() -> String {
+		"Duplicate changes (old = new): searching...".to_string()
+	}
+	pub fn compare_commits_title(
+		mode: CompareMode,
+		base: &str,
+		target: &str,
+	) -> String {
+		format!("Commits {base}{}{target}", mode.as_str())
+	}
+	pub fn details_message_title(
+		_key_config: &SharedKeyConfig,
+	) -> String {
+		"Message".to_string()
+	}
+	pub fn details_files_title(
+		_key_config: &SharedKeyConfig,
+	) -> String {
+		"Files:".to_string()
+	}
+}
+
+pub mod commands {
+	use crate::components::CommandText;
+	use crate::keys::SharedKeyConfig;
+	use asyncgit::sync::{
+		ApplyPatchTarget, IgnoreFile, LogScope, SequencerAction,
+	};
+
+	static CMD_GROUP_GENERAL: &str = "-- General --";
+	static CMD_GROUP_DIFF: &str = "-- Diff --";
+	static CMD_GROUP_CHANGES: &str = "-- Changes --";
+	static CMD_GROUP_COMMIT_POPUP: &str = "-- Commit Popup --";
+	static CMD_GROUP_STASHING: &str = "-- Stashing --";
+	static CMD_GROUP_STASHES: &str = "-- Stashes --";
+	static CMD_GROUP_LOG: &str = "-- Log --";
+	static CMD_GROUP_BRANCHES: &str = "-- Branches --";
+
+	pub fn toggle_tabs(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Next [{}]",
+				key_config.get_hint(key_config.keys.tab_toggle)
+			),
+			"switch to next tab",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn find_file(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Find [{}]",
+				key_config.get_hint(key_config.keys.file_find)
+			),
+			"find file in tree",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn find_branch(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Find [{}]",
+				key_config.get_hint(key_config.keys.branch_find)
+			),
+			"find branch in list",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn toggle_tabs_direct(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Tab [{}{}{}{}{}]",
+				key_config.get_hint(key_config.keys.tab_status),
+				key_config.get_hint(key_config.keys.tab_log),
+				key_config.get_hint(key_config.keys.tab_files),
+				key_config.get_hint(key_config.keys.tab_stashing),
+				key_config.get_hint(key_config.keys.tab_stashes),
+			),
+			"switch top level tabs directly",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn options_popup(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Options [{}]",
+				key_config.get_hint(key_config.keys.open_options),
+			),
+			"open options popup",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn undo_history(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Undo [{}]",
+				key_config.get_hint(key_config.keys.undo_history),
+			),
+			"list recent destructive operations to undo them",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn undo_restore(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Restore [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"restore the state before the selected operation",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn git_config_popup(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Git Config [{}]",
+				key_config.get_hint(key_config.keys.open_git_config),
+			),
+			"view and edit git config values",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn git_config_edit_local(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Edit Local [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"set value in the repository config",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn git_config_edit_global(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Edit Global [{}]",
+				key_config
+					.get_hint(key_config.keys.config_edit_global),
+			),
+			"set value in the global config",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn git_config_add(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Add [{}]",
+				key_config.get_hint(key_config.keys.config_add),
+			),
+			"set any key in the repository config",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn git_config_unset(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Unset [{}]",
+				key_config.get_hint(key_config.keys.config_unset),
+			),
+			"remove value from the config it is set in",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn git_config_confirm(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Confirm [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"save config value",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn clone_popup(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Clone [{}]",
+				key_config.get_hint(key_config.keys.clone_repo),
+			),
+			"clone a repository and open it",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn clone_confirm(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Clone [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"start cloning",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn cred_storage(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Remember [{}]",
+				key_config.get_hint(key_config.keys.cred_storage),
+			),
+			"keep credentials for this session or in the credential helper",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn clone_toggle_submodules(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Submodules [{}]",
+				key_config.get_hint(
+					key_config.keys.clone_toggle_submodules
+				),
+			),
+			"toggle cloning submodules recursively",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn init_repo_confirm(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Init [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"initialize a new repository here",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn init_repo_quit(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Quit [{}]",
+				key_config.get_hint(key_config.keys.exit_popup),
+			),
+			"quit without initializing a repository",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn clone_next_field(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Next [{}]",
+				key_config.get_hint(key_config.keys.tab_toggle),
+			),
+			"focus next field",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn help_open(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Help [{}]",
+				key_config.get_hint(key_config.keys.open_help)
+			),
+			"open this help screen",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn navigate_commit_message(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Nav [{}{}]",
+				key_config.get_hint(key_config.keys.move_up),
+				key_config.get_hint(key_config.keys.move_down)
+			),
+			"navigate commit message",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn navigate_tree(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Nav [{}{}{}{}]",
+				key_config.get_hint(key_config.keys.move_up),
+				key_config.get_hint(key_config.keys.move_down),
+				key_config.get_hint(key_config.keys.move_right),
+				key_config.get_hint(key_config.keys.move_left)
+			),
+			"navigate tree view, collapse, expand",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn scroll(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Scroll [{}{}]",
+				key_config.get_hint(key_config.keys.move_up),
+				key_config.get_hint(key_config.keys.move_down)
+			),
+			"scroll up or down in focused view",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn commit_list_mark(
+		key_config: &SharedKeyConfig,
+		marked: bool,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"{} [{}]",
+				if marked { "Unmark" } else { "Mark" },
+				key_config.get_hint(key_config.keys.log_mark_commit),
+			),
+			"mark multiple commits",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn copy(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Copy [{}]",
+				key_config.get_hint(key_config.keys.copy),
+			),
+			"copy selected lines to clipboard",
+			CMD_GROUP_DIFF,
+		)
+	}
+	pub fn copy_hash(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Copy Hash [{}]",
+				key_config.get_hint(key_config.keys.copy),
+			),
+			"copy selected commit hash to clipboard",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn compare_revision(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Compare Revision [{}]",
+				key_config.get_hint(key_config.keys.compare_commits),
+			),
+			"compare a revision expression with head",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn reset_select_revision(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Revision [{}]",
+				key_config.get_hint(key_config.keys.select_revision),
+			),
+			"enter a revision expression to reset to",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn copy_describe(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Copy Describe [{}]",
+				key_config.get_hint(key_config.keys.copy_describe),
+			),
+			"copy `git describe --tags` of selected commit to clipboard",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn copy_trailers(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Copy Trailers [{}]",
+				key_config.get_hint(key_config.keys.copy_trailers),
+			),
+			"copy trailers (e.g. Signed-off-by) of selected commit to clipboard",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn copy_path(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Copy Path [{}]",
+				key_config.get_hint(key_config.keys.copy),
+			),
+			"copy selected file path to clipboard",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn push_tags(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Push Tags [{}]",
+				key_config.get_hint(key_config.keys.push),
+			),
+			"push tags to remote",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn toggle_option(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Toggle Option [{}]",
+				key_config.get_hint(key_config.keys.log_mark_commit),
+			),
+			"toggle search option selected",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn show_tag_annotation(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Annotation [{}]",
+				key_config.get_hint(key_config.keys.move_right),
+			),
+			"show tag annotation",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn diff_hunk_next(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Next hunk [{}]",
+				key_config.get_hint(key_config.keys.diff_hunk_next),
+			),
+			"move cursor to next hunk",
+			CMD_GROUP_DIFF,
+		)
+	}
+	pub fn diff_hunk_prev(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Prev hunk [{}]",
+				key_config.get_hint(key_config.keys.diff_hunk_prev),
+			),
+			"move cursor to prev hunk",
+			CMD_GROUP_DIFF,
+		)
+	}
+	pub fn diff_ignore_whitespace(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Whitespace [{}]",
+				key_config
+					.get_hint(key_config.keys.diff_ignore_whitespace),
+			),
+			"cycle between showing whitespace changes, ignoring changes in the amount of whitespace and ignoring all whitespace",
+			CMD_GROUP_DIFF,
+		)
+	}
+	pub fn diff_ignore_blank_lines(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Blank lines [{}]",
+				key_config.get_hint(
+					key_config.keys.diff_ignore_blank_lines
+				),
+			),
+			"toggle ignoring changes of blank lines",
+			CMD_GROUP_DIFF,
+		)
+	}
+	pub fn diff_context_change(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Context [{}{}]",
+				key_config
+					.get_hint(key_config.keys.diff_context_increase),
+				key_config
+					.get_hint(key_config.keys.diff_context_decrease),
+			),
+			"show more or fewer context lines around changes",
+			CMD_GROUP_DIFF,
+		)
+	}
+	pub fn diff_home_end(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Jump up/down [{},{},{},{}]",
+				key_config.get_hint(key_config.keys.home),
+				key_config.get_hint(key_config.keys.end),
+				key_config.get_hint(key_config.keys.move_up),
+				key_config.get_hint(key_config.keys.move_down)
+			),
+			"scroll to top or bottom of diff",
+			CMD_GROUP_DIFF,
+		)
+	}
+	pub fn diff_hunk_add(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Add hunk [{}]",
+				key_config
+					.get_hint(key_config.keys.stage_unstage_item),
+			),
+			"adds selected hunk to stage",
+			CMD_GROUP_DIFF,
+		)
+	}
+	pub fn diff_hunks_apply(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Apply hunks [{}]",
+				key_config.get_hint(key_config.keys.diff_apply_hunks),
+			),
+			"apply selected hunks of the commit onto the working tree",
+			CMD_GROUP_DIFF,
+		)
+	}
+	pub fn diff_hunks_stash(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Stash hunks [{}]",
+				key_config.get_hint(key_config.keys.diff_stash_hunks),
+			),
+			"stash the selected hunks and remove them from the working tree",
+			CMD_GROUP_DIFF,
+		)
+	}
+	pub fn diff_hunks_revert_commit(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Revert hunks [{}]",
+				key_config.get_hint(key_config.keys.diff_revert_hunks),
+			),
+			"apply selected hunks of the commit reversed onto the working tree",
+			CMD_GROUP_DIFF,
+		)
+	}
+	pub fn diff_hunk_revert(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Reset hunk [{}]",
+				key_config
+					.get_hint(key_config.keys.status_reset_item),
+			),
+			"reverts selected hunk",
+			CMD_GROUP_DIFF,
+		)
+	}
+	pub fn diff_lines_revert(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Reset lines [{}]",
+				key_config.get_hint(key_config.keys.diff_reset_lines),
+			),
+			"resets selected lines",
+			CMD_GROUP_DIFF,
+		)
+	}
+	pub fn diff_lines_stage(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Stage lines [{}]",
+				key_config.get_hint(key_config.keys.diff_stage_lines),
+			),
+			"stage selected lines",
+			CMD_GROUP_DIFF,
+		)
+	}
+	pub fn diff_hunk_edit(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Edit hunk [{}]",
+				key_config.get_hint(key_config.keys.diff_edit_hunk),
+			),
+			"edit the hunk and stage the result",
+			CMD_GROUP_DIFF,
+		)
+	}
+	pub fn edit_hunk_confirm(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Stage [{}]",
+				key_config.get_hint(key_config.keys.commit),
+			),
+			"stage the edited hunk",
+			CMD_GROUP_DIFF,
+		)
+	}
+	pub fn diff_lines_unstage(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Unstage lines [{}]",
+				key_config.get_hint(key_config.keys.diff_stage_lines),
+			),
+			"unstage selected lines",
+			CMD_GROUP_DIFF,
+		)
+	}
+	pub fn diff_hunk_remove(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Remove hunk [{}]",
+				key_config
+					.get_hint(key_config.keys.stage_unstage_item),
+			),
+			"removes selected hunk from stage",
+			CMD_GROUP_DIFF,
+		)
+	}
+	pub fn close_fuzzy_finder(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Close [{}{}]",
+				key_config.get_hint(key_config.keys.exit_popup),
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"close fuzzy finder",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn close_popup(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Close [{}]",
+				key_config.get_hint(key_config.keys.exit_popup),
+			),
+			"close overlay (e.g commit, help)",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn scroll_popup(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Scroll [{}{}]",
+				key_config.get_hint(key_config.keys.popup_down),
+				key_config.get_hint(key_config.keys.popup_up),
+			),
+			"scroll up or down in popup",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn close_msg(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Close [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"close msg popup (e.g msg)",
+			CMD_GROUP_GENERAL,
+		)
+		.hide_help()
+	}
+	pub fn validate_msg(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Validate [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"validate msg",
+			CMD_GROUP_GENERAL,
+		)
+		.hide_help()
+	}
+
+	pub fn abort_merge(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Abort merge [{}]",
+				key_config.get_hint(key_config.keys.abort_merge),
+			),
+			"abort ongoing merge",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn abort_revert(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Abort revert [{}]",
+				key_config.get_hint(key_config.keys.abort_merge),
+			),
+			"abort ongoing revert",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn view_submodules(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Submodules [{}]",
+				key_config.get_hint(key_config.keys.view_submodules),
+			),
+			"open submodule view",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn view_worktrees(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Worktrees [{}]",
+				key_config.get_hint(key_config.keys.view_worktrees),
+			),
+			"list, add, prune and switch worktrees",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn worktree_switch(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Switch [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"open the selected worktree",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn worktree_add(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Add [{}]",
+				key_config.get_hint(key_config.keys.worktree_add),
+			),
+			"check out a branch in a new worktree (default path: next to the repo)",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn worktree_add_confirm(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Add [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"add the worktree",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn worktree_prune(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Prune [{}]",
+				key_config.get_hint(key_config.keys.worktree_prune),
+			),
+			"remove worktrees whose directory is gone",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn status_export_locations(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Export locations [{}]",
+				key_config
+					.get_hint(key_config.keys.status_export_locations),
+			),
+			"write conflicts (or hunks) as quickfix list and SARIF log",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn view_remotes(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Remotes [{}]",
+				key_config.get_hint(key_config.keys.view_remotes)
+			),
+			"open remotes view",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn update_remote_name(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Edit name [{}]",
+				key_config
+					.get_hint(key_config.keys.update_remote_name)
+			),
+			"updates a remote name",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn update_remote_url(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Edit url [{}]",
+				key_config
+					.get_hint(key_config.keys.update_remote_url)
+			),
+			"updates a remote url",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn remote_identity_file(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Edit ssh key [{}]",
+				key_config
+					.get_hint(key_config.keys.remote_identity_file)
+			),
+			"sets the ssh identity file used for a remote",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn update_remote_push_url(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Edit push url [{}]",
+				key_config
+					.get_hint(key_config.keys.update_remote_push_url)
+			),
+			"updates the url a remote is pushed to",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn create_remote(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Add [{}]",
+				key_config.get_hint(key_config.keys.add_remote)
+			),
+			"creates a new remote",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn delete_remote_popup(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Remove [{}]",
+				key_config.get_hint(key_config.keys.delete_remote),
+			),
+			"remove a remote",
+			CMD_GROUP_BRANCHES,
+		)
+	}
+
+	pub fn remote_confirm_name_msg(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Confirm name [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"confirm remote name",
+			CMD_GROUP_BRANCHES,
+		)
+		.hide_help()
+	}
+
+	pub fn remote_confirm_url_msg(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Confirm url [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"confirm remote url",
+			CMD_GROUP_BRANCHES,
+		)
+		.hide_help()
+	}
+
+	pub fn open_submodule(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Open [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"open submodule",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn open_submodule_parent(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Open Parent [{}]",
+				key_config
+					.get_hint(key_config.keys.view_submodule_parent),
+			),
+			"open submodule parent repo",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn update_submodule(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Update [{}]",
+				key_config.get_hint(key_config.keys.update_submodule),
+			),
+			"update submodule",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn update_submodule_recursive(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Update recursive [{}]",
+				key_config.get_hint(
+					key_config.keys.update_submodule_recursive
+				),
+			),
+			"update submodule and all submodules nested inside",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn init_submodule(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Init [{}]",
+				key_config.get_hint(key_config.keys.init_submodule),
+			),
+			"register submodule url in the repo config",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn sync_submodule(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Sync url [{}]",
+				key_config.get_hint(key_config.keys.sync_submodule),
+			),
+			"copy submodule url from .gitmodules into the configs",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn continue_sequencer(
+		key_config: &SharedKeyConfig,
+		action: SequencerAction,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Continue {} [{}]",
+				match action {
+					SequencerAction::Pick => "cherry-pick",
+					SequencerAction::Revert => "revert",
+				},
+				key_config.get_hint(key_config.keys.rebase_branch),
+			),
+			"commit the resolved step and run the remaining ones",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn abort_cherry_pick(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Abort cherry-pick [{}]",
+				key_config.get_hint(key_config.keys.abort_merge),
+			),
+			"go back to where the cherry-pick started",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn log_cherry_pick(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Cherry-pick [{}]",
+				key_config.get_hint(key_config.keys.log_cherry_pick),
+			),
+			"cherry-pick the marked (or selected) commits onto HEAD, oldest first",
+			CMD_GROUP_LOG,
+		)
+	}
+
+	pub fn log_cherry_pick_origin(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Cherry-pick -x [{}]",
+				key_config
+					.get_hint(key_config.keys.log_cherry_pick_origin),
+			),
+			"cherry-pick and record the original commit in the message",
+			CMD_GROUP_LOG,
+		)
+	}
+
+	pub fn log_deepen(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Deepen [{}]",
+				key_config.get_hint(key_config.keys.log_deepen),
+			),
+			"fetch more history into the shallow clone",
+			CMD_GROUP_LOG,
+		)
+	}
+
+	pub fn log_unshallow(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Unshallow [{}]",
+				key_config.get_hint(key_config.keys.log_unshallow),
+			),
+			"fetch the complete history of the shallow clone",
+			CMD_GROUP_LOG,
+		)
+	}
+
+	pub fn continue_apply_mailbox(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Continue am [{}]",
+				key_config.get_hint(key_config.keys.rebase_branch),
+			),
+			"commit the resolved patch and apply the remaining ones",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn skip_pending_step(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Skip step [{}]",
+				key_config.get_hint(key_config.keys.status_skip_step),
+			),
+			"drop the current patch, commit or pick of the pending operation and run the remaining ones",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn abort_apply_mailbox(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Abort am [{}]",
+				key_config.get_hint(key_config.keys.abort_merge),
+			),
+			"go back to where applying the patches started",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn status_apply_patch(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Apply patch [{}]",
+				key_config.get_hint(key_config.keys.status_apply_patch),
+			),
+			"apply a patch or mbox file (or the clipboard) to the worktree or as commits",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn apply_patch_confirm(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Apply [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"apply the patch",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn apply_patch_target(
+		key_config: &SharedKeyConfig,
+		target: ApplyPatchTarget,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"{} [{}]",
+				match target {
+					ApplyPatchTarget::Worktree => "As commits",
+					ApplyPatchTarget::Commits => "To worktree",
+				},
+				key_config.get_hint(key_config.keys.apply_patch_target),
+			),
+			"toggle between applying to the worktree (apply) and committing the mails (am)",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn continue_rebase(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Continue rebase [{}]",
+				key_config.get_hint(key_config.keys.rebase_branch),
+			),
+			"continue ongoing rebase",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn abort_rebase(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Abort rebase [{}]",
+				key_config.get_hint(key_config.keys.abort_merge),
+			),
+			"abort ongoing rebase",
+			CMD_GROUP_GENERAL,
+		)
+	}
+
+	pub fn select_staging(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"To stage [{}]",
+				key_config.get_hint(key_config.keys.toggle_workarea),
+			),
+			"focus/select staging area",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn select_unstaged(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"To unstaged [{}]",
+				key_config.get_hint(key_config.keys.toggle_workarea),
+			),
+			"focus/select unstaged area",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn undo_commit(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Undo Commit [{}]",
+				key_config.get_hint(key_config.keys.undo_commit),
+			),
+			"undo last commit",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn commit_open(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Commit [{}]",
+				key_config.get_hint(key_config.keys.open_commit),
+			),
+			"open commit popup (available in non-empty stage)",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn commit_open_editor(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Open editor [{}]",
+				key_config
+					.get_hint(key_config.keys.open_commit_editor),
+			),
+			"open commit editor (available in commit popup)",
+			CMD_GROUP_COMMIT_POPUP,
+		)
+	}
+	pub fn commit_next_msg_from_history(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Previous Msg [{}]",
+				key_config
+					.get_hint(key_config.keys.commit_history_next),
+			),
+			"use previous commit message from history",
+			CMD_GROUP_COMMIT_POPUP,
+		)
+	}
+	pub fn commit_submit(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Do Commit [{}]",
+				key_config.get_hint(key_config.keys.commit),
+			),
+			"commit (available when commit message is non-empty)",
+			CMD_GROUP_COMMIT_POPUP,
+		)
+		.hide_help()
+	}
+	pub fn newline(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"New line [{}]",
+				key_config.get_hint(key_config.keys.newline),
+			),
+			"create line break",
+			CMD_GROUP_COMMIT_POPUP,
+		)
+		.hide_help()
+	}
+	pub fn toggle_verify(
+		key_config: &SharedKeyConfig,
+		current_verify: bool,
+	) -> CommandText {
+		let verb = if current_verify { "disable" } else { "enable" };
+		CommandText::new(
+			format!(
+				"{} hooks [{}]",
+				verb,
+				key_config.get_hint(key_config.keys.toggle_verify),
+			),
+			"toggle running on commit hooks (available in commit popup)",
+			CMD_GROUP_COMMIT_POPUP,
+		)
+	}
+
+	pub fn commit_amend(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Amend [{}]",
+				key_config.get_hint(key_config.keys.commit_amend),
+			),
+			"amend last commit (available in commit popup)",
+			CMD_GROUP_COMMIT_POPUP,
+		)
+	}
+	pub fn commit_amend_author(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Author [{}]",
+				key_config
+					.get_hint(key_config.keys.commit_amend_author),
+			),
+			"change author name and email of amended commit",
+			CMD_GROUP_COMMIT_POPUP,
+		)
+	}
+	pub fn commit_amend_date(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Author date [{}]",
+				key_config
+					.get_hint(key_config.keys.commit_amend_date),
+			),
+			"change author date of amended commit",
+			CMD_GROUP_COMMIT_POPUP,
+		)
+	}
+	pub fn commit_amend_reset_date(
+		key_config: &SharedKeyConfig,
+		reset: bool,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"{} committer date [{}]",
+				if reset { "Keep" } else { "Reset" },
+				key_config.get_hint(
+					key_config.keys.commit_amend_reset_date
+				),
+			),
+			"toggle setting committer date of amended commit to now",
+			CMD_GROUP_COMMIT_POPUP,
+		)
+	}
+	pub fn commit_signing_key(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Signing [{}]",
+				key_config
+					.get_hint(key_config.keys.commit_signing_key),
+			),
+			"choose the signing key and toggle commit signing",
+			CMD_GROUP_COMMIT_POPUP,
+		)
+	}
+	pub fn signing_key_select(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Use key [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"sign with the selected key (stored in the repo config)",
+			CMD_GROUP_COMMIT_POPUP,
+		)
+	}
+	pub fn signing_toggle(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Toggle signing [{}]",
+				key_config.get_hint(key_config.keys.signing_toggle),
+			),
+			"enable or disable commit signing (commit.gpgSign)",
+			CMD_GROUP_COMMIT_POPUP,
+		)
+	}
+	pub fn commit_signoff(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Sign-off [{}]",
+				key_config.get_hint(key_config.keys.toggle_signoff),
+			),
+			"sign-off commit (-s option)",
+			CMD_GROUP_COMMIT_POPUP,
+		)
+	}
+	pub fn edit_item(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Edit [{}]",
+				key_config.get_hint(key_config.keys.edit_file),
+			),
+			"edit the currently selected file in an external editor",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn stage_item(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Stage [{}]",
+				key_config
+					.get_hint(key_config.keys.stage_unstage_item),
+			),
+			"stage currently selected file or entire path",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn stage_all(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Stage All [{}]",
+				key_config.get_hint(key_config.keys.status_stage_all),
+			),
+			"stage all changes (in unstaged files)",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn unstage_item(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Unstage [{}]",
+				key_config
+					.get_hint(key_config.keys.stage_unstage_item),
+			),
+			"unstage currently selected file or entire path",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn unstage_all(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Unstage all [{}]",
+				key_config.get_hint(key_config.keys.status_stage_all),
+			),
+			"unstage all files (in staged files)",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn reset_item(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Reset [{}]",
+				key_config
+					.get_hint(key_config.keys.status_reset_item),
+			),
+			"revert changes in selected file or entire path",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn stage_submodule(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Stage Submodule [{}]",
+				key_config
+					.get_hint(key_config.keys.stage_unstage_item),
+			),
+			"stage the commit currently checked out in the submodule",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn discard_submodule(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Discard Submodule [{}]",
+				key_config
+					.get_hint(key_config.keys.status_reset_item),
+			),
+			"discard the changes inside of the submodule",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn ignore_item(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Ignore [{}]",
+				key_config
+					.get_hint(key_config.keys.status_ignore_file),
+			),
+			"pick a pattern to ignore the file or path with",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn ignore_pattern_add(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Add pattern [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"append the selected pattern to the ignore file",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn ignore_toggle_file(
+		key_config: &SharedKeyConfig,
+		file: IgnoreFile,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Use {} [{}]",
+				super::ignore_file_name(match file {
+					IgnoreFile::Gitignore => IgnoreFile::InfoExclude,
+					IgnoreFile::InfoExclude => IgnoreFile::Gitignore,
+				}),
+				key_config.get_hint(key_config.keys.ignore_toggle_file),
+			),
+			"switch between the shared .gitignore and the local exclude file",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn ignore_edit(
+		key_config: &SharedKeyConfig,
+		file: IgnoreFile,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Edit {} [{}]",
+				super::ignore_file_name(file),
+				key_config.get_hint(key_config.keys.ignore_edit),
+			),
+			"edit the ignore file with a preview of the matched paths",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn ignore_edit_save(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Save [{}]",
+				key_config.get_hint(key_config.keys.commit),
+			),
+			"write the ignore file",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn toggle_show_ignored(
+		key_config: &SharedKeyConfig,
+		shown: bool,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"{} ignored [{}]",
+				if shown { "Hide" } else { "Show" },
+				key_config
+					.get_hint(key_config.keys.status_show_ignored),
+			),
+			"Toggle listing of gitignored files",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn status_discarded(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Discarded [{}]",
+				key_config.get_hint(key_config.keys.status_discarded),
+			),
+			"list backups of recently discarded changes to restore them",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn status_clean(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Clean [{}]",
+				key_config.get_hint(key_config.keys.status_clean),
+			),
+			"preview and remove untracked files",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn clean_toggle_entry(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Toggle [{}]",
+				key_config
+					.get_hint(key_config.keys.clean_toggle_entry),
+			),
+			"keep or remove the selected path",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn clean_toggle_dirs(
+		key_config: &SharedKeyConfig,
+		enabled: bool,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"{} folders [{}]",
+				if enabled { "Skip" } else { "Include" },
+				key_config
+					.get_hint(key_config.keys.clean_toggle_dirs),
+			),
+			"toggle removing untracked folders (-d)",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn clean_toggle_ignored(
+		key_config: &SharedKeyConfig,
+		enabled: bool,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"{} ignored [{}]",
+				if enabled { "Skip" } else { "Include" },
+				key_config
+					.get_hint(key_config.keys.clean_toggle_ignored),
+			),
+			"toggle removing gitignored files (-x)",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn clean_confirm(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Clean [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"remove the checked paths",
+			CMD_GROUP_CHANGES,
+		)
+	}
+	pub fn discarded_restore(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Next [{}]",
-				key_config.get_hint(key_config.keys.tab_toggle)
+				"Restore [{}]",
+				key_config.get_hint(key_config.keys.enter),
 			),
-			"switch to next tab",
-			CMD_GROUP_GENERAL,
+			"write the discarded content back into the working tree",
+			CMD_GROUP_CHANGES,
 		)
 	}
-	pub fn find_file(key_config: &SharedKeyConfig) -> CommandText {
+	pub fn stage_ignored_item(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Find [{}]",
-				key_config.get_hint(key_config.keys.file_find)
+				"Force add [{}]",
+				key_config
+					.get_hint(key_config.keys.stage_unstage_item),
 			),
-			"find file in tree",
-			CMD_GROUP_GENERAL,
+			"Stage ignored file or folder anyway",
+			CMD_GROUP_CHANGES,
 		)
 	}
-	pub fn find_branch(key_config: &SharedKeyConfig) -> CommandText {
+	pub fn open_ignore_rule(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Find [{}]",
-				key_config.get_hint(key_config.keys.branch_find)
+				"Ignore rule [{}]",
+				key_config
+					.get_hint(key_config.keys.status_ignore_rule),
 			),
-			"find branch in list",
-			CMD_GROUP_GENERAL,
+			"Open the rule that ignores the selected item",
+			CMD_GROUP_CHANGES,
 		)
 	}
-	pub fn toggle_tabs_direct(
+	pub fn toggle_assume_unchanged(
 		key_config: &SharedKeyConfig,
+		set: bool,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Tab [{}{}{}{}{}]",
-				key_config.get_hint(key_config.keys.tab_status),
-				key_config.get_hint(key_config.keys.tab_log),
-				key_config.get_hint(key_config.keys.tab_files),
-				key_config.get_hint(key_config.keys.tab_stashing),
-				key_config.get_hint(key_config.keys.tab_stashes),
+				"{} assume-unchanged [{}]",
+				if set { "Clear" } else { "Set" },
+				key_config
+					.get_hint(key_config.keys.status_assume_unchanged),
 			),
-			"switch top level tabs directly",
-			CMD_GROUP_GENERAL,
+			"Toggle the assume-unchanged index bit of the selected file",
+			CMD_GROUP_CHANGES,
 		)
 	}
-	pub fn options_popup(
+	pub fn toggle_skip_worktree(
 		key_config: &SharedKeyConfig,
+		set: bool,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Options [{}]",
-				key_config.get_hint(key_config.keys.open_options),
+				"{} skip-worktree [{}]",
+				if set { "Clear" } else { "Set" },
+				key_config
+					.get_hint(key_config.keys.status_skip_worktree),
 			),
-			"open options popup",
-			CMD_GROUP_GENERAL,
+			"Toggle the skip-worktree index bit of the selected file",
+			CMD_GROUP_CHANGES,
 		)
 	}
-	pub fn help_open(key_config: &SharedKeyConfig) -> CommandText {
+	pub fn lfs_lock(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
-				"Help [{}]",
-				key_config.get_hint(key_config.keys.open_help)
+				"LFS lock [{}]",
+				key_config.get_hint(key_config.keys.lfs_lock),
 			),
-			"open this help screen",
-			CMD_GROUP_GENERAL,
+			"lock the selected LFS file on the server (git lfs lock)",
+			CMD_GROUP_CHANGES,
 		)
 	}
-	pub fn navigate_commit_message(
+	pub fn lfs_unlock(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"LFS unlock [{}]",
+				key_config.get_hint(key_config.keys.lfs_unlock),
+			),
+			"release the lock of the selected LFS file (git lfs unlock)",
+			CMD_GROUP_CHANGES,
+		)
+	}
+
+	pub fn diff_focus_left(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Nav [{}{}]",
-				key_config.get_hint(key_config.keys.move_up),
-				key_config.get_hint(key_config.keys.move_down)
+				"Back [{}]",
+				key_config.get_hint(key_config.keys.move_left),
 			),
-			"navigate commit message",
+			"view and select changed files",
 			CMD_GROUP_GENERAL,
 		)
 	}
-	pub fn navigate_tree(
+	pub fn diff_focus_right(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Nav [{}{}{}{}]",
-				key_config.get_hint(key_config.keys.move_up),
-				key_config.get_hint(key_config.keys.move_down),
+				"Diff [{}]",
 				key_config.get_hint(key_config.keys.move_right),
-				key_config.get_hint(key_config.keys.move_left)
 			),
-			"navigate tree view, collapse, expand",
+			"inspect file diff",
 			CMD_GROUP_GENERAL,
 		)
 	}
-	pub fn scroll(key_config: &SharedKeyConfig) -> CommandText {
+	pub fn quit(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
-				"Scroll [{}{}]",
-				key_config.get_hint(key_config.keys.move_up),
-				key_config.get_hint(key_config.keys.move_down)
+				"Quit [{}]",
+				key_config.get_hint(key_config.keys.exit),
 			),
-			"scroll up or down in focused view",
+			"quit gitui application",
 			CMD_GROUP_GENERAL,
 		)
 	}
-	pub fn commit_list_mark(
+	pub fn confirm_action(
 		key_config: &SharedKeyConfig,
-		marked: bool,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"{} [{}]",
-				if marked { "Unmark" } else { "Mark" },
-				key_config.get_hint(key_config.keys.log_mark_commit),
+				"Confirm [{}]",
+				key_config.get_hint(key_config.keys.enter),
 			),
-			"mark multiple commits",
+			"confirm action",
 			CMD_GROUP_GENERAL,
 		)
 	}
-	pub fn copy(key_config: &SharedKeyConfig) -> CommandText {
+	pub fn force_push_with_lease(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Copy [{}]",
-				key_config.get_hint(key_config.keys.copy),
+				"With lease [{}]",
+				key_config.get_hint(key_config.keys.force_push_lease),
 			),
-			"copy selected lines to clipboard",
-			CMD_GROUP_DIFF,
+			"force push only if the remote branch did not move since the last fetch",
+			CMD_GROUP_GENERAL,
 		)
 	}
-	pub fn copy_hash(key_config: &SharedKeyConfig) -> CommandText {
+	pub fn stashing_save(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Copy Hash [{}]",
-				key_config.get_hint(key_config.keys.copy),
+				"Save [{}]",
+				key_config.get_hint(key_config.keys.stashing_save),
 			),
-			"copy selected commit hash to clipboard",
-			CMD_GROUP_LOG,
+			"opens stash name input popup",
+			CMD_GROUP_STASHING,
 		)
 	}
-	pub fn copy_path(key_config: &SharedKeyConfig) -> CommandText {
+	pub fn stashing_mark_file(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Copy Path [{}]",
-				key_config.get_hint(key_config.keys.copy),
+				"Mark [{}]",
+				key_config
+					.get_hint(key_config.keys.stashing_mark_file),
 			),
-			"copy selected file path to clipboard",
-			CMD_GROUP_LOG,
+			"mark file or folder to only stash the marked files",
+			CMD_GROUP_STASHING,
 		)
 	}
-	pub fn push_tags(key_config: &SharedKeyConfig) -> CommandText {
+	pub fn stashing_toggle_indexed(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Push Tags [{}]",
-				key_config.get_hint(key_config.keys.push),
+				"Toggle Staged [{}]",
+				key_config
+					.get_hint(key_config.keys.stashing_toggle_index),
 			),
-			"push tags to remote",
-			CMD_GROUP_LOG,
+			"toggle including staged files into stash",
+			CMD_GROUP_STASHING,
 		)
 	}
-	pub fn toggle_option(
+	pub fn stashing_toggle_untracked(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Toggle Option [{}]",
-				key_config.get_hint(key_config.keys.log_mark_commit),
+				"Toggle Untracked [{}]",
+				key_config.get_hint(
+					key_config.keys.stashing_toggle_untracked
+				),
 			),
-			"toggle search option selected",
-			CMD_GROUP_LOG,
+			"toggle including untracked files into stash",
+			CMD_GROUP_STASHING,
 		)
 	}
-	pub fn show_tag_annotation(
+	pub fn stashing_toggle_ignored(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Annotation [{}]",
-				key_config.get_hint(key_config.keys.move_right),
+				"Toggle Ignored [{}]",
+				key_config.get_hint(
+					key_config.keys.stashing_toggle_ignored
+				),
 			),
-			"show tag annotation",
-			CMD_GROUP_LOG,
+			"toggle including ignored files into stash",
+			CMD_GROUP_STASHING,
 		)
 	}
-	pub fn diff_hunk_next(
+	pub fn stash_msg_toggle_untracked(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Next hunk [{}]",
-				key_config.get_hint(key_config.keys.diff_hunk_next),
+				"Untracked [{}]",
+				key_config.get_hint(
+					key_config.keys.stash_msg_toggle_untracked
+				),
 			),
-			"move cursor to next hunk",
-			CMD_GROUP_DIFF,
+			"toggle including untracked files into stash (-u)",
+			CMD_GROUP_STASHING,
 		)
 	}
-	pub fn diff_hunk_prev(
+	pub fn stash_msg_toggle_ignored(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Prev hunk [{}]",
-				key_config.get_hint(key_config.keys.diff_hunk_prev),
+				"Ignored [{}]",
+				key_config.get_hint(
+					key_config.keys.stash_msg_toggle_ignored
+				),
 			),
-			"move cursor to prev hunk",
-			CMD_GROUP_DIFF,
+			"toggle including ignored files into stash (-a)",
+			CMD_GROUP_STASHING,
 		)
 	}
-	pub fn diff_home_end(
+	pub fn stashing_confirm_msg(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Jump up/down [{},{},{},{}]",
-				key_config.get_hint(key_config.keys.home),
-				key_config.get_hint(key_config.keys.end),
-				key_config.get_hint(key_config.keys.move_up),
-				key_config.get_hint(key_config.keys.move_down)
+				"Stash [{}]",
+				key_config.get_hint(key_config.keys.enter),
 			),
-			"scroll to top or bottom of diff",
-			CMD_GROUP_DIFF,
+			"save files to stash",
+			CMD_GROUP_STASHING,
 		)
 	}
-	pub fn diff_hunk_add(
+	pub fn stashlist_apply(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Add hunk [{}]",
+				"Apply [{}]",
+				key_config.get_hint(key_config.keys.stash_apply),
+			),
+			"apply selected stash",
+			CMD_GROUP_STASHES,
+		)
+	}
+	pub fn stashlist_drop(
+		key_config: &SharedKeyConfig,
+		marked: usize,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Drop{} [{}]",
+				if marked == 0 {
+					String::default()
+				} else {
+					format!(" {marked}")
+				},
+				key_config.get_hint(key_config.keys.stash_drop),
+			),
+			"drop selected stash",
+			CMD_GROUP_STASHES,
+		)
+	}
+	pub fn stashlist_pop(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Pop [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"pop selected stash",
+			CMD_GROUP_STASHES,
+		)
+	}
+	pub fn stashlist_worktree_diff(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Worktree diff [{}]",
 				key_config
-					.get_hint(key_config.keys.stage_unstage_item),
+					.get_hint(key_config.keys.stash_worktree_diff),
 			),
-			"adds selected hunk to stage",
-			CMD_GROUP_DIFF,
+			"diff selected stash against the working tree and apply parts of it",
+			CMD_GROUP_STASHES,
 		)
 	}
-	pub fn diff_hunk_revert(
+	pub fn stash_worktree_apply_file(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Apply file [{}]",
+				key_config.get_hint(key_config.keys.stash_apply),
+			),
+			"apply the stashed version of the selected file",
+			CMD_GROUP_STASHES,
+		)
+	}
+	pub fn stash_worktree_apply_hunks(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Apply hunks [{}]",
+				key_config.get_hint(key_config.keys.diff_apply_hunks),
+			),
+			"apply the selected hunks of the stash to the working tree",
+			CMD_GROUP_STASHES,
+		)
+	}
+	pub fn stashlist_inspect(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Reset hunk [{}]",
-				key_config
-					.get_hint(key_config.keys.status_reset_item),
+				"Inspect [{}]",
+				key_config.get_hint(key_config.keys.stash_open),
 			),
-			"reverts selected hunk",
-			CMD_GROUP_DIFF,
+			"open stash commit details (allows to diff files)",
+			CMD_GROUP_STASHES,
 		)
 	}
-	pub fn diff_lines_revert(
+	pub fn log_details_toggle(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Reset lines [{}]",
-				key_config.get_hint(key_config.keys.diff_reset_lines),
+				"Details [{}]",
+				key_config.get_hint(key_config.keys.enter),
 			),
-			"resets selected lines",
-			CMD_GROUP_DIFF,
+			"open details of selected commit",
+			CMD_GROUP_LOG,
 		)
 	}
-	pub fn diff_lines_stage(
+
+	pub fn commit_details_open(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Stage lines [{}]",
-				key_config.get_hint(key_config.keys.diff_stage_lines),
+				"Inspect [{}]",
+				key_config.get_hint(key_config.keys.move_right),
 			),
-			"stage selected lines",
-			CMD_GROUP_DIFF,
+			"inspect selected commit in detail",
+			CMD_GROUP_GENERAL,
 		)
 	}
-	pub fn diff_lines_unstage(
-		key_config: &SharedKeyConfig,
-	) -> CommandText {
+
+	pub fn blame_file(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
-				"Unstage lines [{}]",
-				key_config.get_hint(key_config.keys.diff_stage_lines),
+				"Blame [{}]",
+				key_config.get_hint(key_config.keys.blame),
 			),
-			"unstage selected lines",
-			CMD_GROUP_DIFF,
+			"open blame view of selected file",
+			CMD_GROUP_GENERAL,
 		)
 	}
-	pub fn diff_hunk_remove(
-		key_config: &SharedKeyConfig,
-	) -> CommandText {
+	pub fn restore_file(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
-				"Remove hunk [{}]",
-				key_config
-					.get_hint(key_config.keys.stage_unstage_item),
+				"Restore [{}]",
+				key_config.get_hint(key_config.keys.restore_file),
 			),
-			"removes selected hunk from stage",
-			CMD_GROUP_DIFF,
+			"restore selected file to working tree from this commit",
+			CMD_GROUP_GENERAL,
 		)
 	}
-	pub fn close_fuzzy_finder(
+	pub fn restore_file_confirm(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Close [{}{}]",
-				key_config.get_hint(key_config.keys.exit_popup),
+				"Restore [{}]",
 				key_config.get_hint(key_config.keys.enter),
 			),
-			"close fuzzy finder",
+			"restore file",
 			CMD_GROUP_GENERAL,
 		)
 	}
-	pub fn close_popup(key_config: &SharedKeyConfig) -> CommandText {
+	pub fn restore_file_toggle_stage(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Close [{}]",
-				key_config.get_hint(key_config.keys.exit_popup),
+				"Toggle stage [{}]",
+				key_config
+					.get_hint(key_config.keys.restore_toggle_stage),
 			),
-			"close overlay (e.g commit, help)",
+			"also stage the restored file",
 			CMD_GROUP_GENERAL,
 		)
 	}
-	pub fn scroll_popup(key_config: &SharedKeyConfig) -> CommandText {
+	pub fn open_file_history(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Scroll [{}{}]",
-				key_config.get_hint(key_config.keys.popup_down),
-				key_config.get_hint(key_config.keys.popup_up),
+				"History [{}]",
+				key_config.get_hint(key_config.keys.file_history),
 			),
-			"scroll up or down in popup",
-			CMD_GROUP_GENERAL,
+			"open history of selected file",
+			CMD_GROUP_LOG,
 		)
 	}
-	pub fn close_msg(key_config: &SharedKeyConfig) -> CommandText {
+	pub fn blame_mark_lines(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Close [{}]",
-				key_config.get_hint(key_config.keys.enter),
+				"Mark lines [{}]",
+				key_config.get_hint(key_config.keys.blame_mark_lines),
 			),
-			"close msg popup (e.g msg)",
-			CMD_GROUP_GENERAL,
+			"mark a range of lines to show their history",
+			CMD_GROUP_LOG,
 		)
-		.hide_help()
 	}
-	pub fn validate_msg(key_config: &SharedKeyConfig) -> CommandText {
+	pub fn blame_parent(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
-				"Validate [{}]",
-				key_config.get_hint(key_config.keys.enter),
+				"Blame Parent [{}]",
+				key_config.get_hint(key_config.keys.blame_parent),
 			),
-			"validate msg",
-			CMD_GROUP_GENERAL,
+			"blame the file at the parent of the commit that changed the selected line",
+			CMD_GROUP_LOG,
 		)
-		.hide_help()
 	}
-
-	pub fn abort_merge(key_config: &SharedKeyConfig) -> CommandText {
+	pub fn blame_back(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
-				"Abort merge [{}]",
-				key_config.get_hint(key_config.keys.abort_merge),
+				"Back [{}]",
+				key_config.get_hint(key_config.keys.exit_popup),
 			),
-			"abort ongoing merge",
+			"go back to the previous blame",
 			CMD_GROUP_GENERAL,
 		)
 	}
-
-	pub fn abort_revert(key_config: &SharedKeyConfig) -> CommandText {
+	pub fn blame_ignore_revs(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Abort revert [{}]",
-				key_config.get_hint(key_config.keys.abort_merge),
+				"Ignore Revs File [{}]",
+				key_config.get_hint(key_config.keys.blame_ignore_revs),
 			),
-			"abort ongoing revert",
-			CMD_GROUP_GENERAL,
+			"set the file listing commits to skip when blaming (blame.ignoreRevsFile)",
+			CMD_GROUP_LOG,
 		)
 	}
-
-	pub fn view_submodules(
+	pub fn blame_ignore_revs_confirm(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Submodules [{}]",
-				key_config.get_hint(key_config.keys.view_submodules),
+				"Set Ignore Revs File [{}]",
+				key_config.get_hint(key_config.keys.enter),
 			),
-			"open submodule view",
+			"use the file to skip its commits when blaming",
 			CMD_GROUP_GENERAL,
 		)
 	}
-
-	pub fn view_remotes(key_config: &SharedKeyConfig) -> CommandText {
+	pub fn open_line_history(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Remotes [{}]",
-				key_config.get_hint(key_config.keys.view_remotes)
+				"Lines History [{}]",
+				key_config.get_hint(key_config.keys.file_history),
 			),
-			"open remotes view",
-			CMD_GROUP_GENERAL,
+			"open history of the marked lines",
+			CMD_GROUP_LOG,
 		)
 	}
-
-	pub fn update_remote_name(
+	pub fn file_revision_step(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Edit name [{}]",
-				key_config
-					.get_hint(key_config.keys.update_remote_name)
+				"Older/Newer [{}{}]",
+				key_config.get_hint(key_config.keys.file_revision_prev),
+				key_config.get_hint(key_config.keys.file_revision_next),
 			),
-			"updates a remote name",
-			CMD_GROUP_GENERAL,
+			"show the file as of the previous/next commit changing it",
+			CMD_GROUP_LOG,
 		)
 	}
-
-	pub fn update_remote_url(
+	pub fn open_path_log(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Edit url [{}]",
-				key_config
-					.get_hint(key_config.keys.update_remote_url)
+				"Log [{}]",
+				key_config.get_hint(key_config.keys.file_log),
 			),
-			"updates a remote url",
-			CMD_GROUP_GENERAL,
+			"open commit log limited to selected file or folder",
+			CMD_GROUP_LOG,
 		)
 	}
-
-	pub fn create_remote(
+	pub fn log_tag_commit(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Add [{}]",
-				key_config.get_hint(key_config.keys.add_remote)
+				"Tag [{}]",
+				key_config.get_hint(key_config.keys.log_tag_commit),
 			),
-			"creates a new remote",
-			CMD_GROUP_GENERAL,
+			"tag commit",
+			CMD_GROUP_LOG,
 		)
 	}
-
-	pub fn delete_remote_popup(
+	pub fn log_checkout_commit(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Remove [{}]",
-				key_config.get_hint(key_config.keys.delete_remote),
+				"Checkout [{}]",
+				key_config
+					.get_hint(key_config.keys.log_checkout_commit),
 			),
-			"remove a remote",
-			CMD_GROUP_BRANCHES,
+			"checkout commit",
+			CMD_GROUP_LOG,
 		)
 	}
-
-	pub fn remote_confirm_name_msg(
+	pub fn inspect_file_tree(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Confirm name [{}]",
-				key_config.get_hint(key_config.keys.enter),
+				"Files [{}]",
+				key_config.get_hint(key_config.keys.open_file_tree),
 			),
-			"confirm remote name",
-			CMD_GROUP_BRANCHES,
+			"inspect file tree at specific revision",
+			CMD_GROUP_LOG,
 		)
-		.hide_help()
 	}
-
-	pub fn remote_confirm_url_msg(
+	pub fn diff_merge_mode(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Confirm url [{}]",
-				key_config.get_hint(key_config.keys.enter),
+				"Merge diff [{}]",
+				key_config.get_hint(key_config.keys.diff_merge_mode),
 			),
-			"confirm remote url",
-			CMD_GROUP_BRANCHES,
+			"switch between diffing against each parent and the combined diff",
+			CMD_GROUP_LOG,
 		)
-		.hide_help()
 	}
-
-	pub fn open_submodule(
+	pub fn revert_commit(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Open [{}]",
-				key_config.get_hint(key_config.keys.enter),
+				"Revert [{}]",
+				key_config
+					.get_hint(key_config.keys.status_reset_item),
 			),
-			"open submodule",
-			CMD_GROUP_GENERAL,
+			"revert commit",
+			CMD_GROUP_LOG,
 		)
 	}
-
-	pub fn open_submodule_parent(
+	pub fn log_reset_commit(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Open Parent [{}]",
-				key_config
-					.get_hint(key_config.keys.view_submodule_parent),
+				"Reset [{}]",
+				key_config.get_hint(key_config.keys.log_reset_commit),
 			),
-			"open submodule parent repo",
-			CMD_GROUP_GENERAL,
+			"reset to commit",
+			CMD_GROUP_LOG,
 		)
 	}
-
-	pub fn update_submodule(
+	pub fn log_reword_commit(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Update [{}]",
-				key_config.get_hint(key_config.keys.update_submodule),
+				"Reword [{}]",
+				key_config
+					.get_hint(key_config.keys.log_reword_commit),
 			),
-			"update submodule",
-			CMD_GROUP_GENERAL,
+			"reword commit message",
+			CMD_GROUP_LOG,
 		)
 	}
-
-	pub fn continue_rebase(
+	pub fn log_move_commit(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Continue rebase [{}]",
-				key_config.get_hint(key_config.keys.rebase_branch),
+				"Move [{}{}]",
+				key_config
+					.get_hint(key_config.keys.log_move_commit_up),
+				key_config
+					.get_hint(key_config.keys.log_move_commit_down),
 			),
-			"continue ongoing rebase",
-			CMD_GROUP_GENERAL,
+			"move unpushed commit up or down in the history",
+			CMD_GROUP_LOG,
 		)
 	}
-
-	pub fn abort_rebase(key_config: &SharedKeyConfig) -> CommandText {
+	pub fn log_rebase_interactive(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Abort rebase [{}]",
-				key_config.get_hint(key_config.keys.abort_merge),
+				"Rebase -i [{}]",
+				key_config
+					.get_hint(key_config.keys.log_rebase_interactive),
 			),
-			"abort ongoing rebase",
-			CMD_GROUP_GENERAL,
+			"interactively rebase the selected commit and its descendants",
+			CMD_GROUP_LOG,
 		)
 	}
-
-	pub fn select_staging(
+	pub fn log_fixup_commit(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"To stage [{}]",
-				key_config.get_hint(key_config.keys.toggle_workarea),
+				"Fixup/Squash [{}{}]",
+				key_config.get_hint(key_config.keys.log_fixup_commit),
+				key_config.get_hint(key_config.keys.log_squash_commit),
 			),
-			"focus/select staging area",
-			CMD_GROUP_GENERAL,
+			"commit staged changes as fixup!/squash! of the selected commit",
+			CMD_GROUP_LOG,
 		)
 	}
-	pub fn select_unstaged(
+	pub fn log_autosquash(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"To unstaged [{}]",
-				key_config.get_hint(key_config.keys.toggle_workarea),
+				"Autosquash [{}]",
+				key_config.get_hint(key_config.keys.log_autosquash),
 			),
-			"focus/select unstaged area",
-			CMD_GROUP_GENERAL,
+			"rebase the selected commit and its descendants folding in fixup!/squash! commits",
+			CMD_GROUP_LOG,
 		)
 	}
-	pub fn undo_commit(key_config: &SharedKeyConfig) -> CommandText {
+	pub fn rebase_interactive_action(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		let keys = &key_config.keys;
 		CommandText::new(
 			format!(
-				"Undo Commit [{}]",
-				key_config.get_hint(key_config.keys.undo_commit),
+				"Pick/Reword/Edit [{}{}{}]",
+				key_config.get_hint(keys.rebase_pick),
+				key_config.get_hint(keys.rebase_reword),
+				key_config.get_hint(keys.rebase_edit),
 			),
-			"undo last commit",
-			CMD_GROUP_GENERAL,
+			"set the action of the selected commit",
+			CMD_GROUP_LOG,
 		)
 	}
-	pub fn commit_open(key_config: &SharedKeyConfig) -> CommandText {
+	pub fn rebase_interactive_meld(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		let keys = &key_config.keys;
 		CommandText::new(
 			format!(
-				"Commit [{}]",
-				key_config.get_hint(key_config.keys.open_commit),
+				"Squash/Fixup/Drop [{}{}{}]",
+				key_config.get_hint(keys.rebase_squash),
+				key_config.get_hint(keys.rebase_fixup),
+				key_config.get_hint(keys.rebase_drop),
 			),
-			"open commit popup (available in non-empty stage)",
-			CMD_GROUP_GENERAL,
+			"meld the selected commit into the previous one or drop it",
+			CMD_GROUP_LOG,
 		)
 	}
-	pub fn commit_open_editor(
+	pub fn rebase_interactive_move(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Open editor [{}]",
+				"Move [{}{}]",
 				key_config
-					.get_hint(key_config.keys.open_commit_editor),
+					.get_hint(key_config.keys.log_move_commit_up),
+				key_config
+					.get_hint(key_config.keys.log_move_commit_down),
 			),
-			"open commit editor (available in commit popup)",
-			CMD_GROUP_COMMIT_POPUP,
+			"reorder the selected commit",
+			CMD_GROUP_LOG,
 		)
 	}
-	pub fn commit_next_msg_from_history(
+	pub fn rebase_interactive_start(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Previous Msg [{}]",
-				key_config
-					.get_hint(key_config.keys.commit_history_next),
+				"Rebase [{}]",
+				key_config.get_hint(key_config.keys.enter),
 			),
-			"use previous commit message from history",
-			CMD_GROUP_COMMIT_POPUP,
+			"start the rebase",
+			CMD_GROUP_LOG,
 		)
 	}
-	pub fn commit_submit(
+	pub fn rebase_reword_confirm(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Do Commit [{}]",
+				"Set message [{}]",
 				key_config.get_hint(key_config.keys.commit),
 			),
-			"commit (available when commit message is non-empty)",
-			CMD_GROUP_COMMIT_POPUP,
+			"use the new message for the commit",
+			CMD_GROUP_LOG,
 		)
-		.hide_help()
 	}
-	pub fn newline(key_config: &SharedKeyConfig) -> CommandText {
+	pub fn log_reflog(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
-				"New line [{}]",
-				key_config.get_hint(key_config.keys.newline),
+				"Reflog [{}]",
+				key_config.get_hint(key_config.keys.log_reflog),
 			),
-			"create line break",
-			CMD_GROUP_COMMIT_POPUP,
+			"show the reflog to recover lost commits",
+			CMD_GROUP_LOG,
 		)
-		.hide_help()
 	}
-	pub fn toggle_verify(
+	pub fn reflog_switch_ref(
 		key_config: &SharedKeyConfig,
-		current_verify: bool,
 	) -> CommandText {
-		let verb = if current_verify { "disable" } else { "enable" };
 		CommandText::new(
 			format!(
-				"{} hooks [{}]",
-				verb,
-				key_config.get_hint(key_config.keys.toggle_verify),
+				"Ref [{}{}]",
+				key_config.get_hint(key_config.keys.move_left),
+				key_config.get_hint(key_config.keys.move_right),
 			),
-			"toggle running on commit hooks (available in commit popup)",
-			CMD_GROUP_COMMIT_POPUP,
+			"switch between the reflogs of HEAD and the branches",
+			CMD_GROUP_LOG,
 		)
 	}
-
-	pub fn commit_amend(key_config: &SharedKeyConfig) -> CommandText {
+	pub fn reflog_inspect(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Amend [{}]",
-				key_config.get_hint(key_config.keys.commit_amend),
+				"Inspect [{}]",
+				key_config.get_hint(key_config.keys.enter),
 			),
-			"amend last commit (available in commit popup)",
-			CMD_GROUP_COMMIT_POPUP,
+			"inspect the commit of the entry",
+			CMD_GROUP_LOG,
 		)
 	}
-	pub fn commit_signoff(
+	pub fn reflog_checkout(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Sign-off [{}]",
-				key_config.get_hint(key_config.keys.toggle_signoff),
+				"Checkout [{}]",
+				key_config
+					.get_hint(key_config.keys.log_checkout_commit),
 			),
-			"sign-off commit (-s option)",
-			CMD_GROUP_COMMIT_POPUP,
+			"checkout the commit of the entry (detached HEAD)",
+			CMD_GROUP_LOG,
 		)
 	}
-	pub fn edit_item(key_config: &SharedKeyConfig) -> CommandText {
+	pub fn reflog_branch(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Edit [{}]",
-				key_config.get_hint(key_config.keys.edit_file),
+				"Branch [{}]",
+				key_config.get_hint(key_config.keys.create_branch),
 			),
-			"edit the currently selected file in an external editor",
-			CMD_GROUP_CHANGES,
+			"create a branch at the commit of the entry",
+			CMD_GROUP_LOG,
 		)
 	}
-	pub fn stage_item(key_config: &SharedKeyConfig) -> CommandText {
+	pub fn reflog_reset(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
-				"Stage [{}]",
-				key_config
-					.get_hint(key_config.keys.stage_unstage_item),
+				"Reset [{}]",
+				key_config.get_hint(key_config.keys.log_reset_commit),
 			),
-			"stage currently selected file or entire path",
-			CMD_GROUP_CHANGES,
+			"reset the current branch to the commit of the entry",
+			CMD_GROUP_LOG,
 		)
 	}
-	pub fn stage_all(key_config: &SharedKeyConfig) -> CommandText {
+	pub fn log_bisect(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
-				"Stage All [{}]",
-				key_config.get_hint(key_config.keys.status_stage_all),
+				"Bisect [{}]",
+				key_config.get_hint(key_config.keys.log_bisect),
 			),
-			"stage all changes (in unstaged files)",
-			CMD_GROUP_CHANGES,
+			"mark the selected commit good or bad to find the first bad commit",
+			CMD_GROUP_LOG,
 		)
 	}
-	pub fn unstage_item(key_config: &SharedKeyConfig) -> CommandText {
+	pub fn bisect_mark(key_config: &SharedKeyConfig) -> CommandText {
+		let keys = &key_config.keys;
 		CommandText::new(
 			format!(
-				"Unstage [{}]",
-				key_config
-					.get_hint(key_config.keys.stage_unstage_item),
+				"Good/Bad/Skip [{}{}{}]",
+				key_config.get_hint(keys.bisect_good),
+				key_config.get_hint(keys.bisect_bad),
+				key_config.get_hint(keys.bisect_skip),
 			),
-			"unstage currently selected file or entire path",
-			CMD_GROUP_CHANGES,
+			"mark the commit and check out the next one to test",
+			CMD_GROUP_LOG,
 		)
 	}
-	pub fn unstage_all(key_config: &SharedKeyConfig) -> CommandText {
+	pub fn status_resolve_conflict(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Unstage all [{}]",
-				key_config.get_hint(key_config.keys.status_stage_all),
+				"Resolve [{}]",
+				key_config.get_hint(
+					key_config.keys.status_resolve_conflict
+				),
 			),
-			"unstage all files (in staged files)",
+			"resolve the conflicts of the file region by region",
 			CMD_GROUP_CHANGES,
 		)
 	}
-	pub fn reset_item(key_config: &SharedKeyConfig) -> CommandText {
+	pub fn open_diff_tool(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Reset [{}]",
-				key_config
-					.get_hint(key_config.keys.status_reset_item),
+				"Difftool [{}]",
+				key_config.get_hint(key_config.keys.open_diff_tool),
 			),
-			"revert changes in selected file or entire path",
-			CMD_GROUP_CHANGES,
+			"open the changes of the file in the configured diff.tool",
+			CMD_GROUP_DIFF,
 		)
 	}
-	pub fn ignore_item(key_config: &SharedKeyConfig) -> CommandText {
+	pub fn open_merge_tool(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Ignore [{}]",
-				key_config
-					.get_hint(key_config.keys.status_ignore_file),
+				"Mergetool [{}]",
+				key_config.get_hint(key_config.keys.open_merge_tool),
 			),
-			"Add file or path to .gitignore",
+			"resolve the conflicts of the file in the configured merge.tool",
 			CMD_GROUP_CHANGES,
 		)
 	}
-
-	pub fn diff_focus_left(
+	pub fn conflict_take(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
+		let keys = &key_config.keys;
 		CommandText::new(
 			format!(
-				"Back [{}]",
-				key_config.get_hint(key_config.keys.move_left),
+				"Ours/Theirs/Both/Base [{}{}{}{}]",
+				key_config.get_hint(keys.conflict_take_ours),
+				key_config.get_hint(keys.conflict_take_theirs),
+				key_config.get_hint(keys.conflict_take_both),
+				key_config.get_hint(keys.conflict_take_base),
 			),
-			"view and select changed files",
-			CMD_GROUP_GENERAL,
+			"resolve the selected region",
+			CMD_GROUP_CHANGES,
 		)
 	}
-	pub fn diff_focus_right(
+	pub fn conflict_edit(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Diff [{}]",
-				key_config.get_hint(key_config.keys.move_right),
+				"Edit [{}]",
+				key_config.get_hint(key_config.keys.conflict_edit),
 			),
-			"inspect file diff",
-			CMD_GROUP_GENERAL,
+			"write the result of the selected region by hand",
+			CMD_GROUP_CHANGES,
 		)
 	}
-	pub fn quit(key_config: &SharedKeyConfig) -> CommandText {
+	pub fn conflict_edit_confirm(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Quit [{}]",
-				key_config.get_hint(key_config.keys.exit),
+				"Set result [{}]",
+				key_config.get_hint(key_config.keys.commit),
 			),
-			"quit gitui application",
-			CMD_GROUP_GENERAL,
+			"use the text as result of the region",
+			CMD_GROUP_CHANGES,
 		)
 	}
-	pub fn confirm_action(
+	pub fn conflict_write(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Confirm [{}]",
+				"Write & stage [{}]",
 				key_config.get_hint(key_config.keys.enter),
 			),
-			"confirm action",
-			CMD_GROUP_GENERAL,
+			"write the merged file and stage it",
+			CMD_GROUP_CHANGES,
 		)
 	}
-	pub fn stashing_save(
+	pub fn bisect_reset(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Reset [{}]",
+				key_config.get_hint(key_config.keys.bisect_reset),
+			),
+			"end bisecting and check out the original branch",
+			CMD_GROUP_LOG,
+		)
+	}
+	pub fn log_find_commit(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Save [{}]",
-				key_config.get_hint(key_config.keys.stashing_save),
+				"Find [{}]",
+				key_config.get_hint(key_config.keys.file_find),
 			),
-			"opens stash name input popup",
-			CMD_GROUP_STASHING,
+			"start commit search",
+			CMD_GROUP_LOG,
 		)
 	}
-	pub fn stashing_toggle_indexed(
+	pub fn log_first_parent(
 		key_config: &SharedKeyConfig,
+		first_parent: bool,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Toggle Staged [{}]",
-				key_config
-					.get_hint(key_config.keys.stashing_toggle_index),
+				"{} [{}]",
+				if first_parent {
+					"All parents"
+				} else {
+					"First parent"
+				},
+				key_config.get_hint(key_config.keys.log_first_parent),
 			),
-			"toggle including staged files into stash",
-			CMD_GROUP_STASHING,
+			"toggle following only the first parent of merges",
+			CMD_GROUP_LOG,
 		)
 	}
-	pub fn stashing_toggle_untracked(
+	pub fn log_clear_pathspec(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Toggle Untracked [{}]",
-				key_config.get_hint(
-					key_config.keys.stashing_toggle_untracked
-				),
+				"All Paths [{}]",
+				key_config.get_hint(key_config.keys.exit_popup),
 			),
-			"toggle including untracked files into stash",
-			CMD_GROUP_STASHING,
+			"stop limiting the log to a path",
+			CMD_GROUP_LOG,
 		)
 	}
-	pub fn stashing_confirm_msg(
+	pub fn log_scope(
 		key_config: &SharedKeyConfig,
+		scope: &LogScope,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Stash [{}]",
-				key_config.get_hint(key_config.keys.enter),
+				"Scope: {} [{}]",
+				super::log_scope(scope),
+				key_config.get_hint(key_config.keys.log_scope),
 			),
-			"save files to stash",
-			CMD_GROUP_STASHING,
+			"switch log between current branch, local branches and all refs",
+			CMD_GROUP_LOG,
 		)
 	}
-	pub fn stashlist_apply(
+	pub fn log_edit_note(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Apply [{}]",
-				key_config.get_hint(key_config.keys.stash_apply),
+				"Note [{}]",
+				key_config.get_hint(key_config.keys.log_edit_note),
 			),
-			"apply selected stash",
-			CMD_GROUP_STASHES,
+			"add, edit or remove the note of the commit",
+			CMD_GROUP_LOG,
 		)
 	}
-	pub fn stashlist_drop(
+	pub fn log_push_notes(
 		key_config: &SharedKeyConfig,
-		marked: usize,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Drop{} [{}]",
-				if marked == 0 {
-					String::default()
-				} else {
-					format!(" {marked}")
-				},
-				key_config.get_hint(key_config.keys.stash_drop),
+				"Push Notes [{}]",
+				key_config.get_hint(key_config.keys.log_push_notes),
 			),
-			"drop selected stash",
-			CMD_GROUP_STASHES,
+			"push commit notes to the default remote",
+			CMD_GROUP_LOG,
 		)
 	}
-	pub fn stashlist_pop(
+	pub fn log_fetch_notes(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Pop [{}]",
-				key_config.get_hint(key_config.keys.enter),
+				"Fetch Notes [{}]",
+				key_config.get_hint(key_config.keys.log_fetch_notes),
 			),
-			"pop selected stash",
-			CMD_GROUP_STASHES,
+			"fetch commit notes from the default remote",
+			CMD_GROUP_LOG,
 		)
 	}
-	pub fn stashlist_inspect(
+	pub fn log_format_patch(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Inspect [{}]",
-				key_config.get_hint(key_config.keys.stash_open),
+				"Export Patches [{}]",
+				key_config.get_hint(key_config.keys.log_format_patch),
 			),
-			"open stash commit details (allows to diff files)",
-			CMD_GROUP_STASHES,
+			"write the marked (or selected) commits as patch files (format-patch)",
+			CMD_GROUP_LOG,
 		)
 	}
-	pub fn log_details_toggle(
+	pub fn format_patch_confirm(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Details [{}]",
+				"Export [{}]",
 				key_config.get_hint(key_config.keys.enter),
 			),
-			"open details of selected commit",
-			CMD_GROUP_LOG,
+			"write the patch files",
+			CMD_GROUP_GENERAL,
 		)
 	}
-
-	pub fn commit_details_open(
+	pub fn format_patch_numbered(
 		key_config: &SharedKeyConfig,
+		numbered: bool,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Inspect [{}]",
-				key_config.get_hint(key_config.keys.move_right),
+				"{} numbering [{}]",
+				if numbered { "Auto" } else { "Always" },
+				key_config
+					.get_hint(key_config.keys.format_patch_numbered),
 			),
-			"inspect selected commit in detail",
+			"number a single patch too ([PATCH 1/1])",
 			CMD_GROUP_GENERAL,
 		)
 	}
-
-	pub fn blame_file(key_config: &SharedKeyConfig) -> CommandText {
+	pub fn format_patch_cover_letter(
+		key_config: &SharedKeyConfig,
+		cover_letter: bool,
+	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Blame [{}]",
-				key_config.get_hint(key_config.keys.blame),
+				"{} cover letter [{}]",
+				if cover_letter { "No" } else { "Add" },
+				key_config.get_hint(
+					key_config.keys.format_patch_cover_letter
+				),
 			),
-			"open blame view of selected file",
+			"write a 0000-cover-letter.patch describing the series",
 			CMD_GROUP_GENERAL,
 		)
 	}
-	pub fn open_file_history(
+	pub fn commit_note_confirm(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"History [{}]",
-				key_config.get_hint(key_config.keys.file_history),
+				"Save Note [{}]",
+				key_config.get_hint(key_config.keys.commit),
 			),
-			"open history of selected file",
-			CMD_GROUP_LOG,
+			"save the note, an empty note removes it",
+			CMD_GROUP_GENERAL,
 		)
 	}
-	pub fn log_tag_commit(
+	pub fn log_select_refs(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
-			format!(
-				"Tag [{}]",
-				key_config.get_hint(key_config.keys.log_tag_commit),
+			format!(
+				"Select Refs [{}]",
+				key_config.get_hint(key_config.keys.log_select_refs),
 			),
-			"tag commit",
+			"pick branches and tags to show the history of together",
 			CMD_GROUP_LOG,
 		)
 	}
-	pub fn log_checkout_commit(
+	pub fn log_refs_toggle(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Checkout [{}]",
-				key_config
-					.get_hint(key_config.keys.log_checkout_commit),
+				"Toggle [{}]",
+				key_config.get_hint(key_config.keys.log_mark_commit),
 			),
-			"checkout commit",
+			"include or exclude the selected ref",
 			CMD_GROUP_LOG,
 		)
 	}
-	pub fn inspect_file_tree(
+	pub fn revert_merge_confirm(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Files [{}]",
-				key_config.get_hint(key_config.keys.open_file_tree),
+				"Revert [{}]",
+				key_config.get_hint(key_config.keys.enter),
 			),
-			"inspect file tree at specific revision",
+			"revert the merge keeping the selected parent as mainline (-m)",
 			CMD_GROUP_LOG,
 		)
 	}
-	pub fn revert_commit(
+	pub fn log_refs_confirm(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Revert [{}]",
-				key_config
-					.get_hint(key_config.keys.status_reset_item),
+				"Show [{}]",
+				key_config.get_hint(key_config.keys.enter),
 			),
-			"revert commit",
+			"show the history of the checked refs",
 			CMD_GROUP_LOG,
 		)
 	}
-	pub fn log_reset_commit(
+	pub fn remote_progress_cancel(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Reset [{}]",
-				key_config.get_hint(key_config.keys.log_reset_commit),
+				"Cancel [{}]",
+				key_config.get_hint(key_config.keys.exit_popup),
 			),
-			"reset to commit",
-			CMD_GROUP_LOG,
+			"abort the transfer",
+			CMD_GROUP_GENERAL,
 		)
 	}
-	pub fn log_reword_commit(
+	pub fn hook_progress_cancel(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Reword [{}]",
-				key_config
-					.get_hint(key_config.keys.log_reword_commit),
+				"Cancel [{}]",
+				key_config.get_hint(key_config.keys.exit_popup),
 			),
-			"reword commit message",
-			CMD_GROUP_LOG,
+			"kill the running hook and abort the commit",
+			CMD_GROUP_COMMIT_POPUP,
 		)
 	}
-	pub fn log_find_commit(
+	pub fn pre_commit_restage(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
 		CommandText::new(
 			format!(
-				"Find [{}]",
-				key_config.get_hint(key_config.keys.file_find),
+				"Re-stage Fixed Files [{}]",
+				key_config
+					.get_hint(key_config.keys.stage_unstage_item),
 			),
-			"start commit search",
-			CMD_GROUP_LOG,
+			"stage the files modified by the pre-commit hooks",
+			CMD_GROUP_COMMIT_POPUP,
 		)
 	}
 	pub fn log_close_search(
@@ -1613,6 +4467,32 @@ pub mod commands {
 		)
 	}
 
+	pub fn create_branch_toggle_orphan(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Orphan [{}]",
+				key_config.get_hint(key_config.keys.create_branch_orphan),
+			),
+			"toggle creating a branch without history (checkout --orphan)",
+			CMD_GROUP_BRANCHES,
+		)
+	}
+	pub fn create_branch_toggle_orphan_clear(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Clear Files [{}]",
+				key_config.get_hint(
+					key_config.keys.create_branch_orphan_clear
+				),
+			),
+			"toggle removing all tracked files for the orphan branch",
+			CMD_GROUP_BRANCHES,
+		)
+	}
 	pub fn create_branch_confirm_msg(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -1688,6 +4568,60 @@ pub mod commands {
 		)
 	}
 
+	pub fn branch_popup_flow(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Git Flow [{}]",
+				key_config.get_hint(key_config.keys.branch_flow),
+			),
+			"start or finish feature, release and hotfix branches",
+			CMD_GROUP_BRANCHES,
+		)
+	}
+
+	pub fn branch_popup_symrefs(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Symrefs [{}]",
+				key_config.get_hint(key_config.keys.branch_symrefs),
+			),
+			"view and change HEAD (default branch) and other symbolic refs",
+			CMD_GROUP_BRANCHES,
+		)
+	}
+
+	pub fn branch_popup_compare_base(
+		key_config: &SharedKeyConfig,
+		is_base: bool,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"{} [{}]",
+				if is_base { "Unset base" } else { "Set base" },
+				key_config.get_hint(key_config.keys.branch_compare_base),
+			),
+			"count commits ahead/behind of the selected branch instead of the upstream",
+			CMD_GROUP_BRANCHES,
+		)
+	}
+
+	pub fn branch_popup_diff_base(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Diff base [{}]",
+				key_config.get_hint(key_config.keys.branch_diff_base),
+			),
+			"changes and commits of the branch since it forked off the base (or upstream)",
+			CMD_GROUP_BRANCHES,
+		)
+	}
+
 	pub fn branch_popup_rebase(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -1701,6 +4635,32 @@ pub mod commands {
 		)
 	}
 
+	pub fn branch_popup_stack(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Stack on [{}]",
+				key_config.get_hint(key_config.keys.stack_branch),
+			),
+			"stack current branch on (or unstack from) selected branch",
+			CMD_GROUP_BRANCHES,
+		)
+	}
+
+	pub fn branch_popup_push_stack(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Push stack [{}]",
+				key_config.get_hint(key_config.keys.stack_push),
+			),
+			"force push (with lease) all branches of the selected branch's stack",
+			CMD_GROUP_BRANCHES,
+		)
+	}
+
 	pub fn compare_with_head(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -1727,6 +4687,61 @@ pub mod commands {
 		)
 	}
 
+	pub fn compare_toggle_mode(
+		key_config: &SharedKeyConfig,
+		mode: CompareMode,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Range {} [{}]",
+				mode.toggled().as_str(),
+				key_config.get_hint(key_config.keys.compare_toggle_mode),
+			),
+			"switch between diffing both commits (..) and diffing from their merge base (...)",
+			CMD_GROUP_LOG,
+		)
+	}
+
+	pub fn compare_toggle_commits(
+		key_config: &SharedKeyConfig,
+		showing_commits: bool,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"{} [{}]",
+				if showing_commits { "Files" } else { "Commits" },
+				key_config
+					.get_hint(key_config.keys.compare_toggle_commits),
+			),
+			"switch between changed files and commits in the range",
+			CMD_GROUP_LOG,
+		)
+	}
+
+	pub fn compare_swap(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Swap [{}]",
+				key_config.get_hint(key_config.keys.compare_swap),
+			),
+			"swap base and target of the comparison",
+			CMD_GROUP_LOG,
+		)
+	}
+
+	pub fn compare_set_base(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Base [{}]",
+				key_config.get_hint(key_config.keys.compare_set_base),
+			),
+			"compare the target with a different commit, branch or tag",
+			CMD_GROUP_LOG,
+		)
+	}
+
 	pub fn select_branch_popup(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -1811,6 +4826,18 @@ pub mod commands {
 			CMD_GROUP_GENERAL,
 		)
 	}
+	pub fn status_push_no_verify(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Push (no verify) [{}]",
+				key_config.get_hint(key_config.keys.push_no_verify),
+			),
+			"push to origin skipping the pre-push hook",
+			CMD_GROUP_GENERAL,
+		)
+	}
 	pub fn status_force_push(
 		key_config: &SharedKeyConfig,
 	) -> CommandText {
@@ -1834,6 +4861,141 @@ pub mod commands {
 			CMD_GROUP_GENERAL,
 		)
 	}
+	pub fn status_pull_options(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Pull with [{}]",
+				key_config.get_hint(key_config.keys.pull_options),
+			),
+			"pick merge, rebase or fast-forward only and autostash for this pull",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn pull_start(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Pull [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"pull with the selected strategy",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn pull_toggle_autostash(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Autostash [{}]",
+				key_config
+					.get_hint(key_config.keys.pull_toggle_autostash),
+			),
+			"stash local changes before and reapply them after the pull",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn status_fetch_options(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Fetch with [{}]",
+				key_config.get_hint(key_config.keys.fetch_options),
+			),
+			"pick the remote to fetch and whether to prune and fetch tags",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn fetch_start(key_config: &SharedKeyConfig) -> CommandText {
+		CommandText::new(
+			format!(
+				"Fetch [{}]",
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"fetch the selected remotes",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn fetch_toggle_prune(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Prune [{}]",
+				key_config
+					.get_hint(key_config.keys.fetch_toggle_prune),
+			),
+			"remove remote tracking branches deleted on the remote",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn fetch_toggle_tags(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Tags [{}]",
+				key_config
+					.get_hint(key_config.keys.fetch_toggle_tags),
+			),
+			"fetch all tags instead of only those in fetched history",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn status_push_to(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Push to [{}]",
+				key_config.get_hint(key_config.keys.push_to),
+			),
+			"push to a chosen remote and branch",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn status_pull_from(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Pull from [{}]",
+				key_config.get_hint(key_config.keys.pull_from),
+			),
+			"pull from a chosen remote and branch",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn remote_target_confirm(
+		key_config: &SharedKeyConfig,
+		push: bool,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"{} [{}]",
+				if push { "Push" } else { "Pull" },
+				key_config.get_hint(key_config.keys.enter),
+			),
+			"push or pull with the selected remote branch",
+			CMD_GROUP_GENERAL,
+		)
+	}
+	pub fn remote_target_toggle_upstream(
+		key_config: &SharedKeyConfig,
+	) -> CommandText {
+		CommandText::new(
+			format!(
+				"Set upstream [{}]",
+				key_config.get_hint(
+					key_config.keys.remote_target_toggle_upstream
+				),
+			),
+			"make the selected remote branch the upstream afterwards",
+			CMD_GROUP_GENERAL,
+		)
+	}
 	pub fn status_pull(key_config: &SharedKeyConfig) -> CommandText {
 		CommandText::new(
 			format!(
@@ -3,11 +3,13 @@ use crate::{
 	components::{
 		visibility_blocking, CommandBlocking, CommandInfo,
 		CommitDetailsComponent, CommitList, Component,
-		DrawableComponent, EventState,
+		DrawableComponent, EventState, ScrollType,
 	},
 	keys::{key_match, SharedKeyConfig},
-	popups::{FileTreeOpen, InspectCommitOpen},
-	queue::{InternalEvent, Queue, StackablePopupOpen},
+	popups::{FileTreeOpen, InspectCommitOpen, RevisionTarget},
+	queue::{
+		Action, InternalEvent, NeedsUpdate, Queue, StackablePopupOpen,
+	},
 	strings::{self, order},
 	try_or_popup,
 	ui::style::{SharedTheme, Theme},
@@ -16,12 +18,14 @@ use anyhow::Result;
 use asyncgit::{
 	asyncjob::AsyncSingleJob,
 	sync::{
-		self, filter_commit_by_search, CommitId, LogFilterSearch,
-		LogFilterSearchOptions, RepoPathRef,
+		self, filter_commit_by_search, BisectState,
+		CherryPickOptions, CommitId, FetchDepth, LogFilterSearch,
+		LogFilterSearchOptions, LogScope, RepoPathRef,
+		SequencerAction, SequencerResult, ShallowInfo,
 	},
-	AsyncBranchesJob, AsyncCommitFilterJob, AsyncGitNotification,
-	AsyncLog, AsyncTags, CommitFilesParams, FetchStatus,
-	ProgressPercent,
+	AsyncBranchesJob, AsyncCherryJob, AsyncCommitFilterJob,
+	AsyncGitNotification, AsyncLog, AsyncSignaturesJob, AsyncTags,
+	CommitFilesParams, FetchStatus, NotesSync, ProgressPercent,
 };
 use crossbeam_channel::Sender;
 use crossterm::event::Event;
@@ -42,6 +46,9 @@ use std::{
 };
 use sync::CommitTags;
 
+/// commits fetched per deepen of a shallow clone
+const DEEPEN_STEP: u32 = 50;
+
 struct LogSearchResult {
 	options: LogFilterSearchOptions,
 	duration: Duration,
@@ -65,10 +72,18 @@ pub struct Revlog {
 	commit_details: CommitDetailsComponent,
 	list: CommitList,
 	git_log: AsyncLog,
+	first_parent: bool,
+	scope: LogScope,
 	search: LogSearch,
 	git_tags: AsyncTags,
 	git_local_branches: AsyncSingleJob<AsyncBranchesJob>,
 	git_remote_branches: AsyncSingleJob<AsyncBranchesJob>,
+	git_cherry: AsyncSingleJob<AsyncCherryJob>,
+	git_signatures: AsyncSingleJob<AsyncSignaturesJob>,
+	bisect: Option<BisectState>,
+	shallow: Option<ShallowInfo>,
+	/// commit to select once the walk listed it
+	pending_selection: Option<CommitId>,
 	queue: Queue,
 	visible: bool,
 	key_config: SharedKeyConfig,
@@ -92,6 +107,8 @@ impl Revlog {
 				&env.sender_git,
 				None,
 			),
+			first_parent: false,
+			scope: LogScope::Head,
 			search: LogSearch::Off,
 			git_tags: AsyncTags::new(
 				env.repo.borrow().clone(),
@@ -103,6 +120,13 @@ impl Revlog {
 			git_remote_branches: AsyncSingleJob::new(
 				env.sender_git.clone(),
 			),
+			git_cherry: AsyncSingleJob::new(env.sender_git.clone()),
+			git_signatures: AsyncSingleJob::new(
+				env.sender_git.clone(),
+			),
+			bisect: None,
+			shallow: None,
+			pending_selection: None,
 			visible: false,
 			key_config: env.key_config.clone(),
 			sender: env.sender_git.clone(),
@@ -117,6 +141,8 @@ impl Revlog {
 			|| self.git_tags.is_pending()
 			|| self.git_local_branches.is_pending()
 			|| self.git_remote_branches.is_pending()
+			|| self.git_cherry.is_pending()
+			|| self.git_signatures.is_pending()
 			|| self.commit_details.any_work_pending()
 	}
 
@@ -127,15 +153,26 @@ impl Revlog {
 	///
 	pub fn update(&mut self) -> Result<()> {
 		if self.is_visible() {
+			self.update_shallow()?;
+
 			if self.git_log.fetch()? == FetchStatus::Started {
 				self.list.clear();
+				self.update_cherry();
 			}
 
 			self.list
 				.refresh_extend_data(self.git_log.extract_items()?);
+			self.list
+				.extend_ref_masks(self.git_log.extract_ref_masks()?);
+			self.list.set_complete(self.git_log.is_complete()?);
+			self.git_log.ensure_loaded(self.list.selection());
+			self.update_pending_selection()?;
 
 			self.git_tags.request(Duration::from_secs(3), false)?;
 
+			self.update_bisect();
+			self.update_signatures();
+
 			if self.commit_details.is_visible() {
 				let commit = self.selected_commit();
 				let tags = self.selected_commit_tags(commit.as_ref());
@@ -193,6 +230,40 @@ impl Revlog {
 						}
 					}
 				}
+				AsyncGitNotification::Describe => {
+					self.commit_details.update_git(ev);
+				}
+				AsyncGitNotification::Signatures => {
+					if let Some(signatures) = self
+						.git_signatures
+						.take_last()
+						.and_then(|job| job.result())
+					{
+						self.list.extend_signatures(
+							signatures
+								.into_iter()
+								.map(|(id, verification)| {
+									(
+										id,
+										verification
+											.map(|v| v.validity),
+									)
+								})
+								.collect(),
+						);
+						self.update_signatures();
+					}
+					self.commit_details.update_git(ev);
+				}
+				AsyncGitNotification::Cherry => {
+					if let Some(Ok(applied)) = self
+						.git_cherry
+						.take_last()
+						.and_then(|job| job.result())
+					{
+						self.list.set_applied_upstream(applied);
+					}
+				}
 				_ => (),
 			}
 		}
@@ -200,6 +271,28 @@ impl Revlog {
 		Ok(())
 	}
 
+	/// verifies the signatures of the commits in view, one batch at
+	/// a time
+	fn update_signatures(&self) {
+		if self.git_signatures.is_pending() {
+			return;
+		}
+
+		let ids = self.list.unverified_signatures();
+		if !ids.is_empty() {
+			self.git_signatures.spawn(AsyncSignaturesJob::new(
+				self.repo.borrow().clone(),
+				ids,
+			));
+		}
+	}
+
+	fn update_cherry(&self) {
+		self.git_cherry.spawn(AsyncCherryJob::new_upstream(
+			self.repo.borrow().clone(),
+		));
+	}
+
 	fn selected_commit(&self) -> Option<CommitId> {
 		self.list.selected_entry().map(|e| e.id)
 	}
@@ -215,15 +308,274 @@ impl Revlog {
 		})
 	}
 
-	///
+	/// if `id` is not listed yet the rest of the history gets walked
+	/// and it is selected once it shows up
 	pub fn select_commit(&mut self, id: CommitId) -> Result<()> {
-		self.list.select_commit(id)
+		let res = self.list.select_commit(id);
+
+		if res.is_err() && !self.git_log.is_complete()? {
+			self.git_log.load_all();
+			self.pending_selection = Some(id);
+			return Ok(());
+		}
+
+		self.pending_selection = None;
+
+		res
+	}
+
+	fn update_pending_selection(&mut self) -> Result<()> {
+		let Some(id) = self.pending_selection else {
+			return Ok(());
+		};
+
+		if self.list.select_commit(id).is_ok() {
+			self.pending_selection = None;
+		} else if self.git_log.is_complete()? {
+			self.pending_selection = None;
+			self.queue.push(InternalEvent::ShowErrorMsg(
+				strings::LOG_SELECT_COMMIT_NOT_FOUND_MSG.to_string(),
+			));
+		}
+
+		Ok(())
+	}
+
+	fn toggle_first_parent(&mut self) -> Result<()> {
+		self.first_parent = !self.first_parent;
+		self.git_log.set_first_parent(self.first_parent)?;
+
+		self.update_title();
+
+		self.update()
+	}
+
+	fn switch_scope(&mut self) -> Result<()> {
+		self.set_scope(match self.scope {
+			LogScope::Head => LogScope::LocalBranches,
+			LogScope::LocalBranches => LogScope::AllRefs,
+			LogScope::AllRefs | LogScope::Refs(_) => LogScope::Head,
+		})
+	}
+
+	/// show the history of `refs` together (like `git log a b`),
+	/// back to the current branch if empty
+	pub fn set_log_refs(&mut self, refs: Vec<String>) -> Result<()> {
+		self.set_scope(if refs.is_empty() {
+			LogScope::Head
+		} else {
+			LogScope::Refs(refs)
+		})
+	}
+
+	///
+	pub fn log_refs(&self) -> Vec<String> {
+		match &self.scope {
+			LogScope::Refs(refs) => refs.clone(),
+			_ => Vec::new(),
+		}
+	}
+
+	fn set_scope(&mut self, scope: LogScope) -> Result<()> {
+		self.git_log.set_scope(scope.clone())?;
+		self.list.set_ref_columns(match &scope {
+			LogScope::Refs(refs) => refs.len().min(64),
+			_ => 0,
+		});
+		self.scope = scope;
+
+		self.update_title();
+
+		self.update()
+	}
+
+	/// limit the log to commits touching `pathspec`
+	pub fn set_pathspec(
+		&mut self,
+		pathspec: Option<String>,
+	) -> Result<()> {
+		if self.git_log.pathspec() != pathspec.as_deref() {
+			self.cancel_search();
+			self.list.set_highlighting(None);
+			self.search = LogSearch::Off;
+
+			self.git_log.set_pathspec(pathspec)?;
+			self.update_title();
+			self.update()?;
+		}
+
+		Ok(())
+	}
+
+	fn update_bisect(&mut self) {
+		let bisect =
+			sync::bisect_state(&self.repo.borrow()).ok().flatten();
+
+		if bisect != self.bisect {
+			self.list.set_bisect(bisect.clone());
+			self.bisect = bisect;
+			self.update_title();
+		}
+	}
+
+	fn update_shallow(&mut self) -> Result<()> {
+		let boundary = sync::shallow_boundary(&self.repo.borrow())
+			.unwrap_or_default();
+		let current = self
+			.shallow
+			.as_ref()
+			.map_or(&[][..], |shallow| shallow.boundary.as_slice());
+
+		if boundary.as_slice() != current {
+			// history beyond the old graft points got fetched
+			if self.shallow.is_some() {
+				self.git_log.restart()?;
+			}
+
+			self.shallow = sync::shallow_info(&self.repo.borrow())
+				.ok()
+				.flatten();
+			self.update_title();
+		}
+
+		Ok(())
+	}
+
+	/// fetches `DEEPEN_STEP` more commits or, if `unshallow`, the
+	/// complete history from the default remote
+	fn deepen(&self, unshallow: bool) -> Result<()> {
+		let Some(shallow) = &self.shallow else {
+			return Ok(());
+		};
+
+		let repo = self.repo.borrow();
+		let depth = if unshallow {
+			FetchDepth::Unshallow
+		} else {
+			FetchDepth::Commits(
+				u32::try_from(shallow.depth)
+					.unwrap_or(u32::MAX)
+					.saturating_add(DEEPEN_STEP),
+			)
+		};
+
+		let mut options = sync::fetch_options_config(&repo)?;
+		options.remote =
+			sync::get_default_remote_for_fetch(&repo).ok();
+		options.depth = Some(depth);
+
+		self.queue.push(InternalEvent::FetchRemotes(Some(options)));
+
+		Ok(())
+	}
+
+	fn update_title(&mut self) {
+		let title = strings::log_title_options(
+			&self.key_config,
+			&self.scope,
+			self.first_parent,
+		);
+
+		let title = match self.git_log.pathspec() {
+			Some(pathspec) => {
+				strings::log_title_pathspec(&title, pathspec)
+			}
+			None => title,
+		};
+
+		let title = match &self.bisect {
+			Some(bisect) => strings::log_title_bisect(&title, bisect),
+			None => title,
+		};
+
+		let title = match &self.shallow {
+			Some(shallow) => {
+				strings::log_title_shallow(&title, shallow.depth)
+			}
+			None => title,
+		};
+
+		self.list.set_title(&title);
+	}
+
+	fn marked_or_selected(&self) -> Vec<CommitId> {
+		if self.list.marked_count() > 0 {
+			self.list.marked_commits()
+		} else {
+			self.selected_commit().into_iter().collect()
+		}
+	}
+
+	fn cherry_pick(&mut self, record_origin: bool) -> Result<()> {
+		let commits = self.marked_or_selected();
+		if commits.is_empty() {
+			return Ok(());
+		}
+
+		let result = sync::cherry_pick_commits(
+			&self.repo.borrow(),
+			&commits,
+			CherryPickOptions { record_origin },
+		)?;
+
+		self.list.clear_marked();
+		self.queue.push(InternalEvent::ShowInfoMsg(
+			strings::sequencer_result(SequencerAction::Pick, result),
+		));
+		if matches!(result, SequencerResult::Conflicts(_)) {
+			self.queue.push(InternalEvent::TabSwitchStatus);
+		}
+		self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
+
+		Ok(())
 	}
 
 	fn revert_commit(&self) -> Result<()> {
 		if let Some(c) = self.selected_commit() {
-			sync::revert_commit(&self.repo.borrow(), c)?;
-			self.queue.push(InternalEvent::TabSwitchStatus);
+			if sync::commit_parent_count(&self.repo.borrow(), c)? > 1
+			{
+				self.queue
+					.push(InternalEvent::OpenRevertMergePopup(c));
+			} else {
+				sync::revert_commit(&self.repo.borrow(), c, None)?;
+				self.queue.push(InternalEvent::TabSwitchStatus);
+			}
+		}
+
+		Ok(())
+	}
+
+	fn reword_commit(&self, id: CommitId) {
+		let pushed = sync::is_commit_pushed(&self.repo.borrow(), id)
+			.unwrap_or_default();
+
+		self.queue.push(if pushed {
+			InternalEvent::ConfirmAction(Action::RewordPushedCommit(
+				id,
+			))
+		} else {
+			InternalEvent::RewordCommit(id)
+		});
+	}
+
+	fn move_commit(
+		&mut self,
+		direction: sync::CommitMove,
+	) -> Result<()> {
+		if let Some(c) = self.selected_commit() {
+			let state =
+				sync::move_commit(&self.repo.borrow(), c, direction)?;
+
+			if state == sync::RebaseState::Conflicted {
+				self.queue.push(InternalEvent::TabSwitchStatus);
+			} else {
+				self.list.move_selection(match direction {
+					sync::CommitMove::Up => ScrollType::Up,
+					sync::CommitMove::Down => ScrollType::Down,
+				})?;
+			}
+
+			self.queue.push(InternalEvent::Update(NeedsUpdate::ALL));
 		}
 
 		Ok(())
@@ -313,6 +665,12 @@ impl Revlog {
 							self.search = if was_aborted {
 								LogSearch::Off
 							} else {
+								// matches come from the whole history,
+								// the walk has to get to all of them
+								if !search.result.is_empty() {
+									self.git_log.load_all();
+								}
+
 								self.list.set_highlighting(Some(
 									Rc::new(
 										search
@@ -470,6 +828,8 @@ impl Component for Revlog {
 					} else if self.can_close_search() {
 						self.list.set_highlighting(None);
 						self.search = LogSearch::Off;
+					} else if self.git_log.pathspec().is_some() {
+						self.set_pathspec(None)?;
 					}
 					return Ok(EventState::Consumed);
 				} else if key_match(k, self.key_config.keys.copy) {
@@ -479,6 +839,28 @@ impl Component for Revlog {
 						self.list.copy_commit_hash()
 					);
 					return Ok(EventState::Consumed);
+				} else if key_match(
+					k,
+					self.key_config.keys.copy_describe,
+				) && self.commit_details.is_visible()
+				{
+					try_or_popup!(
+						self,
+						strings::POPUP_FAIL_COPY,
+						self.commit_details.copy_describe()
+					);
+					return Ok(EventState::Consumed);
+				} else if key_match(
+					k,
+					self.key_config.keys.copy_trailers,
+				) && self.commit_details.is_visible()
+				{
+					try_or_popup!(
+						self,
+						strings::POPUP_FAIL_COPY,
+						self.commit_details.copy_trailers()
+					);
+					return Ok(EventState::Consumed);
 				} else if key_match(k, self.key_config.keys.push) {
 					self.queue.push(InternalEvent::PushTags);
 					return Ok(EventState::Consumed);
@@ -559,12 +941,107 @@ impl Component for Revlog {
 					k,
 					self.key_config.keys.log_reword_commit,
 				) && !self.is_search_pending()
+				{
+					return self.selected_commit().map_or(
+						Ok(EventState::NotConsumed),
+						|id| {
+							self.reword_commit(id);
+							Ok(EventState::Consumed)
+						},
+					);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_move_commit_up,
+				) && !self.is_search_pending()
+				{
+					try_or_popup!(
+						self,
+						"move commit error:",
+						self.move_commit(sync::CommitMove::Up)
+					);
+					return Ok(EventState::Consumed);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_move_commit_down,
+				) && !self.is_search_pending()
+				{
+					try_or_popup!(
+						self,
+						"move commit error:",
+						self.move_commit(sync::CommitMove::Down)
+					);
+					return Ok(EventState::Consumed);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_reflog,
+				) {
+					self.queue.push(InternalEvent::OpenReflogPopup);
+					return Ok(EventState::Consumed);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_bisect,
+				) && !self.is_search_pending()
 				{
 					return self.selected_commit().map_or(
 						Ok(EventState::NotConsumed),
 						|id| {
 							self.queue.push(
-								InternalEvent::RewordCommit(id),
+								InternalEvent::OpenBisectPopup(id),
+							);
+							Ok(EventState::Consumed)
+						},
+					);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_rebase_interactive,
+				) && !self.is_search_pending()
+				{
+					return self.selected_commit().map_or(
+						Ok(EventState::NotConsumed),
+						|id| {
+							self.queue.push(
+								InternalEvent::OpenRebaseInteractive(
+									id,
+								),
+							);
+							Ok(EventState::Consumed)
+						},
+					);
+				} else if (key_match(
+					k,
+					self.key_config.keys.log_fixup_commit,
+				) || key_match(
+					k,
+					self.key_config.keys.log_squash_commit,
+				)) && !self.is_search_pending()
+				{
+					let squash = key_match(
+						k,
+						self.key_config.keys.log_squash_commit,
+					);
+					return self.selected_commit().map_or(
+						Ok(EventState::NotConsumed),
+						|id| {
+							self.queue.push(
+								InternalEvent::OpenCommitFixup(
+									id, squash,
+								),
+							);
+							Ok(EventState::Consumed)
+						},
+					);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_autosquash,
+				) && !self.is_search_pending()
+				{
+					return self.selected_commit().map_or(
+						Ok(EventState::NotConsumed),
+						|id| {
+							self.queue.push(
+								InternalEvent::OpenRebaseAutosquash(
+									id,
+								),
 							);
 							Ok(EventState::Consumed)
 						},
@@ -577,11 +1054,122 @@ impl Component for Revlog {
 					return Ok(EventState::Consumed);
 				} else if key_match(
 					k,
-					self.key_config.keys.compare_commits,
-				) && self.list.marked_count() > 0
+					self.key_config.keys.log_first_parent,
+				) && !self.is_search_pending()
+				{
+					self.toggle_first_parent()?;
+					return Ok(EventState::Consumed);
+				} else if key_match(k, self.key_config.keys.log_scope)
 					&& !self.is_search_pending()
 				{
-					if self.list.marked_count() == 1 {
+					self.switch_scope()?;
+					return Ok(EventState::Consumed);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_select_refs,
+				) && !self.is_search_pending()
+				{
+					self.queue.push(InternalEvent::OpenLogRefsPopup);
+					return Ok(EventState::Consumed);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_edit_note,
+				) && !self.is_search_pending()
+				{
+					return self.selected_commit().map_or(
+						Ok(EventState::NotConsumed),
+						|id| {
+							self.queue.push(
+								InternalEvent::OpenCommitNotePopup(
+									id,
+								),
+							);
+							Ok(EventState::Consumed)
+						},
+					);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_format_patch,
+				) && !self.is_search_pending()
+				{
+					let commits = self.marked_or_selected();
+					if !commits.is_empty() {
+						self.queue.push(
+							InternalEvent::OpenFormatPatchPopup(
+								commits,
+							),
+						);
+					}
+					return Ok(EventState::Consumed);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_cherry_pick,
+				) && !self.is_search_pending()
+				{
+					try_or_popup!(
+						self,
+						"cherry-pick error:",
+						self.cherry_pick(false)
+					);
+					return Ok(EventState::Consumed);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_cherry_pick_origin,
+				) && !self.is_search_pending()
+				{
+					try_or_popup!(
+						self,
+						"cherry-pick error:",
+						self.cherry_pick(true)
+					);
+					return Ok(EventState::Consumed);
+				} else if (key_match(
+					k,
+					self.key_config.keys.log_deepen,
+				) || key_match(
+					k,
+					self.key_config.keys.log_unshallow,
+				)) && self.shallow.is_some()
+				{
+					let unshallow = key_match(
+						k,
+						self.key_config.keys.log_unshallow,
+					);
+					try_or_popup!(
+						self,
+						"fetch error:",
+						self.deepen(unshallow)
+					);
+					return Ok(EventState::Consumed);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_push_notes,
+				) {
+					self.queue.push(InternalEvent::SyncNotes(
+						NotesSync::Push,
+					));
+					return Ok(EventState::Consumed);
+				} else if key_match(
+					k,
+					self.key_config.keys.log_fetch_notes,
+				) {
+					self.queue.push(InternalEvent::SyncNotes(
+						NotesSync::Fetch,
+					));
+					return Ok(EventState::Consumed);
+				} else if key_match(
+					k,
+					self.key_config.keys.compare_commits,
+				) && !self.is_search_pending()
+				{
+					if self.list.marked_count() == 0 {
+						self.queue.push(
+							InternalEvent::OpenRevisionPopup(
+								RevisionTarget::Compare,
+							),
+						);
+						return Ok(EventState::Consumed);
+					} else if self.list.marked_count() == 1 {
 						// compare against head
 						self.queue.push(InternalEvent::OpenPopup(
 							StackablePopupOpen::CompareCommits(
@@ -599,6 +1187,7 @@ impl Component for Revlog {
 								InspectCommitOpen {
 									commit_id: marked[0].1,
 									compare_id: Some(marked[1].1),
+									compare_mode: None,
 									tags: None,
 								},
 							),
@@ -612,6 +1201,7 @@ impl Component for Revlog {
 		Ok(EventState::NotConsumed)
 	}
 
+	#[allow(clippy::too_many_lines)]
 	fn commands(
 		&self,
 		out: &mut Vec<CommandInfo>,
@@ -633,6 +1223,15 @@ impl Component for Revlog {
 			.order(order::PRIORITY),
 		);
 
+		out.push(CommandInfo::new(
+			strings::commands::log_clear_pathspec(&self.key_config),
+			true,
+			(self.visible
+				&& !self.is_in_search_mode()
+				&& self.git_log.pathspec().is_some())
+				|| force_all,
+		));
+
 		out.push(CommandInfo::new(
 			strings::commands::log_details_toggle(&self.key_config),
 			true,
@@ -659,7 +1258,16 @@ impl Component for Revlog {
 			self.list.marked_count() == 1,
 			(self.visible
 				&& !self.is_search_pending()
-				&& self.list.marked_count() <= 1)
+				&& self.list.marked_count() == 1)
+				|| force_all,
+		));
+
+		out.push(CommandInfo::new(
+			strings::commands::compare_revision(&self.key_config),
+			true,
+			(self.visible
+				&& !self.is_search_pending()
+				&& self.list.marked_count() == 0)
 				|| force_all,
 		));
 
@@ -678,6 +1286,20 @@ impl Component for Revlog {
 			self.visible || force_all,
 		));
 
+		out.push(CommandInfo::new(
+			strings::commands::copy_describe(&self.key_config),
+			self.selected_commit().is_some(),
+			(self.visible && self.commit_details.is_visible())
+				|| force_all,
+		));
+
+		out.push(CommandInfo::new(
+			strings::commands::copy_trailers(&self.key_config),
+			self.selected_commit().is_some(),
+			(self.visible && self.commit_details.is_visible())
+				|| force_all,
+		));
+
 		out.push(CommandInfo::new(
 			strings::commands::log_tag_commit(&self.key_config),
 			self.selected_commit().is_some(),
@@ -724,11 +1346,110 @@ impl Component for Revlog {
 			self.selected_commit().is_some(),
 			(self.visible && !self.is_search_pending()) || force_all,
 		));
+		out.push(CommandInfo::new(
+			strings::commands::log_move_commit(&self.key_config),
+			self.selected_commit().is_some(),
+			(self.visible && !self.is_search_pending()) || force_all,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::log_reflog(&self.key_config),
+			true,
+			self.visible || force_all,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::log_bisect(&self.key_config),
+			self.selected_commit().is_some(),
+			(self.visible && !self.is_search_pending()) || force_all,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::log_rebase_interactive(
+				&self.key_config,
+			),
+			self.selected_commit().is_some(),
+			(self.visible && !self.is_search_pending()) || force_all,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::log_fixup_commit(&self.key_config),
+			self.selected_commit().is_some(),
+			(self.visible && !self.is_search_pending()) || force_all,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::log_autosquash(&self.key_config),
+			self.selected_commit().is_some(),
+			(self.visible && !self.is_search_pending()) || force_all,
+		));
 		out.push(CommandInfo::new(
 			strings::commands::log_find_commit(&self.key_config),
 			self.can_start_search(),
 			self.visible || force_all,
 		));
+		out.push(CommandInfo::new(
+			strings::commands::log_first_parent(
+				&self.key_config,
+				self.first_parent,
+			),
+			!self.is_search_pending(),
+			self.visible || force_all,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::log_select_refs(&self.key_config),
+			!self.is_search_pending(),
+			self.visible || force_all,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::log_edit_note(&self.key_config),
+			self.selected_commit().is_some()
+				&& !self.is_search_pending(),
+			self.visible || force_all,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::log_format_patch(&self.key_config),
+			self.selected_commit().is_some()
+				&& !self.is_search_pending(),
+			self.visible || force_all,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::log_cherry_pick(&self.key_config),
+			self.selected_commit().is_some()
+				&& !self.is_search_pending(),
+			self.visible || force_all,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::log_cherry_pick_origin(
+				&self.key_config,
+			),
+			self.selected_commit().is_some()
+				&& !self.is_search_pending(),
+			self.visible || force_all,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::log_deepen(&self.key_config),
+			true,
+			(self.visible && self.shallow.is_some()) || force_all,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::log_unshallow(&self.key_config),
+			true,
+			(self.visible && self.shallow.is_some()) || force_all,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::log_push_notes(&self.key_config),
+			true,
+			self.visible || force_all,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::log_fetch_notes(&self.key_config),
+			true,
+			self.visible || force_all,
+		));
+		out.push(CommandInfo::new(
+			strings::commands::log_scope(
+				&self.key_config,
+				&self.scope,
+			),
+			!self.is_search_pending(),
+			self.visible || force_all,
+		));
 
 		visibility_blocking(self)
 	}
@@ -755,6 +1476,8 @@ impl Component for Revlog {
 			false,
 		));
 
+		self.update_cherry();
+
 		self.update()?;
 
 		Ok(())
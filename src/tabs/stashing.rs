@@ -7,6 +7,8 @@ use crate::{
 		EventState, StatusTreeComponent,
 	},
 	keys::{key_match, SharedKeyConfig},
+	options::SharedOptions,
+	popups::StashSelection,
 	queue::{InternalEvent, Queue},
 	strings,
 	ui::style::SharedTheme,
@@ -27,14 +29,39 @@ use std::borrow::Cow;
 #[derive(Default, Clone, Copy, Debug)]
 pub struct StashingOptions {
 	pub stash_untracked: bool,
+	pub stash_ignored: bool,
 	pub keep_index: bool,
 }
 
+impl StashingOptions {
+	/// remembered defaults for including untracked and ignored files,
+	/// untracked follows `status.showUntrackedFiles` if never toggled
+	pub fn from_options(
+		repo: &RepoPathRef,
+		options: &SharedOptions,
+	) -> Result<Self> {
+		let options = options.borrow();
+
+		let stash_untracked = match options.stash_untracked() {
+			Some(value) => value,
+			None => !sync::untracked_files_config(&repo.borrow())?
+				.include_none(),
+		};
+
+		Ok(Self {
+			stash_untracked,
+			stash_ignored: options.stash_ignored(),
+			keep_index: false,
+		})
+	}
+}
+
 pub struct Stashing {
 	repo: RepoPathRef,
 	index: StatusTreeComponent,
 	visible: bool,
 	options: StashingOptions,
+	shared_options: SharedOptions,
 	theme: SharedTheme,
 	git_status: AsyncStatus,
 	queue: Queue,
@@ -57,7 +84,9 @@ impl Stashing {
 			options: StashingOptions {
 				keep_index: false,
 				stash_untracked: true,
+				stash_ignored: false,
 			},
+			shared_options: env.options.clone(),
 			theme: env.theme.clone(),
 			git_status: AsyncStatus::new(
 				env.repo.borrow().clone(),
@@ -107,27 +136,23 @@ impl Stashing {
 		let option_off =
 			Span::styled(Cow::from("_"), self.theme.option(false));
 
-		vec![
+		let option = |on: bool, label: &'static str| {
 			Line::from(vec![
 				bracket_open.clone(),
-				if self.options.stash_untracked {
+				if on {
 					option_on.clone()
 				} else {
 					option_off.clone()
 				},
 				bracket_close.clone(),
-				Span::raw(Cow::from(" stash untracked")),
-			]),
-			Line::from(vec![
-				bracket_open,
-				if self.options.keep_index {
-					option_on
-				} else {
-					option_off
-				},
-				bracket_close,
-				Span::raw(Cow::from(" keep index")),
-			]),
+				Span::raw(Cow::from(label)),
+			])
+		};
+
+		vec![
+			option(self.options.stash_untracked, " stash untracked"),
+			option(self.options.stash_ignored, " stash ignored"),
+			option(self.options.keep_index, " keep index"),
 		]
 	}
 }
@@ -148,7 +173,7 @@ impl DrawableComponent for Stashing {
 		let right_chunks = Layout::default()
 			.direction(Direction::Vertical)
 			.constraints(
-				[Constraint::Length(4), Constraint::Min(1)].as_ref(),
+				[Constraint::Length(5), Constraint::Min(1)].as_ref(),
 			)
 			.split(chunks[1]);
 
@@ -185,6 +210,13 @@ impl Component for Stashing {
 				self.visible && !self.index.is_empty(),
 				self.visible || force_all,
 			));
+			out.push(CommandInfo::new(
+				strings::commands::stashing_mark_file(
+					&self.key_config,
+				),
+				self.visible && !self.index.is_empty(),
+				self.visible || force_all,
+			));
 			out.push(CommandInfo::new(
 				strings::commands::stashing_toggle_indexed(
 					&self.key_config,
@@ -199,6 +231,13 @@ impl Component for Stashing {
 				self.visible,
 				self.visible || force_all,
 			));
+			out.push(CommandInfo::new(
+				strings::commands::stashing_toggle_ignored(
+					&self.key_config,
+				),
+				self.visible,
+				self.visible || force_all,
+			));
 		}
 
 		visibility_blocking(self)
@@ -221,10 +260,22 @@ impl Component for Stashing {
 					self.key_config.keys.stashing_save,
 				) && !self.index.is_empty()
 				{
+					let marked = self.index.marked();
 					self.queue.push(InternalEvent::PopupStashing(
 						self.options,
+						if marked.is_empty() {
+							StashSelection::All
+						} else {
+							StashSelection::Paths(marked)
+						},
 					));
 
+					Ok(EventState::Consumed)
+				} else if key_match(
+					k,
+					self.key_config.keys.stashing_mark_file,
+				) {
+					self.index.toggle_mark();
 					Ok(EventState::Consumed)
 				} else if key_match(
 					k,
@@ -240,6 +291,24 @@ impl Component for Stashing {
 				) {
 					self.options.stash_untracked =
 						!self.options.stash_untracked;
+					self.shared_options
+						.borrow_mut()
+						.set_stash_untracked(
+							self.options.stash_untracked,
+						);
+					self.update()?;
+					Ok(EventState::Consumed)
+				} else if key_match(
+					k,
+					self.key_config.keys.stashing_toggle_ignored,
+				) {
+					self.options.stash_ignored =
+						!self.options.stash_ignored;
+					self.shared_options
+						.borrow_mut()
+						.set_stash_ignored(
+							self.options.stash_ignored,
+						);
 					self.update()?;
 					Ok(EventState::Consumed)
 				} else {
@@ -260,11 +329,13 @@ impl Component for Stashing {
 	}
 
 	fn show(&mut self) -> Result<()> {
-		let config_untracked_files =
-			sync::untracked_files_config(&self.repo.borrow())?;
-
-		self.options.stash_untracked =
-			!config_untracked_files.include_none();
+		self.options = StashingOptions {
+			keep_index: self.options.keep_index,
+			..StashingOptions::from_options(
+				&self.repo,
+				&self.shared_options,
+			)?
+		};
 
 		self.index.show()?;
 		self.visible = true;
@@ -92,6 +92,13 @@ impl StashList {
 		}
 	}
 
+	fn worktree_diff(&self) {
+		if let Some(e) = self.list.selected_entry() {
+			self.queue
+				.push(InternalEvent::OpenStashWorktreePopup(e.id));
+		}
+	}
+
 	/// Called when a pending stash action has been confirmed
 	pub fn action_confirmed(
 		&mut self,
@@ -175,6 +182,13 @@ impl Component for StashList {
 				selection_valid,
 				true,
 			));
+			out.push(CommandInfo::new(
+				strings::commands::stashlist_worktree_diff(
+					&self.key_config,
+				),
+				selection_valid,
+				true,
+			));
 			out.push(CommandInfo::new(
 				strings::commands::stashlist_inspect(
 					&self.key_config,
@@ -214,6 +228,11 @@ impl Component for StashList {
 					self.key_config.keys.stash_open,
 				) {
 					self.inspect();
+				} else if key_match(
+					k,
+					self.key_config.keys.stash_worktree_diff,
+				) {
+					self.worktree_diff();
 				}
 			}
 		}
@@ -19,15 +19,19 @@ use asyncgit::{
 	sync::{
 		self, status::StatusType, RepoPath, RepoPathRef, RepoState,
 	},
-	sync::{BranchCompare, CommitId},
+	sync::{
+		ApplyPatchResult, BranchCompare, CommitId, SequencerAction,
+		SequencerResult,
+	},
 	AsyncDiff, AsyncGitNotification, AsyncStatus, DiffParams,
-	DiffType, PushType, StatusItem, StatusParams,
+	DiffType, PushType, StatusItem, StatusItemType, StatusParams,
 };
 use crossterm::event::Event;
 use itertools::Itertools;
 use ratatui::{
 	layout::{Alignment, Constraint, Direction, Layout},
 	style::{Color, Style},
+	text::{Line, Span},
 	widgets::{Block, BorderType, Borders, Paragraph},
 };
 
@@ -60,8 +64,10 @@ enum DiffTarget {
 struct RemoteStatus {
 	has_remote_for_fetch: bool,
 	has_remote_for_push: bool,
+	count: usize,
 }
 
+#[allow(clippy::struct_excessive_bools)]
 pub struct Status {
 	repo: RepoPathRef,
 	visible: bool,
@@ -77,8 +83,11 @@ pub struct Status {
 	git_status_stage: AsyncStatus,
 	git_branch_state: Option<BranchCompare>,
 	git_branch_name: cached::BranchName,
+	/// checked out branch matches `gitui.protectedBranches`
+	git_branch_protected: bool,
 	queue: Queue,
 	git_action_executed: bool,
+	show_ignored: bool,
 	options: SharedOptions,
 	key_config: SharedKeyConfig,
 }
@@ -163,6 +172,7 @@ impl Status {
 			remotes: RemoteStatus {
 				has_remote_for_fetch: false,
 				has_remote_for_push: false,
+				count: 0,
 			},
 			git_state: RepoState::Clean,
 			focus: Focus::WorkDir,
@@ -193,10 +203,12 @@ impl Status {
 				env.sender_git.clone(),
 			),
 			git_action_executed: false,
+			show_ignored: false,
 			git_branch_state: None,
 			git_branch_name: cached::BranchName::new(
 				env.repo.clone(),
 			),
+			git_branch_protected: false,
 			key_config: env.key_config.clone(),
 			options: env.options.clone(),
 			repo: env.repo.clone(),
@@ -219,10 +231,19 @@ impl Status {
 					)
 				});
 
-			let w = Paragraph::new(format!(
+			let mut spans = Vec::with_capacity(2);
+			if self.git_branch_protected {
+				spans.push(Span::styled(
+					strings::STATUS_BRANCH_PROTECTED,
+					Theme::attention_block(),
+				));
+			}
+			spans.push(Span::raw(format!(
 				"{ahead_behind}{{{branch_name}}}"
-			))
-			.alignment(Alignment::Right);
+			)));
+
+			let w = Paragraph::new(Line::from(spans))
+				.alignment(Alignment::Right);
 
 			let mut rect = if self.index_wd.focused() {
 				let mut rect = chunks[0];
@@ -270,16 +291,28 @@ impl Status {
 						)
 					},
 				),
-			RepoState::Revert => {
-				format!(
-					"Revert {}",
-					sync::revert_head(repo)
-						.ok()
-						.as_ref()
-						.map(CommitId::get_short_string)
-						.unwrap_or_default(),
-				)
+			RepoState::ApplyMailbox => {
+				sync::apply_mailbox_progress(repo)
+					.ok()
+					.flatten()
+					.as_ref()
+					.map(strings::apply_mailbox_progress)
+					.unwrap_or_default()
 			}
+			RepoState::Revert | RepoState::CherryPick => {
+				sync::sequencer_progress(repo)
+					.ok()
+					.flatten()
+					.as_ref()
+					.map(strings::sequencer_progress)
+					.unwrap_or_default()
+			}
+			RepoState::Bisect => sync::bisect_state(repo)
+				.ok()
+				.flatten()
+				.as_ref()
+				.map(strings::bisect_progress)
+				.unwrap_or_default(),
 			_ => format!("{state:?}"),
 		}
 	}
@@ -301,9 +334,9 @@ impl Status {
 						.border_type(BorderType::Plain)
 						.borders(Borders::all())
 						.border_style(Theme::attention_block())
-						.title(format!(
-							"Pending {:?}",
-							self.git_state
+						.title(strings::pending_state_title(
+							&self.key_config,
+							&self.git_state,
 						)),
 				)
 				.style(Style::default().fg(Color::Red))
@@ -380,6 +413,22 @@ impl Status {
 		None
 	}
 
+	/// path of the selected unstaged file if it is conflicted
+	fn selected_conflict(&self) -> Option<String> {
+		if self.diff_target != DiffTarget::WorkingDir {
+			return None;
+		}
+
+		match self.index_wd.selection()?.kind {
+			FileTreeItemKind::File(item)
+				if item.status == StatusItemType::Conflicted =>
+			{
+				Some(item.path)
+			}
+			_ => None,
+		}
+	}
+
 	///
 	pub fn update(&mut self) -> Result<()> {
 		self.git_branch_name.lookup().map(Some).unwrap_or(None);
@@ -389,10 +438,11 @@ impl Status {
 				self.options.borrow().status_show_untracked();
 
 			self.git_diff.refresh()?;
-			self.git_status_workdir.fetch(&StatusParams::new(
-				StatusType::WorkingDir,
-				config,
-			))?;
+			self.git_status_workdir.fetch(
+				&StatusParams::new(StatusType::WorkingDir, config)
+					.with_ignored(self.show_ignored)
+					.with_submodules(true),
+			)?;
 			self.git_status_stage.fetch(&StatusParams::new(
 				StatusType::Stage,
 				config,
@@ -401,6 +451,15 @@ impl Status {
 			self.git_state = sync::repo_state(&self.repo.borrow())
 				.unwrap_or(RepoState::Clean);
 
+			self.git_branch_protected =
+				self.git_branch_name.last().is_some_and(|branch| {
+					sync::is_branch_protected(
+						&self.repo.borrow(),
+						&branch,
+					)
+					.unwrap_or_default()
+				});
+
 			self.branch_compare();
 		}
 
@@ -412,6 +471,8 @@ impl Status {
 		self.git_diff.is_pending()
 			|| self.git_status_stage.is_pending()
 			|| self.git_status_workdir.is_pending()
+			|| self.index.any_work_pending()
+			|| self.index_wd.any_work_pending()
 	}
 
 	fn check_remotes(&mut self) {
@@ -425,6 +486,9 @@ impl Status {
 				&self.repo.borrow().clone(),
 			)
 			.is_ok();
+		self.remotes.count = sync::get_remotes(&self.repo.borrow())
+			.map(|remotes| remotes.len())
+			.unwrap_or_default();
 	}
 
 	///
@@ -432,6 +496,9 @@ impl Status {
 		&mut self,
 		ev: AsyncGitNotification,
 	) -> Result<()> {
+		self.index.update_git(ev);
+		self.index_wd.update_git(ev);
+
 		if !self.is_visible() {
 			return Ok(());
 		}
@@ -458,9 +525,14 @@ impl Status {
 	fn update_status(&mut self) -> Result<()> {
 		let stage_status = self.git_status_stage.last()?;
 		self.index.set_items(&stage_status.items)?;
+		self.index.set_lfs_files(stage_status.lfs);
 
 		let workdir_status = self.git_status_workdir.last()?;
 		self.index_wd.set_items(&workdir_status.items)?;
+		self.index_wd.set_submodules(workdir_status.submodules);
+		self.index_wd.set_lfs_files(workdir_status.lfs);
+		self.index_wd
+			.set_submodule_states(workdir_status.submodule_states);
 
 		self.update_diff()?;
 
@@ -487,6 +559,10 @@ impl Status {
 		if let Some((path, is_stage)) = self.selected_path() {
 			let diff_type = if is_stage {
 				DiffType::Stage
+			} else if let Some(submodule) =
+				self.index_wd.submodule_of(&path)
+			{
+				DiffType::Submodule(submodule)
 			} else {
 				DiffType::WorkDir
 			};
@@ -494,7 +570,10 @@ impl Status {
 			let diff_params = DiffParams {
 				path: path.clone(),
 				diff_type,
-				options: self.options.borrow().diff_options(),
+				options: self.diff.limit_options(
+					&path,
+					self.options.borrow().diff_options(),
+				),
 			};
 
 			if self.diff.current() == (path.clone(), is_stage) {
@@ -555,6 +634,21 @@ impl Status {
 		}
 	}
 
+	///
+	pub fn discard_submodule(&self, path: &str) -> bool {
+		if let Err(e) =
+			sync::discard_submodule_changes(&self.repo.borrow(), path)
+		{
+			self.queue.push(InternalEvent::ShowErrorMsg(format!(
+				"discard submodule failed:\n{e}"
+			)));
+
+			false
+		} else {
+			true
+		}
+	}
+
 	pub fn last_file_moved(&mut self) -> Result<()> {
 		if !self.is_focus_on_diff() && self.is_visible() {
 			self.switch_focus(self.focus.toggled_focus())?;
@@ -562,12 +656,23 @@ impl Status {
 		Ok(())
 	}
 
-	fn push(&self, force: bool) {
-		if self.can_push() {
+	fn push(&self, force: bool, no_verify: bool) {
+		if !force && self.needs_remote_target() {
+			self.push_to();
+		} else if self.can_push() {
 			if let Some(branch) = self.git_branch_name.last() {
 				if force {
+					self.queue.push(
+						InternalEvent::OpenForcePushPopup(branch),
+					);
+				} else if sync::is_branch_protected(
+					&self.repo.borrow(),
+					&branch,
+				)
+				.unwrap_or_default()
+				{
 					self.queue.push(InternalEvent::ConfirmAction(
-						Action::ForcePush(branch, force),
+						Action::PushProtected(branch, no_verify),
 					));
 				} else {
 					self.queue.push(InternalEvent::Push(
@@ -575,6 +680,7 @@ impl Status {
 						PushType::Branch,
 						force,
 						false,
+						no_verify,
 					));
 				}
 			}
@@ -583,14 +689,82 @@ impl Status {
 
 	fn fetch(&self) {
 		if self.can_fetch() {
-			self.queue.push(InternalEvent::FetchRemotes);
+			self.queue.push(InternalEvent::FetchRemotes(None));
+		}
+	}
+
+	fn fetch_options(&self) {
+		if self.can_fetch() {
+			self.queue.push(InternalEvent::OpenFetchOptionsPopup);
 		}
 	}
 
 	fn pull(&self) {
+		if self.git_branch_state.is_none() {
+			self.pull_from();
+		} else if let Some(branch) = self.git_branch_name.last() {
+			self.queue.push(InternalEvent::Pull(branch, None));
+		}
+	}
+
+	fn push_to(&self) {
+		if let Some(branch) = self.git_branch_name.last() {
+			self.queue.push(InternalEvent::OpenRemoteTargetPopup(
+				branch, true,
+			));
+		}
+	}
+
+	fn pull_from(&self) {
+		if let Some(branch) = self.git_branch_name.last() {
+			self.queue.push(InternalEvent::OpenRemoteTargetPopup(
+				branch, false,
+			));
+		}
+	}
+
+	fn pull_options(&self) {
 		if let Some(branch) = self.git_branch_name.last() {
-			self.queue.push(InternalEvent::Pull(branch));
+			self.queue
+				.push(InternalEvent::OpenPullOptionsPopup(branch));
+		}
+	}
+
+	/// writes conflict markers (or all hunks if there are no conflicts)
+	/// as quickfix list and SARIF log into the git dir
+	fn export_locations(&self) -> Result<()> {
+		let repo = self.repo.borrow();
+
+		let mut locations = sync::get_conflict_locations(&repo)?;
+		if locations.is_empty() {
+			locations = sync::get_hunk_locations(&repo)?;
 		}
+
+		let work_dir = sync::utils::repo_work_dir(&repo)?;
+		let git_dir = sync::utils::repo_dir(&repo)?;
+
+		let mut files = Vec::new();
+		for (format, file) in [
+			(sync::LocationsFormat::Quickfix, "gitui.quickfix"),
+			(sync::LocationsFormat::Sarif, "gitui.sarif"),
+		] {
+			let path = git_dir.join(file);
+			std::fs::write(
+				&path,
+				sync::format_locations(
+					&locations,
+					std::path::Path::new(&work_dir),
+					format,
+				)?,
+			)?;
+			files.push(path);
+		}
+
+		self.queue.push(InternalEvent::ShowInfoMsg(
+			strings::locations_exported(locations.len(), &files),
+		));
+
+		Ok(())
 	}
 
 	fn undo_last_commit(&self) {
@@ -623,6 +797,19 @@ impl Status {
 			&& self.git_branch_state.is_some()
 	}
 
+	fn can_pick_remote(&self) -> bool {
+		self.remotes.count > 0
+			&& self.git_branch_name.last().is_some()
+	}
+
+	/// without upstream and with several remotes there is no obvious
+	/// place to push to, so ask for one
+	fn needs_remote_target(&self) -> bool {
+		self.git_branch_state.is_none()
+			&& self.remotes.count > 1
+			&& self.can_pick_remote()
+	}
+
 	fn can_abort_merge(&self) -> bool {
 		self.git_state == RepoState::Merge
 	}
@@ -635,6 +822,36 @@ impl Status {
 		self.git_state == RepoState::Revert
 	}
 
+	fn pending_apply_mailbox(&self) -> bool {
+		self.git_state == RepoState::ApplyMailbox
+	}
+
+	fn pending_cherry_pick(&self) -> bool {
+		self.git_state == RepoState::CherryPick
+	}
+
+	/// a stopped operation that can be continued or have its current
+	/// step skipped
+	const fn can_continue_pending(&self) -> bool {
+		matches!(
+			self.git_state,
+			RepoState::Rebase
+				| RepoState::Revert
+				| RepoState::ApplyMailbox
+				| RepoState::CherryPick
+		)
+	}
+
+	const fn pending_sequencer_action(
+		&self,
+	) -> Option<SequencerAction> {
+		match self.git_state {
+			RepoState::CherryPick => Some(SequencerAction::Pick),
+			RepoState::Revert => Some(SequencerAction::Revert),
+			_ => None,
+		}
+	}
+
 	pub fn revert_pending_state(&self) {
 		try_or_popup!(
 			self,
@@ -651,14 +868,116 @@ impl Status {
 		);
 	}
 
-	fn continue_rebase(&self) {
+	pub fn abort_apply_mailbox(&self) {
+		try_or_popup!(
+			self,
+			"abort am",
+			sync::apply_mailbox_abort(&self.repo.borrow())
+		);
+	}
+
+	pub fn abort_revert(&self) {
+		try_or_popup!(
+			self,
+			"abort revert",
+			sync::sequencer_abort(&self.repo.borrow())
+		);
+	}
+
+	fn continue_pending(&self, skip: bool) {
+		match self.git_state {
+			RepoState::Rebase => self.continue_rebase(skip),
+			RepoState::ApplyMailbox => {
+				self.continue_apply_mailbox(skip);
+			}
+			RepoState::CherryPick | RepoState::Revert => {
+				self.continue_sequencer(skip);
+			}
+			_ => (),
+		}
+	}
+
+	fn continue_apply_mailbox(&self, skip: bool) {
+		let repo = self.repo.borrow();
+		let result = if skip {
+			sync::apply_mailbox_skip(&repo)
+		} else {
+			sync::apply_mailbox_continue(&repo)
+		};
+
+		match result {
+			Ok(ApplyPatchResult::Applied(_)) => (),
+			Ok(result) => {
+				self.queue.push(InternalEvent::ShowInfoMsg(
+					strings::apply_patch_result(result),
+				));
+			}
+			Err(e) => {
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!("continue am:\n{e}"),
+				));
+			}
+		}
+	}
+
+	pub fn abort_cherry_pick(&self) {
 		try_or_popup!(
 			self,
-			"continue rebase",
-			sync::continue_pending_rebase(&self.repo.borrow())
+			"abort cherry-pick",
+			sync::sequencer_abort(&self.repo.borrow())
 		);
 	}
 
+	fn continue_sequencer(&self, skip: bool) {
+		let Some(action) = self.pending_sequencer_action() else {
+			return;
+		};
+
+		let repo = self.repo.borrow();
+		let result = if skip {
+			sync::sequencer_skip(&repo)
+		} else {
+			sync::sequencer_continue(&repo)
+		};
+
+		match result {
+			Ok(SequencerResult::Done(_)) => (),
+			Ok(result) => {
+				self.queue.push(InternalEvent::ShowInfoMsg(
+					strings::sequencer_result(action, result),
+				));
+			}
+			Err(e) => {
+				self.queue.push(InternalEvent::ShowErrorMsg(
+					format!(
+						"continue {}:\n{e}",
+						match action {
+							SequencerAction::Pick => "cherry-pick",
+							SequencerAction::Revert => "revert",
+						}
+					),
+				));
+			}
+		}
+	}
+
+	fn continue_rebase(&self, skip: bool) {
+		let repo = self.repo.borrow();
+		if skip {
+			try_or_popup!(
+				self,
+				"skip rebase step",
+				sync::skip_pending_rebase(&repo)
+			);
+		} else {
+			try_or_popup!(
+				self,
+				"continue rebase",
+				sync::continue_pending_rebase(&repo)
+			);
+		}
+	}
+
 	fn commands_nav(
 		&self,
 		out: &mut Vec<CommandInfo>,
@@ -709,10 +1028,13 @@ impl Status {
 		self.index.focused()
 			&& !self.index.is_empty()
 			&& !self.pending_rebase()
+			&& !self.pending_apply_mailbox()
+			&& !self.pending_cherry_pick()
 	}
 }
 
 impl Component for Status {
+	#[allow(clippy::too_many_lines)]
 	fn commands(
 		&self,
 		out: &mut Vec<CommandInfo>,
@@ -746,6 +1068,13 @@ impl Component for Status {
 
 			out.push(CommandInfo::new(
 				strings::commands::status_push(&self.key_config),
+				self.can_push() || self.needs_remote_target(),
+				!focus_on_diff,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::status_push_no_verify(
+					&self.key_config,
+				),
 				self.can_push(),
 				!focus_on_diff,
 			));
@@ -762,11 +1091,35 @@ impl Component for Status {
 				self.can_fetch(),
 				!focus_on_diff,
 			));
+			out.push(CommandInfo::new(
+				strings::commands::status_fetch_options(
+					&self.key_config,
+				),
+				self.can_fetch(),
+				!focus_on_diff,
+			));
 			out.push(CommandInfo::new(
 				strings::commands::status_pull(&self.key_config),
+				self.can_fetch() || self.can_pick_remote(),
+				!focus_on_diff,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::status_pull_options(
+					&self.key_config,
+				),
 				self.can_fetch(),
 				!focus_on_diff,
 			));
+			out.push(CommandInfo::new(
+				strings::commands::status_push_to(&self.key_config),
+				self.can_pick_remote(),
+				!focus_on_diff,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::status_pull_from(&self.key_config),
+				self.can_pick_remote(),
+				!focus_on_diff,
+			));
 
 			out.push(CommandInfo::new(
 				strings::commands::undo_commit(&self.key_config),
@@ -799,11 +1152,102 @@ impl Component for Status {
 				self.pending_revert() || force_all,
 			));
 
+			out.push(CommandInfo::new(
+				strings::commands::continue_apply_mailbox(
+					&self.key_config,
+				),
+				true,
+				self.pending_apply_mailbox() || force_all,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::skip_pending_step(
+					&self.key_config,
+				),
+				true,
+				self.can_continue_pending() || force_all,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::abort_apply_mailbox(
+					&self.key_config,
+				),
+				true,
+				self.pending_apply_mailbox() || force_all,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::continue_sequencer(
+					&self.key_config,
+					self.pending_sequencer_action()
+						.unwrap_or(SequencerAction::Pick),
+				),
+				true,
+				self.pending_sequencer_action().is_some()
+					|| force_all,
+			));
+			out.push(CommandInfo::new(
+				strings::commands::abort_cherry_pick(
+					&self.key_config,
+				),
+				true,
+				self.pending_cherry_pick() || force_all,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::status_apply_patch(
+					&self.key_config,
+				),
+				true,
+				!focus_on_diff || force_all,
+			));
+
 			out.push(CommandInfo::new(
 				strings::commands::view_submodules(&self.key_config),
 				true,
 				true,
 			));
+
+			out.push(CommandInfo::new(
+				strings::commands::view_worktrees(&self.key_config),
+				true,
+				true,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::status_resolve_conflict(
+					&self.key_config,
+				),
+				self.selected_conflict().is_some(),
+				!focus_on_diff,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::status_export_locations(
+					&self.key_config,
+				),
+				true,
+				!focus_on_diff,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::toggle_show_ignored(
+					&self.key_config,
+					self.show_ignored,
+				),
+				true,
+				!focus_on_diff,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::status_discarded(&self.key_config),
+				true,
+				!focus_on_diff,
+			));
+
+			out.push(CommandInfo::new(
+				strings::commands::status_clean(&self.key_config),
+				true,
+				!focus_on_diff,
+			));
 		}
 
 		self.commands_nav(out, force_all);
@@ -864,6 +1308,29 @@ impl Component for Status {
 					&& !self.index_wd.is_empty()
 				{
 					self.switch_focus(Focus::WorkDir).map(Into::into)
+				} else if key_match(
+					k,
+					self.key_config.keys.status_show_ignored,
+				) && !self.is_focus_on_diff()
+				{
+					self.show_ignored = !self.show_ignored;
+					self.update()?;
+					Ok(EventState::Consumed)
+				} else if key_match(
+					k,
+					self.key_config.keys.status_discarded,
+				) && !self.is_focus_on_diff()
+				{
+					self.queue
+						.push(InternalEvent::OpenDiscardedPopup);
+					Ok(EventState::Consumed)
+				} else if key_match(
+					k,
+					self.key_config.keys.status_clean,
+				) && !self.is_focus_on_diff()
+				{
+					self.queue.push(InternalEvent::OpenCleanPopup);
+					Ok(EventState::Consumed)
 				} else if key_match(
 					k,
 					self.key_config.keys.select_branch,
@@ -877,12 +1344,31 @@ impl Component for Status {
 				) && !self.is_focus_on_diff()
 					&& self.can_push()
 				{
-					self.push(true);
+					self.push(true, false);
+					Ok(EventState::Consumed)
+				} else if key_match(k, self.key_config.keys.push_to)
+					&& !self.is_focus_on_diff()
+					&& self.can_pick_remote()
+				{
+					self.push_to();
+					Ok(EventState::Consumed)
+				} else if key_match(k, self.key_config.keys.pull_from)
+					&& !self.is_focus_on_diff()
+					&& self.can_pick_remote()
+				{
+					self.pull_from();
 					Ok(EventState::Consumed)
 				} else if key_match(k, self.key_config.keys.push)
 					&& !self.is_focus_on_diff()
 				{
-					self.push(false);
+					self.push(false, false);
+					Ok(EventState::Consumed)
+				} else if key_match(
+					k,
+					self.key_config.keys.push_no_verify,
+				) && !self.is_focus_on_diff()
+				{
+					self.push(false, true);
 					Ok(EventState::Consumed)
 				} else if key_match(k, self.key_config.keys.fetch)
 					&& !self.is_focus_on_diff()
@@ -890,12 +1376,28 @@ impl Component for Status {
 				{
 					self.fetch();
 					Ok(EventState::Consumed)
+				} else if key_match(
+					k,
+					self.key_config.keys.fetch_options,
+				) && !self.is_focus_on_diff()
+					&& self.can_fetch()
+				{
+					self.fetch_options();
+					Ok(EventState::Consumed)
 				} else if key_match(k, self.key_config.keys.pull)
 					&& !self.is_focus_on_diff()
-					&& self.can_fetch()
+					&& (self.can_fetch() || self.can_pick_remote())
 				{
 					self.pull();
 					Ok(EventState::Consumed)
+				} else if key_match(
+					k,
+					self.key_config.keys.pull_options,
+				) && !self.is_focus_on_diff()
+					&& self.can_fetch()
+				{
+					self.pull_options();
+					Ok(EventState::Consumed)
 				} else if key_match(
 					k,
 					self.key_config.keys.undo_commit,
@@ -928,25 +1430,83 @@ impl Component for Status {
 								Action::AbortRevert,
 							),
 						);
+					} else if self.pending_apply_mailbox() {
+						self.queue.push(
+							InternalEvent::ConfirmAction(
+								Action::AbortApplyMailbox,
+							),
+						);
+					} else if self.pending_cherry_pick() {
+						self.queue.push(
+							InternalEvent::ConfirmAction(
+								Action::AbortCherryPick,
+							),
+						);
 					}
 
 					Ok(EventState::Consumed)
 				} else if key_match(
 					k,
 					self.key_config.keys.rebase_branch,
-				) && self.pending_rebase()
+				) && self.can_continue_pending()
+				{
+					self.continue_pending(false);
+					self.queue.push(InternalEvent::Update(
+						NeedsUpdate::ALL,
+					));
+					Ok(EventState::Consumed)
+				} else if key_match(
+					k,
+					self.key_config.keys.status_skip_step,
+				) && self.can_continue_pending()
 				{
-					self.continue_rebase();
+					self.continue_pending(true);
 					self.queue.push(InternalEvent::Update(
 						NeedsUpdate::ALL,
 					));
 					Ok(EventState::Consumed)
+				} else if key_match(
+					k,
+					self.key_config.keys.status_apply_patch,
+				) && !self.is_focus_on_diff()
+				{
+					self.queue
+						.push(InternalEvent::OpenApplyPatchPopup);
+					Ok(EventState::Consumed)
 				} else if key_match(
 					k,
 					self.key_config.keys.view_submodules,
 				) {
 					self.queue.push(InternalEvent::ViewSubmodules);
 					Ok(EventState::Consumed)
+				} else if key_match(
+					k,
+					self.key_config.keys.view_worktrees,
+				) {
+					self.queue.push(InternalEvent::ViewWorktrees);
+					Ok(EventState::Consumed)
+				} else if key_match(
+					k,
+					self.key_config.keys.status_resolve_conflict,
+				) && !self.is_focus_on_diff()
+				{
+					if let Some(path) = self.selected_conflict() {
+						self.queue.push(
+							InternalEvent::OpenConflictEditor(path),
+						);
+					}
+					Ok(EventState::Consumed)
+				} else if key_match(
+					k,
+					self.key_config.keys.status_export_locations,
+				) && !self.is_focus_on_diff()
+				{
+					try_or_popup!(
+						self,
+						"export error:",
+						self.export_locations()
+					);
+					Ok(EventState::Consumed)
 				} else {
 					Ok(EventState::NotConsumed)
 				};
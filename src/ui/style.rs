@@ -1,6 +1,8 @@
 use crate::ui::syntax_text::DEFAULT_SYNTAX_THEME;
 use anyhow::Result;
-use asyncgit::{DiffLineType, StatusItemType};
+use asyncgit::{
+	sync::SignatureValidity, DiffLineType, StatusItemType,
+};
 use ratatui::style::{Color, Modifier, Style};
 use ron::ser::{to_string_pretty, PrettyConfig};
 use serde::{Deserialize, Serialize};
@@ -22,6 +24,8 @@ pub struct Theme {
 	disabled_fg: Color,
 	diff_line_add: Color,
 	diff_line_delete: Color,
+	diff_line_add_bg: Color,
+	diff_line_delete_bg: Color,
 	diff_file_added: Color,
 	diff_file_removed: Color,
 	diff_file_moved: Color,
@@ -37,6 +41,7 @@ pub struct Theme {
 	line_break: String,
 	block_title_focused: Color,
 	syntax: String,
+	blame_age_gradient: Vec<Color>,
 }
 
 impl Theme {
@@ -120,13 +125,20 @@ impl Theme {
 			StatusItemType::Deleted => {
 				Style::default().fg(self.diff_file_removed)
 			}
-			StatusItemType::Renamed => {
+			StatusItemType::Renamed | StatusItemType::Copied => {
 				Style::default().fg(self.diff_file_moved)
 			}
 			StatusItemType::Conflicted => Style::default()
 				.fg(self.diff_file_modified)
 				.add_modifier(Modifier::BOLD),
 			StatusItemType::Typechange => Style::default(),
+			StatusItemType::Ignored => Style::default()
+				.fg(self.disabled_fg)
+				.add_modifier(Modifier::ITALIC),
+			StatusItemType::AssumeUnchanged
+			| StatusItemType::SkipWorktree => {
+				Style::default().fg(self.disabled_fg)
+			}
 		};
 
 		self.apply_select(style, selected)
@@ -207,6 +219,25 @@ impl Theme {
 		Style::default().fg(self.danger_fg)
 	}
 
+	/// style of a syntax highlighted part of a diff line, added and
+	/// removed lines are marked by their background
+	pub const fn diff_line_highlighted(
+		&self,
+		typ: DiffLineType,
+		syntax: Style,
+		selected: bool,
+	) -> Style {
+		let style = match typ {
+			DiffLineType::Add => syntax.bg(self.diff_line_add_bg),
+			DiffLineType::Delete => {
+				syntax.bg(self.diff_line_delete_bg)
+			}
+			DiffLineType::Header | DiffLineType::None => syntax,
+		};
+
+		self.apply_select(style, selected)
+	}
+
 	pub fn line_break(&self) -> String {
 		self.line_break.clone()
 	}
@@ -245,6 +276,23 @@ impl Theme {
 		style
 	}
 
+	pub fn log_signature(
+		&self,
+		validity: SignatureValidity,
+		selected: bool,
+	) -> Style {
+		let color = match validity {
+			SignatureValidity::Verified => self.diff_line_add,
+			SignatureValidity::Unverified => self.disabled_fg,
+			SignatureValidity::Bad => self.danger_fg,
+		};
+
+		self.apply_select(
+			Style::default().fg(color).add_modifier(Modifier::BOLD),
+			selected,
+		)
+	}
+
 	pub fn commit_time(&self, selected: bool) -> Style {
 		self.apply_select(
 			Style::default().fg(self.commit_time),
@@ -272,6 +320,29 @@ impl Theme {
 		}
 	}
 
+	/// colors the blame gutter of a line by where `time` lies within
+	/// `range`, the oldest and newest commit times of the file
+	pub fn blame_age(&self, time: i64, range: (i64, i64)) -> Style {
+		let (oldest, newest) = range;
+		let steps = self.blame_age_gradient.len().saturating_sub(1);
+
+		let index = if newest > oldest {
+			i128::from(time.clamp(oldest, newest) - oldest)
+				* i128::try_from(steps).unwrap_or_default()
+				/ i128::from(newest - oldest)
+		} else {
+			i128::try_from(steps).unwrap_or_default()
+		};
+
+		usize::try_from(index)
+			.ok()
+			.and_then(|index| self.blame_age_gradient.get(index))
+			.map_or_else(
+				|| self.text(true, false),
+				|color| Style::default().fg(*color),
+			)
+	}
+
 	pub fn push_gauge(&self) -> Style {
 		Style::default()
 			.fg(self.push_gauge_fg)
@@ -351,6 +422,8 @@ impl Default for Theme {
 			disabled_fg: Color::DarkGray,
 			diff_line_add: Color::Green,
 			diff_line_delete: Color::Red,
+			diff_line_add_bg: Color::Rgb(0x1f, 0x3a, 0x1f),
+			diff_line_delete_bg: Color::Rgb(0x45, 0x1f, 0x1f),
 			diff_file_added: Color::LightGreen,
 			diff_file_removed: Color::LightRed,
 			diff_file_moved: Color::LightMagenta,
@@ -368,6 +441,13 @@ impl Default for Theme {
 			// Available themes can be found in:
 			// [ThemeSet::load_defaults function](https://github.com/trishume/syntect/blob/7fe13c0fd53cdfa0f9fea1aa14c5ba37f81d8b71/src/dumps.rs#L215).
 			syntax: DEFAULT_SYNTAX_THEME.to_string(),
+			blame_age_gradient: vec![
+				Color::Blue,
+				Color::Cyan,
+				Color::Green,
+				Color::Yellow,
+				Color::Red,
+			],
 		}
 	}
 }
@@ -410,4 +490,38 @@ mod tests {
 		assert_eq!(theme.selection_fg, Color::Rgb(255, 255, 255));
 		assert_eq!(theme.syntax, "InspiredGitHub");
 	}
+
+	#[test]
+	fn test_blame_age() {
+		let mut file = NamedTempFile::new().unwrap();
+
+		writeln!(
+			file,
+			r##"
+(
+	blame_age_gradient: Some(["Blue", "Yellow", "#ff0000"]),
+)
+"##
+		)
+		.unwrap();
+
+		let theme = Theme::init(&file.path().to_path_buf());
+
+		assert_eq!(
+			theme.blame_age(10, (10, 30)).fg,
+			Some(Color::Blue)
+		);
+		assert_eq!(
+			theme.blame_age(20, (10, 30)).fg,
+			Some(Color::Yellow)
+		);
+		assert_eq!(
+			theme.blame_age(30, (10, 30)).fg,
+			Some(Color::Rgb(255, 0, 0))
+		);
+		assert_eq!(
+			theme.blame_age(10, (10, 10)).fg,
+			Some(Color::Rgb(255, 0, 0))
+		);
+	}
 }
@@ -1,6 +1,6 @@
 use asyncgit::{
 	asyncjob::{AsyncJob, RunParams},
-	ProgressPercent,
+	DiffLineType, FileDiff, ProgressPercent,
 };
 use once_cell::sync::{Lazy, OnceCell};
 use ratatui::text::{Line, Span};
@@ -19,7 +19,10 @@ use syntect::{
 	parsing::{ParseState, ScopeStack, SyntaxSet},
 };
 
-use crate::{AsyncAppNotification, SyntaxHighlightProgress};
+use crate::{
+	string_utils::tabs_to_spaces, AsyncAppNotification,
+	SyntaxHighlightProgress,
+};
 
 pub const DEFAULT_SYNTAX_THEME: &str = "base16-eighties.dark";
 
@@ -89,23 +92,7 @@ impl SyntaxText {
 			ParseState::new(syntax)
 		};
 
-		let theme = THEME.get_or_try_init(|| -> Result<Theme, asyncgit::Error> {
-			let theme_path = crate::args::get_app_config_path()
-				.map_err(|e| asyncgit::Error::Generic(e.to_string()))?.join(format!("{syntax}.tmTheme"));
-
-			match ThemeSet::get_theme(&theme_path) {
-				Ok(t) => return Ok(t),
-			    Err(e) => log::info!("could not load '{}': {e}, trying from the set of default themes", theme_path.display()),
-			}
-
-			let mut theme_set = ThemeSet::load_defaults();
-			if let Some(t) = theme_set.themes.remove(syntax) {
-			    return Ok(t);
-			}
-
-			log::error!("the syntax theme '{syntax}' cannot be found. Using default theme ('{DEFAULT_SYNTAX_THEME}') instead");
-			Ok(theme_set.themes.remove(DEFAULT_SYNTAX_THEME).expect("the default theme should be there"))
-		})?;
+		let theme = load_theme(syntax)?;
 
 		let highlighter = Highlighter::new(theme);
 		let mut syntax_lines: Vec<SyntaxLine> = Vec::new();
@@ -173,6 +160,123 @@ impl SyntaxText {
 	}
 }
 
+fn load_theme(syntax: &str) -> asyncgit::Result<&'static Theme> {
+	THEME.get_or_try_init(|| -> Result<Theme, asyncgit::Error> {
+		let theme_path = crate::args::get_app_config_path()
+			.map_err(|e| asyncgit::Error::Generic(e.to_string()))?.join(format!("{syntax}.tmTheme"));
+
+		match ThemeSet::get_theme(&theme_path) {
+			Ok(t) => return Ok(t),
+		    Err(e) => log::info!("could not load '{}': {e}, trying from the set of default themes", theme_path.display()),
+		}
+
+		let mut theme_set = ThemeSet::load_defaults();
+		if let Some(t) = theme_set.themes.remove(syntax) {
+		    return Ok(t);
+		}
+
+		log::error!("the syntax theme '{syntax}' cannot be found. Using default theme ('{DEFAULT_SYNTAX_THEME}') instead");
+		Ok(theme_set.themes.remove(DEFAULT_SYNTAX_THEME).expect("the default theme should be there"))
+	})
+}
+
+/// highlighted ranges of a single diff line
+pub type DiffLineHighlight =
+	Vec<(ratatui::style::Style, Range<usize>)>;
+
+/// diffs with more lines are not highlighted
+const DIFF_HIGHLIGHT_MAX_LINES: usize = 10_000;
+
+/// highlights the content of every line of `diff` (one entry per line,
+/// headers stay empty). The old and the new side of a hunk are parsed
+/// separately so removed and added lines do not break each others
+/// state. Returns `None` if the file type is unknown or the diff is
+/// too big.
+pub fn highlight_diff(
+	file_path: &Path,
+	diff: &FileDiff,
+	syntax: &str,
+) -> Option<Vec<DiffLineHighlight>> {
+	scope_time!("highlight_diff");
+
+	if diff.lines > DIFF_HIGHLIGHT_MAX_LINES {
+		return None;
+	}
+
+	let syntax_ref = SYNTAX_SET
+		.find_syntax_for_file(file_path)
+		.ok()
+		.flatten()
+		.filter(|s| {
+			s.name != SYNTAX_SET.find_syntax_plain_text().name
+		})?;
+
+	let theme = load_theme(syntax)
+		.map_err(|e| log::error!("syntax theme error: {e}"))
+		.ok()?;
+	let highlighter = Highlighter::new(theme);
+
+	let mut res = Vec::with_capacity(diff.lines);
+
+	for hunk in &diff.hunks {
+		let mut old_state = (
+			ParseState::new(syntax_ref),
+			HighlightState::new(&highlighter, ScopeStack::new()),
+		);
+		let mut new_state = old_state.clone();
+
+		for line in &hunk.lines {
+			let content = tabs_to_spaces(line.content.to_string());
+
+			let (in_old, in_new) = match line.line_type {
+				DiffLineType::Header => {
+					res.push(Vec::new());
+					continue;
+				}
+				DiffLineType::Add => (false, true),
+				DiffLineType::Delete => (true, false),
+				DiffLineType::None => (true, true),
+			};
+
+			// context lines feed both sides, only the first result
+			// is used
+			let mut items = None;
+			for (state, used) in
+				[(&mut old_state, in_old), (&mut new_state, in_new)]
+			{
+				if used {
+					let line_items =
+						highlight_line(state, &content, &highlighter);
+					items.get_or_insert(line_items);
+				}
+			}
+
+			res.push(items.unwrap_or_default());
+		}
+	}
+
+	Some(res)
+}
+
+fn highlight_line(
+	(parse_state, highlight_state): &mut (ParseState, HighlightState),
+	content: &str,
+	highlighter: &Highlighter,
+) -> DiffLineHighlight {
+	let Ok(ops) = parse_state.parse_line(content, &SYNTAX_SET) else {
+		return Vec::new();
+	};
+
+	RangedHighlightIterator::new(
+		highlight_state,
+		&ops[..],
+		content,
+		highlighter,
+	)
+	.map(|(style, _, range)| (syntact_style_to_tui(&style), range))
+	.collect()
+}
+
 impl<'a> From<&'a SyntaxText> for ratatui::text::Text<'a> {
 	fn from(v: &'a SyntaxText) -> Self {
 		let mut result_lines: Vec<Line> =
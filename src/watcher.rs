@@ -1,4 +1,5 @@
 use anyhow::Result;
+use asyncgit::sync::RepoWatchDirs;
 use crossbeam_channel::{unbounded, Sender};
 use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use notify_debouncer_mini::{new_debouncer, DebounceEventResult};
@@ -10,7 +11,11 @@ pub struct RepoWatcher {
 }
 
 impl RepoWatcher {
-	pub fn new(workdir: &str) -> Self {
+	/// watches `workdir` and the `git_dirs` outside of it
+	pub fn new(
+		workdir: &str,
+		git_dirs: Option<RepoWatchDirs>,
+	) -> Self {
 		log::trace!(
 			"recommended watcher: {:?}",
 			RecommendedWatcher::kind()
@@ -22,7 +27,7 @@ impl RepoWatcher {
 
 		thread::spawn(move || {
 			let timeout = Duration::from_secs(2);
-			create_watcher(timeout, tx, &workdir);
+			create_watcher(timeout, tx, &workdir, git_dirs);
 		});
 
 		let (out_tx, out_rx) = unbounded();
@@ -68,15 +73,37 @@ fn create_watcher(
 	timeout: Duration,
 	tx: std::sync::mpsc::Sender<DebounceEventResult>,
 	workdir: &str,
+	git_dirs: Option<RepoWatchDirs>,
 ) {
 	scope_time!("create_watcher");
 
+	let workdir = Path::new(workdir);
 	let mut bouncer =
 		new_debouncer(timeout, tx).expect("Watch create error");
 	bouncer
 		.watcher()
-		.watch(Path::new(&workdir), RecursiveMode::Recursive)
+		.watch(workdir, RecursiveMode::Recursive)
 		.expect("Watch error");
 
+	// a `.git` inside the workdir is covered already
+	let git_dirs = git_dirs.into_iter().flat_map(|dirs| {
+		dirs.dirs
+			.into_iter()
+			.map(|dir| (dir, RecursiveMode::NonRecursive))
+			.chain(std::iter::once((
+				dirs.refs,
+				RecursiveMode::Recursive,
+			)))
+	});
+	for (dir, mode) in git_dirs {
+		if dir.starts_with(workdir) {
+			continue;
+		}
+
+		if let Err(e) = bouncer.watcher().watch(&dir, mode) {
+			log::error!("watch error: {:?}: {}", dir, e);
+		}
+	}
+
 	std::mem::forget(bouncer);
 }